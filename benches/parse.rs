@@ -0,0 +1,69 @@
+//! Parse throughput of Binance trade & depth payloads with the `serde_json` backend & the JSON
+//! backend of [`from_bytes`], which is `simd_json` if the `simd-json` feature is enabled.
+//!
+//! Compare both backends with `cargo bench --bench parse --features simd-json`.
+
+use barter_data::{
+    exchange::binance::{spot::l2::BinanceSpotOrderBookL2Delta, trade::BinanceTrade},
+    parser::from_bytes,
+};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use serde::de::DeserializeOwned;
+
+/// Recorded BinanceSpot trade fixtures, see the `fixture_corpus` tests.
+const TRADES: [&str; 2] = [
+    include_str!("../tests/fixtures/binance_spot/public_trades/0000.json"),
+    include_str!("../tests/fixtures/binance_spot/public_trades/0001.json"),
+];
+
+/// BinanceSpot diff depth payload with the ~20 levels per side of a busy 100ms update.
+const DEPTH: [&str; 1] = [include_str!("fixtures/binance_spot_depth_update.json")];
+
+fn bench_parse<T>(c: &mut Criterion, name: &str, payloads: &[&str])
+where
+    T: DeserializeOwned,
+{
+    let bytes = payloads.iter().map(|payload| payload.len() as u64).sum();
+    let payloads = payloads
+        .iter()
+        .map(|payload| payload.as_bytes().to_vec())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(bytes));
+
+    group.bench_function("serde_json", |b| {
+        b.iter(|| {
+            for payload in &payloads {
+                serde_json::from_slice::<T>(payload).unwrap();
+            }
+        })
+    });
+
+    let backend = match cfg!(feature = "simd-json") {
+        true => "simd_json",
+        false => "from_bytes",
+    };
+    group.bench_function(backend, |b| {
+        // Each iteration parses fresh buffers, since simd_json parses in place
+        b.iter_batched_ref(
+            || payloads.clone(),
+            |payloads| {
+                for payload in payloads.iter_mut() {
+                    from_bytes::<T>(payload).unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn parse(c: &mut Criterion) {
+    bench_parse::<BinanceTrade>(c, "binance_spot_trade", &TRADES);
+    bench_parse::<BinanceSpotOrderBookL2Delta>(c, "binance_spot_depth_update", &DEPTH);
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
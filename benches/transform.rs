@@ -0,0 +1,124 @@
+//! Transform throughput of pre-parsed Binance depth & Gateio trade messages, ie/ the work the
+//! [`Transformer`] of a connection does per message after the [`WsParser`] has parsed it.
+//!
+//! [`WsParser`]: barter_data::parser::WsParser
+//!
+//! Run with `cargo bench --bench transform`.
+
+use barter_data::{
+    exchange::{
+        binance::{
+            book::l2::BinanceOrderBookL2Snapshot,
+            spot::{
+                l2::{BinanceSpotBookUpdater, BinanceSpotOrderBookL2Delta},
+                BinanceSpot,
+            },
+        },
+        gateio::spot::{trade::GateioSpotTrade, GateioSpot},
+    },
+    streams::config::StreamConfig,
+    subscription::{book::OrderBooksL2, trade::PublicTrades, Map},
+    transformer::{
+        book::{InstrumentOrderBook, MultiBookTransformer},
+        stateless::StatelessTransformer,
+        ExchangeTransformer,
+    },
+    Identifier,
+};
+use barter_integration::{
+    model::{Instrument, InstrumentKind},
+    Transformer,
+};
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+
+/// BinanceSpot diff depth payload with the ~20 levels per side of a busy 100ms update.
+const DEPTH: &str = include_str!("fixtures/binance_spot_depth_update.json");
+
+/// Recorded GateioSpot trade fixture, see the `fixture_corpus` tests.
+const TRADE: &str = include_str!("../tests/fixtures/gateio_spot/public_trades/0000.json");
+
+/// Number of levels on each side of the OrderBook snapshot the [`DEPTH`] update is applied to,
+/// matching the snapshot `limit` requested by the [`BinanceSpotBookUpdater`].
+const SNAPSHOT_LEVELS: usize = 100;
+
+/// BinanceSpot OrderBook snapshot of [`SNAPSHOT_LEVELS`] around the prices of the provided
+/// update, that the update is the next in sequence of.
+fn depth_snapshot(delta: &BinanceSpotOrderBookL2Delta) -> BinanceOrderBookL2Snapshot {
+    let levels = |start: f64, step: f64| {
+        (0..SNAPSHOT_LEVELS)
+            .map(|index| format!(r#"["{:.2}","1.0"]"#, start + step * index as f64))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let snapshot = format!(
+        r#"{{"lastUpdateId":{},"bids":[{}],"asks":[{}]}}"#,
+        delta.first_update_id - 1,
+        levels(25791.37, -0.01),
+        levels(25791.38, 0.01),
+    );
+    serde_json::from_str(&snapshot).unwrap()
+}
+
+fn binance_spot_depth(c: &mut Criterion) {
+    let delta = serde_json::from_str::<BinanceSpotOrderBookL2Delta>(DEPTH).unwrap();
+    let snapshot = depth_snapshot(&delta);
+    let (ws_sink_tx, _ws_sink_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let transformer = || {
+        let book = InstrumentOrderBook {
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            updater: BinanceSpotBookUpdater::new(snapshot.last_update_id),
+            book: snapshot.clone().into(),
+        };
+        MultiBookTransformer::<BinanceSpot, OrderBooksL2, _>::from_books(
+            ws_sink_tx.clone(),
+            Map::from_iter([(delta.id().unwrap(), book)]),
+            &StreamConfig::default(),
+        )
+    };
+
+    let mut group = c.benchmark_group("binance_spot_depth_update");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("transform", |b| {
+        // Each iteration applies the update to a fresh OrderBook since it's sequenced, which is
+        // dropped outside of the measurement
+        b.iter_batched_ref(
+            || (transformer(), Some(delta.clone())),
+            |(transformer, delta)| transformer.transform((Utc::now(), delta.take().unwrap())),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn gateio_spot_trade(c: &mut Criterion) {
+    let trade = serde_json::from_str::<GateioSpotTrade>(TRADE).unwrap();
+    let (ws_sink_tx, _ws_sink_rx) = tokio::sync::mpsc::unbounded_channel();
+    let instrument_map = Map::from_iter([(
+        trade.id().unwrap(),
+        Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+    )]);
+    let mut transformer: StatelessTransformer<GateioSpot, PublicTrades, GateioSpotTrade> =
+        futures::executor::block_on(ExchangeTransformer::new(
+            ws_sink_tx,
+            instrument_map,
+            &StreamConfig::default(),
+        ))
+        .unwrap();
+
+    let mut group = c.benchmark_group("gateio_spot_trade");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("transform", |b| {
+        b.iter_batched(
+            || trade.clone(),
+            |trade| transformer.transform((Utc::now(), trade)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, binance_spot_depth, gateio_spot_trade);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Generate the prost types of the MarketEvent protobuf schema, compiled with protox so no
+    // protoc installation is required
+    #[cfg(feature = "proto")]
+    {
+        println!("cargo:rerun-if-changed=proto/market_event.proto");
+
+        let descriptors = protox::compile(["proto/market_event.proto"], ["proto"])
+            .expect("failed to compile proto/market_event.proto");
+
+        prost_build::Config::new()
+            .compile_fds(descriptors)
+            .expect("failed to generate prost types of proto/market_event.proto");
+    }
+}
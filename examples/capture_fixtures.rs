@@ -0,0 +1,239 @@
+use barter_data::{
+    error::DataError,
+    exchange::{
+        binance::{
+            book::l1::BinanceOrderBookL1,
+            candle::BinanceKline,
+            futures::{liquidation::BinanceLiquidation, BinanceFuturesUsd},
+            spot::{average_price::BinanceAveragePrice, ticker::BinanceTicker, BinanceSpot},
+            trade::{BinanceAggTrade, BinanceTrade},
+        },
+        bitfinex::{message::BitfinexMessage, Bitfinex},
+        coinbase::{book::l1::CoinbaseOrderBookL1, trade::CoinbaseTrade, Coinbase},
+        gateio::spot::{trade::GateioSpotTrade, GateioSpot},
+        kraken::{book::l1::KrakenOrderBookL1, trade::KrakenTrades, Kraken},
+        okx::{trade::OkxTrades, Okx},
+        Connector,
+    },
+    subscription::{
+        average_price::AveragePrices,
+        book::OrderBooksL1,
+        candle::{Candles, Interval},
+        liquidation::Liquidations,
+        ticker::Tickers,
+        trade::{PublicTrades, PublicTradesAggregated},
+        SubKind, Subscription,
+    },
+    test_util::fixture::capture_fixtures,
+    Identifier,
+};
+use barter_integration::model::InstrumentKind;
+use serde::Deserialize;
+use tracing::info;
+
+/// Root directory of the captured exchange message fixture corpus.
+const FIXTURES_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Capture raw exchange messages into the `tests/fixtures/{exchange}/{channel}` corpus.
+///
+/// eg/ `cargo run --example capture_fixtures --features test-util -- binance_spot public_trades btc usdt 5`
+///
+/// Generate the expected normalised output of the new fixtures afterwards using
+/// `UPDATE_FIXTURES=1 cargo test --features test-util --test fixture_corpus`, and review it.
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let [exchange, channel, base, quote, rest @ ..] = args.as_slice() else {
+        panic!("usage: capture_fixtures <exchange> <channel> <base> <quote> [count]")
+    };
+    let count = rest
+        .first()
+        .map(|count| count.parse::<usize>().expect("count must be an integer"))
+        .unwrap_or(5);
+
+    let capture = Capture {
+        channel,
+        base,
+        quote,
+        count,
+    };
+
+    let written = match (exchange.as_str(), channel.as_str()) {
+        ("binance_spot", "public_trades") => {
+            capture
+                .run::<_, _, BinanceTrade>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                )
+                .await
+        }
+        ("binance_spot", "public_trades_aggregated") => {
+            capture
+                .run::<_, _, BinanceAggTrade>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    PublicTradesAggregated,
+                )
+                .await
+        }
+        ("binance_spot", "order_books_l1") => {
+            capture
+                .run::<_, _, BinanceOrderBookL1>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    OrderBooksL1,
+                )
+                .await
+        }
+        ("binance_spot", "candles") => {
+            capture
+                .run::<_, _, BinanceKline>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    Candles(Interval::M1),
+                )
+                .await
+        }
+        ("binance_spot", "average_prices") => {
+            capture
+                .run::<_, _, BinanceAveragePrice>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    AveragePrices,
+                )
+                .await
+        }
+        ("binance_spot", "tickers") => {
+            capture
+                .run::<_, _, BinanceTicker>(
+                    BinanceSpot::default(),
+                    InstrumentKind::Spot,
+                    Tickers(Interval::H1),
+                )
+                .await
+        }
+        ("binance_futures_usd", "public_trades") => {
+            capture
+                .run::<_, _, BinanceTrade>(
+                    BinanceFuturesUsd::default(),
+                    InstrumentKind::FuturePerpetual,
+                    PublicTrades,
+                )
+                .await
+        }
+        ("binance_futures_usd", "liquidations") => {
+            capture
+                .run::<_, _, BinanceLiquidation>(
+                    BinanceFuturesUsd::default(),
+                    InstrumentKind::FuturePerpetual,
+                    Liquidations,
+                )
+                .await
+        }
+        ("bitfinex", "public_trades") => {
+            capture
+                .run::<_, _, BitfinexMessage>(
+                    Bitfinex::default(),
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                )
+                .await
+        }
+        ("coinbase", "public_trades") => {
+            capture
+                .run::<_, _, CoinbaseTrade>(Coinbase, InstrumentKind::Spot, PublicTrades)
+                .await
+        }
+        ("coinbase", "order_books_l1") => {
+            capture
+                .run::<_, _, CoinbaseOrderBookL1>(Coinbase, InstrumentKind::Spot, OrderBooksL1)
+                .await
+        }
+        ("gateio_spot", "public_trades") => {
+            capture
+                .run::<_, _, GateioSpotTrade>(
+                    GateioSpot::default(),
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                )
+                .await
+        }
+        ("kraken", "public_trades") => {
+            capture
+                .run::<_, _, KrakenTrades>(Kraken, InstrumentKind::Spot, PublicTrades)
+                .await
+        }
+        ("kraken", "order_books_l1") => {
+            capture
+                .run::<_, _, KrakenOrderBookL1>(Kraken, InstrumentKind::Spot, OrderBooksL1)
+                .await
+        }
+        ("okx", "public_trades") => {
+            capture
+                .run::<_, _, OkxTrades>(Okx, InstrumentKind::Spot, PublicTrades)
+                .await
+        }
+        (exchange, channel) => panic!("capturing {exchange} {channel} is not supported"),
+    }
+    .unwrap();
+
+    info!(fixtures = written, "capture complete");
+}
+
+/// Capture configuration parsed from the command line arguments.
+struct Capture<'a> {
+    channel: &'a str,
+    base: &'a str,
+    quote: &'a str,
+    count: usize,
+}
+
+impl Capture<'_> {
+    /// Capture the next `count` messages of the provided [`Connector`] & [`SubKind`] that
+    /// deserialise as the `Model`, returning the number of fixtures written.
+    async fn run<Exchange, Kind, Model>(
+        &self,
+        exchange: Exchange,
+        instrument_kind: InstrumentKind,
+        kind: Kind,
+    ) -> Result<usize, DataError>
+    where
+        Exchange: Connector + Send + Sync,
+        Kind: SubKind + Send + Sync,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+        Model: for<'de> Deserialize<'de>,
+    {
+        let subscription =
+            Subscription::from((exchange, self.base, self.quote, instrument_kind, kind));
+
+        capture_fixtures::<Exchange, Kind, Model, _>(
+            &[subscription],
+            FIXTURES_ROOT,
+            self.channel,
+            self.count,
+        )
+        .await
+        .map(|written| written.len())
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
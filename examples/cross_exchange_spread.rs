@@ -0,0 +1,156 @@
+use barter_data::{
+    event::MarketEvent,
+    exchange::{binance::spot::BinanceSpot, coinbase::Coinbase, kraken::Kraken, ExchangeId},
+    num::Num,
+    streams::Streams,
+    subscription::book::{OrderBookL1, OrderBooksL1},
+};
+use barter_integration::model::InstrumentKind;
+use futures::StreamExt;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// Quotes older than this are considered stale, eg/ because the exchange feed dropped.
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// Interval at which the cross-exchange spread is reported.
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    // Initialise OrderBooksL1 Streams for the same BTC/USDT spot market on each exchange
+    // '--> each call to StreamBuilder::subscribe() initialises a separate WebSocket connection
+    // '--> Kraken names bitcoin "xbt", whereas Binance & Coinbase name it "btc"
+    // '--> Coinbase quotes come from it's real-time "ticker" channel, updated on every match
+    let streams = Streams::<OrderBooksL1>::builder()
+        .subscribe([
+            (BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, OrderBooksL1),
+        ])
+        .subscribe([
+            (Coinbase, "btc", "usdt", InstrumentKind::Spot, OrderBooksL1),
+        ])
+        .subscribe([
+            (Kraken, "xbt", "usdt", InstrumentKind::Spot, OrderBooksL1),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    // Join all exchange OrderBooksL1 streams into a single tokio_stream::StreamMap, so each
+    // MarketEvent<OrderBookL1> is routed alongside the ExchangeId it originated from
+    let mut joined_stream = streams.join_map().await;
+
+    let mut quotes = Quotes::default();
+    let mut report = tokio::time::interval(REPORT_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = joined_stream.next() => match event {
+                Some((exchange, order_book_l1)) => quotes.update(exchange, order_book_l1),
+                None => break,
+            },
+            _ = report.tick() => quotes.report(Instant::now()),
+        }
+    }
+
+    warn!("all exchange OrderBooksL1 streams ended");
+}
+
+/// Most recent [`OrderBookL1`] received from each exchange.
+#[derive(Debug, Default)]
+struct Quotes(HashMap<ExchangeId, (Instant, MarketEvent<OrderBookL1>)>);
+
+impl Quotes {
+    fn update(&mut self, exchange: ExchangeId, order_book_l1: MarketEvent<OrderBookL1>) {
+        self.0.insert(exchange, (Instant::now(), order_book_l1));
+    }
+
+    /// Log the cross-exchange spread between the highest bid & lowest ask of the fresh quotes,
+    /// or why it's unavailable.
+    fn report(&self, now: Instant) {
+        let (fresh, stale): (Vec<_>, Vec<_>) = self
+            .0
+            .iter()
+            .partition(|(_, (received, _))| now.duration_since(*received) < STALE_AFTER);
+
+        for (exchange, (received, _)) in &stale {
+            warn!(
+                %exchange,
+                stale_for_ms = now.duration_since(*received).as_millis() as u64,
+                "excluding stale OrderBookL1 quote",
+            );
+        }
+
+        if fresh.len() < 2 {
+            warn!(
+                fresh_exchanges = fresh.len(),
+                "cross-exchange spread unavailable: at least two live exchange quotes required",
+            );
+            return;
+        }
+
+        let best_bid = fresh
+            .iter()
+            .max_by(|(_, (_, a)), (_, (_, b))| {
+                a.kind
+                    .best_bid
+                    .price
+                    .partial_cmp(&b.kind.best_bid.price)
+                    .unwrap()
+            })
+            .map(|(exchange, (_, event))| (**exchange, event.kind.best_bid.price))
+            .unwrap();
+
+        let best_ask = fresh
+            .iter()
+            .min_by(|(_, (_, a)), (_, (_, b))| {
+                a.kind
+                    .best_ask
+                    .price
+                    .partial_cmp(&b.kind.best_ask.price)
+                    .unwrap()
+            })
+            .map(|(exchange, (_, event))| (**exchange, event.kind.best_ask.price))
+            .unwrap();
+
+        // Positive spread: buying on the best ask exchange & selling on the best bid exchange
+        // would be profitable (before fees)
+        let spread = best_bid.1 - best_ask.1;
+        let mid = (best_bid.1 + best_ask.1) / Num::from(2);
+        let spread_bps = spread / mid * Num::from(10_000);
+
+        info!(
+            best_bid_exchange = %best_bid.0,
+            best_bid = %best_bid.1,
+            best_ask_exchange = %best_ask.0,
+            best_ask = %best_ask.1,
+            spread = %spread,
+            spread_bps = %spread_bps,
+            "cross-exchange spread",
+        );
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
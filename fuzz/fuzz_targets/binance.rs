@@ -0,0 +1,47 @@
+#![no_main]
+
+use barter_data::{
+    exchange::{
+        binance::{
+            book::l1::BinanceOrderBookL1,
+            candle::BinanceKline,
+            futures::{
+                l2::{BinanceFuturesBookUpdater, BinanceFuturesOrderBookL2Delta},
+                liquidation::BinanceLiquidation,
+            },
+            spot::{
+                average_price::BinanceAveragePrice,
+                l2::{BinanceSpotBookUpdater, BinanceSpotOrderBookL2Delta},
+                ticker::BinanceTicker,
+            },
+            trade::BinanceTrade,
+        },
+        ExchangeId,
+    },
+    subscription::{
+        average_price::AveragePrice, book::OrderBookL1, candle::Candle, liquidation::Liquidation,
+        ticker::Ticker, trade::PublicTrade,
+    },
+    transformer::book::OrderBookUpdater,
+};
+use barter_data_fuzz::{empty_book, normalise, parse};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    normalise::<BinanceTrade, PublicTrade>(ExchangeId::BinanceSpot, data);
+    normalise::<BinanceOrderBookL1, OrderBookL1>(ExchangeId::BinanceSpot, data);
+    normalise::<BinanceKline, Candle>(ExchangeId::BinanceSpot, data);
+    normalise::<BinanceAveragePrice, AveragePrice>(ExchangeId::BinanceSpot, data);
+    normalise::<BinanceTicker, Ticker>(ExchangeId::BinanceSpot, data);
+    normalise::<BinanceLiquidation, Liquidation>(ExchangeId::BinanceFuturesUsd, data);
+
+    // OrderBook L2 deltas are applied to an OrderBook as if they were the first update
+    if let Some(delta) = parse::<BinanceSpotOrderBookL2Delta>(data) {
+        let mut updater = BinanceSpotBookUpdater::new(delta.first_update_id.saturating_sub(1));
+        let _ = updater.update(&mut empty_book(), delta);
+    }
+    if let Some(delta) = parse::<BinanceFuturesOrderBookL2Delta>(data) {
+        let mut updater = BinanceFuturesBookUpdater::new(delta.first_update_id.saturating_sub(1));
+        let _ = updater.update(&mut empty_book(), delta);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use barter_data::{
+    exchange::{bitfinex::message::BitfinexMessage, ExchangeId},
+    subscription::trade::PublicTrade,
+};
+use barter_data_fuzz::normalise;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    normalise::<BitfinexMessage, PublicTrade>(ExchangeId::Bitfinex, data);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use barter_data::{
+    exchange::{
+        kraken::{book::l1::KrakenOrderBookL1, trade::KrakenTrades},
+        ExchangeId,
+    },
+    subscription::{book::OrderBookL1, trade::PublicTrade},
+};
+use barter_data_fuzz::normalise;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    normalise::<KrakenTrades, PublicTrade>(ExchangeId::Kraken, data);
+    normalise::<KrakenOrderBookL1, OrderBookL1>(ExchangeId::Kraken, data);
+});
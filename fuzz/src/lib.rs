@@ -0,0 +1,46 @@
+//! Shared harness of the `cargo-fuzz` targets, each feeding arbitrary bytes through the
+//! production deserialisation & normalisation of an exchange's raw WebSocket messages.
+//!
+//! A target must never panic, whatever the input. Memory is bounded by running the targets with
+//! the libFuzzer `-max_len`, `-malloc_limit_mb` & `-rss_limit_mb` options (see the README).
+
+use barter_data::{
+    event::MarketIter,
+    exchange::ExchangeId,
+    parser::process_payload,
+    subscription::book::{Level, OrderBook, OrderBookSide},
+};
+use barter_integration::model::{Instrument, InstrumentKind, Side};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+
+/// Deserialise the raw message as the `Model` exactly as the
+/// [`WsParser`](barter_data::parser::WsParser) does, & normalise it into the
+/// [`MarketIter<Event>`] the production `StatelessTransformer` would yield.
+pub fn normalise<Model, Event>(exchange: ExchangeId, data: &[u8])
+where
+    Model: DeserializeOwned,
+    MarketIter<Event>: From<(ExchangeId, Instrument, Model)>,
+{
+    if let Some(Ok(model)) = process_payload::<Model>(data.to_vec()) {
+        let instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let _ = MarketIter::<Event>::from((exchange, instrument, model));
+    }
+}
+
+/// Deserialise the raw message as the `Model`, returning it if successful.
+pub fn parse<Model>(data: &[u8]) -> Option<Model>
+where
+    Model: DeserializeOwned,
+{
+    process_payload::<Model>(data.to_vec()).and_then(Result::ok)
+}
+
+/// Empty [`OrderBook`] that parsed deltas can be applied to.
+pub fn empty_book() -> OrderBook {
+    OrderBook {
+        last_update_time: Utc::now(),
+        bids: OrderBookSide::new(Side::Buy, Vec::<Level>::new()),
+        asks: OrderBookSide::new(Side::Sell, Vec::<Level>::new()),
+    }
+}
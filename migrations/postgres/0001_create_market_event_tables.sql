@@ -0,0 +1,59 @@
+CREATE TABLE IF NOT EXISTS trades (
+    exchange TEXT NOT NULL,
+    base TEXT NOT NULL,
+    quote TEXT NOT NULL,
+    instrument_kind TEXT NOT NULL,
+    exchange_time TIMESTAMPTZ NOT NULL,
+    received_time TIMESTAMPTZ NOT NULL,
+    id TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    amount DOUBLE PRECISION NOT NULL,
+    side TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS trades_instrument_time_idx ON trades (exchange, base, quote, instrument_kind, exchange_time);
+
+CREATE TABLE IF NOT EXISTS order_book_l1 (
+    exchange TEXT NOT NULL,
+    base TEXT NOT NULL,
+    quote TEXT NOT NULL,
+    instrument_kind TEXT NOT NULL,
+    exchange_time TIMESTAMPTZ NOT NULL,
+    received_time TIMESTAMPTZ NOT NULL,
+    last_update_time TIMESTAMPTZ NOT NULL,
+    bid_price DOUBLE PRECISION NOT NULL,
+    bid_amount DOUBLE PRECISION NOT NULL,
+    ask_price DOUBLE PRECISION NOT NULL,
+    ask_amount DOUBLE PRECISION NOT NULL
+);
+CREATE INDEX IF NOT EXISTS order_book_l1_instrument_time_idx ON order_book_l1 (exchange, base, quote, instrument_kind, exchange_time);
+
+CREATE TABLE IF NOT EXISTS candles (
+    exchange TEXT NOT NULL,
+    base TEXT NOT NULL,
+    quote TEXT NOT NULL,
+    instrument_kind TEXT NOT NULL,
+    exchange_time TIMESTAMPTZ NOT NULL,
+    received_time TIMESTAMPTZ NOT NULL,
+    close_time TIMESTAMPTZ NOT NULL,
+    open DOUBLE PRECISION NOT NULL,
+    high DOUBLE PRECISION NOT NULL,
+    low DOUBLE PRECISION NOT NULL,
+    close DOUBLE PRECISION NOT NULL,
+    volume DOUBLE PRECISION NOT NULL,
+    trade_count BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS candles_instrument_time_idx ON candles (exchange, base, quote, instrument_kind, exchange_time);
+
+CREATE TABLE IF NOT EXISTS liquidations (
+    exchange TEXT NOT NULL,
+    base TEXT NOT NULL,
+    quote TEXT NOT NULL,
+    instrument_kind TEXT NOT NULL,
+    exchange_time TIMESTAMPTZ NOT NULL,
+    received_time TIMESTAMPTZ NOT NULL,
+    side TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    quantity DOUBLE PRECISION NOT NULL,
+    time TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS liquidations_instrument_time_idx ON liquidations (exchange, base, quote, instrument_kind, exchange_time);
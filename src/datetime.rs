@@ -0,0 +1,442 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Number of nanoseconds in one second.
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Number of nanoseconds in one millisecond.
+const NANOS_PER_MILLI: i64 = 1_000_000;
+
+/// Convert integer milliseconds since the Unix epoch into a [`DateTime<Utc>`], if it's in range.
+///
+/// Pre-epoch (negative) timestamps are supported.
+pub fn epoch_ms_to_datetime_utc(epoch_ms: i64) -> Option<DateTime<Utc>> {
+    epoch_to_datetime_utc(epoch_ms, 1_000)
+}
+
+/// Convert integer microseconds since the Unix epoch into a [`DateTime<Utc>`], if it's in range.
+///
+/// Pre-epoch (negative) timestamps are supported.
+pub fn epoch_us_to_datetime_utc(epoch_us: i64) -> Option<DateTime<Utc>> {
+    epoch_to_datetime_utc(epoch_us, 1_000_000)
+}
+
+/// Convert an integer epoch timestamp with `per_second` units in one second into a
+/// [`DateTime<Utc>`], if it's in range.
+fn epoch_to_datetime_utc(value: i64, per_second: i64) -> Option<DateTime<Utc>> {
+    let seconds = value.div_euclid(per_second);
+    let subsec_nanos = value.rem_euclid(per_second) * (NANOS_PER_SECOND / per_second);
+    DateTime::from_timestamp(seconds, subsec_nanos as u32)
+}
+
+/// Parse a decimal epoch seconds string (eg/ Kraken's "1534614057.321597") into a
+/// [`DateTime<Utc>`] directly, without floating point rounding.
+///
+/// Fractional digits beyond nanosecond precision are truncated.
+pub fn parse_fractional_seconds_str(input: &str) -> Option<DateTime<Utc>> {
+    parse_fractional_epoch_str(input, NANOS_PER_SECOND, 9)
+}
+
+/// Parse a decimal epoch milliseconds string (eg/ Gateio's "1606292218213.4578") into a
+/// [`DateTime<Utc>`] directly, without floating point rounding.
+///
+/// Fractional digits beyond nanosecond precision are truncated.
+pub fn parse_fractional_millis_str(input: &str) -> Option<DateTime<Utc>> {
+    parse_fractional_epoch_str(input, NANOS_PER_MILLI, 6)
+}
+
+/// Parse a decimal epoch timestamp string in a unit of `unit_nanos` nanoseconds, whose fraction
+/// has at most `fraction_digits` digits of nanosecond precision.
+fn parse_fractional_epoch_str(
+    input: &str,
+    unit_nanos: i64,
+    fraction_digits: usize,
+) -> Option<DateTime<Utc>> {
+    let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+    if whole.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole = whole.parse::<i64>().ok()?;
+    let fraction = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(fraction_digits)
+        .fold(0, |acc, digit| acc * 10 + i128::from(digit - b'0'));
+
+    // i128 nanoseconds cannot overflow, so out of range timestamps are rejected by chrono
+    let magnitude = i128::from(whole.unsigned_abs()) * i128::from(unit_nanos) + fraction;
+    let nanos = match input.starts_with('-') {
+        true => -magnitude,
+        false => magnitude,
+    };
+
+    let seconds = i64::try_from(nanos.div_euclid(i128::from(NANOS_PER_SECOND))).ok()?;
+    let subsec_nanos = nanos.rem_euclid(i128::from(NANOS_PER_SECOND)) as u32;
+    DateTime::from_timestamp(seconds, subsec_nanos)
+}
+
+/// Deserialize an integer epoch seconds value (eg/ 1688671200) as a [`DateTime<Utc>`].
+pub fn de_epoch_s_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    de_epoch_as_datetime_utc(deserializer, 1)
+}
+
+/// Deserialize an integer epoch milliseconds value (eg/ 1649324825173) as a [`DateTime<Utc>`].
+pub fn de_epoch_ms_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    de_epoch_as_datetime_utc(deserializer, 1_000)
+}
+
+fn de_epoch_as_datetime_utc<'de, D>(
+    deserializer: D,
+    per_second: i64,
+) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let epoch = i64::deserialize(deserializer)?;
+    epoch_to_datetime_utc(epoch, per_second)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid epoch timestamp: {epoch}")))
+}
+
+/// Deserialize a decimal epoch seconds string (eg/ "1534614057.321597") as a [`DateTime<Utc>`],
+/// without floating point rounding.
+pub fn de_str_epoch_s_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    de_str_epoch_as_datetime_utc(deserializer, parse_fractional_seconds_str)
+}
+
+/// Deserialize a decimal epoch milliseconds string (eg/ "1606292218213.4578" or
+/// "1630048897897") as a [`DateTime<Utc>`], without floating point rounding.
+pub fn de_str_epoch_ms_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    de_str_epoch_as_datetime_utc(deserializer, parse_fractional_millis_str)
+}
+
+fn de_str_epoch_as_datetime_utc<'de, D>(
+    deserializer: D,
+    parse: fn(&str) -> Option<DateTime<Utc>>,
+) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let input = <&str as Deserialize>::deserialize(deserializer)?;
+    parse(input)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid epoch timestamp: {input}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{RawTimestamp, TimestampUnit};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn datetime(seconds: i64, nanos: u32) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(seconds, nanos)
+    }
+
+    #[test]
+    fn test_epoch_ms_to_datetime_utc() {
+        struct TestCase {
+            input: i64,
+            expected: Option<DateTime<Utc>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Unix epoch
+                input: 0,
+                expected: datetime(0, 0),
+            },
+            TestCase {
+                // TC1: Binance trade time
+                input: 1649324825173,
+                expected: datetime(1649324825, 173_000_000),
+            },
+            TestCase {
+                // TC2: pre-1970 millisecond floors to the previous second
+                input: -1,
+                expected: datetime(-1, 999_000_000),
+            },
+            TestCase {
+                // TC3: pre-1970 whole second
+                input: -86_400_000,
+                expected: datetime(-86_400, 0),
+            },
+            TestCase {
+                // TC4: beyond year 2262, where i64 nanoseconds overflow
+                input: 9_300_000_000_000,
+                expected: datetime(9_300_000_000, 0),
+            },
+            TestCase {
+                // TC5: out of range
+                input: i64::MAX,
+                expected: None,
+            },
+            TestCase {
+                // TC6: out of range
+                input: i64::MIN,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = epoch_ms_to_datetime_utc(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_epoch_us_to_datetime_utc() {
+        struct TestCase {
+            input: i64,
+            expected: Option<DateTime<Utc>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Unix epoch
+                input: 0,
+                expected: datetime(0, 0),
+            },
+            TestCase {
+                // TC1: Kraken trade time
+                input: 1534614057321597,
+                expected: datetime(1534614057, 321_597_000),
+            },
+            TestCase {
+                // TC2: pre-1970 microsecond floors to the previous second
+                input: -1,
+                expected: datetime(-1, 999_999_000),
+            },
+            TestCase {
+                // TC3: beyond year 2262, where i64 nanoseconds overflow
+                input: 9_300_000_000_000_001,
+                expected: datetime(9_300_000_000, 1_000),
+            },
+            TestCase {
+                // TC4: out of range
+                input: i64::MAX,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = epoch_us_to_datetime_utc(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_parse_fractional_seconds_str() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<DateTime<Utc>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Unix epoch
+                input: "0",
+                expected: datetime(0, 0),
+            },
+            TestCase {
+                // TC1: Kraken seconds w/ microsecond fraction
+                input: "1534614057.321597",
+                expected: datetime(1534614057, 321_597_000),
+            },
+            TestCase {
+                // TC2: trailing dot
+                input: "1534614057.",
+                expected: datetime(1534614057, 0),
+            },
+            TestCase {
+                // TC3: digits beyond nanoseconds are truncated
+                input: "1534614057.1234567891",
+                expected: datetime(1534614057, 123_456_789),
+            },
+            TestCase {
+                // TC4: pre-1970 fraction floors to the previous second
+                input: "-1.25",
+                expected: datetime(-2, 750_000_000),
+            },
+            TestCase {
+                // TC5: pre-1970 fraction w/ zero whole seconds keeps it's sign
+                input: "-0.5",
+                expected: datetime(-1, 500_000_000),
+            },
+            TestCase {
+                // TC6: beyond year 2262, where i64 nanoseconds overflow
+                input: "9300000000.000000001",
+                expected: datetime(9_300_000_000, 1),
+            },
+            TestCase {
+                // TC7: out of range
+                input: "9223372036854775807.5",
+                expected: None,
+            },
+            TestCase {
+                // TC8: overflows i64 whole seconds
+                input: "9223372036854775808",
+                expected: None,
+            },
+            TestCase {
+                // TC9: exponent
+                input: "1534614057.12e3",
+                expected: None,
+            },
+            TestCase {
+                // TC10: missing whole seconds
+                input: ".5",
+                expected: None,
+            },
+            TestCase {
+                // TC11: empty input
+                input: "",
+                expected: None,
+            },
+            TestCase {
+                // TC12: sign in the fraction
+                input: "1.-5",
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = parse_fractional_seconds_str(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_parse_fractional_millis_str() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<DateTime<Utc>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Gateio milliseconds w/ sub-millisecond fraction
+                input: "1606292218213.4578",
+                expected: datetime(1606292218, 213_457_800),
+            },
+            TestCase {
+                // TC1: Okx integer milliseconds
+                input: "1630048897897",
+                expected: datetime(1630048897, 897_000_000),
+            },
+            TestCase {
+                // TC2: digits beyond nanoseconds are truncated
+                input: "1.0000019",
+                expected: datetime(0, 1_000_001),
+            },
+            TestCase {
+                // TC3: pre-1970 millisecond floors to the previous second
+                input: "-1",
+                expected: datetime(-1, 999_000_000),
+            },
+            TestCase {
+                // TC4: invalid input
+                input: "1606292218213,4578",
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = parse_fractional_millis_str(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_de_epoch_as_datetime_utc() {
+        #[derive(Debug, Deserialize)]
+        struct Message {
+            #[serde(default, deserialize_with = "de_epoch_s_as_datetime_utc")]
+            s: DateTime<Utc>,
+            #[serde(deserialize_with = "de_epoch_ms_as_datetime_utc")]
+            ms: DateTime<Utc>,
+            #[serde(deserialize_with = "de_str_epoch_s_as_datetime_utc")]
+            s_str: DateTime<Utc>,
+            #[serde(deserialize_with = "de_str_epoch_ms_as_datetime_utc")]
+            ms_str: DateTime<Utc>,
+        }
+
+        let input = r#"{"s":1688671200,"ms":1649324825173,"s_str":"1534614057.321597","ms_str":"1606292218213.4578"}"#;
+        let actual = serde_json::from_str::<Message>(input).unwrap();
+        assert_eq!(Some(actual.s), datetime(1688671200, 0));
+        assert_eq!(Some(actual.ms), datetime(1649324825, 173_000_000));
+        assert_eq!(Some(actual.s_str), datetime(1534614057, 321_597_000));
+        assert_eq!(Some(actual.ms_str), datetime(1606292218, 213_457_800));
+
+        // Out of range & unsigned values beyond i64 are errors rather than panics
+        for input in [
+            r#"{"ms":9223372036854775807,"s_str":"0","ms_str":"0"}"#,
+            r#"{"ms":18446744073709551615,"s_str":"0","ms_str":"0"}"#,
+            r#"{"ms":0,"s_str":"1e9","ms_str":"0"}"#,
+            r#"{"s":9223372036854775807,"ms":0,"s_str":"0","ms_str":"0"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Message>(input).is_err(),
+                "{input} failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fuzz_datetime_conversions_match_reference() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100_000 {
+            // Integer conversions match chrono
+            let value = match rng.gen_range(0..3) {
+                0 => rng.gen::<i64>(),
+                1 => rng.gen_range(-100_000_000_000_000..100_000_000_000_000),
+                _ => rng.gen_range(-10_000..10_000),
+            };
+            assert_eq!(
+                epoch_ms_to_datetime_utc(value),
+                DateTime::from_timestamp_millis(value),
+                "{value} ms failed"
+            );
+            assert_eq!(
+                epoch_us_to_datetime_utc(value),
+                DateTime::from_timestamp_micros(value),
+                "{value} us failed"
+            );
+
+            // String parsing matches the precision retaining RawTimestamp, wherever it's i64
+            // value does not overflow
+            let fraction = (0..rng.gen_range(0..12))
+                .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                .collect::<String>();
+            let input = match fraction.is_empty() {
+                true => value.to_string(),
+                false => format!("{value}.{fraction}"),
+            };
+            let input = input.as_str();
+
+            for (actual, unit) in [
+                (parse_fractional_seconds_str(input), TimestampUnit::Seconds),
+                (
+                    parse_fractional_millis_str(input),
+                    TimestampUnit::Milliseconds,
+                ),
+            ] {
+                if let Some(raw) = RawTimestamp::parse_decimal(input, unit) {
+                    assert_eq!(actual, raw.to_datetime(), "{input} {unit:?} failed");
+                }
+            }
+        }
+    }
+}
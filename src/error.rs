@@ -1,4 +1,9 @@
-use barter_integration::error::SocketError;
+use crate::exchange::ExchangeId;
+use barter_integration::{error::SocketError, model::Instrument};
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+};
 use thiserror::Error;
 
 /// All errors generated in `barter-data`.
@@ -17,14 +22,116 @@ pub enum DataError {
         prev_last_update_id: u64,
         first_update_id: u64,
     },
+
+    #[error(
+        "InstrumentResync: re-initialising {instrument} OrderBook in isolation after: {reason}"
+    )]
+    InstrumentResync {
+        instrument: Instrument,
+        reason: String,
+    },
+
+    #[error("Auth: {exchange} login failed: {reason}")]
+    Auth {
+        exchange: ExchangeId,
+        reason: String,
+    },
+
+    #[error("Exchange: {exchange} error event code {code}: {message}")]
+    Exchange {
+        exchange: ExchangeId,
+        code: i64,
+        message: String,
+    },
+
+    #[error("Reconnect: {exchange} requested re-connection: {reason}")]
+    Reconnect {
+        exchange: ExchangeId,
+        reason: String,
+    },
+
+    #[error("ConsumerDropped: {0} MarketEvent receiver dropped, shutting down consumer loop")]
+    ConsumerDropped(ExchangeId),
+
+    #[error("InvalidSubscriptions: {}", fmt_invalid_subscriptions(.0))]
+    InvalidSubscriptions(Vec<InvalidSubscription>),
+
+    #[error(
+        "UnknownInstrumentKind: {0:?} is not a known instrument kind, expected one of: spot, \
+        future_perpetual, perpetual, perp, swap, perpetual_future, future"
+    )]
+    UnknownInstrumentKind(String),
+
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Protobuf: {0}")]
+    Protobuf(String),
+
+    #[error("Parquet: {0}")]
+    Parquet(String),
+
+    #[error("Publish: failed to publish MarketEvent to {topic}: {reason}")]
+    Publish { topic: String, reason: String },
+
+    #[error("Flush: {sink} failed to flush buffered events: {reason}")]
+    Flush { sink: &'static str, reason: String },
+
+    #[error("UnsupportedSchemaVersion: MarketEvent schema version {0} is not supported")]
+    UnsupportedSchemaVersion(u16),
+
+    #[error("KindMismatch: expected MarketEvent kind {expected}, but found {actual}")]
+    KindMismatch {
+        expected: &'static str,
+        actual: &'static str,
+    },
+}
+
+/// [`Subscription`](crate::subscription::Subscription) that failed validation, including the
+/// reason it is invalid.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct InvalidSubscription {
+    pub exchange: ExchangeId,
+    pub instrument: Instrument,
+    pub reason: String,
+}
+
+impl Display for InvalidSubscription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.exchange, self.instrument, self.reason)
+    }
+}
+
+/// Format every [`InvalidSubscription`] into a single "; " separated `String`.
+fn fmt_invalid_subscriptions(invalid: &[InvalidSubscription]) -> String {
+    invalid
+        .iter()
+        .map(InvalidSubscription::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl From<Infallible> for DataError {
+    fn from(infallible: Infallible) -> Self {
+        match infallible {}
+    }
 }
 
 impl DataError {
     /// Determine if an error requires a [`MarketStream`](super::MarketStream) to re-initialise.
+    ///
+    /// eg/ an exchange invalidating the authenticated session of an established connection
+    /// yields a [`DataError::Auth`], so it re-connects & authenticates again, and an exchange
+    /// asking clients to re-connect (eg/ a server restart) yields a [`DataError::Reconnect`].
     #[allow(clippy::match_like_matches_macro)]
     pub fn is_terminal(&self) -> bool {
         match self {
             DataError::InvalidSequence { .. } => true,
+            DataError::Auth { .. } => true,
+            DataError::Reconnect { .. } => true,
             _ => false,
         }
     }
@@ -51,10 +158,57 @@ mod tests {
                 expected: true,
             },
             TestCase {
-                // TC1: is not terminal w/ DataError::Socket
+                // TC1: is not terminal w/ DataError::InvalidSubscriptions
+                input: DataError::InvalidSubscriptions(vec![]),
+                expected: false,
+            },
+            TestCase {
+                // TC2: is not terminal w/ DataError::Socket
                 input: DataError::Socket(SocketError::Sink),
                 expected: false,
             },
+            TestCase {
+                // TC3: is not terminal w/ DataError::InstrumentResync
+                input: DataError::InstrumentResync {
+                    instrument: Instrument::from((
+                        "btc",
+                        "usdt",
+                        barter_integration::model::InstrumentKind::Spot,
+                    )),
+                    reason: "gap".to_string(),
+                },
+                expected: false,
+            },
+            TestCase {
+                // TC4: is not terminal w/ DataError::ConsumerDropped
+                input: DataError::ConsumerDropped(ExchangeId::Okx),
+                expected: false,
+            },
+            TestCase {
+                // TC5: is terminal w/ DataError::Auth
+                input: DataError::Auth {
+                    exchange: ExchangeId::Kraken,
+                    reason: "ESession:Invalid session".to_string(),
+                },
+                expected: true,
+            },
+            TestCase {
+                // TC6: is not terminal w/ DataError::Exchange
+                input: DataError::Exchange {
+                    exchange: ExchangeId::Bitfinex,
+                    code: 10300,
+                    message: "Subscription failed (generic)".to_string(),
+                },
+                expected: false,
+            },
+            TestCase {
+                // TC7: is terminal w/ DataError::Reconnect
+                input: DataError::Reconnect {
+                    exchange: ExchangeId::Bitfinex,
+                    reason: "maintenance ended".to_string(),
+                },
+                expected: true,
+            },
         ];
 
         for (index, test) in tests.into_iter().enumerate() {
@@ -10,10 +10,25 @@ use crate::{
 use barter_integration::model::{Exchange, Instrument};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::{cmp::Ordering, fmt::Debug};
+
+/// Current version of the [`MarketEventEnvelope`] serialisation schema.
+///
+/// Must be incremented whenever the serde representation of a [`MarketEvent<T>`](MarketEvent) or
+/// any of the normalised [`DataKind`] payloads changes.
+pub const MARKET_EVENT_SCHEMA_VERSION: u16 = 1;
+
+/// Collection of [`MarketEvent<T>`](MarketEvent) results generated from a single exchange
+/// message.
+///
+/// Most exchange messages generate exactly one [`MarketEvent<T>`](MarketEvent), which is stored
+/// inline so the per-message output does not allocate.
+pub type MarketEvents<T> = SmallVec<[Result<MarketEvent<T>, DataError>; 1]>;
 
 /// Convenient new type containing a collection of [`MarketEvent<T>`](MarketEvent)s.
 #[derive(Debug)]
-pub struct MarketIter<T>(pub Vec<Result<MarketEvent<T>, DataError>>);
+pub struct MarketIter<T>(pub MarketEvents<T>);
 
 impl<T> FromIterator<Result<MarketEvent<T>, DataError>> for MarketIter<T> {
     fn from_iter<Iter>(iter: Iter) -> Self
@@ -34,13 +49,152 @@ impl<T> FromIterator<Result<MarketEvent<T>, DataError>> for MarketIter<T> {
 /// - [`MarketEvent<PublicTrade>`](crate::subscription::trade::PublicTrade)
 /// - [`MarketEvent<OrderBookL1>`](crate::subscription::book::OrderBookL1)
 /// - [`MarketEvent<DataKind>`](DataKind)
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize, Serialize)]
-pub struct MarketEvent<T> {
+///
+/// ### Metadata
+/// `Meta` is user metadata (eg/ a strategy id or label) attached to every event of a tagged
+/// [`Subscription`](crate::subscription::Subscription), see
+/// [`StreamBuilder::subscribe_with_meta`](crate::streams::builder::StreamBuilder::subscribe_with_meta).
+/// It defaults to `()`, so untagged events carry nothing. Comparing & ordering
+/// [`MarketEvent<T, Meta>`](Self)s ignores the `meta`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MarketEvent<T, Meta = ()> {
     pub exchange_time: DateTime<Utc>,
     pub received_time: DateTime<Utc>,
     pub exchange: Exchange,
     pub instrument: Instrument,
     pub kind: T,
+    /// Exchange timestamp exactly as provided by the exchange, if any. See [`RawTimestamp`] for
+    /// the precision of each exchange.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_exchange_time: Option<RawTimestamp>,
+    /// User metadata of the [`Subscription`](crate::subscription::Subscription) that generated
+    /// this event.
+    ///
+    /// Never serialised, since it's type is only known to the user. The
+    /// [`MarketEventStreamExt`](crate::streams::adapter::MarketEventStreamExt) adapters operate
+    /// on untagged events, see [`MarketEvent::with_meta`].
+    #[serde(skip)]
+    pub meta: Meta,
+}
+
+/// Unit of a [`RawTimestamp`] since the Unix epoch.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimestampUnit {
+    /// Number of nanoseconds in one of this [`TimestampUnit`].
+    pub fn nanos(&self) -> i64 {
+        match self {
+            Self::Seconds => 1_000_000_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Microseconds => 1_000,
+            Self::Nanoseconds => 1,
+        }
+    }
+
+    /// Next finer [`TimestampUnit`], if any.
+    fn finer(&self) -> Option<Self> {
+        match self {
+            Self::Seconds => Some(Self::Milliseconds),
+            Self::Milliseconds => Some(Self::Microseconds),
+            Self::Microseconds => Some(Self::Nanoseconds),
+            Self::Nanoseconds => None,
+        }
+    }
+}
+
+/// Exchange timestamp as provided by the exchange, retaining it's original precision alongside
+/// the normalised [`MarketEvent`] `exchange_time`.
+///
+/// ### Exchange Precision
+/// | Exchange          | Unit         | Source                                           |
+/// |-------------------|--------------|--------------------------------------------------|
+/// | Binance           | milliseconds | trade `T`, kline close time & liquidation `T`    |
+/// | Bitfinex          | milliseconds | trade `MTS`                                      |
+/// | Coinbase          | microseconds | RFC3339 trade `time`                             |
+/// | Gateio Spot       | sub-ms       | trade `create_time_ms`, eg/ "1606292218213.4578" |
+/// | Gateio Futures    | milliseconds | trade `create_time_ms`                           |
+/// | Kraken            | microseconds | trade & spread `time`, eg/ "1534614057.321597"   |
+/// | Okx               | milliseconds | trade `ts`                                       |
+///
+/// Binance OrderBookL1 (bookTicker) events carry no exchange timestamp, and OrderBook (L2)
+/// snapshots are normalised from several messages, so neither have a [`RawTimestamp`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct RawTimestamp {
+    pub value: i64,
+    pub unit: TimestampUnit,
+}
+
+impl RawTimestamp {
+    /// Construct a new [`Self`] from the provided value & [`TimestampUnit`].
+    pub fn new(value: i64, unit: TimestampUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Construct a new [`Self`] from a normalised [`DateTime<Utc>`], truncated to the provided
+    /// [`TimestampUnit`].
+    ///
+    /// Only use for timestamps that were normalised losslessly, eg/ integer milliseconds.
+    pub fn from_datetime(time: DateTime<Utc>, unit: TimestampUnit) -> Self {
+        let nanos = i128::from(time.timestamp()) * 1_000_000_000
+            + i128::from(time.timestamp_subsec_nanos());
+        let value = nanos.div_euclid(i128::from(unit.nanos()));
+        Self::new(i64::try_from(value).unwrap_or(i64::MAX), unit)
+    }
+
+    /// Parse a decimal epoch timestamp string in the provided [`TimestampUnit`] (eg/ Kraken's
+    /// "1534614057.321597" seconds) without floating point rounding. Fractional digits are
+    /// retained by selecting a finer [`TimestampUnit`], up to nanoseconds.
+    pub fn parse_decimal(input: &str, unit: TimestampUnit) -> Option<Self> {
+        let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+        if whole.is_empty() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut value = whole.parse::<i64>().ok()?;
+        let sign = if whole.starts_with('-') { -1 } else { 1 };
+        let mut unit = unit;
+        let mut digits = fraction.trim_end_matches('0').as_bytes();
+
+        while !digits.is_empty() {
+            let Some(finer) = unit.finer() else {
+                // Truncate digits beyond nanosecond precision
+                break;
+            };
+
+            let (chunk, rest) = digits.split_at(digits.len().min(3));
+            let chunk = chunk
+                .iter()
+                .chain(std::iter::repeat(&b'0'))
+                .take(3)
+                .fold(0, |acc, digit| acc * 10 + i64::from(digit - b'0'));
+
+            value = value.checked_mul(1000)?.checked_add(sign * chunk)?;
+            unit = finer;
+            digits = rest;
+        }
+
+        Some(Self::new(value, unit))
+    }
+
+    /// Nanoseconds since the Unix epoch.
+    pub fn as_nanos(&self) -> i128 {
+        i128::from(self.value) * i128::from(self.unit.nanos())
+    }
+
+    /// Normalise [`Self`] into a [`DateTime<Utc>`], if it's in range.
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        let nanos = self.as_nanos();
+        let seconds = i64::try_from(nanos.div_euclid(1_000_000_000)).ok()?;
+        let subsec_nanos = nanos.rem_euclid(1_000_000_000) as u32;
+        DateTime::from_timestamp(seconds, subsec_nanos)
+    }
 }
 
 /// Available kinds of normalised Barter [`MarketEvent<T>`](MarketEvent).
@@ -49,11 +203,14 @@ pub struct MarketEvent<T> {
 /// - [`Self`] is only used as the [`MarketEvent<DataKind>`](MarketEvent) `Output` when combining
 ///   several [`Streams<SubKind::Event>`](crate::streams::Streams) using the
 ///   [`MultiStreamBuilder<Output>`](crate::streams::builder::multi::MultiStreamBuilder).
-/// - [`Self`] is purposefully not supported in any
-///   [`Subscription`](crate::subscription::Subscription)s directly, it is only used to
-///   make ergonomic [`Streams`](crate::streams::Streams) containing many
-///   [`MarketEvent<T>`](MarketEvent) kinds.
+/// - [`Self`] is also the `SubKind::Event` of the [`combined`](crate::subscription::combined)
+///   [`SubKind`](crate::subscription::SubKind)s that yield several kinds of
+///   [`MarketEvent<T>`](MarketEvent) from a single connection.
+///
+/// ### Serde
+/// [`Self`] is adjacently tagged, serialising as `{"kind": "trade", "data": {...}}`.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
 pub enum DataKind {
     Trade(PublicTrade),
     OrderBookL1(OrderBookL1),
@@ -62,10 +219,12 @@ pub enum DataKind {
     Liquidation(Liquidation),
 }
 
-impl From<MarketEvent<PublicTrade>> for MarketEvent<DataKind> {
-    fn from(event: MarketEvent<PublicTrade>) -> Self {
+impl<Meta> From<MarketEvent<PublicTrade, Meta>> for MarketEvent<DataKind, Meta> {
+    fn from(event: MarketEvent<PublicTrade, Meta>) -> Self {
         Self {
             exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            meta: event.meta,
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
@@ -74,10 +233,12 @@ impl From<MarketEvent<PublicTrade>> for MarketEvent<DataKind> {
     }
 }
 
-impl From<MarketEvent<OrderBookL1>> for MarketEvent<DataKind> {
-    fn from(event: MarketEvent<OrderBookL1>) -> Self {
+impl<Meta> From<MarketEvent<OrderBookL1, Meta>> for MarketEvent<DataKind, Meta> {
+    fn from(event: MarketEvent<OrderBookL1, Meta>) -> Self {
         Self {
             exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            meta: event.meta,
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
@@ -86,10 +247,12 @@ impl From<MarketEvent<OrderBookL1>> for MarketEvent<DataKind> {
     }
 }
 
-impl From<MarketEvent<OrderBook>> for MarketEvent<DataKind> {
-    fn from(event: MarketEvent<OrderBook>) -> Self {
+impl<Meta> From<MarketEvent<OrderBook, Meta>> for MarketEvent<DataKind, Meta> {
+    fn from(event: MarketEvent<OrderBook, Meta>) -> Self {
         Self {
             exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            meta: event.meta,
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
@@ -98,10 +261,12 @@ impl From<MarketEvent<OrderBook>> for MarketEvent<DataKind> {
     }
 }
 
-impl From<MarketEvent<Candle>> for MarketEvent<DataKind> {
-    fn from(event: MarketEvent<Candle>) -> Self {
+impl<Meta> From<MarketEvent<Candle, Meta>> for MarketEvent<DataKind, Meta> {
+    fn from(event: MarketEvent<Candle, Meta>) -> Self {
         Self {
             exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            meta: event.meta,
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
@@ -110,10 +275,12 @@ impl From<MarketEvent<Candle>> for MarketEvent<DataKind> {
     }
 }
 
-impl From<MarketEvent<Liquidation>> for MarketEvent<DataKind> {
-    fn from(event: MarketEvent<Liquidation>) -> Self {
+impl<Meta> From<MarketEvent<Liquidation, Meta>> for MarketEvent<DataKind, Meta> {
+    fn from(event: MarketEvent<Liquidation, Meta>) -> Self {
         Self {
             exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            meta: event.meta,
             received_time: event.received_time,
             exchange: event.exchange,
             instrument: event.instrument,
@@ -121,3 +288,441 @@ impl From<MarketEvent<Liquidation>> for MarketEvent<DataKind> {
         }
     }
 }
+
+impl DataKind {
+    /// Return the &str name of this [`DataKind`] variant, as used in the serialised `kind` tag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataKind::Trade(_) => "trade",
+            DataKind::OrderBookL1(_) => "order_book_l1",
+            DataKind::OrderBook(_) => "order_book",
+            DataKind::Candle(_) => "candle",
+            DataKind::Liquidation(_) => "liquidation",
+        }
+    }
+}
+
+impl<Meta> TryFrom<MarketEvent<DataKind, Meta>> for MarketEvent<PublicTrade, Meta> {
+    type Error = DataError;
+
+    fn try_from(event: MarketEvent<DataKind, Meta>) -> Result<Self, Self::Error> {
+        match event.kind {
+            DataKind::Trade(trade) => Ok(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                meta: event.meta,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: trade,
+            }),
+            other => Err(DataError::KindMismatch {
+                expected: "trade",
+                actual: other.as_str(),
+            }),
+        }
+    }
+}
+
+impl<Meta> TryFrom<MarketEvent<DataKind, Meta>> for MarketEvent<OrderBookL1, Meta> {
+    type Error = DataError;
+
+    fn try_from(event: MarketEvent<DataKind, Meta>) -> Result<Self, Self::Error> {
+        match event.kind {
+            DataKind::OrderBookL1(book) => Ok(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                meta: event.meta,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: book,
+            }),
+            other => Err(DataError::KindMismatch {
+                expected: "order_book_l1",
+                actual: other.as_str(),
+            }),
+        }
+    }
+}
+
+impl<Meta> TryFrom<MarketEvent<DataKind, Meta>> for MarketEvent<OrderBook, Meta> {
+    type Error = DataError;
+
+    fn try_from(event: MarketEvent<DataKind, Meta>) -> Result<Self, Self::Error> {
+        match event.kind {
+            DataKind::OrderBook(book) => Ok(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                meta: event.meta,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: book,
+            }),
+            other => Err(DataError::KindMismatch {
+                expected: "order_book",
+                actual: other.as_str(),
+            }),
+        }
+    }
+}
+
+impl<Meta> TryFrom<MarketEvent<DataKind, Meta>> for MarketEvent<Candle, Meta> {
+    type Error = DataError;
+
+    fn try_from(event: MarketEvent<DataKind, Meta>) -> Result<Self, Self::Error> {
+        match event.kind {
+            DataKind::Candle(candle) => Ok(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                meta: event.meta,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: candle,
+            }),
+            other => Err(DataError::KindMismatch {
+                expected: "candle",
+                actual: other.as_str(),
+            }),
+        }
+    }
+}
+
+impl<Meta> TryFrom<MarketEvent<DataKind, Meta>> for MarketEvent<Liquidation, Meta> {
+    type Error = DataError;
+
+    fn try_from(event: MarketEvent<DataKind, Meta>) -> Result<Self, Self::Error> {
+        match event.kind {
+            DataKind::Liquidation(liquidation) => Ok(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                meta: event.meta,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: liquidation,
+            }),
+            other => Err(DataError::KindMismatch {
+                expected: "liquidation",
+                actual: other.as_str(),
+            }),
+        }
+    }
+}
+
+/// Stable, self-describing & versioned serialisation envelope of a
+/// [`MarketEvent<T>`](MarketEvent), used when persisting events.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "version": 1,
+///     "exchange": "binance_spot",
+///     "instrument": {"base": "btc", "quote": "usdt", "kind": "spot"},
+///     "exchange_time": "2023-01-01T00:00:00Z",
+///     "received_time": "2023-01-01T00:00:00.001Z",
+///     "kind": "trade",
+///     "data": {"id": "1", "price": 16500.5, "amount": 0.25, "side": "buy"}
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MarketEventEnvelope {
+    pub version: u16,
+    pub exchange: Exchange,
+    pub instrument: Instrument,
+    pub exchange_time: DateTime<Utc>,
+    pub received_time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_exchange_time: Option<RawTimestamp>,
+    #[serde(flatten)]
+    pub kind: DataKind,
+}
+
+impl From<MarketEvent<DataKind>> for MarketEventEnvelope {
+    fn from(event: MarketEvent<DataKind>) -> Self {
+        Self {
+            version: MARKET_EVENT_SCHEMA_VERSION,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            received_time: event.received_time,
+            kind: event.kind,
+        }
+    }
+}
+
+impl TryFrom<MarketEventEnvelope> for MarketEvent<DataKind> {
+    type Error = DataError;
+
+    fn try_from(envelope: MarketEventEnvelope) -> Result<Self, Self::Error> {
+        if envelope.version != MARKET_EVENT_SCHEMA_VERSION {
+            return Err(DataError::UnsupportedSchemaVersion(envelope.version));
+        }
+
+        Ok(Self {
+            exchange_time: envelope.exchange_time,
+            raw_exchange_time: envelope.raw_exchange_time,
+            meta: (),
+            received_time: envelope.received_time,
+            exchange: envelope.exchange,
+            instrument: envelope.instrument,
+            kind: envelope.kind,
+        })
+    }
+}
+
+impl<T, Meta> PartialEq for MarketEvent<T, Meta>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.fields() == other.fields()
+    }
+}
+
+impl<T, Meta> Eq for MarketEvent<T, Meta> where T: Eq {}
+
+impl<T, Meta> PartialOrd for MarketEvent<T, Meta>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.fields().partial_cmp(&other.fields())
+    }
+}
+
+impl<T, Meta> Ord for MarketEvent<T, Meta>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.fields().cmp(&other.fields())
+    }
+}
+
+impl<T, Meta> MarketEvent<T, Meta> {
+    /// Replace the user metadata of [`Self`] with the provided `meta`.
+    pub fn with_meta<NewMeta>(self, meta: NewMeta) -> MarketEvent<T, NewMeta> {
+        MarketEvent {
+            exchange_time: self.exchange_time,
+            received_time: self.received_time,
+            exchange: self.exchange,
+            instrument: self.instrument,
+            kind: self.kind,
+            raw_exchange_time: self.raw_exchange_time,
+            meta,
+        }
+    }
+
+    /// Every field of [`Self`] except the `meta`, in declaration order, used to compare & order
+    /// [`MarketEvent<T, Meta>`](Self)s.
+    #[allow(clippy::type_complexity)]
+    fn fields(
+        &self,
+    ) -> (
+        &DateTime<Utc>,
+        &DateTime<Utc>,
+        &Exchange,
+        &Instrument,
+        &T,
+        &Option<RawTimestamp>,
+    ) {
+        (
+            &self.exchange_time,
+            &self.received_time,
+            &self.exchange,
+            &self.instrument,
+            &self.kind,
+            &self.raw_exchange_time,
+        )
+    }
+}
+
+impl<T> MarketEvent<T> {
+    /// Serialise [`Self`] as a JSON [`MarketEventEnvelope`].
+    #[allow(clippy::result_large_err)]
+    pub fn to_json(&self) -> Result<String, DataError>
+    where
+        Self: Clone,
+        MarketEvent<DataKind>: From<Self>,
+    {
+        let envelope = MarketEventEnvelope::from(MarketEvent::<DataKind>::from(self.clone()));
+        serde_json::to_string(&envelope).map_err(DataError::from)
+    }
+
+    /// Deserialise [`Self`] from a JSON [`MarketEventEnvelope`], validating the schema version
+    /// and the event `kind`.
+    #[allow(clippy::result_large_err)]
+    pub fn from_json(input: &str) -> Result<Self, DataError>
+    where
+        Self: TryFrom<MarketEvent<DataKind>>,
+        DataError: From<<Self as TryFrom<MarketEvent<DataKind>>>::Error>,
+    {
+        let envelope = serde_json::from_str::<MarketEventEnvelope>(input)?;
+        let event = MarketEvent::<DataKind>::try_from(envelope)?;
+        Ok(Self::try_from(event)?)
+    }
+
+    /// Serialise [`Self`] as a `barter.data.v1.MarketEvent` protobuf message, as defined by
+    /// [`MARKET_EVENT_PROTO`](crate::protobuf::MARKET_EVENT_PROTO). Requires the `proto` feature.
+    #[cfg(feature = "proto")]
+    pub fn to_protobuf(&self) -> Vec<u8>
+    where
+        Self: Clone,
+        MarketEvent<DataKind>: From<Self>,
+    {
+        crate::protobuf::encode(&MarketEvent::<DataKind>::from(self.clone()))
+    }
+
+    /// Deserialise [`Self`] from a `barter.data.v1.MarketEvent` protobuf message, validating the
+    /// schema version and the event `kind`. Requires the `proto` feature.
+    #[cfg(feature = "proto")]
+    #[allow(clippy::result_large_err)]
+    pub fn from_protobuf(input: &[u8]) -> Result<Self, DataError>
+    where
+        Self: TryFrom<MarketEvent<DataKind>>,
+        DataError: From<<Self as TryFrom<MarketEvent<DataKind>>>::Error>,
+    {
+        let event = crate::protobuf::decode(input)?;
+        Ok(Self::try_from(event)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    #[test]
+    fn test_raw_timestamp_parse_decimal() {
+        struct TestCase {
+            input: &'static str,
+            unit: TimestampUnit,
+            expected: Option<RawTimestamp>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Kraken seconds w/ microsecond fraction
+                input: "1534614057.321597",
+                unit: TimestampUnit::Seconds,
+                expected: Some(RawTimestamp::new(
+                    1534614057321597,
+                    TimestampUnit::Microseconds,
+                )),
+            },
+            TestCase {
+                // TC1: Gateio milliseconds w/ sub-millisecond fraction
+                input: "1606292218213.4578",
+                unit: TimestampUnit::Milliseconds,
+                expected: Some(RawTimestamp::new(
+                    1606292218213457800,
+                    TimestampUnit::Nanoseconds,
+                )),
+            },
+            TestCase {
+                // TC2: integer milliseconds retain their unit
+                input: "1606292218213",
+                unit: TimestampUnit::Milliseconds,
+                expected: Some(RawTimestamp::new(
+                    1606292218213,
+                    TimestampUnit::Milliseconds,
+                )),
+            },
+            TestCase {
+                // TC3: trailing zeros do not increase precision
+                input: "1534614057.100",
+                unit: TimestampUnit::Seconds,
+                expected: Some(RawTimestamp::new(
+                    1534614057100,
+                    TimestampUnit::Milliseconds,
+                )),
+            },
+            TestCase {
+                // TC4: digits beyond nanoseconds are truncated
+                input: "1534614057.1234567891",
+                unit: TimestampUnit::Seconds,
+                expected: Some(RawTimestamp::new(
+                    1534614057123456789,
+                    TimestampUnit::Nanoseconds,
+                )),
+            },
+            TestCase {
+                // TC5: negative timestamps keep the fraction's sign
+                input: "-1.5",
+                unit: TimestampUnit::Seconds,
+                expected: Some(RawTimestamp::new(-1500, TimestampUnit::Milliseconds)),
+            },
+            TestCase {
+                // TC6: invalid input
+                input: "1534614057.12e3",
+                unit: TimestampUnit::Seconds,
+                expected: None,
+            },
+            TestCase {
+                // TC7: empty input
+                input: "",
+                unit: TimestampUnit::Seconds,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = RawTimestamp::parse_decimal(test.input, test.unit);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_raw_timestamp_datetime_round_trip() {
+        let time = DateTime::from_timestamp(1534614057, 321_597_000).unwrap();
+
+        let raw = RawTimestamp::from_datetime(time, TimestampUnit::Microseconds);
+        assert_eq!(
+            raw,
+            RawTimestamp::new(1534614057321597, TimestampUnit::Microseconds)
+        );
+        assert_eq!(raw.as_nanos(), 1534614057321597000);
+        assert_eq!(raw.to_datetime(), Some(time));
+
+        // Truncated to milliseconds
+        let raw = RawTimestamp::from_datetime(time, TimestampUnit::Milliseconds);
+        assert_eq!(
+            raw.to_datetime(),
+            DateTime::from_timestamp(1534614057, 321_000_000)
+        );
+
+        // Pre-epoch timestamps floor rather than truncate towards zero
+        let time = DateTime::from_timestamp(-1, 500_000_000).unwrap();
+        let raw = RawTimestamp::from_datetime(time, TimestampUnit::Seconds);
+        assert_eq!(raw, RawTimestamp::new(-1, TimestampUnit::Seconds));
+    }
+
+    #[test]
+    fn test_market_event_compare_ignores_meta() {
+        let event = |id: &str| MarketEvent {
+            exchange_time: DateTime::from_timestamp(1534614057, 0).unwrap(),
+            received_time: DateTime::from_timestamp(1534614058, 0).unwrap(),
+            exchange: Exchange::from("binance_spot"),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            kind: id.to_string(),
+            raw_exchange_time: None,
+            meta: (),
+        };
+
+        let tagged = event("1").with_meta("strategy_1");
+        let retagged = event("1").with_meta("strategy_2");
+
+        assert_eq!(tagged.meta, "strategy_1");
+        assert_eq!(tagged, retagged);
+        assert_eq!(tagged.cmp(&retagged), Ordering::Equal);
+        assert_ne!(tagged, event("2").with_meta("strategy_1"));
+        assert_eq!(
+            tagged.cmp(&event("2").with_meta("strategy_1")),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use super::ExchangeId;
+use barter_integration::model::{Instrument, InstrumentKind};
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+/// Default asset aliases of each exchange, as (exchange, canonical asset, exchange asset).
+///
+/// ### Notes
+/// - Kraken uses "XBT" for Bitcoin & "XDG" for Dogecoin.
+/// - Bitfinex uses "UST" for Tether & "UDC" for USD Coin.
+pub const DEFAULT_ASSET_ALIASES: &[(ExchangeId, &str, &str)] = &[
+    (ExchangeId::Kraken, "btc", "xbt"),
+    (ExchangeId::Kraken, "doge", "xdg"),
+    (ExchangeId::Bitfinex, "usdt", "ust"),
+    (ExchangeId::Bitfinex, "usdc", "udc"),
+];
+
+/// Table of exchange specific asset codes (eg/ Kraken "XBT") that differ from the canonical
+/// Barter asset symbol (eg/ "btc"), used by each [`Connector::Market`](super::Connector::Market)
+/// translation & [`ParseMarket`](super::market::ParseMarket) reverse mapping.
+///
+/// Assets are matched case insensitively, and assets without an alias are used as is.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct AssetAliases {
+    to_exchange: HashMap<(ExchangeId, String), String>,
+    to_canonical: HashMap<(ExchangeId, String), String>,
+}
+
+impl AssetAliases {
+    /// Construct a new [`Self`] containing the [`DEFAULT_ASSET_ALIASES`].
+    pub fn new() -> Self {
+        DEFAULT_ASSET_ALIASES.iter().fold(
+            Self::default(),
+            |mut aliases, (exchange, canonical, asset)| {
+                aliases.insert(*exchange, canonical, asset);
+                aliases
+            },
+        )
+    }
+
+    /// Insert an alias between the canonical asset & the exchange specific asset code of the
+    /// provided exchange, replacing any existing alias of either.
+    pub fn insert(&mut self, exchange: ExchangeId, canonical: &str, asset: &str) {
+        let canonical = canonical.to_lowercase();
+        let asset = asset.to_lowercase();
+
+        if let Some(previous) = self
+            .to_exchange
+            .insert((exchange, canonical.clone()), asset.clone())
+        {
+            self.to_canonical.remove(&(exchange, previous));
+        }
+        if let Some(previous) = self
+            .to_canonical
+            .insert((exchange, asset.clone()), canonical)
+        {
+            self.to_exchange.remove(&(exchange, previous));
+        }
+    }
+
+    /// Map the canonical asset to the lowercase asset code used by the provided exchange.
+    pub fn exchange_asset(&self, exchange: ExchangeId, canonical: &str) -> String {
+        let canonical = canonical.to_lowercase();
+        self.to_exchange
+            .get(&(exchange, canonical.clone()))
+            .cloned()
+            .unwrap_or(canonical)
+    }
+
+    /// Map the asset code used by the provided exchange to the lowercase canonical asset.
+    pub fn canonical_asset(&self, exchange: ExchangeId, asset: &str) -> String {
+        let asset = asset.to_lowercase();
+        self.to_canonical
+            .get(&(exchange, asset.clone()))
+            .cloned()
+            .unwrap_or(asset)
+    }
+}
+
+fn registry() -> &'static RwLock<AssetAliases> {
+    static REGISTRY: OnceLock<RwLock<AssetAliases>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(AssetAliases::new()))
+}
+
+/// Register an alias between the canonical asset & the exchange specific asset code of the
+/// provided exchange, in addition to the [`DEFAULT_ASSET_ALIASES`].
+///
+/// Useful for exchanges that list a market under a different asset code, or to deliberately map
+/// a quote onto another (eg/ `register_asset_alias(ExchangeId::BinanceSpot, "usd", "usdt")` to
+/// subscribe to "BTCUSDT" via a btc/usd [`Instrument`], which is then also how "BTCUSDT" is
+/// parsed).
+///
+/// Aliases must be registered before subscribing, since they determine the exchange market of
+/// each [`Subscription`](crate::subscription::Subscription).
+pub fn register_asset_alias(exchange: ExchangeId, canonical: &str, asset: &str) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(exchange, canonical, asset)
+}
+
+/// Map the canonical asset to the lowercase asset code used by the provided exchange
+/// (eg/ "btc" -> "xbt" for Kraken), using the registered [`AssetAliases`].
+pub fn exchange_asset(exchange: ExchangeId, canonical: &str) -> String {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .exchange_asset(exchange, canonical)
+}
+
+/// Map the asset code used by the provided exchange to the lowercase canonical asset
+/// (eg/ "XBT" -> "btc" for Kraken), using the registered [`AssetAliases`].
+pub fn canonical_asset(exchange: ExchangeId, asset: &str) -> String {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .canonical_asset(exchange, asset)
+}
+
+/// Map the base & quote of the provided [`Instrument`] to the lowercase asset codes used by the
+/// provided exchange.
+pub fn exchange_assets(exchange: ExchangeId, instrument: &Instrument) -> (String, String) {
+    (
+        exchange_asset(exchange, instrument.base.as_ref()),
+        exchange_asset(exchange, instrument.quote.as_ref()),
+    )
+}
+
+/// Construct the canonical [`Instrument`] of the provided exchange specific base & quote assets.
+pub fn canonical_instrument(
+    exchange: ExchangeId,
+    base: &str,
+    quote: &str,
+    kind: InstrumentKind,
+) -> Instrument {
+    Instrument::from((
+        canonical_asset(exchange, base),
+        canonical_asset(exchange, quote),
+        kind,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_aliases() {
+        struct TestCase {
+            exchange: ExchangeId,
+            canonical: &'static str,
+            asset: &'static str,
+        }
+
+        let aliases = AssetAliases::new();
+
+        let tests = vec![
+            TestCase {
+                // TC0: Kraken XBT alias for Bitcoin
+                exchange: ExchangeId::Kraken,
+                canonical: "btc",
+                asset: "xbt",
+            },
+            TestCase {
+                // TC1: Kraken XDG alias for Dogecoin
+                exchange: ExchangeId::Kraken,
+                canonical: "doge",
+                asset: "xdg",
+            },
+            TestCase {
+                // TC2: Bitfinex UST alias for Tether
+                exchange: ExchangeId::Bitfinex,
+                canonical: "usdt",
+                asset: "ust",
+            },
+            TestCase {
+                // TC3: aliases are exchange specific
+                exchange: ExchangeId::Coinbase,
+                canonical: "btc",
+                asset: "btc",
+            },
+            TestCase {
+                // TC4: assets without an alias are used as is
+                exchange: ExchangeId::Kraken,
+                canonical: "eth",
+                asset: "eth",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(
+                aliases.exchange_asset(test.exchange, test.canonical),
+                test.asset,
+                "TC{} failed",
+                index
+            );
+            assert_eq!(
+                aliases.canonical_asset(test.exchange, &test.asset.to_uppercase()),
+                test.canonical,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_asset_aliases_insert_replaces_existing() {
+        let mut aliases = AssetAliases::new();
+        aliases.insert(ExchangeId::Kraken, "BTC", "XXBT");
+
+        assert_eq!(aliases.exchange_asset(ExchangeId::Kraken, "btc"), "xxbt");
+        assert_eq!(aliases.canonical_asset(ExchangeId::Kraken, "xxbt"), "btc");
+        // Previous alias of the canonical asset no longer maps back to it
+        assert_eq!(aliases.canonical_asset(ExchangeId::Kraken, "xbt"), "xbt");
+    }
+
+    #[test]
+    fn test_register_asset_alias() {
+        // Synthetic assets avoid interfering with concurrently running tests
+        register_asset_alias(ExchangeId::Okx, "Barter", "BRTR");
+
+        let instrument = Instrument::from(("barter", "usdt", InstrumentKind::Spot));
+        assert_eq!(
+            exchange_assets(ExchangeId::Okx, &instrument),
+            ("brtr".to_string(), "usdt".to_string())
+        );
+        assert_eq!(
+            canonical_instrument(ExchangeId::Okx, "BRTR", "USDT", InstrumentKind::Spot),
+            instrument
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use super::{ExchangeId, StreamSelector};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, RawTimestamp, TimestampUnit},
+    streams::{adapter::AsTrade, config::StreamConfig},
+    subscription::{candle::Candle, SubKind, Subscription},
+};
+use async_trait::async_trait;
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use std::{collections::HashSet, future::Future, time::Duration};
+
+/// Defines how an exchange fetches recent historical [`MarketEvent<T>`](MarketEvent)s for a
+/// [`Subscription`] via it's REST API.
+///
+/// Used by [`StreamBuilder::subscribe_with_backfill`](crate::streams::builder::StreamBuilder::subscribe_with_backfill)
+/// to emit historical context before the live [`MarketStream`](crate::MarketStream) events.
+#[async_trait]
+pub trait Backfill<Kind>
+where
+    Self: StreamSelector<Kind> + Sync,
+    Kind: SubKind + Sync,
+{
+    /// Fetch (at most) the last `limit` [`MarketEvent<T>`](MarketEvent)s for the provided
+    /// [`Subscription`], ordered by `exchange_time` ascending, using the REST configuration (eg/
+    /// [`Proxy`](crate::subscriber::proxy::Proxy)) of the provided [`StreamConfig`].
+    async fn backfill(
+        subscription: &Subscription<Self, Kind>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Kind::Event>>, DataError>;
+}
+
+/// Fetch (at most) the last `limit` closed [`Candle`]s by walking backwards through pages of at
+/// most `page_limit` candles fetched via an exchange REST API, waiting `page_interval` between
+/// requests to respect the exchange REST rate limits.
+///
+/// `fetch_page` is provided the close time the candles of the page must be older than (`None`
+/// for the most recent page) and the number of candles to request. Returning an empty page (eg/
+/// from an exchange that cannot paginate backwards) ends the walk.
+///
+/// The returned [`Candle`]s are ordered by `close_time` ascending, contain no duplicates, exclude
+/// the still open candle, and are all marked as `historical`.
+pub async fn fetch_candle_pages<FnPage, Fut>(
+    limit: usize,
+    page_limit: usize,
+    page_interval: Duration,
+    mut fetch_page: FnPage,
+) -> Result<Vec<Candle>, DataError>
+where
+    FnPage: FnMut(Option<DateTime<Utc>>, usize) -> Fut,
+    Fut: Future<Output = Result<Vec<Candle>, DataError>>,
+{
+    let now = Utc::now();
+    let mut candles: Vec<Candle> = Vec::with_capacity(limit);
+    let mut before = None;
+
+    while candles.len() < limit {
+        if before.is_some() {
+            tokio::time::sleep(page_interval).await;
+        }
+
+        // Request an additional candle since the most recent is usually still open
+        let requested = (limit - candles.len() + 1).min(page_limit.max(1));
+        let mut page = fetch_page(before, requested).await?;
+        let exhausted = page.len() < requested;
+
+        page.retain(|candle| {
+            candle.close_time <= now && before.is_none_or(|before| candle.close_time < before)
+        });
+        page.sort_by_key(|candle| candle.close_time);
+        page.dedup_by_key(|candle| candle.close_time);
+
+        let Some(oldest) = page.first() else {
+            break;
+        };
+        before = Some(oldest.close_time);
+
+        page.append(&mut candles);
+        candles = page;
+
+        if exhausted {
+            break;
+        }
+    }
+
+    // Keep only the most recent `limit` closed candles
+    let excess = candles.len().saturating_sub(limit);
+    candles.drain(..excess);
+
+    for candle in candles.iter_mut() {
+        candle.historical = true;
+    }
+
+    Ok(candles)
+}
+
+/// Construct the [`MarketEvent<Candle>`](MarketEvent)s of historical [`Candle`]s fetched via an
+/// exchange REST API, using each `close_time` as the `exchange_time`.
+pub fn candle_events(
+    exchange: ExchangeId,
+    instrument: &Instrument,
+    candles: Vec<Candle>,
+) -> Vec<MarketEvent<Candle>> {
+    let received_time = Utc::now();
+    candles
+        .into_iter()
+        .map(|candle| MarketEvent {
+            exchange_time: candle.close_time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                candle.close_time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange),
+            instrument: instrument.clone(),
+            kind: candle,
+            meta: (),
+        })
+        .collect()
+}
+
+/// Tail of a backfill for a single instrument, used to drop live
+/// [`MarketEvent<T>`](MarketEvent)s that overlap with the backfilled events.
+///
+/// A live event overlaps if it's `exchange_time` is older than the latest backfilled
+/// `exchange_time`, or equal to it and it is either a trade with an already backfilled id, or
+/// not a trade at all (eg/ the same candle).
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct BackfillWatermark {
+    pub time: Option<DateTime<Utc>>,
+    pub ids: HashSet<String>,
+}
+
+impl<T> From<&[MarketEvent<T>]> for BackfillWatermark
+where
+    T: AsTrade,
+{
+    fn from(events: &[MarketEvent<T>]) -> Self {
+        let time = events.iter().map(|event| event.exchange_time).max();
+
+        let ids = events
+            .iter()
+            .filter(|event| Some(event.exchange_time) == time)
+            .filter_map(|event| event.kind.as_trade().map(|trade| trade.id.clone()))
+            .collect();
+
+        Self { time, ids }
+    }
+}
+
+impl BackfillWatermark {
+    /// Determine if the provided live [`MarketEvent<T>`](MarketEvent) overlaps with the backfill.
+    pub fn overlaps<T>(&self, event: &MarketEvent<T>) -> bool
+    where
+        T: AsTrade,
+    {
+        let Some(time) = self.time else {
+            return false;
+        };
+
+        if event.exchange_time != time {
+            return event.exchange_time < time;
+        }
+
+        match event.kind.as_trade() {
+            Some(trade) => self.ids.contains(&trade.id),
+            None => true,
+        }
+    }
+
+    /// Determine if the provided live [`MarketEvent<T>`](MarketEvent) is newer than every
+    /// backfilled event, meaning no subsequent live event can overlap.
+    pub fn is_passed_by<T>(&self, event: &MarketEvent<T>) -> bool {
+        self.time.is_none_or(|time| event.exchange_time > time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::{candle::Candle, trade::PublicTrade};
+    use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+    use chrono::{Duration, TimeZone};
+
+    fn time(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(ms).unwrap()
+    }
+
+    fn event<T>(ms: i64, kind: T) -> MarketEvent<T> {
+        MarketEvent {
+            exchange_time: time(ms),
+            raw_exchange_time: None,
+            received_time: time(ms),
+            exchange: Exchange::from("binance_spot"),
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            kind,
+            meta: (),
+        }
+    }
+
+    fn trade(ms: i64, id: &str) -> MarketEvent<PublicTrade> {
+        event(
+            ms,
+            PublicTrade {
+                id: id.to_string(),
+                price: num!(1.0),
+                amount: num!(1.0),
+                side: Side::Buy,
+            },
+        )
+    }
+
+    fn candle(close_ms: i64) -> MarketEvent<Candle> {
+        event(
+            close_ms,
+            Candle {
+                close_time: time(close_ms),
+                open: num!(1.0),
+                high: num!(1.0),
+                low: num!(1.0),
+                close: num!(1.0),
+                volume: num!(1.0),
+                trade_count: 1,
+                historical: true,
+            },
+        )
+    }
+
+    #[test]
+    fn test_trade_watermark_overlaps() {
+        let backfill = vec![trade(100, "1"), trade(200, "2"), trade(200, "3")];
+        let watermark = BackfillWatermark::from(backfill.as_slice());
+
+        struct TestCase {
+            input: MarketEvent<PublicTrade>,
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: older than backfill tail
+                input: trade(150, "x"),
+                expected: true,
+            },
+            TestCase {
+                // TC1: same time as tail & already backfilled id
+                input: trade(200, "3"),
+                expected: true,
+            },
+            TestCase {
+                // TC2: same time as tail & new id
+                input: trade(200, "4"),
+                expected: false,
+            },
+            TestCase {
+                // TC3: newer than backfill tail
+                input: trade(201, "5"),
+                expected: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = watermark.overlaps(&test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_candle_watermark_overlaps() {
+        let minute = Duration::minutes(1).num_milliseconds();
+        let backfill = vec![candle(minute), candle(2 * minute)];
+        let watermark = BackfillWatermark::from(backfill.as_slice());
+
+        assert!(watermark.overlaps(&candle(2 * minute)));
+        assert!(!watermark.overlaps(&candle(3 * minute)));
+        assert!(watermark.is_passed_by(&candle(3 * minute)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetch_candle_pages() {
+        // Exchange history of 25 closed 1m candles, plus the still open candle
+        let minute = Duration::minutes(1).num_milliseconds();
+        let open = Utc::now().timestamp_millis() / minute * minute;
+        let history = (-25..=0)
+            .map(|offset| candle(open + offset * minute + minute - 1).kind)
+            .collect::<Vec<_>>();
+
+        // Mock exchange page of (at most) `page_limit` candles closed before `before`, newest
+        // first, with the oldest candle of the previous page overlapping
+        let mut requests = Vec::new();
+        let actual = fetch_candle_pages(
+            22,
+            10,
+            std::time::Duration::from_millis(100),
+            |before: Option<DateTime<Utc>>, page_limit| {
+                requests.push((before.is_some(), page_limit));
+                let mut page = history
+                    .iter()
+                    .rev()
+                    .filter(|candle| before.is_none_or(|before| candle.close_time <= before))
+                    .take(page_limit)
+                    .copied()
+                    .collect::<Vec<_>>();
+                page.reverse();
+                std::future::ready(Ok(page))
+            },
+        )
+        .await
+        .unwrap();
+
+        // TC0: pages are requested until the limit is reached
+        assert_eq!(requests, vec![(false, 10), (true, 10), (true, 5)]);
+
+        // TC1: the most recent 22 closed candles, with no gaps, duplicates or open candle
+        let expected = history[3..25]
+            .iter()
+            .map(|candle| Candle {
+                historical: true,
+                ..*candle
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_empty_watermark_never_overlaps() {
+        let watermark = BackfillWatermark::from(Vec::<MarketEvent<Candle>>::new().as_slice());
+
+        assert!(!watermark.overlaps(&candle(0)));
+        assert!(watermark.is_passed_by(&candle(0)));
+    }
+}
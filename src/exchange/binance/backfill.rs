@@ -0,0 +1,376 @@
+use super::{
+    futures::{BinanceFuturesUsd, HTTP_BASE_URL_BINANCE_FUTURES_USD},
+    market::BinanceMarket,
+    spot::{BinanceSpot, HTTP_BASE_URL_BINANCE_SPOT},
+    us::{BinanceUs, HTTP_BASE_URL_BINANCE_US},
+    Binance,
+};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, RawTimestamp, TimestampUnit},
+    exchange::{
+        backfill::{candle_events, fetch_candle_pages, Backfill},
+        Connector, ExchangeServer,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        candle::{Candle, Candles},
+        trade::{PublicTrade, PublicTrades},
+        Subscription,
+    },
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{model::Exchange, model::Side};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Maximum number of trades or klines [`Binance`] returns from a single REST request.
+pub const BINANCE_MAX_BACKFILL_LIMIT: usize = 1000;
+
+/// [`Duration`] waited between paginated [`Binance`] kline requests, comfortably within the
+/// REST request weight limits.
+pub const BINANCE_BACKFILL_PAGE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// [`Binance`] REST recent trade.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#recent-trades-list>
+/// ```json
+/// {
+///     "id": 28457,
+///     "price": "4.00000100",
+///     "qty": "12.00000000",
+///     "quoteQty": "48.000012",
+///     "time": 1499865549590,
+///     "isBuyerMaker": true,
+///     "isBestMatch": true
+/// }
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceTradeRest {
+    pub id: u64,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(alias = "qty", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    #[serde(deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(
+        alias = "isBuyerMaker",
+        deserialize_with = "super::trade::de_side_from_buyer_is_maker"
+    )]
+    pub side: Side,
+}
+
+impl From<BinanceTradeRest> for PublicTrade {
+    fn from(trade: BinanceTradeRest) -> Self {
+        Self {
+            id: trade.id.to_string(),
+            price: trade.price,
+            amount: trade.amount,
+            side: trade.side,
+        }
+    }
+}
+
+/// [`Binance`] REST kline (candle).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data>
+/// ```json
+/// [
+///     1499040000000,
+///     "0.01634790",
+///     "0.80000000",
+///     "0.01575800",
+///     "0.01577100",
+///     "148976.11427815",
+///     1499644799999,
+///     "2434.19055334",
+///     308,
+///     "1756.87402397",
+///     "28.46694368",
+///     "0"
+/// ]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceKlineRest(
+    pub u64,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc")] pub DateTime<Utc>,
+    pub String,
+    pub u64,
+    pub String,
+    pub String,
+    pub String,
+);
+
+impl From<BinanceKlineRest> for Candle {
+    fn from(kline: BinanceKlineRest) -> Self {
+        Self {
+            close_time: kline.6,
+            open: kline.1,
+            high: kline.2,
+            low: kline.3,
+            close: kline.4,
+            volume: kline.5,
+            trade_count: kline.8,
+            historical: true,
+        }
+    }
+}
+
+/// Fetch the most recent [`PublicTrade`]s for the provided [`Subscription`] from the [`Binance`]
+/// REST API with the provided base url, using the [`StreamConfig`] of the connection.
+pub async fn fetch_trades<Server>(
+    base_url: &str,
+    subscription: &Subscription<Binance<Server>, PublicTrades>,
+    limit: usize,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<PublicTrade>>, DataError>
+where
+    Server: ExchangeServer,
+{
+    let market: BinanceMarket = subscription.id();
+    let base_url = config.endpoints.rest_base_url(base_url);
+    let url = format!(
+        "{base_url}/trades?symbol={}&limit={}",
+        market.as_ref(),
+        limit.min(BINANCE_MAX_BACKFILL_LIMIT)
+    );
+
+    let trades = get_json::<Vec<BinanceTradeRest>>(config.proxy.as_ref(), &url).await?;
+
+    Ok(trades
+        .into_iter()
+        .map(|trade| MarketEvent {
+            exchange_time: trade.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time: Utc::now(),
+            exchange: Exchange::from(Binance::<Server>::ID),
+            instrument: subscription.instrument.clone(),
+            kind: PublicTrade::from(trade),
+            meta: (),
+        })
+        .collect())
+}
+
+/// Fetch the most recent closed [`Candle`]s for the provided [`Subscription`] from the
+/// [`Binance`] REST API with the provided base url, using the [`StreamConfig`] of the connection.
+///
+/// A `limit` beyond [`BINANCE_MAX_BACKFILL_LIMIT`] is fetched via multiple paginated requests
+/// (see [`fetch_candle_pages`]).
+pub async fn fetch_candles<Server>(
+    base_url: &str,
+    subscription: &Subscription<Binance<Server>, Candles>,
+    limit: usize,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<Candle>>, DataError>
+where
+    Server: ExchangeServer,
+{
+    let market: BinanceMarket = subscription.id();
+    let base_url = config.endpoints.rest_base_url(base_url);
+    let interval = subscription.kind.0;
+    let proxy = config.proxy.as_ref();
+
+    let candles = fetch_candle_pages(
+        limit,
+        BINANCE_MAX_BACKFILL_LIMIT,
+        BINANCE_BACKFILL_PAGE_INTERVAL,
+        |before, page_limit| {
+            let mut url = format!(
+                "{base_url}/klines?symbol={}&interval={interval}&limit={page_limit}",
+                market.as_ref(),
+            );
+            if let Some(before) = before {
+                url.push_str(&format!(
+                    "&endTime={}",
+                    (before - interval.duration()).timestamp_millis()
+                ));
+            }
+
+            async move {
+                let klines = get_json::<Vec<BinanceKlineRest>>(proxy, &url).await?;
+                Ok(klines.into_iter().map(Candle::from).collect())
+            }
+        },
+    )
+    .await?;
+
+    Ok(candle_events(
+        Binance::<Server>::ID,
+        &subscription.instrument,
+        candles,
+    ))
+}
+
+#[async_trait]
+impl Backfill<PublicTrades> for BinanceSpot {
+    async fn backfill(
+        subscription: &Subscription<Self, PublicTrades>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<PublicTrade>>, DataError> {
+        fetch_trades(HTTP_BASE_URL_BINANCE_SPOT, subscription, limit, config).await
+    }
+}
+
+#[async_trait]
+impl Backfill<PublicTrades> for BinanceFuturesUsd {
+    async fn backfill(
+        subscription: &Subscription<Self, PublicTrades>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<PublicTrade>>, DataError> {
+        fetch_trades(
+            HTTP_BASE_URL_BINANCE_FUTURES_USD,
+            subscription,
+            limit,
+            config,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Backfill<PublicTrades> for BinanceUs {
+    async fn backfill(
+        subscription: &Subscription<Self, PublicTrades>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<PublicTrade>>, DataError> {
+        fetch_trades(HTTP_BASE_URL_BINANCE_US, subscription, limit, config).await
+    }
+}
+
+#[async_trait]
+impl Backfill<Candles> for BinanceSpot {
+    async fn backfill(
+        subscription: &Subscription<Self, Candles>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+        fetch_candles(HTTP_BASE_URL_BINANCE_SPOT, subscription, limit, config).await
+    }
+}
+
+#[async_trait]
+impl Backfill<Candles> for BinanceFuturesUsd {
+    async fn backfill(
+        subscription: &Subscription<Self, Candles>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+        fetch_candles(
+            HTTP_BASE_URL_BINANCE_FUTURES_USD,
+            subscription,
+            limit,
+            config,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Backfill<Candles> for BinanceUs {
+    async fn backfill(
+        subscription: &Subscription<Self, Candles>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+        fetch_candles(HTTP_BASE_URL_BINANCE_US, subscription, limit, config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    mod de {
+        use super::*;
+        use barter_integration::de::datetime_utc_from_epoch_duration;
+        use std::time::Duration;
+
+        #[test]
+        fn test_binance_trade_rest() {
+            let input = r#"
+            [
+                {
+                    "id": 28457, "price": "4.00000100", "qty": "12.00000000",
+                    "quoteQty": "48.000012", "time": 1499865549590, "isBuyerMaker": true,
+                    "isBestMatch": true
+                },
+                {
+                    "id": 28458, "price": "4.5", "qty": "1.0", "quoteQty": "4.5",
+                    "time": 1499865549591, "isBuyerMaker": false
+                }
+            ]
+            "#;
+
+            let actual = serde_json::from_str::<Vec<BinanceTradeRest>>(input).unwrap();
+            let expected = vec![
+                BinanceTradeRest {
+                    id: 28457,
+                    price: num!(4.000001),
+                    amount: num!(12.0),
+                    time: datetime_utc_from_epoch_duration(Duration::from_millis(1499865549590)),
+                    side: Side::Sell,
+                },
+                BinanceTradeRest {
+                    id: 28458,
+                    price: num!(4.5),
+                    amount: num!(1.0),
+                    time: datetime_utc_from_epoch_duration(Duration::from_millis(1499865549591)),
+                    side: Side::Buy,
+                },
+            ];
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn test_binance_kline_rest() {
+            let input = r#"
+            [
+                [
+                    1499040000000, "0.01634790", "0.80000000", "0.01575800", "0.01577100",
+                    "148976.11427815", 1499644799999, "2434.19055334", 308, "1756.87402397",
+                    "28.46694368", "0"
+                ]
+            ]
+            "#;
+
+            let actual = serde_json::from_str::<Vec<BinanceKlineRest>>(input)
+                .unwrap()
+                .into_iter()
+                .map(Candle::from)
+                .collect::<Vec<_>>();
+
+            let expected = vec![Candle {
+                close_time: datetime_utc_from_epoch_duration(Duration::from_millis(1499644799999)),
+                open: num!(0.0163479),
+                high: num!(0.8),
+                low: num!(0.015758),
+                close: num!(0.015771),
+                volume: num!(148976.11427815),
+                trade_count: 308,
+                historical: true,
+            }];
+
+            assert_eq!(actual, expected);
+        }
+    }
+}
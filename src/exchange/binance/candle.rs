@@ -0,0 +1,236 @@
+use super::BinanceChannel;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{ExchangeId, ExchangeSub},
+    num::Num,
+    subscription::candle::{Candle, Interval},
+    Identifier,
+};
+use barter_integration::model::{Exchange, Instrument, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+
+/// Binance real-time kline (candle) message.
+///
+/// Note:
+/// Binance sends a kline message for every update to the open candle, so only the final message
+/// of each candle (`"x": true`) is normalised into a Barter [`Candle`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-streams>
+/// ```json
+/// {
+///     "e": "kline",
+///     "E": 1672515782136,
+///     "s": "BTCUSDT",
+///     "k": {
+///         "t": 1672515780000,
+///         "T": 1672515839999,
+///         "s": "BTCUSDT",
+///         "i": "1m",
+///         "f": 100,
+///         "L": 200,
+///         "o": "16500.10",
+///         "c": "16510.20",
+///         "h": "16520.00",
+///         "l": "16490.00",
+///         "v": "10.5",
+///         "n": 101,
+///         "x": true,
+///         "q": "173355.10",
+///         "V": "5.5",
+///         "Q": "90812.12",
+///         "B": "0"
+///     }
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceKline {
+    #[serde(alias = "k")]
+    pub kline: BinanceKlineData,
+}
+
+/// Binance kline (candle) data contained in a [`BinanceKline`] message.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-streams>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceKlineData {
+    #[serde(alias = "s")]
+    pub market: String,
+    #[serde(alias = "i")]
+    pub interval: Interval,
+    #[serde(
+        alias = "T",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub close_time: DateTime<Utc>,
+    #[serde(alias = "o", deserialize_with = "barter_integration::de::de_str")]
+    pub open: Num,
+    #[serde(alias = "h", deserialize_with = "barter_integration::de::de_str")]
+    pub high: Num,
+    #[serde(alias = "l", deserialize_with = "barter_integration::de::de_str")]
+    pub low: Num,
+    #[serde(alias = "c", deserialize_with = "barter_integration::de::de_str")]
+    pub close: Num,
+    #[serde(alias = "v", deserialize_with = "barter_integration::de::de_str")]
+    pub volume: Num,
+    #[serde(alias = "n")]
+    pub trade_count: u64,
+    #[serde(alias = "x")]
+    pub closed: bool,
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceKline {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(
+            ExchangeSub::from((
+                BinanceChannel::candles(self.kline.interval),
+                self.kline.market.as_str(),
+            ))
+            .id(),
+        )
+    }
+}
+
+impl From<&BinanceKlineData> for Candle {
+    fn from(kline: &BinanceKlineData) -> Self {
+        Self {
+            close_time: kline.close_time,
+            open: kline.open,
+            high: kline.high,
+            low: kline.low,
+            close: kline.close,
+            volume: kline.volume,
+            trade_count: kline.trade_count,
+            historical: false,
+        }
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceKline)> for MarketIter<Candle> {
+    fn from(
+        (exchange_id, instrument, received_time, kline): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceKline,
+        ),
+    ) -> Self {
+        // Ignore updates to a candle that has not yet closed
+        if !kline.kline.closed {
+            return Self(smallvec![]);
+        }
+
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: kline.kline.close_time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                kline.kline.close_time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: Candle::from(&kline.kline),
+            meta: (),
+        })])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    mod de {
+        use super::*;
+        use barter_integration::de::datetime_utc_from_epoch_duration;
+        use barter_integration::error::SocketError;
+        use serde::de::Error;
+        use std::time::Duration;
+
+        #[test]
+        fn test_binance_kline() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BinanceKline, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: valid closed kline
+                    input: r#"
+                    {
+                        "e":"kline","E":1672515782136,"s":"BTCUSDT",
+                        "k":{
+                            "t":1672515780000,"T":1672515839999,"s":"BTCUSDT","i":"1m","f":100,
+                            "L":200,"o":"16500.10","c":"16510.20","h":"16520.00","l":"16490.00",
+                            "v":"10.5","n":101,"x":true,"q":"173355.10","V":"5.5","Q":"90812.12",
+                            "B":"0"
+                        }
+                    }
+                    "#,
+                    expected: Ok(BinanceKline {
+                        kline: BinanceKlineData {
+                            market: "BTCUSDT".to_string(),
+                            interval: Interval::M1,
+                            close_time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                                1672515839999,
+                            )),
+                            open: num!(16500.10),
+                            high: num!(16520.00),
+                            low: num!(16490.00),
+                            close: num!(16510.20),
+                            volume: num!(10.5),
+                            trade_count: 101,
+                            closed: true,
+                        },
+                    }),
+                },
+                TestCase {
+                    // TC1: invalid kline w/ non-numeric open price
+                    input: r#"
+                    {
+                        "e":"kline","E":1672515782136,"s":"BTCUSDT",
+                        "k":{
+                            "t":1672515780000,"T":1672515839999,"s":"BTCUSDT","i":"1m",
+                            "o":"open","c":"16510.20","h":"16520.00","l":"16490.00","v":"10.5",
+                            "n":101,"x":true
+                        }
+                    }
+                    "#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde_json::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceKline>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binance_kline_subscription_id() {
+        let kline = serde_json::from_str::<BinanceKline>(
+            r#"{"k":{"T":1672515839999,"s":"BTCUSDT","i":"15m","o":"1","c":"1","h":"1","l":"1","v":"1","n":1,"x":false}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(kline.id(), Some(SubscriptionId::from("@kline_15m|BTCUSDT")));
+    }
+}
@@ -1,15 +1,26 @@
-use super::{futures::BinanceFuturesUsd, Binance};
+use super::{futures::BinanceFuturesUsd, spot::BinanceSpot, Binance};
 use crate::{
     subscription::{
+        average_price::AveragePrices,
+        balance::Balances,
         book::{OrderBooksL1, OrderBooksL2},
+        candle::{Candles, Interval},
+        combined::TradesAndBooksL1,
+        instrument_status::InstrumentStatuses,
         liquidation::Liquidations,
-        trade::PublicTrades,
+        open_interest::OpenInterests,
+        order::OrderUpdates,
+        ticker::Tickers,
+        trade::{PublicTrades, PublicTradesAggregated},
         Subscription,
     },
     Identifier,
 };
 use serde::Serialize;
 
+/// Rolling window [`Interval`]s supported by the [`BinanceChannel::tickers`] channel.
+pub const BINANCE_TICKER_WINDOWS: [Interval; 3] = [Interval::H1, Interval::H4, Interval::D1];
+
 /// Type that defines how to translate a Barter [`Subscription`] into a [`Binance`](super::Binance)
 /// channel to be subscribed to.
 ///
@@ -30,12 +41,28 @@ impl BinanceChannel {
     /// See discord: <https://discord.com/channels/910237311332151317/923160222711812126/975712874582388757>
     pub const TRADES: Self = Self("@trade");
 
+    /// [`Binance`](super::Binance) real-time aggregate trades channel name.
+    ///
+    /// Consecutive fills of the same taker order at the same price are aggregated into a single
+    /// trade covering a range of trade ids, so it's a lower volume feed than [`Self::TRADES`].
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#aggregate-trade-streams>
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#aggregate-trade-streams>
+    pub const AGG_TRADES: Self = Self("@aggTrade");
+
     /// [`Binance`](super::Binance) real-time OrderBook Level1 (top of book) channel name.
     ///
     /// See docs:<https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-book-ticker-streams>
     /// See docs:<https://binance-docs.github.io/apidocs/futures/en/#individual-symbol-book-ticker-streams>
     pub const ORDER_BOOK_L1: Self = Self("@bookTicker");
 
+    /// [`Binance`](super::Binance) combined real-time trades & OrderBook Level1 channel name.
+    ///
+    /// Note:
+    /// This is not a channel Binance recognises, [`Binance`](super::Binance) expands it into
+    /// both the [`Self::TRADES`] and [`Self::ORDER_BOOK_L1`] streams of the same connection.
+    pub const TRADES_AND_ORDER_BOOK_L1: Self = Self("@trade+@bookTicker");
+
     /// [`Binance`](super::Binance) OrderBook Level2 channel name (100ms delta updates).
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#diff-depth-stream>
@@ -46,6 +73,75 @@ impl BinanceChannel {
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#liquidation-order-streams>
     pub const LIQUIDATIONS: Self = Self("@forceOrder");
+
+    /// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) open interest REST endpoint.
+    ///
+    /// Note:
+    /// This is not a WebSocket channel, open interest is polled via the REST API.
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#open-interest>
+    pub const OPEN_INTEREST: Self = Self("openInterest");
+
+    /// [`Binance`](super::Binance) `exchangeInfo` REST endpoint, listing the trading status of
+    /// each symbol.
+    ///
+    /// Note:
+    /// This is not a WebSocket channel, instrument statuses are polled via the REST API.
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
+    pub const EXCHANGE_INFO: Self = Self("exchangeInfo");
+
+    /// [`Binance`](super::Binance) user data stream of the logged in account.
+    ///
+    /// Note:
+    /// This is not a channel that can be subscribed to, the user data stream is connected to
+    /// via a `listenKey`, see [`BinanceUserDataStream`](super::user_data::BinanceUserDataStream).
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#user-data-streams>
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#user-data-streams>
+    pub const USER_DATA: Self = Self("userData");
+
+    /// [`BinanceSpot`](super::spot::BinanceSpot) current average price channel name.
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#average-price>
+    pub const AVERAGE_PRICE: Self = Self("@avgPrice");
+
+    /// [`BinanceSpot`](super::spot::BinanceSpot) rolling window ticker channel name for the
+    /// provided window [`Interval`].
+    ///
+    /// Note:
+    /// Only the windows in [`BINANCE_TICKER_WINDOWS`] are supported by Binance, any other
+    /// window is rejected before subscribing so it's mapped to the default 1d window here.
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-rolling-window-statistics-streams>
+    pub fn tickers(window: Interval) -> Self {
+        match window {
+            Interval::H1 => Self("@ticker_1h"),
+            Interval::H4 => Self("@ticker_4h"),
+            _ => Self("@ticker_1d"),
+        }
+    }
+
+    /// [`Binance`](super::Binance) kline (candle) channel name for the provided [`Interval`].
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-streams>
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#kline-candlestick-streams>
+    pub fn candles(interval: Interval) -> Self {
+        match interval {
+            Interval::M1 => Self("@kline_1m"),
+            Interval::M3 => Self("@kline_3m"),
+            Interval::M5 => Self("@kline_5m"),
+            Interval::M15 => Self("@kline_15m"),
+            Interval::M30 => Self("@kline_30m"),
+            Interval::H1 => Self("@kline_1h"),
+            Interval::H2 => Self("@kline_2h"),
+            Interval::H4 => Self("@kline_4h"),
+            Interval::H6 => Self("@kline_6h"),
+            Interval::H12 => Self("@kline_12h"),
+            Interval::D1 => Self("@kline_1d"),
+            Interval::W1 => Self("@kline_1w"),
+        }
+    }
 }
 
 impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, PublicTrades> {
@@ -54,24 +150,84 @@ impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, Public
     }
 }
 
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, PublicTradesAggregated> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::AGG_TRADES
+    }
+}
+
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, OrderUpdates> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::USER_DATA
+    }
+}
+
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, Balances> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::USER_DATA
+    }
+}
+
 impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, OrderBooksL1> {
     fn id(&self) -> BinanceChannel {
         BinanceChannel::ORDER_BOOK_L1
     }
 }
 
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, TradesAndBooksL1> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::TRADES_AND_ORDER_BOOK_L1
+    }
+}
+
 impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, OrderBooksL2> {
     fn id(&self) -> BinanceChannel {
         BinanceChannel::ORDER_BOOK_L2
     }
 }
 
+impl<Server> Identifier<BinanceChannel> for Subscription<Binance<Server>, Candles> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::candles(self.kind.0)
+    }
+}
+
+impl Identifier<BinanceChannel> for Subscription<BinanceSpot, AveragePrices> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::AVERAGE_PRICE
+    }
+}
+
+impl Identifier<BinanceChannel> for Subscription<BinanceSpot, Tickers> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::tickers(self.kind.0)
+    }
+}
+
 impl Identifier<BinanceChannel> for Subscription<BinanceFuturesUsd, Liquidations> {
     fn id(&self) -> BinanceChannel {
         BinanceChannel::LIQUIDATIONS
     }
 }
 
+impl Identifier<BinanceChannel> for Subscription<BinanceFuturesUsd, OpenInterests> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::OPEN_INTEREST
+    }
+}
+
+impl Identifier<BinanceChannel> for Subscription<BinanceSpot, InstrumentStatuses> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::EXCHANGE_INFO
+    }
+}
+
+impl Identifier<BinanceChannel> for Subscription<BinanceFuturesUsd, InstrumentStatuses> {
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::EXCHANGE_INFO
+    }
+}
+
 impl AsRef<str> for BinanceChannel {
     fn as_ref(&self) -> &str {
         self.0
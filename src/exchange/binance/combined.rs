@@ -0,0 +1,340 @@
+use super::{
+    book::l1::BinanceOrderBookL1,
+    channel::BinanceChannel,
+    trade::{BinanceTrade, BinanceTradeType},
+};
+use crate::{
+    datetime::epoch_ms_to_datetime_utc,
+    event::{DataKind, MarketEvent, MarketIter},
+    exchange::{subscription::ExchangeSub, ExchangeId},
+    num::Num,
+    subscription::{book::OrderBookL1, trade::PublicTrade},
+    Identifier,
+};
+use barter_integration::model::{Instrument, Side, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// [`Binance`](super::Binance) real-time trade or OrderBook Level1 (top of book) message
+/// received over a connection subscribed to both, via the
+/// [`BinanceChannel::TRADES_AND_ORDER_BOOK_L1`] channel.
+///
+/// See [`BinanceTrade`] & [`BinanceOrderBookL1`] for the raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+#[serde(untagged)]
+pub enum BinanceTradeOrOrderBookL1 {
+    Trade(BinanceTrade),
+    OrderBookL1(BinanceOrderBookL1),
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceTradeOrOrderBookL1 {
+    fn id(&self) -> Option<SubscriptionId> {
+        let subscription_id = match self {
+            Self::Trade(trade) => &trade.subscription_id,
+            Self::OrderBookL1(book) => &book.subscription_id,
+        };
+
+        // Map the underlying channel SubscriptionId (eg/ "@trade|BTCUSDT") to the combined
+        // channel SubscriptionId (eg/ "@trade+@bookTicker|BTCUSDT")
+        let (_, market) = subscription_id.as_ref().split_once('|')?;
+        Some(ExchangeSub::from((BinanceChannel::TRADES_AND_ORDER_BOOK_L1, market)).id())
+    }
+}
+
+impl
+    From<(
+        ExchangeId,
+        Instrument,
+        DateTime<Utc>,
+        BinanceTradeOrOrderBookL1,
+    )> for MarketIter<DataKind>
+{
+    fn from(
+        (exchange_id, instrument, received_time, message): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceTradeOrOrderBookL1,
+        ),
+    ) -> Self {
+        match message {
+            BinanceTradeOrOrderBookL1::Trade(trade) => {
+                MarketIter::<PublicTrade>::from((exchange_id, instrument, received_time, trade))
+                    .0
+                    .into_iter()
+                    .map(|result| result.map(MarketEvent::from))
+                    .collect()
+            }
+            BinanceTradeOrOrderBookL1::OrderBookL1(book) => {
+                MarketIter::<OrderBookL1>::from((exchange_id, instrument, received_time, book))
+                    .0
+                    .into_iter()
+                    .map(|result| result.map(MarketEvent::from))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BinanceTradeOrOrderBookL1 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct MessageVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for MessageVisitor {
+            type Value = BinanceTradeOrOrderBookL1;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter
+                    .write_str("BinanceTrade or BinanceOrderBookL1 from the Binance WebSocket API")
+            }
+
+            fn visit_map<MapAccessor>(
+                self,
+                mut map: MapAccessor,
+            ) -> Result<Self::Value, MapAccessor::Error>
+            where
+                MapAccessor: serde::de::MapAccess<'de>,
+            {
+                // Spot OrderBookL1 messages have no event type "e" to dispatch on, so collect the
+                // borrowed fields of both messages in a single pass rather than via
+                // #[serde(untagged)], which buffers the entire message into owned content
+                let mut market: Option<&str> = None;
+                let mut id: Option<u64> = None;
+                let mut time: Option<i64> = None;
+                let mut price: Option<&str> = None;
+                let mut amount: Option<&str> = None;
+                let mut buyer_is_maker: Option<bool> = None;
+                let mut trade_type: Option<BinanceTradeType> = None;
+                let mut best_bid_price: Option<&str> = None;
+                let mut best_bid_amount: Option<&str> = None;
+                let mut best_ask_price: Option<&str> = None;
+                let mut best_ask_amount: Option<&str> = None;
+
+                while let Some(key) = map.next_key::<&str>()? {
+                    match key {
+                        "s" => market = Some(map.next_value()?),
+                        "t" => id = Some(map.next_value()?),
+                        "T" => time = Some(map.next_value()?),
+                        "p" => price = Some(map.next_value()?),
+                        "q" => amount = Some(map.next_value()?),
+                        "m" => buyer_is_maker = Some(map.next_value()?),
+                        "X" => trade_type = Some(map.next_value()?),
+                        // Spot trades may carry the integer buyer & seller order ids as "b" & "a"
+                        "b" => best_bid_price = map.next_value::<BinancePriceField<'_>>()?.0,
+                        "a" => best_ask_price = map.next_value::<BinancePriceField<'_>>()?.0,
+                        "B" => best_bid_amount = Some(map.next_value()?),
+                        "A" => best_ask_amount = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                let market = market.ok_or_else(|| serde::de::Error::missing_field("s"))?;
+
+                // Only trades have a trade id "t"
+                let Some(id) = id else {
+                    return Ok(BinanceTradeOrOrderBookL1::OrderBookL1(BinanceOrderBookL1 {
+                        subscription_id: ExchangeSub::from((BinanceChannel::ORDER_BOOK_L1, market))
+                            .id(),
+                        best_bid_price: parse_num(best_bid_price, "b")?,
+                        best_bid_amount: parse_num(best_bid_amount, "B")?,
+                        best_ask_price: parse_num(best_ask_price, "a")?,
+                        best_ask_amount: parse_num(best_ask_amount, "A")?,
+                    }));
+                };
+
+                let time = time.ok_or_else(|| serde::de::Error::missing_field("T"))?;
+                let buyer_is_maker =
+                    buyer_is_maker.ok_or_else(|| serde::de::Error::missing_field("m"))?;
+
+                Ok(BinanceTradeOrOrderBookL1::Trade(BinanceTrade {
+                    subscription_id: ExchangeSub::from((BinanceChannel::TRADES, market)).id(),
+                    time: epoch_ms_to_datetime_utc(time).ok_or_else(|| {
+                        serde::de::Error::custom(format!("invalid epoch timestamp: {time}"))
+                    })?,
+                    id,
+                    price: parse_num(price, "p")?,
+                    amount: parse_num(amount, "q")?,
+                    side: match buyer_is_maker {
+                        true => Side::Sell,
+                        false => Side::Buy,
+                    },
+                    trade_type,
+                }))
+            }
+        }
+
+        deserializer.deserialize_map(MessageVisitor)
+    }
+}
+
+/// Parse the required borrowed numeric string field of a [`BinanceTradeOrOrderBookL1`] as a
+/// [`Num`].
+fn parse_num<E>(field: Option<&str>, name: &'static str) -> Result<Num, E>
+where
+    E: serde::de::Error,
+{
+    field
+        .ok_or_else(|| E::missing_field(name))?
+        .parse()
+        .map_err(E::custom)
+}
+
+/// [`BinanceOrderBookL1`] price "b" or "a" field, which are integer order ids on a
+/// [`BinanceTrade`].
+struct BinancePriceField<'a>(Option<&'a str>);
+
+impl<'de> Deserialize<'de> for BinancePriceField<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for FieldVisitor {
+            type Value = BinancePriceField<'de>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("BinanceOrderBookL1 price string or BinanceTrade order id")
+            }
+
+            fn visit_borrowed_str<E>(self, price: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(BinancePriceField(Some(price)))
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(BinancePriceField(None))
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(BinancePriceField(None))
+            }
+        }
+
+        deserializer.deserialize_any(FieldVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binance_requests_expand_combined_channel() {
+        use crate::exchange::{
+            binance::{market::BinanceMarket, spot::BinanceSpot},
+            Connector,
+        };
+        use barter_integration::protocol::websocket::WsMessage;
+
+        let actual = BinanceSpot::requests(vec![
+            ExchangeSub::from((
+                BinanceChannel::TRADES_AND_ORDER_BOOK_L1,
+                BinanceMarket("BTCUSDT".to_string()),
+            )),
+            ExchangeSub::from((BinanceChannel::TRADES, BinanceMarket("ETHUSDT".to_string()))),
+        ]);
+
+        let expected = vec![WsMessage::Text(
+            serde_json::json!({
+                "method": "SUBSCRIBE",
+                "params": ["btcusdt@trade", "btcusdt@bookTicker", "ethusdt@trade"],
+                "id": 1
+            })
+            .to_string(),
+        )];
+
+        assert_eq!(actual, expected);
+    }
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_binance_trade_or_order_book_l1() {
+            struct TestCase {
+                input: &'static str,
+                expected: SubscriptionId,
+                is_trade: bool,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: valid spot BinanceTrade
+                    input: r#"
+                    {
+                        "e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,
+                        "p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,
+                        "T":1749354825200,"m":false,"M":true
+                    }
+                    "#,
+                    expected: SubscriptionId::from("@trade+@bookTicker|ETHUSDT"),
+                    is_trade: true,
+                },
+                TestCase {
+                    // TC1: valid spot BinanceOrderBookL1
+                    input: r#"
+                    {
+                        "u":22606535573,"s":"ETHUSDT","b":"1215.27000000","B":"32.49110000",
+                        "a":"1215.28000000","A":"13.93900000"
+                    }
+                    "#,
+                    expected: SubscriptionId::from("@trade+@bookTicker|ETHUSDT"),
+                    is_trade: false,
+                },
+                TestCase {
+                    // TC2: valid futures BinanceOrderBookL1 with event type & transaction time
+                    input: r#"
+                    {
+                        "e":"bookTicker","u":400900217,"E":1568014460893,"T":1568014460891,
+                        "s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000",
+                        "A":"40.66000000"
+                    }
+                    "#,
+                    expected: SubscriptionId::from("@trade+@bookTicker|BNBUSDT"),
+                    is_trade: false,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceTradeOrOrderBookL1>(test.input).unwrap();
+                assert_eq!(actual.id(), Some(test.expected), "TC{} failed", index);
+                assert_eq!(
+                    matches!(actual, BinanceTradeOrOrderBookL1::Trade(_)),
+                    test.is_trade,
+                    "TC{} failed",
+                    index
+                );
+
+                // Single pass deserialisation is equivalent to that of the underlying message
+                match actual {
+                    BinanceTradeOrOrderBookL1::Trade(trade) => assert_eq!(
+                        trade,
+                        serde_json::from_str::<BinanceTrade>(test.input).unwrap(),
+                        "TC{} failed",
+                        index
+                    ),
+                    BinanceTradeOrOrderBookL1::OrderBookL1(book) => assert_eq!(
+                        book,
+                        serde_json::from_str::<BinanceOrderBookL1>(test.input).unwrap(),
+                        "TC{} failed",
+                        index
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,11 @@ use self::{l2::BinanceFuturesBookUpdater, liquidation::BinanceLiquidation};
 use super::{Binance, ExchangeServer};
 use crate::{
     exchange::{ExchangeId, StreamSelector},
-    subscription::{book::OrderBooksL2, liquidation::Liquidations},
+    streams::polling::PollingStream,
+    subscription::{
+        book::OrderBooksL2, instrument_status::InstrumentStatuses, liquidation::Liquidations,
+        open_interest::OpenInterests,
+    },
     transformer::{book::MultiBookTransformer, stateless::StatelessTransformer},
     ExchangeWsStream,
 };
@@ -14,11 +18,20 @@ pub mod l2;
 /// Liquidation types.
 pub mod liquidation;
 
+/// Open interest types and [`Poll`](crate::exchange::poll::Poll) implementation, since open
+/// interest is only available via the REST API.
+pub mod open_interest;
+
 /// [`BinanceFuturesUsd`] WebSocket server base url.
 ///
 /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#websocket-market-streams>
 pub const WEBSOCKET_BASE_URL_BINANCE_FUTURES_USD: &str = "wss://fstream.binance.com/ws";
 
+/// [`BinanceFuturesUsd`] HTTP REST API base url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#general-info>
+pub const HTTP_BASE_URL_BINANCE_FUTURES_USD: &str = "https://fapi.binance.com/fapi/v1";
+
 /// [`Binance`](super::Binance) futures usd exchange.
 pub type BinanceFuturesUsd = Binance<BinanceServerFuturesUsd>;
 
@@ -42,3 +55,11 @@ impl StreamSelector<OrderBooksL2> for BinanceFuturesUsd {
 impl StreamSelector<Liquidations> for BinanceFuturesUsd {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, Liquidations, BinanceLiquidation>>;
 }
+
+impl StreamSelector<OpenInterests> for BinanceFuturesUsd {
+    type Stream = PollingStream<Self, OpenInterests>;
+}
+
+impl StreamSelector<InstrumentStatuses> for BinanceFuturesUsd {
+    type Stream = PollingStream<Self, InstrumentStatuses>;
+}
@@ -0,0 +1,105 @@
+use super::{BinanceFuturesUsd, HTTP_BASE_URL_BINANCE_FUTURES_USD};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, RawTimestamp, TimestampUnit},
+    exchange::{binance::market::BinanceMarket, poll::Poll, Connector},
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        open_interest::{OpenInterest, OpenInterests},
+        Subscription,
+    },
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::model::Exchange;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// [`BinanceFuturesUsd`] REST open interest.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#open-interest>
+/// ```json
+/// {
+///     "openInterest": "10659.509",
+///     "symbol": "BTCUSDT",
+///     "time": 1589437530011
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceOpenInterest {
+    #[serde(
+        alias = "openInterest",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub open_interest: Num,
+    pub symbol: String,
+    #[serde(deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+}
+
+impl From<BinanceOpenInterest> for OpenInterest {
+    fn from(open_interest: BinanceOpenInterest) -> Self {
+        Self {
+            contracts: open_interest.open_interest,
+        }
+    }
+}
+
+#[async_trait]
+impl Poll<OpenInterests> for BinanceFuturesUsd {
+    async fn poll(
+        subscription: &Subscription<Self, OpenInterests>,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<OpenInterest>>, DataError> {
+        let market: BinanceMarket = subscription.id();
+        let base_url = config
+            .endpoints
+            .rest_base_url(HTTP_BASE_URL_BINANCE_FUTURES_USD);
+        let url = format!("{base_url}/openInterest?symbol={}", market.as_ref());
+
+        let open_interest = get_json::<BinanceOpenInterest>(config.proxy.as_ref(), &url).await?;
+
+        Ok(vec![MarketEvent {
+            exchange_time: open_interest.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                open_interest.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time: Utc::now(),
+            exchange: Exchange::from(Self::ID),
+            instrument: subscription.instrument.clone(),
+            kind: OpenInterest::from(open_interest),
+            meta: (),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_binance_open_interest() {
+        let input = r#"{"openInterest": "10659.509", "symbol": "BTCUSDT", "time": 1589437530011}"#;
+
+        let actual = serde_json::from_str::<BinanceOpenInterest>(input).unwrap();
+        assert_eq!(
+            actual,
+            BinanceOpenInterest {
+                open_interest: num!(10659.509),
+                symbol: "BTCUSDT".to_string(),
+                time: DateTime::from_timestamp_millis(1589437530011).unwrap(),
+            }
+        );
+        assert_eq!(
+            OpenInterest::from(actual),
+            OpenInterest {
+                contracts: num!(10659.509)
+            }
+        );
+    }
+}
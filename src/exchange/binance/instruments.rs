@@ -0,0 +1,412 @@
+use super::{
+    futures::{BinanceFuturesUsd, HTTP_BASE_URL_BINANCE_FUTURES_USD},
+    market::BinanceMarket,
+    spot::{BinanceSpot, HTTP_BASE_URL_BINANCE_SPOT},
+    Binance,
+};
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    exchange::{
+        alias::canonical_instrument,
+        instruments::{parse_size, InstrumentInfo, MarketStatus},
+        poll::Poll,
+        ExchangeId, ExchangeServer,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        instrument_status::{InstrumentStatus, InstrumentStatuses, TradingStatus},
+        Subscription,
+    },
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::model::{Exchange, InstrumentKind};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// [`Binance`](super::Binance) REST `exchangeInfo` response, of which only the listed
+/// [`BinanceSymbol`]s are used.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BinanceExchangeSymbols {
+    pub symbols: Vec<BinanceSymbol>,
+}
+
+/// [`Binance`](super::Binance) REST `exchangeInfo` symbol.
+///
+/// Futures symbols include a `contractType`, of which only `PERPETUAL` symbols are supported.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
+/// ```json
+/// {
+///     "symbol": "BTCUSDT",
+///     "status": "TRADING",
+///     "baseAsset": "BTC",
+///     "quoteAsset": "USDT",
+///     "filters": [
+///         {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+///         {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000.00", "stepSize": "0.00001"}
+///     ]
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSymbol {
+    pub symbol: String,
+    pub status: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    #[serde(default)]
+    pub contract_type: Option<String>,
+    #[serde(default)]
+    pub filters: Vec<BinanceSymbolFilter>,
+}
+
+/// [`Binance`](super::Binance) REST `exchangeInfo` symbol filter, of which only the
+/// `PRICE_FILTER` `tickSize` & `LOT_SIZE` `stepSize` are used.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#filters>
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSymbolFilter {
+    pub filter_type: String,
+    #[serde(default)]
+    pub tick_size: Option<String>,
+    #[serde(default)]
+    pub step_size: Option<String>,
+}
+
+impl BinanceSymbol {
+    /// Normalise into an [`InstrumentInfo`] of the provided exchange, or `None` if it's a
+    /// futures symbol that is not a perpetual (eg/ `CURRENT_QUARTER`).
+    pub fn into_instrument_info(self, exchange: ExchangeId) -> Option<InstrumentInfo> {
+        let (kind, contract_size) = match self.contract_type.as_deref() {
+            None => (InstrumentKind::Spot, None),
+            Some("PERPETUAL") => (InstrumentKind::FuturePerpetual, Some(Num::from(1u8))),
+            Some(_) => return None,
+        };
+
+        let filter = |filter_type: &str| {
+            self.filters
+                .iter()
+                .find(|filter| filter.filter_type == filter_type)
+        };
+        let tick_size = filter("PRICE_FILTER")
+            .and_then(|filter| filter.tick_size.as_deref())
+            .and_then(parse_size);
+        let lot_size = filter("LOT_SIZE")
+            .and_then(|filter| filter.step_size.as_deref())
+            .and_then(parse_size);
+
+        let status = match self.status.as_str() {
+            "TRADING" => MarketStatus::Trading,
+            _ => MarketStatus::Halted(self.status),
+        };
+
+        Some(InstrumentInfo {
+            exchange,
+            market: self.symbol.to_uppercase(),
+            instrument: canonical_instrument(exchange, &self.base_asset, &self.quote_asset, kind),
+            tick_size,
+            lot_size,
+            contract_size,
+            status,
+        })
+    }
+}
+
+impl From<&BinanceSymbol> for InstrumentStatus {
+    fn from(symbol: &BinanceSymbol) -> Self {
+        // Spot & futures statuses, see docs:
+        // <https://binance-docs.github.io/apidocs/spot/en/#public-api-definitions>
+        // <https://binance-docs.github.io/apidocs/futures/en/#public-endpoints-info>
+        let status = match symbol.status.as_str() {
+            "TRADING" => TradingStatus::Trading,
+            "PRE_TRADING" | "PENDING_TRADING" => TradingStatus::PreTrading,
+            "BREAK" | "HALT" | "AUCTION_MATCH" | "POST_TRADING" | "END_OF_DAY"
+            | "PRE_DELIVERING" | "DELIVERING" | "PRE_SETTLE" | "SETTLING" => TradingStatus::Halted,
+            "DELIVERED" | "CLOSE" => TradingStatus::Delisted,
+            _ => TradingStatus::Unknown,
+        };
+
+        Self {
+            status,
+            exchange_status: symbol.status.clone(),
+        }
+    }
+}
+
+/// Normalise the [`InstrumentStatus`] of the provided market from the [`BinanceExchangeSymbols`]
+/// of an `exchangeInfo` response. A market that is no longer listed is
+/// [`TradingStatus::Delisted`].
+pub fn instrument_status(info: &BinanceExchangeSymbols, market: &str) -> InstrumentStatus {
+    info.symbols
+        .iter()
+        .find(|symbol| symbol.symbol.eq_ignore_ascii_case(market))
+        .map(InstrumentStatus::from)
+        .unwrap_or_else(|| InstrumentStatus {
+            status: TradingStatus::Delisted,
+            exchange_status: String::new(),
+        })
+}
+
+/// Fetch the current [`InstrumentStatus`] [`MarketEvent`] of the provided [`Subscription`] via
+/// the provided [`Binance`](super::Binance) REST API `exchangeInfo` url, using the
+/// [`StreamConfig`] of the connection.
+async fn poll_instrument_status<Server>(
+    subscription: &Subscription<Binance<Server>, InstrumentStatuses>,
+    url: &str,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<InstrumentStatus>>, DataError>
+where
+    Server: ExchangeServer,
+{
+    let market: BinanceMarket = subscription.id();
+    let info = get_json::<BinanceExchangeSymbols>(config.proxy.as_ref(), url).await?;
+    let now = Utc::now();
+
+    Ok(vec![MarketEvent {
+        exchange_time: now,
+        raw_exchange_time: None,
+        received_time: now,
+        exchange: Exchange::from(Server::ID),
+        instrument: subscription.instrument.clone(),
+        kind: instrument_status(&info, market.as_ref()),
+        meta: (),
+    }])
+}
+
+#[async_trait]
+impl Poll<InstrumentStatuses> for BinanceSpot {
+    async fn poll(
+        subscription: &Subscription<Self, InstrumentStatuses>,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<InstrumentStatus>>, DataError> {
+        // Spot exchangeInfo is filtered to the single symbol to avoid fetching every symbol
+        let market: BinanceMarket = subscription.id();
+        let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_BINANCE_SPOT);
+        let url = format!(
+            "{base_url}/exchangeInfo?symbol={}",
+            market.as_ref().to_uppercase()
+        );
+        poll_instrument_status(subscription, &url, config).await
+    }
+}
+
+#[async_trait]
+impl Poll<InstrumentStatuses> for BinanceFuturesUsd {
+    async fn poll(
+        subscription: &Subscription<Self, InstrumentStatuses>,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<InstrumentStatus>>, DataError> {
+        let base_url = config
+            .endpoints
+            .rest_base_url(HTTP_BASE_URL_BINANCE_FUTURES_USD);
+        let url = format!("{base_url}/exchangeInfo");
+        poll_instrument_status(subscription, &url, config).await
+    }
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the [`Binance`](super::Binance) REST API
+/// `exchangeInfo` endpoint of the provided exchange & base url, using the provided
+/// [`StreamConfig`].
+pub async fn fetch_instruments(
+    exchange: ExchangeId,
+    base_url: &str,
+    config: &StreamConfig,
+) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(base_url);
+    get_json::<BinanceExchangeSymbols>(config.proxy.as_ref(), &format!("{base_url}/exchangeInfo"))
+        .await
+        .map(|info| {
+            info.symbols
+                .into_iter()
+                .filter_map(|symbol| symbol.into_instrument_info(exchange))
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::Instrument;
+
+    #[test]
+    fn test_binance_instrument_info() {
+        struct TestCase {
+            exchange: ExchangeId,
+            input: &'static str,
+            expected: Vec<InstrumentInfo>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot exchangeInfo
+                exchange: ExchangeId::BinanceSpot,
+                input: r#"
+                {
+                    "timezone": "UTC",
+                    "serverTime": 1565246363776,
+                    "rateLimits": [],
+                    "symbols": [
+                        {
+                            "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "quoteAsset": "USDT",
+                            "filters": [
+                                {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                                {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000.00", "stepSize": "0.00001"},
+                                {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200}
+                            ]
+                        },
+                        {"symbol": "LUNAUSDT", "status": "BREAK", "baseAsset": "LUNA", "quoteAsset": "USDT", "filters": []}
+                    ]
+                }
+                "#,
+                expected: vec![
+                    InstrumentInfo {
+                        exchange: ExchangeId::BinanceSpot,
+                        market: "BTCUSDT".to_string(),
+                        instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                        tick_size: parse_size("0.01"),
+                        lot_size: parse_size("0.00001"),
+                        contract_size: None,
+                        status: MarketStatus::Trading,
+                    },
+                    InstrumentInfo {
+                        exchange: ExchangeId::BinanceSpot,
+                        market: "LUNAUSDT".to_string(),
+                        instrument: Instrument::from(("luna", "usdt", InstrumentKind::Spot)),
+                        tick_size: None,
+                        lot_size: None,
+                        contract_size: None,
+                        status: MarketStatus::Halted("BREAK".to_string()),
+                    },
+                ],
+            },
+            TestCase {
+                // TC1: futures exchangeInfo, dated contracts are not supported
+                exchange: ExchangeId::BinanceFuturesUsd,
+                input: r#"
+                {
+                    "symbols": [
+                        {
+                            "symbol": "BTCUSDT", "pair": "BTCUSDT", "contractType": "PERPETUAL", "status": "TRADING",
+                            "baseAsset": "BTC", "quoteAsset": "USDT", "marginAsset": "USDT",
+                            "filters": [
+                                {"filterType": "PRICE_FILTER", "minPrice": "556.80", "maxPrice": "4529764", "tickSize": "0.10"},
+                                {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"}
+                            ]
+                        },
+                        {
+                            "symbol": "BTCUSDT_240628", "pair": "BTCUSDT", "contractType": "CURRENT_QUARTER", "status": "TRADING",
+                            "baseAsset": "BTC", "quoteAsset": "USDT", "marginAsset": "USDT", "filters": []
+                        }
+                    ]
+                }
+                "#,
+                expected: vec![InstrumentInfo {
+                    exchange: ExchangeId::BinanceFuturesUsd,
+                    market: "BTCUSDT".to_string(),
+                    instrument: Instrument::from(("btc", "usdt", InstrumentKind::FuturePerpetual)),
+                    tick_size: parse_size("0.1"),
+                    lot_size: parse_size("0.001"),
+                    contract_size: parse_size("1"),
+                    status: MarketStatus::Trading,
+                }],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<BinanceExchangeSymbols>(test.input)
+                .unwrap()
+                .symbols
+                .into_iter()
+                .filter_map(|symbol| symbol.into_instrument_info(test.exchange))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_binance_instrument_status() {
+        struct TestCase {
+            market: &'static str,
+            expected: InstrumentStatus,
+        }
+
+        let info = serde_json::from_str::<BinanceExchangeSymbols>(
+            r#"
+            {
+                "symbols": [
+                    {"symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "quoteAsset": "USDT"},
+                    {"symbol": "LUNAUSDT", "status": "BREAK", "baseAsset": "LUNA", "quoteAsset": "USDT"},
+                    {"symbol": "NEWUSDT", "status": "PENDING_TRADING", "baseAsset": "NEW", "quoteAsset": "USDT"},
+                    {"symbol": "OLDUSDT", "status": "CLOSE", "baseAsset": "OLD", "quoteAsset": "USDT"},
+                    {"symbol": "ODDUSDT", "status": "ODD", "baseAsset": "ODD", "quoteAsset": "USDT"}
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let tests = vec![
+            TestCase {
+                // TC0: trading
+                market: "btcusdt",
+                expected: InstrumentStatus {
+                    status: TradingStatus::Trading,
+                    exchange_status: "TRADING".to_string(),
+                },
+            },
+            TestCase {
+                // TC1: halted
+                market: "LUNAUSDT",
+                expected: InstrumentStatus {
+                    status: TradingStatus::Halted,
+                    exchange_status: "BREAK".to_string(),
+                },
+            },
+            TestCase {
+                // TC2: new listing
+                market: "NEWUSDT",
+                expected: InstrumentStatus {
+                    status: TradingStatus::PreTrading,
+                    exchange_status: "PENDING_TRADING".to_string(),
+                },
+            },
+            TestCase {
+                // TC3: delisted
+                market: "OLDUSDT",
+                expected: InstrumentStatus {
+                    status: TradingStatus::Delisted,
+                    exchange_status: "CLOSE".to_string(),
+                },
+            },
+            TestCase {
+                // TC4: unrecognised status
+                market: "ODDUSDT",
+                expected: InstrumentStatus {
+                    status: TradingStatus::Unknown,
+                    exchange_status: "ODD".to_string(),
+                },
+            },
+            TestCase {
+                // TC5: no longer listed
+                market: "GONEUSDT",
+                expected: InstrumentStatus {
+                    status: TradingStatus::Delisted,
+                    exchange_status: String::new(),
+                },
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = instrument_status(&info, test.market);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
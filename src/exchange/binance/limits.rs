@@ -0,0 +1,191 @@
+use crate::{
+    error::DataError,
+    exchange::{
+        limits::{ConnectionLimits, LimitsSource},
+        ExchangeId,
+    },
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+};
+use barter_integration::error::SocketError;
+use serde::{Deserialize, Serialize};
+
+/// [`Binance`](super::Binance) REST `exchangeInfo` response, of which only the advertised
+/// [`BinanceRateLimit`]s are used.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#exchange-information>
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BinanceExchangeInfo {
+    #[serde(alias = "rateLimits")]
+    pub rate_limits: Vec<BinanceRateLimit>,
+}
+
+/// [`Binance`](super::Binance) REST `exchangeInfo` rate limit.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#limits>
+/// ```json
+/// {
+///     "rateLimitType": "REQUEST_WEIGHT",
+///     "interval": "MINUTE",
+///     "intervalNum": 1,
+///     "limit": 6000
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BinanceRateLimit {
+    #[serde(alias = "rateLimitType")]
+    pub kind: String,
+    pub interval: String,
+    #[serde(alias = "intervalNum")]
+    pub interval_num: u32,
+    pub limit: u32,
+}
+
+impl BinanceRateLimit {
+    /// [`Self`] normalised to a limit per minute, or `None` if the interval is unrecognised.
+    pub fn per_minute(&self) -> Option<u32> {
+        let interval_secs: u64 = match self.interval.as_str() {
+            "SECOND" => 1,
+            "MINUTE" => 60,
+            "HOUR" => 3600,
+            "DAY" => 86400,
+            _ => return None,
+        };
+
+        let window_secs = interval_secs * u64::from(self.interval_num.max(1));
+        u32::try_from(u64::from(self.limit) * 60 / window_secs).ok()
+    }
+}
+
+impl BinanceExchangeInfo {
+    /// Construct the [`ConnectionLimits`] advertised by [`Self`], starting from the hardcoded
+    /// defaults for the provided Binance [`ExchangeId`] since only REST limits are advertised.
+    ///
+    /// The strictest `REQUEST_WEIGHT` limit is used if several are advertised.
+    #[allow(clippy::result_large_err)]
+    pub fn connection_limits(&self, exchange: ExchangeId) -> Result<ConnectionLimits, DataError> {
+        let request_weight_per_minute = self
+            .rate_limits
+            .iter()
+            .filter(|rate_limit| rate_limit.kind == "REQUEST_WEIGHT")
+            .filter_map(BinanceRateLimit::per_minute)
+            .min()
+            .ok_or_else(|| {
+                SocketError::Exchange(format!(
+                    "{exchange} exchangeInfo does not advertise a REQUEST_WEIGHT rate limit"
+                ))
+            })?;
+
+        Ok(ConnectionLimits {
+            request_weight_per_minute: Some(request_weight_per_minute),
+            source: LimitsSource::Advertised,
+            ..ConnectionLimits::default_for(exchange)
+        })
+    }
+}
+
+/// Fetch the [`ConnectionLimits`] advertised by the [`Binance`](super::Binance) REST API
+/// `exchangeInfo` endpoint with the provided base url, using the provided [`StreamConfig`].
+pub async fn fetch_limits(
+    exchange: ExchangeId,
+    base_url: &str,
+    config: &StreamConfig,
+) -> Result<ConnectionLimits, DataError> {
+    let base_url = config.endpoints.rest_base_url(base_url);
+    get_json::<BinanceExchangeInfo>(config.proxy.as_ref(), &format!("{base_url}/exchangeInfo"))
+        .await?
+        .connection_limits(exchange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binance_exchange_info_connection_limits() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<u32>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: REQUEST_WEIGHT per minute is advertised
+                input: r#"
+                {
+                    "timezone": "UTC",
+                    "serverTime": 1565246363776,
+                    "rateLimits": [
+                        {"rateLimitType": "REQUEST_WEIGHT", "interval": "MINUTE", "intervalNum": 1, "limit": 6000},
+                        {"rateLimitType": "ORDERS", "interval": "SECOND", "intervalNum": 10, "limit": 100},
+                        {"rateLimitType": "RAW_REQUESTS", "interval": "MINUTE", "intervalNum": 5, "limit": 61000}
+                    ],
+                    "exchangeFilters": [],
+                    "symbols": []
+                }
+                "#,
+                expected: Some(6000),
+            },
+            TestCase {
+                // TC1: strictest REQUEST_WEIGHT is normalised to per minute
+                input: r#"
+                {
+                    "rateLimits": [
+                        {"rateLimitType": "REQUEST_WEIGHT", "interval": "SECOND", "intervalNum": 10, "limit": 300},
+                        {"rateLimitType": "REQUEST_WEIGHT", "interval": "HOUR", "intervalNum": 1, "limit": 60000}
+                    ]
+                }
+                "#,
+                expected: Some(1000),
+            },
+            TestCase {
+                // TC2: no REQUEST_WEIGHT advertised is an error
+                input: r#"
+                {
+                    "rateLimits": [
+                        {"rateLimitType": "ORDERS", "interval": "SECOND", "intervalNum": 10, "limit": 100}
+                    ]
+                }
+                "#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let info = serde_json::from_str::<BinanceExchangeInfo>(test.input).unwrap();
+            let actual = info.connection_limits(ExchangeId::BinanceSpot);
+
+            match (actual, test.expected) {
+                (Ok(actual), Some(expected)) => {
+                    assert_eq!(
+                        actual.request_weight_per_minute,
+                        Some(expected),
+                        "TC{} failed",
+                        index
+                    );
+                    assert_eq!(
+                        actual.source,
+                        LimitsSource::Advertised,
+                        "TC{} failed",
+                        index
+                    );
+                    assert_eq!(
+                        actual.max_subscriptions_per_connection,
+                        ConnectionLimits::default_for(ExchangeId::BinanceSpot)
+                            .max_subscriptions_per_connection,
+                        "TC{} failed",
+                        index
+                    );
+                }
+                (Err(_), None) => {
+                    // Test passed
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,14 @@
 use super::Binance;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector, ExchangeServer,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a [`Binance`](super::Binance)
@@ -10,12 +19,16 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct BinanceMarket(pub String);
 
-impl<Server, Kind> Identifier<BinanceMarket> for Subscription<Binance<Server>, Kind> {
+impl<Server, Kind> Identifier<BinanceMarket> for Subscription<Binance<Server>, Kind>
+where
+    Server: ExchangeServer,
+{
     fn id(&self) -> BinanceMarket {
         // Notes:
         // - Must be lowercase when subscribing (transformed to lowercase by Binance fn requests).
         // - Must be uppercase since Binance sends message with uppercase MARKET (eg/ BTCUSDT).
-        BinanceMarket(format!("{}{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Server::ID, &self.instrument);
+        BinanceMarket(format!("{base}{quote}").to_uppercase())
     }
 }
 
@@ -24,3 +37,195 @@ impl AsRef<str> for BinanceMarket {
         &self.0
     }
 }
+
+/// Quote assets listed by [`Binance`](super::Binance), used to split concatenated markets (eg/
+/// "BTCUSDT") into a base & quote when parsing without the exchange instrument catalog.
+///
+/// Where several quote assets are a suffix of a market, the longest match is used (eg/ "USDT"
+/// over "USD").
+pub const BINANCE_QUOTE_ASSETS: &[&str] = &[
+    "USDT", "USDC", "FDUSD", "BUSD", "TUSD", "DAI", "USD", "BTC", "ETH", "BNB", "XRP", "DOGE",
+    "TRX", "EUR", "GBP", "TRY", "BRL", "ARS", "AUD", "JPY", "MXN", "PLN", "RON", "UAH", "ZAR",
+    "BIDR", "IDRT",
+];
+
+impl<Server> ParseMarket for Binance<Server>
+where
+    Server: ExchangeServer,
+{
+    /// Binance markets are a concatenation of the base & quote (eg/ "BTCUSDT"), and are split
+    /// on the longest [`BINANCE_QUOTE_ASSETS`] suffix. Futures markets of
+    /// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) are perpetuals, unless they are
+    /// suffixed with a delivery date (eg/ "BTCUSDT_240628").
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        if market.contains('_') {
+            return Err(ParseMarketError::UnsupportedKind {
+                exchange: Self::ID,
+                market: market.to_owned(),
+                kind: "delivery future",
+            });
+        }
+
+        let market_upper = market.to_uppercase();
+        let (base, quote) = BINANCE_QUOTE_ASSETS
+            .iter()
+            .filter_map(|quote| {
+                market_upper
+                    .strip_suffix(quote)
+                    .filter(|base| !base.is_empty())
+                    .map(|base| (base, *quote))
+            })
+            .max_by_key(|(_, quote)| quote.len())
+            .ok_or_else(|| ParseMarketError::Ambiguous {
+                exchange: Self::ID,
+                market: market.to_owned(),
+            })?;
+
+        let kind = if Self::ID.supports_futures() {
+            InstrumentKind::FuturePerpetual
+        } else {
+            InstrumentKind::Spot
+        };
+
+        Ok(canonical_instrument(Self::ID, base, quote, kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::{
+        binance::{futures::BinanceFuturesUsd, spot::BinanceSpot},
+        instruments::{InstrumentInfo, MarketStatus},
+        ExchangeId,
+    };
+
+    fn catalog_info(market: &str, base: &str, quote: &str) -> InstrumentInfo {
+        InstrumentInfo {
+            exchange: ExchangeId::BinanceSpot,
+            market: market.to_owned(),
+            instrument: Instrument::from((base, quote, InstrumentKind::Spot)),
+            tick_size: None,
+            lot_size: None,
+            contract_size: None,
+            status: MarketStatus::Trading,
+        }
+    }
+
+    #[test]
+    fn test_parse_market() {
+        struct TestCase {
+            market: &'static str,
+            expected: Result<Instrument, ParseMarketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: longest quote suffix is preferred, ie/ USDT over USD
+                market: "BTCUSDT",
+                expected: Ok(Instrument::from(("btc", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: lowercase market
+                market: "ethbtc",
+                expected: Ok(Instrument::from(("eth", "btc", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC2: base that is itself a quote asset
+                market: "BNBFDUSD",
+                expected: Ok(Instrument::from(("bnb", "fdusd", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC3: unknown quote asset is ambiguous
+                market: "BTCXYZ",
+                expected: Err(ParseMarketError::Ambiguous {
+                    exchange: ExchangeId::BinanceSpot,
+                    market: "BTCXYZ".to_owned(),
+                }),
+            },
+            TestCase {
+                // TC4: market that is only a quote asset is ambiguous
+                market: "USDT",
+                expected: Err(ParseMarketError::Ambiguous {
+                    exchange: ExchangeId::BinanceSpot,
+                    market: "USDT".to_owned(),
+                }),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = BinanceSpot::parse_market(test.market);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_parse_market_futures() {
+        assert_eq!(
+            BinanceFuturesUsd::parse_market("BTCUSDT"),
+            Ok(Instrument::from((
+                "btc",
+                "usdt",
+                InstrumentKind::FuturePerpetual
+            )))
+        );
+        assert_eq!(
+            BinanceFuturesUsd::parse_market("BTCUSDT_240628"),
+            Err(ParseMarketError::UnsupportedKind {
+                exchange: ExchangeId::BinanceFuturesUsd,
+                market: "BTCUSDT_240628".to_owned(),
+                kind: "delivery future",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_market_with_catalog() {
+        struct TestCase {
+            market: &'static str,
+            catalog: Vec<InstrumentInfo>,
+            expected: Result<Instrument, ParseMarketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: catalog lists BTCUSDT as btc/usdt
+                market: "BTCUSDT",
+                catalog: vec![catalog_info("BTCUSDT", "btc", "usdt")],
+                expected: Ok(Instrument::from(("btc", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: catalog resolves hypothetical BTCUSD + T listing over the USDT suffix
+                market: "BTCUSDT",
+                catalog: vec![catalog_info("BTCUSDT", "btcusd", "t")],
+                expected: Ok(Instrument::from(("btcusd", "t", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC2: catalog resolves a quote asset that is not in BINANCE_QUOTE_ASSETS
+                market: "btcxyz",
+                catalog: vec![catalog_info("BTCXYZ", "btc", "xyz")],
+                expected: Ok(Instrument::from(("btc", "xyz", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC3: market not in catalog falls back to BINANCE_QUOTE_ASSETS suffixes
+                market: "ETHUSDT",
+                catalog: vec![catalog_info("BTCUSDT", "btcusd", "t")],
+                expected: Ok(Instrument::from(("eth", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC4: catalog of another exchange is ignored
+                market: "BTCUSDT",
+                catalog: vec![InstrumentInfo {
+                    exchange: ExchangeId::BinanceUs,
+                    ..catalog_info("BTCUSDT", "btcusd", "t")
+                }],
+                expected: Ok(Instrument::from(("btc", "usdt", InstrumentKind::Spot))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = BinanceSpot::parse_market_with_catalog(test.market, &test.catalog);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
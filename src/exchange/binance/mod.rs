@@ -1,11 +1,25 @@
 use self::{
-    book::l1::BinanceOrderBookL1, channel::BinanceChannel, market::BinanceMarket,
-    subscription::BinanceSubResponse, trade::BinanceTrade,
+    book::l1::BinanceOrderBookL1,
+    candle::BinanceKline,
+    channel::BinanceChannel,
+    combined::BinanceTradeOrOrderBookL1,
+    market::BinanceMarket,
+    subscription::BinanceSubResponse,
+    trade::{BinanceAggTrade, BinanceTrade},
+    user_data::{BinanceUserDataServer, BinanceUserDataStream},
 };
 use crate::{
     exchange::{Connector, ExchangeId, ExchangeServer, ExchangeSub, StreamSelector},
     subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::{book::OrderBooksL1, trade::PublicTrades, Map},
+    subscription::{
+        balance::Balances,
+        book::OrderBooksL1,
+        candle::Candles,
+        combined::TradesAndBooksL1,
+        order::OrderUpdates,
+        trade::{PublicTrades, PublicTradesAggregated},
+        Map,
+    },
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
@@ -13,10 +27,22 @@ use barter_integration::{error::SocketError, model::Instrument, protocol::websoc
 use std::{fmt::Debug, marker::PhantomData};
 use url::Url;
 
+/// [`Backfill`](super::backfill::Backfill) implementations that fetch recent trades & candles via
+/// the [`Binance`] REST API.
+pub mod backfill;
+
 /// OrderBook types common to both [`BinanceSpot`](spot::BinanceSpot) and
 /// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod book;
 
+/// Kline (candle) types common to both [`BinanceSpot`](spot::BinanceSpot) and
+/// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
+pub mod candle;
+
+/// Combined public trade & OrderBook Level1 types common to both
+/// [`BinanceSpot`](spot::BinanceSpot) and [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
+pub mod combined;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
@@ -25,6 +51,14 @@ pub mod channel;
 /// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod futures;
 
+/// [`InstrumentInfo`](crate::exchange::instruments::InstrumentInfo)s listed by the [`Binance`] REST
+/// API `exchangeInfo` symbols.
+pub mod instruments;
+
+/// [`ConnectionLimits`](crate::exchange::limits::ConnectionLimits) advertised by the
+/// [`Binance`] REST API `exchangeInfo` rate limits.
+pub mod limits;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
@@ -38,15 +72,44 @@ pub mod spot;
 /// and [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod subscription;
 
+/// [`ExchangeServer`] implementation for [`BinanceUs`](us::BinanceUs).
+pub mod us;
+
 /// Public trade types common to both [`BinanceSpot`](spot::BinanceSpot) and
 /// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod trade;
 
+/// Private user data stream of the account owning a `listenKey`, yielding the
+/// [`OrderUpdates`](crate::subscription::order::OrderUpdates) &
+/// [`Balances`](crate::subscription::balance::Balances) of both
+/// [`BinanceSpot`](spot::BinanceSpot) and [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
+pub mod user_data;
+
+/// Exchange message fields [`Binance`] models intentionally don't deserialise, see
+/// [`IgnoredFields`](crate::parser::strict::IgnoredFields).
+pub const IGNORED_FIELDS: crate::parser::strict::IgnoredFields = &[
+    ("BinanceTrade", &["e", "E", "b", "a", "M"]),
+    ("BinanceAggTrade", &["e", "E", "M"]),
+    ("BinanceOrderBookL1", &["u"]),
+    ("BinanceSpotOrderBookL2Delta", &["e", "E"]),
+    ("BinanceFuturesOrderBookL2Delta", &["e", "E", "T"]),
+    ("BinanceKline", &["e", "E", "s"]),
+    ("BinanceKlineData", &["t", "f", "L", "q", "V", "Q", "B"]),
+    ("BinanceLiquidation", &["e", "E"]),
+    ("BinanceLiquidationOrder", &["o", "f", "ap", "X", "l", "z"]),
+];
+
+/// Optional exchange message fields [`Binance`] is expected to send, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields).
+pub const EXPECTED_FIELDS: crate::parser::strict::ExpectedFields =
+    &[(ExchangeId::BinanceFuturesUsd, "BinanceTrade", &["X"])];
+
 /// Generic [`Binance<Server>`](Binance) exchange.
 ///
 /// ### Notes
 /// A `Server` [`ExchangeServer`](super::ExchangeServer) implementations exists for
-/// [`BinanceSpot`](spot::BinanceSpot) and [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
+/// [`BinanceSpot`](spot::BinanceSpot), [`BinanceFuturesUsd`](futures::BinanceFuturesUsd) and
+/// [`BinanceUs`](us::BinanceUs).
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub struct Binance<Server> {
     server: PhantomData<Server>,
@@ -70,15 +133,22 @@ where
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
         let stream_names = exchange_subs
             .into_iter()
-            .map(|sub| {
+            .flat_map(|sub| {
+                // Combined channels are actioned as each of their underlying channels
+                let channels = match sub.channel {
+                    BinanceChannel::TRADES_AND_ORDER_BOOK_L1 => {
+                        vec![BinanceChannel::TRADES, BinanceChannel::ORDER_BOOK_L1]
+                    }
+                    channel => vec![channel],
+                };
+
                 // Note:
                 // Market must be lowercase when subscribing, but lowercase in general since
                 // Binance sends message with uppercase MARKET (eg/ BTCUSDT).
-                format!(
-                    "{}{}",
-                    sub.market.as_ref().to_lowercase(),
-                    sub.channel.as_ref()
-                )
+                let market = sub.market.as_ref().to_lowercase();
+                channels
+                    .into_iter()
+                    .map(move |channel| format!("{}{}", market, channel.as_ref()))
             })
             .collect::<Vec<String>>();
 
@@ -104,6 +174,14 @@ where
     type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BinanceTrade>>;
 }
 
+impl<Server> StreamSelector<PublicTradesAggregated> for Binance<Server>
+where
+    Server: ExchangeServer + Debug + Send + Sync,
+{
+    type Stream =
+        ExchangeWsStream<StatelessTransformer<Self, PublicTradesAggregated, BinanceAggTrade>>;
+}
+
 impl<Server> StreamSelector<OrderBooksL1> for Binance<Server>
 where
     Server: ExchangeServer + Debug + Send + Sync,
@@ -111,6 +189,35 @@ where
     type Stream = ExchangeWsStream<StatelessTransformer<Self, OrderBooksL1, BinanceOrderBookL1>>;
 }
 
+impl<Server> StreamSelector<TradesAndBooksL1> for Binance<Server>
+where
+    Server: ExchangeServer + Debug + Send + Sync,
+{
+    type Stream =
+        ExchangeWsStream<StatelessTransformer<Self, TradesAndBooksL1, BinanceTradeOrOrderBookL1>>;
+}
+
+impl<Server> StreamSelector<Candles> for Binance<Server>
+where
+    Server: ExchangeServer + Debug + Send + Sync,
+{
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, Candles, BinanceKline>>;
+}
+
+impl<Server> StreamSelector<OrderUpdates> for Binance<Server>
+where
+    Server: BinanceUserDataServer + Debug + Send + Sync + 'static,
+{
+    type Stream = BinanceUserDataStream<Server, OrderUpdates>;
+}
+
+impl<Server> StreamSelector<Balances> for Binance<Server>
+where
+    Server: BinanceUserDataServer + Debug + Send + Sync + 'static,
+{
+    type Stream = BinanceUserDataStream<Server, Balances>;
+}
+
 impl<'de, Server> serde::Deserialize<'de> for Binance<Server>
 where
     Server: ExchangeServer,
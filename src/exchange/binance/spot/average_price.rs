@@ -0,0 +1,169 @@
+use super::super::BinanceChannel;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{ExchangeId, ExchangeSub},
+    num::Num,
+    subscription::{average_price::AveragePrice, candle::Interval},
+    Identifier,
+};
+use barter_integration::model::{Exchange, Instrument, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+
+/// [`BinanceSpot`](super::BinanceSpot) real-time average price message.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#average-price>
+/// ```json
+/// {
+///     "e": "avgPrice",
+///     "E": 1693907033000,
+///     "s": "BTCUSDT",
+///     "i": "5m",
+///     "w": "25776.86000000",
+///     "T": 1693907032213
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceAveragePrice {
+    #[serde(alias = "s")]
+    pub market: String,
+    #[serde(
+        alias = "E",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    #[serde(alias = "i")]
+    pub interval: Interval,
+    #[serde(alias = "w", deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(
+        alias = "T",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub last_trade_time: DateTime<Utc>,
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceAveragePrice {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(ExchangeSub::from((BinanceChannel::AVERAGE_PRICE, self.market.as_str())).id())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceAveragePrice)>
+    for MarketIter<AveragePrice>
+{
+    fn from(
+        (exchange_id, instrument, received_time, average): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceAveragePrice,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: average.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                average.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: AveragePrice {
+                interval: average.interval,
+                price: average.price,
+                last_trade_time: average.last_trade_time,
+            },
+            meta: (),
+        })])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    mod de {
+        use super::*;
+        use barter_integration::de::datetime_utc_from_epoch_duration;
+        use barter_integration::error::SocketError;
+        use serde::de::Error;
+        use std::time::Duration;
+
+        #[test]
+        fn test_binance_average_price() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BinanceAveragePrice, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: valid average price
+                    input: r#"
+                    {
+                        "e":"avgPrice","E":1693907033000,"s":"BTCUSDT","i":"5m",
+                        "w":"25776.86000000","T":1693907032213
+                    }
+                    "#,
+                    expected: Ok(BinanceAveragePrice {
+                        market: "BTCUSDT".to_string(),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1693907033000,
+                        )),
+                        interval: Interval::M5,
+                        price: num!(25776.86),
+                        last_trade_time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1693907032213,
+                        )),
+                    }),
+                },
+                TestCase {
+                    // TC1: invalid average price w/ non-numeric price
+                    input: r#"
+                    {
+                        "e":"avgPrice","E":1693907033000,"s":"BTCUSDT","i":"5m",
+                        "w":"price","T":1693907032213
+                    }
+                    "#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde_json::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceAveragePrice>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binance_average_price_subscription_id() {
+        let average = serde_json::from_str::<BinanceAveragePrice>(
+            r#"{"E":1693907033000,"s":"BTCUSDT","i":"5m","w":"1","T":1693907032213}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            average.id(),
+            Some(SubscriptionId::from("@avgPrice|BTCUSDT"))
+        );
+    }
+}
@@ -1,21 +1,36 @@
-use self::l2::BinanceSpotBookUpdater;
-use super::{Binance, ExchangeServer};
+use self::{average_price::BinanceAveragePrice, l2::BinanceSpotBookUpdater, ticker::BinanceTicker};
+use super::{channel::BINANCE_TICKER_WINDOWS, Binance, ExchangeServer};
 use crate::{
     exchange::{ExchangeId, StreamSelector},
-    subscription::book::OrderBooksL2,
-    transformer::book::MultiBookTransformer,
+    streams::polling::PollingStream,
+    subscription::{
+        average_price::AveragePrices, book::OrderBooksL2, instrument_status::InstrumentStatuses,
+        ticker::Tickers,
+    },
+    transformer::{book::MultiBookTransformer, stateless::StatelessTransformer},
     ExchangeWsStream,
 };
 
+/// Average price types for [`BinanceSpot`].
+pub mod average_price;
+
 /// Level 2 OrderBook types (top of book) and spot
 /// [`OrderBookUpdater`](crate::transformer::book::OrderBookUpdater) implementation.
 pub mod l2;
 
+/// Rolling window ticker types for [`BinanceSpot`].
+pub mod ticker;
+
 /// [`BinanceSpot`] WebSocket server base url.
 ///
 /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#websocket-market-streams>
 pub const WEBSOCKET_BASE_URL_BINANCE_SPOT: &str = "wss://stream.binance.com:9443/ws";
 
+/// [`BinanceSpot`] HTTP REST API base url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#general-api-information>
+pub const HTTP_BASE_URL_BINANCE_SPOT: &str = "https://api.binance.com/api/v3";
+
 /// [`Binance`](super::Binance) spot exchange.
 pub type BinanceSpot = Binance<BinanceServerSpot>;
 
@@ -35,3 +50,27 @@ impl StreamSelector<OrderBooksL2> for BinanceSpot {
     type Stream =
         ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BinanceSpotBookUpdater>>;
 }
+
+impl StreamSelector<AveragePrices> for BinanceSpot {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, AveragePrices, BinanceAveragePrice>>;
+}
+
+impl StreamSelector<InstrumentStatuses> for BinanceSpot {
+    type Stream = PollingStream<Self, InstrumentStatuses>;
+}
+
+impl StreamSelector<Tickers> for BinanceSpot {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, Tickers, BinanceTicker>>;
+
+    fn unsupported(kind: &Tickers) -> Option<String> {
+        (!BINANCE_TICKER_WINDOWS.contains(&kind.0)).then(|| {
+            format!(
+                "{} does not support {kind} rolling window tickers, supported windows: {}",
+                ExchangeId::BinanceSpot,
+                BINANCE_TICKER_WINDOWS
+                    .map(|window| window.as_str())
+                    .join(", ")
+            )
+        })
+    }
+}
@@ -0,0 +1,278 @@
+use super::super::BinanceChannel;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{ExchangeId, ExchangeSub},
+    num::Num,
+    subscription::{candle::Interval, ticker::Ticker},
+    Identifier,
+};
+use barter_integration::model::{Exchange, Instrument, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use smallvec::smallvec;
+
+/// [`BinanceSpot`](super::BinanceSpot) real-time rolling window ticker message.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#individual-symbol-rolling-window-statistics-streams>
+/// ```json
+/// {
+///     "e": "1hTicker",
+///     "E": 1672515782136,
+///     "s": "BNBBTC",
+///     "p": "0.0015",
+///     "P": "250.00",
+///     "o": "0.0010",
+///     "h": "0.0025",
+///     "l": "0.0010",
+///     "c": "0.0025",
+///     "w": "0.0018",
+///     "v": "10000",
+///     "q": "18",
+///     "O": 0,
+///     "C": 1675216573749,
+///     "F": 0,
+///     "L": 18150,
+///     "n": 18151
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceTicker {
+    #[serde(alias = "e", deserialize_with = "de_ticker_window")]
+    pub window: Interval,
+    #[serde(
+        alias = "E",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    #[serde(alias = "s")]
+    pub market: String,
+    #[serde(alias = "p", deserialize_with = "barter_integration::de::de_str")]
+    pub price_change: Num,
+    #[serde(alias = "P", deserialize_with = "barter_integration::de::de_str")]
+    pub price_change_percent: Num,
+    #[serde(alias = "o", deserialize_with = "barter_integration::de::de_str")]
+    pub open: Num,
+    #[serde(alias = "h", deserialize_with = "barter_integration::de::de_str")]
+    pub high: Num,
+    #[serde(alias = "l", deserialize_with = "barter_integration::de::de_str")]
+    pub low: Num,
+    #[serde(alias = "c", deserialize_with = "barter_integration::de::de_str")]
+    pub close: Num,
+    #[serde(alias = "w", deserialize_with = "barter_integration::de::de_str")]
+    pub vwap: Num,
+    #[serde(alias = "v", deserialize_with = "barter_integration::de::de_str")]
+    pub volume: Num,
+    #[serde(alias = "q", deserialize_with = "barter_integration::de::de_str")]
+    pub quote_volume: Num,
+    #[serde(
+        alias = "O",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub open_time: DateTime<Utc>,
+    #[serde(
+        alias = "C",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub close_time: DateTime<Utc>,
+    #[serde(alias = "n")]
+    pub trade_count: u64,
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceTicker {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(ExchangeSub::from((BinanceChannel::tickers(self.window), self.market.as_str())).id())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceTicker)> for MarketIter<Ticker> {
+    fn from(
+        (exchange_id, instrument, received_time, ticker): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceTicker,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: ticker.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                ticker.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: Ticker {
+                window: ticker.window,
+                open_time: ticker.open_time,
+                close_time: ticker.close_time,
+                open: ticker.open,
+                high: ticker.high,
+                low: ticker.low,
+                close: ticker.close,
+                vwap: Some(ticker.vwap),
+                volume: ticker.volume,
+                quote_volume: Some(ticker.quote_volume),
+                price_change: ticker.price_change,
+                price_change_percent: ticker.price_change_percent,
+                trade_count: Some(ticker.trade_count),
+            },
+            meta: (),
+        })])
+    }
+}
+
+/// Deserialize a [`BinanceTicker`] event type "e" (eg/ "4hTicker") as the associated window
+/// [`Interval`].
+///
+/// eg/ "4hTicker" -> Interval::H4
+pub fn de_ticker_window<'de, D>(deserializer: D) -> Result<Interval, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let event = <&str as Deserialize>::deserialize(deserializer)?;
+    let window = event.strip_suffix("Ticker").ok_or_else(|| {
+        serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(event),
+            &"rolling window ticker event type, eg/ 4hTicker",
+        )
+    })?;
+
+    Interval::deserialize(window.into_deserializer())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    mod de {
+        use super::*;
+        use barter_integration::de::datetime_utc_from_epoch_duration;
+        use barter_integration::error::SocketError;
+        use serde::de::Error;
+        use std::time::Duration;
+
+        #[test]
+        fn test_binance_ticker() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BinanceTicker, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: valid 1h ticker
+                    input: r#"
+                    {
+                        "e":"1hTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015","P":"250.00",
+                        "o":"0.0010","h":"0.0025","l":"0.0010","c":"0.0025","w":"0.0018",
+                        "v":"10000","q":"18","O":0,"C":1675216573749,"F":0,"L":18150,"n":18151
+                    }
+                    "#,
+                    expected: Ok(BinanceTicker {
+                        window: Interval::H1,
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1672515782136,
+                        )),
+                        market: "BNBBTC".to_string(),
+                        price_change: num!(0.0015),
+                        price_change_percent: num!(250.00),
+                        open: num!(0.0010),
+                        high: num!(0.0025),
+                        low: num!(0.0010),
+                        close: num!(0.0025),
+                        vwap: num!(0.0018),
+                        volume: num!(10000.0),
+                        quote_volume: num!(18.0),
+                        open_time: datetime_utc_from_epoch_duration(Duration::from_millis(0)),
+                        close_time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1675216573749,
+                        )),
+                        trade_count: 18151,
+                    }),
+                },
+                TestCase {
+                    // TC1: invalid ticker w/ 24hr ticker event type
+                    input: r#"
+                    {
+                        "e":"24hrTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015",
+                        "P":"250.00","o":"0.0010","h":"0.0025","l":"0.0010","c":"0.0025",
+                        "w":"0.0018","v":"10000","q":"18","O":0,"C":1675216573749,"n":18151
+                    }
+                    "#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde_json::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+                TestCase {
+                    // TC2: invalid ticker w/ non-numeric vwap
+                    input: r#"
+                    {
+                        "e":"4hTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015",
+                        "P":"250.00","o":"0.0010","h":"0.0025","l":"0.0010","c":"0.0025",
+                        "w":"vwap","v":"10000","q":"18","O":0,"C":1675216573749,"n":18151
+                    }
+                    "#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde_json::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceTicker>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binance_ticker_subscription_id() {
+        struct TestCase {
+            input: &'static str,
+            expected: SubscriptionId,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: 1h window
+                input: "1hTicker",
+                expected: SubscriptionId::from("@ticker_1h|BNBBTC"),
+            },
+            TestCase {
+                // TC1: 4h window
+                input: "4hTicker",
+                expected: SubscriptionId::from("@ticker_4h|BNBBTC"),
+            },
+            TestCase {
+                // TC2: 1d window
+                input: "1dTicker",
+                expected: SubscriptionId::from("@ticker_1d|BNBBTC"),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let ticker = serde_json::from_str::<BinanceTicker>(&format!(
+                r#"{{"e":"{}","E":0,"s":"BNBBTC","p":"0","P":"0","o":"0","h":"0","l":"0","c":"0","w":"0","v":"0","q":"0","O":0,"C":0,"n":0}}"#,
+                test.input
+            ))
+            .unwrap();
+            assert_eq!(ticker.id(), Some(test.expected), "TC{} failed", index);
+        }
+    }
+}
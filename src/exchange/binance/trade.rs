@@ -1,13 +1,15 @@
 use super::BinanceChannel;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, Side, SubscriptionId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Binance real-time trade message.
 ///
@@ -36,6 +38,10 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 ///
+/// Spot & FuturePerpetual trades share the same fields apart from the FuturePerpetual "X"
+/// [`BinanceTradeType`]. For both, "T" is the trade time, "t" is the trade id, and "m" is true if
+/// the buyer was the maker (ie/ the seller was the aggressor).
+///
 /// #### FuturePerpetual Side::Sell Trade
 /// ```json
 /// {
@@ -56,17 +62,42 @@ pub struct BinanceTrade {
     pub subscription_id: SubscriptionId,
     #[serde(
         alias = "T",
-        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
     #[serde(alias = "t")]
     pub id: u64,
     #[serde(alias = "p", deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     #[serde(alias = "q", deserialize_with = "barter_integration::de::de_str")]
-    pub amount: f64,
+    pub amount: Num,
     #[serde(alias = "m", deserialize_with = "de_side_from_buyer_is_maker")]
     pub side: Side,
+    #[serde(alias = "X", default)]
+    pub trade_type: Option<BinanceTradeType>,
+}
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) trade "X" type, not present on Spot
+/// trades.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BinanceTradeType {
+    Market,
+    Liquidation,
+    InsuranceFund,
+    Adl,
+    /// Not a trade (eg/ a zero amount update), so it is not normalised into a [`PublicTrade`].
+    Na,
+    #[serde(other)]
+    Unknown,
+}
+
+impl BinanceTrade {
+    /// Determine if this [`BinanceTrade`] is an executed trade that should be normalised into a
+    /// [`PublicTrade`], rather than a FuturePerpetual "NA" or zero amount entry.
+    pub fn is_executed(&self) -> bool {
+        self.trade_type != Some(BinanceTradeType::Na) && self.amount > crate::num::zero()
+    }
 }
 
 impl Identifier<Option<SubscriptionId>> for BinanceTrade {
@@ -75,11 +106,125 @@ impl Identifier<Option<SubscriptionId>> for BinanceTrade {
     }
 }
 
-impl From<(ExchangeId, Instrument, BinanceTrade)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, BinanceTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceTrade,
+        ),
+    ) -> Self {
+        if !trade.is_executed() {
+            return Self(smallvec![]);
+        }
+
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: trade.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: PublicTrade {
+                id: trade.id.to_string(),
+                price: trade.price,
+                amount: trade.amount,
+                side: trade.side,
+            },
+            meta: (),
+        })])
+    }
+}
+
+/// Binance real-time aggregate trade message.
+///
+/// Consecutive fills of the same taker order at the same price are aggregated, so each
+/// [`BinanceAggTrade`] covers the inclusive range of trade ids "f" to "l", and it's identified
+/// by the aggregate trade id "a" rather than a single trade id.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#aggregate-trade-streams>
+/// #### Spot Side::Buy Aggregate Trade
+/// ```json
+/// {
+///     "e":"aggTrade",
+///     "E":1672515782136,
+///     "s":"BTCUSDT",
+///     "a":2347593285,
+///     "p":"16610.21000000",
+///     "q":"0.00330000",
+///     "f":2482451912,
+///     "l":2482451914,
+///     "T":1672515782134,
+///     "m":false,
+///     "M":true
+/// }
+/// ```
+///
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#aggregate-trade-streams>
+/// #### FuturePerpetual Side::Sell Aggregate Trade
+/// ```json
+/// {
+///     "e":"aggTrade",
+///     "E":1672515782136,
+///     "s":"BTCUSDT",
+///     "a":1733129612,
+///     "p":"16605.10",
+///     "q":"0.120",
+///     "f":3237814150,
+///     "l":3237814152,
+///     "T":1672515782134,
+///     "m":true
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceAggTrade {
+    #[serde(alias = "s", deserialize_with = "de_agg_trade_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    #[serde(
+        alias = "T",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    #[serde(alias = "a")]
+    pub id: u64,
+    #[serde(alias = "f")]
+    pub first_trade_id: u64,
+    #[serde(alias = "l")]
+    pub last_trade_id: u64,
+    #[serde(alias = "p", deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(alias = "q", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    #[serde(alias = "m", deserialize_with = "de_side_from_buyer_is_maker")]
+    pub side: Side,
+}
+
+impl Identifier<Option<SubscriptionId>> for BinanceAggTrade {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceAggTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceAggTrade,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: trade.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: PublicTrade {
@@ -88,6 +233,7 @@ impl From<(ExchangeId, Instrument, BinanceTrade)> for MarketIter<PublicTrade> {
                 amount: trade.amount,
                 side: trade.side,
             },
+            meta: (),
         })])
     }
 }
@@ -102,6 +248,16 @@ where
         .map(|market| ExchangeSub::from((BinanceChannel::TRADES, market)).id())
 }
 
+/// Deserialize a [`BinanceAggTrade`] "s" (eg/ "BTCUSDT") as the associated [`SubscriptionId`]
+/// (eg/ "@aggTrade|BTCUSDT").
+pub fn de_agg_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer)
+        .map(|market| ExchangeSub::from((BinanceChannel::AGG_TRADES, market)).id())
+}
+
 /// Deserialize a [`BinanceTrade`] "buyer_is_maker" boolean field to a Barter [`Side`].
 ///
 /// Variants:
@@ -123,6 +279,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -154,9 +311,10 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
+                        trade_type: None,
                     }),
                 },
                 TestCase {
@@ -185,9 +343,10 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Sell,
+                        trade_type: Some(BinanceTradeType::Market),
                     }),
                 },
                 TestCase {
@@ -204,9 +363,10 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
+                        trade_type: Some(BinanceTradeType::Liquidation),
                     }),
                 },
                 TestCase {
@@ -221,9 +381,46 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
+                        trade_type: Some(BinanceTradeType::InsuranceFund),
+                    }),
+                },
+                TestCase {
+                    // TC5: FuturePerpetual trade w/ type NA & zero quantity
+                    input: r#"{
+                        "e":"trade","E":1696118408137,"T":1696118408134,"s":"BTCUSDT",
+                        "t":4065328812,"p":"0","q":"0","X":"NA","m":true
+                    }"#,
+                    expected: Ok(BinanceTrade {
+                        subscription_id: SubscriptionId::from("@trade|BTCUSDT"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1696118408134,
+                        )),
+                        id: 4065328812,
+                        price: crate::num::zero(),
+                        amount: crate::num::zero(),
+                        side: Side::Sell,
+                        trade_type: Some(BinanceTradeType::Na),
+                    }),
+                },
+                TestCase {
+                    // TC6: FuturePerpetual trade w/ unrecognised type
+                    input: r#"{
+                        "e":"trade","E":1649839266194,"T":1749354825200,"s":"ETHUSDT",
+                        "t":1000000000,"p":"10000.19","q":"0.239000","X":"OTHER","m":true
+                    }"#,
+                    expected: Ok(BinanceTrade {
+                        subscription_id: SubscriptionId::from("@trade|ETHUSDT"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1749354825200,
+                        )),
+                        id: 1000000000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
+                        side: Side::Sell,
+                        trade_type: Some(BinanceTradeType::Unknown),
                     }),
                 },
             ];
@@ -244,5 +441,209 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn test_binance_agg_trade() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<BinanceAggTrade, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: Spot aggregate trade valid
+                    input: r#"{
+                        "e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":2347593285,
+                        "p":"16610.21000000","q":"0.00330000","f":2482451912,"l":2482451914,
+                        "T":1672515782134,"m":false,"M":true
+                    }"#,
+                    expected: Ok(BinanceAggTrade {
+                        subscription_id: SubscriptionId::from("@aggTrade|BTCUSDT"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1672515782134,
+                        )),
+                        id: 2347593285,
+                        first_trade_id: 2482451912,
+                        last_trade_id: 2482451914,
+                        price: num!(16610.21),
+                        amount: num!(0.0033),
+                        side: Side::Buy,
+                    }),
+                },
+                TestCase {
+                    // TC1: FuturePerpetual aggregate trade valid
+                    input: r#"{
+                        "e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":1733129612,
+                        "p":"16605.10","q":"0.120","f":3237814150,"l":3237814152,
+                        "T":1672515782134,"m":true
+                    }"#,
+                    expected: Ok(BinanceAggTrade {
+                        subscription_id: SubscriptionId::from("@aggTrade|BTCUSDT"),
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1672515782134,
+                        )),
+                        id: 1733129612,
+                        first_trade_id: 3237814150,
+                        last_trade_id: 3237814152,
+                        price: num!(16605.1),
+                        amount: num!(0.12),
+                        side: Side::Sell,
+                    }),
+                },
+                TestCase {
+                    // TC2: individual trade is not an aggregate trade since it has no "f" & "l"
+                    input: r#"{
+                        "e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,
+                        "p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,
+                        "T":1749354825200,"m":false,"M":true
+                    }"#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde_json::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<BinanceAggTrade>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_binance_trade_into_public_trades() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<(i64, &'static str, Num, Num, Side)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Spot trade where the seller is the maker is a Side::Buy
+                input: r#"{
+                    "e":"trade","E":1696118408000,"s":"BTCUSDT","t":3224187164,
+                    "p":"26962.01000000","q":"0.00120000","b":22722428131,"a":22722427912,
+                    "T":1696118407998,"m":false,"M":true
+                }"#,
+                expected: vec![(
+                    1696118407998,
+                    "3224187164",
+                    num!(26962.01),
+                    num!(0.0012),
+                    Side::Buy,
+                )],
+            },
+            TestCase {
+                // TC1: FuturePerpetual MARKET trade where the buyer is the maker is a Side::Sell
+                input: r#"{
+                    "e":"trade","E":1696118408137,"T":1696118408134,"s":"BTCUSDT",
+                    "t":4065328811,"p":"26954.80","q":"0.015","X":"MARKET","m":true
+                }"#,
+                expected: vec![(
+                    1696118408134,
+                    "4065328811",
+                    num!(26954.8),
+                    num!(0.015),
+                    Side::Sell,
+                )],
+            },
+            TestCase {
+                // TC2: FuturePerpetual NA entry is not a PublicTrade
+                input: r#"{
+                    "e":"trade","E":1696118408137,"T":1696118408134,"s":"BTCUSDT",
+                    "t":4065328812,"p":"0","q":"0","X":"NA","m":true
+                }"#,
+                expected: vec![],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let trade = serde_json::from_str::<BinanceTrade>(test.input).unwrap();
+            let actual = MarketIter::<PublicTrade>::from((
+                ExchangeId::BinanceFuturesUsd,
+                Instrument::from((
+                    "btc",
+                    "usdt",
+                    barter_integration::model::InstrumentKind::Spot,
+                )),
+                Utc::now(),
+                trade,
+            ))
+            .0
+            .into_iter()
+            .map(|event| {
+                let event = event.unwrap();
+                (
+                    event.exchange_time.timestamp_millis(),
+                    event.kind.id,
+                    event.kind.price,
+                    event.kind.amount,
+                    event.kind.side,
+                )
+            })
+            .collect::<Vec<_>>();
+
+            let expected = test
+                .expected
+                .into_iter()
+                .map(|(time, id, price, amount, side)| (time, id.to_string(), price, amount, side))
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_binance_trades_subscribe_to_channel_of_kind() {
+        use crate::{
+            exchange::{binance::spot::BinanceSpot, Connector},
+            subscription::{
+                trade::{PublicTrades, PublicTradesAggregated},
+                Subscription,
+            },
+        };
+        use barter_integration::{model::InstrumentKind, protocol::websocket::WsMessage};
+
+        let trades = Subscription::from((
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        ));
+        let agg_trades = Subscription::from((
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTradesAggregated,
+        ));
+
+        let actual = BinanceSpot::requests(vec![
+            ExchangeSub::new(&trades),
+            ExchangeSub::new(&agg_trades),
+        ]);
+
+        let expected = vec![WsMessage::Text(
+            serde_json::json!({
+                "method": "SUBSCRIBE",
+                "params": ["btcusdt@trade", "btcusdt@aggTrade"],
+                "id": 1
+            })
+            .to_string(),
+        )];
+
+        assert_eq!(actual, expected);
     }
 }
@@ -0,0 +1,31 @@
+use super::{Binance, ExchangeServer};
+use crate::exchange::ExchangeId;
+
+/// [`BinanceUs`] WebSocket server base url.
+///
+/// See docs: <https://docs.binance.us/#websocket-streams>
+pub const WEBSOCKET_BASE_URL_BINANCE_US: &str = "wss://stream.binance.us:9443/ws";
+
+/// [`BinanceUs`] HTTP REST API base url.
+///
+/// See docs: <https://docs.binance.us/#general-api-information>
+pub const HTTP_BASE_URL_BINANCE_US: &str = "https://api.binance.us/api/v3";
+
+/// [`Binance`](super::Binance) US exchange.
+///
+/// Shares every [`Connector`](crate::exchange::Connector) implementation detail with
+/// [`BinanceSpot`](super::spot::BinanceSpot), apart from the server it connects to and the
+/// markets that are available.
+pub type BinanceUs = Binance<BinanceServerUs>;
+
+/// [`Binance`](super::Binance) US [`ExchangeServer`](super::super::ExchangeServer).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct BinanceServerUs;
+
+impl ExchangeServer for BinanceServerUs {
+    const ID: ExchangeId = ExchangeId::BinanceUs;
+
+    fn websocket_url() -> &'static str {
+        WEBSOCKET_BASE_URL_BINANCE_US
+    }
+}
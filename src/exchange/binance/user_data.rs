@@ -0,0 +1,1383 @@
+use super::{
+    channel::BinanceChannel,
+    futures::{BinanceServerFuturesUsd, HTTP_BASE_URL_BINANCE_FUTURES_USD},
+    market::BinanceMarket,
+    spot::{BinanceServerSpot, HTTP_BASE_URL_BINANCE_SPOT},
+    Binance,
+};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{alias::exchange_assets, ExchangeId, ExchangeServer},
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::{
+        auth::Credentials,
+        proxy::request_json,
+        transport::{connect, WebSocket},
+    },
+    subscription::{
+        balance::Balance,
+        order::{OrderStatus, OrderUpdate},
+        SubKind, Subscription,
+    },
+    Identifier, MarketStream,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    error::SocketError,
+    model::{Exchange, Instrument, Side},
+    protocol::websocket::WsMessage,
+};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// [`Duration`] between the `listenKey` keepalive requests of a [`BinanceUserDataStream`].
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#listen-key-spot>
+pub const BINANCE_LISTEN_KEY_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// [`Duration`] a `listenKey` remains valid after it's creation or last keepalive.
+pub const BINANCE_LISTEN_KEY_VALIDITY: Duration = Duration::from_secs(60 * 60);
+
+/// [`Duration`] after which a [`BinanceUserDataStream`] proactively rotates to a new `listenKey`
+/// & connection, before the [`BINANCE_LISTEN_KEY_VALIDITY`] of the current `listenKey` elapses.
+pub const BINANCE_LISTEN_KEY_ROTATION_INTERVAL: Duration = Duration::from_secs(50 * 60);
+
+/// Header used to authenticate [`Binance`] `listenKey` REST requests with an API key.
+const BINANCE_API_KEY_HEADER: &str = "X-MBX-APIKEY";
+
+/// [`Binance`] [`ExchangeServer`] serving a user data stream of the account that owns a
+/// `listenKey`, created & kept alive via it's REST API.
+pub trait BinanceUserDataServer: ExchangeServer {
+    /// HTTP REST API base url, overridable via [`Endpoints::rest`](crate::exchange::endpoint::Endpoints::rest).
+    const HTTP_BASE_URL: &'static str;
+
+    /// Path of the `listenKey` REST endpoint, relative to the [`Self::HTTP_BASE_URL`].
+    const LISTEN_KEY_PATH: &'static str;
+}
+
+impl BinanceUserDataServer for BinanceServerSpot {
+    const HTTP_BASE_URL: &'static str = HTTP_BASE_URL_BINANCE_SPOT;
+
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#listen-key-spot>
+    const LISTEN_KEY_PATH: &'static str = "/userDataStream";
+}
+
+impl BinanceUserDataServer for BinanceServerFuturesUsd {
+    const HTTP_BASE_URL: &'static str = HTTP_BASE_URL_BINANCE_FUTURES_USD;
+
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#start-user-data-stream-user_stream>
+    const LISTEN_KEY_PATH: &'static str = "/listenKey";
+}
+
+/// [`Binance`] `listenKey` REST response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///   "listenKey": "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BinanceListenKey {
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// Create a new `listenKey` of the account owning the API key of the provided [`Credentials`],
+/// using the [`Proxy`](crate::subscriber::proxy::Proxy) of the provided [`StreamConfig`].
+///
+/// An API key rejected by [`Binance`] fails with a [`DataError::Auth`].
+pub async fn create_listen_key<Server>(
+    credentials: &Credentials,
+    config: &StreamConfig,
+) -> Result<String, DataError>
+where
+    Server: BinanceUserDataServer,
+{
+    let url = listen_key_url::<Server>(None, config);
+    let headers = [(BINANCE_API_KEY_HEADER, credentials.api_key.as_str())];
+
+    match request_json::<BinanceListenKey>(
+        config.proxy.as_ref(),
+        Method::POST,
+        &url,
+        &headers,
+        None,
+    )
+    .await
+    {
+        Ok(response) => Ok(response.listen_key),
+        Err(DataError::Socket(SocketError::HttpResponse(status, body)))
+            if status.is_client_error() =>
+        {
+            Err(DataError::Auth {
+                exchange: Server::ID,
+                reason: format!("failed to create listenKey with status {status}: {body}"),
+            })
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Extend the validity of the provided `listenKey` by [`BINANCE_LISTEN_KEY_VALIDITY`], using the
+/// [`Proxy`](crate::subscriber::proxy::Proxy) of the provided [`StreamConfig`].
+pub async fn keepalive_listen_key<Server>(
+    credentials: &Credentials,
+    listen_key: &str,
+    config: &StreamConfig,
+) -> Result<(), DataError>
+where
+    Server: BinanceUserDataServer,
+{
+    let url = listen_key_url::<Server>(Some(listen_key), config);
+    let headers = [(BINANCE_API_KEY_HEADER, credentials.api_key.as_str())];
+
+    request_json::<serde_json::Value>(config.proxy.as_ref(), Method::PUT, &url, &headers, None)
+        .await
+        .map(|_| ())
+}
+
+/// Construct the `listenKey` REST url of the [`BinanceUserDataServer`], including the
+/// `listenKey` query parameter if provided, using the [`Endpoints::rest`](crate::exchange::endpoint::Endpoints::rest) override of the
+/// provided [`StreamConfig`] as the base url if there is one.
+fn listen_key_url<Server>(listen_key: Option<&str>, config: &StreamConfig) -> String
+where
+    Server: BinanceUserDataServer,
+{
+    let url = format!(
+        "{}{}",
+        config.endpoints.rest_base_url(Server::HTTP_BASE_URL),
+        Server::LISTEN_KEY_PATH
+    );
+
+    match listen_key {
+        Some(listen_key) => format!("{url}?listenKey={listen_key}"),
+        None => url,
+    }
+}
+
+/// Construct the user data stream WebSocket url of the provided `listenKey`, using the
+/// [`Endpoints::websocket`](crate::exchange::endpoint::Endpoints::websocket) override of the provided [`StreamConfig`] as the base url if there
+/// is one.
+#[allow(clippy::result_large_err)]
+fn user_data_url<Server>(listen_key: &str, config: &StreamConfig) -> Result<Url, SocketError>
+where
+    Server: ExchangeServer,
+{
+    let base = match &config.endpoints.websocket {
+        Some(url) => url.as_str().trim_end_matches('/').to_owned(),
+        None => Server::websocket_url().to_owned(),
+    };
+
+    Url::parse(&format!("{base}/{listen_key}")).map_err(SocketError::UrlParse)
+}
+
+/// [`Binance`] user data stream event, for both [`BinanceSpot`](super::spot::BinanceSpot) &
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd).
+///
+/// Events not normalised by Barter (eg/ "balanceUpdate", "MARGIN_CALL") are deserialised as
+/// [`BinanceUserData::Other`].
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#user-data-streams>
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#user-data-streams>
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "e")]
+pub enum BinanceUserData {
+    #[serde(rename = "executionReport")]
+    ExecutionReport(BinanceOrder),
+    #[serde(rename = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(BinanceFuturesOrderUpdate),
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(BinanceAccountPosition),
+    #[serde(rename = "ACCOUNT_UPDATE")]
+    AccountUpdate(BinanceFuturesAccountUpdate),
+    /// The `listenKey` of the connection expired, so no further events will be received.
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired,
+    #[serde(other)]
+    Other,
+}
+
+/// [`Binance`] order update of the logged in account, either a spot "executionReport" or the "o"
+/// order of a futures "ORDER_TRADE_UPDATE".
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#payload-order-update>
+/// #### Spot Limit Order Partially Filled
+/// ```json
+/// {
+///   "e": "executionReport",
+///   "E": 1499405658658,
+///   "s": "ETHBTC",
+///   "c": "mUvoqJxFIILMdfAW5iGSOW",
+///   "S": "BUY",
+///   "o": "LIMIT",
+///   "f": "GTC",
+///   "q": "1.00000000",
+///   "p": "0.10264410",
+///   "x": "TRADE",
+///   "X": "PARTIALLY_FILLED",
+///   "i": 4293153,
+///   "l": "0.50000000",
+///   "z": "0.50000000",
+///   "L": "0.10264410",
+///   "T": 1499405658657,
+///   "r": "NONE"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceOrder {
+    #[serde(rename = "s")]
+    pub market: String,
+    #[serde(rename = "i")]
+    pub id: u64,
+    #[serde(rename = "c")]
+    pub client_id: String,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "X")]
+    pub status: BinanceOrderStatus,
+    #[serde(rename = "p", deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(rename = "q", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    #[serde(rename = "z", deserialize_with = "barter_integration::de::de_str")]
+    pub filled_amount: Num,
+    #[serde(
+        rename = "T",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    /// Reject reason of a spot order, "NONE" unless it was rejected.
+    #[serde(rename = "r", default)]
+    pub reject_reason: Option<String>,
+}
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) "ORDER_TRADE_UPDATE" event.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#event-order-update>
+/// ```json
+/// {
+///   "e": "ORDER_TRADE_UPDATE",
+///   "E": 1568879465651,
+///   "T": 1568879465650,
+///   "o": {
+///     "s": "BTCUSDT",
+///     "c": "TEST",
+///     "S": "SELL",
+///     "o": "MARKET",
+///     "f": "GTC",
+///     "q": "0.001",
+///     "p": "0",
+///     "ap": "0",
+///     "x": "NEW",
+///     "X": "NEW",
+///     "i": 8886774,
+///     "l": "0",
+///     "z": "0",
+///     "T": 1568879465650
+///   }
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceFuturesOrderUpdate {
+    #[serde(rename = "o")]
+    pub order: BinanceOrder,
+}
+
+/// [`BinanceOrder`] "X" status.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BinanceOrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    /// Currently unused by [`Binance`].
+    PendingCancel,
+    Rejected,
+    Expired,
+    /// Expired by self trade prevention.
+    ExpiredInMatch,
+}
+
+impl From<BinanceOrderStatus> for OrderStatus {
+    fn from(status: BinanceOrderStatus) -> Self {
+        match status {
+            BinanceOrderStatus::New | BinanceOrderStatus::PendingCancel => OrderStatus::Open,
+            BinanceOrderStatus::PartiallyFilled => OrderStatus::PartiallyFilled,
+            BinanceOrderStatus::Filled => OrderStatus::Filled,
+            BinanceOrderStatus::Canceled
+            | BinanceOrderStatus::Expired
+            | BinanceOrderStatus::ExpiredInMatch => OrderStatus::Cancelled,
+            BinanceOrderStatus::Rejected => OrderStatus::Rejected,
+        }
+    }
+}
+
+/// [`BinanceSpot`](super::spot::BinanceSpot) "outboundAccountPosition" event, containing the
+/// balances of every asset that changed.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#payload-account-update>
+/// ```json
+/// {
+///   "e": "outboundAccountPosition",
+///   "E": 1564034571105,
+///   "u": 1564034571073,
+///   "B": [
+///     {
+///       "a": "ETH",
+///       "f": "10000.000000",
+///       "l": "0.000000"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceAccountPosition {
+    #[serde(
+        rename = "E",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    #[serde(rename = "B")]
+    pub balances: Vec<BinanceSpotBalance>,
+}
+
+/// [`BinanceAccountPosition`] balance of a single asset.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceSpotBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "f", deserialize_with = "barter_integration::de::de_str")]
+    pub free: Num,
+    #[serde(rename = "l", deserialize_with = "barter_integration::de::de_str")]
+    pub locked: Num,
+}
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) "ACCOUNT_UPDATE" event, containing
+/// the balances of every asset that changed.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#event-balance-and-position-update>
+/// ```json
+/// {
+///   "e": "ACCOUNT_UPDATE",
+///   "E": 1564745798939,
+///   "T": 1564745798938,
+///   "a": {
+///     "m": "ORDER",
+///     "B": [
+///       {
+///         "a": "USDT",
+///         "wb": "122624.12345678",
+///         "cw": "100.12345678",
+///         "bc": "50.12345678"
+///       }
+///     ],
+///     "P": []
+///   }
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceFuturesAccountUpdate {
+    #[serde(
+        rename = "T",
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    #[serde(rename = "a")]
+    pub account: BinanceFuturesAccount,
+}
+
+/// [`BinanceFuturesAccountUpdate`] account data.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceFuturesAccount {
+    #[serde(rename = "B")]
+    pub balances: Vec<BinanceFuturesBalance>,
+}
+
+/// [`BinanceFuturesAccount`] balance of a single asset.
+///
+/// Note:
+/// Binance does not push the available balance of futures assets, so the cross wallet balance
+/// ("cw") is used as the available balance.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceFuturesBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "wb", deserialize_with = "barter_integration::de::de_str")]
+    pub wallet: Num,
+    #[serde(rename = "cw", deserialize_with = "barter_integration::de::de_str")]
+    pub cross_wallet: Num,
+}
+
+/// [`Instrument`]s of the [`Subscription`]s of a [`BinanceUserDataStream`], used to identify the
+/// [`Instrument`]s a [`BinanceUserData`] event is relevant to.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BinanceUserDataInstruments {
+    exchange: ExchangeId,
+    markets: HashMap<String, Instrument>,
+}
+
+impl BinanceUserDataInstruments {
+    /// Construct a new [`Self`] from the provided [`Subscription`]s.
+    pub fn new<Server, Kind>(subscriptions: &[Subscription<Binance<Server>, Kind>]) -> Self
+    where
+        Server: ExchangeServer,
+    {
+        Self {
+            exchange: Server::ID,
+            markets: subscriptions
+                .iter()
+                .map(|subscription| {
+                    let BinanceMarket(market) = Identifier::<BinanceMarket>::id(subscription);
+                    (market, subscription.instrument.clone())
+                })
+                .collect(),
+        }
+    }
+
+    /// Determine the subscribed [`Instrument`] of the provided market (eg/ "BTCUSDT"), if any.
+    fn instrument(&self, market: &str) -> Option<&Instrument> {
+        self.markets.get(market)
+    }
+
+    /// Determine every subscribed [`Instrument`] with the provided asset as it's base or quote,
+    /// alongside the canonical asset.
+    fn with_asset<'a>(
+        &'a self,
+        asset: &'a str,
+    ) -> impl Iterator<Item = (&'a Instrument, &'a barter_integration::model::Symbol)> + 'a {
+        self.markets.values().filter_map(move |instrument| {
+            let (base, quote) = exchange_assets(self.exchange, instrument);
+            if asset.eq_ignore_ascii_case(&base) {
+                Some((instrument, &instrument.base))
+            } else if asset.eq_ignore_ascii_case(&quote) {
+                Some((instrument, &instrument.quote))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl From<(&BinanceUserDataInstruments, DateTime<Utc>, BinanceUserData)>
+    for MarketIter<OrderUpdate>
+{
+    fn from(
+        (instruments, received_time, event): (
+            &BinanceUserDataInstruments,
+            DateTime<Utc>,
+            BinanceUserData,
+        ),
+    ) -> Self {
+        let order = match event {
+            BinanceUserData::ExecutionReport(order)
+            | BinanceUserData::OrderTradeUpdate(BinanceFuturesOrderUpdate { order }) => order,
+            _ => return Self(Default::default()),
+        };
+
+        // Orders of markets that were not subscribed to are dropped
+        let Some(instrument) = instruments.instrument(&order.market) else {
+            return Self(Default::default());
+        };
+
+        std::iter::once(Ok(MarketEvent {
+            exchange_time: order.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                order.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(instruments.exchange),
+            instrument: instrument.clone(),
+            kind: OrderUpdate {
+                order_id: order.id.to_string(),
+                client_order_id: (!order.client_id.is_empty()).then_some(order.client_id),
+                status: OrderStatus::from(order.status),
+                side: order.side,
+                // Market orders have a zero price
+                price: (order.price != Num::default()).then_some(order.price),
+                amount: order.amount,
+                filled_amount: order.filled_amount,
+                reason: order.reject_reason.filter(|reason| reason != "NONE"),
+            },
+            meta: (),
+        }))
+        .collect()
+    }
+}
+
+impl From<(&BinanceUserDataInstruments, DateTime<Utc>, BinanceUserData)> for MarketIter<Balance> {
+    fn from(
+        (instruments, received_time, event): (
+            &BinanceUserDataInstruments,
+            DateTime<Utc>,
+            BinanceUserData,
+        ),
+    ) -> Self {
+        let (time, balances) = match event {
+            BinanceUserData::AccountPosition(position) => (
+                position.time,
+                position
+                    .balances
+                    .into_iter()
+                    .map(|balance| (balance.asset, balance.free + balance.locked, balance.free))
+                    .collect::<Vec<_>>(),
+            ),
+            BinanceUserData::AccountUpdate(update) => (
+                update.time,
+                update
+                    .account
+                    .balances
+                    .into_iter()
+                    .map(|balance| (balance.asset, balance.wallet, balance.cross_wallet))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => return Self(Default::default()),
+        };
+
+        balances
+            .iter()
+            .flat_map(|(asset, total, available)| {
+                instruments
+                    .with_asset(asset)
+                    .map(move |(instrument, asset)| {
+                        Ok(MarketEvent {
+                            exchange_time: time,
+                            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                                time,
+                                TimestampUnit::Milliseconds,
+                            )),
+                            received_time,
+                            exchange: Exchange::from(instruments.exchange),
+                            instrument: instrument.clone(),
+                            kind: Balance {
+                                asset: asset.clone(),
+                                total: *total,
+                                available: *available,
+                            },
+                            meta: (),
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Convenient type alias for the boxed [`Stream`] driving a [`BinanceUserDataStream`].
+type UserDataStream<T> = Pin<Box<dyn Stream<Item = Result<MarketEvent<T>, DataError>> + Send>>;
+
+/// [`MarketStream`] of the private [`Binance`] user data stream of the account owning the API
+/// key of the [`StreamConfig::credentials`].
+///
+/// - On initialisation a `listenKey` is created via the REST API, and the WebSocket connects to
+///   `<websocket_url>/<listenKey>`. No subscription requests are sent, since the stream pushes
+///   every event of the account.
+/// - The `listenKey` is kept alive every [`BINANCE_LISTEN_KEY_KEEPALIVE_INTERVAL`] by this
+///   stream itself, so it's driven by the same task that owns the connection.
+/// - Every [`BINANCE_LISTEN_KEY_ROTATION_INTERVAL`], before the [`BINANCE_LISTEN_KEY_VALIDITY`]
+///   elapses, the same task creates a new `listenKey` & connects to it, only then dropping the
+///   old connection.
+/// - If a keepalive or rotation fails, or [`Binance`] reports the `listenKey` expired, the stream
+///   ends so the re-connecting consumer loop starts again with a new `listenKey`.
+///
+/// Only events for the [`Instrument`]s of the [`Subscription`]s are yielded, see
+/// [`BinanceUserDataInstruments`].
+pub struct BinanceUserDataStream<Server, Kind>
+where
+    Kind: SubKind,
+{
+    stream: UserDataStream<Kind::Event>,
+    phantom: PhantomData<fn() -> Server>,
+}
+
+impl<Server, Kind> Debug for BinanceUserDataStream<Server, Kind>
+where
+    Kind: SubKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinanceUserDataStream")
+            .field("server", &std::any::type_name::<Server>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Server, Kind> Stream for BinanceUserDataStream<Server, Kind>
+where
+    Kind: SubKind,
+{
+    type Item = Result<MarketEvent<Kind::Event>, DataError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+#[async_trait]
+impl<Server, Kind> MarketStream<Binance<Server>, Kind> for BinanceUserDataStream<Server, Kind>
+where
+    Server: BinanceUserDataServer + Send + Sync + 'static,
+    Kind: SubKind + Send + Sync + 'static,
+    Kind::Event: Send,
+    MarketIter<Kind::Event>: for<'a> From<(
+        &'a BinanceUserDataInstruments,
+        DateTime<Utc>,
+        BinanceUserData,
+    )>,
+{
+    async fn init_with(
+        subscriptions: &[Subscription<Binance<Server>, Kind>],
+        config: &StreamConfig,
+    ) -> Result<Self, DataError>
+    where
+        Subscription<Binance<Server>, Kind>: Identifier<BinanceChannel> + Identifier<BinanceMarket>,
+    {
+        Self::init_with_schedule(
+            subscriptions,
+            config,
+            BINANCE_LISTEN_KEY_KEEPALIVE_INTERVAL,
+            BINANCE_LISTEN_KEY_ROTATION_INTERVAL,
+        )
+        .await
+    }
+}
+
+impl<Server, Kind> BinanceUserDataStream<Server, Kind>
+where
+    Server: BinanceUserDataServer + Send + Sync + 'static,
+    Kind: SubKind + Send + Sync + 'static,
+    Kind::Event: Send,
+    MarketIter<Kind::Event>: for<'a> From<(
+        &'a BinanceUserDataInstruments,
+        DateTime<Utc>,
+        BinanceUserData,
+    )>,
+{
+    /// Create a `listenKey` & connect to it's user data stream, keeping it alive every
+    /// `keepalive_interval` & rotating to a new `listenKey` every `rotation_interval`.
+    async fn init_with_schedule(
+        subscriptions: &[Subscription<Binance<Server>, Kind>],
+        config: &StreamConfig,
+        keepalive_interval: Duration,
+        rotation_interval: Duration,
+    ) -> Result<Self, DataError> {
+        let exchange = Server::ID;
+        let credentials = config.credentials.clone().ok_or_else(|| DataError::Auth {
+            exchange,
+            reason: "user data stream requires Credentials with an API key".to_owned(),
+        })?;
+
+        let (listen_key, websocket) = connect_listen_key::<Server>(&credentials, config).await?;
+        info!(%exchange, ?subscriptions, "connected to user data stream");
+
+        let state = UserDataState::<Server, Kind::Event> {
+            websocket,
+            credentials,
+            listen_key,
+            keepalive: schedule(keepalive_interval),
+            rotation: schedule(rotation_interval),
+            config: config.clone(),
+            instruments: BinanceUserDataInstruments::new(subscriptions),
+            pending: VecDeque::new(),
+            ended: false,
+            phantom: PhantomData,
+        };
+
+        let stream = futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(next) = state.pending.pop_front() {
+                    return Some((next, state));
+                }
+
+                if state.ended || !state.next().await {
+                    return None;
+                }
+            }
+        });
+
+        Ok(Self {
+            stream: Box::pin(stream),
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// Create a new `listenKey` & connect to it's user data stream.
+async fn connect_listen_key<Server>(
+    credentials: &Credentials,
+    config: &StreamConfig,
+) -> Result<(String, WebSocket), DataError>
+where
+    Server: BinanceUserDataServer,
+{
+    let listen_key = create_listen_key::<Server>(credentials, config).await?;
+    debug!(exchange = %Server::ID, "created user data stream listenKey");
+
+    let websocket = connect(
+        user_data_url::<Server>(&listen_key, config)?,
+        &config.ws,
+        config.proxy.as_ref(),
+    )
+    .await?;
+
+    Ok((listen_key, websocket))
+}
+
+/// Construct an [`Interval`] first ticking after one `period` has elapsed.
+fn schedule(period: Duration) -> Interval {
+    let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
+
+/// State of a [`BinanceUserDataStream`], owning it's connection & `listenKey`.
+struct UserDataState<Server, T> {
+    websocket: WebSocket,
+    credentials: Credentials,
+    listen_key: String,
+    keepalive: Interval,
+    rotation: Interval,
+    config: StreamConfig,
+    instruments: BinanceUserDataInstruments,
+    pending: VecDeque<Result<MarketEvent<T>, DataError>>,
+    ended: bool,
+    phantom: PhantomData<fn() -> Server>,
+}
+
+impl<Server, T> UserDataState<Server, T>
+where
+    Server: BinanceUserDataServer,
+    MarketIter<T>: for<'a> From<(
+        &'a BinanceUserDataInstruments,
+        DateTime<Utc>,
+        BinanceUserData,
+    )>,
+{
+    /// Wait for the next WebSocket message, scheduled keepalive or scheduled rotation, queueing
+    /// any resulting [`MarketEvent<T>`](MarketEvent)s. Returns false if the stream has ended.
+    async fn next(&mut self) -> bool {
+        let exchange = Server::ID;
+
+        tokio::select! {
+            _ = self.keepalive.tick() => {
+                match keepalive_listen_key::<Server>(
+                    &self.credentials,
+                    &self.listen_key,
+                    &self.config,
+                )
+                .await
+                {
+                    Ok(()) => debug!(%exchange, "kept alive user data stream listenKey"),
+                    Err(error) => {
+                        warn!(
+                            %exchange,
+                            %error,
+                            action = "ending stream to re-connect with a new listenKey",
+                            "failed to keep alive user data stream listenKey"
+                        );
+                        self.pending.push_back(Err(error));
+                        self.ended = true;
+                    }
+                }
+                true
+            }
+            _ = self.rotation.tick() => {
+                match connect_listen_key::<Server>(&self.credentials, &self.config).await {
+                    Ok((listen_key, websocket)) => {
+                        // Only drop the old connection once the new one is established
+                        let old = std::mem::replace(&mut self.websocket, websocket);
+                        self.listen_key = listen_key;
+                        self.keepalive.reset();
+                        drop(old);
+                        info!(%exchange, "rotated user data stream to a new listenKey");
+                    }
+                    Err(error) => {
+                        warn!(
+                            %exchange,
+                            %error,
+                            action = "ending stream to re-connect with a new listenKey",
+                            "failed to rotate user data stream listenKey"
+                        );
+                        self.pending.push_back(Err(error));
+                        self.ended = true;
+                    }
+                }
+                true
+            }
+            message = self.websocket.next() => match message {
+                Some(Ok(WsMessage::Text(payload))) => {
+                    let received_time = Utc::now();
+                    match serde_json::from_str::<BinanceUserData>(&payload) {
+                        Ok(BinanceUserData::ListenKeyExpired) => {
+                            warn!(
+                                %exchange,
+                                action = "ending stream to re-connect with a new listenKey",
+                                "user data stream listenKey expired"
+                            );
+                            false
+                        }
+                        Ok(event) => {
+                            self.pending
+                                .extend(MarketIter::<T>::from((&self.instruments, received_time, event)).0);
+                            true
+                        }
+                        Err(error) => {
+                            self.pending.push_back(Err(DataError::Socket(
+                                SocketError::Deserialise { error, payload },
+                            )));
+                            true
+                        }
+                    }
+                }
+                Some(Ok(WsMessage::Close(close_frame))) => {
+                    debug!(%exchange, ?close_frame, "user data stream closed by exchange");
+                    false
+                }
+                // Pings are responded to by tungstenite
+                Some(Ok(_)) => true,
+                Some(Err(error)) => {
+                    self.pending
+                        .push_back(Err(DataError::Socket(SocketError::WebSocket(error))));
+                    self.ended = true;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::{
+            binance::{futures::BinanceFuturesUsd, spot::BinanceSpot},
+            endpoint::Endpoints,
+        },
+        num,
+        subscription::{balance::Balances, order::OrderUpdates},
+    };
+    use barter_integration::model::{InstrumentKind, Symbol};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn spot_instruments() -> BinanceUserDataInstruments {
+        BinanceUserDataInstruments::new(&[
+            Subscription::from((
+                BinanceSpot::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                OrderUpdates,
+            )),
+            Subscription::from((
+                BinanceSpot::default(),
+                "eth",
+                "btc",
+                InstrumentKind::Spot,
+                OrderUpdates,
+            )),
+        ])
+    }
+
+    #[test]
+    fn test_binance_user_data_into_order_updates() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<(Instrument, OrderUpdate)>,
+        }
+
+        let futures = BinanceUserDataInstruments::new(&[Subscription::from((
+            BinanceFuturesUsd::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::FuturePerpetual,
+            OrderUpdates,
+        ))]);
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot limit order partially filled
+                input: r#"{
+                    "e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"mUvoqJxFIILMdfAW5iGSOW",
+                    "S":"BUY","o":"LIMIT","f":"GTC","q":"1.00000000","p":"0.10264410","x":"TRADE",
+                    "X":"PARTIALLY_FILLED","i":4293153,"l":"0.50000000","z":"0.50000000",
+                    "L":"0.10264410","T":1499405658657,"r":"NONE"
+                }"#,
+                expected: vec![(
+                    Instrument::from(("eth", "btc", InstrumentKind::Spot)),
+                    OrderUpdate {
+                        order_id: "4293153".to_string(),
+                        client_order_id: Some("mUvoqJxFIILMdfAW5iGSOW".to_string()),
+                        status: OrderStatus::PartiallyFilled,
+                        side: Side::Buy,
+                        price: Some(num!(0.1026441)),
+                        amount: num!(1.0),
+                        filled_amount: num!(0.5),
+                        reason: None,
+                    },
+                )],
+            },
+            TestCase {
+                // TC1: spot order of a market that was not subscribed to is dropped
+                input: r#"{
+                    "e":"executionReport","E":1499405658658,"s":"BNBBTC","c":"",
+                    "S":"SELL","o":"LIMIT","f":"GTC","q":"1.0","p":"0.01","x":"NEW",
+                    "X":"NEW","i":4293154,"l":"0","z":"0","L":"0","T":1499405658657
+                }"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC2: spot balance update is not an OrderUpdate
+                input: r#"{
+                    "e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,
+                    "B":[{"a":"BTC","f":"1.0","l":"0.0"}]
+                }"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC3: spot order rejected w/ reason
+                input: r#"{
+                    "e":"executionReport","E":1499405658658,"s":"BTCUSDT","c":"",
+                    "S":"BUY","o":"LIMIT","f":"GTC","q":"1.0","p":"100.0","x":"REJECTED",
+                    "X":"REJECTED","i":4293155,"l":"0","z":"0","L":"0","T":1499405658657,
+                    "r":"INSUFFICIENT_BALANCES"
+                }"#,
+                expected: vec![(
+                    Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                    OrderUpdate {
+                        order_id: "4293155".to_string(),
+                        client_order_id: None,
+                        status: OrderStatus::Rejected,
+                        side: Side::Buy,
+                        price: Some(num!(100.0)),
+                        amount: num!(1.0),
+                        filled_amount: num!(0.0),
+                        reason: Some("INSUFFICIENT_BALANCES".to_string()),
+                    },
+                )],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let event = serde_json::from_str::<BinanceUserData>(test.input).unwrap();
+            let actual = MarketIter::<OrderUpdate>::from((&spot_instruments(), Utc::now(), event))
+                .0
+                .into_iter()
+                .map(|event| {
+                    let event = event.unwrap();
+                    (event.instrument, event.kind)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        // Futures expired market order w/ zero price
+        let event = serde_json::from_str::<BinanceUserData>(
+            r#"{
+                "e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,
+                "o":{
+                    "s":"BTCUSDT","c":"TEST","S":"SELL","o":"MARKET","f":"GTC","q":"0.001",
+                    "p":"0","ap":"0","x":"EXPIRED","X":"EXPIRED","i":8886774,"l":"0","z":"0",
+                    "T":1568879465650
+                }
+            }"#,
+        )
+        .unwrap();
+        let actual = MarketIter::<OrderUpdate>::from((&futures, Utc::now(), event))
+            .0
+            .into_iter()
+            .map(|event| event.unwrap().kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![OrderUpdate {
+                order_id: "8886774".to_string(),
+                client_order_id: Some("TEST".to_string()),
+                status: OrderStatus::Cancelled,
+                side: Side::Sell,
+                price: None,
+                amount: num!(0.001),
+                filled_amount: num!(0.0),
+                reason: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_binance_user_data_into_balances() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<(Instrument, Balance)>,
+        }
+
+        let btc_usdt = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let eth_btc = Instrument::from(("eth", "btc", InstrumentKind::Spot));
+        let balance = |asset: &str, total: Num, available: Num| Balance {
+            asset: Symbol::from(asset),
+            total,
+            available,
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot balance with free & locked amounts, BNB is not subscribed to
+                input: r#"{
+                    "e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,
+                    "B":[
+                        {"a":"USDT","f":"100.5","l":"50.0"},
+                        {"a":"BNB","f":"1.0","l":"0.0"}
+                    ]
+                }"#,
+                expected: vec![(btc_usdt.clone(), balance("usdt", num!(150.5), num!(100.5)))],
+            },
+            TestCase {
+                // TC1: futures wallet balance, cross wallet balance is the available balance
+                input: r#"{
+                    "e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,
+                    "a":{"m":"ORDER","B":[{"a":"USDT","wb":"122624.5","cw":"100.25","bc":"50.1"}],"P":[]}
+                }"#,
+                expected: vec![(
+                    btc_usdt.clone(),
+                    balance("usdt", num!(122624.5), num!(100.25)),
+                )],
+            },
+            TestCase {
+                // TC2: spot order update is not a Balance
+                input: r#"{
+                    "e":"executionReport","E":1499405658658,"s":"ETHBTC","c":"",
+                    "S":"BUY","o":"LIMIT","f":"GTC","q":"1.0","p":"0.1","x":"NEW",
+                    "X":"NEW","i":4293153,"l":"0","z":"0","L":"0","T":1499405658657
+                }"#,
+                expected: vec![],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let event = serde_json::from_str::<BinanceUserData>(test.input).unwrap();
+            let actual = MarketIter::<Balance>::from((&spot_instruments(), Utc::now(), event))
+                .0
+                .into_iter()
+                .map(|event| {
+                    let event = event.unwrap();
+                    (event.instrument, event.kind)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        // BTC balance is yielded for every subscribed Instrument it's an asset of
+        let event = serde_json::from_str::<BinanceUserData>(
+            r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"BTC","f":"1","l":"1"}]}"#,
+        )
+        .unwrap();
+        let mut actual = MarketIter::<Balance>::from((&spot_instruments(), Utc::now(), event))
+            .0
+            .into_iter()
+            .map(|event| event.unwrap().instrument)
+            .collect::<Vec<_>>();
+        actual.sort_by_key(|instrument| instrument.to_string());
+        let mut expected = vec![btc_usdt, eth_btc];
+        expected.sort_by_key(|instrument| instrument.to_string());
+        assert_eq!(actual, expected);
+    }
+
+    /// Serve the provided HTTP responses to consecutive REST requests in order, recording the
+    /// request line & API key header of each request.
+    async fn mock_rest(responses: Vec<(u16, &'static str)>) -> (Url, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        tokio::spawn({
+            let requests = Arc::clone(&requests);
+            async move {
+                for (status, body) in responses {
+                    let (mut tcp, _) = listener.accept().await.unwrap();
+                    let mut request = vec![0u8; 4096];
+                    let read = tcp.read(&mut request).await.unwrap();
+                    let request = String::from_utf8_lossy(&request[..read]).to_lowercase();
+                    let line = request.lines().next().unwrap_or_default().to_owned();
+                    let api_key = request
+                        .lines()
+                        .find_map(|header| header.strip_prefix("x-mbx-apikey: "))
+                        .unwrap_or_default()
+                        .to_owned();
+                    requests.lock().unwrap().push(format!("{line} {api_key}"));
+
+                    let response = format!(
+                        "HTTP/1.1 {status} OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    tcp.write_all(response.as_bytes()).await.unwrap();
+                }
+            }
+        });
+
+        (url, requests)
+    }
+
+    /// Accept consecutive user data stream WebSocket connections, recording the request path of
+    /// each & sending it the provided messages before holding the connection open until the
+    /// client drops it.
+    async fn mock_user_data(connections: Vec<Vec<&'static str>>) -> (Url, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}/ws", listener.local_addr().unwrap())).unwrap();
+        let paths = Arc::new(Mutex::new(Vec::new()));
+
+        tokio::spawn({
+            let paths = Arc::clone(&paths);
+            async move {
+                for messages in connections {
+                    let (tcp, _) = listener.accept().await.unwrap();
+                    let paths = Arc::clone(&paths);
+                    tokio::spawn(async move {
+                        use futures::SinkExt;
+                        use tokio_tungstenite::tungstenite::{
+                            handshake::server::{Request, Response},
+                            Message,
+                        };
+
+                        let mut websocket = tokio_tungstenite::accept_hdr_async(
+                            tcp,
+                            |request: &Request, response: Response| {
+                                paths.lock().unwrap().push(request.uri().path().to_owned());
+                                Ok(response)
+                            },
+                        )
+                        .await
+                        .unwrap();
+
+                        for message in messages {
+                            websocket
+                                .send(Message::Text(message.to_owned()))
+                                .await
+                                .unwrap();
+                        }
+                        while let Some(Ok(_)) = websocket.next().await {}
+                    });
+                }
+            }
+        });
+
+        (url, paths)
+    }
+
+    #[tokio::test]
+    async fn test_binance_user_data_stream_listen_key_lifecycle() {
+        let (rest, requests) = mock_rest(vec![
+            (200, r#"{"listenKey":"pqia91ma19a5s61cv6a8"}"#),
+            (200, "{}"),
+            (
+                400,
+                r#"{"code":-1125,"msg":"This listenKey does not exist."}"#,
+            ),
+        ])
+        .await;
+        let (websocket, paths) = mock_user_data(vec![vec![
+            r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#,
+            r#"{"e":"executionReport","E":1499405658658,"s":"BTCUSDT","c":"barter1","S":"BUY","o":"LIMIT","f":"GTC","q":"1.0","p":"30000","x":"NEW","X":"NEW","i":1,"l":"0","z":"0","L":"0","T":1499405658657}"#,
+        ]])
+        .await;
+
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: Some(websocket),
+                rest: Some(rest),
+            },
+            credentials: Some(Credentials::new("api_key", "secret")),
+            ..StreamConfig::default()
+        };
+        let subscriptions = [Subscription::from((
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            OrderUpdates,
+        ))];
+
+        let mut stream =
+            BinanceUserDataStream::<BinanceServerSpot, OrderUpdates>::init_with_schedule(
+                &subscriptions,
+                &config,
+                Duration::from_millis(100),
+                BINANCE_LISTEN_KEY_ROTATION_INTERVAL,
+            )
+            .await
+            .unwrap();
+
+        // Un-normalised "balanceUpdate" is skipped, second keepalive fails
+        let order = stream.next().await.unwrap().unwrap();
+        let error = stream.next().await.unwrap().unwrap_err();
+        let end = stream.next().await;
+
+        assert_eq!(order.kind.order_id, "1");
+        assert_eq!(order.kind.client_order_id.as_deref(), Some("barter1"));
+        assert_eq!(order.kind.status, OrderStatus::Open);
+        assert!(
+            matches!(
+                error,
+                DataError::Socket(SocketError::HttpResponse(status, _)) if status.as_u16() == 400
+            ),
+            "unexpected error: {error}"
+        );
+
+        // Stream ends so the consumer loop re-connects with a new listenKey
+        assert!(end.is_none());
+        assert_eq!(*paths.lock().unwrap(), vec!["/ws/pqia91ma19a5s61cv6a8"]);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "post /userdatastream http/1.1 api_key",
+                "put /userdatastream?listenkey=pqia91ma19a5s61cv6a8 http/1.1 api_key",
+                "put /userdatastream?listenkey=pqia91ma19a5s61cv6a8 http/1.1 api_key",
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_binance_user_data_stream_listen_key_rotation() {
+        let (rest, requests) = mock_rest(vec![
+            (200, r#"{"listenKey":"pqia91ma19a5s61cv6a8"}"#),
+            (200, "{}"),
+            (200, r#"{"listenKey":"kx8c1p2n3m4b5v6z7q9w"}"#),
+        ])
+        .await;
+        let (websocket, paths) = mock_user_data(vec![
+            vec![
+                r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"barter1","S":"SELL","o":"LIMIT","f":"GTC","q":"0.001","p":"30000","x":"NEW","X":"NEW","i":1,"l":"0","z":"0","L":"0","T":1568879465650}}"#,
+            ],
+            vec![
+                r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465751,"T":1568879465750,"o":{"s":"BTCUSDT","c":"barter2","S":"SELL","o":"LIMIT","f":"GTC","q":"0.001","p":"30000","x":"NEW","X":"NEW","i":2,"l":"0","z":"0","L":"0","T":1568879465750}}"#,
+            ],
+        ])
+        .await;
+
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: Some(websocket),
+                rest: Some(rest),
+            },
+            credentials: Some(Credentials::new("api_key", "secret")),
+            ..StreamConfig::default()
+        };
+        let subscriptions = [Subscription::from((
+            BinanceFuturesUsd::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::FuturePerpetual,
+            OrderUpdates,
+        ))];
+
+        let start = Instant::now();
+        let mut stream = BinanceUserDataStream::<BinanceServerFuturesUsd, OrderUpdates>::init_with(
+            &subscriptions,
+            &config,
+        )
+        .await
+        .unwrap();
+
+        // First order is yielded from the connection of the initial listenKey, which is kept alive
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.kind.client_order_id.as_deref(), Some("barter1"));
+
+        // Rotation creates a new listenKey before the initial one expires, connecting to it before
+        // dropping the old connection, so the second order is yielded from the new connection
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(second.kind.client_order_id.as_deref(), Some("barter2"));
+        assert!(start.elapsed() >= BINANCE_LISTEN_KEY_ROTATION_INTERVAL);
+
+        assert_eq!(
+            *paths.lock().unwrap(),
+            vec!["/ws/pqia91ma19a5s61cv6a8", "/ws/kx8c1p2n3m4b5v6z7q9w"]
+        );
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "post /listenkey http/1.1 api_key",
+                "put /listenkey?listenkey=pqia91ma19a5s61cv6a8 http/1.1 api_key",
+                "post /listenkey http/1.1 api_key",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binance_user_data_stream_init_errors() {
+        let subscriptions = [Subscription::from((
+            BinanceFuturesUsd::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::FuturePerpetual,
+            Balances,
+        ))];
+
+        // No Credentials
+        let error =
+            BinanceUserDataStream::<BinanceServerFuturesUsd, Balances>::init(&subscriptions)
+                .await
+                .unwrap_err();
+        assert!(
+            matches!(
+                error,
+                DataError::Auth {
+                    exchange: ExchangeId::BinanceFuturesUsd,
+                    ..
+                }
+            ),
+            "unexpected error: {error}"
+        );
+
+        // API key rejected when creating the listenKey
+        let (rest, requests) =
+            mock_rest(vec![(401, r#"{"code":-2015,"msg":"Invalid API-key."}"#)]).await;
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: None,
+                rest: Some(rest),
+            },
+            credentials: Some(Credentials::new("invalid", "secret")),
+            ..StreamConfig::default()
+        };
+        let error = BinanceUserDataStream::<BinanceServerFuturesUsd, Balances>::init_with(
+            &subscriptions,
+            &config,
+        )
+        .await
+        .unwrap_err();
+
+        match error {
+            DataError::Auth { exchange, reason } => {
+                assert_eq!(exchange, ExchangeId::BinanceFuturesUsd);
+                assert!(
+                    reason.contains("Invalid API-key."),
+                    "unexpected reason: {reason}"
+                );
+            }
+            error => panic!("expected DataError::Auth, found: {error}"),
+        }
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["post /listenkey http/1.1 invalid"]
+        );
+    }
+}
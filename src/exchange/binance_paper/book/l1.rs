@@ -1,12 +1,14 @@
 use crate::{
     event::{MarketEvent, MarketIter},
     exchange::{binance::channel::BinanceChannel, subscription::ExchangeSub, ExchangeId},
+    num::Num,
     subscription::book::{Level, OrderBookL1},
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, SubscriptionId};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// [`Binance`](super::super::Binance) real-time OrderBook Level1 (top of book) message.
 ///
@@ -41,13 +43,13 @@ pub struct BinanceOrderBookL1 {
     #[serde(alias = "s", deserialize_with = "de_ob_l1_subscription_id")]
     pub subscription_id: SubscriptionId,
     #[serde(alias = "b", deserialize_with = "barter_integration::de::de_str")]
-    pub best_bid_price: f64,
+    pub best_bid_price: Num,
     #[serde(alias = "B", deserialize_with = "barter_integration::de::de_str")]
-    pub best_bid_amount: f64,
+    pub best_bid_amount: Num,
     #[serde(alias = "a", deserialize_with = "barter_integration::de::de_str")]
-    pub best_ask_price: f64,
+    pub best_ask_price: Num,
     #[serde(alias = "A", deserialize_with = "barter_integration::de::de_str")]
-    pub best_ask_amount: f64,
+    pub best_ask_amount: Num,
 }
 
 impl Identifier<Option<SubscriptionId>> for BinanceOrderBookL1 {
@@ -56,21 +58,28 @@ impl Identifier<Option<SubscriptionId>> for BinanceOrderBookL1 {
     }
 }
 
-impl From<(ExchangeId, Instrument, BinanceOrderBookL1)> for MarketIter<OrderBookL1> {
-    fn from((exchange_id, instrument, book): (ExchangeId, Instrument, BinanceOrderBookL1)) -> Self {
-        // Create timestamp to be used for all required time fields that are not present
-        let time_now = Utc::now();
-
-        Self(vec![Ok(MarketEvent {
-            exchange_time: time_now,
-            received_time: time_now,
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceOrderBookL1)> for MarketIter<OrderBookL1> {
+    fn from(
+        (exchange_id, instrument, received_time, book): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceOrderBookL1,
+        ),
+    ) -> Self {
+        // Use the received time for all required time fields that are not present
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: received_time,
+            raw_exchange_time: None,
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: OrderBookL1 {
-                last_update_time: time_now,
+                last_update_time: received_time,
                 best_bid: Level::new(book.best_bid_price, book.best_bid_amount),
                 best_ask: Level::new(book.best_ask_price, book.best_ask_amount),
             },
+            meta: (),
         })])
     }
 }
@@ -89,6 +98,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -115,10 +125,10 @@ mod tests {
                 "#,
                     expected: BinanceOrderBookL1 {
                         subscription_id: SubscriptionId::from("@bookTicker|ETHUSDT"),
-                        best_bid_price: 1215.27000000,
-                        best_bid_amount: 32.49110000,
-                        best_ask_price: 1215.28000000,
-                        best_ask_amount: 13.93900000,
+                        best_bid_price: num!(1215.27000000),
+                        best_bid_amount: num!(32.49110000),
+                        best_ask_price: num!(1215.28000000),
+                        best_ask_amount: num!(13.93900000),
                     },
                 },
                 TestCase {
@@ -137,10 +147,10 @@ mod tests {
                     }"#,
                     expected: BinanceOrderBookL1 {
                         subscription_id: SubscriptionId::from("@bookTicker|BTCUSDT"),
-                        best_bid_price: 16858.90,
-                        best_bid_amount: 13.692,
-                        best_ask_price: 16859.00,
-                        best_ask_amount: 30.219,
+                        best_bid_price: num!(16858.90),
+                        best_bid_amount: num!(13.692),
+                        best_ask_price: num!(16859.00),
+                        best_ask_amount: num!(30.219),
                     },
                 },
             ];
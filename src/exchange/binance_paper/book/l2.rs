@@ -79,6 +79,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -113,12 +114,12 @@ mod tests {
                     expected: BinanceOrderBookL2Snapshot {
                         last_update_id: 1027024,
                         bids: vec![BinanceLevel {
-                            price: 4.0,
-                            amount: 431.0,
+                            price: num!(4.0),
+                            amount: num!(431.0),
                         }],
                         asks: vec![BinanceLevel {
-                            price: 4.00000200,
-                            amount: 12.0,
+                            price: num!(4.00000200),
+                            amount: num!(12.0),
                         }],
                     },
                 },
@@ -146,12 +147,12 @@ mod tests {
                     expected: BinanceOrderBookL2Snapshot {
                         last_update_id: 1027024,
                         bids: vec![BinanceLevel {
-                            price: 4.0,
-                            amount: 431.0,
+                            price: num!(4.0),
+                            amount: num!(431.0),
                         }],
                         asks: vec![BinanceLevel {
-                            price: 4.00000200,
-                            amount: 12.0,
+                            price: num!(4.00000200),
+                            amount: num!(12.0),
                         }],
                     },
                 },
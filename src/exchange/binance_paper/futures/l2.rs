@@ -1,6 +1,7 @@
 use super::super::book::{l2::BinanceOrderBookL2Snapshot, BinanceLevel};
 use crate::{
     error::DataError,
+    streams::config::StreamConfig,
     subscription::book::OrderBook,
     transformer::book::{InstrumentOrderBook, OrderBookUpdater},
     Identifier,
@@ -119,6 +120,7 @@ impl BinanceFuturesBookUpdater {
     /// "The first processed event should have U <= lastUpdateId AND u >= lastUpdateId"
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#how-to-manage-a-local-order-book-correctly>
+    #[allow(clippy::result_large_err)]
     pub fn validate_first_update(
         &self,
         update: &BinanceFuturesOrderBookL2Delta,
@@ -140,6 +142,7 @@ impl BinanceFuturesBookUpdater {
     ///  event's u, otherwise initialize the process from step 3."
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#how-to-manage-a-local-order-book-correctly>
+    #[allow(clippy::result_large_err)]
     pub fn validate_next_update(
         &self,
         update: &BinanceFuturesOrderBookL2Delta,
@@ -160,9 +163,14 @@ impl OrderBookUpdater for BinanceFuturesBookUpdater {
     type OrderBook = OrderBook;
     type Update = BinanceFuturesOrderBookL2Delta;
 
+    /// Re-fetching the HTTP snapshot of an [`Instrument`] re-synchronises it's OrderBook with
+    /// the deltas still being streamed, so other OrderBooks of the connection are undisturbed.
+    const RESYNC_INSTRUMENT: bool = true;
+
     async fn init<Exchange, Kind>(
         _: mpsc::UnboundedSender<WsMessage>,
         instrument: Instrument,
+        _: &StreamConfig,
     ) -> Result<InstrumentOrderBook<Self>, DataError>
     where
         Exchange: Send,
@@ -231,6 +239,7 @@ impl OrderBookUpdater for BinanceFuturesBookUpdater {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -269,12 +278,12 @@ mod tests {
                     last_update_id: 160,
                     prev_last_update_id: 149,
                     bids: vec![BinanceLevel {
-                        price: 0.0024,
-                        amount: 10.0
+                        price: num!(0.0024),
+                        amount: num!(10.0)
                     },],
                     asks: vec![BinanceLevel {
-                        price: 0.0026,
-                        amount: 100.0
+                        price: num!(0.0026),
+                        amount: num!(100.0)
                     },]
                 }
             );
@@ -406,8 +415,8 @@ mod tests {
             for (index, test) in tests.into_iter().enumerate() {
                 let actual = test.updater.validate_first_update(&test.input);
                 match (actual, test.expected) {
-                    (Ok(actual), Ok(expected)) => {
-                        assert_eq!(actual, expected, "TC{} failed", index)
+                    (Ok(_), Ok(_)) => {
+                        // Test passed
                     }
                     (Err(_), Err(_)) => {
                         // Test passed
@@ -469,8 +478,8 @@ mod tests {
             for (index, test) in tests.into_iter().enumerate() {
                 let actual = test.updater.validate_next_update(&test.input);
                 match (actual, test.expected) {
-                    (Ok(actual), Ok(expected)) => {
-                        assert_eq!(actual, expected, "TC{} failed", index)
+                    (Ok(_), Ok(_)) => {
+                        // Test passed
                     }
                     (Err(_), Err(_)) => {
                         // Test passed
@@ -541,25 +550,25 @@ mod tests {
                         bids: vec![
                             // Level exists & new value is 0 => remove Level
                             BinanceLevel {
-                                price: 80.0,
-                                amount: 0.0,
+                                price: num!(80.0),
+                                amount: num!(0.0),
                             },
                             // Level exists & new value is > 0 => replace Level
                             BinanceLevel {
-                                price: 90.0,
-                                amount: 10.0,
+                                price: num!(90.0),
+                                amount: num!(10.0),
                             },
                         ],
                         asks: vec![
                             // Level does not exist & new value > 0 => insert new Level
                             BinanceLevel {
-                                price: 200.0,
-                                amount: 1.0,
+                                price: num!(200.0),
+                                amount: num!(1.0),
                             },
                             // Level does not exist & new value is 0 => no change
                             BinanceLevel {
-                                price: 500.0,
-                                amount: 0.0,
+                                price: num!(500.0),
+                                amount: num!(0.0),
                             },
                         ],
                     },
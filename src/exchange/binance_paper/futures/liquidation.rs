@@ -1,13 +1,15 @@
 use super::super::BinanceChannel;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::ExchangeId,
+    num::Num,
     subscription::liquidation::Liquidation,
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, Side, SubscriptionId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// [`BinanceFuturesUsd`](super::BinanceFuturesUsd) Liquidation order message.
 ///
@@ -65,12 +67,12 @@ pub struct BinanceLiquidationOrder {
     #[serde(alias = "S")]
     pub side: Side,
     #[serde(alias = "p", deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     #[serde(alias = "q", deserialize_with = "barter_integration::de::de_str")]
-    pub quantity: f64,
+    pub quantity: Num,
     #[serde(
         alias = "T",
-        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
 }
@@ -81,13 +83,22 @@ impl Identifier<Option<SubscriptionId>> for BinanceLiquidation {
     }
 }
 
-impl From<(ExchangeId, Instrument, BinanceLiquidation)> for MarketIter<Liquidation> {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceLiquidation)> for MarketIter<Liquidation> {
     fn from(
-        (exchange_id, instrument, liquidation): (ExchangeId, Instrument, BinanceLiquidation),
+        (exchange_id, instrument, received_time, liquidation): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceLiquidation,
+        ),
     ) -> Self {
-        Self(vec![Ok(MarketEvent {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: liquidation.order.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                liquidation.order.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: Liquidation {
@@ -96,6 +107,7 @@ impl From<(ExchangeId, Instrument, BinanceLiquidation)> for MarketIter<Liquidati
                 quantity: liquidation.order.quantity,
                 time: liquidation.order.time,
             },
+            meta: (),
         })])
     }
 }
@@ -116,6 +128,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -150,8 +163,8 @@ mod tests {
                     order: BinanceLiquidationOrder {
                         subscription_id: SubscriptionId::from("@forceOrder|BTCUSDT"),
                         side: Side::Sell,
-                        price: 18917.15,
-                        quantity: 0.009,
+                        price: num!(18917.15),
+                        quantity: num!(0.009),
                         time: datetime_utc_from_epoch_duration(Duration::from_millis(
                             1665523974217,
                         )),
@@ -1,5 +1,9 @@
 use super::Binance;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{alias::exchange_assets, ExchangeServer},
+    subscription::Subscription,
+    Identifier,
+};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a [`Binance`](super::Binance)
@@ -10,12 +14,16 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct BinanceMarket(pub String);
 
-impl<Server, Kind> Identifier<BinanceMarket> for Subscription<Binance<Server>, Kind> {
+impl<Server, Kind> Identifier<BinanceMarket> for Subscription<Binance<Server>, Kind>
+where
+    Server: ExchangeServer,
+{
     fn id(&self) -> BinanceMarket {
         // Notes:
         // - Must be lowercase when subscribing (transformed to lowercase by Binance fn requests).
         // - Must be uppercase since Binance sends message with uppercase MARKET (eg/ BTCUSDT).
-        BinanceMarket(format!("{}{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Server::ID, &self.instrument);
+        BinanceMarket(format!("{base}{quote}").to_uppercase())
     }
 }
 
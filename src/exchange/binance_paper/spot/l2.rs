@@ -1,6 +1,7 @@
 use super::super::book::{l2::BinanceOrderBookL2Snapshot, BinanceLevel};
 use crate::{
     error::DataError,
+    streams::config::StreamConfig,
     subscription::book::OrderBook,
     transformer::book::{InstrumentOrderBook, OrderBookUpdater},
     Identifier,
@@ -115,6 +116,7 @@ impl BinanceSpotBookUpdater {
     /// "The first processed event should have U <= lastUpdateId+1 AND u >= lastUpdateId+1"
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#how-to-manage-a-local-order-book-correctly>
+    #[allow(clippy::result_large_err)]
     pub fn validate_first_update(
         &self,
         update: &BinanceSpotOrderBookL2Delta,
@@ -135,6 +137,7 @@ impl BinanceSpotBookUpdater {
     ///  previous event's u+1, otherwise initialize the process from step 3."
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#how-to-manage-a-local-order-book-correctly>
+    #[allow(clippy::result_large_err)]
     pub fn validate_next_update(
         &self,
         update: &BinanceSpotOrderBookL2Delta,
@@ -156,9 +159,14 @@ impl OrderBookUpdater for BinanceSpotBookUpdater {
     type OrderBook = OrderBook;
     type Update = BinanceSpotOrderBookL2Delta;
 
+    /// Re-fetching the HTTP snapshot of an [`Instrument`] re-synchronises it's OrderBook with
+    /// the deltas still being streamed, so other OrderBooks of the connection are undisturbed.
+    const RESYNC_INSTRUMENT: bool = true;
+
     async fn init<Exchange, Kind>(
         _: mpsc::UnboundedSender<WsMessage>,
         instrument: Instrument,
+        _: &StreamConfig,
     ) -> Result<InstrumentOrderBook<Self>, DataError>
     where
         Exchange: Send,
@@ -228,6 +236,7 @@ impl OrderBookUpdater for BinanceSpotBookUpdater {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -257,12 +266,12 @@ mod tests {
                     last_update_id: 22611425151,
                     bids: vec![
                         BinanceLevel {
-                            price: 1209.67000000,
-                            amount: 85.48210000
+                            price: num!(1209.67000000),
+                            amount: num!(85.48210000)
                         },
                         BinanceLevel {
-                            price: 1209.66000000,
-                            amount: 20.68790000
+                            price: num!(1209.66000000),
+                            amount: num!(20.68790000)
                         },
                     ],
                     asks: vec![]
@@ -397,8 +406,8 @@ mod tests {
             for (index, test) in tests.into_iter().enumerate() {
                 let actual = test.updater.validate_first_update(&test.input);
                 match (actual, test.expected) {
-                    (Ok(actual), Ok(expected)) => {
-                        assert_eq!(actual, expected, "TC{} failed", index)
+                    (Ok(_), Ok(_)) => {
+                        // Test passed
                     }
                     (Err(_), Err(_)) => {
                         // Test passed
@@ -460,8 +469,8 @@ mod tests {
             for (index, test) in tests.into_iter().enumerate() {
                 let actual = test.updater.validate_next_update(&test.input);
                 match (actual, test.expected) {
-                    (Ok(actual), Ok(expected)) => {
-                        assert_eq!(actual, expected, "TC{} failed", index)
+                    (Ok(_), Ok(_)) => {
+                        // Test passed
                     }
                     (Err(_), Err(_)) => {
                         // Test passed
@@ -532,25 +541,25 @@ mod tests {
                         bids: vec![
                             // Level exists & new value is 0 => remove Level
                             BinanceLevel {
-                                price: 80.0,
-                                amount: 0.0,
+                                price: num!(80.0),
+                                amount: num!(0.0),
                             },
                             // Level exists & new value is > 0 => replace Level
                             BinanceLevel {
-                                price: 90.0,
-                                amount: 10.0,
+                                price: num!(90.0),
+                                amount: num!(10.0),
                             },
                         ],
                         asks: vec![
                             // Level does not exist & new value > 0 => insert new Level
                             BinanceLevel {
-                                price: 200.0,
-                                amount: 1.0,
+                                price: num!(200.0),
+                                amount: num!(1.0),
                             },
                             // Level does not exist & new value is 0 => no change
                             BinanceLevel {
-                                price: 500.0,
-                                amount: 0.0,
+                                price: num!(500.0),
+                                amount: num!(0.0),
                             },
                         ],
                     },
@@ -1,13 +1,15 @@
 use super::BinanceChannel;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, Side, SubscriptionId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Binance real-time trade message.
 ///
@@ -56,15 +58,15 @@ pub struct BinanceTrade {
     pub subscription_id: SubscriptionId,
     #[serde(
         alias = "T",
-        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
     #[serde(alias = "t")]
     pub id: u64,
     #[serde(alias = "p", deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     #[serde(alias = "q", deserialize_with = "barter_integration::de::de_str")]
-    pub amount: f64,
+    pub amount: Num,
     #[serde(alias = "m", deserialize_with = "de_side_from_buyer_is_maker")]
     pub side: Side,
 }
@@ -75,11 +77,22 @@ impl Identifier<Option<SubscriptionId>> for BinanceTrade {
     }
 }
 
-impl From<(ExchangeId, Instrument, BinanceTrade)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, BinanceTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BinanceTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BinanceTrade,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: trade.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: PublicTrade {
@@ -88,6 +101,7 @@ impl From<(ExchangeId, Instrument, BinanceTrade)> for MarketIter<PublicTrade> {
                 amount: trade.amount,
                 side: trade.side,
             },
+            meta: (),
         })])
     }
 }
@@ -123,6 +137,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -154,8 +169,8 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
                     }),
                 },
@@ -185,8 +200,8 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Sell,
                     }),
                 },
@@ -204,8 +219,8 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
                     }),
                 },
@@ -221,8 +236,8 @@ mod tests {
                             1749354825200,
                         )),
                         id: 1000000000,
-                        price: 10000.19,
-                        amount: 0.239000,
+                        price: num!(10000.19),
+                        amount: num!(0.239000),
                         side: Side::Buy,
                     }),
                 },
@@ -0,0 +1,820 @@
+use super::{subscription::BitfinexPlatformEvent, Bitfinex};
+use crate::{
+    error::DataError,
+    exchange::Connector,
+    num::{self, Num},
+    streams::config::StreamConfig,
+    subscription::book::{Level, OrderBook, OrderBookSide},
+    transformer::book::{InstrumentOrderBook, OrderBookUpdater},
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    de::extract_next,
+    model::{Instrument, Side, SubscriptionId},
+    protocol::websocket::WsMessage,
+};
+use chrono::Utc;
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use tokio::sync::mpsc;
+
+/// [`Bitfinex`] `book` channel parameters, sent in the subscribe request of every
+/// [`OrderBooksL2`](crate::subscription::book::OrderBooksL2) subscription made via the
+/// [`Bitfinex`] instance holding them.
+///
+/// Defaults to the Bitfinex defaults, ie/ [`BitfinexBookPrecision::P0`],
+/// [`BitfinexBookFrequency::F0`] & [`BitfinexBookLength::L25`].
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub struct BitfinexBookConfig {
+    #[serde(default)]
+    pub prec: BitfinexBookPrecision,
+    #[serde(default)]
+    pub freq: BitfinexBookFrequency,
+    #[serde(default)]
+    pub len: BitfinexBookLength,
+}
+
+impl BitfinexBookConfig {
+    /// Construct a new [`Self`] using the provided `book` channel parameters.
+    pub fn new(
+        prec: BitfinexBookPrecision,
+        freq: BitfinexBookFrequency,
+        len: BitfinexBookLength,
+    ) -> Self {
+        Self { prec, freq, len }
+    }
+}
+
+/// [`Bitfinex`] `book` channel price aggregation level, from [`Self::P0`] (5 significant
+/// figures) to [`Self::P4`] (1 significant figure). Lower precisions aggregate more price points
+/// into each [`Level`], so fewer updates are sent.
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum BitfinexBookPrecision {
+    #[default]
+    P0,
+    P1,
+    P2,
+    P3,
+    P4,
+}
+
+impl AsRef<str> for BitfinexBookPrecision {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::P0 => "P0",
+            Self::P1 => "P1",
+            Self::P2 => "P2",
+            Self::P3 => "P3",
+            Self::P4 => "P4",
+        }
+    }
+}
+
+/// [`Bitfinex`] `book` channel update frequency, either real-time ([`Self::F0`]) or throttled to
+/// every 2 seconds ([`Self::F1`]).
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum BitfinexBookFrequency {
+    #[default]
+    F0,
+    F1,
+}
+
+impl AsRef<str> for BitfinexBookFrequency {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::F0 => "F0",
+            Self::F1 => "F1",
+        }
+    }
+}
+
+/// [`Bitfinex`] `book` channel number of price points on each side of the book, which bounds
+/// the number of [`Level`]s in the initial snapshot & those maintained by the updates that follow.
+///
+/// (De)serialised as the number of price points, rejecting any number Bitfinex doesn't accept.
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum BitfinexBookLength {
+    L1,
+    #[default]
+    L25,
+    L100,
+    L250,
+}
+
+impl BitfinexBookLength {
+    /// Number of price points on each side of the book.
+    pub fn levels(&self) -> u32 {
+        match self {
+            Self::L1 => 1,
+            Self::L25 => 25,
+            Self::L100 => 100,
+            Self::L250 => 250,
+        }
+    }
+}
+
+impl TryFrom<u32> for BitfinexBookLength {
+    type Error = BitfinexBookLengthError;
+
+    fn try_from(levels: u32) -> Result<Self, Self::Error> {
+        match levels {
+            1 => Ok(Self::L1),
+            25 => Ok(Self::L25),
+            100 => Ok(Self::L100),
+            250 => Ok(Self::L250),
+            _ => Err(BitfinexBookLengthError(levels)),
+        }
+    }
+}
+
+impl From<BitfinexBookLength> for u32 {
+    fn from(len: BitfinexBookLength) -> Self {
+        len.levels()
+    }
+}
+
+/// Number of `book` channel price points not accepted by [`Bitfinex`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BitfinexBookLengthError(pub u32);
+
+impl Display for BitfinexBookLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid Bitfinex book len {}, expected one of: 1, 25, 100, 250",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for BitfinexBookLengthError {}
+
+/// [`Bitfinex`] message received over the `book` channel of an
+/// [`OrderBooksL2`](crate::subscription::book::OrderBooksL2) subscription.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+/// #### Snapshot
+/// Format: \[CHANNEL_ID, \[\[PRICE, COUNT, AMOUNT\], ..\]\]
+/// ```json
+/// [17082,[[7254.7,3,3.3],[7254.6,2,1.9],[7254.8,1,-0.7]]]
+/// ```
+///
+/// #### Update
+/// Format: \[CHANNEL_ID, \[PRICE, COUNT, AMOUNT\]\]
+/// ```json
+/// [17082,[7254.7,0,1]]
+/// ```
+///
+/// #### Heartbeat
+/// ```json
+/// [17082,"hb"]
+/// ```
+///
+/// Platform events (eg/ `{"event": "info", ..}`) are deserialised as
+/// [`BitfinexBookMessage::Event`]s.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub enum BitfinexBookMessage {
+    Channel {
+        channel_id: u32,
+        payload: BitfinexBookPayload,
+    },
+    Event(BitfinexPlatformEvent),
+}
+
+/// [`Bitfinex`] `book` channel variants associated with an active
+/// [`Subscription`](crate::Subscription).
+///
+/// See [`BitfinexBookMessage`] for full raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub enum BitfinexBookPayload {
+    Heartbeat,
+    Snapshot(Vec<BitfinexLevel>),
+    Update(BitfinexLevel),
+}
+
+impl Identifier<Option<SubscriptionId>> for BitfinexBookMessage {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            BitfinexBookMessage::Channel {
+                channel_id,
+                payload: BitfinexBookPayload::Snapshot(_) | BitfinexBookPayload::Update(_),
+            } => Some(SubscriptionId::from(channel_id.to_string())),
+            BitfinexBookMessage::Channel { .. } | BitfinexBookMessage::Event(_) => None,
+        }
+    }
+}
+
+/// [`Bitfinex`] aggregated price point of the `book` channel.
+///
+/// Format: \[PRICE, COUNT, AMOUNT\], <br> where a positive `AMOUNT` is a bid & a negative
+/// `AMOUNT` is an ask, and a `COUNT` of 0 removes the price point (with an `AMOUNT` of 1 for
+/// bids & -1 for asks).
+///
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct BitfinexLevel {
+    pub price: Num,
+    pub count: u64,
+    pub amount: Num,
+}
+
+impl BitfinexLevel {
+    /// Apply [`Self`] to the [`OrderBookSide`] of the provided [`OrderBook`] it belongs to.
+    fn apply(self, book: &mut OrderBook) {
+        let side = match self.amount.is_sign_positive() {
+            true => &mut book.bids,
+            false => &mut book.asks,
+        };
+
+        match self.count {
+            0 => side.upsert_single(Level::new(self.price, num::zero())),
+            _ => side.upsert_single(Level::new(self.price, self.amount.abs())),
+        }
+    }
+}
+
+/// [`Bitfinex`] [`OrderBookUpdater`] maintaining an [`OrderBook`] from the snapshot sent when the
+/// `book` channel is subscribed to, followed by it's updates.
+///
+/// ### Notes
+/// - No HTTP snapshot is required, so [`OrderBookUpdater::init`] starts from an empty
+///   [`OrderBook`]. The WebSocket snapshot is received whilst the subscriptions are validated, so
+///   the [`BitfinexWebSocketSubValidator`](super::validator::BitfinexWebSocketSubValidator)
+///   always buffers `book` channel messages.
+/// - The number of [`Level`]s depends on the [`BitfinexBookLength`] subscribed to, since Bitfinex
+///   removes price points that fall outside of it.
+/// - Bitfinex `book` messages carry no timestamp, so the [`OrderBook::last_update_time`] is the
+///   time each update is applied.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub struct BitfinexBookUpdater {
+    pub snapshot_received: bool,
+}
+
+#[async_trait]
+impl OrderBookUpdater for BitfinexBookUpdater {
+    type OrderBook = OrderBook;
+    type Update = BitfinexBookMessage;
+
+    async fn init<Exchange, Kind>(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument: Instrument,
+        _: &StreamConfig,
+    ) -> Result<InstrumentOrderBook<Self>, DataError>
+    where
+        Exchange: Send,
+        Kind: Send,
+    {
+        Ok(InstrumentOrderBook {
+            instrument,
+            updater: Self::default(),
+            book: OrderBook {
+                last_update_time: Utc::now(),
+                bids: OrderBookSide::new(Side::Buy, Vec::<Level>::new()),
+                asks: OrderBookSide::new(Side::Sell, Vec::<Level>::new()),
+            },
+        })
+    }
+
+    fn update(
+        &mut self,
+        book: &mut Self::OrderBook,
+        update: Self::Update,
+    ) -> Result<Option<Self::OrderBook>, DataError> {
+        let payload = match update {
+            BitfinexBookMessage::Channel { payload, .. } => payload,
+            BitfinexBookMessage::Event(_) => return Ok(None),
+        };
+
+        match payload {
+            BitfinexBookPayload::Heartbeat => return Ok(None),
+            BitfinexBookPayload::Snapshot(levels) => {
+                book.bids = OrderBookSide::new(Side::Buy, Vec::<Level>::new());
+                book.asks = OrderBookSide::new(Side::Sell, Vec::<Level>::new());
+                levels.into_iter().for_each(|level| level.apply(book));
+                self.snapshot_received = true;
+            }
+            BitfinexBookPayload::Update(_) if !self.snapshot_received => {
+                // Re-subscribing is the only way to receive the snapshot again
+                return Err(DataError::Reconnect {
+                    exchange: Bitfinex::ID,
+                    reason: "book update received before it's snapshot".to_string(),
+                });
+            }
+            BitfinexBookPayload::Update(level) => level.apply(book),
+        }
+
+        book.last_update_time = Utc::now();
+        Ok(Some(book.snapshot()))
+    }
+}
+
+impl<'de> Deserialize<'de> for BitfinexBookMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct ShapeVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ShapeVisitor {
+            type Value = BitfinexBookMessage;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str(
+                    "BitfinexBookMessage array or event object from the Bitfinex WebSocket API",
+                )
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // Snapshot: [CHANNEL_ID, [[PRICE, COUNT, AMOUNT], ...]]
+                // Update: [CHANNEL_ID, [PRICE, COUNT, AMOUNT]]
+                // Heartbeat: [CHANNEL_ID, "hb"]
+                let channel_id: u32 = extract_next(&mut seq, "channel_id")?;
+
+                let payload = match seq
+                    .next_element::<BitfinexBookElement<'_>>()?
+                    .ok_or_else(|| serde::de::Error::missing_field("payload"))?
+                {
+                    BitfinexBookElement::Snapshot(levels) => BitfinexBookPayload::Snapshot(levels),
+                    BitfinexBookElement::Update(level) => BitfinexBookPayload::Update(level),
+                    BitfinexBookElement::Tag("hb") => BitfinexBookPayload::Heartbeat,
+                    BitfinexBookElement::Tag(other) => {
+                        return Err(serde::de::Error::unknown_variant(
+                            other,
+                            &["heartbeat (hb)", "snapshot", "update"],
+                        ))
+                    }
+                };
+
+                // Ignore any additional elements or SerDe will fail
+                //  '--> Bitfinex may add fields without warning
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+                Ok(BitfinexBookMessage::Channel {
+                    channel_id,
+                    payload,
+                })
+            }
+
+            fn visit_map<MapAccessor>(
+                self,
+                map: MapAccessor,
+            ) -> Result<Self::Value, MapAccessor::Error>
+            where
+                MapAccessor: serde::de::MapAccess<'de>,
+            {
+                BitfinexPlatformEvent::deserialize(serde::de::value::MapAccessDeserializer::new(
+                    map,
+                ))
+                .map(BitfinexBookMessage::Event)
+            }
+        }
+
+        // Discriminate between array channel messages & object platform events
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
+
+/// 2nd element of a [`BitfinexBookMessage`] array, either a message tag, an array snapshot of
+/// price points, or a single price point update.
+enum BitfinexBookElement<'a> {
+    Tag(&'a str),
+    Snapshot(Vec<BitfinexLevel>),
+    Update(BitfinexLevel),
+}
+
+impl<'de> Deserialize<'de> for BitfinexBookElement<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct ElementVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ElementVisitor {
+            type Value = BitfinexBookElement<'de>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("BitfinexBookMessage tag, snapshot or update")
+            }
+
+            fn visit_borrowed_str<E>(self, tag: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(BitfinexBookElement::Tag(tag))
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // Snapshot: [[PRICE, COUNT, AMOUNT], ...]
+                // Update: [PRICE, COUNT, AMOUNT]
+                match seq.next_element::<BitfinexBookEntry>()? {
+                    None => Ok(BitfinexBookElement::Snapshot(vec![])),
+                    Some(BitfinexBookEntry::Level(first)) => {
+                        // Size for the remaining levels, else for both sides of the default
+                        // BitfinexBookLength snapshot
+                        let capacity = seq.size_hint().map_or(
+                            2 * BitfinexBookLength::default().levels() as usize,
+                            |remaining| remaining + 1,
+                        );
+                        let mut levels = Vec::with_capacity(capacity);
+                        levels.push(first);
+                        while let Some(level) = seq.next_element()? {
+                            levels.push(level);
+                        }
+                        Ok(BitfinexBookElement::Snapshot(levels))
+                    }
+                    Some(BitfinexBookEntry::Price(price)) => {
+                        let count = extract_next(&mut seq, "count")?;
+                        let amount = extract_next(&mut seq, "amount")?;
+
+                        // Ignore any additional elements or SerDe will fail
+                        //  '--> Bitfinex may add fields without warning
+                        while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+                        Ok(BitfinexBookElement::Update(BitfinexLevel {
+                            price,
+                            count,
+                            amount,
+                        }))
+                    }
+                }
+            }
+        }
+
+        // Discriminate on the element shape rather than #[serde(untagged)], which buffers every
+        // price point into owned content before trying each variant
+        deserializer.deserialize_any(ElementVisitor)
+    }
+}
+
+/// 1st element of a [`BitfinexBookElement`] array, which is a [`BitfinexLevel`] for snapshots &
+/// the `PRICE` of the [`BitfinexLevel`] for updates.
+enum BitfinexBookEntry {
+    Level(BitfinexLevel),
+    Price(Num),
+}
+
+impl<'de> Deserialize<'de> for BitfinexBookEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct EntryVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for EntryVisitor {
+            type Value = BitfinexBookEntry;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("BitfinexLevel array or price number")
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                BitfinexLevel::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))
+                    .map(BitfinexBookEntry::Level)
+            }
+
+            fn visit_f64<E>(self, price: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                <Num as Deserialize>::deserialize(price.into_deserializer())
+                    .map(BitfinexBookEntry::Price)
+            }
+
+            fn visit_i64<E>(self, price: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                <Num as Deserialize>::deserialize(price.into_deserializer())
+                    .map(BitfinexBookEntry::Price)
+            }
+
+            fn visit_u64<E>(self, price: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                <Num as Deserialize>::deserialize(price.into_deserializer())
+                    .map(BitfinexBookEntry::Price)
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitfinexLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct SeqVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SeqVisitor {
+            type Value = BitfinexLevel;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("BitfinexLevel struct from the Bitfinex WebSocket API")
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // Level: [PRICE, COUNT, AMOUNT]
+                let price = extract_next(&mut seq, "price")?;
+                let count = extract_next(&mut seq, "count")?;
+                let amount = extract_next(&mut seq, "amount")?;
+
+                // Ignore any additional elements or SerDe will fail
+                //  '--> Bitfinex may add fields without warning
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+                Ok(BitfinexLevel {
+                    price,
+                    count,
+                    amount,
+                })
+            }
+        }
+
+        // Use Visitor implementation to deserialise the BitfinexLevel message
+        deserializer.deserialize_seq(SeqVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_de_bitfinex_book_message() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<BitfinexBookMessage>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: snapshot of bids & asks
+                input: r#"[17082,[[7254.7,3,3.3],[7254.8,1,-0.7]]]"#,
+                expected: Some(BitfinexBookMessage::Channel {
+                    channel_id: 17082,
+                    payload: BitfinexBookPayload::Snapshot(vec![
+                        BitfinexLevel {
+                            price: num!(7254.7),
+                            count: 3,
+                            amount: num!(3.3),
+                        },
+                        BitfinexLevel {
+                            price: num!(7254.8),
+                            count: 1,
+                            amount: num!(-0.7),
+                        },
+                    ]),
+                }),
+            },
+            TestCase {
+                // TC1: empty snapshot
+                input: r#"[17082,[]]"#,
+                expected: Some(BitfinexBookMessage::Channel {
+                    channel_id: 17082,
+                    payload: BitfinexBookPayload::Snapshot(vec![]),
+                }),
+            },
+            TestCase {
+                // TC2: update removing a bid
+                input: r#"[17082,[7254.7,0,1]]"#,
+                expected: Some(BitfinexBookMessage::Channel {
+                    channel_id: 17082,
+                    payload: BitfinexBookPayload::Update(BitfinexLevel {
+                        price: num!(7254.7),
+                        count: 0,
+                        amount: num!(1),
+                    }),
+                }),
+            },
+            TestCase {
+                // TC3: heartbeat
+                input: r#"[17082,"hb"]"#,
+                expected: Some(BitfinexBookMessage::Channel {
+                    channel_id: 17082,
+                    payload: BitfinexBookPayload::Heartbeat,
+                }),
+            },
+            TestCase {
+                // TC4: platform event
+                input: r#"{"event":"conf","status":"OK","flags":65536}"#,
+                expected: Some(BitfinexBookMessage::Event(BitfinexPlatformEvent::Unknown)),
+            },
+            TestCase {
+                // TC5: unknown message tag
+                input: r#"[17082,"cs",-1234567]"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<BitfinexBookMessage>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_de_bitfinex_book_config() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<BitfinexBookConfig>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: every parameter provided
+                input: r#"{"prec":"P2","freq":"F1","len":100}"#,
+                expected: Some(BitfinexBookConfig::new(
+                    BitfinexBookPrecision::P2,
+                    BitfinexBookFrequency::F1,
+                    BitfinexBookLength::L100,
+                )),
+            },
+            TestCase {
+                // TC1: missing parameters use the Bitfinex defaults
+                input: r#"{"len":1}"#,
+                expected: Some(BitfinexBookConfig {
+                    len: BitfinexBookLength::L1,
+                    ..BitfinexBookConfig::default()
+                }),
+            },
+            TestCase {
+                // TC2: precision outside of P0-P4 is rejected
+                input: r#"{"prec":"P5"}"#,
+                expected: None,
+            },
+            TestCase {
+                // TC3: raw books frequency is rejected
+                input: r#"{"freq":"F2"}"#,
+                expected: None,
+            },
+            TestCase {
+                // TC4: length not accepted by Bitfinex is rejected
+                input: r#"{"len":50}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<BitfinexBookConfig>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_bitfinex_book_length_try_from() {
+        assert_eq!(
+            BitfinexBookLength::try_from(250),
+            Ok(BitfinexBookLength::L250)
+        );
+        assert_eq!(
+            BitfinexBookLength::try_from(10).unwrap_err().to_string(),
+            "invalid Bitfinex book len 10, expected one of: 1, 25, 100, 250"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bitfinex_book_updater() {
+        // Bid & ask Levels of an OrderBook snapshot
+        type Levels = (Vec<Level>, Vec<Level>);
+
+        struct TestCase {
+            input: &'static str,
+            expected: Result<Option<Levels>, ()>,
+        }
+
+        let (ws_sink_tx, _) = mpsc::unbounded_channel();
+        let InstrumentOrderBook {
+            mut updater,
+            mut book,
+            ..
+        } = BitfinexBookUpdater::init::<Bitfinex, ()>(
+            ws_sink_tx,
+            Instrument::from((
+                "btc",
+                "usd",
+                barter_integration::model::InstrumentKind::Spot,
+            )),
+            &StreamConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let tests = vec![
+            TestCase {
+                // TC0: update before the snapshot re-connects
+                input: r#"[17082,[7254.7,1,1.0]]"#,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC1: snapshot of len 1 initialises the single Level of each side
+                input: r#"[17082,[[7254.7,3,3.3],[7254.8,1,-0.7]]]"#,
+                expected: Ok(Some((
+                    vec![Level::new(num!(7254.7), num!(3.3))],
+                    vec![Level::new(num!(7254.8), num!(0.7))],
+                ))),
+            },
+            TestCase {
+                // TC2: heartbeat yields no OrderBook
+                input: r#"[17082,"hb"]"#,
+                expected: Ok(None),
+            },
+            TestCase {
+                // TC3: update inserting a deeper bid & replacing the ask
+                input: r#"[17082,[7254.6,2,1.9]]"#,
+                expected: Ok(Some((
+                    vec![
+                        Level::new(num!(7254.7), num!(3.3)),
+                        Level::new(num!(7254.6), num!(1.9)),
+                    ],
+                    vec![Level::new(num!(7254.8), num!(0.7))],
+                ))),
+            },
+            TestCase {
+                // TC4: count of 0 with an amount of 1 removes the bid
+                input: r#"[17082,[7254.7,0,1]]"#,
+                expected: Ok(Some((
+                    vec![Level::new(num!(7254.6), num!(1.9))],
+                    vec![Level::new(num!(7254.8), num!(0.7))],
+                ))),
+            },
+            TestCase {
+                // TC5: count of 0 with an amount of -1 removes the ask
+                input: r#"[17082,[7254.8,0,-1]]"#,
+                expected: Ok(Some((vec![Level::new(num!(7254.6), num!(1.9))], vec![]))),
+            },
+            TestCase {
+                // TC6: a new snapshot of a different len replaces every Level
+                input: r#"[17082,[[7250.0,1,1.0],[7249.0,1,2.0],[7251.0,2,-3.0],[7252.0,1,-4.0]]]"#,
+                expected: Ok(Some((
+                    vec![
+                        Level::new(num!(7250.0), num!(1.0)),
+                        Level::new(num!(7249.0), num!(2.0)),
+                    ],
+                    vec![
+                        Level::new(num!(7251.0), num!(3.0)),
+                        Level::new(num!(7252.0), num!(4.0)),
+                    ],
+                ))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let update = serde_json::from_str::<BitfinexBookMessage>(test.input).unwrap();
+            let actual = updater
+                .update(&mut book, update)
+                .map(|book| {
+                    book.map(|book| (book.bids.levels().to_vec(), book.asks.levels().to_vec()))
+                })
+                .map_err(|_| ());
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
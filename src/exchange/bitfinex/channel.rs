@@ -1,6 +1,6 @@
 use super::Bitfinex;
 use crate::{
-    subscription::{trade::PublicTrades, Subscription},
+    subscription::{book::OrderBooksL2, trade::PublicTrades, Subscription},
     Identifier,
 };
 use serde::Serialize;
@@ -17,6 +17,12 @@ impl BitfinexChannel {
     ///
     /// See docs: <https://docs.bitfinex.com/reference/ws-public-trades>
     pub const TRADES: Self = Self("trades");
+
+    /// [`Bitfinex`] real-time aggregated OrderBook Level2 channel, subscribed to with the
+    /// [`BitfinexBookConfig`](super::book::BitfinexBookConfig) of the [`Bitfinex`] instance.
+    ///
+    /// See docs: <https://docs.bitfinex.com/reference/ws-public-books>
+    pub const BOOK: Self = Self("book");
 }
 
 impl Identifier<BitfinexChannel> for Subscription<Bitfinex, PublicTrades> {
@@ -25,6 +31,12 @@ impl Identifier<BitfinexChannel> for Subscription<Bitfinex, PublicTrades> {
     }
 }
 
+impl Identifier<BitfinexChannel> for Subscription<Bitfinex, OrderBooksL2> {
+    fn id(&self) -> BitfinexChannel {
+        BitfinexChannel::BOOK
+    }
+}
+
 impl AsRef<str> for BitfinexChannel {
     fn as_ref(&self) -> &str {
         self.0
@@ -1,5 +1,14 @@
 use super::Bitfinex;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
@@ -11,11 +20,8 @@ pub struct BitfinexMarket(pub String);
 
 impl<Kind> Identifier<BitfinexMarket> for Subscription<Bitfinex, Kind> {
     fn id(&self) -> BitfinexMarket {
-        BitfinexMarket(format!(
-            "t{}{}",
-            self.instrument.base.to_string().to_uppercase(),
-            self.instrument.quote.to_string().to_uppercase()
-        ))
+        let (base, quote) = exchange_assets(Bitfinex::ID, &self.instrument);
+        BitfinexMarket(format!("t{}{}", base.to_uppercase(), quote.to_uppercase()))
     }
 }
 
@@ -24,3 +30,157 @@ impl AsRef<str> for BitfinexMarket {
         &self.0
     }
 }
+
+impl ParseMarket for Bitfinex {
+    /// Bitfinex trading pairs are prefixed with "t", and are formatted as "tBASEQUOTE" for
+    /// 3 character assets (eg/ "tBTCUSD"), else "tBASE:QUOTE" (eg/ "tMATIC:USD"). Perpetual
+    /// derivatives suffix both assets with "F0" (eg/ "tBTCF0:USTF0"). Funding currencies
+    /// prefixed with "f" (eg/ "fUSD") are not supported. Bitfinex specific asset codes are
+    /// normalised to their canonical equivalent (eg/ "UST" -> usdt).
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        let pair = match market.strip_prefix('t') {
+            Some(pair) => pair,
+            None if market.starts_with('f') => {
+                return Err(ParseMarketError::UnsupportedKind {
+                    exchange: Self::ID,
+                    market: market.to_owned(),
+                    kind: "funding currency",
+                })
+            }
+            None => return Err(ParseMarketError::format(Self::ID, market)),
+        };
+
+        let (base, quote) = match pair.split_once(':') {
+            Some(assets) => assets,
+            None if pair.len() == 6 && pair.is_ascii() => pair.split_at(3),
+            None => return Err(ParseMarketError::format(Self::ID, market)),
+        };
+
+        let (base, quote, kind) = match (base.strip_suffix("F0"), quote.strip_suffix("F0")) {
+            (Some(base), Some(quote)) => (base, quote, InstrumentKind::FuturePerpetual),
+            (None, None) => (base, quote, InstrumentKind::Spot),
+            _ => return Err(ParseMarketError::format(Self::ID, market)),
+        };
+
+        if base.is_empty() || quote.is_empty() {
+            return Err(ParseMarketError::format(Self::ID, market));
+        }
+
+        Ok(canonical_instrument(Self::ID, base, quote, kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exchange::ExchangeId, subscription::trade::PublicTrades};
+
+    #[test]
+    fn test_bitfinex_market() {
+        struct TestCase {
+            base: &'static str,
+            quote: &'static str,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: assets without an alias
+                base: "btc",
+                quote: "usd",
+                expected: "tBTCUSD",
+            },
+            TestCase {
+                // TC1: canonical usdt quote maps to the Bitfinex UST alias
+                base: "btc",
+                quote: "usdt",
+                expected: "tBTCUST",
+            },
+            TestCase {
+                // TC2: canonical usdc quote maps to the Bitfinex UDC alias
+                base: "eth",
+                quote: "usdc",
+                expected: "tETHUDC",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let subscription = Subscription::from((
+                Bitfinex::default(),
+                test.base,
+                test.quote,
+                InstrumentKind::Spot,
+                PublicTrades,
+            ));
+            let actual: BitfinexMarket = subscription.id();
+            assert_eq!(actual.as_ref(), test.expected, "TC{} failed", index);
+
+            // Market parses back into the canonical Instrument
+            assert_eq!(
+                Bitfinex::parse_market(actual.as_ref()),
+                Ok(subscription.instrument),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_market() {
+        struct TestCase {
+            market: &'static str,
+            expected: Result<Instrument, ParseMarketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: trading pair of 3 character assets
+                market: "tBTCUSD",
+                expected: Ok(Instrument::from(("btc", "usd", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: trading pair with ':' separator
+                market: "tMATIC:USD",
+                expected: Ok(Instrument::from(("matic", "usd", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC2: perpetual with F0 suffixes & UST alias for Tether
+                market: "tBTCF0:USTF0",
+                expected: Ok(Instrument::from((
+                    "btc",
+                    "usdt",
+                    InstrumentKind::FuturePerpetual,
+                ))),
+            },
+            TestCase {
+                // TC3: funding currency
+                market: "fUSD",
+                expected: Err(ParseMarketError::UnsupportedKind {
+                    exchange: ExchangeId::Bitfinex,
+                    market: "fUSD".to_owned(),
+                    kind: "funding currency",
+                }),
+            },
+            TestCase {
+                // TC4: missing prefix
+                market: "BTCUSD",
+                expected: Err(ParseMarketError::format(ExchangeId::Bitfinex, "BTCUSD")),
+            },
+            TestCase {
+                // TC5: ambiguous concatenation of assets that are not 3 characters
+                market: "tMATICUSD",
+                expected: Err(ParseMarketError::format(ExchangeId::Bitfinex, "tMATICUSD")),
+            },
+            TestCase {
+                // TC6: F0 suffix on only one asset
+                market: "tBTCF0:USD",
+                expected: Err(ParseMarketError::format(ExchangeId::Bitfinex, "tBTCF0:USD")),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Bitfinex::parse_market(test.market);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -1,19 +1,51 @@
-use super::trade::BitfinexTrade;
+use super::{
+    status::{
+        BitfinexInfo, BitfinexInfoCode, BITFINEX_INFO_MAINTENANCE_END,
+        BITFINEX_INFO_MAINTENANCE_START, BITFINEX_INFO_RECONNECT,
+    },
+    subscription::{BitfinexError, BitfinexPlatformEvent},
+    trade::BitfinexTrade,
+    Bitfinex,
+};
 use crate::{
-    event::MarketIter, exchange::ExchangeId, subscription::trade::PublicTrade, Identifier,
+    error::DataError,
+    event::{MarketEvent, MarketEvents, MarketIter},
+    exchange::{
+        status::{ExchangeStatus, StatusHandling},
+        Connector, ExchangeId,
+    },
+    streams::{config::StreamConfig, health::ConnectionHealth},
+    subscription::{
+        trade::{PublicTrade, PublicTrades},
+        Map,
+    },
+    transformer::{stateless, ExchangeTransformer},
+    Identifier,
 };
+use async_trait::async_trait;
 use barter_integration::{
     de::extract_next,
     model::{Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+    Transformer,
 };
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::debug;
 
-/// [`Bitfinex`](super::Bitfinex) message received over
-/// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) relating to an active
-/// [`Subscription`](crate::Subscription).
+/// [`Bitfinex`] message received over
+/// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) after the
+/// [`Subscription`](crate::Subscription)s have been validated.
 ///
-/// The message is associated with the original [`Subscription`](crate::Subscription) using the
-/// `channel_id` field as the [`SubscriptionId`](barter_integration::model::SubscriptionId).
+/// Bitfinex sends two distinct message shapes over the same connection, so the shape is
+/// discriminated before the payload is deserialised:
+/// - JSON arrays are [`BitfinexMessage::Channel`] messages relating to an active
+///   [`Subscription`](crate::Subscription) (see [`BitfinexChannelMessage`]).
+/// - JSON objects are [`BitfinexMessage::Event`] platform events (eg/ "info", "error"), which
+///   are not associated with any [`Subscription`](crate::Subscription).
 ///
 /// ### Raw Payload Examples
 /// #### Heartbeat
@@ -33,13 +65,44 @@ use serde::Serialize;
 /// ```json
 /// [420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]
 /// ```
+///
+/// #### Trades Snapshot
+/// See docs: <https://docs.bitfinex.com/reference/ws-public-trades>
+/// ```json
+/// [420191,[[1225484398,1665452200022,-0.08980641,19027.02807752]]]
+/// ```
+///
+/// #### Platform Info
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#info-messages>
+/// ```json
+/// {"event":"info","code":20051,"msg":"Stopping. Please try to reconnect"}
+/// ```
+///
+/// #### Platform Error
+/// See docs: <https://docs.bitfinex.com/docs/abbreviations-glossary#error-codes>
+/// ```json
+/// {"event":"error","msg":"Unknown event","code":10000}
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub enum BitfinexMessage {
+    Channel(BitfinexChannelMessage),
+    Event(BitfinexPlatformEvent),
+}
+
+/// [`Bitfinex`] array message relating to an active
+/// [`Subscription`](crate::Subscription).
+///
+/// The message is associated with the original [`Subscription`](crate::Subscription) using the
+/// `channel_id` field as the [`SubscriptionId`](barter_integration::model::SubscriptionId).
+///
+/// See [`BitfinexMessage`] for full raw payload examples.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize)]
-pub struct BitfinexMessage {
+pub struct BitfinexChannelMessage {
     pub channel_id: u32,
     pub payload: BitfinexPayload,
 }
 
-/// [`Bitfinex`](super::Bitfinex) market data variants associated with an
+/// [`Bitfinex`] market data variants associated with an
 /// active [`Subscription`](crate::Subscription).
 ///
 /// See [`BitfinexMessage`] for full raw payload examples.
@@ -49,92 +112,299 @@ pub struct BitfinexMessage {
 pub enum BitfinexPayload {
     Heartbeat,
     Trade(BitfinexTrade),
+    /// Initial snapshot of trades executed before the [`Subscription`](crate::Subscription)
+    /// was made, which are not published.
+    Snapshot,
 }
 
 impl Identifier<Option<SubscriptionId>> for BitfinexMessage {
     fn id(&self) -> Option<SubscriptionId> {
-        match self.payload {
-            BitfinexPayload::Heartbeat => None,
-            BitfinexPayload::Trade(_) => Some(SubscriptionId::from(self.channel_id.to_string())),
+        match self {
+            BitfinexMessage::Channel(BitfinexChannelMessage {
+                channel_id,
+                payload: BitfinexPayload::Trade(_),
+            }) => Some(SubscriptionId::from(channel_id.to_string())),
+            BitfinexMessage::Channel(_) | BitfinexMessage::Event(_) => None,
+        }
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BitfinexMessage)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, message): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BitfinexMessage,
+        ),
+    ) -> Self {
+        match message {
+            BitfinexMessage::Channel(BitfinexChannelMessage {
+                payload: BitfinexPayload::Trade(trade),
+                ..
+            }) => Self::from((exchange_id, instrument, received_time, trade)),
+            BitfinexMessage::Channel(_) | BitfinexMessage::Event(_) => Self(smallvec![]),
         }
     }
 }
 
-impl From<(ExchangeId, Instrument, BitfinexMessage)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, message): (ExchangeId, Instrument, BitfinexMessage)) -> Self {
-        match message.payload {
-            BitfinexPayload::Heartbeat => Self(vec![]),
-            BitfinexPayload::Trade(trade) => Self::from((exchange_id, instrument, trade)),
+/// [`Bitfinex`] [`PublicTrades`] [`ExchangeTransformer`], normalising trades alongside the
+/// heartbeats & platform events sent over the same connection.
+///
+/// - Heartbeats refresh the staleness of the connection (see [`ConnectionHealth::heartbeat`]), yielding no
+///   events.
+/// - `info` events are recorded as an [`ExchangeStatus`] (see [`StatusHandling::record`]).
+/// - Trades are paused (ie/ dropped) after [`BITFINEX_INFO_MAINTENANCE_START`]. Bitfinex advises
+///   re-subscribing once maintenance ends, so [`BITFINEX_INFO_MAINTENANCE_END`] resumes by
+///   yielding a terminal [`DataError::Reconnect`], as does the server restart
+///   [`BITFINEX_INFO_RECONNECT`].
+/// - `error` events yield a [`DataError::Exchange`] containing the Bitfinex error code.
+#[derive(Clone, Debug)]
+pub struct BitfinexTradesTransformer {
+    instrument_map: Map<Instrument>,
+    status: StatusHandling,
+    health: Option<Arc<ConnectionHealth>>,
+    paused: bool,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Bitfinex, PublicTrades> for BitfinexTradesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        config: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            status: config.status.clone(),
+            health: config.health.clone(),
+            paused: false,
+        })
+    }
+}
+
+impl Transformer for BitfinexTradesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, BitfinexMessage);
+    type Output = MarketEvent<PublicTrade>;
+    type OutputIter = MarketEvents<PublicTrade>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        match input {
+            BitfinexMessage::Channel(BitfinexChannelMessage {
+                payload: BitfinexPayload::Heartbeat,
+                ..
+            }) => {
+                if let Some(health) = &self.health {
+                    health.heartbeat();
+                }
+                smallvec![]
+            }
+            BitfinexMessage::Event(event) => self.platform_event(event),
+            BitfinexMessage::Channel(message) if self.paused => {
+                debug!(
+                    exchange = %Bitfinex::ID,
+                    channel_id = message.channel_id,
+                    "dropping message whilst paused for maintenance"
+                );
+                smallvec![]
+            }
+            message => stateless::transform::<Bitfinex, PublicTrades, _>(
+                received_time,
+                message,
+                &self.instrument_map,
+            ),
         }
     }
 }
 
-impl<'de> serde::Deserialize<'de> for BitfinexMessage {
+impl BitfinexTradesTransformer {
+    /// Handle a [`BitfinexPlatformEvent`] received after the subscriptions were validated.
+    fn platform_event(&mut self, event: BitfinexPlatformEvent) -> MarketEvents<PublicTrade> {
+        match event {
+            BitfinexPlatformEvent::Info(info) => {
+                let reconnect = match &info {
+                    BitfinexInfo::Code(BitfinexInfoCode { code, msg }) => match *code {
+                        BITFINEX_INFO_MAINTENANCE_START => {
+                            self.paused = true;
+                            None
+                        }
+                        BITFINEX_INFO_MAINTENANCE_END | BITFINEX_INFO_RECONNECT => Some(format!(
+                            "info code {code}: {}",
+                            msg.as_deref().unwrap_or_default()
+                        )),
+                        _ => None,
+                    },
+                    BitfinexInfo::PlatformStatus(_) => None,
+                };
+
+                self.status.record(Bitfinex::ID, ExchangeStatus::from(info));
+
+                match reconnect {
+                    Some(reason) => smallvec![Err(DataError::Reconnect {
+                        exchange: Bitfinex::ID,
+                        reason,
+                    })],
+                    None => smallvec![],
+                }
+            }
+            BitfinexPlatformEvent::Error(BitfinexError { msg, code }) => {
+                smallvec![Err(DataError::Exchange {
+                    exchange: Bitfinex::ID,
+                    code: i64::from(code),
+                    message: msg,
+                })]
+            }
+            BitfinexPlatformEvent::Subscribed(_) | BitfinexPlatformEvent::Unknown => smallvec![],
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BitfinexMessage {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::de::Deserializer<'de>,
     {
-        struct SeqVisitor;
+        struct ShapeVisitor;
 
-        impl<'de> serde::de::Visitor<'de> for SeqVisitor {
+        impl<'de> serde::de::Visitor<'de> for ShapeVisitor {
             type Value = BitfinexMessage;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("BitfinexMessage struct from the Bitfinex WebSocket API")
+                formatter.write_str(
+                    "BitfinexMessage array or event object from the Bitfinex WebSocket API",
+                )
             }
 
             fn visit_seq<SeqAccessor>(
                 self,
-                mut seq: SeqAccessor,
+                seq: SeqAccessor,
             ) -> Result<Self::Value, SeqAccessor::Error>
             where
                 SeqAccessor: serde::de::SeqAccess<'de>,
             {
-                // Trade: [CHANNEL_ID, <"te", "tu">, [ID, TIME, AMOUNT, PRICE]]
-                // Heartbeat: [ CHANNEL_ID, "hb" ]
-                // Candle: [CHANNEL_ID, [MTS, OPEN, CLOSE, HIGH, LOW, VOLUME]]
+                BitfinexChannelMessage::from_seq(seq).map(BitfinexMessage::Channel)
+            }
+
+            fn visit_map<MapAccessor>(
+                self,
+                map: MapAccessor,
+            ) -> Result<Self::Value, MapAccessor::Error>
+            where
+                MapAccessor: serde::de::MapAccess<'de>,
+            {
+                BitfinexPlatformEvent::deserialize(serde::de::value::MapAccessDeserializer::new(
+                    map,
+                ))
+                .map(BitfinexMessage::Event)
+            }
+        }
+
+        // Discriminate between array channel messages & object platform events
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
 
-                // Extract CHANNEL_ID used to identify SubscriptionId: 1st element of the sequence
-                let channel_id: u32 = extract_next(&mut seq, "channel_id")?;
+impl BitfinexChannelMessage {
+    /// Deserialise a [`BitfinexChannelMessage`] from the elements of a JSON array.
+    fn from_seq<'de, SeqAccessor>(mut seq: SeqAccessor) -> Result<Self, SeqAccessor::Error>
+    where
+        SeqAccessor: serde::de::SeqAccess<'de>,
+    {
+        // Trade: [CHANNEL_ID, <"te", "tu">, [ID, TIME, AMOUNT, PRICE]]
+        // Heartbeat: [ CHANNEL_ID, "hb" ]
+        // Snapshot: [CHANNEL_ID, [[ID, TIME, AMOUNT, PRICE], ...]]
 
-                // Extract message tag to identify payload type: 2nd element of the sequence
-                let message_tag: String = extract_next(&mut seq, "message_tag")?;
+        // Extract CHANNEL_ID used to identify SubscriptionId: 1st element of the sequence
+        let channel_id: u32 = extract_next(&mut seq, "channel_id")?;
 
+        // Extract message tag or snapshot to identify payload type: 2nd element of the sequence
+        let payload = match seq
+            .next_element::<BitfinexMessageTag<'_>>()?
+            .ok_or_else(|| serde::de::Error::missing_field("message_tag"))?
+        {
+            BitfinexMessageTag::Snapshot => BitfinexPayload::Snapshot,
+            BitfinexMessageTag::Tag(tag) => match tag {
+                // Filter "tu" Trades since they are identical but slower
+                // '--> use as additional Heartbeat
+                "hb" | "tu" => BitfinexPayload::Heartbeat,
                 // Use message tag to extract the payload: 3rd element of sequence
-                let payload = match message_tag.as_str() {
-                    // Filter "tu" Trades since they are identical but slower
-                    // '--> use as additional Heartbeat
-                    "hb" | "tu" => BitfinexPayload::Heartbeat,
-                    "te" => BitfinexPayload::Trade(extract_next(&mut seq, "BitfinexTrade")?),
-                    other => {
-                        return Err(serde::de::Error::unknown_variant(
-                            other,
-                            &["heartbeat (hb)", "trade (te | tu)"],
-                        ))
-                    }
-                };
+                "te" => BitfinexPayload::Trade(extract_next(&mut seq, "BitfinexTrade")?),
+                other => {
+                    return Err(serde::de::Error::unknown_variant(
+                        other,
+                        &["heartbeat (hb)", "trade (te | tu)"],
+                    ))
+                }
+            },
+        };
+
+        // Ignore any additional elements or SerDe will fail
+        //  '--> Bitfinex may add fields without warning
+        while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+        Ok(Self {
+            channel_id,
+            payload,
+        })
+    }
+}
+
+/// 2nd element of a [`BitfinexChannelMessage`] array, either a message tag, or an array snapshot.
+enum BitfinexMessageTag<'a> {
+    Tag(&'a str),
+    Snapshot,
+}
+
+impl<'de> Deserialize<'de> for BitfinexMessageTag<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct TagVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for TagVisitor {
+            type Value = BitfinexMessageTag<'de>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("BitfinexChannelMessage tag or snapshot")
+            }
+
+            fn visit_borrowed_str<E>(self, tag: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(BitfinexMessageTag::Tag(tag))
+            }
 
-                // Ignore any additional elements or SerDe will fail
-                //  '--> Bitfinex may add fields without warning
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // Snapshot trades are not normalised, so skip them without buffering
                 while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
-                Ok(BitfinexMessage {
-                    channel_id,
-                    payload,
-                })
+                Ok(BitfinexMessageTag::Snapshot)
             }
         }
 
-        // Use Visitor implementation to deserialise the WebSocket BitfinexMessage
-        deserializer.deserialize_seq(SeqVisitor)
+        deserializer.deserialize_any(TagVisitor)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exchange::bitfinex::{
+        status::{BitfinexInfo, BitfinexInfoCode},
+        subscription::{BitfinexChannelId, BitfinexSubResponse},
+    };
+    use crate::exchange::status::SystemStatus;
+    use crate::num;
     use barter_integration::de::datetime_utc_from_epoch_duration;
     use barter_integration::error::SocketError;
-    use barter_integration::model::Side;
+    use barter_integration::model::{InstrumentKind, Side};
     use std::time::Duration;
 
     #[test]
@@ -146,13 +416,14 @@ mod tests {
 
         // Trade: [CHANNEL_ID, <"te", "tu">, [ID, TIME, AMOUNT, PRICE]]
         // Heartbeat: [ CHANNEL_ID, "hb" ]
-        // Candle: [CHANNEL_ID, [MTS, OPEN, CLOSE, HIGH, LOW, VOLUME]]
+        // Snapshot: [CHANNEL_ID, [[ID, TIME, AMOUNT, PRICE], ...]]
+        // Event: {"event": EVENT, ...}
 
         let cases = vec![
             // TC0: Trade message te Sell
             TestCase {
                 input: r#"[420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#,
-                expected: Ok(BitfinexMessage {
+                expected: Ok(BitfinexMessage::Channel(BitfinexChannelMessage {
                     channel_id: 420191,
                     payload: BitfinexPayload::Trade(BitfinexTrade {
                         id: 1225484398,
@@ -160,15 +431,15 @@ mod tests {
                             1665452200022,
                         )),
                         side: Side::Sell,
-                        price: 19027.02807752,
-                        amount: 0.08980641,
+                        price: num!(19027.02807752),
+                        amount: num!(0.08980641),
                     }),
-                }),
+                })),
             },
             // TC1: Trade message te Buy
             TestCase {
                 input: r#"[420191,"te",[1225484398,1665452200022,0.08980641,19027.02807752]]"#,
-                expected: Ok(BitfinexMessage {
+                expected: Ok(BitfinexMessage::Channel(BitfinexChannelMessage {
                     channel_id: 420191,
                     payload: BitfinexPayload::Trade(BitfinexTrade {
                         id: 1225484398,
@@ -176,25 +447,75 @@ mod tests {
                             1665452200022,
                         )),
                         side: Side::Buy,
-                        price: 19027.02807752,
-                        amount: 0.08980641,
+                        price: num!(19027.02807752),
+                        amount: num!(0.08980641),
                     }),
-                }),
+                })),
             },
             // TC2: Trade tu --> Should be marked as a heartbeat
             TestCase {
                 input: r#"[420191,"tu",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#,
-                expected: Ok(BitfinexMessage {
+                expected: Ok(BitfinexMessage::Channel(BitfinexChannelMessage {
                     channel_id: 420191,
                     payload: BitfinexPayload::Heartbeat,
-                }),
+                })),
             },
             // TC3: Heartbeat message
             TestCase {
                 input: r#"[420191,"hb"]"#,
-                expected: Ok(BitfinexMessage {
+                expected: Ok(BitfinexMessage::Channel(BitfinexChannelMessage {
                     channel_id: 420191,
                     payload: BitfinexPayload::Heartbeat,
+                })),
+            },
+            // TC4: Trades snapshot message
+            TestCase {
+                input: r#"[420191,[[1225484398,1665452200022,-0.08980641,19027.02807752],[1225484397,1665452200021,0.1,19027.0]]]"#,
+                expected: Ok(BitfinexMessage::Channel(BitfinexChannelMessage {
+                    channel_id: 420191,
+                    payload: BitfinexPayload::Snapshot,
+                })),
+            },
+            // TC5: Unknown message tag
+            TestCase {
+                input: r#"[420191,"cs",-1234567]"#,
+                expected: Err(SocketError::Deserialise {
+                    error: serde::de::Error::custom(""),
+                    payload: "".to_owned(),
+                }),
+            },
+            // TC6: Info event with platform code
+            TestCase {
+                input: r#"{"event":"info","code":20051,"msg":"Stopping. Please try to reconnect"}"#,
+                expected: Ok(BitfinexMessage::Event(BitfinexPlatformEvent::Info(
+                    BitfinexInfo::Code(BitfinexInfoCode {
+                        code: 20051,
+                        msg: Some("Stopping. Please try to reconnect".to_owned()),
+                    }),
+                ))),
+            },
+            // TC7: Subscribed event
+            TestCase {
+                input: r#"{"event":"subscribed","channel":"trades","chanId":2203,"symbol":"tBTCUSD","pair":"BTCUSD"}"#,
+                expected: Ok(BitfinexMessage::Event(BitfinexPlatformEvent::Subscribed(
+                    BitfinexSubResponse {
+                        channel: "trades".to_owned(),
+                        market: "tBTCUSD".to_owned(),
+                        channel_id: BitfinexChannelId(2203),
+                    },
+                ))),
+            },
+            // TC8: Unrecognised event
+            TestCase {
+                input: r#"{"event":"conf","status":"OK","flags":65536}"#,
+                expected: Ok(BitfinexMessage::Event(BitfinexPlatformEvent::Unknown)),
+            },
+            // TC9: Neither array nor object
+            TestCase {
+                input: r#""hb""#,
+                expected: Err(SocketError::Deserialise {
+                    error: serde::de::Error::custom(""),
+                    payload: "".to_owned(),
                 }),
             },
         ];
@@ -215,4 +536,145 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_bitfinex_message_id() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<SubscriptionId>,
+        }
+
+        let cases = vec![
+            // TC0: Trade is associated with it's channel
+            TestCase {
+                input: r#"[420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#,
+                expected: Some(SubscriptionId::from("420191")),
+            },
+            // TC1: Heartbeat is not associated with a Subscription
+            TestCase {
+                input: r#"[420191,"hb"]"#,
+                expected: None,
+            },
+            // TC2: Snapshot is not published
+            TestCase {
+                input: r#"[420191,[[1225484398,1665452200022,-0.08980641,19027.02807752]]]"#,
+                expected: None,
+            },
+            // TC3: Platform event is not associated with a Subscription
+            TestCase {
+                input: r#"{"event":"info","code":20061,"msg":"Maintenance ended"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let message = serde_json::from_str::<BitfinexMessage>(test.input).unwrap();
+            assert_eq!(message.id(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_bitfinex_trades_transformer_pause_resume() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<String>,
+            expected_paused: bool,
+            expected_status: SystemStatus,
+        }
+
+        let mut transformer = BitfinexTradesTransformer {
+            instrument_map: Map([(
+                SubscriptionId::from("420191"),
+                Instrument::from(("btc", "usd", InstrumentKind::Spot)),
+            )]
+            .into_iter()
+            .collect()),
+            status: StatusHandling::default(),
+            health: None,
+            paused: false,
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: trade is yielded
+                input: r#"[420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#,
+                expected: vec!["trade 1225484398".to_string()],
+                expected_paused: false,
+                expected_status: SystemStatus::Online,
+            },
+            TestCase {
+                // TC1: heartbeat yields nothing
+                input: r#"[420191,"hb"]"#,
+                expected: vec![],
+                expected_paused: false,
+                expected_status: SystemStatus::Online,
+            },
+            TestCase {
+                // TC2: entering maintenance pauses trades
+                input: r#"{"event":"info","code":20060,"msg":"Entering in Maintenance mode"}"#,
+                expected: vec![],
+                expected_paused: true,
+                expected_status: SystemStatus::Maintenance,
+            },
+            TestCase {
+                // TC3: trade whilst paused is dropped
+                input: r#"[420191,"te",[1225484399,1665452200023,0.1,19027.0]]"#,
+                expected: vec![],
+                expected_paused: true,
+                expected_status: SystemStatus::Maintenance,
+            },
+            TestCase {
+                // TC4: error event yields it's code whilst paused
+                input: r#"{"event":"error","msg":"Unknown event","code":10000}"#,
+                expected: vec!["exchange error 10000: Unknown event".to_string()],
+                expected_paused: true,
+                expected_status: SystemStatus::Maintenance,
+            },
+            TestCase {
+                // TC5: maintenance ended re-connects to re-subscribe
+                input: r#"{"event":"info","code":20061,"msg":"Maintenance ended"}"#,
+                expected: vec!["reconnect: info code 20061: Maintenance ended".to_string()],
+                expected_paused: true,
+                expected_status: SystemStatus::Online,
+            },
+            TestCase {
+                // TC6: server restarting re-connects
+                input: r#"{"event":"info","code":20051,"msg":"Stopping. Please try to reconnect"}"#,
+                expected: vec![
+                    "reconnect: info code 20051: Stopping. Please try to reconnect".to_string(),
+                ],
+                expected_paused: true,
+                expected_status: SystemStatus::Online,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let input = serde_json::from_str::<BitfinexMessage>(test.input).unwrap();
+            let actual = transformer
+                .transform((Utc::now(), input))
+                .into_iter()
+                .map(|result| match result {
+                    Ok(event) => format!("trade {}", event.kind.id),
+                    Err(DataError::Exchange { code, message, .. }) => {
+                        format!("exchange error {code}: {message}")
+                    }
+                    Err(DataError::Reconnect { reason, .. }) => format!("reconnect: {reason}"),
+                    Err(error) => panic!("TC{index} failed: unexpected error {error}"),
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+            assert_eq!(
+                transformer.paused, test.expected_paused,
+                "TC{} failed",
+                index
+            );
+            assert_eq!(
+                SystemStatus::current(ExchangeId::Bitfinex),
+                test.expected_status,
+                "TC{} failed",
+                index
+            );
+        }
+    }
 }
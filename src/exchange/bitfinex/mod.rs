@@ -18,23 +18,54 @@
 //! - Bitfinex trades subscriptions results in receiving tag="te" & tag="tu" trades.
 //! - Both appear to be identical payloads, but "te" arriving marginally faster.
 //! - Therefore, tag="tu" trades are filtered out and considered only as additional Heartbeats.
+//!
+//! #### Order Books
+//! - [`OrderBooksL2`] subscribe to the "book" channel using the [`BitfinexBookConfig`] of the
+//!   [`Bitfinex`] instance, selecting the price aggregation (`prec`), update frequency (`freq`)
+//!   & number of price points (`len`).
+//! - The book snapshot is the first message of the channel, so `book` channel messages received
+//!   whilst validating the subscriptions are always buffered.
+//! - Heartbeats & platform events are not associated with an OrderBook, so are ignored by the
+//!   [`MultiBookTransformer`].
+//!
+//! #### Message Shapes
+//! - Messages relating to a subscription are JSON arrays, eg/ `[CHANNEL_ID, "te", [..]]` trades,
+//!   `[CHANNEL_ID, "hb"]` heartbeats & `[CHANNEL_ID, [[..], ..]]` snapshots.
+//! - Platform events are JSON objects, eg/ `{"event": "info", ..}`.
+//! - Heartbeats, `info` & `error` events are handled by the [`BitfinexTradesTransformer`],
+//!   which records `info` events as an [`ExchangeStatus`](crate::exchange::status::ExchangeStatus).
+//!
+//! #### Maintenance
+//! - Info code 20060 announces maintenance, during which trades are paused.
+//! - Info code 20061 announces maintenance has ended, and 20051 that the server is restarting.
+//!   Both re-connect (re-subscribing with new `CHANNEL_ID`s).
 
 use self::{
-    channel::BitfinexChannel, market::BitfinexMarket, message::BitfinexMessage,
-    subscription::BitfinexPlatformEvent, validator::BitfinexWebSocketSubValidator,
+    book::{BitfinexBookConfig, BitfinexBookUpdater},
+    channel::BitfinexChannel,
+    market::BitfinexMarket,
+    message::BitfinexTradesTransformer,
+    subscription::BitfinexPlatformEvent,
+    validator::BitfinexWebSocketSubValidator,
 };
 use crate::{
+    error::DataError,
     exchange::{Connector, ExchangeId, ExchangeSub, StreamSelector},
+    streams::config::StreamConfig,
     subscriber::WebSocketSubscriber,
-    subscription::trade::PublicTrades,
-    transformer::stateless::StatelessTransformer,
+    subscription::{book::OrderBooksL2, trade::PublicTrades},
+    transformer::book::MultiBookTransformer,
     ExchangeWsStream,
 };
 use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
-use barter_macro::{DeExchange, SerExchange};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use url::Url;
 
+/// Level 2 OrderBook types & the [`BitfinexBookConfig`] "book" channel parameters for
+/// [`Bitfinex`].
+pub mod book;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
@@ -43,13 +74,17 @@ pub mod channel;
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
 
-/// [`BitfinexMessage`](message::BitfinexMessage) type for [`Bitfinex`].
+/// [`BitfinexMessage`](message::BitfinexMessage) type & the [`BitfinexTradesTransformer`] for
+/// [`Bitfinex`].
 pub mod message;
 
 /// [`Subscription`](crate::subscription::Subscription) response types and response
 /// [`Validator`](barter_integration::Validator) for [`Bitfinex`].
 pub mod subscription;
 
+/// `info` message types for [`Bitfinex`] platform status & maintenance notifications.
+pub mod status;
+
 /// Public trade types for [`Bitfinex`].
 pub mod trade;
 
@@ -64,11 +99,23 @@ pub const BASE_URL_BITFINEX: &str = "wss://api-pub.bitfinex.com/ws/2";
 
 /// [`Bitfinex`] exchange.
 ///
+/// Configured with the [`BitfinexBookConfig`] parameters of it's [`OrderBooksL2`] subscriptions.
+/// Serialised as "bitfinex" if they're the defaults, else as an object containing them, eg/
+/// `{"id": "bitfinex", "book": {"prec": "P1", "freq": "F0", "len": 100}}`.
+///
 /// See docs: <https://docs.bitfinex.com/docs/ws-general>
-#[derive(
-    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, DeExchange, SerExchange,
-)]
-pub struct Bitfinex;
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Bitfinex {
+    pub book: BitfinexBookConfig,
+}
+
+impl Bitfinex {
+    /// Construct a new [`Self`] subscribing to [`OrderBooksL2`] using the provided
+    /// [`BitfinexBookConfig`].
+    pub fn new(book: BitfinexBookConfig) -> Self {
+        Self { book }
+    }
+}
 
 impl Connector for Bitfinex {
     const ID: ExchangeId = ExchangeId::Bitfinex;
@@ -83,22 +130,217 @@ impl Connector for Bitfinex {
     }
 
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        exchange_subs
-            .into_iter()
-            .map(|ExchangeSub { channel, market }| {
-                WsMessage::Text(
-                    json!({
-                        "event": "subscribe",
-                        "channel": channel.as_ref(),
-                        "symbol": market.as_ref(),
-                    })
-                    .to_string(),
-                )
-            })
-            .collect()
+        subscribe_requests(exchange_subs, &BitfinexBookConfig::default())
     }
+
+    fn subscribe_requests(
+        &self,
+        exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        _: &StreamConfig,
+    ) -> Result<Vec<WsMessage>, DataError> {
+        Ok(subscribe_requests(exchange_subs, &self.book))
+    }
+}
+
+/// Construct the [`Bitfinex`] subscribe requests of the provided [`ExchangeSub`]s, including the
+/// [`BitfinexBookConfig`] parameters in those of the [`BitfinexChannel::BOOK`] channel.
+fn subscribe_requests(
+    exchange_subs: Vec<ExchangeSub<BitfinexChannel, BitfinexMarket>>,
+    book: &BitfinexBookConfig,
+) -> Vec<WsMessage> {
+    exchange_subs
+        .into_iter()
+        .map(|ExchangeSub { channel, market }| {
+            let mut request = json!({
+                "event": "subscribe",
+                "channel": channel.as_ref(),
+                "symbol": market.as_ref(),
+            });
+
+            if channel == BitfinexChannel::BOOK {
+                request["prec"] = json!(book.prec.as_ref());
+                request["freq"] = json!(book.freq.as_ref());
+                request["len"] = json!(book.len.levels().to_string());
+            }
+
+            WsMessage::Text(request.to_string())
+        })
+        .collect()
 }
 
 impl StreamSelector<PublicTrades> for Bitfinex {
-    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, BitfinexMessage>>;
+    type Stream = ExchangeWsStream<BitfinexTradesTransformer>;
+}
+
+impl StreamSelector<OrderBooksL2> for Bitfinex {
+    type Stream = ExchangeWsStream<MultiBookTransformer<Self, OrderBooksL2, BitfinexBookUpdater>>;
+}
+
+impl<'de> Deserialize<'de> for Bitfinex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Id(String),
+            Configured {
+                id: String,
+                #[serde(default)]
+                book: BitfinexBookConfig,
+            },
+        }
+
+        let (id, book) = match Repr::deserialize(deserializer)? {
+            Repr::Id(id) => (id, BitfinexBookConfig::default()),
+            Repr::Configured { id, book } => (id, book),
+        };
+
+        if id.as_str() == Self::ID.as_str() {
+            Ok(Self { book })
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(id.as_str()),
+                &Self::ID.as_str(),
+            ))
+        }
+    }
+}
+
+impl Serialize for Bitfinex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if self.book == BitfinexBookConfig::default() {
+            return serializer.serialize_str(Self::ID.as_str());
+        }
+
+        #[derive(Serialize)]
+        struct Configured<'a> {
+            id: &'static str,
+            book: &'a BitfinexBookConfig,
+        }
+
+        Configured {
+            id: Self::ID.as_str(),
+            book: &self.book,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{book::*, *};
+
+    #[test]
+    fn test_bitfinex_subscribe_requests() {
+        struct TestCase {
+            exchange: Bitfinex,
+            channel: BitfinexChannel,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: trades have no parameters
+                exchange: Bitfinex::default(),
+                channel: BitfinexChannel::TRADES,
+                expected: r#"{"channel":"trades","event":"subscribe","symbol":"tBTCUSD"}"#,
+            },
+            TestCase {
+                // TC1: book uses the Bitfinex default parameters
+                exchange: Bitfinex::default(),
+                channel: BitfinexChannel::BOOK,
+                expected: r#"{"channel":"book","event":"subscribe","freq":"F0","len":"25","prec":"P0","symbol":"tBTCUSD"}"#,
+            },
+            TestCase {
+                // TC2: book uses the configured parameters
+                exchange: Bitfinex::new(BitfinexBookConfig::new(
+                    BitfinexBookPrecision::P3,
+                    BitfinexBookFrequency::F1,
+                    BitfinexBookLength::L250,
+                )),
+                channel: BitfinexChannel::BOOK,
+                expected: r#"{"channel":"book","event":"subscribe","freq":"F1","len":"250","prec":"P3","symbol":"tBTCUSD"}"#,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = test
+                .exchange
+                .subscribe_requests(
+                    vec![ExchangeSub {
+                        channel: test.channel,
+                        market: BitfinexMarket("tBTCUSD".to_string()),
+                    }],
+                    &StreamConfig::default(),
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![WsMessage::Text(test.expected.to_string())],
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_de_bitfinex() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<Bitfinex>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: ExchangeId uses the default book parameters
+                input: r#""bitfinex""#,
+                expected: Some(Bitfinex::default()),
+            },
+            TestCase {
+                // TC1: configured book parameters
+                input: r#"{"id":"bitfinex","book":{"prec":"P1","freq":"F1","len":100}}"#,
+                expected: Some(Bitfinex::new(BitfinexBookConfig::new(
+                    BitfinexBookPrecision::P1,
+                    BitfinexBookFrequency::F1,
+                    BitfinexBookLength::L100,
+                ))),
+            },
+            TestCase {
+                // TC2: invalid book parameters are rejected
+                input: r#"{"id":"bitfinex","book":{"len":30}}"#,
+                expected: None,
+            },
+            TestCase {
+                // TC3: another ExchangeId is rejected
+                input: r#""kraken""#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<Bitfinex>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+
+            // Serialising round trips
+            if let Some(expected) = test.expected {
+                let serialised = serde_json::to_string(&expected).unwrap();
+                assert_eq!(
+                    serde_json::from_str::<Bitfinex>(&serialised).unwrap(),
+                    expected,
+                    "TC{} failed",
+                    index
+                );
+            }
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Bitfinex::default()).unwrap(),
+            r#""bitfinex""#
+        );
+    }
 }
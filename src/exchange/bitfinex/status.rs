@@ -0,0 +1,128 @@
+use super::subscription::{BitfinexPlatformStatus, Status};
+use crate::exchange::status::{ExchangeStatus, SystemStatus};
+use serde::{Deserialize, Serialize};
+
+/// [`BitfinexInfoCode`] sent when the WebSocket server is stopping, and the client should
+/// re-connect.
+pub const BITFINEX_INFO_RECONNECT: u32 = 20051;
+
+/// [`BitfinexInfoCode`] sent when the platform is entering maintenance mode.
+pub const BITFINEX_INFO_MAINTENANCE_START: u32 = 20060;
+
+/// [`BitfinexInfoCode`] sent when the platform has finished maintenance, and the client should
+/// re-subscribe.
+pub const BITFINEX_INFO_MAINTENANCE_END: u32 = 20061;
+
+/// [`Bitfinex`](super::Bitfinex) `info` message, sent on connection (a
+/// [`BitfinexPlatformStatus`]) and whenever the platform changes state (a [`BitfinexInfoCode`]).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#info-messages>
+/// #### Platform Status
+/// ```json
+/// {"event":"info","version":2,"serverId":"5b73a436-19ca-4a15-8160-9069bdd7f181","platform":{"status":1}}
+/// ```
+///
+/// #### Maintenance Start
+/// ```json
+/// {"event":"info","code":20060,"msg":"Entering in Maintenance mode"}
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BitfinexInfo {
+    PlatformStatus(BitfinexPlatformStatus),
+    Code(BitfinexInfoCode),
+}
+
+/// [`Bitfinex`](super::Bitfinex) `info` message communicating a platform state change via a
+/// numeric code.
+///
+/// ### Info Codes:
+/// 20051: Stop/Restart WebSocket server (please re-connect)
+/// 20060: Entering maintenance mode
+/// 20061: Maintenance ended (please re-subscribe)
+///
+/// See [`BitfinexInfo`] for full raw payload examples.
+///
+/// See docs: <https://docs.bitfinex.com/docs/ws-general#info-messages>
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitfinexInfoCode {
+    pub code: u32,
+    #[serde(default)]
+    pub msg: Option<String>,
+}
+
+impl From<BitfinexInfo> for ExchangeStatus {
+    fn from(info: BitfinexInfo) -> Self {
+        match info {
+            BitfinexInfo::PlatformStatus(platform) => match platform.status {
+                Status::Operative => Self::new(SystemStatus::Online, None::<String>),
+                Status::Maintenance => Self::new(SystemStatus::Maintenance, None::<String>),
+            },
+            BitfinexInfo::Code(BitfinexInfoCode { code, msg }) => {
+                let status = match code {
+                    BITFINEX_INFO_MAINTENANCE_START => SystemStatus::Maintenance,
+                    BITFINEX_INFO_MAINTENANCE_END => SystemStatus::Online,
+                    _ => SystemStatus::Notice,
+                };
+                Self::new(status, msg)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::bitfinex::subscription::BitfinexPlatformEvent;
+
+    #[test]
+    fn test_bitfinex_exchange_status() {
+        struct TestCase {
+            input: &'static str,
+            expected: ExchangeStatus,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: platform status operative
+                input: r#"{"event":"info","version":2,"serverId":"5b73a436-19ca-4a15-8160-9069bdd7f181","platform":{"status":1}}"#,
+                expected: ExchangeStatus::new(SystemStatus::Online, None::<String>),
+            },
+            TestCase {
+                // TC1: platform status in maintenance
+                input: r#"{"event":"info","version":2,"serverId":"5b73a436-19ca-4a15-8160-9069bdd7f181","platform":{"status":0}}"#,
+                expected: ExchangeStatus::new(SystemStatus::Maintenance, None::<String>),
+            },
+            TestCase {
+                // TC2: entering maintenance mode
+                input: r#"{"event":"info","code":20060,"msg":"Entering in Maintenance mode"}"#,
+                expected: ExchangeStatus::new(
+                    SystemStatus::Maintenance,
+                    Some("Entering in Maintenance mode"),
+                ),
+            },
+            TestCase {
+                // TC3: maintenance ended
+                input: r#"{"event":"info","code":20061,"msg":"Maintenance ended"}"#,
+                expected: ExchangeStatus::new(SystemStatus::Online, Some("Maintenance ended")),
+            },
+            TestCase {
+                // TC4: server restarting is a notice
+                input: r#"{"event":"info","code":20051,"msg":"Stopping. Please try to reconnect"}"#,
+                expected: ExchangeStatus::new(
+                    SystemStatus::Notice,
+                    Some("Stopping. Please try to reconnect"),
+                ),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = match serde_json::from_str::<BitfinexPlatformEvent>(test.input).unwrap() {
+                BitfinexPlatformEvent::Info(info) => ExchangeStatus::from(info),
+                other => panic!("TC{index} failed: unexpected event {other:?}"),
+            };
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
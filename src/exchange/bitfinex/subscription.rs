@@ -1,3 +1,4 @@
+use super::status::{BitfinexInfo, BitfinexInfoCode, BITFINEX_INFO_MAINTENANCE_START};
 use barter_integration::{error::SocketError, Validator};
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,15 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 ///
+/// #### Platform Info Code
+/// ``` json
+/// {
+///   "event": "info",
+///   "code": 20060,
+///   "msg": "Entering in Maintenance mode"
+/// }
+/// ```
+///
 /// #### Subscription Trades Success
 /// ``` json
 /// {
@@ -35,13 +45,18 @@ use serde::{Deserialize, Serialize};
 ///    "msg": ERROR_MSG,
 ///    "code": ERROR_CODE
 /// }
+/// ```
+///
+/// Any other event (eg/ "conf", "unsubscribed") is deserialised as
+/// [`BitfinexPlatformEvent::Unknown`] rather than failing.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 #[serde(tag = "event", rename_all = "lowercase")]
 pub enum BitfinexPlatformEvent {
-    #[serde(rename = "info")]
-    PlatformStatus(BitfinexPlatformStatus),
+    Info(BitfinexInfo),
     Subscribed(BitfinexSubResponse),
     Error(BitfinexError),
+    #[serde(other)]
+    Unknown,
 }
 
 impl Validator for BitfinexPlatformEvent {
@@ -50,14 +65,25 @@ impl Validator for BitfinexPlatformEvent {
         Self: Sized,
     {
         match &self {
-            BitfinexPlatformEvent::PlatformStatus(status) => match status.status {
-                Status::Operative => Ok(self),
-                Status::Maintenance => Err(SocketError::Subscribe(format!(
-                    "exchange version: {} with server_id: {} is in maintenance mode",
-                    status.api_version, status.server_id,
-                ))),
-            },
-            BitfinexPlatformEvent::Subscribed(_) => Ok(self),
+            BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(status)) => {
+                match status.status {
+                    Status::Operative => Ok(self),
+                    Status::Maintenance => Err(SocketError::Subscribe(format!(
+                        "exchange version: {} with server_id: {} is in maintenance mode",
+                        status.api_version, status.server_id,
+                    ))),
+                }
+            }
+            BitfinexPlatformEvent::Info(BitfinexInfo::Code(BitfinexInfoCode { code, msg }))
+                if *code == BITFINEX_INFO_MAINTENANCE_START =>
+            {
+                Err(SocketError::Subscribe(format!(
+                    "exchange is entering maintenance mode with code: {code} and message: {msg:?}",
+                )))
+            }
+            BitfinexPlatformEvent::Info(BitfinexInfo::Code(_))
+            | BitfinexPlatformEvent::Subscribed(_)
+            | BitfinexPlatformEvent::Unknown => Ok(self),
             BitfinexPlatformEvent::Error(error) => Err(SocketError::Subscribe(format!(
                 "received failure subscription response code: {} with message: {}",
                 error.code, error.msg,
@@ -97,11 +123,11 @@ impl Validator for BitfinexPlatformEvent {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct BitfinexPlatformStatus {
     #[serde(rename = "version")]
-    api_version: u8,
+    pub api_version: u8,
     #[serde(rename = "serverId")]
-    server_id: String,
+    pub server_id: String,
     #[serde(rename = "platform")]
-    status: Status,
+    pub status: Status,
 }
 
 /// [`Bitfinex`](super::Bitfinex) platform [`Status`] indicating if the API is in maintenance mode.
@@ -155,9 +181,11 @@ pub struct BitfinexSubResponse {
 pub struct BitfinexChannelId(pub u32);
 
 /// [`Bitfinex`](super::Bitfinex) error message that is received if a [`BitfinexSubResponse`]
-/// indicates a WebSocket subscription failure.
+/// indicates a WebSocket subscription failure, or if any other request fails once subscribed.
 ///
-/// ### Subscription Error Codes:
+/// ### Error Codes:
+/// 10000: Unknown event
+/// 10001: Unknown pair
 /// 10300: Generic failure
 /// 10301: Already subscribed
 /// 10302: Unknown channel
@@ -167,8 +195,8 @@ pub struct BitfinexChannelId(pub u32);
 /// See docs: <https://docs.bitfinex.com/docs/ws-general>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct BitfinexError {
-    msg: String,
-    code: u32,
+    pub msg: String,
+    pub code: u32,
 }
 
 impl<'de> Deserialize<'de> for Status {
@@ -223,26 +251,26 @@ mod tests {
             // TC0: platform status is online
             TestCase {
                 input: r#"{"event": "info", "version": 2, "serverId": "5b73a436-19ca-4a15-8160-9069bdd7f181", "platform": { "status": 1 }}"#,
-                expected: Ok(BitfinexPlatformEvent::PlatformStatus(
+                expected: Ok(BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(
                     BitfinexPlatformStatus {
                         api_version: 2,
                         server_id: "5b73a436-19ca-4a15-8160-9069bdd7f181".to_string(),
                         status: Status::Operative,
                     },
-                )),
+                ))),
             },
             // TC1: platform status is offline
             TestCase {
                 input: r#"{"event": "info", "version": 2, "serverId": "5b73a436-19ca-4a15-8160-9069bdd7f181", "platform": { "status": 0 }}"#,
-                expected: Ok(BitfinexPlatformEvent::PlatformStatus(
+                expected: Ok(BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(
                     BitfinexPlatformStatus {
                         api_version: 2,
                         server_id: "5b73a436-19ca-4a15-8160-9069bdd7f181".to_string(),
                         status: Status::Maintenance,
                     },
-                )),
+                ))),
             },
-            // TC1: successful trades channel subscription
+            // TC2: successful trades channel subscription
             TestCase {
                 input: r#"{"event": "subscribed", "channel": "trades", "chanId": 2203, "symbol": "tBTCUSD", "pair": "BTCUSD"}"#,
                 expected: Ok(BitfinexPlatformEvent::Subscribed(BitfinexSubResponse {
@@ -251,7 +279,7 @@ mod tests {
                     market: "tBTCUSD".to_owned(),
                 })),
             },
-            // TC2: Input response is error
+            // TC3: Input response is error
             TestCase {
                 input: r#"{"event": "error", "msg": "Already subscribed", "code": 10202}"#,
                 expected: Ok(BitfinexPlatformEvent::Error(BitfinexError {
@@ -259,6 +287,29 @@ mod tests {
                     code: 10202,
                 })),
             },
+            // TC4: info code message
+            TestCase {
+                input: r#"{"event": "info", "code": 20051, "msg": "Stopping. Please try to reconnect"}"#,
+                expected: Ok(BitfinexPlatformEvent::Info(BitfinexInfo::Code(
+                    BitfinexInfoCode {
+                        code: 20051,
+                        msg: Some("Stopping. Please try to reconnect".to_owned()),
+                    },
+                ))),
+            },
+            // TC5: unrecognised event
+            TestCase {
+                input: r#"{"event": "conf", "status": "OK"}"#,
+                expected: Ok(BitfinexPlatformEvent::Unknown),
+            },
+            // TC6: info message with neither platform status nor code is invalid
+            TestCase {
+                input: r#"{"event": "info", "version": 2}"#,
+                expected: Err(SocketError::Deserialise {
+                    error: serde::de::Error::custom(""),
+                    payload: "".to_owned(),
+                }),
+            },
         ];
 
         for (index, test) in cases.into_iter().enumerate() {
@@ -288,11 +339,13 @@ mod tests {
         let tests = vec![
             TestCase {
                 // TC0: bitfinex server is offline
-                input: BitfinexPlatformEvent::PlatformStatus(BitfinexPlatformStatus {
-                    api_version: 2,
-                    server_id: "server_id".to_string(),
-                    status: Status::Maintenance,
-                }),
+                input: BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(
+                    BitfinexPlatformStatus {
+                        api_version: 2,
+                        server_id: "server_id".to_string(),
+                        status: Status::Maintenance,
+                    },
+                )),
                 expected: Err(SocketError::Subscribe(format!(
                     "exchange version: {} with server_id: {} is in maintenance mode",
                     2, "server_id",
@@ -300,18 +353,20 @@ mod tests {
             },
             TestCase {
                 // TC1: bitfinex server is online
-                input: BitfinexPlatformEvent::PlatformStatus(BitfinexPlatformStatus {
-                    api_version: 2,
-                    server_id: "server_id".to_string(),
-                    status: Status::Operative,
-                }),
-                expected: Ok(BitfinexPlatformEvent::PlatformStatus(
+                input: BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(
                     BitfinexPlatformStatus {
                         api_version: 2,
                         server_id: "server_id".to_string(),
                         status: Status::Operative,
                     },
                 )),
+                expected: Ok(BitfinexPlatformEvent::Info(BitfinexInfo::PlatformStatus(
+                    BitfinexPlatformStatus {
+                        api_version: 2,
+                        server_id: "server_id".to_string(),
+                        status: Status::Operative,
+                    },
+                ))),
             },
             TestCase {
                 // TC2: subscription success
@@ -337,6 +392,22 @@ mod tests {
                     0, "error message",
                 ))),
             },
+            TestCase {
+                // TC4: bitfinex server is entering maintenance
+                input: BitfinexPlatformEvent::Info(BitfinexInfo::Code(BitfinexInfoCode {
+                    code: BITFINEX_INFO_MAINTENANCE_START,
+                    msg: None,
+                })),
+                expected: Err(SocketError::Subscribe(format!(
+                    "exchange is entering maintenance mode with code: {} and message: {:?}",
+                    BITFINEX_INFO_MAINTENANCE_START, None::<String>,
+                ))),
+            },
+            TestCase {
+                // TC5: unrecognised event is ignored
+                input: BitfinexPlatformEvent::Unknown,
+                expected: Ok(BitfinexPlatformEvent::Unknown),
+            },
         ];
 
         for (index, test) in tests.into_iter().enumerate() {
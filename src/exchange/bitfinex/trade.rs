@@ -1,14 +1,17 @@
 use crate::{
-    event::{MarketEvent, MarketIter},
+    datetime::epoch_ms_to_datetime_utc,
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::ExchangeId,
+    num::Num,
     subscription::trade::PublicTrade,
 };
 use barter_integration::{
-    de::{datetime_utc_from_epoch_duration, extract_next},
+    de::extract_next,
     model::{Exchange, Instrument, Side},
 };
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use smallvec::smallvec;
 
 /// [`Bitfinex`](super::Bitfinex) real-time trade message.
 ///
@@ -31,7 +34,7 @@ use serde::Serialize;
 ///
 /// ## Notes:
 /// - [`Bitfinex`](super::Bitfinex) trades subscriptions results in receiving tag="te" & tag="tu"
-/// trades, both of which are identical.
+///   trades, both of which are identical.
 /// - "te" trades arrive marginally faster.
 /// - Therefore, tag="tu" trades are filtered out and considered only as additional Heartbeats.
 ///
@@ -41,15 +44,26 @@ pub struct BitfinexTrade {
     pub id: u64,
     pub time: DateTime<Utc>,
     pub side: Side,
-    pub price: f64,
-    pub amount: f64,
+    pub price: Num,
+    pub amount: Num,
 }
 
-impl From<(ExchangeId, Instrument, BitfinexTrade)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, BitfinexTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, BitfinexTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            BitfinexTrade,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: trade.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Milliseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: PublicTrade {
@@ -58,6 +72,7 @@ impl From<(ExchangeId, Instrument, BitfinexTrade)> for MarketIter<PublicTrade> {
                 amount: trade.amount,
                 side: trade.side,
             },
+            meta: (),
         })])
     }
 }
@@ -85,8 +100,11 @@ impl<'de> serde::Deserialize<'de> for BitfinexTrade {
             {
                 // Trade: [ID, TIME, AMOUNT,PRICE]
                 let id = extract_next(&mut seq, "id")?;
-                let time_millis = extract_next(&mut seq, "time")?;
-                let amount: f64 = extract_next(&mut seq, "amount")?;
+                let time_millis: i64 = extract_next(&mut seq, "time")?;
+                let time = epoch_ms_to_datetime_utc(time_millis).ok_or_else(|| {
+                    serde::de::Error::custom(format!("invalid epoch timestamp: {time_millis}"))
+                })?;
+                let amount: Num = extract_next(&mut seq, "amount")?;
                 let price = extract_next(&mut seq, "price")?;
                 let side = match amount.is_sign_positive() {
                     true => Side::Buy,
@@ -99,9 +117,7 @@ impl<'de> serde::Deserialize<'de> for BitfinexTrade {
 
                 Ok(BitfinexTrade {
                     id,
-                    time: datetime_utc_from_epoch_duration(std::time::Duration::from_millis(
-                        time_millis,
-                    )),
+                    time,
                     price,
                     amount: amount.abs(),
                     side,
@@ -1,7 +1,14 @@
-use super::subscription::{BitfinexPlatformEvent, BitfinexSubResponse};
+use super::{
+    channel::BitfinexChannel,
+    subscription::{BitfinexPlatformEvent, BitfinexSubResponse},
+};
 use crate::{
     exchange::{Connector, ExchangeSub},
-    subscriber::validator::SubscriptionValidator,
+    parser::WsParser,
+    streams::config::StreamConfig,
+    subscriber::{
+        buffer::ValidationBuffer, transport::WebSocket, validator::SubscriptionValidator,
+    },
     subscription::{Map, SubKind},
     Identifier,
 };
@@ -9,14 +16,12 @@ use async_trait::async_trait;
 use barter_integration::{
     error::SocketError,
     model::{Instrument, SubscriptionId},
-    protocol::{
-        websocket::{WebSocket, WebSocketParser},
-        StreamParser,
-    },
+    protocol::StreamParser,
     Validator,
 };
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tracing::debug;
 
 /// [`Bitfinex`](super::Bitfinex) specific [`SubscriptionValidator`].
@@ -30,16 +35,20 @@ use tracing::debug;
 /// - Therefore the [`SubscriptionId`] format must change during [`BitfinexWebSocketSubValidator::validate`]
 ///   to use the [`BitfinexChannelId`](super::subscription::BitfinexChannelId)
 ///   (see module level "SubscriptionId" documentation notes for more details).
+/// - Messages of the [`BitfinexChannel::BOOK`] channel received during validation are always
+///   [`retained`](ValidationBuffer::retain), since the OrderBook snapshot is only sent once.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct BitfinexWebSocketSubValidator;
 
 #[async_trait]
 impl SubscriptionValidator for BitfinexWebSocketSubValidator {
-    type Parser = WebSocketParser;
+    type Parser = WsParser;
 
     async fn validate<Exchange, Kind>(
         mut map: Map<Instrument>,
         websocket: &mut WebSocket,
+        buffer: &mut ValidationBuffer,
+        _: &StreamConfig,
     ) -> Result<Map<Instrument>, SocketError>
     where
         Exchange: Connector + Send,
@@ -54,6 +63,9 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
         let mut success_responses = 0usize;
         let mut init_snapshots_received = 0usize;
 
+        // Channel ids of the OrderBook subscriptions, whose messages are always retained
+        let mut book_channels = HashSet::new();
+
         loop {
             // Break if all Subscriptions were a success
             if success_responses == expected_responses
@@ -77,16 +89,22 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
                         None => break Err(SocketError::Subscribe("WebSocket stream terminated unexpectedly".to_string()))
                     };
 
+                    // Retain a copy of the raw message in case it's market data to be buffered
+                    let raw = match (buffer.is_enabled() || !book_channels.is_empty(), &response) {
+                        (true, Ok(message)) => Some(message.clone()),
+                        _ => None,
+                    };
+
                     match Self::Parser::parse::<BitfinexPlatformEvent>(response) {
                         Some(Ok(response)) => match response.validate() {
-                            // Bitfinex server is online
-                            Ok(BitfinexPlatformEvent::PlatformStatus(status)) => {
+                            // Bitfinex server is online, or communicated a non-maintenance info code
+                            Ok(BitfinexPlatformEvent::Info(info)) => {
                                 debug!(
                                     exchange = %Exchange::ID,
                                     %success_responses,
                                     %expected_responses,
-                                    payload = ?status,
-                                    "received Bitfinex platform status",
+                                    payload = ?info,
+                                    "received Bitfinex platform info",
                                 );
                             }
 
@@ -99,6 +117,9 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
                                 // Replace SubscriptionId with SubscriptionId(channel_id)
                                 if let Some(subscription) = map.0.remove(&subscription_id) {
                                     success_responses += 1;
+                                    if channel == BitfinexChannel::BOOK.as_ref() {
+                                        book_channels.insert(channel_id.0);
+                                    }
                                     map.0.insert(SubscriptionId(channel_id.0.to_string()), subscription);
 
                                     debug!(
@@ -111,6 +132,9 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
                                 }
                             }
 
+                            // Unrecognised platform events (eg/ "conf") are not init snapshots
+                            Ok(BitfinexPlatformEvent::Unknown) => continue,
+
                             // Subscription failure
                             Err(err) => break Err(err),
 
@@ -128,6 +152,13 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
                                 %payload,
                                 "failed to deserialise non SubResponse payload"
                             );
+                            match (raw, payload_channel_id(&payload)) {
+                                (Some(raw), Some(channel_id)) if book_channels.contains(&channel_id) => {
+                                    buffer.retain(raw)
+                                }
+                                (Some(raw), _) => buffer.push(raw),
+                                (None, _) => {}
+                            }
                             continue
                         }
                         Some(Err(SocketError::Terminated(close_frame))) => {
@@ -145,3 +176,50 @@ impl SubscriptionValidator for BitfinexWebSocketSubValidator {
         }
     }
 }
+
+/// Extract the `CHANNEL_ID` of a Bitfinex array channel message payload, eg/ 17082 from
+/// `[17082,[7254.7,0,1]]`.
+fn payload_channel_id(payload: &str) -> Option<u32> {
+    let (channel_id, _) = payload.trim_start().strip_prefix('[')?.split_once(',')?;
+    channel_id.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_channel_id() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<u32>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: book update
+                input: r#"[17082,[7254.7,0,1]]"#,
+                expected: Some(17082),
+            },
+            TestCase {
+                // TC1: heartbeat
+                input: r#"[17082,"hb"]"#,
+                expected: Some(17082),
+            },
+            TestCase {
+                // TC2: platform event
+                input: r#"{"event":"conf"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(
+                payload_channel_id(test.input),
+                test.expected,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{error::DataError, exchange::ExchangeId, subscriber::auth::Credentials};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+/// [`Coinbase`](super::Coinbase) request path signed alongside the timestamp of an authenticated
+/// WebSocket subscription.
+pub const COINBASE_SIGNATURE_PATH: &str = "/users/self/verify";
+
+/// Sign the provided [`Coinbase`](super::Coinbase) subscription request using the provided
+/// [`Credentials`] and the current Unix timestamp, as required by the authenticated channels
+/// (eg/ [`CoinbaseChannel::USER`](super::channel::CoinbaseChannel::USER)).
+///
+/// Fails with a [`DataError::Auth`] if there are no [`Credentials`], rather than sending a
+/// subscription that [`Coinbase`](super::Coinbase) would reject.
+#[allow(clippy::result_large_err)]
+pub fn authenticate(
+    request: &mut Value,
+    credentials: Option<&Credentials>,
+) -> Result<(), DataError> {
+    let credentials = credentials.ok_or_else(|| DataError::Auth {
+        exchange: ExchangeId::Coinbase,
+        reason: "no Credentials are configured, which are required to sign the subscription"
+            .to_string(),
+    })?;
+
+    authenticate_at(
+        request,
+        credentials,
+        &chrono::Utc::now().timestamp().to_string(),
+    )
+}
+
+/// Sign the provided [`Coinbase`](super::Coinbase) subscription request using the provided
+/// [`Credentials`] & Unix timestamp (in seconds), failing with a [`DataError::Auth`] if the
+/// [`Credentials`] have no passphrase or a secret that is not valid Base64.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-auth>
+/// ```json
+/// {
+///     "type": "subscribe",
+///     "product_ids": ["BTC-USD"],
+///     "channels": ["user"],
+///     "signature": "h20qqF42NqRID+o7tUcdukLG0LWk6lAoCX7LEp3FJLE=",
+///     "key": "8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c",
+///     "passphrase": "barter",
+///     "timestamp": "1700000000"
+/// }
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn authenticate_at(
+    request: &mut Value,
+    credentials: &Credentials,
+    timestamp: &str,
+) -> Result<(), DataError> {
+    let auth_error = |reason: &str| DataError::Auth {
+        exchange: ExchangeId::Coinbase,
+        reason: reason.to_string(),
+    };
+
+    let passphrase = credentials.passphrase.as_deref().ok_or_else(|| {
+        auth_error("Credentials have no passphrase, which is required to sign the subscription")
+    })?;
+    let signature = sign(&credentials.secret, timestamp)
+        .ok_or_else(|| auth_error("Credentials secret is not valid Base64"))?;
+
+    request["signature"] = json!(signature);
+    request["key"] = json!(credentials.api_key);
+    request["passphrase"] = json!(passphrase);
+    request["timestamp"] = json!(timestamp);
+    Ok(())
+}
+
+/// Sign the provided Unix timestamp (in seconds) using the provided Base64 encoded API secret,
+/// generating the [`Coinbase`](super::Coinbase) `CB-ACCESS-SIGN`
+/// (Base64(HMAC_SHA256(Base64Decode(secret), timestamp + "GET" + path))).
+///
+/// Returns `None` if the secret is not valid Base64.
+pub fn sign(secret: &str, timestamp: &str) -> Option<String> {
+    let secret = STANDARD.decode(secret).ok()?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(&secret).expect("HMAC can take a key of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(b"GET");
+    mac.update(COINBASE_SIGNATURE_PATH.as_bytes());
+    Some(STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "YmFydGVyLWNvaW5iYXNlLXRlc3Qtc2VjcmV0LTAwMDE=";
+
+    #[test]
+    fn test_coinbase_sign() {
+        struct TestCase {
+            secret: &'static str,
+            timestamp: &'static str,
+            expected: Option<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: integer timestamp
+                secret: SECRET,
+                timestamp: "1700000000",
+                expected: Some("h20qqF42NqRID+o7tUcdukLG0LWk6lAoCX7LEp3FJLE="),
+            },
+            TestCase {
+                // TC1: fractional timestamp
+                secret: SECRET,
+                timestamp: "1700000000.123",
+                expected: Some("t1Z3W9EbyDZa2QbP/9o5dwXIlXhPiEItNIRo8UHZxTM="),
+            },
+            TestCase {
+                // TC2: secret is not Base64
+                secret: "not base64!",
+                timestamp: "1700000000",
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = sign(test.secret, test.timestamp);
+            assert_eq!(actual.as_deref(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_coinbase_authenticate_at() {
+        let credentials = Credentials::new("8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c", SECRET);
+        let request = || {
+            json!({
+                "type": "subscribe",
+                "product_ids": ["BTC-USD"],
+                "channels": ["user"],
+            })
+        };
+
+        // Coinbase requires a passphrase
+        let mut actual = request();
+        assert!(matches!(
+            authenticate_at(&mut actual, &credentials, "1700000000"),
+            Err(DataError::Auth {
+                exchange: ExchangeId::Coinbase,
+                ..
+            })
+        ));
+        assert_eq!(actual, request());
+
+        // Secret must be valid Base64
+        let mut actual = request();
+        assert!(matches!(
+            authenticate_at(
+                &mut actual,
+                &Credentials::new("8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c", "not base64!")
+                    .with_passphrase("barter"),
+                "1700000000"
+            ),
+            Err(DataError::Auth {
+                exchange: ExchangeId::Coinbase,
+                ..
+            })
+        ));
+        assert_eq!(actual, request());
+
+        let mut actual = request();
+        authenticate_at(
+            &mut actual,
+            &credentials.with_passphrase("barter"),
+            "1700000000",
+        )
+        .unwrap();
+        assert_eq!(
+            actual,
+            json!({
+                "type": "subscribe",
+                "product_ids": ["BTC-USD"],
+                "channels": ["user"],
+                "signature": "h20qqF42NqRID+o7tUcdukLG0LWk6lAoCX7LEp3FJLE=",
+                "key": "8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c",
+                "passphrase": "barter",
+                "timestamp": "1700000000",
+            })
+        );
+    }
+}
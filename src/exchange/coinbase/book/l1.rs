@@ -0,0 +1,152 @@
+use super::super::CoinbaseChannel;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{ExchangeId, ExchangeSub},
+    num::Num,
+    subscription::book::{Level, OrderBookL1},
+    Identifier,
+};
+use barter_integration::model::{Exchange, Instrument, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+
+/// [`Coinbase`](super::super::Coinbase) real-time OrderBook Level1 (top of book) message, taken
+/// from the best bid & ask of the [`CoinbaseChannel::TICKER`] channel.
+///
+/// Coinbase has no dedicated top of book channel, and the real-time ticker is published on every
+/// match, so best bid & ask changes without a match are not observed.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#ticker-channel>
+/// ```json
+/// {
+///     "type": "ticker",
+///     "sequence": 37475248783,
+///     "product_id": "ETH-USD",
+///     "price": "1285.22",
+///     "open_24h": "1310.79",
+///     "volume_24h": "245532.79269678",
+///     "low_24h": "1280.52",
+///     "high_24h": "1313.8",
+///     "volume_30d": "9788783.60117027",
+///     "best_bid": "1285.04",
+///     "best_bid_size": "0.46688654",
+///     "best_ask": "1285.27",
+///     "best_ask_size": "1.56637040",
+///     "side": "buy",
+///     "time": "2022-10-19T23:28:22.061769Z",
+///     "trade_id": 370843401,
+///     "last_size": "11.4396987"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseOrderBookL1 {
+    #[serde(alias = "product_id", deserialize_with = "de_l1_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    pub time: DateTime<Utc>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub best_bid: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub best_bid_size: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub best_ask: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub best_ask_size: Num,
+}
+
+impl Identifier<Option<SubscriptionId>> for CoinbaseOrderBookL1 {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, CoinbaseOrderBookL1)>
+    for MarketIter<OrderBookL1>
+{
+    fn from(
+        (exchange_id, instrument, received_time, book): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            CoinbaseOrderBookL1,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: book.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                book.time,
+                TimestampUnit::Microseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: OrderBookL1 {
+                last_update_time: book.time,
+                best_bid: Level::new(book.best_bid, book.best_bid_size),
+                best_ask: Level::new(book.best_ask, book.best_ask_size),
+            },
+            meta: (),
+        })])
+    }
+}
+
+/// Deserialize a [`CoinbaseOrderBookL1`] "product_id" (eg/ "BTC-USD") as the associated
+/// [`SubscriptionId`] (eg/ SubscriptionId("ticker|BTC-USD")).
+pub fn de_l1_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer)
+        .map(|product_id| ExchangeSub::from((CoinbaseChannel::TICKER, product_id)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_de_coinbase_order_book_l1() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<CoinbaseOrderBookL1>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid real-time ticker
+                input: r#"
+                {
+                    "type": "ticker", "sequence": 37475248783, "product_id": "ETH-USD",
+                    "price": "1285.22", "open_24h": "1310.79", "volume_24h": "245532.79269678",
+                    "low_24h": "1280.52", "high_24h": "1313.8", "volume_30d": "9788783.60117027",
+                    "best_bid": "1285.04", "best_bid_size": "0.46688654", "best_ask": "1285.27",
+                    "best_ask_size": "1.56637040", "side": "buy",
+                    "time": "2022-10-19T23:28:22.061769Z", "trade_id": 370843401,
+                    "last_size": "11.4396987"
+                }"#,
+                expected: Some(CoinbaseOrderBookL1 {
+                    subscription_id: SubscriptionId::from("ticker|ETH-USD"),
+                    time: DateTime::parse_from_rfc3339("2022-10-19T23:28:22.061769Z")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                    best_bid: num!(1285.04),
+                    best_bid_size: num!(0.46688654),
+                    best_ask: num!(1285.27),
+                    best_ask_size: num!(1.56637040),
+                }),
+            },
+            TestCase {
+                // TC1: invalid ticker w/o a best bid & ask
+                input: r#"{"type": "ticker", "product_id": "ETH-USD", "price": "1285.22", "time": "2022-10-19T23:28:22.061769Z"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<CoinbaseOrderBookL1>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use super::Coinbase;
 use crate::{
-    subscription::{trade::PublicTrades, Subscription},
+    subscription::{
+        book::OrderBooksL1, fill::Fills, order::OrderUpdates, ticker::Tickers, trade::PublicTrades,
+        Subscription,
+    },
     Identifier,
 };
 use serde::Serialize;
@@ -17,6 +20,25 @@ impl CoinbaseChannel {
     ///
     /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#match>
     pub const TRADES: Self = Self("matches");
+
+    /// [`Coinbase`] real-time ticker channel, updated on every match. Also carries the best bid &
+    /// ask used for [`OrderBooksL1`], since Coinbase has no dedicated top of book channel.
+    ///
+    /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#ticker-channel>
+    pub const TICKER: Self = Self("ticker");
+
+    /// [`Coinbase`] batched ticker channel, updated every 5 seconds. Carries the same payload as
+    /// the [`CoinbaseChannel::TICKER`] channel for a fraction of the bandwidth.
+    ///
+    /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#ticker-batch-channel>
+    pub const TICKER_BATCH: Self = Self("ticker_batch");
+
+    /// [`Coinbase`] authenticated user channel, carrying the full channel messages (eg/ `match`)
+    /// of the orders of the account owning the
+    /// [`Credentials`](crate::subscriber::auth::Credentials) the subscription is signed with.
+    ///
+    /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#user-channel>
+    pub const USER: Self = Self("user");
 }
 
 impl Identifier<CoinbaseChannel> for Subscription<Coinbase, PublicTrades> {
@@ -25,6 +47,30 @@ impl Identifier<CoinbaseChannel> for Subscription<Coinbase, PublicTrades> {
     }
 }
 
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, OrderBooksL1> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::TICKER
+    }
+}
+
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, Tickers> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::TICKER_BATCH
+    }
+}
+
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, OrderUpdates> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::USER
+    }
+}
+
+impl Identifier<CoinbaseChannel> for Subscription<Coinbase, Fills> {
+    fn id(&self) -> CoinbaseChannel {
+        CoinbaseChannel::USER
+    }
+}
+
 impl AsRef<str> for CoinbaseChannel {
     fn as_ref(&self) -> &str {
         self.0
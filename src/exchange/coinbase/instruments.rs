@@ -0,0 +1,145 @@
+use super::HTTP_BASE_URL_COINBASE;
+use crate::{
+    error::DataError,
+    exchange::{
+        alias::canonical_instrument,
+        instruments::{parse_size, InstrumentInfo, MarketStatus},
+        ExchangeId,
+    },
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+};
+use barter_integration::model::InstrumentKind;
+use serde::{Deserialize, Serialize};
+
+/// [`Coinbase`](super::Coinbase) REST `products` response product.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/reference/exchangerestapi_getproducts>
+/// ```json
+/// {
+///     "id": "BTC-USD",
+///     "base_currency": "BTC",
+///     "quote_currency": "USD",
+///     "quote_increment": "0.01",
+///     "base_increment": "0.00000001",
+///     "status": "online",
+///     "trading_disabled": false
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CoinbaseProduct {
+    pub id: String,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub quote_increment: String,
+    pub base_increment: String,
+    pub status: String,
+    #[serde(default)]
+    pub trading_disabled: bool,
+}
+
+impl From<CoinbaseProduct> for InstrumentInfo {
+    fn from(product: CoinbaseProduct) -> Self {
+        let status = match (product.status.as_str(), product.trading_disabled) {
+            ("online", false) => MarketStatus::Trading,
+            ("online", true) => MarketStatus::Halted("trading_disabled".to_string()),
+            _ => MarketStatus::Halted(product.status),
+        };
+
+        Self {
+            exchange: ExchangeId::Coinbase,
+            market: product.id.to_uppercase(),
+            instrument: canonical_instrument(
+                ExchangeId::Coinbase,
+                &product.base_currency,
+                &product.quote_currency,
+                InstrumentKind::Spot,
+            ),
+            tick_size: parse_size(&product.quote_increment),
+            lot_size: parse_size(&product.base_increment),
+            contract_size: None,
+            status,
+        }
+    }
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the [`Coinbase`](super::Coinbase) REST API
+/// `products` endpoint, using the provided [`StreamConfig`].
+pub async fn fetch_instruments(config: &StreamConfig) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_COINBASE);
+    get_json::<Vec<CoinbaseProduct>>(config.proxy.as_ref(), &format!("{base_url}/products"))
+        .await
+        .map(|products| products.into_iter().map(InstrumentInfo::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::Instrument;
+
+    #[test]
+    fn test_coinbase_instrument_info() {
+        let input = r#"
+        [
+            {
+                "id": "BTC-USD", "base_currency": "BTC", "quote_currency": "USD",
+                "quote_increment": "0.01", "base_increment": "0.00000001", "display_name": "BTC/USD",
+                "min_market_funds": "1", "margin_enabled": false, "post_only": false,
+                "limit_only": false, "cancel_only": false, "status": "online",
+                "status_message": "", "trading_disabled": false
+            },
+            {
+                "id": "ETH-USD", "base_currency": "ETH", "quote_currency": "USD",
+                "quote_increment": "0.01", "base_increment": "0.00000001",
+                "status": "online", "trading_disabled": true
+            },
+            {
+                "id": "UST-USD", "base_currency": "UST", "quote_currency": "USD",
+                "quote_increment": "0.0001", "base_increment": "0.01",
+                "status": "delisted", "trading_disabled": true
+            }
+        ]
+        "#;
+
+        let actual = serde_json::from_str::<Vec<CoinbaseProduct>>(input)
+            .unwrap()
+            .into_iter()
+            .map(InstrumentInfo::from)
+            .collect::<Vec<_>>();
+
+        let info = |market: &str, base: &str, tick: &str, lot: &str, status| InstrumentInfo {
+            exchange: ExchangeId::Coinbase,
+            market: market.to_string(),
+            instrument: Instrument::from((base, "usd", InstrumentKind::Spot)),
+            tick_size: parse_size(tick),
+            lot_size: parse_size(lot),
+            contract_size: None,
+            status,
+        };
+        let expected = vec![
+            info(
+                "BTC-USD",
+                "btc",
+                "0.01",
+                "0.00000001",
+                MarketStatus::Trading,
+            ),
+            info(
+                "ETH-USD",
+                "eth",
+                "0.01",
+                "0.00000001",
+                MarketStatus::Halted("trading_disabled".to_string()),
+            ),
+            info(
+                "UST-USD",
+                "ust",
+                "0.0001",
+                "0.01",
+                MarketStatus::Halted("delisted".to_string()),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
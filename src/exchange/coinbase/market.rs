@@ -1,5 +1,14 @@
 use super::Coinbase;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
@@ -11,7 +20,8 @@ pub struct CoinbaseMarket(pub String);
 
 impl<Kind> Identifier<CoinbaseMarket> for Subscription<Coinbase, Kind> {
     fn id(&self) -> CoinbaseMarket {
-        CoinbaseMarket(format!("{}-{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Coinbase::ID, &self.instrument);
+        CoinbaseMarket(format!("{base}-{quote}").to_uppercase())
     }
 }
 
@@ -20,3 +30,22 @@ impl AsRef<str> for CoinbaseMarket {
         &self.0
     }
 }
+
+impl ParseMarket for Coinbase {
+    /// Coinbase markets are formatted as "BASE-QUOTE" (eg/ "BTC-USD").
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        match market.split_once('-') {
+            Some((base, quote))
+                if !base.is_empty() && !quote.is_empty() && !quote.contains('-') =>
+            {
+                Ok(canonical_instrument(
+                    Self::ID,
+                    base,
+                    quote,
+                    InstrumentKind::Spot,
+                ))
+            }
+            _ => Err(ParseMarketError::format(Self::ID, market)),
+        }
+    }
+}
@@ -1,11 +1,24 @@
 use self::{
-    channel::CoinbaseChannel, market::CoinbaseMarket, subscription::CoinbaseSubResponse,
+    book::l1::CoinbaseOrderBookL1,
+    channel::CoinbaseChannel,
+    market::CoinbaseMarket,
+    subscription::CoinbaseSubResponse,
+    ticker::{CoinbaseTicker, COINBASE_TICKER_WINDOW},
     trade::CoinbaseTrade,
+    user::{CoinbaseOrderUpdatesTransformer, CoinbaseUserMessage},
 };
 use crate::{
-    exchange::{Connector, ExchangeId, ExchangeSub, StreamSelector},
-    subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::trade::PublicTrades,
+    error::DataError,
+    exchange::{status::ExchangeStatus, Connector, ExchangeId, ExchangeSub, StreamSelector},
+    streams::config::StreamConfig,
+    subscriber::{auth::Credentials, validator::WebSocketSubValidator, WebSocketSubscriber},
+    subscription::{
+        book::OrderBooksL1,
+        fill::Fills,
+        order::OrderUpdates,
+        ticker::{TickerUpdates, Tickers},
+        trade::PublicTrades,
+    },
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
@@ -14,26 +27,99 @@ use barter_macro::{DeExchange, SerExchange};
 use serde_json::json;
 use url::Url;
 
+/// Signing of authenticated [`Coinbase`] subscriptions (eg/ to the
+/// [`CoinbaseChannel::USER`] channel) using the
+/// [`Credentials`](crate::subscriber::auth::Credentials).
+pub mod auth;
+
+/// OrderBook types for [`Coinbase`].
+pub mod book;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
 
+/// [`InstrumentInfo`](crate::exchange::instruments::InstrumentInfo)s listed by the [`Coinbase`] REST
+/// API `products`.
+pub mod instruments;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
 
+/// [`CoinbaseStatus`](status::CoinbaseStatus) `status` channel type for [`Coinbase`].
+pub mod status;
+
 /// [`Subscription`](crate::subscription::Subscription) response type and response
 /// [`Validator`](barter_integration::Validator) for [`Coinbase`].
 pub mod subscription;
 
+/// Rolling 24 hour [`Tickers`] types for [`Coinbase`].
+pub mod ticker;
+
 /// Public trade types for [`Coinbase`].
 pub mod trade;
 
+/// Private [`OrderUpdates`] & [`Fills`] types for [`Coinbase`], normalised from the authenticated
+/// [`CoinbaseChannel::USER`] channel.
+pub mod user;
+
 /// [`Coinbase`] server base url.
 ///
 /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview>
 pub const BASE_URL_COINBASE: &str = "wss://ws-feed.exchange.coinbase.com";
 
+/// [`Coinbase`] REST API base url.
+///
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/rest-requests>
+pub const HTTP_BASE_URL_COINBASE: &str = "https://api.exchange.coinbase.com";
+
+/// Exchange message fields [`Coinbase`] models intentionally don't deserialise, see
+/// [`IgnoredFields`](crate::parser::strict::IgnoredFields).
+pub const IGNORED_FIELDS: crate::parser::strict::IgnoredFields = &[
+    (
+        "CoinbaseTrade",
+        &["type", "sequence", "maker_order_id", "taker_order_id"],
+    ),
+    (
+        "CoinbaseTicker",
+        &[
+            "type",
+            "sequence",
+            "volume_30d",
+            "best_bid",
+            "best_bid_size",
+            "best_ask",
+            "best_ask_size",
+            "side",
+            "trade_id",
+            "last_size",
+        ],
+    ),
+    (
+        "CoinbaseOrderBookL1",
+        &[
+            "type",
+            "sequence",
+            "price",
+            "open_24h",
+            "volume_24h",
+            "low_24h",
+            "high_24h",
+            "volume_30d",
+            "side",
+            "trade_id",
+            "last_size",
+        ],
+    ),
+];
+
+/// Optional exchange message fields [`Coinbase`] is expected to send, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields). None yet, since the optional
+/// fields of [`Coinbase`] models are those of the internally tagged
+/// [`CoinbaseUserMessage`], which strict mode does not check.
+pub const EXPECTED_FIELDS: crate::parser::strict::ExpectedFields = &[];
+
 /// [`Coinbase`] exchange.
 ///
 /// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview>
@@ -57,20 +143,262 @@ impl Connector for Coinbase {
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
         exchange_subs
             .into_iter()
-            .map(|ExchangeSub { channel, market }| {
+            .map(|exchange_sub| {
                 WsMessage::Text(
-                    json!({
-                        "type": "subscribe",
-                        "product_ids": [market.as_ref()],
-                        "channels": [channel.as_ref()],
-                    })
-                    .to_string(),
+                    subscribe_request(exchange_sub, TickerUpdates::default()).to_string(),
                 )
             })
             .collect()
     }
+
+    fn subscribe_requests(
+        &self,
+        exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        config: &StreamConfig,
+    ) -> Result<Vec<WsMessage>, DataError> {
+        subscribe_requests(
+            exchange_subs,
+            config.ticker_updates,
+            config.credentials.as_ref(),
+        )
+    }
+
+    fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+        status::exchange_status(payload)
+    }
+}
+
+/// Construct the [`Coinbase`] subscribe requests of the provided [`ExchangeSub`]s, signing those
+/// of the [`CoinbaseChannel::USER`] channel with the optional [`Credentials`].
+///
+/// Fails with a [`DataError::Auth`] if a [`CoinbaseChannel::USER`] subscription cannot be signed
+/// (eg/ there are no [`Credentials`]).
+#[allow(clippy::result_large_err)]
+fn subscribe_requests(
+    exchange_subs: Vec<ExchangeSub<CoinbaseChannel, CoinbaseMarket>>,
+    ticker_updates: TickerUpdates,
+    credentials: Option<&Credentials>,
+) -> Result<Vec<WsMessage>, DataError> {
+    exchange_subs
+        .into_iter()
+        .map(|exchange_sub| {
+            let authenticated = exchange_sub.channel == CoinbaseChannel::USER;
+            let mut request = subscribe_request(exchange_sub, ticker_updates);
+
+            if authenticated {
+                auth::authenticate(&mut request, credentials)?;
+            }
+
+            Ok(WsMessage::Text(request.to_string()))
+        })
+        .collect()
+}
+
+/// Construct the unsigned [`Coinbase`] subscribe request of the provided [`ExchangeSub`].
+///
+/// [`Tickers`] subscriptions are subscribed to the [`CoinbaseChannel::TICKER`] channel instead
+/// of the default [`CoinbaseChannel::TICKER_BATCH`] channel if [`TickerUpdates::Realtime`] are
+/// selected.
+fn subscribe_request(
+    ExchangeSub { channel, market }: ExchangeSub<CoinbaseChannel, CoinbaseMarket>,
+    ticker_updates: TickerUpdates,
+) -> serde_json::Value {
+    let channel = match (channel, ticker_updates) {
+        (CoinbaseChannel::TICKER_BATCH, TickerUpdates::Realtime) => CoinbaseChannel::TICKER,
+        (channel, _) => channel,
+    };
+
+    json!({
+        "type": "subscribe",
+        "product_ids": [market.as_ref()],
+        "channels": [channel.as_ref()],
+    })
 }
 
 impl StreamSelector<PublicTrades> for Coinbase {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, CoinbaseTrade>>;
 }
+
+impl StreamSelector<OrderBooksL1> for Coinbase {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, OrderBooksL1, CoinbaseOrderBookL1>>;
+}
+
+impl StreamSelector<Tickers> for Coinbase {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, Tickers, CoinbaseTicker>>;
+
+    fn unsupported(kind: &Tickers) -> Option<String> {
+        (kind.0 != COINBASE_TICKER_WINDOW).then(|| {
+            format!(
+                "{} does not support {kind} rolling window tickers, supported windows: {}",
+                ExchangeId::Coinbase,
+                COINBASE_TICKER_WINDOW.as_str()
+            )
+        })
+    }
+}
+
+impl StreamSelector<OrderUpdates> for Coinbase {
+    type Stream = ExchangeWsStream<CoinbaseOrderUpdatesTransformer>;
+}
+
+impl StreamSelector<Fills> for Coinbase {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, Fills, CoinbaseUserMessage>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coinbase_requests() {
+        struct TestCase {
+            channel: CoinbaseChannel,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: real-time trades channel
+                channel: CoinbaseChannel::TRADES,
+                expected: r#"{"channels":["matches"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+            TestCase {
+                // TC1: real-time ticker channel
+                channel: CoinbaseChannel::TICKER,
+                expected: r#"{"channels":["ticker"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+            TestCase {
+                // TC2: batched ticker channel
+                channel: CoinbaseChannel::TICKER_BATCH,
+                expected: r#"{"channels":["ticker_batch"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+            TestCase {
+                // TC3: authenticated user channel is unsigned w/o the StreamConfig Credentials
+                channel: CoinbaseChannel::USER,
+                expected: r#"{"channels":["user"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Coinbase::requests(vec![ExchangeSub {
+                channel: test.channel,
+                market: CoinbaseMarket("BTC-USD".to_string()),
+            }]);
+            assert_eq!(
+                actual,
+                vec![WsMessage::Text(test.expected.to_string())],
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_coinbase_subscribe_requests_ticker_updates() {
+        struct TestCase {
+            ticker_updates: TickerUpdates,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: batched ticker updates subscribe to the ticker_batch channel
+                ticker_updates: TickerUpdates::Batched,
+                expected: r#"{"channels":["ticker_batch"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+            TestCase {
+                // TC1: real-time ticker updates subscribe to the ticker channel
+                ticker_updates: TickerUpdates::Realtime,
+                expected: r#"{"channels":["ticker"],"product_ids":["BTC-USD"],"type":"subscribe"}"#,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let config = StreamConfig {
+                ticker_updates: test.ticker_updates,
+                ..StreamConfig::default()
+            };
+
+            let actual = Coinbase
+                .subscribe_requests(
+                    vec![ExchangeSub {
+                        channel: CoinbaseChannel::TICKER_BATCH,
+                        market: CoinbaseMarket("BTC-USD".to_string()),
+                    }],
+                    &config,
+                )
+                .unwrap();
+            assert_eq!(
+                actual,
+                vec![WsMessage::Text(test.expected.to_string())],
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_coinbase_tickers_unsupported() {
+        use crate::subscription::candle::Interval;
+
+        assert_eq!(
+            <Coinbase as StreamSelector<Tickers>>::unsupported(&Tickers(Interval::D1)),
+            None
+        );
+        assert_eq!(
+            <Coinbase as StreamSelector<Tickers>>::unsupported(&Tickers(Interval::H4)),
+            Some(
+                "coinbase does not support tickers_4h rolling window tickers, supported windows: 1d"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_coinbase_subscribe_requests_signs_user_channel() {
+        let config = StreamConfig {
+            credentials: Some(
+                Credentials::new("8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c", "c2VjcmV0")
+                    .with_passphrase("barter"),
+            ),
+            ..StreamConfig::default()
+        };
+
+        let actual = Coinbase
+            .subscribe_requests(
+                vec![ExchangeSub {
+                    channel: CoinbaseChannel::USER,
+                    market: CoinbaseMarket("BTC-USD".to_string()),
+                }],
+                &config,
+            )
+            .unwrap();
+
+        let WsMessage::Text(actual) = &actual[0] else {
+            panic!("request is not WsMessage::Text");
+        };
+        let actual = serde_json::from_str::<serde_json::Value>(actual).unwrap();
+        assert_eq!(actual["key"], "8a8a1e2b4a1f4f7e9b3c2d1e0f9a8b7c");
+        assert_eq!(actual["passphrase"], "barter");
+        assert!(actual["signature"].is_string());
+    }
+
+    #[test]
+    fn test_coinbase_subscribe_requests_user_channel_without_credentials() {
+        let actual = Coinbase.subscribe_requests(
+            vec![ExchangeSub {
+                channel: CoinbaseChannel::USER,
+                market: CoinbaseMarket("BTC-USD".to_string()),
+            }],
+            &StreamConfig::default(),
+        );
+
+        assert!(matches!(
+            actual,
+            Err(DataError::Auth {
+                exchange: ExchangeId::Coinbase,
+                ..
+            })
+        ));
+    }
+}
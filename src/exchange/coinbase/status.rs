@@ -0,0 +1,148 @@
+use crate::exchange::status::{ExchangeStatus, SystemStatus};
+use serde::{Deserialize, Serialize};
+
+/// [`Coinbase`](super::Coinbase) `status` channel message, sent on subscription and
+/// periodically thereafter.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#status-channel>
+/// ```json
+/// {
+///     "type": "status",
+///     "products": [
+///         {
+///             "id": "BTC-USD",
+///             "base_currency": "BTC",
+///             "quote_currency": "USD",
+///             "display_name": "BTC/USD",
+///             "status": "online",
+///             "status_message": null,
+///             "post_only": false,
+///             "limit_only": false,
+///             "cancel_only": false
+///         }
+///     ],
+///     "currencies": []
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "status")]
+pub struct CoinbaseStatus {
+    pub products: Vec<CoinbaseProductStatus>,
+}
+
+/// [`Coinbase`](super::Coinbase) product status contained in a [`CoinbaseStatus`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct CoinbaseProductStatus {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub status_message: Option<String>,
+    #[serde(default)]
+    pub cancel_only: bool,
+    #[serde(default)]
+    pub limit_only: bool,
+    #[serde(default)]
+    pub post_only: bool,
+}
+
+impl From<CoinbaseStatus> for ExchangeStatus {
+    fn from(status: CoinbaseStatus) -> Self {
+        let products = &status.products;
+        let all = |restricted: fn(&CoinbaseProductStatus) -> bool| {
+            !products.is_empty() && products.iter().all(restricted)
+        };
+
+        // Coinbase is only considered under maintenance once every product is offline
+        let system = if !products.is_empty() && products.iter().all(|p| p.status != "online") {
+            SystemStatus::Maintenance
+        } else if all(|p| p.cancel_only) {
+            SystemStatus::CancelOnly
+        } else if all(|p| p.limit_only) {
+            SystemStatus::LimitOnly
+        } else if all(|p| p.post_only) {
+            SystemStatus::PostOnly
+        } else {
+            SystemStatus::Online
+        };
+
+        let message = status
+            .products
+            .into_iter()
+            .filter_map(|product| product.status_message)
+            .find(|message| !message.is_empty());
+
+        ExchangeStatus::new(system, message)
+    }
+}
+
+/// Recognise a [`Coinbase`](super::Coinbase) `status` channel message as an [`ExchangeStatus`].
+///
+/// See [`Connector::exchange_status`](crate::exchange::Connector::exchange_status).
+pub fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+    // Avoid deserialising every market data message
+    if !payload.contains(r#""status""#) || !payload.contains(r#""products""#) {
+        return None;
+    }
+
+    serde_json::from_str::<CoinbaseStatus>(payload)
+        .ok()
+        .map(ExchangeStatus::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coinbase_exchange_status() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<ExchangeStatus>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: all products online
+                input: r#"{"type":"status","products":[{"id":"BTC-USD","base_currency":"BTC","quote_currency":"USD","display_name":"BTC/USD","status":"online","status_message":null,"post_only":false,"limit_only":false,"cancel_only":false},{"id":"ETH-USD","status":"online","status_message":""}],"currencies":[]}"#,
+                expected: Some(ExchangeStatus::new(SystemStatus::Online, None::<String>)),
+            },
+            TestCase {
+                // TC1: all products offline for maintenance
+                input: r#"{"type":"status","products":[{"id":"BTC-USD","status":"offline","status_message":"Scheduled maintenance"},{"id":"ETH-USD","status":"delisted","status_message":null}],"currencies":[]}"#,
+                expected: Some(ExchangeStatus::new(
+                    SystemStatus::Maintenance,
+                    Some("Scheduled maintenance"),
+                )),
+            },
+            TestCase {
+                // TC2: single product offline is not exchange maintenance
+                input: r#"{"type":"status","products":[{"id":"BTC-USD","status":"offline","status_message":"Delisting"},{"id":"ETH-USD","status":"online"}],"currencies":[]}"#,
+                expected: Some(ExchangeStatus::new(SystemStatus::Online, Some("Delisting"))),
+            },
+            TestCase {
+                // TC3: all products cancel_only
+                input: r#"{"type":"status","products":[{"id":"BTC-USD","status":"online","cancel_only":true},{"id":"ETH-USD","status":"online","cancel_only":true}],"currencies":[]}"#,
+                expected: Some(ExchangeStatus::new(
+                    SystemStatus::CancelOnly,
+                    None::<String>,
+                )),
+            },
+            TestCase {
+                // TC4: all products post_only
+                input: r#"{"type":"status","products":[{"id":"BTC-USD","status":"online","post_only":true}],"currencies":[]}"#,
+                expected: Some(ExchangeStatus::new(SystemStatus::PostOnly, None::<String>)),
+            },
+            TestCase {
+                // TC5: match is not a status message
+                input: r#"{"type":"match","trade_id":10,"sequence":50,"maker_order_id":"ac928c66","taker_order_id":"132fb6ae","time":"2014-11-07T08:19:27.028459Z","product_id":"BTC-USD","size":"5.23512","price":"400.23","side":"sell"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = exchange_status(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -96,7 +96,24 @@ mod tests {
                     }),
                 },
                 TestCase {
-                    // TC1: input response is failed subscription
+                    // TC1: input response is Subscribed to the batched ticker channel
+                    input: r#"
+                    {
+                        "type":"subscriptions",
+                        "channels":[
+                            {"name":"ticker_batch","product_ids":["BTC-USD"]}
+                        ]
+                    }
+                    "#,
+                    expected: Ok(CoinbaseSubResponse::Subscribed {
+                        channels: vec![CoinbaseChannels {
+                            channel: "ticker_batch".to_string(),
+                            product_ids: vec!["BTC-USD".to_string()],
+                        }],
+                    }),
+                },
+                TestCase {
+                    // TC2: input response is failed subscription
                     input: r#"
                     {
                         "type":"error",
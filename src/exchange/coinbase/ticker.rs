@@ -0,0 +1,253 @@
+use super::CoinbaseChannel;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{ExchangeId, ExchangeSub},
+    num::{self, Num},
+    subscription::{candle::Interval, ticker::Ticker},
+    Identifier,
+};
+use barter_integration::model::{Exchange, Instrument, SubscriptionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+
+/// Rolling window of every [`Coinbase`](super::Coinbase) ticker.
+pub const COINBASE_TICKER_WINDOW: Interval = Interval::D1;
+
+/// [`Coinbase`](super::Coinbase) rolling 24 hour ticker message, received on both the
+/// [`CoinbaseChannel::TICKER`] & [`CoinbaseChannel::TICKER_BATCH`] channels.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#ticker-channel>
+/// ```json
+/// {
+///     "type": "ticker",
+///     "sequence": 37475248783,
+///     "product_id": "ETH-USD",
+///     "price": "1285.22",
+///     "open_24h": "1310.79",
+///     "volume_24h": "245532.79269678",
+///     "low_24h": "1280.52",
+///     "high_24h": "1313.8",
+///     "volume_30d": "9788783.60117027",
+///     "best_bid": "1285.04",
+///     "best_bid_size": "0.46688654",
+///     "best_ask": "1285.27",
+///     "best_ask_size": "1.56637040",
+///     "side": "buy",
+///     "time": "2022-10-19T23:28:22.061769Z",
+///     "trade_id": 370843401,
+///     "last_size": "11.4396987"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTicker {
+    #[serde(alias = "product_id", deserialize_with = "de_ticker_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    pub time: DateTime<Utc>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(
+        alias = "open_24h",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub open: Num,
+    #[serde(
+        alias = "high_24h",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub high: Num,
+    #[serde(alias = "low_24h", deserialize_with = "barter_integration::de::de_str")]
+    pub low: Num,
+    #[serde(
+        alias = "volume_24h",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub volume: Num,
+}
+
+impl Identifier<Option<SubscriptionId>> for CoinbaseTicker {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, CoinbaseTicker)> for MarketIter<Ticker> {
+    fn from(
+        (exchange_id, instrument, received_time, ticker): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            CoinbaseTicker,
+        ),
+    ) -> Self {
+        let price_change = ticker.price - ticker.open;
+        let price_change_percent = if ticker.open == num::zero() {
+            num::zero()
+        } else {
+            price_change / ticker.open * Num::from(100u8)
+        };
+
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: ticker.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                ticker.time,
+                TimestampUnit::Microseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: Ticker {
+                window: COINBASE_TICKER_WINDOW,
+                open_time: ticker.time - chrono::Duration::days(1),
+                close_time: ticker.time,
+                open: ticker.open,
+                high: ticker.high,
+                low: ticker.low,
+                close: ticker.price,
+                vwap: None,
+                volume: ticker.volume,
+                quote_volume: None,
+                price_change,
+                price_change_percent,
+                trade_count: None,
+            },
+            meta: (),
+        })])
+    }
+}
+
+/// Deserialize a [`CoinbaseTicker`] "product_id" (eg/ "BTC-USD") as the associated
+/// [`SubscriptionId`] (eg/ SubscriptionId("ticker_batch|BTC-USD")).
+///
+/// Both ticker channels are identified by the [`CoinbaseChannel::TICKER_BATCH`] channel of the
+/// [`Tickers`](crate::subscription::ticker::Tickers) subscriptions, since they carry the same
+/// messages.
+pub fn de_ticker_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    <&str as Deserialize>::deserialize(deserializer)
+        .map(|product_id| ExchangeSub::from((CoinbaseChannel::TICKER_BATCH, product_id)).id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_de_coinbase_ticker() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<CoinbaseTicker>,
+        }
+
+        let time = DateTime::parse_from_rfc3339("2022-10-19T23:28:22.061769Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid real-time ticker
+                input: r#"
+                {
+                    "type": "ticker", "sequence": 37475248783, "product_id": "ETH-USD",
+                    "price": "1285.22", "open_24h": "1310.79", "volume_24h": "245532.79269678",
+                    "low_24h": "1280.52", "high_24h": "1313.8", "volume_30d": "9788783.60117027",
+                    "best_bid": "1285.04", "best_bid_size": "0.46688654", "best_ask": "1285.27",
+                    "best_ask_size": "1.56637040", "side": "buy",
+                    "time": "2022-10-19T23:28:22.061769Z", "trade_id": 370843401,
+                    "last_size": "11.4396987"
+                }"#,
+                expected: Some(CoinbaseTicker {
+                    subscription_id: SubscriptionId::from("ticker_batch|ETH-USD"),
+                    time,
+                    price: num!(1285.22),
+                    open: num!(1310.79),
+                    high: num!(1313.8),
+                    low: num!(1280.52),
+                    volume: num!(245532.79269678),
+                }),
+            },
+            TestCase {
+                // TC1: valid initial ticker w/o the latest trade fields
+                input: r#"
+                {
+                    "type": "ticker", "sequence": 37475248783, "product_id": "ETH-USD",
+                    "price": "1285.22", "open_24h": "1310.79", "volume_24h": "245532.79269678",
+                    "low_24h": "1280.52", "high_24h": "1313.8", "volume_30d": "9788783.60117027",
+                    "best_bid": "1285.04", "best_bid_size": "0.46688654", "best_ask": "1285.27",
+                    "best_ask_size": "1.56637040", "time": "2022-10-19T23:28:22.061769Z"
+                }"#,
+                expected: Some(CoinbaseTicker {
+                    subscription_id: SubscriptionId::from("ticker_batch|ETH-USD"),
+                    time,
+                    price: num!(1285.22),
+                    open: num!(1310.79),
+                    high: num!(1313.8),
+                    low: num!(1280.52),
+                    volume: num!(245532.79269678),
+                }),
+            },
+            TestCase {
+                // TC2: invalid ticker w/o a price
+                input: r#"{"type": "ticker", "product_id": "ETH-USD", "time": "2022-10-19T23:28:22.061769Z"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::from_str::<CoinbaseTicker>(test.input).ok();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_coinbase_ticker_normalise() {
+        let time = DateTime::parse_from_rfc3339("2022-10-19T23:28:22.061769Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let ticker = CoinbaseTicker {
+            subscription_id: SubscriptionId::from("ticker_batch|ETH-USD"),
+            time,
+            price: num!(1100.0),
+            open: num!(1000.0),
+            high: num!(1200.0),
+            low: num!(900.0),
+            volume: num!(10.0),
+        };
+
+        let MarketIter(events) = MarketIter::<Ticker>::from((
+            ExchangeId::Coinbase,
+            Instrument::from((
+                "eth",
+                "usd",
+                barter_integration::model::InstrumentKind::Spot,
+            )),
+            Utc::now(),
+            ticker,
+        ));
+
+        let actual = events.into_iter().next().unwrap().unwrap().kind;
+        assert_eq!(
+            actual,
+            Ticker {
+                window: Interval::D1,
+                open_time: time - chrono::Duration::days(1),
+                close_time: time,
+                open: num!(1000.0),
+                high: num!(1200.0),
+                low: num!(900.0),
+                close: num!(1100.0),
+                vwap: None,
+                volume: num!(10.0),
+                quote_volume: None,
+                price_change: num!(100.0),
+                price_change_percent: num!(10.0),
+                trade_count: None,
+            }
+        );
+    }
+}
@@ -1,13 +1,15 @@
 use super::CoinbaseChannel;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, Side, SubscriptionId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Coinbase real-time trade WebSocket message.
 ///
@@ -36,9 +38,9 @@ pub struct CoinbaseTrade {
     pub id: u64,
     pub time: DateTime<Utc>,
     #[serde(alias = "size", deserialize_with = "barter_integration::de::de_str")]
-    pub amount: f64,
+    pub amount: Num,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     pub side: Side,
 }
 
@@ -48,11 +50,22 @@ impl Identifier<Option<SubscriptionId>> for CoinbaseTrade {
     }
 }
 
-impl From<(ExchangeId, Instrument, CoinbaseTrade)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, CoinbaseTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, CoinbaseTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            CoinbaseTrade,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: trade.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Microseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: PublicTrade {
@@ -61,6 +74,7 @@ impl From<(ExchangeId, Instrument, CoinbaseTrade)> for MarketIter<PublicTrade> {
                 amount: trade.amount,
                 side: trade.side,
             },
+            meta: (),
         })])
     }
 }
@@ -78,6 +92,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
     use barter_integration::error::SocketError;
     use chrono::NaiveDateTime;
     use serde::de::Error;
@@ -112,10 +127,10 @@ mod tests {
                 expected: Ok(CoinbaseTrade {
                     subscription_id: SubscriptionId::from("matches|BTC-USD"),
                     id: 10,
-                    price: 400.23,
-                    amount: 5.23512,
+                    price: num!(400.23),
+                    amount: num!(5.23512),
                     side: Side::Sell,
-                    time: DateTime::from_utc(
+                    time: DateTime::from_naive_utc_and_offset(
                         NaiveDateTime::from_str("2014-11-07T08:19:27.028459").unwrap(),
                         Utc,
                     ),
@@ -0,0 +1,705 @@
+use super::{channel::CoinbaseChannel, Coinbase};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, MarketEvents, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::{Connector, ExchangeId, ExchangeSub},
+    num::{self, de_optional_str, Num},
+    streams::config::StreamConfig,
+    subscription::{
+        fill::{Fill, Liquidity},
+        order::{OrderStatus, OrderUpdate, OrderUpdates},
+        Map,
+    },
+    transformer::ExchangeTransformer,
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    model::{Exchange, Instrument, Side, SubscriptionId},
+    protocol::websocket::WsMessage,
+    Transformer,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// [`Coinbase`] authenticated user channel message, ie/ a full channel message concerning one of
+/// the orders of the authenticated account.
+///
+/// Only the `received`, `open`, `match` & `done` messages are normalised, every other message
+/// (eg/ `change`) is [`CoinbaseUserMessage::Other`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#user-channel>
+/// #### Received
+/// ```json
+/// {
+///     "type": "received",
+///     "time": "2014-11-07T08:19:27.028459Z",
+///     "product_id": "BTC-USD",
+///     "sequence": 10,
+///     "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+///     "size": "1.34",
+///     "price": "502.1",
+///     "side": "buy",
+///     "order_type": "limit",
+///     "client_oid": "d50ec974-76a2-454b-66f135b1ea8c"
+/// }
+/// ```
+/// #### Open
+/// ```json
+/// {
+///     "type": "open",
+///     "time": "2014-11-07T08:19:27.028459Z",
+///     "product_id": "BTC-USD",
+///     "sequence": 11,
+///     "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+///     "price": "502.1",
+///     "remaining_size": "1.34",
+///     "side": "buy"
+/// }
+/// ```
+/// #### Match
+/// ```json
+/// {
+///     "type": "match",
+///     "trade_id": 10,
+///     "sequence": 50,
+///     "maker_order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+///     "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+///     "time": "2014-11-07T08:19:28.464459Z",
+///     "product_id": "BTC-USD",
+///     "size": "0.34",
+///     "price": "502.1",
+///     "side": "buy",
+///     "maker_user_id": "5844eceecf7e803e259d0365",
+///     "user_id": "5844eceecf7e803e259d0365",
+///     "maker_profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352",
+///     "profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352",
+///     "maker_fee_rate": "0.001"
+/// }
+/// ```
+/// #### Done
+/// ```json
+/// {
+///     "type": "done",
+///     "time": "2014-11-07T08:19:29.028459Z",
+///     "product_id": "BTC-USD",
+///     "sequence": 51,
+///     "price": "502.1",
+///     "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+///     "reason": "canceled",
+///     "side": "buy",
+///     "remaining_size": "1.0"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoinbaseUserMessage {
+    Received(CoinbaseUserReceived),
+    Open(CoinbaseUserOpen),
+    Match(CoinbaseUserMatch),
+    Done(CoinbaseUserDone),
+    #[serde(other)]
+    Other,
+}
+
+/// [`CoinbaseUserMessage::Received`] order accepted by the matching engine.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseUserReceived {
+    pub product_id: String,
+    pub time: DateTime<Utc>,
+    pub order_id: String,
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub client_oid: Option<String>,
+    /// Order size, `None` for market orders placed by funds.
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub size: Option<Num>,
+    /// Limit price, `None` for market orders.
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub price: Option<Num>,
+    pub side: Side,
+}
+
+/// [`CoinbaseUserMessage::Open`] order resting on the OrderBook.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseUserOpen {
+    pub product_id: String,
+    pub time: DateTime<Utc>,
+    pub order_id: String,
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub price: Option<Num>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub remaining_size: Num,
+    pub side: Side,
+}
+
+/// [`CoinbaseUserMessage::Match`] trade, involving the authenticated account as the maker if it
+/// has a `maker_user_id`, or the taker if it has a `taker_user_id`.
+///
+/// Note: the `side` is the side of the maker order.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseUserMatch {
+    pub product_id: String,
+    pub time: DateTime<Utc>,
+    pub trade_id: u64,
+    pub maker_order_id: String,
+    pub taker_order_id: String,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub size: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    pub side: Side,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub maker_user_id: Option<String>,
+    #[serde(default)]
+    pub taker_user_id: Option<String>,
+    /// Fee rate charged to the authenticated account, only included if it's the maker.
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub maker_fee_rate: Option<Num>,
+    /// Fee rate charged to the authenticated account, only included if it's the taker.
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub taker_fee_rate: Option<Num>,
+}
+
+/// [`CoinbaseUserMessage::Done`] order no longer on the OrderBook, either filled or canceled.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseUserDone {
+    pub product_id: String,
+    pub time: DateTime<Utc>,
+    pub order_id: String,
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub price: Option<Num>,
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub remaining_size: Option<Num>,
+    pub reason: CoinbaseDoneReason,
+    /// Reason a canceled order was cancelled by [`Coinbase`] (eg/ "101:Time In Force"), if any.
+    #[serde(default)]
+    pub cancel_reason: Option<String>,
+    pub side: Side,
+}
+
+/// [`CoinbaseUserDone`] "reason".
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinbaseDoneReason {
+    Filled,
+    Canceled,
+}
+
+impl From<CoinbaseDoneReason> for OrderStatus {
+    fn from(reason: CoinbaseDoneReason) -> Self {
+        match reason {
+            CoinbaseDoneReason::Filled => OrderStatus::Filled,
+            CoinbaseDoneReason::Canceled => OrderStatus::Cancelled,
+        }
+    }
+}
+
+impl CoinbaseUserMatch {
+    /// [`Liquidity`] the authenticated account provided, & the id & [`Side`] of it's order, or
+    /// `None` if the match does not involve the authenticated account.
+    pub fn own_order(&self) -> Option<(Liquidity, &str, Side)> {
+        let is_own = |order_user_id: &Option<String>| match (order_user_id, &self.user_id) {
+            (Some(order_user_id), Some(user_id)) => order_user_id == user_id,
+            (order_user_id, None) => order_user_id.is_some(),
+            (None, Some(_)) => false,
+        };
+
+        if is_own(&self.maker_user_id) {
+            Some((Liquidity::Maker, &self.maker_order_id, self.side))
+        } else if is_own(&self.taker_user_id) {
+            let side = match self.side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            Some((Liquidity::Taker, &self.taker_order_id, side))
+        } else {
+            None
+        }
+    }
+
+    /// Fee charged to the authenticated account for the provided [`Liquidity`] of it's order, in
+    /// the quote asset, if the fee rate is included.
+    pub fn fee(&self, liquidity: Liquidity) -> Option<Num> {
+        let rate = match liquidity {
+            Liquidity::Maker => self.maker_fee_rate,
+            Liquidity::Taker => self.taker_fee_rate,
+        }?;
+        Some(rate * (self.price * self.size))
+    }
+}
+
+impl CoinbaseUserMessage {
+    /// Exchange time of the [`CoinbaseUserMessage`], if it's normalised.
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Received(received) => Some(received.time),
+            Self::Open(open) => Some(open.time),
+            Self::Match(trade) => Some(trade.time),
+            Self::Done(done) => Some(done.time),
+            Self::Other => None,
+        }
+    }
+
+    /// "product_id" (eg/ "BTC-USD") of the [`CoinbaseUserMessage`], if it's normalised.
+    pub fn product_id(&self) -> Option<&str> {
+        match self {
+            Self::Received(received) => Some(&received.product_id),
+            Self::Open(open) => Some(&open.product_id),
+            Self::Match(trade) => Some(&trade.product_id),
+            Self::Done(done) => Some(&done.product_id),
+            Self::Other => None,
+        }
+    }
+}
+
+impl Identifier<Option<SubscriptionId>> for CoinbaseUserMessage {
+    fn id(&self) -> Option<SubscriptionId> {
+        self.product_id()
+            .map(|product_id| ExchangeSub::from((CoinbaseChannel::USER, product_id)).id())
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, CoinbaseUserMessage)> for MarketIter<Fill> {
+    fn from(
+        (exchange_id, instrument, received_time, message): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            CoinbaseUserMessage,
+        ),
+    ) -> Self {
+        let CoinbaseUserMessage::Match(trade) = message else {
+            return Self(smallvec![]);
+        };
+        let Some((liquidity, order_id, side)) = trade.own_order() else {
+            return Self(smallvec![]);
+        };
+
+        // Coinbase charges fees in the quote asset
+        let fee = trade.fee(liquidity);
+        let fee_asset = fee.map(|_| instrument.quote.clone());
+
+        Self(smallvec![Ok(MarketEvent {
+            exchange_time: trade.time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.time,
+                TimestampUnit::Microseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(exchange_id),
+            instrument,
+            kind: Fill {
+                trade_id: trade.trade_id.to_string(),
+                order_id: order_id.to_string(),
+                side,
+                price: trade.price,
+                amount: trade.size,
+                liquidity,
+                fee,
+                fee_asset,
+            },
+            meta: (),
+        })])
+    }
+}
+
+/// State of an order of the authenticated account tracked by the
+/// [`CoinbaseOrderUpdatesTransformer`], since each [`CoinbaseUserMessage`] only describes part of
+/// the order.
+#[derive(Clone, PartialEq, Debug)]
+struct CoinbaseOrderState {
+    client_order_id: Option<String>,
+    /// Order size, `None` if the order was received before connecting, or is a market order
+    /// placed by funds.
+    amount: Option<Num>,
+    price: Option<Num>,
+    filled_amount: Num,
+}
+
+/// [`Coinbase`] [`OrderUpdates`] [`ExchangeTransformer`], yielding an [`OrderUpdate`] for each
+/// `open`, `match` & `done` [`CoinbaseUserMessage`] of the authenticated account.
+///
+/// The order size, limit price & client order id are only included in the `received` message, so
+/// they're tracked per order until it's `done`. For orders received before connecting, the size
+/// is derived from the `remaining_size` of the `open` & `done` messages, and is the amount filled
+/// so far for any `match` in between.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoinbaseOrderUpdatesTransformer {
+    instrument_map: Map<Instrument>,
+    orders: HashMap<String, CoinbaseOrderState>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Coinbase, OrderUpdates> for CoinbaseOrderUpdatesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            orders: HashMap::new(),
+        })
+    }
+}
+
+impl Transformer for CoinbaseOrderUpdatesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, CoinbaseUserMessage);
+    type Output = MarketEvent<OrderUpdate>;
+    type OutputIter = MarketEvents<OrderUpdate>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        let Some(subscription_id) = input.id() else {
+            return smallvec![];
+        };
+        let instrument = match self.instrument_map.find(&subscription_id) {
+            Ok(instrument) => instrument,
+            Err(unidentifiable) => return smallvec![Err(DataError::Socket(unidentifiable))],
+        };
+        let Some(time) = input.time() else {
+            return smallvec![];
+        };
+
+        let update = match input {
+            CoinbaseUserMessage::Received(received) => {
+                self.orders.insert(
+                    received.order_id,
+                    CoinbaseOrderState {
+                        client_order_id: received.client_oid,
+                        amount: received.size,
+                        price: received.price,
+                        filled_amount: num::zero(),
+                    },
+                );
+                return smallvec![];
+            }
+            CoinbaseUserMessage::Open(open) => {
+                let order = self.order(&open.order_id, open.price);
+                let amount = *order
+                    .amount
+                    .get_or_insert(open.remaining_size + order.filled_amount);
+                order.filled_amount = amount - open.remaining_size;
+
+                order_update(
+                    open.order_id,
+                    order,
+                    OrderStatus::Open,
+                    open.side,
+                    amount,
+                    None,
+                )
+            }
+            CoinbaseUserMessage::Match(trade) => {
+                let Some((_, order_id, side)) = trade.own_order() else {
+                    return smallvec![];
+                };
+                let order_id = order_id.to_string();
+                let order = self.order(&order_id, None);
+                order.filled_amount += trade.size;
+                let status = match order.amount {
+                    Some(amount) if order.filled_amount >= amount => OrderStatus::Filled,
+                    _ => OrderStatus::PartiallyFilled,
+                };
+                let amount = order.amount.unwrap_or(order.filled_amount);
+
+                order_update(order_id, order, status, side, amount, None)
+            }
+            CoinbaseUserMessage::Done(done) => {
+                let mut order = self
+                    .orders
+                    .remove(&done.order_id)
+                    .unwrap_or(CoinbaseOrderState {
+                        client_order_id: None,
+                        amount: None,
+                        price: done.price,
+                        filled_amount: num::zero(),
+                    });
+                let remaining = done.remaining_size.unwrap_or_else(num::zero);
+                let amount = *order.amount.get_or_insert(remaining + order.filled_amount);
+                order.filled_amount = amount - remaining;
+
+                order_update(
+                    done.order_id,
+                    &order,
+                    OrderStatus::from(done.reason),
+                    done.side,
+                    amount,
+                    done.cancel_reason,
+                )
+            }
+            CoinbaseUserMessage::Other => return smallvec![],
+        };
+
+        smallvec![Ok(MarketEvent {
+            exchange_time: time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                time,
+                TimestampUnit::Microseconds
+            )),
+            received_time,
+            exchange: Exchange::from(Coinbase::ID),
+            instrument,
+            kind: update,
+            meta: (),
+        })]
+    }
+}
+
+impl CoinbaseOrderUpdatesTransformer {
+    /// Tracked [`CoinbaseOrderState`] of the provided order, inserting an empty state if the
+    /// order was received before connecting.
+    fn order(&mut self, order_id: &str, price: Option<Num>) -> &mut CoinbaseOrderState {
+        self.orders
+            .entry(order_id.to_string())
+            .or_insert_with(|| CoinbaseOrderState {
+                client_order_id: None,
+                amount: None,
+                price,
+                filled_amount: num::zero(),
+            })
+    }
+}
+
+/// Construct the [`OrderUpdate`] of the provided order & [`CoinbaseOrderState`].
+fn order_update(
+    order_id: String,
+    order: &CoinbaseOrderState,
+    status: OrderStatus,
+    side: Side,
+    amount: Num,
+    reason: Option<String>,
+) -> OrderUpdate {
+    OrderUpdate {
+        order_id,
+        client_order_id: order.client_order_id.clone(),
+        status,
+        side,
+        price: order.price,
+        amount,
+        filled_amount: order.filled_amount,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use barter_integration::model::{InstrumentKind, Symbol};
+
+    fn btc_usd() -> Instrument {
+        Instrument::from(("btc", "usd", InstrumentKind::Spot))
+    }
+
+    #[test]
+    fn test_coinbase_user_message_into_fills() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<Fill>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: match w/ the authenticated account as the maker
+                input: r#"{
+                    "type": "match", "trade_id": 10, "sequence": 50,
+                    "maker_order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                    "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+                    "time": "2014-11-07T08:19:28.464459Z", "product_id": "BTC-USD",
+                    "size": "0.34", "price": "502.1", "side": "buy",
+                    "maker_user_id": "5844eceecf7e803e259d0365", "user_id": "5844eceecf7e803e259d0365",
+                    "maker_profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352",
+                    "profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352", "maker_fee_rate": "0.001"
+                }"#,
+                expected: vec![Fill {
+                    trade_id: "10".to_string(),
+                    order_id: "d50ec984-77a8-460a-b958-66f114b0de9b".to_string(),
+                    side: Side::Buy,
+                    price: num!(502.1),
+                    amount: num!(0.34),
+                    liquidity: Liquidity::Maker,
+                    fee: Some(num!(0.001) * (num!(502.1) * num!(0.34))),
+                    fee_asset: Some(Symbol::from("usd")),
+                }],
+            },
+            TestCase {
+                // TC1: match w/ the authenticated account as the taker takes the opposite side
+                input: r#"{
+                    "type": "match", "trade_id": 11, "sequence": 51,
+                    "maker_order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                    "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+                    "time": "2014-11-07T08:19:28.464459Z", "product_id": "BTC-USD",
+                    "size": "1.5", "price": "502.1", "side": "buy",
+                    "taker_user_id": "5844eceecf7e803e259d0365", "user_id": "5844eceecf7e803e259d0365",
+                    "taker_fee_rate": "0.005"
+                }"#,
+                expected: vec![Fill {
+                    trade_id: "11".to_string(),
+                    order_id: "132fb6ae-456b-4654-b4e0-d681ac05cea1".to_string(),
+                    side: Side::Sell,
+                    price: num!(502.1),
+                    amount: num!(1.5),
+                    liquidity: Liquidity::Taker,
+                    fee: Some(num!(0.005) * (num!(502.1) * num!(1.5))),
+                    fee_asset: Some(Symbol::from("usd")),
+                }],
+            },
+            TestCase {
+                // TC2: match not involving the authenticated account
+                input: r#"{
+                    "type": "match", "trade_id": 12, "sequence": 52,
+                    "maker_order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                    "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+                    "time": "2014-11-07T08:19:28.464459Z", "product_id": "BTC-USD",
+                    "size": "1.5", "price": "502.1", "side": "buy"
+                }"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC3: open order is not a fill
+                input: r#"{
+                    "type": "open", "time": "2014-11-07T08:19:27.028459Z", "product_id": "BTC-USD",
+                    "sequence": 11, "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                    "price": "502.1", "remaining_size": "1.34", "side": "buy"
+                }"#,
+                expected: vec![],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let message = serde_json::from_str::<CoinbaseUserMessage>(test.input).unwrap();
+            assert_eq!(
+                message.id(),
+                Some(SubscriptionId::from("user|BTC-USD")),
+                "TC{} failed",
+                index
+            );
+
+            let actual =
+                MarketIter::<Fill>::from((ExchangeId::Coinbase, btc_usd(), Utc::now(), message))
+                    .0
+                    .into_iter()
+                    .map(|event| event.unwrap().kind)
+                    .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_coinbase_order_updates_transformer() {
+        let mut transformer = CoinbaseOrderUpdatesTransformer {
+            instrument_map: Map([(SubscriptionId::from("user|BTC-USD"), btc_usd())]
+                .into_iter()
+                .collect()),
+            orders: HashMap::new(),
+        };
+
+        let inputs = [
+            // Limit order received & resting
+            r#"{
+                "type": "received", "time": "2014-11-07T08:19:27.028459Z", "product_id": "BTC-USD",
+                "sequence": 10, "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b", "size": "1.5",
+                "price": "502.1", "side": "buy", "order_type": "limit", "client_oid": "barter1"
+            }"#,
+            r#"{
+                "type": "open", "time": "2014-11-07T08:19:27.028459Z", "product_id": "BTC-USD",
+                "sequence": 11, "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                "price": "502.1", "remaining_size": "1.5", "side": "buy"
+            }"#,
+            // Partially filled as the maker
+            r#"{
+                "type": "match", "trade_id": 10, "sequence": 50,
+                "maker_order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+                "time": "2014-11-07T08:19:28.464459Z", "product_id": "BTC-USD",
+                "size": "0.25", "price": "502.1", "side": "buy",
+                "maker_user_id": "5844eceecf7e803e259d0365", "user_id": "5844eceecf7e803e259d0365"
+            }"#,
+            // Change messages are not normalised
+            r#"{
+                "type": "change", "reason": "STP", "time": "2014-11-07T08:19:28.464459Z",
+                "sequence": 51, "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                "side": "buy", "product_id": "BTC-USD", "old_size": "1.0", "new_size": "1.0"
+            }"#,
+            // Remainder canceled
+            r#"{
+                "type": "done", "time": "2014-11-07T08:19:29.028459Z", "product_id": "BTC-USD",
+                "sequence": 52, "price": "502.1",
+                "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b", "reason": "canceled",
+                "side": "buy", "remaining_size": "1.25"
+            }"#,
+            // Order received before connecting is derived from the remaining size
+            r#"{
+                "type": "open", "time": "2014-11-07T08:19:30.028459Z", "product_id": "BTC-USD",
+                "sequence": 53, "order_id": "b8d2a1e0-1c5e-4b0c-9d5e-7b5d0b5d2f10",
+                "price": "501.0", "remaining_size": "2.0", "side": "sell"
+            }"#,
+            r#"{
+                "type": "done", "time": "2014-11-07T08:19:31.028459Z", "product_id": "BTC-USD",
+                "sequence": 54, "price": "501.0",
+                "order_id": "b8d2a1e0-1c5e-4b0c-9d5e-7b5d0b5d2f10", "reason": "canceled",
+                "cancel_reason": "101:Time In Force", "side": "sell", "remaining_size": "2.0"
+            }"#,
+        ];
+
+        let actual = inputs
+            .into_iter()
+            .flat_map(|input| {
+                let input = serde_json::from_str::<CoinbaseUserMessage>(input).unwrap();
+                transformer.transform((Utc::now(), input))
+            })
+            .map(|event| {
+                let event = event.unwrap();
+                assert_eq!(event.instrument, btc_usd());
+                event.kind
+            })
+            .collect::<Vec<_>>();
+
+        let order = |status, filled_amount| OrderUpdate {
+            order_id: "d50ec984-77a8-460a-b958-66f114b0de9b".to_string(),
+            client_order_id: Some("barter1".to_string()),
+            status,
+            side: Side::Buy,
+            price: Some(num!(502.1)),
+            amount: num!(1.5),
+            filled_amount,
+            reason: None,
+        };
+
+        let expected = vec![
+            order(OrderStatus::Open, num!(0.0)),
+            order(OrderStatus::PartiallyFilled, num!(0.25)),
+            order(OrderStatus::Cancelled, num!(0.25)),
+            OrderUpdate {
+                order_id: "b8d2a1e0-1c5e-4b0c-9d5e-7b5d0b5d2f10".to_string(),
+                client_order_id: None,
+                status: OrderStatus::Open,
+                side: Side::Sell,
+                price: Some(num!(501.0)),
+                amount: num!(2.0),
+                filled_amount: num!(0.0),
+                reason: None,
+            },
+            OrderUpdate {
+                order_id: "b8d2a1e0-1c5e-4b0c-9d5e-7b5d0b5d2f10".to_string(),
+                client_order_id: None,
+                status: OrderStatus::Cancelled,
+                side: Side::Sell,
+                price: Some(num!(501.0)),
+                amount: num!(2.0),
+                filled_amount: num!(0.0),
+                reason: Some("101:Time In Force".to_string()),
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        assert!(transformer.orders.is_empty());
+    }
+}
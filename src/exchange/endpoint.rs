@@ -0,0 +1,141 @@
+use barter_integration::error::SocketError;
+use url::Url;
+
+/// Overrides of the WebSocket & REST base urls of an exchange, eg/ to point the production
+/// [`Connector`](super::Connector)s at a testnet, a regional mirror, or a local mock server.
+///
+/// The `websocket` url takes precedence over [`Connector::url`](super::Connector::url) &
+/// [`Connector::channel_url`](super::Connector::channel_url) when the
+/// [`Subscriber`](crate::subscriber::Subscriber) connects, and the `rest` base url takes
+/// precedence over the hardcoded REST base url used by snapshot & backfill fetches (eg/
+/// `https://api.binance.com/api/v3`).
+///
+/// Set per exchange via
+/// [`StreamBuilder::with_url`](crate::streams::builder::StreamBuilder::with_url) &
+/// [`StreamBuilder::with_rest_url`](crate::streams::builder::StreamBuilder::with_rest_url), and
+/// handed to each connection via it's [`StreamConfig`](crate::streams::config::StreamConfig).
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Endpoints {
+    pub websocket: Option<Url>,
+    pub rest: Option<Url>,
+}
+
+impl Endpoints {
+    /// Parse a `ws` or `wss` WebSocket url override.
+    #[allow(clippy::result_large_err)]
+    pub fn parse_websocket(url: &str) -> Result<Url, SocketError> {
+        parse(url, &["ws", "wss"])
+    }
+
+    /// Parse an `http` or `https` REST base url override.
+    #[allow(clippy::result_large_err)]
+    pub fn parse_rest(url: &str) -> Result<Url, SocketError> {
+        parse(url, &["http", "https"])
+    }
+
+    /// Determine the REST base url to use in place of the provided default, which is the `rest`
+    /// override of [`Self`] if there is one.
+    pub fn rest_base_url(&self, default: &str) -> String {
+        self.rest
+            .as_ref()
+            .map(|url| url.as_str().trim_end_matches('/').to_owned())
+            .unwrap_or_else(|| default.to_owned())
+    }
+}
+
+/// Parse the provided url, ensuring it has one of the provided schemes.
+#[allow(clippy::result_large_err)]
+fn parse(url: &str, schemes: &[&str]) -> Result<Url, SocketError> {
+    let url = Url::parse(url)?;
+
+    if schemes.contains(&url.scheme()) {
+        Ok(url)
+    } else {
+        Err(SocketError::Unsupported {
+            entity: "url override",
+            item: format!("{url} (expected a {} url)", schemes.join(" or ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoints_parse() {
+        struct TestCase {
+            actual: Result<Url, SocketError>,
+            expected: Result<&'static str, SocketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid WebSocket url
+                actual: Endpoints::parse_websocket("wss://testnet.binance.vision/ws"),
+                expected: Ok("wss://testnet.binance.vision/ws"),
+            },
+            TestCase {
+                // TC1: WebSocket url with REST scheme
+                actual: Endpoints::parse_websocket("https://testnet.binance.vision/ws"),
+                expected: Err(SocketError::Unsupported {
+                    entity: "url override",
+                    item: "https://testnet.binance.vision/ws (expected a ws or wss url)"
+                        .to_string(),
+                }),
+            },
+            TestCase {
+                // TC2: invalid url
+                actual: Endpoints::parse_websocket("testnet.binance.vision"),
+                expected: Err(SocketError::UrlParse(
+                    url::ParseError::RelativeUrlWithoutBase,
+                )),
+            },
+            TestCase {
+                // TC3: valid REST url
+                actual: Endpoints::parse_rest("http://127.0.0.1:8080/api/v3"),
+                expected: Ok("http://127.0.0.1:8080/api/v3"),
+            },
+            TestCase {
+                // TC4: REST url with WebSocket scheme
+                actual: Endpoints::parse_rest("ws://127.0.0.1:8080"),
+                expected: Err(SocketError::Unsupported {
+                    entity: "url override",
+                    item: "ws://127.0.0.1:8080/ (expected a http or https url)".to_string(),
+                }),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            match (test.actual, test.expected) {
+                (Ok(actual), Ok(expected)) => {
+                    assert_eq!(actual.as_str(), expected, "TC{} failed", index)
+                }
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rest_base_url() {
+        const DEFAULT: &str = "https://api.binance.com/api/v3";
+        assert_eq!(Endpoints::default().rest_base_url(DEFAULT), DEFAULT);
+
+        let endpoints = Endpoints {
+            websocket: None,
+            rest: Some(Url::parse("http://127.0.0.1:8080/").unwrap()),
+        };
+        assert_eq!(endpoints.rest_base_url(DEFAULT), "http://127.0.0.1:8080");
+    }
+}
@@ -0,0 +1,250 @@
+use super::{market::GateioMarket, Gateio, GateioServer, HTTP_BASE_URL_GATEIO};
+use crate::{
+    datetime::parse_fractional_seconds_str,
+    error::DataError,
+    event::MarketEvent,
+    exchange::{
+        backfill::{candle_events, fetch_candle_pages},
+        gateio::futures::GateioSettle,
+        Connector,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        candle::{Candle, Candles, Interval},
+        Subscription,
+    },
+    Identifier,
+};
+use barter_integration::error::SocketError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Maximum number of candles [`Gateio`] returns from a single `candlesticks` REST request.
+pub const GATEIO_MAX_CANDLES_LIMIT: usize = 1000;
+
+/// [`Duration`] waited between paginated [`Gateio`] `candlesticks` requests, respecting the
+/// public endpoint rate limit of 200 requests per 10 seconds.
+pub const GATEIO_CANDLES_PAGE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// [`Gateio`] REST spot `candlesticks` candle.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#market-candlesticks>
+/// ```json
+/// ["1539852480", "971519.677", "0.0021724", "0.0021922", "0.0021724", "0.0021737", "447.2", "true"]
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct GateioSpotCandleRest(pub Vec<String>);
+
+impl GateioSpotCandleRest {
+    /// Normalise into a [`Candle`] of the provided [`Interval`], or `None` if malformed.
+    ///
+    /// Fields are (open time, quote volume, close, high, low, open, base volume, closed).
+    pub fn into_candle(self, interval: Interval) -> Option<Candle> {
+        let field = |index: usize| self.0.get(index)?.parse::<Num>().ok();
+        let open_time = parse_fractional_seconds_str(self.0.first()?)?;
+
+        Some(Candle {
+            close_time: open_time + interval.duration() - chrono::Duration::milliseconds(1),
+            open: field(5)?,
+            high: field(3)?,
+            low: field(4)?,
+            close: field(2)?,
+            volume: field(6)?,
+            trade_count: 0,
+            historical: true,
+        })
+    }
+}
+
+/// [`Gateio`] REST futures `candlesticks` candle.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#get-futures-candlesticks>
+/// ```json
+/// {"t": 1539852480, "v": 97151, "c": "1.032", "h": "1.032", "l": "1.032", "o": "1.032", "sum": "3580"}
+/// ```
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct GateioFuturesCandleRest {
+    #[serde(deserialize_with = "crate::datetime::de_epoch_s_as_datetime_utc")]
+    pub t: DateTime<Utc>,
+    #[serde(default)]
+    pub v: i64,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub o: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub h: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub l: Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub c: Num,
+}
+
+impl GateioFuturesCandleRest {
+    /// Normalise into a [`Candle`] of the provided [`Interval`], with a `volume` denominated in
+    /// contracts.
+    pub fn into_candle(self, interval: Interval) -> Option<Candle> {
+        Some(Candle {
+            close_time: self.t + interval.duration() - chrono::Duration::milliseconds(1),
+            open: self.o,
+            high: self.h,
+            low: self.l,
+            close: self.c,
+            volume: self.v.to_string().parse().ok()?,
+            trade_count: 0,
+            historical: true,
+        })
+    }
+}
+
+/// Determine the [`Gateio`] `candlesticks` interval of the provided [`Interval`], if supported
+/// by the spot or futures endpoint.
+pub fn gateio_interval(interval: Interval, futures: bool) -> Option<&'static str> {
+    match interval {
+        Interval::M1 => Some("1m"),
+        Interval::M5 => Some("5m"),
+        Interval::M15 => Some("15m"),
+        Interval::M30 => Some("30m"),
+        Interval::H1 => Some("1h"),
+        Interval::H4 => Some("4h"),
+        Interval::D1 => Some("1d"),
+        Interval::W1 => Some("7d"),
+        Interval::H2 if futures => Some("2h"),
+        Interval::H6 if futures => Some("6h"),
+        Interval::H12 if futures => Some("12h"),
+        Interval::M3 | Interval::H2 | Interval::H6 | Interval::H12 => None,
+    }
+}
+
+/// Fetch the most recent closed [`Candle`]s for the provided [`Subscription`] from the
+/// [`Gateio`] REST API spot or futures `candlesticks` endpoint (depending on the server
+/// [`GateioSettle`]), paginating (see [`fetch_candle_pages`]) beyond
+/// [`GATEIO_MAX_CANDLES_LIMIT`], using the [`StreamConfig`] of the connection.
+///
+/// Gateio does not provide the trade count of a candle, so it is always 0.
+pub async fn fetch_candles<Server>(
+    subscription: &Subscription<Gateio<Server>, Candles>,
+    limit: usize,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<Candle>>, DataError>
+where
+    Server: GateioServer,
+{
+    let market: GateioMarket = subscription.id();
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_GATEIO);
+    let interval = subscription.kind.0;
+    let proxy = config.proxy.as_ref();
+    let gateio_interval = gateio_interval(interval, Server::SETTLE.is_some()).ok_or_else(|| {
+        DataError::Socket(SocketError::Unsupported {
+            entity: "Gateio candlesticks interval",
+            item: interval.to_string(),
+        })
+    })?;
+
+    let endpoint = match Server::SETTLE {
+        Some(settle) => {
+            let settle = match settle {
+                GateioSettle::Usdt => "usdt",
+                GateioSettle::Btc => "btc",
+            };
+            format!(
+                "{base_url}/futures/{settle}/candlesticks?contract={}&interval={gateio_interval}",
+                market.0
+            )
+        }
+        None => format!(
+            "{base_url}/spot/candlesticks?currency_pair={}&interval={gateio_interval}",
+            market.0
+        ),
+    };
+
+    let candles = fetch_candle_pages(
+        limit,
+        GATEIO_MAX_CANDLES_LIMIT,
+        GATEIO_CANDLES_PAGE_INTERVAL,
+        |before, page_limit| {
+            // Gateio rejects "limit" combined with a "from" & "to" range
+            let url = match before {
+                None => format!("{endpoint}&limit={page_limit}"),
+                Some(before) => {
+                    let to = (before - interval.duration()).timestamp();
+                    let from = to - (page_limit as i64 - 1) * interval.duration().num_seconds();
+                    format!("{endpoint}&from={from}&to={to}")
+                }
+            };
+
+            async move {
+                match Server::SETTLE {
+                    Some(_) => Ok(get_json::<Vec<GateioFuturesCandleRest>>(proxy, &url)
+                        .await?
+                        .into_iter()
+                        .filter_map(|candle| candle.into_candle(interval))
+                        .collect()),
+                    None => Ok(get_json::<Vec<GateioSpotCandleRest>>(proxy, &url)
+                        .await?
+                        .into_iter()
+                        .filter_map(|candle| candle.into_candle(interval))
+                        .collect()),
+                }
+            }
+        },
+    )
+    .await?;
+
+    Ok(candle_events(
+        Gateio::<Server>::ID,
+        &subscription.instrument,
+        candles,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_gateio_candles_rest() {
+        let expected = |volume: Num| Candle {
+            close_time: DateTime::from_timestamp_millis(1539852539999).unwrap(),
+            open: num!(0.0021737),
+            high: num!(0.0021922),
+            low: num!(0.0021724),
+            close: num!(0.0021724),
+            volume,
+            trade_count: 0,
+            historical: true,
+        };
+
+        // TC0: spot candle
+        let spot = serde_json::from_str::<Vec<GateioSpotCandleRest>>(
+            r#"[["1539852480", "971519.677", "0.0021724", "0.0021922", "0.0021724", "0.0021737", "447.2", "true"]]"#,
+        )
+        .unwrap();
+        let actual = spot[0].clone().into_candle(Interval::M1);
+        assert_eq!(actual, Some(expected(num!(447.2))), "TC0 failed");
+
+        // TC1: futures candle, with volume in contracts
+        let futures = serde_json::from_str::<Vec<GateioFuturesCandleRest>>(
+            r#"[{"t": 1539852480, "v": 97151, "c": "0.0021724", "h": "0.0021922", "l": "0.0021724", "o": "0.0021737", "sum": "3580"}]"#,
+        )
+        .unwrap();
+        let actual = futures[0].into_candle(Interval::M1);
+        assert_eq!(actual, Some(expected(num!(97151.0))), "TC1 failed");
+
+        // TC2: malformed spot candle
+        let actual = GateioSpotCandleRest(vec!["1539852480".to_string()]).into_candle(Interval::M1);
+        assert_eq!(actual, None, "TC2 failed");
+    }
+
+    #[test]
+    fn test_gateio_interval() {
+        assert_eq!(gateio_interval(Interval::W1, false), Some("7d"));
+        assert_eq!(gateio_interval(Interval::H2, false), None);
+        assert_eq!(gateio_interval(Interval::H2, true), Some("2h"));
+        assert_eq!(gateio_interval(Interval::M3, true), None);
+    }
+}
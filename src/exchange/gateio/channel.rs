@@ -3,7 +3,7 @@ use crate::{
     Identifier,
 };
 use barter_integration::model::InstrumentKind;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
 /// [`Gateio`](super::Gateio) channel to be subscribed to.
@@ -22,6 +22,9 @@ impl GateioChannel {
     ///
     /// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
     pub const FUTURE_PERPETUAL_TRADES: Self = Self("futures.trades");
+
+    /// Every [`GateioChannel`] that can be subscribed to.
+    pub const ALL: [Self; 2] = [Self::SPOT_TRADES, Self::FUTURE_PERPETUAL_TRADES];
 }
 
 impl<Server> Identifier<GateioChannel> for Subscription<Server, PublicTrades> {
@@ -33,6 +36,25 @@ impl<Server> Identifier<GateioChannel> for Subscription<Server, PublicTrades> {
     }
 }
 
+impl<'de> Deserialize<'de> for GateioChannel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // Map to the static channel subscribed to, rather than allocating a String per message
+        let channel = <&str as Deserialize>::deserialize(deserializer)?;
+        Self::ALL
+            .into_iter()
+            .find(|known| known.0 == channel)
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(channel),
+                    &"Gateio channel, eg/ spot.trades",
+                )
+            })
+    }
+}
+
 impl AsRef<str> for GateioChannel {
     fn as_ref(&self) -> &str {
         self.0
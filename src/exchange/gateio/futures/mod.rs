@@ -1,16 +1,72 @@
 use self::trade::GateioFuturesTrades;
-use super::Gateio;
+use super::{Gateio, GateioServer};
 use crate::{
     exchange::{ExchangeId, ExchangeServer, StreamSelector},
     subscription::trade::PublicTrades,
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
+use barter_integration::protocol::websocket::WsMessage;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// Public trades types.
 pub mod trade;
 
+/// Settle currency of [`Gateio`](super::Gateio) futures contracts, which determines both the
+/// WebSocket endpoint & the contract symbol quote.
+///
+/// Contracts subscribed on the wrong endpoint, or with the wrong contract symbol quote, silently
+/// yield no data.
+///
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/>
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateioSettle {
+    /// USDT-margined contracts, eg/ `BTC_USDT`.
+    Usdt,
+    /// BTC-margined (inverse) contracts, eg/ `BTC_USD`.
+    Btc,
+}
+
+impl GateioSettle {
+    /// WebSocket server base url serving contracts with this [`GateioSettle`] currency.
+    pub fn websocket_url(&self) -> &'static str {
+        match self {
+            Self::Usdt => WEBSOCKET_BASE_URL_GATEIO_FUTURES_USD,
+            Self::Btc => WEBSOCKET_BASE_URL_GATEIO_FUTURES_BTC,
+        }
+    }
+
+    /// Quote currency of contract symbols with this [`GateioSettle`] currency, eg/ `BTC_USD`.
+    pub fn contract_quote(&self) -> &'static str {
+        match self {
+            Self::Usdt => "USDT",
+            Self::Btc => "USD",
+        }
+    }
+}
+
+/// Channel of the [`Gateio`](super::Gateio) futures application-level pong.
+const PONG_CHANNEL_GATEIO_FUTURES: &str = "futures.pong";
+
+/// [`Gateio`](super::Gateio) futures application-level ping, which unlike the spot ping requires
+/// the current `time` in seconds.
+///
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/#ping-and-pong>
+/// ```json
+/// {"time": 1545404023, "channel": "futures.ping"}
+/// ```
+fn futures_ping() -> WsMessage {
+    WsMessage::Text(
+        json!({
+            "time": chrono::Utc::now().timestamp(),
+            "channel": "futures.ping"
+        })
+        .to_string(),
+    )
+}
+
 /// [`GateioFuturesUsd`] WebSocket server base url.
 ///
 /// See docs: <https://www.gate.io/docs/developers/futures/ws/en/>
@@ -27,7 +83,16 @@ impl ExchangeServer for GateioServerFuturesUsd {
     const ID: ExchangeId = ExchangeId::GateioFuturesUsd;
 
     fn websocket_url() -> &'static str {
-        WEBSOCKET_BASE_URL_GATEIO_FUTURES_USD
+        GateioSettle::Usdt.websocket_url()
+    }
+}
+
+impl GateioServer for GateioServerFuturesUsd {
+    const SETTLE: Option<GateioSettle> = Some(GateioSettle::Usdt);
+    const PONG_CHANNEL: &'static str = PONG_CHANNEL_GATEIO_FUTURES;
+
+    fn ping() -> WsMessage {
+        futures_ping()
     }
 }
 
@@ -53,10 +118,146 @@ impl ExchangeServer for GateioServerFuturesBtc {
     const ID: ExchangeId = ExchangeId::GateioFuturesBtc;
 
     fn websocket_url() -> &'static str {
-        WEBSOCKET_BASE_URL_GATEIO_FUTURES_BTC
+        GateioSettle::Btc.websocket_url()
+    }
+}
+
+impl GateioServer for GateioServerFuturesBtc {
+    const SETTLE: Option<GateioSettle> = Some(GateioSettle::Btc);
+    const PONG_CHANNEL: &'static str = PONG_CHANNEL_GATEIO_FUTURES;
+
+    fn ping() -> WsMessage {
+        futures_ping()
     }
 }
 
 impl StreamSelector<PublicTrades> for GateioFuturesBtc {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, GateioFuturesTrades>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::{subscription::ExchangeSub, Connector},
+        subscription::Subscription,
+    };
+    use barter_integration::{
+        error::SocketError, model::InstrumentKind, protocol::websocket::WsMessage, Validator,
+    };
+
+    fn request<Server>(base: &str, quote: &str) -> (String, serde_json::Value)
+    where
+        Server: GateioServer,
+    {
+        let subscription = Subscription::from((
+            Gateio::<Server>::default(),
+            base,
+            quote,
+            InstrumentKind::FuturePerpetual,
+            PublicTrades,
+        ));
+
+        let url = Gateio::<Server>::url().unwrap().to_string();
+        let request =
+            match Gateio::<Server>::requests(vec![ExchangeSub::new(&subscription)]).remove(0) {
+                WsMessage::Text(request) => serde_json::from_str(&request).unwrap(),
+                message => panic!("unexpected request: {message:?}"),
+            };
+
+        (url, request)
+    }
+
+    #[test]
+    fn test_gateio_futures_endpoint_and_contract() {
+        struct TestCase {
+            actual: (String, serde_json::Value),
+            expected_url: &'static str,
+            expected_contract: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: GateioFuturesUsd uses the usdt endpoint & USDT contracts
+                actual: request::<GateioServerFuturesUsd>("btc", "usdt"),
+                expected_url: "wss://fx-ws.gateio.ws/v4/ws/usdt",
+                expected_contract: "BTC_USDT",
+            },
+            TestCase {
+                // TC1: GateioFuturesBtc uses the btc endpoint & USD contracts
+                actual: request::<GateioServerFuturesBtc>("btc", "usd"),
+                expected_url: "wss://fx-ws.gateio.ws/v4/ws/btc",
+                expected_contract: "BTC_USD",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (url, request) = test.actual;
+            assert_eq!(url, test.expected_url, "TC{} failed", index);
+            assert_eq!(request["channel"], "futures.trades", "TC{} failed", index);
+            assert_eq!(
+                request["payload"],
+                serde_json::json!([test.expected_contract]),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_gateio_futures_quote() {
+        struct TestCase {
+            input: Result<(), SocketError>,
+            expected_valid: bool,
+        }
+
+        #[allow(clippy::result_large_err)]
+        fn validate<Server>(quote: &str) -> Result<(), SocketError>
+        where
+            Gateio<Server>: StreamSelector<PublicTrades>,
+            Server: GateioServer,
+        {
+            let subscription = Subscription::from((
+                Gateio::<Server>::default(),
+                "btc",
+                quote,
+                InstrumentKind::FuturePerpetual,
+                PublicTrades,
+            ));
+            subscription.validate().map(|_| ())
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: GateioFuturesUsd contract quoted in the USDT settle currency
+                input: validate::<GateioServerFuturesUsd>("usdt"),
+                expected_valid: true,
+            },
+            TestCase {
+                // TC1: GateioFuturesUsd contract quoted in USD is not served
+                input: validate::<GateioServerFuturesUsd>("usd"),
+                expected_valid: false,
+            },
+            TestCase {
+                // TC2: GateioFuturesBtc contract quoted in USD of the BTC settle currency
+                input: validate::<GateioServerFuturesBtc>("usd"),
+                expected_valid: true,
+            },
+            TestCase {
+                // TC3: GateioFuturesBtc contract quoted in USDT is not served
+                input: validate::<GateioServerFuturesBtc>("usdt"),
+                expected_valid: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            match (test.input, test.expected_valid) {
+                (Ok(()), true) => {}
+                (Err(SocketError::Unsupported { entity, .. }), false) => {
+                    assert!(entity.starts_with("gateio_futures"), "TC{} failed", index)
+                }
+                (actual, _) => panic!("TC{index} failed: {actual:?}"),
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use super::super::message::GateioMessage;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
@@ -34,14 +35,14 @@ pub struct GateioFuturesTradeInner {
     pub market: String,
     #[serde(
         rename = "create_time_ms",
-        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
     pub id: u64,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     #[serde(rename = "size")]
-    pub amount: f64,
+    pub amount: Num,
 }
 
 impl Identifier<Option<SubscriptionId>> for GateioFuturesTrades {
@@ -52,9 +53,16 @@ impl Identifier<Option<SubscriptionId>> for GateioFuturesTrades {
     }
 }
 
-impl From<(ExchangeId, Instrument, GateioFuturesTrades)> for MarketIter<PublicTrade> {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, GateioFuturesTrades)>
+    for MarketIter<PublicTrade>
+{
     fn from(
-        (exchange_id, instrument, trades): (ExchangeId, Instrument, GateioFuturesTrades),
+        (exchange_id, instrument, received_time, trades): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            GateioFuturesTrades,
+        ),
     ) -> Self {
         trades
             .data
@@ -62,7 +70,11 @@ impl From<(ExchangeId, Instrument, GateioFuturesTrades)> for MarketIter<PublicTr
             .map(|trade| {
                 Ok(MarketEvent {
                     exchange_time: trade.time,
-                    received_time: Utc::now(),
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        trade.time,
+                        TimestampUnit::Milliseconds,
+                    )),
+                    received_time,
                     exchange: Exchange::from(exchange_id),
                     instrument: instrument.clone(),
                     kind: PublicTrade {
@@ -75,6 +87,7 @@ impl From<(ExchangeId, Instrument, GateioFuturesTrades)> for MarketIter<PublicTr
                             Side::Sell
                         },
                     },
+                    meta: (),
                 })
             })
             .collect()
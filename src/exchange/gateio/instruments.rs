@@ -0,0 +1,254 @@
+use super::{futures::GateioSettle, HTTP_BASE_URL_GATEIO};
+use crate::{
+    error::DataError,
+    exchange::{
+        alias::canonical_instrument,
+        instruments::{decimal_places_size, parse_size, InstrumentInfo, MarketStatus},
+        ExchangeId,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+};
+use barter_integration::model::InstrumentKind;
+use serde::{Deserialize, Serialize};
+
+/// [`Gateio`](super::Gateio) REST spot `currency_pairs` response currency pair.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#list-all-currency-pairs-supported>
+/// ```json
+/// {
+///     "id": "BTC_USDT",
+///     "base": "BTC",
+///     "quote": "USDT",
+///     "amount_precision": 4,
+///     "precision": 1,
+///     "trade_status": "tradable"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct GateioCurrencyPair {
+    pub id: String,
+    pub base: String,
+    pub quote: String,
+    pub amount_precision: u32,
+    pub precision: u32,
+    pub trade_status: String,
+}
+
+impl From<GateioCurrencyPair> for InstrumentInfo {
+    fn from(pair: GateioCurrencyPair) -> Self {
+        let status = match pair.trade_status.as_str() {
+            "tradable" => MarketStatus::Trading,
+            _ => MarketStatus::Halted(pair.trade_status),
+        };
+
+        Self {
+            exchange: ExchangeId::GateioSpot,
+            market: pair.id.to_uppercase(),
+            instrument: canonical_instrument(
+                ExchangeId::GateioSpot,
+                &pair.base,
+                &pair.quote,
+                InstrumentKind::Spot,
+            ),
+            tick_size: decimal_places_size(pair.precision),
+            lot_size: decimal_places_size(pair.amount_precision),
+            contract_size: None,
+            status,
+        }
+    }
+}
+
+/// [`Gateio`](super::Gateio) REST futures `contracts` response contract.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#list-all-futures-contracts>
+/// ```json
+/// {
+///     "name": "BTC_USDT",
+///     "type": "direct",
+///     "quanto_multiplier": "0.0001",
+///     "order_price_round": "0.1",
+///     "order_size_min": 1,
+///     "in_delisting": false
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct GateioContract {
+    pub name: String,
+    /// Contract type, ie/ "direct" (linear) or "inverse".
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub quanto_multiplier: String,
+    pub order_price_round: String,
+    #[serde(default)]
+    pub in_delisting: bool,
+}
+
+impl GateioContract {
+    /// Normalise into an [`InstrumentInfo`] of the provided exchange, or `None` if the contract
+    /// name is not a "{BASE}_{QUOTE}" pair.
+    pub fn into_instrument_info(self, exchange: ExchangeId) -> Option<InstrumentInfo> {
+        let (base, quote) = self.name.split_once('_')?;
+        let instrument =
+            canonical_instrument(exchange, base, quote, InstrumentKind::FuturePerpetual);
+
+        let status = match self.in_delisting {
+            false => MarketStatus::Trading,
+            true => MarketStatus::Halted("in_delisting".to_string()),
+        };
+
+        Some(InstrumentInfo {
+            exchange,
+            market: self.name.to_uppercase(),
+            instrument,
+            tick_size: parse_size(&self.order_price_round),
+            // Futures are traded in whole contracts
+            lot_size: Some(Num::from(1u8)),
+            // Inverse contracts are worth 1 unit of the quote currency, & are listed without a
+            // quanto_multiplier (ie/ "0")
+            contract_size: match self.kind.as_str() {
+                "inverse" => Some(Num::from(1u8)),
+                _ => parse_size(&self.quanto_multiplier),
+            },
+            status,
+        })
+    }
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the [`Gateio`](super::Gateio) REST API spot
+/// `currency_pairs` endpoint, using the provided [`StreamConfig`].
+pub async fn fetch_spot_instruments(
+    config: &StreamConfig,
+) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_GATEIO);
+    get_json::<Vec<GateioCurrencyPair>>(
+        config.proxy.as_ref(),
+        &format!("{base_url}/spot/currency_pairs"),
+    )
+    .await
+    .map(|pairs| pairs.into_iter().map(InstrumentInfo::from).collect())
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the [`Gateio`](super::Gateio) REST API futures
+/// `contracts` endpoint of the provided [`GateioSettle`] currency, using the provided
+/// [`StreamConfig`].
+pub async fn fetch_futures_instruments(
+    exchange: ExchangeId,
+    settle: GateioSettle,
+    config: &StreamConfig,
+) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_GATEIO);
+    let settle = match settle {
+        GateioSettle::Usdt => "usdt",
+        GateioSettle::Btc => "btc",
+    };
+    get_json::<Vec<GateioContract>>(
+        config.proxy.as_ref(),
+        &format!("{base_url}/futures/{settle}/contracts"),
+    )
+    .await
+    .map(|contracts| {
+        contracts
+            .into_iter()
+            .filter_map(|contract| contract.into_instrument_info(exchange))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::Instrument;
+
+    #[test]
+    fn test_gateio_spot_instrument_info() {
+        let input = r#"
+        [
+            {
+                "id": "BTC_USDT", "base": "BTC", "quote": "USDT", "fee": "0.2",
+                "min_base_amount": "0.0001", "min_quote_amount": "1",
+                "amount_precision": 4, "precision": 1, "trade_status": "tradable",
+                "sell_start": 1516378650, "buy_start": 1516378650
+            },
+            {
+                "id": "LUNA_USDT", "base": "LUNA", "quote": "USDT", "fee": "0.2",
+                "amount_precision": 2, "precision": 6, "trade_status": "untradable"
+            }
+        ]
+        "#;
+
+        let actual = serde_json::from_str::<Vec<GateioCurrencyPair>>(input)
+            .unwrap()
+            .into_iter()
+            .map(InstrumentInfo::from)
+            .collect::<Vec<_>>();
+        let expected = vec![
+            InstrumentInfo {
+                exchange: ExchangeId::GateioSpot,
+                market: "BTC_USDT".to_string(),
+                instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                tick_size: parse_size("0.1"),
+                lot_size: parse_size("0.0001"),
+                contract_size: None,
+                status: MarketStatus::Trading,
+            },
+            InstrumentInfo {
+                exchange: ExchangeId::GateioSpot,
+                market: "LUNA_USDT".to_string(),
+                instrument: Instrument::from(("luna", "usdt", InstrumentKind::Spot)),
+                tick_size: parse_size("0.000001"),
+                lot_size: parse_size("0.01"),
+                contract_size: None,
+                status: MarketStatus::Halted("untradable".to_string()),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_gateio_futures_instrument_info() {
+        let input = r#"
+        [
+            {
+                "name": "BTC_USD", "type": "inverse", "quanto_multiplier": "0",
+                "order_price_round": "0.1", "mark_price_round": "0.01",
+                "order_size_min": 1, "order_size_max": 1000000, "in_delisting": false
+            },
+            {
+                "name": "LUNA_USD", "type": "inverse", "quanto_multiplier": "0",
+                "order_price_round": "0.001", "in_delisting": true
+            }
+        ]
+        "#;
+
+        let actual = serde_json::from_str::<Vec<GateioContract>>(input)
+            .unwrap()
+            .into_iter()
+            .filter_map(|contract| contract.into_instrument_info(ExchangeId::GateioFuturesBtc))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            InstrumentInfo {
+                exchange: ExchangeId::GateioFuturesBtc,
+                market: "BTC_USD".to_string(),
+                instrument: Instrument::from(("btc", "usd", InstrumentKind::FuturePerpetual)),
+                tick_size: parse_size("0.1"),
+                lot_size: parse_size("1"),
+                contract_size: parse_size("1"),
+                status: MarketStatus::Trading,
+            },
+            InstrumentInfo {
+                exchange: ExchangeId::GateioFuturesBtc,
+                market: "LUNA_USD".to_string(),
+                instrument: Instrument::from(("luna", "usd", InstrumentKind::FuturePerpetual)),
+                tick_size: parse_size("0.001"),
+                lot_size: parse_size("1"),
+                contract_size: parse_size("1"),
+                status: MarketStatus::Halted("in_delisting".to_string()),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+}
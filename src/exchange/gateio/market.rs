@@ -1,17 +1,44 @@
-use super::Gateio;
-use crate::{subscription::Subscription, Identifier};
+use super::{Gateio, GateioServer};
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
 /// [`Gateio`](super::Gateio) market that can be subscribed to.
 ///
-/// See docs: <https://www.okx.com/docs-v5/en/#websocket-api-public-channel>
+/// Spot markets are formatted as `BASE_QUOTE`, whereas futures contracts are quoted in the
+/// contract quote of the server [`GateioSettle`](super::futures::GateioSettle) currency, eg/
+/// `BTC_USDT` for [`GateioFuturesUsd`](super::futures::GateioFuturesUsd) & `BTC_USD` for
+/// [`GateioFuturesBtc`](super::futures::GateioFuturesBtc). Futures [`Subscription`]s with any
+/// other quote fail validation, see
+/// [`Connector::unsupported_instrument`](crate::exchange::Connector::unsupported_instrument).
+///
+/// See docs: <https://www.gate.io/docs/developers/futures/ws/en/>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct GateioMarket(pub String);
 
-impl<Server, Kind> Identifier<GateioMarket> for Subscription<Gateio<Server>, Kind> {
+impl<Server, Kind> Identifier<GateioMarket> for Subscription<Gateio<Server>, Kind>
+where
+    Server: GateioServer,
+{
     fn id(&self) -> GateioMarket {
-        GateioMarket(format!("{}_{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Server::ID, &self.instrument);
+        match Server::SETTLE {
+            Some(settle) => GateioMarket(format!(
+                "{}_{}",
+                base.to_uppercase(),
+                settle.contract_quote()
+            )),
+            None => GateioMarket(format!("{base}_{quote}").to_uppercase()),
+        }
     }
 }
 
@@ -20,3 +47,24 @@ impl AsRef<str> for GateioMarket {
         &self.0
     }
 }
+
+impl<Server> ParseMarket for Gateio<Server>
+where
+    Server: GateioServer,
+{
+    /// Gateio markets are formatted as "BASE_QUOTE" (eg/ "BTC_USDT"). Markets of futures servers
+    /// are perpetuals quoted in the [`GateioSettle`](super::futures::GateioSettle) contract quote.
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        let (base, quote) = market
+            .split_once('_')
+            .filter(|(base, quote)| !base.is_empty() && !quote.is_empty() && !quote.contains('_'))
+            .ok_or_else(|| ParseMarketError::format(Self::ID, market))?;
+
+        let kind = match Server::SETTLE {
+            Some(_) => InstrumentKind::FuturePerpetual,
+            None => InstrumentKind::Spot,
+        };
+
+        Ok(canonical_instrument(Self::ID, base, quote, kind))
+    }
+}
@@ -1,3 +1,4 @@
+use super::channel::GateioChannel;
 use serde::{Deserialize, Serialize};
 
 /// [`Gateio`](super::Gateio) WebSocket message.
@@ -56,12 +57,41 @@ use serde::{Deserialize, Serialize};
 /// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct GateioMessage<T> {
-    pub channel: String,
+    pub channel: GateioChannel,
     pub error: Option<GateioError>,
     #[serde(rename = "result")]
     pub data: T,
 }
 
+/// [`Gateio`](super::Gateio) application-level pong, sent in reply to a
+/// [`GateioServer::ping`](super::GateioServer::ping).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#application-ping-pong>
+/// ```json
+/// {
+///     "time": 1545404023,
+///     "time_ms": 1545404023123,
+///     "channel": "spot.pong",
+///     "event": "",
+///     "result": null
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct GateioPong {
+    pub channel: String,
+}
+
+/// Determine if the provided payload is a [`GateioPong`] on the provided pong channel (eg/
+/// `spot.pong`).
+///
+/// Only payloads containing the pong channel are deserialised, so market data is cheap to reject.
+pub fn is_pong(payload: &str, pong_channel: &str) -> bool {
+    payload.contains(pong_channel)
+        && serde_json::from_str::<GateioPong>(payload)
+            .is_ok_and(|pong| pong.channel == pong_channel)
+}
+
 /// [`Gateio`](super::Gateio) WebSocket error message.
 ///
 /// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
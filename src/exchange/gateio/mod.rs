@@ -1,13 +1,23 @@
-use self::{channel::GateioChannel, market::GateioMarket, subscription::GateioSubResponse};
+use self::{
+    channel::GateioChannel, futures::GateioSettle, market::GateioMarket,
+    subscription::GateioSubResponse,
+};
 use crate::{
-    exchange::{subscription::ExchangeSub, Connector, ExchangeId, ExchangeServer},
+    exchange::{
+        alias::exchange_assets, subscription::ExchangeSub, Connector, ExchangeId, ExchangeServer,
+        PingInterval,
+    },
     subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
 };
-use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
+use barter_integration::{error::SocketError, model::Instrument, protocol::websocket::WsMessage};
 use serde_json::json;
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, time::Duration};
 use url::Url;
 
+/// REST spot & futures `candlesticks` fetcher of the trailing closed
+/// [`Candle`](crate::subscription::candle::Candle)s of a [`Gateio`] market.
+pub mod backfill;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
@@ -17,6 +27,10 @@ pub mod channel;
 /// [`GateioFuturesBtc`](futures::GateioFuturesBtc).
 pub mod futures;
 
+/// [`InstrumentInfo`](crate::exchange::instruments::InstrumentInfo)s listed by the [`Gateio`] REST
+/// API spot `currency_pairs` & futures `contracts`.
+pub mod instruments;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
@@ -36,6 +50,50 @@ pub mod spot;
 /// [`GateioFuturesBtc`](futures::GateioFuturesBtc).
 pub mod subscription;
 
+/// [`Gateio`] REST API base url.
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/en/#api-overview>
+pub const HTTP_BASE_URL_GATEIO: &str = "https://api.gateio.ws/api/v4";
+
+/// Exchange message fields [`Gateio`] models intentionally don't deserialise, see
+/// [`IgnoredFields`](crate::parser::strict::IgnoredFields).
+pub const IGNORED_FIELDS: crate::parser::strict::IgnoredFields = &[
+    ("GateioMessage", &["time", "time_ms", "event"]),
+    ("GateioSpotTradeInner", &["create_time"]),
+    ("GateioFuturesTradeInner", &["create_time"]),
+];
+
+/// Optional exchange message fields [`Gateio`] is expected to send, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields). None yet, since the only optional
+/// field of a streamed [`Gateio`] model is the `error` of a [`GateioMessage`](message::GateioMessage).
+pub const EXPECTED_FIELDS: crate::parser::strict::ExpectedFields = &[];
+
+/// [`Gateio`] server [`PingInterval`] duration.
+///
+/// Gateio silently drops connections that do not send application-level pings, so they are sent
+/// at a fixed interval regardless of how busy the connection is.
+///
+/// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#application-ping-pong>
+pub const PING_INTERVAL_GATEIO: Duration = Duration::from_secs(10);
+
+/// Number of consecutive [`Gateio`] pings without a pong after which the connection is ended
+/// & re-connected, see [`MissedPongs`](super::ping::MissedPongs).
+pub const MAX_MISSED_PONGS_GATEIO: u32 = 3;
+
+/// [`ExchangeServer`] of a [`Gateio<Server>`](Gateio) exchange, defining the [`GateioSettle`]
+/// currency of the futures contracts it serves, and it's application-level ping & pong.
+pub trait GateioServer: ExchangeServer {
+    /// [`GateioSettle`] currency of the futures contracts served, or `None` for spot.
+    const SETTLE: Option<GateioSettle>;
+
+    /// Channel of the application-level pong sent by the server in reply to a
+    /// [`GateioServer::ping`], eg/ `spot.pong`.
+    const PONG_CHANNEL: &'static str;
+
+    /// Construct the application-level ping expected by the server, eg/ `spot.ping`.
+    fn ping() -> WsMessage;
+}
+
 /// Generic [`Gateio<Server>`](Gateio) exchange.
 ///
 /// ### Notes
@@ -49,7 +107,7 @@ pub struct Gateio<Server> {
 
 impl<Server> Connector for Gateio<Server>
 where
-    Server: ExchangeServer,
+    Server: GateioServer,
 {
     const ID: ExchangeId = Server::ID;
     type Channel = GateioChannel;
@@ -62,6 +120,30 @@ where
         Url::parse(Server::websocket_url()).map_err(SocketError::UrlParse)
     }
 
+    fn ping_interval() -> Option<PingInterval> {
+        Some(
+            PingInterval::new(tokio::time::interval(PING_INTERVAL_GATEIO), Server::ping)
+                .with_max_missed_pongs(MAX_MISSED_PONGS_GATEIO),
+        )
+    }
+
+    fn pong(payload: &str) -> bool {
+        message::is_pong(payload, Server::PONG_CHANNEL)
+    }
+
+    fn unsupported_instrument(instrument: &Instrument) -> Option<String> {
+        // Futures contracts are quoted in the settle currency, so any other quote would
+        // subscribe to a different contract than the one requested
+        let settle = Server::SETTLE?;
+        let (_, quote) = exchange_assets(Self::ID, instrument);
+        (!quote.eq_ignore_ascii_case(settle.contract_quote())).then(|| {
+            format!(
+                "{quote} quoted contracts, expected {} for {settle:?} settled contracts",
+                settle.contract_quote()
+            )
+        })
+    }
+
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
         exchange_subs
             .into_iter()
@@ -82,7 +164,7 @@ where
 
 impl<'de, Server> serde::Deserialize<'de> for Gateio<Server>
 where
-    Server: ExchangeServer,
+    Server: GateioServer,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -104,7 +186,7 @@ where
 
 impl<Server> serde::Serialize for Gateio<Server>
 where
-    Server: ExchangeServer,
+    Server: GateioServer,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -114,3 +196,140 @@ where
         serializer.serialize_str(exchange_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        futures::{GateioFuturesBtc, GateioFuturesUsd},
+        spot::GateioSpot,
+        *,
+    };
+    use serde_json::Value;
+
+    fn ping<Exchange>() -> Value
+    where
+        Exchange: Connector,
+    {
+        let ping_interval = Exchange::ping_interval().unwrap();
+        assert_eq!(ping_interval.interval.period(), PING_INTERVAL_GATEIO);
+        assert!(ping_interval.adaptive.is_none());
+        assert_eq!(
+            ping_interval.missed_pongs.map(|policy| policy.max_missed),
+            Some(MAX_MISSED_PONGS_GATEIO)
+        );
+
+        match (ping_interval.ping)() {
+            WsMessage::Text(ping) => serde_json::from_str(&ping).unwrap(),
+            message => panic!("unexpected ping: {message:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gateio_ping() {
+        struct TestCase {
+            actual: Value,
+            expected_channel: &'static str,
+            expected_time: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: GateioSpot ping has no time
+                actual: ping::<GateioSpot>(),
+                expected_channel: "spot.ping",
+                expected_time: false,
+            },
+            TestCase {
+                // TC1: GateioFuturesUsd ping has the current time in seconds
+                actual: ping::<GateioFuturesUsd>(),
+                expected_channel: "futures.ping",
+                expected_time: true,
+            },
+            TestCase {
+                // TC2: GateioFuturesBtc ping has the current time in seconds
+                actual: ping::<GateioFuturesBtc>(),
+                expected_channel: "futures.ping",
+                expected_time: true,
+            },
+        ];
+
+        for (index, mut test) in tests.into_iter().enumerate() {
+            if test.expected_time {
+                let time = test.actual["time"].as_i64().unwrap();
+                let now = chrono::Utc::now().timestamp();
+                assert!((now - 5..=now).contains(&time), "TC{} failed", index);
+                test.actual.as_object_mut().unwrap().remove("time");
+            }
+
+            assert_eq!(
+                test.actual,
+                json!({"channel": test.expected_channel}),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_gateio_pong() {
+        struct TestCase {
+            input: &'static str,
+            expected_spot: bool,
+            expected_futures: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot pong
+                input: r#"{"time":1545404023,"time_ms":1545404023123,"channel":"spot.pong","event":"","result":null}"#,
+                expected_spot: true,
+                expected_futures: false,
+            },
+            TestCase {
+                // TC1: futures pong
+                input: r#"{"time":1545404023,"time_ms":1545404023123,"channel":"futures.pong","event":"","result":null}"#,
+                expected_spot: false,
+                expected_futures: true,
+            },
+            TestCase {
+                // TC2: trade is not a pong
+                input: r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.trades","event":"update","result":{"id":309143071,"create_time":1606292218,"create_time_ms":"1606292218213.4578","side":"sell","currency_pair":"GT_USDT","amount":"16.4700000000","price":"0.4705000000"}}"#,
+                expected_spot: false,
+                expected_futures: false,
+            },
+            TestCase {
+                // TC3: pong channel mentioned outside of the channel field is not a pong
+                input: r#"{"channel":"spot.trades","event":"subscribe","error":{"code":2,"message":"spot.pong"},"result":null}"#,
+                expected_spot: false,
+                expected_futures: false,
+            },
+            TestCase {
+                // TC4: malformed payload is not a pong
+                input: r#"{"channel":"spot.pong""#,
+                expected_spot: false,
+                expected_futures: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(
+                GateioSpot::pong(test.input),
+                test.expected_spot,
+                "TC{} failed",
+                index
+            );
+            assert_eq!(
+                GateioFuturesUsd::pong(test.input),
+                test.expected_futures,
+                "TC{} failed",
+                index
+            );
+            assert_eq!(
+                GateioFuturesBtc::pong(test.input),
+                test.expected_futures,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+}
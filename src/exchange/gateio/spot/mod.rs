@@ -1,12 +1,14 @@
 use self::trade::GateioSpotTrade;
-use super::Gateio;
+use super::{futures::GateioSettle, Gateio, GateioServer};
 use crate::{
     exchange::{ExchangeId, ExchangeServer, StreamSelector},
     subscription::trade::PublicTrades,
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
+use barter_integration::protocol::websocket::WsMessage;
 use barter_macro::{DeExchange, SerExchange};
+use serde_json::json;
 
 /// Public trades types.
 pub mod trade;
@@ -33,6 +35,21 @@ impl ExchangeServer for GateioServerSpot {
     }
 }
 
+impl GateioServer for GateioServerSpot {
+    const SETTLE: Option<GateioSettle> = None;
+    const PONG_CHANNEL: &'static str = "spot.pong";
+
+    /// [`GateioSpot`] application-level ping.
+    ///
+    /// See docs: <https://www.gate.io/docs/developers/apiv4/ws/en/#application-ping-pong>
+    /// ```json
+    /// {"channel": "spot.ping"}
+    /// ```
+    fn ping() -> WsMessage {
+        WsMessage::Text(json!({"channel": "spot.ping"}).to_string())
+    }
+}
+
 impl StreamSelector<PublicTrades> for GateioSpot {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, GateioSpotTrade>>;
 }
@@ -1,13 +1,15 @@
 use super::super::message::GateioMessage;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_integration::model::{Exchange, Instrument, Side, SubscriptionId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Terse type alias for an [`GateioSpot`](super::GateioSpot) real-time trades WebSocket message.
 pub type GateioSpotTrade = GateioMessage<GateioSpotTradeInner>;
@@ -33,15 +35,15 @@ pub struct GateioSpotTradeInner {
     pub market: String,
     #[serde(
         rename = "create_time_ms",
-        deserialize_with = "barter_integration::de::de_str_f64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
     pub id: u64,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
 
     #[serde(alias = "size", deserialize_with = "barter_integration::de::de_str")]
-    pub amount: f64,
+    pub amount: Num,
     /// Taker [`Side`] of the trade.
     pub side: Side,
 }
@@ -52,11 +54,22 @@ impl Identifier<Option<SubscriptionId>> for GateioSpotTrade {
     }
 }
 
-impl From<(ExchangeId, Instrument, GateioSpotTrade)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trade): (ExchangeId, Instrument, GateioSpotTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, GateioSpotTrade)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trade): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            GateioSpotTrade,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: trade.data.time,
-            received_time: Utc::now(),
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                trade.data.time,
+                TimestampUnit::Nanoseconds,
+            )),
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: PublicTrade {
@@ -65,6 +78,7 @@ impl From<(ExchangeId, Instrument, GateioSpotTrade)> for MarketIter<PublicTrade>
                 amount: trade.data.amount,
                 side: trade.data.side,
             },
+            meta: (),
         })])
     }
 }
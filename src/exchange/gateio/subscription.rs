@@ -35,7 +35,7 @@ impl Validator for GateioSubResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::exchange::gateio::message::GateioError;
+    use crate::exchange::gateio::{channel::GateioChannel, message::GateioError};
 
     mod de {
         use super::*;
@@ -61,7 +61,7 @@ mod tests {
                     }
                     "#,
                 expected: Ok(GateioSubResponse {
-                    channel: "spot.trades".to_string(),
+                    channel: GateioChannel::SPOT_TRADES,
                     error: None,
                     data: GateioSubResult {
                         status: "success".to_string(),
@@ -98,7 +98,7 @@ mod tests {
             TestCase {
                 // TC0: input response is successful subscription
                 input_response: GateioSubResponse {
-                    channel: "spot.trades".to_string(),
+                    channel: GateioChannel::SPOT_TRADES,
                     error: None,
                     data: GateioSubResult {
                         status: "success".to_string(),
@@ -109,7 +109,7 @@ mod tests {
             TestCase {
                 // TC1: input response is failed subscription
                 input_response: GateioSubResponse {
-                    channel: "spot.trades".to_string(),
+                    channel: GateioChannel::SPOT_TRADES,
                     error: Some(GateioError {
                         code: 0,
                         message: "".to_string(),
@@ -0,0 +1,426 @@
+use super::ExchangeId;
+use crate::{
+    error::{DataError, InvalidSubscription},
+    num::Num,
+    streams::config::StreamConfig,
+};
+use barter_integration::{error::SocketError, model::Instrument};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+};
+use tracing::debug;
+use url::Url;
+
+/// Trading status of an exchange market, as listed by the exchange REST API.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum MarketStatus {
+    Trading,
+    /// Listed but not currently trading, with the exchange specific status (eg/ "BREAK").
+    Halted(String),
+}
+
+/// Normalised description of an instrument listed by an exchange REST API (eg/ a Binance
+/// `exchangeInfo` symbol), as returned by [`fetch_instruments`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct InstrumentInfo {
+    pub exchange: ExchangeId,
+    /// Uppercase exchange specific market (eg/ "BTCUSDT") that
+    /// [`Subscription`](crate::subscription::Subscription)s to the instrument are identified by.
+    pub market: String,
+    /// Base, quote & [`InstrumentKind`](barter_integration::model::InstrumentKind) of the
+    /// instrument, eg/ (btc, usdt, future_perpetual).
+    pub instrument: Instrument,
+    /// Minimum price increment, if advertised.
+    pub tick_size: Option<Num>,
+    /// Minimum quantity increment, if advertised. Expressed in contracts for instruments with a
+    /// `contract_size`, else in the base asset.
+    pub lot_size: Option<Num>,
+    /// Quantity each contract represents, denominated in the base asset (eg/ 0.01 BTC), or the
+    /// quote asset for inverse contracts (eg/ 100 USD). `None` for spot instruments.
+    pub contract_size: Option<Num>,
+    pub status: MarketStatus,
+}
+
+impl InstrumentInfo {
+    /// Determine if the instrument is currently trading.
+    pub fn is_trading(&self) -> bool {
+        self.status == MarketStatus::Trading
+    }
+}
+
+/// Every market listed by an exchange REST API (eg/ Binance `exchangeInfo`), keyed by the
+/// uppercase exchange specific market (eg/ "BTCUSDT") that
+/// [`Subscription`](crate::subscription::Subscription)s are identified by.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct ExchangeMarkets(pub HashMap<String, MarketStatus>);
+
+impl ExchangeMarkets {
+    /// [`MarketStatus`] of the provided exchange specific market, if it's listed.
+    pub fn status(&self, market: &str) -> Option<&MarketStatus> {
+        self.0.get(&market.to_uppercase())
+    }
+}
+
+impl From<&[InstrumentInfo]> for ExchangeMarkets {
+    fn from(instruments: &[InstrumentInfo]) -> Self {
+        instruments
+            .iter()
+            .map(|info| (&info.market, info.status.clone()))
+            .collect()
+    }
+}
+
+impl<Market> FromIterator<(Market, MarketStatus)> for ExchangeMarkets
+where
+    Market: AsRef<str>,
+{
+    fn from_iter<Iter>(markets: Iter) -> Self
+    where
+        Iter: IntoIterator<Item = (Market, MarketStatus)>,
+    {
+        Self(
+            markets
+                .into_iter()
+                .map(|(market, status)| (market.as_ref().to_uppercase(), status))
+                .collect(),
+        )
+    }
+}
+
+/// Exchange specific market (eg/ "BTCUSDT") of a requested
+/// [`Subscription`](crate::subscription::Subscription), checked against the
+/// [`ExchangeMarkets`] listed by the exchange during pre-flight validation.
+///
+/// See [`StreamBuilder::validate_instruments`](crate::streams::builder::StreamBuilder::validate_instruments).
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct RequestedMarket {
+    pub exchange: ExchangeId,
+    pub instrument: Instrument,
+    pub market: String,
+}
+
+/// [`InstrumentInfo`]s fetched from each exchange, keyed by any REST base url override, cached
+/// for the lifetime of the process.
+type InstrumentsCache = Mutex<HashMap<(ExchangeId, Option<Url>), Arc<[InstrumentInfo]>>>;
+
+fn cache() -> &'static InstrumentsCache {
+    static CACHE: OnceLock<InstrumentsCache> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Fetch the normalised catalog of every [`InstrumentInfo`] listed by the provided exchange via
+/// it's public REST API (eg/ Binance `exchangeInfo`, Kraken `AssetPairs`).
+///
+/// Returns a [`SocketError::Unsupported`] if the exchange does not support listing it's
+/// instruments (eg/ Bitfinex). Shares the per process cache of [`fetch_markets`].
+///
+/// ```rust,no_run
+/// # async fn usdt_perpetuals() -> Result<(), barter_data::error::DataError> {
+/// use barter_data::exchange::{instruments::fetch_instruments, ExchangeId};
+/// use barter_integration::model::InstrumentKind;
+///
+/// let usdt_perpetuals = fetch_instruments(ExchangeId::Okx)
+///     .await?
+///     .into_iter()
+///     .filter(|info| info.is_trading())
+///     .filter(|info| info.instrument.kind == InstrumentKind::FuturePerpetual)
+///     .filter(|info| info.instrument.quote.as_ref() == "usdt")
+///     .collect::<Vec<_>>();
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_instruments(exchange: ExchangeId) -> Result<Vec<InstrumentInfo>, DataError> {
+    fetch_instruments_with(exchange, &StreamConfig::default()).await
+}
+
+/// Fetch the normalised catalog of every [`InstrumentInfo`] listed by the provided exchange, as
+/// [`fetch_instruments`], using the REST configuration (eg/
+/// [`Proxy`](crate::subscriber::proxy::Proxy)) of the provided [`StreamConfig`].
+pub async fn fetch_instruments_with(
+    exchange: ExchangeId,
+    config: &StreamConfig,
+) -> Result<Vec<InstrumentInfo>, DataError> {
+    match fetch_catalog(exchange, config).await? {
+        Some(instruments) => Ok(instruments.to_vec()),
+        None => Err(DataError::Socket(SocketError::Unsupported {
+            entity: "instrument catalog",
+            item: exchange.to_string(),
+        })),
+    }
+}
+
+/// Fetch the [`ExchangeMarkets`] listed by the provided exchange via it's REST API, or `None` if
+/// the exchange does not support listing it's markets (eg/ Bitfinex).
+///
+/// Derived from the exchange [`fetch_instruments`] catalog. Catalogs are cached per process, so only the first
+/// call for each exchange makes a request. Failures are not cached, so the next call retries.
+pub async fn fetch_markets(exchange: ExchangeId) -> Result<Option<ExchangeMarkets>, DataError> {
+    fetch_markets_with(exchange, &StreamConfig::default()).await
+}
+
+/// Fetch the [`ExchangeMarkets`] listed by the provided exchange, as [`fetch_markets`], using the
+/// REST configuration (eg/ [`Proxy`](crate::subscriber::proxy::Proxy)) of the provided
+/// [`StreamConfig`], respecting it's [`Endpoints`](super::endpoint::Endpoints) REST base url override.
+pub async fn fetch_markets_with(
+    exchange: ExchangeId,
+    config: &StreamConfig,
+) -> Result<Option<ExchangeMarkets>, DataError> {
+    fetch_catalog(exchange, config)
+        .await
+        .map(|instruments| instruments.map(|instruments| ExchangeMarkets::from(&*instruments)))
+}
+
+/// Fetch the cached [`InstrumentInfo`] catalog of the provided exchange, fetching & caching it
+/// on first use, or `None` if it does not support listing it's instruments.
+async fn fetch_catalog(
+    exchange: ExchangeId,
+    config: &StreamConfig,
+) -> Result<Option<Arc<[InstrumentInfo]>>, DataError> {
+    let key = (exchange, config.endpoints.rest.clone());
+    if let Some(instruments) = cache().lock().unwrap().get(&key) {
+        return Ok(Some(Arc::clone(instruments)));
+    }
+
+    let Some(instruments) = fetch_listed_instruments(exchange, config).await? else {
+        return Ok(None);
+    };
+
+    debug!(
+        %exchange,
+        instruments = instruments.len(),
+        "fetched exchange instrument catalog"
+    );
+    let instruments = Arc::<[InstrumentInfo]>::from(instruments);
+    cache()
+        .lock()
+        .unwrap()
+        .insert(key, Arc::clone(&instruments));
+    Ok(Some(instruments))
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the provided exchange, or `None` if it does not
+/// support listing it's instruments.
+#[cfg_attr(
+    not(any(
+        feature = "binance",
+        feature = "coinbase",
+        feature = "gateio",
+        feature = "kraken",
+        feature = "okx"
+    )),
+    allow(unused_variables)
+)]
+async fn fetch_listed_instruments(
+    exchange: ExchangeId,
+    config: &StreamConfig,
+) -> Result<Option<Vec<InstrumentInfo>>, DataError> {
+    match exchange {
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceSpot => super::binance::instruments::fetch_instruments(
+            exchange,
+            super::binance::spot::HTTP_BASE_URL_BINANCE_SPOT,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceFuturesUsd => super::binance::instruments::fetch_instruments(
+            exchange,
+            super::binance::futures::HTTP_BASE_URL_BINANCE_FUTURES_USD,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceUs => super::binance::instruments::fetch_instruments(
+            exchange,
+            super::binance::us::HTTP_BASE_URL_BINANCE_US,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "coinbase")]
+        ExchangeId::Coinbase => super::coinbase::instruments::fetch_instruments(config)
+            .await
+            .map(Some),
+        #[cfg(feature = "gateio")]
+        ExchangeId::GateioSpot => super::gateio::instruments::fetch_spot_instruments(config)
+            .await
+            .map(Some),
+        #[cfg(feature = "gateio")]
+        ExchangeId::GateioFuturesUsd => super::gateio::instruments::fetch_futures_instruments(
+            exchange,
+            super::gateio::futures::GateioSettle::Usdt,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "gateio")]
+        ExchangeId::GateioFuturesBtc => super::gateio::instruments::fetch_futures_instruments(
+            exchange,
+            super::gateio::futures::GateioSettle::Btc,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "kraken")]
+        ExchangeId::Kraken => super::kraken::instruments::fetch_instruments(config)
+            .await
+            .map(Some),
+        #[cfg(feature = "okx")]
+        ExchangeId::Okx => super::okx::instruments::fetch_instruments(config)
+            .await
+            .map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Parse an exchange size (eg/ tick size "0.01"), or `None` if it's empty or invalid.
+pub(crate) fn parse_size(size: &str) -> Option<Num> {
+    size.parse().ok()
+}
+
+/// Size of the smallest increment with the provided number of decimal places, eg/ 0.01 for 2.
+#[cfg_attr(not(any(feature = "gateio", feature = "kraken")), allow(dead_code))]
+pub(crate) fn decimal_places_size(decimals: u32) -> Option<Num> {
+    match decimals {
+        0 => parse_size("1"),
+        decimals => parse_size(&format!("0.{}1", "0".repeat(decimals as usize - 1))),
+    }
+}
+
+/// Determine every [`RequestedMarket`] that is not listed in, or not trading according to, the
+/// provided [`ExchangeMarkets`].
+pub fn invalid_markets<'a, Requested>(
+    requested: Requested,
+    markets: &ExchangeMarkets,
+) -> Vec<InvalidSubscription>
+where
+    Requested: IntoIterator<Item = &'a RequestedMarket>,
+{
+    requested
+        .into_iter()
+        .filter_map(|requested| {
+            let reason = match markets.status(&requested.market) {
+                Some(MarketStatus::Trading) => return None,
+                Some(MarketStatus::Halted(status)) => format!(
+                    "{} market {} is not trading (status: {status})",
+                    requested.exchange, requested.market
+                ),
+                None => format!(
+                    "{} market {} is not listed by the exchange",
+                    requested.exchange, requested.market
+                ),
+            };
+
+            Some(InvalidSubscription {
+                exchange: requested.exchange,
+                instrument: requested.instrument.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    fn requested(base: &str, quote: &str, market: &str) -> RequestedMarket {
+        RequestedMarket {
+            exchange: ExchangeId::BinanceSpot,
+            instrument: Instrument::from((base, quote, InstrumentKind::Spot)),
+            market: market.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_invalid_markets() {
+        struct TestCase {
+            input: RequestedMarket,
+            expected: Option<&'static str>,
+        }
+
+        let markets = ExchangeMarkets::from_iter([
+            ("btcusdt", MarketStatus::Trading),
+            ("LUNAUSDT", MarketStatus::Halted("BREAK".to_string())),
+        ]);
+
+        let tests = vec![
+            TestCase {
+                // TC0: listed & trading market
+                input: requested("btc", "usdt", "BTCUSDT"),
+                expected: None,
+            },
+            TestCase {
+                // TC1: unlisted market (eg/ typo)
+                input: requested("btc", "usd", "BTCUSD"),
+                expected: Some("binance_spot market BTCUSD is not listed by the exchange"),
+            },
+            TestCase {
+                // TC2: listed market that is not trading
+                input: requested("luna", "usdt", "LUNAUSDT"),
+                expected: Some("binance_spot market LUNAUSDT is not trading (status: BREAK)"),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = invalid_markets([&test.input], &markets);
+            let expected = test
+                .expected
+                .map(|reason| InvalidSubscription {
+                    exchange: test.input.exchange,
+                    instrument: test.input.instrument.clone(),
+                    reason: reason.to_string(),
+                })
+                .into_iter()
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_decimal_places_size() {
+        assert_eq!(decimal_places_size(0), parse_size("1"));
+        assert_eq!(decimal_places_size(1), parse_size("0.1"));
+        assert_eq!(decimal_places_size(8), parse_size("0.00000001"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_instruments_uses_cache() {
+        // Bitfinex does not support listing it's instruments, so no request is made
+        assert_eq!(fetch_markets(ExchangeId::Bitfinex).await.unwrap(), None);
+        assert!(matches!(
+            fetch_instruments(ExchangeId::Bitfinex).await,
+            Err(DataError::Socket(SocketError::Unsupported { .. }))
+        ));
+
+        let instruments = vec![InstrumentInfo {
+            exchange: ExchangeId::Kraken,
+            market: "XBT/USD".to_string(),
+            instrument: Instrument::from(("xbt", "usd", InstrumentKind::Spot)),
+            tick_size: parse_size("0.1"),
+            lot_size: parse_size("0.00000001"),
+            contract_size: None,
+            status: MarketStatus::Trading,
+        }];
+        cache()
+            .lock()
+            .unwrap()
+            .insert((ExchangeId::Kraken, None), Arc::from(instruments.clone()));
+
+        assert_eq!(
+            fetch_instruments(ExchangeId::Kraken).await.unwrap(),
+            instruments
+        );
+        assert_eq!(
+            fetch_markets(ExchangeId::Kraken).await.unwrap(),
+            Some(ExchangeMarkets::from_iter([(
+                "XBT/USD",
+                MarketStatus::Trading
+            )]))
+        );
+    }
+}
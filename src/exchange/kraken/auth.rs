@@ -0,0 +1,503 @@
+use super::{channel::KrakenChannel, market::KrakenMarket, Kraken, HTTP_BASE_URL_KRAKEN};
+use crate::{
+    error::DataError,
+    exchange::Connector,
+    streams::config::StreamConfig,
+    subscriber::{
+        auth::{AuthToken, Credentials},
+        proxy::request_json,
+    },
+    subscription::{SubKind, Subscription},
+    transformer::ExchangeTransformer,
+    ExchangeWsStream, Identifier, MarketStream,
+};
+use async_trait::async_trait;
+use barter_integration::error::SocketError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::debug;
+
+/// [`Kraken`] REST API path of the `GetWebSocketsToken` endpoint, which is also the path signed
+/// by the `API-Sign` header.
+///
+/// See docs: <https://docs.kraken.com/rest/#tag/Websockets-Authentication>
+pub const KRAKEN_WS_TOKEN_PATH: &str = "/0/private/GetWebSocketsToken";
+
+/// [`Kraken`] `GetWebSocketsToken` REST response.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/rest/#tag/Websockets-Authentication>
+/// ```json
+/// {
+///   "error": [],
+///   "result": {
+///     "token": "1Dwc4lzSwNWOAwkMdqhssNNFhs1ed606d1WcF3XfEMw",
+///     "expires": 900
+///   }
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenWsTokenResponse {
+    #[serde(default)]
+    pub error: Vec<String>,
+    pub result: Option<KrakenWsToken>,
+}
+
+/// [`Kraken`] WebSockets token, which must be used to subscribe within `expires` seconds of being
+/// created, but remains valid for as long as the resulting private subscription is maintained.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenWsToken {
+    pub token: String,
+    pub expires: u64,
+}
+
+impl KrakenWsTokenResponse {
+    /// Extract the [`KrakenWsToken`], failing with a [`DataError::Auth`] if [`Kraken`] returned
+    /// an error (eg/ "EAPI:Invalid key").
+    #[allow(clippy::result_large_err)]
+    pub fn into_token(self) -> Result<KrakenWsToken, DataError> {
+        match (self.error.is_empty(), self.result) {
+            (true, Some(token)) => Ok(token),
+            (_, _) => Err(DataError::Auth {
+                exchange: Kraken::ID,
+                reason: format!(
+                    "failed to fetch WebSockets token: {}",
+                    self.error.join(", ")
+                ),
+            }),
+        }
+    }
+}
+
+/// Fetch a new WebSockets token of the account owning the provided [`Credentials`] via the
+/// signed `GetWebSocketsToken` REST endpoint, using the
+/// [`Proxy`](crate::subscriber::proxy::Proxy) of the provided [`StreamConfig`].
+///
+/// [`Credentials`] rejected by [`Kraken`] fail with a [`DataError::Auth`].
+pub async fn fetch_ws_token(
+    credentials: &Credentials,
+    config: &StreamConfig,
+) -> Result<KrakenWsToken, DataError> {
+    // Nonce must be greater than that of any previous request signed with the same API key
+    let nonce = Utc::now().timestamp_millis();
+    let post_data = format!("nonce={nonce}");
+    let signature = sign(
+        &credentials.secret,
+        KRAKEN_WS_TOKEN_PATH,
+        &nonce.to_string(),
+        &post_data,
+    )
+    .ok_or_else(|| DataError::Auth {
+        exchange: Kraken::ID,
+        reason: "API secret is not valid Base64".to_owned(),
+    })?;
+
+    let url = format!(
+        "{}/private/GetWebSocketsToken",
+        config.endpoints.rest_base_url(HTTP_BASE_URL_KRAKEN)
+    );
+    let headers = [
+        ("API-Key", credentials.api_key.as_str()),
+        ("API-Sign", signature.as_str()),
+        ("Content-Type", "application/x-www-form-urlencoded"),
+    ];
+
+    match request_json::<KrakenWsTokenResponse>(
+        config.proxy.as_ref(),
+        Method::POST,
+        &url,
+        &headers,
+        Some(post_data),
+    )
+    .await
+    {
+        Ok(response) => response.into_token(),
+        Err(DataError::Socket(SocketError::HttpResponse(status, body)))
+            if status.is_client_error() =>
+        {
+            Err(DataError::Auth {
+                exchange: Kraken::ID,
+                reason: format!("failed to fetch WebSockets token with status {status}: {body}"),
+            })
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Sign the provided [`Kraken`] REST request using the provided Base64 encoded API secret,
+/// generating the `API-Sign` header
+/// (Base64(HMAC_SHA512(Base64Decode(secret), path + SHA256(nonce + post_data)))).
+///
+/// Returns `None` if the secret is not valid Base64.
+pub fn sign(secret: &str, path: &str, nonce: &str, post_data: &str) -> Option<String> {
+    let secret = STANDARD.decode(secret).ok()?;
+    let digest = Sha256::new()
+        .chain_update(nonce)
+        .chain_update(post_data)
+        .finalize();
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(&secret).expect("HMAC can take a key of any size");
+    mac.update(path.as_bytes());
+    mac.update(&digest);
+    Some(STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+/// [`MarketStream`] of the private [`Kraken`] channels (eg/ [`KrakenChannel::OWN_TRADES`]),
+/// authenticated with a WebSockets token of the account owning the API key of the
+/// [`StreamConfig::credentials`].
+///
+/// - On initialisation a new token is fetched via the REST API, and set as the
+///   [`StreamConfig::auth_token`] of the connection, so it's included in the [`Kraken`]
+///   subscription requests.
+/// - Tokens must be used within 15 minutes of being created, but don't expire whilst a private
+///   subscription is maintained. Since every (re)connection of the consumer loop fetches a new
+///   token, an expired token is never used.
+/// - If [`Kraken`] invalidates the session of an established connection, a terminal
+///   [`DataError::Auth`] is yielded so the consumer loop re-connects with a new token.
+pub struct KrakenPrivateStream<Transformer>
+where
+    Transformer: barter_integration::Transformer,
+{
+    stream: ExchangeWsStream<Transformer>,
+}
+
+impl<Transformer> Debug for KrakenPrivateStream<Transformer>
+where
+    Transformer: barter_integration::Transformer,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KrakenPrivateStream")
+            .field("transformer", &std::any::type_name::<Transformer>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Transformer> Stream for KrakenPrivateStream<Transformer>
+where
+    Transformer: barter_integration::Transformer,
+    ExchangeWsStream<Transformer>: Stream + Unpin,
+{
+    type Item = <ExchangeWsStream<Transformer> as Stream>::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+#[async_trait]
+impl<Kind, Transformer, Message> MarketStream<Kraken, Kind> for KrakenPrivateStream<Transformer>
+where
+    Kind: SubKind + Send + Sync,
+    Transformer: ExchangeTransformer<Kraken, Kind, Input = (DateTime<Utc>, Message)> + Send,
+    Message: DeserializeOwned,
+    Kind::Event: Send,
+{
+    async fn init_with(
+        subscriptions: &[Subscription<Kraken, Kind>],
+        config: &StreamConfig,
+    ) -> Result<Self, DataError>
+    where
+        Subscription<Kraken, Kind>: Identifier<KrakenChannel> + Identifier<KrakenMarket>,
+    {
+        let credentials = config.credentials.as_ref().ok_or_else(|| DataError::Auth {
+            exchange: Kraken::ID,
+            reason: "private channels require Credentials with an API key".to_owned(),
+        })?;
+
+        let token = fetch_ws_token(credentials, config).await?;
+        debug!(exchange = %Kraken::ID, expires = token.expires, "fetched WebSockets token");
+
+        let config = StreamConfig {
+            auth_token: Some(AuthToken(token.token)),
+            ..config.clone()
+        };
+        let stream = <ExchangeWsStream<Transformer> as MarketStream<Kraken, Kind>>::init_with(
+            subscriptions,
+            &config,
+        )
+        .await?;
+
+        Ok(Self { stream })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::{endpoint::Endpoints, kraken::user::KrakenFillsTransformer, ExchangeId},
+        subscription::fill::{Fills, Liquidity},
+    };
+    use barter_integration::model::{InstrumentKind, Side};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use url::Url;
+
+    const SECRET: &str =
+        "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==";
+
+    #[test]
+    fn test_kraken_sign() {
+        struct TestCase {
+            secret: &'static str,
+            path: &'static str,
+            nonce: &'static str,
+            post_data: &'static str,
+            expected: Option<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Kraken documentation example
+                secret: SECRET,
+                path: "/0/private/AddOrder",
+                nonce: "1616492376594",
+                post_data: "nonce=1616492376594&ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25",
+                expected: Some("4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ=="),
+            },
+            TestCase {
+                // TC1: GetWebSocketsToken request
+                secret: SECRET,
+                path: KRAKEN_WS_TOKEN_PATH,
+                nonce: "1700000000000",
+                post_data: "nonce=1700000000000",
+                expected: Some("L/HkUJA19dVkFWobMGRZ+ieHJspDjuRKCO4HaDSDOJLC6G5uhsGzdjFlh7pqRkri+0xgT0dgSyICpRtNqxzHsg=="),
+            },
+            TestCase {
+                // TC2: secret is not Base64
+                secret: "not base64!",
+                path: KRAKEN_WS_TOKEN_PATH,
+                nonce: "1700000000000",
+                post_data: "nonce=1700000000000",
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = sign(test.secret, test.path, test.nonce, test.post_data);
+            assert_eq!(actual.as_deref(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    /// Serve the provided HTTP response to a single REST request, recording the lowercase request
+    /// line, "API-Key" & "API-Sign" headers, and the body of the request.
+    async fn mock_rest(response: &'static str) -> (Url, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let request = Arc::new(Mutex::new(Vec::new()));
+
+        tokio::spawn({
+            let request = Arc::clone(&request);
+            async move {
+                let (mut tcp, _) = listener.accept().await.unwrap();
+
+                // Read until the whole body has been received, since it may be sent separately
+                let mut buffer = Vec::new();
+                let (head, body) = loop {
+                    let mut chunk = vec![0u8; 4096];
+                    let read = tcp.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                    let received = String::from_utf8_lossy(&buffer).into_owned();
+                    if let Some((head, body)) = received.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(str::to_owned)
+                            })
+                            .and_then(|length| length.parse::<usize>().ok())
+                            .unwrap_or_default();
+                        if body.len() >= length {
+                            break (head.to_owned(), body.to_owned());
+                        }
+                    }
+                };
+
+                let header = |name: &str| {
+                    head.lines()
+                        .find_map(|line| {
+                            let (key, value) = line.split_once(": ")?;
+                            key.eq_ignore_ascii_case(name).then(|| value.to_owned())
+                        })
+                        .unwrap_or_default()
+                };
+                *request.lock().unwrap() = vec![
+                    head.lines().next().unwrap_or_default().to_lowercase(),
+                    header("API-Key"),
+                    header("API-Sign"),
+                    body,
+                ];
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                    response.len()
+                );
+                tcp.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, request)
+    }
+
+    /// Accept a single WebSocket connection, recording the first message received (ie/ the
+    /// subscription request) before sending the provided messages & holding the connection open.
+    async fn mock_private_ws(messages: Vec<&'static str>) -> (Url, Arc<Mutex<Option<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        let subscription = Arc::new(Mutex::new(None));
+
+        tokio::spawn({
+            let subscription = Arc::clone(&subscription);
+            async move {
+                use futures::SinkExt;
+                use tokio_tungstenite::tungstenite::Message;
+
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+
+                if let Some(Ok(Message::Text(request))) = websocket.next().await {
+                    *subscription.lock().unwrap() = Some(request);
+                }
+                for message in messages {
+                    websocket
+                        .send(Message::Text(message.to_owned()))
+                        .await
+                        .unwrap();
+                }
+                while websocket.next().await.is_some() {}
+            }
+        });
+
+        (url, subscription)
+    }
+
+    fn subscriptions() -> [Subscription<Kraken, Fills>; 1] {
+        [Subscription::from((
+            Kraken,
+            "btc",
+            "eur",
+            InstrumentKind::Spot,
+            Fills,
+        ))]
+    }
+
+    #[tokio::test]
+    async fn test_kraken_private_stream_subscribes_with_fetched_token() {
+        let (rest, request) = mock_rest(
+            r#"{"error":[],"result":{"token":"WW91ciBhdXRoZW50aWNhdGlvbiB0b2tlbg","expires":900}}"#,
+        )
+        .await;
+        let (websocket, subscription) = mock_private_ws(vec![
+            r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"online","version":"1.0.0"}"#,
+            r#"{"channelName":"ownTrades","event":"subscriptionStatus","status":"subscribed","subscription":{"name":"ownTrades"}}"#,
+            r#"[[{"TDLH43-DVQXD-2KHVYY":{"ordertxid":"OGTT3Y-C6I3P-XRI6HX","ordertype":"limit","pair":"XBT/EUR","price":"30000.5","time":"1560516023.070651","type":"buy","vol":"0.5"}}],"ownTrades",{"sequence":1}]"#,
+        ])
+        .await;
+
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: Some(websocket),
+                rest: Some(rest),
+            },
+            credentials: Some(Credentials::new("api_key", SECRET)),
+            ..StreamConfig::default()
+        };
+
+        let mut stream = <KrakenPrivateStream<KrakenFillsTransformer> as MarketStream<
+            Kraken,
+            Fills,
+        >>::init_with(&subscriptions(), &config)
+        .await
+        .unwrap();
+        let fill = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(fill.kind.trade_id, "TDLH43-DVQXD-2KHVYY");
+        assert_eq!(fill.kind.side, Side::Buy);
+        assert_eq!(fill.kind.liquidity, Liquidity::Maker);
+
+        // Token request is signed with the nonce of it's body
+        let request = request.lock().unwrap().clone();
+        let nonce = request[3].strip_prefix("nonce=").unwrap();
+        assert_eq!(request[0], "post /private/getwebsocketstoken http/1.1");
+        assert_eq!(request[1], "api_key");
+        assert_eq!(
+            Some(request[2].as_str()),
+            sign(SECRET, KRAKEN_WS_TOKEN_PATH, nonce, &request[3]).as_deref()
+        );
+
+        // Subscription is authenticated with the fetched token
+        let subscription = subscription.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&subscription).unwrap(),
+            serde_json::json!({
+                "event": "subscribe",
+                "subscription": {
+                    "name": "ownTrades",
+                    "token": "WW91ciBhdXRoZW50aWNhdGlvbiB0b2tlbg",
+                    "snapshot": false
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_kraken_private_stream_init_errors() {
+        // No Credentials
+        let error =
+            <KrakenPrivateStream<KrakenFillsTransformer> as MarketStream<Kraken, Fills>>::init(
+                &subscriptions(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                error,
+                DataError::Auth {
+                    exchange: ExchangeId::Kraken,
+                    ..
+                }
+            ),
+            "unexpected error: {error}"
+        );
+
+        // API key rejected when fetching the token
+        let (rest, _) = mock_rest(r#"{"error":["EAPI:Invalid key"]}"#).await;
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: None,
+                rest: Some(rest),
+            },
+            credentials: Some(Credentials::new("invalid", SECRET)),
+            ..StreamConfig::default()
+        };
+        let error =
+            <KrakenPrivateStream<KrakenFillsTransformer> as MarketStream<Kraken, Fills>>::init_with(
+                &subscriptions(),
+                &config,
+            )
+            .await
+            .unwrap_err();
+
+        match error {
+            DataError::Auth { exchange, reason } => {
+                assert_eq!(exchange, ExchangeId::Kraken);
+                assert!(
+                    reason.contains("EAPI:Invalid key"),
+                    "unexpected reason: {reason}"
+                );
+            }
+            error => panic!("expected DataError::Auth, found: {error}"),
+        }
+    }
+}
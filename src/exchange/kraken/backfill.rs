@@ -0,0 +1,263 @@
+use super::{market::KrakenMarket, Kraken, HTTP_BASE_URL_KRAKEN};
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    exchange::{
+        backfill::{candle_events, fetch_candle_pages, Backfill},
+        Connector,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        candle::{Candle, Candles, Interval},
+        Subscription,
+    },
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::error::SocketError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+
+/// Maximum number of candles [`Kraken`] returns from the REST `OHLC` endpoint, including the
+/// still open candle. Older candles cannot be fetched.
+pub const KRAKEN_MAX_OHLC_LIMIT: usize = 720;
+
+/// [`Duration`] waited between [`Kraken`] REST `OHLC` requests, respecting the public endpoint
+/// rate limit of one request per second.
+pub const KRAKEN_OHLC_PAGE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// [`Kraken`] REST `OHLC` response.
+///
+/// See docs: <https://docs.kraken.com/rest/#tag/Market-Data/operation/getOHLCData>
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenOhlc {
+    #[serde(default)]
+    pub error: Vec<String>,
+    #[serde(default)]
+    pub result: Option<KrakenOhlcResult>,
+}
+
+/// [`Kraken`] REST `OHLC` result, keyed by the Kraken pair name (eg/ "XXBTZUSD").
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenOhlcResult {
+    pub last: u64,
+    #[serde(flatten)]
+    pub pairs: HashMap<String, Vec<KrakenOhlcRest>>,
+}
+
+/// [`Kraken`] REST `OHLC` candle.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/rest/#tag/Market-Data/operation/getOHLCData>
+/// ```json
+/// [
+///     1688671200,
+///     "30306.1",
+///     "30306.2",
+///     "30305.7",
+///     "30305.7",
+///     "30306.1",
+///     "3.39243896",
+///     23
+/// ]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenOhlcRest(
+    #[serde(deserialize_with = "crate::datetime::de_epoch_s_as_datetime_utc")] pub DateTime<Utc>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    pub String,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    pub u64,
+);
+
+impl KrakenOhlcRest {
+    /// Normalise into a [`Candle`] of the provided [`Interval`].
+    pub fn into_candle(self, interval: Interval) -> Option<Candle> {
+        Some(Candle {
+            close_time: self.0 + interval.duration() - chrono::Duration::milliseconds(1),
+            open: self.1,
+            high: self.2,
+            low: self.3,
+            close: self.4,
+            volume: self.6,
+            trade_count: self.7,
+            historical: true,
+        })
+    }
+}
+
+impl TryFrom<KrakenOhlc> for Vec<KrakenOhlcRest> {
+    type Error = DataError;
+
+    fn try_from(ohlc: KrakenOhlc) -> Result<Self, Self::Error> {
+        if !ohlc.error.is_empty() {
+            return Err(DataError::Socket(SocketError::Exchange(format!(
+                "Kraken OHLC error: {}",
+                ohlc.error.join(", ")
+            ))));
+        }
+
+        Ok(ohlc
+            .result
+            .into_iter()
+            .flat_map(|result| result.pairs.into_values())
+            .flatten()
+            .collect())
+    }
+}
+
+/// Determine the [`Kraken`] `OHLC` interval (in minutes) of the provided [`Interval`], if
+/// supported.
+pub fn kraken_ohlc_interval(interval: Interval) -> Option<u32> {
+    match interval {
+        Interval::M1 => Some(1),
+        Interval::M5 => Some(5),
+        Interval::M15 => Some(15),
+        Interval::M30 => Some(30),
+        Interval::H1 => Some(60),
+        Interval::H4 => Some(240),
+        Interval::D1 => Some(1440),
+        Interval::W1 => Some(10080),
+        Interval::M3 | Interval::H2 | Interval::H6 | Interval::H12 => None,
+    }
+}
+
+/// Fetch the most recent closed [`Candle`]s for the provided [`Subscription`] from the
+/// [`Kraken`] REST API `OHLC` endpoint, using the [`StreamConfig`] of the connection.
+///
+/// Kraken only serves the last [`KRAKEN_MAX_OHLC_LIMIT`] candles of each interval and cannot
+/// paginate backwards, so at most 719 closed candles are returned.
+pub async fn fetch_candles(
+    subscription: &Subscription<Kraken, Candles>,
+    limit: usize,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+    let market: KrakenMarket = subscription.id();
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_KRAKEN);
+    let interval = subscription.kind.0;
+    let proxy = config.proxy.as_ref();
+    let minutes = kraken_ohlc_interval(interval).ok_or_else(|| {
+        DataError::Socket(SocketError::Unsupported {
+            entity: "Kraken OHLC interval",
+            item: interval.to_string(),
+        })
+    })?;
+
+    // Kraken REST accepts the market "altname", ie/ without the "/" separator (eg/ "XBTUSD")
+    let url = format!(
+        "{base_url}/public/OHLC?pair={}&interval={minutes}",
+        market.0.replace('/', "")
+    );
+
+    let candles = fetch_candle_pages(
+        limit.min(KRAKEN_MAX_OHLC_LIMIT - 1),
+        KRAKEN_MAX_OHLC_LIMIT,
+        KRAKEN_OHLC_PAGE_INTERVAL,
+        |before, _| {
+            let url = url.clone();
+            async move {
+                if before.is_some() {
+                    return Ok(vec![]);
+                }
+
+                let ohlc = get_json::<KrakenOhlc>(proxy, &url).await?;
+                Ok(Vec::<KrakenOhlcRest>::try_from(ohlc)?
+                    .into_iter()
+                    .filter_map(|candle| candle.into_candle(interval))
+                    .collect())
+            }
+        },
+    )
+    .await?;
+
+    Ok(candle_events(Kraken::ID, &subscription.instrument, candles))
+}
+
+#[async_trait]
+impl Backfill<Candles> for Kraken {
+    async fn backfill(
+        subscription: &Subscription<Self, Candles>,
+        limit: usize,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+        fetch_candles(subscription, limit, config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_kraken_ohlc() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<Vec<Candle>, DataError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: candles keyed by Kraken pair name
+                input: r#"
+                {
+                    "error": [],
+                    "result": {
+                        "XXBTZUSD": [
+                            [1688671200, "30306.1", "30306.2", "30305.7", "30305.7", "30306.1", "3.39243896", 23]
+                        ],
+                        "last": 1688671200
+                    }
+                }
+                "#,
+                expected: Ok(vec![Candle {
+                    close_time: DateTime::from_timestamp_millis(1688671259999).unwrap(),
+                    open: num!(30306.1),
+                    high: num!(30306.2),
+                    low: num!(30305.7),
+                    close: num!(30305.7),
+                    volume: num!(3.39243896),
+                    trade_count: 23,
+                    historical: true,
+                }]),
+            },
+            TestCase {
+                // TC1: error response
+                input: r#"{"error": ["EQuery:Unknown asset pair"]}"#,
+                expected: Err(DataError::Socket(SocketError::Exchange(
+                    "Kraken OHLC error: EQuery:Unknown asset pair".to_string(),
+                ))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let ohlc = serde_json::from_str::<KrakenOhlc>(test.input).unwrap();
+            let actual = Vec::<KrakenOhlcRest>::try_from(ohlc).map(|candles| {
+                candles
+                    .into_iter()
+                    .filter_map(|candle| candle.into_candle(Interval::M1))
+                    .collect::<Vec<_>>()
+            });
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "TC{} failed", index),
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                (actual, expected) => {
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+}
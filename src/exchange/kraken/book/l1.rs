@@ -1,9 +1,10 @@
-use super::super::KrakenMessage;
+use super::super::{message::extract_next_str, KrakenMessage};
 use crate::exchange::kraken::channel::KrakenChannel;
 use crate::exchange::subscription::ExchangeSub;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::ExchangeId,
+    num::Num,
     subscription::book::{Level, OrderBookL1},
     Identifier,
 };
@@ -13,6 +14,7 @@ use barter_integration::{
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Terse type alias for an [`Kraken`](super::super::Kraken) real-time OrderBook Level1
 /// (top of book) WebSocket message.
@@ -38,15 +40,15 @@ pub struct KrakenOrderBookL1Inner {
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct KrakenSpread {
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub best_bid_price: f64,
+    pub best_bid_price: Num,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub best_ask_price: f64,
-    #[serde(deserialize_with = "barter_integration::de::de_str_f64_epoch_s_as_datetime_utc")]
+    pub best_ask_price: Num,
+    #[serde(deserialize_with = "crate::datetime::de_str_epoch_s_as_datetime_utc")]
     pub time: DateTime<Utc>,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub best_bid_amount: f64,
+    pub best_bid_amount: Num,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
-    pub best_ask_amount: f64,
+    pub best_ask_amount: Num,
 }
 
 impl Identifier<Option<SubscriptionId>> for KrakenOrderBookL1Inner {
@@ -55,12 +57,23 @@ impl Identifier<Option<SubscriptionId>> for KrakenOrderBookL1Inner {
     }
 }
 
-impl From<(ExchangeId, Instrument, KrakenOrderBookL1)> for MarketIter<OrderBookL1> {
-    fn from((exchange_id, instrument, book): (ExchangeId, Instrument, KrakenOrderBookL1)) -> Self {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, KrakenOrderBookL1)> for MarketIter<OrderBookL1> {
+    fn from(
+        (exchange_id, instrument, received_time, book): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            KrakenOrderBookL1,
+        ),
+    ) -> Self {
         match book {
-            KrakenOrderBookL1::Data(book) => Self(vec![Ok(MarketEvent {
+            KrakenOrderBookL1::Data(book) => Self(smallvec![Ok(MarketEvent {
                 exchange_time: book.spread.time,
-                received_time: Utc::now(),
+                raw_exchange_time: Some(RawTimestamp::from_datetime(
+                    book.spread.time,
+                    TimestampUnit::Microseconds,
+                )),
+                received_time,
                 exchange: Exchange::from(exchange_id),
                 instrument,
                 kind: OrderBookL1 {
@@ -68,8 +81,9 @@ impl From<(ExchangeId, Instrument, KrakenOrderBookL1)> for MarketIter<OrderBookL
                     best_bid: Level::new(book.spread.best_bid_price, book.spread.best_bid_amount),
                     best_ask: Level::new(book.spread.best_ask_price, book.spread.best_ask_amount),
                 },
+                meta: (),
             })]),
-            KrakenOrderBookL1::Event(_) => MarketIter(vec![]),
+            KrakenOrderBookL1::Event(_) => MarketIter(smallvec![]),
         }
     }
 }
@@ -109,7 +123,7 @@ impl<'de> serde::de::Deserialize<'de> for KrakenOrderBookL1Inner {
                 let _: serde::de::IgnoredAny = extract_next(&mut seq, "channelName")?;
 
                 // Extract pair (eg/ "XBT/USD") & map to SubscriptionId (ie/ "spread|{pair}")
-                let subscription_id = extract_next::<SeqAccessor, String>(&mut seq, "pair")
+                let subscription_id = extract_next_str(&mut seq, "pair")
                     .map(|market| ExchangeSub::from((KrakenChannel::ORDER_BOOK_L1, market)).id())?;
 
                 // Ignore any additional elements or SerDe will fail
@@ -131,6 +145,7 @@ impl<'de> serde::de::Deserialize<'de> for KrakenOrderBookL1Inner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -164,13 +179,13 @@ mod tests {
                 expected: Ok(KrakenOrderBookL1::Data(KrakenOrderBookL1Inner {
                     subscription_id: SubscriptionId::from("spread|XBT/USD"),
                     spread: KrakenSpread {
-                        best_bid_price: 5698.4,
-                        best_bid_amount: 1.01234567,
-                        time: datetime_utc_from_epoch_duration(std::time::Duration::from_secs_f64(
-                            1542057299.545897,
+                        best_bid_price: num!(5698.4),
+                        best_bid_amount: num!(1.01234567),
+                        time: datetime_utc_from_epoch_duration(std::time::Duration::from_micros(
+                            1542057299545897,
                         )),
-                        best_ask_price: 5700.0,
-                        best_ask_amount: 0.98765432,
+                        best_ask_price: num!(5700.0),
+                        best_ask_amount: num!(0.98765432),
                     },
                 })),
             }];
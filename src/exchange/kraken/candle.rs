@@ -0,0 +1,436 @@
+use super::{message::extract_next_str, Kraken, KrakenMessage};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, MarketEvents, RawTimestamp, TimestampUnit},
+    exchange::{Connector, ExchangeSub},
+    num::Num,
+    streams::config::StreamConfig,
+    subscription::{
+        candle::{Candle, Candles},
+        Map,
+    },
+    transformer::ExchangeTransformer,
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    de::extract_next,
+    model::{Exchange, Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+    Transformer,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// Terse type alias for an [`Kraken`] real-time OHLC WebSocket message.
+pub type KrakenCandles = KrakenMessage<KrakenCandle>;
+
+/// [`Kraken`] OHLC update of the currently open candle, with an associated [`SubscriptionId`]
+/// (eg/ "ohlc-5|XBT/USD").
+///
+/// Kraken pushes an update on every trade, so a candle is only known to be closed once an update
+/// with a later `etime` arrives, see [`KrakenCandlesTransformer`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/websockets/#message-ohlc>
+/// ```json
+/// [
+///     42,
+///     [
+///         "1542057314.748456",
+///         "1542057360.000000",
+///         "3586.70000",
+///         "3586.70000",
+///         "3586.60000",
+///         "3586.60000",
+///         "3586.68894",
+///         "0.03373000",
+///         2
+///     ],
+///     "ohlc-5",
+///     "XBT/USD"
+/// ]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct KrakenCandle {
+    pub subscription_id: SubscriptionId,
+    /// Time of the last update of the candle.
+    pub time: DateTime<Utc>,
+    /// End time of the candle interval, exclusive.
+    pub end_time: DateTime<Utc>,
+    pub open: Num,
+    pub high: Num,
+    pub low: Num,
+    pub close: Num,
+    pub vwap: Num,
+    pub volume: Num,
+    pub trade_count: u64,
+}
+
+impl Identifier<Option<SubscriptionId>> for KrakenCandle {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl From<&KrakenCandle> for Candle {
+    fn from(candle: &KrakenCandle) -> Self {
+        Self {
+            // Align with the inclusive close_time of the REST OHLC backfilled candles
+            close_time: candle.end_time - chrono::Duration::milliseconds(1),
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+            trade_count: candle.trade_count,
+            historical: false,
+        }
+    }
+}
+
+/// [`Kraken`] OHLC candle data, ie/
+/// `[time, etime, open, high, low, close, vwap, volume, count]`.
+#[derive(Deserialize)]
+struct KrakenCandleData(
+    #[serde(deserialize_with = "crate::datetime::de_str_epoch_s_as_datetime_utc")] DateTime<Utc>,
+    #[serde(deserialize_with = "crate::datetime::de_str_epoch_s_as_datetime_utc")] DateTime<Utc>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] Num,
+    u64,
+);
+
+impl<'de> serde::de::Deserialize<'de> for KrakenCandle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SeqVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SeqVisitor {
+            type Value = KrakenCandle;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("KrakenCandle struct from the Kraken WebSocket API")
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // KrakenCandle Sequence Format:
+                // [channelID, [time, etime, open, high, low, close, vwap, volume, count], channelName, pair]
+                // <https://docs.kraken.com/websockets/#message-ohlc>
+
+                // Extract deprecated channelID & ignore
+                let _: serde::de::IgnoredAny = extract_next(&mut seq, "channelID")?;
+
+                // Extract KrakenCandleData
+                let data: KrakenCandleData = extract_next(&mut seq, "KrakenCandleData")?;
+
+                // Extract channelName, which includes the interval (eg/ "ohlc-5")
+                let channel_name = extract_next_str(&mut seq, "channelName")?;
+
+                // Extract pair (eg/ "XBT/USD") & map to SubscriptionId (ie/ "ohlc-5|{pair}")
+                let subscription_id = extract_next_str(&mut seq, "pair")
+                    .map(|pair| ExchangeSub::from((channel_name, pair)).id())?;
+
+                // Ignore any additional elements or SerDe will fail
+                //  '--> Exchange may add fields without warning
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+                Ok(KrakenCandle {
+                    subscription_id,
+                    time: data.0,
+                    end_time: data.1,
+                    open: data.2,
+                    high: data.3,
+                    low: data.4,
+                    close: data.5,
+                    vwap: data.6,
+                    volume: data.7,
+                    trade_count: data.8,
+                })
+            }
+        }
+
+        // Use Visitor implementation to deserialise the KrakenCandle
+        deserializer.deserialize_seq(SeqVisitor)
+    }
+}
+
+/// [`Kraken`] [`Candles`] [`ExchangeTransformer`], yielding each closed [`Candle`] of the
+/// subscribed [`Instrument`]s.
+///
+/// The latest [`KrakenCandle`] update of each subscription is held until an update with a later
+/// `etime` arrives, at which point the held candle is closed and yielded. Kraken sends no updates
+/// for an interval without trades, so the closed candle is yielded on the first trade of a later
+/// interval.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KrakenCandlesTransformer {
+    instrument_map: Map<Instrument>,
+    candles: HashMap<SubscriptionId, KrakenCandle>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Kraken, Candles> for KrakenCandlesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            candles: HashMap::new(),
+        })
+    }
+}
+
+impl Transformer for KrakenCandlesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, KrakenCandles);
+    type Output = MarketEvent<Candle>;
+    type OutputIter = MarketEvents<Candle>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        let candle = match input {
+            KrakenCandles::Data(candle) => candle,
+            KrakenCandles::Event(_) => return smallvec![],
+        };
+
+        let instrument = match self.instrument_map.find(&candle.subscription_id) {
+            Ok(instrument) => instrument,
+            Err(unidentifiable) => return smallvec![Err(DataError::Socket(unidentifiable))],
+        };
+
+        // Close the held candle once an update of a later candle arrives, ignoring out of order
+        // updates of an already closed candle
+        let closed = match self.candles.get(&candle.subscription_id) {
+            Some(open) if candle.end_time < open.end_time => return smallvec![],
+            Some(open) if candle.end_time > open.end_time => Some(open.clone()),
+            _ => None,
+        };
+        self.candles.insert(candle.subscription_id.clone(), candle);
+
+        closed
+            .map(|closed| {
+                let candle = Candle::from(&closed);
+                Ok(MarketEvent {
+                    exchange_time: candle.close_time,
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        closed.end_time,
+                        TimestampUnit::Microseconds,
+                    )),
+                    received_time,
+                    exchange: Exchange::from(Kraken::ID),
+                    instrument,
+                    kind: candle,
+                    meta: (),
+                })
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::kraken::message::KrakenEvent;
+    use crate::num;
+    use barter_integration::model::InstrumentKind;
+
+    mod de {
+        use super::*;
+        use barter_integration::error::SocketError;
+
+        #[test]
+        fn test_kraken_message_candles() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrakenCandles, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: valid KrakenCandles::Data(KrakenCandle)
+                    input: r#"
+                    [
+                        42,
+                        [
+                            "1542057314.748456",
+                            "1542057360.000000",
+                            "3586.70000",
+                            "3586.70000",
+                            "3586.60000",
+                            "3586.60000",
+                            "3586.68894",
+                            "0.03373000",
+                            2
+                        ],
+                        "ohlc-5",
+                        "XBT/USD"
+                    ]
+                    "#,
+                    expected: Ok(KrakenCandles::Data(KrakenCandle {
+                        subscription_id: SubscriptionId::from("ohlc-5|XBT/USD"),
+                        time: DateTime::from_timestamp_micros(1542057314748456).unwrap(),
+                        end_time: DateTime::from_timestamp(1542057360, 0).unwrap(),
+                        open: num!(3586.7),
+                        high: num!(3586.7),
+                        low: num!(3586.6),
+                        close: num!(3586.6),
+                        vwap: num!(3586.68894),
+                        volume: num!(0.03373),
+                        trade_count: 2,
+                    })),
+                },
+                TestCase {
+                    // TC1: invalid KrakenCandles w/ non-numeric open
+                    input: r#"
+                    [
+                        42,
+                        ["1542057314.748456", "1542057360.000000", "nan?", "3586.7", "3586.6", "3586.6", "3586.68894", "0.03373", 2],
+                        "ohlc-5",
+                        "XBT/USD"
+                    ]
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "",
+                        item: "".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC2: valid KrakenCandles::Event(Heartbeat)
+                    input: r#"{"event": "heartbeat"}"#,
+                    expected: Ok(KrakenCandles::Event(KrakenEvent::Heartbeat)),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenCandles>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kraken_candles_transformer() {
+        let btc_usd = Instrument::from(("btc", "usd", InstrumentKind::Spot));
+        let mut transformer = KrakenCandlesTransformer {
+            instrument_map: Map([(SubscriptionId::from("ohlc-1|XBT/USD"), btc_usd.clone())]
+                .into_iter()
+                .collect()),
+            candles: HashMap::new(),
+        };
+
+        let update = |end_time: i64, close: Num| {
+            KrakenCandles::Data(KrakenCandle {
+                subscription_id: SubscriptionId::from("ohlc-1|XBT/USD"),
+                time: DateTime::from_timestamp(end_time - 30, 0).unwrap(),
+                end_time: DateTime::from_timestamp(end_time, 0).unwrap(),
+                open: num!(100.0),
+                high: num!(110.0),
+                low: num!(90.0),
+                close,
+                vwap: num!(100.0),
+                volume: num!(1.0),
+                trade_count: 1,
+            })
+        };
+
+        struct TestCase {
+            input: KrakenCandles,
+            expected: Vec<(DateTime<Utc>, Num)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: first update of an open candle is held
+                input: update(1688671260, num!(101.0)),
+                expected: vec![],
+            },
+            TestCase {
+                // TC1: update of the same candle replaces the held candle
+                input: update(1688671260, num!(102.0)),
+                expected: vec![],
+            },
+            TestCase {
+                // TC2: update w/ a later etime closes the held candle
+                input: update(1688671380, num!(103.0)),
+                expected: vec![(
+                    DateTime::from_timestamp_millis(1688671259999).unwrap(),
+                    num!(102.0),
+                )],
+            },
+            TestCase {
+                // TC3: out of order update of the closed candle is ignored
+                input: update(1688671260, num!(104.0)),
+                expected: vec![],
+            },
+            TestCase {
+                // TC4: heartbeat yields nothing
+                input: KrakenCandles::Event(KrakenEvent::Heartbeat),
+                expected: vec![],
+            },
+            TestCase {
+                // TC5: next candle closes the candle after the interval without trades
+                input: update(1688671440, num!(105.0)),
+                expected: vec![(
+                    DateTime::from_timestamp_millis(1688671379999).unwrap(),
+                    num!(103.0),
+                )],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = transformer
+                .transform((Utc::now(), test.input))
+                .into_iter()
+                .map(|event| {
+                    let event = event.unwrap();
+                    assert_eq!(event.instrument, btc_usd, "TC{} failed", index);
+                    assert_eq!(event.exchange_time, event.kind.close_time);
+                    (event.kind.close_time, event.kind.close)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        // Unsubscribed candles are unidentifiable
+        let unidentifiable = KrakenCandles::Data(KrakenCandle {
+            subscription_id: SubscriptionId::from("ohlc-5|XBT/USD"),
+            ..match update(1688671440, num!(1.0)) {
+                KrakenCandles::Data(candle) => candle,
+                KrakenCandles::Event(_) => unreachable!(),
+            }
+        });
+        assert!(matches!(
+            transformer
+                .transform((Utc::now(), unidentifiable))
+                .as_slice(),
+            [Err(DataError::Socket(_))]
+        ));
+    }
+}
@@ -1,6 +1,13 @@
 use super::Kraken;
 use crate::{
-    subscription::{book::OrderBooksL1, trade::PublicTrades, Subscription},
+    subscription::{
+        book::OrderBooksL1,
+        candle::{Candles, Interval},
+        fill::Fills,
+        order::OrderUpdates,
+        trade::PublicTrades,
+        Subscription,
+    },
     Identifier,
 };
 use serde::Serialize;
@@ -22,6 +29,54 @@ impl KrakenChannel {
     ///
     /// See docs: <https://docs.kraken.com/websockets/#message-subscribe>
     pub const ORDER_BOOK_L1: Self = Self("spread");
+
+    /// [`Kraken`] private trades channel name, carrying the trades of the account owning the
+    /// WebSockets token the subscription is authenticated with.
+    ///
+    /// See docs: <https://docs.kraken.com/websockets/#message-ownTrades>
+    pub const OWN_TRADES: Self = Self("ownTrades");
+
+    /// [`Kraken`] private open orders channel name, carrying the orders of the account owning the
+    /// WebSockets token the subscription is authenticated with.
+    ///
+    /// See docs: <https://docs.kraken.com/websockets/#message-openOrders>
+    pub const OPEN_ORDERS: Self = Self("openOrders");
+
+    /// [`Kraken`] real-time OHLC channel of the provided [`Interval`], named as it's channelName
+    /// (eg/ "ohlc-5"), ie/ the "ohlc" subscription name & the interval in minutes.
+    ///
+    /// Intervals not supported by Kraken (eg/ [`Interval::M3`]) are rejected by
+    /// [`StreamSelector::unsupported`](crate::exchange::StreamSelector::unsupported) before
+    /// subscribing.
+    ///
+    /// See docs: <https://docs.kraken.com/websockets/#message-ohlc>
+    pub fn candles(interval: Interval) -> Self {
+        match interval {
+            Interval::M1 => Self("ohlc-1"),
+            Interval::M5 => Self("ohlc-5"),
+            Interval::M15 => Self("ohlc-15"),
+            Interval::M30 => Self("ohlc-30"),
+            Interval::H1 => Self("ohlc-60"),
+            Interval::H4 => Self("ohlc-240"),
+            Interval::D1 => Self("ohlc-1440"),
+            Interval::W1 => Self("ohlc-10080"),
+            Interval::M3 | Interval::H2 | Interval::H6 | Interval::H12 => Self("ohlc"),
+        }
+    }
+
+    /// Determine the OHLC interval in minutes of [`Self`], if it's an OHLC channel (eg/ 5 for
+    /// "ohlc-5").
+    pub fn ohlc_interval(&self) -> Option<u32> {
+        self.0
+            .strip_prefix("ohlc-")
+            .and_then(|minutes| minutes.parse().ok())
+    }
+
+    /// Determine if [`Self`] is a private channel, which is subscribed to on the authenticated
+    /// [`BASE_URL_KRAKEN_AUTH`](super::BASE_URL_KRAKEN_AUTH) server with a WebSockets token.
+    pub fn is_private(&self) -> bool {
+        matches!(*self, Self::OWN_TRADES | Self::OPEN_ORDERS)
+    }
 }
 
 impl Identifier<KrakenChannel> for Subscription<Kraken, PublicTrades> {
@@ -36,6 +91,24 @@ impl Identifier<KrakenChannel> for Subscription<Kraken, OrderBooksL1> {
     }
 }
 
+impl Identifier<KrakenChannel> for Subscription<Kraken, Candles> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::candles(self.kind.0)
+    }
+}
+
+impl Identifier<KrakenChannel> for Subscription<Kraken, Fills> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::OWN_TRADES
+    }
+}
+
+impl Identifier<KrakenChannel> for Subscription<Kraken, OrderUpdates> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::OPEN_ORDERS
+    }
+}
+
 impl AsRef<str> for KrakenChannel {
     fn as_ref(&self) -> &str {
         self.0
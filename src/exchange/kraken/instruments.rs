@@ -0,0 +1,209 @@
+use super::{Kraken, HTTP_BASE_URL_KRAKEN};
+use crate::{
+    error::DataError,
+    exchange::{
+        instruments::{decimal_places_size, parse_size, InstrumentInfo, MarketStatus},
+        market::ParseMarket,
+        ExchangeId,
+    },
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+};
+use barter_integration::error::SocketError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// [`Kraken`](super::Kraken) REST `AssetPairs` response.
+///
+/// See docs: <https://docs.kraken.com/rest/#tag/Market-Data/operation/getTradableAssetPairs>
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenAssetPairs {
+    #[serde(default)]
+    pub error: Vec<String>,
+    #[serde(default)]
+    pub result: HashMap<String, KrakenAssetPair>,
+}
+
+/// [`Kraken`](super::Kraken) REST `AssetPairs` asset pair, whose `wsname` (eg/ "XBT/USD") is the
+/// market [`Subscription`](crate::subscription::Subscription)s are identified by.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/rest/#tag/Market-Data/operation/getTradableAssetPairs>
+/// ```json
+/// {
+///     "XXBTZUSD": {
+///         "altname": "XBTUSD",
+///         "wsname": "XBT/USD",
+///         "base": "XXBT",
+///         "quote": "ZUSD",
+///         "pair_decimals": 1,
+///         "lot_decimals": 8,
+///         "tick_size": "0.1",
+///         "status": "online"
+///     }
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KrakenAssetPair {
+    pub wsname: Option<String>,
+    pub pair_decimals: u32,
+    pub lot_decimals: u32,
+    #[serde(default)]
+    pub tick_size: Option<String>,
+    pub status: String,
+}
+
+impl KrakenAssetPair {
+    /// Normalise into an [`InstrumentInfo`], or `None` if the pair is listed without a
+    /// "{BASE}/{QUOTE}" `wsname` (ie/ it cannot be subscribed to).
+    ///
+    /// The [`Instrument`](barter_integration::model::Instrument) is parsed from the `wsname` via [`Kraken::parse_market`], and
+    /// therefore has Kraken specific asset codes normalised (eg/ "XBT/USD" -> btc/usd).
+    pub fn into_instrument_info(self) -> Option<InstrumentInfo> {
+        let wsname = self.wsname?;
+        let instrument = Kraken::parse_market(&wsname).ok()?;
+
+        let status = match self.status.as_str() {
+            "online" | "limit_only" | "post_only" => MarketStatus::Trading,
+            _ => MarketStatus::Halted(self.status),
+        };
+
+        Some(InstrumentInfo {
+            exchange: ExchangeId::Kraken,
+            market: wsname.to_uppercase(),
+            instrument,
+            tick_size: self
+                .tick_size
+                .as_deref()
+                .and_then(parse_size)
+                .or_else(|| decimal_places_size(self.pair_decimals)),
+            lot_size: decimal_places_size(self.lot_decimals),
+            contract_size: None,
+            status,
+        })
+    }
+}
+
+impl TryFrom<KrakenAssetPairs> for Vec<InstrumentInfo> {
+    type Error = DataError;
+
+    fn try_from(pairs: KrakenAssetPairs) -> Result<Self, Self::Error> {
+        if !pairs.error.is_empty() {
+            return Err(DataError::Socket(SocketError::Exchange(format!(
+                "Kraken AssetPairs error: {}",
+                pairs.error.join(", ")
+            ))));
+        }
+
+        let mut instruments = pairs
+            .result
+            .into_values()
+            .filter_map(KrakenAssetPair::into_instrument_info)
+            .collect::<Vec<_>>();
+        instruments.sort_by(|a, b| a.market.cmp(&b.market));
+
+        Ok(instruments)
+    }
+}
+
+/// Fetch every [`InstrumentInfo`] listed by the [`Kraken`](super::Kraken) REST API `AssetPairs`
+/// endpoint, using the provided [`StreamConfig`].
+pub async fn fetch_instruments(config: &StreamConfig) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_KRAKEN);
+    get_json::<KrakenAssetPairs>(
+        config.proxy.as_ref(),
+        &format!("{base_url}/public/AssetPairs"),
+    )
+    .await
+    .and_then(Vec::<InstrumentInfo>::try_from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::{Instrument, InstrumentKind};
+
+    #[test]
+    fn test_kraken_instrument_info() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<Vec<InstrumentInfo>, DataError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: listed asset pairs, sorted by market
+                input: r#"
+                {
+                    "error": [],
+                    "result": {
+                        "XXBTZUSD": {
+                            "altname": "XBTUSD", "wsname": "XBT/USD", "aclass_base": "currency",
+                            "base": "XXBT", "aclass_quote": "currency", "quote": "ZUSD",
+                            "pair_decimals": 1, "cost_decimals": 5, "lot_decimals": 8,
+                            "lot_multiplier": 1, "ordermin": "0.0001", "tick_size": "0.1",
+                            "status": "online"
+                        },
+                        "XETHZUSD": {
+                            "altname": "ETHUSD", "wsname": "ETH/USD", "base": "XETH", "quote": "ZUSD",
+                            "pair_decimals": 2, "lot_decimals": 8, "status": "cancel_only"
+                        },
+                        "LUNAUSD": {
+                            "altname": "LUNAUSD", "base": "LUNA", "quote": "ZUSD",
+                            "pair_decimals": 4, "lot_decimals": 8, "status": "delisted"
+                        }
+                    }
+                }
+                "#,
+                expected: Ok(vec![
+                    InstrumentInfo {
+                        exchange: ExchangeId::Kraken,
+                        market: "ETH/USD".to_string(),
+                        instrument: Instrument::from(("eth", "usd", InstrumentKind::Spot)),
+                        tick_size: parse_size("0.01"),
+                        lot_size: parse_size("0.00000001"),
+                        contract_size: None,
+                        status: MarketStatus::Halted("cancel_only".to_string()),
+                    },
+                    InstrumentInfo {
+                        exchange: ExchangeId::Kraken,
+                        market: "XBT/USD".to_string(),
+                        instrument: Instrument::from(("btc", "usd", InstrumentKind::Spot)),
+                        tick_size: parse_size("0.1"),
+                        lot_size: parse_size("0.00000001"),
+                        contract_size: None,
+                        status: MarketStatus::Trading,
+                    },
+                ]),
+            },
+            TestCase {
+                // TC1: error response
+                input: r#"{"error": ["EGeneral:Too many requests"]}"#,
+                expected: Err(DataError::Socket(SocketError::Exchange(
+                    "Kraken AssetPairs error: EGeneral:Too many requests".to_string(),
+                ))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Vec::<InstrumentInfo>::try_from(
+                serde_json::from_str::<KrakenAssetPairs>(test.input).unwrap(),
+            );
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "TC{} failed", index),
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+}
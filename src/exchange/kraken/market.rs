@@ -1,5 +1,14 @@
 use super::Kraken;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
@@ -11,7 +20,8 @@ pub struct KrakenMarket(pub String);
 
 impl<Kind> Identifier<KrakenMarket> for Subscription<Kraken, Kind> {
     fn id(&self) -> KrakenMarket {
-        KrakenMarket(format!("{}/{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Kraken::ID, &self.instrument);
+        KrakenMarket(format!("{base}/{quote}").to_uppercase())
     }
 }
 
@@ -20,3 +30,116 @@ impl AsRef<str> for KrakenMarket {
         &self.0
     }
 }
+
+impl ParseMarket for Kraken {
+    /// Kraken markets are formatted as "BASE/QUOTE" (eg/ "XBT/USD"), with Kraken specific asset
+    /// codes normalised to their canonical equivalent (eg/ "XBT" -> btc, "XDG" -> doge) via the
+    /// [`AssetAliases`](crate::exchange::alias::AssetAliases).
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        match market.split_once('/') {
+            Some((base, quote))
+                if !base.is_empty() && !quote.is_empty() && !quote.contains('/') =>
+            {
+                Ok(canonical_instrument(
+                    Self::ID,
+                    base,
+                    quote,
+                    InstrumentKind::Spot,
+                ))
+            }
+            _ => Err(ParseMarketError::format(Self::ID, market)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exchange::ExchangeId, subscription::trade::PublicTrades};
+
+    #[test]
+    fn test_kraken_market() {
+        struct TestCase {
+            base: &'static str,
+            quote: &'static str,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: canonical btc maps to the Kraken XBT alias
+                base: "btc",
+                quote: "usd",
+                expected: "XBT/USD",
+            },
+            TestCase {
+                // TC1: canonical btc quote & doge base map to the Kraken XBT & XDG aliases
+                base: "doge",
+                quote: "btc",
+                expected: "XDG/XBT",
+            },
+            TestCase {
+                // TC2: assets without an alias
+                base: "eth",
+                quote: "usdt",
+                expected: "ETH/USDT",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let subscription = Subscription::from((
+                Kraken,
+                test.base,
+                test.quote,
+                InstrumentKind::Spot,
+                PublicTrades,
+            ));
+            let actual: KrakenMarket = subscription.id();
+            assert_eq!(actual.as_ref(), test.expected, "TC{} failed", index);
+
+            // Market parses back into the canonical Instrument
+            assert_eq!(
+                Kraken::parse_market(actual.as_ref()),
+                Ok(subscription.instrument),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_market() {
+        struct TestCase {
+            market: &'static str,
+            expected: Result<Instrument, ParseMarketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: XBT alias
+                market: "XBT/USD",
+                expected: Ok(Instrument::from(("btc", "usd", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: XBT alias as quote & XDG alias as base
+                market: "XDG/XBT",
+                expected: Ok(Instrument::from(("doge", "btc", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC2: assets without an alias
+                market: "ETH/USDT",
+                expected: Ok(Instrument::from(("eth", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC3: invalid format
+                market: "XBTUSD",
+                expected: Err(ParseMarketError::format(ExchangeId::Kraken, "XBTUSD")),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Kraken::parse_market(test.market);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use super::status::KrakenSystemStatus;
 use crate::Identifier;
 use barter_integration::model::SubscriptionId;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 /// [`Kraken`](super::Kraken) message variants that can be received over
 /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket).
@@ -53,6 +55,27 @@ use serde::{Deserialize, Serialize};
 /// ]
 /// ```
 ///
+/// #### Candles
+/// See docs: <https://docs.kraken.com/websockets/#message-ohlc>
+/// ```json
+/// [
+///     42,
+///     [
+///         "1542057314.748456",
+///         "1542057360.000000",
+///         "3586.70000",
+///         "3586.70000",
+///         "3586.60000",
+///         "3586.60000",
+///         "3586.68894",
+///         "0.03373000",
+///         2
+///     ],
+///     "ohlc-5",
+///     "XBT/USD"
+/// ]
+/// ```
+///
 /// #### Heartbeat
 /// See docs: <https://docs.kraken.com/websockets/#message-heartbeat>
 /// ```json
@@ -61,6 +84,17 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 ///
+/// #### SystemStatus
+/// See docs: <https://docs.kraken.com/websockets/#message-systemStatus>
+/// ```json
+/// {
+///   "connectionID": 8628615390848610000,
+///   "event": "systemStatus",
+///   "status": "maintenance",
+///   "version": "1.0.0"
+/// }
+/// ```
+///
 /// #### KrakenError Generic
 /// See docs: <https://docs.kraken.com/websockets/#errortypes>
 /// ```json
@@ -69,7 +103,7 @@ use serde::{Deserialize, Serialize};
 ///     "event": "error"
 /// }
 /// ```
-#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum KrakenMessage<T> {
     Data(T),
@@ -88,10 +122,62 @@ where
     }
 }
 
+impl<'de, T> Deserialize<'de> for KrakenMessage<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct MessageVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> serde::de::Visitor<'de> for MessageVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = KrakenMessage<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str(
+                    "KrakenMessage data sequence or event map from the Kraken WebSocket API",
+                )
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                T::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))
+                    .map(KrakenMessage::Data)
+            }
+
+            fn visit_map<MapAccessor>(
+                self,
+                map: MapAccessor,
+            ) -> Result<Self::Value, MapAccessor::Error>
+            where
+                MapAccessor: serde::de::MapAccess<'de>,
+            {
+                KrakenEvent::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(KrakenMessage::Event)
+            }
+        }
+
+        // Data is always a sequence & events are always a map, so dispatch on the payload type
+        // rather than #[serde(untagged)], which buffers the entire message into owned content
+        deserializer.deserialize_any(MessageVisitor(PhantomData))
+    }
+}
+
 /// [`Kraken`](super::Kraken) messages received over the WebSocket which are not subscription data.
 ///
 /// eg/ [`Kraken`](super::Kraken) sends a [`KrakenEvent::Heartbeat`] if no subscription traffic
-/// has been sent within the last second.
+/// has been sent within the last second, and a [`KrakenEvent::SystemStatus`] whenever it's
+/// system status changes (eg/ before closing connections for maintenance).
 ///
 /// See [`KrakenMessage`] for full raw payload examples.
 ///
@@ -100,6 +186,7 @@ where
 #[serde(tag = "event", rename_all = "camelCase")]
 pub enum KrakenEvent {
     Heartbeat,
+    SystemStatus(KrakenSystemStatus),
     Error(KrakenError),
 }
 
@@ -119,12 +206,27 @@ pub struct KrakenError {
     pub message: String,
 }
 
+/// Borrowed counterpart of [`extract_next`](barter_integration::de::extract_next), extracting the
+/// next element of a [`Kraken`](super::Kraken) data sequence as a `&str` without allocating.
+pub(crate) fn extract_next_str<'de, SeqAccessor>(
+    sequence: &mut SeqAccessor,
+    name: &'static str,
+) -> Result<&'de str, SeqAccessor::Error>
+where
+    SeqAccessor: serde::de::SeqAccess<'de>,
+{
+    sequence
+        .next_element::<&str>()?
+        .ok_or_else(|| serde::de::Error::missing_field(name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     mod de {
         use super::*;
+        use crate::exchange::kraken::status::KrakenStatus;
         use barter_integration::error::SocketError;
 
         #[test]
@@ -147,6 +249,17 @@ mod tests {
                         message: "Malformed request".to_string(),
                     }))),
                 },
+                TestCase {
+                    // TC2: valid KrakenTrades::Event(KrakenEvent::SystemStatus(KrakenSystemStatus))
+                    input: r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"maintenance","version":"1.0.0"}"#,
+                    expected: Ok(KrakenMessage::Event(KrakenEvent::SystemStatus(
+                        KrakenSystemStatus {
+                            status: KrakenStatus::Maintenance,
+                            connection_id: Some(8628615390848610000),
+                            version: Some("1.0.0".to_string()),
+                        },
+                    ))),
+                },
             ];
 
             for (index, test) in tests.into_iter().enumerate() {
@@ -1,26 +1,61 @@
 use self::{
-    book::l1::KrakenOrderBookL1, channel::KrakenChannel, market::KrakenMarket,
-    message::KrakenMessage, subscription::KrakenSubResponse, trade::KrakenTrades,
+    auth::KrakenPrivateStream,
+    backfill::kraken_ohlc_interval,
+    book::l1::KrakenOrderBookL1,
+    candle::KrakenCandlesTransformer,
+    channel::KrakenChannel,
+    market::KrakenMarket,
+    message::KrakenMessage,
+    subscription::KrakenSubResponse,
+    trade::KrakenTrades,
+    user::{KrakenFillsTransformer, KrakenOrderUpdatesTransformer},
 };
 use crate::{
-    exchange::{Connector, ExchangeId, ExchangeSub, StreamSelector},
-    subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::{book::OrderBooksL1, trade::PublicTrades},
+    error::DataError,
+    exchange::{status::ExchangeStatus, Connector, ExchangeId, ExchangeSub, StreamSelector},
+    streams::config::StreamConfig,
+    subscriber::{auth::AuthToken, validator::WebSocketSubValidator, WebSocketSubscriber},
+    subscription::{
+        book::OrderBooksL1, candle::Candles, fill::Fills, order::OrderUpdates, trade::PublicTrades,
+        Map,
+    },
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
-use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
+use barter_integration::{
+    error::SocketError,
+    model::{Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+};
 use barter_macro::{DeExchange, SerExchange};
 use serde_json::json;
+use std::collections::HashSet;
+use tracing::warn;
 use url::Url;
 
+/// WebSockets token authentication of the private [`Kraken`] channels (eg/
+/// [`KrakenChannel::OWN_TRADES`]), fetched via the signed REST API by the
+/// [`KrakenPrivateStream`](auth::KrakenPrivateStream).
+pub mod auth;
+
+/// REST `OHLC` fetcher of the trailing closed [`Candle`](crate::subscription::candle::Candle)s
+/// of a [`Kraken`] market.
+pub mod backfill;
+
 /// Order book types for [`Kraken`]
 pub mod book;
 
+/// Real-time OHLC [`Candles`] types for [`Kraken`].
+pub mod candle;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
 
+/// [`InstrumentInfo`](crate::exchange::instruments::InstrumentInfo)s listed by the [`Kraken`] REST
+/// API `AssetPairs`.
+pub mod instruments;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`]  specific market used for generating [`Connector::requests`].
 pub mod market;
@@ -28,6 +63,10 @@ pub mod market;
 /// [`KrakenMessage`](message::KrakenMessage) type for [`Kraken`].
 pub mod message;
 
+/// [`KrakenSystemStatus`](status::KrakenSystemStatus) type and maintenance state handling for
+/// [`Kraken`].
+pub mod status;
+
 /// [`Subscription`](crate::subscription::Subscription) response type and response
 /// [`Validator`](barter_integration) for [`Kraken`].
 pub mod subscription;
@@ -35,11 +74,36 @@ pub mod subscription;
 /// Public trade types for [`Kraken`].
 pub mod trade;
 
+/// Private [`OrderUpdates`] & [`Fills`] types for [`Kraken`], normalised from the authenticated
+/// [`KrakenChannel::OPEN_ORDERS`] & [`KrakenChannel::OWN_TRADES`] channels.
+pub mod user;
+
 /// [`Kraken`] server base url.
 ///
 /// See docs: <https://docs.kraken.com/websockets/#overview>
 pub const BASE_URL_KRAKEN: &str = "wss://ws.kraken.com/";
 
+/// [`Kraken`] authenticated server base url, serving the private channels (eg/
+/// [`KrakenChannel::OWN_TRADES`]).
+///
+/// See docs: <https://docs.kraken.com/websockets/#connectionDetails>
+pub const BASE_URL_KRAKEN_AUTH: &str = "wss://ws-auth.kraken.com/";
+
+/// [`Kraken`] REST API base url.
+///
+/// See docs: <https://docs.kraken.com/rest/>
+pub const HTTP_BASE_URL_KRAKEN: &str = "https://api.kraken.com/0";
+
+/// Exchange message fields [`Kraken`] models intentionally don't deserialise, see
+/// [`IgnoredFields`](crate::parser::strict::IgnoredFields).
+pub const IGNORED_FIELDS: crate::parser::strict::IgnoredFields =
+    &[("KrakenSystemStatus", &["event"])];
+
+/// Optional exchange message fields [`Kraken`] is expected to send, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields).
+pub const EXPECTED_FIELDS: crate::parser::strict::ExpectedFields =
+    &[(ExchangeId::Kraken, "KrakenOwnTrade", &["fee"])];
+
 /// [`Kraken`] exchange.
 ///
 /// See docs: <https://docs.kraken.com/websockets/#overview>
@@ -60,23 +124,113 @@ impl Connector for Kraken {
         Url::parse(BASE_URL_KRAKEN).map_err(SocketError::UrlParse)
     }
 
+    fn channel_url(channel: &Self::Channel) -> Result<Url, SocketError> {
+        match channel.is_private() {
+            true => Url::parse(BASE_URL_KRAKEN_AUTH).map_err(SocketError::UrlParse),
+            false => Self::url(),
+        }
+    }
+
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        exchange_subs
-            .into_iter()
-            .map(|ExchangeSub { channel, market }| {
-                WsMessage::Text(
+        subscribe_requests(exchange_subs, None)
+    }
+
+    fn subscribe_requests(
+        &self,
+        exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        config: &StreamConfig,
+    ) -> Result<Vec<WsMessage>, DataError> {
+        Ok(subscribe_requests(
+            exchange_subs,
+            config.auth_token.as_ref(),
+        ))
+    }
+
+    fn expected_responses(map: &Map<Instrument>) -> usize {
+        // Private channels are acknowledged once, regardless of how many markets are subscribed
+        map.0
+            .keys()
+            .map(|SubscriptionId(id)| match id.split_once('|') {
+                Some((channel, _))
+                    if KrakenChannel::OWN_TRADES.as_ref() == channel
+                        || KrakenChannel::OPEN_ORDERS.as_ref() == channel =>
+                {
+                    channel
+                }
+                _ => id.as_str(),
+            })
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+        status::exchange_status(payload)
+    }
+}
+
+/// Construct the [`Kraken`] subscribe requests of the provided [`ExchangeSub`]s, authenticating
+/// those of private channels with the optional WebSockets [`AuthToken`].
+fn subscribe_requests(
+    exchange_subs: Vec<ExchangeSub<KrakenChannel, KrakenMarket>>,
+    token: Option<&AuthToken>,
+) -> Vec<WsMessage> {
+    let mut private_channels = HashSet::new();
+
+    exchange_subs
+        .into_iter()
+        .filter_map(|ExchangeSub { channel, market }| {
+            if !channel.is_private() {
+                // OHLC channels are subscribed by name & interval, eg/ "ohlc-5" as ohlc & 5
+                let subscription = match channel.ohlc_interval() {
+                    Some(interval) => json!({ "name": "ohlc", "interval": interval }),
+                    None => json!({ "name": channel.as_ref() }),
+                };
+
+                return Some(WsMessage::Text(
                     json!({
                         "event": "subscribe",
                         "pair": [market.as_ref()],
-                        "subscription": {
-                            "name": channel.as_ref()
-                        }
+                        "subscription": subscription
                     })
                     .to_string(),
-                )
-            })
-            .collect()
-    }
+                ));
+            }
+
+            // Private channels are for every market of the account, so are subscribed once
+            if !private_channels.insert(channel) {
+                return None;
+            }
+
+            let token = match token {
+                Some(AuthToken(token)) => token.as_str(),
+                None => {
+                    warn!(
+                        channel = channel.0,
+                        "Kraken private channel requires a WebSockets token"
+                    );
+                    ""
+                }
+            };
+
+            // Skip the snapshot of recent trades, else they'd be re-yielded as Fills every
+            // time the connection is re-initialised
+            let mut subscription = json!({
+                "name": channel.as_ref(),
+                "token": token,
+            });
+            if channel == KrakenChannel::OWN_TRADES {
+                subscription["snapshot"] = json!(false);
+            }
+
+            Some(WsMessage::Text(
+                json!({
+                    "event": "subscribe",
+                    "subscription": subscription,
+                })
+                .to_string(),
+            ))
+        })
+        .collect()
 }
 
 impl StreamSelector<PublicTrades> for Kraken {
@@ -86,3 +240,130 @@ impl StreamSelector<PublicTrades> for Kraken {
 impl StreamSelector<OrderBooksL1> for Kraken {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, OrderBooksL1, KrakenOrderBookL1>>;
 }
+
+impl StreamSelector<Candles> for Kraken {
+    type Stream = ExchangeWsStream<KrakenCandlesTransformer>;
+
+    fn unsupported(kind: &Candles) -> Option<String> {
+        kraken_ohlc_interval(kind.0).is_none().then(|| {
+            format!(
+                "{} does not support {} candles, supported intervals: 1m, 5m, 15m, 30m, 1h, 4h, 1d, 1w",
+                ExchangeId::Kraken,
+                kind.0
+            )
+        })
+    }
+}
+
+impl StreamSelector<OrderUpdates> for Kraken {
+    type Stream = KrakenPrivateStream<KrakenOrderUpdatesTransformer>;
+}
+
+impl StreamSelector<Fills> for Kraken {
+    type Stream = KrakenPrivateStream<KrakenFillsTransformer>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscription::candle::Interval;
+
+    #[tokio::test]
+    async fn test_kraken_requests() {
+        struct TestCase {
+            channel: KrakenChannel,
+            expected: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: public channel is subscribed per market
+                channel: KrakenChannel::TRADES,
+                expected: vec![
+                    r#"{"event":"subscribe","pair":["XBT/USD"],"subscription":{"name":"trade"}}"#,
+                    r#"{"event":"subscribe","pair":["ETH/USD"],"subscription":{"name":"trade"}}"#,
+                ],
+            },
+            TestCase {
+                // TC1: ohlc channel is subscribed per market w/ the interval in minutes
+                channel: KrakenChannel::candles(Interval::M5),
+                expected: vec![
+                    r#"{"event":"subscribe","pair":["XBT/USD"],"subscription":{"interval":5,"name":"ohlc"}}"#,
+                    r#"{"event":"subscribe","pair":["ETH/USD"],"subscription":{"interval":5,"name":"ohlc"}}"#,
+                ],
+            },
+            TestCase {
+                // TC2: private channel is subscribed once w/ token
+                channel: KrakenChannel::OPEN_ORDERS,
+                expected: vec![
+                    r#"{"event":"subscribe","subscription":{"name":"openOrders","token":"t0k3n"}}"#,
+                ],
+            },
+            TestCase {
+                // TC3: private trades channel skips the snapshot
+                channel: KrakenChannel::OWN_TRADES,
+                expected: vec![
+                    r#"{"event":"subscribe","subscription":{"name":"ownTrades","snapshot":false,"token":"t0k3n"}}"#,
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let exchange_subs = ["XBT/USD", "ETH/USD"]
+                .into_iter()
+                .map(|market| ExchangeSub {
+                    channel: test.channel,
+                    market: KrakenMarket(market.to_string()),
+                })
+                .collect::<Vec<_>>();
+
+            let map = Map(exchange_subs
+                .iter()
+                .map(|sub| {
+                    (
+                        crate::Identifier::<SubscriptionId>::id(sub),
+                        Instrument::from((
+                            "btc",
+                            "usd",
+                            barter_integration::model::InstrumentKind::Spot,
+                        )),
+                    )
+                })
+                .collect());
+
+            let config = StreamConfig {
+                auth_token: Some(AuthToken("t0k3n".to_string())),
+                ..StreamConfig::default()
+            };
+            let actual = Kraken.subscribe_requests(exchange_subs, &config).unwrap();
+            let expected = test
+                .expected
+                .iter()
+                .map(|payload| WsMessage::Text(payload.to_string()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected, "TC{} failed", index);
+            assert_eq!(
+                Kraken::expected_responses(&map),
+                expected.len(),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_kraken_candles_unsupported() {
+        assert_eq!(
+            <Kraken as StreamSelector<Candles>>::unsupported(&Candles(Interval::M5)),
+            None
+        );
+        assert_eq!(
+            <Kraken as StreamSelector<Candles>>::unsupported(&Candles(Interval::M3)),
+            Some(
+                "kraken does not support 3m candles, supported intervals: 1m, 5m, 15m, 30m, 1h, 4h, 1d, 1w"
+                    .to_string()
+            )
+        );
+    }
+}
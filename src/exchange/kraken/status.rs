@@ -0,0 +1,275 @@
+use super::message::KrakenEvent;
+use crate::exchange::status::{ExchangeStatus, SystemStatus, DEFAULT_MAINTENANCE_PROBE_INTERVAL};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, time::Duration};
+
+/// Default duration the [`consume`](crate::streams::consumer::consume) loop waits between
+/// [`Kraken`](super::Kraken) connection attempts whilst it's in [`KrakenStatus::Maintenance`],
+/// rather than rapidly retrying with the exponential reconnect backoff.
+pub const KRAKEN_MAINTENANCE_BACKOFF: Duration = DEFAULT_MAINTENANCE_PROBE_INTERVAL;
+
+/// [`Kraken`](super::Kraken) `systemStatus` message, sent on connection and whenever the
+/// system status changes.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.kraken.com/websockets/#message-systemStatus>
+/// ```json
+/// {
+///   "connectionID": 8628615390848610000,
+///   "event": "systemStatus",
+///   "status": "online",
+///   "version": "1.0.0"
+/// }
+/// ```
+///
+/// ### State Handling
+/// - [`KrakenStatus::Online`]: subscription responses are validated as normal, the
+///   `systemStatus` message is skipped.
+/// - [`KrakenStatus::Maintenance`]: subscription validation fails and the
+///   [`consume`](crate::streams::consumer::consume) loop pauses re-connection attempts for the
+///   maintenance probe interval (defaulting to [`KRAKEN_MAINTENANCE_BACKOFF`]) until a
+///   connection is validated again.
+/// - [`KrakenStatus::CancelOnly`], [`KrakenStatus::LimitOnly`] & [`KrakenStatus::PostOnly`]:
+///   only order entry is restricted, so market data subscriptions continue as normal.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct KrakenSystemStatus {
+    pub status: KrakenStatus,
+    #[serde(alias = "connectionID", default)]
+    pub connection_id: Option<u64>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// [`Kraken`](super::Kraken) system status communicated via a [`KrakenSystemStatus`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KrakenStatus {
+    Online,
+    Maintenance,
+    CancelOnly,
+    LimitOnly,
+    PostOnly,
+    #[serde(other)]
+    Unknown,
+}
+
+impl KrakenStatus {
+    /// Determines if [`Kraken`](super::Kraken) is unavailable for market data subscriptions.
+    pub fn is_maintenance(&self) -> bool {
+        matches!(self, Self::Maintenance)
+    }
+
+    /// Re-connection backoff to use whilst [`Kraken`](super::Kraken) is in this [`KrakenStatus`],
+    /// if it differs from the exponential reconnect backoff.
+    pub fn backoff(&self) -> Option<Duration> {
+        self.is_maintenance().then_some(KRAKEN_MAINTENANCE_BACKOFF)
+    }
+}
+
+impl From<KrakenStatus> for SystemStatus {
+    fn from(status: KrakenStatus) -> Self {
+        match status {
+            KrakenStatus::Online => SystemStatus::Online,
+            KrakenStatus::Maintenance => SystemStatus::Maintenance,
+            KrakenStatus::CancelOnly => SystemStatus::CancelOnly,
+            KrakenStatus::LimitOnly => SystemStatus::LimitOnly,
+            KrakenStatus::PostOnly => SystemStatus::PostOnly,
+            KrakenStatus::Unknown => SystemStatus::Unknown,
+        }
+    }
+}
+
+impl From<KrakenStatus> for ExchangeStatus {
+    fn from(status: KrakenStatus) -> Self {
+        ExchangeStatus::new(SystemStatus::from(status), None::<String>)
+    }
+}
+
+/// Recognise a [`Kraken`](super::Kraken) `systemStatus` message as an [`ExchangeStatus`].
+///
+/// See [`Connector::exchange_status`](crate::exchange::Connector::exchange_status).
+pub fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+    // Avoid deserialising every market data message
+    if !payload.contains("systemStatus") {
+        return None;
+    }
+
+    match serde_json::from_str::<KrakenEvent>(payload).ok()? {
+        KrakenEvent::SystemStatus(system) => Some(ExchangeStatus::from(system.status)),
+        _ => None,
+    }
+}
+
+impl Display for KrakenStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Online => "online",
+                Self::Maintenance => "maintenance",
+                Self::CancelOnly => "cancel_only",
+                Self::LimitOnly => "limit_only",
+                Self::PostOnly => "post_only",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+        use barter_integration::error::SocketError;
+
+        #[test]
+        fn test_kraken_system_status() {
+            struct TestCase {
+                input: &'static str,
+                expected: Result<KrakenSystemStatus, SocketError>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: input is online systemStatus
+                    input: r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"online","version":"1.0.0"}"#,
+                    expected: Ok(KrakenSystemStatus {
+                        status: KrakenStatus::Online,
+                        connection_id: Some(8628615390848610000),
+                        version: Some("1.0.0".to_string()),
+                    }),
+                },
+                TestCase {
+                    // TC1: input is maintenance systemStatus without optional fields
+                    input: r#"{"event":"systemStatus","status":"maintenance"}"#,
+                    expected: Ok(KrakenSystemStatus {
+                        status: KrakenStatus::Maintenance,
+                        connection_id: None,
+                        version: None,
+                    }),
+                },
+                TestCase {
+                    // TC2: input is cancel_only systemStatus
+                    input: r#"{"event":"systemStatus","status":"cancel_only","version":"1.9.0"}"#,
+                    expected: Ok(KrakenSystemStatus {
+                        status: KrakenStatus::CancelOnly,
+                        connection_id: None,
+                        version: Some("1.9.0".to_string()),
+                    }),
+                },
+                TestCase {
+                    // TC3: input is unrecognised systemStatus
+                    input: r#"{"event":"systemStatus","status":"degraded"}"#,
+                    expected: Ok(KrakenSystemStatus {
+                        status: KrakenStatus::Unknown,
+                        connection_id: None,
+                        version: None,
+                    }),
+                },
+                TestCase {
+                    // TC4: input is missing status
+                    input: r#"{"event":"systemStatus","version":"1.9.0"}"#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde::de::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = serde_json::from_str::<KrakenSystemStatus>(test.input);
+                match (actual, test.expected) {
+                    (Ok(actual), Ok(expected)) => {
+                        assert_eq!(actual, expected, "TC{} failed", index)
+                    }
+                    (Err(_), Err(_)) => {
+                        // Test passed
+                    }
+                    (actual, expected) => {
+                        // Test failed
+                        panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kraken_exchange_status() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<SystemStatus>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: maintenance systemStatus
+                input: r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"maintenance","version":"1.0.0"}"#,
+                expected: Some(SystemStatus::Maintenance),
+            },
+            TestCase {
+                // TC1: post_only systemStatus
+                input: r#"{"event":"systemStatus","status":"post_only","version":"1.9.0"}"#,
+                expected: Some(SystemStatus::PostOnly),
+            },
+            TestCase {
+                // TC2: online systemStatus
+                input: r#"{"event":"systemStatus","status":"online"}"#,
+                expected: Some(SystemStatus::Online),
+            },
+            TestCase {
+                // TC3: heartbeat is not a status message
+                input: r#"{"event":"heartbeat"}"#,
+                expected: None,
+            },
+            TestCase {
+                // TC4: trade is not a status message
+                input: r#"[0,[["5541.20000","0.15850568","1534614057.321597","s","l",""]],"trade","XBT/USD"]"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = exchange_status(test.input).map(|status| status.status);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_kraken_status_backoff() {
+        struct TestCase {
+            input: KrakenStatus,
+            expected: Option<Duration>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: online uses exponential reconnect backoff
+                input: KrakenStatus::Online,
+                expected: None,
+            },
+            TestCase {
+                // TC1: maintenance uses maintenance backoff
+                input: KrakenStatus::Maintenance,
+                expected: Some(KRAKEN_MAINTENANCE_BACKOFF),
+            },
+            TestCase {
+                // TC2: cancel_only still serves market data
+                input: KrakenStatus::CancelOnly,
+                expected: None,
+            },
+            TestCase {
+                // TC3: unknown status is not assumed to be maintenance
+                input: KrakenStatus::Unknown,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input.backoff(), test.expected, "TC{} failed", index);
+        }
+    }
+}
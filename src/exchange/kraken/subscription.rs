@@ -1,4 +1,4 @@
-use super::message::KrakenError;
+use super::{message::KrakenError, status::KrakenSystemStatus};
 use barter_integration::{error::SocketError, Validator};
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +20,36 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 ///
+/// #### Subscription OHLC Success
+/// The "channelName" of an OHLC subscription includes the interval in minutes.
+/// ```json
+/// {
+///   "channelID": 42,
+///   "channelName": "ohlc-5",
+///   "event": "subscriptionStatus",
+///   "pair": "XBT/USD",
+///   "status": "subscribed",
+///   "subscription": {
+///     "interval": 5,
+///     "name": "ohlc"
+///   }
+/// }
+/// ```
+///
+/// #### Subscription Private OwnTrades Success
+/// Private channels are subscribed to for every market of the account, so there is no
+/// "channelID" or "pair".
+/// ```json
+/// {
+///   "channelName": "ownTrades",
+///   "event": "subscriptionStatus",
+///   "status": "subscribed",
+///   "subscription": {
+///     "name": "ownTrades"
+///   }
+/// }
+/// ```
+///
 /// #### Subscription Trade Failure
 /// ```json
 /// {
@@ -32,19 +62,90 @@ use serde::{Deserialize, Serialize};
 ///   }
 /// }
 /// ```
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+///
+/// #### System Status Maintenance
+/// A `systemStatus` is sent on connection before any `subscriptionStatus`. Only a
+/// [`KrakenStatus::Maintenance`](super::status::KrakenStatus::Maintenance) `systemStatus`
+/// deserialises into a [`KrakenSubResponse`], any other status fails to deserialise so it's
+/// skipped by the [`WebSocketSubValidator`](crate::subscriber::validator::WebSocketSubValidator).
+///
+/// See docs: <https://docs.kraken.com/websockets/#message-systemStatus>
+/// ```json
+/// {
+///   "connectionID": 8628615390848610000,
+///   "event": "systemStatus",
+///   "status": "maintenance",
+///   "version": "1.0.0"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
 #[serde(tag = "status", rename_all = "camelCase")]
 pub enum KrakenSubResponse {
     Subscribed {
         #[serde(alias = "channelID")]
-        channel_id: u64,
+        channel_id: Option<u64>,
+        #[serde(alias = "channelName")]
+        channel_name: String,
+        pair: Option<String>,
+    },
+    Error(KrakenError),
+    #[serde(skip_serializing)]
+    SystemStatus(KrakenSystemStatus),
+}
+
+/// [`Kraken`](super::Kraken) control messages that may be received whilst validating
+/// subscriptions, used to deserialise a [`KrakenSubResponse`].
+#[derive(Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum KrakenSubResponseEvent {
+    SubscriptionStatus(KrakenSubscriptionStatus),
+    SystemStatus(KrakenSystemStatus),
+    Error(KrakenError),
+}
+
+/// [`Kraken`](super::Kraken) `subscriptionStatus` message, which is the actual subscription ack.
+#[derive(Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+enum KrakenSubscriptionStatus {
+    Subscribed {
+        #[serde(alias = "channelID", default)]
+        channel_id: Option<u64>,
         #[serde(alias = "channelName")]
         channel_name: String,
-        pair: String,
+        #[serde(default)]
+        pair: Option<String>,
     },
     Error(KrakenError),
 }
 
+impl<'de> Deserialize<'de> for KrakenSubResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match KrakenSubResponseEvent::deserialize(deserializer)? {
+            KrakenSubResponseEvent::SubscriptionStatus(KrakenSubscriptionStatus::Subscribed {
+                channel_id,
+                channel_name,
+                pair,
+            }) => Ok(Self::Subscribed {
+                channel_id,
+                channel_name,
+                pair,
+            }),
+            KrakenSubResponseEvent::SubscriptionStatus(KrakenSubscriptionStatus::Error(error))
+            | KrakenSubResponseEvent::Error(error) => Ok(Self::Error(error)),
+            KrakenSubResponseEvent::SystemStatus(system) if system.status.is_maintenance() => {
+                Ok(Self::SystemStatus(system))
+            }
+            KrakenSubResponseEvent::SystemStatus(system) => Err(serde::de::Error::custom(format!(
+                "Kraken systemStatus {} is not a subscription response",
+                system.status
+            ))),
+        }
+    }
+}
+
 impl Validator for KrakenSubResponse {
     fn validate(self) -> Result<Self, SocketError>
     where
@@ -56,6 +157,11 @@ impl Validator for KrakenSubResponse {
                 "received failure subscription response: {}",
                 error.message
             ))),
+            KrakenSubResponse::SystemStatus(system) => Err(SocketError::Subscribe(format!(
+                "received Kraken systemStatus {}: subscription attempts are paused until it's \
+                 back online",
+                system.status
+            ))),
         }
     }
 }
@@ -63,6 +169,7 @@ impl Validator for KrakenSubResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exchange::kraken::status::KrakenStatus;
 
     mod de {
         use super::*;
@@ -90,9 +197,9 @@ mod tests {
                     }
                     "#,
                     expected: Ok(KrakenSubResponse::Subscribed {
-                        channel_id: 10001,
+                        channel_id: Some(10001),
                         channel_name: "ticker".to_string(),
-                        pair: "XBT/EUR".to_string(),
+                        pair: Some("XBT/EUR".to_string()),
                     }),
                 },
                 TestCase {
@@ -112,6 +219,117 @@ mod tests {
                         message: "Subscription name invalid".to_string(),
                     })),
                 },
+                TestCase {
+                    // TC2: input response is generic error event
+                    input: r#"{"errorMessage": "Malformed request", "event": "error"}"#,
+                    expected: Ok(KrakenSubResponse::Error(KrakenError {
+                        message: "Malformed request".to_string(),
+                    })),
+                },
+                TestCase {
+                    // TC3: input response is online systemStatus, which is not a response
+                    input: r#"
+                    {
+                        "connectionID": 8628615390848610000,
+                        "event": "systemStatus",
+                        "status": "online",
+                        "version": "1.0.0"
+                    }
+                    "#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde::de::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+                TestCase {
+                    // TC4: input response is maintenance systemStatus
+                    input: r#"
+                    {
+                        "connectionID": 8628615390848610000,
+                        "event": "systemStatus",
+                        "status": "maintenance",
+                        "version": "1.0.0"
+                    }
+                    "#,
+                    expected: Ok(KrakenSubResponse::SystemStatus(KrakenSystemStatus {
+                        status: KrakenStatus::Maintenance,
+                        connection_id: Some(8628615390848610000),
+                        version: Some("1.0.0".to_string()),
+                    })),
+                },
+                TestCase {
+                    // TC5: input response is cancel_only systemStatus, which is not a response
+                    input: r#"{"event": "systemStatus", "status": "cancel_only"}"#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde::de::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+                TestCase {
+                    // TC6: input response is heartbeat, which is not a response
+                    input: r#"{"event": "heartbeat"}"#,
+                    expected: Err(SocketError::Deserialise {
+                        error: serde::de::Error::custom(""),
+                        payload: "".to_owned(),
+                    }),
+                },
+                TestCase {
+                    // TC7: input response is Subscribed to a private channel
+                    input: r#"
+                    {
+                        "channelName": "ownTrades",
+                        "event": "subscriptionStatus",
+                        "status": "subscribed",
+                        "subscription": {
+                            "name": "ownTrades"
+                        }
+                    }
+                    "#,
+                    expected: Ok(KrakenSubResponse::Subscribed {
+                        channel_id: None,
+                        channel_name: "ownTrades".to_string(),
+                        pair: None,
+                    }),
+                },
+                TestCase {
+                    // TC8: input response is Subscribed to an ohlc channel w/ interval
+                    input: r#"
+                    {
+                        "channelID": 42,
+                        "channelName": "ohlc-5",
+                        "event": "subscriptionStatus",
+                        "pair": "XBT/USD",
+                        "status": "subscribed",
+                        "subscription": {
+                            "interval": 5,
+                            "name": "ohlc"
+                        }
+                    }
+                    "#,
+                    expected: Ok(KrakenSubResponse::Subscribed {
+                        channel_id: Some(42),
+                        channel_name: "ohlc-5".to_string(),
+                        pair: Some("XBT/USD".to_string()),
+                    }),
+                },
+                TestCase {
+                    // TC9: input response is failed ohlc subscription w/ unsupported interval
+                    input: r#"
+                    {
+                        "errorMessage": "Subscription ohlc interval not supported",
+                        "event": "subscriptionStatus",
+                        "pair": "XBT/USD",
+                        "status": "error",
+                        "subscription": {
+                            "interval": 3,
+                            "name": "ohlc"
+                        }
+                    }
+                    "#,
+                    expected: Ok(KrakenSubResponse::Error(KrakenError {
+                        message: "Subscription ohlc interval not supported".to_string(),
+                    })),
+                },
             ];
 
             for (index, test) in cases.into_iter().enumerate() {
@@ -143,9 +361,9 @@ mod tests {
             TestCase {
                 // TC0: input response is successful subscription
                 input_response: KrakenSubResponse::Subscribed {
-                    channel_id: 10001,
+                    channel_id: Some(10001),
                     channel_name: "ticker".to_string(),
-                    pair: "XBT/EUR".to_string(),
+                    pair: Some("XBT/EUR".to_string()),
                 },
                 is_valid: true,
             },
@@ -156,6 +374,15 @@ mod tests {
                 }),
                 is_valid: false,
             },
+            TestCase {
+                // TC2: input response is maintenance systemStatus
+                input_response: KrakenSubResponse::SystemStatus(KrakenSystemStatus {
+                    status: KrakenStatus::Maintenance,
+                    connection_id: None,
+                    version: None,
+                }),
+                is_valid: false,
+            },
         ];
 
         for (index, test) in cases.into_iter().enumerate() {
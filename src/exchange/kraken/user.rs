@@ -0,0 +1,779 @@
+use super::{
+    channel::KrakenChannel,
+    market::KrakenMarket,
+    message::{KrakenEvent, KrakenMessage},
+    Kraken,
+};
+use crate::{
+    datetime::parse_fractional_seconds_str,
+    error::DataError,
+    event::{MarketEvent, MarketEvents, RawTimestamp, TimestampUnit},
+    exchange::{Connector, ExchangeSub},
+    num::{self, de_optional_str, Num},
+    streams::config::StreamConfig,
+    subscription::{
+        fill::{Fill, Fills, Liquidity},
+        order::{OrderStatus, OrderUpdate, OrderUpdates},
+        Map,
+    },
+    transformer::ExchangeTransformer,
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    de::extract_next,
+    model::{Exchange, Instrument, Side},
+    protocol::websocket::WsMessage,
+    Transformer,
+};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use smallvec::smallvec;
+use std::{collections::HashMap, marker::PhantomData};
+use tokio::sync::mpsc;
+
+/// Terse type alias for a [`Kraken`] private `ownTrades` WebSocket message.
+pub type KrakenOwnTrades = KrakenMessage<KrakenPrivateData<KrakenOwnTrade>>;
+
+/// Terse type alias for a [`Kraken`] private `openOrders` WebSocket message.
+pub type KrakenOpenOrders = KrakenMessage<KrakenPrivateData<KrakenOpenOrder>>;
+
+/// [`Kraken`] private channel data, ie/ the trades or orders of the account owning the WebSockets
+/// token, keyed by their trade or order id.
+///
+/// Private messages are positional arrays, containing an array of single entry maps rather than
+/// the channelID & pair of public messages, since they're for every market of the account.
+///
+/// ### Raw Payload Examples
+/// #### OwnTrades
+/// See docs: <https://docs.kraken.com/websockets/#message-ownTrades>
+/// ```json
+/// [
+///   [
+///     {
+///       "TDLH43-DVQXD-2KHVYY": {
+///         "cost": "1000000.00000",
+///         "fee": "1600.00000",
+///         "margin": "0.00000",
+///         "ordertxid": "OGTT3Y-C6I3P-XRI6HX",
+///         "ordertype": "limit",
+///         "pair": "XBT/EUR",
+///         "postxid": "TKH2SE-M7IF5-CFI7LT",
+///         "price": "100000.00000",
+///         "time": "1560516023.070651",
+///         "type": "sell",
+///         "vol": "1000000000.00000000"
+///       }
+///     }
+///   ],
+///   "ownTrades",
+///   {
+///     "sequence": 2948
+///   }
+/// ]
+/// ```
+///
+/// #### OpenOrders
+/// See docs: <https://docs.kraken.com/websockets/#message-openOrders>
+/// ```json
+/// [
+///   [
+///     {
+///       "OGTT3Y-C6I3P-XRI6HX": {
+///         "status": "closed",
+///         "vol_exec": "10.00345345",
+///         "lastupdated": "1560516023.070651"
+///       }
+///     }
+///   ],
+///   "openOrders",
+///   {
+///     "sequence": 59342
+///   }
+/// ]
+/// ```
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct KrakenPrivateData<T> {
+    pub items: Vec<(String, T)>,
+    pub sequence: u64,
+}
+
+impl<'de, T> Deserialize<'de> for KrakenPrivateData<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SeqVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> serde::de::Visitor<'de> for SeqVisitor<T>
+        where
+            T: DeserializeOwned,
+        {
+            type Value = KrakenPrivateData<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("KrakenPrivateData struct from the Kraken WebSocket API")
+            }
+
+            fn visit_seq<SeqAccessor>(
+                self,
+                mut seq: SeqAccessor,
+            ) -> Result<Self::Value, SeqAccessor::Error>
+            where
+                SeqAccessor: serde::de::SeqAccess<'de>,
+            {
+                // KrakenPrivateData Sequence Format:
+                // [[{id: item}], channelName, {"sequence": sequence}]
+                // <https://docs.kraken.com/websockets/#message-ownTrades>
+
+                // Extract Vec<{id: item}> & flatten into (id, item) tuples
+                let items =
+                    extract_next::<SeqAccessor, Vec<HashMap<String, T>>>(&mut seq, "items")?
+                        .into_iter()
+                        .flatten()
+                        .collect();
+
+                // Extract channelName (eg/ "ownTrades") & ignore
+                let _: serde::de::IgnoredAny = extract_next(&mut seq, "channelName")?;
+
+                // Extract sequence number of this channel's messages
+                let KrakenSequence { sequence } = extract_next(&mut seq, "sequence")?;
+
+                // Ignore any additional elements or SerDe will fail
+                //  '--> Exchange may add fields without warning
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+                Ok(KrakenPrivateData { items, sequence })
+            }
+        }
+
+        deserializer.deserialize_seq(SeqVisitor(PhantomData))
+    }
+}
+
+/// Sequence number of a [`KrakenPrivateData`] message.
+#[derive(Deserialize)]
+struct KrakenSequence {
+    sequence: u64,
+}
+
+/// [`Kraken`] trade of the account owning the WebSockets token.
+///
+/// See [`KrakenPrivateData`] for full raw payload examples.
+///
+/// See docs: <https://docs.kraken.com/websockets/#message-ownTrades>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenOwnTrade {
+    #[serde(rename = "ordertxid")]
+    pub order_id: String,
+    pub pair: String,
+    #[serde(rename = "type")]
+    pub side: Side,
+    #[serde(rename = "ordertype")]
+    pub order_kind: String,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(rename = "vol", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    #[serde(deserialize_with = "crate::datetime::de_str_epoch_s_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    /// Whether the trade provided liquidity, only included by newer API versions.
+    #[serde(default)]
+    pub maker: Option<bool>,
+    /// Fee charged for the trade, in the quote asset unless the order prefers fees in the base
+    /// asset (ie/ "fcib" order flag).
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub fee: Option<Num>,
+}
+
+impl KrakenOwnTrade {
+    /// Determine the [`Liquidity`] of [`Self`], using the "maker" flag if it's included.
+    /// Otherwise, market orders always remove liquidity, and any other order is assumed to have
+    /// rested on the OrderBook.
+    pub fn liquidity(&self) -> Liquidity {
+        match (self.maker, self.order_kind.as_str()) {
+            (Some(true), _) => Liquidity::Maker,
+            (Some(false), _) | (None, "market") => Liquidity::Taker,
+            (None, _) => Liquidity::Maker,
+        }
+    }
+}
+
+/// [`Kraken`] order of the account owning the WebSockets token.
+///
+/// The first message of an order (including those of the initial snapshot) contains every
+/// field, whereas any subsequent update only contains the fields that changed.
+///
+/// See [`KrakenPrivateData`] for full raw payload examples.
+///
+/// See docs: <https://docs.kraken.com/websockets/#message-openOrders>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct KrakenOpenOrder {
+    #[serde(default)]
+    pub status: Option<KrakenOrderStatus>,
+    #[serde(default)]
+    pub descr: Option<KrakenOrderDescription>,
+    #[serde(default, rename = "vol", deserialize_with = "de_optional_str")]
+    pub amount: Option<Num>,
+    #[serde(default, rename = "vol_exec", deserialize_with = "de_optional_str")]
+    pub filled_amount: Option<Num>,
+    #[serde(default, rename = "cl_ord_id")]
+    pub client_order_id: Option<String>,
+    #[serde(default, rename = "opentm")]
+    pub open_time: Option<String>,
+    #[serde(default, rename = "lastupdated")]
+    pub last_updated: Option<String>,
+    /// Reason the order was cancelled (eg/ "User requested"), only included once it's cancelled.
+    #[serde(default)]
+    pub cancel_reason: Option<String>,
+}
+
+impl KrakenOpenOrder {
+    /// Time of the latest change to [`Self`], if it's included.
+    pub fn time(&self) -> Option<DateTime<Utc>> {
+        self.last_updated
+            .as_deref()
+            .or(self.open_time.as_deref())
+            .and_then(parse_fractional_seconds_str)
+    }
+}
+
+/// Description of a [`KrakenOpenOrder`], only included in the first message of an order.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenOrderDescription {
+    pub pair: String,
+    #[serde(rename = "type")]
+    pub side: Side,
+    /// Limit price of the order, zero for market orders.
+    #[serde(default, deserialize_with = "de_optional_str")]
+    pub price: Option<Num>,
+}
+
+/// [`KrakenOpenOrder`] "status".
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KrakenOrderStatus {
+    Pending,
+    Open,
+    Closed,
+    Canceled,
+    Expired,
+}
+
+impl KrakenOrderStatus {
+    /// Determine if an order with [`Self`] is finished, and will receive no further updates.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Closed | Self::Canceled | Self::Expired)
+    }
+
+    /// Normalise [`Self`] into an [`OrderStatus`], using the amount filled so far to distinguish
+    /// partially filled open orders.
+    pub fn order_status(&self, filled_amount: Num) -> OrderStatus {
+        match self {
+            Self::Pending | Self::Open if filled_amount > num::zero() => {
+                OrderStatus::PartiallyFilled
+            }
+            Self::Pending | Self::Open => OrderStatus::Open,
+            Self::Closed => OrderStatus::Filled,
+            Self::Canceled | Self::Expired => OrderStatus::Cancelled,
+        }
+    }
+}
+
+/// Determine the subscribed [`Instrument`] of the provided private channel & pair (eg/ "XBT/USD"),
+/// if any.
+fn private_instrument(
+    instrument_map: &Map<Instrument>,
+    channel: KrakenChannel,
+    pair: &str,
+) -> Option<Instrument> {
+    let subscription_id = ExchangeSub::from((channel, KrakenMarket(pair.to_owned()))).id();
+    instrument_map.0.get(&subscription_id).cloned()
+}
+
+/// Convert a [`KrakenEvent`] received on a private channel into a terminal [`DataError::Auth`] if
+/// [`Kraken`] invalidated the session (eg/ "ESession:Invalid session"), so the connection is
+/// re-initialised with a new WebSockets token.
+fn session_error<T>(event: KrakenEvent) -> MarketEvents<T> {
+    match event {
+        KrakenEvent::Error(error)
+            if error.message.starts_with("ESession") || error.message.starts_with("EAPI") =>
+        {
+            smallvec![Err(DataError::Auth {
+                exchange: Kraken::ID,
+                reason: error.message,
+            })]
+        }
+        _ => smallvec![],
+    }
+}
+
+/// [`Kraken`] [`Fills`] [`ExchangeTransformer`], yielding a [`Fill`] for each [`KrakenOwnTrade`]
+/// of a subscribed [`Instrument`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct KrakenFillsTransformer {
+    instrument_map: Map<Instrument>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Kraken, Fills> for KrakenFillsTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self { instrument_map })
+    }
+}
+
+impl Transformer for KrakenFillsTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, KrakenOwnTrades);
+    type Output = MarketEvent<Fill>;
+    type OutputIter = MarketEvents<Fill>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        let trades = match input {
+            KrakenMessage::Data(data) => data.items,
+            KrakenMessage::Event(event) => return session_error(event),
+        };
+
+        trades
+            .into_iter()
+            .filter_map(|(trade_id, trade)| {
+                // Trades of markets that were not subscribed to are dropped
+                let instrument = private_instrument(
+                    &self.instrument_map,
+                    KrakenChannel::OWN_TRADES,
+                    &trade.pair,
+                )?;
+
+                Some(Ok(MarketEvent {
+                    exchange_time: trade.time,
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        trade.time,
+                        TimestampUnit::Microseconds,
+                    )),
+                    received_time,
+                    exchange: Exchange::from(Kraken::ID),
+                    instrument,
+                    kind: Fill {
+                        trade_id,
+                        liquidity: trade.liquidity(),
+                        order_id: trade.order_id,
+                        side: trade.side,
+                        price: trade.price,
+                        amount: trade.amount,
+                        // Fee asset depends on the order flags, which are not included
+                        fee: trade.fee,
+                        fee_asset: None,
+                    },
+                    meta: (),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// State of an order of the account tracked by the [`KrakenOrderUpdatesTransformer`], since each
+/// [`KrakenOpenOrder`] update only contains the fields that changed.
+#[derive(Clone, PartialEq, Debug)]
+struct KrakenOrderState {
+    instrument: Instrument,
+    client_order_id: Option<String>,
+    status: KrakenOrderStatus,
+    side: Side,
+    price: Option<Num>,
+    amount: Num,
+    filled_amount: Num,
+    reason: Option<String>,
+}
+
+impl KrakenOrderState {
+    /// Apply the changed fields of the provided [`KrakenOpenOrder`] update.
+    fn update(&mut self, order: KrakenOpenOrder) {
+        if let Some(status) = order.status {
+            self.status = status;
+        }
+        if let Some(amount) = order.amount {
+            self.amount = amount;
+        }
+        if let Some(filled_amount) = order.filled_amount {
+            self.filled_amount = filled_amount;
+        }
+        if order.client_order_id.is_some() {
+            self.client_order_id = order.client_order_id;
+        }
+        if order.cancel_reason.is_some() {
+            self.reason = order.cancel_reason;
+        }
+    }
+}
+
+/// [`Kraken`] [`OrderUpdates`] [`ExchangeTransformer`], yielding an [`OrderUpdate`] for each
+/// [`KrakenOpenOrder`] of a subscribed [`Instrument`] that changes the normalised order.
+///
+/// The market, side & limit price are only included in the first message of an order, so every
+/// order is tracked until it's closed, cancelled or expired. Orders placed before connecting are
+/// included in the initial snapshot.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KrakenOrderUpdatesTransformer {
+    instrument_map: Map<Instrument>,
+    orders: HashMap<String, KrakenOrderState>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Kraken, OrderUpdates> for KrakenOrderUpdatesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            orders: HashMap::new(),
+        })
+    }
+}
+
+impl Transformer for KrakenOrderUpdatesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, KrakenOpenOrders);
+    type Output = MarketEvent<OrderUpdate>;
+    type OutputIter = MarketEvents<OrderUpdate>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        let orders = match input {
+            KrakenMessage::Data(data) => data.items,
+            KrakenMessage::Event(event) => return session_error(event),
+        };
+
+        orders
+            .into_iter()
+            .filter_map(|(order_id, order)| self.update(received_time, order_id, order))
+            .map(Ok)
+            .collect()
+    }
+}
+
+impl KrakenOrderUpdatesTransformer {
+    /// Apply the provided [`KrakenOpenOrder`], received at the provided `received_time`, to it's
+    /// tracked [`KrakenOrderState`], returning the resulting [`OrderUpdate`] if the order is new
+    /// or the normalised order changed.
+    ///
+    /// Updates of orders that are not tracked (ie/ of markets that were not subscribed to) are
+    /// dropped.
+    fn update(
+        &mut self,
+        received_time: DateTime<Utc>,
+        order_id: String,
+        order: KrakenOpenOrder,
+    ) -> Option<MarketEvent<OrderUpdate>> {
+        let time = order.time().unwrap_or(received_time);
+
+        let state = match (self.orders.get_mut(&order_id), &order.descr) {
+            (Some(state), _) => {
+                let previous = state.clone();
+                state.update(order);
+                if *state == previous {
+                    return None;
+                }
+                state.clone()
+            }
+            (None, Some(descr)) => {
+                let instrument = private_instrument(
+                    &self.instrument_map,
+                    KrakenChannel::OPEN_ORDERS,
+                    &descr.pair,
+                )?;
+                let mut state = KrakenOrderState {
+                    instrument,
+                    client_order_id: None,
+                    status: KrakenOrderStatus::Pending,
+                    side: descr.side,
+                    // Market orders have a zero price
+                    price: descr.price.filter(|price| *price != num::zero()),
+                    amount: num::zero(),
+                    filled_amount: num::zero(),
+                    reason: None,
+                };
+                state.update(order);
+                self.orders.insert(order_id.clone(), state.clone());
+                state
+            }
+            (None, None) => return None,
+        };
+
+        if state.status.is_finished() {
+            self.orders.remove(&order_id);
+        }
+
+        Some(MarketEvent {
+            exchange_time: time,
+            raw_exchange_time: Some(RawTimestamp::from_datetime(
+                time,
+                TimestampUnit::Microseconds,
+            )),
+            received_time,
+            exchange: Exchange::from(Kraken::ID),
+            instrument: state.instrument,
+            kind: OrderUpdate {
+                order_id,
+                client_order_id: state.client_order_id,
+                status: state.status.order_status(state.filled_amount),
+                side: state.side,
+                price: state.price,
+                amount: state.amount,
+                filled_amount: state.filled_amount,
+                reason: state.reason,
+            },
+            meta: (),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use barter_integration::model::InstrumentKind;
+
+    fn btc_eur() -> Instrument {
+        Instrument::from(("btc", "eur", InstrumentKind::Spot))
+    }
+
+    fn instrument_map(channel: KrakenChannel) -> Map<Instrument> {
+        Map([(
+            ExchangeSub::from((channel, KrakenMarket("XBT/EUR".to_owned()))).id(),
+            btc_eur(),
+        )]
+        .into_iter()
+        .collect())
+    }
+
+    #[test]
+    fn test_kraken_private_data() {
+        let input = r#"[[{"TDLH43-DVQXD-2KHVYY":{"cost":"1000000.00000","fee":"1600.00000","margin":"0.00000","ordertxid":"OGTT3Y-C6I3P-XRI6HX","ordertype":"limit","pair":"XBT/EUR","postxid":"TKH2SE-M7IF5-CFI7LT","price":"100000.00000","time":"1560516023.070651","type":"sell","vol":"1000000000.00000000"}}],"ownTrades",{"sequence":2948}]"#;
+
+        let actual = serde_json::from_str::<KrakenOwnTrades>(input).unwrap();
+        let expected = KrakenMessage::Data(KrakenPrivateData {
+            items: vec![(
+                "TDLH43-DVQXD-2KHVYY".to_owned(),
+                KrakenOwnTrade {
+                    order_id: "OGTT3Y-C6I3P-XRI6HX".to_owned(),
+                    pair: "XBT/EUR".to_owned(),
+                    side: Side::Sell,
+                    order_kind: "limit".to_owned(),
+                    price: num!(100000.0),
+                    amount: num!(1000000000.0),
+                    time: DateTime::from_timestamp(1560516023, 70651000).unwrap(),
+                    maker: None,
+                    fee: Some(num!(1600.0)),
+                },
+            )],
+            sequence: 2948,
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_kraken_fills_transformer() {
+        let mut transformer = KrakenFillsTransformer {
+            instrument_map: instrument_map(KrakenChannel::OWN_TRADES),
+        };
+
+        // Trades: limit taker w/ "maker" flag, market order, & unsubscribed market
+        let input = serde_json::from_str::<KrakenOwnTrades>(
+            r#"[
+                [
+                    {"TDLH43-DVQXD-2KHVYY":{"ordertxid":"OGTT3Y-C6I3P-XRI6HX","ordertype":"limit","pair":"XBT/EUR","price":"30000.5","time":"1560516023.070651","type":"buy","vol":"0.5","maker":false,"fee":"24.0004"}},
+                    {"TQ8ZXX-LHI3A-QZ4RE6":{"ordertxid":"OQCLML-BW3P3-BUCMWZ","ordertype":"market","pair":"XBT/EUR","price":"30001","time":"1560516024.5","type":"sell","vol":"0.25"}},
+                    {"TCCCTY-WE2O6-P3NB37":{"ordertxid":"OMMDB2-FSB6Z-7W3HPO","ordertype":"limit","pair":"ETH/EUR","price":"2000","time":"1560516025.5","type":"buy","vol":"1"}}
+                ],
+                "ownTrades",
+                {"sequence":2}
+            ]"#,
+        )
+        .unwrap();
+
+        let actual = transformer
+            .transform((Utc::now(), input))
+            .into_iter()
+            .map(|event| {
+                let event = event.unwrap();
+                assert_eq!(event.instrument, btc_eur());
+                event.kind
+            })
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            Fill {
+                trade_id: "TDLH43-DVQXD-2KHVYY".to_owned(),
+                order_id: "OGTT3Y-C6I3P-XRI6HX".to_owned(),
+                side: Side::Buy,
+                price: num!(30000.5),
+                amount: num!(0.5),
+                liquidity: Liquidity::Taker,
+                fee: Some(num!(24.0004)),
+                fee_asset: None,
+            },
+            Fill {
+                trade_id: "TQ8ZXX-LHI3A-QZ4RE6".to_owned(),
+                order_id: "OQCLML-BW3P3-BUCMWZ".to_owned(),
+                side: Side::Sell,
+                price: num!(30001.0),
+                amount: num!(0.25),
+                liquidity: Liquidity::Taker,
+                fee: None,
+                fee_asset: None,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_kraken_private_session_error() {
+        struct TestCase {
+            input: &'static str,
+            expected_auth_error: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: invalidated session is a terminal auth error
+                input: r#"{"errorMessage":"ESession:Invalid session","event":"error"}"#,
+                expected_auth_error: true,
+            },
+            TestCase {
+                // TC1: heartbeat is skipped
+                input: r#"{"event":"heartbeat"}"#,
+                expected_auth_error: false,
+            },
+            TestCase {
+                // TC2: other errors are skipped
+                input: r#"{"errorMessage":"Malformed request","event":"error"}"#,
+                expected_auth_error: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut transformer = KrakenFillsTransformer {
+                instrument_map: instrument_map(KrakenChannel::OWN_TRADES),
+            };
+            let input = serde_json::from_str::<KrakenOwnTrades>(test.input).unwrap();
+            let actual = transformer.transform((Utc::now(), input));
+
+            match (actual.as_slice(), test.expected_auth_error) {
+                ([Err(error)], true) => assert!(error.is_terminal(), "TC{} failed", index),
+                ([], false) => {}
+                (actual, _) => panic!("TC{index} failed: {actual:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_kraken_order_updates_transformer() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<OrderUpdate>,
+        }
+
+        let mut transformer = KrakenOrderUpdatesTransformer {
+            instrument_map: instrument_map(KrakenChannel::OPEN_ORDERS),
+            orders: HashMap::new(),
+        };
+
+        let update = |status, filled_amount| OrderUpdate {
+            order_id: "OGTT3Y-C6I3P-XRI6HX".to_owned(),
+            client_order_id: Some("barter-1".to_owned()),
+            status,
+            side: Side::Sell,
+            price: Some(num!(34.5)),
+            amount: num!(10.5),
+            filled_amount,
+            reason: None,
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: snapshot w/ open order & order of an unsubscribed market
+                input: r#"[
+                    [
+                        {"OGTT3Y-C6I3P-XRI6HX":{"avg_price":"0.00000","cost":"0.00000","descr":{"close":"","leverage":"0:1","order":"sell 10.5 XBT/EUR @ limit 34.50000","ordertype":"limit","pair":"XBT/EUR","price":"34.50000","price2":"0.00000","type":"sell"},"expiretm":"0.000000","fee":"0.00000","limitprice":"34.50000","misc":"","oflags":"fcib","opentm":"1560516023.070651","refid":"OKIVMP-5GVZN-Z2D2UA","starttm":"0.000000","status":"open","stopprice":"0.000000","timeinforce":"GTC","userref":0,"cl_ord_id":"barter-1","vol":"10.5","vol_exec":"0.00000000"}},
+                        {"OQCLML-BW3P3-BUCMWZ":{"descr":{"ordertype":"limit","pair":"ETH/EUR","price":"2000.0","type":"buy"},"opentm":"1560516023.5","status":"open","vol":"1","vol_exec":"0"}}
+                    ],
+                    "openOrders",
+                    {"sequence":1}
+                ]"#,
+                expected: vec![update(OrderStatus::Open, num!(0.0))],
+            },
+            TestCase {
+                // TC1: partial fill
+                input: r#"[[{"OGTT3Y-C6I3P-XRI6HX":{"vol_exec":"4.25","cost":"146.625","fee":"0.2","avg_price":"34.5","lastupdated":"1560516024.5"}}],"openOrders",{"sequence":2}]"#,
+                expected: vec![update(OrderStatus::PartiallyFilled, num!(4.25))],
+            },
+            TestCase {
+                // TC2: update w/o any normalised change, & update of an untracked order
+                input: r#"[[{"OGTT3Y-C6I3P-XRI6HX":{"fee":"0.25"}},{"OQCLML-BW3P3-BUCMWZ":{"status":"canceled"}}],"openOrders",{"sequence":3}]"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC3: fully filled & closed
+                input: r#"[[{"OGTT3Y-C6I3P-XRI6HX":{"status":"closed","vol_exec":"10.5","lastupdated":"1560516025.5"}}],"openOrders",{"sequence":4}]"#,
+                expected: vec![update(OrderStatus::Filled, num!(10.5))],
+            },
+            TestCase {
+                // TC4: closed order is no longer tracked
+                input: r#"[[{"OGTT3Y-C6I3P-XRI6HX":{"status":"closed"}}],"openOrders",{"sequence":5}]"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC5: new market order that is cancelled
+                input: r#"[[{"OHZ4DC-6MNAK-WSTOXL":{"descr":{"ordertype":"market","pair":"XBT/EUR","price":"0.00000","type":"buy"},"opentm":"1560516026.5","status":"pending","vol":"2","vol_exec":"0"}},{"OHZ4DC-6MNAK-WSTOXL":{"status":"canceled","lastupdated":"1560516027.5","cancel_reason":"User requested"}}],"openOrders",{"sequence":6}]"#,
+                expected: vec![
+                    OrderUpdate {
+                        order_id: "OHZ4DC-6MNAK-WSTOXL".to_owned(),
+                        client_order_id: None,
+                        status: OrderStatus::Open,
+                        side: Side::Buy,
+                        price: None,
+                        amount: num!(2.0),
+                        filled_amount: num!(0.0),
+                        reason: None,
+                    },
+                    OrderUpdate {
+                        order_id: "OHZ4DC-6MNAK-WSTOXL".to_owned(),
+                        client_order_id: None,
+                        status: OrderStatus::Cancelled,
+                        side: Side::Buy,
+                        price: None,
+                        amount: num!(2.0),
+                        filled_amount: num!(0.0),
+                        reason: Some("User requested".to_owned()),
+                    },
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let input = serde_json::from_str::<KrakenOpenOrders>(test.input).unwrap();
+            let actual = transformer
+                .transform((Utc::now(), input))
+                .into_iter()
+                .map(|event| {
+                    let event = event.unwrap();
+                    assert_eq!(event.instrument, btc_eur(), "TC{} failed", index);
+                    event.kind
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        assert!(transformer.orders.is_empty());
+    }
+}
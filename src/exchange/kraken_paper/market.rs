@@ -1,5 +1,9 @@
 use super::Kraken;
-use crate::{subscription::Subscription, Identifier};
+use crate::{
+    exchange::{alias::exchange_assets, Connector},
+    subscription::Subscription,
+    Identifier,
+};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
@@ -11,7 +15,8 @@ pub struct KrakenMarket(pub String);
 
 impl<Kind> Identifier<KrakenMarket> for Subscription<Kraken, Kind> {
     fn id(&self) -> KrakenMarket {
-        KrakenMarket(format!("{}/{}", self.instrument.base, self.instrument.quote).to_uppercase())
+        let (base, quote) = exchange_assets(Kraken::ID, &self.instrument);
+        KrakenMarket(format!("{base}/{quote}").to_uppercase())
     }
 }
 
@@ -1,16 +1,19 @@
 use super::KrakenMessage;
 use crate::{
-    event::{MarketEvent, MarketIter},
+    datetime::parse_fractional_seconds_str,
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::ExchangeId,
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_integration::{
-    de::{datetime_utc_from_epoch_duration, extract_next},
+    de::extract_next,
     model::{Exchange, Instrument, Side, SubscriptionId},
 };
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use smallvec::smallvec;
 
 /// Terse type alias for an [`Kraken`](super::Kraken) real-time trades WebSocket message.
 pub type KrakenTrades = KrakenMessage<KrakenTradesInner>;
@@ -33,9 +36,9 @@ pub struct KrakenTradesInner {
 /// See docs: <https://docs.kraken.com/websockets/#message-trade>
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Serialize)]
 pub struct KrakenTrade {
-    pub price: f64,
+    pub price: Num,
     #[serde(rename = "quantity")]
-    pub amount: f64,
+    pub amount: Num,
     pub time: DateTime<Utc>,
     pub side: Side,
 }
@@ -51,15 +54,22 @@ impl Identifier<Option<SubscriptionId>> for KrakenTradesInner {
 fn custom_kraken_trade_id(trade: &KrakenTrade) -> String {
     format!(
         "{}_{}_{}_{}",
-        trade.time.timestamp_nanos(),
+        trade.time.timestamp_nanos_opt().unwrap_or_default(),
         trade.side,
         trade.price,
         trade.amount
     )
 }
 
-impl From<(ExchangeId, Instrument, KrakenTrades)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, KrakenTrades)) -> Self {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, KrakenTrades)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trades): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            KrakenTrades,
+        ),
+    ) -> Self {
         match trades {
             KrakenTrades::Data(trades) => trades
                 .trades
@@ -67,7 +77,11 @@ impl From<(ExchangeId, Instrument, KrakenTrades)> for MarketIter<PublicTrade> {
                 .map(|trade| {
                     Ok(MarketEvent {
                         exchange_time: trade.time,
-                        received_time: Utc::now(),
+                        raw_exchange_time: Some(RawTimestamp::from_datetime(
+                            trade.time,
+                            TimestampUnit::Microseconds,
+                        )),
+                        received_time,
                         exchange: Exchange::from(exchange_id),
                         instrument: instrument.clone(),
                         kind: PublicTrade {
@@ -76,10 +90,11 @@ impl From<(ExchangeId, Instrument, KrakenTrades)> for MarketIter<PublicTrade> {
                             amount: trade.amount,
                             side: trade.side,
                         },
+                        meta: (),
                     })
                 })
                 .collect(),
-            KrakenTrades::Event(_) => Self(vec![]),
+            KrakenTrades::Event(_) => Self(smallvec![]),
         }
     }
 }
@@ -163,23 +178,21 @@ impl<'de> serde::de::Deserialize<'de> for KrakenTrade {
                 // [price, volume, time, side, orderType, misc]
                 // <https://docs.kraken.com/websockets/#message-trade>
 
-                // Extract String price & parse to f64
+                // Extract String price & parse to Num
                 let price = extract_next::<SeqAccessor, String>(&mut seq, "price")?
                     .parse()
                     .map_err(serde::de::Error::custom)?;
 
-                // Extract String amount & parse to f64
+                // Extract String amount & parse to Num
                 let amount = extract_next::<SeqAccessor, String>(&mut seq, "quantity")?
                     .parse()
                     .map_err(serde::de::Error::custom)?;
 
-                // Extract String price, parse to f64, map to DateTime<Utc>
-                let time = extract_next::<SeqAccessor, String>(&mut seq, "time")?
-                    .parse()
-                    .map(|time| {
-                        datetime_utc_from_epoch_duration(std::time::Duration::from_secs_f64(time))
-                    })
-                    .map_err(serde::de::Error::custom)?;
+                // Extract String epoch seconds & map to DateTime<Utc> without f64 rounding
+                let time = extract_next::<SeqAccessor, String>(&mut seq, "time")?;
+                let time = parse_fractional_seconds_str(&time).ok_or_else(|| {
+                    serde::de::Error::custom(format!("invalid epoch timestamp: {time}"))
+                })?;
 
                 // Extract Side
                 let side: Side = extract_next(&mut seq, "side")?;
@@ -205,6 +218,7 @@ impl<'de> serde::de::Deserialize<'de> for KrakenTrade {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -250,18 +264,18 @@ mod tests {
                     subscription_id: SubscriptionId::from("trade|XBT/USD"),
                     trades: vec![
                         KrakenTrade {
-                            price: 5541.2,
-                            amount: 0.15850568,
+                            price: num!(5541.2),
+                            amount: num!(0.15850568),
                             time: datetime_utc_from_epoch_duration(
-                                std::time::Duration::from_secs_f64(1534614057.321597),
+                                std::time::Duration::from_micros(1534614057321597),
                             ),
                             side: Side::Sell,
                         },
                         KrakenTrade {
-                            price: 6060.0,
-                            amount: 0.02455000,
+                            price: num!(6060.0),
+                            amount: num!(0.02455000),
                             time: datetime_utc_from_epoch_duration(
-                                std::time::Duration::from_secs_f64(1534614057.324998),
+                                std::time::Duration::from_micros(1534614057324998),
                             ),
                             side: Side::Buy,
                         },
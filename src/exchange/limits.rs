@@ -0,0 +1,183 @@
+use super::ExchangeId;
+use crate::{error::DataError, streams::config::StreamConfig};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+use tracing::{debug, warn};
+
+/// Connection-level limits of an exchange server, used to configure how many
+/// [`Subscription`](crate::subscription::Subscription)s are actioned per connection and how
+/// quickly connections & messages are sent.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct ConnectionLimits {
+    /// Maximum number of [`Subscription`](crate::subscription::Subscription)s (ie/ streams or
+    /// channels) per WebSocket connection.
+    pub max_subscriptions_per_connection: usize,
+    /// Maximum number of new WebSocket connections per minute.
+    pub max_connections_per_minute: u32,
+    /// Maximum number of messages (eg/ subscription requests) sent per second per connection.
+    pub max_messages_per_second: u32,
+    /// Maximum REST request weight per minute, if the exchange weights REST requests.
+    pub request_weight_per_minute: Option<u32>,
+    /// Whether these [`ConnectionLimits`] were advertised by the exchange or are the hardcoded
+    /// defaults.
+    pub source: LimitsSource,
+}
+
+/// Origin of a set of [`ConnectionLimits`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitsSource {
+    /// Advertised by the exchange via it's REST API.
+    Advertised,
+    /// Conservative hardcoded defaults.
+    Default,
+}
+
+impl ConnectionLimits {
+    /// Conservative hardcoded [`ConnectionLimits`] for the provided exchange, used whenever the
+    /// exchange does not advertise it's limits or they fail to be fetched.
+    ///
+    /// Each is at or below the limit documented by the exchange at the time of writing.
+    pub fn default_for(exchange: ExchangeId) -> Self {
+        let (subscriptions, connections, messages, weight) = match exchange {
+            // 1024 streams per connection, 300 connections per 5 minutes, 5 messages per second
+            ExchangeId::BinanceSpot | ExchangeId::BinanceUs => (200, 60, 5, Some(1200)),
+            // 200 streams per connection, 10 messages per second
+            ExchangeId::BinanceFuturesUsd => (200, 60, 10, Some(2400)),
+            // 25 channels per connection, 20 connections per minute
+            ExchangeId::Bitfinex => (25, 20, 5, None),
+            ExchangeId::Coinbase => (100, 60, 5, None),
+            ExchangeId::GateioSpot
+            | ExchangeId::GateioFuturesUsd
+            | ExchangeId::GateioFuturesBtc => (100, 60, 5, None),
+            // 150 connections per 10 minutes
+            ExchangeId::Kraken => (100, 15, 5, None),
+            // 3 connection requests per second, 480 subscription requests per hour
+            ExchangeId::Okx => (100, 60, 1, None),
+        };
+
+        Self {
+            max_subscriptions_per_connection: subscriptions,
+            max_connections_per_minute: connections,
+            max_messages_per_second: messages,
+            request_weight_per_minute: weight,
+            source: LimitsSource::Default,
+        }
+    }
+}
+
+/// [`ConnectionLimits`] fetched from each exchange, cached for the lifetime of the process.
+fn cache() -> &'static Mutex<HashMap<ExchangeId, ConnectionLimits>> {
+    static CACHE: OnceLock<Mutex<HashMap<ExchangeId, ConnectionLimits>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Fetch the [`ConnectionLimits`] advertised by the provided exchange via it's REST API (eg/
+/// Binance `exchangeInfo` rate limits).
+///
+/// Advertised limits are cached per process, so only the first call for each exchange makes a
+/// request. Falls back to the conservative [`ConnectionLimits::default_for`] the exchange if it
+/// does not advertise limits or the request fails - failures are not cached, so the next call
+/// retries.
+pub async fn fetch_limits(exchange: ExchangeId) -> ConnectionLimits {
+    fetch_limits_with(exchange, &StreamConfig::default()).await
+}
+
+/// Fetch the [`ConnectionLimits`] advertised by the provided exchange, as [`fetch_limits`], using
+/// the REST configuration (eg/ [`Proxy`](crate::subscriber::proxy::Proxy)) of the provided
+/// [`StreamConfig`].
+pub async fn fetch_limits_with(exchange: ExchangeId, config: &StreamConfig) -> ConnectionLimits {
+    if let Some(limits) = cache().lock().unwrap().get(&exchange) {
+        return *limits;
+    }
+
+    match fetch_advertised_limits(exchange, config).await {
+        Ok(Some(limits)) => {
+            debug!(%exchange, ?limits, "fetched exchange advertised ConnectionLimits");
+            cache().lock().unwrap().insert(exchange, limits);
+            limits
+        }
+        Ok(None) => ConnectionLimits::default_for(exchange),
+        Err(error) => {
+            let limits = ConnectionLimits::default_for(exchange);
+            warn!(
+                %exchange,
+                %error,
+                ?limits,
+                action = "falling back to default ConnectionLimits",
+                "failed to fetch exchange advertised ConnectionLimits"
+            );
+            limits
+        }
+    }
+}
+
+/// Fetch the [`ConnectionLimits`] advertised by the provided exchange, or `None` if it does not
+/// advertise any.
+#[cfg_attr(not(feature = "binance"), allow(unused_variables))]
+async fn fetch_advertised_limits(
+    exchange: ExchangeId,
+    config: &StreamConfig,
+) -> Result<Option<ConnectionLimits>, DataError> {
+    match exchange {
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceSpot => super::binance::limits::fetch_limits(
+            exchange,
+            super::binance::spot::HTTP_BASE_URL_BINANCE_SPOT,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceFuturesUsd => super::binance::limits::fetch_limits(
+            exchange,
+            super::binance::futures::HTTP_BASE_URL_BINANCE_FUTURES_USD,
+            config,
+        )
+        .await
+        .map(Some),
+        #[cfg(feature = "binance")]
+        ExchangeId::BinanceUs => super::binance::limits::fetch_limits(
+            exchange,
+            super::binance::us::HTTP_BASE_URL_BINANCE_US,
+            config,
+        )
+        .await
+        .map(Some),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_limits_falls_back_to_defaults() {
+        // Kraken does not advertise it's limits, so no request is made
+        let limits = fetch_limits(ExchangeId::Kraken).await;
+        assert_eq!(limits, ConnectionLimits::default_for(ExchangeId::Kraken));
+        assert_eq!(limits.source, LimitsSource::Default);
+        assert!(!cache().lock().unwrap().contains_key(&ExchangeId::Kraken));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_limits_uses_cache() {
+        let advertised = ConnectionLimits {
+            max_subscriptions_per_connection: 1024,
+            max_connections_per_minute: 60,
+            max_messages_per_second: 5,
+            request_weight_per_minute: Some(6000),
+            source: LimitsSource::Advertised,
+        };
+        cache()
+            .lock()
+            .unwrap()
+            .insert(ExchangeId::GateioSpot, advertised);
+
+        assert_eq!(fetch_limits(ExchangeId::GateioSpot).await, advertised);
+    }
+}
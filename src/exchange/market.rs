@@ -0,0 +1,69 @@
+use super::{instruments::InstrumentInfo, Connector, ExchangeId};
+use barter_integration::model::Instrument;
+use thiserror::Error;
+
+/// Reverse mapping of an exchange specific [`Connector::Market`] (eg/ "BTCUSDT") back into the
+/// Barter [`Instrument`] it identifies.
+///
+/// Useful for resolving the markets of messages that were not requested via a
+/// [`Subscription`](crate::subscription::Subscription) (eg/ wildcard channels), and therefore
+/// are not present in the [`Map`](crate::subscription::Map) built at subscription time.
+pub trait ParseMarket
+where
+    Self: Connector,
+{
+    /// Parse an exchange specific market into the [`Instrument`] it identifies.
+    ///
+    /// Returns [`ParseMarketError::Ambiguous`] if the market cannot be split into a base & quote
+    /// without knowledge of the assets listed by the exchange, in which case
+    /// [`Self::parse_market_with_catalog`] should be used.
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError>;
+
+    /// Parse an exchange specific market into the [`Instrument`] it identifies, preferring the
+    /// [`Instrument`] listed for the market in the provided exchange [`InstrumentInfo`] catalog
+    /// (eg/ fetched via [`fetch_instruments`](super::instruments::fetch_instruments)), and falling
+    /// back to [`Self::parse_market`] if it is not listed.
+    fn parse_market_with_catalog(
+        market: &str,
+        catalog: &[InstrumentInfo],
+    ) -> Result<Instrument, ParseMarketError> {
+        catalog
+            .iter()
+            .find(|info| info.exchange == Self::ID && info.market.eq_ignore_ascii_case(market))
+            .map(|info| Ok(info.instrument.clone()))
+            .unwrap_or_else(|| Self::parse_market(market))
+    }
+}
+
+/// All errors generated when parsing an exchange specific market into an [`Instrument`].
+#[derive(Clone, Eq, PartialEq, Debug, Error)]
+pub enum ParseMarketError {
+    #[error("{exchange} market {market} is not in a recognised format")]
+    Format {
+        exchange: ExchangeId,
+        market: String,
+    },
+
+    #[error("{exchange} market {market} is a {kind}, which is not a supported InstrumentKind")]
+    UnsupportedKind {
+        exchange: ExchangeId,
+        market: String,
+        kind: &'static str,
+    },
+
+    #[error("{exchange} market {market} is ambiguous without the exchange instrument catalog")]
+    Ambiguous {
+        exchange: ExchangeId,
+        market: String,
+    },
+}
+
+impl ParseMarketError {
+    /// Construct a [`ParseMarketError::Format`] for the provided exchange market.
+    pub fn format(exchange: ExchangeId, market: &str) -> Self {
+        Self::Format {
+            exchange,
+            market: market.to_owned(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use self::subscription::ExchangeSub;
 use crate::subscription::SubKind;
 use crate::{
+    streams::config::StreamConfig,
     subscriber::{validator::SubscriptionValidator, Subscriber},
     subscription::Map,
     MarketStream,
@@ -15,32 +16,77 @@ use std::{
 };
 use url::Url;
 
-/// `BinanceSpot` & `BinanceFuturesUsd` [`Connector`] and [`StreamSelector`] implementations.
+/// [`AssetAliases`](alias::AssetAliases) table of exchange specific asset codes (eg/ Kraken
+/// "XBT"), used to translate between Barter [`Instrument`]s & exchange markets.
+pub mod alias;
+
+/// [`Backfill`](backfill::Backfill) trait used to fetch recent historical market data via an
+/// exchange REST API.
+pub mod backfill;
+
+/// `BinanceSpot`, `BinanceFuturesUsd` & `BinanceUs` [`Connector`] and [`StreamSelector`]
+/// implementations.
+#[cfg(feature = "binance")]
 pub mod binance;
 
 /// `BinanceSpot` & `BinanceFuturesUsd` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "binance")]
 pub mod binance_paper;
 
-
 /// `Bitfinex` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "bitfinex")]
 pub mod bitfinex;
 
 /// `Coinbase` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "coinbase")]
 pub mod coinbase;
 
+/// [`Endpoints`](endpoint::Endpoints) overriding the WebSocket & REST base urls of an exchange,
+/// eg/ for testnets, mirrors & local mock servers.
+pub mod endpoint;
+
 /// `GateioSpot`, `GateioFuturesUsd` & `GateioFuturesBtc` [`Connector`] and [`StreamSelector`]
 /// implementations.
+#[cfg(feature = "gateio")]
 pub mod gateio;
 
+/// Normalised [`InstrumentInfo`](instruments::InstrumentInfo) catalog of the instruments listed
+/// by each exchange REST API, cached per process, and pre-flight validation of
+/// [`Subscription`](crate::subscription::Subscription) markets against it.
+pub mod instruments;
+
 /// `Kraken` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "kraken")]
 pub mod kraken;
 
+/// [`ConnectionLimits`](limits::ConnectionLimits) of each exchange server, fetched via the
+/// exchange REST API where advertised and cached per process.
+pub mod limits;
+
 /// `Kraken` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "kraken")]
 pub mod kraken_paper;
 
+/// [`ParseMarket`](market::ParseMarket) reverse mapping of exchange specific markets (eg/
+/// "BTCUSDT") back into Barter [`Instrument`]s.
+pub mod market;
+
 /// `Okx` [`Connector`] and [`StreamSelector`] implementations.
+#[cfg(feature = "okx")]
 pub mod okx;
 
+/// [`Poll`](poll::Poll) trait used to periodically fetch market data without a WebSocket channel
+/// via an exchange REST API.
+pub mod poll;
+
+/// [`AdaptivePing`](ping::AdaptivePing) configuration and the connection activity tracking used
+/// to only send application-level pings after a true idle period.
+pub mod ping;
+
+/// Normalised [`ExchangeStatus`](status::ExchangeStatus) diagnostics recognised from exchange
+/// status & maintenance messages, and the maintenance aware re-connection backoff.
+pub mod status;
+
 /// Defines the generic [`ExchangeSub`] containing a market and channel combination used by an
 /// exchange [`Connector`] to build [`WsMessage`] subscription payloads.
 pub mod subscription;
@@ -61,6 +107,15 @@ where
     Kind: SubKind,
 {
     type Stream: MarketStream<Self, Kind>;
+
+    /// Determine if the provided [`SubKind`](crate::subscription::SubKind) parameters are
+    /// unsupported by the exchange, returning the reason if so.
+    ///
+    /// eg/ A parameterised [`SubKind`](crate::subscription::SubKind) such as a rolling window
+    /// ticker may only be available for a subset of window sizes.
+    fn unsupported(_kind: &Kind) -> Option<String> {
+        None
+    }
 }
 
 /// Primary exchange abstraction. Defines how to translate Barter types into exchange specific
@@ -68,9 +123,18 @@ where
 ///
 /// ### Notes
 /// This must be implemented for a new exchange integration!
+///
+/// ### Connector Instances
+/// Every [`Subscription`](crate::subscription::Subscription) carries a [`Connector`] value, so a
+/// [`Connector`] may hold per-instance configuration (eg/ a regional endpoint, API
+/// [`Credentials`](crate::subscriber::auth::Credentials) or channel parameters). The `&self`
+/// methods ([`Self::subscribe_url`], [`Self::subscribe_requests`] & [`Self::credentials`]) are
+/// invoked on the instance of the connection's [`Subscription`](crate::subscription::Subscription)s,
+/// and default to the equivalent associated functions, so zero-config connectors only need to
+/// implement the latter.
 pub trait Connector
 where
-    Self: Clone + Default + Debug + for<'de> Deserialize<'de> + Serialize + Sized,
+    Self: Clone + Debug + for<'de> Deserialize<'de> + Serialize + Sized,
 {
     /// Unique identifier for the exchange server being connected with.
     const ID: ExchangeId;
@@ -110,8 +174,28 @@ where
     type SubResponse: Validator + Debug + DeserializeOwned;
 
     /// Base [`Url`] of the exchange server being connected with.
+    #[allow(clippy::result_large_err)]
     fn url() -> Result<Url, SocketError>;
 
+    /// [`Url`] of the exchange server that serves the provided [`Self::Channel`].
+    ///
+    /// Defaults to [`Self::url`], and only needs overriding if the exchange serves some channels
+    /// from a distinct server (eg/ [`Okx`](okx::Okx) "trades-all").
+    #[allow(clippy::result_large_err)]
+    fn channel_url(_channel: &Self::Channel) -> Result<Url, SocketError> {
+        Self::url()
+    }
+
+    /// [`Url`] of the exchange server this [`Connector`] instance connects to in order to serve
+    /// the provided [`Self::Channel`].
+    ///
+    /// Defaults to [`Self::channel_url`], and only needs overriding if the instance is configured
+    /// with a distinct server (eg/ a regional endpoint).
+    #[allow(clippy::result_large_err)]
+    fn subscribe_url(&self, channel: &Self::Channel) -> Result<Url, SocketError> {
+        Self::channel_url(channel)
+    }
+
     /// Defines [`PingInterval`] of custom application-level
     /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) pings for the exchange
     /// server being connected with.
@@ -121,10 +205,42 @@ where
         None
     }
 
+    /// Recognise an exchange specific application-level pong sent in reply to a
+    /// [`Self::ping_interval`] ping (eg/ Gateio `spot.pong`).
+    ///
+    /// Recognised pongs are recorded by the [`PongStream`](ping::PongStream) rather than being
+    /// passed on to the [`ExchangeTransformer`](crate::transformer::ExchangeTransformer), and
+    /// feed the [`MissedPongs`](ping::MissedPongs) policy of the [`PingInterval`].
+    ///
+    /// Defaults to false, meaning that the exchange has no application-level pongs.
+    fn pong(_payload: &str) -> bool {
+        false
+    }
+
     /// Defines how to translate a collection of [`ExchangeSub`]s into the [`WsMessage`]
     /// subscription payloads sent to the exchange server.
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage>;
 
+    /// Defines how this [`Connector`] instance translates a collection of [`ExchangeSub`]s into
+    /// the [`WsMessage`] subscription payloads sent to the exchange server, using the
+    /// [`StreamConfig`] of the connection.
+    ///
+    /// Defaults to [`Self::requests`], and only needs overriding if the instance is configured
+    /// with subscription parameters (eg/ an OrderBook depth), or the requests include per
+    /// connection configuration (eg/ the [`StreamConfig::ticket`]).
+    ///
+    /// Fails with a [`DataError::Auth`](crate::error::DataError::Auth) if an authenticated
+    /// channel cannot be signed with the [`StreamConfig::credentials`] (eg/ none are configured),
+    /// rather than subscribing unsigned.
+    #[allow(clippy::result_large_err)]
+    fn subscribe_requests(
+        &self,
+        exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        _: &StreamConfig,
+    ) -> Result<Vec<WsMessage>, crate::error::DataError> {
+        Ok(Self::requests(exchange_subs))
+    }
+
     /// Number of [`Subscription`](crate::subscription::Subscription) responses expected from the
     /// exchange server in responses to the requests send. Used to validate all
     /// [`Subscription`](crate::subscription::Subscription)s were accepted.
@@ -137,6 +253,84 @@ where
     fn subscription_timeout() -> Duration {
         DEFAULT_SUBSCRIPTION_TIMEOUT
     }
+
+    /// Determine if the provided [`Instrument`] is unsupported by the exchange server, returning
+    /// the reason if so.
+    ///
+    /// eg/ A futures server may only serve contracts quoted in it's settle currency.
+    ///
+    /// Defaults to `None`, meaning that every [`Instrument`] of a supported
+    /// [`InstrumentKind`](barter_integration::model::InstrumentKind) is supported.
+    fn unsupported_instrument(_instrument: &Instrument) -> Option<String> {
+        None
+    }
+
+    /// Defines if permessage-deflate compression is offered when connecting to the exchange
+    /// server, unless overridden by the [`WsConfig`](crate::subscriber::transport::WsConfig).
+    ///
+    /// Defaults to false, meaning that messages are sent uncompressed. Since every exchange uses
+    /// the default, permessage-deflate is opt-in per exchange via
+    /// [`WsConfig::with_permessage_deflate`](crate::subscriber::transport::WsConfig::with_permessage_deflate).
+    fn permessage_deflate() -> bool {
+        false
+    }
+
+    /// Defines the login [`WsMessage`] sent (eg/ signed with the API secret) before any
+    /// subscription requests when the consumer loop has
+    /// [`Credentials`](crate::subscriber::auth::Credentials) for the exchange.
+    ///
+    /// Defaults to `Ok(None)`, meaning that the exchange connection is always public and any
+    /// [`Credentials`](crate::subscriber::auth::Credentials) are ignored. Fails with a
+    /// [`DataError::Auth`](crate::error::DataError::Auth) if the
+    /// [`Credentials`](crate::subscriber::auth::Credentials) cannot be used to log in (eg/ a
+    /// missing passphrase), rather than connecting unauthenticated.
+    #[allow(clippy::result_large_err)]
+    fn login(
+        _credentials: &crate::subscriber::auth::Credentials,
+    ) -> Result<Option<WsMessage>, crate::error::DataError> {
+        Ok(None)
+    }
+
+    /// [`Credentials`](crate::subscriber::auth::Credentials) this [`Connector`] instance is
+    /// configured with, used to [`Connector::login`] in preference to the
+    /// [`StreamConfig::credentials`] of the connection.
+    ///
+    /// Defaults to `None`, meaning that the consumer loop
+    /// [`Credentials`](crate::subscriber::auth::Credentials) (if any) are used.
+    fn credentials(&self) -> Option<crate::subscriber::auth::Credentials> {
+        None
+    }
+
+    /// Recognise the exchange response to the [`Connector::login`] request, returning a
+    /// [`DataError::Auth`](crate::error::DataError::Auth) if the login failed.
+    ///
+    /// Returns `None` for any other payload. Only used if [`Connector::login`] is implemented.
+    fn login_response(_payload: &str) -> Option<Result<(), crate::error::DataError>> {
+        None
+    }
+
+    /// Recognise an exchange specific status or maintenance message (eg/ Kraken `systemStatus`)
+    /// and normalise it into an [`ExchangeStatus`](status::ExchangeStatus).
+    ///
+    /// Recognised messages are recorded by the [`StatusStream`](status::StatusStream) rather than
+    /// being passed on to the [`ExchangeTransformer`](crate::transformer::ExchangeTransformer).
+    ///
+    /// Defaults to `None`, meaning that the exchange has no status messages.
+    fn exchange_status(_payload: &str) -> Option<status::ExchangeStatus> {
+        None
+    }
+
+    /// Defines the [`Duration`] the [`consume`](crate::streams::consumer::consume) loop waits
+    /// before re-connecting whilst the exchange server is under maintenance, in place of the
+    /// exponential reconnect backoff.
+    ///
+    /// Defaults to the maintenance probe interval of the provided
+    /// [`StatusHandling`](status::StatusHandling) whilst the exchange last reported
+    /// [`SystemStatus::Maintenance`](status::SystemStatus::Maintenance), see
+    /// [`StatusHandling::maintenance_backoff`](status::StatusHandling::maintenance_backoff).
+    fn maintenance_backoff(status: &status::StatusHandling) -> Option<Duration> {
+        status.maintenance_backoff(Self::ID)
+    }
 }
 
 /// Used when an exchange has servers different
@@ -154,10 +348,56 @@ pub trait ExchangeServer: Default + Debug + Clone + Send {
 /// Defines the frequency and construction function for custom
 /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) pings - used for exchanges
 /// that require additional application-level pings.
+///
+/// By default pings are sent at a fixed `interval`. If `adaptive` is provided, the `interval`
+/// period is instead used as the idle period after the last received message before a ping is
+/// sent - see [`AdaptivePing`](ping::AdaptivePing).
+///
+/// If `missed_pongs` is provided, the connection is ended (and re-connected) once too many
+/// consecutive pings have not been answered by a pong recognised by [`Connector::pong`] - see
+/// [`MissedPongs`](ping::MissedPongs).
 #[derive(Debug)]
 pub struct PingInterval {
     pub interval: tokio::time::Interval,
     pub ping: fn() -> WsMessage,
+    pub adaptive: Option<ping::AdaptivePing>,
+    pub missed_pongs: Option<ping::MissedPongs>,
+}
+
+impl PingInterval {
+    /// Construct a new fixed [`Self`] that sends a ping every `interval`.
+    pub fn new(interval: tokio::time::Interval, ping: fn() -> WsMessage) -> Self {
+        Self {
+            interval,
+            ping,
+            adaptive: None,
+            missed_pongs: None,
+        }
+    }
+
+    /// Construct a new adaptive [`Self`] that only sends a ping once the connection has been idle
+    /// for the `interval` period, shortened if the connection is marginal.
+    pub fn adaptive(
+        interval: tokio::time::Interval,
+        ping: fn() -> WsMessage,
+        adaptive: ping::AdaptivePing,
+    ) -> Self {
+        Self {
+            interval,
+            ping,
+            adaptive: Some(adaptive),
+            missed_pongs: None,
+        }
+    }
+
+    /// End the connection once `max_missed` consecutive pings have not been answered by a pong
+    /// recognised by [`Connector::pong`].
+    pub fn with_max_missed_pongs(self, max_missed: u32) -> Self {
+        Self {
+            missed_pongs: Some(ping::MissedPongs::new(max_missed)),
+            ..self
+        }
+    }
 }
 
 /// Unique identifier an exchange server [`Connector`].
@@ -171,6 +411,7 @@ pub struct PingInterval {
 pub enum ExchangeId {
     BinanceFuturesUsd,
     BinanceSpot,
+    BinanceUs,
     Bitfinex,
     Coinbase,
     GateioFuturesBtc,
@@ -198,6 +439,7 @@ impl ExchangeId {
         match self {
             ExchangeId::BinanceSpot => "binance_spot",
             ExchangeId::BinanceFuturesUsd => "binance_futures_usd",
+            ExchangeId::BinanceUs => "binance_us",
             ExchangeId::Bitfinex => "bitfinex",
             ExchangeId::Coinbase => "coinbase",
             ExchangeId::GateioSpot => "gateio_spot",
@@ -213,7 +455,9 @@ impl ExchangeId {
     #[allow(clippy::match_like_matches_macro)]
     pub fn supports_spot(&self) -> bool {
         match self {
-            ExchangeId::BinanceFuturesUsd => false,
+            ExchangeId::BinanceFuturesUsd
+            | ExchangeId::GateioFuturesUsd
+            | ExchangeId::GateioFuturesBtc => false,
             _ => true,
         }
     }
@@ -225,8 +469,58 @@ impl ExchangeId {
     pub fn supports_futures(&self) -> bool {
         match self {
             ExchangeId::BinanceFuturesUsd => true,
+            ExchangeId::GateioFuturesUsd => true,
+            ExchangeId::GateioFuturesBtc => true,
             ExchangeId::Okx => true,
             _ => false,
         }
     }
+
+    /// Determines whether the [`Connector`] associated with this [`ExchangeId`] supports the
+    /// ingestion of market data for the provided
+    /// [`InstrumentKind`](barter_integration::model::InstrumentKind).
+    pub fn supports(&self, kind: barter_integration::model::InstrumentKind) -> bool {
+        match kind {
+            barter_integration::model::InstrumentKind::Spot => self.supports_spot(),
+            barter_integration::model::InstrumentKind::FuturePerpetual => self.supports_futures(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enabled_connector_ids() {
+        #[allow(unused_mut)]
+        let mut ids: Vec<ExchangeId> = Vec::new();
+
+        #[cfg(feature = "binance")]
+        ids.extend([
+            binance::spot::BinanceSpot::ID,
+            binance::futures::BinanceFuturesUsd::ID,
+            binance::us::BinanceUs::ID,
+        ]);
+        #[cfg(feature = "bitfinex")]
+        ids.push(bitfinex::Bitfinex::ID);
+        #[cfg(feature = "coinbase")]
+        ids.push(coinbase::Coinbase::ID);
+        #[cfg(feature = "gateio")]
+        ids.extend([
+            gateio::spot::GateioSpot::ID,
+            gateio::futures::GateioFuturesUsd::ID,
+            gateio::futures::GateioFuturesBtc::ID,
+        ]);
+        #[cfg(feature = "kraken")]
+        ids.push(kraken::Kraken::ID);
+        #[cfg(feature = "okx")]
+        ids.push(okx::Okx::ID);
+
+        // Every enabled Connector must serialise it's ExchangeId as ExchangeId::as_str
+        for id in ids {
+            let actual = serde_json::to_string(&id).unwrap();
+            assert_eq!(actual, format!("\"{}\"", id.as_str()), "{id} failed");
+        }
+    }
 }
@@ -0,0 +1,222 @@
+use super::{market::OkxMarket, Okx, HTTP_BASE_URL_OKX};
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    exchange::{
+        backfill::{candle_events, fetch_candle_pages},
+        Connector,
+    },
+    num::Num,
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+    subscription::{
+        candle::{Candle, Candles, Interval},
+        Subscription,
+    },
+    Identifier,
+};
+use barter_integration::{error::SocketError, model::InstrumentKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Maximum number of candles [`Okx`] returns from a single `history-candles` REST request.
+pub const OKX_MAX_CANDLES_LIMIT: usize = 100;
+
+/// [`Duration`] waited between paginated [`Okx`] `history-candles` requests, respecting the
+/// rate limit of 20 requests per 2 seconds.
+pub const OKX_CANDLES_PAGE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// [`Okx`] REST `history-candles` response.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-candlesticks-history>
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct OkxCandles {
+    pub code: String,
+    #[serde(default)]
+    pub msg: String,
+    #[serde(default)]
+    pub data: Vec<OkxCandleRest>,
+}
+
+/// [`Okx`] REST candle, ordered from newest to oldest in an [`OkxCandles`] response.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-candlesticks-history>
+/// ```json
+/// [
+///     "1597026383085",
+///     "3.721",
+///     "3.743",
+///     "3.677",
+///     "3.708",
+///     "8422410",
+///     "22698348.04828491",
+///     "12698348.04828491",
+///     "1"
+/// ]
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxCandleRest(
+    #[serde(deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc")]
+    pub  DateTime<Utc>,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    #[serde(deserialize_with = "barter_integration::de::de_str")] pub Num,
+    pub String,
+    pub String,
+);
+
+impl OkxCandleRest {
+    /// Normalise into a [`Candle`] of the provided [`Interval`], using the base asset volume of
+    /// the [`InstrumentKind`] (ie/ `vol` for spot, `volCcy` for perpetual swap contracts).
+    pub fn into_candle(self, interval: Interval, kind: InstrumentKind) -> Candle {
+        Candle {
+            close_time: self.0 + interval.duration() - chrono::Duration::milliseconds(1),
+            open: self.1,
+            high: self.2,
+            low: self.3,
+            close: self.4,
+            volume: match kind {
+                InstrumentKind::Spot => self.5,
+                InstrumentKind::FuturePerpetual => self.6,
+            },
+            trade_count: 0,
+            historical: true,
+        }
+    }
+}
+
+/// Determine the [`Okx`] `bar` of the provided [`Interval`], using the UTC aligned bars for
+/// intervals of 6 hours and above.
+pub fn okx_bar(interval: Interval) -> &'static str {
+    match interval {
+        Interval::M1 => "1m",
+        Interval::M3 => "3m",
+        Interval::M5 => "5m",
+        Interval::M15 => "15m",
+        Interval::M30 => "30m",
+        Interval::H1 => "1H",
+        Interval::H2 => "2H",
+        Interval::H4 => "4H",
+        Interval::H6 => "6Hutc",
+        Interval::H12 => "12Hutc",
+        Interval::D1 => "1Dutc",
+        Interval::W1 => "1Wutc",
+    }
+}
+
+/// Fetch the most recent closed [`Candle`]s for the provided [`Subscription`] from the [`Okx`]
+/// REST API `history-candles`, paginating (see [`fetch_candle_pages`]) beyond
+/// [`OKX_MAX_CANDLES_LIMIT`], using the [`StreamConfig`] of the connection.
+///
+/// Okx does not provide the trade count of a candle, so it is always 0.
+pub async fn fetch_candles(
+    subscription: &Subscription<Okx, Candles>,
+    limit: usize,
+    config: &StreamConfig,
+) -> Result<Vec<MarketEvent<Candle>>, DataError> {
+    let market: OkxMarket = subscription.id();
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_OKX);
+    let interval = subscription.kind.0;
+    let kind = subscription.instrument.kind;
+    let proxy = config.proxy.as_ref();
+
+    let candles = fetch_candle_pages(
+        limit,
+        OKX_MAX_CANDLES_LIMIT,
+        OKX_CANDLES_PAGE_INTERVAL,
+        |before, page_limit| {
+            // Okx paginates via "after", returning candles opened before the provided time
+            let mut url = format!(
+                "{base_url}/market/history-candles?instId={}&bar={}&limit={page_limit}",
+                market.0,
+                okx_bar(interval),
+            );
+            if let Some(before) = before {
+                let open_time = before - interval.duration() + chrono::Duration::milliseconds(1);
+                url.push_str(&format!("&after={}", open_time.timestamp_millis()));
+            }
+
+            async move {
+                let candles = get_json::<OkxCandles>(proxy, &url).await?;
+                if candles.code != "0" {
+                    return Err(DataError::Socket(SocketError::Exchange(format!(
+                        "Okx history-candles error code {}: {}",
+                        candles.code, candles.msg
+                    ))));
+                }
+
+                Ok(candles
+                    .data
+                    .into_iter()
+                    .map(|candle| candle.into_candle(interval, kind))
+                    .collect())
+            }
+        },
+    )
+    .await?;
+
+    Ok(candle_events(Okx::ID, &subscription.instrument, candles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_okx_candle_rest() {
+        let input = r#"
+        {
+            "code": "0",
+            "msg": "",
+            "data": [
+                [
+                    "1597026360000", "3.721", "3.743", "3.677", "3.708", "8422410",
+                    "22698348.04828491", "12698348.04828491", "1"
+                ]
+            ]
+        }
+        "#;
+
+        let candles = serde_json::from_str::<OkxCandles>(input).unwrap();
+        assert_eq!(candles.code, "0");
+
+        struct TestCase {
+            kind: InstrumentKind,
+            expected_volume: Num,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot volume is vol
+                kind: InstrumentKind::Spot,
+                expected_volume: num!(8422410.0),
+            },
+            TestCase {
+                // TC1: perpetual swap volume is volCcy
+                kind: InstrumentKind::FuturePerpetual,
+                expected_volume: num!(22698348.04828491),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = candles.data[0].clone().into_candle(Interval::M1, test.kind);
+            let expected = Candle {
+                close_time: DateTime::from_timestamp_millis(1597026419999).unwrap(),
+                open: num!(3.721),
+                high: num!(3.743),
+                low: num!(3.677),
+                close: num!(3.708),
+                volume: test.expected_volume,
+                trade_count: 0,
+                historical: true,
+            };
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+}
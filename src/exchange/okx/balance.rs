@@ -0,0 +1,210 @@
+use super::Okx;
+use crate::{
+    error::DataError,
+    event::{MarketEvent, MarketEvents, RawTimestamp, TimestampUnit},
+    exchange::{alias::exchange_assets, Connector},
+    num::Num,
+    streams::config::StreamConfig,
+    subscription::{
+        balance::{Balance, Balances},
+        Map,
+    },
+    transformer::ExchangeTransformer,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    model::{Exchange, Instrument},
+    protocol::websocket::WsMessage,
+    Transformer,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// [`Okx`] private account channel message, containing the balances of the logged in account.
+///
+/// Unlike an [`OkxMessage`](super::trade::OkxMessage), the "arg" has no "instId" since balances
+/// are per asset rather than per market, so it's normalised by the [`OkxBalancesTransformer`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#trading-account-websocket-account-channel>
+/// ```json
+/// {
+///   "arg": {
+///     "channel": "account",
+///     "uid": "44*********584"
+///   },
+///   "data": [
+///     {
+///       "uTime": "1705564223311",
+///       "totalEq": "55444.12",
+///       "details": [
+///         {
+///           "ccy": "USDT",
+///           "eq": "4734.37",
+///           "cashBal": "4734.37",
+///           "availBal": "4734.37",
+///           "frozenBal": "0",
+///           "uTime": "1705564213903"
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxAccountMessage {
+    pub data: Vec<OkxAccount>,
+}
+
+/// [`Okx`] account balances of an [`OkxAccountMessage`].
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxAccount {
+    pub details: Vec<OkxBalance>,
+}
+
+/// [`Okx`] balance of a single currency of an [`OkxAccount`].
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxBalance {
+    #[serde(rename = "ccy")]
+    pub currency: String,
+    #[serde(rename = "eq", deserialize_with = "barter_integration::de::de_str")]
+    pub total: Num,
+    #[serde(
+        rename = "availBal",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub available: Num,
+    #[serde(
+        rename = "uTime",
+        deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+}
+
+/// [`Okx`] [`Balances`] [`ExchangeTransformer`], yielding the [`Balance`] of each currency of an
+/// [`OkxAccountMessage`] for every subscribed [`Instrument`] with it as the base or quote asset.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct OkxBalancesTransformer {
+    instrument_map: Map<Instrument>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Okx, Balances> for OkxBalancesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self { instrument_map })
+    }
+}
+
+impl Transformer for OkxBalancesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, OkxAccountMessage);
+    type Output = MarketEvent<Balance>;
+    type OutputIter = MarketEvents<Balance>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        let balances = input
+            .data
+            .into_iter()
+            .flat_map(|account| account.details)
+            .collect::<Vec<_>>();
+
+        self.instrument_map
+            .0
+            .values()
+            .flat_map(|instrument| {
+                let (base, quote) = exchange_assets(Okx::ID, instrument);
+                balances.iter().filter_map(move |balance| {
+                    let asset = if balance.currency.eq_ignore_ascii_case(&base) {
+                        &instrument.base
+                    } else if balance.currency.eq_ignore_ascii_case(&quote) {
+                        &instrument.quote
+                    } else {
+                        return None;
+                    };
+
+                    Some(Ok(MarketEvent {
+                        exchange_time: balance.time,
+                        raw_exchange_time: Some(RawTimestamp::from_datetime(
+                            balance.time,
+                            TimestampUnit::Milliseconds,
+                        )),
+                        received_time,
+                        exchange: Exchange::from(Okx::ID),
+                        instrument: instrument.clone(),
+                        kind: Balance {
+                            asset: asset.clone(),
+                            total: balance.total,
+                            available: balance.available,
+                        },
+                        meta: (),
+                    }))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use barter_integration::model::{InstrumentKind, SubscriptionId, Symbol};
+
+    #[test]
+    fn test_okx_balances_transformer() {
+        let btc_usdt = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let mut transformer = OkxBalancesTransformer {
+            instrument_map: Map(
+                [(SubscriptionId::from("account|BTC-USDT"), btc_usdt.clone())]
+                    .into_iter()
+                    .collect(),
+            ),
+        };
+
+        let input = serde_json::from_str::<OkxAccountMessage>(
+            r#"{
+                "arg": {"channel":"account","uid":"44*********584"},
+                "data": [{
+                    "uTime":"1705564223311","totalEq":"55444.12",
+                    "details": [
+                        {"ccy":"USDT","eq":"4734.37","cashBal":"4734.37","availBal":"4700.5","frozenBal":"33.87","uTime":"1705564213903"},
+                        {"ccy":"ETH","eq":"2","cashBal":"2","availBal":"2","frozenBal":"0","uTime":"1705564213903"},
+                        {"ccy":"BTC","eq":"0.5","cashBal":"0.5","availBal":"0.25","frozenBal":"0.25","uTime":"1705564213903"}
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let actual = transformer
+            .transform((Utc::now(), input))
+            .into_iter()
+            .map(|event| {
+                let event = event.unwrap();
+                assert_eq!(event.instrument, btc_usdt);
+                event.kind
+            })
+            .collect::<Vec<_>>();
+
+        // ETH balance is not an asset of any subscribed Instrument
+        let expected = vec![
+            Balance {
+                asset: Symbol::from("usdt"),
+                total: num!(4734.37),
+                available: num!(4700.5),
+            },
+            Balance {
+                asset: Symbol::from("btc"),
+                total: num!(0.5),
+                available: num!(0.25),
+            },
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
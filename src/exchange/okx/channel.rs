@@ -1,6 +1,13 @@
 use super::Okx;
 use crate::{
-    subscription::{trade::PublicTrades, Subscription},
+    subscription::{
+        balance::Balances,
+        fill::Fills,
+        instrument_status::InstrumentStatuses,
+        order::OrderUpdates,
+        trade::{PublicTrades, PublicTradesAll},
+        Subscription,
+    },
     Identifier,
 };
 use serde::Serialize;
@@ -15,8 +22,51 @@ pub struct OkxChannel(pub &'static str);
 impl OkxChannel {
     /// [`Okx`] real-time trades channel.
     ///
+    /// Fills of the same taker order at the same price are aggregated into a single trade, so
+    /// not every individual trade is pushed.
+    ///
     /// See docs: <https://www.okx.com/docs-v5/en/#websocket-api-public-channel-trades-channel>
     pub const TRADES: Self = Self("trades");
+
+    /// [`Okx`] real-time all trades channel, served by the
+    /// [`BASE_URL_OKX_BUSINESS`](super::BASE_URL_OKX_BUSINESS) server.
+    ///
+    /// Every individual trade is pushed, giving complete trade flow at the cost of a higher
+    /// message rate than [`Self::TRADES`].
+    ///
+    /// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-all-trades-channel>
+    pub const TRADES_ALL: Self = Self("trades-all");
+
+    /// [`Okx`] private orders channel, served by the
+    /// [`BASE_URL_OKX_PRIVATE`](super::BASE_URL_OKX_PRIVATE) server to logged in connections.
+    ///
+    /// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-order-channel>
+    pub const ORDERS: Self = Self("orders");
+
+    /// [`Okx`] private fills channel, served by the
+    /// [`BASE_URL_OKX_PRIVATE`](super::BASE_URL_OKX_PRIVATE) server to logged in connections of
+    /// accounts VIP6 and above.
+    ///
+    /// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-fills-channel>
+    pub const FILLS: Self = Self("fills");
+
+    /// [`Okx`] private account (balances) channel, served by the
+    /// [`BASE_URL_OKX_PRIVATE`](super::BASE_URL_OKX_PRIVATE) server to logged in connections.
+    ///
+    /// See docs: <https://www.okx.com/docs-v5/en/#trading-account-websocket-account-channel>
+    pub const ACCOUNT: Self = Self("account");
+
+    /// [`Okx`] public instruments channel, pushing every instrument of an "instType" on
+    /// subscription, followed by each instrument whose state changes.
+    ///
+    /// See docs: <https://www.okx.com/docs-v5/en/#public-data-websocket-instruments-channel>
+    pub const INSTRUMENTS: Self = Self("instruments");
+
+    /// Determine if [`Self`] is a private channel that requires a logged in connection to the
+    /// [`BASE_URL_OKX_PRIVATE`](super::BASE_URL_OKX_PRIVATE) server.
+    pub fn is_private(&self) -> bool {
+        matches!(*self, Self::ORDERS | Self::FILLS | Self::ACCOUNT)
+    }
 }
 
 impl Identifier<OkxChannel> for Subscription<Okx, PublicTrades> {
@@ -25,6 +75,36 @@ impl Identifier<OkxChannel> for Subscription<Okx, PublicTrades> {
     }
 }
 
+impl Identifier<OkxChannel> for Subscription<Okx, PublicTradesAll> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::TRADES_ALL
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, OrderUpdates> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::ORDERS
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, Fills> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::FILLS
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, Balances> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::ACCOUNT
+    }
+}
+
+impl Identifier<OkxChannel> for Subscription<Okx, InstrumentStatuses> {
+    fn id(&self) -> OkxChannel {
+        OkxChannel::INSTRUMENTS
+    }
+}
+
 impl AsRef<str> for OkxChannel {
     fn as_ref(&self) -> &str {
         self.0
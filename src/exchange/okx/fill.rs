@@ -0,0 +1,204 @@
+use super::trade::OkxMessage;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::ExchangeId,
+    num::Num,
+    subscription::fill::{Fill, Liquidity},
+};
+use barter_integration::model::{Exchange, Instrument, Side};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Terse type alias for an [`Okx`](super::Okx) private fills WebSocket message.
+pub type OkxFills = OkxMessage<OkxFill>;
+
+/// [`Okx`](super::Okx) private fills channel trade of an order of the logged in account.
+///
+/// Note:
+/// [`Okx`](super::Okx) only serves the fills channel to accounts VIP6 and above.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-fills-channel>
+/// #### FuturePerpetual Taker Fill
+/// ```json
+/// {
+///   "arg": {
+///     "channel": "fills",
+///     "instId": "BTC-USDT-SWAP",
+///     "uid": "614488474791111"
+///   },
+///   "data": [
+///     {
+///       "instId": "BTC-USDT-SWAP",
+///       "fillSz": "100",
+///       "fillPx": "70000",
+///       "side": "buy",
+///       "ts": "1705449605015",
+///       "ordId": "680800019749904384",
+///       "tradeId": "12345",
+///       "execType": "T",
+///       "count": "10"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxFill {
+    #[serde(rename = "tradeId")]
+    pub trade_id: String,
+    #[serde(rename = "ordId")]
+    pub order_id: String,
+    #[serde(rename = "fillPx", deserialize_with = "barter_integration::de::de_str")]
+    pub price: Num,
+    #[serde(rename = "fillSz", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    pub side: Side,
+    #[serde(rename = "execType")]
+    pub liquidity: OkxLiquidity,
+    #[serde(
+        rename = "ts",
+        deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+}
+
+/// [`OkxFill`] "execType" liquidity flag.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum OkxLiquidity {
+    #[serde(rename = "M")]
+    Maker,
+    #[serde(rename = "T")]
+    Taker,
+}
+
+impl From<OkxLiquidity> for Liquidity {
+    fn from(liquidity: OkxLiquidity) -> Self {
+        match liquidity {
+            OkxLiquidity::Maker => Liquidity::Maker,
+            OkxLiquidity::Taker => Liquidity::Taker,
+        }
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, OkxFills)> for MarketIter<Fill> {
+    fn from(
+        (exchange_id, instrument, received_time, fills): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            OkxFills,
+        ),
+    ) -> Self {
+        fills
+            .data
+            .into_iter()
+            .map(|fill| {
+                Ok(MarketEvent {
+                    exchange_time: fill.time,
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        fill.time,
+                        TimestampUnit::Milliseconds,
+                    )),
+                    received_time,
+                    exchange: Exchange::from(exchange_id),
+                    instrument: instrument.clone(),
+                    kind: Fill {
+                        trade_id: fill.trade_id,
+                        order_id: fill.order_id,
+                        side: fill.side,
+                        price: fill.price,
+                        amount: fill.amount,
+                        liquidity: Liquidity::from(fill.liquidity),
+                        // Fees are only included in the orders channel
+                        fee: None,
+                        fee_asset: None,
+                    },
+                    meta: (),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use barter_integration::model::{InstrumentKind, SubscriptionId};
+
+    #[test]
+    fn test_okx_fills_into_fills() {
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<Fill>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: taker buy fill
+                input: r#"{
+                    "arg": {"channel":"fills","instId":"BTC-USDT-SWAP","uid":"614488474791111"},
+                    "data": [{
+                        "instId":"BTC-USDT-SWAP","fillSz":"100","fillPx":"70000","side":"buy",
+                        "ts":"1705449605015","ordId":"680800019749904384","tradeId":"12345",
+                        "execType":"T","count":"10"
+                    }]
+                }"#,
+                expected: vec![Fill {
+                    trade_id: "12345".to_string(),
+                    order_id: "680800019749904384".to_string(),
+                    side: Side::Buy,
+                    price: num!(70000.0),
+                    amount: num!(100.0),
+                    liquidity: Liquidity::Taker,
+                    fee: None,
+                    fee_asset: None,
+                }],
+            },
+            TestCase {
+                // TC1: maker sell fill
+                input: r#"{
+                    "arg": {"channel":"fills","instId":"BTC-USDT-SWAP","uid":"614488474791111"},
+                    "data": [{
+                        "instId":"BTC-USDT-SWAP","fillSz":"3","fillPx":"70001.5","side":"sell",
+                        "ts":"1705449605016","ordId":"680800019749904385","tradeId":"12346",
+                        "execType":"M","count":"1"
+                    }]
+                }"#,
+                expected: vec![Fill {
+                    trade_id: "12346".to_string(),
+                    order_id: "680800019749904385".to_string(),
+                    side: Side::Sell,
+                    price: num!(70001.5),
+                    amount: num!(3.0),
+                    liquidity: Liquidity::Maker,
+                    fee: None,
+                    fee_asset: None,
+                }],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let fills = serde_json::from_str::<OkxFills>(test.input).unwrap();
+            assert_eq!(
+                fills.subscription_id,
+                SubscriptionId::from("fills|BTC-USDT-SWAP"),
+                "TC{} failed",
+                index
+            );
+
+            let actual = MarketIter::<Fill>::from((
+                ExchangeId::Okx,
+                Instrument::from(("btc", "usdt", InstrumentKind::FuturePerpetual)),
+                Utc::now(),
+                fills,
+            ))
+            .0
+            .into_iter()
+            .map(|event| event.unwrap().kind)
+            .collect::<Vec<_>>();
+
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::{channel::OkxChannel, instruments::OkxInstrument, market::OkxMarket, Okx};
+use crate::{
+    error::DataError,
+    event::{MarketEvent, MarketEvents},
+    exchange::{subscription::ExchangeSub, Connector},
+    streams::config::StreamConfig,
+    subscription::{
+        instrument_status::{InstrumentStatus, InstrumentStatuses, TradingStatus},
+        Map,
+    },
+    transformer::ExchangeTransformer,
+    Identifier,
+};
+use async_trait::async_trait;
+use barter_integration::{
+    model::{Exchange, Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+    Transformer,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// [`Okx`] public instruments channel message, containing every instrument of the subscribed
+/// "instType" on subscription, and each instrument whose state changed thereafter.
+///
+/// Unlike an [`OkxMessage`](super::trade::OkxMessage), the "arg" has no "instId" since
+/// instruments are subscribed per "instType", so it's normalised by the
+/// [`OkxInstrumentStatusesTransformer`].
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-websocket-instruments-channel>
+/// ```json
+/// {
+///   "arg": {
+///     "channel": "instruments",
+///     "instType": "SPOT"
+///   },
+///   "data": [
+///     {
+///       "instType": "SPOT",
+///       "instId": "BTC-USDT",
+///       "tickSz": "0.1",
+///       "lotSz": "0.00000001",
+///       "state": "suspend"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct OkxInstrumentsMessage {
+    pub data: Vec<OkxInstrument>,
+}
+
+impl From<&OkxInstrument> for InstrumentStatus {
+    fn from(instrument: &OkxInstrument) -> Self {
+        let status = match instrument.state.as_str() {
+            "live" => TradingStatus::Trading,
+            "preopen" => TradingStatus::PreTrading,
+            "suspend" | "test" => TradingStatus::Halted,
+            "expired" => TradingStatus::Delisted,
+            _ => TradingStatus::Unknown,
+        };
+
+        Self {
+            status,
+            exchange_status: instrument.state.clone(),
+        }
+    }
+}
+
+/// [`Okx`] [`InstrumentStatuses`] [`ExchangeTransformer`], yielding the [`InstrumentStatus`] of
+/// each subscribed [`Instrument`] of an [`OkxInstrumentsMessage`] whenever it changes.
+///
+/// Every instrument of the "instType" is pushed on subscription, so instruments that are not
+/// subscribed to are ignored.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct OkxInstrumentStatusesTransformer {
+    instrument_map: Map<Instrument>,
+    statuses: HashMap<SubscriptionId, InstrumentStatus>,
+}
+
+#[async_trait]
+impl ExchangeTransformer<Okx, InstrumentStatuses> for OkxInstrumentStatusesTransformer {
+    async fn new(
+        _: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+        _: &StreamConfig,
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            instrument_map,
+            statuses: HashMap::new(),
+        })
+    }
+}
+
+impl Transformer for OkxInstrumentStatusesTransformer {
+    type Error = DataError;
+    type Input = (DateTime<Utc>, OkxInstrumentsMessage);
+    type Output = MarketEvent<InstrumentStatus>;
+    type OutputIter = MarketEvents<InstrumentStatus>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        input
+            .data
+            .iter()
+            .filter_map(|okx_instrument| {
+                let subscription_id = ExchangeSub::from((
+                    OkxChannel::INSTRUMENTS,
+                    OkxMarket(okx_instrument.inst_id.clone()),
+                ))
+                .id();
+                let instrument = self.instrument_map.0.get(&subscription_id)?;
+
+                // Only yield the InstrumentStatus if it changed
+                let status = InstrumentStatus::from(okx_instrument);
+                if self.statuses.get(&subscription_id) == Some(&status) {
+                    return None;
+                }
+                self.statuses.insert(subscription_id, status.clone());
+
+                Some(Ok(MarketEvent {
+                    exchange_time: received_time,
+                    raw_exchange_time: None,
+                    received_time,
+                    exchange: Exchange::from(Okx::ID),
+                    instrument: instrument.clone(),
+                    kind: status,
+                    meta: (),
+                }))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::model::InstrumentKind;
+
+    #[test]
+    fn test_okx_instrument_statuses_transformer() {
+        let btc_usdt = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let mut transformer = OkxInstrumentStatusesTransformer {
+            instrument_map: Map([(
+                SubscriptionId::from("instruments|BTC-USDT"),
+                btc_usdt.clone(),
+            )]
+            .into_iter()
+            .collect()),
+            statuses: HashMap::new(),
+        };
+
+        let message = |state: &str| {
+            serde_json::from_str::<OkxInstrumentsMessage>(&format!(
+                r#"{{
+                    "arg": {{"channel":"instruments","instType":"SPOT"}},
+                    "data": [
+                        {{"instType":"SPOT","instId":"ETH-USDT","tickSz":"0.01","lotSz":"0.000001","state":"live"}},
+                        {{"instType":"SPOT","instId":"BTC-USDT","tickSz":"0.1","lotSz":"0.00000001","state":"{state}"}}
+                    ]
+                }}"#
+            ))
+            .unwrap()
+        };
+
+        let mut transform = |state: &str| {
+            transformer
+                .transform((Utc::now(), message(state)))
+                .into_iter()
+                .map(|event| {
+                    let event = event.unwrap();
+                    assert_eq!(event.instrument, btc_usdt);
+                    event.kind.status
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // ETH-USDT is not subscribed, & unchanged statuses are not yielded
+        assert_eq!(transform("live"), vec![TradingStatus::Trading]);
+        assert_eq!(transform("live"), vec![]);
+        assert_eq!(transform("suspend"), vec![TradingStatus::Halted]);
+        assert_eq!(transform("live"), vec![TradingStatus::Trading]);
+    }
+}
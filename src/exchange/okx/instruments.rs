@@ -0,0 +1,245 @@
+use super::HTTP_BASE_URL_OKX;
+use crate::{
+    error::DataError,
+    exchange::{
+        instruments::{parse_size, InstrumentInfo, MarketStatus},
+        ExchangeId,
+    },
+    streams::config::StreamConfig,
+    subscriber::proxy::get_json,
+};
+use barter_integration::{
+    error::SocketError,
+    model::{Instrument, InstrumentKind},
+};
+use serde::{Deserialize, Serialize};
+
+/// [`Okx`](super::Okx) instrument types supported by the [`Okx`](super::Okx)
+/// [`Connector`](crate::exchange::Connector), ie/ spot & perpetual swap instruments.
+pub const OKX_INSTRUMENT_TYPES: [&str; 2] = ["SPOT", "SWAP"];
+
+/// [`Okx`](super::Okx) REST public `instruments` response.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-instruments>
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct OkxInstruments {
+    pub code: String,
+    #[serde(default)]
+    pub msg: String,
+    #[serde(default)]
+    pub data: Vec<OkxInstrument>,
+}
+
+/// [`Okx`](super::Okx) REST public `instruments` instrument.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-instruments>
+/// ```json
+/// {
+///     "instType": "SWAP",
+///     "instId": "BTC-USDT-SWAP",
+///     "ctVal": "0.01",
+///     "tickSz": "0.1",
+///     "lotSz": "1",
+///     "state": "live"
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxInstrument {
+    pub inst_type: String,
+    pub inst_id: String,
+    #[serde(default)]
+    pub ct_val: String,
+    pub tick_sz: String,
+    pub lot_sz: String,
+    pub state: String,
+}
+
+impl OkxInstrument {
+    /// Normalise into an [`InstrumentInfo`], or `None` if it's not a spot or perpetual swap
+    /// instrument with a "{BASE}-{QUOTE}" prefixed `instId`.
+    pub fn into_instrument_info(self) -> Option<InstrumentInfo> {
+        let kind = match self.inst_type.as_str() {
+            "SPOT" => InstrumentKind::Spot,
+            "SWAP" => InstrumentKind::FuturePerpetual,
+            _ => return None,
+        };
+
+        let mut currencies = self.inst_id.split('-');
+        let instrument = Instrument::from((currencies.next()?, currencies.next()?, kind));
+
+        let status = match self.state.as_str() {
+            "live" => MarketStatus::Trading,
+            _ => MarketStatus::Halted(self.state),
+        };
+
+        Some(InstrumentInfo {
+            exchange: ExchangeId::Okx,
+            market: self.inst_id.to_uppercase(),
+            instrument,
+            tick_size: parse_size(&self.tick_sz),
+            lot_size: parse_size(&self.lot_sz),
+            contract_size: parse_size(&self.ct_val),
+            status,
+        })
+    }
+}
+
+impl TryFrom<OkxInstruments> for Vec<InstrumentInfo> {
+    type Error = DataError;
+
+    fn try_from(instruments: OkxInstruments) -> Result<Self, Self::Error> {
+        if instruments.code != "0" {
+            return Err(DataError::Socket(SocketError::Exchange(format!(
+                "Okx instruments error code {}: {}",
+                instruments.code, instruments.msg
+            ))));
+        }
+
+        Ok(instruments
+            .data
+            .into_iter()
+            .filter_map(OkxInstrument::into_instrument_info)
+            .collect())
+    }
+}
+
+/// Fetch every [`InstrumentInfo`] of the [`OKX_INSTRUMENT_TYPES`] listed by the
+/// [`Okx`](super::Okx) REST API public `instruments` endpoint, using the provided
+/// [`StreamConfig`].
+pub async fn fetch_instruments(config: &StreamConfig) -> Result<Vec<InstrumentInfo>, DataError> {
+    let base_url = config.endpoints.rest_base_url(HTTP_BASE_URL_OKX);
+
+    let mut instruments = Vec::new();
+    for instrument_type in OKX_INSTRUMENT_TYPES {
+        let listed = get_json::<OkxInstruments>(
+            config.proxy.as_ref(),
+            &format!("{base_url}/public/instruments?instType={instrument_type}"),
+        )
+        .await
+        .and_then(Vec::<InstrumentInfo>::try_from)?;
+        instruments.extend(listed);
+    }
+
+    Ok(instruments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_okx_instrument_info() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<Vec<InstrumentInfo>, DataError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: listed spot instruments
+                input: r#"
+                {
+                    "code": "0",
+                    "msg": "",
+                    "data": [
+                        {
+                            "instType": "SPOT", "instId": "BTC-USDT", "uly": "", "baseCcy": "BTC",
+                            "quoteCcy": "USDT", "settleCcy": "", "ctVal": "", "ctMult": "",
+                            "ctValCcy": "", "tickSz": "0.1", "lotSz": "0.00000001",
+                            "minSz": "0.00001", "state": "live"
+                        }
+                    ]
+                }
+                "#,
+                expected: Ok(vec![InstrumentInfo {
+                    exchange: ExchangeId::Okx,
+                    market: "BTC-USDT".to_string(),
+                    instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                    tick_size: parse_size("0.1"),
+                    lot_size: parse_size("0.00000001"),
+                    contract_size: None,
+                    status: MarketStatus::Trading,
+                }]),
+            },
+            TestCase {
+                // TC1: listed perpetual swap instruments, linear & inverse
+                input: r#"
+                {
+                    "code": "0",
+                    "msg": "",
+                    "data": [
+                        {
+                            "instType": "SWAP", "instId": "BTC-USDT-SWAP", "uly": "BTC-USDT",
+                            "baseCcy": "", "quoteCcy": "", "settleCcy": "USDT", "ctVal": "0.01",
+                            "ctValCcy": "BTC", "tickSz": "0.1", "lotSz": "1", "state": "live"
+                        },
+                        {
+                            "instType": "SWAP", "instId": "BTC-USD-SWAP", "uly": "BTC-USD",
+                            "settleCcy": "BTC", "ctVal": "100", "ctValCcy": "USD",
+                            "tickSz": "0.1", "lotSz": "1", "state": "suspend"
+                        }
+                    ]
+                }
+                "#,
+                expected: Ok(vec![
+                    InstrumentInfo {
+                        exchange: ExchangeId::Okx,
+                        market: "BTC-USDT-SWAP".to_string(),
+                        instrument: Instrument::from((
+                            "btc",
+                            "usdt",
+                            InstrumentKind::FuturePerpetual,
+                        )),
+                        tick_size: parse_size("0.1"),
+                        lot_size: parse_size("1"),
+                        contract_size: parse_size("0.01"),
+                        status: MarketStatus::Trading,
+                    },
+                    InstrumentInfo {
+                        exchange: ExchangeId::Okx,
+                        market: "BTC-USD-SWAP".to_string(),
+                        instrument: Instrument::from((
+                            "btc",
+                            "usd",
+                            InstrumentKind::FuturePerpetual,
+                        )),
+                        tick_size: parse_size("0.1"),
+                        lot_size: parse_size("1"),
+                        contract_size: parse_size("100"),
+                        status: MarketStatus::Halted("suspend".to_string()),
+                    },
+                ]),
+            },
+            TestCase {
+                // TC2: error response
+                input: r#"{"code": "51000", "msg": "Parameter instType error", "data": []}"#,
+                expected: Err(DataError::Socket(SocketError::Exchange(
+                    "Okx instruments error code 51000: Parameter instType error".to_string(),
+                ))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Vec::<InstrumentInfo>::try_from(
+                serde_json::from_str::<OkxInstruments>(test.input).unwrap(),
+            );
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "TC{} failed", index),
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+}
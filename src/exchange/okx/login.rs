@@ -0,0 +1,227 @@
+use crate::{error::DataError, exchange::ExchangeId, subscriber::auth::Credentials};
+use barter_integration::protocol::websocket::WsMessage;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+
+/// [`Okx`](super::Okx) request path signed alongside the timestamp of an [`Okx`](super::Okx)
+/// WebSocket login request.
+pub const OKX_LOGIN_PATH: &str = "/users/self/verify";
+
+/// [`Okx`](super::Okx) public channels that are only served to logged in connections.
+///
+/// - `books-l2-tbt`: tick-by-tick 400 level order book, accounts VIP6 and above.
+/// - `books50-l2-tbt`: tick-by-tick 50 level order book, accounts VIP5 and above.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+pub const OKX_LOGIN_CHANNELS: &[&str] = &["books-l2-tbt", "books50-l2-tbt"];
+
+/// Generate the [`Okx`](super::Okx) WebSocket `login` request using the provided
+/// [`Credentials`] and the current Unix timestamp.
+///
+/// Logging in is optional for public market data, but ties the connection to the account so
+/// it's served using the account (VIP) tier, unlocking higher limits and the
+/// [`OKX_LOGIN_CHANNELS`]. Fails with a [`DataError::Auth`] if the [`Credentials`] have no
+/// passphrase, since [`Okx`](super::Okx) requires one.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-login>
+#[allow(clippy::result_large_err)]
+pub fn login_request(credentials: &Credentials) -> Result<WsMessage, DataError> {
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    login_request_at(credentials, &timestamp)
+}
+
+/// Generate the [`Okx`](super::Okx) WebSocket `login` request using the provided
+/// [`Credentials`] & Unix timestamp (in seconds).
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "op": "login",
+///     "args": [
+///         {
+///             "apiKey": "985d5b66-57ce-40fb-b714-afc0b9787083",
+///             "passphrase": "123456",
+///             "timestamp": "1538054050",
+///             "sign": "+LdIr8lkkvhr5hoA3g9TMC0+uQJ849ftAcocA/ouu4M="
+///         }
+///     ]
+/// }
+/// ```
+#[allow(clippy::result_large_err)]
+pub fn login_request_at(
+    credentials: &Credentials,
+    timestamp: &str,
+) -> Result<WsMessage, DataError> {
+    let passphrase = credentials
+        .passphrase
+        .as_deref()
+        .ok_or_else(|| DataError::Auth {
+            exchange: ExchangeId::Okx,
+            reason: "Credentials have no passphrase, which is required to log in".to_string(),
+        })?;
+
+    Ok(WsMessage::Text(
+        json!({
+            "op": "login",
+            "args": [{
+                "apiKey": credentials.api_key,
+                "passphrase": passphrase,
+                "timestamp": timestamp,
+                "sign": sign(&credentials.secret, timestamp),
+            }]
+        })
+        .to_string(),
+    ))
+}
+
+/// Sign the provided Unix timestamp (in seconds) using the provided API secret, generating the
+/// [`Okx`](super::Okx) login `sign` (Base64(HMAC_SHA256(timestamp + "GET" + path))).
+pub fn sign(secret: &str, timestamp: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(b"GET");
+    mac.update(OKX_LOGIN_PATH.as_bytes());
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// [`Okx`](super::Okx) WebSocket `login` response.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-login>
+/// #### Login Success
+/// ```json
+/// {"event": "login", "code": "0", "msg": "", "connId": "a4d3ae55"}
+/// ```
+/// #### Login Failure
+/// ```json
+/// {"event": "error", "code": "60009", "msg": "Login failed.", "connId": "a4d3ae55"}
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum OkxLoginResponse {
+    Login { code: String },
+    Error { code: String, msg: String },
+}
+
+impl OkxLoginResponse {
+    /// Determine if the login succeeded, returning a [`DataError::Auth`] if it failed.
+    #[allow(clippy::result_large_err)]
+    pub fn validate(self) -> Result<(), DataError> {
+        let reason = match self {
+            Self::Login { code } if code == "0" => return Ok(()),
+            Self::Login { code } => format!("received login failure response code: {code}"),
+            Self::Error { code, msg } => {
+                format!("received login failure response code {code}: {msg}")
+            }
+        };
+
+        Err(DataError::Auth {
+            exchange: ExchangeId::Okx,
+            reason,
+        })
+    }
+}
+
+/// Recognise an [`Okx`](super::Okx) WebSocket `login` response, see
+/// [`Connector::login_response`](crate::exchange::Connector::login_response).
+pub fn login_response(payload: &str) -> Option<Result<(), DataError>> {
+    serde_json::from_str::<OkxLoginResponse>(payload)
+        .ok()
+        .map(OkxLoginResponse::validate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_okx_login_request() {
+        let credentials = Credentials::new(
+            "985d5b66-57ce-40fb-b714-afc0b9787083",
+            "22582BD0CFF14C41EDBF1AB98506286D",
+        );
+
+        // Okx requires a passphrase
+        match login_request_at(&credentials, "1538054050") {
+            Err(DataError::Auth { exchange, reason }) => {
+                assert_eq!(exchange, ExchangeId::Okx);
+                assert_eq!(
+                    reason,
+                    "Credentials have no passphrase, which is required to log in"
+                );
+            }
+            actual => panic!("expected DataError::Auth, but found: {actual:?}"),
+        }
+
+        let actual = login_request_at(&credentials.with_passphrase("123456"), "1538054050");
+        let expected = WsMessage::Text(
+            json!({
+                "op": "login",
+                "args": [{
+                    "apiKey": "985d5b66-57ce-40fb-b714-afc0b9787083",
+                    "passphrase": "123456",
+                    "timestamp": "1538054050",
+                    "sign": "+LdIr8lkkvhr5hoA3g9TMC0+uQJ849ftAcocA/ouu4M=",
+                }]
+            })
+            .to_string(),
+        );
+        assert_eq!(actual.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_okx_login_response() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<Result<(), DataError>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: login success
+                input: r#"{"event":"login","code":"0","msg":"","connId":"a4d3ae55"}"#,
+                expected: Some(Ok(())),
+            },
+            TestCase {
+                // TC1: login failure
+                input: r#"{"event":"error","code":"60009","msg":"Login failed.","connId":"a4d3ae55"}"#,
+                expected: Some(Err(DataError::Auth {
+                    exchange: ExchangeId::Okx,
+                    reason: "received login failure response code 60009: Login failed.".to_string(),
+                })),
+            },
+            TestCase {
+                // TC2: subscription response is not a login response
+                input: r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = login_response(test.input);
+            match (actual, test.expected) {
+                (None, None) | (Some(Ok(())), Some(Ok(()))) => {
+                    // Test passed
+                }
+                (
+                    Some(Err(DataError::Auth { exchange, reason })),
+                    Some(Err(DataError::Auth {
+                        exchange: expected_exchange,
+                        reason: expected_reason,
+                    })),
+                ) => {
+                    assert_eq!(exchange, expected_exchange, "TC{} failed", index);
+                    assert_eq!(reason, expected_reason, "TC{} failed", index);
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,14 @@
 use super::Okx;
-use crate::{subscription::Subscription, Identifier};
-use barter_integration::model::InstrumentKind;
+use crate::{
+    exchange::{
+        alias::{canonical_instrument, exchange_assets},
+        market::{ParseMarket, ParseMarketError},
+        Connector,
+    },
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::model::{Instrument, InstrumentKind};
 use serde::{Deserialize, Serialize};
 
 /// Type that defines how to translate a Barter [`Subscription`] into a
@@ -12,13 +20,10 @@ pub struct OkxMarket(pub String);
 
 impl<Kind> Identifier<OkxMarket> for Subscription<Okx, Kind> {
     fn id(&self) -> OkxMarket {
+        let (base, quote) = exchange_assets(Okx::ID, &self.instrument);
         OkxMarket(match self.instrument.kind {
-            InstrumentKind::Spot => {
-                format!("{}-{}", self.instrument.base, self.instrument.quote).to_uppercase()
-            }
-            InstrumentKind::FuturePerpetual => {
-                format!("{}-{}-SWAP", self.instrument.base, self.instrument.quote).to_uppercase()
-            }
+            InstrumentKind::Spot => format!("{base}-{quote}").to_uppercase(),
+            InstrumentKind::FuturePerpetual => format!("{base}-{quote}-SWAP").to_uppercase(),
         })
     }
 }
@@ -28,3 +33,96 @@ impl AsRef<str> for OkxMarket {
         &self.0
     }
 }
+
+impl ParseMarket for Okx {
+    /// Okx markets are formatted as "BASE-QUOTE" for spot (eg/ "BTC-USDT"), and
+    /// "BASE-QUOTE-SWAP" for perpetuals (eg/ "BTC-USDT-SWAP"). Delivery futures (eg/
+    /// "BTC-USD-240628") & options are not supported.
+    fn parse_market(market: &str) -> Result<Instrument, ParseMarketError> {
+        let parts = market.split('-').collect::<Vec<_>>();
+        if parts.len() < 2 || parts.iter().any(|part| part.is_empty()) {
+            return Err(ParseMarketError::format(Self::ID, market));
+        }
+
+        let kind = match &parts[2..] {
+            [] => InstrumentKind::Spot,
+            [swap] if swap.eq_ignore_ascii_case("SWAP") => InstrumentKind::FuturePerpetual,
+            [_expiry] => {
+                return Err(ParseMarketError::UnsupportedKind {
+                    exchange: Self::ID,
+                    market: market.to_owned(),
+                    kind: "delivery future",
+                })
+            }
+            [_expiry, _strike, _option] => {
+                return Err(ParseMarketError::UnsupportedKind {
+                    exchange: Self::ID,
+                    market: market.to_owned(),
+                    kind: "option",
+                })
+            }
+            _ => return Err(ParseMarketError::format(Self::ID, market)),
+        };
+
+        Ok(canonical_instrument(Self::ID, parts[0], parts[1], kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::ExchangeId;
+
+    #[test]
+    fn test_parse_market() {
+        struct TestCase {
+            market: &'static str,
+            expected: Result<Instrument, ParseMarketError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: spot
+                market: "BTC-USDT",
+                expected: Ok(Instrument::from(("btc", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: perpetual
+                market: "BTC-USDT-SWAP",
+                expected: Ok(Instrument::from((
+                    "btc",
+                    "usdt",
+                    InstrumentKind::FuturePerpetual,
+                ))),
+            },
+            TestCase {
+                // TC2: delivery future
+                market: "BTC-USD-240628",
+                expected: Err(ParseMarketError::UnsupportedKind {
+                    exchange: ExchangeId::Okx,
+                    market: "BTC-USD-240628".to_owned(),
+                    kind: "delivery future",
+                }),
+            },
+            TestCase {
+                // TC3: option
+                market: "BTC-USD-240628-60000-C",
+                expected: Err(ParseMarketError::UnsupportedKind {
+                    exchange: ExchangeId::Okx,
+                    market: "BTC-USD-240628-60000-C".to_owned(),
+                    kind: "option",
+                }),
+            },
+            TestCase {
+                // TC4: invalid format
+                market: "BTCUSDT",
+                expected: Err(ParseMarketError::format(ExchangeId::Okx, "BTCUSDT")),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Okx::parse_market(test.market);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
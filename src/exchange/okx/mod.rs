@@ -1,26 +1,71 @@
 use self::{
-    channel::OkxChannel, market::OkxMarket, subscription::OkxSubResponse, trade::OkxTrades,
+    balance::OkxBalancesTransformer, channel::OkxChannel, fill::OkxFills,
+    instrument_status::OkxInstrumentStatusesTransformer, market::OkxMarket, order::OkxOrders,
+    subscription::OkxSubResponse, trade::OkxTrades,
 };
 use crate::{
-    exchange::{Connector, ExchangeId, ExchangeSub, StreamSelector},
-    subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::trade::PublicTrades,
+    error::DataError,
+    exchange::{
+        ping::AdaptivePing, status::ExchangeStatus, Connector, ExchangeId, ExchangeSub,
+        PingInterval, StreamSelector,
+    },
+    streams::config::StreamConfig,
+    subscriber::{
+        auth::Credentials, ticket::ConnectionTicket, validator::WebSocketSubValidator,
+        WebSocketSubscriber,
+    },
+    subscription::{
+        balance::Balances,
+        fill::Fills,
+        instrument_status::InstrumentStatuses,
+        order::OrderUpdates,
+        trade::{PublicTrades, PublicTradesAll},
+    },
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream,
 };
 use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
 use barter_macro::{DeExchange, SerExchange};
 use serde_json::json;
+use std::time::Duration;
+use tracing::debug;
 use url::Url;
 
+/// Private account [`Balances`] types & the [`OkxBalancesTransformer`] for [`Okx`].
+pub mod balance;
+
+/// REST `history-candles` fetcher of the trailing closed [`Candle`](crate::subscription::candle::Candle)s
+/// of an [`Okx`] market.
+pub mod backfill;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
 pub mod channel;
 
+/// Private account [`Fills`] types for [`Okx`].
+pub mod fill;
+
+/// [`InstrumentStatuses`] types & the [`OkxInstrumentStatusesTransformer`] for [`Okx`].
+pub mod instrument_status;
+
+/// [`InstrumentInfo`](crate::exchange::instruments::InstrumentInfo)s listed by the [`Okx`] REST
+/// API public `instruments`.
+pub mod instruments;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
 
+/// Optional [`Okx`] connection login, granting logged in connections the account tier limits
+/// and the [`OKX_LOGIN_CHANNELS`](login::OKX_LOGIN_CHANNELS).
+pub mod login;
+
+/// Private account [`OrderUpdates`] types for [`Okx`].
+pub mod order;
+
+/// [`OkxStatus`](status::OkxStatus) notice & maintenance types for [`Okx`].
+pub mod status;
+
 /// [`Subscription`](crate::subscription::Subscription) response type and response
 /// [`Validator`](barter_integration::Validator) for [`Okx`].
 pub mod subscription;
@@ -33,6 +78,41 @@ pub mod trade;
 /// See docs: <https://www.okx.com/docs-v5/en/#overview-api-resources-and-support>
 pub const BASE_URL_OKX: &str = "wss://wsaws.okx.com:8443/ws/v5/public";
 
+/// [`Okx`] business server base url, serving the [`OkxChannel::TRADES_ALL`] channel.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-production-trading-services>
+pub const BASE_URL_OKX_BUSINESS: &str = "wss://wsaws.okx.com:8443/ws/v5/business";
+
+/// [`Okx`] private server base url, serving the logged in private channels (eg/
+/// [`OkxChannel::ORDERS`]).
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-production-trading-services>
+pub const BASE_URL_OKX_PRIVATE: &str = "wss://ws.okx.com:8443/ws/v5/private";
+
+/// [`Okx`] REST API base url.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-production-trading-services>
+pub const HTTP_BASE_URL_OKX: &str = "https://www.okx.com/api/v5";
+
+/// [`Okx`] idle period after which an application-level `ping` is sent, since Okx closes
+/// connections that have been silent for 30 seconds.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-connect>
+pub const PING_INTERVAL_OKX: Duration = Duration::from_secs(25);
+
+/// Number of consecutive [`Okx`] pings without a `pong` (or any other message) after which the
+/// connection is re-connected.
+pub const MAX_MISSED_PONGS_OKX: u32 = 2;
+
+/// Exchange message fields [`Okx`] models intentionally don't deserialise, see
+/// [`IgnoredFields`](crate::parser::strict::IgnoredFields).
+pub const IGNORED_FIELDS: crate::parser::strict::IgnoredFields = &[("OkxTrade", &["instId"])];
+
+/// Optional exchange message fields [`Okx`] is expected to send, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields).
+pub const EXPECTED_FIELDS: crate::parser::strict::ExpectedFields =
+    &[(ExchangeId::Okx, "OkxOrder", &["cancelSourceReason"])];
+
 /// [`Okx`] exchange.
 ///
 /// See docs: <https://www.okx.com/docs-v5/en/#websocket-api>
@@ -53,17 +133,268 @@ impl Connector for Okx {
         Url::parse(BASE_URL_OKX).map_err(SocketError::UrlParse)
     }
 
+    fn channel_url(channel: &Self::Channel) -> Result<Url, SocketError> {
+        match *channel {
+            OkxChannel::TRADES_ALL => {
+                Url::parse(BASE_URL_OKX_BUSINESS).map_err(SocketError::UrlParse)
+            }
+            channel if channel.is_private() => {
+                Url::parse(BASE_URL_OKX_PRIVATE).map_err(SocketError::UrlParse)
+            }
+            _ => Self::url(),
+        }
+    }
+
     fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        vec![WsMessage::Text(
-            json!({
-                "op": "subscribe",
-                "args": &exchange_subs,
-            })
-            .to_string(),
-        )]
+        subscribe_requests(exchange_subs, None)
+    }
+
+    fn subscribe_requests(
+        &self,
+        exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        config: &StreamConfig,
+    ) -> Result<Vec<WsMessage>, DataError> {
+        Ok(subscribe_requests(exchange_subs, config.ticket.as_ref()))
+    }
+
+    fn ping_interval() -> Option<PingInterval> {
+        // Okx closes silent connections, so only quiet connections (eg/ low-activity instruments)
+        // need pinging
+        Some(
+            PingInterval::adaptive(
+                tokio::time::interval(PING_INTERVAL_OKX),
+                || WsMessage::Text("ping".to_string()),
+                AdaptivePing::default(),
+            )
+            .with_max_missed_pongs(MAX_MISSED_PONGS_OKX),
+        )
+    }
+
+    fn pong(payload: &str) -> bool {
+        payload == "pong"
+    }
+
+    fn login(credentials: &Credentials) -> Result<Option<WsMessage>, DataError> {
+        login::login_request(credentials).map(Some)
+    }
+
+    fn login_response(payload: &str) -> Option<Result<(), DataError>> {
+        login::login_response(payload)
+    }
+
+    fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+        status::exchange_status(payload)
     }
 }
 
+/// Construct the [`Okx`] subscribe request of the provided [`ExchangeSub`]s, including the
+/// optional [`ConnectionTicket`] as it's "id" if Okx accepts it.
+fn subscribe_requests(
+    exchange_subs: Vec<ExchangeSub<OkxChannel, OkxMarket>>,
+    ticket: Option<&ConnectionTicket>,
+) -> Vec<WsMessage> {
+    let mut request = json!({
+        "op": "subscribe",
+        "args": &exchange_subs,
+    });
+
+    // Okx echoes the optional client supplied "id" (alphanumeric, max 32 chars) in it's
+    // subscription responses
+    match ticket {
+        Some(ticket) if ticket.is_alphanumeric(32) => {
+            request["id"] = json!(ticket.as_str());
+        }
+        Some(ticket) => {
+            debug!(%ticket, "ignoring ConnectionTicket that Okx does not accept as an id");
+        }
+        None => {}
+    }
+
+    vec![WsMessage::Text(request.to_string())]
+}
+
 impl StreamSelector<PublicTrades> for Okx {
     type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, OkxTrades>>;
 }
+
+impl StreamSelector<PublicTradesAll> for Okx {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTradesAll, OkxTrades>>;
+}
+
+impl StreamSelector<OrderUpdates> for Okx {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, OrderUpdates, OkxOrders>>;
+}
+
+impl StreamSelector<Fills> for Okx {
+    type Stream = ExchangeWsStream<StatelessTransformer<Self, Fills, OkxFills>>;
+}
+
+impl StreamSelector<Balances> for Okx {
+    type Stream = ExchangeWsStream<OkxBalancesTransformer>;
+}
+
+impl StreamSelector<InstrumentStatuses> for Okx {
+    type Stream = ExchangeWsStream<OkxInstrumentStatusesTransformer>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_okx_channel_url() {
+        struct TestCase {
+            input: OkxChannel,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: trades channel is served by the public server
+                input: OkxChannel::TRADES,
+                expected: BASE_URL_OKX,
+            },
+            TestCase {
+                // TC1: trades-all channel is served by the business server
+                input: OkxChannel::TRADES_ALL,
+                expected: BASE_URL_OKX_BUSINESS,
+            },
+            TestCase {
+                // TC2: orders channel is served by the private server
+                input: OkxChannel::ORDERS,
+                expected: BASE_URL_OKX_PRIVATE,
+            },
+            TestCase {
+                // TC3: fills channel is served by the private server
+                input: OkxChannel::FILLS,
+                expected: BASE_URL_OKX_PRIVATE,
+            },
+            TestCase {
+                // TC4: account channel is served by the private server
+                input: OkxChannel::ACCOUNT,
+                expected: BASE_URL_OKX_PRIVATE,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Okx::channel_url(&test.input).unwrap();
+            assert_eq!(actual.as_str(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_okx_ping() {
+        let ping_interval = Okx::ping_interval().unwrap();
+        assert_eq!(ping_interval.interval.period(), PING_INTERVAL_OKX);
+        assert!(ping_interval.adaptive.is_some());
+        assert_eq!(
+            ping_interval.missed_pongs.map(|missed| missed.max_missed),
+            Some(MAX_MISSED_PONGS_OKX)
+        );
+        assert_eq!((ping_interval.ping)(), WsMessage::Text("ping".to_string()));
+
+        assert!(Okx::pong("pong"));
+        assert!(!Okx::pong(r#"{"event":"subscribe"}"#));
+    }
+
+    #[test]
+    fn test_okx_subscribe_requests_connection_ticket() {
+        struct TestCase {
+            ticket: Option<&'static str>,
+            expected: serde_json::Value,
+        }
+
+        let args = json!([{"channel": "trades", "instId": "BTC-USDT"}]);
+        let tests = vec![
+            TestCase {
+                // TC0: no ConnectionTicket
+                ticket: None,
+                expected: json!({"op": "subscribe", "args": args}),
+            },
+            TestCase {
+                // TC1: valid ConnectionTicket is included as the id
+                ticket: Some("barter1"),
+                expected: json!({"op": "subscribe", "args": args, "id": "barter1"}),
+            },
+            TestCase {
+                // TC2: non-alphanumeric ConnectionTicket is ignored
+                ticket: Some("barter-1"),
+                expected: json!({"op": "subscribe", "args": args}),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let config = StreamConfig {
+                ticket: test.ticket.map(ConnectionTicket::from),
+                ..StreamConfig::default()
+            };
+
+            let actual = Okx
+                .subscribe_requests(
+                    vec![ExchangeSub {
+                        channel: OkxChannel::TRADES,
+                        market: OkxMarket("BTC-USDT".to_string()),
+                    }],
+                    &config,
+                )
+                .unwrap();
+
+            let WsMessage::Text(actual) = &actual[0] else {
+                panic!("TC{index} failed because request is not WsMessage::Text");
+            };
+            let actual = serde_json::from_str::<serde_json::Value>(actual).unwrap();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_okx_login_without_passphrase_fails_connection() {
+        use crate::{exchange::endpoint::Endpoints, subscription::Subscription, MarketStream};
+        use barter_integration::model::InstrumentKind;
+        use futures::StreamExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Mock Okx server: the connection must be dropped without a login or subscription
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            match websocket.next().await {
+                None | Some(Ok(Message::Close(_))) | Some(Err(_)) => {}
+                Some(Ok(message)) => panic!("unexpected message sent to server: {message:?}"),
+            }
+        });
+
+        let config = StreamConfig {
+            endpoints: Endpoints {
+                websocket: Some(url),
+                rest: None,
+            },
+            credentials: Some(Credentials::new("key", "secret")),
+            ..StreamConfig::default()
+        };
+
+        let result = <<Okx as StreamSelector<PublicTrades>>::Stream as MarketStream<
+            Okx,
+            PublicTrades,
+        >>::init_with(
+            &[Subscription::from((
+                Okx,
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            ))],
+            &config,
+        )
+        .await;
+
+        match result {
+            Err(DataError::Auth { exchange, .. }) => assert_eq!(exchange, ExchangeId::Okx),
+            Err(error) => panic!("expected DataError::Auth, but found: {error:?}"),
+            Ok(_) => panic!("expected DataError::Auth, but connected"),
+        }
+        server.await.unwrap();
+    }
+}
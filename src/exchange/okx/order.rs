@@ -0,0 +1,269 @@
+use super::trade::OkxMessage;
+use crate::{
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
+    exchange::ExchangeId,
+    num::Num,
+    subscription::order::{OrderStatus, OrderUpdate},
+};
+use barter_integration::model::{Exchange, Instrument, Side};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Terse type alias for an [`Okx`](super::Okx) private orders WebSocket message.
+pub type OkxOrders = OkxMessage<OkxOrder>;
+
+/// [`Okx`](super::Okx) private orders channel update of an order of the logged in account.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-order-channel>
+/// #### Spot Limit Order Filled
+/// ```json
+/// {
+///   "arg": {
+///     "channel": "orders",
+///     "instType": "SPOT",
+///     "instId": "BTC-USDT",
+///     "uid": "614488474791936"
+///   },
+///   "data": [
+///     {
+///       "instType": "SPOT",
+///       "instId": "BTC-USDT",
+///       "ordId": "452197707845865472",
+///       "clOrdId": "",
+///       "px": "31527.1",
+///       "sz": "0.001",
+///       "ordType": "limit",
+///       "side": "sell",
+///       "accFillSz": "0.001",
+///       "fillPx": "31527.1",
+///       "fillSz": "0.001",
+///       "tradeId": "242589207",
+///       "state": "filled",
+///       "cTime": "1654084334977",
+///       "uTime": "1654084353264"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxOrder {
+    #[serde(rename = "ordId")]
+    pub id: String,
+    #[serde(rename = "clOrdId", deserialize_with = "de_okx_optional_str")]
+    pub client_id: Option<String>,
+    #[serde(rename = "px", deserialize_with = "de_okx_optional_str")]
+    pub price: Option<Num>,
+    #[serde(rename = "sz", deserialize_with = "barter_integration::de::de_str")]
+    pub amount: Num,
+    #[serde(
+        rename = "accFillSz",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub filled_amount: Num,
+    pub side: Side,
+    pub state: OkxOrderState,
+    /// Reason the order was cancelled, empty unless it was cancelled by [`Okx`](super::Okx).
+    #[serde(
+        rename = "cancelSourceReason",
+        default,
+        deserialize_with = "de_okx_optional_str"
+    )]
+    pub cancel_reason: Option<String>,
+    #[serde(
+        rename = "uTime",
+        deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+}
+
+/// [`OkxOrder`] "state".
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OkxOrderState {
+    Live,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    /// Cancelled automatically by market maker protection.
+    MmpCanceled,
+}
+
+impl From<OkxOrderState> for OrderStatus {
+    fn from(state: OkxOrderState) -> Self {
+        match state {
+            OkxOrderState::Live => OrderStatus::Open,
+            OkxOrderState::PartiallyFilled => OrderStatus::PartiallyFilled,
+            OkxOrderState::Filled => OrderStatus::Filled,
+            OkxOrderState::Canceled | OkxOrderState::MmpCanceled => OrderStatus::Cancelled,
+        }
+    }
+}
+
+impl From<(ExchangeId, Instrument, DateTime<Utc>, OkxOrders)> for MarketIter<OrderUpdate> {
+    fn from(
+        (exchange_id, instrument, received_time, orders): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            OkxOrders,
+        ),
+    ) -> Self {
+        orders
+            .data
+            .into_iter()
+            .map(|order| {
+                Ok(MarketEvent {
+                    exchange_time: order.time,
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        order.time,
+                        TimestampUnit::Milliseconds,
+                    )),
+                    received_time,
+                    exchange: Exchange::from(exchange_id),
+                    instrument: instrument.clone(),
+                    kind: OrderUpdate {
+                        order_id: order.id,
+                        client_order_id: order.client_id,
+                        status: OrderStatus::from(order.state),
+                        side: order.side,
+                        price: order.price,
+                        amount: order.amount,
+                        filled_amount: order.filled_amount,
+                        reason: order.cancel_reason,
+                    },
+                    meta: (),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Deserialize an optional [`Okx`](super::Okx) `String` field, which is empty rather than
+/// absent when it has no value (eg/ the "px" of a market order), as an `Option<T>`.
+pub fn de_okx_optional_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = <&str as Deserialize>::deserialize(deserializer)?;
+    match value {
+        "" => Ok(None),
+        value => value.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use barter_integration::model::{InstrumentKind, SubscriptionId};
+
+    #[test]
+    fn test_okx_orders_into_order_updates() {
+        struct TestCase {
+            input: &'static str,
+            expected_id: &'static str,
+            expected: Vec<OrderUpdate>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: limit order filled
+                input: r#"{
+                    "arg": {"channel":"orders","instType":"SPOT","instId":"BTC-USDT","uid":"614488474791936"},
+                    "data": [{
+                        "instType":"SPOT","instId":"BTC-USDT","ordId":"452197707845865472",
+                        "clOrdId":"","px":"31527.1","sz":"0.001","ordType":"limit","side":"sell",
+                        "accFillSz":"0.001","fillPx":"31527.1","fillSz":"0.001",
+                        "tradeId":"242589207","state":"filled","cTime":"1654084334977",
+                        "uTime":"1654084353264"
+                    }]
+                }"#,
+                expected_id: "orders|BTC-USDT",
+                expected: vec![OrderUpdate {
+                    order_id: "452197707845865472".to_string(),
+                    client_order_id: None,
+                    status: OrderStatus::Filled,
+                    side: Side::Sell,
+                    price: Some(num!(31527.1)),
+                    amount: num!(0.001),
+                    filled_amount: num!(0.001),
+                    reason: None,
+                }],
+            },
+            TestCase {
+                // TC1: market order w/ client order id is live
+                input: r#"{
+                    "arg": {"channel":"orders","instType":"SWAP","instId":"BTC-USDT-SWAP","uid":"614488474791936"},
+                    "data": [{
+                        "instType":"SWAP","instId":"BTC-USDT-SWAP","ordId":"452197707845865473",
+                        "clOrdId":"barter1","px":"","sz":"2","ordType":"market","side":"buy",
+                        "accFillSz":"0","fillPx":"","fillSz":"0","tradeId":"","state":"live",
+                        "cTime":"1654084334977","uTime":"1654084334977"
+                    }]
+                }"#,
+                expected_id: "orders|BTC-USDT-SWAP",
+                expected: vec![OrderUpdate {
+                    order_id: "452197707845865473".to_string(),
+                    client_order_id: Some("barter1".to_string()),
+                    status: OrderStatus::Open,
+                    side: Side::Buy,
+                    price: None,
+                    amount: num!(2.0),
+                    filled_amount: num!(0.0),
+                    reason: None,
+                }],
+            },
+            TestCase {
+                // TC2: order cancelled by market maker protection
+                input: r#"{
+                    "arg": {"channel":"orders","instType":"SPOT","instId":"BTC-USDT","uid":"614488474791936"},
+                    "data": [{
+                        "instType":"SPOT","instId":"BTC-USDT","ordId":"452197707845865474",
+                        "clOrdId":"","px":"30000","sz":"0.5","ordType":"limit","side":"buy",
+                        "accFillSz":"0.1","fillPx":"","fillSz":"0","tradeId":"",
+                        "state":"mmp_canceled","cancelSourceReason":"Order canceled by mmp",
+                        "cTime":"1654084334977","uTime":"1654084353264"
+                    }]
+                }"#,
+                expected_id: "orders|BTC-USDT",
+                expected: vec![OrderUpdate {
+                    order_id: "452197707845865474".to_string(),
+                    client_order_id: None,
+                    status: OrderStatus::Cancelled,
+                    side: Side::Buy,
+                    price: Some(num!(30000.0)),
+                    amount: num!(0.5),
+                    filled_amount: num!(0.1),
+                    reason: Some("Order canceled by mmp".to_string()),
+                }],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let orders = serde_json::from_str::<OkxOrders>(test.input).unwrap();
+            assert_eq!(
+                orders.subscription_id,
+                SubscriptionId::from(test.expected_id),
+                "TC{} failed",
+                index
+            );
+
+            let actual = MarketIter::<OrderUpdate>::from((
+                ExchangeId::Okx,
+                Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                Utc::now(),
+                orders,
+            ))
+            .0
+            .into_iter()
+            .map(|event| event.unwrap().kind)
+            .collect::<Vec<_>>();
+
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
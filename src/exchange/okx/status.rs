@@ -0,0 +1,178 @@
+use crate::exchange::status::{ExchangeStatus, SystemStatus};
+use serde::{Deserialize, Serialize};
+
+/// [`Okx`](super::Okx) status & maintenance message.
+///
+/// ### Raw Payload Examples
+/// #### Notice
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket-notification-of-websocket-service-upgrade>
+/// ```json
+/// {
+///     "event": "notice",
+///     "code": "64008",
+///     "msg": "The connection will soon be closed for a service upgrade. Please reconnect.",
+///     "connId": "a4d3ae55"
+/// }
+/// ```
+///
+/// #### Status Channel
+/// See docs: <https://www.okx.com/docs-v5/en/#status-websocket-status-channel>
+/// ```json
+/// {
+///     "arg": {"channel": "status"},
+///     "data": [
+///         {
+///             "title": "Spot System Upgrade",
+///             "state": "ongoing",
+///             "begin": "1610019546",
+///             "end": "1610019546",
+///             "href": "",
+///             "serviceType": "1",
+///             "system": "classic",
+///             "scheDesc": "",
+///             "ts": "1597026383085"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum OkxStatus {
+    Notice(OkxNotice),
+    Maintenance(OkxMaintenanceMessage),
+}
+
+/// [`Okx`](super::Okx) `notice` event, eg/ an upcoming disconnect for a service upgrade.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename = "notice")]
+pub struct OkxNotice {
+    pub code: String,
+    pub msg: String,
+}
+
+/// [`Okx`](super::Okx) `status` channel message.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct OkxMaintenanceMessage {
+    pub arg: OkxStatusArg,
+    pub data: Vec<OkxMaintenance>,
+}
+
+/// [`Okx`](super::Okx) channel argument of an [`OkxMaintenanceMessage`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct OkxStatusArg {
+    pub channel: String,
+}
+
+/// [`Okx`](super::Okx) system maintenance contained in an [`OkxMaintenanceMessage`].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct OkxMaintenance {
+    pub title: String,
+    pub state: String,
+}
+
+impl OkxMaintenance {
+    /// Normalise the [`Okx`](super::Okx) maintenance `state` into a [`SystemStatus`].
+    pub fn system_status(&self) -> SystemStatus {
+        match self.state.as_str() {
+            "ongoing" => SystemStatus::Maintenance,
+            "completed" | "canceled" => SystemStatus::Online,
+            "scheduled" | "pre_open" => SystemStatus::Notice,
+            _ => SystemStatus::Unknown,
+        }
+    }
+}
+
+impl From<OkxStatus> for Option<ExchangeStatus> {
+    fn from(status: OkxStatus) -> Self {
+        match status {
+            OkxStatus::Notice(notice) => Some(ExchangeStatus::new(
+                SystemStatus::Notice,
+                Some(format!("{}: {}", notice.code, notice.msg)),
+            )),
+            OkxStatus::Maintenance(message) if message.arg.channel == "status" => {
+                // Ongoing maintenance takes precedence over other concurrent updates
+                message
+                    .data
+                    .into_iter()
+                    .max_by_key(|maintenance| maintenance.system_status().is_maintenance())
+                    .map(|maintenance| {
+                        ExchangeStatus::new(maintenance.system_status(), Some(maintenance.title))
+                    })
+            }
+            OkxStatus::Maintenance(_) => None,
+        }
+    }
+}
+
+/// Recognise an [`Okx`](super::Okx) notice or `status` channel message as an
+/// [`ExchangeStatus`].
+///
+/// See [`Connector::exchange_status`](crate::exchange::Connector::exchange_status).
+pub fn exchange_status(payload: &str) -> Option<ExchangeStatus> {
+    // Avoid deserialising every market data message
+    if !payload.contains(r#""notice""#) && !payload.contains(r#""status""#) {
+        return None;
+    }
+
+    serde_json::from_str::<OkxStatus>(payload).ok()?.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_okx_exchange_status() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<ExchangeStatus>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: service upgrade notice
+                input: r#"{"event":"notice","code":"64008","msg":"The connection will soon be closed for a service upgrade. Please reconnect.","connId":"a4d3ae55"}"#,
+                expected: Some(ExchangeStatus::new(
+                    SystemStatus::Notice,
+                    Some("64008: The connection will soon be closed for a service upgrade. Please reconnect."),
+                )),
+            },
+            TestCase {
+                // TC1: ongoing maintenance
+                input: r#"{"arg":{"channel":"status"},"data":[{"title":"Spot System Upgrade","state":"ongoing","begin":"1610019546","href":"","end":"1610019546","serviceType":"1","system":"classic","scheDesc":"","ts":"1597026383085"}]}"#,
+                expected: Some(ExchangeStatus::new(
+                    SystemStatus::Maintenance,
+                    Some("Spot System Upgrade"),
+                )),
+            },
+            TestCase {
+                // TC2: completed maintenance
+                input: r#"{"arg":{"channel":"status"},"data":[{"title":"Spot System Upgrade","state":"completed","ts":"1597026383085"}]}"#,
+                expected: Some(ExchangeStatus::new(
+                    SystemStatus::Online,
+                    Some("Spot System Upgrade"),
+                )),
+            },
+            TestCase {
+                // TC3: ongoing maintenance takes precedence over scheduled maintenance
+                input: r#"{"arg":{"channel":"status"},"data":[{"title":"Upcoming","state":"scheduled"},{"title":"Now","state":"ongoing"}]}"#,
+                expected: Some(ExchangeStatus::new(SystemStatus::Maintenance, Some("Now"))),
+            },
+            TestCase {
+                // TC4: subscription response is not a status message
+                input: r#"{"event":"subscribe","arg":{"channel":"status"},"connId":"a4d3ae55"}"#,
+                expected: None,
+            },
+            TestCase {
+                // TC5: trades are not a status message
+                input: r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}]}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = exchange_status(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -9,10 +9,46 @@ impl Serialize for ExchangeSub<OkxChannel, OkxMarket> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("OkxSubArg", 2)?;
-        state.serialize_field("channel", self.channel.as_ref())?;
-        state.serialize_field("instId", self.market.as_ref())?;
-        state.end()
+        match self.channel {
+            // Account balances are per currency, so every balance of the account is subscribed
+            OkxChannel::ACCOUNT => {
+                let mut state = serializer.serialize_struct("OkxSubArg", 1)?;
+                state.serialize_field("channel", self.channel.as_ref())?;
+                state.end()
+            }
+            // Instruments are subscribed per "instType", rather than per "instId"
+            OkxChannel::INSTRUMENTS => {
+                let mut state = serializer.serialize_struct("OkxSubArg", 2)?;
+                state.serialize_field("channel", self.channel.as_ref())?;
+                state.serialize_field("instType", inst_type(self.market.as_ref()))?;
+                state.end()
+            }
+            // Orders additionally require the "instType" of the "instId"
+            OkxChannel::ORDERS => {
+                let inst_type = inst_type(self.market.as_ref());
+
+                let mut state = serializer.serialize_struct("OkxSubArg", 3)?;
+                state.serialize_field("channel", self.channel.as_ref())?;
+                state.serialize_field("instType", inst_type)?;
+                state.serialize_field("instId", self.market.as_ref())?;
+                state.end()
+            }
+            _ => {
+                let mut state = serializer.serialize_struct("OkxSubArg", 2)?;
+                state.serialize_field("channel", self.channel.as_ref())?;
+                state.serialize_field("instId", self.market.as_ref())?;
+                state.end()
+            }
+        }
+    }
+}
+
+/// [`Okx`](super::Okx) "instType" of the provided "instId", ie/ "SWAP" for perpetual swaps, else
+/// "SPOT".
+fn inst_type(inst_id: &str) -> &'static str {
+    match inst_id.ends_with("-SWAP") {
+        true => "SWAP",
+        false => "SPOT",
     }
 }
 
@@ -70,6 +106,62 @@ impl Validator for OkxSubResponse {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_okx_exchange_sub_serialize() {
+        struct TestCase {
+            input: ExchangeSub<OkxChannel, OkxMarket>,
+            expected: serde_json::Value,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: public trades channel
+                input: ExchangeSub::from((OkxChannel::TRADES, OkxMarket("BTC-USDT".to_string()))),
+                expected: serde_json::json!({"channel": "trades", "instId": "BTC-USDT"}),
+            },
+            TestCase {
+                // TC1: private spot orders channel w/ instType
+                input: ExchangeSub::from((OkxChannel::ORDERS, OkxMarket("BTC-USDT".to_string()))),
+                expected: serde_json::json!({
+                    "channel": "orders", "instType": "SPOT", "instId": "BTC-USDT"
+                }),
+            },
+            TestCase {
+                // TC2: private perpetual orders channel w/ instType
+                input: ExchangeSub::from((
+                    OkxChannel::ORDERS,
+                    OkxMarket("BTC-USDT-SWAP".to_string()),
+                )),
+                expected: serde_json::json!({
+                    "channel": "orders", "instType": "SWAP", "instId": "BTC-USDT-SWAP"
+                }),
+            },
+            TestCase {
+                // TC3: private fills channel
+                input: ExchangeSub::from((OkxChannel::FILLS, OkxMarket("BTC-USDT".to_string()))),
+                expected: serde_json::json!({"channel": "fills", "instId": "BTC-USDT"}),
+            },
+            TestCase {
+                // TC4: private account channel w/o instId
+                input: ExchangeSub::from((OkxChannel::ACCOUNT, OkxMarket("BTC-USDT".to_string()))),
+                expected: serde_json::json!({"channel": "account"}),
+            },
+            TestCase {
+                // TC5: public instruments channel w/ instType rather than instId
+                input: ExchangeSub::from((
+                    OkxChannel::INSTRUMENTS,
+                    OkxMarket("BTC-USDT-SWAP".to_string()),
+                )),
+                expected: serde_json::json!({"channel": "instruments", "instType": "SWAP"}),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = serde_json::to_value(&test.input).unwrap();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
     mod de {
         use super::*;
 
@@ -105,6 +197,32 @@ mod tests {
                         message: "Invalid request: {\"op\": \"subscribe\", \"args\":[{ \"channel\" : \"trades\", \"instId\" : \"BTC-USD-191227\"}]}".to_string()
                     }),
                 },
+                TestCase {
+                    // TC2: input response is trades-all subscription success
+                    input: r#"
+                {
+                    "event": "subscribe",
+                    "arg": {"channel": "trades-all", "instId": "BTC-USDT"},
+                    "connId": "a4d3ae55"
+                }
+                "#,
+                    expected: Ok(OkxSubResponse::Subscribed),
+                },
+                TestCase {
+                    // TC3: input response is failed trades-all subscription on the public server
+                    input: r#"
+                {
+                    "event": "error",
+                    "code": "60018",
+                    "msg": "Wrong URL or channel:trades-all,instId:BTC-USDT doesn't exist. Please use the correct URL, channel and parameters referring to API document.",
+                    "connId": "a4d3ae55"
+                }
+                "#,
+                    expected: Ok(OkxSubResponse::Error {
+                        code: "60018".to_string(),
+                        message: "Wrong URL or channel:trades-all,instId:BTC-USDT doesn't exist. Please use the correct URL, channel and parameters referring to API document.".to_string()
+                    }),
+                },
             ];
 
             for (index, test) in cases.into_iter().enumerate() {
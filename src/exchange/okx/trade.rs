@@ -1,6 +1,7 @@
 use crate::{
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketIter, RawTimestamp, TimestampUnit},
     exchange::{ExchangeId, ExchangeSub},
+    num::Num,
     subscription::trade::PublicTrade,
     Identifier,
 };
@@ -61,26 +62,37 @@ pub struct OkxTrade {
     #[serde(rename = "tradeId")]
     pub id: String,
     #[serde(rename = "px", deserialize_with = "barter_integration::de::de_str")]
-    pub price: f64,
+    pub price: Num,
     #[serde(rename = "sz", deserialize_with = "barter_integration::de::de_str")]
-    pub amount: f64,
+    pub amount: Num,
     pub side: Side,
     #[serde(
         rename = "ts",
-        deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc"
+        deserialize_with = "crate::datetime::de_str_epoch_ms_as_datetime_utc"
     )]
     pub time: DateTime<Utc>,
 }
 
-impl From<(ExchangeId, Instrument, OkxTrades)> for MarketIter<PublicTrade> {
-    fn from((exchange_id, instrument, trades): (ExchangeId, Instrument, OkxTrades)) -> Self {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, OkxTrades)> for MarketIter<PublicTrade> {
+    fn from(
+        (exchange_id, instrument, received_time, trades): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            OkxTrades,
+        ),
+    ) -> Self {
         trades
             .data
             .into_iter()
             .map(|trade| {
                 Ok(MarketEvent {
                     exchange_time: trade.time,
-                    received_time: Utc::now(),
+                    raw_exchange_time: Some(RawTimestamp::from_datetime(
+                        trade.time,
+                        TimestampUnit::Milliseconds,
+                    )),
+                    received_time,
                     exchange: Exchange::from(exchange_id),
                     instrument: instrument.clone(),
                     kind: PublicTrade {
@@ -89,6 +101,7 @@ impl From<(ExchangeId, Instrument, OkxTrades)> for MarketIter<PublicTrade> {
                         amount: trade.amount,
                         side: trade.side,
                     },
+                    meta: (),
                 })
             })
             .collect()
@@ -116,6 +129,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod de {
         use super::*;
@@ -149,8 +163,8 @@ mod tests {
                 subscription_id: SubscriptionId::from("trades|BTC-USDT"),
                 data: vec![OkxTrade {
                     id: "130639474".to_string(),
-                    price: 42219.9,
-                    amount: 0.12060306,
+                    price: num!(42219.9),
+                    amount: num!(0.12060306),
                     side: Side::Buy,
                     time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897897)),
                 }],
@@ -169,5 +183,41 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn test_okx_message_trades_all() {
+            let input = r#"
+            {
+                "arg": {
+                    "channel": "trades-all",
+                    "instId": "BTC-USDT"
+                },
+                "data": [
+                    {
+                        "instId": "BTC-USDT",
+                        "tradeId": "130639475",
+                        "px": "42220.1",
+                        "sz": "25.5",
+                        "side": "sell",
+                        "ts": "1630048897898"
+                    }
+                ]
+            }
+            "#;
+
+            let actual = serde_json::from_str::<OkxTrades>(input).unwrap();
+            let expected = OkxTrades {
+                subscription_id: SubscriptionId::from("trades-all|BTC-USDT"),
+                data: vec![OkxTrade {
+                    id: "130639475".to_string(),
+                    price: num!(42220.1),
+                    amount: num!(25.5),
+                    side: Side::Sell,
+                    time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897898)),
+                }],
+            };
+
+            assert_eq!(actual, expected);
+        }
     }
 }
@@ -0,0 +1,643 @@
+use super::ExchangeId;
+use crate::subscriber::{buffer::BufferedStream, transport::WsStream};
+use barter_integration::protocol::websocket::{WsError, WsMessage};
+use chrono::{DateTime, Utc};
+use futures::{task::AtomicWaker, Stream, StreamExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::Instant};
+use tracing::debug;
+
+/// Maximum number of recent disconnects remembered per [`ExchangeId`].
+const MAX_DISCONNECTS_TRACKED: usize = 64;
+
+/// Configuration for adaptive application-level pings, used by a
+/// [`PingInterval`](super::PingInterval) to only ping after a true idle period.
+///
+/// In adaptive mode the ping timer is reset whenever any message is received from, or sent to,
+/// the exchange, so busy connections are never pinged. The [`PingInterval`](super::PingInterval) `interval`
+/// period is used as the idle period, and it's halved for every recent disconnect from the same
+/// exchange (ie/ a marginal connection), down to `min_idle`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AdaptivePing {
+    /// Shortest idle period before a marginal connection is pinged.
+    pub min_idle: Duration,
+    /// Window within which disconnects from the same exchange mark a connection as marginal.
+    pub reconnect_window: Duration,
+}
+
+impl Default for AdaptivePing {
+    fn default() -> Self {
+        Self {
+            min_idle: Duration::from_secs(5),
+            reconnect_window: Duration::from_secs(300),
+        }
+    }
+}
+
+impl AdaptivePing {
+    /// Construct a new [`Self`] using the provided minimum idle period and reconnect window.
+    pub fn new(min_idle: Duration, reconnect_window: Duration) -> Self {
+        Self {
+            min_idle,
+            reconnect_window,
+        }
+    }
+
+    /// Determine the idle period to wait before pinging, given the base idle period and the
+    /// number of recent disconnects from the same exchange.
+    ///
+    /// Each recent disconnect halves the idle period, down to `min_idle`. The result never
+    /// exceeds the base idle period.
+    pub fn idle_period(&self, idle: Duration, recent_disconnects: usize) -> Duration {
+        let shortened = idle / (1u32 << recent_disconnects.min(16));
+        shortened.max(self.min_idle).min(idle)
+    }
+}
+
+/// Shared record of when a WebSocket connection last received a message from the exchange, when
+/// it last sent one, how many application-level pongs it has received, and whether it has been
+/// expired for missing too many of them.
+///
+/// Cheaply cloneable - every clone observes the same connection.
+#[derive(Clone, Debug)]
+pub struct ConnectionActivity {
+    start: Instant,
+    last_nanos: Arc<AtomicU64>,
+    last_sent_nanos: Arc<AtomicU64>,
+    pongs: Arc<AtomicU64>,
+    expired: Arc<AtomicBool>,
+    waker: Arc<AtomicWaker>,
+}
+
+impl Default for ConnectionActivity {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            last_nanos: Arc::new(AtomicU64::new(0)),
+            last_sent_nanos: Arc::new(AtomicU64::new(0)),
+            pongs: Arc::new(AtomicU64::new(0)),
+            expired: Arc::new(AtomicBool::new(false)),
+            waker: Arc::new(AtomicWaker::new()),
+        }
+    }
+}
+
+impl ConnectionActivity {
+    /// Record that a message was received from the exchange now.
+    pub fn record(&self) {
+        let elapsed = self.start.elapsed().as_nanos() as u64;
+        self.last_nanos.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    /// Time the last message was received, or when [`Self`] was constructed if none have been.
+    pub fn last_activity(&self) -> Instant {
+        self.start + Duration::from_nanos(self.last_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Record that a message was sent to the exchange now.
+    pub fn record_sent(&self) {
+        let elapsed = self.start.elapsed().as_nanos() as u64;
+        self.last_sent_nanos.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    /// Time the last message was sent, or when [`Self`] was constructed if none have been.
+    pub fn last_sent(&self) -> Instant {
+        self.start + Duration::from_nanos(self.last_sent_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Record that an application-level pong was received from the exchange.
+    pub fn record_pong(&self) {
+        self.pongs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Total number of application-level pongs received from the exchange.
+    pub fn pongs(&self) -> u64 {
+        self.pongs.load(Ordering::Relaxed)
+    }
+
+    /// Expire the connection (eg/ after too many missed pongs), ending it's [`PongStream`] so
+    /// the [`consume`](crate::streams::consumer::consume) loop re-connects.
+    pub fn expire(&self) {
+        self.expired.store(true, Ordering::Release);
+        self.waker.wake();
+    }
+
+    /// Determine if the connection has been expired.
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::Acquire)
+    }
+}
+
+/// Recogniser of exchange specific application-level pongs, see
+/// [`Connector::pong`](super::Connector::pong).
+pub type PongRecogniser = fn(&str) -> bool;
+
+/// Policy that expires a connection once it has missed `max_missed` consecutive
+/// application-level pongs, ie/ no pong (or any other message, see [`MissedPongs::answered`])
+/// was received between consecutive pings.
+///
+/// Used by a [`PingInterval`](super::PingInterval) to detect connections the exchange has
+/// silently dropped, since they otherwise only end once the TCP connection times out.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MissedPongs {
+    pub max_missed: u32,
+    missed: u32,
+    pongs: Option<u64>,
+}
+
+impl MissedPongs {
+    /// Construct a new [`Self`] that expires a connection after `max_missed` consecutive missed
+    /// pongs.
+    pub fn new(max_missed: u32) -> Self {
+        Self {
+            max_missed,
+            missed: 0,
+            pongs: None,
+        }
+    }
+
+    /// Record that the previous ping was answered by a message other than a pong (eg/ market
+    /// data), which also proves the connection is alive.
+    pub fn answered(&mut self) {
+        self.pongs = None;
+    }
+
+    /// Record that a ping is about to be sent, given the total number of pongs received so far.
+    ///
+    /// Returns true if the previous ping was also not answered by a pong, and `max_missed`
+    /// consecutive pongs have now been missed.
+    pub fn ping(&mut self, pongs: u64) -> bool {
+        match self.pongs.replace(pongs) {
+            Some(previous) if previous == pongs => self.missed += 1,
+            _ => self.missed = 0,
+        }
+        self.missed >= self.max_missed
+    }
+}
+
+/// [`Stream`] wrapper that records every message received from the exchange in a
+/// [`ConnectionActivity`], and records a disconnect once the inner [`Stream`] ends.
+///
+/// Used as the inner [`Stream`] of an [`ExchangeWsStream`](crate::ExchangeWsStream).
+#[derive(Debug)]
+pub struct ActivityStream<InnerStream = WsStream> {
+    pub exchange: ExchangeId,
+    pub stream: InnerStream,
+    pub activity: ConnectionActivity,
+    ended: bool,
+}
+
+impl<InnerStream> ActivityStream<InnerStream> {
+    /// Construct a new [`Self`] wrapping the provided [`Stream`].
+    pub fn new(exchange: ExchangeId, stream: InnerStream, activity: ConnectionActivity) -> Self {
+        Self {
+            exchange,
+            stream,
+            activity,
+            ended: false,
+        }
+    }
+}
+
+impl<InnerStream> Stream for ActivityStream<InnerStream>
+where
+    InnerStream: Stream + Unpin,
+{
+    type Item = InnerStream::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(message)) => {
+                this.activity.record();
+                Poll::Ready(Some(message))
+            }
+            Poll::Ready(None) => {
+                if !this.ended {
+                    this.ended = true;
+                    record_disconnect(this.exchange);
+                }
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// [`Stream`] wrapper that intercepts the application-level pongs recognised by the
+/// [`Connector::pong`](super::Connector::pong) of it's exchange, recording them in the
+/// [`ConnectionActivity`] rather than yielding them to the parser (where they'd fail to
+/// deserialise).
+///
+/// Ends (recording a disconnect) once the [`ConnectionActivity`] is expired, eg/ by the
+/// [`MissedPongs`] policy of the exchange [`PingInterval`](super::PingInterval).
+///
+/// Holds an [`mpsc::UnboundedSender`] of the messages sent to the exchange, so the connection
+/// (and it's scheduled pings) stays open for as long as the stream, even if the
+/// [`ExchangeTransformer`](crate::transformer::ExchangeTransformer) never sends a message.
+///
+/// Used as the inner [`Stream`] of an [`ExchangeWsStream`](crate::ExchangeWsStream).
+#[derive(Debug)]
+pub struct PongStream<InnerStream = ActivityStream<BufferedStream>> {
+    pub exchange: ExchangeId,
+    recognise: PongRecogniser,
+    pub stream: InnerStream,
+    pub activity: ConnectionActivity,
+    ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+    ended: bool,
+}
+
+impl<InnerStream> PongStream<InnerStream> {
+    /// Construct a new [`Self`] wrapping the provided [`Stream`].
+    pub fn new(
+        exchange: ExchangeId,
+        recognise: PongRecogniser,
+        stream: InnerStream,
+        activity: ConnectionActivity,
+        ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+    ) -> Self {
+        Self {
+            exchange,
+            recognise,
+            stream,
+            activity,
+            ws_sink_tx,
+            ended: false,
+        }
+    }
+
+    /// [`mpsc::UnboundedSender`] of the messages sent to the exchange over this connection.
+    pub fn ws_sink_tx(&self) -> &mpsc::UnboundedSender<WsMessage> {
+        &self.ws_sink_tx
+    }
+}
+
+impl<InnerStream> Stream for PongStream<InnerStream>
+where
+    InnerStream: Stream<Item = Result<(DateTime<Utc>, WsMessage), WsError>> + Unpin,
+{
+    type Item = Result<(DateTime<Utc>, WsMessage), WsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.ended {
+            return Poll::Ready(None);
+        }
+
+        // End an expired connection, even whilst the exchange is silent
+        this.activity.waker.register(cx.waker());
+        if this.activity.is_expired() {
+            debug!(exchange = %this.exchange, "ending expired connection");
+            this.ended = true;
+            record_disconnect(this.exchange);
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok((_, WsMessage::Text(text))))) if (this.recognise)(&text) => {
+                    this.activity.record_pong();
+                }
+                poll => return poll,
+            }
+        }
+    }
+}
+
+/// Recent disconnect times for each [`ExchangeId`], shared by every connection.
+fn disconnects() -> &'static Mutex<HashMap<ExchangeId, VecDeque<Instant>>> {
+    static DISCONNECTS: OnceLock<Mutex<HashMap<ExchangeId, VecDeque<Instant>>>> = OnceLock::new();
+    DISCONNECTS.get_or_init(Default::default)
+}
+
+/// Record that a connection to the provided exchange has ended.
+pub fn record_disconnect(exchange: ExchangeId) {
+    let mut disconnects = disconnects().lock().unwrap();
+    let history = disconnects.entry(exchange).or_default();
+    if history.len() == MAX_DISCONNECTS_TRACKED {
+        history.pop_front();
+    }
+    history.push_back(Instant::now());
+}
+
+/// Number of disconnects from the provided exchange within the `window` before now.
+pub fn recent_disconnects(exchange: ExchangeId, window: Duration) -> usize {
+    let now = Instant::now();
+    disconnects()
+        .lock()
+        .unwrap()
+        .get(&exchange)
+        .map(|history| {
+            history
+                .iter()
+                .filter(|time| now.saturating_duration_since(**time) <= window)
+                .count()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exchange::PingInterval, schedule_pings_to_exchange};
+    use barter_integration::protocol::websocket::WsMessage;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_adaptive_ping_idle_period() {
+        struct TestCase {
+            idle: Duration,
+            recent_disconnects: usize,
+            expected: Duration,
+        }
+
+        let adaptive = AdaptivePing::new(Duration::from_secs(5), Duration::from_secs(300));
+
+        let tests = vec![
+            TestCase {
+                // TC0: healthy connection uses the base idle period
+                idle: Duration::from_secs(30),
+                recent_disconnects: 0,
+                expected: Duration::from_secs(30),
+            },
+            TestCase {
+                // TC1: each recent disconnect halves the idle period
+                idle: Duration::from_secs(30),
+                recent_disconnects: 2,
+                expected: Duration::from_millis(7500),
+            },
+            TestCase {
+                // TC2: idle period is floored at min_idle
+                idle: Duration::from_secs(30),
+                recent_disconnects: 3,
+                expected: Duration::from_secs(5),
+            },
+            TestCase {
+                // TC3: many recent disconnects does not overflow
+                idle: Duration::from_secs(30),
+                recent_disconnects: 1000,
+                expected: Duration::from_secs(5),
+            },
+            TestCase {
+                // TC4: idle period never exceeds the base idle period, even if below min_idle
+                idle: Duration::from_secs(1),
+                recent_disconnects: 1,
+                expected: Duration::from_secs(1),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = adaptive.idle_period(test.idle, test.recent_disconnects);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_missed_pongs() {
+        struct TestCase {
+            pongs: Vec<u64>,
+            expected: Vec<bool>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: every ping answered by a pong
+                pongs: vec![0, 1, 2, 3],
+                expected: vec![false, false, false, false],
+            },
+            TestCase {
+                // TC1: max_missed consecutive unanswered pings expire the connection
+                pongs: vec![0, 0, 0],
+                expected: vec![false, false, true],
+            },
+            TestCase {
+                // TC2: a pong resets the consecutive missed count
+                pongs: vec![0, 0, 1, 1, 2],
+                expected: vec![false, false, false, false, false],
+            },
+            TestCase {
+                // TC3: several pongs between pings count as answered
+                pongs: vec![0, 3, 3, 3],
+                expected: vec![false, false, false, true],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut missed_pongs = MissedPongs::new(2);
+            let actual = test
+                .pongs
+                .into_iter()
+                .map(|pongs| missed_pongs.ping(pongs))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pong_stream_records_pongs_and_ends_once_expired() {
+        fn pong(payload: &str) -> bool {
+            payload == "pong"
+        }
+
+        let activity = ConnectionActivity::default();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (ws_sink_tx, _ws_sink_rx) = mpsc::unbounded_channel();
+        let mut stream = PongStream::new(
+            ExchangeId::GateioSpot,
+            pong,
+            tokio_stream::wrappers::UnboundedReceiverStream::new(rx),
+            activity.clone(),
+            ws_sink_tx,
+        );
+
+        // Pongs are recorded rather than yielded
+        for text in ["pong", "trade", "pong"] {
+            tx.send(Ok((Utc::now(), WsMessage::Text(text.to_string()))))
+                .unwrap();
+        }
+        assert_eq!(
+            stream.next().await.unwrap().unwrap().1,
+            WsMessage::Text("trade".to_string())
+        );
+        assert_eq!(activity.pongs(), 1);
+
+        // Expired connection ends, even whilst the exchange is silent
+        let expire = activity.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            expire.expire();
+        });
+        let ended = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("expired PongStream did not end");
+        assert!(ended.is_none());
+        assert_eq!(activity.pongs(), 2);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_missed_pongs_expire_connection() {
+        fn ping() -> WsMessage {
+            WsMessage::Text("ping".to_string())
+        }
+
+        let activity = ConnectionActivity::default();
+        let (ws_sink_tx, mut ws_sink_rx) = mpsc::unbounded_channel();
+        let pinger = tokio::spawn(schedule_pings_to_exchange(
+            ExchangeId::GateioSpot,
+            ws_sink_tx.downgrade(),
+            PingInterval::new(tokio::time::interval(Duration::from_secs(10)), ping)
+                .with_max_missed_pongs(2),
+            activity.clone(),
+        ));
+
+        // Answered pings keep the connection alive
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+            activity.record_pong();
+            tokio::time::sleep(Duration::from_secs(9)).await;
+        }
+        assert!(!activity.is_expired());
+
+        // Unanswered pings expire the connection, instead of sending another ping
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+        assert!(!activity.is_expired());
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert!(ws_sink_rx.try_recv().is_err());
+        assert!(activity.is_expired());
+        pinger.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_activity_stream_records_messages_and_disconnect() {
+        let activity = ConnectionActivity::default();
+        let mut stream = ActivityStream::new(
+            ExchangeId::GateioFuturesBtc,
+            futures::stream::iter([1, 2]),
+            activity.clone(),
+        );
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(stream.next().await, Some(1));
+        assert_eq!(
+            activity.last_activity(),
+            activity.start + Duration::from_secs(10)
+        );
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(stream.next().await, Some(2));
+        assert_eq!(
+            activity.last_activity(),
+            activity.start + Duration::from_secs(20)
+        );
+
+        let window = Duration::from_secs(60);
+        let before = recent_disconnects(ExchangeId::GateioFuturesBtc, window);
+        assert_eq!(stream.next().await, None);
+        assert_eq!(stream.next().await, None);
+        assert_eq!(
+            recent_disconnects(ExchangeId::GateioFuturesBtc, window),
+            before + 1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_adaptive_pings_only_sent_after_idle_period() {
+        fn ping() -> WsMessage {
+            WsMessage::Text("ping".to_string())
+        }
+
+        let activity = ConnectionActivity::default();
+        let (ws_sink_tx, mut ws_sink_rx) = mpsc::unbounded_channel();
+        tokio::spawn(schedule_pings_to_exchange(
+            ExchangeId::GateioFuturesUsd,
+            ws_sink_tx.downgrade(),
+            PingInterval::adaptive(
+                tokio::time::interval(Duration::from_secs(10)),
+                ping,
+                AdaptivePing::new(Duration::from_secs(1), Duration::from_secs(60)),
+            ),
+            activity.clone(),
+        ));
+
+        // Busy connection: messages received every 5s are never pinged
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            activity.record();
+        }
+        assert!(ws_sink_rx.try_recv().is_err());
+
+        // Idle connection: pinged once the idle period has elapsed since the last message
+        tokio::time::sleep(Duration::from_millis(9_900)).await;
+        assert!(ws_sink_rx.try_recv().is_err());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+
+        // Still idle: pinged again one idle period after the previous ping
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+
+        // Pinging stops once the MarketStream has been dropped
+        drop(ws_sink_tx);
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert!(ws_sink_rx.recv().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_adaptive_pings_reset_by_sent_messages_and_answered_by_any_message() {
+        fn ping() -> WsMessage {
+            WsMessage::Text("ping".to_string())
+        }
+
+        let activity = ConnectionActivity::default();
+        let (ws_sink_tx, mut ws_sink_rx) = mpsc::unbounded_channel();
+        let pinger = tokio::spawn(schedule_pings_to_exchange(
+            ExchangeId::Okx,
+            ws_sink_tx.downgrade(),
+            PingInterval::adaptive(
+                tokio::time::interval(Duration::from_secs(10)),
+                ping,
+                AdaptivePing::new(Duration::from_secs(10), Duration::from_secs(60)),
+            )
+            .with_max_missed_pongs(1),
+            activity.clone(),
+        ));
+
+        // Messages sent every 5s also keep the connection from being pinged
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            activity.record_sent();
+        }
+        assert!(ws_sink_rx.try_recv().is_err());
+
+        // Idle in both directions: pinged once the idle period has elapsed
+        tokio::time::sleep(Duration::from_millis(10_100)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+
+        // Ping answered by market data rather than a pong does not expire the connection
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        activity.record();
+        tokio::time::sleep(Duration::from_millis(10_100)).await;
+        assert_eq!(ws_sink_rx.try_recv().unwrap(), ping());
+        assert!(!activity.is_expired());
+
+        // Unanswered ping expires the connection
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert!(ws_sink_rx.try_recv().is_err());
+        assert!(activity.is_expired());
+        pinger.await.unwrap();
+    }
+}
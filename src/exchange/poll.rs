@@ -0,0 +1,30 @@
+use super::Connector;
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    streams::config::StreamConfig,
+    subscription::{SubKind, Subscription},
+};
+use async_trait::async_trait;
+
+/// Defines how an exchange fetches the current [`MarketEvent<T>`](MarketEvent)s of a
+/// [`Subscription`] via it's REST API, for market data without a WebSocket channel (eg/ open
+/// interest).
+///
+/// Used by the [`PollingStream`](crate::streams::polling::PollingStream) that is the
+/// [`StreamSelector::Stream`](super::StreamSelector::Stream) of such [`SubKind`]s, which
+/// periodically polls each [`Subscription`] & drops unchanged values.
+#[async_trait]
+pub trait Poll<Kind>
+where
+    Self: Connector + Sync,
+    Kind: SubKind + Sync,
+{
+    /// Fetch the current [`MarketEvent<T>`](MarketEvent)s for the provided [`Subscription`],
+    /// ordered by `exchange_time` ascending, using the REST configuration (eg/
+    /// [`Proxy`](crate::subscriber::proxy::Proxy)) of the provided [`StreamConfig`].
+    async fn poll(
+        subscription: &Subscription<Self, Kind>,
+        config: &StreamConfig,
+    ) -> Result<Vec<MarketEvent<Kind::Event>>, DataError>;
+}
@@ -0,0 +1,486 @@
+use super::{
+    ping::{ActivityStream, PongStream},
+    ExchangeId,
+};
+use crate::subscriber::buffer::BufferedStream;
+use barter_integration::protocol::websocket::{WsError, WsMessage};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tracing::{info, warn};
+
+/// Default [`Duration`] the [`consume`](crate::streams::consumer::consume) loop waits between
+/// connection attempts whilst an exchange reports [`SystemStatus::Maintenance`], rather than
+/// rapidly retrying with the exponential reconnect backoff.
+pub const DEFAULT_MAINTENANCE_PROBE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Callback fired with each [`ExchangeStatus`] reported by an exchange, see
+/// [`StatusConfig::observer`].
+pub type OnExchangeStatus = Arc<dyn Fn(ExchangeId, &ExchangeStatus) + Send + Sync>;
+
+/// Normalised Barter system status of an exchange, reported via exchange specific status &
+/// maintenance messages (eg/ Kraken `systemStatus`).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemStatus {
+    /// Exchange is fully available.
+    #[default]
+    Online,
+    /// Exchange is unavailable for market data, eg/ connections are closed for maintenance.
+    Maintenance,
+    /// Only order cancellations are accepted, market data continues as normal.
+    CancelOnly,
+    /// Only limit orders are accepted, market data continues as normal.
+    LimitOnly,
+    /// Only post-only orders are accepted, market data continues as normal.
+    PostOnly,
+    /// Informational notice that does not change availability, eg/ scheduled maintenance or an
+    /// upcoming disconnect for a service upgrade.
+    Notice,
+    /// Status the exchange reported that is not recognised.
+    Unknown,
+}
+
+impl SystemStatus {
+    /// Determines if the exchange is unavailable for market data subscriptions.
+    pub fn is_maintenance(&self) -> bool {
+        matches!(self, Self::Maintenance)
+    }
+
+    /// Most recent [`SystemStatus`] reported by the provided exchange, defaulting to
+    /// [`SystemStatus::Online`].
+    pub fn current(exchange: ExchangeId) -> Self {
+        registry().current(exchange)
+    }
+}
+
+impl Display for SystemStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Online => "online",
+                Self::Maintenance => "maintenance",
+                Self::CancelOnly => "cancel_only",
+                Self::LimitOnly => "limit_only",
+                Self::PostOnly => "post_only",
+                Self::Notice => "notice",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+/// Normalised Barter exchange status diagnostic, recognised from an exchange specific status or
+/// maintenance message by [`Connector::exchange_status`](super::Connector::exchange_status).
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct ExchangeStatus {
+    pub status: SystemStatus,
+    /// Human readable detail provided by the exchange, if any (eg/ a maintenance notice title).
+    pub message: Option<String>,
+}
+
+impl ExchangeStatus {
+    /// Construct a new [`Self`] using the provided [`SystemStatus`] & optional message.
+    pub fn new<S>(status: SystemStatus, message: Option<S>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            status,
+            message: message.map(Into::into),
+        }
+    }
+}
+
+/// Exchange status handling configuration of a
+/// [`StreamBuilder`](crate::streams::builder::StreamBuilder).
+///
+/// Set via
+/// [`StreamBuilder::maintenance_probe_interval`](crate::streams::builder::StreamBuilder::maintenance_probe_interval)
+/// & [`StreamBuilder::on_exchange_status`](crate::streams::builder::StreamBuilder::on_exchange_status).
+#[derive(Clone, Default)]
+pub struct StatusConfig {
+    /// Per exchange interval between connection attempts whilst the exchange reports
+    /// [`SystemStatus::Maintenance`], or `None` to keep using the exponential reconnect backoff.
+    /// Exchanges without an entry use the [`DEFAULT_MAINTENANCE_PROBE_INTERVAL`].
+    pub probe_intervals: HashMap<ExchangeId, Option<Duration>>,
+    /// Callback fired with each [`ExchangeStatus`] reported by an exchange, on the consumer loop
+    /// task of the connection that received it, so it should be cheap.
+    pub observer: Option<OnExchangeStatus>,
+}
+
+impl std::fmt::Debug for StatusConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusConfig")
+            .field("probe_intervals", &self.probe_intervals)
+            .field("has_observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl StatusConfig {
+    /// Determine the maintenance probe interval of the provided exchange.
+    pub fn probe_interval(&self, exchange: ExchangeId) -> Option<Duration> {
+        self.probe_intervals
+            .get(&exchange)
+            .copied()
+            .unwrap_or(Some(DEFAULT_MAINTENANCE_PROBE_INTERVAL))
+    }
+
+    /// Resolve the [`StatusHandling`] of the provided exchange.
+    pub fn handling(&self, exchange: ExchangeId) -> StatusHandling {
+        StatusHandling {
+            probe_interval: self.probe_interval(exchange),
+            observer: self.observer.clone(),
+        }
+    }
+}
+
+/// Exchange status handling of a connection, resolved from a [`StatusConfig`] & handed to it via
+/// it's [`StreamConfig`](crate::streams::config::StreamConfig).
+#[derive(Clone)]
+pub struct StatusHandling {
+    /// Interval between connection attempts whilst the exchange reports
+    /// [`SystemStatus::Maintenance`], or `None` to keep using the exponential reconnect backoff.
+    pub probe_interval: Option<Duration>,
+    /// Callback fired with each [`ExchangeStatus`] reported by the exchange.
+    pub observer: Option<OnExchangeStatus>,
+}
+
+impl std::fmt::Debug for StatusHandling {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusHandling")
+            .field("probe_interval", &self.probe_interval)
+            .field("has_observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Default for StatusHandling {
+    fn default() -> Self {
+        Self {
+            probe_interval: Some(DEFAULT_MAINTENANCE_PROBE_INTERVAL),
+            observer: None,
+        }
+    }
+}
+
+impl StatusHandling {
+    /// Record the provided [`ExchangeStatus`] reported by the provided exchange, notifying the
+    /// [`StatusHandling::observer`] (if any).
+    pub fn record(&self, exchange: ExchangeId, status: ExchangeStatus) {
+        let previous = registry().record(exchange, status.status);
+
+        if status.status.is_maintenance() {
+            warn!(
+                %exchange,
+                status = %status.status,
+                message = ?status.message,
+                "exchange reported it's under maintenance"
+            );
+        } else {
+            info!(
+                %exchange,
+                status = %status.status,
+                %previous,
+                message = ?status.message,
+                "exchange reported it's system status"
+            );
+        }
+
+        if let Some(observer) = &self.observer {
+            observer(exchange, &status)
+        }
+    }
+
+    /// Record the provided exchange as [`SystemStatus::Online`] if it's currently under
+    /// [`SystemStatus::Maintenance`], eg/ once subscriptions are validated again.
+    pub fn clear_maintenance(&self, exchange: ExchangeId) {
+        if registry().current(exchange).is_maintenance() {
+            self.record(
+                exchange,
+                ExchangeStatus::new(SystemStatus::Online, Some("subscriptions validated")),
+            );
+        }
+    }
+
+    /// Determine the [`Duration`] to wait before the next connection attempt to the provided
+    /// exchange if it's under [`SystemStatus::Maintenance`], using the
+    /// [`StatusHandling::probe_interval`].
+    ///
+    /// Returns `None` if the exchange is not under maintenance, or maintenance probing is
+    /// disabled.
+    pub fn maintenance_backoff(&self, exchange: ExchangeId) -> Option<Duration> {
+        probe_backoff(SystemStatus::current(exchange), self.probe_interval)
+    }
+}
+
+/// Most recent [`SystemStatus`] reported by each exchange.
+#[derive(Debug, Default)]
+struct StatusRegistry(Mutex<HashMap<ExchangeId, SystemStatus>>);
+
+impl StatusRegistry {
+    fn current(&self, exchange: ExchangeId) -> SystemStatus {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&exchange)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Record the provided [`SystemStatus`], returning the previous [`SystemStatus`].
+    fn record(&self, exchange: ExchangeId, status: SystemStatus) -> SystemStatus {
+        let mut statuses = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match status {
+            // Notices don't change availability, so the current status is retained
+            SystemStatus::Notice => statuses.get(&exchange).copied().unwrap_or_default(),
+            status => statuses.insert(exchange, status).unwrap_or_default(),
+        }
+    }
+}
+
+fn registry() -> &'static StatusRegistry {
+    static REGISTRY: OnceLock<StatusRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(StatusRegistry::default)
+}
+
+/// Determine the maintenance backoff of the provided [`SystemStatus`] & probe interval.
+fn probe_backoff(status: SystemStatus, probe_interval: Option<Duration>) -> Option<Duration> {
+    status.is_maintenance().then_some(probe_interval).flatten()
+}
+
+/// Recogniser of exchange specific status messages, see
+/// [`Connector::exchange_status`](super::Connector::exchange_status).
+pub type StatusRecogniser = fn(&str) -> Option<ExchangeStatus>;
+
+/// [`Stream`] wrapper that intercepts the exchange status & maintenance messages recognised by
+/// the [`Connector::exchange_status`](super::Connector::exchange_status) of it's exchange,
+/// [`record`](StatusHandling::record)ing them as [`ExchangeStatus`]es rather than yielding them
+/// to the parser (where they'd fail to deserialise or be dropped).
+///
+/// Used as the inner [`Stream`] of an [`ExchangeWsStream`](crate::ExchangeWsStream).
+#[derive(Debug)]
+pub struct StatusStream<InnerStream = PongStream<ActivityStream<BufferedStream>>> {
+    pub exchange: ExchangeId,
+    recognise: StatusRecogniser,
+    handling: StatusHandling,
+    pub stream: InnerStream,
+}
+
+impl<InnerStream> StatusStream<InnerStream> {
+    /// Construct a new [`Self`] wrapping the provided [`Stream`], recording exchange statuses
+    /// using the provided [`StatusHandling`].
+    pub fn new(
+        exchange: ExchangeId,
+        recognise: StatusRecogniser,
+        handling: StatusHandling,
+        stream: InnerStream,
+    ) -> Self {
+        Self {
+            exchange,
+            recognise,
+            handling,
+            stream,
+        }
+    }
+}
+
+impl<InnerStream> Stream for StatusStream<InnerStream>
+where
+    InnerStream: Stream<Item = Result<(DateTime<Utc>, WsMessage), WsError>> + Unpin,
+{
+    type Item = Result<(DateTime<Utc>, WsMessage), WsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok((received_time, WsMessage::Text(text))))) => {
+                    match (this.recognise)(&text) {
+                        Some(status) => this.handling.record(this.exchange, status),
+                        None => {
+                            return Poll::Ready(Some(Ok((received_time, WsMessage::Text(text)))))
+                        }
+                    }
+                }
+                poll => return poll,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_backoff() {
+        struct TestCase {
+            status: SystemStatus,
+            probe_interval: Option<Duration>,
+            expected: Option<Duration>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: online uses exponential reconnect backoff
+                status: SystemStatus::Online,
+                probe_interval: Some(DEFAULT_MAINTENANCE_PROBE_INTERVAL),
+                expected: None,
+            },
+            TestCase {
+                // TC1: maintenance uses the probe interval
+                status: SystemStatus::Maintenance,
+                probe_interval: Some(Duration::from_secs(5)),
+                expected: Some(Duration::from_secs(5)),
+            },
+            TestCase {
+                // TC2: maintenance w/ probing disabled uses exponential reconnect backoff
+                status: SystemStatus::Maintenance,
+                probe_interval: None,
+                expected: None,
+            },
+            TestCase {
+                // TC3: cancel_only still serves market data
+                status: SystemStatus::CancelOnly,
+                probe_interval: Some(DEFAULT_MAINTENANCE_PROBE_INTERVAL),
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = probe_backoff(test.status, test.probe_interval);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_status_registry_record() {
+        let registry = StatusRegistry::default();
+        assert_eq!(registry.current(ExchangeId::Okx), SystemStatus::Online);
+
+        // Notices retain the current status
+        registry.record(ExchangeId::Okx, SystemStatus::Maintenance);
+        registry.record(ExchangeId::Okx, SystemStatus::Notice);
+        assert_eq!(registry.current(ExchangeId::Okx), SystemStatus::Maintenance);
+        assert_eq!(registry.current(ExchangeId::Coinbase), SystemStatus::Online);
+
+        assert_eq!(
+            registry.record(ExchangeId::Okx, SystemStatus::Online),
+            SystemStatus::Maintenance
+        );
+        assert_eq!(registry.current(ExchangeId::Okx), SystemStatus::Online);
+    }
+
+    #[test]
+    fn test_maintenance_backoff_suppresses_reconnects() {
+        // ExchangeId only used by this test, since the registry is global
+        let exchange = ExchangeId::BinanceUs;
+        let handling = StatusHandling::default();
+
+        // Online exchanges reconnect with the exponential backoff
+        assert_eq!(handling.maintenance_backoff(exchange), None);
+
+        handling.record(
+            exchange,
+            ExchangeStatus::new(SystemStatus::Maintenance, None::<String>),
+        );
+        assert_eq!(
+            handling.maintenance_backoff(exchange),
+            Some(DEFAULT_MAINTENANCE_PROBE_INTERVAL)
+        );
+
+        let configured = StatusConfig {
+            probe_intervals: HashMap::from([(exchange, Some(Duration::from_secs(5)))]),
+            observer: None,
+        };
+        assert_eq!(
+            configured.handling(exchange).maintenance_backoff(exchange),
+            Some(Duration::from_secs(5))
+        );
+
+        let disabled = StatusConfig {
+            probe_intervals: HashMap::from([(exchange, None)]),
+            observer: None,
+        };
+        assert_eq!(
+            disabled.handling(exchange).maintenance_backoff(exchange),
+            None
+        );
+
+        // Validated subscriptions end maintenance
+        handling.clear_maintenance(exchange);
+        assert_eq!(SystemStatus::current(exchange), SystemStatus::Online);
+        assert_eq!(handling.maintenance_backoff(exchange), None);
+    }
+
+    #[tokio::test]
+    async fn test_status_stream_intercepts_status_messages() {
+        fn recognise(text: &str) -> Option<ExchangeStatus> {
+            text.strip_prefix("status:")
+                .map(|message| ExchangeStatus::new(SystemStatus::Notice, Some(message)))
+        }
+
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let config = StatusConfig {
+            probe_intervals: HashMap::new(),
+            observer: Some(Arc::new({
+                let observed = Arc::clone(&observed);
+                move |exchange, status: &ExchangeStatus| {
+                    observed.lock().unwrap().push((exchange, status.clone()))
+                }
+            })),
+        };
+
+        let inner = futures::stream::iter(
+            [
+                WsMessage::Text("0".to_string()),
+                WsMessage::Text("status:upgrade".to_string()),
+                WsMessage::Binary(b"status:binary".to_vec()),
+                WsMessage::Text("1".to_string()),
+            ]
+            .map(|message| Ok((Utc::now(), message))),
+        );
+        let actual = StatusStream::new(
+            ExchangeId::GateioSpot,
+            recognise,
+            config.handling(ExchangeId::GateioSpot),
+            inner,
+        )
+        .map(|message| message.unwrap().1)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                WsMessage::Text("0".to_string()),
+                WsMessage::Binary(b"status:binary".to_vec()),
+                WsMessage::Text("1".to_string()),
+            ]
+        );
+        assert_eq!(
+            *observed.lock().unwrap(),
+            vec![(
+                ExchangeId::GateioSpot,
+                ExchangeStatus::new(SystemStatus::Notice, Some("upgrade"))
+            )]
+        );
+    }
+}
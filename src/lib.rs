@@ -84,19 +84,38 @@
 use crate::{
     error::DataError,
     event::MarketEvent,
-    exchange::{Connector, ExchangeId, PingInterval},
-    subscriber::Subscriber,
-    subscription::{SubKind, Subscription},
+    exchange::{
+        ping::{self, ActivityStream, ConnectionActivity, PongStream},
+        status::StatusStream,
+        Connector, ExchangeId, PingInterval,
+    },
+    parser::{strict::StrictStream, WsParser},
+    streams::config::StreamConfig,
+    subscriber::{buffer::BufferedStream, transport::WsSink, Subscriber},
+    subscription::{Map, SubKind, Subscription},
     transformer::ExchangeTransformer,
 };
 use async_trait::async_trait;
 use barter_integration::{
-    protocol::websocket::{WebSocketParser, WsMessage, WsSink, WsStream},
-    ExchangeStream,
+    error::SocketError,
+    model::Instrument,
+    protocol::{websocket::WsMessage, StreamParser},
 };
+use chrono::{DateTime, Utc};
 use futures::{SinkExt, Stream, StreamExt};
-use tokio::sync::mpsc;
-use tracing::{debug, error};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::Instant};
+use tracing::{debug, debug_span, error, warn, Instrument as _};
+
+/// Direct conversions of exchange epoch timestamps into [`DateTime<Utc>`](chrono::DateTime),
+/// and the serde deserializers used by every exchange model.
+pub mod datetime;
 
 /// All [`Error`](std::error::Error)s generated in Barter-Data.
 pub mod error;
@@ -107,6 +126,40 @@ pub mod event;
 /// [`Connector`] implementations for each exchange.
 pub mod exchange;
 
+/// Prometheus [`MetricsRegistry`](metrics::MetricsRegistry) recording connection, throughput &
+/// latency metrics of every [`MarketStream`] consumer loop.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// [`Num`](num::Num) type used for normalised trade & OrderBook prices and amounts, configurable
+/// via the `decimal` feature.
+pub mod num;
+
+/// [`WsParser`](parser::WsParser) that deserialises WebSocket messages into exchange specific
+/// messages via a single JSON backend entry point, which is `simd_json` if the `simd-json`
+/// feature is enabled.
+pub mod parser;
+
+/// Convenient re-exports of the builder, exchange connectors, [`SubKind`]s, normalised data
+/// models and stream adapters, so `use barter_data::prelude::*` suffices for the common case.
+pub mod prelude;
+
+/// Protobuf schema & wire encoding of [`MarketEvent<DataKind>`](event::MarketEvent), used by
+/// [`MarketEvent::to_protobuf`](event::MarketEvent::to_protobuf) for compact, language agnostic
+/// transport. Requires the `proto` feature.
+#[cfg(feature = "proto")]
+pub mod protobuf;
+
+/// [`JsonlRecorder`](recorder::JsonlRecorder) that records
+/// [`MarketEvent<DataKind>`](event::MarketEvent)s to rotating JSON Lines files on disk, and the
+/// [`ReplayStream`](recorder::replay::ReplayStream) that replays them.
+pub mod recorder;
+
+/// [`RebroadcastServer`](server::RebroadcastServer) that fans out
+/// [`MarketEvent<DataKind>`](event::MarketEvent)s to local WebSocket clients.
+#[cfg(feature = "server")]
+pub mod server;
+
 /// High-level API types used for building [`MarketStream`]s from collections
 /// of Barter [`Subscription`]s.
 pub mod streams;
@@ -122,6 +175,12 @@ pub mod subscriber;
 /// Barter output type the exchange will be transformed into.
 pub mod subscription;
 
+/// [`MockExchangeServer`](test_util::MockExchangeServer) that plays scripted WebSocket frames to
+/// the production [`Connector`]s, for integration testing without hitting live exchanges, and
+/// the captured exchange message [`fixture`](test_util::fixture) corpus harness.
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
 /// Generic [`ExchangeTransformer`] implementations used by [`MarketStream`]s to translate exchange
 /// specific types to normalised Barter types.
 ///
@@ -134,9 +193,88 @@ pub mod subscription;
 ///   [`OrderBooksL3`](crate::subscription::book::OrderBooksL3) streams.
 pub mod transformer;
 
-/// Convenient type alias for an [`ExchangeStream`] utilising a tungstenite
-/// [`WebSocket`](barter_integration::protocol::websocket::WebSocket).
-pub type ExchangeWsStream<Transformer> = ExchangeStream<WebSocketParser, WsStream, Transformer>;
+/// [`Stream`] of transformed exchange messages utilising a tungstenite
+/// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) and the [`WsParser`].
+///
+/// The inner [`WsStream`](barter_integration::protocol::websocket::WsStream) is wrapped in a
+/// [`BufferedStream`] that first replays any market data buffered whilst validating
+/// subscriptions, an [`ActivityStream`] so received traffic can drive adaptive pings, a
+/// [`PongStream`] that records application-level pongs, a [`StatusStream`] that records
+/// exchange status & maintenance messages, and a [`StrictStream`] that reports unknown fields in
+/// [`DeserialiseMode::Strict`](parser::strict::DeserialiseMode::Strict).
+///
+/// Each message is deserialised into the exchange specific `Message` & passed to the
+/// `Transformer` as a `(received_time, Message)` input, where `received_time` is the time the
+/// message was read from the [`WebSocket`](barter_integration::protocol::websocket::WebSocket)
+/// (or buffered), so the transform duration is excluded from event latency.
+#[derive(Debug)]
+pub struct ExchangeWsStream<Transformer>
+where
+    Transformer: barter_integration::Transformer,
+{
+    pub stream: StrictStream<StatusStream<PongStream<ActivityStream<BufferedStream>>>>,
+    pub transformer: Transformer,
+    pub buffer: VecDeque<Result<Transformer::Output, Transformer::Error>>,
+}
+
+impl<Transformer> ExchangeWsStream<Transformer>
+where
+    Transformer: barter_integration::Transformer,
+{
+    /// Construct a new [`Self`] transforming the messages of the provided inner stream.
+    pub fn new(
+        stream: StrictStream<StatusStream<PongStream<ActivityStream<BufferedStream>>>>,
+        transformer: Transformer,
+    ) -> Self {
+        Self {
+            stream,
+            transformer,
+            buffer: VecDeque::with_capacity(6),
+        }
+    }
+}
+
+// No field is structurally pinned, the inner stream is always polled via `poll_next_unpin`
+impl<Transformer> Unpin for ExchangeWsStream<Transformer> where
+    Transformer: barter_integration::Transformer
+{
+}
+
+impl<Transformer, Message> Stream for ExchangeWsStream<Transformer>
+where
+    Transformer: barter_integration::Transformer<Input = (DateTime<Utc>, Message)>,
+    Transformer::Error: From<SocketError>,
+    Message: DeserializeOwned,
+{
+    type Item = Result<Transformer::Output, Transformer::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(output) = this.buffer.pop_front() {
+                return Poll::Ready(Some(output));
+            }
+
+            let (received_time, message) = match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(input))) => input,
+                Poll::Ready(Some(Err(error))) => {
+                    return Poll::Ready(Some(Err(SocketError::WebSocket(error).into())))
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let message = match WsParser::parse::<Message>(Ok(message)) {
+                Some(Ok(message)) => message,
+                Some(Err(error)) => return Poll::Ready(Some(Err(error.into()))),
+                None => continue,
+            };
+
+            let outputs = this.transformer.transform((received_time, message));
+            this.buffer.extend(outputs);
+        }
+    }
+}
 
 /// Defines a generic identification type for the implementor.
 pub trait Identifier<T> {
@@ -152,55 +290,198 @@ where
     Exchange: Connector,
     Kind: SubKind,
 {
+    /// Initialise [`Self`] using the [`StreamConfig::default`].
     async fn init(subscriptions: &[Subscription<Exchange, Kind>]) -> Result<Self, DataError>
+    where
+        Exchange: Sync,
+        Kind: Sync,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        Self::init_with(subscriptions, &StreamConfig::default()).await
+    }
+
+    /// Initialise [`Self`] using the provided per connection [`StreamConfig`].
+    async fn init_with(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        config: &StreamConfig,
+    ) -> Result<Self, DataError>
     where
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>;
+
+    /// Collect the next `n` [`MarketEvent<T>`](MarketEvent)s yielded by this [`MarketStream`],
+    /// before closing the connection.
+    ///
+    /// Non-terminal errors are skipped, whereas a terminal error is returned immediately. If the
+    /// [`MarketStream`] ends before `n` events are collected, the events collected so far are
+    /// returned. Useful for tests & scripts that only require a sample of market data.
+    async fn take_events(mut self, n: usize) -> Result<Vec<MarketEvent<Kind::Event>>, DataError>
+    where
+        Kind::Event: Send,
+    {
+        let mut events = Vec::with_capacity(n);
+
+        while events.len() < n {
+            match self.next().await {
+                Some(Ok(event)) => events.push(event),
+                Some(Err(error)) if error.is_terminal() => return Err(error),
+                Some(Err(error)) => debug!(%error, "skipping non-terminal MarketStream error"),
+                None => break,
+            }
+        }
+
+        // Dropping the MarketStream closes the WebSocket connection with a close frame
+        drop(self);
+        Ok(events)
+    }
+
+    /// Collect every [`MarketEvent<T>`](MarketEvent) yielded by this [`MarketStream`] until the
+    /// provided `duration` has elapsed, before closing the connection.
+    ///
+    /// Errors are handled the same as [`take_events`](MarketStream::take_events).
+    async fn take_until_deadline(
+        mut self,
+        duration: Duration,
+    ) -> Result<Vec<MarketEvent<Kind::Event>>, DataError>
+    where
+        Kind::Event: Send,
+    {
+        let deadline = Instant::now() + duration;
+        let mut events = Vec::new();
+
+        while let Ok(next) = tokio::time::timeout_at(deadline, self.next()).await {
+            match next {
+                Some(Ok(event)) => events.push(event),
+                Some(Err(error)) if error.is_terminal() => return Err(error),
+                Some(Err(error)) => debug!(%error, "skipping non-terminal MarketStream error"),
+                None => break,
+            }
+        }
+
+        // Dropping the MarketStream closes the WebSocket connection with a close frame
+        drop(self);
+        Ok(events)
+    }
 }
 
 #[async_trait]
-impl<Exchange, Kind, Transformer> MarketStream<Exchange, Kind> for ExchangeWsStream<Transformer>
+impl<Exchange, Kind, Transformer, Message> MarketStream<Exchange, Kind>
+    for ExchangeWsStream<Transformer>
 where
     Exchange: Connector + Send + Sync,
     Kind: SubKind + Send + Sync,
-    Transformer: ExchangeTransformer<Exchange, Kind> + Send,
+    Transformer: ExchangeTransformer<Exchange, Kind, Input = (DateTime<Utc>, Message)> + Send,
+    Message: DeserializeOwned,
     Kind::Event: Send,
 {
-    async fn init(subscriptions: &[Subscription<Exchange, Kind>]) -> Result<Self, DataError>
+    async fn init_with(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        config: &StreamConfig,
+    ) -> Result<Self, DataError>
     where
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
     {
         // Connect & subscribe
-        let (websocket, map) = Exchange::Subscriber::subscribe(subscriptions).await?;
+        let (ws_stream, ws_sink_tx, map) = connect(subscriptions, config).await?;
+
+        // Construct Transformer associated with this Exchange and SubKind
+        let transformer = Transformer::new(ws_sink_tx, map, config)
+            .instrument(debug_span!("transformer"))
+            .await?;
+        debug!(exchange = %Exchange::ID, "initialised MarketStream transformer");
 
-        // Split WebSocket into WsStream & WsSink components
-        let (ws_sink, ws_stream) = websocket.split();
+        Ok(ExchangeWsStream::new(
+            StrictStream::new::<Message>(
+                Exchange::ID,
+                parser::strict::channel(subscriptions),
+                config.deserialise_mode,
+                ws_stream,
+            ),
+            transformer,
+        ))
+    }
+}
+
+/// Connect & subscribe to the provided [`Subscription`]s using the provided [`StreamConfig`],
+/// returning the inner stream of an [`ExchangeWsStream`], the [`mpsc::UnboundedSender`] used to
+/// send messages to the exchange, and the [`Map<Instrument>`] of the [`Subscription`]s.
+///
+/// Spawns the tasks that distribute messages (and any custom application-level pings) to the
+/// exchange, which end once the returned [`mpsc::UnboundedSender`] has been dropped.
+pub(crate) async fn connect<Exchange, Kind>(
+    subscriptions: &[Subscription<Exchange, Kind>],
+    config: &StreamConfig,
+) -> Result<
+    (
+        StatusStream<PongStream<ActivityStream<BufferedStream>>>,
+        mpsc::UnboundedSender<WsMessage>,
+        Map<Instrument>,
+    ),
+    DataError,
+>
+where
+    Exchange: Connector + Send + Sync,
+    Kind: SubKind + Send + Sync,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    // Connect & subscribe
+    let (websocket, map, buffer) = Exchange::Subscriber::subscribe(subscriptions, config)
+        .instrument(debug_span!("subscribe"))
+        .await?;
 
-        // Spawn task to distribute Transformer messages (eg/ custom pongs) to the exchange
-        let (ws_sink_tx, ws_sink_rx) = mpsc::unbounded_channel();
-        tokio::spawn(distribute_messages_to_exchange(
+    // Split WebSocket into WsStream & WsSink components
+    let (ws_sink, ws_stream) = websocket.split();
+
+    // Track when messages are received from & sent to the exchange (eg/ for adaptive pings)
+    let activity = ConnectionActivity::default();
+
+    // Spawn task to distribute Transformer messages (eg/ custom pongs) to the exchange
+    let (ws_sink_tx, ws_sink_rx) = mpsc::unbounded_channel();
+    tokio::spawn(
+        distribute_messages_to_exchange(Exchange::ID, ws_sink, ws_sink_rx, activity.clone())
+            .in_current_span(),
+    );
+    // '--> market data buffered whilst validating subscriptions is replayed first
+    // '--> application-level pongs are recorded rather than parsed
+    // '--> exchange status & maintenance messages are recorded rather than parsed
+    let ws_stream = StatusStream::new(
+        Exchange::ID,
+        Exchange::exchange_status,
+        config.status.clone(),
+        PongStream::new(
             Exchange::ID,
-            ws_sink,
-            ws_sink_rx,
-        ));
+            Exchange::pong,
+            ActivityStream::new(
+                Exchange::ID,
+                buffer.into_stream(ws_stream),
+                activity.clone(),
+            ),
+            activity.clone(),
+            ws_sink_tx.clone(),
+        ),
+    );
 
-        // Spawn optional task to distribute custom application-level pings to the exchange
-        if let Some(ping_interval) = Exchange::ping_interval() {
-            tokio::spawn(schedule_pings_to_exchange(
+    // Spawn optional task to distribute custom application-level pings to the exchange
+    // '--> only once subscribed, so pings are never sent mid login & subscription handshake
+    if let Some(ping_interval) = Exchange::ping_interval() {
+        tokio::spawn(
+            schedule_pings_to_exchange(
                 Exchange::ID,
-                ws_sink_tx.clone(),
+                ws_sink_tx.downgrade(),
                 ping_interval,
-            ));
-        }
-
-        // Construct Transformer associated with this Exchange and SubKind
-        let transformer = Transformer::new(ws_sink_tx, map).await?;
-
-        Ok(ExchangeWsStream::new(ws_stream, transformer))
+                activity,
+            )
+            .in_current_span(),
+        );
     }
+
+    Ok((ws_stream, ws_sink_tx, map))
 }
 
 /// Transmit [`WsMessage`]s sent from the [`ExchangeTransformer`] to the exchange via
-/// the [`WsSink`].
+/// the [`WsSink`], recording each sent message in the [`ConnectionActivity`].
+///
+/// Once every [`mpsc::UnboundedSender`] has been dropped (ie/ the [`MarketStream`] has been
+/// dropped), the [`WsSink`] is closed, sending a close frame to the exchange.
 ///
 /// **Note:**
 /// ExchangeTransformer is operating in a synchronous trait context so we use this separate task
@@ -209,21 +490,30 @@ pub async fn distribute_messages_to_exchange(
     exchange: ExchangeId,
     mut ws_sink: WsSink,
     mut ws_sink_rx: mpsc::UnboundedReceiver<WsMessage>,
+    activity: ConnectionActivity,
 ) {
     while let Some(message) = ws_sink_rx.recv().await {
-        if let Err(error) = ws_sink.send(message).await {
-            if barter_integration::protocol::websocket::is_websocket_disconnected(&error) {
-                break;
-            }
+        match ws_sink.send(message).await {
+            Ok(()) => activity.record_sent(),
+            Err(error) => {
+                if barter_integration::protocol::websocket::is_websocket_disconnected(&error) {
+                    break;
+                }
 
-            // Log error only if WsMessage failed to send over a connected WebSocket
-            error!(
-                %exchange,
-                %error,
-                "failed to send  output message to the exchange via WsSink"
-            );
+                // Log error only if WsMessage failed to send over a connected WebSocket
+                error!(
+                    %exchange,
+                    %error,
+                    "failed to send  output message to the exchange via WsSink"
+                );
+            }
         }
     }
+
+    // Cleanly close the WebSocket connection
+    if let Err(error) = ws_sink.close().await {
+        debug!(%exchange, %error, "failed to send close frame to the exchange via WsSink");
+    }
 }
 
 /// Schedule the sending of custom application-level ping [`WsMessage`]s to the exchange using
@@ -232,14 +522,77 @@ pub async fn distribute_messages_to_exchange(
 /// **Notes:**
 ///  - This is only used for those exchanges that require custom application-level pings.
 ///  - This is additional to the protocol-level pings already handled by `tokio_tungstenite`.
+///  - A [`mpsc::WeakUnboundedSender`] is used so scheduled pings do not keep the connection
+///    alive once the [`MarketStream`] has been dropped.
+///  - Pings are only scheduled once the connection is subscribed, so they're never sent in the
+///    middle of the login & subscription handshake.
+///  - Adaptive [`PingInterval`]s only ping once no message has been received or sent (as
+///    recorded by the [`ConnectionActivity`]) for the idle period, so busy connections are never
+///    pinged.
+///  - [`PingInterval`]s with a [`MissedPongs`](ping::MissedPongs) policy expire the
+///    [`ConnectionActivity`] (ending the connection) instead of sending the next ping once too
+///    many consecutive pings have not been answered by a pong, or any other message.
 pub async fn schedule_pings_to_exchange(
     exchange: ExchangeId,
-    ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
-    PingInterval { mut interval, ping }: PingInterval,
+    ws_sink_tx: mpsc::WeakUnboundedSender<WsMessage>,
+    PingInterval {
+        mut interval,
+        ping,
+        adaptive,
+        mut missed_pongs,
+    }: PingInterval,
+    activity: ConnectionActivity,
 ) {
+    let mut last_ping = Instant::now();
+
     loop {
         // Wait for next scheduled ping
-        interval.tick().await;
+        match adaptive {
+            None => {
+                interval.tick().await;
+            }
+            Some(adaptive) => {
+                // Idle period is shortened if the connection to this exchange is marginal
+                let idle = adaptive.idle_period(
+                    interval.period(),
+                    ping::recent_disconnects(exchange, adaptive.reconnect_window),
+                );
+
+                // Reset the ping timer if a message (or ping) has been received or sent during the
+                // idle period
+                let deadline = activity
+                    .last_activity()
+                    .max(activity.last_sent())
+                    .max(last_ping)
+                    + idle;
+                if Instant::now() < deadline {
+                    tokio::time::sleep_until(deadline).await;
+                    continue;
+                }
+            }
+        }
+
+        // Stop pinging if the MarketStream has been dropped
+        let Some(ws_sink_tx) = ws_sink_tx.upgrade() else {
+            break;
+        };
+
+        // Expire the connection if the exchange has stopped answering pings
+        if let Some(missed_pongs) = missed_pongs.as_mut() {
+            // Any message received since the previous ping also proves the connection is alive
+            if activity.last_activity() > last_ping {
+                missed_pongs.answered();
+            }
+            if missed_pongs.ping(activity.pongs()) {
+                warn!(
+                    %exchange,
+                    max_missed = missed_pongs.max_missed,
+                    "exchange missed too many application-level pongs, expiring connection"
+                );
+                activity.expire();
+                break;
+            }
+        }
 
         // Construct exchange custom application-level ping payload
         let payload = ping();
@@ -248,5 +601,6 @@ pub async fn schedule_pings_to_exchange(
         if ws_sink_tx.send(payload).is_err() {
             break;
         }
+        last_ping = Instant::now();
     }
 }
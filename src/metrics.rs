@@ -0,0 +1,783 @@
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    exchange::ExchangeId,
+    streams::{
+        inbound::Admission,
+        latency::{LatencySnapshot, LatencyTracker, PublishedLatency},
+    },
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Arc, Mutex, OnceLock, Weak,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, ToSocketAddrs},
+    task::JoinHandle,
+};
+use tracing::{debug, info};
+
+/// Number of messages (parsed events & errors) yielded by each exchange [`MarketStream`](crate::MarketStream).
+pub const MESSAGES_RECEIVED: &str = "barter_data_messages_received_total";
+
+/// Number of [`MarketEvent`]s distributed downstream.
+pub const EVENTS_EMITTED: &str = "barter_data_events_emitted_total";
+
+/// Number of non-terminal errors, eg/ messages that failed to deserialise.
+pub const PARSE_ERRORS: &str = "barter_data_parse_errors_total";
+
+/// Number of re-connections attempted after a [`MarketStream`](crate::MarketStream) ended.
+pub const RECONNECTS: &str = "barter_data_reconnects_total";
+
+/// Number of [`MarketEvent`]s dropped because the downstream receiver was dropped.
+pub const DROPPED_EVENTS: &str = "barter_data_dropped_events_total";
+
+/// Number of [`Subscription`](crate::subscription::Subscription)s on connected streams.
+pub const ACTIVE_SUBSCRIPTIONS: &str = "barter_data_active_subscriptions";
+
+/// Number of connected exchange WebSocket connections.
+pub const CONNECTIONS_UP: &str = "barter_data_connections_up";
+
+/// Latency in seconds between the exchange_time & received_time of each [`MarketEvent`].
+pub const EVENT_LATENCY_SECONDS: &str = "barter_data_event_latency_seconds";
+
+/// Rolling p50 & p99 latency in seconds between the exchange_time & received_time of the most
+/// recent [`MarketEvent`]s received, see [`LatencyTracker`].
+pub const EVENT_LATENCY_ROLLING_SECONDS: &str = "barter_data_event_latency_rolling_seconds";
+
+/// Number of [`MarketEvent`]s received with an exchange_time after their received_time (ie/
+/// local clock skew), whose latency is clamped to zero.
+pub const CLOCK_SKEW_EVENTS: &str = "barter_data_clock_skew_events_total";
+
+/// Number of OrderBook [`Level`](crate::subscription::book::Level)s evicted from managed L2
+/// books exceeding the configured cap, see
+/// [`StreamBuilder::max_book_levels`](crate::streams::builder::StreamBuilder::max_book_levels).
+pub const BOOK_LEVELS_EVICTED: &str = "barter_data_book_levels_evicted_total";
+
+/// Number of [`MarketEvent`]s held back by an [`InboundThrottle`](crate::streams::inbound::InboundThrottle),
+/// labelled by the `action` taken: `dropped`, `delayed` or `coalesced` (ie/ superseded by a later
+/// event before it could be distributed).
+pub const THROTTLED_EVENTS: &str = "barter_data_throttled_events_total";
+
+/// Number of fields of parsed exchange messages unknown to their model, labelled by the
+/// `channel` & `model` they were found in, see [`DeserialiseMode::Strict`](crate::parser::strict::DeserialiseMode::Strict).
+pub const UNKNOWN_FIELDS: &str = "barter_data_unknown_fields_total";
+
+/// Number of optional fields an exchange is expected to send that are missing from parsed
+/// exchange messages, labelled by the `channel` & `model` they were missing from, see
+/// [`ExpectedFields`](crate::parser::strict::ExpectedFields).
+pub const MISSING_FIELDS: &str = "barter_data_missing_fields_total";
+
+/// Upper bounds in seconds of the [`EVENT_LATENCY_SECONDS`] histogram buckets.
+pub const LATENCY_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+fn help(name: &str) -> &'static str {
+    match name {
+        MESSAGES_RECEIVED => "Messages yielded by each exchange MarketStream.",
+        EVENTS_EMITTED => "MarketEvents distributed downstream.",
+        PARSE_ERRORS => "Non-terminal MarketStream errors, eg/ deserialisation failures.",
+        RECONNECTS => "Re-connections attempted after a MarketStream ended.",
+        DROPPED_EVENTS => "MarketEvents dropped because the downstream receiver was dropped.",
+        ACTIVE_SUBSCRIPTIONS => "Subscriptions on connected MarketStreams.",
+        CONNECTIONS_UP => "Connected exchange WebSocket connections.",
+        EVENT_LATENCY_SECONDS => "Latency between MarketEvent exchange_time & received_time.",
+        EVENT_LATENCY_ROLLING_SECONDS => {
+            "Rolling p50 & p99 latency of the most recent MarketEvents received."
+        }
+        CLOCK_SKEW_EVENTS => "MarketEvents received with an exchange_time in the future.",
+        BOOK_LEVELS_EVICTED => "OrderBook Levels evicted from managed books exceeding their cap.",
+        THROTTLED_EVENTS => "MarketEvents held back by an InboundThrottle, by action taken.",
+        UNKNOWN_FIELDS => {
+            "Fields of parsed exchange messages unknown to their model (strict mode)."
+        }
+        MISSING_FIELDS => "Expected fields missing from parsed exchange messages (strict mode).",
+        _ => "",
+    }
+}
+
+/// Name & label pairs identifying a time series.
+type Series = (&'static str, Vec<(&'static str, String)>);
+
+/// Monotonically increasing counter.
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Gauge that can go up & down.
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicI64);
+
+impl Gauge {
+    pub fn add(&self, value: i64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn sub(&self, value: i64) {
+        self.0.fetch_sub(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Histogram with the fixed [`LATENCY_BUCKETS`] upper bounds.
+#[derive(Debug, Default)]
+pub struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum: Mutex<f64>,
+}
+
+impl Histogram {
+    pub fn observe(&self, value: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| value <= *bound) {
+            self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        *self.sum.lock().unwrap() += value;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> f64 {
+        *self.sum.lock().unwrap()
+    }
+}
+
+/// Summary of the rolling latency quantiles merged from the [`PublishedLatency`] of every
+/// registered [`LatencyTracker`] that is still alive.
+#[derive(Debug, Default)]
+pub struct Summary(Mutex<Vec<Weak<PublishedLatency>>>);
+
+impl Summary {
+    pub fn register(&self, tracker: &LatencyTracker) {
+        let mut published = self.0.lock().unwrap();
+        published.retain(|published| published.strong_count() > 0);
+        published.push(Arc::downgrade(tracker.published()));
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let published = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+
+        PublishedLatency::merged(published.iter().map(Arc::as_ref))
+    }
+}
+
+/// Registry of every Barter-Data metric, rendered in the Prometheus text exposition format.
+///
+/// Series are labelled by `exchange` & `kind` only, bounding label cardinality. Per-instrument
+/// labels on [`EVENTS_EMITTED`] & [`EVENT_LATENCY_SECONDS`] are opt-in via
+/// [`set_per_instrument_labels`](Self::set_per_instrument_labels).
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    per_instrument: AtomicBool,
+    counters: Mutex<BTreeMap<Series, Arc<Counter>>>,
+    gauges: Mutex<BTreeMap<Series, Arc<Gauge>>>,
+    histograms: Mutex<BTreeMap<Series, Arc<Histogram>>>,
+    summaries: Mutex<BTreeMap<Series, Arc<Summary>>>,
+}
+
+impl MetricsRegistry {
+    /// Global [`MetricsRegistry`] recorded to by every [`MarketStream`](crate::MarketStream)
+    /// consumer loop.
+    pub fn global() -> &'static Arc<MetricsRegistry> {
+        static GLOBAL: OnceLock<Arc<MetricsRegistry>> = OnceLock::new();
+        GLOBAL.get_or_init(Arc::default)
+    }
+
+    /// Enable or disable the opt-in `instrument` label. Only affects consumer loops initialised
+    /// afterwards.
+    pub fn set_per_instrument_labels(&self, enabled: bool) {
+        self.per_instrument.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn per_instrument_labels(&self) -> bool {
+        self.per_instrument.load(Ordering::Relaxed)
+    }
+
+    /// Get or register the [`Counter`] with the provided name & labels.
+    pub fn counter(&self, name: &'static str, labels: &[(&'static str, &str)]) -> Arc<Counter> {
+        series(&self.counters, name, labels)
+    }
+
+    /// Get or register the [`Gauge`] with the provided name & labels.
+    pub fn gauge(&self, name: &'static str, labels: &[(&'static str, &str)]) -> Arc<Gauge> {
+        series(&self.gauges, name, labels)
+    }
+
+    /// Get or register the [`Histogram`] with the provided name & labels.
+    pub fn histogram(&self, name: &'static str, labels: &[(&'static str, &str)]) -> Arc<Histogram> {
+        series(&self.histograms, name, labels)
+    }
+
+    /// Register the [`LatencyTracker`] rendered as part of the [`Summary`] with the provided
+    /// name & labels, merged with every other live [`LatencyTracker`] registered to it.
+    pub fn register_summary(
+        &self,
+        name: &'static str,
+        labels: &[(&'static str, &str)],
+        tracker: &LatencyTracker,
+    ) {
+        series(&self.summaries, name, labels).register(tracker);
+    }
+
+    /// Render every registered series in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        render_family(
+            &mut output,
+            "counter",
+            &self.counters,
+            |output, name, labels, counter| {
+                let _ = writeln!(
+                    output,
+                    "{name}{} {}",
+                    fmt_labels(labels, None),
+                    counter.get()
+                );
+            },
+        );
+
+        render_family(
+            &mut output,
+            "gauge",
+            &self.gauges,
+            |output, name, labels, gauge| {
+                let _ = writeln!(output, "{name}{} {}", fmt_labels(labels, None), gauge.get());
+            },
+        );
+
+        render_family(
+            &mut output,
+            "histogram",
+            &self.histograms,
+            |output, name, labels, histogram| {
+                let mut cumulative = 0;
+                for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+                    cumulative += bucket.load(Ordering::Relaxed);
+                    let le = bound.to_string();
+                    let _ = writeln!(
+                        output,
+                        "{name}_bucket{} {cumulative}",
+                        fmt_labels(labels, Some(("le", &le)))
+                    );
+                }
+                let count = histogram.count();
+                let _ = writeln!(
+                    output,
+                    "{name}_bucket{} {count}",
+                    fmt_labels(labels, Some(("le", "+Inf")))
+                );
+                let _ = writeln!(
+                    output,
+                    "{name}_sum{} {}",
+                    fmt_labels(labels, None),
+                    histogram.sum()
+                );
+                let _ = writeln!(output, "{name}_count{} {count}", fmt_labels(labels, None));
+            },
+        );
+
+        render_family(
+            &mut output,
+            "summary",
+            &self.summaries,
+            |output, name, labels, summary| {
+                let snapshot = summary.snapshot();
+                for (quantile, latency) in [("0.5", snapshot.p50), ("0.99", snapshot.p99)] {
+                    let value = latency.map_or(f64::NAN, |latency| latency.as_secs_f64());
+                    let _ = writeln!(
+                        output,
+                        "{name}{} {value}",
+                        fmt_labels(labels, Some(("quantile", quantile)))
+                    );
+                }
+                let _ = writeln!(
+                    output,
+                    "{name}_count{} {}",
+                    fmt_labels(labels, None),
+                    snapshot.observed
+                );
+            },
+        );
+
+        output
+    }
+}
+
+fn series<T>(
+    registry: &Mutex<BTreeMap<Series, Arc<T>>>,
+    name: &'static str,
+    labels: &[(&'static str, &str)],
+) -> Arc<T>
+where
+    T: Default,
+{
+    let labels = labels
+        .iter()
+        .map(|(key, value)| (*key, value.to_string()))
+        .collect();
+
+    registry
+        .lock()
+        .unwrap()
+        .entry((name, labels))
+        .or_default()
+        .clone()
+}
+
+fn render_family<T, F>(
+    output: &mut String,
+    kind: &str,
+    registry: &Mutex<BTreeMap<Series, Arc<T>>>,
+    mut render: F,
+) where
+    F: FnMut(&mut String, &str, &[(&'static str, String)], &T),
+{
+    let registry = registry.lock().unwrap();
+    let mut previous = None;
+
+    for ((name, labels), metric) in registry.iter() {
+        // BTreeMap orders series by name, so each family is rendered contiguously
+        if previous != Some(*name) {
+            let _ = writeln!(output, "# HELP {name} {}", help(name));
+            let _ = writeln!(output, "# TYPE {name} {kind}");
+            previous = Some(*name);
+        }
+        render(output, name, labels, metric);
+    }
+}
+
+fn fmt_labels(labels: &[(&'static str, String)], extra: Option<(&str, &str)>) -> String {
+    let labels = labels
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .chain(extra)
+        .map(|(key, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{key}=\"{value}\"")
+        })
+        .collect::<Vec<_>>();
+
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels.join(","))
+    }
+}
+
+/// Serve the provided [`MetricsRegistry`] as a Prometheus text endpoint on any HTTP path (eg/
+/// `GET /metrics`), returning the local address it's bound to & the server task.
+pub async fn serve<Addr>(
+    addr: Addr,
+    registry: Arc<MetricsRegistry>,
+) -> Result<(SocketAddr, JoinHandle<()>), DataError>
+where
+    Addr: ToSocketAddrs,
+{
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    info!(%local_addr, "serving Prometheus metrics");
+
+    let server = tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, client)) = listener.accept().await else {
+                continue;
+            };
+
+            let registry = Arc::clone(&registry);
+            tokio::spawn(async move {
+                // Read (& ignore) the request head, any path returns the metrics
+                let mut request = [0; 1024];
+                if let Err(error) = stream.read(&mut request).await {
+                    debug!(%client, %error, "failed to read metrics request");
+                    return;
+                }
+
+                let body = registry.render();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\n\
+                     Content-Type: text/plain; version=0.0.4\r\n\
+                     Content-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                if let Err(error) = stream.write_all(response.as_bytes()).await {
+                    debug!(%client, %error, "failed to write metrics response");
+                }
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    Ok((local_addr, server))
+}
+
+/// Metrics recorded by a [`MarketStream`](crate::MarketStream) consumer loop, with the bounded
+/// `exchange` & `kind` label series resolved once up front.
+#[derive(Debug)]
+pub(crate) struct StreamMetrics {
+    registry: Arc<MetricsRegistry>,
+    exchange: ExchangeId,
+    kind: &'static str,
+    subscriptions: i64,
+    per_instrument: bool,
+    messages_received: Arc<Counter>,
+    events_emitted: Arc<Counter>,
+    parse_errors: Arc<Counter>,
+    reconnects: Arc<Counter>,
+    dropped_events: Arc<Counter>,
+    clock_skew_events: Arc<Counter>,
+    throttled_dropped: Arc<Counter>,
+    throttled_delayed: Arc<Counter>,
+    throttled_coalesced: Arc<Counter>,
+    active_subscriptions: Arc<Gauge>,
+    connections_up: Arc<Gauge>,
+    latency: Arc<Histogram>,
+    connected: AtomicBool,
+}
+
+impl StreamMetrics {
+    pub(crate) fn new<Kind>(
+        registry: Arc<MetricsRegistry>,
+        exchange: ExchangeId,
+        subscriptions: usize,
+    ) -> Self {
+        let kind = crate::subscription::sub_kind_name::<Kind>();
+        let labels = [("exchange", exchange.as_str()), ("kind", kind)];
+        let throttled = |action| {
+            registry.counter(
+                THROTTLED_EVENTS,
+                &[
+                    ("exchange", exchange.as_str()),
+                    ("kind", kind),
+                    ("action", action),
+                ],
+            )
+        };
+
+        Self {
+            exchange,
+            kind,
+            subscriptions: subscriptions as i64,
+            per_instrument: registry.per_instrument_labels(),
+            messages_received: registry.counter(MESSAGES_RECEIVED, &labels),
+            events_emitted: registry.counter(EVENTS_EMITTED, &labels),
+            parse_errors: registry.counter(PARSE_ERRORS, &labels),
+            reconnects: registry.counter(RECONNECTS, &labels),
+            dropped_events: registry.counter(DROPPED_EVENTS, &labels),
+            clock_skew_events: registry.counter(CLOCK_SKEW_EVENTS, &labels),
+            throttled_dropped: throttled("dropped"),
+            throttled_delayed: throttled("delayed"),
+            throttled_coalesced: throttled("coalesced"),
+            active_subscriptions: registry.gauge(ACTIVE_SUBSCRIPTIONS, &labels),
+            connections_up: registry.gauge(CONNECTIONS_UP, &labels),
+            latency: registry.histogram(EVENT_LATENCY_SECONDS, &labels),
+            registry,
+            connected: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn connected(&self) {
+        if !self.connected.swap(true, Ordering::Relaxed) {
+            self.connections_up.add(1);
+            self.active_subscriptions.add(self.subscriptions);
+        }
+    }
+
+    pub(crate) fn disconnected(&self) {
+        if self.connected.swap(false, Ordering::Relaxed) {
+            self.connections_up.sub(1);
+            self.active_subscriptions.sub(self.subscriptions);
+        }
+    }
+
+    pub(crate) fn reconnecting(&self) {
+        self.reconnects.inc();
+    }
+
+    pub(crate) fn received<T>(&self, event: &Result<MarketEvent<T>, DataError>) {
+        self.messages_received.inc();
+        match event {
+            Ok(event) if event.exchange_time > event.received_time => self.clock_skew_events.inc(),
+            Err(error) if !error.is_terminal() => self.parse_errors.inc(),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn emitted<T>(&self, event: &MarketEvent<T>) {
+        let latency = (event.received_time - event.exchange_time)
+            .to_std()
+            .map(|latency| latency.as_secs_f64())
+            .unwrap_or_default();
+
+        if self.per_instrument {
+            let instrument = event.instrument.to_string();
+            let labels = [
+                ("exchange", self.exchange.as_str()),
+                ("kind", self.kind),
+                ("instrument", instrument.as_str()),
+            ];
+            self.registry.counter(EVENTS_EMITTED, &labels).inc();
+            self.registry
+                .histogram(EVENT_LATENCY_SECONDS, &labels)
+                .observe(latency);
+        } else {
+            self.events_emitted.inc();
+            self.latency.observe(latency);
+        }
+    }
+
+    pub(crate) fn dropped(&self) {
+        self.dropped_events.inc();
+    }
+
+    /// Expose the rolling latency quantiles of the [`LatencyTracker`] of the consumer loop.
+    pub(crate) fn track_latency(&self, tracker: &LatencyTracker) {
+        self.registry.register_summary(
+            EVENT_LATENCY_ROLLING_SECONDS,
+            &[("exchange", self.exchange.as_str()), ("kind", self.kind)],
+            tracker,
+        );
+    }
+
+    pub(crate) fn throttled<T>(&self, admission: &Admission<T>) {
+        match admission {
+            Admission::Emit(_) => {}
+            Admission::Dropped => self.throttled_dropped.inc(),
+            Admission::Delayed => self.throttled_delayed.inc(),
+            Admission::Coalesced => self.throttled_coalesced.inc(),
+        }
+    }
+}
+
+impl Drop for StreamMetrics {
+    fn drop(&mut self) {
+        self.disconnected();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::trade::{PublicTrade, PublicTrades};
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::{error::SocketError, model::Side};
+    use chrono::{Duration, Utc};
+
+    fn trade(base: &str, latency_ms: i64) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().with_latency_ms(latency_ms).trade(
+            0,
+            base,
+            num!(1.0),
+            num!(1.0),
+            Side::Buy,
+        )
+    }
+
+    #[test]
+    fn test_stream_metrics() {
+        let registry = Arc::new(MetricsRegistry::default());
+        let labels = [("exchange", "binance_spot"), ("kind", "PublicTrades")];
+
+        let metrics =
+            StreamMetrics::new::<PublicTrades>(Arc::clone(&registry), ExchangeId::BinanceSpot, 2);
+        metrics.connected();
+        assert_eq!(registry.gauge(CONNECTIONS_UP, &labels).get(), 1);
+        assert_eq!(registry.gauge(ACTIVE_SUBSCRIPTIONS, &labels).get(), 2);
+
+        // Canned messages: three events (one with a future exchange_time) & one
+        // deserialisation error
+        let messages: Vec<Result<MarketEvent<PublicTrade>, DataError>> = vec![
+            Ok(trade("btc", 3)),
+            Err(DataError::Socket(SocketError::Unsupported {
+                entity: "test",
+                item: "message".to_string(),
+            })),
+            Ok(trade("eth", 30)),
+            Ok(trade("sol", -20)),
+        ];
+        for message in &messages {
+            metrics.received(message);
+            if let Ok(event) = message {
+                metrics.emitted(event);
+            }
+        }
+        metrics.dropped();
+
+        assert_eq!(registry.counter(MESSAGES_RECEIVED, &labels).get(), 4);
+        assert_eq!(registry.counter(PARSE_ERRORS, &labels).get(), 1);
+        assert_eq!(registry.counter(CLOCK_SKEW_EVENTS, &labels).get(), 1);
+        assert_eq!(registry.counter(EVENTS_EMITTED, &labels).get(), 3);
+        assert_eq!(registry.counter(DROPPED_EVENTS, &labels).get(), 1);
+        assert_eq!(
+            registry.histogram(EVENT_LATENCY_SECONDS, &labels).count(),
+            3
+        );
+
+        metrics.disconnected();
+        metrics.reconnecting();
+        assert_eq!(registry.gauge(CONNECTIONS_UP, &labels).get(), 0);
+        assert_eq!(registry.gauge(ACTIVE_SUBSCRIPTIONS, &labels).get(), 0);
+        assert_eq!(registry.counter(RECONNECTS, &labels).get(), 1);
+
+        // Opt-in per-instrument labels
+        registry.set_per_instrument_labels(true);
+        let metrics =
+            StreamMetrics::new::<PublicTrades>(Arc::clone(&registry), ExchangeId::BinanceSpot, 2);
+        metrics.emitted(&trade("btc", 3));
+
+        let instrument_labels = [
+            ("exchange", "binance_spot"),
+            ("kind", "PublicTrades"),
+            ("instrument", "(btc_usdt, spot)"),
+        ];
+        assert_eq!(
+            registry.counter(EVENTS_EMITTED, &instrument_labels).get(),
+            1
+        );
+        assert_eq!(registry.counter(EVENTS_EMITTED, &labels).get(), 3);
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::default();
+        let received_time = Utc::now();
+
+        let mut first = LatencyTracker::default();
+        first.record(received_time - Duration::milliseconds(10), received_time);
+        let mut second = LatencyTracker::default();
+        second.record(received_time - Duration::milliseconds(30), received_time);
+        summary.register(&first);
+        summary.register(&second);
+
+        // Every live LatencyTracker is merged
+        assert_eq!(
+            summary.snapshot(),
+            LatencySnapshot {
+                p50: Some(std::time::Duration::from_millis(10)),
+                p99: Some(std::time::Duration::from_millis(30)),
+                samples: 2,
+                observed: 2,
+                skewed: 0,
+            }
+        );
+
+        // Dropped LatencyTrackers (eg/ of dropped Streams) are no longer included
+        drop(second);
+        assert_eq!(summary.snapshot().observed, 1);
+    }
+
+    #[test]
+    fn test_render() {
+        let registry = MetricsRegistry::default();
+        let labels = [("exchange", "okx"), ("kind", "PublicTrades")];
+
+        registry.counter(EVENTS_EMITTED, &labels).inc_by(5);
+        registry.gauge(CONNECTIONS_UP, &labels).add(1);
+        registry
+            .histogram(EVENT_LATENCY_SECONDS, &labels)
+            .observe(0.02);
+
+        let mut tracker = LatencyTracker::default();
+        let received_time = Utc::now();
+        for latency_ms in [10, 20, 30] {
+            tracker.record(
+                received_time - Duration::milliseconds(latency_ms),
+                received_time,
+            );
+        }
+        tracker.publish();
+        registry.register_summary(EVENT_LATENCY_ROLLING_SECONDS, &labels, &tracker);
+
+        let actual = registry.render();
+
+        assert!(actual.contains("# TYPE barter_data_events_emitted_total counter\n"));
+        assert!(actual.contains(
+            "barter_data_events_emitted_total{exchange=\"okx\",kind=\"PublicTrades\"} 5\n"
+        ));
+        assert!(actual
+            .contains("barter_data_connections_up{exchange=\"okx\",kind=\"PublicTrades\"} 1\n"));
+        assert!(actual.contains(
+            "barter_data_event_latency_seconds_bucket{exchange=\"okx\",kind=\"PublicTrades\",le=\"0.01\"} 0\n"
+        ));
+        assert!(actual.contains(
+            "barter_data_event_latency_seconds_bucket{exchange=\"okx\",kind=\"PublicTrades\",le=\"0.025\"} 1\n"
+        ));
+        assert!(actual.contains(
+            "barter_data_event_latency_seconds_bucket{exchange=\"okx\",kind=\"PublicTrades\",le=\"+Inf\"} 1\n"
+        ));
+        assert!(actual.contains(
+            "barter_data_event_latency_seconds_count{exchange=\"okx\",kind=\"PublicTrades\"} 1\n"
+        ));
+        assert!(actual.contains("# TYPE barter_data_event_latency_rolling_seconds summary\n"));
+        assert!(actual.contains(
+            "barter_data_event_latency_rolling_seconds{exchange=\"okx\",kind=\"PublicTrades\",quantile=\"0.5\"} 0.02\n"
+        ));
+        assert!(actual.contains(
+            "barter_data_event_latency_rolling_seconds{exchange=\"okx\",kind=\"PublicTrades\",quantile=\"0.99\"} 0.03\n"
+        ));
+        assert!(actual.contains(
+            "barter_data_event_latency_rolling_seconds_count{exchange=\"okx\",kind=\"PublicTrades\"} 3\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let registry = Arc::new(MetricsRegistry::default());
+        registry
+            .counter(RECONNECTS, &[("exchange", "okx"), ("kind", "PublicTrades")])
+            .inc();
+
+        let (addr, server) = serve("127.0.0.1:0", Arc::clone(&registry)).await.unwrap();
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(&registry.render()));
+        assert!(response.contains("barter_data_reconnects_total"));
+
+        server.abort();
+    }
+}
@@ -0,0 +1,88 @@
+/// Numeric type of the normalised prices & amounts of [`PublicTrade`](crate::subscription::trade::PublicTrade)s,
+/// OrderBook [`Level`](crate::subscription::book::Level)s, [`Candle`](crate::subscription::candle::Candle)s
+/// and [`Liquidation`](crate::subscription::liquidation::Liquidation)s.
+///
+/// Defaults to `f64`. Enabling the `decimal` feature switches this to a
+/// [`rust_decimal::Decimal`], parsed directly from the exchange payloads so the exact values
+/// (eg/ for checksum verification) are preserved end-to-end.
+#[cfg(not(feature = "decimal"))]
+pub type Num = f64;
+
+/// Numeric type of the normalised prices & amounts of [`PublicTrade`](crate::subscription::trade::PublicTrade)s,
+/// OrderBook [`Level`](crate::subscription::book::Level)s, [`Candle`](crate::subscription::candle::Candle)s
+/// and [`Liquidation`](crate::subscription::liquidation::Liquidation)s.
+///
+/// The `decimal` feature is enabled, so this is a [`rust_decimal::Decimal`] parsed directly from
+/// the exchange payloads, preserving the exact values (eg/ for checksum verification). Values are
+/// serialised as strings so they are also preserved by any downstream consumer.
+#[cfg(feature = "decimal")]
+pub type Num = rust_decimal::Decimal;
+
+/// Zero value of [`Num`].
+pub fn zero() -> Num {
+    Num::from(0u8)
+}
+
+/// Deserialize an optional exchange `String` field, which is absent or empty when it has no value
+/// (eg/ the "price" of a market order), as an `Option<T>`.
+pub fn de_optional_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let value = <Option<&str> as serde::Deserialize>::deserialize(deserializer)?;
+    match value {
+        None | Some("") => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Parse a [`Num`] from the source text of a numeric literal (eg/ "0.1", "20_000.5", "1e-8").
+///
+/// Used by the [`num!`](crate::num!) macro so decimal literals are exact regardless of the
+/// [`Num`] type.
+///
+/// # Panics
+/// Panics if the input is not a valid numeric literal.
+pub fn from_literal(literal: &str) -> Num {
+    let literal = literal.replace('_', "");
+
+    #[cfg(not(feature = "decimal"))]
+    let value = literal.parse::<Num>().ok();
+
+    #[cfg(feature = "decimal")]
+    let value = literal
+        .parse::<Num>()
+        .or_else(|_| Num::from_scientific(&literal))
+        .ok();
+
+    value.unwrap_or_else(|| panic!("invalid Num literal: {literal}"))
+}
+
+/// Construct a [`Num`] from an `f64`.
+///
+/// # Panics
+/// Panics if the `decimal` feature is enabled and the `f64` is not finite or out of range.
+pub fn from_f64(value: f64) -> Num {
+    #[cfg(not(feature = "decimal"))]
+    return value;
+
+    #[cfg(feature = "decimal")]
+    Num::try_from(value).unwrap_or_else(|error| panic!("invalid Num {value}: {error}"))
+}
+
+/// Construct a [`Num`] from a numeric literal (eg/ `num!(0.1)`) or an expression castable to
+/// `f64` (eg/ `num!(index)`), independent of whether the `decimal` feature is enabled.
+///
+/// Literals are parsed from their source text so `num!(0.1)` is exactly the value an exchange
+/// payload of "0.1" deserialises to.
+#[macro_export]
+macro_rules! num {
+    ($value:literal) => {
+        $crate::num::from_literal(stringify!($value))
+    };
+    ($value:expr) => {
+        $crate::num::from_f64(($value) as f64)
+    };
+}
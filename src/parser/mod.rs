@@ -0,0 +1,252 @@
+use crate::subscriber::transport::WebSocket;
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        websocket::{
+            process_close_frame, process_frame, process_ping, process_pong, WsError, WsMessage,
+        },
+        StreamParser,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::debug;
+
+/// Strict [`DeserialiseMode`](strict::DeserialiseMode) reporting exchange message fields unknown
+/// to their model, eg/ to detect exchange schema changes early.
+pub mod strict;
+
+/// [`StreamParser`] used by every [`ExchangeWsStream`](crate::ExchangeWsStream) to deserialise
+/// WebSocket messages into exchange specific messages.
+///
+/// Identical to the [`WebSocketParser`](barter_integration::protocol::websocket::WebSocketParser)
+/// apart from text & binary payloads being deserialised from an owned, mutable byte buffer via
+/// [`from_bytes`], so JSON backends that parse in place can be used on the hot path.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct WsParser;
+
+impl StreamParser for WsParser {
+    type Stream = WebSocket;
+    type Message = WsMessage;
+    type Error = WsError;
+
+    fn parse<Output>(
+        input: Result<Self::Message, Self::Error>,
+    ) -> Option<Result<Output, SocketError>>
+    where
+        Output: DeserializeOwned,
+    {
+        match input {
+            Ok(ws_message) => match ws_message {
+                WsMessage::Text(text) => process_payload(text.into_bytes()),
+                WsMessage::Binary(binary) => process_payload(binary),
+                WsMessage::Ping(ping) => process_ping(ping),
+                WsMessage::Pong(pong) => process_pong(pong),
+                WsMessage::Close(close_frame) => process_close_frame(close_frame),
+                WsMessage::Frame(frame) => process_frame(frame),
+            },
+            Err(ws_err) => Some(Err(SocketError::WebSocket(ws_err))),
+        }
+    }
+}
+
+/// Deserialise the provided owned payload into an `ExchangeMessage`, returning a
+/// [`SocketError::Deserialise`] containing the payload as received if it fails.
+pub fn process_payload<ExchangeMessage>(
+    mut payload: Vec<u8>,
+) -> Option<Result<ExchangeMessage, SocketError>>
+where
+    ExchangeMessage: DeserializeOwned,
+{
+    let original = retain_original(&payload);
+
+    Some(
+        from_bytes::<ExchangeMessage>(&mut payload).map_err(|error| {
+            let payload = original.unwrap_or(payload);
+            let payload = String::from_utf8(payload).unwrap_or_else(|x| x.to_string());
+            debug!(
+                ?error,
+                ?payload,
+                action = "returning Some(Err(err))",
+                "failed to deserialize WebSocket Message into domain specific Message"
+            );
+            SocketError::Deserialise { error, payload }
+        }),
+    )
+}
+
+/// Copy of a payload that [`from_bytes`] may modify whilst parsing, so a failure reports it as
+/// received. The default `serde_json` backend leaves the buffer untouched, so nothing is copied.
+#[cfg(not(feature = "simd-json"))]
+fn retain_original(_: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+/// Copy of a payload that [`from_bytes`] may modify whilst parsing, so a failure reports it as
+/// received. Only payloads containing escape sequences are modified by `simd_json`, so the
+/// copy is skipped for all others.
+#[cfg(feature = "simd-json")]
+fn retain_original(payload: &[u8]) -> Option<Vec<u8>> {
+    payload.contains(&b'\\').then(|| payload.to_vec())
+}
+
+/// Deserialise the provided JSON bytes into a `T`.
+///
+/// This is the single entry point of the JSON backend used for every exchange message. The
+/// default `serde_json` backend leaves the buffer untouched.
+#[cfg(not(feature = "simd-json"))]
+pub fn from_bytes<T>(bytes: &mut [u8]) -> Result<T, serde_json::Error>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_slice::<T>(bytes)
+}
+
+/// Deserialise the provided JSON bytes into a `T`.
+///
+/// This is the single entry point of the JSON backend used for every exchange message. The
+/// `simd-json` feature is enabled, so the buffer is parsed in place by `simd_json`, and errors
+/// are converted into a `serde_json::Error` so the [`SocketError::Deserialise`] is backend
+/// independent.
+///
+/// The `simd_json` scratch buffers are reused by every message parsed on the same thread, so
+/// the per message allocations are only those of it's parse tape.
+///
+/// Note:
+/// Escaped JSON strings are unescaped in place, so a buffer that fails to deserialise is only
+/// identical to the input if it contains no escape sequences. [`process_payload`] retains a copy
+/// of any such payload to report it as received.
+#[cfg(feature = "simd-json")]
+pub fn from_bytes<T>(bytes: &mut [u8]) -> Result<T, serde_json::Error>
+where
+    T: DeserializeOwned,
+{
+    thread_local! {
+        static BUFFERS: std::cell::RefCell<simd_json::Buffers> =
+            std::cell::RefCell::new(simd_json::Buffers::new(4096));
+    }
+
+    BUFFERS
+        .with_borrow_mut(|buffers| simd_json::serde::from_slice_with_buffers::<T>(bytes, buffers))
+        .map_err(<serde_json::Error as serde::de::Error>::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::protocol::websocket::WebSocketParser;
+
+    #[derive(Clone, PartialEq, Debug, Deserialize)]
+    struct Message {
+        id: u64,
+        price: f64,
+    }
+
+    #[test]
+    fn test_ws_parser_parse() {
+        struct TestCase {
+            input: Result<WsMessage, WsError>,
+            expected: Option<Result<Message, SocketError>>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid text payload
+                input: Ok(WsMessage::Text(r#"{"id":1,"price":100.5}"#.to_string())),
+                expected: Some(Ok(Message {
+                    id: 1,
+                    price: 100.5,
+                })),
+            },
+            TestCase {
+                // TC1: valid binary payload
+                input: Ok(WsMessage::Binary(br#"{"id":2,"price":99.0}"#.to_vec())),
+                expected: Some(Ok(Message { id: 2, price: 99.0 })),
+            },
+            TestCase {
+                // TC2: invalid text payload
+                input: Ok(WsMessage::Text(r#"{"id":"one"}"#.to_string())),
+                expected: Some(Err(SocketError::Deserialise {
+                    error: serde_json::from_str::<Message>(r#"{"id":"one"}"#).unwrap_err(),
+                    payload: r#"{"id":"one"}"#.to_string(),
+                })),
+            },
+            TestCase {
+                // TC3: invalid text payload w/ escape sequences is reported as received
+                input: Ok(WsMessage::Text(r#"{"id":"o\u006ee\n"}"#.to_string())),
+                expected: Some(Err(SocketError::Deserialise {
+                    error: serde_json::from_str::<Message>(r#"{"id":"o\u006ee\n"}"#).unwrap_err(),
+                    payload: r#"{"id":"o\u006ee\n"}"#.to_string(),
+                })),
+            },
+            TestCase {
+                // TC4: Ping is ignored
+                input: Ok(WsMessage::Ping(vec![1])),
+                expected: None,
+            },
+            TestCase {
+                // TC5: Close terminates the stream
+                input: Ok(WsMessage::Close(None)),
+                expected: Some(Err(SocketError::Terminated("None".to_string()))),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = WsParser::parse::<Message>(test.input);
+            match (actual, test.expected) {
+                (Some(Ok(actual)), Some(Ok(expected))) => {
+                    assert_eq!(actual, expected, "TC{} failed", index)
+                }
+                (
+                    Some(Err(SocketError::Deserialise { payload, .. })),
+                    Some(Err(SocketError::Deserialise {
+                        payload: expected, ..
+                    })),
+                ) => {
+                    assert_eq!(payload, expected, "TC{} failed", index)
+                }
+                (
+                    Some(Err(SocketError::Terminated(actual))),
+                    Some(Err(SocketError::Terminated(expected))),
+                ) => {
+                    assert_eq!(actual, expected, "TC{} failed", index)
+                }
+                (None, None) => {
+                    // Test passed
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ws_parser_matches_web_socket_parser() {
+        // Error messages are backend specific, so only the payload of an error is compared
+        fn normalise(
+            parsed: Option<Result<Message, SocketError>>,
+        ) -> Option<Result<String, String>> {
+            parsed.map(|result| match result {
+                Ok(message) => Ok(format!("{message:?}")),
+                Err(SocketError::Deserialise { payload, .. }) => Err(payload),
+                Err(error) => Err(error.to_string()),
+            })
+        }
+
+        let payloads = [
+            r#"{"id":1,"price":100.5}"#,
+            r#"{"id":18446744073709551615,"price":1e-8}"#,
+            r#"{"price":-0.0,"id":0,"ignored":["a",{"b":null}]}"#,
+            r#"{"id":1}"#,
+            r#"{"id":1,"note":"a\"b\\c\u00e9"}"#,
+        ];
+
+        for payload in payloads {
+            let actual = WsParser::parse::<Message>(Ok(WsMessage::Text(payload.to_string())));
+            let expected =
+                WebSocketParser::parse::<Message>(Ok(WsMessage::Text(payload.to_string())));
+            assert_eq!(normalise(actual), normalise(expected), "{payload} failed");
+        }
+    }
+}
@@ -0,0 +1,925 @@
+use crate::{
+    exchange::{Connector, ExchangeId},
+    subscription::Subscription,
+    Identifier,
+};
+use barter_integration::protocol::websocket::{WsError, WsMessage};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess,
+        Visitor,
+    },
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::{Map, Value};
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    fmt::{Display, Formatter},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::warn;
+
+/// Exchange message fields that a model intentionally does not deserialise, keyed by the model
+/// (ie/ struct) name, so [`DeserialiseMode::Strict`] does not report them as unknown.
+///
+/// Declared by each exchange module for it's models, eg/ `binance::IGNORED_FIELDS`.
+pub type IgnoredFields = &'static [(&'static str, &'static [&'static str])];
+
+/// Optional exchange message fields that an exchange is nonetheless expected to send, keyed by
+/// the exchange & the model (ie/ struct) name, so [`DeserialiseMode::Strict`] reports them if
+/// they go missing.
+///
+/// Fields are named by their key in the exchange message (rather than by the model field or
+/// any other alias it accepts). Declared by each exchange module for it's models, eg/
+/// `okx::EXPECTED_FIELDS`.
+pub type ExpectedFields = &'static [(ExchangeId, &'static str, &'static [&'static str])];
+
+/// Determines how exchange messages that diverge from the expected schema are handled.
+///
+/// Exchanges add fields & occasionally change schemas. Models ignore fields they don't
+/// deserialise, and default optional fields that are missing, which hides breakage until values
+/// are wrong. So [`DeserialiseMode::Strict`] reports each field of a parsed message that it's
+/// model neither deserialises nor declares as intentionally ignored (see [`IgnoredFields`]), and
+/// each optional field the exchange is expected to send that is missing (see
+/// [`ExpectedFields`]).
+///
+/// Set per exchange via
+/// [`StreamBuilder::deserialise_mode`](crate::streams::builder::StreamBuilder::deserialise_mode),
+/// which hands it to each connection via it's
+/// [`StreamConfig`](crate::streams::config::StreamConfig).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeserialiseMode {
+    /// Silently ignore unknown fields.
+    #[default]
+    Lenient,
+    /// Report unknown & missing expected fields of successfully parsed messages as
+    /// [`SchemaDiagnostic`]s, logged at WARN & counted by the
+    /// `barter_data_unknown_fields_total` & `barter_data_missing_fields_total` metrics (if
+    /// enabled).
+    /// Messages are parsed exactly as in [`DeserialiseMode::Lenient`], so this never causes a
+    /// parse failure, but each message is parsed a second time to diagnose it.
+    Strict,
+}
+
+/// Field of an exchange message that diverges from it's model, reported in
+/// [`DeserialiseMode::Strict`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SchemaDiagnostic {
+    pub kind: SchemaDiagnosticKind,
+    pub exchange: ExchangeId,
+    /// Exchange channel(s) of the connection the message was received on, eg/ "trades".
+    pub channel: String,
+    /// Name of the model (ie/ struct) the field belongs to, eg/ "BinanceTrade".
+    pub model: &'static str,
+    /// Path of the field from the root of the message, eg/ `data[0].count`.
+    pub field: String,
+}
+
+/// How a [`SchemaDiagnostic`] field diverges from it's model.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum SchemaDiagnosticKind {
+    /// Field is neither deserialised nor ignored by it's model, see [`IgnoredFields`].
+    UnknownField,
+    /// Optional field the exchange is expected to send is missing, see [`ExpectedFields`].
+    MissingField,
+}
+
+impl Display for SchemaDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            SchemaDiagnosticKind::UnknownField => "unknown field",
+            SchemaDiagnosticKind::MissingField => "missing expected field",
+        };
+        write!(
+            f,
+            "{kind} {} in {} of {} channel {}",
+            self.field, self.model, self.exchange, self.channel
+        )
+    }
+}
+
+/// Exchange message fields that are ignored or expected by the models of the exchange & channel
+/// being diagnosed.
+#[derive(Copy, Clone, Debug)]
+pub struct SchemaFields<'a> {
+    pub exchange: ExchangeId,
+    pub channel: &'a str,
+    pub ignored: &'a [IgnoredFields],
+    pub expected: &'a [ExpectedFields],
+}
+
+impl<'a> SchemaFields<'a> {
+    /// Construct a new [`Self`] for the provided exchange & channel, using the
+    /// [`IgnoredFields`] & [`ExpectedFields`] declared by every exchange module.
+    pub fn new(exchange: ExchangeId, channel: &'a str) -> Self {
+        Self {
+            exchange,
+            channel,
+            ignored: IGNORED_FIELDS,
+            expected: EXPECTED_FIELDS,
+        }
+    }
+
+    /// Determine if the provided field of the provided model is declared as intentionally
+    /// ignored.
+    fn is_ignored(&self, model: &str, field: &str) -> bool {
+        self.ignored
+            .iter()
+            .flat_map(|fields| fields.iter())
+            .filter(|(name, _)| *name == model)
+            .any(|(_, fields)| fields.contains(&field))
+    }
+
+    /// Determine the fields of the provided model that [`Self::exchange`] is expected to send.
+    fn expected(&self, model: &'a str) -> impl Iterator<Item = &'static str> + 'a {
+        let exchange = self.exchange;
+        self.expected
+            .iter()
+            .flat_map(|fields| fields.iter())
+            .filter(move |(id, name, _)| *id == exchange && *name == model)
+            .flat_map(|(_, _, fields)| fields.iter().copied())
+    }
+
+    /// Construct a [`SchemaDiagnostic`] of the provided model field of [`Self`].
+    fn diagnostic(
+        &self,
+        kind: SchemaDiagnosticKind,
+        model: &'static str,
+        field: String,
+    ) -> SchemaDiagnostic {
+        SchemaDiagnostic {
+            kind,
+            exchange: self.exchange,
+            channel: self.channel.to_owned(),
+            model,
+            field,
+        }
+    }
+}
+
+/// Determine the [`SchemaDiagnostic`]s of deserialising the provided JSON [`Value`] into a
+/// `T`, using the provided [`SchemaFields`].
+///
+/// Every struct deserialised via the derived [`Deserialize`] is checked, however deeply nested.
+/// Fields are not checked inside `untagged`, internally tagged & `flatten` models, since serde
+/// buffers their content before deserialising it.
+pub fn diagnose<T>(value: &Value, fields: SchemaFields<'_>) -> Vec<SchemaDiagnostic>
+where
+    T: DeserializeOwned,
+{
+    track::<T>(value, fields).1
+}
+
+/// Deserialise the provided JSON [`Value`] into a `T` via a [`Tracked`], returning if it
+/// succeeded & the [`SchemaDiagnostic`]s found along the way.
+fn track<T>(value: &Value, fields: SchemaFields<'_>) -> (bool, Vec<SchemaDiagnostic>)
+where
+    T: DeserializeOwned,
+{
+    let diagnostics = RefCell::new(Vec::new());
+    let parsed = T::deserialize(Tracked {
+        value,
+        path: String::new(),
+        fields,
+        diagnostics: &diagnostics,
+    })
+    .is_ok();
+    (parsed, diagnostics.into_inner())
+}
+
+/// Diagnose the provided payload received on the provided exchange channel if it parses into a
+/// `T`, reporting each [`SchemaDiagnostic`].
+///
+/// Payloads that fail to parse are left to the parser to report.
+pub(crate) fn check<T>(exchange: ExchangeId, channel: &str, payload: &[u8]) -> Vec<SchemaDiagnostic>
+where
+    T: DeserializeOwned,
+{
+    let Ok(value) = serde_json::from_slice::<Value>(payload) else {
+        return Vec::new();
+    };
+    let (true, diagnostics) = track::<T>(&value, SchemaFields::new(exchange, channel)) else {
+        return Vec::new();
+    };
+
+    for diagnostic in &diagnostics {
+        match diagnostic.kind {
+            SchemaDiagnosticKind::UnknownField => warn!(
+                %exchange,
+                channel,
+                model = diagnostic.model,
+                field = %diagnostic.field,
+                "exchange message contains a field unknown to it's model"
+            ),
+            SchemaDiagnosticKind::MissingField => warn!(
+                %exchange,
+                channel,
+                model = diagnostic.model,
+                field = %diagnostic.field,
+                "exchange message is missing a field it's exchange is expected to send"
+            ),
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::MetricsRegistry::global()
+            .counter(
+                match diagnostic.kind {
+                    SchemaDiagnosticKind::UnknownField => crate::metrics::UNKNOWN_FIELDS,
+                    SchemaDiagnosticKind::MissingField => crate::metrics::MISSING_FIELDS,
+                },
+                &[
+                    ("exchange", exchange.as_str()),
+                    ("channel", channel),
+                    ("model", diagnostic.model),
+                ],
+            )
+            .inc();
+    }
+
+    diagnostics
+}
+
+/// Determine the exchange channel(s) of the provided [`Subscription`]s, named by the
+/// [`SchemaDiagnostic`]s of their connection, eg/ "trades" or "kline_1m,kline_5m".
+pub(crate) fn channel<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> String
+where
+    Exchange: Connector,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel>,
+{
+    subscriptions
+        .iter()
+        .map(|subscription| {
+            Identifier::<Exchange::Channel>::id(subscription)
+                .as_ref()
+                .to_owned()
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Diagnoses the payloads of an exchange channel, see [`check`].
+type Check = fn(ExchangeId, &str, &[u8]) -> Vec<SchemaDiagnostic>;
+
+/// [`Stream`] wrapper that, in [`DeserialiseMode::Strict`], reports the fields of each text &
+/// binary payload that are unknown to, or missing from, the model it's parsed into, before
+/// yielding it to the parser untouched.
+///
+/// Used as the inner [`Stream`] of an [`ExchangeWsStream`](crate::ExchangeWsStream).
+pub struct StrictStream<InnerStream> {
+    pub exchange: ExchangeId,
+    /// Exchange channel(s) of the connection, see [`channel`].
+    pub channel: String,
+    check: Option<Check>,
+    pub stream: InnerStream,
+}
+
+impl<InnerStream> std::fmt::Debug for StrictStream<InnerStream>
+where
+    InnerStream: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrictStream")
+            .field("exchange", &self.exchange)
+            .field("channel", &self.channel)
+            .field("strict", &self.check.is_some())
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+impl<InnerStream> StrictStream<InnerStream> {
+    /// Construct a new [`Self`] wrapping the provided [`Stream`] of the provided exchange
+    /// channel(s), whose payloads are parsed into a `T`, using the provided [`DeserialiseMode`].
+    pub fn new<T>(
+        exchange: ExchangeId,
+        channel: String,
+        mode: DeserialiseMode,
+        stream: InnerStream,
+    ) -> Self
+    where
+        T: DeserializeOwned,
+    {
+        Self {
+            exchange,
+            channel,
+            check: match mode {
+                DeserialiseMode::Lenient => None,
+                DeserialiseMode::Strict => Some(check::<T>),
+            },
+            stream,
+        }
+    }
+}
+
+impl<InnerStream> Stream for StrictStream<InnerStream>
+where
+    InnerStream: Stream<Item = Result<(DateTime<Utc>, WsMessage), WsError>> + Unpin,
+{
+    type Item = Result<(DateTime<Utc>, WsMessage), WsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.stream.poll_next_unpin(cx);
+
+        if let (Some(check), Poll::Ready(Some(Ok((_, message))))) = (this.check, &poll) {
+            match message {
+                WsMessage::Text(text) => {
+                    check(this.exchange, &this.channel, text.as_bytes());
+                }
+                WsMessage::Binary(binary) => {
+                    check(this.exchange, &this.channel, binary);
+                }
+                _ => {}
+            }
+        }
+
+        poll
+    }
+}
+
+/// [`IgnoredFields`] of every exchange model.
+const IGNORED_FIELDS: &[IgnoredFields] = &[
+    #[cfg(feature = "binance")]
+    crate::exchange::binance::IGNORED_FIELDS,
+    #[cfg(feature = "coinbase")]
+    crate::exchange::coinbase::IGNORED_FIELDS,
+    #[cfg(feature = "gateio")]
+    crate::exchange::gateio::IGNORED_FIELDS,
+    #[cfg(feature = "kraken")]
+    crate::exchange::kraken::IGNORED_FIELDS,
+    #[cfg(feature = "okx")]
+    crate::exchange::okx::IGNORED_FIELDS,
+];
+
+/// [`ExpectedFields`] of every exchange model.
+const EXPECTED_FIELDS: &[ExpectedFields] = &[
+    #[cfg(feature = "binance")]
+    crate::exchange::binance::EXPECTED_FIELDS,
+    #[cfg(feature = "coinbase")]
+    crate::exchange::coinbase::EXPECTED_FIELDS,
+    #[cfg(feature = "gateio")]
+    crate::exchange::gateio::EXPECTED_FIELDS,
+    #[cfg(feature = "kraken")]
+    crate::exchange::kraken::EXPECTED_FIELDS,
+    #[cfg(feature = "okx")]
+    crate::exchange::okx::EXPECTED_FIELDS,
+];
+
+/// [`Deserializer`] of a JSON [`Value`] that records a [`SchemaDiagnostic`] for each field of
+/// a struct that isn't one of it's deserialised (or ignored) fields, and for each of it's
+/// expected fields that is missing.
+struct Tracked<'a> {
+    value: &'a Value,
+    path: String,
+    fields: SchemaFields<'a>,
+    diagnostics: &'a RefCell<Vec<SchemaDiagnostic>>,
+}
+
+impl<'a> Tracked<'a> {
+    fn child(&self, value: &'a Value, path: String) -> Self {
+        Self {
+            value,
+            path,
+            fields: self.fields,
+            diagnostics: self.diagnostics,
+        }
+    }
+
+    fn map(self, map: &'a Map<String, Value>) -> TrackedMap<'a> {
+        TrackedMap {
+            entries: map.iter(),
+            value: None,
+            parent: self,
+        }
+    }
+
+    fn seq(self, seq: &'a [Value]) -> TrackedSeq<'a> {
+        TrackedSeq {
+            elements: seq.iter().enumerate(),
+            parent: self,
+        }
+    }
+}
+
+macro_rules! forward_to_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'a>,
+            {
+                self.value.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'a> Deserializer<'a> for Tracked<'a> {
+    type Error = serde_json::Error;
+
+    forward_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Object(map) => visitor.visit_map(self.map(map)),
+            Value::Array(seq) => visitor.visit_seq(self.seq(seq)),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.value.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Array(seq) => visitor.visit_seq(self.seq(seq)),
+            value => value.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Object(map) => visitor.visit_map(self.map(map)),
+            value => value.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Object(map) => {
+                // Derived FIELDS include every alias, so any other key is unknown to the model
+                let unknown = map
+                    .keys()
+                    .filter(|key| !fields.contains(&key.as_str()))
+                    .filter(|key| !self.fields.is_ignored(name, key))
+                    .map(|key| {
+                        self.fields.diagnostic(
+                            SchemaDiagnosticKind::UnknownField,
+                            name,
+                            join(&self.path, key),
+                        )
+                    });
+                self.diagnostics.borrow_mut().extend(unknown);
+
+                // Optional fields are defaulted when missing, so check those that are expected
+                let missing = self
+                    .fields
+                    .expected(name)
+                    .filter(|field| !map.contains_key(*field))
+                    .map(|field| {
+                        self.fields.diagnostic(
+                            SchemaDiagnosticKind::MissingField,
+                            name,
+                            join(&self.path, field),
+                        )
+                    });
+                self.diagnostics.borrow_mut().extend(missing);
+
+                visitor.visit_map(self.map(map))
+            }
+            Value::Array(seq) => visitor.visit_seq(self.seq(seq)),
+            value => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+}
+
+/// [`MapAccess`] of a JSON object that deserialises each value via a [`Tracked`].
+struct TrackedMap<'a> {
+    entries: serde_json::map::Iter<'a>,
+    value: Option<(&'a String, &'a Value)>,
+    parent: Tracked<'a>,
+}
+
+impl<'a> MapAccess<'a> for TrackedMap<'a> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'a>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                seed.deserialize(BorrowedStrDeserializer::new(key.as_str()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'a>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| serde::de::Error::custom("value requested before key"))?;
+        seed.deserialize(self.parent.child(value, join(&self.parent.path, key)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// [`SeqAccess`] of a JSON array that deserialises each element via a [`Tracked`].
+struct TrackedSeq<'a> {
+    elements: std::iter::Enumerate<std::slice::Iter<'a, Value>>,
+    parent: Tracked<'a>,
+}
+
+impl<'a> SeqAccess<'a> for TrackedSeq<'a> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'a>,
+    {
+        match self.elements.next() {
+            Some((index, value)) => seed
+                .deserialize(
+                    self.parent
+                        .child(value, format!("{}[{index}]", self.parent.path)),
+                )
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+/// Join the provided field to the provided parent path, eg/ "data[0]" & "px" -> "data[0].px".
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Outer {
+        #[serde(alias = "i")]
+        id: u64,
+        #[serde(default)]
+        note: Option<String>,
+        inner: Option<Inner>,
+        levels: Vec<Inner>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Inner {
+        #[serde(rename = "px")]
+        price: f64,
+    }
+
+    const IGNORED: IgnoredFields = &[("Outer", &["e"]), ("Inner", &["ts"])];
+
+    const EXPECTED: ExpectedFields = &[
+        (ExchangeId::Okx, "Outer", &["note"]),
+        (ExchangeId::Kraken, "Inner", &["ts"]),
+    ];
+
+    #[test]
+    fn test_diagnose() {
+        use SchemaDiagnosticKind::{MissingField, UnknownField};
+
+        struct TestCase {
+            input: &'static str,
+            expected: Vec<(SchemaDiagnosticKind, &'static str, &'static str)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: known fields, aliases, ignored & expected fields are not diagnosed
+                input: r#"{"i":1,"note":"x","e":"x","inner":{"px":1.0,"ts":1},"levels":[{"px":2.0}]}"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC1: unknown top level field
+                input: r#"{"id":1,"note":null,"inner":null,"levels":[],"extra":true}"#,
+                expected: vec![(UnknownField, "Outer", "extra")],
+            },
+            TestCase {
+                // TC2: unknown fields nested in an Option & a Vec
+                input: r#"{"id":1,"note":"x","inner":{"px":1.0,"qty":1},"levels":[{"px":2.0},{"px":3.0,"qty":2}]}"#,
+                expected: vec![
+                    (UnknownField, "Inner", "inner.qty"),
+                    (UnknownField, "Inner", "levels[1].qty"),
+                ],
+            },
+            TestCase {
+                // TC3: ignored fields are specific to their model
+                input: r#"{"id":1,"note":"x","ts":1,"inner":{"px":1.0,"e":"x"},"levels":[]}"#,
+                expected: vec![
+                    (UnknownField, "Inner", "inner.e"),
+                    (UnknownField, "Outer", "ts"),
+                ],
+            },
+            TestCase {
+                // TC4: missing expected field, defaulted by the model
+                input: r#"{"id":1,"inner":null,"levels":[]}"#,
+                expected: vec![(MissingField, "Outer", "note")],
+            },
+            TestCase {
+                // TC5: expected fields are specific to their exchange
+                input: r#"{"id":1,"note":"x","inner":{"px":1.0},"levels":[{"px":2.0}]}"#,
+                expected: vec![],
+            },
+        ];
+
+        let fields = SchemaFields {
+            exchange: ExchangeId::Okx,
+            channel: "trades",
+            ignored: &[IGNORED],
+            expected: &[EXPECTED],
+        };
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let value = serde_json::from_str::<Value>(test.input).unwrap();
+            let diagnostics = diagnose::<Outer>(&value, fields);
+            assert!(
+                diagnostics.iter().all(|diagnostic| {
+                    diagnostic.exchange == ExchangeId::Okx && diagnostic.channel == "trades"
+                }),
+                "TC{} failed",
+                index
+            );
+
+            let mut actual = diagnostics
+                .into_iter()
+                .map(|diagnostic| (diagnostic.kind, diagnostic.model, diagnostic.field))
+                .collect::<Vec<_>>();
+            actual.sort();
+
+            let expected = test
+                .expected
+                .into_iter()
+                .map(|(kind, model, field)| (kind, model, field.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_schema_diagnostic_display() {
+        let diagnostic = SchemaDiagnostic {
+            kind: SchemaDiagnosticKind::MissingField,
+            exchange: ExchangeId::Okx,
+            channel: "orders".to_string(),
+            model: "OkxOrder",
+            field: "data[0].cancelSourceReason".to_string(),
+        };
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "missing expected field data[0].cancelSourceReason in OkxOrder of okx channel orders"
+        );
+    }
+
+    #[cfg(all(
+        feature = "binance",
+        feature = "coinbase",
+        feature = "gateio",
+        feature = "okx"
+    ))]
+    #[test]
+    fn test_check_exchange_fixtures() {
+        use crate::exchange::{
+            binance::{book::l1::BinanceOrderBookL1, trade::BinanceTrade},
+            coinbase::trade::CoinbaseTrade,
+            gateio::spot::trade::GateioSpotTrade,
+            okx::{order::OkxOrders, trade::OkxTrades},
+        };
+
+        struct TestCase {
+            exchange: ExchangeId,
+            channel: &'static str,
+            check: Check,
+            input: &'static str,
+            expected: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: BinanceTrade fixture
+                exchange: ExchangeId::BinanceSpot,
+                channel: "@trade",
+                check: check::<BinanceTrade>,
+                input: r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC1: BinanceTrade fixture w/ injected field
+                exchange: ExchangeId::BinanceSpot,
+                channel: "@trade",
+                check: check::<BinanceTrade>,
+                input: r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","T":1749354825200,"m":false,"Z":"new"}"#,
+                expected: vec!["unknown field Z in BinanceTrade of binance_spot channel @trade"],
+            },
+            TestCase {
+                // TC2: BinanceOrderBookL1 fixture w/ injected field
+                exchange: ExchangeId::BinanceFuturesUsd,
+                channel: "@bookTicker",
+                check: check::<BinanceOrderBookL1>,
+                input: r#"{"u":22606535573,"s":"ETHUSDT","b":"1215.27","B":"32.49","a":"1215.28","A":"13.93","E":1671656397761}"#,
+                expected: vec![
+                    "unknown field E in BinanceOrderBookL1 of binance_futures_usd channel @bookTicker",
+                ],
+            },
+            TestCase {
+                // TC3: BinanceFuturesUsd BinanceTrade fixture w/ expected "X" trade type
+                exchange: ExchangeId::BinanceFuturesUsd,
+                channel: "@trade",
+                check: check::<BinanceTrade>,
+                input: r#"{"e":"trade","E":1649839266194,"T":1749354825200,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","X":"MARKET","m":true}"#,
+                expected: vec![],
+            },
+            TestCase {
+                // TC4: BinanceFuturesUsd BinanceTrade fixture w/ expected "X" trade type removed
+                exchange: ExchangeId::BinanceFuturesUsd,
+                channel: "@trade",
+                check: check::<BinanceTrade>,
+                input: r#"{"e":"trade","E":1649839266194,"T":1749354825200,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","m":true}"#,
+                expected: vec![
+                    "missing expected field X in BinanceTrade of binance_futures_usd channel @trade",
+                ],
+            },
+            TestCase {
+                // TC5: CoinbaseTrade fixture w/ injected field
+                exchange: ExchangeId::Coinbase,
+                channel: "matches",
+                check: check::<CoinbaseTrade>,
+                input: r#"{"type":"match","trade_id":10,"sequence":50,"maker_order_id":"a","taker_order_id":"b","time":"2014-11-07T08:19:27.028459Z","product_id":"BTC-USD","size":"5.23512","price":"400.23","side":"sell","fee":"0.1"}"#,
+                expected: vec!["unknown field fee in CoinbaseTrade of coinbase channel matches"],
+            },
+            TestCase {
+                // TC6: GateioSpotTrade fixture w/ injected nested field
+                exchange: ExchangeId::GateioSpot,
+                channel: "spot.trades",
+                check: check::<GateioSpotTrade>,
+                input: r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.trades","event":"update","result":{"id":309143071,"create_time":1606292218,"create_time_ms":"1606292218213.4578","side":"sell","currency_pair":"GT_USDT","amount":"16.47","price":"0.4705","range":"1-2"}}"#,
+                expected: vec![
+                    "unknown field result.range in GateioSpotTradeInner of gateio_spot channel spot.trades",
+                ],
+            },
+            TestCase {
+                // TC7: OkxTrades fixture w/ injected field in a data element
+                exchange: ExchangeId::Okx,
+                channel: "trades",
+                check: check::<OkxTrades>,
+                input: r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897","count":"3"}]}"#,
+                expected: vec!["unknown field data[0].count in OkxTrade of okx channel trades"],
+            },
+            TestCase {
+                // TC8: OkxOrders fixture w/ expected "cancelSourceReason" removed
+                exchange: ExchangeId::Okx,
+                channel: "orders",
+                check: check::<OkxOrders>,
+                input: r#"{"arg":{"channel":"orders","instId":"BTC-USDT"},"data":[{"ordId":"452197707845865474","clOrdId":"","px":"30000","sz":"0.5","accFillSz":"0","side":"buy","state":"live","uTime":"1654084353264"}]}"#,
+                expected: vec![
+                    "missing expected field data[0].cancelSourceReason in OkxOrder of okx channel orders",
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = (test.check)(test.exchange, test.channel, test.input.as_bytes());
+            let actual = actual
+                .iter()
+                .map(SchemaDiagnostic::to_string)
+                .collect::<Vec<_>>();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+
+        // Payloads that fail to parse into the model are never diagnosed
+        let actual =
+            check::<BinanceTrade>(ExchangeId::BinanceSpot, "@trade", br#"{"e":"trade","Z":1}"#);
+        assert!(actual.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_strict_stream_yields_messages_untouched() {
+        let messages = || {
+            futures::stream::iter(
+                [
+                    WsMessage::Text(r#"{"id":1,"px":1.0,"extra":true}"#.to_string()),
+                    WsMessage::Binary(br#"{"id":2}"#.to_vec()),
+                    WsMessage::Ping(vec![1]),
+                ]
+                .map(|message| Ok((DateTime::<Utc>::UNIX_EPOCH, message))),
+            )
+        };
+
+        for mode in [DeserialiseMode::Lenient, DeserialiseMode::Strict] {
+            let stream =
+                StrictStream::new::<Inner>(ExchangeId::Okx, "trades".to_string(), mode, messages());
+            assert_eq!(stream.check.is_some(), mode == DeserialiseMode::Strict);
+
+            let actual = stream.map(Result::unwrap).collect::<Vec<_>>().await;
+            let expected = messages().map(Result::unwrap).collect::<Vec<_>>().await;
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Convenient re-exports of the types required for the most common usage of Barter-Data.
+//!
+//! ```rust,no_run
+//! use barter_data::prelude::*;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut streams = Streams::<PublicTrades>::builder()
+//!         .subscribe([
+//!             (BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, PublicTrades),
+//!             (BinanceSpot::default(), "eth", "usdt", InstrumentKind::Spot, PublicTrades),
+//!         ])
+//!         .subscribe([(Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+//!         .init()
+//!         .await
+//!         .unwrap();
+//!
+//!     // Drop duplicate BinanceSpot trades, eg/ when connections overlap during a reconnection
+//!     let binance_rx = streams.select(ExchangeId::BinanceSpot).unwrap();
+//!     let mut binance_trades =
+//!         UnboundedReceiverStream::new(binance_rx).dedup(DedupConfig::default());
+//!
+//!     while let Some(trade) = binance_trades.next().await {
+//!         let MarketEvent::<PublicTrade> { instrument, kind, .. } = trade;
+//!         let side: Side = kind.side;
+//!         println!("{instrument}: {side} {} @ {}", kind.amount, kind.price);
+//!     }
+//! }
+//! ```
+
+pub use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+    exchange::{ExchangeId, StreamSelector},
+    recorder::{
+        replay::{ReplayConfig, ReplayPacing, ReplayStream},
+        JsonlRecorder, RecorderConfig,
+    },
+    streams::{
+        adapter::{
+            batch::BatchConfig,
+            cluster::{ClusterConfig, ClusteredLiquidation, LiquidationCluster},
+            dedup::DedupConfig,
+            throttle::{ThrottleConfig, ThrottleMode},
+            AsTrade, MarketEventStreamExt,
+        },
+        builder::{multi::MultiStreamBuilder, StreamBuilder},
+        polling::PollConfig,
+        Streams,
+    },
+    subscription::{
+        average_price::{AveragePrice, AveragePrices},
+        balance::{Balance, Balances},
+        book::{Level, OrderBook, OrderBookL1, OrderBooksL1, OrderBooksL2, OrderBooksL3},
+        candle::{Candle, Candles, Interval},
+        combined::TradesAndBooksL1,
+        fill::{Fill, Fills, Liquidity},
+        instrument_status::{InstrumentStatus, InstrumentStatuses, TradingStatus},
+        liquidation::{Liquidation, Liquidations},
+        open_interest::{OpenInterest, OpenInterests},
+        order::{OrderStatus, OrderUpdate, OrderUpdates},
+        position::{Position, Positions},
+        ticker::{Ticker, Tickers},
+        trade::{PublicTrade, PublicTrades, PublicTradesAggregated, PublicTradesAll},
+        SubKind, Subscription,
+    },
+    MarketStream,
+};
+
+#[cfg(feature = "binance")]
+pub use crate::exchange::binance::{futures::BinanceFuturesUsd, spot::BinanceSpot, us::BinanceUs};
+
+#[cfg(feature = "bitfinex")]
+pub use crate::exchange::bitfinex::Bitfinex;
+
+#[cfg(feature = "coinbase")]
+pub use crate::exchange::coinbase::Coinbase;
+
+#[cfg(feature = "gateio")]
+pub use crate::exchange::gateio::{
+    futures::{GateioFuturesBtc, GateioFuturesUsd},
+    spot::GateioSpot,
+};
+
+#[cfg(feature = "kraken")]
+pub use crate::exchange::kraken::Kraken;
+
+#[cfg(feature = "okx")]
+pub use crate::exchange::okx::Okx;
+
+pub use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side, Symbol};
+
+pub use futures::StreamExt as _;
+pub use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -0,0 +1,618 @@
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent, RawTimestamp, TimestampUnit, MARKET_EVENT_SCHEMA_VERSION},
+    num::Num,
+    subscription::{
+        book::{Level, OrderBook, OrderBookL1, OrderBookSide},
+        candle::Candle,
+        liquidation::Liquidation,
+        trade::PublicTrade,
+    },
+};
+use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+use chrono::{DateTime, TimeZone, Utc};
+use prost::Message;
+
+/// Protobuf schema of the [`MarketEvent<DataKind>`](MarketEvent) wire format, also shipped as
+/// barter-data-rs/proto/market_event.proto for consumers in other languages.
+pub const MARKET_EVENT_PROTO: &str = include_str!("../proto/market_event.proto");
+
+/// Types of the `barter.data.v1` package of the [`MARKET_EVENT_PROTO`] schema, generated by
+/// prost.
+#[allow(clippy::all)]
+pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/barter.data.v1.rs"));
+}
+
+/// Encode the provided event as a `barter.data.v1.MarketEvent` protobuf message.
+pub fn encode(event: &MarketEvent<DataKind>) -> Vec<u8> {
+    v1::MarketEvent::from(event).encode_to_vec()
+}
+
+/// Decode a `barter.data.v1.MarketEvent` protobuf message, validating the schema version.
+///
+/// Unknown fields are skipped, so messages encoded with a newer schema that only adds fields
+/// can still be decoded.
+#[allow(clippy::result_large_err)]
+pub fn decode(bytes: &[u8]) -> Result<MarketEvent<DataKind>, DataError> {
+    v1::MarketEvent::decode(bytes)
+        .map_err(|error| DataError::Protobuf(error.to_string()))
+        .and_then(MarketEvent::try_from)
+}
+
+impl From<&MarketEvent<DataKind>> for v1::MarketEvent {
+    fn from(event: &MarketEvent<DataKind>) -> Self {
+        let kind = match &event.kind {
+            DataKind::Trade(trade) => v1::market_event::Kind::Trade(v1::Trade {
+                id: trade.id.clone(),
+                price: trade.price.to_string(),
+                amount: trade.amount.to_string(),
+                side: v1::Side::from(trade.side).into(),
+            }),
+            DataKind::OrderBookL1(book) => v1::market_event::Kind::OrderBookL1(v1::OrderBookL1 {
+                last_update_time: Some(timestamp(&book.last_update_time)),
+                best_bid: Some(v1::Level::from(&book.best_bid)),
+                best_ask: Some(v1::Level::from(&book.best_ask)),
+            }),
+            DataKind::OrderBook(book) => v1::market_event::Kind::OrderBook(v1::OrderBook {
+                last_update_time: Some(timestamp(&book.last_update_time)),
+                bids: book.bids.levels().iter().map(v1::Level::from).collect(),
+                asks: book.asks.levels().iter().map(v1::Level::from).collect(),
+            }),
+            DataKind::Candle(candle) => v1::market_event::Kind::Candle(v1::Candle {
+                close_time: Some(timestamp(&candle.close_time)),
+                open: candle.open.to_string(),
+                high: candle.high.to_string(),
+                low: candle.low.to_string(),
+                close: candle.close.to_string(),
+                volume: candle.volume.to_string(),
+                trade_count: candle.trade_count,
+                historical: candle.historical,
+            }),
+            DataKind::Liquidation(liquidation) => {
+                v1::market_event::Kind::Liquidation(v1::Liquidation {
+                    side: v1::Side::from(liquidation.side).into(),
+                    price: liquidation.price.to_string(),
+                    quantity: liquidation.quantity.to_string(),
+                    time: Some(timestamp(&liquidation.time)),
+                })
+            }
+        };
+
+        Self {
+            version: u32::from(MARKET_EVENT_SCHEMA_VERSION),
+            exchange_time: Some(timestamp(&event.exchange_time)),
+            received_time: Some(timestamp(&event.received_time)),
+            exchange: event.exchange.to_string(),
+            instrument: Some(v1::Instrument {
+                base: event.instrument.base.to_string(),
+                quote: event.instrument.quote.to_string(),
+                kind: match event.instrument.kind {
+                    InstrumentKind::Spot => v1::InstrumentKind::Spot,
+                    InstrumentKind::FuturePerpetual => v1::InstrumentKind::FuturePerpetual,
+                }
+                .into(),
+            }),
+            kind: Some(kind),
+            raw_exchange_time: event.raw_exchange_time.map(|raw| v1::RawTimestamp {
+                value: raw.value,
+                unit: match raw.unit {
+                    TimestampUnit::Seconds => v1::TimestampUnit::Seconds,
+                    TimestampUnit::Milliseconds => v1::TimestampUnit::Milliseconds,
+                    TimestampUnit::Microseconds => v1::TimestampUnit::Microseconds,
+                    TimestampUnit::Nanoseconds => v1::TimestampUnit::Nanoseconds,
+                }
+                .into(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<v1::MarketEvent> for MarketEvent<DataKind> {
+    type Error = DataError;
+
+    fn try_from(event: v1::MarketEvent) -> Result<Self, Self::Error> {
+        let version = u16::try_from(event.version).unwrap_or(u16::MAX);
+        if version != MARKET_EVENT_SCHEMA_VERSION {
+            return Err(DataError::UnsupportedSchemaVersion(version));
+        }
+
+        let kind = match event.kind.ok_or_else(|| missing("MarketEvent.kind"))? {
+            v1::market_event::Kind::Trade(trade) => DataKind::Trade(PublicTrade {
+                side: side(trade.side)?,
+                price: num(&trade.price)?,
+                amount: num(&trade.amount)?,
+                id: trade.id,
+            }),
+            v1::market_event::Kind::OrderBookL1(book) => DataKind::OrderBookL1(OrderBookL1 {
+                last_update_time: datetime(book.last_update_time, "OrderBookL1.last_update_time")?,
+                best_bid: level(
+                    book.best_bid
+                        .ok_or_else(|| missing("OrderBookL1.best_bid"))?,
+                )?,
+                best_ask: level(
+                    book.best_ask
+                        .ok_or_else(|| missing("OrderBookL1.best_ask"))?,
+                )?,
+            }),
+            v1::market_event::Kind::OrderBook(book) => DataKind::OrderBook(OrderBook {
+                last_update_time: datetime(book.last_update_time, "OrderBook.last_update_time")?,
+                bids: OrderBookSide::new(
+                    Side::Buy,
+                    book.bids
+                        .into_iter()
+                        .map(level)
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                asks: OrderBookSide::new(
+                    Side::Sell,
+                    book.asks
+                        .into_iter()
+                        .map(level)
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+            }),
+            v1::market_event::Kind::Candle(candle) => DataKind::Candle(Candle {
+                close_time: datetime(candle.close_time, "Candle.close_time")?,
+                open: num(&candle.open)?,
+                high: num(&candle.high)?,
+                low: num(&candle.low)?,
+                close: num(&candle.close)?,
+                volume: num(&candle.volume)?,
+                trade_count: candle.trade_count,
+                historical: candle.historical,
+            }),
+            v1::market_event::Kind::Liquidation(liquidation) => {
+                DataKind::Liquidation(Liquidation {
+                    side: side(liquidation.side)?,
+                    price: num(&liquidation.price)?,
+                    quantity: num(&liquidation.quantity)?,
+                    time: datetime(liquidation.time, "Liquidation.time")?,
+                })
+            }
+        };
+
+        let instrument = event
+            .instrument
+            .ok_or_else(|| missing("MarketEvent.instrument"))?;
+        let instrument_kind = match v1::InstrumentKind::try_from(instrument.kind) {
+            Ok(v1::InstrumentKind::Spot) => InstrumentKind::Spot,
+            Ok(v1::InstrumentKind::FuturePerpetual) => InstrumentKind::FuturePerpetual,
+            _ => return Err(invalid("InstrumentKind", instrument.kind)),
+        };
+
+        let raw_exchange_time = event
+            .raw_exchange_time
+            .map(|raw| {
+                let unit = match v1::TimestampUnit::try_from(raw.unit) {
+                    Ok(v1::TimestampUnit::Seconds) => TimestampUnit::Seconds,
+                    Ok(v1::TimestampUnit::Milliseconds) => TimestampUnit::Milliseconds,
+                    Ok(v1::TimestampUnit::Microseconds) => TimestampUnit::Microseconds,
+                    Ok(v1::TimestampUnit::Nanoseconds) => TimestampUnit::Nanoseconds,
+                    _ => return Err(invalid("TimestampUnit", raw.unit)),
+                };
+                Ok(RawTimestamp::new(raw.value, unit))
+            })
+            .transpose()?;
+
+        Ok(MarketEvent {
+            exchange_time: datetime(event.exchange_time, "MarketEvent.exchange_time")?,
+            raw_exchange_time,
+            received_time: datetime(event.received_time, "MarketEvent.received_time")?,
+            exchange: Exchange::from(event.exchange),
+            instrument: Instrument::from((
+                instrument.base.as_str(),
+                instrument.quote.as_str(),
+                instrument_kind,
+            )),
+            kind,
+            meta: (),
+        })
+    }
+}
+
+impl From<Side> for v1::Side {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => Self::Buy,
+            Side::Sell => Self::Sell,
+        }
+    }
+}
+
+impl From<&Level> for v1::Level {
+    fn from(level: &Level) -> Self {
+        Self {
+            price: level.price.to_string(),
+            amount: level.amount.to_string(),
+        }
+    }
+}
+
+fn timestamp(time: &DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn datetime(
+    timestamp: Option<prost_types::Timestamp>,
+    field: &str,
+) -> Result<DateTime<Utc>, DataError> {
+    let timestamp = timestamp.ok_or_else(|| missing(field))?;
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(timestamp.seconds, nanos).single())
+        .ok_or_else(|| {
+            DataError::Protobuf(format!(
+                "invalid Timestamp: {}s {}ns",
+                timestamp.seconds, timestamp.nanos
+            ))
+        })
+}
+
+#[allow(clippy::result_large_err)]
+fn side(value: i32) -> Result<Side, DataError> {
+    match v1::Side::try_from(value) {
+        Ok(v1::Side::Buy) => Ok(Side::Buy),
+        Ok(v1::Side::Sell) => Ok(Side::Sell),
+        _ => Err(invalid("Side", value)),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn level(level: v1::Level) -> Result<Level, DataError> {
+    Ok(Level {
+        price: num(&level.price)?,
+        amount: num(&level.amount)?,
+    })
+}
+
+/// Parse a [`Num`] from it's exact decimal string, also accepting scientific notation (eg/
+/// "1e-8") from encoders in other languages.
+#[allow(clippy::result_large_err)]
+fn num(value: &str) -> Result<Num, DataError> {
+    #[cfg(not(feature = "decimal"))]
+    let num = value.parse::<Num>().ok();
+
+    #[cfg(feature = "decimal")]
+    let num = value
+        .parse::<Num>()
+        .or_else(|_| Num::from_scientific(value))
+        .ok();
+
+    num.ok_or_else(|| DataError::Protobuf(format!("invalid decimal: {value:?}")))
+}
+
+fn missing(field: &str) -> DataError {
+    DataError::Protobuf(format!("missing required field {field}"))
+}
+
+fn invalid(name: &str, value: i32) -> DataError {
+    DataError::Protobuf(format!("invalid {name}: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic xorshift64 generator, so failing round-trips are reproducible.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn time(&mut self) -> DateTime<Utc> {
+            let seconds = (self.next() % 4_102_444_800) as i64 - 2_000_000_000;
+            Utc.timestamp_opt(seconds, (self.next() % 1_000_000_000) as u32)
+                .unwrap()
+        }
+
+        #[cfg(not(feature = "decimal"))]
+        fn double(&mut self) -> f64 {
+            match self.next() % 4 {
+                0 => 0.0,
+                1 => -0.0,
+                2 => (self.next() % 100_000_000) as f64 / 1000.0,
+                _ => f64::from_bits(self.next() >> 2),
+            }
+        }
+
+        fn num(&mut self) -> Num {
+            #[cfg(not(feature = "decimal"))]
+            return self.double();
+            #[cfg(feature = "decimal")]
+            return Num::new((self.next() % 100_000_000) as i64, 3).normalize();
+        }
+
+        fn side(&mut self) -> Side {
+            if self.next().is_multiple_of(2) {
+                Side::Buy
+            } else {
+                Side::Sell
+            }
+        }
+
+        fn level(&mut self) -> Level {
+            Level {
+                price: self.num(),
+                amount: self.num(),
+            }
+        }
+
+        fn event(&mut self, kind: u64) -> MarketEvent<DataKind> {
+            let kind = match kind % 5 {
+                0 => DataKind::Trade(PublicTrade {
+                    id: self.next().to_string(),
+                    price: self.num(),
+                    amount: self.num(),
+                    side: self.side(),
+                }),
+                1 => DataKind::OrderBookL1(OrderBookL1 {
+                    last_update_time: self.time(),
+                    best_bid: self.level(),
+                    best_ask: self.level(),
+                }),
+                2 => DataKind::OrderBook(OrderBook {
+                    last_update_time: self.time(),
+                    bids: OrderBookSide::new(
+                        Side::Buy,
+                        (0..self.next() % 5)
+                            .map(|_| self.level())
+                            .collect::<Vec<_>>(),
+                    ),
+                    asks: OrderBookSide::new(
+                        Side::Sell,
+                        (0..self.next() % 5)
+                            .map(|_| self.level())
+                            .collect::<Vec<_>>(),
+                    ),
+                }),
+                3 => DataKind::Candle(Candle {
+                    close_time: self.time(),
+                    open: self.num(),
+                    high: self.num(),
+                    low: self.num(),
+                    close: self.num(),
+                    volume: self.num(),
+                    trade_count: self.next() >> (self.next() % 64),
+                    historical: self.next() & 1 == 0,
+                }),
+                _ => DataKind::Liquidation(Liquidation {
+                    side: self.side(),
+                    price: self.num(),
+                    quantity: self.num(),
+                    time: self.time(),
+                }),
+            };
+
+            let raw_exchange_time = match self.next() % 5 {
+                0 => None,
+                1 => Some(RawTimestamp::new(
+                    self.next() as i64,
+                    TimestampUnit::Seconds,
+                )),
+                2 => Some(RawTimestamp::new(
+                    self.next() as i64,
+                    TimestampUnit::Milliseconds,
+                )),
+                3 => Some(RawTimestamp::new(
+                    self.next() as i64,
+                    TimestampUnit::Microseconds,
+                )),
+                _ => Some(RawTimestamp::new(
+                    self.next() as i64,
+                    TimestampUnit::Nanoseconds,
+                )),
+            };
+
+            MarketEvent {
+                exchange_time: self.time(),
+                raw_exchange_time,
+                received_time: self.time(),
+                exchange: Exchange::from(["binance_spot", "okx", ""][self.next() as usize % 3]),
+                instrument: Instrument::from((
+                    "btc",
+                    "usdt",
+                    if self.next().is_multiple_of(2) {
+                        InstrumentKind::Spot
+                    } else {
+                        InstrumentKind::FuturePerpetual
+                    },
+                )),
+                kind,
+                meta: (),
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+        for n in 0..1000 {
+            let event = rng.event(n);
+            let actual = decode(&encode(&event)).unwrap();
+
+            // Compare bit patterns via Debug so -0.0 & 0.0 are distinguished
+            assert_eq!(
+                format!("{actual:?}"),
+                format!("{event:?}"),
+                "round-trip {n} failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_round_trip_is_lossless() {
+        struct TestCase {
+            input: Num,
+            expected: &'static str,
+        }
+
+        #[cfg(not(feature = "decimal"))]
+        let tests = vec![
+            TestCase {
+                // TC0: shortest representation that round-trips
+                input: 0.1,
+                expected: "0.1",
+            },
+            TestCase {
+                // TC1: negative zero
+                input: -0.0,
+                expected: "-0",
+            },
+            TestCase {
+                // TC2: all 17 significant digits
+                input: 0.1 + 0.2,
+                expected: "0.30000000000000004",
+            },
+        ];
+
+        #[cfg(feature = "decimal")]
+        let tests = vec![
+            TestCase {
+                // TC0: more significant digits than a double holds
+                input: "16500.1234567890123456789".parse().unwrap(),
+                expected: "16500.1234567890123456789",
+            },
+            TestCase {
+                // TC1: trailing zeros of the exchange payload are preserved
+                input: "0.2500".parse().unwrap(),
+                expected: "0.2500",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let level = v1::Level::from(&Level::new(test.input, test.input));
+            assert_eq!(level.price, test.expected, "TC{} failed", index);
+
+            let actual = super::level(level).unwrap();
+            assert_eq!(
+                format!("{:?}", actual.price),
+                format!("{:?}", test.input),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        struct TestCase {
+            input: Vec<u8>,
+            expected: Result<&'static str, &'static str>,
+        }
+
+        let mut rng = XorShift(42);
+        let trade = encode(&rng.event(0));
+
+        // Unknown fields of every wire type appended to a valid message
+        let mut unknown_fields = trade.clone();
+        unknown_fields.extend([0x98, 0x06, 0x01]); // field 99, varint 1
+        unknown_fields.extend([0xA1, 0x06, 0, 0, 0, 0, 0, 0, 0, 0]); // field 100, fixed64
+        unknown_fields.extend([0xAA, 0x06, 0x02, b'h', b'i']); // field 101, bytes "hi"
+        unknown_fields.extend([0xB5, 0x06, 0, 0, 0, 0]); // field 102, fixed32
+
+        let mut bad_version = vec![0x08, 0x02];
+        bad_version.extend(&trade[2..]);
+
+        let message = |kind: Option<v1::market_event::Kind>| {
+            v1::MarketEvent {
+                version: u32::from(MARKET_EVENT_SCHEMA_VERSION),
+                exchange_time: Some(prost_types::Timestamp::default()),
+                received_time: Some(prost_types::Timestamp::default()),
+                exchange: "binance_spot".to_string(),
+                instrument: Some(v1::Instrument {
+                    base: "btc".to_string(),
+                    quote: "usdt".to_string(),
+                    kind: v1::InstrumentKind::Spot.into(),
+                }),
+                kind,
+                raw_exchange_time: None,
+            }
+            .encode_to_vec()
+        };
+        let trade_with = |side: v1::Side, price: &str| {
+            message(Some(v1::market_event::Kind::Trade(v1::Trade {
+                id: "1".to_string(),
+                price: price.to_string(),
+                amount: "1".to_string(),
+                side: side.into(),
+            })))
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: valid trade
+                input: trade.clone(),
+                expected: Ok("trade"),
+            },
+            TestCase {
+                // TC1: unknown fields are skipped
+                input: unknown_fields,
+                expected: Ok("trade"),
+            },
+            TestCase {
+                // TC2: unsupported schema version
+                input: bad_version,
+                expected: Err(
+                    "UnsupportedSchemaVersion: MarketEvent schema version 2 is not supported",
+                ),
+            },
+            TestCase {
+                // TC3: truncated message
+                input: trade[..3].to_vec(),
+                expected: Err(
+                    "Protobuf: failed to decode Protobuf message: MarketEvent.exchange_time: invalid varint",
+                ),
+            },
+            TestCase {
+                // TC4: empty message is missing the schema version
+                input: vec![],
+                expected: Err(
+                    "UnsupportedSchemaVersion: MarketEvent schema version 0 is not supported",
+                ),
+            },
+            TestCase {
+                // TC5: kind is missing
+                input: message(None),
+                expected: Err("Protobuf: missing required field MarketEvent.kind"),
+            },
+            TestCase {
+                // TC6: trade has an unspecified side
+                input: trade_with(v1::Side::Unspecified, "1"),
+                expected: Err("Protobuf: invalid Side: 0"),
+            },
+            TestCase {
+                // TC7: trade has an empty price
+                input: trade_with(v1::Side::Buy, ""),
+                expected: Err("Protobuf: invalid decimal: \"\""),
+            },
+            TestCase {
+                // TC8: scientific notation price
+                input: trade_with(v1::Side::Buy, "1e-8"),
+                expected: Ok("trade"),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = decode(&test.input)
+                .map(|event| event.kind.as_str())
+                .map_err(|error| error.to_string());
+            assert_eq!(
+                actual,
+                test.expected.map_err(str::to_string),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+}
@@ -0,0 +1,507 @@
+use crate::{
+    event::{DataKind, MarketEvent},
+    num::Num,
+};
+use barter_integration::model::Side;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// Default number of rows in a [`ColumnBatch`] before it is yielded by a [`ColumnarBatcher`].
+pub const DEFAULT_COLUMN_BATCH_SIZE: usize = 8192;
+
+/// Logical type of a column, mirroring the Arrow data type a columnar file writer maps it to.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ColumnType {
+    /// UTC timestamp (Arrow `Timestamp(Nanosecond, "UTC")`).
+    Timestamp,
+    /// UTF-8 string (Arrow `Utf8`).
+    Utf8,
+    /// Unsigned integer (Arrow `UInt64`).
+    UInt64,
+    /// [`Num`] price or amount (Arrow `Float64`, or `Decimal128` with the `decimal` feature).
+    Num,
+}
+
+/// Name & [`ColumnType`] of a column in a [`ColumnBatch`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ColumnSchema {
+    pub name: &'static str,
+    pub data_type: ColumnType,
+}
+
+const fn column(name: &'static str, data_type: ColumnType) -> ColumnSchema {
+    ColumnSchema { name, data_type }
+}
+
+/// Columns common to every [`DataKind`] schema, describing the [`MarketEvent`] itself.
+macro_rules! event_columns {
+    ($($kind_column:expr),* $(,)?) => {
+        &[
+            column("exchange", ColumnType::Utf8),
+            column("base", ColumnType::Utf8),
+            column("quote", ColumnType::Utf8),
+            column("instrument_kind", ColumnType::Utf8),
+            column("exchange_time", ColumnType::Timestamp),
+            column("received_time", ColumnType::Timestamp),
+            $($kind_column),*
+        ]
+    };
+}
+
+/// [`DataKind::Trade`] schema, with one row per trade.
+pub const TRADE_SCHEMA: &[ColumnSchema] = event_columns![
+    column("id", ColumnType::Utf8),
+    column("price", ColumnType::Num),
+    column("amount", ColumnType::Num),
+    column("side", ColumnType::Utf8),
+];
+
+/// [`DataKind::OrderBookL1`] schema, with one row per best bid & ask update.
+pub const ORDER_BOOK_L1_SCHEMA: &[ColumnSchema] = event_columns![
+    column("last_update_time", ColumnType::Timestamp),
+    column("bid_price", ColumnType::Num),
+    column("bid_amount", ColumnType::Num),
+    column("ask_price", ColumnType::Num),
+    column("ask_amount", ColumnType::Num),
+];
+
+/// [`DataKind::OrderBook`] schema, with one row per level of each OrderBook snapshot.
+///
+/// Levels are ordered from best to worst, with `depth` 0 being the best level of each side.
+pub const ORDER_BOOK_SCHEMA: &[ColumnSchema] = event_columns![
+    column("last_update_time", ColumnType::Timestamp),
+    column("side", ColumnType::Utf8),
+    column("depth", ColumnType::UInt64),
+    column("price", ColumnType::Num),
+    column("amount", ColumnType::Num),
+];
+
+/// [`DataKind::Candle`] schema, with one row per candle.
+pub const CANDLE_SCHEMA: &[ColumnSchema] = event_columns![
+    column("close_time", ColumnType::Timestamp),
+    column("open", ColumnType::Num),
+    column("high", ColumnType::Num),
+    column("low", ColumnType::Num),
+    column("close", ColumnType::Num),
+    column("volume", ColumnType::Num),
+    column("trade_count", ColumnType::UInt64),
+];
+
+/// [`DataKind::Liquidation`] schema, with one row per liquidation.
+pub const LIQUIDATION_SCHEMA: &[ColumnSchema] = event_columns![
+    column("side", ColumnType::Utf8),
+    column("price", ColumnType::Num),
+    column("quantity", ColumnType::Num),
+    column("time", ColumnType::Timestamp),
+];
+
+/// Determine the schema of the rows generated from the provided [`DataKind`].
+pub fn schema(kind: &DataKind) -> &'static [ColumnSchema] {
+    match kind {
+        DataKind::Trade(_) => TRADE_SCHEMA,
+        DataKind::OrderBookL1(_) => ORDER_BOOK_L1_SCHEMA,
+        DataKind::OrderBook(_) => ORDER_BOOK_SCHEMA,
+        DataKind::Candle(_) => CANDLE_SCHEMA,
+        DataKind::Liquidation(_) => LIQUIDATION_SCHEMA,
+    }
+}
+
+/// Single value of a row, in [`ColumnSchema`] order.
+#[derive(Clone, PartialEq, Debug)]
+enum Value {
+    Timestamp(DateTime<Utc>),
+    Utf8(String),
+    UInt64(u64),
+    Num(Num),
+}
+
+/// Column of values of a single [`ColumnType`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum Column {
+    Timestamp(Vec<DateTime<Utc>>),
+    Utf8(Vec<String>),
+    UInt64(Vec<u64>),
+    Num(Vec<Num>),
+}
+
+impl Column {
+    fn new(data_type: ColumnType, capacity: usize) -> Self {
+        match data_type {
+            ColumnType::Timestamp => Self::Timestamp(Vec::with_capacity(capacity)),
+            ColumnType::Utf8 => Self::Utf8(Vec::with_capacity(capacity)),
+            ColumnType::UInt64 => Self::UInt64(Vec::with_capacity(capacity)),
+            ColumnType::Num => Self::Num(Vec::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        match (self, value) {
+            (Self::Timestamp(column), Value::Timestamp(value)) => column.push(value),
+            (Self::Utf8(column), Value::Utf8(value)) => column.push(value),
+            (Self::UInt64(column), Value::UInt64(value)) => column.push(value),
+            (Self::Num(column), Value::Num(value)) => column.push(value),
+            (column, value) => unreachable!("{value:?} does not match column {column:?}"),
+        }
+    }
+}
+
+/// Batch of rows generated from [`MarketEvent<DataKind>`](MarketEvent)s of the same kind, stored
+/// column-wise ready to be converted into a columnar format (eg/ an Arrow `RecordBatch`).
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnBatch {
+    pub schema: &'static [ColumnSchema],
+    pub columns: Vec<Column>,
+    pub num_rows: usize,
+}
+
+impl ColumnBatch {
+    /// Construct a new empty [`Self`] with the provided schema.
+    pub fn new(schema: &'static [ColumnSchema], capacity: usize) -> Self {
+        Self {
+            schema,
+            columns: schema
+                .iter()
+                .map(|column| Column::new(column.data_type, capacity))
+                .collect(),
+            num_rows: 0,
+        }
+    }
+
+    /// Find the [`Column`] with the provided name.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.schema
+            .iter()
+            .position(|column| column.name == name)
+            .map(|index| &self.columns[index])
+    }
+
+    /// Append the row(s) generated from the provided event, which must match [`Self::schema`].
+    pub fn push(&mut self, event: &MarketEvent<DataKind>) {
+        debug_assert_eq!(self.schema, schema(&event.kind));
+
+        for row in rows(event) {
+            for (column, value) in self.columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+            self.num_rows += 1;
+        }
+    }
+}
+
+/// Generate the rows of the provided event, each in [`schema`] order.
+fn rows(event: &MarketEvent<DataKind>) -> Vec<Vec<Value>> {
+    let event_values = || {
+        vec![
+            Value::Utf8(event.exchange.to_string()),
+            Value::Utf8(event.instrument.base.to_string()),
+            Value::Utf8(event.instrument.quote.to_string()),
+            Value::Utf8(event.instrument.kind.to_string()),
+            Value::Timestamp(event.exchange_time),
+            Value::Timestamp(event.received_time),
+        ]
+    };
+    let row = |kind_values: Vec<Value>| {
+        let mut values = event_values();
+        values.extend(kind_values);
+        values
+    };
+
+    match &event.kind {
+        DataKind::Trade(trade) => vec![row(vec![
+            Value::Utf8(trade.id.clone()),
+            Value::Num(trade.price),
+            Value::Num(trade.amount),
+            Value::Utf8(side(trade.side)),
+        ])],
+        DataKind::OrderBookL1(book) => vec![row(vec![
+            Value::Timestamp(book.last_update_time),
+            Value::Num(book.best_bid.price),
+            Value::Num(book.best_bid.amount),
+            Value::Num(book.best_ask.price),
+            Value::Num(book.best_ask.amount),
+        ])],
+        DataKind::OrderBook(book) => [(Side::Buy, &book.bids), (Side::Sell, &book.asks)]
+            .into_iter()
+            .flat_map(|(book_side, levels)| {
+                levels
+                    .levels()
+                    .iter()
+                    .enumerate()
+                    .map(move |(depth, level)| {
+                        row(vec![
+                            Value::Timestamp(book.last_update_time),
+                            Value::Utf8(side(book_side)),
+                            Value::UInt64(depth as u64),
+                            Value::Num(level.price),
+                            Value::Num(level.amount),
+                        ])
+                    })
+            })
+            .collect(),
+        DataKind::Candle(candle) => vec![row(vec![
+            Value::Timestamp(candle.close_time),
+            Value::Num(candle.open),
+            Value::Num(candle.high),
+            Value::Num(candle.low),
+            Value::Num(candle.close),
+            Value::Num(candle.volume),
+            Value::UInt64(candle.trade_count),
+        ])],
+        DataKind::Liquidation(liquidation) => vec![row(vec![
+            Value::Utf8(side(liquidation.side)),
+            Value::Num(liquidation.price),
+            Value::Num(liquidation.quantity),
+            Value::Timestamp(liquidation.time),
+        ])],
+    }
+}
+
+fn side(side: Side) -> String {
+    match side {
+        Side::Buy => "buy",
+        Side::Sell => "sell",
+    }
+    .to_string()
+}
+
+/// Partition of a [`ColumnarBatcher`], one per [`DataKind`] per UTC day of `exchange_time`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Partition {
+    pub kind: &'static str,
+    pub date: NaiveDate,
+}
+
+impl From<&MarketEvent<DataKind>> for Partition {
+    fn from(event: &MarketEvent<DataKind>) -> Self {
+        Self {
+            kind: event.kind.as_str(),
+            date: event.exchange_time.date_naive(),
+        }
+    }
+}
+
+/// Buffers [`MarketEvent<DataKind>`](MarketEvent)s into a [`ColumnBatch`] per [`Partition`],
+/// yielding each batch once it contains at least the configured number of rows.
+///
+/// Used as the format agnostic front end of a columnar file writer, which writes each yielded
+/// [`ColumnBatch`] to the file (or row group) of it's [`Partition`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnarBatcher {
+    batch_size: usize,
+    batches: HashMap<Partition, ColumnBatch>,
+}
+
+impl Default for ColumnarBatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_COLUMN_BATCH_SIZE)
+    }
+}
+
+impl ColumnarBatcher {
+    /// Construct a new [`Self`] that yields batches of (at least) the provided number of rows.
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            batches: HashMap::new(),
+        }
+    }
+
+    /// Add the provided event to the batch of it's [`Partition`], returning the batch if full.
+    pub fn push(&mut self, event: &MarketEvent<DataKind>) -> Option<(Partition, ColumnBatch)> {
+        let partition = Partition::from(event);
+        let batch_size = self.batch_size;
+
+        let batch = self
+            .batches
+            .entry(partition)
+            .or_insert_with(|| ColumnBatch::new(schema(&event.kind), batch_size));
+        batch.push(event);
+
+        (batch.num_rows >= batch_size)
+            .then(|| self.batches.remove(&partition))
+            .flatten()
+            .map(|batch| (partition, batch))
+    }
+
+    /// Remove every partially filled batch, sorted by [`Partition`].
+    pub fn flush(&mut self) -> Vec<(Partition, ColumnBatch)> {
+        let mut batches = self.batches.drain().collect::<Vec<_>>();
+        batches.sort_by_key(|(partition, _)| *partition);
+        batches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::{
+        book::{Level, OrderBook, OrderBookL1, OrderBookSide},
+        trade::PublicTrade,
+    };
+    use crate::test_util::trade::TradeFixture;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_base_ms(0).with_latency_ms(1)
+    }
+
+    fn time(ms: i64) -> DateTime<Utc> {
+        fixture().time(ms)
+    }
+
+    fn event(ms: i64, kind: DataKind) -> MarketEvent<DataKind> {
+        fixture().event(ms, "btc", kind)
+    }
+
+    fn trade(ms: i64, id: u64) -> MarketEvent<DataKind> {
+        event(
+            ms,
+            DataKind::Trade(PublicTrade {
+                id: id.to_string(),
+                price: num!(100 + id),
+                amount: num!(0.5),
+                side: if id.is_multiple_of(2) {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+            }),
+        )
+    }
+
+    fn book_l1(ms: i64) -> MarketEvent<DataKind> {
+        event(
+            ms,
+            DataKind::OrderBookL1(OrderBookL1 {
+                last_update_time: time(ms),
+                best_bid: Level::new(num!(99.0), num!(1.0)),
+                best_ask: Level::new(num!(101.0), num!(2.0)),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_columnar_batcher_trades_and_books() {
+        let mut batcher = ColumnarBatcher::new(1000);
+
+        let mut full = Vec::new();
+        for id in 0..2500 {
+            full.extend(batcher.push(&trade(id as i64, id)));
+            full.extend(batcher.push(&book_l1(id as i64)));
+        }
+        let partial = batcher.flush();
+
+        let date = time(0).date_naive();
+        let trades = Partition {
+            kind: "trade",
+            date,
+        };
+        let books = Partition {
+            kind: "order_book_l1",
+            date,
+        };
+
+        // Full batches are yielded as they fill, then the remainder is flushed
+        let actual = full
+            .iter()
+            .chain(&partial)
+            .map(|(partition, batch)| (*partition, batch.num_rows))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (trades, 1000),
+            (books, 1000),
+            (trades, 1000),
+            (books, 1000),
+            (books, 500),
+            (trades, 500),
+        ];
+        assert_eq!(actual, expected);
+
+        // Schema & values survive batching
+        let (_, batch) = &full[0];
+        assert_eq!(batch.schema, TRADE_SCHEMA);
+        assert_eq!(batch.columns.len(), TRADE_SCHEMA.len());
+        assert!(batch.columns.iter().all(|column| match column {
+            Column::Timestamp(values) => values.len() == 1000,
+            Column::Utf8(values) => values.len() == 1000,
+            Column::UInt64(values) => values.len() == 1000,
+            Column::Num(values) => values.len() == 1000,
+        }));
+
+        let Some(Column::Utf8(ids)) = batch.column("id") else {
+            panic!("missing id column");
+        };
+        assert_eq!(ids[999], "999");
+        let Some(Column::Num(prices)) = batch.column("price") else {
+            panic!("missing price column");
+        };
+        assert_eq!(prices[999], num!(1099.0));
+        let Some(Column::Utf8(sides)) = batch.column("side") else {
+            panic!("missing side column");
+        };
+        assert_eq!(&sides[..2], ["buy", "sell"]);
+        let Some(Column::Timestamp(received)) = batch.column("received_time") else {
+            panic!("missing received_time column");
+        };
+        assert_eq!(received[0], time(1));
+
+        let (_, batch) = &full[1];
+        assert_eq!(batch.schema, ORDER_BOOK_L1_SCHEMA);
+        assert_eq!(
+            batch.column("ask_amount"),
+            Some(&Column::Num(vec![num!(2.0); 1000]))
+        );
+    }
+
+    #[test]
+    fn test_columnar_batcher_partitions_by_day() {
+        let mut batcher = ColumnarBatcher::default();
+
+        batcher.push(&trade(DAY_MS - 1, 0));
+        batcher.push(&trade(DAY_MS, 1));
+        batcher.push(&trade(DAY_MS + 1, 2));
+
+        let actual = batcher
+            .flush()
+            .into_iter()
+            .map(|(partition, batch)| (partition.date, batch.num_rows))
+            .collect::<Vec<_>>();
+
+        let expected = vec![(time(0).date_naive(), 1), (time(DAY_MS).date_naive(), 2)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_column_batch_order_book_row_per_level() {
+        let mut batch = ColumnBatch::new(ORDER_BOOK_SCHEMA, 4);
+
+        batch.push(&event(
+            0,
+            DataKind::OrderBook(OrderBook {
+                last_update_time: time(0),
+                bids: OrderBookSide::new(
+                    Side::Buy,
+                    [(num!(99.0), num!(1.0)), (num!(98.0), num!(2.0))],
+                ),
+                asks: OrderBookSide::new(Side::Sell, [(num!(101.0), num!(3.0))]),
+            }),
+        ));
+
+        assert_eq!(batch.num_rows, 3);
+        assert_eq!(
+            batch.column("side"),
+            Some(&Column::Utf8(vec![
+                "buy".to_string(),
+                "buy".to_string(),
+                "sell".to_string()
+            ]))
+        );
+        assert_eq!(batch.column("depth"), Some(&Column::UInt64(vec![0, 1, 0])));
+        assert_eq!(
+            batch.column("price"),
+            Some(&Column::Num(vec![num!(99.0), num!(98.0), num!(101.0)]))
+        );
+    }
+}
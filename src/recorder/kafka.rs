@@ -0,0 +1,520 @@
+use super::instrument_key;
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+};
+use futures::{Stream, StreamExt};
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::sync::{mpsc, watch};
+use tracing::warn;
+
+/// Default time to wait before retrying to enqueue a [`KafkaRecord`] after the
+/// [`KafkaProducer`] queue was full.
+pub const DEFAULT_QUEUE_FULL_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Determines the topic each [`MarketEvent<DataKind>`](MarketEvent) is published to.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum TopicNaming {
+    /// Publish every event to the same topic.
+    Single(String),
+    /// Publish each event to the "{prefix}.{exchange}.{kind}" topic, eg/ "market.binance_spot.trade".
+    PerExchangeKind { prefix: String },
+}
+
+impl TopicNaming {
+    /// Determine the topic the provided event is published to.
+    pub fn topic(&self, event: &MarketEvent<DataKind>) -> String {
+        match self {
+            Self::Single(topic) => topic.clone(),
+            Self::PerExchangeKind { prefix } => {
+                format!("{prefix}.{}.{}", event.exchange, event.kind.as_str())
+            }
+        }
+    }
+}
+
+/// Encoding of each published [`KafkaRecord`] payload.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum PayloadEncoding {
+    /// Versioned [`MarketEventEnvelope`](crate::event::MarketEventEnvelope) JSON, see
+    /// [`MarketEvent::to_json`].
+    #[default]
+    Json,
+    /// Compact `barter.data.v1.MarketEvent` protobuf, see [`MarketEvent::to_protobuf`].
+    /// Requires the `proto` feature.
+    #[cfg(feature = "proto")]
+    Protobuf,
+}
+
+impl PayloadEncoding {
+    /// Encode the provided event into a payload.
+    #[allow(clippy::result_large_err)]
+    pub fn encode(&self, event: &MarketEvent<DataKind>) -> Result<Vec<u8>, DataError> {
+        match self {
+            Self::Json => event.to_json().map(String::into_bytes),
+            #[cfg(feature = "proto")]
+            Self::Protobuf => Ok(event.to_protobuf()),
+        }
+    }
+}
+
+/// Configuration of a [`KafkaSink`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct KafkaSinkConfig {
+    /// Determines the topic each event is published to.
+    pub topic: TopicNaming,
+    /// Encoding of each published payload.
+    pub encoding: PayloadEncoding,
+    /// Time to wait before retrying to enqueue a record after the producer queue was full.
+    pub queue_full_backoff: Duration,
+}
+
+impl KafkaSinkConfig {
+    /// Construct a new [`Self`] using the provided [`TopicNaming`], JSON payloads & the default
+    /// queue full backoff.
+    pub fn new(topic: TopicNaming) -> Self {
+        Self {
+            topic,
+            encoding: PayloadEncoding::default(),
+            queue_full_backoff: DEFAULT_QUEUE_FULL_BACKOFF,
+        }
+    }
+}
+
+/// Record published to Kafka by a [`KafkaProducer`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct KafkaRecord {
+    pub topic: String,
+    /// Instrument key (eg/ "btc_usdt_spot"), so every event of an instrument is published to the
+    /// same partition & therefore consumed in order.
+    pub key: String,
+    pub payload: Vec<u8>,
+}
+
+/// Reason a [`KafkaProducer`] failed to enqueue a [`KafkaRecord`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum KafkaProduceError {
+    /// Producer queue is full, so the [`KafkaRecord`] should be retried once it has drained.
+    QueueFull,
+    /// Record could not be enqueued (eg/ payload too large, unknown topic), and is discarded.
+    Rejected(String),
+}
+
+/// Kafka producer used by a [`KafkaSink`] (eg/ an `rdkafka::producer::FutureProducer`, which
+/// implements it when the `kafka` feature is enabled).
+pub trait KafkaProducer: Send + Sync + 'static {
+    /// Resolves once the broker has acknowledged (or failed to acknowledge) the record.
+    type Delivery: Future<Output = Result<(), String>> + Send + 'static;
+
+    /// Enqueue a [`KafkaRecord`] for delivery without waiting, returning the record alongside
+    /// the [`KafkaProduceError`] if it could not be enqueued.
+    fn send(&self, record: KafkaRecord)
+        -> Result<Self::Delivery, (KafkaRecord, KafkaProduceError)>;
+}
+
+#[cfg(feature = "kafka")]
+impl<Context> KafkaProducer for rdkafka::producer::FutureProducer<Context>
+where
+    Context: rdkafka::ClientContext + 'static,
+{
+    type Delivery = futures::future::Map<
+        rdkafka::producer::DeliveryFuture,
+        fn(<rdkafka::producer::DeliveryFuture as Future>::Output) -> Result<(), String>,
+    >;
+
+    fn send(
+        &self,
+        record: KafkaRecord,
+    ) -> Result<Self::Delivery, (KafkaRecord, KafkaProduceError)> {
+        use futures::FutureExt;
+        use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+
+        let enqueued = self
+            .send_result(
+                rdkafka::producer::FutureRecord::to(&record.topic)
+                    .key(&record.key)
+                    .payload(&record.payload),
+            )
+            .map_err(|(error, _)| error);
+
+        match enqueued {
+            Ok(delivery) => Ok(delivery.map(|delivery| match delivery {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err((error, _))) => Err(error.to_string()),
+                Err(_) => Err("producer dropped before delivery".to_string()),
+            })),
+            Err(KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull)) => {
+                Err((record, KafkaProduceError::QueueFull))
+            }
+            Err(error) => Err((record, KafkaProduceError::Rejected(error.to_string()))),
+        }
+    }
+}
+
+/// Sink that publishes [`MarketEvent<DataKind>`](MarketEvent)s to Kafka via a [`KafkaProducer`].
+///
+/// Enqueueing waits while the producer queue is full, applying backpressure to the publishing
+/// task. Encoding, enqueueing & delivery failures never panic, instead they are sent to the
+/// [`DataError`] receiver returned by [`KafkaSink::new`] and the event is discarded.
+///
+/// ### Durability
+/// - [`KafkaSink::flush`] returns once the broker has acknowledged (or failed to acknowledge)
+///   every enqueued record, with failures sent to the [`DataError`] receiver as usual.
+/// - Records enqueued but not yet acknowledged when a [`KafkaSink`] is dropped are owned by the
+///   [`KafkaProducer`], so flush the producer itself before exiting.
+#[derive(Debug)]
+pub struct KafkaSink<Producer> {
+    producer: Arc<Producer>,
+    config: KafkaSinkConfig,
+    errors: mpsc::UnboundedSender<DataError>,
+    /// Number of enqueued records whose delivery has not yet been acknowledged.
+    in_flight: Arc<watch::Sender<usize>>,
+}
+
+impl<Producer> KafkaSink<Producer>
+where
+    Producer: KafkaProducer,
+{
+    /// Construct a new [`Self`] using the provided [`KafkaProducer`], returning it alongside the
+    /// receiver of every publishing [`DataError`].
+    pub fn new(
+        producer: Producer,
+        config: KafkaSinkConfig,
+    ) -> (Self, mpsc::UnboundedReceiver<DataError>) {
+        let (errors, errors_rx) = mpsc::unbounded_channel();
+        let sink = Self {
+            producer: Arc::new(producer),
+            config,
+            errors,
+            in_flight: Arc::new(watch::channel(0).0),
+        };
+
+        (sink, errors_rx)
+    }
+
+    /// Construct the [`KafkaRecord`] the provided event is published as.
+    #[allow(clippy::result_large_err)]
+    pub fn record(&self, event: &MarketEvent<DataKind>) -> Result<KafkaRecord, DataError> {
+        Ok(KafkaRecord {
+            topic: self.config.topic.topic(event),
+            key: instrument_key(&event.instrument),
+            payload: self.config.encoding.encode(event)?,
+        })
+    }
+
+    /// Publish the provided event, waiting while the producer queue is full.
+    ///
+    /// Returns once the record is enqueued, with delivery awaited on a separate task.
+    pub async fn publish(&self, event: &MarketEvent<DataKind>) {
+        let mut record = match self.record(event) {
+            Ok(record) => record,
+            Err(error) => return self.error(error),
+        };
+        let topic = record.topic.clone();
+
+        let delivery = loop {
+            match self.producer.send(record) {
+                Ok(delivery) => break delivery,
+                Err((queued, KafkaProduceError::QueueFull)) => {
+                    record = queued;
+                    tokio::time::sleep(self.config.queue_full_backoff).await;
+                }
+                Err((_, KafkaProduceError::Rejected(reason))) => {
+                    return self.error(DataError::Publish { topic, reason });
+                }
+            }
+        };
+
+        let errors = self.errors.clone();
+        let in_flight = Arc::clone(&self.in_flight);
+        in_flight.send_modify(|in_flight| *in_flight += 1);
+        tokio::spawn(async move {
+            if let Err(reason) = delivery.await {
+                let _ = errors.send(DataError::Publish { topic, reason });
+            }
+            in_flight.send_modify(|in_flight| *in_flight -= 1);
+        });
+    }
+
+    /// Wait until the delivery of every enqueued record has been acknowledged (or failed).
+    pub async fn flush(&self) {
+        let mut in_flight = self.in_flight.subscribe();
+        while *in_flight.borrow_and_update() > 0 {
+            if in_flight.changed().await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Publish every event yielded by the provided [`Stream`] until it ends.
+    ///
+    /// The stream is not polled while the producer queue is full.
+    pub async fn publish_stream<St>(&self, stream: St)
+    where
+        St: Stream<Item = MarketEvent<DataKind>>,
+    {
+        futures::pin_mut!(stream);
+        while let Some(event) = stream.next().await {
+            self.publish(&event).await;
+        }
+    }
+
+    fn error(&self, error: DataError) {
+        warn!(%error, "KafkaSink failed to publish MarketEvent");
+        let _ = self.errors.send(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use futures::future::BoxFuture;
+    use std::sync::Mutex;
+    use tokio::sync::oneshot;
+
+    /// Mock [`KafkaProducer`] with a bounded queue, where each record is delivered (or fails)
+    /// once the test sends the outcome to it's delivery receiver.
+    #[derive(Default)]
+    struct MockProducer {
+        capacity: usize,
+        reject: bool,
+        queued: Arc<Mutex<Vec<KafkaRecord>>>,
+        deliveries: Mutex<Vec<oneshot::Sender<Result<(), String>>>>,
+    }
+
+    impl KafkaProducer for MockProducer {
+        type Delivery = BoxFuture<'static, Result<(), String>>;
+
+        fn send(
+            &self,
+            record: KafkaRecord,
+        ) -> Result<Self::Delivery, (KafkaRecord, KafkaProduceError)> {
+            if self.reject {
+                return Err((record, KafkaProduceError::Rejected("too large".to_string())));
+            }
+
+            let mut queued = self.queued.lock().unwrap();
+            if queued.len() >= self.capacity {
+                return Err((record, KafkaProduceError::QueueFull));
+            }
+            queued.push(record.clone());
+
+            let (tx, rx) = oneshot::channel();
+            self.deliveries.lock().unwrap().push(tx);
+
+            let queue = Arc::clone(&self.queued);
+            Ok(Box::pin(async move {
+                let outcome = rx.await.unwrap_or(Err("dropped".to_string()));
+                queue.lock().unwrap().retain(|queued| queued != &record);
+                outcome
+            }))
+        }
+    }
+
+    impl MockProducer {
+        fn deliver(&self, outcome: Result<(), String>) {
+            let tx = self.deliveries.lock().unwrap().remove(0);
+            tx.send(outcome).unwrap();
+        }
+    }
+
+    fn trade(id: &str) -> MarketEvent<DataKind> {
+        let mut trade = TradeFixture::new()
+            .with_base_ms(1672531200000)
+            .with_latency_ms(1)
+            .trade(0, "btc", num!(100.0), num!(1.0), Side::Buy);
+        trade.kind.id = id.to_string();
+        MarketEvent::from(trade)
+    }
+
+    #[test]
+    fn test_kafka_sink_record() {
+        let event = trade("1");
+
+        struct TestCase {
+            topic: TopicNaming,
+            expected_topic: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: single topic
+                topic: TopicNaming::Single("market_events".to_string()),
+                expected_topic: "market_events",
+            },
+            TestCase {
+                // TC1: topic per exchange & kind
+                topic: TopicNaming::PerExchangeKind {
+                    prefix: "market".to_string(),
+                },
+                expected_topic: "market.binance_spot.trade",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (sink, _) =
+                KafkaSink::new(MockProducer::default(), KafkaSinkConfig::new(test.topic));
+            let record = sink.record(&event).unwrap();
+            assert_eq!(record.topic, test.expected_topic, "TC{} failed", index);
+            assert_eq!(record.key, "btc_usdt_spot", "TC{} failed", index);
+
+            let payload = String::from_utf8(record.payload).unwrap();
+            let actual = MarketEvent::<DataKind>::from_json(&payload).unwrap();
+            assert_eq!(actual, event, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_kafka_sink_backpressure_when_queue_full() {
+        let producer = MockProducer {
+            capacity: 1,
+            ..MockProducer::default()
+        };
+        let (sink, mut errors) = KafkaSink::new(
+            producer,
+            KafkaSinkConfig::new(TopicNaming::Single("trades".to_string())),
+        );
+        let sink = Arc::new(sink);
+
+        sink.publish(&trade("1")).await;
+
+        // Queue is full, so publishing the second event waits for the first to be delivered
+        let publisher = tokio::spawn({
+            let sink = Arc::clone(&sink);
+            async move { sink.publish(&trade("2")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!publisher.is_finished());
+
+        sink.producer.deliver(Ok(()));
+        publisher.await.unwrap();
+
+        let queued = sink.producer.queued.lock().unwrap().clone();
+        assert_eq!(queued.len(), 1);
+        let payload = String::from_utf8(queued[0].payload.clone()).unwrap();
+        assert_eq!(
+            MarketEvent::<DataKind>::from_json(&payload).unwrap(),
+            trade("2")
+        );
+        assert!(errors.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_kafka_sink_flush_waits_for_deliveries() {
+        let producer = MockProducer {
+            capacity: 10,
+            ..MockProducer::default()
+        };
+        let (sink, mut errors) = KafkaSink::new(
+            producer,
+            KafkaSinkConfig::new(TopicNaming::Single("trades".to_string())),
+        );
+        let sink = Arc::new(sink);
+
+        // Flushing without any enqueued records returns immediately
+        sink.flush().await;
+
+        sink.publish(&trade("1")).await;
+        sink.publish(&trade("2")).await;
+        let flush = tokio::spawn({
+            let sink = Arc::clone(&sink);
+            async move { sink.flush().await }
+        });
+
+        sink.producer.deliver(Ok(()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!flush.is_finished());
+
+        sink.producer.deliver(Ok(()));
+        flush.await.unwrap();
+        assert!(errors.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_kafka_sink_surfaces_errors() {
+        // Delivery failures are surfaced once the broker responds
+        let producer = MockProducer {
+            capacity: 10,
+            ..MockProducer::default()
+        };
+        let (sink, mut errors) = KafkaSink::new(
+            producer,
+            KafkaSinkConfig::new(TopicNaming::Single("trades".to_string())),
+        );
+        sink.publish_stream(futures::stream::iter([trade("1"), trade("2")]))
+            .await;
+
+        sink.producer.deliver(Ok(()));
+        sink.producer.deliver(Err("broker unavailable".to_string()));
+
+        match errors.recv().await {
+            Some(DataError::Publish { topic, reason }) => {
+                assert_eq!(topic, "trades");
+                assert_eq!(reason, "broker unavailable");
+            }
+            other => panic!("unexpected publish error: {other:?}"),
+        }
+
+        // Rejected records are surfaced & discarded
+        let producer = MockProducer {
+            reject: true,
+            ..MockProducer::default()
+        };
+        let (sink, mut errors) = KafkaSink::new(
+            producer,
+            KafkaSinkConfig::new(TopicNaming::Single("trades".to_string())),
+        );
+        sink.publish(&trade("1")).await;
+
+        match errors.try_recv() {
+            Ok(DataError::Publish { topic, reason }) => {
+                assert_eq!(topic, "trades");
+                assert_eq!(reason, "too large");
+            }
+            other => panic!("unexpected publish error: {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "kafka")]
+    #[tokio::test]
+    async fn test_rdkafka_future_producer() {
+        // Unreachable broker & a single record queue, so records are enqueued but never delivered
+        let producer: rdkafka::producer::FutureProducer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", "127.0.0.1:1")
+            .set("queue.buffering.max.messages", "1")
+            .set("message.max.bytes", "1000")
+            .set("message.timeout.ms", "100")
+            .set("log_level", "0")
+            .create()
+            .unwrap();
+
+        let record = |payload: Vec<u8>| KafkaRecord {
+            topic: "trades".to_string(),
+            key: "btc_usdt_spot".to_string(),
+            payload,
+        };
+
+        // Enqueued
+        let delivery = KafkaProducer::send(&producer, record(vec![b'a'])).unwrap();
+
+        // Producer queue is full, so the record is returned to be retried
+        match KafkaProducer::send(&producer, record(vec![b'b'])) {
+            Err((queued, KafkaProduceError::QueueFull)) => {
+                assert_eq!(queued, record(vec![b'b']))
+            }
+            other => panic!("unexpected send result: {:?}", other.err()),
+        }
+
+        // Payload exceeds message.max.bytes, so the record is rejected
+        assert!(matches!(
+            KafkaProducer::send(&producer, record(vec![b'c'; 2000])),
+            Err((_, KafkaProduceError::Rejected(_)))
+        ));
+
+        // Broker never acknowledges the enqueued record, so delivery fails once it times out
+        assert!(delivery.await.is_err());
+    }
+}
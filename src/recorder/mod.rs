@@ -0,0 +1,746 @@
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent, MarketEventEnvelope},
+};
+use barter_integration::model::Instrument;
+use chrono::Utc;
+use flate2::{write::GzEncoder, Compression};
+use futures::{Stream, StreamExt};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{
+        mpsc::{self, error::TryRecvError},
+        oneshot,
+    },
+    task::JoinHandle,
+};
+use tracing::{debug, warn};
+
+/// Format agnostic columnar batching of recorded events, partitioned per event kind & UTC day,
+/// ready to be converted into columnar file formats (eg/ Arrow `RecordBatch`es written to Parquet).
+pub mod columnar;
+
+/// [`ParquetWriter`](parquet::ParquetWriter) that writes the [`columnar`] batches of recorded
+/// events to zstd compressed Parquet files, one per event kind & UTC day.
+#[cfg(feature = "parquet")]
+pub mod parquet;
+
+/// [`KafkaSink`](kafka::KafkaSink) that publishes events to Kafka topics via a pluggable
+/// [`KafkaProducer`](kafka::KafkaProducer), with backpressure when the producer queue is full.
+/// The `kafka` feature implements [`KafkaProducer`](kafka::KafkaProducer) for the `rdkafka`
+/// `FutureProducer`.
+pub mod kafka;
+
+/// [`RedisSink`](redis::RedisSink) that publishes events to Redis pub/sub channels or streams
+/// via a pluggable [`RedisClient`](redis::RedisClient), buffering whilst reconnecting. The
+/// `redis` feature provides a `RedisConnection` backed by the `redis` crate.
+pub mod redis;
+
+/// [`PostgresSink`](postgres::PostgresSink) that persists events into per-kind Postgres tables
+/// via a pluggable [`PostgresExecutor`](postgres::PostgresExecutor), using batched multi-row
+/// inserts and buffering whilst reconnecting. The `postgres` feature provides a
+/// `PostgresConnection` backed by `tokio_postgres`.
+pub mod postgres;
+
+/// [`ReplayStream`](replay::ReplayStream) that replays files recorded by a [`JsonlRecorder`],
+/// optionally paced according to the recorded `received_time`s.
+pub mod replay;
+
+/// Default maximum size of a recorded file before it is rotated.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default maximum age of a recorded file before it is rotated.
+pub const DEFAULT_MAX_FILE_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Default number of [`MarketEvent<DataKind>`](MarketEvent)s buffered between the recorded
+/// stream and the writer task.
+pub const DEFAULT_RECORDER_BUFFER_CAPACITY: usize = 10_000;
+
+/// Configuration of a [`JsonlRecorder`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RecorderConfig {
+    /// Directory the recorded files are written to, created if it does not exist.
+    pub directory: PathBuf,
+    /// File name prefix of every recorded file.
+    pub prefix: String,
+    /// Rotate the active file once it contains at least this many bytes, if any.
+    pub max_file_bytes: Option<u64>,
+    /// Rotate the active file once it was opened at least this long ago, if any.
+    pub max_file_age: Option<Duration>,
+    /// Gzip compress each rotated file.
+    pub compress: bool,
+    /// Number of events buffered between the recorded stream and the writer task.
+    pub buffer_capacity: usize,
+}
+
+impl RecorderConfig {
+    /// Construct a new [`Self`] that writes to the provided directory, using the default
+    /// rotation & buffering configuration.
+    pub fn new<P>(directory: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            directory: directory.into(),
+            prefix: "market_events".to_string(),
+            max_file_bytes: Some(DEFAULT_MAX_FILE_BYTES),
+            max_file_age: Some(DEFAULT_MAX_FILE_AGE),
+            compress: true,
+            buffer_capacity: DEFAULT_RECORDER_BUFFER_CAPACITY,
+        }
+    }
+}
+
+/// Key identifying an [`Instrument`] when publishing events, eg/ "btc_usdt_spot".
+fn instrument_key(instrument: &Instrument) -> String {
+    format!(
+        "{}_{}_{}",
+        instrument.base, instrument.quote, instrument.kind
+    )
+}
+
+/// Counters describing the work done by a [`JsonlRecorder`].
+#[derive(Debug, Default)]
+pub struct RecorderCounters {
+    events_written: AtomicU64,
+    events_dropped: AtomicU64,
+    write_errors: AtomicU64,
+}
+
+impl RecorderCounters {
+    /// Number of events written to disk.
+    pub fn events_written(&self) -> u64 {
+        self.events_written.load(Ordering::Relaxed)
+    }
+
+    /// Number of events dropped by [`JsonlRecorder::record`] because the buffer was full.
+    pub fn events_dropped(&self) -> u64 {
+        self.events_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of failed serialisation, write, rotation or compression operations.
+    pub fn write_errors(&self) -> u64 {
+        self.write_errors.load(Ordering::Relaxed)
+    }
+}
+
+/// Records [`MarketEvent<DataKind>`](MarketEvent)s to disk as JSON Lines, one versioned
+/// [`MarketEventEnvelope`] per line.
+///
+/// Events are sent over a bounded channel to a dedicated writer task, so recording never blocks
+/// on disk IO. The active file is rotated once it exceeds the configured size or age (checked as
+/// each event is written), and rotated files are optionally gzip compressed to `<file>.jsonl.gz`.
+/// File names are `<prefix>-<opened_time>-<sequence>.jsonl`, so they sort chronologically.
+///
+/// Buffered events are flushed whenever the writer is idle, and on [`JsonlRecorder::shutdown`].
+/// The active file at shutdown is left uncompressed.
+///
+/// ### Durability
+/// - [`JsonlRecorder::flush`] returns once every event recorded before it has been written &
+///   the active file has been synced to disk (`fsync`), so they survive a crash or power loss.
+/// - [`JsonlRecorder::shutdown`] additionally closes the active file, after which every recorded
+///   event is durable.
+/// - Dropping a [`JsonlRecorder`] without shutting it down is best-effort: the writer task still
+///   writes every buffered event & closes the active file, as long as the tokio runtime waits
+///   for it (which it does by default when shutting down, since it's a blocking task).
+/// - Each event is written as a single line, so a crash part way through writing (eg/ the
+///   process is killed) can at most truncate the final line of the active file, which a
+///   [`ReplayStream`](replay::ReplayStream) yields as an error without ending the replay.
+/// - Rotated files are compressed into a temporary file that is renamed into place, so a
+///   crash whilst compressing never leaves a corrupt `.jsonl.gz` file behind.
+#[derive(Debug)]
+pub struct JsonlRecorder {
+    tx: mpsc::Sender<RecorderCommand>,
+    counters: Arc<RecorderCounters>,
+    writer: JoinHandle<()>,
+}
+
+impl JsonlRecorder {
+    /// Spawn a writer task using the provided [`RecorderConfig`].
+    ///
+    /// Must be called from within a tokio runtime.
+    #[allow(clippy::result_large_err)]
+    pub fn spawn(config: RecorderConfig) -> Result<Self, DataError> {
+        std::fs::create_dir_all(&config.directory)?;
+
+        let (tx, rx) = mpsc::channel(config.buffer_capacity.max(1));
+        let counters = Arc::new(RecorderCounters::default());
+        let writer = RotatingWriter::new(config, Arc::clone(&counters));
+
+        Ok(Self {
+            tx,
+            counters,
+            writer: tokio::task::spawn_blocking(move || writer.run(rx)),
+        })
+    }
+
+    /// Record the provided event without waiting, dropping it if the buffer is full.
+    pub fn record(&self, event: MarketEvent<DataKind>) {
+        if self
+            .tx
+            .try_send(RecorderCommand::Record(Box::new(event)))
+            .is_err()
+        {
+            self.counters.events_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// [`RecorderCounters`] of this recorder, which remain accessible after shutdown.
+    pub fn counters(&self) -> Arc<RecorderCounters> {
+        Arc::clone(&self.counters)
+    }
+
+    /// Record every event yielded by the provided stream until it ends, then shutdown.
+    ///
+    /// Unlike [`JsonlRecorder::record`], no events are dropped if the buffer is full, instead the
+    /// stream is not polled until the writer task catches up.
+    pub async fn record_stream<St>(self, stream: St) -> Result<Arc<RecorderCounters>, DataError>
+    where
+        St: Stream<Item = MarketEvent<DataKind>>,
+    {
+        futures::pin_mut!(stream);
+        while let Some(event) = stream.next().await {
+            if self
+                .tx
+                .send(RecorderCommand::Record(Box::new(event)))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        self.shutdown().await
+    }
+
+    /// Write every event recorded before this call & sync the active file to disk, waiting
+    /// until it's durable.
+    ///
+    /// Unlike [`JsonlRecorder::record`], this waits if the buffer is full rather than failing.
+    pub async fn flush(&self) -> Result<(), DataError> {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        self.tx
+            .send(RecorderCommand::Flush(ack_tx))
+            .await
+            .map_err(|_| writer_terminated())?;
+
+        ack_rx.await.map_err(|_| writer_terminated())??;
+        Ok(())
+    }
+
+    /// Flush all buffered events to disk, sync & close the active file, returning the final
+    /// [`RecorderCounters`].
+    pub async fn shutdown(self) -> Result<Arc<RecorderCounters>, DataError> {
+        let Self {
+            tx,
+            counters,
+            writer,
+        } = self;
+
+        drop(tx);
+        writer.await.map_err(std::io::Error::other)?;
+
+        Ok(counters)
+    }
+}
+
+/// Command sent from a [`JsonlRecorder`] to it's [`RotatingWriter`] task.
+#[derive(Debug)]
+enum RecorderCommand {
+    Record(Box<MarketEvent<DataKind>>),
+    Flush(oneshot::Sender<std::io::Result<()>>),
+}
+
+/// [`DataError`] returned when the [`RotatingWriter`] task of a [`JsonlRecorder`] has terminated
+/// (eg/ it panicked) before acknowledging a flush.
+fn writer_terminated() -> DataError {
+    DataError::Flush {
+        sink: "JsonlRecorder",
+        reason: "writer task terminated".to_string(),
+    }
+}
+
+/// File currently being written to by a [`RotatingWriter`].
+#[derive(Debug)]
+struct ActiveFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    bytes: u64,
+    opened: Instant,
+}
+
+/// Blocking writer that appends events to the [`ActiveFile`], rotating it as configured.
+#[derive(Debug)]
+struct RotatingWriter {
+    config: RecorderConfig,
+    counters: Arc<RecorderCounters>,
+    active: Option<ActiveFile>,
+    sequence: u64,
+}
+
+impl RotatingWriter {
+    fn new(config: RecorderConfig, counters: Arc<RecorderCounters>) -> Self {
+        Self {
+            config,
+            counters,
+            active: None,
+            sequence: 0,
+        }
+    }
+
+    /// Write every event received until all senders are dropped, then close the active file.
+    fn run(mut self, mut rx: mpsc::Receiver<RecorderCommand>) {
+        loop {
+            let command = match rx.try_recv() {
+                Ok(command) => command,
+                Err(TryRecvError::Empty) => {
+                    // Flush buffered lines whilst waiting for the next event
+                    self.flush();
+                    match rx.blocking_recv() {
+                        Some(command) => command,
+                        None => break,
+                    }
+                }
+                Err(TryRecvError::Disconnected) => break,
+            };
+
+            match command {
+                RecorderCommand::Record(event) => self.write(*event),
+                RecorderCommand::Flush(ack) => {
+                    let result = self.sync();
+                    if let Err(error) = &result {
+                        self.error("failed to sync file", error);
+                    }
+                    let _ = ack.send(result);
+                }
+            }
+        }
+
+        if let Some(active) = self.active.take() {
+            self.close(active);
+        }
+        debug!(
+            events_written = self.counters.events_written(),
+            write_errors = self.counters.write_errors(),
+            "JsonlRecorder shutdown"
+        );
+    }
+
+    fn write(&mut self, event: MarketEvent<DataKind>) {
+        // Rotate the active file if it is too old
+        if let (Some(active), Some(max_age)) = (&self.active, self.config.max_file_age) {
+            if active.opened.elapsed() >= max_age {
+                self.rotate();
+            }
+        }
+
+        let mut line = match serde_json::to_vec(&MarketEventEnvelope::from(event)) {
+            Ok(line) => line,
+            Err(error) => return self.error("failed to serialise MarketEvent", error),
+        };
+        line.push(b'\n');
+
+        let active = match self.active.take() {
+            Some(active) => active,
+            None => match self.open() {
+                Ok(active) => active,
+                Err(error) => return self.error("failed to open file", error),
+            },
+        };
+        let active = self.active.insert(active);
+
+        if let Err(error) = active.writer.write_all(&line) {
+            return self.error("failed to write MarketEvent", error);
+        }
+        active.bytes += line.len() as u64;
+        self.counters.events_written.fetch_add(1, Ordering::Relaxed);
+
+        // Rotate the active file if it is too large
+        if self
+            .config
+            .max_file_bytes
+            .is_some_and(|max_bytes| active.bytes >= max_bytes)
+        {
+            self.rotate();
+        }
+    }
+
+    fn open(&mut self) -> std::io::Result<ActiveFile> {
+        let path = self.config.directory.join(format!(
+            "{}-{}-{:06}.jsonl",
+            self.config.prefix,
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            self.sequence
+        ));
+        self.sequence += 1;
+
+        Ok(ActiveFile {
+            writer: BufWriter::new(File::create(&path)?),
+            path,
+            bytes: 0,
+            opened: Instant::now(),
+        })
+    }
+
+    fn flush(&mut self) {
+        if let Some(Err(error)) = self.active.as_mut().map(|active| active.writer.flush()) {
+            self.error("failed to flush file", error);
+        }
+    }
+
+    /// Flush buffered lines & sync the active file to disk.
+    fn sync(&mut self) -> std::io::Result<()> {
+        match self.active.as_mut() {
+            Some(active) => {
+                active.writer.flush()?;
+                active.writer.get_ref().sync_data()
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Close the active file, compressing it if configured.
+    fn rotate(&mut self) {
+        let Some(active) = self.active.take() else {
+            return;
+        };
+
+        let path = active.path.clone();
+        if self.close(active) && self.config.compress {
+            if let Err(error) = compress_file(&path) {
+                self.error("failed to compress rotated file", error);
+            }
+        }
+    }
+
+    /// Flush, sync & close the provided file, returning true if successful.
+    fn close(&self, active: ActiveFile) -> bool {
+        let file = match active.writer.into_inner() {
+            Ok(file) => file,
+            Err(error) => {
+                self.error("failed to close file", error.into_error());
+                return false;
+            }
+        };
+
+        match file.sync_all() {
+            Ok(()) => true,
+            Err(error) => {
+                self.error("failed to sync file", error);
+                false
+            }
+        }
+    }
+
+    fn error<E>(&self, message: &str, error: E)
+    where
+        E: std::fmt::Display,
+    {
+        self.counters.write_errors.fetch_add(1, Ordering::Relaxed);
+        warn!(%error, "JsonlRecorder {message}");
+    }
+}
+
+impl Drop for RotatingWriter {
+    fn drop(&mut self) {
+        // Best-effort close of the active file if the writer did not shutdown cleanly (eg/ it
+        // panicked), so buffered lines are not lost
+        if let Some(active) = self.active.take() {
+            self.close(active);
+        }
+    }
+}
+
+/// Gzip compress the file at the provided path to `<path>.gz`, removing the original.
+///
+/// The original is streamed through the encoder, so it's never held in memory whole. The
+/// compressed file is written & synced to `<path>.gz.tmp` before being renamed into place, so
+/// `<path>.gz` is never partially written, and the original is only removed afterwards.
+fn compress_file(path: &std::path::Path) -> std::io::Result<()> {
+    let mut input = BufReader::new(File::open(path)?);
+
+    let mut compressed = path.as_os_str().to_owned();
+    compressed.push(".gz");
+    let mut temporary = compressed.clone();
+    temporary.push(".tmp");
+
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&temporary)?),
+        Compression::default(),
+    );
+    std::io::copy(&mut input, &mut encoder)?;
+    let file = encoder
+        .finish()?
+        .into_inner()
+        .map_err(std::io::IntoInnerError::into_error)?;
+    file.sync_all()?;
+    std::fs::rename(&temporary, compressed)?;
+
+    std::fs::remove_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::{
+        book::{Level, OrderBookL1},
+        trade::PublicTrade,
+    };
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use std::{io::Read, path::Path};
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_base_ms(0).with_latency_ms(1)
+    }
+
+    fn events(n: usize) -> Vec<MarketEvent<DataKind>> {
+        let fixture = fixture();
+        (0..n)
+            .map(|index| {
+                let kind = match index % 2 {
+                    0 => DataKind::Trade(PublicTrade {
+                        id: index.to_string(),
+                        price: num!(100 + index),
+                        amount: num!(0.5),
+                        side: Side::Buy,
+                    }),
+                    _ => DataKind::OrderBookL1(OrderBookL1 {
+                        last_update_time: fixture.time(index as i64),
+                        best_bid: Level::new(num!(99.5), num!(1.0)),
+                        best_ask: Level::new(num!(100.5), num!(2.0)),
+                    }),
+                };
+                fixture.event(index as i64, "btc", kind)
+            })
+            .collect()
+    }
+
+    fn directory(test: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "barter-data-recorder-{}-{test}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    /// Read the recorded files in the provided directory, sorted by name.
+    fn read_files(directory: &Path) -> Vec<(String, Vec<MarketEvent<DataKind>>)> {
+        let mut paths = std::fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let bytes = std::fs::read(&path).unwrap();
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let bytes = match name.ends_with(".gz") {
+                    true => {
+                        let mut decompressed = Vec::new();
+                        flate2::read::GzDecoder::new(bytes.as_slice())
+                            .read_to_end(&mut decompressed)
+                            .unwrap();
+                        decompressed
+                    }
+                    false => bytes,
+                };
+
+                let events = String::from_utf8(bytes)
+                    .unwrap()
+                    .lines()
+                    .map(|line| MarketEvent::<DataKind>::from_json(line).unwrap())
+                    .collect();
+
+                (name, events)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_record_stream_rotates_by_size() {
+        let directory = directory("size");
+        let config = RecorderConfig {
+            max_file_bytes: Some(2048),
+            max_file_age: None,
+            compress: false,
+            buffer_capacity: 4,
+            ..RecorderConfig::new(&directory)
+        };
+        let input = events(50);
+
+        let counters = JsonlRecorder::spawn(config)
+            .unwrap()
+            .record_stream(futures::stream::iter(input.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(counters.events_written(), 50);
+        assert_eq!(counters.events_dropped(), 0);
+        assert_eq!(counters.write_errors(), 0);
+
+        let files = read_files(&directory);
+        assert!(
+            files.len() > 1,
+            "expected rotation, found {} file",
+            files.len()
+        );
+        for (name, _) in &files {
+            assert!(name.starts_with("market_events-") && name.ends_with(".jsonl"));
+        }
+
+        let actual = files
+            .into_iter()
+            .flat_map(|(_, events)| events)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, input);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_stream_compresses_rotated_files() {
+        let directory = directory("compress");
+        let config = RecorderConfig {
+            max_file_bytes: Some(4096),
+            max_file_age: None,
+            ..RecorderConfig::new(&directory)
+        };
+        let input = events(100);
+
+        let counters = JsonlRecorder::spawn(config)
+            .unwrap()
+            .record_stream(futures::stream::iter(input.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(counters.events_written(), 100);
+        assert_eq!(counters.write_errors(), 0);
+
+        let files = read_files(&directory);
+        let (last, rotated) = files.split_last().unwrap();
+        assert!(!rotated.is_empty());
+        assert!(rotated.iter().all(|(name, _)| name.ends_with(".jsonl.gz")));
+        assert!(last.0.ends_with(".jsonl"));
+
+        let actual = files
+            .into_iter()
+            .flat_map(|(_, events)| events)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, input);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flush_writes_recorded_events() {
+        let directory = directory("flush");
+        let config = RecorderConfig {
+            compress: false,
+            ..RecorderConfig::new(&directory)
+        };
+        let input = events(10);
+
+        let recorder = JsonlRecorder::spawn(config).unwrap();
+        input
+            .iter()
+            .cloned()
+            .for_each(|event| recorder.record(event));
+        recorder.flush().await.unwrap();
+
+        // Every recorded event is on disk before the recorder is shutdown
+        let actual = read_files(&directory)
+            .into_iter()
+            .flat_map(|(_, events)| events)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, input);
+
+        let counters = recorder.shutdown().await.unwrap();
+        assert_eq!(counters.events_written(), 10);
+        assert_eq!(counters.write_errors(), 0);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drop_closes_active_file() {
+        let directory = directory("drop");
+        let config = RecorderConfig {
+            compress: false,
+            ..RecorderConfig::new(&directory)
+        };
+        let input = events(10);
+
+        let recorder = JsonlRecorder::spawn(config).unwrap();
+        let counters = recorder.counters();
+        input
+            .iter()
+            .cloned()
+            .for_each(|event| recorder.record(event));
+        drop(recorder);
+
+        // Writer task keeps writing the buffered events after the recorder is dropped
+        let lines = || {
+            std::fs::read_dir(&directory)
+                .unwrap()
+                .map(|entry| std::fs::read(entry.unwrap().path()).unwrap())
+                .map(|bytes| bytes.iter().filter(|byte| **byte == b'\n').count())
+                .sum::<usize>()
+        };
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while lines() < 10 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(counters.events_written(), 10);
+
+        let actual = read_files(&directory)
+            .into_iter()
+            .flat_map(|(_, events)| events)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, input);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_rotates_by_age() {
+        let directory = directory("age");
+        let config = RecorderConfig {
+            max_file_bytes: None,
+            max_file_age: Some(Duration::ZERO),
+            compress: false,
+            ..RecorderConfig::new(&directory)
+        };
+
+        let recorder = JsonlRecorder::spawn(config).unwrap();
+        events(3)
+            .into_iter()
+            .for_each(|event| recorder.record(event));
+        let counters = recorder.shutdown().await.unwrap();
+
+        assert_eq!(counters.events_written(), 3);
+
+        // Every file has expired by the time the next event is written
+        let files = read_files(&directory);
+        assert_eq!(files.len(), 3);
+        assert!(files.iter().all(|(_, events)| events.len() == 1));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
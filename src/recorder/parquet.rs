@@ -0,0 +1,455 @@
+use super::columnar::{
+    Column, ColumnBatch, ColumnSchema, ColumnType, ColumnarBatcher, Partition,
+    DEFAULT_COLUMN_BATCH_SIZE,
+};
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+};
+use arrow_array::{ArrayRef, RecordBatch, StringArray, TimestampNanosecondArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use std::{collections::HashMap, fs::File, path::PathBuf, sync::Arc};
+use tracing::debug;
+
+/// Default zstd compression level of the written Parquet files.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Scale of the Arrow `Decimal128` columns [`ColumnType::Num`] values are written to with the
+/// `decimal` feature, ie/ values are rounded to 18 decimal places.
+#[cfg(feature = "decimal")]
+pub const DECIMAL_SCALE: i8 = 18;
+
+/// Precision of the Arrow `Decimal128` columns [`ColumnType::Num`] values are written to with
+/// the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub const DECIMAL_PRECISION: u8 = 38;
+
+/// Configuration of a [`ParquetWriter`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParquetConfig {
+    /// Directory the Parquet files are written to, created if it does not exist.
+    pub directory: PathBuf,
+    /// File name prefix of every Parquet file.
+    pub prefix: String,
+    /// Number of rows in each `RecordBatch`, which is also the size of each row group.
+    pub batch_size: usize,
+    /// Zstd compression level of every column.
+    pub zstd_level: i32,
+}
+
+impl ParquetConfig {
+    /// Construct a new [`Self`] that writes to the provided directory, using the default batch
+    /// size & compression level.
+    pub fn new<P>(directory: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            directory: directory.into(),
+            prefix: "market_events".to_string(),
+            batch_size: DEFAULT_COLUMN_BATCH_SIZE,
+            zstd_level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+}
+
+/// Writes [`MarketEvent<DataKind>`](MarketEvent)s to zstd compressed Parquet files, one per
+/// [`Partition`] (ie/ per kind & UTC day) named `<prefix>-<kind>-<date>.parquet`.
+///
+/// Events are batched by a [`ColumnarBatcher`], and each full [`ColumnBatch`] is converted into
+/// an Arrow [`RecordBatch`] (see [`arrow_schema`]) written as a row group of it's partition's
+/// file.
+///
+/// ### Durability
+/// Parquet files are only readable once their footer has been written, so every file remains
+/// open (and every partially filled batch buffered) until [`ParquetWriter::close`]. Close (and
+/// re-create) the writer periodically (eg/ daily) to bound the number of open files. Since
+/// files are named after their partition, re-writing a partition overwrites it's file.
+#[derive(Debug)]
+pub struct ParquetWriter {
+    config: ParquetConfig,
+    properties: WriterProperties,
+    batcher: ColumnarBatcher,
+    writers: HashMap<Partition, (PathBuf, ArrowWriter<File>)>,
+}
+
+impl ParquetWriter {
+    /// Construct a new [`Self`] using the provided [`ParquetConfig`].
+    #[allow(clippy::result_large_err)]
+    pub fn new(config: ParquetConfig) -> Result<Self, DataError> {
+        std::fs::create_dir_all(&config.directory)?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(
+                ZstdLevel::try_new(config.zstd_level).map_err(parquet_error)?,
+            ))
+            .set_max_row_group_size(config.batch_size.max(1))
+            .build();
+
+        Ok(Self {
+            batcher: ColumnarBatcher::new(config.batch_size),
+            config,
+            properties,
+            writers: HashMap::new(),
+        })
+    }
+
+    /// Buffer the provided event, writing the batch of it's [`Partition`] if full.
+    #[allow(clippy::result_large_err)]
+    pub fn write(&mut self, event: &MarketEvent<DataKind>) -> Result<(), DataError> {
+        match self.batcher.push(event) {
+            Some((partition, batch)) => self.write_batch(partition, &batch),
+            None => Ok(()),
+        }
+    }
+
+    /// Write every partially filled batch & the footer of every file, returning the paths of
+    /// the written files sorted by [`Partition`].
+    #[allow(clippy::result_large_err)]
+    pub fn close(mut self) -> Result<Vec<PathBuf>, DataError> {
+        for (partition, batch) in self.batcher.flush() {
+            self.write_batch(partition, &batch)?;
+        }
+
+        let mut writers = self.writers.drain().collect::<Vec<_>>();
+        writers.sort_by_key(|(partition, _)| *partition);
+
+        writers
+            .into_iter()
+            .map(|(_, (path, writer))| {
+                writer.close().map_err(parquet_error)?;
+                debug!(path = %path.display(), "ParquetWriter closed file");
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Write the provided batch to the file of it's [`Partition`], creating it if required.
+    #[allow(clippy::result_large_err)]
+    fn write_batch(&mut self, partition: Partition, batch: &ColumnBatch) -> Result<(), DataError> {
+        let record_batch = record_batch(batch).map_err(parquet_error)?;
+
+        let (_, writer) = match self.writers.entry(partition) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let path = self.config.directory.join(format!(
+                    "{}-{}-{}.parquet",
+                    self.config.prefix, partition.kind, partition.date
+                ));
+                let writer = ArrowWriter::try_new(
+                    File::create(&path)?,
+                    record_batch.schema(),
+                    Some(self.properties.clone()),
+                )
+                .map_err(parquet_error)?;
+                entry.insert((path, writer))
+            }
+        };
+
+        writer.write(&record_batch).map_err(parquet_error)
+    }
+}
+
+/// [`DataError`] of a failed Arrow conversion or Parquet write.
+fn parquet_error<E>(error: E) -> DataError
+where
+    E: std::fmt::Display,
+{
+    DataError::Parquet(error.to_string())
+}
+
+/// Arrow [`Schema`] of the provided [`ColumnSchema`]s, with every column non-nullable.
+///
+/// [`ColumnType::Num`] columns are `Float64`, or `Decimal128(38, 18)` with the `decimal`
+/// feature.
+pub fn arrow_schema(schema: &[ColumnSchema]) -> Schema {
+    Schema::new(
+        schema
+            .iter()
+            .map(|column| Field::new(column.name, data_type(column.data_type), false))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn data_type(data_type: ColumnType) -> DataType {
+    match data_type {
+        ColumnType::Timestamp => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        ColumnType::Utf8 => DataType::Utf8,
+        ColumnType::UInt64 => DataType::UInt64,
+        #[cfg(not(feature = "decimal"))]
+        ColumnType::Num => DataType::Float64,
+        #[cfg(feature = "decimal")]
+        ColumnType::Num => DataType::Decimal128(DECIMAL_PRECISION, DECIMAL_SCALE),
+    }
+}
+
+/// Convert the provided [`ColumnBatch`] into an Arrow [`RecordBatch`] with the
+/// [`arrow_schema`] of it's schema.
+pub fn record_batch(batch: &ColumnBatch) -> Result<RecordBatch, ArrowError> {
+    let schema: SchemaRef = Arc::new(arrow_schema(batch.schema));
+    let columns = batch
+        .columns
+        .iter()
+        .map(array)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    RecordBatch::try_new(schema, columns)
+}
+
+fn array(column: &Column) -> Result<ArrayRef, ArrowError> {
+    Ok(match column {
+        Column::Timestamp(values) => {
+            let nanos = values
+                .iter()
+                .map(|time| {
+                    time.timestamp_nanos_opt().ok_or_else(|| {
+                        ArrowError::InvalidArgumentError(format!(
+                            "{time} is out of range of a nanosecond timestamp"
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(TimestampNanosecondArray::from(nanos).with_timezone("UTC"))
+        }
+        Column::Utf8(values) => Arc::new(StringArray::from_iter_values(values)),
+        Column::UInt64(values) => Arc::new(UInt64Array::from(values.clone())),
+        Column::Num(values) => num_array(values)?,
+    })
+}
+
+#[cfg(not(feature = "decimal"))]
+fn num_array(values: &[crate::num::Num]) -> Result<ArrayRef, ArrowError> {
+    Ok(Arc::new(arrow_array::Float64Array::from(values.to_vec())))
+}
+
+#[cfg(feature = "decimal")]
+fn num_array(values: &[crate::num::Num]) -> Result<ArrayRef, ArrowError> {
+    let scale = DECIMAL_SCALE as u32;
+    let mantissas = values
+        .iter()
+        .map(|value| {
+            let value = value.round_dp(scale);
+            value
+                .mantissa()
+                .checked_mul(10_i128.pow(scale - value.scale()))
+                .ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "{value} is out of range of Decimal128({DECIMAL_PRECISION}, {DECIMAL_SCALE})"
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Arc::new(
+        arrow_array::Decimal128Array::from(mantissas)
+            .with_precision_and_scale(DECIMAL_PRECISION, DECIMAL_SCALE)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::trade::TradeFixture;
+    use crate::{
+        num,
+        recorder::columnar::{ORDER_BOOK_SCHEMA, TRADE_SCHEMA},
+        subscription::{
+            book::{OrderBook, OrderBookSide},
+            trade::PublicTrade,
+        },
+    };
+    use arrow_array::{cast::AsArray, types::TimestampNanosecondType};
+    use barter_integration::model::Side;
+    use chrono::{DateTime, Utc};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_base_ms(0).with_latency_ms(1)
+    }
+
+    fn time(ms: i64) -> DateTime<Utc> {
+        fixture().time(ms)
+    }
+
+    fn event(ms: i64, kind: DataKind) -> MarketEvent<DataKind> {
+        fixture().event(ms, "btc", kind)
+    }
+
+    fn trade(ms: i64, id: u64) -> MarketEvent<DataKind> {
+        event(
+            ms,
+            DataKind::Trade(PublicTrade {
+                id: id.to_string(),
+                price: num!(100 + id) + num!(0.25),
+                amount: num!(0.5),
+                side: if id.is_multiple_of(2) {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+            }),
+        )
+    }
+
+    fn book(ms: i64) -> MarketEvent<DataKind> {
+        event(
+            ms,
+            DataKind::OrderBook(OrderBook {
+                last_update_time: time(ms),
+                bids: OrderBookSide::new(
+                    Side::Buy,
+                    [(num!(99.0), num!(1.0)), (num!(98.5), num!(2.0))],
+                ),
+                asks: OrderBookSide::new(Side::Sell, [(num!(101.0), num!(3.0))]),
+            }),
+        )
+    }
+
+    fn directory(test: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("barter-data-parquet-{}-{test}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    /// Read the provided Parquet file into a single [`RecordBatch`], checking it's zstd
+    /// compressed, returning it's schema & the number of rows in each row group.
+    fn read(path: &PathBuf) -> (Schema, Vec<i64>, RecordBatch) {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .with_batch_size(usize::MAX);
+        assert!(builder
+            .metadata()
+            .row_groups()
+            .iter()
+            .flat_map(|row_group| row_group.columns())
+            .all(|column| matches!(column.compression(), Compression::ZSTD(_))));
+
+        let schema = builder.schema().as_ref().clone();
+        let row_groups = builder
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|row_group| row_group.num_rows())
+            .collect();
+        let mut batches = builder
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+
+        (schema, row_groups, batches.remove(0))
+    }
+
+    #[cfg(not(feature = "decimal"))]
+    fn nums(batch: &RecordBatch, name: &str) -> Vec<crate::num::Num> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_primitive::<arrow_array::types::Float64Type>()
+            .values()
+            .to_vec()
+    }
+
+    #[cfg(feature = "decimal")]
+    fn nums(batch: &RecordBatch, name: &str) -> Vec<crate::num::Num> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_primitive::<arrow_array::types::Decimal128Type>()
+            .values()
+            .iter()
+            .map(|mantissa| {
+                rust_decimal::Decimal::from_i128_with_scale(*mantissa, DECIMAL_SCALE as u32)
+                    .normalize()
+            })
+            .collect()
+    }
+
+    fn strings(batch: &RecordBatch, name: &str) -> Vec<String> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .map(|value| value.unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parquet_writer_trades_and_books() {
+        let directory = directory("trades_and_books");
+        let mut config = ParquetConfig::new(&directory);
+        config.batch_size = 1000;
+        let mut writer = ParquetWriter::new(config).unwrap();
+
+        // 2500 trades & 1000 OrderBooks of 3 levels each on the first day, 1 trade on the next
+        for id in 0..2500 {
+            writer.write(&trade(id as i64, id)).unwrap();
+        }
+        for ms in 0..1000 {
+            writer.write(&book(ms)).unwrap();
+        }
+        writer.write(&trade(DAY_MS, 2500)).unwrap();
+
+        let actual = writer
+            .close()
+            .unwrap()
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "market_events-order_book-1970-01-01.parquet",
+            "market_events-trade-1970-01-01.parquet",
+            "market_events-trade-1970-01-02.parquet",
+        ];
+        assert_eq!(actual, expected);
+
+        // Trades: one row group per full batch, plus the flushed remainder
+        let (schema, row_groups, trades) = read(&directory.join(expected[1]));
+        assert_eq!(schema, arrow_schema(TRADE_SCHEMA));
+        assert_eq!(row_groups, vec![1000, 1000, 500]);
+        assert_eq!(strings(&trades, "id")[2499], "2499");
+        assert_eq!(strings(&trades, "exchange")[0], "binance_spot");
+        assert_eq!(strings(&trades, "instrument_kind")[0], "spot");
+        assert_eq!(&strings(&trades, "side")[..2], ["buy", "sell"]);
+        assert_eq!(nums(&trades, "price")[2499], num!(2599.25));
+        assert_eq!(nums(&trades, "amount")[0], num!(0.5));
+        let received = trades
+            .column_by_name("received_time")
+            .unwrap()
+            .as_primitive::<TimestampNanosecondType>();
+        assert_eq!(received.value(10), time(11).timestamp_nanos_opt().unwrap());
+
+        // OrderBooks: one row per level
+        let (schema, row_groups, books) = read(&directory.join(expected[0]));
+        assert_eq!(schema, arrow_schema(ORDER_BOOK_SCHEMA));
+        assert_eq!(row_groups, vec![1000, 1000, 1000]);
+        assert_eq!(&strings(&books, "side")[..3], ["buy", "buy", "sell"]);
+        assert_eq!(
+            books
+                .column_by_name("depth")
+                .unwrap()
+                .as_primitive::<arrow_array::types::UInt64Type>()
+                .values()[..3],
+            [0, 1, 0]
+        );
+        assert_eq!(
+            nums(&books, "price")[..3],
+            [num!(99.0), num!(98.5), num!(101.0)]
+        );
+
+        let (_, row_groups, _) = read(&directory.join(expected[2]));
+        assert_eq!(row_groups, vec![1]);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
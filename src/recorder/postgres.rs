@@ -0,0 +1,1079 @@
+use super::columnar::{
+    Column, ColumnBatch, ColumnSchema, ColumnType, CANDLE_SCHEMA, LIQUIDATION_SCHEMA,
+    ORDER_BOOK_L1_SCHEMA, TRADE_SCHEMA,
+};
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+    num::Num,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::MissedTickBehavior,
+};
+use tracing::{debug, warn};
+
+/// Schema migration creating a table per [`PostgresTable`], to be run before a [`PostgresSink`]
+/// is spawned.
+///
+/// Price & amount columns are `DOUBLE PRECISION`, so a [`PostgresExecutor`] built with the
+/// `decimal` feature should bind [`SqlValue::Num`] as `f64` (as the `PostgresConnection` does),
+/// or alter the columns to `NUMERIC`.
+pub const POSTGRES_MIGRATION_SQL: &str =
+    include_str!("../../migrations/postgres/0001_create_market_event_tables.sql");
+
+/// Default maximum number of rows inserted by a single [`PostgresStatement`].
+pub const DEFAULT_POSTGRES_BATCH_SIZE: usize = 1000;
+
+/// Default interval at which partially filled batches are inserted.
+pub const DEFAULT_POSTGRES_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default maximum number of events buffered by a [`PostgresSink`] while the database is
+/// unavailable.
+pub const DEFAULT_POSTGRES_BUFFER_CAPACITY: usize = 100_000;
+
+/// Default time to wait between [`PostgresExecutor::reconnect`] attempts.
+pub const DEFAULT_POSTGRES_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of bind parameters Postgres accepts in a single statement.
+const MAX_BIND_PARAMETERS: usize = u16::MAX as usize;
+
+/// Table a [`MarketEvent<DataKind>`](MarketEvent) is persisted to, with columns defined by the
+/// [`columnar`](super::columnar) schema of it's kind.
+///
+/// [`DataKind::OrderBook`] snapshots are not persisted.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum PostgresTable {
+    Trades,
+    OrderBookL1,
+    Candles,
+    Liquidations,
+}
+
+impl PostgresTable {
+    /// Every [`PostgresTable`], in the order they are created & flushed.
+    pub const ALL: [Self; 4] = [
+        Self::Trades,
+        Self::OrderBookL1,
+        Self::Candles,
+        Self::Liquidations,
+    ];
+
+    /// Determine the [`PostgresTable`] of the provided [`DataKind`], if it is persisted.
+    pub fn of(kind: &DataKind) -> Option<Self> {
+        match kind {
+            DataKind::Trade(_) => Some(Self::Trades),
+            DataKind::OrderBookL1(_) => Some(Self::OrderBookL1),
+            DataKind::Candle(_) => Some(Self::Candles),
+            DataKind::Liquidation(_) => Some(Self::Liquidations),
+            DataKind::OrderBook(_) => None,
+        }
+    }
+
+    /// Name of the table.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Trades => "trades",
+            Self::OrderBookL1 => "order_book_l1",
+            Self::Candles => "candles",
+            Self::Liquidations => "liquidations",
+        }
+    }
+
+    /// Columns of the table.
+    pub fn schema(&self) -> &'static [ColumnSchema] {
+        match self {
+            Self::Trades => TRADE_SCHEMA,
+            Self::OrderBookL1 => ORDER_BOOK_L1_SCHEMA,
+            Self::Candles => CANDLE_SCHEMA,
+            Self::Liquidations => LIQUIDATION_SCHEMA,
+        }
+    }
+
+    /// Maximum number of rows a single multi-row insert into the table may contain.
+    fn max_rows(&self) -> usize {
+        MAX_BIND_PARAMETERS / self.schema().len()
+    }
+}
+
+/// Generate the [`POSTGRES_MIGRATION_SQL`] from the [`PostgresTable`] schemas.
+pub fn migration_sql() -> String {
+    let mut sql = String::new();
+
+    for table in PostgresTable::ALL {
+        let name = table.name();
+        let _ = writeln!(sql, "CREATE TABLE IF NOT EXISTS {name} (");
+        let columns = table
+            .schema()
+            .iter()
+            .map(|column| {
+                format!(
+                    "    {} {} NOT NULL",
+                    column.name,
+                    sql_type(column.data_type)
+                )
+            })
+            .collect::<Vec<_>>();
+        let _ = writeln!(sql, "{}\n);", columns.join(",\n"));
+        let _ = writeln!(
+            sql,
+            "CREATE INDEX IF NOT EXISTS {name}_instrument_time_idx \
+            ON {name} (exchange, base, quote, instrument_kind, exchange_time);\n"
+        );
+    }
+
+    sql.truncate(sql.trim_end().len());
+    sql.push('\n');
+    sql
+}
+
+fn sql_type(data_type: ColumnType) -> &'static str {
+    match data_type {
+        ColumnType::Timestamp => "TIMESTAMPTZ",
+        ColumnType::Utf8 => "TEXT",
+        ColumnType::UInt64 => "BIGINT",
+        ColumnType::Num => "DOUBLE PRECISION",
+    }
+}
+
+/// Bind parameter of a [`PostgresStatement`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum SqlValue {
+    /// `TIMESTAMPTZ`
+    Timestamp(DateTime<Utc>),
+    /// `TEXT`
+    Text(String),
+    /// `BIGINT`
+    BigInt(i64),
+    /// `DOUBLE PRECISION` price or amount, see [`POSTGRES_MIGRATION_SQL`].
+    Num(Num),
+}
+
+#[cfg(feature = "postgres")]
+impl tokio_postgres::types::ToSql for SqlValue {
+    fn to_sql(
+        &self,
+        ty: &tokio_postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self {
+            Self::Timestamp(time) => time.to_sql(ty, out),
+            Self::Text(text) => text.to_sql(ty, out),
+            Self::BigInt(value) => value.to_sql(ty, out),
+            #[cfg(not(feature = "decimal"))]
+            Self::Num(num) => num.to_sql(ty, out),
+            #[cfg(feature = "decimal")]
+            Self::Num(num) => rust_decimal::prelude::ToPrimitive::to_f64(num)
+                .ok_or_else(|| format!("{num} is not representable as a f64"))?
+                .to_sql(ty, out),
+        }
+    }
+
+    fn accepts(ty: &tokio_postgres::types::Type) -> bool {
+        use tokio_postgres::types::ToSql;
+        <DateTime<Utc> as ToSql>::accepts(ty)
+            || <String as ToSql>::accepts(ty)
+            || <i64 as ToSql>::accepts(ty)
+            || <f64 as ToSql>::accepts(ty)
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+/// Multi-row `INSERT` of events of the same [`PostgresTable`], with the bind parameters of every
+/// row in order (ie/ `$1`, `$2`, ...).
+#[derive(Clone, PartialEq, Debug)]
+pub struct PostgresStatement {
+    pub table: PostgresTable,
+    pub sql: String,
+    pub params: Vec<SqlValue>,
+    /// Number of events inserted by the statement.
+    pub events: usize,
+}
+
+impl PostgresStatement {
+    /// Construct the multi-row `INSERT` of the provided events, which must all be persisted to
+    /// the provided [`PostgresTable`].
+    pub fn insert<'a, Events>(table: PostgresTable, events: Events) -> Self
+    where
+        Events: IntoIterator<Item = &'a MarketEvent<DataKind>>,
+    {
+        let schema = table.schema();
+        let mut batch = ColumnBatch::new(schema, 0);
+        let mut num_events = 0;
+        for event in events {
+            debug_assert_eq!(PostgresTable::of(&event.kind), Some(table));
+            batch.push(event);
+            num_events += 1;
+        }
+
+        let columns = schema
+            .iter()
+            .map(|column| column.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let rows = (0..batch.num_rows)
+            .map(|row| {
+                let placeholders = (1..=schema.len())
+                    .map(|column| format!("${}", row * schema.len() + column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("({placeholders})")
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let params = (0..batch.num_rows)
+            .flat_map(|row| batch.columns.iter().map(move |column| value(column, row)))
+            .collect();
+
+        Self {
+            table,
+            sql: format!("INSERT INTO {} ({columns}) VALUES {rows}", table.name()),
+            params,
+            events: num_events,
+        }
+    }
+}
+
+fn value(column: &Column, row: usize) -> SqlValue {
+    match column {
+        Column::Timestamp(values) => SqlValue::Timestamp(values[row]),
+        Column::Utf8(values) => SqlValue::Text(values[row].clone()),
+        Column::UInt64(values) => SqlValue::BigInt(i64::try_from(values[row]).unwrap_or(i64::MAX)),
+        Column::Num(values) => SqlValue::Num(values[row]),
+    }
+}
+
+/// Reason a [`PostgresExecutor`] failed to execute a [`PostgresStatement`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PostgresExecutorError {
+    /// Connection was lost, so the [`PostgresStatement`] is retried after reconnecting.
+    ConnectionLost(String),
+    /// Statement was rejected by the database, and it's events are discarded.
+    Statement(String),
+}
+
+/// Postgres client used by a [`PostgresSink`] (eg/ the `PostgresConnection` enabled by the
+/// `postgres` feature, or a thin wrapper around a `sqlx::PgPool`).
+#[async_trait]
+pub trait PostgresExecutor: Send + 'static {
+    /// Execute the provided [`PostgresStatement`], binding it's [`SqlValue`] parameters in order.
+    async fn execute(&mut self, statement: &PostgresStatement)
+        -> Result<(), PostgresExecutorError>;
+
+    /// Re-establish the connection after a [`PostgresExecutorError::ConnectionLost`].
+    async fn reconnect(&mut self) -> Result<(), String>;
+}
+
+/// [`PostgresExecutor`] executing [`PostgresStatement`]s over a `tokio_postgres::Client`
+/// connected without TLS.
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub struct PostgresConnection {
+    config: tokio_postgres::Config,
+    client: tokio_postgres::Client,
+}
+
+#[cfg(feature = "postgres")]
+impl PostgresConnection {
+    /// Connect to the database using the provided `tokio_postgres::Config`, reusing it to
+    /// reconnect.
+    ///
+    /// Must be called from within a tokio runtime, since the connection is driven by a spawned
+    /// task.
+    pub async fn connect(config: tokio_postgres::Config) -> Result<Self, tokio_postgres::Error> {
+        let client = Self::client(&config).await?;
+        Ok(Self { config, client })
+    }
+
+    async fn client(
+        config: &tokio_postgres::Config,
+    ) -> Result<tokio_postgres::Client, tokio_postgres::Error> {
+        let (client, connection) = config.connect(tokio_postgres::NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(error) = connection.await {
+                warn!(%error, "PostgresConnection terminated");
+            }
+        });
+        Ok(client)
+    }
+}
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl PostgresExecutor for PostgresConnection {
+    async fn execute(
+        &mut self,
+        statement: &PostgresStatement,
+    ) -> Result<(), PostgresExecutorError> {
+        let params = statement
+            .params
+            .iter()
+            .map(|param| param as &(dyn tokio_postgres::types::ToSql + Sync))
+            .collect::<Vec<_>>();
+
+        self.client
+            .execute(statement.sql.as_str(), &params)
+            .await
+            .map(|_| ())
+            .map_err(|error| {
+                if error.is_closed() || self.client.is_closed() {
+                    PostgresExecutorError::ConnectionLost(error.to_string())
+                } else {
+                    PostgresExecutorError::Statement(error.to_string())
+                }
+            })
+    }
+
+    async fn reconnect(&mut self) -> Result<(), String> {
+        self.client = Self::client(&self.config)
+            .await
+            .map_err(|error| error.to_string())?;
+        Ok(())
+    }
+}
+
+/// Configuration of a [`PostgresSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PostgresSinkConfig {
+    /// Maximum number of rows inserted by a single [`PostgresStatement`], which are inserted as
+    /// soon as they are buffered.
+    pub batch_size: usize,
+    /// Interval at which partially filled batches are inserted.
+    pub flush_interval: Duration,
+    /// Maximum number of events buffered for each [`PostgresTable`] while the database is
+    /// unavailable, after which the oldest buffered event is dropped for each new one.
+    pub buffer_capacity: usize,
+    /// Time to wait between reconnection attempts.
+    pub reconnect_interval: Duration,
+}
+
+impl Default for PostgresSinkConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_POSTGRES_BATCH_SIZE,
+            flush_interval: DEFAULT_POSTGRES_FLUSH_INTERVAL,
+            buffer_capacity: DEFAULT_POSTGRES_BUFFER_CAPACITY,
+            reconnect_interval: DEFAULT_POSTGRES_RECONNECT_INTERVAL,
+        }
+    }
+}
+
+/// Counters describing the work done by a [`PostgresSink`].
+#[derive(Debug, Default)]
+pub struct PostgresCounters {
+    events_written: AtomicU64,
+    events_failed: AtomicU64,
+    events_dropped: AtomicU64,
+    events_skipped: AtomicU64,
+}
+
+impl PostgresCounters {
+    /// Number of events inserted into the database.
+    pub fn events_written(&self) -> u64 {
+        self.events_written.load(Ordering::Relaxed)
+    }
+
+    /// Number of events discarded because their statement was rejected by the database.
+    pub fn events_failed(&self) -> u64 {
+        self.events_failed.load(Ordering::Relaxed)
+    }
+
+    /// Number of events dropped because the buffer overflowed while the database was
+    /// unavailable.
+    pub fn events_dropped(&self) -> u64 {
+        self.events_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of events of a kind without a [`PostgresTable`] (ie/ OrderBook snapshots).
+    pub fn events_skipped(&self) -> u64 {
+        self.events_skipped.load(Ordering::Relaxed)
+    }
+}
+
+/// Persists [`MarketEvent<DataKind>`](MarketEvent)s into a Postgres table per kind (see
+/// [`PostgresTable`] & [`POSTGRES_MIGRATION_SQL`]) via a [`PostgresExecutor`].
+///
+/// Events are sent to a dedicated writer task, so writing never blocks on the database. Events
+/// are inserted using batched multi-row `INSERT`s, once a batch is full or at the flush
+/// interval. If the connection is lost, the task reconnects at the configured interval whilst
+/// buffering up to the configured number of events per table, dropping the oldest buffered
+/// event on overflow.
+///
+/// ### Durability
+/// - [`PostgresSink::flush`] returns once every event written before it has been inserted (or
+///   rejected by the database), waiting for the connection to be re-established if necessary.
+/// - [`PostgresSink::shutdown`] inserts every buffered event before closing the sink, unless
+///   the connection is lost whilst shutting down, in which case they are dropped & counted.
+/// - Dropping a [`PostgresSink`] without shutting it down is best-effort: the writer task still
+///   inserts every buffered event, but only whilst the tokio runtime is running.
+#[derive(Debug)]
+pub struct PostgresSink {
+    tx: mpsc::UnboundedSender<PostgresCommand>,
+    counters: Arc<PostgresCounters>,
+    writer: JoinHandle<()>,
+}
+
+impl PostgresSink {
+    /// Spawn a writer task using the provided [`PostgresExecutor`] & [`PostgresSinkConfig`].
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn spawn<Executor>(executor: Executor, config: PostgresSinkConfig) -> Self
+    where
+        Executor: PostgresExecutor,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let counters = Arc::new(PostgresCounters::default());
+        let writer = PostgresWriter {
+            executor,
+            config,
+            counters: Arc::clone(&counters),
+            buffers: PostgresTable::ALL.map(|_| VecDeque::new()),
+            connected: true,
+            flushes: Vec::new(),
+        };
+
+        Self {
+            tx,
+            counters,
+            writer: tokio::spawn(writer.run(rx)),
+        }
+    }
+
+    /// Write the provided event without waiting.
+    pub fn write(&self, event: MarketEvent<DataKind>) {
+        let _ = self.tx.send(PostgresCommand::Write(Box::new(event)));
+    }
+
+    /// Insert every event written before this call, waiting until they have been inserted.
+    pub async fn flush(&self) -> Result<(), DataError> {
+        let writer_terminated = || DataError::Flush {
+            sink: "PostgresSink",
+            reason: "writer task terminated".to_string(),
+        };
+
+        let (ack_tx, ack_rx) = oneshot::channel();
+        self.tx
+            .send(PostgresCommand::Flush(ack_tx))
+            .map_err(|_| writer_terminated())?;
+
+        ack_rx.await.map_err(|_| writer_terminated())
+    }
+
+    /// [`PostgresCounters`] of this sink, which remain accessible after shutdown.
+    pub fn counters(&self) -> Arc<PostgresCounters> {
+        Arc::clone(&self.counters)
+    }
+
+    /// Write every event yielded by the provided [`Stream`] until it ends, then
+    /// [`shutdown`](Self::shutdown) the sink.
+    pub async fn write_stream<St>(self, stream: St) -> Arc<PostgresCounters>
+    where
+        St: Stream<Item = MarketEvent<DataKind>>,
+    {
+        futures::pin_mut!(stream);
+        while let Some(event) = stream.next().await {
+            self.write(event);
+        }
+
+        self.shutdown().await
+    }
+
+    /// Insert all buffered events, returning the final [`PostgresCounters`].
+    ///
+    /// Buffered events are dropped if the connection is lost during shutdown.
+    pub async fn shutdown(self) -> Arc<PostgresCounters> {
+        let Self {
+            tx,
+            counters,
+            writer,
+        } = self;
+
+        drop(tx);
+        let _ = writer.await;
+
+        counters
+    }
+}
+
+/// Command sent from a [`PostgresSink`] to it's [`PostgresWriter`] task.
+#[derive(Debug)]
+enum PostgresCommand {
+    Write(Box<MarketEvent<DataKind>>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Task that inserts buffered events, reconnecting if the connection is lost.
+#[derive(Debug)]
+struct PostgresWriter<Executor> {
+    executor: Executor,
+    config: PostgresSinkConfig,
+    counters: Arc<PostgresCounters>,
+    /// Buffered events of each [`PostgresTable`], indexed in [`PostgresTable::ALL`] order.
+    buffers: [VecDeque<MarketEvent<DataKind>>; 4],
+    connected: bool,
+    /// Pending [`PostgresSink::flush`] acknowledgements, sent once the buffers are empty.
+    flushes: Vec<oneshot::Sender<()>>,
+}
+
+impl<Executor> PostgresWriter<Executor>
+where
+    Executor: PostgresExecutor,
+{
+    /// Insert every event received until the sink is shutdown & the buffers are empty.
+    async fn run(mut self, mut rx: mpsc::UnboundedReceiver<PostgresCommand>) {
+        let mut flush = tokio::time::interval_at(
+            tokio::time::Instant::now() + self.config.flush_interval,
+            self.config.flush_interval,
+        );
+        flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut open = true;
+
+        while open || !self.is_empty() {
+            // Buffer events until a batch is full, the flush interval elapses, a flush is
+            // requested, or shutdown
+            if open {
+                tokio::select! {
+                    biased;
+
+                    command = rx.recv() => match command {
+                        Some(PostgresCommand::Write(event)) => {
+                            self.buffer(*event);
+                            if !self.is_batch_full() {
+                                continue;
+                            }
+                        }
+                        Some(PostgresCommand::Flush(ack)) => {
+                            self.flushes.push(ack);
+                            if self.is_empty() {
+                                self.ack_flushes();
+                                continue;
+                            }
+                        }
+                        None => open = false,
+                    },
+                    _ = flush.tick() => {
+                        if self.is_empty() {
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            // Reconnect if the connection was lost, giving up if already shutting down
+            if !self.connected {
+                if !open {
+                    self.drop_buffers();
+                    break;
+                }
+                match self.executor.reconnect().await {
+                    Ok(()) => self.connected = true,
+                    Err(error) => {
+                        warn!(
+                            %error,
+                            action = "retrying after interval",
+                            "PostgresSink failed to reconnect"
+                        );
+                        tokio::time::sleep(self.config.reconnect_interval).await;
+                        continue;
+                    }
+                }
+            }
+
+            self.flush().await;
+            if self.is_empty() {
+                self.ack_flushes();
+            }
+        }
+    }
+
+    /// Acknowledge every pending [`PostgresSink::flush`].
+    fn ack_flushes(&mut self) {
+        for ack in self.flushes.drain(..) {
+            let _ = ack.send(());
+        }
+    }
+
+    /// Insert every buffered event in batches, stopping if the connection is lost.
+    async fn flush(&mut self) {
+        for (index, table) in PostgresTable::ALL.into_iter().enumerate() {
+            while !self.buffers[index].is_empty() {
+                let rows = self.config.batch_size.clamp(1, table.max_rows());
+                let statement =
+                    PostgresStatement::insert(table, self.buffers[index].iter().take(rows));
+
+                match self.executor.execute(&statement).await {
+                    Ok(()) => {
+                        debug!(
+                            table = table.name(),
+                            events = statement.events,
+                            "PostgresSink inserted batch"
+                        );
+                        self.counters
+                            .events_written
+                            .fetch_add(statement.events as u64, Ordering::Relaxed);
+                    }
+                    Err(PostgresExecutorError::ConnectionLost(error)) => {
+                        warn!(%error, action = "reconnecting", "PostgresSink lost connection");
+                        self.connected = false;
+                        return;
+                    }
+                    Err(PostgresExecutorError::Statement(error)) => {
+                        warn!(
+                            table = table.name(),
+                            events = statement.events,
+                            %error,
+                            action = "discarding events",
+                            "PostgresSink statement rejected"
+                        );
+                        self.counters
+                            .events_failed
+                            .fetch_add(statement.events as u64, Ordering::Relaxed);
+                    }
+                }
+
+                self.buffers[index].drain(..statement.events);
+            }
+        }
+    }
+
+    /// Buffer the provided event, dropping the oldest buffered event of the same
+    /// [`PostgresTable`] if it's buffer is full.
+    fn buffer(&mut self, event: MarketEvent<DataKind>) {
+        let Some(table) = PostgresTable::of(&event.kind) else {
+            self.counters.events_skipped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+
+        let buffer = &mut self.buffers[table as usize];
+        if buffer.len() >= self.config.buffer_capacity.max(1) {
+            buffer.pop_front();
+            self.counters.events_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        buffer.push_back(event);
+    }
+
+    fn is_batch_full(&self) -> bool {
+        self.buffers
+            .iter()
+            .any(|buffer| buffer.len() >= self.config.batch_size)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buffers.iter().all(VecDeque::is_empty)
+    }
+
+    fn drop_buffers(&mut self) {
+        for buffer in &mut self.buffers {
+            self.counters
+                .events_dropped
+                .fetch_add(buffer.len() as u64, Ordering::Relaxed);
+            buffer.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::{
+        book::{OrderBook, OrderBookSide},
+        candle::Candle,
+        trade::PublicTrade,
+    };
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MockState {
+        connected: bool,
+        reconnectable: bool,
+        reject: Option<PostgresTable>,
+        executed: Vec<PostgresStatement>,
+    }
+
+    /// Mock [`PostgresExecutor`] recording every executed [`PostgresStatement`], that loses it's
+    /// connection whenever the test sets `connected` to false.
+    #[derive(Debug, Clone, Default)]
+    struct MockExecutor(Arc<Mutex<MockState>>);
+
+    #[async_trait]
+    impl PostgresExecutor for MockExecutor {
+        async fn execute(
+            &mut self,
+            statement: &PostgresStatement,
+        ) -> Result<(), PostgresExecutorError> {
+            let mut state = self.0.lock().unwrap();
+            if !state.connected {
+                return Err(PostgresExecutorError::ConnectionLost(
+                    "connection reset".to_string(),
+                ));
+            }
+            if state.reject == Some(statement.table) {
+                return Err(PostgresExecutorError::Statement(
+                    "relation does not exist".to_string(),
+                ));
+            }
+            state.executed.push(statement.clone());
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> Result<(), String> {
+            let mut state = self.0.lock().unwrap();
+            state.connected = state.reconnectable;
+            state
+                .connected
+                .then_some(())
+                .ok_or_else(|| "connection refused".to_string())
+        }
+    }
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_base_ms(0).with_latency_ms(1)
+    }
+
+    fn time(ms: i64) -> DateTime<Utc> {
+        fixture().time(ms)
+    }
+
+    fn event(kind: DataKind) -> MarketEvent<DataKind> {
+        fixture().event(1672531200000, "btc", kind)
+    }
+
+    fn trade(id: u64) -> MarketEvent<DataKind> {
+        event(DataKind::Trade(PublicTrade {
+            id: id.to_string(),
+            price: num!(100.0),
+            amount: num!(1.0),
+            side: Side::Buy,
+        }))
+    }
+
+    fn candle() -> MarketEvent<DataKind> {
+        event(DataKind::Candle(Candle {
+            close_time: time(1672531260000),
+            open: num!(1.0),
+            high: num!(2.0),
+            low: num!(0.5),
+            close: num!(1.5),
+            volume: num!(10.0),
+            trade_count: 7,
+            historical: false,
+        }))
+    }
+
+    fn order_book() -> MarketEvent<DataKind> {
+        event(DataKind::OrderBook(OrderBook {
+            last_update_time: time(1672531200000),
+            bids: OrderBookSide::new(Side::Buy, [(num!(99.0), num!(1.0))]),
+            asks: OrderBookSide::new(Side::Sell, [(num!(101.0), num!(1.0))]),
+        }))
+    }
+
+    fn trade_ids(statement: &PostgresStatement) -> Vec<String> {
+        statement
+            .params
+            .chunks(TRADE_SCHEMA.len())
+            .map(|row| match &row[6] {
+                SqlValue::Text(id) => id.clone(),
+                value => panic!("unexpected trade id: {value:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_migration_sql_matches_table_schemas() {
+        assert_eq!(POSTGRES_MIGRATION_SQL, migration_sql());
+    }
+
+    #[test]
+    fn test_postgres_statement_insert() {
+        struct TestCase {
+            table: PostgresTable,
+            events: Vec<MarketEvent<DataKind>>,
+            expected_sql: &'static str,
+            expected_params: Vec<SqlValue>,
+        }
+
+        let event_params = || {
+            vec![
+                SqlValue::Text("binance_spot".to_string()),
+                SqlValue::Text("btc".to_string()),
+                SqlValue::Text("usdt".to_string()),
+                SqlValue::Text("spot".to_string()),
+                SqlValue::Timestamp(time(1672531200000)),
+                SqlValue::Timestamp(time(1672531200001)),
+            ]
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: multi-row trades insert
+                table: PostgresTable::Trades,
+                events: vec![trade(1), trade(2)],
+                expected_sql: "INSERT INTO trades \
+                    (exchange, base, quote, instrument_kind, exchange_time, received_time, id, price, amount, side) \
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10), \
+                    ($11, $12, $13, $14, $15, $16, $17, $18, $19, $20)",
+                expected_params: [1, 2]
+                    .into_iter()
+                    .flat_map(|id| {
+                        let mut params = event_params();
+                        params.extend([
+                            SqlValue::Text(id.to_string()),
+                            SqlValue::Num(num!(100.0)),
+                            SqlValue::Num(num!(1.0)),
+                            SqlValue::Text("buy".to_string()),
+                        ]);
+                        params
+                    })
+                    .collect(),
+            },
+            TestCase {
+                // TC1: single candle insert
+                table: PostgresTable::Candles,
+                events: vec![candle()],
+                expected_sql: "INSERT INTO candles \
+                    (exchange, base, quote, instrument_kind, exchange_time, received_time, close_time, open, high, low, close, volume, trade_count) \
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+                expected_params: {
+                    let mut params = event_params();
+                    params.extend([
+                        SqlValue::Timestamp(time(1672531260000)),
+                        SqlValue::Num(num!(1.0)),
+                        SqlValue::Num(num!(2.0)),
+                        SqlValue::Num(num!(0.5)),
+                        SqlValue::Num(num!(1.5)),
+                        SqlValue::Num(num!(10.0)),
+                        SqlValue::BigInt(7),
+                    ]);
+                    params
+                },
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = PostgresStatement::insert(test.table, &test.events);
+            assert_eq!(actual.sql, test.expected_sql, "TC{} failed", index);
+            assert_eq!(actual.params, test.expected_params, "TC{} failed", index);
+            assert_eq!(actual.events, test.events.len(), "TC{} failed", index);
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_sql_value_to_sql() {
+        use tokio_postgres::types::{ToSql, Type};
+
+        struct TestCase {
+            input: SqlValue,
+            ty: Type,
+            expected: Option<Vec<u8>>,
+        }
+
+        let encode = |value: &(dyn ToSql + Sync), ty: &Type| {
+            let mut out = bytes::BytesMut::new();
+            value
+                .to_sql_checked(ty, &mut out)
+                .ok()
+                .map(|_| out.to_vec())
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: timestamp bound as TIMESTAMPTZ
+                input: SqlValue::Timestamp(time(1672531200000)),
+                ty: Type::TIMESTAMPTZ,
+                expected: encode(&time(1672531200000), &Type::TIMESTAMPTZ),
+            },
+            TestCase {
+                // TC1: text bound as TEXT
+                input: SqlValue::Text("btc".to_string()),
+                ty: Type::TEXT,
+                expected: Some(b"btc".to_vec()),
+            },
+            TestCase {
+                // TC2: bigint bound as BIGINT
+                input: SqlValue::BigInt(7),
+                ty: Type::INT8,
+                expected: Some(7i64.to_be_bytes().to_vec()),
+            },
+            TestCase {
+                // TC3: num bound as DOUBLE PRECISION
+                input: SqlValue::Num(num!(100.5)),
+                ty: Type::FLOAT8,
+                expected: Some(100.5f64.to_be_bytes().to_vec()),
+            },
+            TestCase {
+                // TC4: column of an unsupported type is rejected
+                input: SqlValue::Text("btc".to_string()),
+                ty: Type::BOOL,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = encode(&test.input, &test.ty);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_postgres_sink_batches_per_table() {
+        let executor = MockExecutor::default();
+        let state = Arc::clone(&executor.0);
+        state.lock().unwrap().connected = true;
+
+        let sink = PostgresSink::spawn(
+            executor,
+            PostgresSinkConfig {
+                batch_size: 3,
+                ..PostgresSinkConfig::default()
+            },
+        );
+
+        // Full batch of trades is inserted immediately
+        for id in 0..3 {
+            sink.write(trade(id));
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(state.lock().unwrap().executed.len(), 1);
+
+        // Partially filled batches are inserted at the flush interval, skipping OrderBooks
+        sink.write(trade(3));
+        sink.write(candle());
+        sink.write(order_book());
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(state.lock().unwrap().executed.len(), 1);
+        tokio::time::sleep(DEFAULT_POSTGRES_FLUSH_INTERVAL).await;
+
+        let counters = sink.shutdown().await;
+        assert_eq!(counters.events_written(), 5);
+        assert_eq!(counters.events_skipped(), 1);
+        assert_eq!(counters.events_failed(), 0);
+        assert_eq!(counters.events_dropped(), 0);
+
+        let state = state.lock().unwrap();
+        let actual = state
+            .executed
+            .iter()
+            .map(|statement| (statement.table, statement.events))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                (PostgresTable::Trades, 3),
+                (PostgresTable::Trades, 1),
+                (PostgresTable::Candles, 1),
+            ]
+        );
+        assert_eq!(trade_ids(&state.executed[0]), vec!["0", "1", "2"]);
+        assert_eq!(trade_ids(&state.executed[1]), vec!["3"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_postgres_sink_flush_inserts_partial_batches() {
+        let executor = MockExecutor::default();
+        let state = Arc::clone(&executor.0);
+        state.lock().unwrap().connected = true;
+
+        let sink = PostgresSink::spawn(
+            executor,
+            PostgresSinkConfig {
+                batch_size: 3,
+                ..PostgresSinkConfig::default()
+            },
+        );
+
+        // Flushing an empty sink returns immediately
+        sink.flush().await.unwrap();
+        assert!(state.lock().unwrap().executed.is_empty());
+
+        // Partially filled batches are inserted before the flush interval elapses
+        sink.write(trade(0));
+        sink.write(candle());
+        sink.flush().await.unwrap();
+
+        let actual = state
+            .lock()
+            .unwrap()
+            .executed
+            .iter()
+            .map(|statement| (statement.table, statement.events))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![(PostgresTable::Trades, 1), (PostgresTable::Candles, 1)]
+        );
+
+        let counters = sink.shutdown().await;
+        assert_eq!(counters.events_written(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_postgres_sink_buffers_while_reconnecting() {
+        let executor = MockExecutor::default();
+        let state = Arc::clone(&executor.0);
+
+        let sink = PostgresSink::spawn(
+            executor,
+            PostgresSinkConfig {
+                batch_size: 2,
+                buffer_capacity: 3,
+                ..PostgresSinkConfig::default()
+            },
+        );
+
+        // Database unavailable & reconnection refused, so only the latest 3 trades are buffered
+        for id in 0..5 {
+            sink.write(trade(id));
+        }
+        tokio::time::sleep(DEFAULT_POSTGRES_RECONNECT_INTERVAL * 3).await;
+        assert!(state.lock().unwrap().executed.is_empty());
+
+        // Reconnection succeeds, so the buffered trades are inserted in batches
+        state.lock().unwrap().reconnectable = true;
+        tokio::time::sleep(DEFAULT_POSTGRES_RECONNECT_INTERVAL * 2).await;
+
+        // Rejected statements are discarded & counted as failed
+        state.lock().unwrap().reject = Some(PostgresTable::Candles);
+        sink.write(candle());
+
+        let counters = sink.shutdown().await;
+        assert_eq!(counters.events_written(), 3);
+        assert_eq!(counters.events_dropped(), 2);
+        assert_eq!(counters.events_failed(), 1);
+
+        let state = state.lock().unwrap();
+        let actual = state
+            .executed
+            .iter()
+            .flat_map(trade_ids)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec!["2", "3", "4"]);
+        assert_eq!(
+            state
+                .executed
+                .iter()
+                .map(|statement| statement.events)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+    }
+}
@@ -0,0 +1,739 @@
+use super::instrument_key;
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+};
+use async_trait::async_trait;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{
+        mpsc::{self, error::TryRecvError},
+        oneshot,
+    },
+    task::JoinHandle,
+};
+use tracing::warn;
+
+/// Default maximum number of messages buffered by a [`RedisSink`] while the connection is lost.
+pub const DEFAULT_REDIS_BUFFER_CAPACITY: usize = 10_000;
+
+/// Default time to wait between [`RedisClient::reconnect`] attempts.
+pub const DEFAULT_REDIS_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Determines how each [`MarketEvent<DataKind>`](MarketEvent) is published to Redis, using the
+/// "md:{exchange}:{instrument}:{kind}" channel or stream key, eg/ "md:binance_spot:btc_usdt_spot:trade".
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RedisTarget {
+    /// Classic pub/sub channels via `PUBLISH`.
+    PubSub,
+    /// Redis Streams via `XADD`, optionally trimming each stream.
+    Stream { trim: Option<StreamTrim> },
+}
+
+/// `MAXLEN` trimming applied to each `XADD`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct StreamTrim {
+    pub maxlen: usize,
+    /// Trim with `MAXLEN ~`, which is much more efficient but may retain slightly more entries.
+    pub approximate: bool,
+}
+
+/// Configuration of a [`RedisSink`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RedisSinkConfig {
+    /// Determines how each event is published.
+    pub target: RedisTarget,
+    /// Maximum number of messages buffered while the connection is lost, after which the oldest
+    /// buffered message is dropped for each new one.
+    pub buffer_capacity: usize,
+    /// Time to wait between reconnection attempts.
+    pub reconnect_interval: Duration,
+}
+
+impl RedisSinkConfig {
+    /// Construct a new [`Self`] using the provided [`RedisTarget`] & the default buffering &
+    /// reconnection configuration.
+    pub fn new(target: RedisTarget) -> Self {
+        Self {
+            target,
+            buffer_capacity: DEFAULT_REDIS_BUFFER_CAPACITY,
+            reconnect_interval: DEFAULT_REDIS_RECONNECT_INTERVAL,
+        }
+    }
+}
+
+/// Redis command published by a [`RedisSink`], with the versioned
+/// [`MarketEventEnvelope`](crate::event::MarketEventEnvelope) JSON as the payload.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RedisCommand {
+    Publish {
+        channel: String,
+        payload: String,
+    },
+    XAdd {
+        key: String,
+        trim: Option<StreamTrim>,
+        payload: String,
+    },
+}
+
+impl RedisCommand {
+    /// Construct the [`RedisCommand`] that publishes the provided event to the [`RedisTarget`].
+    pub fn new(target: RedisTarget, event: &MarketEvent<DataKind>) -> Result<Self, String> {
+        let key = format!(
+            "md:{}:{}:{}",
+            event.exchange,
+            instrument_key(&event.instrument),
+            event.kind.as_str()
+        );
+        let payload = event.to_json().map_err(|error| error.to_string())?;
+
+        Ok(match target {
+            RedisTarget::PubSub => Self::Publish {
+                channel: key,
+                payload,
+            },
+            RedisTarget::Stream { trim } => Self::XAdd { key, trim, payload },
+        })
+    }
+
+    /// Raw command arguments, eg/ `["XADD", key, "MAXLEN", "~", "1000", "*", "event", payload]`.
+    pub fn args(&self) -> Vec<String> {
+        match self {
+            Self::Publish { channel, payload } => {
+                vec!["PUBLISH".to_string(), channel.clone(), payload.clone()]
+            }
+            Self::XAdd { key, trim, payload } => {
+                let mut args = vec!["XADD".to_string(), key.clone()];
+                if let Some(trim) = trim {
+                    args.push("MAXLEN".to_string());
+                    if trim.approximate {
+                        args.push("~".to_string());
+                    }
+                    args.push(trim.maxlen.to_string());
+                }
+                args.extend(["*".to_string(), "event".to_string(), payload.clone()]);
+                args
+            }
+        }
+    }
+}
+
+/// Reason a [`RedisClient`] failed to execute a [`RedisCommand`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RedisClientError {
+    /// Connection was lost, so the [`RedisCommand`] is retried after reconnecting.
+    ConnectionLost(String),
+    /// Command was rejected by the server, and is discarded.
+    Command(String),
+}
+
+/// Redis client used by a [`RedisSink`] (eg/ the `RedisConnection` enabled by the `redis`
+/// feature).
+#[async_trait]
+pub trait RedisClient: Send + 'static {
+    /// Execute the provided [`RedisCommand`] (see [`RedisCommand::args`]).
+    async fn execute(&mut self, command: &RedisCommand) -> Result<(), RedisClientError>;
+
+    /// Re-establish the connection after a [`RedisClientError::ConnectionLost`].
+    async fn reconnect(&mut self) -> Result<(), String>;
+}
+
+/// [`RedisClient`] executing [`RedisCommand`]s over a `redis` crate multiplexed connection.
+#[cfg(feature = "redis")]
+#[derive(Debug)]
+pub struct RedisConnection {
+    client: ::redis::Client,
+    connection: ::redis::aio::MultiplexedConnection,
+}
+
+#[cfg(feature = "redis")]
+impl RedisConnection {
+    /// Connect to the Redis server of the provided `redis::Client`, reusing it to reconnect.
+    pub async fn connect(client: ::redis::Client) -> Result<Self, ::redis::RedisError> {
+        let connection = client.get_multiplexed_tokio_connection().await?;
+        Ok(Self { client, connection })
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl RedisClient for RedisConnection {
+    async fn execute(&mut self, command: &RedisCommand) -> Result<(), RedisClientError> {
+        let args = command.args();
+        ::redis::cmd(&args[0])
+            .arg(&args[1..])
+            .query_async::<::redis::Value>(&mut self.connection)
+            .await
+            .map(|_| ())
+            .map_err(|error| {
+                if error.is_io_error() || error.is_unrecoverable_error() {
+                    RedisClientError::ConnectionLost(error.to_string())
+                } else {
+                    RedisClientError::Command(error.to_string())
+                }
+            })
+    }
+
+    async fn reconnect(&mut self) -> Result<(), String> {
+        self.connection = self
+            .client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(|error| error.to_string())?;
+        Ok(())
+    }
+}
+
+/// Counters describing the work done by a [`RedisSink`].
+#[derive(Debug, Default)]
+pub struct RedisCounters {
+    messages_published: AtomicU64,
+    messages_dropped: AtomicU64,
+    publish_errors: AtomicU64,
+}
+
+impl RedisCounters {
+    /// Number of messages published to Redis.
+    pub fn messages_published(&self) -> u64 {
+        self.messages_published.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages dropped because the buffer overflowed while the connection was lost.
+    pub fn messages_dropped(&self) -> u64 {
+        self.messages_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of events that failed serialisation or were rejected by the server.
+    pub fn publish_errors(&self) -> u64 {
+        self.publish_errors.load(Ordering::Relaxed)
+    }
+}
+
+/// Publishes [`MarketEvent<DataKind>`](MarketEvent)s to Redis pub/sub channels or streams via a
+/// [`RedisClient`].
+///
+/// Events are sent to a dedicated publisher task, so publishing never blocks on the network. If
+/// the connection is lost, the task reconnects at the configured interval whilst buffering up to
+/// the configured number of messages, dropping the oldest buffered message on overflow so the
+/// freshest data is published once reconnected.
+///
+/// ### Durability
+/// - [`RedisSink::flush`] returns once every event published before it has been executed by
+///   Redis (or rejected), waiting for the connection to be re-established if necessary.
+/// - [`RedisSink::shutdown`] publishes every buffered message before closing the sink, unless
+///   the connection is lost whilst shutting down, in which case they are dropped & counted.
+/// - Dropping a [`RedisSink`] without shutting it down is best-effort: the publisher task still
+///   publishes every buffered message, but only whilst the tokio runtime is running.
+#[derive(Debug)]
+pub struct RedisSink {
+    tx: mpsc::UnboundedSender<RedisSinkCommand>,
+    counters: Arc<RedisCounters>,
+    publisher: JoinHandle<()>,
+}
+
+impl RedisSink {
+    /// Spawn a publisher task using the provided [`RedisClient`] & [`RedisSinkConfig`].
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn spawn<Client>(client: Client, config: RedisSinkConfig) -> Self
+    where
+        Client: RedisClient,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let counters = Arc::new(RedisCounters::default());
+        let publisher = RedisPublisher {
+            client,
+            config,
+            counters: Arc::clone(&counters),
+            buffer: VecDeque::new(),
+            connected: true,
+            flushes: Vec::new(),
+        };
+
+        Self {
+            tx,
+            counters,
+            publisher: tokio::spawn(publisher.run(rx)),
+        }
+    }
+
+    /// Publish the provided event without waiting.
+    pub fn publish(&self, event: MarketEvent<DataKind>) {
+        let _ = self.tx.send(RedisSinkCommand::Publish(Box::new(event)));
+    }
+
+    /// Publish every event published before this call, waiting until Redis has executed them.
+    pub async fn flush(&self) -> Result<(), DataError> {
+        let publisher_terminated = || DataError::Flush {
+            sink: "RedisSink",
+            reason: "publisher task terminated".to_string(),
+        };
+
+        let (ack_tx, ack_rx) = oneshot::channel();
+        self.tx
+            .send(RedisSinkCommand::Flush(ack_tx))
+            .map_err(|_| publisher_terminated())?;
+
+        ack_rx.await.map_err(|_| publisher_terminated())
+    }
+
+    /// [`RedisCounters`] of this sink, which remain accessible after shutdown.
+    pub fn counters(&self) -> Arc<RedisCounters> {
+        Arc::clone(&self.counters)
+    }
+
+    /// Publish all buffered messages, returning the final [`RedisCounters`].
+    ///
+    /// Buffered messages are dropped if the connection is lost during shutdown.
+    pub async fn shutdown(self) -> Arc<RedisCounters> {
+        let Self {
+            tx,
+            counters,
+            publisher,
+        } = self;
+
+        drop(tx);
+        let _ = publisher.await;
+
+        counters
+    }
+}
+
+/// Command sent from a [`RedisSink`] to it's [`RedisPublisher`] task.
+#[derive(Debug)]
+enum RedisSinkCommand {
+    Publish(Box<MarketEvent<DataKind>>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Task that publishes buffered [`RedisCommand`]s, reconnecting if the connection is lost.
+#[derive(Debug)]
+struct RedisPublisher<Client> {
+    client: Client,
+    config: RedisSinkConfig,
+    counters: Arc<RedisCounters>,
+    buffer: VecDeque<RedisCommand>,
+    connected: bool,
+    /// Pending [`RedisSink::flush`] acknowledgements, sent once the buffer is empty.
+    flushes: Vec<oneshot::Sender<()>>,
+}
+
+impl<Client> RedisPublisher<Client>
+where
+    Client: RedisClient,
+{
+    /// Publish every event received until the sink is shutdown & the buffer is empty.
+    async fn run(mut self, mut rx: mpsc::UnboundedReceiver<RedisSinkCommand>) {
+        let mut open = true;
+
+        loop {
+            // Buffer every event received since the last iteration
+            loop {
+                match rx.try_recv() {
+                    Ok(command) => self.handle(command),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        open = false;
+                        break;
+                    }
+                }
+            }
+
+            // Wait for the next event if there is nothing to publish
+            if self.buffer.is_empty() {
+                self.ack_flushes();
+                if !open {
+                    break;
+                }
+                match rx.recv().await {
+                    Some(command) => self.handle(command),
+                    None => open = false,
+                }
+                continue;
+            }
+
+            // Reconnect if the connection was lost, giving up if already shutting down
+            if !self.connected {
+                if !open {
+                    self.drop_buffer();
+                    break;
+                }
+                match self.client.reconnect().await {
+                    Ok(()) => self.connected = true,
+                    Err(error) => {
+                        warn!(
+                            %error,
+                            action = "retrying after interval",
+                            "RedisSink failed to reconnect"
+                        );
+                        tokio::time::sleep(self.config.reconnect_interval).await;
+                    }
+                }
+                continue;
+            }
+
+            let Some(command) = self.buffer.front() else {
+                continue;
+            };
+            match self.client.execute(command).await {
+                Ok(()) => {
+                    self.buffer.pop_front();
+                    self.counters
+                        .messages_published
+                        .fetch_add(1, Ordering::Relaxed);
+                }
+                Err(RedisClientError::ConnectionLost(error)) => {
+                    warn!(%error, action = "reconnecting", "RedisSink lost connection");
+                    self.connected = false;
+                }
+                Err(RedisClientError::Command(error)) => {
+                    warn!(%error, action = "discarding message", "RedisSink command rejected");
+                    self.buffer.pop_front();
+                    self.counters.publish_errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Buffer the event of the provided [`RedisSinkCommand`], or record the pending flush.
+    fn handle(&mut self, command: RedisSinkCommand) {
+        match command {
+            RedisSinkCommand::Publish(event) => self.buffer(*event),
+            RedisSinkCommand::Flush(ack) => self.flushes.push(ack),
+        }
+    }
+
+    /// Acknowledge every pending [`RedisSink::flush`].
+    fn ack_flushes(&mut self) {
+        for ack in self.flushes.drain(..) {
+            let _ = ack.send(());
+        }
+    }
+
+    /// Buffer the [`RedisCommand`] of the provided event, dropping the oldest buffered command
+    /// if the buffer is full.
+    fn buffer(&mut self, event: MarketEvent<DataKind>) {
+        let command = match RedisCommand::new(self.config.target, &event) {
+            Ok(command) => command,
+            Err(error) => {
+                warn!(%error, "RedisSink failed to serialise MarketEvent");
+                self.counters.publish_errors.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        if self.buffer.len() >= self.config.buffer_capacity.max(1) {
+            self.buffer.pop_front();
+            self.counters
+                .messages_dropped
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.buffer.push_back(command);
+    }
+
+    fn drop_buffer(&mut self) {
+        self.counters
+            .messages_dropped
+            .fetch_add(self.buffer.len() as u64, Ordering::Relaxed);
+        self.buffer.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MockState {
+        connected: bool,
+        reconnectable: bool,
+        executed: Vec<RedisCommand>,
+    }
+
+    /// Mock [`RedisClient`] recording every executed [`RedisCommand`], that loses it's connection
+    /// whenever the test sets `connected` to false.
+    #[derive(Debug, Clone, Default)]
+    struct MockClient(Arc<Mutex<MockState>>);
+
+    #[async_trait]
+    impl RedisClient for MockClient {
+        async fn execute(&mut self, command: &RedisCommand) -> Result<(), RedisClientError> {
+            let mut state = self.0.lock().unwrap();
+            if !state.connected {
+                return Err(RedisClientError::ConnectionLost("broken pipe".to_string()));
+            }
+            state.executed.push(command.clone());
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> Result<(), String> {
+            let mut state = self.0.lock().unwrap();
+            state.connected = state.reconnectable;
+            state
+                .connected
+                .then_some(())
+                .ok_or_else(|| "connection refused".to_string())
+        }
+    }
+
+    fn trade(id: u64) -> MarketEvent<DataKind> {
+        let mut trade = TradeFixture::new()
+            .with_base_ms(1672531200000)
+            .with_latency_ms(1)
+            .trade(0, "btc", num!(100.0), num!(1.0), Side::Buy);
+        trade.kind.id = id.to_string();
+        MarketEvent::from(trade)
+    }
+
+    #[test]
+    fn test_redis_command() {
+        struct TestCase {
+            target: RedisTarget,
+            expected: Vec<&'static str>,
+        }
+
+        let event = trade(1);
+        let payload = event.to_json().unwrap();
+
+        let tests = vec![
+            TestCase {
+                // TC0: pub/sub channel
+                target: RedisTarget::PubSub,
+                expected: vec!["PUBLISH", "md:binance_spot:btc_usdt_spot:trade"],
+            },
+            TestCase {
+                // TC1: stream without trimming
+                target: RedisTarget::Stream { trim: None },
+                expected: vec!["XADD", "md:binance_spot:btc_usdt_spot:trade", "*", "event"],
+            },
+            TestCase {
+                // TC2: stream with exact trimming
+                target: RedisTarget::Stream {
+                    trim: Some(StreamTrim {
+                        maxlen: 1000,
+                        approximate: false,
+                    }),
+                },
+                expected: vec![
+                    "XADD",
+                    "md:binance_spot:btc_usdt_spot:trade",
+                    "MAXLEN",
+                    "1000",
+                    "*",
+                    "event",
+                ],
+            },
+            TestCase {
+                // TC3: stream with approximate trimming
+                target: RedisTarget::Stream {
+                    trim: Some(StreamTrim {
+                        maxlen: 500,
+                        approximate: true,
+                    }),
+                },
+                expected: vec![
+                    "XADD",
+                    "md:binance_spot:btc_usdt_spot:trade",
+                    "MAXLEN",
+                    "~",
+                    "500",
+                    "*",
+                    "event",
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut args = RedisCommand::new(test.target, &event).unwrap().args();
+            assert_eq!(args.pop().unwrap(), payload, "TC{} failed", index);
+            assert_eq!(args, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_redis_sink_flush_waits_for_reconnection() {
+        let client = MockClient::default();
+        let state = Arc::clone(&client.0);
+
+        let sink = Arc::new(RedisSink::spawn(
+            client,
+            RedisSinkConfig::new(RedisTarget::PubSub),
+        ));
+
+        // Connection lost, so the flush waits for the buffered events to be published
+        sink.publish(trade(0));
+        sink.publish(trade(1));
+        let flush = tokio::spawn({
+            let sink = Arc::clone(&sink);
+            async move { sink.flush().await }
+        });
+        tokio::time::sleep(DEFAULT_REDIS_RECONNECT_INTERVAL * 3).await;
+        assert!(!flush.is_finished());
+
+        // Reconnection succeeds, so the flush completes once every event is published
+        state.lock().unwrap().reconnectable = true;
+        flush.await.unwrap().unwrap();
+        assert_eq!(state.lock().unwrap().executed.len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_redis_sink_buffers_while_reconnecting() {
+        let client = MockClient::default();
+        let state = Arc::clone(&client.0);
+        state.lock().unwrap().connected = true;
+
+        let sink = RedisSink::spawn(
+            client,
+            RedisSinkConfig {
+                buffer_capacity: 3,
+                ..RedisSinkConfig::new(RedisTarget::PubSub)
+            },
+        );
+
+        // Connected, so the first event is published
+        sink.publish(trade(0));
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(state.lock().unwrap().executed.len(), 1);
+
+        // Connection lost & reconnection refused, so only the latest 3 events are buffered
+        state.lock().unwrap().connected = false;
+        for id in 1..=5 {
+            sink.publish(trade(id));
+        }
+        tokio::time::sleep(DEFAULT_REDIS_RECONNECT_INTERVAL * 3).await;
+        assert_eq!(state.lock().unwrap().executed.len(), 1);
+
+        // Reconnection succeeds, so the buffered events are published
+        state.lock().unwrap().reconnectable = true;
+        tokio::time::sleep(DEFAULT_REDIS_RECONNECT_INTERVAL * 2).await;
+
+        let counters = sink.shutdown().await;
+        assert_eq!(counters.messages_published(), 4);
+        assert_eq!(counters.messages_dropped(), 2);
+        assert_eq!(counters.publish_errors(), 0);
+
+        let actual = state
+            .lock()
+            .unwrap()
+            .executed
+            .iter()
+            .map(|command| match command {
+                RedisCommand::Publish { payload, .. } => {
+                    match MarketEvent::<DataKind>::from_json(payload).unwrap().kind {
+                        DataKind::Trade(trade) => trade.id,
+                        kind => panic!("unexpected kind: {kind:?}"),
+                    }
+                }
+                command => panic!("unexpected command: {command:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec!["0", "3", "4", "5"]);
+    }
+
+    /// Minimal RESP server accepting one connection at a time, that acknowledges the connection
+    /// setup commands, records every other command, rejects `XADD`s & drops the connection on a
+    /// `PUBLISH` to the "close" channel.
+    #[cfg(feature = "redis")]
+    async fn resp_server(
+        listener: tokio::net::TcpListener,
+        commands: mpsc::UnboundedSender<Vec<String>>,
+    ) {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        while let Ok((stream, _)) = listener.accept().await {
+            let mut stream = BufReader::new(stream);
+            let mut line = String::new();
+            'connection: loop {
+                line.clear();
+                if stream.read_line(&mut line).await.unwrap_or(0) == 0 {
+                    break;
+                }
+                let len = line.trim_end()[1..].parse::<usize>().unwrap();
+
+                let mut command = Vec::with_capacity(len);
+                for _ in 0..len {
+                    // Skip the "$<len>" line preceding each bulk string argument
+                    for _ in 0..2 {
+                        line.clear();
+                        stream.read_line(&mut line).await.unwrap();
+                    }
+                    command.push(line.trim_end().to_string());
+                }
+
+                let reply = match command[0].as_str() {
+                    "CLIENT" => "+OK\r\n",
+                    "PUBLISH" if command[1] == "close" => break 'connection,
+                    "PUBLISH" => ":1\r\n",
+                    _ => "-ERR unsupported command\r\n",
+                };
+                if command[0] != "CLIENT" {
+                    commands.send(command).unwrap();
+                }
+                stream.write_all(reply.as_bytes()).await.unwrap();
+            }
+        }
+    }
+
+    #[cfg(feature = "redis")]
+    #[tokio::test]
+    async fn test_redis_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (commands_tx, mut commands_rx) = mpsc::unbounded_channel();
+        tokio::spawn(resp_server(listener, commands_tx));
+
+        let client = ::redis::Client::open(format!("redis://{address}")).unwrap();
+        let mut connection = RedisConnection::connect(client).await.unwrap();
+
+        let publish = |channel: &str| RedisCommand::Publish {
+            channel: channel.to_string(),
+            payload: "{}".to_string(),
+        };
+
+        // Published
+        assert_eq!(connection.execute(&publish("md:a")).await, Ok(()));
+        assert_eq!(
+            commands_rx.recv().await.unwrap(),
+            vec!["PUBLISH", "md:a", "{}"]
+        );
+
+        // Rejected by the server
+        let xadd = RedisCommand::XAdd {
+            key: "md:b".to_string(),
+            trim: Some(StreamTrim {
+                maxlen: 10,
+                approximate: true,
+            }),
+            payload: "{}".to_string(),
+        };
+        assert!(matches!(
+            connection.execute(&xadd).await,
+            Err(RedisClientError::Command(_))
+        ));
+        assert_eq!(commands_rx.recv().await.unwrap(), xadd.args());
+
+        // Connection dropped by the server, so it's lost until reconnected
+        assert!(matches!(
+            connection.execute(&publish("close")).await,
+            Err(RedisClientError::ConnectionLost(_))
+        ));
+        assert_eq!(connection.reconnect().await, Ok(()));
+        assert_eq!(connection.execute(&publish("md:c")).await, Ok(()));
+        assert_eq!(
+            commands_rx.recv().await.unwrap(),
+            vec!["PUBLISH", "md:c", "{}"]
+        );
+    }
+}
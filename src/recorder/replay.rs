@@ -0,0 +1,426 @@
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use futures::{Stream, StreamExt};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    fs::File,
+    future::Future,
+    io::{BufRead, BufReader, Lines},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    sync::mpsc,
+    time::{Instant, Sleep},
+};
+use tracing::warn;
+
+/// Defines the rate at which a [`ReplayStream`] emits recorded events.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum ReplayPacing {
+    /// Emit every event as fast as possible.
+    #[default]
+    Unpaced,
+    /// Emit events with the original `received_time` deltas between them, divided by the
+    /// provided speed multiplier (eg/ 2.0 replays twice as fast as recorded).
+    ReceivedTime { speed: f64 },
+}
+
+/// Configuration of a [`ReplayStream`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct ReplayConfig {
+    /// Rate at which recorded events are emitted.
+    pub pacing: ReplayPacing,
+    /// Skip every recorded event with a `received_time` before this time, if any.
+    pub start: Option<DateTime<Utc>>,
+}
+
+impl ReplayConfig {
+    /// Construct a new [`Self`] using the provided configuration.
+    pub fn new(pacing: ReplayPacing, start: Option<DateTime<Utc>>) -> Self {
+        Self { pacing, start }
+    }
+}
+
+/// Recorded file being replayed by a [`ReplayStream`].
+struct ReplaySource {
+    lines: Lines<Box<dyn BufRead + Send>>,
+    next: Option<MarketEvent<DataKind>>,
+}
+
+impl std::fmt::Debug for ReplaySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplaySource")
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl ReplaySource {
+    /// Open the recorded file at the provided path, decompressing it as it's read if it is
+    /// gzipped.
+    #[allow(clippy::result_large_err)]
+    fn open(path: &Path) -> Result<Self, DataError> {
+        let reader: Box<dyn BufRead + Send> =
+            match path.extension().is_some_and(|extension| extension == "gz") {
+                true => Box::new(BufReader::new(GzDecoder::new(File::open(path)?))),
+                false => Box::new(BufReader::new(File::open(path)?)),
+            };
+
+        Ok(Self {
+            lines: reader.lines(),
+            next: None,
+        })
+    }
+
+    /// Read the next recorded event at or after the provided start time into [`Self::next`],
+    /// returning any errors encountered along the way.
+    fn advance(&mut self, start: Option<DateTime<Utc>>) -> Vec<DataError> {
+        let mut errors = Vec::new();
+
+        self.next = loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(error)) => {
+                    errors.push(DataError::from(error));
+                    break None;
+                }
+                None => break None,
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match MarketEvent::<DataKind>::from_json(&line) {
+                Ok(event) if start.is_some_and(|start| event.received_time < start) => continue,
+                Ok(event) => break Some(event),
+                Err(error) => errors.push(error),
+            }
+        };
+
+        errors
+    }
+}
+
+/// Replays [`MarketEvent<DataKind>`](MarketEvent)s recorded by a
+/// [`JsonlRecorder`](super::JsonlRecorder), for backtesting against recorded data.
+///
+/// Events from every provided file (eg/ one per exchange, or rotated files) are interleaved in
+/// `received_time` order via a k-way merge, assuming each file is itself ordered as recorded.
+/// Files ending in `.gz` are decompressed up front, whilst others are read lazily. Lines that
+/// fail to parse are yielded as errors without ending the replay, like a live
+/// [`MarketStream`](crate::MarketStream).
+#[derive(Debug)]
+pub struct ReplayStream {
+    config: ReplayConfig,
+    sources: Vec<ReplaySource>,
+    heap: BinaryHeap<Reverse<(DateTime<Utc>, usize)>>,
+    errors: VecDeque<DataError>,
+    anchor: Option<(DateTime<Utc>, Instant)>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl ReplayStream {
+    /// Construct a new [`Self`] that replays the recorded files at the provided paths.
+    #[allow(clippy::result_large_err)]
+    pub fn new<Paths, P>(paths: Paths, config: ReplayConfig) -> Result<Self, DataError>
+    where
+        Paths: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut replay = Self {
+            config,
+            sources: Vec::new(),
+            heap: BinaryHeap::new(),
+            errors: VecDeque::new(),
+            anchor: None,
+            sleep: None,
+        };
+
+        for path in paths {
+            replay.sources.push(ReplaySource::open(path.as_ref())?);
+            replay.advance(replay.sources.len() - 1);
+        }
+
+        Ok(replay)
+    }
+
+    /// Spawn a task that forwards every replayed event to an [`mpsc::UnboundedReceiver`], the same
+    /// output a [`Streams`](crate::streams::Streams) exchange channel provides.
+    ///
+    /// Errors are logged and skipped.
+    pub fn into_receiver(mut self) -> mpsc::UnboundedReceiver<MarketEvent<DataKind>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Some(result) = self.next().await {
+                match result {
+                    Ok(event) => {
+                        if tx.send(event).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => warn!(%error, "ReplayStream skipping invalid recorded event"),
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// Read the next event of the source at the provided index, adding it to the merge heap.
+    fn advance(&mut self, index: usize) {
+        let source = &mut self.sources[index];
+        self.errors.extend(source.advance(self.config.start));
+
+        if let Some(event) = &source.next {
+            self.heap.push(Reverse((event.received_time, index)));
+        }
+    }
+
+    /// Determine the [`Instant`] the event with the provided `received_time` should be emitted.
+    fn deadline(&mut self, received_time: DateTime<Utc>) -> Option<Instant> {
+        let ReplayPacing::ReceivedTime { speed } = self.config.pacing else {
+            return None;
+        };
+
+        // First emitted event anchors the recorded timeline to the replay timeline
+        let (anchor_time, anchor_instant) =
+            *self.anchor.get_or_insert((received_time, Instant::now()));
+
+        let delta = (received_time - anchor_time).to_std().unwrap_or_default();
+        Some(anchor_instant + delta.div_f64(speed))
+    }
+}
+
+impl Stream for ReplayStream {
+    type Item = Result<MarketEvent<DataKind>, DataError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(error) = this.errors.pop_front() {
+            return Poll::Ready(Some(Err(error)));
+        }
+
+        let Some(&Reverse((received_time, index))) = this.heap.peek() else {
+            return Poll::Ready(None);
+        };
+
+        // Wait until the event is due
+        if let Some(deadline) = this.deadline(received_time) {
+            let sleep = this
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.sleep = None;
+        }
+
+        this.heap.pop();
+        let event = this.sources[index]
+            .next
+            .take()
+            .expect("heap only contains sources with a next event");
+        this.advance(index);
+
+        Poll::Ready(Some(Ok(event)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use std::{path::PathBuf, time::Duration};
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_base_ms(0).with_latency_ms(1)
+    }
+
+    fn time(ms: i64) -> DateTime<Utc> {
+        fixture().time(ms)
+    }
+
+    fn trade(exchange: &'static str, received_ms: i64) -> MarketEvent<DataKind> {
+        let mut trade = fixture().with_market(exchange, "usdt").trade(
+            received_ms - 1,
+            "btc",
+            num!(100.0),
+            num!(1.0),
+            Side::Buy,
+        );
+        trade.kind.id = received_ms.to_string();
+        MarketEvent::from(trade)
+    }
+
+    fn directory(test: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("barter-data-replay-{}-{test}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn jsonl(events: &[MarketEvent<DataKind>]) -> String {
+        events
+            .iter()
+            .map(|event| event.to_json().unwrap() + "\n")
+            .collect()
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn key(event: &MarketEvent<DataKind>) -> (String, i64) {
+        (
+            event.exchange.to_string(),
+            event.received_time.timestamp_millis(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replay_merges_files_in_received_time_order() {
+        let directory = directory("merge");
+        let binance = directory.join("binance.jsonl");
+        let okx = directory.join("okx.jsonl.gz");
+        std::fs::write(
+            &binance,
+            jsonl(&[trade("binance_spot", 1), trade("binance_spot", 4)]),
+        )
+        .unwrap();
+        std::fs::write(
+            &okx,
+            gzip(jsonl(&[trade("okx", 2), trade("okx", 3), trade("okx", 5)]).as_bytes()),
+        )
+        .unwrap();
+
+        let actual = ReplayStream::new([&binance, &okx], ReplayConfig::default())
+            .unwrap()
+            .map(|result| key(&result.unwrap()))
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected = vec![
+            ("binance_spot".to_string(), 1),
+            ("okx".to_string(), 2),
+            ("okx".to_string(), 3),
+            ("binance_spot".to_string(), 4),
+            ("okx".to_string(), 5),
+        ];
+        assert_eq!(actual, expected);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_seeks_to_start_time() {
+        let directory = directory("seek");
+        let binance = directory.join("binance.jsonl");
+        let okx = directory.join("okx.jsonl");
+        std::fs::write(
+            &binance,
+            jsonl(&[trade("binance_spot", 10), trade("binance_spot", 30)]),
+        )
+        .unwrap();
+        std::fs::write(&okx, jsonl(&[trade("okx", 20), trade("okx", 40)])).unwrap();
+
+        let config = ReplayConfig::new(ReplayPacing::Unpaced, Some(time(20)));
+        let mut rx = ReplayStream::new([&binance, &okx], config)
+            .unwrap()
+            .into_receiver();
+
+        let mut actual = Vec::new();
+        while let Some(event) = rx.recv().await {
+            actual.push(key(&event));
+        }
+
+        let expected = vec![
+            ("okx".to_string(), 20),
+            ("binance_spot".to_string(), 30),
+            ("okx".to_string(), 40),
+        ];
+        assert_eq!(actual, expected);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_replay_paced_by_received_time() {
+        let directory = directory("pacing");
+        let binance = directory.join("binance.jsonl");
+        std::fs::write(
+            &binance,
+            jsonl(&[
+                trade("binance_spot", 1_000),
+                trade("binance_spot", 2_000),
+                trade("binance_spot", 2_000),
+                trade("binance_spot", 5_000),
+            ]),
+        )
+        .unwrap();
+
+        let config = ReplayConfig::new(ReplayPacing::ReceivedTime { speed: 2.0 }, None);
+        let mut replay = ReplayStream::new([&binance], config).unwrap();
+
+        let start = Instant::now();
+        let mut actual = Vec::new();
+        while let Some(result) = replay.next().await {
+            result.unwrap();
+            actual.push(start.elapsed());
+        }
+
+        // Recorded deltas of 1s, 0s & 3s replayed at twice the speed
+        let expected = vec![
+            Duration::ZERO,
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+            Duration::from_millis(2_000),
+        ];
+        assert_eq!(actual, expected);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_yields_invalid_lines_as_errors() {
+        let directory = directory("invalid");
+        let binance = directory.join("binance.jsonl");
+        std::fs::write(
+            &binance,
+            jsonl(&[trade("binance_spot", 1)])
+                + "{\"not\":\"an event\"}\n\n"
+                + &jsonl(&[trade("binance_spot", 2)]),
+        )
+        .unwrap();
+
+        let actual = ReplayStream::new([&binance], ReplayConfig::default())
+            .unwrap()
+            .map(|result| result.map(|event| key(&event)).map_err(drop))
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected = vec![
+            Ok(("binance_spot".to_string(), 1)),
+            Err(()),
+            Ok(("binance_spot".to_string(), 2)),
+        ];
+        assert_eq!(actual, expected);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
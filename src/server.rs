@@ -0,0 +1,513 @@
+use crate::{
+    error::DataError,
+    event::{DataKind, MarketEvent},
+};
+use barter_integration::model::{Exchange, Instrument};
+use futures::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::broadcast,
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message,
+};
+use tracing::{debug, info, warn};
+
+/// Default number of serialised events buffered for each client before it's considered slow.
+pub const DEFAULT_CLIENT_BUFFER: usize = 1024;
+
+/// Default maximum duration a single WebSocket write to a client may take before the client is
+/// disconnected.
+pub const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Names of every [`DataKind`] a client can subscribe to, see [`DataKind::as_str`].
+const DATA_KINDS: [&str; 5] = [
+    "trade",
+    "order_book_l1",
+    "order_book",
+    "candle",
+    "liquidation",
+];
+
+/// Action taken when a client falls more than [`ServerConfig::client_buffer`] events behind the
+/// ingest side.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum SlowClientPolicy {
+    /// Drop the oldest buffered events & continue from the most recent ones.
+    #[default]
+    DropEvents,
+    /// Disconnect the client with a close frame.
+    Disconnect,
+}
+
+/// Configuration of a [`RebroadcastServer`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ServerConfig {
+    /// Number of serialised events buffered for each client.
+    pub client_buffer: usize,
+    /// Action taken when a client falls behind by more than the `client_buffer`.
+    pub slow_client_policy: SlowClientPolicy,
+    /// Maximum duration a single write to a client may take before it's disconnected.
+    pub write_timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            client_buffer: DEFAULT_CLIENT_BUFFER,
+            slow_client_policy: SlowClientPolicy::default(),
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Construct a new [`Self`] with the provided [`SlowClientPolicy`].
+    pub fn new(slow_client_policy: SlowClientPolicy) -> Self {
+        Self {
+            slow_client_policy,
+            ..Self::default()
+        }
+    }
+}
+
+/// Selection of the [`MarketEvent<DataKind>`](MarketEvent)s forwarded to a client. Empty
+/// collections match everything.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct EventFilter {
+    #[serde(default)]
+    pub exchanges: Vec<Exchange>,
+    #[serde(default)]
+    pub instruments: Vec<Instrument>,
+    #[serde(default)]
+    pub kinds: Vec<String>,
+}
+
+impl EventFilter {
+    /// Determine if the provided [`MarketEvent<DataKind>`](MarketEvent) matches this filter.
+    pub fn matches(&self, event: &MarketEvent<DataKind>) -> bool {
+        (self.exchanges.is_empty() || self.exchanges.contains(&event.exchange))
+            && (self.instruments.is_empty() || self.instruments.contains(&event.instrument))
+            && (self.kinds.is_empty() || self.kinds.iter().any(|kind| kind == event.kind.as_str()))
+    }
+
+    /// Validate every subscribed kind is a known [`DataKind`].
+    fn validate(&self) -> Result<(), String> {
+        match self
+            .kinds
+            .iter()
+            .find(|kind| !DATA_KINDS.contains(&kind.as_str()))
+        {
+            Some(kind) => Err(format!(
+                "unknown kind {kind}, expected one of {DATA_KINDS:?}"
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Message sent by a client to select the events it receives.
+///
+/// eg/ `{"type": "subscribe", "exchanges": ["binance_spot"], "kinds": ["trade"]}`
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Replace the client's [`EventFilter`].
+    Subscribe(EventFilter),
+    /// Stop forwarding events to the client.
+    Unsubscribe,
+}
+
+/// Control message sent by the server in response to a [`ClientMessage`]. Events themselves are
+/// forwarded as [`MarketEventEnvelope`](crate::event::MarketEventEnvelope) JSON.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Subscribed(EventFilter),
+    Unsubscribed,
+    Error { message: String },
+}
+
+/// Event fanned out to every client task.
+#[derive(Debug)]
+struct Broadcast {
+    event: MarketEvent<DataKind>,
+    json: String,
+}
+
+/// WebSocket server that re-broadcasts the [`MarketEvent<DataKind>`](MarketEvent)s published to
+/// it to any number of local clients, each selecting events with a [`ClientMessage`].
+///
+/// Events are serialised once and fanned out via a bounded buffer per client, so a slow client
+/// never stalls [`publish`](Self::publish) - it's handled according to the
+/// [`SlowClientPolicy`] instead.
+#[derive(Debug)]
+pub struct RebroadcastServer {
+    local_addr: SocketAddr,
+    events_tx: broadcast::Sender<Arc<Broadcast>>,
+    acceptor: JoinHandle<()>,
+}
+
+impl Drop for RebroadcastServer {
+    fn drop(&mut self) {
+        // Client tasks exit once the events_tx is dropped
+        self.acceptor.abort();
+    }
+}
+
+impl RebroadcastServer {
+    /// Bind a [`Self`] to the provided address & start accepting client connections.
+    pub async fn bind<Addr>(addr: Addr, config: ServerConfig) -> Result<Self, DataError>
+    where
+        Addr: ToSocketAddrs,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let (events_tx, _) = broadcast::channel(config.client_buffer.max(1));
+
+        let acceptor = tokio::spawn(accept_clients(listener, events_tx.clone(), config));
+        info!(%local_addr, ?config, "RebroadcastServer listening");
+
+        Ok(Self {
+            local_addr,
+            events_tx,
+            acceptor,
+        })
+    }
+
+    /// Local address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of currently connected clients.
+    pub fn clients(&self) -> usize {
+        self.events_tx.receiver_count()
+    }
+
+    /// Forward the provided event to every client whose [`EventFilter`] it matches.
+    #[allow(clippy::result_large_err)]
+    pub fn publish(&self, event: MarketEvent<DataKind>) -> Result<(), DataError> {
+        if self.events_tx.receiver_count() == 0 {
+            return Ok(());
+        }
+
+        let json = event.to_json()?;
+
+        // Only fails if every client disconnected since the receiver_count check
+        let _ = self.events_tx.send(Arc::new(Broadcast { event, json }));
+        Ok(())
+    }
+
+    /// [`publish`](Self::publish) every event yielded by the provided [`Stream`].
+    pub async fn publish_stream<St>(&self, stream: St)
+    where
+        St: Stream<Item = MarketEvent<DataKind>>,
+    {
+        futures::pin_mut!(stream);
+        while let Some(event) = stream.next().await {
+            if let Err(error) = self.publish(event) {
+                warn!(%error, "RebroadcastServer failed to serialise MarketEvent");
+            }
+        }
+    }
+}
+
+async fn accept_clients(
+    listener: TcpListener,
+    events_tx: broadcast::Sender<Arc<Broadcast>>,
+    config: ServerConfig,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, client)) => {
+                let events_rx = events_tx.subscribe();
+                tokio::spawn(async move {
+                    debug!(%client, "RebroadcastServer client connected");
+                    serve_client(stream, events_rx, config).await;
+                    debug!(%client, "RebroadcastServer client disconnected");
+                });
+            }
+            Err(error) => warn!(%error, "RebroadcastServer failed to accept client connection"),
+        }
+    }
+}
+
+async fn serve_client(
+    stream: TcpStream,
+    mut events_rx: broadcast::Receiver<Arc<Broadcast>>,
+    config: ServerConfig,
+) {
+    let websocket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(websocket) => websocket,
+        Err(error) => {
+            debug!(%error, "RebroadcastServer client WebSocket handshake failed");
+            return;
+        }
+    };
+    let (mut ws_sink, mut ws_stream) = websocket.split();
+
+    // No events are forwarded until the client subscribes
+    let mut filter = None;
+
+    loop {
+        let message = tokio::select! {
+            message = ws_stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let response = handle_client_message(&text, &mut filter);
+                    Message::Text(serde_json::to_string(&response).unwrap_or_default())
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            event = events_rx.recv() => match event {
+                Ok(broadcast) => match &filter {
+                    Some(filter) if filter.matches(&broadcast.event) => {
+                        Message::Text(broadcast.json.clone())
+                    }
+                    _ => continue,
+                },
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    match config.slow_client_policy {
+                        SlowClientPolicy::DropEvents => {
+                            debug!(skipped, "RebroadcastServer dropped events for slow client");
+                            continue;
+                        }
+                        SlowClientPolicy::Disconnect => {
+                            debug!(skipped, "RebroadcastServer disconnecting slow client");
+                            let _ = tokio::time::timeout(
+                                config.write_timeout,
+                                ws_sink.send(Message::Close(Some(CloseFrame {
+                                    code: CloseCode::Policy,
+                                    reason: "client too slow".into(),
+                                }))),
+                            )
+                            .await;
+                            break;
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        };
+
+        match tokio::time::timeout(config.write_timeout, ws_sink.send(message)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                debug!(%error, "RebroadcastServer failed to write to client");
+                break;
+            }
+            Err(_) => {
+                debug!("RebroadcastServer client write timed out");
+                break;
+            }
+        }
+    }
+}
+
+/// Apply a client's text message to it's [`EventFilter`], returning the [`ServerMessage`] reply.
+fn handle_client_message(text: &str, filter: &mut Option<EventFilter>) -> ServerMessage {
+    match serde_json::from_str::<ClientMessage>(text) {
+        Ok(ClientMessage::Subscribe(subscribe)) => match subscribe.validate() {
+            Ok(()) => {
+                *filter = Some(subscribe.clone());
+                ServerMessage::Subscribed(subscribe)
+            }
+            Err(message) => ServerMessage::Error { message },
+        },
+        Ok(ClientMessage::Unsubscribe) => {
+            *filter = None;
+            ServerMessage::Unsubscribed
+        }
+        Err(error) => ServerMessage::Error {
+            message: format!("invalid ClientMessage: {error}"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    fn trade(exchange: &'static str, id: u64) -> MarketEvent<DataKind> {
+        let trade = TradeFixture::new().with_market(exchange, "usdt").trade(
+            id as i64,
+            "btc",
+            num!(1.0),
+            num!(1.0),
+            Side::Buy,
+        );
+        MarketEvent::from(trade)
+    }
+
+    async fn connect(server: &RebroadcastServer, subscribe: &str) -> Client {
+        let (mut client, _) = connect_async(format!("ws://{}", server.local_addr()))
+            .await
+            .unwrap();
+        client
+            .send(Message::Text(subscribe.to_string()))
+            .await
+            .unwrap();
+
+        let response = next_text(&mut client).await.unwrap();
+        assert!(matches!(
+            serde_json::from_str(&response).unwrap(),
+            ServerMessage::Subscribed(_)
+        ));
+        client
+    }
+
+    async fn next_text(client: &mut Client) -> Option<String> {
+        match client.next().await? {
+            Ok(Message::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn trade_id(json: &str) -> String {
+        match MarketEvent::<DataKind>::from_json(json).unwrap().kind {
+            DataKind::Trade(trade) => trade.id,
+            other => panic!("expected trade, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_event_filter_matches() {
+        struct TestCase {
+            filter: &'static str,
+            expected: bool,
+        }
+
+        let event = trade("binance_spot", 1);
+
+        let tests = vec![
+            TestCase {
+                // TC0: empty filter matches everything
+                filter: r#"{}"#,
+                expected: true,
+            },
+            TestCase {
+                // TC1: matching exchange & kind
+                filter: r#"{"exchanges": ["okx", "binance_spot"], "kinds": ["trade"]}"#,
+                expected: true,
+            },
+            TestCase {
+                // TC2: different exchange
+                filter: r#"{"exchanges": ["okx"]}"#,
+                expected: false,
+            },
+            TestCase {
+                // TC3: different kind
+                filter: r#"{"kinds": ["order_book_l1"]}"#,
+                expected: false,
+            },
+            TestCase {
+                // TC4: matching instrument
+                filter: r#"{"instruments": [{"base": "btc", "quote": "usdt", "instrument_type": "spot"}]}"#,
+                expected: true,
+            },
+            TestCase {
+                // TC5: different instrument kind
+                filter: r#"{"instruments": [{"base": "btc", "quote": "usdt", "instrument_type": "future_perpetual"}]}"#,
+                expected: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let filter = serde_json::from_str::<EventFilter>(test.filter).unwrap();
+            assert_eq!(filter.matches(&event), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_handle_client_message() {
+        let mut filter = None;
+
+        let actual =
+            handle_client_message(r#"{"type": "subscribe", "kinds": ["trades"]}"#, &mut filter);
+        assert!(matches!(actual, ServerMessage::Error { .. }));
+        assert_eq!(filter, None);
+
+        let actual =
+            handle_client_message(r#"{"type": "subscribe", "kinds": ["trade"]}"#, &mut filter);
+        assert!(matches!(actual, ServerMessage::Subscribed(_)));
+        assert!(filter.is_some());
+
+        let actual = handle_client_message(r#"{"type": "unsubscribe"}"#, &mut filter);
+        assert_eq!(actual, ServerMessage::Unsubscribed);
+        assert_eq!(filter, None);
+
+        let actual = handle_client_message("not json", &mut filter);
+        assert!(matches!(actual, ServerMessage::Error { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_server_filtered_delivery() {
+        let server = RebroadcastServer::bind("127.0.0.1:0", ServerConfig::default())
+            .await
+            .unwrap();
+
+        let mut client = connect(
+            &server,
+            r#"{"type": "subscribe", "exchanges": ["binance_spot"], "kinds": ["trade"]}"#,
+        )
+        .await;
+
+        server.publish(trade("binance_spot", 1)).unwrap();
+        server.publish(trade("okx", 2)).unwrap();
+        server.publish(trade("binance_spot", 3)).unwrap();
+
+        assert_eq!(trade_id(&next_text(&mut client).await.unwrap()), "1");
+        assert_eq!(trade_id(&next_text(&mut client).await.unwrap()), "3");
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_server_slow_client_policy() {
+        // DropEvents: lagging client continues from the most recent buffered events
+        let config = ServerConfig {
+            client_buffer: 2,
+            ..ServerConfig::new(SlowClientPolicy::DropEvents)
+        };
+        let server = RebroadcastServer::bind("127.0.0.1:0", config)
+            .await
+            .unwrap();
+        let mut client = connect(&server, r#"{"type": "subscribe"}"#).await;
+
+        // Publish without yielding, so the client task falls behind
+        for id in 0..10 {
+            server.publish(trade("binance_spot", id)).unwrap();
+        }
+
+        assert_eq!(trade_id(&next_text(&mut client).await.unwrap()), "8");
+        assert_eq!(trade_id(&next_text(&mut client).await.unwrap()), "9");
+
+        // Disconnect: lagging client is sent a close frame
+        let config = ServerConfig {
+            client_buffer: 2,
+            ..ServerConfig::new(SlowClientPolicy::Disconnect)
+        };
+        let server = RebroadcastServer::bind("127.0.0.1:0", config)
+            .await
+            .unwrap();
+        let mut client = connect(&server, r#"{"type": "subscribe"}"#).await;
+
+        for id in 0..10 {
+            server.publish(trade("binance_spot", id)).unwrap();
+        }
+
+        match client.next().await {
+            Some(Ok(Message::Close(Some(frame)))) => assert_eq!(frame.code, CloseCode::Policy),
+            other => panic!("expected close frame, found {other:?}"),
+        }
+    }
+}
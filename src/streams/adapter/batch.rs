@@ -0,0 +1,236 @@
+use crate::event::MarketEvent;
+use futures::{Stream, StreamExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Configuration of a [`Batch`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BatchConfig {
+    /// Maximum number of events in a batch, reaching it flushes the batch immediately.
+    pub max_size: usize,
+    /// Maximum [`Duration`] the first event of a batch is buffered before the batch is flushed.
+    pub interval: Duration,
+}
+
+impl BatchConfig {
+    /// Construct a new [`Self`] using the provided configuration.
+    pub fn new(max_size: usize, interval: Duration) -> Self {
+        Self { max_size, interval }
+    }
+}
+
+/// Stream adapter that buffers [`MarketEvent<T>`](MarketEvent)s and yields them as a `Vec` once
+/// either the `max_size` or the flush `interval` of the [`BatchConfig`] is reached, whichever
+/// comes first.
+///
+/// Events retain their original order within a batch, empty batches are never yielded, and any
+/// buffered events are flushed when the inner stream ends.
+#[derive(Debug)]
+pub struct Batch<St, T> {
+    stream: St,
+    config: BatchConfig,
+    buffer: Vec<MarketEvent<T>>,
+    timer: Pin<Box<Sleep>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for Batch<St, T> where St: Unpin {}
+
+impl<St, T> Batch<St, T> {
+    /// Construct a new [`Self`] that batches the provided stream.
+    pub fn new(stream: St, config: BatchConfig) -> Self {
+        Self {
+            stream,
+            // A max_size of zero would never flush on size, so treat it as one
+            config: BatchConfig {
+                max_size: config.max_size.max(1),
+                ..config
+            },
+            buffer: Vec::with_capacity(config.max_size),
+            timer: Box::pin(tokio::time::sleep(config.interval)),
+            stream_ended: false,
+        }
+    }
+
+    /// Take the buffered events, leaving an empty buffer for the next batch.
+    fn take(&mut self) -> Vec<MarketEvent<T>> {
+        std::mem::replace(&mut self.buffer, Vec::with_capacity(self.config.max_size))
+    }
+}
+
+impl<St, T> Stream for Batch<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+{
+    type Item = Vec<MarketEvent<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Flush remaining buffered events so nothing is lost at stream end
+            if this.stream_ended {
+                return match this.buffer.is_empty() {
+                    true => Poll::Ready(None),
+                    false => Poll::Ready(Some(this.take())),
+                };
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => {
+                    // First event of a new batch starts the flush interval
+                    if this.buffer.is_empty() {
+                        let deadline = Instant::now() + this.config.interval;
+                        this.timer.as_mut().reset(deadline);
+                    }
+
+                    this.buffer.push(event);
+                    if this.buffer.len() >= this.config.max_size {
+                        return Poll::Ready(Some(this.take()));
+                    }
+                    continue;
+                }
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            if this.buffer.is_empty() {
+                return Poll::Pending;
+            }
+
+            return match this.timer.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Some(this.take())),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind, num, subscription::trade::PublicTrade, test_util::trade::TradeFixture,
+    };
+    use barter_integration::model::Side;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn trade(id: u64) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().trade(id as i64, "btc", num!(1.0), num!(1.0), Side::Buy)
+    }
+
+    fn data_kind(id: u64) -> MarketEvent<DataKind> {
+        MarketEvent::from(trade(id))
+    }
+
+    fn id(event: &MarketEvent<DataKind>) -> String {
+        match &event.kind {
+            DataKind::Trade(trade) => trade.id.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Send each event after it's associated millisecond offset, then batch & collect the
+    /// emitted (millisecond offset, ids) pairs.
+    async fn run(
+        inputs: Vec<(u64, MarketEvent<DataKind>)>,
+        config: BatchConfig,
+    ) -> Vec<(u64, Vec<String>)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, event) in inputs {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+                tx.send(event).unwrap();
+            }
+        });
+
+        Batch::new(UnboundedReceiverStream::new(rx), config)
+            .map(|batch| {
+                let elapsed = start.elapsed().as_millis() as u64;
+                (elapsed, batch.iter().map(id).collect())
+            })
+            .collect()
+            .await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_time_based_flush() {
+        let inputs = vec![
+            (0, data_kind(0)),
+            (100, data_kind(1)),
+            (600, data_kind(2)),
+            (1500, data_kind(3)),
+        ];
+
+        let actual = run(inputs, BatchConfig::new(100, Duration::from_millis(500))).await;
+
+        assert_eq!(
+            actual,
+            vec![
+                (500, vec!["0".to_string(), "1".to_string()]),
+                (1100, vec!["2".to_string()]),
+                (1500, vec!["3".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_size_based_flush() {
+        let inputs = vec![
+            (0, data_kind(0)),
+            (0, data_kind(1)),
+            (0, data_kind(2)),
+            (10, data_kind(3)),
+            (20, data_kind(4)),
+            (20, data_kind(5)),
+        ];
+
+        let actual = run(inputs, BatchConfig::new(2, Duration::from_secs(60))).await;
+
+        assert_eq!(
+            actual,
+            vec![
+                (0, vec!["0".to_string(), "1".to_string()]),
+                (10, vec!["2".to_string(), "3".to_string()]),
+                (20, vec!["4".to_string(), "5".to_string()]),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_end_of_stream_flush_typed() {
+        let inputs = (0..5).map(trade).collect::<Vec<_>>();
+
+        let actual = Batch::new(
+            futures::stream::iter(inputs),
+            BatchConfig::new(3, Duration::from_secs(60)),
+        )
+        .map(|batch| {
+            batch
+                .into_iter()
+                .map(|event| event.kind.id)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                vec!["0".to_string(), "1".to_string(), "2".to_string()],
+                vec!["3".to_string(), "4".to_string()],
+            ]
+        );
+    }
+}
@@ -0,0 +1,621 @@
+use super::AsOrderBookL1;
+use crate::{event::MarketEvent, subscription::book::Level};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// [`Exchange`] of the [`MarketEvent<ConsolidatedBbo>`](MarketEvent)s yielded by a
+/// [`ConsolidateBbo`] adapter, since each side may be sourced from a different exchange.
+pub const CONSOLIDATED_EXCHANGE: &str = "consolidated";
+
+/// Configuration of a [`ConsolidateBbo`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BboConfig {
+    /// [`Duration`] since the last update of an exchange quote after which it's considered
+    /// stale & excluded from the consolidated BBO, until it's next update.
+    pub stale_after: Duration,
+}
+
+impl BboConfig {
+    /// Construct a new [`Self`] using the provided staleness timeout.
+    pub fn new(stale_after: Duration) -> Self {
+        Self { stale_after }
+    }
+}
+
+/// OrderBook [`Level`] of the [`Exchange`] it was sourced from.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SourcedLevel {
+    pub exchange: Exchange,
+    pub level: Level,
+}
+
+/// Normalised Barter consolidated best bid & offer (BBO) of an instrument across exchanges,
+/// yielded by a [`ConsolidateBbo`] adapter.
+///
+/// Each side is `None` if no exchange has a fresh quote for the instrument, eg/ once every
+/// exchange feed has gone stale.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct ConsolidatedBbo {
+    /// Highest bid across every exchange with a fresh quote.
+    pub best_bid: Option<SourcedLevel>,
+    /// Lowest ask across every exchange with a fresh quote.
+    pub best_ask: Option<SourcedLevel>,
+}
+
+impl ConsolidatedBbo {
+    /// Determine if the best bid is above the best ask, ie/ the exchanges are crossed & buying
+    /// on the best ask exchange to sell on the best bid exchange is an arbitrage opportunity
+    /// (before fees).
+    pub fn is_crossed(&self) -> bool {
+        match (&self.best_bid, &self.best_ask) {
+            (Some(bid), Some(ask)) => bid.level.price > ask.level.price,
+            _ => false,
+        }
+    }
+
+    /// Consolidated spread (best ask price - best bid price), negative if crossed.
+    pub fn spread(&self) -> Option<crate::num::Num> {
+        match (&self.best_bid, &self.best_ask) {
+            (Some(bid), Some(ask)) => Some(ask.level.price - bid.level.price),
+            _ => None,
+        }
+    }
+}
+
+/// Latest L1 quote of an exchange for an instrument.
+#[derive(Debug)]
+struct Quote {
+    best_bid: Level,
+    best_ask: Level,
+    exchange_time: DateTime<Utc>,
+    updated: Instant,
+}
+
+/// Consolidation state of an instrument.
+#[derive(Debug, Default)]
+struct InstrumentBbo {
+    quotes: BTreeMap<Exchange, Quote>,
+    current: ConsolidatedBbo,
+}
+
+impl InstrumentBbo {
+    /// Consolidate the quotes that are fresh at the provided [`Instant`].
+    ///
+    /// Price ties are won by the larger amount, and then by the first [`Exchange`] in name
+    /// order, so the result is deterministic.
+    fn consolidate(&self, now: Instant, stale_after: Duration) -> ConsolidatedBbo {
+        let fresh = self
+            .quotes
+            .iter()
+            .filter(|(_, quote)| now.duration_since(quote.updated) < stale_after);
+
+        let mut best_bid: Option<(&Exchange, Level)> = None;
+        let mut best_ask: Option<(&Exchange, Level)> = None;
+        for (exchange, quote) in fresh {
+            if best_bid.is_none_or(|(_, best)| better(quote.best_bid, best, Ordering::Greater)) {
+                best_bid = Some((exchange, quote.best_bid));
+            }
+            if best_ask.is_none_or(|(_, best)| better(quote.best_ask, best, Ordering::Less)) {
+                best_ask = Some((exchange, quote.best_ask));
+            }
+        }
+
+        let sourced = |(exchange, level): (&Exchange, Level)| SourcedLevel {
+            exchange: exchange.clone(),
+            level,
+        };
+        ConsolidatedBbo {
+            best_bid: best_bid.map(sourced),
+            best_ask: best_ask.map(sourced),
+        }
+    }
+
+    /// Determine the latest exchange time of the quotes sourcing the current consolidated BBO.
+    fn exchange_time(&self) -> Option<DateTime<Utc>> {
+        [&self.current.best_bid, &self.current.best_ask]
+            .into_iter()
+            .flatten()
+            .filter_map(|side| self.quotes.get(&side.exchange))
+            .map(|quote| quote.exchange_time)
+            .max()
+    }
+
+    /// Determine the earliest [`Instant`] a fresh quote goes stale.
+    fn next_stale(&self, now: Instant, stale_after: Duration) -> Option<Instant> {
+        self.quotes
+            .values()
+            .map(|quote| quote.updated + stale_after)
+            .filter(|stale| *stale > now)
+            .min()
+    }
+}
+
+/// Determine if the provided candidate [`Level`] is strictly better than the current best,
+/// where a better price compares as the provided [`Ordering`] (ie/ `Greater` for bids).
+fn better(candidate: Level, best: Level, better_price: Ordering) -> bool {
+    match candidate.price.partial_cmp(&best.price) {
+        Some(ordering) if ordering == better_price => true,
+        Some(Ordering::Equal) => candidate.amount > best.amount,
+        _ => false,
+    }
+}
+
+/// Stream adapter that consolidates the L1 quotes of each [`Instrument`] from every
+/// [`Exchange`] of the inner stream into a [`ConsolidatedBbo`] (ie/ an NBBO-style view),
+/// sourcing the highest bid & lowest ask from the exchanges quoting them.
+///
+/// Typically the inner stream merges the L1 streams of several exchanges, eg/ via
+/// [`futures::stream::select_all`]. Quotes are keyed by their normalised [`Instrument`], so the
+/// same instrument on different exchanges is consolidated together, and different instruments
+/// are consolidated independently.
+///
+/// A [`MarketEvent<ConsolidatedBbo>`](MarketEvent) is yielded whenever the consolidated BBO of
+/// an [`Instrument`] changes (price, amount or source [`Exchange`] of either side), including
+/// when a quote is excluded for going stale. It's [`MarketEvent::exchange`] is
+/// [`CONSOLIDATED_EXCHANGE`], it's exchange time is the latest exchange time of the sourcing
+/// quotes, and it's received time is when the change was observed.
+///
+/// Events that do not contain an [`OrderBookL1`](crate::subscription::book::OrderBookL1) are
+/// dropped.
+///
+/// Uses the `tokio` clock to track staleness, so a paused runtime (eg/ in tests)
+/// deterministically controls when quotes go stale.
+#[derive(Debug)]
+pub struct ConsolidateBbo<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: BboConfig,
+    instruments: HashMap<Instrument, InstrumentBbo>,
+    ready: VecDeque<MarketEvent<ConsolidatedBbo>>,
+    timer: Pin<Box<Sleep>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for ConsolidateBbo<St, T> where St: Unpin {}
+
+impl<St, T> ConsolidateBbo<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsOrderBookL1,
+{
+    /// Construct a new [`Self`] that consolidates the L1 quotes of the provided stream.
+    pub fn new(stream: St, config: BboConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            instruments: HashMap::new(),
+            ready: VecDeque::new(),
+            timer: Box::pin(tokio::time::sleep(Duration::ZERO)),
+            stream_ended: false,
+        }
+    }
+
+    /// Update the quote of the provided [`MarketEvent<T>`](MarketEvent) exchange, returning the
+    /// consolidated BBO of it's [`Instrument`] if it changed.
+    fn process(
+        &mut self,
+        event: MarketEvent<T>,
+        now: Instant,
+    ) -> Option<MarketEvent<ConsolidatedBbo>> {
+        let l1 = event.kind.as_order_book_l1()?;
+        let bbo = self
+            .instruments
+            .entry(event.instrument.clone())
+            .or_default();
+        bbo.quotes.insert(
+            event.exchange,
+            Quote {
+                best_bid: l1.best_bid,
+                best_ask: l1.best_ask,
+                exchange_time: event.exchange_time,
+                updated: now,
+            },
+        );
+
+        update(
+            bbo,
+            event.instrument,
+            now,
+            self.config.stale_after,
+            event.received_time,
+        )
+    }
+
+    /// Re-consolidate every [`Instrument`] with a quote that went stale, queueing the changed
+    /// consolidated BBOs.
+    fn expire(&mut self, now: Instant) {
+        let stale_after = self.config.stale_after;
+        let mut changed = self
+            .instruments
+            .iter_mut()
+            .filter_map(|(instrument, bbo)| {
+                update(bbo, instrument.clone(), now, stale_after, Utc::now())
+            })
+            .collect::<Vec<_>>();
+        changed.sort_by(|a, b| a.instrument.cmp(&b.instrument));
+        self.ready.extend(changed);
+    }
+
+    /// Determine the earliest [`Instant`] a fresh quote goes stale.
+    fn next_stale(&self, now: Instant) -> Option<Instant> {
+        self.instruments
+            .values()
+            .filter_map(|bbo| bbo.next_stale(now, self.config.stale_after))
+            .min()
+    }
+}
+
+/// Re-consolidate the provided [`InstrumentBbo`], returning the consolidated BBO if it changed.
+fn update(
+    bbo: &mut InstrumentBbo,
+    instrument: Instrument,
+    now: Instant,
+    stale_after: Duration,
+    received_time: DateTime<Utc>,
+) -> Option<MarketEvent<ConsolidatedBbo>> {
+    let consolidated = bbo.consolidate(now, stale_after);
+    if consolidated == bbo.current {
+        return None;
+    }
+
+    // Keep the exchange time of the last sourcing quotes if every quote went stale
+    let previous_time = bbo.exchange_time();
+    bbo.current = consolidated;
+    let exchange_time = bbo.exchange_time().or(previous_time)?;
+
+    Some(MarketEvent {
+        exchange_time,
+        raw_exchange_time: None,
+        received_time,
+        exchange: Exchange::from(CONSOLIDATED_EXCHANGE),
+        instrument,
+        kind: bbo.current.clone(),
+        meta: (),
+    })
+}
+
+impl<St, T> Stream for ConsolidateBbo<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsOrderBookL1,
+{
+    type Item = MarketEvent<ConsolidatedBbo>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Emit events that are ready before consuming more from the inner stream
+            if let Some(event) = this.ready.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+
+            // Exclude quotes that went stale before processing the next event
+            this.expire(Instant::now());
+            if !this.ready.is_empty() {
+                continue;
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event, Instant::now()) {
+                    Some(event) => return Poll::Ready(Some(event)),
+                    None => continue,
+                },
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            // Wake up when the next fresh quote goes stale
+            match this.next_stale(Instant::now()) {
+                Some(deadline) => {
+                    this.timer.as_mut().reset(deadline);
+                    if this.timer.as_mut().poll(cx).is_ready() {
+                        continue;
+                    }
+                    return Poll::Pending;
+                }
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        streams::adapter::MarketEventStreamExt,
+        subscription::{book::OrderBookL1, trade::PublicTrade},
+        test_util::trade::TradeFixture,
+    };
+    use crate::{num, num::Num};
+    use barter_integration::model::Side;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn l1(
+        offset_ms: i64,
+        exchange: &'static str,
+        base: &str,
+        bid: (Num, Num),
+        ask: (Num, Num),
+    ) -> MarketEvent<OrderBookL1> {
+        let fixture = TradeFixture::new().with_market(exchange, "usdt");
+        fixture.event(
+            offset_ms,
+            base,
+            OrderBookL1 {
+                last_update_time: fixture.time(offset_ms),
+                best_bid: Level::from(bid),
+                best_ask: Level::from(ask),
+            },
+        )
+    }
+
+    /// Send each event after it's associated millisecond offset, ending the inner stream at the
+    /// provided millisecond offset, then consolidate & collect the emitted (millisecond offset,
+    /// event) pairs.
+    async fn run(
+        inputs: Vec<(u64, MarketEvent<OrderBookL1>)>,
+        end_ms: u64,
+        config: BboConfig,
+    ) -> Vec<(u64, MarketEvent<ConsolidatedBbo>)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, event) in inputs {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+                tx.send(event).unwrap();
+            }
+            tokio::time::sleep_until(start + Duration::from_millis(end_ms)).await;
+        });
+
+        ConsolidateBbo::new(UnboundedReceiverStream::new(rx), config)
+            .map(|event| (start.elapsed().as_millis() as u64, event))
+            .collect()
+            .await
+    }
+
+    fn s(side: &str) -> String {
+        side.to_string()
+    }
+
+    /// Summarise a consolidated BBO as (bid "exchange@price", ask "exchange@price").
+    fn sides(bbo: &ConsolidatedBbo) -> (String, String) {
+        let side = |side: &Option<SourcedLevel>| match side {
+            Some(side) => format!("{}@{}", side.exchange, side.level.price),
+            None => "none".to_string(),
+        };
+        (side(&bbo.best_bid), side(&bbo.best_ask))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_consolidate_bbo() {
+        let inputs = vec![
+            (
+                0,
+                l1(
+                    0,
+                    "binance_spot",
+                    "btc",
+                    (num!(100), num!(1)),
+                    (num!(102), num!(1)),
+                ),
+            ),
+            (
+                10,
+                l1(10, "okx", "btc", (num!(101), num!(1)), (num!(103), num!(1))),
+            ),
+            // Worse quote on both sides does not change the consolidated BBO
+            (
+                20,
+                l1(
+                    20,
+                    "coinbase",
+                    "btc",
+                    (num!(99), num!(1)),
+                    (num!(104), num!(1)),
+                ),
+            ),
+            (
+                30,
+                l1(
+                    30,
+                    "coinbase",
+                    "btc",
+                    (num!(99), num!(1)),
+                    (num!(101.5), num!(1)),
+                ),
+            ),
+            // Other instruments are consolidated independently
+            (
+                40,
+                l1(40, "okx", "eth", (num!(10), num!(1)), (num!(11), num!(1))),
+            ),
+            // Okx stays fresh, binance_spot & coinbase go stale
+            (
+                600,
+                l1(
+                    600,
+                    "okx",
+                    "btc",
+                    (num!(101), num!(1)),
+                    (num!(103), num!(1)),
+                ),
+            ),
+        ];
+
+        let actual = run(inputs, 2000, BboConfig::new(Duration::from_millis(1000))).await;
+        let actual = actual
+            .iter()
+            .map(|(offset, event)| (*offset, event.instrument.base.as_ref(), sides(&event.kind)))
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            // TC0: first quote sources both sides
+            (0, "btc", (s("binance_spot@100"), s("binance_spot@102"))),
+            // TC1: higher bid from another exchange
+            (10, "btc", (s("okx@101"), s("binance_spot@102"))),
+            // TC2: lower ask from a third exchange
+            (30, "btc", (s("okx@101"), s("coinbase@101.5"))),
+            // TC3: independent instrument
+            (40, "eth", (s("okx@10"), s("okx@11"))),
+            // TC4: binance_spot going stale doesn't change the BBO, coinbase going stale does
+            (1030, "btc", (s("okx@101"), s("okx@103"))),
+            // TC5: okx eth quote goes stale, leaving no BBO
+            (1040, "eth", (s("none"), s("none"))),
+            // TC6: okx btc quote goes stale, leaving no BBO
+            (1600, "btc", (s("none"), s("none"))),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_consolidate_bbo_stale_quote_rejoins() {
+        let inputs = vec![
+            (
+                0,
+                l1(
+                    0,
+                    "binance_spot",
+                    "btc",
+                    (num!(101), num!(1)),
+                    (num!(102), num!(1)),
+                ),
+            ),
+            (
+                10,
+                l1(10, "okx", "btc", (num!(100), num!(1)), (num!(103), num!(1))),
+            ),
+            (
+                500,
+                l1(
+                    500,
+                    "okx",
+                    "btc",
+                    (num!(100), num!(1)),
+                    (num!(103), num!(1)),
+                ),
+            ),
+            // binance_spot went stale at 300ms & rejoins with it's next update
+            (
+                700,
+                l1(
+                    700,
+                    "binance_spot",
+                    "btc",
+                    (num!(101), num!(1)),
+                    (num!(102), num!(1)),
+                ),
+            ),
+        ];
+
+        let actual = run(inputs, 1500, BboConfig::new(Duration::from_millis(300))).await;
+        let actual = actual
+            .iter()
+            .map(|(offset, event)| (*offset, sides(&event.kind)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (0, (s("binance_spot@101"), s("binance_spot@102"))),
+                // TC0: binance_spot goes stale, okx is still fresh
+                (300, (s("okx@100"), s("okx@103"))),
+                // TC1: okx goes stale
+                (310, (s("none"), s("none"))),
+                // TC2: okx rejoins
+                (500, (s("okx@100"), s("okx@103"))),
+                // TC3: binance_spot rejoins, okx going stale at 800ms doesn't change the BBO
+                (700, (s("binance_spot@101"), s("binance_spot@102"))),
+                // TC4: binance_spot goes stale
+                (1000, (s("none"), s("none"))),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_consolidate_bbo_ties_crossed_data_kind() {
+        let trade = MarketEvent {
+            kind: DataKind::Trade(PublicTrade {
+                id: "1".to_string(),
+                price: num!(100),
+                amount: num!(1),
+                side: Side::Buy,
+            }),
+            ..MarketEvent::from(l1(0, "okx", "btc", (num!(0), num!(0)), (num!(0), num!(0))))
+        };
+        let inputs = vec![
+            MarketEvent::from(l1(
+                0,
+                "okx",
+                "btc",
+                (num!(100), num!(1)),
+                (num!(101), num!(1)),
+            )),
+            trade,
+            // Equal price w/ a larger amount wins, equal amount is won by the first exchange by name
+            MarketEvent::from(l1(
+                1,
+                "binance_spot",
+                "btc",
+                (num!(100), num!(2)),
+                (num!(101), num!(1)),
+            )),
+            // Crossed exchanges
+            MarketEvent::from(l1(
+                2,
+                "coinbase",
+                "btc",
+                (num!(102), num!(1)),
+                (num!(103), num!(1)),
+            )),
+        ];
+
+        let actual = futures::stream::iter(inputs)
+            .consolidate_bbo(BboConfig::new(Duration::from_secs(60)))
+            .collect::<Vec<_>>()
+            .await;
+
+        // Trade is dropped
+        assert_eq!(actual.len(), 3);
+        assert_eq!(
+            sides(&actual[1].kind),
+            (s("binance_spot@100"), s("binance_spot@101"))
+        );
+        assert!(!actual[1].kind.is_crossed());
+        assert_eq!(actual[1].kind.spread(), Some(num!(1)));
+
+        assert_eq!(
+            sides(&actual[2].kind),
+            (s("coinbase@102"), s("binance_spot@101"))
+        );
+        assert!(actual[2].kind.is_crossed());
+        assert_eq!(actual[2].kind.spread(), Some(num!(-1)));
+        assert_eq!(actual[2].exchange, Exchange::from(CONSOLIDATED_EXCHANGE));
+        assert_eq!(actual[2].exchange_time, TradeFixture::new().time(2));
+    }
+}
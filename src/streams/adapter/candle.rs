@@ -0,0 +1,838 @@
+use super::AsTrade;
+use crate::{
+    event::MarketEvent,
+    num::{self, Num},
+    subscription::candle::{Candle, Interval},
+};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::time::{Instant, Sleep};
+use tracing::debug;
+
+/// Clock a [`CandlesFromTrades`] adapter uses to determine when a [`Candle`] closes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum CandleClock {
+    /// A [`Candle`] closes when a trade with an exchange time in a later [`Interval`] is
+    /// received, so the latest [`Candle`] of an instrument stays open until it trades again.
+    #[default]
+    Exchange,
+    /// A [`Candle`] closes when the wall clock reaches the end of it's [`Interval`], whether or
+    /// not the instrument trades again.
+    ///
+    /// The wall clock is the local clock shifted by the
+    /// [`CandleConfig::exchange_time_offset`], if any, so [`Interval`] boundaries align to the
+    /// exchange clock.
+    Wall,
+}
+
+/// Configuration of a [`CandlesFromTrades`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CandleConfig {
+    pub interval: Interval,
+    pub clock: CandleClock,
+    /// Emit a carry-forward [`Candle`] for each [`Interval`] without trades, with the open, high,
+    /// low & close set to the previous close, and zero volume. Only [`Interval`]s after the
+    /// first trade of an instrument are filled.
+    pub fill_gaps: bool,
+    /// Offset of the exchange clock from the local clock (ie/ exchange time minus local time),
+    /// used by the [`CandleClock::Wall`] to close [`Interval`]s on the exchange clock, so
+    /// locally built candles match the exchange candles.
+    ///
+    /// If `None` the local clock is assumed to be in sync with the exchange. Any skew then
+    /// shifts when each [`Interval`] closes: closing too early drops the late trades of the
+    /// [`Interval`], and closing too late delays the [`Candle`]. The [`CandleClock::Exchange`]
+    /// buckets & closes [`Interval`]s by the exchange time of each trade, so never needs it.
+    pub exchange_time_offset: Option<chrono::Duration>,
+}
+
+impl CandleConfig {
+    /// Construct a new [`Self`] using the provided [`Interval`], closing candles on the
+    /// [`CandleClock::Exchange`] without filling gaps.
+    pub fn new(interval: Interval) -> Self {
+        Self {
+            interval,
+            clock: CandleClock::default(),
+            fill_gaps: false,
+            exchange_time_offset: None,
+        }
+    }
+
+    /// Set the [`CandleClock`] used to close candles.
+    pub fn with_clock(mut self, clock: CandleClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set whether carry-forward candles are emitted for [`Interval`]s without trades.
+    pub fn with_fill_gaps(mut self, fill_gaps: bool) -> Self {
+        self.fill_gaps = fill_gaps;
+        self
+    }
+
+    /// Set the offset of the exchange clock from the local clock (ie/ exchange time minus local
+    /// time), eg/ measured from the server time of the exchange REST API.
+    pub fn with_exchange_time_offset(mut self, offset: chrono::Duration) -> Self {
+        self.exchange_time_offset = Some(offset);
+        self
+    }
+}
+
+/// Determine the open time of the [`Interval`] containing the provided time, aligned to UTC
+/// interval boundaries since the Unix epoch. Weekly intervals are aligned to Monday 00:00 UTC.
+fn open_time(interval: Interval, time: DateTime<Utc>) -> DateTime<Utc> {
+    // The Unix epoch is a Thursday, so weeks are offset by 4 days to open on a Monday
+    let offset = match interval {
+        Interval::W1 => chrono::Duration::days(4).num_milliseconds(),
+        _ => 0,
+    };
+    let width = interval.duration().num_milliseconds();
+    let open = (time.timestamp_millis() - offset).div_euclid(width) * width + offset;
+
+    DateTime::from_timestamp_millis(open).unwrap_or(time)
+}
+
+/// Trades accumulated into the open [`Candle`] of an instrument.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct OpenCandle {
+    open: Num,
+    high: Num,
+    low: Num,
+    close: Num,
+    volume: Num,
+    trade_count: u64,
+    first_trade_time: DateTime<Utc>,
+    last_trade_time: DateTime<Utc>,
+}
+
+impl OpenCandle {
+    fn new(time: DateTime<Utc>, price: Num, amount: Num) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: amount,
+            trade_count: 1,
+            first_trade_time: time,
+            last_trade_time: time,
+        }
+    }
+
+    /// Accumulate a trade, using the exchange time to determine the open & close so trades
+    /// received out-of-order within the [`Interval`] are handled.
+    fn update(&mut self, time: DateTime<Utc>, price: Num, amount: Num) {
+        if time < self.first_trade_time {
+            self.open = price;
+            self.first_trade_time = time;
+        }
+        if time >= self.last_trade_time {
+            self.close = price;
+            self.last_trade_time = time;
+        }
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.volume += amount;
+        self.trade_count += 1;
+    }
+}
+
+/// [`Candle`] aggregation state of an instrument.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct CandleState {
+    /// Open time of the current [`Interval`].
+    open_time: DateTime<Utc>,
+    /// Trades of the current [`Interval`], if any have been received.
+    candle: Option<OpenCandle>,
+    /// Close of the latest [`Candle`] with trades, used to carry-forward empty candles.
+    last_close: Option<Num>,
+}
+
+impl CandleState {
+    fn new(open_time: DateTime<Utc>) -> Self {
+        Self {
+            open_time,
+            candle: None,
+            last_close: None,
+        }
+    }
+
+    /// Close every [`Interval`] opening before the provided open time, returning the closed
+    /// candles (including carry-forward candles if the [`CandleConfig`] fills gaps).
+    fn roll(&mut self, config: &CandleConfig, open_time: DateTime<Utc>) -> Vec<Candle> {
+        let width = config.interval.duration();
+        let mut closed = Vec::new();
+
+        while self.open_time < open_time {
+            let close_time = self.open_time + width - chrono::Duration::milliseconds(1);
+
+            match self.candle.take() {
+                Some(candle) => {
+                    self.last_close = Some(candle.close);
+                    closed.push(Candle {
+                        close_time,
+                        open: candle.open,
+                        high: candle.high,
+                        low: candle.low,
+                        close: candle.close,
+                        volume: candle.volume,
+                        trade_count: candle.trade_count,
+                        historical: false,
+                    });
+                }
+                None => match (config.fill_gaps, self.last_close) {
+                    (true, Some(close)) => closed.push(Candle {
+                        close_time,
+                        open: close,
+                        high: close,
+                        low: close,
+                        close,
+                        volume: num::zero(),
+                        trade_count: 0,
+                        historical: false,
+                    }),
+                    // Nothing to emit for the remaining empty intervals, so skip straight to
+                    // the provided open time
+                    _ => {
+                        self.open_time = open_time;
+                        break;
+                    }
+                },
+            }
+
+            self.open_time += width;
+        }
+
+        closed
+    }
+
+    fn update(&mut self, time: DateTime<Utc>, price: Num, amount: Num) {
+        match &mut self.candle {
+            Some(candle) => candle.update(time, price, amount),
+            None => self.candle = Some(OpenCandle::new(time, price, amount)),
+        }
+    }
+}
+
+/// Wall clock driving a [`CandleClock::Wall`], derived from the tokio clock so it can be paused
+/// in tests.
+#[derive(Debug)]
+struct WallClock {
+    start_time: DateTime<Utc>,
+    start: Instant,
+    /// Offset of the exchange clock from the local clock, see
+    /// [`CandleConfig::exchange_time_offset`].
+    offset: chrono::Duration,
+    timer: Pin<Box<Sleep>>,
+}
+
+impl WallClock {
+    fn new(start_time: DateTime<Utc>, offset: chrono::Duration, interval: Interval) -> Self {
+        let mut clock = Self {
+            start_time,
+            start: Instant::now(),
+            offset,
+            timer: Box::pin(tokio::time::sleep_until(Instant::now())),
+        };
+        clock.reset(interval);
+        clock
+    }
+
+    /// Current local time.
+    fn now(&self) -> DateTime<Utc> {
+        self.start_time
+            + chrono::Duration::from_std(self.start.elapsed())
+                .unwrap_or_else(|_| chrono::Duration::zero())
+    }
+
+    /// Current exchange time, ie/ the local time shifted by the exchange clock offset.
+    fn exchange_now(&self) -> DateTime<Utc> {
+        self.now() + self.offset
+    }
+
+    /// Reset the timer to fire at the end of the current [`Interval`] on the exchange clock.
+    fn reset(&mut self, interval: Interval) {
+        let next = open_time(interval, self.exchange_now()) + interval.duration() - self.offset;
+        let until_next = (next - self.start_time).to_std().unwrap_or_default();
+        self.timer.as_mut().reset(self.start + until_next);
+    }
+}
+
+/// Stream adapter that aggregates the trades of each ([`Exchange`], [`Instrument`]) into
+/// normalised [`Candle`]s of the configured [`Interval`], for exchanges without a candle
+/// channel (eg/ Bitfinex).
+///
+/// Trades are bucketed by their exchange time into [`Interval`]s aligned to UTC boundaries, and
+/// a [`Candle`] is yielded once it's [`Interval`] closes according to the [`CandleClock`]. The
+/// [`Candle`] `close_time` is the last millisecond of the [`Interval`] (eg/ 00:00:59.999 for the
+/// 00:00 one minute candle), matching exchange candles.
+///
+/// ### Notes
+/// - Trades received out-of-order within the open [`Interval`] are accumulated by exchange time,
+///   but trades for an already closed [`Interval`] are dropped.
+/// - A [`CandleClock::Wall`] closes [`Interval`]s on the local clock unless it is given the
+///   [`CandleConfig::exchange_time_offset`], so local clock skew misaligns it's boundaries with
+///   the exchange candles.
+/// - The open [`Candle`] of each instrument is not yielded when the inner stream ends, since it's
+///   incomplete.
+/// - Events that do not contain a [`PublicTrade`](crate::subscription::trade::PublicTrade) (eg/
+///   [`DataKind`](crate::event::DataKind) order books) are dropped.
+#[derive(Debug)]
+pub struct CandlesFromTrades<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: CandleConfig,
+    candles: HashMap<(Exchange, Instrument), CandleState>,
+    clock: Option<WallClock>,
+    pending: VecDeque<MarketEvent<Candle>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for CandlesFromTrades<St, T> where St: Unpin {}
+
+impl<St, T> CandlesFromTrades<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    /// Construct a new [`Self`] that aggregates the trades of the provided stream into
+    /// [`Candle`]s.
+    pub fn new(stream: St, config: CandleConfig) -> Self {
+        Self::new_at(stream, config, Utc::now())
+    }
+
+    /// Construct a new [`Self`] with a [`CandleClock::Wall`] starting at the provided time.
+    fn new_at(stream: St, config: CandleConfig, now: DateTime<Utc>) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            candles: HashMap::new(),
+            clock: match config.clock {
+                CandleClock::Exchange => None,
+                CandleClock::Wall => Some(WallClock::new(
+                    now,
+                    config
+                        .exchange_time_offset
+                        .unwrap_or_else(chrono::Duration::zero),
+                    config.interval,
+                )),
+            },
+            pending: VecDeque::new(),
+            stream_ended: false,
+        }
+    }
+
+    /// Accumulate the trade of the provided [`MarketEvent<T>`](MarketEvent) into it's instrument
+    /// [`Candle`], closing the preceding [`Interval`]s if it opens a new one.
+    fn process(&mut self, event: MarketEvent<T>) {
+        let Some(trade) = event.kind.as_trade() else {
+            return;
+        };
+
+        let open_time = open_time(self.config.interval, event.exchange_time);
+        let key = (event.exchange, event.instrument);
+        let state = self
+            .candles
+            .entry(key.clone())
+            .or_insert_with(|| CandleState::new(open_time));
+
+        if open_time < state.open_time {
+            debug!(
+                exchange = %key.0,
+                instrument = %key.1,
+                exchange_time = %event.exchange_time,
+                "dropping trade for an already closed Candle interval"
+            );
+            return;
+        }
+
+        let closed = state.roll(&self.config, open_time);
+        state.update(event.exchange_time, trade.price, trade.amount);
+
+        let (exchange, instrument) = key;
+        self.pending
+            .extend(closed.into_iter().map(|candle| MarketEvent {
+                exchange_time: candle.close_time,
+                raw_exchange_time: None,
+                received_time: event.received_time,
+                exchange: exchange.clone(),
+                instrument: instrument.clone(),
+                kind: candle,
+                meta: (),
+            }));
+    }
+
+    /// Close the [`Interval`] of every instrument that the [`CandleClock::Wall`] has passed.
+    fn tick(&mut self) {
+        let Some(clock) = &mut self.clock else {
+            return;
+        };
+
+        let now = clock.now();
+        let open_time = open_time(self.config.interval, clock.exchange_now());
+        clock.reset(self.config.interval);
+
+        for ((exchange, instrument), state) in self.candles.iter_mut() {
+            let closed = state.roll(&self.config, open_time);
+            self.pending
+                .extend(closed.into_iter().map(|candle| MarketEvent {
+                    exchange_time: candle.close_time,
+                    raw_exchange_time: None,
+                    received_time: now,
+                    exchange: exchange.clone(),
+                    instrument: instrument.clone(),
+                    kind: candle,
+                    meta: (),
+                }));
+        }
+    }
+}
+
+impl<St, T> Stream for CandlesFromTrades<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<Candle>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(candle) = this.pending.pop_front() {
+                return Poll::Ready(Some(candle));
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => {
+                    this.process(event);
+                    continue;
+                }
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            let ticked = match &mut this.clock {
+                Some(clock) => clock.timer.as_mut().poll(cx).is_ready(),
+                None => false,
+            };
+
+            match ticked {
+                true => this.tick(),
+                false => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        num,
+        streams::adapter::MarketEventStreamExt,
+        subscription::{book::OrderBookL1, trade::PublicTrade},
+        test_util::trade::{run_adapter, TradeFixture},
+    };
+    use barter_integration::model::Side;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    /// Unix timestamp in milliseconds aligned to a one minute boundary.
+    const BASE_MS: i64 = 1_700_000_040_000;
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new()
+            .with_base_ms(BASE_MS)
+            .with_market("bitfinex", "usd")
+    }
+
+    fn time(offset_ms: i64) -> DateTime<Utc> {
+        fixture().time(offset_ms)
+    }
+
+    fn trade(offset_ms: i64, base: &str, price: Num, amount: Num) -> MarketEvent<PublicTrade> {
+        fixture().trade(offset_ms, base, price, amount, Side::Buy)
+    }
+
+    fn candle(close_offset_ms: i64, ohlc: [Num; 4], volume: Num, trade_count: u64) -> Candle {
+        Candle {
+            close_time: time(close_offset_ms),
+            open: ohlc[0],
+            high: ohlc[1],
+            low: ohlc[2],
+            close: ohlc[3],
+            volume,
+            trade_count,
+            historical: false,
+        }
+    }
+
+    async fn run(inputs: Vec<MarketEvent<PublicTrade>>, config: CandleConfig) -> Vec<Candle> {
+        run_adapter(inputs, |trades| {
+            trades.candles_from_trades(config).map(|event| event.kind)
+        })
+        .await
+    }
+
+    #[test]
+    fn test_open_time() {
+        struct TestCase {
+            interval: Interval,
+            input: &'static str,
+            expected: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: one minute interval
+                interval: Interval::M1,
+                input: "2023-11-14T22:14:59.999Z",
+                expected: "2023-11-14T22:14:00Z",
+            },
+            TestCase {
+                // TC1: boundary is the open of the next interval
+                interval: Interval::M5,
+                input: "2023-11-14T22:15:00Z",
+                expected: "2023-11-14T22:15:00Z",
+            },
+            TestCase {
+                // TC2: four hour interval
+                interval: Interval::H4,
+                input: "2023-11-14T22:14:00Z",
+                expected: "2023-11-14T20:00:00Z",
+            },
+            TestCase {
+                // TC3: daily interval
+                interval: Interval::D1,
+                input: "2023-11-14T22:14:00Z",
+                expected: "2023-11-14T00:00:00Z",
+            },
+            TestCase {
+                // TC4: weekly interval opens on Monday
+                interval: Interval::W1,
+                input: "2023-11-19T23:59:59Z",
+                expected: "2023-11-13T00:00:00Z",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let input = test.input.parse::<DateTime<Utc>>().unwrap();
+            let expected = test.expected.parse::<DateTime<Utc>>().unwrap();
+            assert_eq!(
+                open_time(test.interval, input),
+                expected,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_candles_from_trades_exchange_clock() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0)),
+                trade(20_000, "btc", num!(110.0), num!(2.0)),
+                trade(50_000, "btc", num!(90.0), num!(1.0)),
+                // Closes the first candle
+                trade(61_000, "btc", num!(95.0), num!(1.0)),
+                // Closes the second candle, skipping the empty third interval
+                trade(185_000, "btc", num!(105.0), num!(1.0)),
+                // Open candle is not emitted at stream end
+                trade(190_000, "btc", num!(106.0), num!(1.0)),
+            ],
+            CandleConfig::new(Interval::M1),
+        )
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                candle(
+                    59_999,
+                    [num!(100.0), num!(110.0), num!(90.0), num!(90.0)],
+                    num!(4.0),
+                    3
+                ),
+                candle(
+                    119_999,
+                    [num!(95.0), num!(95.0), num!(95.0), num!(95.0)],
+                    num!(1.0),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_candles_from_trades_out_of_order() {
+        let actual = run(
+            vec![
+                trade(30_000, "btc", num!(100.0), num!(1.0)),
+                // Earlier trade within the open interval becomes the open
+                trade(10_000, "btc", num!(90.0), num!(1.0)),
+                // Later trade received before an earlier one remains the close
+                trade(50_000, "btc", num!(120.0), num!(1.0)),
+                trade(40_000, "btc", num!(110.0), num!(1.0)),
+                trade(60_000, "btc", num!(130.0), num!(1.0)),
+                // Trade for the closed interval is dropped
+                trade(59_000, "btc", num!(1.0), num!(1.0)),
+                trade(120_000, "btc", num!(140.0), num!(1.0)),
+            ],
+            CandleConfig::new(Interval::M1),
+        )
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                candle(
+                    59_999,
+                    [num!(90.0), num!(120.0), num!(90.0), num!(120.0)],
+                    num!(4.0),
+                    4
+                ),
+                candle(
+                    119_999,
+                    [num!(130.0), num!(130.0), num!(130.0), num!(130.0)],
+                    num!(1.0),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_candles_from_trades_fill_gaps() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0)),
+                trade(180_000, "btc", num!(110.0), num!(1.0)),
+                trade(240_000, "btc", num!(120.0), num!(1.0)),
+            ],
+            CandleConfig::new(Interval::M1).with_fill_gaps(true),
+        )
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                candle(
+                    59_999,
+                    [num!(100.0), num!(100.0), num!(100.0), num!(100.0)],
+                    num!(1.0),
+                    1
+                ),
+                // Empty intervals carry forward the previous close
+                candle(
+                    119_999,
+                    [num!(100.0), num!(100.0), num!(100.0), num!(100.0)],
+                    num!(0.0),
+                    0
+                ),
+                candle(
+                    179_999,
+                    [num!(100.0), num!(100.0), num!(100.0), num!(100.0)],
+                    num!(0.0),
+                    0
+                ),
+                candle(
+                    239_999,
+                    [num!(110.0), num!(110.0), num!(110.0), num!(110.0)],
+                    num!(1.0),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_candles_from_trades_per_instrument() {
+        let inputs = vec![
+            MarketEvent::from(trade(0, "btc", num!(100.0), num!(1.0))),
+            MarketEvent::from(trade(1_000, "eth", num!(10.0), num!(1.0))),
+            MarketEvent {
+                kind: DataKind::OrderBookL1(OrderBookL1 {
+                    last_update_time: time(2_000),
+                    best_bid: Default::default(),
+                    best_ask: Default::default(),
+                }),
+                ..MarketEvent::from(trade(2_000, "btc", num!(0.0), num!(0.0)))
+            },
+            MarketEvent::from(trade(60_000, "btc", num!(200.0), num!(1.0))),
+            MarketEvent::from(trade(61_000, "eth", num!(20.0), num!(3.0))),
+        ];
+
+        let actual = futures::stream::iter(inputs)
+            .candles_from_trades(CandleConfig::new(Interval::M1))
+            .map(|event| (event.instrument.base.to_string(), event.kind))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "btc".to_string(),
+                    candle(
+                        59_999,
+                        [num!(100.0), num!(100.0), num!(100.0), num!(100.0)],
+                        num!(1.0),
+                        1
+                    )
+                ),
+                (
+                    "eth".to_string(),
+                    candle(
+                        59_999,
+                        [num!(10.0), num!(10.0), num!(10.0), num!(10.0)],
+                        num!(1.0),
+                        1
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_candles_from_trades_wall_clock() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, price) in [
+                (1_000, num!(100.0)),
+                (30_000, num!(110.0)),
+                (130_000, num!(120.0)),
+            ] {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms as u64)).await;
+                tx.send(trade(offset_ms, "btc", price, num!(1.0))).unwrap();
+            }
+            // Keep the stream open so candles are only closed by the wall clock
+            std::future::pending::<()>().await;
+        });
+
+        let config = CandleConfig::new(Interval::M1)
+            .with_clock(CandleClock::Wall)
+            .with_fill_gaps(true);
+
+        let actual = CandlesFromTrades::new_at(UnboundedReceiverStream::new(rx), config, time(0))
+            .map(|event| (start.elapsed().as_millis() as i64, event.kind))
+            .take(3)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: candle closes at the interval boundary without a subsequent trade
+                (
+                    60_000,
+                    candle(
+                        59_999,
+                        [num!(100.0), num!(110.0), num!(100.0), num!(110.0)],
+                        num!(2.0),
+                        2
+                    )
+                ),
+                // TC1: empty interval is carried forward at it's boundary
+                (
+                    120_000,
+                    candle(
+                        119_999,
+                        [num!(110.0), num!(110.0), num!(110.0), num!(110.0)],
+                        num!(0.0),
+                        0
+                    )
+                ),
+                // TC2: next candle closes at it's boundary
+                (
+                    180_000,
+                    candle(
+                        179_999,
+                        [num!(120.0), num!(120.0), num!(120.0), num!(120.0)],
+                        num!(1.0),
+                        1
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_candles_from_trades_wall_clock_exchange_time_offset() {
+        // Exchange clock is 15s ahead of the local clock
+        const OFFSET_MS: i64 = 15_000;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (local_ms, price) in [
+                (1_000, num!(100.0)),
+                (40_000, num!(110.0)),
+                (50_000, num!(120.0)),
+            ] {
+                tokio::time::sleep_until(start + Duration::from_millis(local_ms as u64)).await;
+                tx.send(trade(local_ms + OFFSET_MS, "btc", price, num!(1.0)))
+                    .unwrap();
+            }
+            std::future::pending::<()>().await;
+        });
+
+        let config = CandleConfig::new(Interval::M1)
+            .with_clock(CandleClock::Wall)
+            .with_exchange_time_offset(chrono::Duration::milliseconds(OFFSET_MS));
+
+        let actual = CandlesFromTrades::new_at(UnboundedReceiverStream::new(rx), config, time(0))
+            .map(|event| (start.elapsed().as_millis() as i64, event.kind))
+            .take(2)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: candle closes at the exchange interval boundary, 15s early on the local
+                // clock, so the trade at exchange time 01:05 opens the next candle
+                (
+                    45_000,
+                    candle(
+                        59_999,
+                        [num!(100.0), num!(110.0), num!(100.0), num!(110.0)],
+                        num!(2.0),
+                        2
+                    )
+                ),
+                // TC1: next candle closes at the following exchange interval boundary
+                (
+                    105_000,
+                    candle(
+                        119_999,
+                        [num!(120.0), num!(120.0), num!(120.0), num!(120.0)],
+                        num!(1.0),
+                        1
+                    )
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use super::AsLiquidation;
+use crate::{
+    event::MarketEvent,
+    num::{self, Num},
+    subscription::liquidation::Liquidation,
+};
+use barter_integration::model::{Exchange, Instrument, Side};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Configuration of a [`ClusterLiquidations`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ClusterConfig {
+    /// [`Duration`] after the first [`Liquidation`] of a cluster during which subsequent
+    /// [`Liquidation`]s of the same instrument are aggregated into it.
+    pub window: Duration,
+    /// If true, every raw [`Liquidation`] is also emitted as soon as it's received.
+    pub emit_raw: bool,
+}
+
+impl ClusterConfig {
+    /// Construct a new [`Self`] using the provided cluster window.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            emit_raw: false,
+        }
+    }
+
+    /// Set whether raw [`Liquidation`]s are emitted alongside the clusters.
+    pub fn with_emit_raw(mut self, emit_raw: bool) -> Self {
+        self.emit_raw = emit_raw;
+        self
+    }
+}
+
+/// Normalised Barter burst of [`Liquidation`]s of an instrument, aggregated by a
+/// [`ClusterLiquidations`] adapter.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct LiquidationCluster {
+    /// [`Side`] with the greatest liquidated notional, with ties retaining the previously
+    /// dominant [`Side`] (initially that of the first [`Liquidation`]).
+    pub side: Side,
+    /// Total notional (price * quantity) of the [`Side::Buy`] [`Liquidation`]s.
+    pub buy_notional: Num,
+    /// Total notional (price * quantity) of the [`Side::Sell`] [`Liquidation`]s.
+    pub sell_notional: Num,
+    /// Total quantity of every [`Liquidation`].
+    pub quantity: Num,
+    /// Number of aggregated [`Liquidation`]s.
+    pub count: usize,
+    /// Time of the first [`Liquidation`].
+    pub start_time: DateTime<Utc>,
+    /// Time of the last [`Liquidation`].
+    pub end_time: DateTime<Utc>,
+}
+
+impl LiquidationCluster {
+    /// Construct a new [`Self`] from the first [`Liquidation`] of the cluster.
+    fn new(liquidation: &Liquidation) -> Self {
+        let mut cluster = Self {
+            side: liquidation.side,
+            buy_notional: num::zero(),
+            sell_notional: num::zero(),
+            quantity: num::zero(),
+            count: 0,
+            start_time: liquidation.time,
+            end_time: liquidation.time,
+        };
+        cluster.aggregate(liquidation);
+        cluster
+    }
+
+    /// Aggregate the provided [`Liquidation`] into [`Self`].
+    fn aggregate(&mut self, liquidation: &Liquidation) {
+        let notional = liquidation.price * liquidation.quantity;
+        match liquidation.side {
+            Side::Buy => self.buy_notional += notional,
+            Side::Sell => self.sell_notional += notional,
+        }
+        self.quantity += liquidation.quantity;
+        self.count += 1;
+        self.start_time = self.start_time.min(liquidation.time);
+        self.end_time = self.end_time.max(liquidation.time);
+
+        if self.buy_notional > self.sell_notional {
+            self.side = Side::Buy;
+        } else if self.sell_notional > self.buy_notional {
+            self.side = Side::Sell;
+        }
+    }
+
+    /// Total notional of every [`Liquidation`].
+    pub fn notional(&self) -> Num {
+        self.buy_notional + self.sell_notional
+    }
+}
+
+/// [`MarketEvent<T>`](MarketEvent) kind yielded by a [`ClusterLiquidations`] adapter.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusteredLiquidation {
+    /// Raw [`Liquidation`], only yielded if [`ClusterConfig::emit_raw`] is enabled.
+    Raw(Liquidation),
+    /// [`LiquidationCluster`] yielded once it's window has elapsed.
+    Cluster(LiquidationCluster),
+}
+
+/// [`LiquidationCluster`] that is still within it's window.
+#[derive(Debug)]
+struct PendingCluster {
+    deadline: Instant,
+    exchange_time: DateTime<Utc>,
+    received_time: DateTime<Utc>,
+    cluster: LiquidationCluster,
+}
+
+/// Stream adapter that aggregates the [`Liquidation`]s of each ([`Exchange`], [`Instrument`])
+/// received within the [`ClusterConfig`] window of the first one into a single
+/// [`LiquidationCluster`], turning a burst of small liquidation prints into one signal (eg/ for
+/// liquidation cascade detection).
+///
+/// A cluster is yielded once it's window has elapsed, or when the inner stream ends. It's
+/// [`MarketEvent`] carries the exchange & received times of the last aggregated
+/// [`Liquidation`].
+///
+/// Events that do not contain a [`Liquidation`] (eg/ [`DataKind`](crate::event::DataKind) trades)
+/// are dropped.
+///
+/// Uses the `tokio` clock, so a paused runtime (eg/ in tests) deterministically controls the
+/// emission schedule.
+#[derive(Debug)]
+pub struct ClusterLiquidations<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: ClusterConfig,
+    pending: HashMap<(Exchange, Instrument), PendingCluster>,
+    ready: VecDeque<MarketEvent<ClusteredLiquidation>>,
+    timer: Pin<Box<Sleep>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for ClusterLiquidations<St, T> where St: Unpin {}
+
+impl<St, T> ClusterLiquidations<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsLiquidation,
+{
+    /// Construct a new [`Self`] that clusters the [`Liquidation`]s of the provided stream.
+    pub fn new(stream: St, config: ClusterConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            pending: HashMap::new(),
+            ready: VecDeque::new(),
+            timer: Box::pin(tokio::time::sleep(Duration::ZERO)),
+            stream_ended: false,
+        }
+    }
+
+    /// Aggregate the [`Liquidation`] of the provided [`MarketEvent<T>`](MarketEvent) into it's
+    /// pending cluster, returning the raw event if [`ClusterConfig::emit_raw`] is enabled.
+    fn process(
+        &mut self,
+        event: MarketEvent<T>,
+        now: Instant,
+    ) -> Option<MarketEvent<ClusteredLiquidation>> {
+        let liquidation = *event.kind.as_liquidation()?;
+        let key = (event.exchange.clone(), event.instrument.clone());
+
+        match self.pending.get_mut(&key) {
+            Some(pending) => {
+                pending.cluster.aggregate(&liquidation);
+                pending.exchange_time = event.exchange_time;
+                pending.received_time = event.received_time;
+            }
+            None => {
+                self.pending.insert(
+                    key,
+                    PendingCluster {
+                        deadline: now + self.config.window,
+                        exchange_time: event.exchange_time,
+                        received_time: event.received_time,
+                        cluster: LiquidationCluster::new(&liquidation),
+                    },
+                );
+            }
+        }
+
+        self.config.emit_raw.then_some(MarketEvent {
+            exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            received_time: event.received_time,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: ClusteredLiquidation::Raw(liquidation),
+            meta: event.meta,
+        })
+    }
+
+    /// Move every pending cluster whose window has elapsed (or every pending cluster if `flush`
+    /// is true) to the ready queue, in window order.
+    fn release(&mut self, now: Instant, flush: bool) {
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| flush || now >= pending.deadline)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        let mut released = expired
+            .into_iter()
+            .filter_map(|key| self.pending.remove_entry(&key))
+            .collect::<Vec<_>>();
+        released.sort_by_key(|(_, pending)| pending.deadline);
+
+        self.ready.extend(
+            released
+                .into_iter()
+                .map(|((exchange, instrument), pending)| MarketEvent {
+                    exchange_time: pending.exchange_time,
+                    raw_exchange_time: None,
+                    received_time: pending.received_time,
+                    exchange,
+                    instrument,
+                    kind: ClusteredLiquidation::Cluster(pending.cluster),
+                    meta: (),
+                }),
+        );
+    }
+
+    /// Determine the earliest [`Instant`] a pending cluster window elapses.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|pending| pending.deadline).min()
+    }
+}
+
+impl<St, T> Stream for ClusterLiquidations<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsLiquidation,
+{
+    type Item = MarketEvent<ClusteredLiquidation>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Emit events that are ready before consuming more from the inner stream
+            if let Some(event) = this.ready.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+
+            this.release(Instant::now(), false);
+            if !this.ready.is_empty() {
+                continue;
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event, Instant::now()) {
+                    Some(event) => return Poll::Ready(Some(event)),
+                    None => continue,
+                },
+                Poll::Ready(None) => {
+                    // Flush pending clusters so nothing aggregated is lost at stream end
+                    this.stream_ended = true;
+                    this.release(Instant::now(), true);
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            // Wake up when the next pending cluster window elapses
+            match this.next_deadline() {
+                Some(deadline) => {
+                    this.timer.as_mut().reset(deadline);
+                    if this.timer.as_mut().poll(cx).is_ready() {
+                        continue;
+                    }
+                    return Poll::Pending;
+                }
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind, num, streams::adapter::MarketEventStreamExt,
+        subscription::trade::PublicTrade, test_util::trade::TradeFixture,
+    };
+    use barter_integration::model::InstrumentKind;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn liquidation(
+        offset_ms: i64,
+        base: &str,
+        side: Side,
+        price: Num,
+        quantity: Num,
+    ) -> MarketEvent<Liquidation> {
+        let fixture = TradeFixture::new()
+            .with_market("binance_futures_usd", "usdt")
+            .with_instrument_kind(InstrumentKind::FuturePerpetual);
+        fixture.event(
+            offset_ms,
+            base,
+            Liquidation {
+                side,
+                price,
+                quantity,
+                time: fixture.time(offset_ms),
+            },
+        )
+    }
+
+    /// Send each event after it's associated millisecond offset, then cluster & collect the
+    /// emitted (millisecond offset, event) pairs.
+    async fn run(
+        inputs: Vec<(u64, MarketEvent<Liquidation>)>,
+        config: ClusterConfig,
+    ) -> Vec<(u64, MarketEvent<ClusteredLiquidation>)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, event) in inputs {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+                tx.send(event).unwrap();
+            }
+        });
+
+        ClusterLiquidations::new(UnboundedReceiverStream::new(rx), config)
+            .map(|event| (start.elapsed().as_millis() as u64, event))
+            .collect()
+            .await
+    }
+
+    fn cluster(event: &MarketEvent<ClusteredLiquidation>) -> (&str, LiquidationCluster) {
+        match event.kind {
+            ClusteredLiquidation::Cluster(cluster) => (event.instrument.base.as_ref(), cluster),
+            ClusteredLiquidation::Raw(_) => panic!("expected Cluster, got: {event:?}"),
+        }
+    }
+
+    fn burst() -> Vec<(u64, MarketEvent<Liquidation>)> {
+        vec![
+            (0, liquidation(0, "btc", Side::Sell, num!(100.0), num!(1.0))),
+            (
+                50,
+                liquidation(50, "btc", Side::Buy, num!(100.0), num!(0.5)),
+            ),
+            (
+                100,
+                liquidation(100, "eth", Side::Buy, num!(10.0), num!(2.0)),
+            ),
+            (
+                150,
+                liquidation(150, "btc", Side::Sell, num!(99.0), num!(2.0)),
+            ),
+            (
+                200,
+                liquidation(200, "btc", Side::Sell, num!(98.0), num!(1.0)),
+            ),
+            (
+                300,
+                liquidation(300, "btc", Side::Buy, num!(98.0), num!(0.5)),
+            ),
+            (
+                1500,
+                liquidation(1500, "btc", Side::Buy, num!(105.0), num!(1.0)),
+            ),
+        ]
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cluster_liquidations_burst() {
+        let actual = run(burst(), ClusterConfig::new(Duration::from_millis(1000))).await;
+        assert_eq!(actual.len(), 3);
+
+        // TC0: btc burst is aggregated once it's window elapses, dominated by Sell liquidations
+        let (offset, event) = &actual[0];
+        let (base, btc) = cluster(event);
+        assert_eq!((*offset, base), (1000, "btc"));
+        assert_eq!(btc.side, Side::Sell);
+        assert_eq!(btc.count, 5);
+        assert_eq!(btc.buy_notional, num!(99.0));
+        assert_eq!(btc.sell_notional, num!(396.0));
+        assert_eq!(btc.notional(), num!(495.0));
+        assert_eq!(btc.quantity, num!(5.0));
+        assert_eq!(btc.start_time, burst()[0].1.kind.time);
+        assert_eq!(btc.end_time, burst()[5].1.kind.time);
+        assert_eq!(event.exchange_time, btc.end_time);
+
+        // TC1: eth cluster has it's own window
+        let (offset, event) = &actual[1];
+        let (base, eth) = cluster(event);
+        assert_eq!((*offset, base), (1100, "eth"));
+        assert_eq!(
+            (eth.side, eth.count, eth.notional()),
+            (Side::Buy, 1, num!(20.0))
+        );
+
+        // TC2: btc liquidation after the window starts a new cluster, flushed at stream end
+        let (offset, event) = &actual[2];
+        let (base, btc) = cluster(event);
+        assert_eq!((*offset, base), (1500, "btc"));
+        assert_eq!(
+            (btc.side, btc.count, btc.notional()),
+            (Side::Buy, 1, num!(105.0))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cluster_liquidations_emit_raw() {
+        let config = ClusterConfig::new(Duration::from_millis(1000)).with_emit_raw(true);
+        let actual = run(burst(), config)
+            .await
+            .into_iter()
+            .map(|(offset, event)| match event.kind {
+                ClusteredLiquidation::Raw(_) => (offset, "raw"),
+                ClusteredLiquidation::Cluster(_) => (offset, "cluster"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (0, "raw"),
+                (50, "raw"),
+                (100, "raw"),
+                (150, "raw"),
+                (200, "raw"),
+                (300, "raw"),
+                (1000, "cluster"),
+                (1100, "cluster"),
+                (1500, "raw"),
+                (1500, "cluster"),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cluster_liquidations_data_kind() {
+        let trade = MarketEvent {
+            kind: DataKind::Trade(PublicTrade {
+                id: "1".to_string(),
+                price: num!(100.0),
+                amount: num!(1.0),
+                side: Side::Buy,
+            }),
+            ..MarketEvent::from(liquidation(0, "btc", Side::Buy, num!(100.0), num!(1.0)))
+        };
+        let inputs = vec![
+            MarketEvent::from(liquidation(0, "btc", Side::Sell, num!(100.0), num!(1.0))),
+            trade,
+            MarketEvent::from(liquidation(10, "btc", Side::Sell, num!(100.0), num!(1.0))),
+        ];
+
+        let config = ClusterConfig::new(Duration::from_millis(1000)).with_emit_raw(true);
+        let actual = futures::stream::iter(inputs)
+            .cluster_liquidations(config)
+            .collect::<Vec<_>>()
+            .await;
+
+        // Trade is dropped, leaving two raw liquidations & their cluster
+        assert_eq!(actual.len(), 3);
+        let (_, btc) = cluster(&actual[2]);
+        assert_eq!(
+            (btc.side, btc.count, btc.notional()),
+            (Side::Sell, 2, num!(200.0))
+        );
+    }
+
+    #[test]
+    fn test_liquidation_cluster_side_tie() {
+        let first = liquidation(0, "btc", Side::Buy, num!(100.0), num!(1.0)).kind;
+        let mut cluster = LiquidationCluster::new(&first);
+        cluster.aggregate(&liquidation(1, "btc", Side::Sell, num!(50.0), num!(2.0)).kind);
+
+        assert_eq!(cluster.buy_notional, cluster.sell_notional);
+        assert_eq!(cluster.side, Side::Buy);
+    }
+}
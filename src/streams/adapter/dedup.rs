@@ -0,0 +1,280 @@
+use super::AsTrade;
+use crate::event::MarketEvent;
+use barter_integration::model::{Exchange, Instrument};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Default number of trade ids remembered by a [`Dedup`] adapter.
+pub const DEFAULT_DEDUP_CAPACITY: usize = 10_000;
+
+/// Configuration of the bounded window of trade ids remembered by a [`Dedup`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DedupConfig {
+    /// Maximum number of trade ids remembered, evicting the oldest first.
+    pub capacity: usize,
+    /// Maximum age of a remembered trade id, if any.
+    pub max_age: Option<Duration>,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_DEDUP_CAPACITY,
+            max_age: None,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Construct a new [`Self`] using the provided configuration.
+    pub fn new(capacity: usize, max_age: Option<Duration>) -> Self {
+        Self { capacity, max_age }
+    }
+}
+
+/// Unique key of a trade seen by a [`Dedup`] adapter.
+type TradeKey = (Exchange, Instrument, String);
+
+/// Stream adapter that drops [`MarketEvent<T>`](MarketEvent)s containing a
+/// [`PublicTrade`](crate::subscription::trade::PublicTrade) that has already been seen, based on
+/// the (exchange, instrument, trade id) combination.
+///
+/// Seen trade ids are remembered in a bounded window (see [`DedupConfig`]), so memory usage is
+/// constant. Trade ids are compared for equality only, so exchanges with non-monotonic or
+/// string trade ids are supported. Events without a trade pass through untouched.
+#[derive(Debug)]
+pub struct Dedup<St> {
+    stream: St,
+    config: DedupConfig,
+    seen: HashSet<TradeKey>,
+    window: VecDeque<(TradeKey, Instant)>,
+}
+
+impl<St> Dedup<St> {
+    /// Construct a new [`Self`] that de-duplicates trades from the provided stream.
+    pub fn new(stream: St, config: DedupConfig) -> Self {
+        Self {
+            stream,
+            config,
+            seen: HashSet::with_capacity(config.capacity),
+            window: VecDeque::with_capacity(config.capacity),
+        }
+    }
+
+    /// Determine if the provided trade key has already been seen, remembering it if not.
+    fn is_duplicate(&mut self, key: TradeKey, now: Instant) -> bool {
+        // Forget trade ids that are older than the max_age
+        if let Some(max_age) = self.config.max_age {
+            while self
+                .window
+                .front()
+                .is_some_and(|(_, seen_at)| now.duration_since(*seen_at) > max_age)
+            {
+                if let Some((oldest, _)) = self.window.pop_front() {
+                    self.seen.remove(&oldest);
+                }
+            }
+        }
+
+        if self.seen.contains(&key) {
+            return true;
+        }
+
+        // Remember trade id, evicting the oldest if the window is full
+        self.seen.insert(key.clone());
+        self.window.push_back((key, now));
+        while self.window.len() > self.config.capacity {
+            if let Some((oldest, _)) = self.window.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        false
+    }
+}
+
+impl<St, T> Stream for Dedup<St>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let event = match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => event,
+                other => return other,
+            };
+
+            let Some(trade) = event.kind.as_trade() else {
+                return Poll::Ready(Some(event));
+            };
+
+            let key = (
+                event.exchange.clone(),
+                event.instrument.clone(),
+                trade.id.clone(),
+            );
+
+            if !this.is_duplicate(key, Instant::now()) {
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::DataKind;
+    use crate::num;
+    use crate::subscription::{liquidation::Liquidation, trade::PublicTrade};
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn trade(exchange: &'static str, id: &str) -> MarketEvent<DataKind> {
+        TradeFixture::new().with_market(exchange, "usdt").event(
+            0,
+            "btc",
+            DataKind::Trade(PublicTrade {
+                id: id.to_string(),
+                price: num!(1.0),
+                amount: num!(1.0),
+                side: Side::Buy,
+            }),
+        )
+    }
+
+    fn liquidation() -> MarketEvent<DataKind> {
+        let fixture = TradeFixture::new();
+        fixture.event(
+            0,
+            "btc",
+            DataKind::Liquidation(Liquidation {
+                side: Side::Sell,
+                price: num!(1.0),
+                quantity: num!(1.0),
+                time: fixture.time(0),
+            }),
+        )
+    }
+
+    fn id(event: &MarketEvent<DataKind>) -> String {
+        match &event.kind {
+            DataKind::Trade(trade) => format!("{}:{}", event.exchange, trade.id),
+            _ => "liquidation".to_string(),
+        }
+    }
+
+    async fn run(inputs: Vec<MarketEvent<DataKind>>, config: DedupConfig) -> Vec<String> {
+        Dedup::new(futures::stream::iter(inputs), config)
+            .map(|event| id(&event))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_dedup_reconnect_overlap() {
+        // Old & new connections overlap during a reconnection, both yielding trades "2" & "3"
+        let inputs = vec![
+            trade("binance_spot", "1"),
+            trade("binance_spot", "2"),
+            trade("binance_spot", "2"),
+            trade("binance_spot", "3"),
+            trade("binance_spot", "3"),
+            trade("binance_spot", "4"),
+            trade("okx", "4"),
+            liquidation(),
+            liquidation(),
+        ];
+
+        let actual = run(inputs, DedupConfig::default()).await;
+
+        assert_eq!(
+            actual,
+            vec![
+                "binance_spot:1",
+                "binance_spot:2",
+                "binance_spot:3",
+                "binance_spot:4",
+                "okx:4",
+                "liquidation",
+                "liquidation",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dedup_non_monotonic_string_ids() {
+        let inputs = vec![
+            trade("coinbase", "b-7"),
+            trade("coinbase", "a-9"),
+            trade("coinbase", "b-7"),
+            trade("coinbase", "c-1"),
+            trade("coinbase", "a-9"),
+        ];
+
+        let actual = run(inputs, DedupConfig::default()).await;
+
+        assert_eq!(actual, vec!["coinbase:b-7", "coinbase:a-9", "coinbase:c-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_capacity_eviction() {
+        let inputs = vec![
+            trade("binance_spot", "1"),
+            trade("binance_spot", "2"),
+            trade("binance_spot", "3"),
+            // "1" was evicted by "3" so is no longer considered a duplicate
+            trade("binance_spot", "1"),
+            // "3" is still remembered
+            trade("binance_spot", "3"),
+        ];
+
+        let actual = run(inputs, DedupConfig::new(2, None)).await;
+
+        assert_eq!(
+            actual,
+            vec![
+                "binance_spot:1",
+                "binance_spot:2",
+                "binance_spot:3",
+                "binance_spot:1",
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_dedup_age_eviction() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut dedup = Dedup::new(
+            UnboundedReceiverStream::new(rx),
+            DedupConfig::new(100, Some(Duration::from_secs(1))),
+        );
+
+        tx.send(trade("binance_spot", "1")).unwrap();
+        assert_eq!(id(&dedup.next().await.unwrap()), "binance_spot:1");
+
+        // Duplicate within max_age is dropped
+        tokio::time::advance(Duration::from_millis(500)).await;
+        tx.send(trade("binance_spot", "1")).unwrap();
+        tx.send(trade("binance_spot", "2")).unwrap();
+        assert_eq!(id(&dedup.next().await.unwrap()), "binance_spot:2");
+
+        // Duplicate after max_age has been forgotten
+        tokio::time::advance(Duration::from_millis(600)).await;
+        tx.send(trade("binance_spot", "1")).unwrap();
+        assert_eq!(id(&dedup.next().await.unwrap()), "binance_spot:1");
+    }
+}
@@ -0,0 +1,375 @@
+use super::{
+    window::{RollingWindow, TradeWindow, WindowTrade},
+    AsTrade,
+};
+use crate::{
+    event::MarketEvent,
+    num::{self, Num},
+};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Default maximum number of trades retained per instrument by an [`OrderFlowImbalance`] window.
+pub const DEFAULT_IMBALANCE_MAX_TRADES: usize = 100_000;
+
+/// Configuration of an [`OrderFlowImbalance`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ImbalanceConfig {
+    pub window: TradeWindow,
+    /// Maximum number of trades retained per instrument, evicting the oldest trades beyond it.
+    /// Bounds the memory of an [`TradeWindow::Time`] during bursts of trades.
+    pub max_trades: usize,
+}
+
+impl ImbalanceConfig {
+    /// Construct a new [`Self`] using an [`TradeWindow::Time`] of the provided [`Duration`].
+    pub fn time(window: Duration) -> Self {
+        Self {
+            window: TradeWindow::Time(window),
+            max_trades: DEFAULT_IMBALANCE_MAX_TRADES,
+        }
+    }
+
+    /// Construct a new [`Self`] using an [`TradeWindow::Trades`] of the provided number of
+    /// trades.
+    pub fn trades(count: usize) -> Self {
+        Self {
+            window: TradeWindow::Trades(count),
+            max_trades: DEFAULT_IMBALANCE_MAX_TRADES,
+        }
+    }
+
+    /// Set the maximum number of trades retained per instrument.
+    pub fn with_max_trades(mut self, max_trades: usize) -> Self {
+        self.max_trades = max_trades;
+        self
+    }
+}
+
+/// Normalised Barter order flow imbalance of the trades of an instrument within an
+/// [`TradeWindow`], yielded by an [`OrderFlowImbalance`] adapter.
+///
+/// Buy & sell volume is attributed using the aggressor
+/// [`Side`](barter_integration::model::Side) of each
+/// [`PublicTrade`](crate::subscription::trade::PublicTrade), so a positive imbalance means takers
+/// lifted offers more than they hit bids.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Imbalance {
+    /// Net aggressive volume, ie/ buy_volume - sell_volume.
+    pub imbalance: Num,
+    /// Total base asset amount of the trades within the window, ie/ buy_volume + sell_volume.
+    pub volume: Num,
+    /// Total base asset amount of the [`Side::Buy`](barter_integration::model::Side::Buy)
+    /// aggressor trades within the window.
+    pub buy_volume: Num,
+    /// Total base asset amount of the [`Side::Sell`](barter_integration::model::Side::Sell)
+    /// aggressor trades within the window.
+    pub sell_volume: Num,
+    /// Number of trades within the window.
+    pub trades: usize,
+    /// Exchange time of the oldest trade within the window.
+    pub start_time: DateTime<Utc>,
+    /// Exchange time of the latest trade within the window.
+    pub end_time: DateTime<Utc>,
+}
+
+impl Imbalance {
+    /// Imbalance normalised by volume into [-1, 1], or `None` without volume.
+    pub fn ratio(&self) -> Option<Num> {
+        (self.volume > num::zero()).then(|| self.imbalance / self.volume)
+    }
+}
+
+/// Determine the [`Imbalance`] of the [`RollingWindow`], or `None` if it contains no trades.
+fn imbalance(window: &RollingWindow) -> Option<Imbalance> {
+    let sums = window.sums();
+    Some(Imbalance {
+        imbalance: sums.buy_volume - sums.sell_volume,
+        volume: sums.buy_volume + sums.sell_volume,
+        buy_volume: sums.buy_volume,
+        sell_volume: sums.sell_volume,
+        trades: window.len(),
+        start_time: window.start_time()?,
+        end_time: window.latest_time(),
+    })
+}
+
+/// Stream adapter that computes the rolling order flow [`Imbalance`] (net aggressive buy vs sell
+/// volume) of the trades of each ([`Exchange`], [`Instrument`]) independently, over the
+/// configured [`TradeWindow`] of trade exchange times or trade counts.
+///
+/// An [`Imbalance`] is yielded on each trade, carrying the exchange & received times of the
+/// trade. As with a [`RollingVwap`](super::vwap::RollingVwap), the window is only advanced by
+/// trades, so the last [`Imbalance`] stands during a gap in trading.
+///
+/// Events that do not contain a [`PublicTrade`](crate::subscription::trade::PublicTrade) (eg/
+/// [`DataKind`](crate::event::DataKind) order books) are dropped.
+#[derive(Debug)]
+pub struct OrderFlowImbalance<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: ImbalanceConfig,
+    windows: HashMap<(Exchange, Instrument), RollingWindow>,
+}
+
+impl<St, T> Unpin for OrderFlowImbalance<St, T> where St: Unpin {}
+
+impl<St, T> OrderFlowImbalance<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    /// Construct a new [`Self`] that computes the rolling order flow [`Imbalance`] of the
+    /// provided stream.
+    pub fn new(stream: St, config: ImbalanceConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Add the trade of the provided [`MarketEvent<T>`](MarketEvent) to it's instrument window,
+    /// returning the updated [`Imbalance`].
+    fn process(&mut self, event: MarketEvent<T>) -> Option<MarketEvent<Imbalance>> {
+        let trade = event.kind.as_trade()?;
+        let window = self
+            .windows
+            .entry((event.exchange.clone(), event.instrument.clone()))
+            .or_insert_with(|| {
+                RollingWindow::new(
+                    self.config.window,
+                    self.config.max_trades,
+                    event.exchange_time,
+                )
+            });
+
+        window.update(WindowTrade {
+            time: event.exchange_time,
+            amount: trade.amount,
+            notional: trade.price * trade.amount,
+            side: trade.side,
+        });
+
+        Some(MarketEvent {
+            exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            received_time: event.received_time,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: imbalance(window)?,
+            meta: event.meta,
+        })
+    }
+}
+
+impl<St, T> Stream for OrderFlowImbalance<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<Imbalance>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event) {
+                    Some(event) => return Poll::Ready(Some(event)),
+                    None => continue,
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        num,
+        streams::adapter::MarketEventStreamExt,
+        subscription::{book::OrderBookL1, trade::PublicTrade},
+        test_util::trade::{run_adapter, TradeFixture},
+    };
+    use barter_integration::model::Side;
+
+    fn trade(offset_ms: i64, base: &str, side: Side, amount: Num) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().trade(offset_ms, base, num!(100.0), amount, side)
+    }
+
+    async fn run(
+        inputs: Vec<MarketEvent<PublicTrade>>,
+        config: ImbalanceConfig,
+    ) -> Vec<(String, Imbalance)> {
+        run_adapter(inputs, |trades| {
+            trades
+                .order_flow_imbalance(config)
+                .map(|event| (event.instrument.base.to_string(), event.kind))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_imbalance_time_window() {
+        let actual = run(
+            vec![
+                trade(0, "btc", Side::Buy, num!(2.0)),
+                trade(500, "btc", Side::Sell, num!(3.0)),
+                trade(1000, "btc", Side::Buy, num!(4.0)),
+                trade(1200, "btc", Side::Sell, num!(0.5)),
+                trade(5000, "btc", Side::Sell, num!(1.0)),
+            ],
+            ImbalanceConfig::time(Duration::from_millis(1000)),
+        )
+        .await;
+
+        let actual = actual
+            .iter()
+            .map(|(_, imbalance)| (imbalance.imbalance, imbalance.volume, imbalance.trades))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: buy aggressor is positive
+                (num!(2.0), num!(2.0), 1),
+                // TC1: sell aggressor is negative
+                (num!(-1.0), num!(5.0), 2),
+                // TC2: first trade ages out of the (0ms, 1000ms] window
+                (num!(1.0), num!(7.0), 2),
+                // TC3: within window
+                (num!(0.5), num!(7.5), 3),
+                // TC4: trade after a gap starts a fresh window
+                (num!(-1.0), num!(1.0), 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_imbalance_trade_window() {
+        let actual = run(
+            vec![
+                trade(0, "btc", Side::Sell, num!(5.0)),
+                trade(1, "btc", Side::Buy, num!(1.0)),
+                trade(2, "btc", Side::Buy, num!(2.0)),
+                trade(3, "btc", Side::Sell, num!(1.0)),
+            ],
+            ImbalanceConfig::trades(3),
+        )
+        .await;
+
+        let (_, last) = actual.last().unwrap();
+        assert_eq!(actual.len(), 4);
+        assert_eq!(
+            (
+                last.imbalance,
+                last.volume,
+                last.buy_volume,
+                last.sell_volume
+            ),
+            (num!(2.0), num!(4.0), num!(3.0), num!(1.0))
+        );
+        assert_eq!(last.ratio(), Some(num!(0.5)));
+        assert_eq!(last.trades, 3);
+        assert_eq!(
+            last.start_time,
+            trade(1, "btc", Side::Buy, num!(0.0)).exchange_time
+        );
+        assert_eq!(
+            last.end_time,
+            trade(3, "btc", Side::Buy, num!(0.0)).exchange_time
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_imbalance_per_instrument() {
+        let actual = run(
+            vec![
+                trade(0, "btc", Side::Buy, num!(1.0)),
+                trade(1, "eth", Side::Sell, num!(2.0)),
+                trade(2, "btc", Side::Buy, num!(1.0)),
+                trade(3, "eth", Side::Buy, num!(3.0)),
+            ],
+            ImbalanceConfig::trades(10),
+        )
+        .await;
+
+        let actual = actual
+            .into_iter()
+            .map(|(base, imbalance)| (base, imbalance.imbalance))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("btc".to_string(), num!(1.0)),
+                ("eth".to_string(), num!(-2.0)),
+                ("btc".to_string(), num!(2.0)),
+                ("eth".to_string(), num!(1.0)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_imbalance_max_trades() {
+        let actual = run(
+            (0..10)
+                .map(|index| trade(index, "btc", Side::Buy, num!(index)))
+                .collect(),
+            ImbalanceConfig::time(Duration::from_secs(60)).with_max_trades(3),
+        )
+        .await;
+
+        let (_, last) = actual.last().unwrap();
+        assert_eq!((last.imbalance, last.trades), (num!(24.0), 3));
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_imbalance_data_kind() {
+        let l1 = MarketEvent {
+            kind: DataKind::OrderBookL1(OrderBookL1 {
+                last_update_time: Utc::now(),
+                best_bid: Default::default(),
+                best_ask: Default::default(),
+            }),
+            ..MarketEvent::from(trade(0, "btc", Side::Buy, num!(1.0)))
+        };
+        let inputs = vec![
+            MarketEvent::from(trade(0, "btc", Side::Buy, num!(1.0))),
+            l1,
+            // Trade without volume leaves the Imbalance unchanged
+            MarketEvent::from(trade(1, "btc", Side::Sell, num!(0.0))),
+            MarketEvent::from(trade(2, "btc", Side::Sell, num!(3.0))),
+        ];
+
+        let actual = futures::stream::iter(inputs)
+            .order_flow_imbalance(ImbalanceConfig::trades(10))
+            .map(|event| (event.kind.imbalance, event.kind.ratio()))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                (num!(1.0), Some(num!(1.0))),
+                (num!(1.0), Some(num!(1.0))),
+                (num!(-2.0), Some(num!(-0.5)))
+            ]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use self::{
+    batch::{Batch, BatchConfig},
+    bbo::{BboConfig, ConsolidateBbo},
+    candle::{CandleConfig, CandlesFromTrades},
+    cluster::{ClusterConfig, ClusterLiquidations},
+    dedup::{Dedup, DedupConfig},
+    imbalance::{Imbalance, ImbalanceConfig, OrderFlowImbalance},
+    monotonic::{Monotonic, MonotonicConfig},
+    snapshot::{BookSnapshots, SnapshotConfig},
+    stats::{RollingStats, StatsConfig, TradeStats},
+    throttle::{Throttle, ThrottleConfig},
+    vwap::{RollingVwap, Vwap, VwapConfig},
+};
+use crate::{
+    event::{DataKind, MarketEvent},
+    subscription::{
+        book::{OrderBook, OrderBookL1},
+        candle::Candle,
+        liquidation::Liquidation,
+        trade::PublicTrade,
+    },
+};
+use futures::Stream;
+
+/// [`Batch`] adapter that yields buffered [`MarketEvent<T>`](MarketEvent)s as a `Vec` once a
+/// size threshold or flush interval is reached.
+pub mod batch;
+
+/// [`ConsolidateBbo`] adapter that consolidates the [`OrderBookL1`]s of multiple exchanges into
+/// a cross-exchange best bid & offer.
+pub mod bbo;
+
+/// [`CandlesFromTrades`] adapter that aggregates the trades of each instrument into [`Candle`]s
+/// of a configured [`Interval`](crate::subscription::candle::Interval).
+pub mod candle;
+
+/// [`ClusterLiquidations`] adapter that aggregates bursts of [`Liquidation`]s per instrument into
+/// a single [`LiquidationCluster`](cluster::LiquidationCluster).
+pub mod cluster;
+
+/// [`Dedup`] adapter that drops trades which have already been seen.
+pub mod dedup;
+
+/// [`OrderFlowImbalance`] adapter that computes the rolling net aggressive buy vs sell volume of
+/// the trades of each instrument over a time or trade count window.
+pub mod imbalance;
+
+/// [`Monotonic`] adapter that enforces a non-decreasing `exchange_time` per instrument, by
+/// clamping, dropping or warning of backwards timestamps.
+pub mod monotonic;
+
+/// [`BookSnapshots`] adapter that yields a full snapshot of every managed [`OrderBook`] at a
+/// fixed interval, regardless of whether it has been updated.
+pub mod snapshot;
+
+/// [`RollingStats`] adapter that computes rolling [`TradeStats`] (VWAP, volume, buy / sell
+/// imbalance & trade count) of the trades of each instrument over a time window.
+pub mod stats;
+
+/// [`Throttle`] adapter that emits at most one [`MarketEvent<T>`](MarketEvent) per instrument
+/// per configured interval.
+pub mod throttle;
+
+/// [`RollingVwap`] adapter that computes the rolling volume weighted average price of the trades
+/// of each instrument over a time or trade count window.
+pub mod vwap;
+
+/// [`RollingWindow`](window::RollingWindow) of trades over a time or trade count window, with
+/// running volume sums, shared by the rolling trade adapters.
+pub mod window;
+
+/// Extension trait providing ergonomic constructors for the stream adapters defined in this
+/// module, available on any `Stream` of [`MarketEvent<T>`](MarketEvent)s.
+///
+/// Note that an exchange [`mpsc::UnboundedReceiver`](tokio::sync::mpsc::UnboundedReceiver) from
+/// [`Streams`](super::Streams) can be turned into a `Stream` via
+/// [`UnboundedReceiverStream`](tokio_stream::wrappers::UnboundedReceiverStream).
+pub trait MarketEventStreamExt<T>
+where
+    Self: Stream<Item = MarketEvent<T>> + Sized,
+{
+    /// Throttle this stream using the provided [`ThrottleConfig`]. See [`Throttle`].
+    fn throttle(self, config: ThrottleConfig) -> Throttle<Self, T>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        Throttle::new(self, config)
+    }
+
+    /// Drop already seen trades from this stream using the provided [`DedupConfig`].
+    /// See [`Dedup`].
+    fn dedup(self, config: DedupConfig) -> Dedup<Self>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        Dedup::new(self, config)
+    }
+
+    /// Enforce a non-decreasing `exchange_time` per instrument in this stream using the provided
+    /// [`MonotonicConfig`]. See [`Monotonic`].
+    ///
+    /// Note that the default [`MonotonicPolicy::Clamp`](monotonic::MonotonicPolicy::Clamp)
+    /// alters the `exchange_time` of backwards events.
+    fn monotonic(self, config: MonotonicConfig) -> Monotonic<Self>
+    where
+        Self: Unpin,
+    {
+        Monotonic::new(self, config)
+    }
+
+    /// Buffer this stream into batches using the provided [`BatchConfig`]. See [`Batch`].
+    fn batch(self, config: BatchConfig) -> Batch<Self, T>
+    where
+        Self: Unpin,
+    {
+        Batch::new(self, config)
+    }
+
+    /// Aggregate the [`Liquidation`]s of this stream into clusters using the provided
+    /// [`ClusterConfig`]. See [`ClusterLiquidations`].
+    fn cluster_liquidations(self, config: ClusterConfig) -> ClusterLiquidations<Self, T>
+    where
+        Self: Unpin,
+        T: AsLiquidation,
+    {
+        ClusterLiquidations::new(self, config)
+    }
+
+    /// Consolidate the [`OrderBookL1`]s of every exchange in this stream into a cross-exchange
+    /// best bid & offer per instrument using the provided [`BboConfig`]. See [`ConsolidateBbo`].
+    fn consolidate_bbo(self, config: BboConfig) -> ConsolidateBbo<Self, T>
+    where
+        Self: Unpin,
+        T: AsOrderBookL1,
+    {
+        ConsolidateBbo::new(self, config)
+    }
+
+    /// Compute the rolling [`Vwap`] of the trades of each instrument in this stream
+    /// using the provided [`VwapConfig`]. See [`RollingVwap`].
+    fn vwap(self, config: VwapConfig) -> RollingVwap<Self, T>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        RollingVwap::new(self, config)
+    }
+
+    /// Compute the rolling order flow [`Imbalance`] of the trades of each instrument in this
+    /// stream using the provided [`ImbalanceConfig`]. See [`OrderFlowImbalance`].
+    fn order_flow_imbalance(self, config: ImbalanceConfig) -> OrderFlowImbalance<Self, T>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        OrderFlowImbalance::new(self, config)
+    }
+
+    /// Aggregate the trades of each instrument in this stream into [`Candle`]s using the
+    /// provided [`CandleConfig`]. See [`CandlesFromTrades`].
+    fn candles_from_trades(self, config: CandleConfig) -> CandlesFromTrades<Self, T>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        CandlesFromTrades::new(self, config)
+    }
+
+    /// Compute the rolling [`TradeStats`] of the trades of each instrument in this stream using
+    /// the provided [`StatsConfig`]. See [`RollingStats`].
+    fn rolling_stats(self, config: StatsConfig) -> RollingStats<Self, T>
+    where
+        Self: Unpin,
+        T: AsTrade,
+    {
+        RollingStats::new(self, config)
+    }
+
+    /// Yield periodic snapshots of the [`OrderBook`]s of each instrument in this stream using the
+    /// provided [`SnapshotConfig`]. See [`BookSnapshots`].
+    fn book_snapshots(self, config: SnapshotConfig) -> BookSnapshots<Self, T>
+    where
+        Self: Unpin,
+        T: AsOrderBook,
+    {
+        BookSnapshots::new(self, config)
+    }
+}
+
+impl<St, T> MarketEventStreamExt<T> for St where St: Stream<Item = MarketEvent<T>> {}
+
+/// Provides access to the [`PublicTrade`] contained in a [`MarketEvent<T>`](MarketEvent) kind,
+/// if there is one.
+///
+/// Used by adapters that treat trades differently to other market data (eg/ never throttling
+/// trades while throttling order books).
+pub trait AsTrade {
+    fn as_trade(&self) -> Option<&PublicTrade>;
+}
+
+impl AsTrade for PublicTrade {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        Some(self)
+    }
+}
+
+impl AsTrade for DataKind {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        match self {
+            DataKind::Trade(trade) => Some(trade),
+            _ => None,
+        }
+    }
+}
+
+impl AsTrade for OrderBookL1 {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for OrderBook {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for Candle {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for Liquidation {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for Vwap {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for Imbalance {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+impl AsTrade for TradeStats {
+    fn as_trade(&self) -> Option<&PublicTrade> {
+        None
+    }
+}
+
+/// Provides access to the [`Liquidation`] contained in a [`MarketEvent<T>`](MarketEvent) kind,
+/// if there is one.
+///
+/// Used by adapters that only operate on liquidations (eg/ [`ClusterLiquidations`]).
+pub trait AsLiquidation {
+    fn as_liquidation(&self) -> Option<&Liquidation>;
+}
+
+impl AsLiquidation for Liquidation {
+    fn as_liquidation(&self) -> Option<&Liquidation> {
+        Some(self)
+    }
+}
+
+impl AsLiquidation for DataKind {
+    fn as_liquidation(&self) -> Option<&Liquidation> {
+        match self {
+            DataKind::Liquidation(liquidation) => Some(liquidation),
+            _ => None,
+        }
+    }
+}
+
+/// Provides access to the [`OrderBookL1`] contained in a [`MarketEvent<T>`](MarketEvent) kind,
+/// if there is one.
+///
+/// Used by adapters that only operate on L1 quotes (eg/ [`ConsolidateBbo`]).
+pub trait AsOrderBookL1 {
+    fn as_order_book_l1(&self) -> Option<&OrderBookL1>;
+}
+
+impl AsOrderBookL1 for OrderBookL1 {
+    fn as_order_book_l1(&self) -> Option<&OrderBookL1> {
+        Some(self)
+    }
+}
+
+impl AsOrderBookL1 for DataKind {
+    fn as_order_book_l1(&self) -> Option<&OrderBookL1> {
+        match self {
+            DataKind::OrderBookL1(l1) => Some(l1),
+            _ => None,
+        }
+    }
+}
+
+/// Provides access to the [`OrderBook`] contained in a [`MarketEvent<T>`](MarketEvent) kind,
+/// if there is one.
+///
+/// Used by adapters that only operate on managed OrderBooks (eg/ [`BookSnapshots`]).
+pub trait AsOrderBook {
+    fn as_order_book(&self) -> Option<&OrderBook>;
+}
+
+impl AsOrderBook for OrderBook {
+    fn as_order_book(&self) -> Option<&OrderBook> {
+        Some(self)
+    }
+}
+
+impl AsOrderBook for DataKind {
+    fn as_order_book(&self) -> Option<&OrderBook> {
+        match self {
+            DataKind::OrderBook(book) => Some(book),
+            _ => None,
+        }
+    }
+}
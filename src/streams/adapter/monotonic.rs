@@ -0,0 +1,227 @@
+use crate::event::MarketEvent;
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::warn;
+
+/// Action taken by a [`Monotonic`] adapter when a [`MarketEvent<T>`](MarketEvent) has an
+/// `exchange_time` earlier than the latest seen for it's instrument.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum MonotonicPolicy {
+    /// Overwrite the backwards `exchange_time` with the latest seen for the instrument.
+    ///
+    /// Note that this alters the event timestamp, although the `raw_exchange_time` is left as
+    /// the exchange provided it.
+    #[default]
+    Clamp,
+    /// Drop the event entirely.
+    Drop,
+    /// Log a warning and yield the event untouched.
+    Warn,
+}
+
+/// Configuration of the [`MonotonicPolicy`] used by a [`Monotonic`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct MonotonicConfig {
+    pub policy: MonotonicPolicy,
+}
+
+impl MonotonicConfig {
+    /// Construct a new [`Self`] using the provided [`MonotonicPolicy`].
+    pub fn new(policy: MonotonicPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+/// Stream adapter that enforces a non-decreasing `exchange_time` for the
+/// [`MarketEvent<T>`](MarketEvent)s of each (exchange, instrument), so downstream time-series
+/// consumers (eg/ bar builders) never observe a negative time delta.
+///
+/// Events with an `exchange_time` earlier than the latest seen for their instrument are handled
+/// according to the configured [`MonotonicPolicy`]. Equal timestamps are never considered
+/// backwards.
+#[derive(Debug)]
+pub struct Monotonic<St> {
+    stream: St,
+    config: MonotonicConfig,
+    latest: HashMap<(Exchange, Instrument), DateTime<Utc>>,
+}
+
+impl<St> Monotonic<St> {
+    /// Construct a new [`Self`] that enforces monotonic timestamps on the provided stream.
+    pub fn new(stream: St, config: MonotonicConfig) -> Self {
+        Self {
+            stream,
+            config,
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Apply the configured [`MonotonicPolicy`] to the provided event, returning `None` if it
+    /// should be dropped.
+    fn enforce<T>(&mut self, mut event: MarketEvent<T>) -> Option<MarketEvent<T>> {
+        let latest = self
+            .latest
+            .entry((event.exchange.clone(), event.instrument.clone()))
+            .or_insert(event.exchange_time);
+
+        if event.exchange_time >= *latest {
+            *latest = event.exchange_time;
+            return Some(event);
+        }
+
+        match self.config.policy {
+            MonotonicPolicy::Clamp => {
+                event.exchange_time = *latest;
+                Some(event)
+            }
+            MonotonicPolicy::Drop => None,
+            MonotonicPolicy::Warn => {
+                warn!(
+                    exchange = %event.exchange,
+                    instrument = %event.instrument,
+                    exchange_time = %event.exchange_time,
+                    latest_exchange_time = %latest,
+                    "received MarketEvent with a backwards exchange_time"
+                );
+                Some(event)
+            }
+        }
+    }
+}
+
+impl<St, T> Stream for Monotonic<St>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+{
+    type Item = MarketEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let event = match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => event,
+                other => return other,
+            };
+
+            if let Some(event) = this.enforce(event) {
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::DataKind;
+    use crate::num;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+
+    fn event(exchange: &'static str, base: &str, time: i64) -> MarketEvent<DataKind> {
+        let trade = TradeFixture::new()
+            .with_base_ms(0)
+            .with_market(exchange, "usdt")
+            .trade(time, base, num!(1.0), num!(1.0), Side::Buy);
+        MarketEvent::from(trade)
+    }
+
+    fn inputs() -> Vec<MarketEvent<DataKind>> {
+        vec![
+            event("okx", "btc", 10),
+            event("okx", "btc", 12),
+            // Backwards for okx btc
+            event("okx", "btc", 11),
+            // Equal is not backwards
+            event("okx", "btc", 12),
+            // Other instruments & exchanges are tracked independently
+            event("okx", "eth", 5),
+            event("binance_spot", "btc", 1),
+            event("okx", "btc", 13),
+            // Backwards for okx eth
+            event("okx", "eth", 4),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_monotonic() {
+        struct TestCase {
+            policy: MonotonicPolicy,
+            // (trade id, exchange_time millis)
+            expected: Vec<(&'static str, i64)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Clamp overwrites backwards exchange_times with the latest seen
+                policy: MonotonicPolicy::Clamp,
+                expected: vec![
+                    ("10", 10),
+                    ("12", 12),
+                    ("11", 12),
+                    ("12", 12),
+                    ("5", 5),
+                    ("1", 1),
+                    ("13", 13),
+                    ("4", 5),
+                ],
+            },
+            TestCase {
+                // TC1: Drop removes events with backwards exchange_times
+                policy: MonotonicPolicy::Drop,
+                expected: vec![
+                    ("10", 10),
+                    ("12", 12),
+                    ("12", 12),
+                    ("5", 5),
+                    ("1", 1),
+                    ("13", 13),
+                ],
+            },
+            TestCase {
+                // TC2: Warn yields every event untouched
+                policy: MonotonicPolicy::Warn,
+                expected: vec![
+                    ("10", 10),
+                    ("12", 12),
+                    ("11", 11),
+                    ("12", 12),
+                    ("5", 5),
+                    ("1", 1),
+                    ("13", 13),
+                    ("4", 4),
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = Monotonic::new(
+                futures::stream::iter(inputs()),
+                MonotonicConfig::new(test.policy),
+            )
+            .map(|event| {
+                let DataKind::Trade(trade) = &event.kind else {
+                    panic!("TC{index} failed: unexpected kind");
+                };
+                (trade.id.clone(), event.exchange_time.timestamp_millis())
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+            let expected = test
+                .expected
+                .into_iter()
+                .map(|(id, time)| (id.to_string(), time))
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected, "TC{index} failed");
+        }
+    }
+}
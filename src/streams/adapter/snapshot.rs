@@ -0,0 +1,373 @@
+use super::AsOrderBook;
+use crate::{event::MarketEvent, subscription::book::OrderBook};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+
+/// Determines whether a [`BookSnapshots`] adapter also yields the delta driven [`OrderBook`]
+/// updates of the inner stream.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum SnapshotMode {
+    /// Yield only the periodic [`OrderBook`] snapshots, instead of the delta driven updates.
+    #[default]
+    Periodic,
+    /// Yield every delta driven [`OrderBook`] update as it's received, in addition to the
+    /// periodic [`OrderBook`] snapshots.
+    WithUpdates,
+}
+
+/// Configuration of a [`BookSnapshots`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SnapshotConfig {
+    /// [`Duration`] between each periodic snapshot of every managed [`OrderBook`].
+    pub interval: Duration,
+    pub mode: SnapshotMode,
+    /// If true, the periodic snapshot of an [`OrderBook`] that has not changed since it's previous
+    /// periodic snapshot is skipped. Defaults to false, giving heartbeat-like regularity.
+    pub skip_unchanged: bool,
+}
+
+impl SnapshotConfig {
+    /// Construct a new [`Self`] that yields a periodic snapshot of every [`OrderBook`] each
+    /// provided interval, instead of the delta driven updates.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            mode: SnapshotMode::default(),
+            skip_unchanged: false,
+        }
+    }
+
+    /// Set the [`SnapshotMode`].
+    pub fn with_mode(mut self, mode: SnapshotMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set whether periodic snapshots of unchanged [`OrderBook`]s are skipped.
+    pub fn with_skip_unchanged(mut self, skip_unchanged: bool) -> Self {
+        self.skip_unchanged = skip_unchanged;
+        self
+    }
+}
+
+/// Latest managed [`OrderBook`] of an instrument.
+#[derive(Debug)]
+struct ManagedBook {
+    book: OrderBook,
+    /// True if the [`OrderBook`] has changed since it's previous periodic snapshot.
+    changed: bool,
+}
+
+/// Stream adapter that yields a full snapshot of the latest [`OrderBook`] of each
+/// ([`Exchange`], [`Instrument`]) at a fixed interval, regardless of whether it has been updated.
+///
+/// Useful for consumers that poll rather than react, eg/ persisting periodic OrderBook states or
+/// a UI that redraws on a timer. Unlike conflation, an unchanged [`OrderBook`] is still yielded
+/// each interval (unless [`SnapshotConfig::skip_unchanged`]).
+///
+/// The managed [`OrderBook`] state is the latest [`OrderBook`] yielded by the inner stream, eg/
+/// by a [`MultiBookTransformer`](crate::transformer::book::MultiBookTransformer) of an
+/// [`OrderBooksL2`](crate::subscription::book::OrderBooksL2) stream. Periodic snapshots carry the
+/// `last_update_time` of the [`OrderBook`] as their exchange time & the time of the snapshot as
+/// their received time. Every [`OrderBook`] is yielded in ([`Exchange`], [`Instrument`]) order.
+///
+/// Events that do not contain an [`OrderBook`] (eg/ [`DataKind`](crate::event::DataKind) trades)
+/// are dropped.
+///
+/// ### Memory & CPU
+/// Every periodic snapshot clones each full [`OrderBook`], so each interval allocates & copies
+/// every [`Level`](crate::subscription::book::Level) of every instrument, whether or not it
+/// changed. For deep books, many instruments or short intervals this can dominate the cost of
+/// the stream, so prefer the longest interval the consumer tolerates, cap the book depth via
+/// [`StreamBuilder::max_book_levels`](crate::streams::builder::StreamBuilder::max_book_levels),
+/// or skip unchanged
+/// snapshots. Detecting unchanged books compares each update to the previous [`OrderBook`], which
+/// adds a comparison of every [`Level`] per update.
+#[derive(Debug)]
+pub struct BookSnapshots<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: SnapshotConfig,
+    books: BTreeMap<(Exchange, Instrument), ManagedBook>,
+    timer: Pin<Box<Sleep>>,
+    pending: VecDeque<MarketEvent<OrderBook>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for BookSnapshots<St, T> where St: Unpin {}
+
+impl<St, T> BookSnapshots<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsOrderBook,
+{
+    /// Construct a new [`Self`] that periodically snapshots the [`OrderBook`]s of the provided
+    /// stream, starting one interval after construction.
+    pub fn new(stream: St, config: SnapshotConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            books: BTreeMap::new(),
+            timer: Box::pin(tokio::time::sleep(config.interval)),
+            pending: VecDeque::new(),
+            stream_ended: false,
+        }
+    }
+
+    /// Replace the managed [`OrderBook`] of the provided [`MarketEvent<T>`](MarketEvent)
+    /// instrument, returning the update if it's yielded in addition to the periodic snapshots.
+    fn process(&mut self, event: MarketEvent<T>) -> Option<MarketEvent<OrderBook>> {
+        let book = event.kind.as_order_book()?.clone();
+        let key = (event.exchange.clone(), event.instrument.clone());
+
+        match self.books.get_mut(&key) {
+            Some(managed) => {
+                let changed = !self.config.skip_unchanged || managed.book != book;
+                managed.changed |= changed;
+                managed.book = book.clone();
+            }
+            None => {
+                self.books.insert(
+                    key,
+                    ManagedBook {
+                        book: book.clone(),
+                        changed: true,
+                    },
+                );
+            }
+        }
+
+        match self.config.mode {
+            SnapshotMode::Periodic => None,
+            SnapshotMode::WithUpdates => Some(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: book,
+                meta: event.meta,
+            }),
+        }
+    }
+
+    /// Queue a snapshot of every managed [`OrderBook`], skipping unchanged [`OrderBook`]s if
+    /// configured.
+    fn snapshot(&mut self) {
+        let received_time = Utc::now();
+        let skip_unchanged = self.config.skip_unchanged;
+
+        let snapshots = self
+            .books
+            .iter_mut()
+            .filter(|(_, managed)| !skip_unchanged || managed.changed)
+            .map(|((exchange, instrument), managed)| {
+                managed.changed = false;
+                MarketEvent {
+                    exchange_time: managed.book.last_update_time,
+                    raw_exchange_time: None,
+                    received_time,
+                    exchange: exchange.clone(),
+                    instrument: instrument.clone(),
+                    kind: managed.book.clone(),
+                    meta: (),
+                }
+            });
+
+        self.pending.extend(snapshots);
+    }
+}
+
+impl<St, T> Stream for BookSnapshots<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsOrderBook,
+{
+    type Item = MarketEvent<OrderBook>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(snapshot) = this.pending.pop_front() {
+                return Poll::Ready(Some(snapshot));
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event) {
+                    Some(update) => return Poll::Ready(Some(update)),
+                    None => continue,
+                },
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            match this.timer.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    let deadline = this.timer.deadline() + this.config.interval;
+                    this.timer.as_mut().reset(deadline);
+                    this.snapshot();
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        streams::adapter::MarketEventStreamExt,
+        subscription::{
+            book::{Level, OrderBookSide},
+            trade::PublicTrade,
+        },
+        test_util::trade::TradeFixture,
+    };
+    use crate::{num, num::Num};
+    use barter_integration::model::Side;
+    use tokio::{sync::mpsc, time::Instant};
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn book(offset_ms: i64, base: &str, best_bid: Num) -> MarketEvent<OrderBook> {
+        let fixture = TradeFixture::new();
+        fixture.event(
+            offset_ms,
+            base,
+            OrderBook {
+                last_update_time: fixture.time(offset_ms),
+                bids: OrderBookSide::new(Side::Buy, vec![Level::new(best_bid, num!(1.0))]),
+                asks: OrderBookSide::new(
+                    Side::Sell,
+                    vec![Level::new(best_bid + num!(1.0), num!(1.0))],
+                ),
+            },
+        )
+    }
+
+    /// Run the provided timed inputs through a [`BookSnapshots`] until the end time, summarising
+    /// each yielded event as (elapsed ms, base, best bid price).
+    async fn run<T>(
+        inputs: Vec<(u64, MarketEvent<T>)>,
+        end_ms: u64,
+        config: SnapshotConfig,
+    ) -> Vec<(u64, String, Num)>
+    where
+        T: AsOrderBook + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, event) in inputs {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+                tx.send(event).unwrap();
+            }
+            tokio::time::sleep_until(start + Duration::from_millis(end_ms)).await;
+        });
+
+        UnboundedReceiverStream::new(rx)
+            .book_snapshots(config)
+            .map(|event| {
+                (
+                    start.elapsed().as_millis() as u64,
+                    event.instrument.base.to_string(),
+                    event.kind.bids.levels()[0].price,
+                )
+            })
+            .collect()
+            .await
+    }
+
+    fn s(base: &str) -> String {
+        base.to_string()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_book_snapshots_periodic() {
+        let inputs = vec![
+            (100, book(100, "eth", num!(10.0))),
+            (200, book(200, "btc", num!(100.0))),
+            (300, book(300, "btc", num!(101.0))),
+            (1_500, book(1_500, "btc", num!(102.0))),
+        ];
+
+        let actual = run(inputs, 3_500, SnapshotConfig::new(Duration::from_secs(1))).await;
+
+        let expected = vec![
+            // TC0: latest OrderBook of every instrument, in instrument order
+            (1_000, s("btc"), num!(101.0)),
+            (1_000, s("eth"), num!(10.0)),
+            // TC1: updated btc OrderBook
+            (2_000, s("btc"), num!(102.0)),
+            (2_000, s("eth"), num!(10.0)),
+            // TC2: unchanged OrderBooks are still snapshot
+            (3_000, s("btc"), num!(102.0)),
+            (3_000, s("eth"), num!(10.0)),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_book_snapshots_skip_unchanged_with_updates() {
+        let inputs = vec![
+            (100, MarketEvent::from(book(100, "btc", num!(100.0)))),
+            (
+                200,
+                MarketEvent {
+                    kind: DataKind::Trade(PublicTrade {
+                        id: "1".to_string(),
+                        price: num!(100.0),
+                        amount: num!(1.0),
+                        side: Side::Buy,
+                    }),
+                    ..MarketEvent::from(book(200, "btc", num!(100.0)))
+                },
+            ),
+            // Identical OrderBook does not count as a change
+            (1_500, MarketEvent::from(book(100, "btc", num!(100.0)))),
+            (2_500, MarketEvent::from(book(2_500, "btc", num!(101.0)))),
+        ];
+
+        let config = SnapshotConfig::new(Duration::from_secs(1))
+            .with_mode(SnapshotMode::WithUpdates)
+            .with_skip_unchanged(true);
+
+        let actual = run(inputs, 4_500, config).await;
+
+        let expected = vec![
+            // TC0: delta driven update yielded as received, trade is dropped
+            (100, s("btc"), num!(100.0)),
+            // TC1: first periodic snapshot
+            (1_000, s("btc"), num!(100.0)),
+            // TC2: identical update yielded, but the 2000ms snapshot is skipped
+            (1_500, s("btc"), num!(100.0)),
+            (2_500, s("btc"), num!(101.0)),
+            // TC3: changed OrderBook snapshot, & the 4000ms snapshot is skipped
+            (3_000, s("btc"), num!(101.0)),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
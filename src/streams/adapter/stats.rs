@@ -0,0 +1,530 @@
+use super::{
+    window::{RollingWindow, TradeWindow, WindowTrade},
+    AsTrade,
+};
+use crate::{
+    event::MarketEvent,
+    num::{self, Num},
+};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Default maximum number of trades retained per instrument by a [`RollingStats`] window.
+pub const DEFAULT_STATS_MAX_TRADES: usize = 100_000;
+
+/// When a [`RollingStats`] adapter emits the [`TradeStats`] of an instrument.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum StatsMode {
+    /// Emit the [`TradeStats`] of an instrument on each of it's trades.
+    #[default]
+    Trade,
+    /// Emit the [`TradeStats`] of every instrument seen once per sampling [`Duration`], including
+    /// instruments without any trades remaining in the window.
+    Sample(Duration),
+}
+
+/// Configuration of a [`RollingStats`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct StatsConfig {
+    /// Trades with an exchange time within the [`Duration`] of the window end are included.
+    pub window: Duration,
+    pub mode: StatsMode,
+    /// Maximum number of trades retained per instrument, evicting the oldest trades beyond it.
+    /// Bounds the memory of the window during bursts of trades.
+    pub max_trades: usize,
+}
+
+impl StatsConfig {
+    /// Construct a new [`Self`] using a window of the provided [`Duration`], emitting on each
+    /// trade.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            mode: StatsMode::default(),
+            max_trades: DEFAULT_STATS_MAX_TRADES,
+        }
+    }
+
+    /// Set the [`StatsMode`] determining when [`TradeStats`] are emitted.
+    pub fn with_mode(mut self, mode: StatsMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the maximum number of trades retained per instrument.
+    pub fn with_max_trades(mut self, max_trades: usize) -> Self {
+        self.max_trades = max_trades;
+        self
+    }
+}
+
+/// Normalised Barter rolling statistics of the trades of an instrument within the window of a
+/// [`StatsConfig`], yielded by a [`RollingStats`] adapter.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TradeStats {
+    /// Volume weighted average price, ie/ quote_volume / volume, or `None` without volume.
+    pub vwap: Option<Num>,
+    /// Total base asset amount of the trades within the window.
+    pub volume: Num,
+    /// Total quote asset notional (ie/ price * amount) of the trades within the window.
+    pub quote_volume: Num,
+    /// Total base asset amount of the [`Side::Buy`](barter_integration::model::Side::Buy)
+    /// aggressor trades within the window.
+    pub buy_volume: Num,
+    /// Total base asset amount of the [`Side::Sell`](barter_integration::model::Side::Sell)
+    /// aggressor trades within the window.
+    pub sell_volume: Num,
+    /// Buy / sell volume imbalance in [-1, 1], ie/ (buy_volume - sell_volume) / volume, or `None`
+    /// without volume.
+    pub imbalance: Option<Num>,
+    /// Number of trades within the window.
+    pub trades: usize,
+    /// Exchange time of the oldest trade within the window, or `None` if it's empty.
+    pub start_time: Option<DateTime<Utc>>,
+    /// Exchange time the window ends at.
+    pub end_time: DateTime<Utc>,
+    /// True once trades have been observed for the full window duration, ie/ false while the
+    /// window is longer than the available trade history.
+    pub complete: bool,
+}
+
+/// [`RollingWindow`] of the trades of an instrument, with the time it's latest trade was
+/// received.
+#[derive(Clone, PartialEq, Debug)]
+struct StatsState {
+    window: RollingWindow,
+    /// Time the latest trade was received, used to advance the window between trades.
+    latest_received: Instant,
+}
+
+impl StatsState {
+    fn new(config: &StatsConfig, time: DateTime<Utc>) -> Self {
+        Self {
+            window: RollingWindow::new(TradeWindow::Time(config.window), config.max_trades, time),
+            latest_received: Instant::now(),
+        }
+    }
+
+    /// Add a trade to the window & evict every trade that has aged out of it. Trades older than
+    /// the window of the latest trade are ignored.
+    fn update(&mut self, trade: WindowTrade) {
+        if trade.time >= self.window.latest_time() {
+            self.latest_received = Instant::now();
+        }
+        self.window.update(trade);
+    }
+
+    /// Determine the end of the window at the time of sampling, ie/ the exchange time of the
+    /// latest trade advanced by the time elapsed since it was received.
+    fn sample_end(&self) -> DateTime<Utc> {
+        let latest = self.window.latest_time();
+        chrono::Duration::from_std(self.latest_received.elapsed())
+            .ok()
+            .and_then(|elapsed| latest.checked_add_signed(elapsed))
+            .unwrap_or(latest)
+    }
+
+    /// Determine the [`TradeStats`] of a window ending at the provided time.
+    fn stats(&self, end: DateTime<Utc>) -> TradeStats {
+        let sums = self.window.sums();
+        let (vwap, imbalance) = match sums.volume > num::zero() {
+            true => (
+                Some(sums.quote_volume / sums.volume),
+                Some((sums.buy_volume - sums.sell_volume) / sums.volume),
+            ),
+            false => (None, None),
+        };
+
+        TradeStats {
+            vwap,
+            volume: sums.volume,
+            quote_volume: sums.quote_volume,
+            buy_volume: sums.buy_volume,
+            sell_volume: sums.sell_volume,
+            imbalance,
+            trades: self.window.len(),
+            start_time: self.window.start_time(),
+            end_time: end,
+            complete: self
+                .window
+                .window()
+                .cutoff(end)
+                .is_some_and(|cutoff| self.window.first_time() <= cutoff),
+        }
+    }
+}
+
+/// Stream adapter that computes rolling [`TradeStats`] (VWAP, base & quote volume, buy / sell
+/// volume imbalance & trade count) of the trades of each ([`Exchange`], [`Instrument`])
+/// independently, over a window of trade exchange times.
+///
+/// In [`StatsMode::Trade`] the [`TradeStats`] are yielded on each trade, carrying the exchange &
+/// received times of the trade. In [`StatsMode::Sample`] the [`TradeStats`] of every instrument
+/// seen are yielded once per sampling interval, with the window advanced by the time elapsed
+/// since the latest trade was received, so quiet instruments age out to an empty window.
+///
+/// Events that do not contain a [`PublicTrade`](crate::subscription::trade::PublicTrade) (eg/
+/// [`DataKind`](crate::event::DataKind) order books) are dropped.
+#[derive(Debug)]
+pub struct RollingStats<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: StatsConfig,
+    windows: HashMap<(Exchange, Instrument), StatsState>,
+    timer: Option<Pin<Box<Sleep>>>,
+    pending: VecDeque<MarketEvent<TradeStats>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for RollingStats<St, T> where St: Unpin {}
+
+impl<St, T> RollingStats<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    /// Construct a new [`Self`] that computes the rolling [`TradeStats`] of the provided stream.
+    pub fn new(stream: St, config: StatsConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            windows: HashMap::new(),
+            timer: match config.mode {
+                StatsMode::Trade => None,
+                StatsMode::Sample(interval) => Some(Box::pin(tokio::time::sleep(interval))),
+            },
+            pending: VecDeque::new(),
+            stream_ended: false,
+        }
+    }
+
+    /// Add the trade of the provided [`MarketEvent<T>`](MarketEvent) to it's instrument window,
+    /// returning the updated [`TradeStats`] if they are emitted on each trade.
+    fn process(&mut self, event: MarketEvent<T>) -> Option<MarketEvent<TradeStats>> {
+        let trade = event.kind.as_trade()?;
+        let window = self
+            .windows
+            .entry((event.exchange.clone(), event.instrument.clone()))
+            .or_insert_with(|| StatsState::new(&self.config, event.exchange_time));
+
+        window.update(WindowTrade {
+            time: event.exchange_time,
+            amount: trade.amount,
+            notional: trade.price * trade.amount,
+            side: trade.side,
+        });
+
+        match self.config.mode {
+            StatsMode::Trade => Some(MarketEvent {
+                exchange_time: event.exchange_time,
+                raw_exchange_time: event.raw_exchange_time,
+                received_time: event.received_time,
+                exchange: event.exchange,
+                instrument: event.instrument,
+                kind: window.stats(window.window.latest_time()),
+                meta: event.meta,
+            }),
+            StatsMode::Sample(_) => None,
+        }
+    }
+
+    /// Advance the window of every instrument seen to the time of sampling, & queue it's
+    /// [`TradeStats`].
+    fn sample(&mut self) {
+        let received_time = Utc::now();
+        for ((exchange, instrument), window) in self.windows.iter_mut() {
+            let end = window.sample_end();
+            window.window.evict(window.window.window().cutoff(end));
+
+            self.pending.push_back(MarketEvent {
+                exchange_time: end,
+                raw_exchange_time: None,
+                received_time,
+                exchange: exchange.clone(),
+                instrument: instrument.clone(),
+                kind: window.stats(end),
+                meta: (),
+            });
+        }
+    }
+}
+
+impl<St, T> Stream for RollingStats<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<TradeStats>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(stats) = this.pending.pop_front() {
+                return Poll::Ready(Some(stats));
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event) {
+                    Some(stats) => return Poll::Ready(Some(stats)),
+                    None => continue,
+                },
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            let sampled = match (&mut this.timer, this.config.mode) {
+                (Some(timer), StatsMode::Sample(interval)) => match timer.as_mut().poll(cx) {
+                    Poll::Ready(()) => {
+                        let deadline = timer.deadline() + interval;
+                        timer.as_mut().reset(deadline);
+                        true
+                    }
+                    Poll::Pending => false,
+                },
+                _ => false,
+            };
+
+            match sampled {
+                true => this.sample(),
+                false => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        num,
+        streams::adapter::MarketEventStreamExt,
+        subscription::{book::OrderBookL1, trade::PublicTrade},
+        test_util::trade::{run_adapter, TradeFixture},
+    };
+    use barter_integration::model::Side;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn time(offset_ms: i64) -> DateTime<Utc> {
+        TradeFixture::new().time(offset_ms)
+    }
+
+    fn trade(
+        offset_ms: i64,
+        base: &str,
+        price: Num,
+        amount: Num,
+        side: Side,
+    ) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().trade(offset_ms, base, price, amount, side)
+    }
+
+    async fn run(inputs: Vec<MarketEvent<PublicTrade>>, config: StatsConfig) -> Vec<TradeStats> {
+        run_adapter(inputs, |trades| {
+            trades.rolling_stats(config).map(|event| event.kind)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_rolling_stats_per_trade() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0), Side::Buy),
+                trade(500, "btc", num!(110.0), num!(3.0), Side::Sell),
+                trade(1000, "btc", num!(120.0), num!(1.0), Side::Buy),
+                trade(1500, "btc", num!(130.0), num!(2.0), Side::Buy),
+            ],
+            StatsConfig::new(Duration::from_millis(1000)),
+        )
+        .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: first trade, window longer than the available history
+                TradeStats {
+                    vwap: Some(num!(100.0)),
+                    volume: num!(1.0),
+                    quote_volume: num!(100.0),
+                    buy_volume: num!(1.0),
+                    sell_volume: num!(0.0),
+                    imbalance: Some(num!(1.0)),
+                    trades: 1,
+                    start_time: Some(time(0)),
+                    end_time: time(0),
+                    complete: false,
+                },
+                // TC1: (100*1 + 110*3) / 4 = 107.5, (1 - 3) / 4 = -0.5
+                TradeStats {
+                    vwap: Some(num!(107.5)),
+                    volume: num!(4.0),
+                    quote_volume: num!(430.0),
+                    buy_volume: num!(1.0),
+                    sell_volume: num!(3.0),
+                    imbalance: Some(num!(-0.5)),
+                    trades: 2,
+                    start_time: Some(time(0)),
+                    end_time: time(500),
+                    complete: false,
+                },
+                // TC2: first trade ages out of the (0ms, 1000ms] window, which is now complete
+                // (110*3 + 120*1) / 4 = 112.5, (1 - 3) / 4 = -0.5
+                TradeStats {
+                    vwap: Some(num!(112.5)),
+                    volume: num!(4.0),
+                    quote_volume: num!(450.0),
+                    buy_volume: num!(1.0),
+                    sell_volume: num!(3.0),
+                    imbalance: Some(num!(-0.5)),
+                    trades: 2,
+                    start_time: Some(time(500)),
+                    end_time: time(1000),
+                    complete: true,
+                },
+                // TC3: (120*1 + 130*2) / 3 = 126.66.., (3 - 0) / 3 = 1
+                TradeStats {
+                    vwap: Some(num!(380.0) / num!(3.0)),
+                    volume: num!(3.0),
+                    quote_volume: num!(380.0),
+                    buy_volume: num!(3.0),
+                    sell_volume: num!(0.0),
+                    imbalance: Some(num!(1.0)),
+                    trades: 2,
+                    start_time: Some(time(1000)),
+                    end_time: time(1500),
+                    complete: true,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_stats_per_instrument() {
+        let inputs = vec![
+            MarketEvent::from(trade(0, "btc", num!(100.0), num!(1.0), Side::Buy)),
+            MarketEvent {
+                kind: DataKind::OrderBookL1(OrderBookL1 {
+                    last_update_time: time(1),
+                    best_bid: Default::default(),
+                    best_ask: Default::default(),
+                }),
+                ..MarketEvent::from(trade(1, "btc", num!(0.0), num!(0.0), Side::Buy))
+            },
+            MarketEvent::from(trade(2, "eth", num!(10.0), num!(2.0), Side::Sell)),
+            MarketEvent::from(trade(3, "btc", num!(200.0), num!(1.0), Side::Sell)),
+        ];
+
+        let actual = futures::stream::iter(inputs)
+            .rolling_stats(StatsConfig::new(Duration::from_secs(60)))
+            .map(|event| {
+                (
+                    event.instrument.base.to_string(),
+                    event.kind.vwap,
+                    event.kind.imbalance,
+                    event.kind.trades,
+                )
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                ("btc".to_string(), Some(num!(100.0)), Some(num!(1.0)), 1),
+                ("eth".to_string(), Some(num!(10.0)), Some(num!(-1.0)), 1),
+                ("btc".to_string(), Some(num!(150.0)), Some(num!(0.0)), 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_stats_max_trades() {
+        let actual = run(
+            (0..10)
+                .map(|index| trade(index, "btc", num!(100 + index), num!(1.0), Side::Buy))
+                .collect(),
+            StatsConfig::new(Duration::from_secs(60)).with_max_trades(3),
+        )
+        .await;
+
+        let last = actual.last().unwrap();
+        assert_eq!(
+            (last.vwap, last.volume, last.trades),
+            (Some(num!(108.0)), num!(3.0), 3)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolling_stats_sampled() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, price, side) in [
+                (100, num!(100.0), Side::Buy),
+                (200, num!(200.0), Side::Sell),
+                (1_500, num!(300.0), Side::Buy),
+            ] {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms as u64)).await;
+                tx.send(trade(offset_ms, "btc", price, num!(1.0), side))
+                    .unwrap();
+            }
+            // Keep the stream open so the window ages out without trades
+            std::future::pending::<()>().await;
+        });
+
+        let config = StatsConfig::new(Duration::from_millis(1_000))
+            .with_mode(StatsMode::Sample(Duration::from_secs(1)));
+
+        let actual = UnboundedReceiverStream::new(rx)
+            .rolling_stats(config)
+            .map(|event| {
+                let elapsed = start.elapsed().as_millis() as i64;
+                (
+                    elapsed,
+                    event.exchange_time,
+                    event.kind.vwap,
+                    event.kind.trades,
+                )
+            })
+            .take(4)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: both trades within the window ending at 1000ms
+                (1_000, time(1_000), Some(num!(150.0)), 2),
+                // TC1: earlier trades aged out of the window ending at 2000ms
+                (2_000, time(2_000), Some(num!(300.0)), 1),
+                // TC2: no trades within the window ending at 3000ms
+                (3_000, time(3_000), None, 0),
+                // TC3: instrument without trades continues to be sampled
+                (4_000, time(4_000), None, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,386 @@
+use super::AsTrade;
+use crate::event::MarketEvent;
+use barter_integration::model::{Exchange, Instrument};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+
+/// Determines what a [`Throttle`] does with events received before the interval of the
+/// previously emitted event (for the same instrument) has elapsed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ThrottleMode {
+    /// Drop the event.
+    #[default]
+    Drop,
+    /// Retain only the latest event, emitting it as soon as the interval elapses.
+    Conflate,
+}
+
+/// Configuration of a [`Throttle`] adapter.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ThrottleConfig {
+    /// Minimum [`Duration`] between two emitted events for the same instrument.
+    pub interval: Duration,
+    /// Instrument specific intervals that take precedence over the global `interval`.
+    ///
+    /// Keyed on the [`Instrument`] alone, so an override applies to that instrument on every
+    /// [`Exchange`], while each ([`Exchange`], [`Instrument`]) is still throttled independently.
+    pub overrides: HashMap<Instrument, Duration>,
+    /// [`ThrottleMode`] applied to events received within the interval.
+    pub mode: ThrottleMode,
+    /// If true, events containing a [`PublicTrade`](crate::subscription::trade::PublicTrade)
+    /// are always passed through untouched.
+    pub passthrough_trades: bool,
+}
+
+impl ThrottleConfig {
+    /// Construct a new [`Self`] using the provided global interval.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            overrides: HashMap::new(),
+            mode: ThrottleMode::default(),
+            passthrough_trades: false,
+        }
+    }
+
+    /// Override the global interval for the provided [`Instrument`] on every [`Exchange`].
+    pub fn with_override<I>(mut self, instrument: I, interval: Duration) -> Self
+    where
+        I: Into<Instrument>,
+    {
+        self.overrides.insert(instrument.into(), interval);
+        self
+    }
+
+    /// Set the [`ThrottleMode`].
+    pub fn with_mode(mut self, mode: ThrottleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set whether trade events are passed through untouched.
+    pub fn with_passthrough_trades(mut self, passthrough_trades: bool) -> Self {
+        self.passthrough_trades = passthrough_trades;
+        self
+    }
+
+    /// Determine the interval that applies to the provided [`Instrument`].
+    pub fn interval(&self, instrument: &Instrument) -> Duration {
+        self.overrides
+            .get(instrument)
+            .copied()
+            .unwrap_or(self.interval)
+    }
+}
+
+/// Stream adapter that emits at most one [`MarketEvent<T>`](MarketEvent) per
+/// ([`Exchange`], [`Instrument`]) per configured interval, dropping or conflating the rest.
+///
+/// Uses the `tokio` clock, so a paused runtime (eg/ in tests) deterministically controls the
+/// emission schedule.
+#[derive(Debug)]
+pub struct Throttle<St, T> {
+    stream: St,
+    config: ThrottleConfig,
+    last_emitted: HashMap<(Exchange, Instrument), Instant>,
+    conflated: HashMap<(Exchange, Instrument), MarketEvent<T>>,
+    ready: VecDeque<MarketEvent<T>>,
+    timer: Pin<Box<Sleep>>,
+    stream_ended: bool,
+}
+
+impl<St, T> Unpin for Throttle<St, T> where St: Unpin {}
+
+impl<St, T> Throttle<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    /// Construct a new [`Self`] that throttles the provided stream.
+    pub fn new(stream: St, config: ThrottleConfig) -> Self {
+        Self {
+            stream,
+            config,
+            last_emitted: HashMap::new(),
+            conflated: HashMap::new(),
+            ready: VecDeque::new(),
+            timer: Box::pin(tokio::time::sleep(Duration::ZERO)),
+            stream_ended: false,
+        }
+    }
+
+    /// Determine if the provided [`MarketEvent<T>`](MarketEvent) should be emitted now. If not,
+    /// the event is either dropped or conflated depending on the [`ThrottleMode`].
+    fn process(&mut self, event: MarketEvent<T>, now: Instant) -> Option<MarketEvent<T>> {
+        if self.config.passthrough_trades && event.kind.as_trade().is_some() {
+            return Some(event);
+        }
+
+        let key = (event.exchange.clone(), event.instrument.clone());
+        let interval = self.config.interval(&event.instrument);
+
+        match self.last_emitted.get(&key) {
+            Some(last) if now < *last + interval => {
+                if let ThrottleMode::Conflate = self.config.mode {
+                    self.conflated.insert(key, event);
+                }
+                None
+            }
+            _ => {
+                self.last_emitted.insert(key, now);
+                Some(event)
+            }
+        }
+    }
+
+    /// Move every conflated event whose interval has elapsed to the ready queue.
+    fn release_conflated(&mut self, now: Instant) {
+        let expired = self
+            .conflated
+            .keys()
+            .filter(|key| {
+                self.last_emitted
+                    .get(*key)
+                    .is_none_or(|last| now >= *last + self.config.interval(&key.1))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut released = expired
+            .into_iter()
+            .filter_map(|key| {
+                self.last_emitted.insert(key.clone(), now);
+                self.conflated.remove(&key)
+            })
+            .collect::<Vec<_>>();
+
+        released.sort_by_key(|event| event.received_time);
+        self.ready.extend(released);
+    }
+
+    /// Move every conflated event to the ready queue, regardless of interval.
+    fn flush_conflated(&mut self) {
+        let mut released = self
+            .conflated
+            .drain()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        released.sort_by_key(|event| event.received_time);
+        self.ready.extend(released);
+    }
+
+    /// Determine the earliest [`Instant`] a conflated event can be released.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.conflated
+            .keys()
+            .filter_map(|key| {
+                self.last_emitted
+                    .get(key)
+                    .map(|last| *last + self.config.interval(&key.1))
+            })
+            .min()
+    }
+}
+
+impl<St, T> Stream for Throttle<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Emit events that are ready before consuming more from the inner stream
+            if let Some(event) = this.ready.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+
+            this.release_conflated(Instant::now());
+            if !this.ready.is_empty() {
+                continue;
+            }
+
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event, Instant::now()) {
+                    Some(event) => return Poll::Ready(Some(event)),
+                    None => continue,
+                },
+                Poll::Ready(None) => {
+                    // Flush conflated events so nothing retained is lost at stream end
+                    this.stream_ended = true;
+                    this.flush_conflated();
+                    continue;
+                }
+                Poll::Pending => {}
+            }
+
+            // Wake up when the next conflated event can be released
+            match this.next_deadline() {
+                Some(deadline) => {
+                    this.timer.as_mut().reset(deadline);
+                    if this.timer.as_mut().poll(cx).is_ready() {
+                        continue;
+                    }
+                    return Poll::Pending;
+                }
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        num,
+        subscription::{liquidation::Liquidation, trade::PublicTrade},
+        test_util::trade::TradeFixture,
+    };
+    use barter_integration::model::Side;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn fixture() -> TradeFixture {
+        TradeFixture::new().with_market("binance_futures_usd", "usdt")
+    }
+
+    fn liquidation(base: &str, id: u64) -> MarketEvent<DataKind> {
+        let fixture = fixture();
+        fixture.event(
+            0,
+            base,
+            DataKind::Liquidation(Liquidation {
+                side: Side::Buy,
+                price: num!(id),
+                quantity: num!(1.0),
+                time: fixture.time(0),
+            }),
+        )
+    }
+
+    fn trade(base: &str, id: u64) -> MarketEvent<DataKind> {
+        fixture().event(
+            0,
+            base,
+            DataKind::Trade(PublicTrade {
+                id: id.to_string(),
+                price: num!(id),
+                amount: num!(1.0),
+                side: Side::Buy,
+            }),
+        )
+    }
+
+    fn id(event: &MarketEvent<DataKind>) -> u64 {
+        match &event.kind {
+            DataKind::Trade(trade) => trade.id.parse().unwrap(),
+            DataKind::Liquidation(liquidation) => liquidation.price.to_string().parse().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Send each event after it's associated millisecond offset, then throttle & collect the
+    /// emitted (millisecond offset, id) pairs.
+    async fn run(
+        inputs: Vec<(u64, MarketEvent<DataKind>)>,
+        config: ThrottleConfig,
+    ) -> Vec<(u64, u64)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let start = Instant::now();
+
+        tokio::spawn(async move {
+            for (offset_ms, event) in inputs {
+                tokio::time::sleep_until(start + Duration::from_millis(offset_ms)).await;
+                tx.send(event).unwrap();
+            }
+        });
+
+        Throttle::new(UnboundedReceiverStream::new(rx), config)
+            .map(|event| (start.elapsed().as_millis() as u64, id(&event)))
+            .collect()
+            .await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_drop() {
+        let inputs = vec![
+            (0, liquidation("btc", 0)),
+            (300, liquidation("btc", 1)),
+            (600, liquidation("btc", 2)),
+            (1000, liquidation("btc", 3)),
+            (1300, liquidation("btc", 4)),
+        ];
+
+        let actual = run(inputs, ThrottleConfig::new(Duration::from_millis(500))).await;
+
+        assert_eq!(actual, vec![(0, 0), (600, 2), (1300, 4)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_conflate() {
+        let inputs = vec![
+            (0, liquidation("btc", 0)),
+            (300, liquidation("btc", 1)),
+            (600, liquidation("btc", 2)),
+            (1100, liquidation("btc", 3)),
+            (1300, liquidation("btc", 4)),
+        ];
+
+        let config =
+            ThrottleConfig::new(Duration::from_millis(500)).with_mode(ThrottleMode::Conflate);
+        let actual = run(inputs, config).await;
+
+        // Event 3 is conflated into event 4, which is flushed when the stream ends
+        assert_eq!(actual, vec![(0, 0), (500, 1), (1000, 2), (1300, 4)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_per_instrument_override() {
+        let inputs = vec![
+            (0, liquidation("btc", 0)),
+            (0, liquidation("eth", 10)),
+            (200, liquidation("btc", 1)),
+            (200, liquidation("eth", 11)),
+            (400, liquidation("btc", 2)),
+            (400, liquidation("eth", 12)),
+        ];
+
+        let config = ThrottleConfig::new(Duration::from_millis(1000))
+            .with_override(fixture().instrument("eth"), Duration::from_millis(100));
+        let actual = run(inputs, config).await;
+
+        assert_eq!(actual, vec![(0, 0), (0, 10), (200, 11), (400, 12)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_throttle_passthrough_trades() {
+        let inputs = vec![
+            (0, trade("btc", 0)),
+            (0, liquidation("btc", 1)),
+            (100, trade("btc", 2)),
+            (100, liquidation("btc", 3)),
+            (200, trade("btc", 4)),
+        ];
+
+        let config = ThrottleConfig::new(Duration::from_millis(1000)).with_passthrough_trades(true);
+        let actual = run(inputs, config).await;
+
+        assert_eq!(actual, vec![(0, 0), (0, 1), (100, 2), (200, 4)]);
+    }
+}
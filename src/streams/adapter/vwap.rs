@@ -0,0 +1,359 @@
+use super::{
+    window::{RollingWindow, TradeWindow, WindowTrade},
+    AsTrade,
+};
+use crate::{
+    event::MarketEvent,
+    num::{self, Num},
+};
+use barter_integration::model::{Exchange, Instrument};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Default maximum number of trades retained per instrument by a [`RollingVwap`] window.
+pub const DEFAULT_VWAP_MAX_TRADES: usize = 100_000;
+
+/// Configuration of a [`RollingVwap`] adapter.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VwapConfig {
+    pub window: TradeWindow,
+    /// Maximum number of trades retained per instrument, evicting the oldest trades beyond it.
+    /// Bounds the memory of a [`TradeWindow::Time`] during bursts of trades.
+    pub max_trades: usize,
+}
+
+impl VwapConfig {
+    /// Construct a new [`Self`] using a [`TradeWindow::Time`] of the provided [`Duration`].
+    pub fn time(window: Duration) -> Self {
+        Self {
+            window: TradeWindow::Time(window),
+            max_trades: DEFAULT_VWAP_MAX_TRADES,
+        }
+    }
+
+    /// Construct a new [`Self`] using a [`TradeWindow::Trades`] of the provided number of trades.
+    pub fn trades(count: usize) -> Self {
+        Self {
+            window: TradeWindow::Trades(count),
+            max_trades: DEFAULT_VWAP_MAX_TRADES,
+        }
+    }
+
+    /// Set the maximum number of trades retained per instrument.
+    pub fn with_max_trades(mut self, max_trades: usize) -> Self {
+        self.max_trades = max_trades;
+        self
+    }
+}
+
+/// Normalised Barter volume weighted average price of the trades of an instrument within a
+/// [`TradeWindow`], yielded by a [`RollingVwap`] adapter.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Vwap {
+    /// Volume weighted average price, ie/ sum(price * amount) / sum(amount).
+    pub vwap: Num,
+    /// Total amount of the trades within the window.
+    pub volume: Num,
+    /// Number of trades within the window.
+    pub trades: usize,
+    /// Exchange time of the oldest trade within the window.
+    pub start_time: DateTime<Utc>,
+    /// Exchange time of the latest trade within the window.
+    pub end_time: DateTime<Utc>,
+}
+
+/// Determine the [`Vwap`] of the [`RollingWindow`], or `None` if it contains no volume.
+fn vwap(window: &RollingWindow) -> Option<Vwap> {
+    let sums = window.sums();
+    if sums.volume <= num::zero() {
+        return None;
+    }
+
+    Some(Vwap {
+        vwap: sums.quote_volume / sums.volume,
+        volume: sums.volume,
+        trades: window.len(),
+        start_time: window.start_time()?,
+        end_time: window.latest_time(),
+    })
+}
+
+/// Stream adapter that computes the rolling [`Vwap`] of the trades of each ([`Exchange`],
+/// [`Instrument`]) independently, over the configured [`TradeWindow`] of trade exchange times or
+/// trade counts.
+///
+/// A [`Vwap`] is yielded on each trade, carrying the exchange & received times of the trade.
+/// The window is only advanced by trades, so the last [`Vwap`] stands during a gap in trading,
+/// and the first trade after the window has aged out starts a fresh window. To emit at a
+/// bounded rate instead, compose with a [`Throttle`](super::throttle::Throttle) in
+/// [`ThrottleMode::Conflate`](super::throttle::ThrottleMode::Conflate), eg/
+/// `trades.vwap(config).throttle(ThrottleConfig::new(interval).with_mode(ThrottleMode::Conflate))`.
+///
+/// Events that do not contain a [`PublicTrade`](crate::subscription::trade::PublicTrade) (eg/
+/// [`DataKind`](crate::event::DataKind) order books) are dropped, as are trades while the window
+/// contains no volume.
+#[derive(Debug)]
+pub struct RollingVwap<St, T> {
+    stream: St,
+    phantom: PhantomData<T>,
+    config: VwapConfig,
+    windows: HashMap<(Exchange, Instrument), RollingWindow>,
+}
+
+impl<St, T> Unpin for RollingVwap<St, T> where St: Unpin {}
+
+impl<St, T> RollingVwap<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    /// Construct a new [`Self`] that computes the rolling [`Vwap`] of the provided stream.
+    pub fn new(stream: St, config: VwapConfig) -> Self {
+        Self {
+            stream,
+            phantom: PhantomData,
+            config,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Add the trade of the provided [`MarketEvent<T>`](MarketEvent) to it's instrument window,
+    /// returning the updated [`Vwap`].
+    fn process(&mut self, event: MarketEvent<T>) -> Option<MarketEvent<Vwap>> {
+        let trade = event.kind.as_trade()?;
+        let window = self
+            .windows
+            .entry((event.exchange.clone(), event.instrument.clone()))
+            .or_insert_with(|| {
+                RollingWindow::new(
+                    self.config.window,
+                    self.config.max_trades,
+                    event.exchange_time,
+                )
+            });
+
+        window.update(WindowTrade {
+            time: event.exchange_time,
+            amount: trade.amount,
+            notional: trade.price * trade.amount,
+            side: trade.side,
+        });
+
+        Some(MarketEvent {
+            exchange_time: event.exchange_time,
+            raw_exchange_time: event.raw_exchange_time,
+            received_time: event.received_time,
+            exchange: event.exchange,
+            instrument: event.instrument,
+            kind: vwap(window)?,
+            meta: event.meta,
+        })
+    }
+}
+
+impl<St, T> Stream for RollingVwap<St, T>
+where
+    St: Stream<Item = MarketEvent<T>> + Unpin,
+    T: AsTrade,
+{
+    type Item = MarketEvent<Vwap>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(event)) => match this.process(event) {
+                    Some(event) => return Poll::Ready(Some(event)),
+                    None => continue,
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::DataKind,
+        num,
+        streams::adapter::{
+            throttle::{ThrottleConfig, ThrottleMode},
+            MarketEventStreamExt,
+        },
+        subscription::{book::OrderBookL1, trade::PublicTrade},
+        test_util::trade::{run_adapter, TradeFixture},
+    };
+    use barter_integration::model::Side;
+
+    fn trade(offset_ms: i64, base: &str, price: Num, amount: Num) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().trade(offset_ms, base, price, amount, Side::Buy)
+    }
+
+    async fn run(inputs: Vec<MarketEvent<PublicTrade>>, config: VwapConfig) -> Vec<(String, Vwap)> {
+        run_adapter(inputs, |trades| {
+            trades
+                .vwap(config)
+                .map(|event| (event.instrument.base.to_string(), event.kind))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_rolling_vwap_time_window() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0)),
+                trade(500, "btc", num!(110.0), num!(3.0)),
+                trade(1000, "btc", num!(120.0), num!(1.0)),
+                trade(5000, "btc", num!(130.0), num!(2.0)),
+            ],
+            VwapConfig::time(Duration::from_millis(1000)),
+        )
+        .await;
+
+        let actual = actual
+            .iter()
+            .map(|(_, vwap)| (vwap.vwap, vwap.volume, vwap.trades))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                // TC0: first trade
+                (num!(100.0), num!(1.0), 1),
+                // TC1: within window
+                (num!(107.5), num!(4.0), 2),
+                // TC2: first trade ages out of the (500ms, 1000ms] window
+                (num!(112.5), num!(4.0), 2),
+                // TC3: trade after a gap starts a fresh window
+                (num!(130.0), num!(2.0), 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_vwap_trade_window() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0)),
+                trade(1, "btc", num!(200.0), num!(1.0)),
+                trade(2, "btc", num!(300.0), num!(2.0)),
+            ],
+            VwapConfig::trades(2),
+        )
+        .await;
+
+        let (_, last) = actual.last().unwrap();
+        assert_eq!(actual.len(), 3);
+        assert_eq!(
+            (last.vwap, last.volume, last.trades),
+            (num!(800.0) / num!(3.0), num!(3.0), 2)
+        );
+        assert_eq!(
+            last.start_time,
+            trade(1, "btc", num!(0.0), num!(0.0)).exchange_time
+        );
+        assert_eq!(
+            last.end_time,
+            trade(2, "btc", num!(0.0), num!(0.0)).exchange_time
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_vwap_per_instrument() {
+        let actual = run(
+            vec![
+                trade(0, "btc", num!(100.0), num!(1.0)),
+                trade(1, "eth", num!(10.0), num!(1.0)),
+                trade(2, "btc", num!(200.0), num!(1.0)),
+                trade(3, "eth", num!(20.0), num!(3.0)),
+            ],
+            VwapConfig::trades(10),
+        )
+        .await;
+
+        let actual = actual
+            .into_iter()
+            .map(|(base, vwap)| (base, vwap.vwap))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("btc".to_string(), num!(100.0)),
+                ("eth".to_string(), num!(10.0)),
+                ("btc".to_string(), num!(150.0)),
+                ("eth".to_string(), num!(17.5)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_vwap_max_trades() {
+        let actual = run(
+            (0..10)
+                .map(|index| trade(index, "btc", num!(100 + index), num!(1.0)))
+                .collect(),
+            VwapConfig::time(Duration::from_secs(60)).with_max_trades(3),
+        )
+        .await;
+
+        let (_, last) = actual.last().unwrap();
+        assert_eq!((last.vwap, last.trades), (num!(108.0), 3));
+    }
+
+    #[tokio::test]
+    async fn test_rolling_vwap_data_kind() {
+        let l1 = MarketEvent {
+            kind: DataKind::OrderBookL1(OrderBookL1 {
+                last_update_time: Utc::now(),
+                best_bid: Default::default(),
+                best_ask: Default::default(),
+            }),
+            ..MarketEvent::from(trade(0, "btc", num!(100.0), num!(1.0)))
+        };
+        let inputs = vec![
+            MarketEvent::from(trade(0, "btc", num!(100.0), num!(1.0))),
+            l1,
+            // Trade without volume leaves the Vwap unchanged
+            MarketEvent::from(trade(1, "btc", num!(100.0), num!(0.0))),
+            MarketEvent::from(trade(2, "btc", num!(200.0), num!(1.0))),
+        ];
+
+        let actual = futures::stream::iter(inputs)
+            .vwap(VwapConfig::trades(10))
+            .map(|event| event.kind.vwap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(actual, vec![num!(100.0), num!(100.0), num!(150.0)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolling_vwap_throttled() {
+        let inputs = (0..5).map(|index| trade(index, "btc", num!(100 * (index + 1)), num!(1.0)));
+
+        let config = ThrottleConfig::new(Duration::from_secs(1)).with_mode(ThrottleMode::Conflate);
+        let actual = futures::stream::iter(inputs)
+            .vwap(VwapConfig::trades(10))
+            .throttle(config)
+            .map(|event| event.kind.vwap)
+            .collect::<Vec<_>>()
+            .await;
+
+        // First Vwap is emitted immediately, & the latest conflated Vwap once the interval elapses
+        assert_eq!(actual, vec![num!(100.0), num!(300.0)]);
+    }
+}
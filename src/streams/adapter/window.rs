@@ -0,0 +1,282 @@
+use crate::num::Num;
+use barter_integration::model::Side;
+use chrono::{DateTime, Utc};
+use std::{collections::VecDeque, time::Duration};
+
+/// Window of trades retained by a rolling trade adapter, eg/ a
+/// [`RollingVwap`](super::vwap::RollingVwap).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TradeWindow {
+    /// Trades with an exchange time within the [`Duration`] of the window end.
+    Time(Duration),
+    /// Latest number of trades.
+    Trades(usize),
+}
+
+impl TradeWindow {
+    /// Determine the exchange time at or before which trades have aged out of a window ending at
+    /// the provided time, or `None` for a [`TradeWindow::Trades`].
+    pub fn cutoff(&self, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Time(window) => chrono::Duration::from_std(*window)
+                .ok()
+                .and_then(|window| end.checked_sub_signed(window)),
+            Self::Trades(_) => None,
+        }
+    }
+
+    /// Determine the maximum number of trades retained by the window, bounded by the provided
+    /// `max_trades`.
+    pub fn capacity(&self, max_trades: usize) -> usize {
+        match self {
+            Self::Time(_) => max_trades,
+            Self::Trades(count) => (*count).min(max_trades),
+        }
+        .max(1)
+    }
+}
+
+/// Trade retained within a [`RollingWindow`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WindowTrade {
+    pub time: DateTime<Utc>,
+    pub amount: Num,
+    /// Quote asset notional of the trade, ie/ price * amount.
+    pub notional: Num,
+    pub side: Side,
+}
+
+/// Running sums of the trades within a [`RollingWindow`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct WindowSums {
+    /// Total base asset amount.
+    pub volume: Num,
+    /// Total quote asset notional.
+    pub quote_volume: Num,
+    /// Total base asset amount of the [`Side::Buy`] aggressor trades.
+    pub buy_volume: Num,
+    /// Total base asset amount of the [`Side::Sell`] aggressor trades.
+    pub sell_volume: Num,
+}
+
+impl WindowSums {
+    fn add(&mut self, trade: &WindowTrade) {
+        self.volume += trade.amount;
+        self.quote_volume += trade.notional;
+        match trade.side {
+            Side::Buy => self.buy_volume += trade.amount,
+            Side::Sell => self.sell_volume += trade.amount,
+        }
+    }
+
+    fn remove(&mut self, trade: &WindowTrade) {
+        self.volume -= trade.amount;
+        self.quote_volume -= trade.notional;
+        match trade.side {
+            Side::Buy => self.buy_volume -= trade.amount,
+            Side::Sell => self.sell_volume -= trade.amount,
+        }
+    }
+}
+
+/// Rolling [`TradeWindow`] of the trades of an instrument, with running [`WindowSums`] so each
+/// trade is O(1) amortised. Shared by the [`RollingVwap`](super::vwap::RollingVwap),
+/// [`RollingStats`](super::stats::RollingStats) &
+/// [`OrderFlowImbalance`](super::imbalance::OrderFlowImbalance) adapters.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RollingWindow {
+    window: TradeWindow,
+    capacity: usize,
+    trades: VecDeque<WindowTrade>,
+    sums: WindowSums,
+    /// Exchange time of the first trade observed.
+    first: DateTime<Utc>,
+    /// Exchange time of the latest trade observed.
+    latest: DateTime<Utc>,
+}
+
+impl RollingWindow {
+    /// Construct a new empty [`Self`] retaining at most `max_trades`, starting at the provided
+    /// exchange time.
+    pub fn new(window: TradeWindow, max_trades: usize, time: DateTime<Utc>) -> Self {
+        Self {
+            window,
+            capacity: window.capacity(max_trades),
+            trades: VecDeque::new(),
+            sums: WindowSums::default(),
+            first: time,
+            latest: time,
+        }
+    }
+
+    /// Add a trade to the window & evict every trade that has aged out of it. Trades older than
+    /// a [`TradeWindow::Time`] window of the latest trade are ignored.
+    pub fn update(&mut self, trade: WindowTrade) {
+        self.first = self.first.min(trade.time);
+        self.latest = self.latest.max(trade.time);
+
+        let cutoff = self.window.cutoff(self.latest);
+        if cutoff.is_none_or(|cutoff| trade.time > cutoff) {
+            self.sums.add(&trade);
+            self.trades.push_back(trade);
+        }
+
+        self.evict(cutoff);
+    }
+
+    /// Evict every trade at or before the cutoff, or beyond the capacity of the window.
+    pub fn evict(&mut self, cutoff: Option<DateTime<Utc>>) {
+        while let Some(oldest) = self.trades.front() {
+            let aged_out = cutoff.is_some_and(|cutoff| oldest.time <= cutoff);
+            if !aged_out && self.trades.len() <= self.capacity {
+                break;
+            }
+            let oldest = self.trades.pop_front().unwrap();
+            self.sums.remove(&oldest);
+        }
+
+        // Reset the running sums of an empty window so float rounding does not accumulate
+        if self.trades.is_empty() {
+            self.sums = WindowSums::default();
+        }
+    }
+
+    /// [`TradeWindow`] of the window.
+    pub fn window(&self) -> TradeWindow {
+        self.window
+    }
+
+    /// Running [`WindowSums`] of the trades within the window.
+    pub fn sums(&self) -> &WindowSums {
+        &self.sums
+    }
+
+    /// Number of trades within the window.
+    pub fn len(&self) -> usize {
+        self.trades.len()
+    }
+
+    /// Determine if the window contains no trades.
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+
+    /// Exchange time of the oldest trade within the window, or `None` if it's empty.
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        self.trades.front().map(|trade| trade.time)
+    }
+
+    /// Exchange time of the first trade observed.
+    pub fn first_time(&self) -> DateTime<Utc> {
+        self.first
+    }
+
+    /// Exchange time of the latest trade observed.
+    pub fn latest_time(&self) -> DateTime<Utc> {
+        self.latest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    fn trade(offset_ms: i64, amount: Num, side: Side) -> WindowTrade {
+        WindowTrade {
+            time: DateTime::from_timestamp_millis(1_700_000_000_000 + offset_ms).unwrap(),
+            amount,
+            notional: num!(100.0) * amount,
+            side,
+        }
+    }
+
+    #[test]
+    fn test_rolling_window_update() {
+        struct TestCase {
+            window: TradeWindow,
+            max_trades: usize,
+            input: Vec<WindowTrade>,
+            expected: (usize, Num, Num, Num),
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: time window evicts trades at or before the cutoff
+                window: TradeWindow::Time(Duration::from_millis(1000)),
+                max_trades: 100,
+                input: vec![
+                    trade(0, num!(1.0), Side::Buy),
+                    trade(500, num!(2.0), Side::Sell),
+                    trade(1000, num!(3.0), Side::Buy),
+                ],
+                expected: (2, num!(5.0), num!(3.0), num!(2.0)),
+            },
+            TestCase {
+                // TC1: time window ignores a trade older than the window of the latest trade
+                window: TradeWindow::Time(Duration::from_millis(1000)),
+                max_trades: 100,
+                input: vec![
+                    trade(2000, num!(1.0), Side::Buy),
+                    trade(500, num!(2.0), Side::Sell),
+                ],
+                expected: (1, num!(1.0), num!(1.0), num!(0.0)),
+            },
+            TestCase {
+                // TC2: trade window retains the latest trades
+                window: TradeWindow::Trades(2),
+                max_trades: 100,
+                input: vec![
+                    trade(0, num!(1.0), Side::Buy),
+                    trade(1, num!(2.0), Side::Sell),
+                    trade(2, num!(3.0), Side::Sell),
+                ],
+                expected: (2, num!(5.0), num!(0.0), num!(5.0)),
+            },
+            TestCase {
+                // TC3: max trades bounds a time window
+                window: TradeWindow::Time(Duration::from_secs(60)),
+                max_trades: 1,
+                input: vec![
+                    trade(0, num!(1.0), Side::Buy),
+                    trade(1, num!(2.0), Side::Buy),
+                ],
+                expected: (1, num!(2.0), num!(2.0), num!(0.0)),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut window = RollingWindow::new(test.window, test.max_trades, test.input[0].time);
+            for trade in test.input {
+                window.update(trade);
+            }
+
+            let sums = window.sums();
+            let actual = (window.len(), sums.volume, sums.buy_volume, sums.sell_volume);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+            assert_eq!(
+                sums.quote_volume,
+                num!(100.0) * sums.volume,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_rolling_window_evict_resets_empty_sums() {
+        let mut window = RollingWindow::new(
+            TradeWindow::Time(Duration::from_millis(1000)),
+            100,
+            trade(0, num!(0.1), Side::Buy).time,
+        );
+        window.update(trade(0, num!(0.1), Side::Buy));
+        window.update(trade(1, num!(0.2), Side::Sell));
+
+        window.evict(Some(trade(1, num!(0.0), Side::Buy).time));
+
+        assert!(window.is_empty());
+        assert_eq!(window.start_time(), None);
+        assert_eq!(*window.sums(), WindowSums::default());
+    }
+}
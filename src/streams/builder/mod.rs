@@ -1,14 +1,59 @@
-use super::{consumer::consume, Streams};
+use super::{
+    adapter::AsTrade,
+    config::StreamConfig,
+    consumer::{
+        consume, consume_custom, consume_with_backfill, EventFilter, SubscriptionCallbacks,
+    },
+    health::{ConnectionHealth, HealthConfig, StreamHealth},
+    inbound::InboundThrottle,
+    latency::{LatencyStats, LatencyTracker},
+    meta::{EventMeta, EventMetas, EventSender},
+    polling::PollConfig,
+    Streams,
+};
 use crate::{
-    error::DataError,
+    error::{DataError, InvalidSubscription},
     event::MarketEvent,
-    exchange::{ExchangeId, StreamSelector},
-    subscription::{SubKind, Subscription},
+    exchange::{
+        backfill::Backfill,
+        endpoint::Endpoints,
+        instruments::{fetch_markets_with, invalid_markets, RequestedMarket},
+        status::{ExchangeStatus, StatusConfig},
+        subscription::ExchangeSub,
+        ExchangeId, StreamSelector,
+    },
+    parser::strict::DeserialiseMode,
+    subscriber::{
+        auth::Credentials,
+        observer::OutboundObserver,
+        proxy::{Proxy, ProxyConfig},
+        ticket::ConnectionTicket,
+        transport::WsConfig,
+    },
+    subscription::{
+        everywhere::{Everywhere, SubscribeEverywhere},
+        sub_kind_name,
+        ticker::TickerUpdates,
+        SubKind, Subscription,
+    },
+    transformer::custom::CustomTransformer,
     Identifier,
 };
-use barter_integration::{error::SocketError, Validator};
-use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin};
+use barter_integration::{
+    error::SocketError,
+    model::{Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 use tokio::sync::mpsc;
+use tracing::{debug, warn};
 
 /// Defines the [`MultiStreamBuilder`](multi::MultiStreamBuilder) API for ergonomically
 /// initialising a common [`Streams<Output>`](Streams) from multiple
@@ -21,23 +66,79 @@ pub type SubscribeFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>>;
 
 /// Builder to configure and initialise a [`Streams<MarketEvent<SubKind::Event>`](Streams) instance
 /// for a specific [`SubKind`].
+///
+/// Every [`MarketEvent`] is tagged with user metadata of type `Meta`, see
+/// [`StreamBuilder::subscribe_with_meta`]. The default `()` tags nothing.
 #[derive(Default)]
-pub struct StreamBuilder<Kind>
+pub struct StreamBuilder<Kind, Meta = ()>
 where
     Kind: SubKind,
 {
-    pub channels: HashMap<ExchangeId, ExchangeChannel<MarketEvent<Kind::Event>>>,
+    pub channels: HashMap<ExchangeId, ExchangeChannel<MarketEvent<Kind::Event, Meta>>>,
     pub futures: Vec<SubscribeFuture>,
+    pub invalid: Vec<InvalidSubscription>,
+    pub filter: Option<EventFilter<Kind::Event>>,
+    pub callbacks: SubscriptionCallbacks<Kind>,
+    pub ticket: Option<ConnectionTicket>,
+    pub ws_configs: HashMap<ExchangeId, WsConfig>,
+    pub poll_configs: HashMap<ExchangeId, PollConfig>,
+    pub inbound_throttles: HashMap<ExchangeId, InboundThrottle>,
+    pub max_book_levels: Option<usize>,
+    pub ticker_updates: TickerUpdates,
+    pub proxies: ProxyConfig,
+    pub endpoints: HashMap<ExchangeId, Endpoints>,
+    pub outbound_observer: Option<OutboundObserver>,
+    pub deserialise_modes: HashMap<ExchangeId, DeserialiseMode>,
+    pub status: StatusConfig,
+    pub credentials: HashMap<ExchangeId, Credentials>,
+    pub health: HealthConfig,
+    /// User metadata of each tagged [`Subscription`], keyed by it's exchange instrument &
+    /// [`SubscriptionId`] (ie/ channel & market), see [`StreamBuilder::subscribe_with_meta`].
+    pub metas: HashMap<(ExchangeId, Instrument, SubscriptionId), Meta>,
+    /// Final configuration set during [`StreamBuilder::init`], shared with every
+    /// [`SubscribeFuture`] to resolve the [`StreamConfig`] of it's connection.
+    shared: Arc<OnceLock<SharedConfig<Kind, Meta>>>,
+    /// [`ConnectionHealth`] of every connection, registered by each [`SubscribeFuture`].
+    connections: StreamHealth,
+    /// [`LatencyTracker`] of every consumer loop, registered by each [`SubscribeFuture`].
+    latency: LatencyStats,
+    /// Validate every subscribed market against the markets listed by the exchange REST API
+    /// during [`StreamBuilder::init`], see [`StreamBuilder::validate_instruments`].
+    pub validate_instruments: bool,
+    /// [`RequestedMarket`] of every [`Subscription`] added, validated if `validate_instruments`.
+    requested: Vec<RequestedMarket>,
+    /// [`Instrument`] & exchange [`SubscriptionId`] (ie/ channel & market) of every
+    /// [`Subscription`] added, used to drop duplicates.
+    subscribed: HashSet<(ExchangeId, Instrument, SubscriptionId)>,
 }
 
-impl<Kind> Debug for StreamBuilder<Kind>
+impl<Kind, Meta> Debug for StreamBuilder<Kind, Meta>
 where
     Kind: SubKind,
+    Meta: Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StreamBuilder<SubKind>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("invalid", &self.invalid)
+            .field("is_filtered", &self.filter.is_some())
+            .field("has_callbacks", &!self.callbacks.is_empty())
+            .field("ticket", &self.ticket)
+            .field("ws_configs", &self.ws_configs)
+            .field("poll_configs", &self.poll_configs)
+            .field("inbound_throttles", &self.inbound_throttles)
+            .field("max_book_levels", &self.max_book_levels)
+            .field("ticker_updates", &self.ticker_updates)
+            .field("proxies", &self.proxies)
+            .field("endpoints", &self.endpoints)
+            .field("has_outbound_observer", &self.outbound_observer.is_some())
+            .field("deserialise_modes", &self.deserialise_modes)
+            .field("status", &self.status)
+            .field("credentials", &self.credentials)
+            .field("health", &self.health)
+            .field("num_metas", &self.metas.len())
+            .field("validate_instruments", &self.validate_instruments)
             .finish()
     }
 }
@@ -48,9 +149,44 @@ where
 {
     /// Construct a new [`Self`].
     pub fn new() -> Self {
+        Self::new_with_meta()
+    }
+}
+
+impl<Kind, Meta> StreamBuilder<Kind, Meta>
+where
+    Kind: SubKind,
+    Meta: EventMeta,
+{
+    /// Construct a new [`Self`] that tags every [`MarketEvent`] with user metadata of type
+    /// `Meta`, see [`StreamBuilder::subscribe_with_meta`].
+    pub fn new_with_meta() -> Self {
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            invalid: Vec::new(),
+            filter: None,
+            callbacks: SubscriptionCallbacks::default(),
+            ticket: None,
+            ws_configs: HashMap::new(),
+            poll_configs: HashMap::new(),
+            inbound_throttles: HashMap::new(),
+            max_book_levels: None,
+            ticker_updates: TickerUpdates::default(),
+            proxies: ProxyConfig::default(),
+            endpoints: HashMap::new(),
+            outbound_observer: None,
+            deserialise_modes: HashMap::new(),
+            status: StatusConfig::default(),
+            credentials: HashMap::new(),
+            health: HealthConfig::default(),
+            metas: HashMap::new(),
+            shared: Arc::new(OnceLock::new()),
+            connections: StreamHealth::default(),
+            latency: LatencyStats::default(),
+            validate_instruments: false,
+            requested: Vec::new(),
+            subscribed: HashSet::new(),
         }
     }
 
@@ -58,7 +194,17 @@ where
     /// a distinct [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection.
     ///
     /// Note that [`Subscription`]s are not actioned until the
-    /// [`init()`](StreamBuilder::init()) method is invoked.
+    /// [`init()`](StreamBuilder::init()) method is invoked, but any that are invalid for the
+    /// exchange are recorded immediately so [`init()`](StreamBuilder::init()) can fail before
+    /// opening a connection.
+    ///
+    /// Duplicate [`Subscription`]s (ie/ same exchange, instrument & [`SubKind`]) are dropped, both
+    /// within the collection and against those added by previous calls, so each is only
+    /// actioned (and validated) once. A collection containing only duplicates is ignored.
+    ///
+    /// Every [`Subscription`] in the collection must share the same
+    /// [`Connector`](crate::exchange::Connector) instance (see [`StreamBuilder::subscribe_with`]),
+    /// since it configures the connection.
     pub fn subscribe<SubIter, Sub, Exchange>(mut self, subscriptions: SubIter) -> Self
     where
         SubIter: IntoIterator<Item = Sub>,
@@ -69,26 +215,730 @@ where
         Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
     {
         // Construct Vec<Subscriptions> from input SubIter
-        let mut subscriptions = subscriptions.into_iter().map(Sub::into).collect::<Vec<_>>();
+        let subscriptions = subscriptions.into_iter().map(Sub::into).collect::<Vec<_>>();
+        self.spawn(subscriptions, consume);
+        self
+    }
+
+    /// Add a collection of instrument & [`SubKind`] pairs to the [`StreamBuilder`] that will be
+    /// actioned against the provided [`Connector`](crate::exchange::Connector) instance on a
+    /// distinct [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection.
+    ///
+    /// Equivalent to [`StreamBuilder::subscribe`] with every [`Subscription`] constructed from a
+    /// clone of the `exchange`, so any per-instance configuration it carries (eg/ a regional
+    /// endpoint or [`Credentials`]) applies to the whole connection.
+    pub fn subscribe_with<SubIter, I, Exchange>(
+        self,
+        exchange: Exchange,
+        subscriptions: SubIter,
+    ) -> Self
+    where
+        SubIter: IntoIterator<Item = (I, Kind)>,
+        I: Into<Instrument>,
+        Exchange: StreamSelector<Kind> + Ord + Send + Sync + 'static,
+        Kind: Ord + Send + Sync + 'static,
+        Kind::Event: Send,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        self.subscribe(
+            subscriptions
+                .into_iter()
+                .map(|(instrument, kind)| Subscription::new(exchange.clone(), instrument, kind))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Add a collection of [`Subscription`]s to the [`StreamBuilder`], each tagged with user
+    /// metadata that is attached to every [`MarketEvent`] it generates (see
+    /// [`MarketEvent::meta`]). Events of untagged [`Subscription`]s carry `Meta::default()`.
+    ///
+    /// Equivalent to [`StreamBuilder::subscribe`] otherwise. The `Meta` type is fixed when the
+    /// [`StreamBuilder`] is constructed, eg/ via
+    /// [`Streams::builder_with_meta`](super::Streams::builder_with_meta), so it's checked at
+    /// compile time. It is cloned onto every event, see [`EventMeta`].
+    ///
+    /// Metadata is recorded per [`Subscription`], so unique [`Subscription`]s to the same
+    /// exchange instrument (eg/ different [`Candles`](crate::subscription::candle::Candles)
+    /// intervals) each keep their own. Since events only identify their instrument, such
+    /// [`Subscription`]s are actioned on distinct connections. Only the metadata of
+    /// [`Subscription`]s that survive de-duplication is recorded, so a dropped duplicate never
+    /// replaces the metadata of the live [`Subscription`].
+    pub fn subscribe_with_meta<SubIter, Sub, Exchange>(mut self, subscriptions: SubIter) -> Self
+    where
+        SubIter: IntoIterator<Item = (Sub, Meta)>,
+        Sub: Into<Subscription<Exchange, Kind>>,
+        Exchange: StreamSelector<Kind> + Ord + Send + Sync + 'static,
+        Kind: Ord + Send + Sync + 'static,
+        Kind::Event: Send,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        let subscriptions = subscriptions
+            .into_iter()
+            .map(|(subscription, meta)| (subscription.into(), meta))
+            .collect::<Vec<_>>();
+
+        // Remove duplicate Subscriptions before recording the metadata of those that remain
+        let Some(subscriptions) = self.unique_by(subscriptions, |(subscription, _)| subscription)
+        else {
+            return self;
+        };
+
+        // Record the metadata of each Subscription, grouping them into connections that hold at
+        // most one Subscription per Instrument so every event maps to a single Subscription
+        let mut connections: Vec<Vec<Subscription<Exchange, Kind>>> = Vec::new();
+        for (subscription, meta) in subscriptions {
+            let id = ExchangeSub::<Exchange::Channel, Exchange::Market>::new(&subscription).id();
+            self.metas
+                .insert((Exchange::ID, subscription.instrument.clone(), id), meta);
+
+            match connections.iter_mut().find(|connection| {
+                connection
+                    .iter()
+                    .all(|other| other.instrument != subscription.instrument)
+            }) {
+                Some(connection) => connection.push(subscription),
+                None => connections.push(vec![subscription]),
+            }
+        }
+
+        for subscriptions in connections {
+            self.spawn_unique(subscriptions, consume);
+        }
+        self
+    }
+
+    /// Add a collection of [`Subscription`]s to the [`StreamBuilder`] that will be actioned on
+    /// a distinct [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection,
+    /// first emitting (at most) the last `limit` events per [`Subscription`] fetched via the
+    /// exchange REST API.
+    ///
+    /// Live events overlapping with the backfilled events are dropped, see
+    /// [`consume_with_backfill`]. Duplicate [`Subscription`]s are dropped as per
+    /// [`StreamBuilder::subscribe`].
+    pub fn subscribe_with_backfill<SubIter, Sub, Exchange>(
+        mut self,
+        subscriptions: SubIter,
+        limit: usize,
+    ) -> Self
+    where
+        SubIter: IntoIterator<Item = Sub>,
+        Sub: Into<Subscription<Exchange, Kind>>,
+        Exchange: Backfill<Kind> + Ord + Send + Sync + 'static,
+        Kind: Ord + Send + Sync + 'static,
+        Kind::Event: AsTrade + Send,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        // Construct Vec<Subscriptions> from input SubIter
+        let subscriptions = subscriptions.into_iter().map(Sub::into).collect::<Vec<_>>();
+        self.spawn(
+            subscriptions,
+            move |subscriptions, exchange_tx, filter, callbacks, health, latency, config| {
+                consume_with_backfill(
+                    subscriptions,
+                    limit,
+                    exchange_tx,
+                    filter,
+                    callbacks,
+                    health,
+                    latency,
+                    config,
+                )
+            },
+        );
+        self
+    }
+
+    /// Add a collection of [`Subscription`]s to the [`StreamBuilder`] that will be actioned on
+    /// a distinct [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection,
+    /// normalising the exchange messages with the provided [`CustomTransformer`] rather than the
+    /// built-in transformer of the exchange & [`SubKind`].
+    ///
+    /// Each (re)connection drives it's own clone of the [`CustomTransformer`], see it's docs for
+    /// the full contract. Duplicate [`Subscription`]s are dropped as per
+    /// [`StreamBuilder::subscribe`].
+    pub fn subscribe_with_transformer<SubIter, Sub, Exchange, Custom>(
+        mut self,
+        subscriptions: SubIter,
+        transformer: Custom,
+    ) -> Self
+    where
+        SubIter: IntoIterator<Item = Sub>,
+        Sub: Into<Subscription<Exchange, Kind>>,
+        Exchange: StreamSelector<Kind> + Ord + Send + Sync + Unpin + 'static,
+        Kind: Ord + Send + Sync + Unpin + 'static,
+        Kind::Event: Send,
+        Custom: CustomTransformer<Exchange, Kind> + Unpin,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        // Construct Vec<Subscriptions> from input SubIter
+        let subscriptions = subscriptions.into_iter().map(Sub::into).collect::<Vec<_>>();
+        self.spawn(
+            subscriptions,
+            move |subscriptions, exchange_tx, filter, callbacks, health, latency, config| {
+                consume_custom(
+                    subscriptions,
+                    transformer,
+                    exchange_tx,
+                    filter,
+                    callbacks,
+                    health,
+                    latency,
+                    config,
+                )
+            },
+        );
+        self
+    }
+
+    /// Add a [`SubscribeFuture`] to the [`StreamBuilder`] that validates the provided
+    /// [`Subscription`]s & spawns the consumer loop constructed by `consume`, handing it the
+    /// [`StreamConfig`] of the connection resolved from the final configuration of the
+    /// [`StreamBuilder`].
+    ///
+    /// Duplicate [`Subscription`]s are dropped, and any the exchange does not support are
+    /// recorded, as per [`StreamBuilder::subscribe`].
+    fn spawn<Exchange, Consume, Fut>(
+        &mut self,
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+        consume: Consume,
+    ) where
+        Exchange: StreamSelector<Kind> + Ord + Send + Sync + 'static,
+        Kind: Ord + Send + Sync + 'static,
+        Kind::Event: Send,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+        Consume: FnOnce(
+                Vec<Subscription<Exchange, Kind>>,
+                EventSender<Kind::Event, Meta>,
+                Option<EventFilter<Kind::Event>>,
+                Option<SubscriptionCallbacks<Kind>>,
+                Arc<ConnectionHealth>,
+                LatencyTracker,
+                StreamConfig,
+            ) -> Fut
+            + 'static,
+        Fut: Future<Output = DataError> + Send + 'static,
+    {
+        // Remove duplicate Subscriptions
+        let Some(subscriptions) = self.unique(subscriptions) else {
+            return;
+        };
+
+        self.spawn_unique(subscriptions, consume);
+    }
+
+    /// Add a [`SubscribeFuture`] for the provided [`Subscription`]s as per
+    /// [`StreamBuilder::spawn`], without removing duplicates since they have already been
+    /// removed by the caller.
+    fn spawn_unique<Exchange, Consume, Fut>(
+        &mut self,
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+        consume: Consume,
+    ) where
+        Exchange: StreamSelector<Kind> + Ord + Send + Sync + 'static,
+        Kind: Ord + Send + Sync + 'static,
+        Kind::Event: Send,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+        Consume: FnOnce(
+                Vec<Subscription<Exchange, Kind>>,
+                EventSender<Kind::Event, Meta>,
+                Option<EventFilter<Kind::Event>>,
+                Option<SubscriptionCallbacks<Kind>>,
+                Arc<ConnectionHealth>,
+                LatencyTracker,
+                StreamConfig,
+            ) -> Fut
+            + 'static,
+        Fut: Future<Output = DataError> + Send + 'static,
+    {
+        // Record any Subscriptions the Exchange does not support
+        self.invalid.extend(invalid_subscriptions(&subscriptions));
+        self.requested.extend(requested_markets(&subscriptions));
 
         // Acquire channel Sender to send Market<Kind::Event> from consumer loop to user
         // '--> Add ExchangeChannel Entry if this Exchange <--> SubKind combination is new
         let exchange_tx = self.channels.entry(Exchange::ID).or_default().tx.clone();
 
+        // Acquire the final configuration, populated during StreamBuilder::init()
+        let shared = Arc::clone(&self.shared);
+        let connections = self.connections.clone();
+        let latency = self.latency.clone();
+
         // Add Future that once awaited will yield the Result<(), SocketError> of subscribing
         self.futures.push(Box::pin(async move {
             // Validate Subscriptions
             validate(&subscriptions)?;
 
-            // Remove duplicate Subscriptions
-            subscriptions.sort();
-            subscriptions.dedup();
+            // Resolve the StreamConfig of this connection
+            let default = SharedConfig::default();
+            let shared = shared.get().unwrap_or(&default);
+            let config = shared.stream_config(Exchange::ID);
+            let exchange_tx =
+                EventSender::new(exchange_tx, shared.metas(Exchange::ID, &subscriptions));
+            let health = connections.register(ConnectionHealth::new::<Kind>(
+                Exchange::ID,
+                subscriptions.len(),
+                shared.health,
+            ));
+            let latency = latency.tracker(Exchange::ID, sub_kind_name::<Kind>());
 
             // Spawn a MarketStream consumer loop with these Subscriptions<Exchange, Kind>
-            tokio::spawn(consume(subscriptions, exchange_tx));
+            let consumer = consume(
+                subscriptions,
+                exchange_tx,
+                shared.filter.clone(),
+                shared.callbacks.clone(),
+                health,
+                latency,
+                config,
+            );
+            // Boxed since the consumer loop future is large
+            tokio::spawn(Box::pin(consumer));
 
             Ok(())
         }));
+    }
+
+    /// Remove every duplicate [`Subscription`] from the provided collection, including those
+    /// already added to the [`StreamBuilder`], returning the sorted unique [`Subscription`]s.
+    ///
+    /// Returns `None` if a non-empty collection contains only duplicates, in which case there is
+    /// nothing left to action.
+    fn unique<Exchange>(
+        &mut self,
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+    ) -> Option<Vec<Subscription<Exchange, Kind>>>
+    where
+        Exchange: StreamSelector<Kind> + Ord,
+        Kind: Ord,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        self.unique_by(subscriptions, |subscription| subscription)
+    }
+
+    /// Remove every item whose [`Subscription`] is a duplicate as per
+    /// [`StreamBuilder::unique`], returning the unique items sorted by [`Subscription`].
+    fn unique_by<Exchange, Item>(
+        &mut self,
+        mut items: Vec<Item>,
+        subscription: fn(&Item) -> &Subscription<Exchange, Kind>,
+    ) -> Option<Vec<Item>>
+    where
+        Exchange: StreamSelector<Kind> + Ord,
+        Kind: Ord,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        if items.is_empty() {
+            return Some(items);
+        }
+
+        items.sort_by(|a, b| subscription(a).cmp(subscription(b)));
+        items.retain(|item| {
+            let subscription = subscription(item);
+            let id = ExchangeSub::<Exchange::Channel, Exchange::Market>::new(subscription).id();
+            let unique =
+                self.subscribed
+                    .insert((Exchange::ID, subscription.instrument.clone(), id));
+            if !unique {
+                warn!(
+                    exchange = %Exchange::ID,
+                    instrument = %subscription.instrument,
+                    sub_kind = sub_kind_name::<Kind>(),
+                    "dropping duplicate Subscription"
+                );
+            }
+            unique
+        });
+
+        (!items.is_empty()).then_some(items)
+    }
+
+    /// Add a [`Subscription`] to the instrument of each provided
+    /// [`Subscription::everywhere`](Subscription::everywhere) for every exchange with a
+    /// [`StreamSelector`] for the [`SubKind`] that supports the instrument [`InstrumentKind`],
+    /// each actioned on a distinct
+    /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection.
+    ///
+    /// Events are yielded from the [`Streams`] entry of their exchange, and are tagged with it via
+    /// [`MarketEvent::exchange`]. Only exchanges with a [`StreamSelector`] for the [`SubKind`] are
+    /// included (see [`SubscribeEverywhere::exchanges`]), and [`init()`](StreamBuilder::init())
+    /// fails if there are none for an instrument.
+    ///
+    /// [`InstrumentKind`]: barter_integration::model::InstrumentKind
+    pub fn subscribe_everywhere<SubIter>(mut self, subscriptions: SubIter) -> Self
+    where
+        SubIter: IntoIterator<Item = Subscription<Everywhere, Kind>>,
+        Kind: SubscribeEverywhere,
+    {
+        for subscription in subscriptions {
+            if Kind::exchanges(subscription.instrument.kind).is_empty() {
+                let error = format!(
+                    "no exchange supports {} for {} instruments",
+                    sub_kind_name::<Kind>(),
+                    subscription.instrument.kind
+                );
+                self.futures.push(Box::pin(async move {
+                    Err(DataError::Socket(SocketError::Subscribe(error)))
+                }));
+                continue;
+            }
+
+            self = Kind::subscribe_everywhere(self, &subscription);
+        }
+
+        self
+    }
+
+    /// Drop every [`MarketEvent<SubKind::Event>`](MarketEvent) that does not match the provided
+    /// predicate, before it leaves the consumer loop task of it's connection.
+    ///
+    /// Applies to every [`Subscription`] in the [`StreamBuilder`], regardless of whether it was
+    /// added before or after this call. Calling this method multiple times requires events to
+    /// match every predicate. The predicate runs inline on the connection task, so it should be
+    /// cheap and never block.
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "binance")]
+    /// # async fn whales() {
+    /// use barter_data::{
+    ///     exchange::binance::spot::BinanceSpot,
+    ///     num,
+    ///     streams::Streams,
+    ///     subscription::trade::PublicTrades,
+    /// };
+    /// use barter_integration::model::InstrumentKind;
+    ///
+    /// // Only receive trades with a notional value of at least $10k
+    /// let streams = Streams::<PublicTrades>::builder()
+    ///     .subscribe([(BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+    ///     .filter_events(|event| event.kind.price * event.kind.amount >= num!(10_000))
+    ///     .init()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn filter_events<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&MarketEvent<Kind::Event>) -> bool + Send + Sync + 'static,
+        Kind::Event: 'static,
+    {
+        self.filter = Some(match self.filter.take() {
+            Some(current) => Arc::new(move |event| current(event) && predicate(event)),
+            None => Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Fire the provided callback for each [`Subscription`] once it is confirmed live, ie/ it's
+    /// connection validated the exchange subscription responses and the first event for it's
+    /// [`Instrument`] was received.
+    ///
+    /// Allows readiness to be tracked incrementally while other connections are still being
+    /// initialised. The callback runs on a dedicated task per connection, off the consumer hot
+    /// path, and fires again after every re-connection. Calling this method again replaces the
+    /// previous callback.
+    pub fn on_subscription_live<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ExchangeId, &Instrument, &Kind) + Send + Sync + 'static,
+    {
+        self.callbacks.on_live = Some(Arc::new(callback));
+        self
+    }
+
+    /// Fire the provided callback with the failure reason for each [`Subscription`] of a
+    /// connection that fails to initialise, eg/ the exchange subscription responses timed out or
+    /// rejected the [`Subscription`].
+    ///
+    /// The callback runs on a dedicated task per connection, off the consumer hot path, and fires
+    /// for every failed re-connection attempt. Calling this method again replaces the previous
+    /// callback.
+    pub fn on_subscription_failed<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ExchangeId, &Instrument, &Kind, &str) + Send + Sync + 'static,
+    {
+        self.callbacks.on_failed = Some(Arc::new(callback));
+        self
+    }
+
+    /// Fire the provided callback with each raw subscription [`WsMessage`] sent to an exchange
+    /// by every connection of this [`StreamBuilder`], eg/ to diagnose why an exchange rejected a
+    /// [`Subscription`].
+    ///
+    /// The callback runs inline on the send path before each payload is sent, and fires again
+    /// after every re-connection. Calling this method again replaces the previous callback.
+    pub fn on_outbound_subscription<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ExchangeId, &WsMessage) + Send + Sync + 'static,
+    {
+        self.outbound_observer = Some(OutboundObserver::new(callback));
+        self
+    }
+
+    /// Set the [`ConnectionTicket`] (eg/ a ticket or client-id) that exchange
+    /// [`Connector`](crate::exchange::Connector)s include in their subscribe requests where
+    /// supported, correlating this [`StreamBuilder`]'s connections in the exchange logs.
+    ///
+    /// Defaults to a randomly generated UUID shared by every connection of this
+    /// [`StreamBuilder`]. Connectors without a use for it ignore it.
+    pub fn connection_ticket<Ticket>(mut self, ticket: Ticket) -> Self
+    where
+        Ticket: Into<ConnectionTicket>,
+    {
+        self.ticket = Some(ticket.into());
+        self
+    }
+
+    /// Set the [`WsConfig`] transport options (eg/ max message size, `TCP_NODELAY`) used by
+    /// every connection of this [`StreamBuilder`] to the provided exchange.
+    ///
+    /// Exchanges without a [`WsConfig`] use the [`WsConfig::default`], which matches the
+    /// tungstenite defaults. Calling this method again for the same exchange replaces the
+    /// previous [`WsConfig`].
+    pub fn ws_config(mut self, exchange: ExchangeId, config: WsConfig) -> Self {
+        self.ws_configs.insert(exchange, config);
+        self
+    }
+
+    /// Set the [`PollConfig`] (eg/ poll interval, REST request spacing) used by every
+    /// [`PollingStream`](crate::streams::polling::PollingStream) of this [`StreamBuilder`] to the
+    /// provided exchange, ie/ for [`SubKind`]s only available via the exchange REST API.
+    ///
+    /// Exchanges without a [`PollConfig`] use the [`PollConfig::default`]. Calling this method
+    /// again for the same exchange replaces the previous [`PollConfig`].
+    pub fn poll_config(mut self, exchange: ExchangeId, config: PollConfig) -> Self {
+        self.poll_configs.insert(exchange, config);
+        self
+    }
+
+    /// Tunnel every connection (WebSocket & REST) of this [`StreamBuilder`] through the provided
+    /// HTTP CONNECT or SOCKS5 [`Proxy`], unless the exchange has it's own
+    /// [`exchange_proxy`](Self::exchange_proxy).
+    ///
+    /// Calling this method again replaces the previous [`Proxy`].
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.default = Some(proxy);
+        self
+    }
+
+    /// Tunnel every connection (WebSocket & REST) of this [`StreamBuilder`] to the provided
+    /// exchange through the provided [`Proxy`], eg/ for regional egress. Takes precedence over
+    /// the [`proxy`](Self::proxy) used by every other exchange.
+    ///
+    /// Calling this method again for the same exchange replaces the previous [`Proxy`].
+    pub fn exchange_proxy(mut self, exchange: ExchangeId, proxy: Proxy) -> Self {
+        self.proxies.exchanges.insert(exchange, proxy);
+        self
+    }
+
+    /// Connect every connection of this [`StreamBuilder`] to the provided exchange using the
+    /// provided `ws` or `wss` url, in place of the [`Connector::url`], eg/ to use a testnet,
+    /// a regional mirror, or a local mock server with the production [`Connector`].
+    ///
+    /// If the url is invalid, [`init()`](StreamBuilder::init()) fails with the
+    /// [`SocketError`] before any connection is opened. Calling this method again for the same
+    /// exchange replaces the previous url.
+    ///
+    /// [`Connector`]: crate::exchange::Connector
+    /// [`Connector::url`]: crate::exchange::Connector::url
+    pub fn with_url<U>(mut self, exchange: ExchangeId, url: U) -> Self
+    where
+        U: AsRef<str>,
+    {
+        match Endpoints::parse_websocket(url.as_ref()) {
+            Ok(url) => self.endpoints.entry(exchange).or_default().websocket = Some(url),
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    /// Fetch REST snapshots & backfills for every connection of this [`StreamBuilder`] to the
+    /// provided exchange using the provided `http` or `https` base url, in place of the
+    /// hardcoded exchange REST base url (eg/ `https://api.binance.com/api/v3`).
+    ///
+    /// If the url is invalid, [`init()`](StreamBuilder::init()) fails with the
+    /// [`SocketError`] before any connection is opened. Calling this method again for the same
+    /// exchange replaces the previous url.
+    pub fn with_rest_url<U>(mut self, exchange: ExchangeId, url: U) -> Self
+    where
+        U: AsRef<str>,
+    {
+        match Endpoints::parse_rest(url.as_ref()) {
+            Ok(url) => self.endpoints.entry(exchange).or_default().rest = Some(url),
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    /// Fail [`init()`](StreamBuilder::init()) with the provided [`SocketError`].
+    fn fail(&mut self, error: SocketError) {
+        self.futures
+            .push(Box::pin(async move { Err(DataError::Socket(error)) }));
+    }
+
+    /// Set the [`DeserialiseMode`] used by every connection of this [`StreamBuilder`] to the
+    /// provided exchange, eg/ [`DeserialiseMode::Strict`] to report exchange message fields
+    /// unknown to their model, surfacing exchange schema changes early.
+    ///
+    /// Exchanges without a [`DeserialiseMode`] use [`DeserialiseMode::Lenient`]. Calling this
+    /// method again for the same exchange replaces the previous [`DeserialiseMode`].
+    pub fn deserialise_mode(mut self, exchange: ExchangeId, mode: DeserialiseMode) -> Self {
+        self.deserialise_modes.insert(exchange, mode);
+        self
+    }
+
+    /// Log in every connection of this [`StreamBuilder`] to the provided exchange using the
+    /// provided [`Credentials`] before subscribing, where the exchange
+    /// [`Connector::login`](crate::exchange::Connector::login) supports it (eg/ Okx, unlocking
+    /// higher limits & login gated channels, see [`exchange::okx::login`](crate::exchange::okx::login)).
+    ///
+    /// [`Credentials`] are never required for public market data, and are ignored by exchanges
+    /// without a login. A failed login fails the connection attempt. Calling this method again
+    /// for the same exchange replaces the previous [`Credentials`].
+    pub fn credentials(mut self, exchange: ExchangeId, credentials: Credentials) -> Self {
+        self.credentials.insert(exchange, credentials);
+        self
+    }
+
+    /// Fire the provided callback with each normalised [`ExchangeStatus`] (eg/ maintenance)
+    /// reported by an exchange to any connection of this [`StreamBuilder`].
+    ///
+    /// Exchange status & maintenance messages are consumed rather than distributed as
+    /// [`MarketEvent`]s, so this is the only way to observe them. Calling this method again
+    /// replaces the previous callback.
+    pub fn on_exchange_status<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ExchangeId, &ExchangeStatus) + Send + Sync + 'static,
+    {
+        self.status.observer = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set the interval every connection of this [`StreamBuilder`] to the provided exchange
+    /// waits between re-connection attempts whilst the exchange reports it's under maintenance,
+    /// in place of the exponential reconnect backoff. Provide `None` to keep using the exponential
+    /// reconnect backoff during maintenance.
+    ///
+    /// Exchanges without a probe interval use the
+    /// [`DEFAULT_MAINTENANCE_PROBE_INTERVAL`](crate::exchange::status::DEFAULT_MAINTENANCE_PROBE_INTERVAL).
+    /// Calling this method again for the same exchange replaces the previous probe interval.
+    pub fn maintenance_probe_interval(
+        mut self,
+        exchange: ExchangeId,
+        interval: Option<Duration>,
+    ) -> Self {
+        self.status.probe_intervals.insert(exchange, interval);
+        self
+    }
+
+    /// Set the [`HealthConfig`] (ie/ rolling window, staleness threshold & scoring weights) of
+    /// the health score of every connection of this [`StreamBuilder`], see
+    /// [`Streams::status`](super::Streams::status). Calling this method again replaces the
+    /// previous [`HealthConfig`].
+    pub fn health_config(mut self, config: HealthConfig) -> Self {
+        self.health = config;
+        self
+    }
+
+    /// Validate the market of every [`Subscription`] (eg/ "BTCUSDT") against the markets listed
+    /// by the exchange REST API (eg/ Binance `exchangeInfo`, Kraken `AssetPairs`) before opening
+    /// any connection, failing [`init()`](StreamBuilder::init()) with a
+    /// [`DataError::InvalidSubscriptions`] listing every unknown or halted market.
+    ///
+    /// Listed markets are fetched once per exchange & cached per process, see
+    /// [`fetch_markets_with`]. Exchanges that don't list their markets (eg/ Bitfinex), or whose
+    /// listing cannot be fetched, are not validated.
+    pub fn validate_instruments(mut self, validate: bool) -> Self {
+        self.validate_instruments = validate;
+        self
+    }
+
+    /// Cap the rate of [`MarketEvent<SubKind::Event>`](MarketEvent)s distributed by each
+    /// connection of this [`StreamBuilder`] to the provided exchange, see [`InboundThrottle`].
+    ///
+    /// Independent of the exchange outbound subscription rate limits. Since it's configured per
+    /// [`StreamBuilder<SubKind>`](StreamBuilder), eg/ trades can drop excess events whilst books
+    /// coalesce to the latest state. Calling this method again for the same exchange replaces
+    /// the previous [`InboundThrottle`].
+    pub fn throttle_inbound(mut self, exchange: ExchangeId, throttle: InboundThrottle) -> Self {
+        self.inbound_throttles.insert(exchange, throttle);
+        self
+    }
+
+    /// Cap the number of [`Level`](crate::subscription::book::Level)s retained on each side of
+    /// every managed [`OrderBook`](crate::subscription::book::OrderBook) of this
+    /// [`StreamBuilder`], protecting long-running services from unbounded memory growth on exotic
+    /// instruments. Defaults to `None` (unlimited).
+    ///
+    /// Once a side exceeds the cap, the [`Level`](crate::subscription::book::Level)s furthest
+    /// from the touch are evicted (counted by the `metrics` feature's `BOOK_LEVELS_EVICTED`). The
+    /// best bid & ask remain correct, but the evicted depth is lost until the exchange next
+    /// updates those prices, so deep levels are approximate. This is therefore incompatible with
+    /// exchange OrderBook checksum verification. Calling this method again replaces the previous
+    /// cap.
+    pub fn max_book_levels(mut self, max_levels: Option<usize>) -> Self {
+        self.max_book_levels = max_levels;
+        self
+    }
+
+    /// Select how often the [`Ticker`](crate::subscription::ticker::Ticker)s of exchanges
+    /// offering both a real-time & a batched ticker channel (eg/ Coinbase `ticker` &
+    /// `ticker_batch`) are updated. Defaults to [`TickerUpdates::Batched`], since the batched
+    /// channel is far cheaper for consumers that don't need a ticker update on every trade.
+    /// Calling this method again replaces the previous selection.
+    pub fn ticker_updates(mut self, updates: TickerUpdates) -> Self {
+        self.ticker_updates = updates;
+        self
+    }
+
+    /// Add every collection of [`Subscription`]s contained in the provided configuration to the
+    /// [`StreamBuilder`], across any number of exchanges identified by their [`ExchangeId`].
+    ///
+    /// Typically used with a `Vec<Vec<Subscription<ExchangeId, Kind>>>` deserialised from a
+    /// configuration file, allowing the entire subscribed universe to be changed without
+    /// re-compiling. A `Subscription<ExchangeId, Kind>` has the same serde representation as the
+    /// [`Subscription`] of the exchange [`Connector`](crate::exchange::Connector) it identifies,
+    /// eg/ `{"exchange": "kraken", "base": "btc", "quote": "usd", "instrument_type": "spot",
+    /// "kind": "public_trades"}`.
+    ///
+    /// Each collection is actioned on a distinct
+    /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection per exchange
+    /// it contains, using the default [`Connector`](crate::exchange::Connector) of the exchange
+    /// (configured via the [`StreamBuilder`], eg/ [`StreamBuilder::with_url`]). Any
+    /// [`Subscription`] to an exchange without an enabled [`StreamSelector`] for the [`SubKind`]
+    /// is recorded as invalid, failing [`init()`](StreamBuilder::init()).
+    pub fn subscriptions_from<Config>(mut self, config: Config) -> Self
+    where
+        Config: IntoIterator<Item = Vec<Subscription<ExchangeId, Kind>>>,
+        Kind: SubscribeEverywhere,
+    {
+        let connectors = Kind::connectors();
+
+        for subscriptions in config {
+            // Group the collection by exchange, since each exchange requires it's own connection
+            let mut exchanges = BTreeMap::<ExchangeId, Vec<_>>::new();
+            for subscription in subscriptions {
+                if !connectors.contains(&subscription.exchange) {
+                    self.invalid.push(InvalidSubscription {
+                        exchange: subscription.exchange,
+                        instrument: subscription.instrument,
+                        reason: format!(
+                            "{} does not support {}",
+                            subscription.exchange,
+                            sub_kind_name::<Kind>()
+                        ),
+                    });
+                    continue;
+                }
+
+                exchanges
+                    .entry(subscription.exchange)
+                    .or_default()
+                    .push(subscription);
+            }
+
+            for (exchange, subscriptions) in exchanges {
+                self = Kind::subscribe_exchange(self, exchange, subscriptions);
+            }
+        }
 
         self
     }
@@ -99,7 +949,46 @@ where
     ///
     /// Each consumer loop distributes consumed [`MarketEvent<SubKind::Event>s`](MarketEvent) to
     /// the [`Streams`] `HashMap` returned by this method.
-    pub async fn init(self) -> Result<Streams<MarketEvent<Kind::Event>>, DataError> {
+    ///
+    /// Returns a [`DataError::InvalidSubscriptions`] listing every invalid [`Subscription`]
+    /// before any connection is opened if the exchange does not support them.
+    pub async fn init(self) -> Result<Streams<MarketEvent<Kind::Event, Meta>>, DataError> {
+        // Ensure every Subscription is valid before opening any connection
+        if !self.invalid.is_empty() {
+            return Err(DataError::InvalidSubscriptions(self.invalid));
+        }
+
+        // Resolve the final configuration
+        let shared = SharedConfig {
+            filter: self.filter,
+            callbacks: (!self.callbacks.is_empty()).then_some(self.callbacks),
+            ticket: self.ticket.unwrap_or_default(),
+            ws_configs: self.ws_configs,
+            poll_configs: self.poll_configs,
+            inbound_throttles: self.inbound_throttles,
+            max_book_levels: self.max_book_levels,
+            ticker_updates: self.ticker_updates,
+            proxies: self.proxies,
+            endpoints: self.endpoints,
+            outbound_observer: self.outbound_observer,
+            deserialise_modes: self.deserialise_modes,
+            status: self.status,
+            credentials: self.credentials,
+            health: self.health,
+            metas: self.metas,
+        };
+
+        // Ensure every subscribed market is listed & trading before opening any connection
+        if self.validate_instruments {
+            let invalid = validate_markets(&self.requested, &shared).await;
+            if !invalid.is_empty() {
+                return Err(DataError::InvalidSubscriptions(invalid));
+            }
+        }
+
+        // Share the final configuration with every Stream initialisation future
+        let _ = self.shared.set(shared);
+
         // Await Stream initialisation futures and ensure success
         futures::future::try_join_all(self.futures).await?;
 
@@ -110,10 +999,146 @@ where
                 .into_iter()
                 .map(|(exchange, channel)| (exchange, channel.rx))
                 .collect(),
+            health: self.connections,
+            latency: self.latency,
         })
     }
 }
 
+/// Final configuration of a [`StreamBuilder`] set during [`StreamBuilder::init`], shared with
+/// every [`SubscribeFuture`] to resolve the [`StreamConfig`] of it's connection.
+struct SharedConfig<Kind, Meta>
+where
+    Kind: SubKind,
+{
+    filter: Option<EventFilter<Kind::Event>>,
+    callbacks: Option<SubscriptionCallbacks<Kind>>,
+    ticket: ConnectionTicket,
+    ws_configs: HashMap<ExchangeId, WsConfig>,
+    poll_configs: HashMap<ExchangeId, PollConfig>,
+    inbound_throttles: HashMap<ExchangeId, InboundThrottle>,
+    max_book_levels: Option<usize>,
+    ticker_updates: TickerUpdates,
+    proxies: ProxyConfig,
+    endpoints: HashMap<ExchangeId, Endpoints>,
+    outbound_observer: Option<OutboundObserver>,
+    deserialise_modes: HashMap<ExchangeId, DeserialiseMode>,
+    status: StatusConfig,
+    credentials: HashMap<ExchangeId, Credentials>,
+    health: HealthConfig,
+    metas: HashMap<(ExchangeId, Instrument, SubscriptionId), Meta>,
+}
+
+impl<Kind, Meta> Default for SharedConfig<Kind, Meta>
+where
+    Kind: SubKind,
+{
+    fn default() -> Self {
+        Self {
+            filter: None,
+            callbacks: None,
+            ticket: ConnectionTicket::default(),
+            ws_configs: HashMap::new(),
+            poll_configs: HashMap::new(),
+            inbound_throttles: HashMap::new(),
+            max_book_levels: None,
+            ticker_updates: TickerUpdates::default(),
+            proxies: ProxyConfig::default(),
+            endpoints: HashMap::new(),
+            outbound_observer: None,
+            deserialise_modes: HashMap::new(),
+            status: StatusConfig::default(),
+            credentials: HashMap::new(),
+            health: HealthConfig::default(),
+            metas: HashMap::new(),
+        }
+    }
+}
+
+impl<Kind, Meta> SharedConfig<Kind, Meta>
+where
+    Kind: SubKind,
+{
+    /// Resolve the [`StreamConfig`] of a connection to the provided exchange.
+    fn stream_config(&self, exchange: ExchangeId) -> StreamConfig {
+        StreamConfig {
+            ticket: Some(self.ticket.clone()),
+            ws: self.ws_configs.get(&exchange).copied().unwrap_or_default(),
+            proxy: self.proxies.get(exchange).cloned(),
+            endpoints: self.endpoints.get(&exchange).cloned().unwrap_or_default(),
+            poll: self.poll_config(exchange),
+            credentials: self.credentials(exchange),
+            // Issued by the private MarketStream whilst connecting, see AuthToken
+            auth_token: None,
+            deserialise_mode: self.deserialise_mode(exchange),
+            status: self.status.handling(exchange),
+            outbound_observer: self.outbound_observer.clone(),
+            throttle: self.inbound_throttles.get(&exchange).copied(),
+            max_book_levels: self.max_book_levels,
+            ticker_updates: self.ticker_updates,
+            // Set per connection by the consumer loop, see StreamConfig::health
+            health: None,
+        }
+    }
+
+    /// Determine the [`PollConfig`] of the provided exchange, falling back to the
+    /// [`PollConfig::default`].
+    fn poll_config(&self, exchange: ExchangeId) -> PollConfig {
+        self.poll_configs
+            .get(&exchange)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Determine the [`DeserialiseMode`] of the provided exchange, defaulting if none was
+    /// configured.
+    fn deserialise_mode(&self, exchange: ExchangeId) -> DeserialiseMode {
+        self.deserialise_modes
+            .get(&exchange)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Determine the [`Credentials`] of the provided exchange, if any were configured.
+    fn credentials(&self, exchange: ExchangeId) -> Option<Credentials> {
+        self.credentials.get(&exchange).cloned()
+    }
+
+    /// Determine the [`EventMetas`] of the provided exchange [`Subscription`]s, empty if none
+    /// are tagged.
+    ///
+    /// Tagged [`Subscription`]s share a connection only if their instruments differ (see
+    /// [`StreamBuilder::subscribe_with_meta`]), so each tagged instrument maps to the metadata
+    /// of exactly one [`Subscription`].
+    fn metas<Exchange>(
+        &self,
+        exchange: ExchangeId,
+        subscriptions: &[Subscription<Exchange, Kind>],
+    ) -> EventMetas<Meta>
+    where
+        Exchange: StreamSelector<Kind>,
+        Meta: Clone,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        if self.metas.is_empty() {
+            return EventMetas::default();
+        }
+
+        EventMetas::new(
+            subscriptions
+                .iter()
+                .filter_map(|subscription| {
+                    let id =
+                        ExchangeSub::<Exchange::Channel, Exchange::Market>::new(subscription).id();
+                    self.metas
+                        .get(&(exchange, subscription.instrument.clone(), id))
+                        .map(|meta| (subscription.instrument.clone(), meta.clone()))
+                })
+                .collect(),
+        )
+    }
+}
+
 /// Convenient type that holds the [`mpsc::UnboundedSender`] and [`mpsc::UnboundedReceiver`] for a
 /// [`MarketEvent<T>`](MarketEvent) channel.
 #[derive(Debug)]
@@ -136,13 +1161,17 @@ impl<T> Default for ExchangeChannel<T> {
     }
 }
 
-/// Validate the provided collection of [`Subscription`]s, ensuring that the associated exchange
-/// supports every [`Subscription`] [`InstrumentKind`](barter_integration::model::InstrumentKind).
+/// Validate the provided collection of [`Subscription`]s, ensuring that they share the same
+/// [`Connector`](crate::exchange::Connector) instance, and that the associated exchange supports
+/// every [`Subscription`] [`InstrumentKind`].
+///
+/// Every invalid [`Subscription`] is aggregated into a single [`DataError::InvalidSubscriptions`].
+#[allow(clippy::result_large_err)]
 pub fn validate<Exchange, Kind>(
     subscriptions: &[Subscription<Exchange, Kind>],
 ) -> Result<(), DataError>
 where
-    Exchange: StreamSelector<Kind>,
+    Exchange: StreamSelector<Kind> + PartialEq,
     Kind: SubKind,
 {
     // Ensure at least one Subscription has been provided
@@ -152,21 +1181,123 @@ where
         )));
     }
 
+    // Ensure the Subscriptions actioned on one connection share a Connector instance
+    if subscriptions
+        .windows(2)
+        .any(|pair| pair[0].exchange != pair[1].exchange)
+    {
+        return Err(DataError::Socket(SocketError::Subscribe(format!(
+            "{} Subscriptions actioned on one connection must share a Connector instance",
+            Exchange::ID
+        ))));
+    }
+
     // Validate the Exchange supports each Subscription InstrumentKind
+    let invalid = invalid_subscriptions(subscriptions);
+    if !invalid.is_empty() {
+        return Err(DataError::InvalidSubscriptions(invalid));
+    }
+
+    Ok(())
+}
+
+/// Determine every [`Subscription`] in the provided collection with an [`InstrumentKind`] that the
+/// associated exchange does not support.
+pub fn invalid_subscriptions<Exchange, Kind>(
+    subscriptions: &[Subscription<Exchange, Kind>],
+) -> Vec<InvalidSubscription>
+where
+    Exchange: StreamSelector<Kind>,
+    Kind: SubKind,
+{
+    let exchange = Exchange::ID;
+
     subscriptions
         .iter()
-        .map(|subscription| subscription.validate())
-        .collect::<Result<Vec<_>, SocketError>>()?;
+        .filter_map(|subscription| {
+            let reason = if !exchange.supports(subscription.instrument.kind) {
+                format!(
+                    "{exchange} does not support {} instruments",
+                    subscription.instrument.kind
+                )
+            } else {
+                Exchange::unsupported(&subscription.kind)?
+            };
 
-    Ok(())
+            Some(InvalidSubscription {
+                exchange,
+                instrument: subscription.instrument.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Determine the [`RequestedMarket`] (ie/ exchange specific market) of every provided
+/// [`Subscription`], used to validate them against the markets listed by the exchange.
+pub fn requested_markets<Exchange, Kind>(
+    subscriptions: &[Subscription<Exchange, Kind>],
+) -> Vec<RequestedMarket>
+where
+    Exchange: StreamSelector<Kind>,
+    Kind: SubKind,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Market>,
+{
+    subscriptions
+        .iter()
+        .map(|subscription| RequestedMarket {
+            exchange: Exchange::ID,
+            instrument: subscription.instrument.clone(),
+            market: subscription.id().as_ref().to_owned(),
+        })
+        .collect()
 }
 
-#[cfg(test)]
+/// Validate every [`RequestedMarket`] against the markets listed by it's exchange REST API,
+/// fetched using the [`StreamConfig`] of the exchange, returning every unknown or
+/// halted market.
+///
+/// Exchanges that don't list their markets, or whose listing cannot be fetched, are skipped.
+async fn validate_markets<Kind, Meta>(
+    requested: &[RequestedMarket],
+    shared: &SharedConfig<Kind, Meta>,
+) -> Vec<InvalidSubscription>
+where
+    Kind: SubKind,
+{
+    let mut by_exchange = HashMap::<ExchangeId, Vec<&RequestedMarket>>::new();
+    for market in requested {
+        by_exchange.entry(market.exchange).or_default().push(market);
+    }
+
+    let mut invalid = Vec::new();
+    for (exchange, requested) in by_exchange {
+        let config = shared.stream_config(exchange);
+        let markets = fetch_markets_with(exchange, &config).await;
+
+        match markets {
+            Ok(Some(markets)) => invalid.extend(invalid_markets(requested, &markets)),
+            Ok(None) => {
+                debug!(%exchange, "exchange does not list it's markets, skipping validation")
+            }
+            Err(error) => {
+                warn!(%exchange, ?error, "failed to fetch exchange markets, skipping validation")
+            }
+        }
+    }
+
+    invalid.sort_by(|a, b| (a.exchange, &a.instrument).cmp(&(b.exchange, &b.instrument)));
+    invalid
+}
+
+#[cfg(all(test, feature = "coinbase"))]
 mod tests {
     use super::*;
     use crate::exchange::coinbase::Coinbase;
-    use crate::subscription::trade::PublicTrades;
-    use barter_integration::model::InstrumentKind;
+    use crate::subscription::trade::{PublicTrade, PublicTrades};
+    use crate::{num, num::Num};
+    use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+    use chrono::Utc;
 
     #[test]
     fn test_validate() {
@@ -228,4 +1359,888 @@ mod tests {
             }
         }
     }
+    #[tokio::test]
+    async fn test_init_aggregates_invalid_subscriptions() {
+        let builder = StreamBuilder::<PublicTrades>::new()
+            .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+            .subscribe([
+                (
+                    Coinbase,
+                    "btc",
+                    "usd",
+                    InstrumentKind::FuturePerpetual,
+                    PublicTrades,
+                ),
+                (
+                    Coinbase,
+                    "eth",
+                    "usd",
+                    InstrumentKind::FuturePerpetual,
+                    PublicTrades,
+                ),
+            ]);
+
+        // Invalid Subscriptions are recorded immediately, before any connection is opened
+        assert_eq!(builder.invalid.len(), 2);
+
+        let actual = builder.init().await.unwrap_err();
+
+        let DataError::InvalidSubscriptions(invalid) = &actual else {
+            panic!("expected DataError::InvalidSubscriptions, found: {actual:?}");
+        };
+        assert_eq!(
+            invalid,
+            &vec![
+                InvalidSubscription {
+                    exchange: ExchangeId::Coinbase,
+                    instrument: Instrument::from(("btc", "usd", InstrumentKind::FuturePerpetual)),
+                    reason: "coinbase does not support future_perpetual instruments".to_string(),
+                },
+                InvalidSubscription {
+                    exchange: ExchangeId::Coinbase,
+                    instrument: Instrument::from(("eth", "usd", InstrumentKind::FuturePerpetual)),
+                    reason: "coinbase does not support future_perpetual instruments".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            actual.to_string(),
+            "InvalidSubscriptions: \
+            coinbase (btc_usd, future_perpetual): coinbase does not support future_perpetual instruments; \
+            coinbase (eth_usd, future_perpetual): coinbase does not support future_perpetual instruments"
+        );
+    }
+
+    #[cfg(feature = "binance")]
+    #[tokio::test]
+    async fn test_init_rejects_unsupported_ticker_window() {
+        use crate::{
+            exchange::binance::spot::BinanceSpot,
+            subscription::{candle::Interval, ticker::Tickers},
+        };
+
+        let builder = StreamBuilder::<Tickers>::new()
+            .subscribe([(
+                BinanceSpot::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                Tickers(Interval::H4),
+            )])
+            .subscribe([(
+                BinanceSpot::default(),
+                "eth",
+                "usdt",
+                InstrumentKind::Spot,
+                Tickers(Interval::H2),
+            )]);
+
+        let actual = builder.init().await.unwrap_err();
+
+        let DataError::InvalidSubscriptions(invalid) = &actual else {
+            panic!("expected DataError::InvalidSubscriptions, found: {actual:?}");
+        };
+        assert_eq!(
+            invalid,
+            &vec![InvalidSubscription {
+                exchange: ExchangeId::BinanceSpot,
+                instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+                reason: "binance_spot does not support tickers_2h rolling window tickers, \
+                supported windows: 1h, 4h, 1d"
+                    .to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_fails_with_invalid_url_override() {
+        struct TestCase {
+            builder: StreamBuilder<PublicTrades>,
+            expected: SocketError,
+        }
+
+        let builder = || {
+            StreamBuilder::<PublicTrades>::new().subscribe([(
+                Coinbase,
+                "btc",
+                "usd",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )])
+        };
+
+        let tests = vec![
+            TestCase {
+                // TC0: unparseable WebSocket url
+                builder: builder().with_url(ExchangeId::Coinbase, "not a url"),
+                expected: SocketError::UrlParse(url::ParseError::RelativeUrlWithoutBase),
+            },
+            TestCase {
+                // TC1: WebSocket url with REST scheme
+                builder: builder().with_url(ExchangeId::Coinbase, "https://127.0.0.1:1"),
+                expected: SocketError::Unsupported {
+                    entity: "url override",
+                    item: "https://127.0.0.1:1/ (expected a ws or wss url)".to_string(),
+                },
+            },
+            TestCase {
+                // TC2: REST url with WebSocket scheme
+                builder: builder().with_rest_url(ExchangeId::Coinbase, "wss://127.0.0.1:1"),
+                expected: SocketError::Unsupported {
+                    entity: "url override",
+                    item: "wss://127.0.0.1:1/ (expected a http or https url)".to_string(),
+                },
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            match test.builder.init().await {
+                Err(DataError::Socket(actual)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        test.expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                actual => panic!("TC{index} failed with unexpected result: {actual:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_init_connects_to_url_override() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Mock Coinbase server: validates the subscription & streams a trade
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/mirror", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let subscription = websocket.next().await.unwrap().unwrap();
+            assert!(subscription.to_text().unwrap().contains("BTC-USD"));
+
+            for message in [
+                r#"{"type":"subscriptions","channels":[{"name":"matches","product_ids":["BTC-USD"]}]}"#,
+                r#"{
+                    "type":"match","trade_id":10,"sequence":50,
+                    "time":"2014-11-07T08:19:27.028459Z",
+                    "product_id":"BTC-USD","size":"5.23512","price":"400.23","side":"sell"
+                }"#,
+            ] {
+                websocket
+                    .send(Message::Text(message.to_string()))
+                    .await
+                    .unwrap();
+            }
+
+            // Hold the connection open until the client is done
+            while websocket.next().await.is_some() {}
+        });
+
+        let mut streams = StreamBuilder::<PublicTrades>::new()
+            .with_url(ExchangeId::Coinbase, &url)
+            .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+            .init()
+            .await
+            .unwrap();
+
+        let mut trades = streams.select(ExchangeId::Coinbase).unwrap();
+        let trade = tokio::time::timeout(std::time::Duration::from_secs(5), trades.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.kind.id, "10");
+        assert_eq!(trade.kind.price, num!(400.23));
+    }
+
+    #[test]
+    fn test_subscribe_with_meta_ignores_dropped_duplicates() {
+        // Duplicate BTC-USD Subscription is dropped, so it's metadata must not replace the live one
+        let builder = StreamBuilder::<PublicTrades, &str>::new_with_meta()
+            .subscribe_with_meta([(
+                (Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades),
+                "live",
+            )])
+            .subscribe_with_meta([
+                (
+                    (Coinbase, "BTC", "USD", InstrumentKind::Spot, PublicTrades),
+                    "duplicate",
+                ),
+                (
+                    (Coinbase, "eth", "usd", InstrumentKind::Spot, PublicTrades),
+                    "eth",
+                ),
+            ]);
+        assert_eq!(builder.futures.len(), 2);
+
+        let meta = |base: &str| {
+            let instrument = Instrument::from((base, "usd", InstrumentKind::Spot));
+            builder
+                .metas
+                .iter()
+                .find(|((exchange, tagged, _), _)| {
+                    *exchange == ExchangeId::Coinbase && *tagged == instrument
+                })
+                .map(|(_, meta)| *meta)
+        };
+        assert_eq!(meta("btc"), Some("live"));
+        assert_eq!(meta("eth"), Some("eth"));
+    }
+
+    #[test]
+    #[cfg(feature = "kraken")]
+    fn test_subscribe_with_meta_keeps_metadata_per_subscription() {
+        use crate::exchange::kraken::Kraken;
+        use crate::subscription::candle::{Candle, Candles, Interval};
+
+        let m1 = Subscription::from((
+            Kraken,
+            "btc",
+            "usd",
+            InstrumentKind::Spot,
+            Candles(Interval::M1),
+        ));
+        let m5 = Subscription::from((
+            Kraken,
+            "btc",
+            "usd",
+            InstrumentKind::Spot,
+            Candles(Interval::M5),
+        ));
+
+        // Both BTC-USD Subscriptions keep their own metadata, each on a distinct connection
+        let builder = StreamBuilder::<Candles, &str>::new_with_meta()
+            .subscribe_with_meta([(m1.clone(), "strat-A"), (m5.clone(), "strat-B")]);
+        assert_eq!(builder.futures.len(), 2);
+        assert_eq!(builder.metas.len(), 2);
+
+        // Each connection tags the events of it's Subscription with the matching metadata
+        let shared = SharedConfig::<Candles, &str> {
+            metas: builder.metas.clone(),
+            ..SharedConfig::default()
+        };
+        let event = MarketEvent {
+            exchange_time: Utc::now(),
+            received_time: Utc::now(),
+            exchange: Exchange::from(ExchangeId::Kraken),
+            instrument: Instrument::from(("btc", "usd", InstrumentKind::Spot)),
+            kind: Candle {
+                close_time: Utc::now(),
+                open: num!(1.0),
+                high: num!(1.0),
+                low: num!(1.0),
+                close: num!(1.0),
+                volume: num!(1.0),
+                trade_count: 1,
+                historical: false,
+            },
+            raw_exchange_time: None,
+            meta: (),
+        };
+        let tag = |subscription: &Subscription<Kraken, Candles>| {
+            shared
+                .metas(ExchangeId::Kraken, std::slice::from_ref(subscription))
+                .tag(event.clone())
+                .meta
+        };
+        assert_eq!(tag(&m1), "strat-A");
+        assert_eq!(tag(&m5), "strat-B");
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_drops_duplicate_subscriptions() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Mock Coinbase server: expects a single BTC-USD subscription, validates it & streams a
+        // trade
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/mirror", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let subscription = websocket.next().await.unwrap().unwrap();
+            let subscription = subscription.into_text().unwrap();
+
+            for message in [
+                r#"{"type":"subscriptions","channels":[{"name":"matches","product_ids":["BTC-USD"]}]}"#,
+                r#"{
+                    "type":"match","trade_id":10,"sequence":50,
+                    "time":"2014-11-07T08:19:27.028459Z",
+                    "product_id":"BTC-USD","size":"5.23512","price":"400.23","side":"sell"
+                }"#,
+            ] {
+                websocket
+                    .send(Message::Text(message.to_string()))
+                    .await
+                    .unwrap();
+            }
+
+            subscription
+        });
+
+        // Duplicates within a single call (regardless of asset case) & across calls
+        let builder = StreamBuilder::<PublicTrades>::new()
+            .with_url(ExchangeId::Coinbase, &url)
+            .subscribe([
+                (Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades),
+                (Coinbase, "BTC", "USD", InstrumentKind::Spot, PublicTrades),
+            ])
+            .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)]);
+        assert_eq!(builder.futures.len(), 1);
+        assert_eq!(builder.requested.len(), 1);
+
+        let mut streams = builder.init().await.unwrap();
+        let mut trades = streams.select(ExchangeId::Coinbase).unwrap();
+        let trade = tokio::time::timeout(std::time::Duration::from_secs(5), trades.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.kind.id, "10");
+
+        let subscription = server.await.unwrap();
+        assert_eq!(subscription.matches("BTC-USD").count(), 1, "{subscription}");
+    }
+
+    #[tokio::test]
+    async fn test_init_validates_instruments() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Mock Coinbase REST server: lists BTC-USD as trading & UST-USD as delisted
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/mirror", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 1024];
+            let read = tcp.read(&mut request).await.unwrap();
+
+            let body = r#"[
+                {
+                    "id":"BTC-USD","base_currency":"BTC","quote_currency":"USD",
+                    "quote_increment":"0.01","base_increment":"0.00000001",
+                    "status":"online","trading_disabled":false
+                },
+                {
+                    "id":"UST-USD","base_currency":"UST","quote_currency":"USD",
+                    "quote_increment":"0.0001","base_increment":"0.01",
+                    "status":"delisted","trading_disabled":true
+                }
+            ]"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            tcp.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+
+        let actual = StreamBuilder::<PublicTrades>::new()
+            .with_rest_url(ExchangeId::Coinbase, &url)
+            .validate_instruments(true)
+            .subscribe([
+                (Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades),
+                (Coinbase, "btc", "usdd", InstrumentKind::Spot, PublicTrades),
+                (Coinbase, "ust", "usd", InstrumentKind::Spot, PublicTrades),
+            ])
+            .init()
+            .await
+            .unwrap_err();
+
+        // Markets are fetched via the REST url override
+        assert!(server
+            .await
+            .unwrap()
+            .starts_with("GET /mirror/products HTTP/1.1"));
+
+        let DataError::InvalidSubscriptions(invalid) = &actual else {
+            panic!("expected DataError::InvalidSubscriptions, found: {actual:?}");
+        };
+        assert_eq!(
+            invalid,
+            &vec![
+                InvalidSubscription {
+                    exchange: ExchangeId::Coinbase,
+                    instrument: Instrument::from(("btc", "usdd", InstrumentKind::Spot)),
+                    reason: "coinbase market BTC-USDD is not listed by the exchange".to_string(),
+                },
+                InvalidSubscription {
+                    exchange: ExchangeId::Coinbase,
+                    instrument: Instrument::from(("ust", "usd", InstrumentKind::Spot)),
+                    reason: "coinbase market UST-USD is not trading (status: delisted)".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "okx")]
+    #[tokio::test]
+    async fn test_init_logs_in_before_subscribing() {
+        use crate::exchange::okx::Okx;
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Mock Okx server: accepts the login, validates the subscription & streams a trade
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+
+            let login = websocket.next().await.unwrap().unwrap();
+            let login =
+                serde_json::from_str::<serde_json::Value>(login.to_text().unwrap()).unwrap();
+            assert_eq!(login["op"], "login");
+            assert_eq!(login["args"][0]["apiKey"], "key");
+            assert_eq!(login["args"][0]["passphrase"], "passphrase");
+            websocket
+                .send(Message::Text(
+                    r#"{"event":"login","code":"0","msg":"","connId":"a4d3ae55"}"#.to_string(),
+                ))
+                .await
+                .unwrap();
+
+            let subscription = websocket.next().await.unwrap().unwrap();
+            assert!(subscription
+                .to_text()
+                .unwrap()
+                .contains(r#""op":"subscribe""#));
+
+            for message in [
+                r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#,
+                r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}]}"#,
+            ] {
+                websocket
+                    .send(Message::Text(message.to_string()))
+                    .await
+                    .unwrap();
+            }
+
+            // Hold the connection open until the client is done
+            while websocket.next().await.is_some() {}
+        });
+
+        let mut streams = StreamBuilder::<PublicTrades>::new()
+            .with_url(ExchangeId::Okx, &url)
+            .credentials(
+                ExchangeId::Okx,
+                Credentials::new("key", "secret").with_passphrase("passphrase"),
+            )
+            .subscribe([(Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+            .init()
+            .await
+            .unwrap();
+
+        let mut trades = streams.select(ExchangeId::Okx).unwrap();
+        let trade = tokio::time::timeout(std::time::Duration::from_secs(5), trades.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.kind.id, "130639474");
+    }
+
+    #[cfg(feature = "binance")]
+    #[tokio::test]
+    async fn test_subscribe_polls_open_interest() {
+        use crate::{
+            exchange::binance::futures::BinanceFuturesUsd, streams::polling::PollConfig,
+            subscription::open_interest::OpenInterests,
+        };
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        };
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Mock Binance futures REST server: open interest unchanged for two polls, then changed
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rest_url = format!("http://{}", listener.local_addr().unwrap());
+        let polls = Arc::new(AtomicUsize::new(0));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        tokio::spawn({
+            let (polls, requests) = (Arc::clone(&polls), Arc::clone(&requests));
+            async move {
+                loop {
+                    let (mut tcp, _) = listener.accept().await.unwrap();
+                    let (polls, requests) = (Arc::clone(&polls), Arc::clone(&requests));
+                    tokio::spawn(async move {
+                        let mut request = vec![0u8; 1024];
+                        while let Ok(read @ 1..) = tcp.read(&mut request).await {
+                            let request = String::from_utf8_lossy(&request[..read]).into_owned();
+                            requests.lock().unwrap().push(request);
+
+                            let open_interest = match polls.fetch_add(1, Ordering::SeqCst) {
+                                0 | 1 => "10659.509",
+                                _ => "10700.000",
+                            };
+                            let body = format!(
+                                r#"{{"openInterest":"{open_interest}","symbol":"BTCUSDT","time":1589437530011}}"#
+                            );
+                            let response = format!(
+                                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                                body.len()
+                            );
+                            tcp.write_all(response.as_bytes()).await.unwrap();
+                        }
+                    });
+                }
+            }
+        });
+
+        let mut streams = StreamBuilder::<OpenInterests>::new()
+            .with_rest_url(ExchangeId::BinanceFuturesUsd, &rest_url)
+            .poll_config(
+                ExchangeId::BinanceFuturesUsd,
+                PollConfig::new(std::time::Duration::from_millis(20)),
+            )
+            .subscribe([(
+                BinanceFuturesUsd::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::FuturePerpetual,
+                OpenInterests,
+            )])
+            .init()
+            .await
+            .unwrap();
+
+        let mut open_interests = streams.select(ExchangeId::BinanceFuturesUsd).unwrap();
+        // Unchanged open interest is only emitted once
+        let first = tokio::time::timeout(std::time::Duration::from_secs(5), open_interests.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.kind.contracts, num!(10659.509));
+        assert_eq!(
+            first.instrument,
+            Instrument::from(("btc", "usdt", InstrumentKind::FuturePerpetual))
+        );
+        let second = tokio::time::timeout(std::time::Duration::from_secs(5), open_interests.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.kind.contracts, num!(10700));
+        assert!(polls.load(Ordering::SeqCst) >= 3);
+
+        let request = requests.lock().unwrap()[0].clone();
+        assert!(
+            request.starts_with("GET /openInterest?symbol=BTCUSDT "),
+            "{request}"
+        );
+    }
+
+    #[cfg(feature = "binance")]
+    #[tokio::test]
+    async fn test_subscribe_with_backfill_stitches_candles() {
+        use crate::{
+            exchange::binance::spot::BinanceSpot,
+            subscription::candle::{Candles, Interval},
+        };
+        use futures::{SinkExt, StreamExt};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Open time of the current (still open) 1m candle
+        let minute = 60_000;
+        let open = Utc::now().timestamp_millis() / minute * minute;
+        let kline_rest = |open: i64| {
+            format!(
+                r#"[{open},"1.0","1.0","1.0","1.0","1.0",{},"1.0",1,"0.5","0.5","0"]"#,
+                open + minute - 1
+            )
+        };
+        let kline_ws = move |open: i64| {
+            format!(
+                r#"{{"e":"kline","E":{open},"s":"BTCUSDT","k":{{"t":{open},"T":{},"s":"BTCUSDT","i":"1m","o":"1.0","c":"1.0","h":"1.0","l":"1.0","v":"1.0","n":1,"x":true}}}}"#,
+                open + minute - 1
+            )
+        };
+
+        // Mock Binance REST server: 3 closed klines & the still open kline
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rest_url = format!("http://{}", listener.local_addr().unwrap());
+        let body = format!(
+            "[{}]",
+            (1..=3)
+                .rev()
+                .map(|offset| open - offset * minute)
+                .chain([open])
+                .map(kline_rest)
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let rest = tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 1024];
+            let read = tcp.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            tcp.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+
+        // Mock Binance WebSocket server: streams the boundary kline again, then the next kline
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let _subscription = websocket.next().await.unwrap().unwrap();
+
+            for message in [
+                r#"{"result":null,"id":1}"#.to_string(),
+                kline_ws(open - minute),
+                kline_ws(open),
+            ] {
+                websocket.send(Message::Text(message)).await.unwrap();
+            }
+
+            // Hold the connection open until the client is done
+            while websocket.next().await.is_some() {}
+        });
+
+        let mut streams = StreamBuilder::<Candles>::new()
+            .with_url(ExchangeId::BinanceSpot, &ws_url)
+            .with_rest_url(ExchangeId::BinanceSpot, &rest_url)
+            .subscribe_with_backfill(
+                [(
+                    BinanceSpot::default(),
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    Candles(Interval::M1),
+                )],
+                3,
+            )
+            .init()
+            .await
+            .unwrap();
+
+        let mut candles = streams.select(ExchangeId::BinanceSpot).unwrap();
+        let mut actual = Vec::new();
+        for _ in 0..4 {
+            let candle = tokio::time::timeout(std::time::Duration::from_secs(5), candles.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            actual.push((
+                candle.kind.close_time.timestamp_millis(),
+                candle.kind.historical,
+            ));
+        }
+
+        assert!(rest
+            .await
+            .unwrap()
+            .starts_with("GET /klines?symbol=BTCUSDT&interval=1m&limit=4 HTTP/1.1"));
+
+        // Backfilled candles are followed by the next live candle, with no gap & the duplicate
+        // boundary candle dropped
+        let expected = (1..=3)
+            .rev()
+            .map(|offset| (open - offset * minute + minute - 1, true))
+            .chain([(open + minute - 1, false)])
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test]
+    async fn test_on_outbound_subscription_observes_payloads() {
+        use futures::StreamExt;
+
+        // Mock Coinbase server: forwards the received subscription payload & never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (received_tx, mut received_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            while let Some(Ok(message)) = websocket.next().await {
+                let _ = received_tx.send(message.to_text().unwrap().to_string());
+            }
+        });
+
+        let (observed_tx, mut observed_rx) = mpsc::unbounded_channel();
+        let _streams = StreamBuilder::<PublicTrades>::new()
+            .with_url(ExchangeId::Coinbase, &url)
+            .on_outbound_subscription(move |exchange, message: &WsMessage| {
+                let _ = observed_tx.send((exchange, message.to_text().unwrap().to_string()));
+            })
+            .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+            .init()
+            .await
+            .unwrap();
+
+        let timeout = std::time::Duration::from_secs(5);
+        let (exchange, observed) = tokio::time::timeout(timeout, observed_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let received = tokio::time::timeout(timeout, received_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(exchange, ExchangeId::Coinbase);
+        assert_eq!(observed, received);
+        assert!(observed.contains("BTC-USD"));
+    }
+
+    #[cfg(feature = "binance")]
+    #[tokio::test]
+    async fn test_multiplexed_socket_preserves_instrument_ordering() {
+        use crate::{
+            event::DataKind, exchange::binance::spot::BinanceSpot,
+            subscription::combined::TradesAndBooksL1,
+        };
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Mock Binance server: validates the subscription & interleaves the trades & top of book
+        // of two instruments over the single multiplexed socket
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            websocket.next().await.unwrap().unwrap();
+
+            let trade = |market: &str, id: u64| {
+                format!(
+                    r#"{{"e":"trade","E":1,"T":1,"s":"{market}","t":{id},"p":"1.0","q":"1.0","m":false}}"#
+                )
+            };
+            let book = |market: &str, bid: &str| {
+                format!(r#"{{"u":1,"s":"{market}","b":"{bid}","B":"1.0","a":"1000.0","A":"1.0"}}"#)
+            };
+
+            for message in [
+                r#"{"result":null,"id":1}"#.to_string(),
+                trade("BTCUSDT", 1),
+                book("BTCUSDT", "100"),
+                book("ETHUSDT", "10"),
+                trade("BTCUSDT", 2),
+                trade("ETHUSDT", 3),
+                book("BTCUSDT", "101"),
+                trade("BTCUSDT", 4),
+                book("ETHUSDT", "11"),
+            ] {
+                websocket.send(Message::Text(message)).await.unwrap();
+            }
+
+            // Hold the connection open until the client is done
+            while websocket.next().await.is_some() {}
+        });
+
+        let streams = Streams::<MarketEvent<DataKind>>::builder_multi()
+            .add(
+                StreamBuilder::<TradesAndBooksL1>::new()
+                    .with_url(ExchangeId::BinanceSpot, &url)
+                    .subscribe([
+                        (
+                            BinanceSpot::default(),
+                            "btc",
+                            "usdt",
+                            InstrumentKind::Spot,
+                            TradesAndBooksL1,
+                        ),
+                        (
+                            BinanceSpot::default(),
+                            "eth",
+                            "usdt",
+                            InstrumentKind::Spot,
+                            TradesAndBooksL1,
+                        ),
+                    ]),
+            )
+            .init()
+            .await
+            .unwrap();
+        let mut events = streams.join().await;
+
+        let mut actual = Vec::new();
+        while actual.len() < 8 {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let kind = match event.kind {
+                DataKind::Trade(trade) => format!("trade {}", trade.id),
+                DataKind::OrderBookL1(book) => format!("l1 {}", book.best_bid.price),
+                kind => panic!("unexpected DataKind: {kind:?}"),
+            };
+            actual.push(format!("{} {kind}", event.instrument.base));
+        }
+
+        // Every event is emitted in the order it arrived on the socket
+        assert_eq!(
+            actual,
+            vec![
+                "btc trade 1",
+                "btc l1 100",
+                "eth l1 10",
+                "btc trade 2",
+                "eth trade 3",
+                "btc l1 101",
+                "btc trade 4",
+                "eth l1 11",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_events_composes_predicates() {
+        let trade = |base: &str, amount: Num| MarketEvent {
+            exchange_time: Utc::now(),
+            raw_exchange_time: None,
+            received_time: Utc::now(),
+            exchange: Exchange::from(ExchangeId::Coinbase),
+            instrument: Instrument::from((base, "usd", InstrumentKind::Spot)),
+            kind: PublicTrade {
+                id: "1".to_string(),
+                price: num!(100),
+                amount,
+                side: Side::Buy,
+            },
+            meta: (),
+        };
+
+        let builder = StreamBuilder::<PublicTrades>::new()
+            .filter_events(|event| event.kind.amount >= num!(1))
+            .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+            .filter_events(|event| event.instrument.base.as_ref() == "btc");
+        let filter = builder.filter.clone().unwrap();
+
+        struct TestCase {
+            input: MarketEvent<PublicTrade>,
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: matches both predicates
+                input: trade("btc", num!(1)),
+                expected: true,
+            },
+            TestCase {
+                // TC1: fails first predicate
+                input: trade("btc", num!(0.5)),
+                expected: false,
+            },
+            TestCase {
+                // TC2: fails second predicate
+                input: trade("eth", num!(1)),
+                expected: false,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = filter(&test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
 }
@@ -1,5 +1,11 @@
 use super::{ExchangeChannel, StreamBuilder, Streams};
-use crate::{error::DataError, event::MarketEvent, exchange::ExchangeId, subscription::SubKind};
+use crate::streams::{health::StreamHealth, latency::LatencyStats, meta::EventMeta, shutdown};
+use crate::{
+    error::{DataError, InvalidSubscription},
+    event::MarketEvent,
+    exchange::ExchangeId,
+    subscription::SubKind,
+};
 use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin};
 
 /// Communicative type alias representing the [`Future`] result of a [`StreamBuilder::init`] call
@@ -8,10 +14,23 @@ pub type BuilderInitFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>
 
 /// Builder to configure and initialise a common [`Streams<Output>`](Streams) instance from
 /// multiple [`StreamBuilder<SubKind>`](StreamBuilder)s.
+///
+/// Every connection of an added [`StreamBuilder`] is distinct, so the events of each added
+/// [`StreamBuilder`] are forwarded in order but no ordering holds between them. Use a combined
+/// [`SubKind`] (eg/ [`TradesAndBooksL1`](crate::subscription::combined::TradesAndBooksL1)) to
+/// multiplex several kinds of an instrument over a single ordered connection. See [`Streams`]
+/// ordering.
 #[derive(Default)]
 pub struct MultiStreamBuilder<Output> {
     pub channels: HashMap<ExchangeId, ExchangeChannel<Output>>,
     pub futures: Vec<BuilderInitFuture>,
+    pub invalid: Vec<InvalidSubscription>,
+    /// [`ConnectionHealth`](crate::streams::health::ConnectionHealth) of every connection of
+    /// each added [`StreamBuilder`], registered once it's initialised.
+    connections: StreamHealth,
+    /// [`LatencyStats`] of every consumer loop of each added [`StreamBuilder`], registered once
+    /// it's initialised.
+    latency: LatencyStats,
 }
 
 impl<Output> Debug for MultiStreamBuilder<Output>
@@ -22,6 +41,7 @@ where
         f.debug_struct("MultiStreamBuilder<Output>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("invalid", &self.invalid)
             .finish()
     }
 }
@@ -32,6 +52,9 @@ impl<Output> MultiStreamBuilder<Output> {
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            invalid: Vec::new(),
+            connections: StreamHealth::default(),
+            latency: LatencyStats::default(),
         }
     }
 
@@ -42,12 +65,16 @@ impl<Output> MultiStreamBuilder<Output> {
     /// Note that the created [`Future`] is not awaited until the [`MultiStreamBuilder::init`]
     /// method is invoked.
     #[allow(clippy::should_implement_trait)]
-    pub fn add<Kind>(mut self, builder: StreamBuilder<Kind>) -> Self
+    pub fn add<Kind, Meta>(mut self, mut builder: StreamBuilder<Kind, Meta>) -> Self
     where
-        Output: From<MarketEvent<Kind::Event>> + Send + 'static,
+        Output: From<MarketEvent<Kind::Event, Meta>> + Send + 'static,
         Kind: SubKind + 'static,
         Kind::Event: Send,
+        Meta: EventMeta,
     {
+        // Take any invalid Subscriptions so they are reported before any connection is opened
+        self.invalid.append(&mut builder.invalid);
+
         // Allocate HashMap to hold the exchange_tx<Output> for each StreamBuilder exchange present
         let mut exchange_txs = HashMap::with_capacity(builder.channels.len());
 
@@ -61,13 +88,16 @@ impl<Output> MultiStreamBuilder<Output> {
         }
 
         // Init Streams<Kind::Event> & send mapped Outputs to the associated exchange_tx
+        let connections = self.connections.clone();
+        let latency = self.latency.clone();
         self.futures.push(Box::pin(async move {
-            builder
-                .init()
-                .await?
+            let streams = builder.init().await?;
+            connections.extend(&streams.health);
+            latency.extend(&streams.latency);
+            streams
                 .streams
                 .into_iter()
-                .for_each(|(exchange, mut exchange_rx)| {
+                .for_each(|(exchange, exchange_rx)| {
                     // Remove exchange_tx<Output> from HashMap that's associated with this tuple:
                     // (ExchangeId, exchange_rx<MarketEvent<SubKind::Event>>)
                     let exchange_tx = exchange_txs
@@ -75,11 +105,7 @@ impl<Output> MultiStreamBuilder<Output> {
                         .expect("all exchange_txs should be present here");
 
                     // Task to receive MarketEvent<SubKind::Event> and send Outputs via exchange_tx
-                    tokio::spawn(async move {
-                        while let Some(event) = exchange_rx.recv().await {
-                            let _ = exchange_tx.send(Output::from(event));
-                        }
-                    });
+                    tokio::spawn(shutdown::forward(exchange_rx, exchange_tx, Output::from));
                 });
 
             Ok(())
@@ -91,7 +117,16 @@ impl<Output> MultiStreamBuilder<Output> {
     /// Initialise each [`StreamBuilder<SubKind>`](StreamBuilder) that was added to the
     /// [`MultiStreamBuilder`] and map all [`Streams<SubKind::Event>`](Streams) into a common
     /// [`Streams<Output>`](Streams).
+    ///
+    /// Returns a [`DataError::InvalidSubscriptions`] listing every invalid
+    /// [`Subscription`](crate::subscription::Subscription) across all added
+    /// [`StreamBuilder`]s before any connection is opened.
     pub async fn init(self) -> Result<Streams<Output>, DataError> {
+        // Ensure every Subscription is valid before opening any connection
+        if !self.invalid.is_empty() {
+            return Err(DataError::InvalidSubscriptions(self.invalid));
+        }
+
         // Await Stream initialisation futures and ensure success
         futures::future::try_join_all(self.futures).await?;
 
@@ -102,6 +137,8 @@ impl<Output> MultiStreamBuilder<Output> {
                 .into_iter()
                 .map(|(exchange, channel)| (exchange, channel.rx))
                 .collect(),
+            health: self.connections,
+            latency: self.latency,
         })
     }
 }
@@ -0,0 +1,61 @@
+use crate::{
+    exchange::{endpoint::Endpoints, status::StatusHandling},
+    parser::strict::DeserialiseMode,
+    streams::{health::ConnectionHealth, inbound::InboundThrottle, polling::PollConfig},
+    subscriber::{
+        auth::{AuthToken, Credentials},
+        observer::OutboundObserver,
+        proxy::Proxy,
+        ticket::ConnectionTicket,
+        transport::WsConfig,
+    },
+    subscription::ticker::TickerUpdates,
+};
+use std::sync::Arc;
+
+/// Per connection configuration of a [`MarketStream`](crate::MarketStream), handed explicitly to
+/// it's [`Subscriber`](crate::subscriber::Subscriber) & [`Connector`](crate::exchange::Connector)
+/// on every (re)connection.
+///
+/// Resolved for each consumer loop by the [`StreamBuilder`](super::builder::StreamBuilder) from
+/// it's per exchange configuration. The [`StreamConfig::default`] (ie/ no configuration) is used
+/// by [`MarketStream::init`](crate::MarketStream::init).
+#[derive(Clone, Debug, Default)]
+pub struct StreamConfig {
+    /// [`ConnectionTicket`] included in subscribe requests where the exchange supports one.
+    pub ticket: Option<ConnectionTicket>,
+    /// [`WsConfig`] WebSocket & TCP transport options applied when connecting.
+    pub ws: WsConfig,
+    /// Optional [`Proxy`] every WebSocket & REST connection is tunnelled through.
+    pub proxy: Option<Proxy>,
+    /// [`Endpoints`] overriding the exchange WebSocket & REST base urls.
+    pub endpoints: Endpoints,
+    /// [`PollConfig`] of REST polled streams, see
+    /// [`PollingStream`](crate::streams::polling::PollingStream).
+    pub poll: PollConfig,
+    /// Optional exchange API key [`Credentials`] used to log in & authenticate private channels.
+    pub credentials: Option<Credentials>,
+    /// Optional exchange issued [`AuthToken`] authenticating private channel subscriptions.
+    pub auth_token: Option<AuthToken>,
+    /// [`DeserialiseMode`] used to parse exchange messages.
+    pub deserialise_mode: DeserialiseMode,
+    /// [`StatusHandling`] of the exchange status & maintenance messages received.
+    pub status: StatusHandling,
+    /// Optional [`OutboundObserver`] notified of each outbound subscription payload.
+    pub outbound_observer: Option<OutboundObserver>,
+    /// Optional [`InboundThrottle`] capping the rate of distributed
+    /// [`MarketEvent<T>`](crate::event::MarketEvent)s.
+    pub throttle: Option<InboundThrottle>,
+    /// Optional cap on the number of [`Level`](crate::subscription::book::Level)s retained on
+    /// each side of every managed [`OrderBook`](crate::subscription::book::OrderBook), see
+    /// [`StreamBuilder::max_book_levels`](super::builder::StreamBuilder::max_book_levels).
+    pub max_book_levels: Option<usize>,
+    /// [`TickerUpdates`] selecting the ticker channel of exchanges offering both a real-time &
+    /// a batched one, see
+    /// [`StreamBuilder::ticker_updates`](super::builder::StreamBuilder::ticker_updates).
+    pub ticker_updates: TickerUpdates,
+    /// [`ConnectionHealth`] of the connection, set by the consumer loop, so an
+    /// [`ExchangeTransformer`](crate::transformer::ExchangeTransformer) may record exchange
+    /// heartbeats via [`ConnectionHealth::heartbeat`].
+    pub health: Option<Arc<ConnectionHealth>>,
+}
@@ -1,32 +1,326 @@
 use crate::{
     error::DataError,
     event::MarketEvent,
-    exchange::StreamSelector,
-    subscription::{SubKind, Subscription},
+    exchange::{
+        backfill::{Backfill, BackfillWatermark},
+        status::StatusHandling,
+        Connector, ExchangeId, StreamSelector,
+    },
+    streams::{
+        adapter::AsTrade,
+        config::StreamConfig,
+        health::ConnectionHealth,
+        inbound::{Admission, InboundLimiter},
+        latency::LatencyTracker,
+        meta::{EventMeta, EventSender},
+        shutdown::{self, Next},
+    },
+    subscriber::ticket::ConnectionTicket,
+    subscription::{sub_kind_name, SubKind, Subscription},
+    transformer::custom::{self, CustomTransformer},
     Identifier, MarketStream,
 };
-use futures::StreamExt;
-use std::time::Duration;
+use barter_integration::{error::SocketError, model::Instrument};
+use futures::{future::BoxFuture, Stream, StreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument as _, Span};
+
+#[cfg(feature = "metrics")]
+use crate::metrics::{MetricsRegistry, StreamMetrics};
 
 /// Initial duration that the [`consume`] function should wait after disconnecting before attempting
 /// to re-initialise a [`MarketStream`]. This duration will increase exponentially as a result
 /// of repeated disconnections with re-initialisation failures.
 pub const STARTING_RECONNECT_BACKOFF_MS: u64 = 125;
 
+/// Maximum number of characters of an unparseable exchange payload that are logged.
+const MAX_LOGGED_PAYLOAD_CHARS: usize = 256;
+
+/// Source of the unique `connection_id` recorded in every [`MarketStream`] connection span.
+static CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Predicate run on the consumer loop task that determines if a [`MarketEvent<T>`](MarketEvent)
+/// should be distributed downstream. Events for which it returns false are dropped.
+///
+/// See [`StreamBuilder::filter_events`](super::builder::StreamBuilder::filter_events).
+pub type EventFilter<T> = Arc<dyn Fn(&MarketEvent<T>) -> bool + Send + Sync>;
+
+/// Callback fired once a [`Subscription`] is confirmed live, see [`SubscriptionCallbacks`].
+pub type OnSubscriptionLive<Kind> = Arc<dyn Fn(ExchangeId, &Instrument, &Kind) + Send + Sync>;
+
+/// Callback fired with the failure reason when a [`Subscription`] fails to go live, see
+/// [`SubscriptionCallbacks`].
+pub type OnSubscriptionFailed<Kind> =
+    Arc<dyn Fn(ExchangeId, &Instrument, &Kind, &str) + Send + Sync>;
+
+/// Optional callbacks notified of the outcome of each [`Subscription`] of a consumer loop.
+///
+/// Callbacks run on a dedicated task per connection, off the consumer hot path:
+/// - `on_live` fires for each [`Subscription`] once it's connection has validated the
+///   subscription responses and the first event for it's [`Instrument`] is received.
+/// - `on_failed` fires for each [`Subscription`] when it's connection fails to initialise, eg/
+///   subscription validation timed out or was rejected by the exchange.
+///
+/// Both fire again for every re-connection attempt of the consumer loop.
+pub struct SubscriptionCallbacks<Kind> {
+    pub on_live: Option<OnSubscriptionLive<Kind>>,
+    pub on_failed: Option<OnSubscriptionFailed<Kind>>,
+}
+
+impl<Kind> SubscriptionCallbacks<Kind> {
+    /// Determine if no callbacks are configured.
+    pub fn is_empty(&self) -> bool {
+        self.on_live.is_none() && self.on_failed.is_none()
+    }
+}
+
+impl<Kind> std::fmt::Debug for SubscriptionCallbacks<Kind> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionCallbacks")
+            .field("on_live", &self.on_live.is_some())
+            .field("on_failed", &self.on_failed.is_some())
+            .finish()
+    }
+}
+
+impl<Kind> Default for SubscriptionCallbacks<Kind> {
+    fn default() -> Self {
+        Self {
+            on_live: None,
+            on_failed: None,
+        }
+    }
+}
+
+impl<Kind> Clone for SubscriptionCallbacks<Kind> {
+    fn clone(&self) -> Self {
+        Self {
+            on_live: self.on_live.clone(),
+            on_failed: self.on_failed.clone(),
+        }
+    }
+}
+
+/// Outcome of the [`Subscription`]s for an [`Instrument`], or of every [`Subscription`] if failed.
+#[derive(Debug)]
+enum SubscriptionOutcome {
+    Live(Instrument),
+    Failed(String),
+}
+
+/// Tracks which [`Subscription`]s of a consumer loop are live, sending each
+/// [`SubscriptionOutcome`] to a task that runs the [`SubscriptionCallbacks`].
+#[derive(Debug)]
+struct SubscriptionNotifier {
+    instruments: HashSet<Instrument>,
+    pending: HashSet<Instrument>,
+    outcome_tx: mpsc::UnboundedSender<SubscriptionOutcome>,
+}
+
+impl SubscriptionNotifier {
+    /// Construct a new [`Self`] for the provided [`Subscription`]s, spawning the task that runs
+    /// the [`SubscriptionCallbacks`].
+    fn spawn<Exchange, Kind>(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        callbacks: SubscriptionCallbacks<Kind>,
+    ) -> Self
+    where
+        Exchange: StreamSelector<Kind>,
+        Kind: SubKind + Send + 'static,
+    {
+        let exchange = Exchange::ID;
+        let mut kinds = HashMap::<Instrument, Vec<Kind>>::new();
+        for subscription in subscriptions {
+            kinds
+                .entry(subscription.instrument.clone())
+                .or_default()
+                .push(subscription.kind.clone());
+        }
+
+        let (outcome_tx, mut outcome_rx) = mpsc::unbounded_channel();
+        let instruments = kinds.keys().cloned().collect();
+
+        tokio::spawn(async move {
+            while let Some(outcome) = outcome_rx.recv().await {
+                match (outcome, &callbacks) {
+                    (
+                        SubscriptionOutcome::Live(instrument),
+                        SubscriptionCallbacks {
+                            on_live: Some(on_live),
+                            ..
+                        },
+                    ) => {
+                        for kind in kinds.get(&instrument).into_iter().flatten() {
+                            on_live(exchange, &instrument, kind);
+                        }
+                    }
+                    (
+                        SubscriptionOutcome::Failed(reason),
+                        SubscriptionCallbacks {
+                            on_failed: Some(on_failed),
+                            ..
+                        },
+                    ) => {
+                        for (instrument, kind) in kinds.iter().flat_map(|(instrument, kinds)| {
+                            kinds.iter().map(move |kind| (instrument, kind))
+                        }) {
+                            on_failed(exchange, instrument, kind, &reason);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        Self {
+            instruments,
+            pending: HashSet::new(),
+            outcome_tx,
+        }
+    }
+
+    /// Connection initialised, so every [`Subscription`] is pending it's first event.
+    fn connected(&mut self) {
+        self.pending = self.instruments.clone();
+    }
+
+    /// Connection failed to initialise, so every [`Subscription`] failed.
+    fn failed(&mut self, error: &DataError) {
+        self.pending.clear();
+        let _ = self
+            .outcome_tx
+            .send(SubscriptionOutcome::Failed(error.to_string()));
+    }
+
+    /// Notify the [`Subscription`]s of the event [`Instrument`] are live if this is the first
+    /// event received for it since connecting.
+    fn observe<T>(&mut self, event: &Result<MarketEvent<T>, DataError>) {
+        if let Ok(event) = event {
+            if self.pending.remove(&event.instrument) {
+                let _ = self
+                    .outcome_tx
+                    .send(SubscriptionOutcome::Live(event.instrument.clone()));
+            }
+        }
+    }
+}
+
 /// Central [`MarketEvent<T>`](MarketEvent) consumer loop.
 ///
-/// Initialises an exchange [`MarketStream`] using a collection of [`Subscription`]s. Consumed
-/// events are distributed downstream via the `exchange_tx mpsc::UnboundedSender`. A re-connection
+/// Initialises an exchange [`MarketStream`] using a collection of [`Subscription`]s & the
+/// [`StreamConfig`] of it's connections. Consumed events matching the optional [`EventFilter`]
+/// are tagged with their user metadata & distributed downstream via the `exchange_tx`
+/// [`EventSender`], at the rate allowed by the optional [`StreamConfig::throttle`], and the
+/// outcome of each [`Subscription`] is notified to the optional [`SubscriptionCallbacks`]. The
+/// latency of every received event is recorded to the provided [`LatencyTracker`], owned by the
+/// consumer loop task (eg/ registered via
+/// [`LatencyStats::tracker`](crate::streams::latency::LatencyStats::tracker)). A re-connection
 /// mechanism with an exponential backoff policy is utilised to ensure maximum up-time.
-pub async fn consume<Exchange, Kind>(
+///
+/// Once the `exchange_tx` receiver is dropped (eg/ after `stream.take(n)`), the consumer loop
+/// closes it's connection and returns a [`DataError::ConsumerDropped`], rather than leaking the
+/// connection until it errors.
+pub async fn consume<Exchange, Kind, Meta>(
+    subscriptions: Vec<Subscription<Exchange, Kind>>,
+    exchange_tx: EventSender<Kind::Event, Meta>,
+    filter: Option<EventFilter<Kind::Event>>,
+    callbacks: Option<SubscriptionCallbacks<Kind>>,
+    health: Arc<ConnectionHealth>,
+    latency: LatencyTracker,
+    config: StreamConfig,
+) -> DataError
+where
+    Exchange: StreamSelector<Kind>,
+    Kind: SubKind + Send + 'static,
+    Meta: EventMeta,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    consume_streams(
+        subscriptions,
+        |subscriptions, config| Box::pin(Exchange::Stream::init_with(subscriptions, config)),
+        exchange_tx,
+        filter,
+        callbacks,
+        health,
+        latency,
+        config,
+        false,
+    )
+    .await
+}
+
+/// Central [`MarketEvent<T>`](MarketEvent) consumer loop of [`MarketStream`]s driven by the
+/// provided [`CustomTransformer`], each connection using it's own clone of it.
+///
+/// Identical to [`consume`] in every other respect.
+#[allow(clippy::too_many_arguments)]
+pub async fn consume_custom<Exchange, Kind, Meta, Custom>(
+    subscriptions: Vec<Subscription<Exchange, Kind>>,
+    custom: Custom,
+    exchange_tx: EventSender<Kind::Event, Meta>,
+    filter: Option<EventFilter<Kind::Event>>,
+    callbacks: Option<SubscriptionCallbacks<Kind>>,
+    health: Arc<ConnectionHealth>,
+    latency: LatencyTracker,
+    config: StreamConfig,
+) -> DataError
+where
+    Exchange: StreamSelector<Kind> + Send + Sync + Unpin,
+    Kind: SubKind + Send + Sync + Unpin + 'static,
+    Kind::Event: Send,
+    Meta: EventMeta,
+    Custom: CustomTransformer<Exchange, Kind> + Unpin,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    consume_streams(
+        subscriptions,
+        |subscriptions, config| Box::pin(custom::init(subscriptions, custom.clone(), config)),
+        exchange_tx,
+        filter,
+        callbacks,
+        health,
+        latency,
+        config,
+        false,
+    )
+    .await
+}
+
+/// Consumer loop shared by [`consume`], [`consume_custom`] & [`consume_with_backfill`],
+/// (re)initialising each connection's [`Stream`] of [`MarketEvent<T>`](MarketEvent)s via the
+/// provided `init` function.
+///
+/// If `reconnecting`, a previous connection of these [`Subscription`]s has already been
+/// established, so a failure to initialise the first [`Stream`] is retried rather than returned.
+#[allow(clippy::too_many_arguments)]
+async fn consume_streams<Exchange, Kind, Meta, St, Init>(
     subscriptions: Vec<Subscription<Exchange, Kind>>,
-    exchange_tx: mpsc::UnboundedSender<MarketEvent<Kind::Event>>,
+    mut init: Init,
+    exchange_tx: EventSender<Kind::Event, Meta>,
+    filter: Option<EventFilter<Kind::Event>>,
+    callbacks: Option<SubscriptionCallbacks<Kind>>,
+    health: Arc<ConnectionHealth>,
+    mut latency: LatencyTracker,
+    mut config: StreamConfig,
+    reconnecting: bool,
 ) -> DataError
 where
     Exchange: StreamSelector<Kind>,
-    Kind: SubKind,
+    Kind: SubKind + Send + 'static,
+    Meta: EventMeta,
+    St: Stream<Item = Result<MarketEvent<Kind::Event>, DataError>> + Unpin,
+    Init: for<'a> FnMut(
+        &'a [Subscription<Exchange, Kind>],
+        &'a StreamConfig,
+    ) -> BoxFuture<'a, Result<St, DataError>>,
     Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
 {
     // Determine ExchangeId associated with these Subscriptions
@@ -39,30 +333,74 @@ where
         "MarketStream consumer loop running",
     );
 
-    // Consumer loop retry parameters
-    let mut attempt: u32 = 0;
+    // Notify SubscriptionCallbacks off the consumer hot path, if any
+    let mut notifier =
+        callbacks.map(|callbacks| SubscriptionNotifier::spawn(&subscriptions, callbacks));
+
+    // Record metrics for this consumer loop, if the metrics feature is enabled
+    let metrics = stream_metrics::<Kind>(exchange, subscriptions.len());
+
+    // Expose the rolling latency of received events tracked by this consumer loop
+    metrics.track_latency(&latency);
+
+    // Resolve the Instrument of unparseable exchange payloads, where possible
+    let markets = MarketInstruments::new(&subscriptions);
+
+    // Record the exchange heartbeats of each connection on it's ConnectionHealth
+    config.health = Some(Arc::clone(&health));
+
+    // Cap the rate of distributed events across re-connections, if configured
+    let mut limiter = config.throttle.map(InboundLimiter::new);
+
+    // Consumer loop retry parameters, continuing the retries of a previous connection if
+    // reconnecting
+    let mut attempt = u32::from(reconnecting);
     let mut backoff_ms: u64 = STARTING_RECONNECT_BACKOFF_MS;
 
     loop {
         // Increment retry parameters at start of every iteration
         attempt += 1;
-        backoff_ms *= 2;
-        info!(%exchange, attempt, "attempting to initialise MarketStream");
+        backoff_ms = backoff_ms.saturating_mul(2);
+
+        // Each connection attempt is traced within it's own uniquely identified span
+        let span = connection_span::<Kind>(exchange, config.ticket.as_ref());
+
+        // Shut down rather than re-connecting if the receiver was dropped whilst disconnected
+        if exchange_tx.tx().is_closed() {
+            return consumer_dropped(&span, exchange);
+        }
+        info!(parent: &span, %exchange, attempt, "attempting to initialise MarketStream");
 
         // Attempt to initialise MarketStream: if it fails on first attempt return DataError
-        let mut stream = match Exchange::Stream::init(&subscriptions).await {
+        let stream = match init(&subscriptions, &config).instrument(span.clone()).await {
             Ok(stream) => {
-                info!(%exchange, attempt, "successfully initialised MarketStream");
+                info!(parent: &span, %exchange, attempt, "successfully initialised MarketStream");
                 attempt = 0;
                 backoff_ms = STARTING_RECONNECT_BACKOFF_MS;
+                if let Some(notifier) = notifier.as_mut() {
+                    notifier.connected();
+                }
+                metrics.connected();
+                health.connected();
                 stream
             }
             Err(error) => {
-                error!(%exchange, attempt, ?error, "failed to initialise MarketStream");
+                error!(parent: &span, %exchange, attempt, ?error, "failed to initialise MarketStream");
+                if let Some(notifier) = notifier.as_mut() {
+                    notifier.failed(&error);
+                }
 
                 // Exit function function if Stream::init failed the first attempt, else retry
+                // after backoff_ms
                 if attempt == 1 {
                     return error;
+                } else if shutdown::sleep_or_closed(
+                    reconnect_backoff::<Exchange>(&span, &config.status, backoff_ms),
+                    exchange_tx.tx(),
+                )
+                .await
+                {
+                    return consumer_dropped(&span, exchange);
                 } else {
                     continue;
                 }
@@ -70,49 +408,1239 @@ where
         };
 
         // Consume Result<MarketEvent<T>, DataError> from MarketStream
-        while let Some(event_result) = stream.next().await {
-            match event_result {
-                // If Ok: send MarketEvent<T> to exchange receiver
-                Ok(market_event) => {
-                    let _ = exchange_tx.send(market_event).map_err(|err| {
-                        error!(
-                            payload = ?err.0,
-                            why = "receiver dropped",
-                            "failed to send Event<MarketData> to Exchange receiver"
-                        );
-                    });
-                }
-                // If terminal DataError: break
-                Err(error) if error.is_terminal() => {
-                    error!(
-                        %exchange,
-                        %error,
-                        action = "re-initialising Stream",
-                        "consumed DataError from MarketStream",
-                    );
-                    break;
-                }
-
-                // If non-terminal DataError: log & continue
-                Err(error) => {
-                    warn!(
-                        %exchange,
-                        %error,
-                        action = "skipping message",
-                        "consumed DataError from MarketStream",
-                    );
-                    continue;
-                }
+        let mut stream = stream.inspect(|event| {
+            metrics.received(event);
+            health.received(event);
+            if let Ok(event) = event {
+                latency.record(event.exchange_time, event.received_time);
+            }
+            if let Some(notifier) = notifier.as_mut() {
+                notifier.observe(event);
             }
+        });
+        let terminal_error = distribute(
+            exchange,
+            &mut stream,
+            &exchange_tx,
+            filter.as_ref(),
+            limiter.as_mut(),
+            &metrics,
+            &markets,
+        )
+        .instrument(span.clone())
+        .await;
+        drop(stream);
+        latency.publish();
+        metrics.disconnected();
+        health.disconnected();
+        log_disconnect(&span, exchange, terminal_error.as_ref());
+
+        // If the receiver was dropped, drop the MarketStream (closing it's connection) & exit
+        if let Some(DataError::ConsumerDropped(_)) = terminal_error {
+            return consumer_dropped(&span, exchange);
         }
+        metrics.reconnecting();
+        health.reconnecting();
 
         // If MarketStream ends unexpectedly, attempt re-connection after backoff_ms
         warn!(
+            parent: &span,
             %exchange,
             backoff_ms,
             action = "attempt re-connection after backoff",
             "exchange MarketStream unexpectedly ended"
         );
-        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+        if shutdown::sleep_or_closed(
+            reconnect_backoff::<Exchange>(&span, &config.status, backoff_ms),
+            exchange_tx.tx(),
+        )
+        .await
+        {
+            return consumer_dropped(&span, exchange);
+        }
+    }
+}
+
+/// Determine the [`Duration`] to wait before the next [`MarketStream`] connection attempt. This
+/// is the [`Connector::maintenance_backoff`] whilst the exchange is under maintenance, else the
+/// exponential `backoff_ms`.
+fn reconnect_backoff<Exchange>(span: &Span, status: &StatusHandling, backoff_ms: u64) -> Duration
+where
+    Exchange: Connector,
+{
+    match Exchange::maintenance_backoff(status) {
+        Some(backoff) => {
+            warn!(
+                parent: span,
+                exchange = %Exchange::ID,
+                ?backoff,
+                action = "pause re-connection until maintenance backoff elapses",
+                "exchange is under maintenance"
+            );
+            backoff
+        }
+        None => Duration::from_millis(backoff_ms),
+    }
+}
+
+/// [`MarketEvent<T>`](MarketEvent) consumer loop that first emits backfilled events fetched via
+/// the exchange REST API, before continuing with the live events.
+///
+/// The live [`MarketStream`] is initialised before the backfill is fetched so there is no gap
+/// between them, and any live events that overlap the backfill tail are dropped (see
+/// [`BackfillWatermark`]). Once the initial [`MarketStream`] ends, the standard re-connecting
+/// consumer loop takes over, retrying failed re-connections as per [`consume`].
+#[allow(clippy::too_many_arguments)]
+pub async fn consume_with_backfill<Exchange, Kind, Meta>(
+    subscriptions: Vec<Subscription<Exchange, Kind>>,
+    limit: usize,
+    exchange_tx: EventSender<Kind::Event, Meta>,
+    filter: Option<EventFilter<Kind::Event>>,
+    callbacks: Option<SubscriptionCallbacks<Kind>>,
+    health: Arc<ConnectionHealth>,
+    mut latency: LatencyTracker,
+    mut config: StreamConfig,
+) -> DataError
+where
+    Exchange: Backfill<Kind>,
+    Kind: SubKind + Send + Sync + 'static,
+    Kind::Event: AsTrade,
+    Meta: EventMeta,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    // Determine ExchangeId associated with these Subscriptions
+    let exchange = Exchange::ID;
+
+    info!(
+        %exchange,
+        ?subscriptions,
+        limit,
+        "MarketStream consumer loop running with backfill",
+    );
+
+    // Notify SubscriptionCallbacks off the consumer hot path, if any
+    let mut notifier = callbacks
+        .clone()
+        .map(|callbacks| SubscriptionNotifier::spawn(&subscriptions, callbacks));
+
+    // Record metrics for the initial MarketStream, if the metrics feature is enabled
+    let metrics = stream_metrics::<Kind>(exchange, subscriptions.len());
+
+    // Expose the rolling latency of received live events tracked by this consumer loop
+    metrics.track_latency(&latency);
+
+    // Resolve the Instrument of unparseable exchange payloads, where possible
+    let markets = MarketInstruments::new(&subscriptions);
+
+    // Record the exchange heartbeats of each connection on it's ConnectionHealth
+    config.health = Some(Arc::clone(&health));
+
+    // Cap the rate of distributed live events, if configured
+    let mut limiter = config.throttle.map(InboundLimiter::new);
+
+    // Initialise MarketStream before fetching backfill so live events are buffered meanwhile
+    let span = connection_span::<Kind>(exchange, config.ticket.as_ref());
+    let stream = match Exchange::Stream::init_with(&subscriptions, &config)
+        .instrument(span.clone())
+        .await
+    {
+        Ok(stream) => {
+            if let Some(notifier) = notifier.as_mut() {
+                notifier.connected();
+            }
+            metrics.connected();
+            health.connected();
+            stream
+        }
+        Err(error) => {
+            error!(parent: &span, %exchange, ?error, "failed to initialise MarketStream");
+            if let Some(notifier) = notifier.as_mut() {
+                notifier.failed(&error);
+            }
+            return error;
+        }
+    };
+
+    // Fetch & distribute the backfill for each Subscription, recording each backfill tail
+    let mut watermarks = HashMap::with_capacity(subscriptions.len());
+    for subscription in &subscriptions {
+        match Exchange::backfill(subscription, limit, &config).await {
+            Ok(mut events) => {
+                events.sort_by_key(|event| event.exchange_time);
+                watermarks.insert(
+                    subscription.instrument.clone(),
+                    BackfillWatermark::from(events.as_slice()),
+                );
+
+                for event in events {
+                    if filter.as_ref().is_none_or(|filter| filter(&event))
+                        && exchange_tx.send(event).is_err()
+                    {
+                        return consumer_dropped(&span, exchange);
+                    }
+                }
+            }
+            Err(error) => {
+                warn!(
+                    %exchange,
+                    instrument = %subscription.instrument,
+                    %error,
+                    action = "continuing with live events only",
+                    "failed to backfill Subscription",
+                );
+            }
+        }
+    }
+
+    // Distribute live events, dropping any that overlap the backfill tail
+    let stream = stream.inspect(|event| {
+        metrics.received(event);
+        health.received(event);
+        if let Ok(event) = event {
+            latency.record(event.exchange_time, event.received_time);
+        }
+        if let Some(notifier) = notifier.as_mut() {
+            notifier.observe(event);
+        }
+    });
+    let mut stream = stream.filter(move |event_result| {
+        let overlaps = match event_result {
+            Ok(event) => match watermarks.get(&event.instrument) {
+                Some(watermark) if watermark.is_passed_by(event) => {
+                    watermarks.remove(&event.instrument);
+                    false
+                }
+                Some(watermark) => watermark.overlaps(event),
+                None => false,
+            },
+            Err(_) => false,
+        };
+
+        std::future::ready(!overlaps)
+    });
+    let terminal_error = distribute(
+        exchange,
+        &mut stream,
+        &exchange_tx,
+        filter.as_ref(),
+        limiter.as_mut(),
+        &metrics,
+        &markets,
+    )
+    .instrument(span.clone())
+    .await;
+    drop(stream);
+    latency.publish();
+    metrics.disconnected();
+    health.disconnected();
+    log_disconnect(&span, exchange, terminal_error.as_ref());
+
+    // If the receiver was dropped, drop the MarketStream (closing it's connection) & exit
+    if let Some(DataError::ConsumerDropped(_)) = terminal_error {
+        return consumer_dropped(&span, exchange);
+    }
+    metrics.reconnecting();
+    health.reconnecting();
+
+    // Initial MarketStream ended, so continue with the standard re-connecting consumer loop
+    warn!(
+        parent: &span,
+        %exchange,
+        backoff_ms = STARTING_RECONNECT_BACKOFF_MS,
+        action = "attempt re-connection after backoff",
+        "exchange MarketStream unexpectedly ended"
+    );
+    if shutdown::sleep_or_closed(
+        Duration::from_millis(STARTING_RECONNECT_BACKOFF_MS),
+        exchange_tx.tx(),
+    )
+    .await
+    {
+        return consumer_dropped(&span, exchange);
+    }
+    consume_streams(
+        subscriptions,
+        |subscriptions, config| Box::pin(Exchange::Stream::init_with(subscriptions, config)),
+        exchange_tx,
+        filter,
+        callbacks,
+        health,
+        latency,
+        config,
+        true,
+    )
+    .await
+}
+
+/// Distribute every [`MarketEvent<T>`](MarketEvent) consumed from the provided stream that
+/// matches the optional [`EventFilter`] to the `exchange_tx`, at the rate allowed by the optional
+/// [`InboundLimiter`]. Returns once the stream ends or yields a terminal [`DataError`] (which is
+/// returned), leaving any events held back by the [`InboundLimiter`] for the next connection.
+///
+/// If the `exchange_tx` receiver is dropped a [`DataError::ConsumerDropped`] is returned, even
+/// whilst the stream is quiet.
+async fn distribute<St, T, Meta>(
+    exchange: ExchangeId,
+    stream: &mut St,
+    exchange_tx: &EventSender<T, Meta>,
+    filter: Option<&EventFilter<T>>,
+    mut limiter: Option<&mut InboundLimiter<T>>,
+    metrics: &StreamMetrics,
+    markets: &MarketInstruments,
+) -> Option<DataError>
+where
+    St: Stream<Item = Result<MarketEvent<T>, DataError>> + Unpin,
+    T: std::fmt::Debug,
+    Meta: EventMeta,
+{
+    loop {
+        // Consume the next event, releasing any held back events as the InboundLimiter allows
+        let next_release = limiter.as_deref().and_then(InboundLimiter::next_release);
+        let event_result = match (next_release, limiter.as_deref_mut()) {
+            (Some(next_release), Some(limiter)) => tokio::select! {
+                biased;
+                _ = exchange_tx.tx().closed() => Next::Closed,
+                _ = tokio::time::sleep_until(next_release) => {
+                    while let Some(market_event) = limiter.release() {
+                        if let Err(error) = send(exchange, exchange_tx, metrics, market_event) {
+                            return Some(error);
+                        }
+                    }
+                    continue;
+                }
+                event_result = stream.next() => Next::from(event_result),
+            },
+            _ => shutdown::next_or_closed(stream, exchange_tx.tx()).await,
+        };
+
+        let event_result = match event_result {
+            Next::Item(event_result) => event_result,
+            Next::Ended => break,
+            Next::Closed => return Some(DataError::ConsumerDropped(exchange)),
+        };
+
+        match event_result {
+            // If Ok & filtered out: drop MarketEvent<T>
+            Ok(market_event) if filter.is_some_and(|filter| !filter(&market_event)) => continue,
+
+            // If Ok: send MarketEvent<T> to exchange receiver if the InboundLimiter allows
+            Ok(market_event) => {
+                let sent = match limiter.as_deref_mut() {
+                    Some(limiter) => match limiter.admit(market_event) {
+                        Admission::Emit(market_event) => {
+                            send(exchange, exchange_tx, metrics, market_event)
+                        }
+                        admission => {
+                            metrics.throttled(&admission);
+                            Ok(())
+                        }
+                    },
+                    None => send(exchange, exchange_tx, metrics, market_event),
+                };
+
+                if let Err(error) = sent {
+                    return Some(error);
+                }
+            }
+
+            // If terminal DataError: break
+            Err(error) if error.is_terminal() => {
+                error!(
+                    %exchange,
+                    %error,
+                    action = "re-initialising Stream",
+                    "consumed DataError from MarketStream",
+                );
+                return Some(error);
+            }
+
+            // If an OrderBook is re-initialising in isolation: log & continue with the others
+            Err(error @ DataError::InstrumentResync { .. }) => {
+                warn!(
+                    %exchange,
+                    %error,
+                    action = "re-initialising Instrument",
+                    "consumed DataError from MarketStream",
+                );
+                continue;
+            }
+
+            // If exchange payload failed to parse: log with the Instrument if resolvable & continue
+            Err(DataError::Socket(SocketError::Deserialise { error, payload })) => {
+                warn!(
+                    %exchange,
+                    instrument = markets.resolve(&payload).map(tracing::field::display),
+                    %error,
+                    payload = truncate_payload(&payload),
+                    action = "skipping message",
+                    "failed to parse exchange message",
+                );
+                continue;
+            }
+
+            // If non-terminal DataError: log & continue
+            Err(error) => {
+                warn!(
+                    %exchange,
+                    %error,
+                    action = "skipping message",
+                    "consumed DataError from MarketStream",
+                );
+                continue;
+            }
+        }
+    }
+
+    None
+}
+
+/// Send the [`MarketEvent<T>`](MarketEvent) to the exchange receiver, returning a
+/// [`DataError::ConsumerDropped`] if the receiver has been dropped.
+#[allow(clippy::result_large_err)]
+fn send<T, Meta>(
+    exchange: ExchangeId,
+    exchange_tx: &EventSender<T, Meta>,
+    metrics: &StreamMetrics,
+    market_event: MarketEvent<T>,
+) -> Result<(), DataError>
+where
+    T: std::fmt::Debug,
+    Meta: EventMeta,
+{
+    metrics.emitted(&market_event);
+    exchange_tx.send(market_event).map_err(|err| {
+        metrics.dropped();
+        debug!(
+            %exchange,
+            payload = ?err.0,
+            why = "receiver dropped",
+            "failed to send Event<MarketData> to Exchange receiver"
+        );
+        DataError::ConsumerDropped(exchange)
+    })
+}
+
+/// Log that the consumer loop is shutting down since it's receiver was dropped, returning the
+/// associated [`DataError::ConsumerDropped`].
+fn consumer_dropped(span: &Span, exchange: ExchangeId) -> DataError {
+    info!(
+        parent: span,
+        %exchange,
+        action = "closing connection & exiting consumer loop",
+        "MarketEvent receiver dropped"
+    );
+    DataError::ConsumerDropped(exchange)
+}
+
+/// Construct the tracing [`Span`] of a new [`MarketStream`] connection, identified by a unique
+/// `connection_id` alongside the `exchange`, `sub_kind` & any [`ConnectionTicket`].
+fn connection_span<Kind>(exchange: ExchangeId, ticket: Option<&ConnectionTicket>) -> Span {
+    info_span!(
+        "connection",
+        %exchange,
+        sub_kind = %sub_kind_name::<Kind>(),
+        connection_id = CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+        ticket = ticket.map(ConnectionTicket::as_str),
+    )
+}
+
+/// Log the reason a [`MarketStream`] connection ended within it's connection [`Span`].
+fn log_disconnect(span: &Span, exchange: ExchangeId, terminal_error: Option<&DataError>) {
+    match terminal_error {
+        Some(error) => {
+            debug!(parent: span, %exchange, reason = %error, "MarketStream disconnected")
+        }
+        None => debug!(
+            parent: span,
+            %exchange,
+            reason = "exchange stream ended",
+            "MarketStream disconnected"
+        ),
+    }
+}
+
+/// Truncate an exchange payload to at most [`MAX_LOGGED_PAYLOAD_CHARS`] characters for logging.
+fn truncate_payload(payload: &str) -> &str {
+    match payload.char_indices().nth(MAX_LOGGED_PAYLOAD_CHARS) {
+        Some((index, _)) => &payload[..index],
+        None => payload,
+    }
+}
+
+/// Exchange specific markets (eg/ "btcusdt") of the consumed [`Subscription`]s and their
+/// associated [`Instrument`], used to resolve the [`Instrument`] an unparseable payload relates to.
+#[derive(Debug, Default)]
+struct MarketInstruments(Vec<(String, Instrument)>);
+
+impl MarketInstruments {
+    fn new<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> Self
+    where
+        Exchange: Connector,
+        Subscription<Exchange, Kind>: Identifier<Exchange::Market>,
+    {
+        let mut markets = subscriptions
+            .iter()
+            .map(|subscription| {
+                let market = Identifier::<Exchange::Market>::id(subscription);
+                (
+                    market.as_ref().to_lowercase(),
+                    subscription.instrument.clone(),
+                )
+            })
+            .filter(|(market, _)| !market.is_empty())
+            .collect::<Vec<_>>();
+
+        // Match longest markets first, so eg/ "btcusdt" is preferred over "btcusd"
+        markets.sort_by_key(|(market, _)| std::cmp::Reverse(market.len()));
+        Self(markets)
+    }
+
+    /// Find the [`Instrument`] whose market is referenced by the payload, if any.
+    fn resolve(&self, payload: &str) -> Option<&Instrument> {
+        let payload = payload.to_lowercase();
+        self.0
+            .iter()
+            .find(|(market, _)| payload.contains(market.as_str()))
+            .map(|(_, instrument)| instrument)
+    }
+}
+
+/// Construct the [`StreamMetrics`] of a consumer loop, recording to the global
+/// [`MetricsRegistry`].
+#[cfg(feature = "metrics")]
+fn stream_metrics<Kind>(exchange: ExchangeId, subscriptions: usize) -> StreamMetrics {
+    StreamMetrics::new::<Kind>(
+        Arc::clone(MetricsRegistry::global()),
+        exchange,
+        subscriptions,
+    )
+}
+
+#[cfg(not(feature = "metrics"))]
+#[allow(clippy::extra_unused_type_parameters)]
+fn stream_metrics<Kind>(_: ExchangeId, _: usize) -> StreamMetrics {
+    StreamMetrics
+}
+
+/// No-op stand-in for the `StreamMetrics` recorded when the `metrics` feature is enabled.
+#[cfg(not(feature = "metrics"))]
+#[derive(Debug)]
+struct StreamMetrics;
+
+#[cfg(not(feature = "metrics"))]
+impl StreamMetrics {
+    fn connected(&self) {}
+    fn disconnected(&self) {}
+    fn reconnecting(&self) {}
+    fn received<T>(&self, _: &Result<MarketEvent<T>, DataError>) {}
+    fn emitted<T>(&self, _: &MarketEvent<T>) {}
+    fn dropped(&self) {}
+    fn throttled<T>(&self, _: &Admission<T>) {}
+    fn track_latency(&self, _: &LatencyTracker) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        num,
+        num::Num,
+        streams::{health::HealthConfig, latency::LatencyStats},
+        subscription::trade::{PublicTrade, PublicTrades},
+        test_util::trade::TradeFixture,
+    };
+    use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+    use chrono::{DateTime, Utc};
+
+    fn trade(base: &str, price: Num, amount: Num) -> MarketEvent<PublicTrade> {
+        let mut trade = TradeFixture::new().trade(0, base, price, amount, Side::Buy);
+        trade.kind.id = format!("{base}-{price}");
+        trade
+    }
+
+    #[tokio::test]
+    async fn test_distribute_with_filter() {
+        let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+        let exchange_tx = EventSender::from(exchange_tx);
+        let whales: EventFilter<PublicTrade> =
+            Arc::new(|event| event.kind.price * event.kind.amount >= num!(10_000));
+
+        let mut stream = futures::stream::iter(vec![
+            Ok(trade("btc", num!(20_000), num!(0.1))),
+            Ok(trade("btc", num!(20_000), num!(1))),
+            Err(DataError::Socket(
+                barter_integration::error::SocketError::Sink,
+            )),
+            Ok(trade("eth", num!(1_500), num!(10))),
+            Ok(trade("eth", num!(1_500), num!(1))),
+        ]);
+
+        distribute(
+            ExchangeId::BinanceSpot,
+            &mut stream,
+            &exchange_tx,
+            Some(&whales),
+            None,
+            &stream_metrics::<PublicTrades>(ExchangeId::BinanceSpot, 1),
+            &MarketInstruments::default(),
+        )
+        .await;
+        drop(exchange_tx);
+
+        let mut actual = Vec::new();
+        while let Some(event) = exchange_rx.recv().await {
+            actual.push(event.kind.id);
+        }
+
+        assert_eq!(actual, vec!["btc-20000", "eth-1500"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_distribute_with_inbound_throttle() {
+        use crate::streams::inbound::{InboundThrottle, OverflowPolicy};
+
+        struct TestCase {
+            throttle: InboundThrottle,
+            expected: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: Drop policy distributes the burst & drops the rest
+                throttle: InboundThrottle::new(2),
+                expected: vec!["btc-1", "btc-2"],
+            },
+            TestCase {
+                // TC1: Coalesce policy later distributes the latest event of each Instrument
+                throttle: InboundThrottle::new(2).with_overflow(OverflowPolicy::Coalesce),
+                expected: vec!["btc-1", "btc-2", "btc-5", "eth-4"],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+            let exchange_tx = EventSender::from(exchange_tx);
+            let mut limiter = InboundLimiter::new(test.throttle);
+
+            // MarketStream bursts events & then stays connected
+            let mut stream = futures::stream::iter(vec![
+                Ok(trade("btc", num!(1), num!(1))),
+                Ok(trade("btc", num!(2), num!(1))),
+                Ok(trade("btc", num!(3), num!(1))),
+                Ok(trade("eth", num!(4), num!(1))),
+                Ok(trade("btc", num!(5), num!(1))),
+            ])
+            .chain(futures::stream::pending());
+
+            let _ = tokio::time::timeout(
+                Duration::from_secs(5),
+                distribute(
+                    ExchangeId::BinanceSpot,
+                    &mut stream,
+                    &exchange_tx,
+                    None,
+                    Some(&mut limiter),
+                    &stream_metrics::<PublicTrades>(ExchangeId::BinanceSpot, 1),
+                    &MarketInstruments::default(),
+                ),
+            )
+            .await;
+
+            let mut actual = Vec::new();
+            while let Ok(event) = exchange_rx.try_recv() {
+                actual.push(event.kind.id);
+            }
+
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn test_distribute_records_metrics() {
+        use crate::metrics::{DROPPED_EVENTS, EVENTS_EMITTED, MESSAGES_RECEIVED, PARSE_ERRORS};
+
+        let registry = Arc::new(MetricsRegistry::default());
+        let metrics =
+            StreamMetrics::new::<PublicTrades>(Arc::clone(&registry), ExchangeId::BinanceSpot, 1);
+        let labels = [("exchange", "binance_spot"), ("kind", "PublicTrades")];
+
+        let (exchange_tx, exchange_rx) = mpsc::unbounded_channel();
+        let exchange_tx = EventSender::from(exchange_tx);
+        let mut stream = futures::stream::iter(vec![
+            Ok(trade("btc", num!(20_000), num!(0.1))),
+            Err(DataError::Socket(
+                barter_integration::error::SocketError::Sink,
+            )),
+            Ok(trade("eth", num!(1_500), num!(10))),
+        ])
+        .inspect(|event| metrics.received(event));
+
+        distribute(
+            ExchangeId::BinanceSpot,
+            &mut stream,
+            &exchange_tx,
+            None,
+            None,
+            &metrics,
+            &MarketInstruments::default(),
+        )
+        .await;
+
+        assert_eq!(registry.counter(MESSAGES_RECEIVED, &labels).get(), 3);
+        assert_eq!(registry.counter(PARSE_ERRORS, &labels).get(), 1);
+        assert_eq!(registry.counter(EVENTS_EMITTED, &labels).get(), 2);
+        assert_eq!(registry.counter(DROPPED_EVENTS, &labels).get(), 0);
+
+        // Once the receiver is dropped no further events are consumed, so none are dropped
+        drop(exchange_rx);
+        let mut stream = futures::stream::iter(vec![Ok(trade("btc", num!(20_000), num!(0.1)))]);
+        let terminal_error = distribute(
+            ExchangeId::BinanceSpot,
+            &mut stream,
+            &exchange_tx,
+            None,
+            None,
+            &metrics,
+            &MarketInstruments::default(),
+        )
+        .await;
+
+        assert!(matches!(
+            terminal_error,
+            Some(DataError::ConsumerDropped(ExchangeId::BinanceSpot))
+        ));
+        assert_eq!(registry.counter(DROPPED_EVENTS, &labels).get(), 0);
+        assert!(stream.next().await.is_some());
+
+        // An event consumed before the receiver was dropped is counted as dropped
+        let actual = send(
+            ExchangeId::BinanceSpot,
+            &exchange_tx,
+            &metrics,
+            trade("btc", num!(20_000), num!(0.1)),
+        );
+        assert!(matches!(
+            actual,
+            Err(DataError::ConsumerDropped(ExchangeId::BinanceSpot))
+        ));
+        assert_eq!(registry.counter(DROPPED_EVENTS, &labels).get(), 1);
+    }
+
+    #[cfg(feature = "binance")]
+    #[tokio::test]
+    async fn test_subscription_notifier() {
+        use crate::{exchange::binance::spot::BinanceSpot, subscription::trade::PublicTrades};
+
+        let subscriptions = vec![
+            Subscription::from((
+                BinanceSpot::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )),
+            Subscription::from((
+                BinanceSpot::default(),
+                "eth",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )),
+        ];
+
+        let (outcome_tx, mut outcome_rx) = mpsc::unbounded_channel();
+        let live_tx = outcome_tx.clone();
+        let callbacks = SubscriptionCallbacks {
+            on_live: Some(Arc::new(
+                move |exchange, instrument: &Instrument, _: &PublicTrades| {
+                    let _ = live_tx.send(format!("live {exchange} {}", instrument.base));
+                },
+            )),
+            on_failed: Some(Arc::new(
+                move |exchange, instrument: &Instrument, _: &PublicTrades, reason: &str| {
+                    let _ =
+                        outcome_tx.send(format!("failed {exchange} {} {reason}", instrument.base));
+                },
+            )),
+        };
+
+        let mut notifier = SubscriptionNotifier::spawn(&subscriptions, callbacks);
+
+        // Connection fails to initialise, so every Subscription fails
+        notifier.failed(&DataError::Socket(
+            barter_integration::error::SocketError::Subscribe("timeout".to_string()),
+        ));
+        let mut failed = vec![
+            outcome_rx.recv().await.unwrap(),
+            outcome_rx.recv().await.unwrap(),
+        ];
+        failed.sort();
+        assert_eq!(
+            failed,
+            vec![
+                "failed binance_spot btc SocketError: error subscribing to resources over the socket: timeout",
+                "failed binance_spot eth SocketError: error subscribing to resources over the socket: timeout",
+            ]
+        );
+
+        // Connection initialised, so each Subscription is live on the first event only
+        notifier.connected();
+        notifier.observe(&Ok(trade("btc", num!(20_000), num!(1))));
+        notifier.observe(&Ok(trade("btc", num!(20_000), num!(2))));
+        notifier.observe::<PublicTrade>(&Err(DataError::Socket(
+            barter_integration::error::SocketError::Sink,
+        )));
+        notifier.observe(&Ok(trade("eth", num!(1_500), num!(1))));
+        assert_eq!(outcome_rx.recv().await.unwrap(), "live binance_spot btc");
+        assert_eq!(outcome_rx.recv().await.unwrap(), "live binance_spot eth");
+
+        // Re-connection notifies again
+        notifier.connected();
+        notifier.observe(&Ok(trade("eth", num!(1_500), num!(1))));
+        assert_eq!(outcome_rx.recv().await.unwrap(), "live binance_spot eth");
+        assert!(outcome_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_market_instruments_resolve() {
+        struct TestCase {
+            payload: &'static str,
+            expected: Option<Instrument>,
+        }
+
+        let markets = MarketInstruments(vec![
+            (
+                "btcusdt".to_string(),
+                Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            ),
+            (
+                "btcusd".to_string(),
+                Instrument::from(("btc", "usd", InstrumentKind::Spot)),
+            ),
+        ]);
+
+        let tests = vec![
+            TestCase {
+                // TC0: market is matched case insensitively
+                payload: r#"{"s":"BTCUSD","p":"invalid"}"#,
+                expected: Some(Instrument::from(("btc", "usd", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC1: longest market is preferred
+                payload: r#"{"s":"BTCUSDT","p":"invalid"}"#,
+                expected: Some(Instrument::from(("btc", "usdt", InstrumentKind::Spot))),
+            },
+            TestCase {
+                // TC2: unresolvable payload
+                payload: r#"{"s":"ETHUSDT","p":"invalid"}"#,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = markets.resolve(test.payload);
+            assert_eq!(actual, test.expected.as_ref(), "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_truncate_payload() {
+        assert_eq!(truncate_payload("short"), "short");
+
+        let long = "é".repeat(MAX_LOGGED_PAYLOAD_CHARS + 1);
+        assert_eq!(
+            truncate_payload(&long).chars().count(),
+            MAX_LOGGED_PAYLOAD_CHARS
+        );
+    }
+
+    mod mock {
+        use super::*;
+        use crate::{
+            event::MarketIter,
+            exchange::{subscription::ExchangeSub, Connector, StreamSelector},
+            subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
+            transformer::stateless::StatelessTransformer,
+            ExchangeWsStream,
+        };
+        use barter_integration::{
+            model::SubscriptionId, protocol::websocket::WsMessage, Validator,
+        };
+        use serde::{Deserialize, Serialize};
+        use std::sync::OnceLock;
+        use url::Url;
+
+        /// Url of the local mock exchange server.
+        pub static MOCK_URL: OnceLock<Url> = OnceLock::new();
+
+        /// Mock exchange [`Connector`] served locally, borrowing the [`ExchangeId::Okx`].
+        #[derive(Copy, Clone, Default, Debug, Deserialize, Serialize)]
+        pub struct MockExchange;
+
+        #[derive(Copy, Clone, Debug)]
+        pub struct MockChannel;
+
+        impl AsRef<str> for MockChannel {
+            fn as_ref(&self) -> &str {
+                "trades"
+            }
+        }
+
+        #[derive(Clone, Debug)]
+        pub struct MockMarket(String);
+
+        impl AsRef<str> for MockMarket {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Identifier<MockChannel> for Subscription<MockExchange, PublicTrades> {
+            fn id(&self) -> MockChannel {
+                MockChannel
+            }
+        }
+
+        impl Identifier<MockMarket> for Subscription<MockExchange, PublicTrades> {
+            fn id(&self) -> MockMarket {
+                MockMarket(format!(
+                    "{}_{}",
+                    self.instrument.base, self.instrument.quote
+                ))
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct MockSubResponse {
+            result: String,
+        }
+
+        impl Validator for MockSubResponse {
+            fn validate(self) -> Result<Self, SocketError> {
+                match self.result.as_str() {
+                    "ok" => Ok(self),
+                    _ => Err(SocketError::Subscribe(self.result)),
+                }
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct MockTrade {
+            id: SubscriptionId,
+            price: Num,
+            amount: Num,
+        }
+
+        impl Identifier<Option<SubscriptionId>> for MockTrade {
+            fn id(&self) -> Option<SubscriptionId> {
+                Some(self.id.clone())
+            }
+        }
+
+        impl From<(ExchangeId, Instrument, DateTime<Utc>, MockTrade)> for MarketIter<PublicTrade> {
+            fn from(
+                (exchange_id, instrument, received_time, trade): (
+                    ExchangeId,
+                    Instrument,
+                    DateTime<Utc>,
+                    MockTrade,
+                ),
+            ) -> Self {
+                Self(smallvec::smallvec![Ok(MarketEvent {
+                    exchange_time: Utc::now(),
+                    raw_exchange_time: None,
+                    received_time,
+                    exchange: Exchange::from(exchange_id),
+                    instrument,
+                    kind: PublicTrade {
+                        id: trade.id.to_string(),
+                        price: trade.price,
+                        amount: trade.amount,
+                        side: Side::Buy,
+                    },
+                    meta: (),
+                })])
+            }
+        }
+
+        impl Connector for MockExchange {
+            const ID: ExchangeId = ExchangeId::Okx;
+            type Channel = MockChannel;
+            type Market = MockMarket;
+            type Subscriber = WebSocketSubscriber;
+            type SubValidator = WebSocketSubValidator;
+            type SubResponse = MockSubResponse;
+
+            fn url() -> Result<Url, SocketError> {
+                Ok(MOCK_URL.get().unwrap().clone())
+            }
+
+            fn requests(
+                exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+            ) -> Vec<WsMessage> {
+                exchange_subs
+                    .into_iter()
+                    .map(|sub| WsMessage::Text(sub.id().to_string()))
+                    .collect()
+            }
+        }
+
+        impl StreamSelector<PublicTrades> for MockExchange {
+            type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, MockTrade>>;
+        }
+    }
+
+    /// [`std::io::Write`] that captures formatted tracing output for assertions.
+    #[derive(Debug)]
+    struct LogWriter(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_traces_connection_lifecycle() {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let logs = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let writer_logs = Arc::clone(&logs);
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::DEBUG)
+                .with_ansi(false)
+                .with_writer(move || LogWriter(Arc::clone(&writer_logs)))
+                .finish(),
+        );
+
+        // Mock exchange server: validates the subscription, streams a trade & an unparseable
+        // payload, then disconnects
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        mock::MOCK_URL.set(url::Url::parse(&url).unwrap()).unwrap();
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let subscription = websocket.next().await.unwrap().unwrap();
+            assert_eq!(subscription.to_text().unwrap(), "trades|btc_usdt");
+
+            for message in [
+                r#"{"result":"ok"}"#,
+                r#"{"id":"trades|btc_usdt","price":20000.0,"amount":1.0}"#,
+                r#"{"id":"trades|btc_usdt","price":"invalid","amount":1.0}"#,
+            ] {
+                websocket
+                    .send(Message::Text(message.to_string()))
+                    .await
+                    .unwrap();
+            }
+            websocket.close(None).await.unwrap();
+        });
+
+        let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+        let exchange_tx = EventSender::from(exchange_tx);
+        let health = Arc::new(ConnectionHealth::new::<PublicTrades>(
+            ExchangeId::Okx,
+            1,
+            HealthConfig::default(),
+        ));
+        let latency = LatencyStats::default();
+        let consumer = tokio::spawn(consume(
+            vec![Subscription::from((
+                mock::MockExchange,
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            ))],
+            exchange_tx,
+            None,
+            None,
+            Arc::clone(&health),
+            latency.tracker(ExchangeId::Okx, sub_kind_name::<PublicTrades>()),
+            StreamConfig::default(),
+        ));
+
+        let trade = exchange_rx.recv().await.unwrap();
+        assert_eq!(trade.kind.price, num!(20_000));
+        server.await.unwrap();
+
+        // Mock exchange server is gone, so re-connecting fails & the consumer loop ends
+        let error = tokio::time::timeout(Duration::from_secs(5), consumer)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(error, DataError::Socket(_)));
+
+        // Health reflects the unparseable payload & disconnection
+        let status = health.status();
+        assert!(!status.connected);
+        assert_eq!(status.score, 0);
+        assert_eq!(status.reconnects, 1);
+        // Received the trade, the unparseable payload & the CloseFrame
+        assert_eq!(status.decode_error_rate, 1.0 / 3.0);
+
+        // Latency of the trade is published to the LatencyStats of this consumer loop only
+        let snapshot = latency.snapshot::<PublicTrades>(ExchangeId::Okx).unwrap();
+        assert_eq!((snapshot.observed, snapshot.samples), (1, 1));
+
+        let logs = String::from_utf8(logs.lock().unwrap().clone()).unwrap();
+        let lines = logs.lines().collect::<Vec<_>>();
+        let find = |span: &str, message: &str| {
+            lines
+                .iter()
+                .find(|line| line.contains(span) && line.contains(message))
+                .unwrap_or_else(|| panic!("missing {span} {message:?} in logs:\n{logs}"))
+        };
+
+        let connection = "connection{exchange=okx sub_kind=PublicTrades connection_id=";
+        find(connection, "attempting to initialise MarketStream");
+        find(":subscribe: ", "connecting to WebSocket");
+        find(":subscribe: ", "connected to WebSocket");
+        find(
+            ":subscribe:validate: ",
+            "validating exchange WebSocket subscriptions",
+        );
+        find(
+            ":subscribe:validate: ",
+            "validated exchange WebSocket subscriptions",
+        );
+        find(":subscribe: ", "subscribed to WebSocket");
+        find(connection, "initialised MarketStream transformer");
+
+        let parse_failure = find(connection, "failed to parse exchange message");
+        assert!(parse_failure.contains("WARN"));
+        assert!(parse_failure.contains("instrument=(btc_usdt, spot)"));
+        assert!(
+            parse_failure.contains(r#"payload="{\"id\":\"trades|btc_usdt\",\"price\":\"invalid\""#)
+        );
+
+        find(connection, "MarketStream disconnected");
+        find(connection, "failed to initialise MarketStream");
+    }
+
+    #[tokio::test]
+    async fn test_consume_buffers_until_validated() {
+        use crate::{exchange::endpoint::Endpoints, subscriber::transport::WsConfig};
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        struct TestCase {
+            buffer_until_validated: Option<usize>,
+            expected: Vec<(&'static str, Num)>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: default discards market data received before every sub is validated
+                buffer_until_validated: None,
+                expected: vec![("btc", num!(3))],
+            },
+            TestCase {
+                // TC1: buffered market data is distributed in order once validated
+                buffer_until_validated: Some(10),
+                expected: vec![("btc", num!(1)), ("eth", num!(2)), ("btc", num!(3))],
+            },
+            TestCase {
+                // TC2: full buffer drops the oldest market data
+                buffer_until_validated: Some(1),
+                expected: vec![("eth", num!(2)), ("btc", num!(3))],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            // Mock exchange server: interleaves market data of the first validated
+            // subscription with the subscription responses
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("ws://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                for _ in 0..2 {
+                    websocket.next().await.unwrap().unwrap();
+                }
+
+                for message in [
+                    r#"{"result":"ok"}"#,
+                    r#"{"id":"trades|btc_usdt","price":1.0,"amount":1.0}"#,
+                    r#"{"id":"trades|eth_usdt","price":2.0,"amount":1.0}"#,
+                    r#"{"result":"ok"}"#,
+                    r#"{"id":"trades|btc_usdt","price":3.0,"amount":1.0}"#,
+                ] {
+                    websocket
+                        .send(Message::Text(message.to_string()))
+                        .await
+                        .unwrap();
+                }
+
+                // Hold the connection open until the consumer is done
+                while websocket.next().await.is_some() {}
+            });
+
+            let config = StreamConfig {
+                ws: WsConfig::new().with_buffer_until_validated(test.buffer_until_validated),
+                endpoints: Endpoints {
+                    websocket: Some(url::Url::parse(&url).unwrap()),
+                    rest: None,
+                },
+                ..StreamConfig::default()
+            };
+
+            let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+            let exchange_tx = EventSender::from(exchange_tx);
+            let consumer = tokio::spawn(consume(
+                vec![
+                    Subscription::from((
+                        mock::MockExchange,
+                        "btc",
+                        "usdt",
+                        InstrumentKind::Spot,
+                        PublicTrades,
+                    )),
+                    Subscription::from((
+                        mock::MockExchange,
+                        "eth",
+                        "usdt",
+                        InstrumentKind::Spot,
+                        PublicTrades,
+                    )),
+                ],
+                exchange_tx,
+                None,
+                None,
+                Arc::new(ConnectionHealth::new::<PublicTrades>(
+                    ExchangeId::Okx,
+                    2,
+                    HealthConfig::default(),
+                )),
+                LatencyTracker::default(),
+                config,
+            ));
+
+            let mut actual = Vec::with_capacity(test.expected.len());
+            while actual.len() < test.expected.len() {
+                let trade = tokio::time::timeout(Duration::from_secs(5), exchange_rx.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                actual.push((trade.instrument.base.to_string(), trade.kind.price));
+            }
+            consumer.abort();
+
+            let expected = test
+                .expected
+                .into_iter()
+                .map(|(base, price)| (base.to_string(), price))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
     }
 }
@@ -0,0 +1,606 @@
+use crate::{error::DataError, event::MarketEvent, exchange::ExchangeId};
+use barter_integration::error::SocketError;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Default rolling window over which the reconnects, decode errors & OrderBook integrity
+/// failures of a connection affect it's health score.
+pub const DEFAULT_HEALTH_WINDOW: Duration = Duration::from_secs(300);
+
+/// Default duration without a message after which a connected connection is considered stale.
+pub const DEFAULT_STALE_AFTER: Duration = Duration::from_secs(30);
+
+/// Weights of each signal deducted from the perfect health score of 100, see [`HealthConfig`].
+///
+/// | Signal (within the rolling window)     | Default penalty                |
+/// |----------------------------------------|--------------------------------|
+/// | Each reconnect                         | 15 points                      |
+/// | Each percentage point of decode errors | 5 points (eg/ 2% errors = 10)  |
+/// | Stale (no message for `stale_after`)   | 50 points                      |
+/// | Each OrderBook integrity failure       | 20 points                      |
+///
+/// OrderBook integrity failures are OrderBook updates that fail sequence (or checksum)
+/// verification, ie/ a [`DataError::InvalidSequence`], or the
+/// [`DataError::InstrumentResync`] of an OrderBook re-initialised in isolation. The score saturates at 0, and a
+/// disconnected connection always scores 0.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HealthWeights {
+    pub reconnect: u32,
+    pub decode_error_percent: u32,
+    pub stale: u32,
+    pub integrity_failure: u32,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            reconnect: 15,
+            decode_error_percent: 5,
+            stale: 50,
+            integrity_failure: 20,
+        }
+    }
+}
+
+/// Configuration of the health score computed for every connection, see [`ConnectionStatus`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HealthConfig {
+    /// Rolling window over which reconnects, decode errors & integrity failures are counted.
+    pub window: Duration,
+    /// Duration without a message after which a connected connection is considered stale.
+    pub stale_after: Duration,
+    pub weights: HealthWeights,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_HEALTH_WINDOW,
+            stale_after: DEFAULT_STALE_AFTER,
+            weights: HealthWeights::default(),
+        }
+    }
+}
+
+/// Point in time health of a connection (ie/ consumer loop), see
+/// [`Streams::status`](super::Streams::status).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ConnectionStatus {
+    pub exchange: ExchangeId,
+    /// [`SubKind`](crate::subscription::SubKind) name, eg/ "PublicTrades".
+    pub kind: &'static str,
+    pub subscriptions: usize,
+    pub connected: bool,
+    /// Health score from 0 (unhealthy) to 100 (healthy), see [`HealthWeights`].
+    pub score: u8,
+    /// Number of reconnects within the rolling window.
+    pub reconnects: usize,
+    /// Fraction of messages (events & errors) received within the rolling window that failed to
+    /// deserialise.
+    pub decode_error_rate: f64,
+    /// Duration since the last message was received (or connecting), `None` if disconnected.
+    pub idle: Option<Duration>,
+    /// Number of OrderBook integrity failures within the rolling window.
+    pub integrity_failures: usize,
+}
+
+/// Health signals of a connection (ie/ consumer loop), recorded by the consumer loop from the
+/// same events observed by the `metrics` feature.
+#[derive(Debug)]
+pub struct ConnectionHealth {
+    pub exchange: ExchangeId,
+    pub kind: &'static str,
+    pub subscriptions: usize,
+    config: HealthConfig,
+    state: Mutex<HealthState>,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    connected: bool,
+    last_message: Option<Instant>,
+    reconnects: VecDeque<Instant>,
+    integrity_failures: VecDeque<Instant>,
+    messages: RollingCount,
+    errors: RollingCount,
+}
+
+impl ConnectionHealth {
+    /// Construct a new [`Self`] for a disconnected connection.
+    pub fn new<Kind>(exchange: ExchangeId, subscriptions: usize, config: HealthConfig) -> Self {
+        Self::new_at::<Kind>(exchange, subscriptions, config, Instant::now())
+    }
+
+    fn new_at<Kind>(
+        exchange: ExchangeId,
+        subscriptions: usize,
+        config: HealthConfig,
+        now: Instant,
+    ) -> Self {
+        Self {
+            exchange,
+            kind: crate::subscription::sub_kind_name::<Kind>(),
+            subscriptions,
+            config,
+            state: Mutex::new(HealthState {
+                connected: false,
+                last_message: None,
+                reconnects: VecDeque::new(),
+                integrity_failures: VecDeque::new(),
+                messages: RollingCount::new(config.window, now),
+                errors: RollingCount::new(config.window, now),
+            }),
+        }
+    }
+
+    pub(crate) fn connected(&self) {
+        self.connected_at(Instant::now())
+    }
+
+    fn connected_at(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.connected = true;
+        state.last_message = Some(now);
+    }
+
+    pub(crate) fn disconnected(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.connected = false;
+        state.last_message = None;
+    }
+
+    pub(crate) fn reconnecting(&self) {
+        self.reconnecting_at(Instant::now())
+    }
+
+    fn reconnecting_at(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.reconnects.push_back(now);
+        prune(&mut state.reconnects, self.config.window, now);
+    }
+
+    /// Record a heartbeat received on this connection, refreshing it's staleness without counting
+    /// as a message.
+    ///
+    /// Intended to be called by an [`ExchangeTransformer`](crate::transformer::ExchangeTransformer)
+    /// via the [`StreamConfig::health`](super::config::StreamConfig::health), so exchange
+    /// heartbeats on quiet subscriptions (eg/ Bitfinex `[CHANNEL_ID, "hb"]`) keep it from being
+    /// considered stale, without yielding an event.
+    pub fn heartbeat(&self) {
+        self.heartbeat_at(Instant::now())
+    }
+
+    fn heartbeat_at(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        if state.connected {
+            state.last_message = Some(now);
+        }
+    }
+
+    pub(crate) fn received<T>(&self, event: &Result<MarketEvent<T>, DataError>) {
+        self.received_at(event, Instant::now())
+    }
+
+    fn received_at<T>(&self, event: &Result<MarketEvent<T>, DataError>, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.last_message = Some(now);
+        state.messages.add(now);
+        match event {
+            Ok(_) => {}
+            Err(DataError::InvalidSequence { .. } | DataError::InstrumentResync { .. }) => {
+                state.integrity_failures.push_back(now);
+                prune(&mut state.integrity_failures, self.config.window, now);
+            }
+            Err(DataError::Socket(
+                SocketError::Deserialise { .. } | SocketError::DeserialiseBinary { .. },
+            )) => state.errors.add(now),
+            Err(_) => {}
+        }
+    }
+
+    /// Compute the current [`ConnectionStatus`] of this connection.
+    pub fn status(&self) -> ConnectionStatus {
+        self.status_at(Instant::now())
+    }
+
+    fn status_at(&self, now: Instant) -> ConnectionStatus {
+        let mut state = self.state.lock().unwrap();
+        let HealthConfig {
+            window,
+            stale_after,
+            weights,
+        } = self.config;
+
+        prune(&mut state.reconnects, window, now);
+        prune(&mut state.integrity_failures, window, now);
+        let reconnects = state.reconnects.len();
+        let integrity_failures = state.integrity_failures.len();
+
+        let messages = state.messages.get(now);
+        let decode_error_rate = match messages {
+            0 => 0.0,
+            messages => state.errors.get(now) as f64 / messages as f64,
+        };
+
+        let idle = state
+            .last_message
+            .filter(|_| state.connected)
+            .map(|last_message| now.saturating_duration_since(last_message));
+
+        let score = match idle {
+            None => 0,
+            Some(idle) => {
+                let penalty = (reconnects as u32)
+                    .saturating_mul(weights.reconnect)
+                    .saturating_add(
+                        (decode_error_rate * 100.0 * weights.decode_error_percent as f64) as u32,
+                    )
+                    .saturating_add(if idle >= stale_after {
+                        weights.stale
+                    } else {
+                        0
+                    })
+                    .saturating_add(
+                        (integrity_failures as u32).saturating_mul(weights.integrity_failure),
+                    );
+                100u32.saturating_sub(penalty) as u8
+            }
+        };
+
+        ConnectionStatus {
+            exchange: self.exchange,
+            kind: self.kind,
+            subscriptions: self.subscriptions,
+            connected: state.connected,
+            score,
+            reconnects,
+            decode_error_rate,
+            idle,
+            integrity_failures,
+        }
+    }
+}
+
+/// Remove every [`Instant`] older than the rolling window.
+fn prune(instants: &mut VecDeque<Instant>, window: Duration, now: Instant) {
+    while instants
+        .front()
+        .is_some_and(|instant| now.saturating_duration_since(*instant) >= window)
+    {
+        instants.pop_front();
+    }
+}
+
+/// Approximate rolling window count, summing the counts of the current & previous windows.
+#[derive(Debug)]
+struct RollingCount {
+    window: Duration,
+    started: Instant,
+    current: u64,
+    previous: u64,
+}
+
+impl RollingCount {
+    fn new(window: Duration, now: Instant) -> Self {
+        Self {
+            window,
+            started: now,
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    fn rotate(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= self.window * 2 {
+            self.previous = 0;
+            self.current = 0;
+            self.started = now;
+        } else if elapsed >= self.window {
+            self.previous = std::mem::take(&mut self.current);
+            self.started += self.window;
+        }
+    }
+
+    fn add(&mut self, now: Instant) {
+        self.rotate(now);
+        self.current += 1;
+    }
+
+    fn get(&mut self, now: Instant) -> u64 {
+        self.rotate(now);
+        self.current + self.previous
+    }
+}
+
+/// Cheaply cloneable registry of the [`ConnectionHealth`] of every connection initialised by a
+/// [`StreamBuilder`](super::builder::StreamBuilder), see [`Streams::status`](super::Streams::status).
+#[derive(Clone, Debug, Default)]
+pub struct StreamHealth {
+    connections: Arc<Mutex<Vec<Arc<ConnectionHealth>>>>,
+}
+
+impl StreamHealth {
+    /// Register the [`ConnectionHealth`] of a new connection.
+    pub fn register(&self, connection: ConnectionHealth) -> Arc<ConnectionHealth> {
+        let connection = Arc::new(connection);
+        self.connections
+            .lock()
+            .unwrap()
+            .push(Arc::clone(&connection));
+        connection
+    }
+
+    /// Register every connection of the provided [`StreamHealth`].
+    pub fn extend(&self, other: &StreamHealth) {
+        let other = other.connections.lock().unwrap().clone();
+        self.connections.lock().unwrap().extend(other);
+    }
+
+    /// Compute the current [`ConnectionStatus`] of every registered connection.
+    pub fn status(&self) -> Vec<ConnectionStatus> {
+        self.connections
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|connection| connection.status())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::trade::{PublicTrade, PublicTrades};
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+
+    #[allow(clippy::result_large_err)]
+    fn trade() -> Result<MarketEvent<PublicTrade>, DataError> {
+        Ok(TradeFixture::new().with_market("okx", "usdt").trade(
+            0,
+            "btc",
+            num!(1.0),
+            num!(1.0),
+            Side::Buy,
+        ))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn decode_error() -> Result<MarketEvent<PublicTrade>, DataError> {
+        Err(DataError::Socket(SocketError::Deserialise {
+            error: serde_json::from_str::<()>("invalid").unwrap_err(),
+            payload: "invalid".to_string(),
+        }))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn integrity_failure() -> Result<MarketEvent<PublicTrade>, DataError> {
+        Err(DataError::InvalidSequence {
+            prev_last_update_id: 1,
+            first_update_id: 3,
+        })
+    }
+
+    #[test]
+    fn test_connection_health_score() {
+        struct TestCase {
+            steps: fn(&ConnectionHealth, Instant),
+            elapsed: Duration,
+            expected_score: u8,
+        }
+
+        fn secs(secs: u64) -> Duration {
+            Duration::from_secs(secs)
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: never connected
+                steps: |_, _| {},
+                elapsed: secs(1),
+                expected_score: 0,
+            },
+            TestCase {
+                // TC1: connected & receiving messages
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.received_at(&trade(), start + secs(1));
+                },
+                elapsed: secs(2),
+                expected_score: 100,
+            },
+            TestCase {
+                // TC2: two recent reconnects
+                steps: |health, start| {
+                    health.reconnecting_at(start);
+                    health.reconnecting_at(start + secs(1));
+                    health.connected_at(start + secs(2));
+                },
+                elapsed: secs(3),
+                expected_score: 70,
+            },
+            TestCase {
+                // TC3: reconnect outside the rolling window is forgotten
+                steps: |health, start| {
+                    health.reconnecting_at(start);
+                    health.connected_at(start + secs(400));
+                },
+                elapsed: secs(401),
+                expected_score: 100,
+            },
+            TestCase {
+                // TC4: 1 in 10 messages failed to decode (10%)
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.received_at(&decode_error(), start);
+                    for _ in 0..9 {
+                        health.received_at(&trade(), start);
+                    }
+                },
+                elapsed: secs(1),
+                expected_score: 50,
+            },
+            TestCase {
+                // TC5: stale connection
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.received_at(&trade(), start);
+                },
+                elapsed: secs(30),
+                expected_score: 50,
+            },
+            TestCase {
+                // TC6: OrderBook integrity failure & reconnect
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.received_at(&integrity_failure(), start);
+                    health.disconnected();
+                    health.reconnecting_at(start);
+                    health.connected_at(start);
+                },
+                elapsed: secs(1),
+                expected_score: 65,
+            },
+            TestCase {
+                // TC7: every penalty saturates at 0
+                steps: |health, start| {
+                    health.connected_at(start);
+                    for _ in 0..10 {
+                        health.reconnecting_at(start);
+                    }
+                },
+                elapsed: secs(1),
+                expected_score: 0,
+            },
+            TestCase {
+                // TC8: disconnected
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.disconnected();
+                },
+                elapsed: secs(1),
+                expected_score: 0,
+            },
+            TestCase {
+                // TC9: heartbeats keep a quiet connection from being stale
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.heartbeat_at(start + secs(20));
+                },
+                elapsed: secs(40),
+                expected_score: 100,
+            },
+            TestCase {
+                // TC10: heartbeats do not revive a disconnected connection
+                steps: |health, start| {
+                    health.connected_at(start);
+                    health.disconnected();
+                    health.heartbeat_at(start + secs(1));
+                },
+                elapsed: secs(2),
+                expected_score: 0,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let start = Instant::now();
+            let health = ConnectionHealth::new_at::<PublicTrades>(
+                ExchangeId::Okx,
+                1,
+                HealthConfig::default(),
+                start,
+            );
+            (test.steps)(&health, start);
+            let actual = health.status_at(start + test.elapsed);
+            assert_eq!(actual.score, test.expected_score, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_connection_health_status() {
+        let start = Instant::now();
+        let config = HealthConfig {
+            weights: HealthWeights {
+                reconnect: 1,
+                decode_error_percent: 1,
+                stale: 1,
+                integrity_failure: 1,
+            },
+            ..HealthConfig::default()
+        };
+        let health = ConnectionHealth::new_at::<PublicTrades>(ExchangeId::Okx, 2, config, start);
+
+        health.connected_at(start);
+        health.received_at(&decode_error(), start);
+        health.received_at(&trade(), start + Duration::from_secs(1));
+        health.received_at(&integrity_failure(), start + Duration::from_secs(2));
+        health.reconnecting_at(start + Duration::from_secs(2));
+
+        let expected = ConnectionStatus {
+            exchange: ExchangeId::Okx,
+            kind: "PublicTrades",
+            subscriptions: 2,
+            connected: true,
+            score: 100 - 1 - 33 - 1,
+            reconnects: 1,
+            decode_error_rate: 1.0 / 3.0,
+            idle: Some(Duration::from_secs(3)),
+            integrity_failures: 1,
+        };
+        assert_eq!(health.status_at(start + Duration::from_secs(5)), expected);
+
+        // Decode errors older than two rolling windows are forgotten
+        let actual = health.status_at(start + DEFAULT_HEALTH_WINDOW * 3);
+        assert_eq!(actual.decode_error_rate, 0.0);
+    }
+
+    #[test]
+    fn test_heartbeat() {
+        let health =
+            ConnectionHealth::new::<PublicTrades>(ExchangeId::Bitfinex, 1, HealthConfig::default());
+
+        // Heartbeats whilst disconnected are ignored
+        health.heartbeat();
+        assert_eq!(health.status().idle, None);
+
+        health.connected();
+        health.state.lock().unwrap().last_message = None;
+        health.heartbeat();
+        assert!(health.status().idle.is_some());
+        assert_eq!(health.state.lock().unwrap().messages.get(Instant::now()), 0);
+    }
+
+    #[test]
+    fn test_stream_health() {
+        let health = StreamHealth::default();
+        let connection = health.register(ConnectionHealth::new::<PublicTrades>(
+            ExchangeId::Okx,
+            1,
+            HealthConfig::default(),
+        ));
+        connection.connected();
+
+        let other = StreamHealth::default();
+        other.register(ConnectionHealth::new::<PublicTrades>(
+            ExchangeId::Kraken,
+            1,
+            HealthConfig::default(),
+        ));
+        health.extend(&other);
+
+        let actual = health
+            .status()
+            .into_iter()
+            .map(|status| (status.exchange, status.score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![(ExchangeId::Okx, 100), (ExchangeId::Kraken, 0)]
+        );
+    }
+}
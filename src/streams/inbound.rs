@@ -0,0 +1,297 @@
+use crate::event::MarketEvent;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, time::Duration};
+use tokio::time::Instant;
+
+/// Determines what an [`InboundThrottle`] does with events consumed from a connection whilst it's
+/// event rate exceeds the configured maximum.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Drop the event. Suits feeds where every event stands alone, eg/ trades.
+    #[default]
+    Drop,
+    /// Hold back only the latest event of each [`Instrument`](barter_integration::model::Instrument),
+    /// emitting the held back events in order as the rate allows. Suits feeds where each event
+    /// supersedes the last, eg/ OrderBook snapshots & top of book.
+    ///
+    /// Events are coalesced per instrument regardless of their kind, so events of the same
+    /// instrument are never emitted out of arrival order.
+    Coalesce,
+}
+
+/// Configuration of an optional per connection cap on the rate of
+/// [`MarketEvent<T>`](MarketEvent)s distributed downstream by the
+/// [`consume`](super::consumer::consume) loop.
+///
+/// The inbound counterpart to the exchange outbound subscription rate limits: an exchange that
+/// bursts more events than a consumer can handle is smoothed before it reaches the
+/// [`Streams`](super::Streams) channel. Bursts of up to one second worth of events are let
+/// through untouched, and events beyond that are handled according to the [`OverflowPolicy`].
+///
+/// Configured per exchange on each [`StreamBuilder<SubKind>`](super::builder::StreamBuilder) via
+/// [`throttle_inbound`](super::builder::StreamBuilder::throttle_inbound), so trades & books can
+/// be given different tolerances.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct InboundThrottle {
+    /// Maximum number of events distributed per second, at least 1.
+    pub max_events_per_sec: u32,
+    pub overflow: OverflowPolicy,
+}
+
+impl InboundThrottle {
+    /// Construct a new [`Self`] capping each connection at the provided events per second, using
+    /// the default [`OverflowPolicy::Drop`].
+    pub fn new(max_events_per_sec: u32) -> Self {
+        Self {
+            max_events_per_sec,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+
+    /// Set the [`OverflowPolicy`].
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// Outcome of an event admitted to an [`InboundLimiter`].
+#[derive(Debug)]
+pub(crate) enum Admission<T> {
+    /// Event is within the rate limit, so should be distributed now.
+    Emit(MarketEvent<T>),
+    /// Event exceeded the rate limit & was dropped.
+    Dropped,
+    /// Event exceeded the rate limit & was held back until the rate allows.
+    Delayed,
+    /// Event exceeded the rate limit & replaced a held back event of the same
+    /// [`Instrument`](barter_integration::model::Instrument), which is never distributed.
+    Coalesced,
+}
+
+/// Rate limiter applying an [`InboundThrottle`] to the events of a connection.
+///
+/// Implemented as a generic cell rate algorithm on the `tokio` clock, so a paused runtime
+/// (eg/ in tests) deterministically controls the schedule.
+#[derive(Debug)]
+pub(crate) struct InboundLimiter<T> {
+    overflow: OverflowPolicy,
+    /// Duration between events at the maximum rate.
+    interval: Duration,
+    /// Duration an event may arrive ahead of schedule, allowing bursts of one second of events.
+    tolerance: Duration,
+    /// Theoretical arrival time of the next event at the maximum rate.
+    next_arrival: Instant,
+    pending: VecDeque<MarketEvent<T>>,
+}
+
+impl<T> InboundLimiter<T> {
+    /// Construct a new [`Self`] from the provided [`InboundThrottle`].
+    pub(crate) fn new(throttle: InboundThrottle) -> Self {
+        let max_events_per_sec = throttle.max_events_per_sec.max(1);
+        let interval = Duration::from_secs(1) / max_events_per_sec;
+
+        Self {
+            overflow: throttle.overflow,
+            interval,
+            tolerance: interval * (max_events_per_sec - 1),
+            next_arrival: Instant::now(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Admit the provided [`MarketEvent<T>`](MarketEvent), determining if it should be
+    /// distributed now or handled according to the [`OverflowPolicy`].
+    pub(crate) fn admit(&mut self, event: MarketEvent<T>) -> Admission<T> {
+        // Held back events are distributed first to preserve ordering
+        if self.pending.is_empty() && self.acquire(Instant::now()) {
+            return Admission::Emit(event);
+        }
+
+        match self.overflow {
+            OverflowPolicy::Drop => Admission::Dropped,
+            OverflowPolicy::Coalesce => match self
+                .pending
+                .iter_mut()
+                .find(|pending| pending.instrument == event.instrument)
+            {
+                Some(pending) => {
+                    *pending = event;
+                    Admission::Coalesced
+                }
+                None => {
+                    self.pending.push_back(event);
+                    Admission::Delayed
+                }
+            },
+        }
+    }
+
+    /// Determine the [`Instant`] the next held back event can be released, if any.
+    pub(crate) fn next_release(&self) -> Option<Instant> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.next_arrival
+                .checked_sub(self.tolerance)
+                .unwrap_or_else(Instant::now),
+        )
+    }
+
+    /// Release the next held back event if the rate allows.
+    pub(crate) fn release(&mut self) -> Option<MarketEvent<T>> {
+        if self.pending.is_empty() || !self.acquire(Instant::now()) {
+            return None;
+        }
+
+        self.pending.pop_front()
+    }
+
+    /// Determine if an event arriving at the provided [`Instant`] is within the rate limit,
+    /// scheduling the next theoretical arrival if so.
+    fn acquire(&mut self, now: Instant) -> bool {
+        if now + self.tolerance < self.next_arrival {
+            return false;
+        }
+
+        self.next_arrival = self.next_arrival.max(now) + self.interval;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::trade::PublicTrade;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+
+    fn trade(base: &str, id: &str) -> MarketEvent<PublicTrade> {
+        let mut trade = TradeFixture::new().with_market("exchange", "usdt").trade(
+            0,
+            base,
+            num!(1.0),
+            num!(1.0),
+            Side::Buy,
+        );
+        trade.kind.id = id.to_string();
+        trade
+    }
+
+    fn outcome(admission: Admission<PublicTrade>) -> String {
+        match admission {
+            Admission::Emit(event) => event.kind.id,
+            Admission::Dropped => "dropped".to_string(),
+            Admission::Delayed => "delayed".to_string(),
+            Admission::Coalesced => "coalesced".to_string(),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_inbound_limiter_admit() {
+        struct TestCase {
+            throttle: InboundThrottle,
+            input: Vec<(u64, &'static str, &'static str)>,
+            expected: Vec<&'static str>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: burst within one second of events is emitted untouched
+                throttle: InboundThrottle::new(3),
+                input: vec![(0, "btc", "0"), (0, "btc", "1"), (0, "eth", "2")],
+                expected: vec!["0", "1", "2"],
+            },
+            TestCase {
+                // TC1: Drop policy drops events beyond the burst until the rate allows
+                throttle: InboundThrottle::new(2),
+                input: vec![
+                    (0, "btc", "0"),
+                    (0, "btc", "1"),
+                    (0, "btc", "2"),
+                    (400, "btc", "3"),
+                    (100, "btc", "4"),
+                    (0, "btc", "5"),
+                ],
+                expected: vec!["0", "1", "dropped", "dropped", "4", "dropped"],
+            },
+            TestCase {
+                // TC2: Coalesce policy holds back the latest event of each Instrument
+                throttle: InboundThrottle::new(1).with_overflow(OverflowPolicy::Coalesce),
+                input: vec![
+                    (0, "btc", "0"),
+                    (0, "btc", "1"),
+                    (0, "eth", "2"),
+                    (0, "btc", "3"),
+                    (0, "eth", "4"),
+                ],
+                expected: vec!["0", "delayed", "delayed", "coalesced", "coalesced"],
+            },
+            TestCase {
+                // TC3: max_events_per_sec of 0 is treated as 1
+                throttle: InboundThrottle::new(0),
+                input: vec![(0, "btc", "0"), (0, "btc", "1"), (1000, "btc", "2")],
+                expected: vec!["0", "dropped", "2"],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut limiter = InboundLimiter::new(test.throttle);
+            let mut actual = Vec::with_capacity(test.input.len());
+            for (advance_ms, base, id) in test.input {
+                tokio::time::advance(Duration::from_millis(advance_ms)).await;
+                actual.push(outcome(limiter.admit(trade(base, id))));
+            }
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_inbound_limiter_release() {
+        let mut limiter =
+            InboundLimiter::new(InboundThrottle::new(2).with_overflow(OverflowPolicy::Coalesce));
+        assert!(limiter.next_release().is_none());
+
+        // Burst of 2 emitted, then btc & eth held back with the latest event of each
+        for (base, id) in [("btc", "0"), ("btc", "1"), ("btc", "2"), ("eth", "3")] {
+            let _ = limiter.admit(trade(base, id));
+        }
+        assert!(matches!(
+            limiter.admit(trade("btc", "4")),
+            Admission::Coalesced
+        ));
+        assert!(limiter.release().is_none());
+
+        // Held back events are released in order at the maximum rate
+        let start = Instant::now();
+        assert_eq!(
+            limiter.next_release(),
+            Some(start + Duration::from_millis(500))
+        );
+        tokio::time::sleep_until(limiter.next_release().unwrap()).await;
+        assert_eq!(limiter.release().unwrap().kind.id, "4");
+        assert!(limiter.release().is_none());
+
+        // Events admitted whilst events are held back queue behind them
+        assert!(matches!(
+            limiter.admit(trade("sol", "5")),
+            Admission::Delayed
+        ));
+        assert_eq!(
+            limiter.next_release(),
+            Some(start + Duration::from_millis(1000))
+        );
+        tokio::time::sleep_until(limiter.next_release().unwrap()).await;
+        assert_eq!(limiter.release().unwrap().kind.id, "3");
+        tokio::time::sleep_until(limiter.next_release().unwrap()).await;
+        assert_eq!(limiter.release().unwrap().kind.id, "5");
+        assert_eq!(Instant::now(), start + Duration::from_millis(1500));
+        assert!(limiter.next_release().is_none());
+    }
+}
@@ -0,0 +1,424 @@
+use crate::{exchange::ExchangeId, subscription::sub_kind_name};
+use chrono::{DateTime, Utc};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Default number of most recent event latencies a [`LatencyTracker`] computes it's rolling
+/// quantiles over.
+pub const DEFAULT_LATENCY_WINDOW: usize = 1024;
+
+/// Default [`Duration`] between the [`LatencySnapshot`]s a [`LatencyTracker`] publishes to it's
+/// [`PublishedLatency`] whilst events are being recorded.
+pub const DEFAULT_LATENCY_PUBLISH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Rolling feed latency (ie/ `received_time - exchange_time`) of the
+/// [`MarketEvent`](crate::event::MarketEvent)s consumed by a consumer loop.
+///
+/// Owned by the consumer loop task, so recording an event takes no lock. The rolling window is
+/// published to the shared [`PublishedLatency`] at most every
+/// [`DEFAULT_LATENCY_PUBLISH_INTERVAL`], and whenever [`LatencyTracker::publish`] is called (eg/
+/// by the consumer loop once a connection ends).
+///
+/// Latencies of events with an `exchange_time` after their `received_time` (ie/ local clock
+/// skew behind the exchange) are clamped to zero, and counted separately as `skewed`.
+#[derive(Debug)]
+pub struct LatencyTracker {
+    window: usize,
+    latencies: VecDeque<Duration>,
+    observed: u64,
+    skewed: u64,
+    published: Arc<PublishedLatency>,
+    next_publish: Option<Instant>,
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_WINDOW)
+    }
+}
+
+impl LatencyTracker {
+    /// Construct a new [`Self`] computing rolling quantiles over the provided number of most
+    /// recent latencies (at least one).
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            latencies: VecDeque::with_capacity(window),
+            observed: 0,
+            skewed: 0,
+            published: Arc::default(),
+            next_publish: None,
+        }
+    }
+
+    /// Record the latency of an event, returning the (clamped) latency recorded.
+    pub fn record(
+        &mut self,
+        exchange_time: DateTime<Utc>,
+        received_time: DateTime<Utc>,
+    ) -> Duration {
+        self.observed += 1;
+
+        let latency = match (received_time - exchange_time).to_std() {
+            Ok(latency) => latency,
+            Err(_) => {
+                self.skewed += 1;
+                Duration::ZERO
+            }
+        };
+
+        if self.latencies.len() == self.window {
+            self.latencies.pop_front();
+        }
+        self.latencies.push_back(latency);
+
+        let now = Instant::now();
+        if self
+            .next_publish
+            .is_none_or(|next_publish| now >= next_publish)
+        {
+            self.publish();
+            self.next_publish = Some(now + DEFAULT_LATENCY_PUBLISH_INTERVAL);
+        }
+
+        latency
+    }
+
+    /// Publish the current rolling window & counters to the shared [`PublishedLatency`].
+    pub fn publish(&self) {
+        let mut published = self.published.window.lock().unwrap();
+        published.latencies.clear();
+        published.latencies.extend(self.latencies.iter().copied());
+        published.observed = self.observed;
+        published.skewed = self.skewed;
+    }
+
+    /// [`PublishedLatency`] this [`LatencyTracker`] publishes to.
+    pub fn published(&self) -> &Arc<PublishedLatency> {
+        &self.published
+    }
+
+    /// Compute a [`LatencySnapshot`] of the current rolling latency quantiles & counters,
+    /// including any events not yet published.
+    pub fn snapshot(&self) -> LatencySnapshot {
+        LatencySnapshot::from_window(
+            self.latencies.iter().copied().collect(),
+            self.observed,
+            self.skewed,
+        )
+    }
+}
+
+/// Rolling window & counters most recently published by a [`LatencyTracker`], read by
+/// [`LatencyStats`] (and the metrics summary if the `metrics` feature is enabled).
+#[derive(Debug, Default)]
+pub struct PublishedLatency {
+    window: Mutex<LatencyWindow>,
+}
+
+/// Rolling window & counters of a [`PublishedLatency`].
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    latencies: Vec<Duration>,
+    observed: u64,
+    skewed: u64,
+}
+
+impl PublishedLatency {
+    /// Compute a [`LatencySnapshot`] merging the windows & counters of every provided
+    /// [`PublishedLatency`] (eg/ of each connection of the same exchange & kind).
+    pub fn merged<'a, Iter>(published: Iter) -> LatencySnapshot
+    where
+        Iter: IntoIterator<Item = &'a PublishedLatency>,
+    {
+        let (latencies, observed, skewed) = published.into_iter().fold(
+            (Vec::new(), 0, 0),
+            |(mut latencies, observed, skewed), published| {
+                let window = published.window.lock().unwrap();
+                latencies.extend_from_slice(&window.latencies);
+                (
+                    latencies,
+                    observed + window.observed,
+                    skewed + window.skewed,
+                )
+            },
+        );
+
+        LatencySnapshot::from_window(latencies, observed, skewed)
+    }
+}
+
+/// Nearest-rank quantile (expressed in per-mille, eg/ 990 for p99) of the sorted latencies.
+fn nearest_rank(sorted: &[Duration], per_mille: usize) -> Option<Duration> {
+    let rank = (sorted.len() * per_mille).div_ceil(1000).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Point in time view of a [`LatencyTracker`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct LatencySnapshot {
+    /// Median latency of the rolling window, `None` if no events have been recorded.
+    pub p50: Option<Duration>,
+    /// 99th percentile latency of the rolling window, `None` if no events have been recorded.
+    pub p99: Option<Duration>,
+    /// Number of latencies in the rolling window.
+    pub samples: usize,
+    /// Total number of events recorded.
+    pub observed: u64,
+    /// Total number of events recorded with an `exchange_time` in the future, whose latency was
+    /// clamped to zero.
+    pub skewed: u64,
+}
+
+impl LatencySnapshot {
+    /// Compute the [`LatencySnapshot`] of the provided (unsorted) rolling window & counters.
+    fn from_window(mut latencies: Vec<Duration>, observed: u64, skewed: u64) -> Self {
+        latencies.sort_unstable();
+
+        Self {
+            p50: nearest_rank(&latencies, 500),
+            p99: nearest_rank(&latencies, 990),
+            samples: latencies.len(),
+            observed,
+            skewed,
+        }
+    }
+}
+
+/// Stats API exposing the rolling latency of every (exchange, [`SubKind`](crate::subscription::SubKind))
+/// consumed by a [`StreamBuilder`](super::builder::StreamBuilder), keyed by the [`ExchangeId`] &
+/// [`sub_kind_name`] (eg/ "PublicTrades"), see [`Streams::latency`](super::Streams::latency).
+///
+/// Each consumer loop records the latency of every live event it receives (backfilled events
+/// are excluded) to it's own [`LatencyTracker`], registered via [`LatencyStats::tracker`]. The
+/// snapshot of an exchange & kind merges the [`PublishedLatency`] of each of it's connections.
+///
+/// Cheaply cloneable, with every clone sharing the same registered trackers.
+#[derive(Clone, Debug, Default)]
+pub struct LatencyStats {
+    trackers: Arc<Mutex<BTreeMap<(ExchangeId, &'static str), Vec<Arc<PublishedLatency>>>>>,
+}
+
+impl LatencyStats {
+    /// Register a new [`LatencyTracker`] of a consumer loop of the provided exchange & kind,
+    /// publishing to these [`LatencyStats`].
+    pub fn tracker(&self, exchange: ExchangeId, kind: &'static str) -> LatencyTracker {
+        let tracker = LatencyTracker::default();
+        self.trackers
+            .lock()
+            .unwrap()
+            .entry((exchange, kind))
+            .or_default()
+            .push(Arc::clone(tracker.published()));
+        tracker
+    }
+
+    /// Register every tracker of the provided [`LatencyStats`].
+    pub fn extend(&self, other: &LatencyStats) {
+        let other = other.trackers.lock().unwrap().clone();
+        let mut trackers = self.trackers.lock().unwrap();
+        for (key, published) in other {
+            trackers.entry(key).or_default().extend(published);
+        }
+    }
+
+    /// [`LatencySnapshot`] of the provided exchange & [`SubKind`](crate::subscription::SubKind),
+    /// if any of it's consumer loops are registered.
+    pub fn snapshot<Kind>(&self, exchange: ExchangeId) -> Option<LatencySnapshot> {
+        self.trackers
+            .lock()
+            .unwrap()
+            .get(&(exchange, sub_kind_name::<Kind>()))
+            .map(|published| PublishedLatency::merged(published.iter().map(Arc::as_ref)))
+    }
+
+    /// [`LatencySnapshot`] of every exchange & kind consumed.
+    pub fn snapshots(&self) -> BTreeMap<(ExchangeId, &'static str), LatencySnapshot> {
+        self.trackers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, published)| {
+                (
+                    *key,
+                    PublishedLatency::merged(published.iter().map(Arc::as_ref)),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscription::trade::PublicTrades;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_latency_tracker_quantiles() {
+        struct TestCase {
+            window: usize,
+            latencies_ms: Vec<i64>,
+            expected: LatencySnapshot,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: no events recorded
+                window: 10,
+                latencies_ms: vec![],
+                expected: LatencySnapshot::default(),
+            },
+            TestCase {
+                // TC1: single event
+                window: 10,
+                latencies_ms: vec![7],
+                expected: LatencySnapshot {
+                    p50: Some(millis(7)),
+                    p99: Some(millis(7)),
+                    samples: 1,
+                    observed: 1,
+                    skewed: 0,
+                },
+            },
+            TestCase {
+                // TC2: 1ms..=100ms recorded out of order
+                window: 100,
+                latencies_ms: (1..=100).rev().collect(),
+                expected: LatencySnapshot {
+                    p50: Some(millis(50)),
+                    p99: Some(millis(99)),
+                    samples: 100,
+                    observed: 100,
+                    skewed: 0,
+                },
+            },
+            TestCase {
+                // TC3: rolling window only retains the most recent latencies
+                window: 4,
+                latencies_ms: vec![1000, 1000, 1, 2, 3, 4],
+                expected: LatencySnapshot {
+                    p50: Some(millis(2)),
+                    p99: Some(millis(4)),
+                    samples: 4,
+                    observed: 6,
+                    skewed: 0,
+                },
+            },
+            TestCase {
+                // TC4: exchange_time in the future is clamped to zero & counted as skewed
+                window: 10,
+                latencies_ms: vec![-5, 10, -250, 20],
+                expected: LatencySnapshot {
+                    p50: Some(Duration::ZERO),
+                    p99: Some(millis(20)),
+                    samples: 4,
+                    observed: 4,
+                    skewed: 2,
+                },
+            },
+        ];
+
+        let received_time = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut tracker = LatencyTracker::new(test.window);
+            for latency_ms in test.latencies_ms {
+                let exchange_time = received_time - chrono::Duration::milliseconds(latency_ms);
+                let recorded = tracker.record(exchange_time, received_time);
+                assert_eq!(
+                    recorded,
+                    millis(latency_ms.max(0) as u64),
+                    "TC{} failed",
+                    index
+                );
+            }
+            assert_eq!(tracker.snapshot(), test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let stats = LatencyStats::default();
+        assert_eq!(stats.snapshot::<PublicTrades>(ExchangeId::Okx), None);
+
+        let received_time = Utc::now();
+        let mut tracker = stats.tracker(ExchangeId::Okx, sub_kind_name::<PublicTrades>());
+        tracker.record(
+            received_time - chrono::Duration::milliseconds(3),
+            received_time,
+        );
+
+        // First event is published straight away
+        let expected = LatencySnapshot {
+            p50: Some(millis(3)),
+            p99: Some(millis(3)),
+            samples: 1,
+            observed: 1,
+            skewed: 0,
+        };
+        assert_eq!(
+            stats.snapshot::<PublicTrades>(ExchangeId::Okx),
+            Some(expected)
+        );
+
+        // Subsequent events within the publish interval are published on demand
+        tracker.record(
+            received_time + chrono::Duration::milliseconds(3),
+            received_time,
+        );
+        assert_eq!(
+            stats.snapshot::<PublicTrades>(ExchangeId::Okx),
+            Some(expected)
+        );
+        tracker.publish();
+        let expected = LatencySnapshot {
+            p50: Some(Duration::ZERO),
+            p99: Some(millis(3)),
+            samples: 2,
+            observed: 2,
+            skewed: 1,
+        };
+        assert_eq!(
+            stats.snapshot::<PublicTrades>(ExchangeId::Okx),
+            Some(expected)
+        );
+        assert_eq!(
+            stats.snapshots(),
+            BTreeMap::from([((ExchangeId::Okx, "PublicTrades"), expected)])
+        );
+
+        // Trackers of every connection of the same exchange & kind are merged
+        let mut other = stats.tracker(ExchangeId::Okx, sub_kind_name::<PublicTrades>());
+        other.record(
+            received_time - chrono::Duration::milliseconds(9),
+            received_time,
+        );
+        assert_eq!(
+            stats.snapshot::<PublicTrades>(ExchangeId::Okx),
+            Some(LatencySnapshot {
+                p50: Some(millis(3)),
+                p99: Some(millis(9)),
+                samples: 3,
+                observed: 3,
+                skewed: 1,
+            })
+        );
+
+        // Independent LatencyStats (eg/ of separate Streams) do not mix their trackers
+        let independent = LatencyStats::default();
+        assert_eq!(independent.snapshot::<PublicTrades>(ExchangeId::Okx), None);
+        independent.extend(&stats);
+        assert_eq!(
+            independent.snapshot::<PublicTrades>(ExchangeId::Okx),
+            stats.snapshot::<PublicTrades>(ExchangeId::Okx)
+        );
+    }
+}
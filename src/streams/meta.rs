@@ -0,0 +1,185 @@
+use crate::event::MarketEvent;
+use barter_integration::model::Instrument;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::mpsc;
+
+/// User metadata (eg/ a strategy id or label) that can tag the
+/// [`MarketEvent<T, Meta>`](MarketEvent)s of a
+/// [`StreamBuilder<Kind, Meta>`](super::builder::StreamBuilder), see
+/// [`StreamBuilder::subscribe_with_meta`](super::builder::StreamBuilder::subscribe_with_meta).
+///
+/// Implemented for every eligible type. Events of untagged instruments carry `Meta::default()`,
+/// so the default `()` costs nothing.
+///
+/// The metadata is cloned onto every [`MarketEvent<T, Meta>`](MarketEvent), so it must be cheap
+/// to clone: use a `Copy` id, a `&'static str` or an [`Arc`] (eg/ `Arc<str>`) rather than an
+/// owned `String` or collection, which would be heap-copied for every message.
+pub trait EventMeta: Clone + Default + Send + Sync + 'static {}
+
+impl<Meta> EventMeta for Meta where Meta: Clone + Default + Send + Sync + 'static {}
+
+/// User metadata of each tagged [`Instrument`] of a consumer loop, attached to every
+/// [`MarketEvent<T, Meta>`](MarketEvent) it distributes.
+///
+/// Empty unless a [`Subscription`](crate::subscription::Subscription) of the consumer loop was
+/// tagged via [`StreamBuilder::subscribe_with_meta`](super::builder::StreamBuilder::subscribe_with_meta),
+/// so untagged consumer loops tag every event with `Meta::default()` without a lookup.
+#[derive(Debug)]
+pub struct EventMetas<Meta>(Option<Arc<HashMap<Instrument, Meta>>>);
+
+impl<Meta> Clone for EventMetas<Meta> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<Meta> Default for EventMetas<Meta> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<Meta> EventMetas<Meta> {
+    /// Construct a new [`Self`] from the provided metadata of each tagged [`Instrument`].
+    pub fn new(metas: HashMap<Instrument, Meta>) -> Self {
+        Self((!metas.is_empty()).then(|| Arc::new(metas)))
+    }
+
+    /// Determine if no [`Instrument`] is tagged with metadata.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Tag the [`MarketEvent<T>`](MarketEvent) with a clone of the metadata of it's
+    /// [`Instrument`], or `Meta::default()` if it's untagged.
+    pub fn tag<T>(&self, event: MarketEvent<T>) -> MarketEvent<T, Meta>
+    where
+        Meta: EventMeta,
+    {
+        let meta = self
+            .0
+            .as_ref()
+            .and_then(|metas| metas.get(&event.instrument))
+            .cloned()
+            .unwrap_or_default();
+
+        event.with_meta(meta)
+    }
+}
+
+/// Sender of a consumer loop to it's exchange channel, tagging every
+/// [`MarketEvent<T>`](MarketEvent) it sends with the [`EventMetas`] of the consumer loop.
+#[derive(Debug)]
+pub struct EventSender<T, Meta = ()> {
+    tx: mpsc::UnboundedSender<MarketEvent<T, Meta>>,
+    metas: EventMetas<Meta>,
+}
+
+impl<T, Meta> Clone for EventSender<T, Meta> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            metas: self.metas.clone(),
+        }
+    }
+}
+
+impl<T, Meta> EventSender<T, Meta> {
+    /// Construct a new [`Self`] that tags every [`MarketEvent<T>`](MarketEvent) it sends via the
+    /// provided `tx` with the provided [`EventMetas`].
+    pub fn new(tx: mpsc::UnboundedSender<MarketEvent<T, Meta>>, metas: EventMetas<Meta>) -> Self {
+        Self { tx, metas }
+    }
+
+    /// Tag & send the [`MarketEvent<T>`](MarketEvent) to the exchange channel, returning it
+    /// untagged if the receiver has been dropped.
+    pub fn send(&self, event: MarketEvent<T>) -> Result<(), mpsc::error::SendError<MarketEvent<T>>>
+    where
+        Meta: EventMeta,
+    {
+        self.tx
+            .send(self.metas.tag(event))
+            .map_err(|error| mpsc::error::SendError(error.0.with_meta(())))
+    }
+
+    /// Reference the [`mpsc::UnboundedSender`] of the exchange channel, eg/ to detect the
+    /// receiver being dropped via the [`shutdown`](super::shutdown) helpers.
+    pub fn tx(&self) -> &mpsc::UnboundedSender<MarketEvent<T, Meta>> {
+        &self.tx
+    }
+}
+
+impl<T> From<mpsc::UnboundedSender<MarketEvent<T>>> for EventSender<T> {
+    fn from(tx: mpsc::UnboundedSender<MarketEvent<T>>) -> Self {
+        Self::new(tx, EventMetas::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::trade::PublicTrade;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::{InstrumentKind, Side};
+
+    fn event(base: &str) -> MarketEvent<PublicTrade> {
+        TradeFixture::new().trade(0, base, num!(1.0), num!(1.0), Side::Buy)
+    }
+
+    #[test]
+    fn test_event_metas_tag() {
+        let metas = EventMetas::new(HashMap::from([(
+            Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            Some("strategy_1"),
+        )]));
+
+        assert_eq!(metas.tag(event("btc")).meta, Some("strategy_1"));
+        assert_eq!(metas.tag(event("eth")).meta, None);
+
+        // Without any tagged Instrument every event is tagged with the default
+        assert_eq!(
+            EventMetas::<Option<&str>>::default().tag(event("btc")).meta,
+            None
+        );
+        assert!(EventMetas::<Option<&str>>::default().is_empty());
+        assert!(EventMetas::<Option<&str>>::new(HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_event_metas_tag_shares_arc_meta() {
+        let strategy = Arc::<str>::from("strategy_1");
+        let metas = EventMetas::new(HashMap::from([(
+            Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            Arc::clone(&strategy),
+        )]));
+
+        // Tagging only bumps the reference count of the shared metadata
+        let first = metas.tag(event("btc")).meta;
+        let second = metas.tag(event("btc")).meta;
+        assert!(Arc::ptr_eq(&first, &strategy));
+        assert!(Arc::ptr_eq(&second, &strategy));
+    }
+
+    #[test]
+    fn test_event_sender_send() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sender = EventSender::new(
+            tx,
+            EventMetas::new(HashMap::from([(
+                Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                "strategy_1",
+            )])),
+        );
+
+        sender.send(event("btc")).unwrap();
+        sender.send(event("eth")).unwrap();
+        assert_eq!(rx.try_recv().unwrap().meta, "strategy_1");
+        assert_eq!(rx.try_recv().unwrap().meta, "");
+
+        // Once the receiver is dropped the untagged event is returned
+        drop(rx);
+        let error = sender.send(event("btc")).unwrap_err();
+        assert_eq!(error.0, event("btc"));
+    }
+}
@@ -0,0 +1,347 @@
+use super::{health::StreamHealth, latency::LatencyStats, Streams};
+use crate::{error::DataError, event::MarketEvent, exchange::ExchangeId};
+use barter_integration::{error::SocketError, model::Exchange};
+use futures::{Stream, StreamExt};
+use serde::{de::IntoDeserializer, Deserialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    time::{Instant, Sleep},
+};
+use tracing::{info, warn};
+
+/// Step of a hand-crafted [`MockStream`] scenario.
+#[derive(Debug)]
+pub enum MockEvent<T> {
+    /// Yield the [`MarketEvent<T>`](MarketEvent).
+    Event(MarketEvent<T>),
+    /// Yield the [`DataError`], like a live [`MarketStream`](crate::MarketStream) does when an
+    /// exchange message fails to be parsed.
+    Error(DataError),
+    /// Wait for the [`Duration`] before continuing with the next step.
+    Delay(Duration),
+    /// Simulate the connection of an exchange dropping, yielding a
+    /// [`SocketError::Terminated`] and then dropping every event of the exchange until it
+    /// re-connects once the `downtime` has elapsed.
+    Reconnect {
+        exchange: ExchangeId,
+        downtime: Duration,
+    },
+}
+
+impl<T> From<MarketEvent<T>> for MockEvent<T> {
+    fn from(event: MarketEvent<T>) -> Self {
+        Self::Event(event)
+    }
+}
+
+/// [`Stream`] that yields a predetermined sequence of [`MarketEvent<T>`](MarketEvent)s and
+/// [`DataError`]s, for deterministically testing code that consumes market data.
+///
+/// Yields the same items as a live [`MarketStream`](crate::MarketStream), interleaving events of
+/// any number of exchanges & instruments in the order they are provided. Timing is controlled
+/// via [`MockEvent::Delay`] steps, which respect paused tokio time (eg/
+/// `#[tokio::test(start_paused = true)]`).
+#[derive(Debug)]
+pub struct MockStream<T> {
+    steps: VecDeque<MockEvent<T>>,
+    reconnecting: HashMap<ExchangeId, Instant>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<T> MockStream<T> {
+    /// Construct a new [`Self`] that yields the provided scenario steps.
+    pub fn new<Steps, Step>(steps: Steps) -> Self
+    where
+        Steps: IntoIterator<Item = Step>,
+        Step: Into<MockEvent<T>>,
+    {
+        Self {
+            steps: steps.into_iter().map(Step::into).collect(),
+            reconnecting: HashMap::new(),
+            sleep: None,
+        }
+    }
+
+    /// Determine if the exchange of the provided event is currently reconnecting.
+    fn is_reconnecting(&mut self, event: &MarketEvent<T>) -> bool {
+        let Some(exchange) = exchange_id(&event.exchange) else {
+            return false;
+        };
+
+        match self.reconnecting.get(&exchange) {
+            Some(reconnected) if Instant::now() < *reconnected => true,
+            Some(_) => {
+                self.reconnecting.remove(&exchange);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T> Stream for MockStream<T>
+where
+    T: Unpin,
+{
+    type Item = Result<MarketEvent<T>, DataError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Wait for any in progress MockEvent::Delay to elapse
+            if let Some(sleep) = this.sleep.as_mut() {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                this.sleep = None;
+            }
+
+            let Some(step) = this.steps.pop_front() else {
+                return Poll::Ready(None);
+            };
+
+            match step {
+                MockEvent::Event(event) if this.is_reconnecting(&event) => continue,
+                MockEvent::Event(event) => return Poll::Ready(Some(Ok(event))),
+                MockEvent::Error(error) => return Poll::Ready(Some(Err(error))),
+                MockEvent::Delay(duration) => {
+                    this.sleep = Some(Box::pin(tokio::time::sleep(duration)));
+                }
+                MockEvent::Reconnect { exchange, downtime } => {
+                    this.reconnecting
+                        .insert(exchange, Instant::now() + downtime);
+                    return Poll::Ready(Some(Err(DataError::Socket(SocketError::Terminated(
+                        format!("{exchange} mock connection dropped"),
+                    )))));
+                }
+            }
+        }
+    }
+}
+
+impl<T> Streams<MarketEvent<T>> {
+    /// Construct [`Streams`] that yield a predetermined sequence of
+    /// [`MarketEvent<T>`](MarketEvent)s via the same interface as [`Streams`] initialised by a
+    /// [`StreamBuilder`](super::builder::StreamBuilder), for deterministically testing
+    /// consumers.
+    ///
+    /// The scenario is driven by a [`MockStream`] on a spawned task, distributing each event to
+    /// the receiver of it's exchange. Like a live consumer loop, [`MockEvent::Error`]s are
+    /// logged & skipped, and events are dropped whilst their exchange is reconnecting. Each
+    /// receiver ends once the scenario is complete.
+    ///
+    /// Fails if an event's [`Exchange`] is not the name of an [`ExchangeId`], since it could not
+    /// be yielded from any of the [`Streams`].
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// ```rust,no_run
+    /// # async fn scenario(
+    /// #     binance_trade: barter_data::event::MarketEvent<barter_data::subscription::trade::PublicTrade>,
+    /// #     okx_trade: barter_data::event::MarketEvent<barter_data::subscription::trade::PublicTrade>,
+    /// # ) -> Result<(), barter_data::error::DataError> {
+    /// use barter_data::{
+    ///     exchange::ExchangeId,
+    ///     streams::{mock::MockEvent, Streams},
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// let mut joined = Streams::mock([
+    ///     MockEvent::Event(binance_trade),
+    ///     MockEvent::Delay(Duration::from_millis(100)),
+    ///     MockEvent::Reconnect {
+    ///         exchange: ExchangeId::Okx,
+    ///         downtime: Duration::from_secs(1),
+    ///     },
+    ///     MockEvent::Event(okx_trade),
+    /// ])?
+    /// .join()
+    /// .await;
+    ///
+    /// while let Some(event) = joined.recv().await {
+    ///     println!("{event:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn mock<Steps, Step>(steps: Steps) -> Result<Self, DataError>
+    where
+        Steps: IntoIterator<Item = Step>,
+        Step: Into<MockEvent<T>>,
+        T: Unpin + Send + 'static,
+    {
+        let mut stream = MockStream::new(steps);
+
+        // Construct a channel for every exchange in the scenario
+        let mut txs = HashMap::new();
+        let mut streams = HashMap::new();
+        for step in &stream.steps {
+            let exchange = match step {
+                MockEvent::Event(event) => exchange_id(&event.exchange).ok_or_else(|| {
+                    DataError::Socket(SocketError::Unsupported {
+                        entity: "MockStream",
+                        item: format!(
+                            "MarketEvent exchange {}, which is not an ExchangeId",
+                            event.exchange
+                        ),
+                    })
+                })?,
+                MockEvent::Reconnect { exchange, .. } => *exchange,
+                MockEvent::Error(_) | MockEvent::Delay(_) => continue,
+            };
+
+            txs.entry(exchange).or_insert_with(|| {
+                let (tx, rx) = mpsc::unbounded_channel();
+                streams.insert(exchange, rx);
+                tx
+            });
+        }
+
+        tokio::spawn(async move {
+            while let Some(result) = stream.next().await {
+                match result {
+                    Ok(event) => {
+                        let exchange = exchange_id(&event.exchange)
+                            .expect("MockEvent exchanges are validated on construction");
+                        let _ = txs[&exchange].send(event);
+                    }
+                    Err(DataError::Socket(SocketError::Terminated(reason))) => {
+                        info!(%reason, "MockStream simulating MarketStream re-connection");
+                    }
+                    Err(error) => {
+                        warn!(
+                            %error,
+                            action = "skipping message",
+                            "consumed DataError from MockStream",
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            streams,
+            health: StreamHealth::default(),
+            latency: LatencyStats::default(),
+        })
+    }
+}
+
+/// Determine the [`ExchangeId`] with the provided [`Exchange`] name, if any.
+fn exchange_id(exchange: &Exchange) -> Option<ExchangeId> {
+    ExchangeId::deserialize(exchange.to_string().as_str().into_deserializer())
+        .map_err(|_: serde::de::value::Error| ())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+    use crate::subscription::trade::PublicTrade;
+    use crate::test_util::trade::TradeFixture;
+    use barter_integration::model::Side;
+
+    fn trade(exchange: ExchangeId, base: &str, id: &str) -> MarketEvent<PublicTrade> {
+        let mut trade = TradeFixture::new()
+            .with_market(exchange.as_str(), "usdt")
+            .trade(0, base, num!(100.0), num!(1.0), Side::Buy);
+        trade.kind.id = id.to_string();
+        trade
+    }
+
+    fn scenario() -> Vec<MockEvent<PublicTrade>> {
+        vec![
+            MockEvent::Event(trade(ExchangeId::BinanceSpot, "btc", "1")),
+            MockEvent::Event(trade(ExchangeId::Okx, "eth", "2")),
+            MockEvent::Delay(Duration::from_secs(1)),
+            MockEvent::Error(DataError::Socket(SocketError::Unsupported {
+                entity: "okx",
+                item: "unknown".to_string(),
+            })),
+            MockEvent::Reconnect {
+                exchange: ExchangeId::Okx,
+                downtime: Duration::from_secs(5),
+            },
+            MockEvent::Event(trade(ExchangeId::Okx, "btc", "3")),
+            MockEvent::Event(trade(ExchangeId::BinanceSpot, "btc", "4")),
+            MockEvent::Delay(Duration::from_secs(5)),
+            MockEvent::Event(trade(ExchangeId::Okx, "btc", "5")),
+        ]
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_mock_stream_yields_scenario() {
+        let start = Instant::now();
+
+        let actual = MockStream::new(scenario())
+            .map(|result| {
+                let elapsed = start.elapsed().as_secs();
+                match result {
+                    Ok(event) => format!("{elapsed}s {} {}", event.exchange, event.kind.id),
+                    Err(error) => format!("{elapsed}s {error}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        let expected = vec![
+            "0s binance_spot 1",
+            "0s okx 2",
+            "1s SocketError: okx does not support: unknown",
+            "1s SocketError: ExchangeStream terminated with closing frame: okx mock connection dropped",
+            "1s binance_spot 4",
+            "6s okx 5",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_streams_mock_distributes_events_per_exchange() {
+        let mut streams = Streams::mock(scenario()).unwrap();
+        assert_eq!(streams.streams.len(), 2);
+
+        let mut binance = streams.select(ExchangeId::BinanceSpot).unwrap();
+        let mut okx = streams.select(ExchangeId::Okx).unwrap();
+
+        let mut actual = Vec::new();
+        while let Some(event) = binance.recv().await {
+            actual.push(event.kind.id);
+        }
+        assert_eq!(actual, vec!["1", "4"]);
+
+        let mut actual = Vec::new();
+        while let Some(event) = okx.recv().await {
+            actual.push(event.kind.id);
+        }
+        assert_eq!(actual, vec!["2", "5"]);
+    }
+
+    #[tokio::test]
+    async fn test_streams_mock_unknown_exchange() {
+        let mut event = trade(ExchangeId::Okx, "btc", "1");
+        event.exchange = Exchange::from("not_an_exchange");
+
+        let actual = Streams::mock([
+            MockEvent::Event(trade(ExchangeId::Okx, "eth", "0")),
+            MockEvent::Event(event),
+        ]);
+
+        assert!(matches!(
+            actual,
+            Err(DataError::Socket(SocketError::Unsupported {
+                entity: "MockStream",
+                ..
+            }))
+        ));
+    }
+}
@@ -1,22 +1,85 @@
-use self::builder::{multi::MultiStreamBuilder, StreamBuilder};
+use self::{
+    builder::{multi::MultiStreamBuilder, StreamBuilder},
+    health::{ConnectionStatus, StreamHealth},
+    latency::LatencyStats,
+    meta::EventMeta,
+};
 use crate::{exchange::ExchangeId, subscription::SubKind};
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamMap};
 
+/// Stream adapters (eg/ [`Throttle`](adapter::throttle::Throttle)) that transform a stream of
+/// [`MarketEvent<T>`](crate::event::MarketEvent)s consumed from [`Streams`].
+pub mod adapter;
+
 /// Defines the [`StreamBuilder`](builder::StreamBuilder) and
 /// [`MultiStreamBuilder`](builder::multi::MultiStreamBuilder) APIs for ergonomically initialising
 /// [`MarketStream`](super::MarketStream) [`Streams`].
 pub mod builder;
 
+/// [`MockStream`](mock::MockStream) & [`Streams::mock`] that yield a hand-crafted sequence of
+/// [`MarketEvent<T>`](crate::event::MarketEvent)s, for deterministically testing consumers.
+pub mod mock;
+
+/// Per connection [`StreamConfig`](config::StreamConfig) handed to the
+/// [`Subscriber`](crate::subscriber::Subscriber) & [`Connector`](crate::exchange::Connector) of
+/// each [`MarketStream`](super::MarketStream).
+pub mod config;
+
+/// Optional per connection [`InboundThrottle`](inbound::InboundThrottle) capping the rate of
+/// [`MarketEvent<T>`](crate::event::MarketEvent)s distributed by the consumer loop.
+pub mod inbound;
+
+/// [`PollingStream`](polling::PollingStream) [`MarketStream`](super::MarketStream) that
+/// periodically polls an exchange REST API for market data without a WebSocket channel.
+pub mod polling;
+
+/// Rolling feed [`LatencyTracker`](latency::LatencyTracker) quantiles per exchange & kind,
+/// exposed via the [`LatencyStats`] API of each [`Streams`].
+pub mod latency;
+
+/// Per connection [`ConnectionHealth`](health::ConnectionHealth) scores derived from recent
+/// reconnects, decode errors, staleness & OrderBook integrity failures, see [`Streams::status`].
+pub mod health;
+
+/// User [`EventMetas`](meta::EventMetas) of each tagged
+/// [`Subscription`](crate::subscription::Subscription), attached to the
+/// [`MarketEvent<T, Meta>`](crate::event::MarketEvent)s distributed by the consumer loop.
+pub mod meta;
+
 /// Central consumer loop functionality used by the [`StreamBuilder`](builder::StreamBuilder) to
 /// to drive a re-connecting [`MarketStream`](super::MarketStream).
 pub mod consumer;
 
+/// Cancellation safe helpers that treat a dropped downstream receiver as a shutdown signal, so
+/// background tasks end (closing their connections) once the consumer has gone.
+pub mod shutdown;
+
 /// Ergonomic collection of exchange [`MarketEvent<T>`](crate::event::MarketEvent) receivers.
+///
+/// ### Ordering
+/// Events originating on the same connection for the same
+/// [`Instrument`](barter_integration::model::Instrument) are emitted in the order they arrived on
+/// the socket, including events of different kinds multiplexed over one socket (eg/
+/// [`TradesAndBooksL1`](crate::subscription::combined::TradesAndBooksL1)). Each connection
+/// sends it's events directly into the exchange channel from it's consumer loop task, and this
+/// order is preserved by [`Streams::join`], [`Streams::join_map`] & the
+/// [`MultiStreamBuilder`], and by the
+/// [`InboundThrottle`](inbound::InboundThrottle) which coalesces per instrument.
+///
+/// No ordering holds between events originating on different connections, even of the same
+/// instrument (eg/ [`PublicTrades`](crate::subscription::trade::PublicTrades) and
+/// [`OrderBooksL1`](crate::subscription::book::OrderBooksL1) of separate
+/// [`StreamBuilder`]s), since each socket is subject to independent network delays.
 #[derive(Debug)]
 pub struct Streams<T> {
     pub streams: HashMap<ExchangeId, mpsc::UnboundedReceiver<T>>,
+    /// Health of every connection distributing to the [`Streams`], see [`Streams::status`].
+    pub health: StreamHealth,
+    /// Rolling feed latency of every exchange & kind distributing to the [`Streams`], see
+    /// [`LatencyStats::snapshot`].
+    pub latency: LatencyStats,
 }
 
 impl<T> Streams<T> {
@@ -29,12 +92,32 @@ impl<T> Streams<T> {
         StreamBuilder::<Kind>::new()
     }
 
+    /// Construct a [`StreamBuilder`] for configuring new
+    /// [`MarketEvent<SubKind::Event, Meta>`](crate::event::MarketEvent) [`Streams`], each tagged
+    /// with user metadata of type `Meta`, see
+    /// [`StreamBuilder::subscribe_with_meta`].
+    pub fn builder_with_meta<Kind, Meta>() -> StreamBuilder<Kind, Meta>
+    where
+        Kind: SubKind,
+        Meta: EventMeta,
+    {
+        StreamBuilder::<Kind, Meta>::new_with_meta()
+    }
+
     /// Construct a [`MultiStreamBuilder`] for configuring new
     /// [`MarketEvent<T>`](crate::event::MarketEvent) [`Streams`].
     pub fn builder_multi() -> MultiStreamBuilder<T> {
         MultiStreamBuilder::<T>::new()
     }
 
+    /// Compute the current [`ConnectionStatus`] (including it's 0-100 health score) of every
+    /// connection distributing to the [`Streams`].
+    ///
+    /// Clone the `health` field beforehand to continue monitoring after [`Streams::join`].
+    pub fn status(&self) -> Vec<ConnectionStatus> {
+        self.health.status()
+    }
+
     /// Remove an exchange [`mpsc::UnboundedReceiver`] from the [`Streams`] `HashMap`.
     pub fn select(&mut self, exchange: ExchangeId) -> Option<mpsc::UnboundedReceiver<T>> {
         self.streams.remove(&exchange)
@@ -42,19 +125,24 @@ impl<T> Streams<T> {
 
     /// Join all exchange [`mpsc::UnboundedReceiver`] streams into a unified
     /// [`mpsc::UnboundedReceiver`].
+    ///
+    /// The events of each exchange are forwarded in order, but are interleaved with those of
+    /// other exchanges as they are received. See [`Streams`] ordering.
+    ///
+    /// Dropping the unified [`mpsc::UnboundedReceiver`] shuts down every consumer loop of the
+    /// [`Streams`], closing their connections.
     pub async fn join(self) -> mpsc::UnboundedReceiver<T>
     where
         T: Send + 'static,
     {
         let (joined_tx, joined_rx) = mpsc::unbounded_channel();
 
-        for mut exchange_rx in self.streams.into_values() {
-            let joined_tx = joined_tx.clone();
-            tokio::spawn(async move {
-                while let Some(event) = exchange_rx.recv().await {
-                    let _ = joined_tx.send(event);
-                }
-            });
+        for exchange_rx in self.streams.into_values() {
+            tokio::spawn(shutdown::forward(
+                exchange_rx,
+                joined_tx.clone(),
+                std::convert::identity,
+            ));
         }
 
         joined_rx
@@ -0,0 +1,446 @@
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    exchange::poll::Poll,
+    streams::config::StreamConfig,
+    subscription::{SubKind, Subscription},
+    Identifier, MarketStream,
+};
+use async_trait::async_trait;
+use futures::Stream;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll as TaskPoll},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Default [`Duration`] between polls of each [`Subscription`] by a [`PollingStream`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default minimum [`Duration`] between consecutive REST requests of a [`PollingStream`].
+pub const DEFAULT_POLL_REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default [`Duration`] waited before retrying the first failed poll of a [`Subscription`].
+pub const DEFAULT_POLL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Configuration of a [`PollingStream`], configured per exchange on each
+/// [`StreamBuilder<SubKind>`](super::builder::StreamBuilder) via
+/// [`poll_config`](super::builder::StreamBuilder::poll_config), and handed to each connection via
+/// it's [`StreamConfig`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PollConfig {
+    /// [`Duration`] between polls of each [`Subscription`].
+    pub interval: Duration,
+    /// Minimum [`Duration`] between consecutive REST requests across every [`Subscription`] of
+    /// the [`PollingStream`], respecting the exchange REST rate limits.
+    pub request_interval: Duration,
+    /// [`Duration`] waited before retrying the first failed poll of a [`Subscription`], doubled
+    /// for each consecutive failure up to the `interval`.
+    pub retry_backoff: Duration,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            request_interval: DEFAULT_POLL_REQUEST_INTERVAL,
+            retry_backoff: DEFAULT_POLL_RETRY_BACKOFF,
+        }
+    }
+}
+
+impl PollConfig {
+    /// Construct a new [`Self`] polling each [`Subscription`] every `interval`, using the
+    /// default request interval & retry backoff.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Self::default()
+        }
+    }
+
+    /// Set the minimum [`Duration`] between consecutive REST requests.
+    pub fn with_request_interval(mut self, request_interval: Duration) -> Self {
+        self.request_interval = request_interval;
+        self
+    }
+
+    /// Set the [`Duration`] waited before retrying the first failed poll of a [`Subscription`].
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// [`Duration`] waited before retrying a [`Subscription`] after the provided number of
+    /// consecutive failed polls.
+    fn backoff(&self, failures: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(1 << failures.saturating_sub(1).min(16))
+            .min(self.interval.max(self.retry_backoff))
+    }
+}
+
+/// Convenient type alias for the boxed [`Stream`] driving a [`PollingStream`].
+type PollStream<T> = Pin<Box<dyn Stream<Item = Result<MarketEvent<T>, DataError>> + Send>>;
+
+/// [`MarketStream`] for market data without a WebSocket channel, periodically fetching the
+/// [`MarketEvent<T>`](MarketEvent)s of each [`Subscription`] via an exchange REST API.
+///
+/// - Each [`Subscription`] is polled every [`PollConfig::interval`], with consecutive requests
+///   spaced by at least [`PollConfig::request_interval`].
+/// - Values equal to the last one yielded for the [`Subscription`] are dropped, so only changes
+///   are yielded.
+/// - Failed polls are yielded as non-terminal [`DataError`]s (logged by the consumer loop) and
+///   retried with a [`PollConfig::retry_backoff`], rather than ending the stream.
+///
+/// Used as the [`StreamSelector::Stream`](crate::exchange::StreamSelector::Stream) of exchanges
+/// implementing [`Poll<SubKind>`](Poll), or constructed directly via [`PollingStream::new`] with
+/// a custom fetch function.
+pub struct PollingStream<Exchange, Kind>
+where
+    Kind: SubKind,
+{
+    stream: PollStream<Kind::Event>,
+    phantom: PhantomData<fn() -> Exchange>,
+}
+
+impl<Exchange, Kind> Debug for PollingStream<Exchange, Kind>
+where
+    Kind: SubKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PollingStream").finish_non_exhaustive()
+    }
+}
+
+impl<Exchange, Kind> PollingStream<Exchange, Kind>
+where
+    Exchange: Clone + Send + 'static,
+    Kind: SubKind + Send + 'static,
+    Kind::Event: Clone + PartialEq + Send,
+{
+    /// Construct a new [`Self`] polling the provided [`Subscription`]s with the provided `fetch`
+    /// function according to the [`PollConfig`].
+    pub fn new<FnFetch, Fut>(
+        subscriptions: Vec<Subscription<Exchange, Kind>>,
+        config: PollConfig,
+        fetch: FnFetch,
+    ) -> Self
+    where
+        FnFetch: FnMut(Subscription<Exchange, Kind>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Vec<MarketEvent<Kind::Event>>, DataError>> + Send,
+    {
+        let now = Instant::now();
+        let state = PollState {
+            next_polls: vec![now; subscriptions.len()],
+            failures: vec![0; subscriptions.len()],
+            last: vec![None; subscriptions.len()],
+            subscriptions,
+            config,
+            fetch,
+            last_request: None,
+            pending: VecDeque::new(),
+        };
+
+        let stream = futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(next) = state.pending.pop_front() {
+                    return Some((next, state));
+                }
+
+                if state.subscriptions.is_empty() {
+                    return None;
+                }
+
+                state.poll_next().await;
+            }
+        });
+
+        Self {
+            stream: Box::pin(stream),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Exchange, Kind> Stream for PollingStream<Exchange, Kind>
+where
+    Kind: SubKind,
+{
+    type Item = Result<MarketEvent<Kind::Event>, DataError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> TaskPoll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+#[async_trait]
+impl<Exchange, Kind> MarketStream<Exchange, Kind> for PollingStream<Exchange, Kind>
+where
+    Exchange: Poll<Kind> + Send + 'static,
+    Kind: SubKind + Send + Sync + 'static,
+    Kind::Event: Clone + PartialEq + Send,
+{
+    async fn init_with(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        config: &StreamConfig,
+    ) -> Result<Self, DataError>
+    where
+        Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    {
+        let config = config.clone();
+        Ok(Self::new(
+            subscriptions.to_vec(),
+            config.poll,
+            move |subscription| {
+                let config = config.clone();
+                async move { Exchange::poll(&subscription, &config).await }
+            },
+        ))
+    }
+}
+
+/// State of a [`PollingStream`], tracking when each [`Subscription`] is next polled & the last
+/// value yielded for it.
+struct PollState<Exchange, Kind, FnFetch>
+where
+    Kind: SubKind,
+{
+    subscriptions: Vec<Subscription<Exchange, Kind>>,
+    config: PollConfig,
+    fetch: FnFetch,
+    next_polls: Vec<Instant>,
+    failures: Vec<u32>,
+    last: Vec<Option<Kind::Event>>,
+    last_request: Option<Instant>,
+    pending: VecDeque<Result<MarketEvent<Kind::Event>, DataError>>,
+}
+
+impl<Exchange, Kind, FnFetch, Fut> PollState<Exchange, Kind, FnFetch>
+where
+    Exchange: Clone,
+    Kind: SubKind,
+    Kind::Event: Clone + PartialEq,
+    FnFetch: FnMut(Subscription<Exchange, Kind>) -> Fut,
+    Fut: Future<Output = Result<Vec<MarketEvent<Kind::Event>>, DataError>>,
+{
+    /// Wait until the next [`Subscription`] is due (& the rate limit allows), poll it, and queue
+    /// the changed values or error.
+    async fn poll_next(&mut self) {
+        let Some(index) = (0..self.subscriptions.len()).min_by_key(|index| self.next_polls[*index])
+        else {
+            return;
+        };
+
+        let ready = match self.last_request {
+            Some(last_request) => {
+                self.next_polls[index].max(last_request + self.config.request_interval)
+            }
+            None => self.next_polls[index],
+        };
+        tokio::time::sleep_until(ready).await;
+
+        let now = Instant::now();
+        self.last_request = Some(now);
+
+        match (self.fetch)(self.subscriptions[index].clone()).await {
+            Ok(events) => {
+                self.failures[index] = 0;
+                self.next_polls[index] = now + self.config.interval;
+
+                for event in events {
+                    if self.last[index].as_ref() == Some(&event.kind) {
+                        continue;
+                    }
+                    self.last[index] = Some(event.kind.clone());
+                    self.pending.push_back(Ok(event));
+                }
+            }
+            Err(error) => {
+                self.failures[index] += 1;
+                self.next_polls[index] = now + self.config.backoff(self.failures[index]);
+                self.pending.push_back(Err(error));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscription::open_interest::{OpenInterest, OpenInterests};
+    use crate::test_util::trade::TradeFixture;
+    use crate::{num, num::Num};
+    use barter_integration::{
+        error::SocketError,
+        model::{Instrument, InstrumentKind},
+    };
+    use futures::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Debug)]
+    struct MockExchange;
+
+    fn subscription(base: &str) -> Subscription<MockExchange, OpenInterests> {
+        Subscription::new(
+            MockExchange,
+            Instrument::from((base, "usdt", InstrumentKind::FuturePerpetual)),
+            OpenInterests,
+        )
+    }
+
+    fn event(
+        subscription: &Subscription<MockExchange, OpenInterests>,
+        contracts: Num,
+    ) -> MarketEvent<OpenInterest> {
+        TradeFixture::new()
+            .with_market("mock", "usdt")
+            .with_instrument_kind(subscription.instrument.kind)
+            .event(
+                0,
+                subscription.instrument.base.as_ref(),
+                OpenInterest { contracts },
+            )
+    }
+
+    /// Mock fetch function yielding the provided responses in order (repeating the last), and
+    /// recording the base asset & elapsed time of each request.
+    #[allow(clippy::type_complexity)]
+    fn mock_fetch(
+        responses: Vec<Result<Num, ()>>,
+    ) -> (
+        Arc<Mutex<Vec<(String, Duration)>>>,
+        impl FnMut(
+                Subscription<MockExchange, OpenInterests>,
+            )
+                -> std::future::Ready<Result<Vec<MarketEvent<OpenInterest>>, DataError>>
+            + Send,
+    ) {
+        let start = Instant::now();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut responses = VecDeque::from(responses);
+
+        let fetch = {
+            let requests = Arc::clone(&requests);
+            move |subscription: Subscription<MockExchange, OpenInterests>| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push((subscription.instrument.base.to_string(), start.elapsed()));
+                let response = match responses.len() {
+                    1 => responses[0],
+                    _ => responses.pop_front().unwrap(),
+                };
+                std::future::ready(
+                    response
+                        .map(|contracts| vec![event(&subscription, contracts)])
+                        .map_err(|()| DataError::Socket(SocketError::Sink)),
+                )
+            }
+        };
+
+        (requests, fetch)
+    }
+
+    fn contracts(event: Result<MarketEvent<OpenInterest>, DataError>) -> Result<Num, ()> {
+        event.map(|event| event.kind.contracts).map_err(|_| ())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_polling_stream_drops_unchanged_values() {
+        let (requests, fetch) = mock_fetch(vec![
+            Ok(num!(1.0)),
+            Ok(num!(1.0)),
+            Ok(num!(2.0)),
+            Ok(num!(2.0)),
+            Ok(num!(3.0)),
+        ]);
+        let mut stream = PollingStream::new(
+            vec![subscription("btc")],
+            PollConfig::new(Duration::from_secs(5)),
+            fetch,
+        );
+
+        let actual = vec![
+            contracts(stream.next().await.unwrap()),
+            contracts(stream.next().await.unwrap()),
+            contracts(stream.next().await.unwrap()),
+        ];
+        assert_eq!(actual, vec![Ok(num!(1.0)), Ok(num!(2.0)), Ok(num!(3.0))]);
+
+        let elapsed = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, elapsed)| elapsed.as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(elapsed, vec![0, 5, 10, 15, 20]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_polling_stream_retries_failed_polls() {
+        let (requests, fetch) = mock_fetch(vec![Err(()), Err(()), Err(()), Ok(num!(1.0))]);
+        let mut stream = PollingStream::new(
+            vec![subscription("btc")],
+            PollConfig::new(Duration::from_secs(5)).with_retry_backoff(Duration::from_secs(2)),
+            fetch,
+        );
+
+        let actual = vec![
+            contracts(stream.next().await.unwrap()),
+            contracts(stream.next().await.unwrap()),
+            contracts(stream.next().await.unwrap()),
+            contracts(stream.next().await.unwrap()),
+        ];
+        assert_eq!(actual, vec![Err(()), Err(()), Err(()), Ok(num!(1.0))]);
+
+        // Backoff doubles for each consecutive failure, capped at the poll interval
+        let elapsed = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, elapsed)| elapsed.as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(elapsed, vec![0, 2, 6, 11]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_polling_stream_rate_limits_requests() {
+        let (requests, fetch) = mock_fetch(vec![
+            Ok(num!(1.0)),
+            Ok(num!(2.0)),
+            Ok(num!(3.0)),
+            Ok(num!(4.0)),
+        ]);
+        let mut stream = PollingStream::new(
+            vec![subscription("btc"), subscription("eth")],
+            PollConfig::new(Duration::from_secs(5)).with_request_interval(Duration::from_secs(2)),
+            fetch,
+        );
+
+        for _ in 0..4 {
+            stream.next().await.unwrap().unwrap();
+        }
+
+        let actual = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(base, elapsed)| (base.clone(), elapsed.as_secs()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![
+                ("btc".to_string(), 0),
+                ("eth".to_string(), 2),
+                ("btc".to_string(), 5),
+                ("eth".to_string(), 7),
+            ]
+        );
+    }
+}
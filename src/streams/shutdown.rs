@@ -0,0 +1,109 @@
+use futures::{Stream, StreamExt};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// Outcome of awaiting the next item of a [`Stream`] via [`next_or_closed`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Next<T> {
+    /// Next item yielded by the [`Stream`].
+    Item(T),
+    /// [`Stream`] ended.
+    Ended,
+    /// Downstream receiver was dropped, so the consumer has shut down.
+    Closed,
+}
+
+impl<T> From<Option<T>> for Next<T> {
+    fn from(item: Option<T>) -> Self {
+        match item {
+            Some(item) => Self::Item(item),
+            None => Self::Ended,
+        }
+    }
+}
+
+/// Await the next item of the provided [`Stream`], unless the receiver of the downstream
+/// `tx` is dropped first (eg/ `stream.take(n)` completed & was dropped), in which case
+/// [`Next::Closed`] is returned.
+///
+/// This is cancellation safe, and notices the receiver being dropped even whilst the
+/// [`Stream`] is quiet, so a task never blocks forever on a consumer that has gone.
+pub async fn next_or_closed<St, T>(stream: &mut St, tx: &mpsc::UnboundedSender<T>) -> Next<St::Item>
+where
+    St: Stream + Unpin,
+{
+    tokio::select! {
+        biased;
+        _ = tx.closed() => Next::Closed,
+        item = stream.next() => Next::from(item),
+    }
+}
+
+/// Sleep for the provided [`Duration`], unless the receiver of the downstream `tx` is dropped
+/// first. Returns `true` if the receiver was dropped.
+pub async fn sleep_or_closed<T>(duration: Duration, tx: &mpsc::UnboundedSender<T>) -> bool {
+    tokio::select! {
+        biased;
+        _ = tx.closed() => true,
+        _ = tokio::time::sleep(duration) => false,
+    }
+}
+
+/// Forward every item received via the upstream `rx` to the downstream `tx`, mapped using the
+/// provided function, until the upstream ends or the downstream receiver is dropped.
+///
+/// Once the downstream receiver is dropped the upstream `rx` is dropped too, propagating the
+/// shutdown to the tasks sending to it (eg/ the consumer loops of [`Streams::join`]).
+///
+/// [`Streams::join`]: super::Streams::join
+pub async fn forward<T, U, F>(rx: mpsc::UnboundedReceiver<T>, tx: mpsc::UnboundedSender<U>, map: F)
+where
+    F: Fn(T) -> U,
+{
+    let mut rx = UnboundedReceiverStream::new(rx);
+    while let Next::Item(item) = next_or_closed(&mut rx, &tx).await {
+        if tx.send(map(item)).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_next_or_closed() {
+        let (tx, rx) = mpsc::unbounded_channel::<u64>();
+        let (stream_tx, stream_rx) = mpsc::unbounded_channel::<u64>();
+        let mut stream = UnboundedReceiverStream::new(stream_rx);
+
+        stream_tx.send(1).unwrap();
+        assert_eq!(next_or_closed(&mut stream, &tx).await, Next::Item(1));
+
+        // Quiet Stream still notices the downstream receiver being dropped
+        drop(rx);
+        assert_eq!(next_or_closed(&mut stream, &tx).await, Next::Closed);
+
+        let (tx, _rx) = mpsc::unbounded_channel::<u64>();
+        drop(stream_tx);
+        assert_eq!(next_or_closed(&mut stream, &tx).await, Next::Ended);
+    }
+
+    #[tokio::test]
+    async fn test_forward_ends_once_downstream_dropped() {
+        let (upstream_tx, upstream_rx) = mpsc::unbounded_channel::<u64>();
+        let (tx, mut rx) = mpsc::unbounded_channel::<u64>();
+        let forwarder = tokio::spawn(forward(upstream_rx, tx, |item| item * 2));
+
+        upstream_tx.send(1).unwrap();
+        assert_eq!(rx.recv().await, Some(2));
+
+        drop(rx);
+        tokio::time::timeout(Duration::from_secs(1), upstream_tx.closed())
+            .await
+            .expect("upstream receiver should be dropped once downstream is dropped");
+        forwarder.await.unwrap();
+    }
+}
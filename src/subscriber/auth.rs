@@ -0,0 +1,164 @@
+use crate::{error::DataError, exchange::Connector, subscriber::transport::WebSocket};
+use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tracing::debug;
+
+/// Exchange API key credentials used to log in to a connection before subscribing, where the
+/// exchange [`Connector::login`] supports it.
+///
+/// Always optional: public market data never requires [`Credentials`], but some exchanges grant
+/// logged in connections higher limits or additional channels (eg/ Okx, see
+/// [`exchange::okx::login`](crate::exchange::okx::login)).
+///
+/// Set via [`StreamBuilder::credentials`](crate::streams::builder::StreamBuilder::credentials),
+/// which hands them to each connection via it's
+/// [`StreamConfig`](crate::streams::config::StreamConfig). Secrets are redacted from the [`Debug`] output, and never logged.
+#[derive(Clone, Eq, PartialEq, Deserialize)]
+pub struct Credentials {
+    pub api_key: String,
+    pub secret: String,
+    /// Passphrase chosen when creating the API key, required by some exchanges (eg/ Okx).
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field(
+                "passphrase",
+                &self.passphrase.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// Construct a new [`Self`] from the provided API key & secret.
+    pub fn new<Key, Secret>(api_key: Key, secret: Secret) -> Self
+    where
+        Key: Into<String>,
+        Secret: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            passphrase: None,
+        }
+    }
+
+    /// Set the API key passphrase of [`Self`].
+    pub fn with_passphrase<S>(self, passphrase: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            passphrase: Some(passphrase.into()),
+            ..self
+        }
+    }
+}
+
+/// Short-lived token issued by an exchange REST API (eg/ a Kraken WebSockets token), used to
+/// authenticate private channel subscriptions of a connection.
+///
+/// Set by the private [`MarketStream`](crate::MarketStream) on the
+/// [`StreamConfig`](crate::streams::config::StreamConfig) of each connection it initialises.
+/// Redacted from the [`Debug`] output, and never logged.
+#[derive(Clone, Eq, PartialEq)]
+pub struct AuthToken(pub String);
+
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AuthToken").field(&"<redacted>").finish()
+    }
+}
+
+/// Log in to the provided [`WebSocket`] using the [`Connector::credentials`] of the provided
+/// exchange instance, else the provided connection [`Credentials`], if any, and the exchange
+/// supports logging in via [`Connector::login`].
+///
+/// Waits for the login response recognised by [`Connector::login_response`] for at most the
+/// [`Connector::subscription_timeout`], so subscriptions are only sent once logged in. Unusable
+/// [`Credentials`] (eg/ a missing passphrase), or a rejected or unanswered login, fail with a
+/// [`DataError::Auth`].
+pub async fn login<Exchange>(
+    exchange: Option<&Exchange>,
+    websocket: &mut WebSocket,
+    credentials: Option<&Credentials>,
+) -> Result<(), DataError>
+where
+    Exchange: Connector,
+{
+    let Some(credentials) = exchange
+        .and_then(Connector::credentials)
+        .or_else(|| credentials.cloned())
+    else {
+        return Ok(());
+    };
+
+    let Some(request) = Exchange::login(&credentials)? else {
+        return Ok(());
+    };
+
+    // Login payloads are not passed to the OutboundObserver since they contain the API key
+    debug!(exchange = %Exchange::ID, "sending exchange login request");
+    websocket
+        .send(request)
+        .await
+        .map_err(SocketError::WebSocket)?;
+
+    let timeout = Exchange::subscription_timeout();
+    tokio::time::timeout(timeout, async {
+        loop {
+            match websocket.next().await {
+                Some(Ok(WsMessage::Text(payload))) => match Exchange::login_response(&payload) {
+                    Some(result) => break result,
+                    None => continue,
+                },
+                Some(Ok(WsMessage::Close(close_frame))) => {
+                    break Err(DataError::Socket(SocketError::Subscribe(format!(
+                        "received WebSocket CloseFrame whilst logging in: {close_frame:?}"
+                    ))))
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(error)) => break Err(DataError::Socket(SocketError::WebSocket(error))),
+                None => {
+                    break Err(DataError::Socket(SocketError::Subscribe(
+                        "WebSocket stream terminated unexpectedly whilst logging in".to_string(),
+                    )))
+                }
+            }
+        }
+    })
+    .await
+    .map_err(|_| DataError::Auth {
+        exchange: Exchange::ID,
+        reason: format!("login timeout reached: {timeout:?}"),
+    })??;
+
+    debug!(exchange = %Exchange::ID, "logged in to exchange");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_debug_redacts_secrets() {
+        let credentials = Credentials::new("key", "s3cr3t").with_passphrase("p4ssphrase");
+        assert_eq!(credentials.passphrase.as_deref(), Some("p4ssphrase"));
+
+        let debug = format!("{credentials:?}");
+        assert!(debug.contains("key"));
+        assert!(!debug.contains("s3cr3t"));
+        assert!(!debug.contains("p4ssphrase"));
+
+        let debug = format!("{:?}", AuthToken("t0k3n".to_string()));
+        assert!(!debug.contains("t0k3n"));
+    }
+}
@@ -0,0 +1,323 @@
+use super::transport::WsStream;
+use barter_integration::protocol::websocket::{WsError, WsMessage};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Bounded buffer of market data [`WsMessage`]s received whilst a
+/// [`SubscriptionValidator`](super::validator::SubscriptionValidator) is still awaiting
+/// subscription responses.
+///
+/// If [`WsConfig::buffer_until_validated`](super::transport::WsConfig::buffer_until_validated)
+/// is configured, the buffered messages are replayed in order ahead of the live messages once
+/// every subscription is validated, see [`BufferedStream`]. Otherwise the buffer is disabled &
+/// such messages are discarded.
+///
+/// Once full, the oldest buffered message is dropped to make room for the newest. Messages a
+/// stream can't be initialised without are [`retained`](ValidationBuffer::retain) regardless.
+///
+/// The time each message was received is buffered alongside it, so it's replayed with the time
+/// it was received rather than the time it was replayed.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationBuffer {
+    capacity: Option<usize>,
+    messages: VecDeque<(DateTime<Utc>, WsMessage)>,
+    /// Indices into `messages` of the [`retained`](Self::retain) messages, which are never
+    /// dropped & don't count towards the capacity.
+    retained: Vec<usize>,
+    dropped: usize,
+}
+
+impl ValidationBuffer {
+    /// Construct a new [`Self`] holding at most `capacity` messages, or a disabled [`Self`] that
+    /// discards every message if `capacity` is `None`.
+    pub fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity,
+            messages: VecDeque::with_capacity(capacity.unwrap_or_default().min(1024)),
+            retained: Vec::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Determine if [`Self`] buffers messages.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity.is_some()
+    }
+
+    /// Buffer the provided [`WsMessage`], dropping the oldest buffered message if full.
+    pub fn push(&mut self, message: WsMessage) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+
+        if self.messages.len() - self.retained.len() >= capacity {
+            self.dropped += 1;
+            match self.oldest_unretained() {
+                Some(index) => {
+                    self.messages.remove(index);
+                    self.retained
+                        .iter_mut()
+                        .filter(|retained| **retained > index)
+                        .for_each(|retained| *retained -= 1);
+                }
+                // Zero capacity, so the provided message is the one dropped
+                None => return,
+            }
+        }
+
+        self.messages.push_back((Utc::now(), message));
+    }
+
+    /// Buffer the provided [`WsMessage`] even if [`Self`] is disabled or full, since the stream
+    /// can't be initialised without it (eg/ the Bitfinex OrderBook snapshot, which is only sent
+    /// once after subscribing). Retained messages are never dropped.
+    pub fn retain(&mut self, message: WsMessage) {
+        self.retained.push(self.messages.len());
+        self.messages.push_back((Utc::now(), message));
+    }
+
+    /// Index of the oldest buffered message that was not [`retained`](Self::retain).
+    fn oldest_unretained(&self) -> Option<usize> {
+        (0..self.messages.len()).find(|index| !self.retained.contains(index))
+    }
+
+    /// Number of buffered messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Determine if no messages are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Number of messages dropped because [`Self`] was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Construct a [`BufferedStream`] that replays the buffered messages before the messages of
+    /// the provided [`Stream`].
+    pub fn into_stream<InnerStream>(self, stream: InnerStream) -> BufferedStream<InnerStream> {
+        BufferedStream {
+            buffered: self.messages,
+            stream,
+        }
+    }
+}
+
+/// [`Stream`] wrapper that yields the market data [`WsMessage`]s buffered by a
+/// [`ValidationBuffer`] in the order they were received, followed by the inner [`Stream`].
+///
+/// Each message is yielded alongside the time it was read (or buffered), which the
+/// [`ExchangeWsStream`](crate::ExchangeWsStream) passes to it's transformer as the
+/// `received_time` of the message.
+///
+/// Used as the inner [`Stream`] of an [`ExchangeWsStream`](crate::ExchangeWsStream).
+#[derive(Debug)]
+pub struct BufferedStream<InnerStream = WsStream> {
+    buffered: VecDeque<(DateTime<Utc>, WsMessage)>,
+    pub stream: InnerStream,
+}
+
+impl<InnerStream> Stream for BufferedStream<InnerStream>
+where
+    InnerStream: Stream<Item = Result<WsMessage, WsError>> + Unpin,
+{
+    type Item = Result<(DateTime<Utc>, WsMessage), WsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(buffered) = this.buffered.pop_front() {
+            return Poll::Ready(Some(Ok(buffered)));
+        }
+
+        this.stream
+            .poll_next_unpin(cx)
+            .map_ok(|message| (Utc::now(), message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(message: &str) -> WsMessage {
+        WsMessage::Text(message.to_string())
+    }
+
+    #[test]
+    fn test_validation_buffer_push() {
+        struct TestCase {
+            capacity: Option<usize>,
+            input: Vec<&'static str>,
+            expected_buffered: Vec<&'static str>,
+            expected_dropped: usize,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: disabled buffer discards every message
+                capacity: None,
+                input: vec!["0", "1"],
+                expected_buffered: vec![],
+                expected_dropped: 0,
+            },
+            TestCase {
+                // TC1: messages within capacity are buffered in order
+                capacity: Some(3),
+                input: vec!["0", "1", "2"],
+                expected_buffered: vec!["0", "1", "2"],
+                expected_dropped: 0,
+            },
+            TestCase {
+                // TC2: full buffer drops the oldest message
+                capacity: Some(2),
+                input: vec!["0", "1", "2", "3"],
+                expected_buffered: vec!["2", "3"],
+                expected_dropped: 2,
+            },
+            TestCase {
+                // TC3: zero capacity buffer drops every message
+                capacity: Some(0),
+                input: vec!["0", "1"],
+                expected_buffered: vec![],
+                expected_dropped: 2,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut buffer = ValidationBuffer::new(test.capacity);
+            for message in test.input {
+                buffer.push(text(message));
+            }
+
+            assert_eq!(
+                buffer.dropped(),
+                test.expected_dropped,
+                "TC{} failed",
+                index
+            );
+            let actual = buffer
+                .messages
+                .into_iter()
+                .map(|(_, message)| message)
+                .collect::<Vec<_>>();
+            let expected = test
+                .expected_buffered
+                .into_iter()
+                .map(text)
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_validation_buffer_retain() {
+        struct TestCase {
+            capacity: Option<usize>,
+            input: Vec<(&'static str, bool)>,
+            expected_buffered: Vec<&'static str>,
+            expected_dropped: usize,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: disabled buffer only keeps retained messages
+                capacity: None,
+                input: vec![("0", false), ("1", true), ("2", false)],
+                expected_buffered: vec!["1"],
+                expected_dropped: 0,
+            },
+            TestCase {
+                // TC1: retained messages are never dropped, nor count towards the capacity
+                capacity: Some(1),
+                input: vec![("0", true), ("1", false), ("2", false), ("3", true)],
+                expected_buffered: vec!["0", "2", "3"],
+                expected_dropped: 1,
+            },
+            TestCase {
+                // TC2: oldest message that was not retained is dropped
+                capacity: Some(2),
+                input: vec![("0", false), ("1", true), ("2", false), ("3", false)],
+                expected_buffered: vec!["1", "2", "3"],
+                expected_dropped: 1,
+            },
+            TestCase {
+                // TC3: zero capacity buffer only keeps retained messages
+                capacity: Some(0),
+                input: vec![("0", true), ("1", false)],
+                expected_buffered: vec!["0"],
+                expected_dropped: 1,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut buffer = ValidationBuffer::new(test.capacity);
+            for (message, retain) in test.input {
+                match retain {
+                    true => buffer.retain(text(message)),
+                    false => buffer.push(text(message)),
+                }
+            }
+
+            assert_eq!(
+                buffer.dropped(),
+                test.expected_dropped,
+                "TC{} failed",
+                index
+            );
+            let actual = buffer
+                .messages
+                .into_iter()
+                .map(|(_, message)| message)
+                .collect::<Vec<_>>();
+            let expected = test
+                .expected_buffered
+                .into_iter()
+                .map(text)
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffered_stream_replays_before_inner_stream() {
+        let mut buffer = ValidationBuffer::new(Some(2));
+        buffer.push(text("0"));
+        buffer.push(text("1"));
+
+        let inner = futures::stream::iter([Ok(text("2")), Ok(text("3"))]);
+        let actual = buffer
+            .into_stream(inner)
+            .map(|message| message.unwrap().1)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(actual, vec![text("0"), text("1"), text("2"), text("3")]);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_stream_yields_received_time() {
+        let mut buffer = ValidationBuffer::new(Some(1));
+        buffer.push(text("0"));
+        let buffered_time = buffer.messages[0].0;
+
+        let inner = futures::stream::iter([Ok(text("1"))]);
+        let mut stream = buffer.into_stream(inner);
+
+        // Buffered message is replayed with the time it was buffered
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let (received_time, _) = stream.next().await.unwrap().unwrap();
+        assert_eq!(received_time, buffered_time);
+
+        // Live message is yielded with the time it was read
+        let (received_time, _) = stream.next().await.unwrap().unwrap();
+        assert!(received_time > buffered_time);
+    }
+}
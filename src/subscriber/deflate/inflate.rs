@@ -0,0 +1,310 @@
+use flate2::{Decompress, FlushDecompress, Status};
+use std::io;
+
+/// Trailer removed by the sender from every permessage-deflate message, which is re-appended
+/// before inflating (ie/ the empty stored block of a `Z_SYNC_FLUSH`).
+const SYNC_FLUSH_TRAILER: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Base-2 logarithm of the LZ77 sliding window size used if the server does not negotiate
+/// `server_max_window_bits`.
+pub const DEFAULT_WINDOW_BITS: u8 = 15;
+
+/// Smallest LZ77 sliding window zlib inflates raw DEFLATE data with. RFC 7692 permits a server
+/// window of 2^8 bytes, which a 2^9 byte window also inflates.
+const MIN_WINDOW_BITS: u8 = 9;
+
+/// Initial capacity of an inflated message relative to it's compressed payload.
+const INITIAL_RATIO: usize = 4;
+
+/// DEFLATE (RFC 1951) decompressor for permessage-deflate (RFC 7692) compressed messages.
+///
+/// Wraps a raw (ie/ headerless) zlib [`Decompress`] stream sized to the negotiated server window.
+/// If the server uses context takeover (the default), back-references may reach into the
+/// previous messages, so the stream is shared between messages rather than reset.
+#[derive(Debug)]
+pub struct Inflater {
+    context_takeover: bool,
+    /// Boxed since the zlib stream state is large, and lives for the whole connection.
+    decompress: Box<Decompress>,
+}
+
+impl Inflater {
+    /// Construct a new [`Inflater`] for a server LZ77 sliding window of 2^`window_bits` bytes.
+    /// If `context_takeover` is false (ie/ the server negotiated `server_no_context_takeover`),
+    /// every message is inflated with an empty sliding window.
+    pub fn new(context_takeover: bool, window_bits: u8) -> Self {
+        Self {
+            context_takeover,
+            decompress: Box::new(Decompress::new_with_window_bits(
+                false,
+                window_bits.clamp(MIN_WINDOW_BITS, DEFAULT_WINDOW_BITS),
+            )),
+        }
+    }
+
+    /// Inflate the payload of a single compressed message, failing if the inflated message is
+    /// larger than `max_size` bytes.
+    pub fn inflate(&mut self, payload: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        if !self.context_takeover {
+            self.decompress.reset(false);
+        }
+
+        let message = self.inflate_message(payload, max_size);
+
+        // A failed message leaves the sliding window in an unknown state, so start afresh
+        if message.is_err() {
+            self.decompress.reset(false);
+        }
+        message
+    }
+
+    fn inflate_message(&mut self, payload: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        let mut input = Vec::with_capacity(payload.len() + SYNC_FLUSH_TRAILER.len());
+        input.extend_from_slice(payload);
+        input.extend_from_slice(&SYNC_FLUSH_TRAILER);
+
+        let mut message = Vec::with_capacity(
+            payload
+                .len()
+                .saturating_mul(INITIAL_RATIO)
+                .min(max_size.saturating_add(1)),
+        );
+        let mut consumed = 0;
+        loop {
+            if message.len() == message.capacity() {
+                message.reserve(message.capacity().max(input.len()));
+            }
+
+            let total_in = self.decompress.total_in();
+            let status = self
+                .decompress
+                .decompress_vec(&input[consumed..], &mut message, FlushDecompress::Sync)
+                .map_err(|error| invalid(&error.to_string()))?;
+            consumed += (self.decompress.total_in() - total_in) as usize;
+
+            if message.len() > max_size {
+                return Err(invalid("inflated message exceeds max message size"));
+            }
+
+            match status {
+                // The sender ended the stream with a final block, so the next message begins
+                // a new one
+                Status::StreamEnd => {
+                    self.decompress.reset(false);
+                    return Ok(message);
+                }
+                // Every byte has been inflated, with output capacity to spare
+                _ if consumed == input.len() && message.len() < message.capacity() => {
+                    return Ok(message);
+                }
+                Status::BufError if message.len() < message.capacity() => {
+                    return Err(invalid("no progress inflating compressed data"));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Construct an [`io::Error`] for invalid compressed data.
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("permessage-deflate: {reason}"),
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flate2::{Compress, Compression, FlushCompress};
+
+    /// Reference OKX books messages compressed with zlib (level 9, raw deflate, `Z_SYNC_FLUSH`
+    /// with the trailer removed) sharing one compression context. The first message is a dynamic
+    /// Huffman block, the second a fixed Huffman block with back-references into the first.
+    pub(crate) const ZLIB_MESSAGES: [(&str, &[u8]); 2] = [
+        (
+            r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["8476.98","415","0","13"],["8477","7","0","2"]],"bids":[["8476.97","256","0","12"]],"ts":"1597026383085","checksum":-855196043}]}"#,
+            &[
+                0x4c, 0x8c, 0xcb, 0x0e, 0x82, 0x30, 0x10, 0x45, 0xff, 0xe5, 0xae, 0x8b, 0x29, 0x94,
+                0x96, 0xd2, 0xa5, 0xba, 0x71, 0x2d, 0xae, 0x48, 0x17, 0xe5, 0x11, 0x21, 0x68, 0x31,
+                0x52, 0x56, 0x84, 0x7f, 0x77, 0xf0, 0x91, 0xb8, 0x98, 0x99, 0xdc, 0xc7, 0x99, 0x05,
+                0xee, 0x79, 0x85, 0x59, 0x50, 0x77, 0xce, 0xfb, 0xf6, 0x06, 0x83, 0x6a, 0x1c, 0x87,
+                0x09, 0x0c, 0xbd, 0x9f, 0xc2, 0xa9, 0x21, 0x63, 0x5f, 0x1c, 0xa2, 0xcb, 0xf9, 0x58,
+                0x60, 0x65, 0x70, 0x75, 0xe8, 0x47, 0x4f, 0xe6, 0xfc, 0x68, 0x5c, 0x68, 0xa9, 0x46,
+                0xc7, 0xc1, 0x94, 0x0b, 0xdc, 0x44, 0x98, 0x29, 0x4b, 0xe8, 0x34, 0x53, 0xbb, 0x5c,
+                0x53, 0x96, 0xc6, 0x92, 0x36, 0xa7, 0x89, 0x05, 0x2c, 0x7b, 0x47, 0x19, 0xa9, 0xec,
+                0xeb, 0x26, 0xb0, 0x96, 0xa1, 0xea, 0x9b, 0x7f, 0x70, 0x0b, 0x13, 0xa9, 0x7e, 0xe0,
+                0xa7, 0x13, 0xa8, 0x81, 0x58, 0xe6, 0x19, 0x4f, 0x94, 0xd0, 0x82, 0xeb, 0xed, 0x71,
+                0xdd, 0xb5, 0xf5, 0x30, 0xcd, 0x77, 0x98, 0x48, 0x4b, 0x19, 0xe7, 0x8a, 0xa7, 0x62,
+                0xb5, 0xeb, 0x0b,
+            ],
+        ),
+        (
+            r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[["8476.99","411","0","12"]],"bids":[["8476.96","250","0","11"],["8476.95","1","0","1"]],"ts":"1597026383185","checksum":-1208466912}]}"#,
+            &[
+                0xaa, 0xa6, 0x99, 0x9f, 0x2c, 0xc1, 0x7e, 0x32, 0x44, 0x75, 0x1a, 0x9a, 0xf3, 0xcd,
+                0xc0, 0xce, 0x37, 0x80, 0xa9, 0x31, 0x84, 0xf9, 0x1b, 0x28, 0x05, 0x72, 0x33, 0x5c,
+                0x33, 0x36, 0x6f, 0x19, 0xa2, 0x7b, 0xcb, 0xd0, 0xc8, 0xc0, 0xc2, 0xc4, 0xcc, 0xcc,
+                0xd2, 0xd0, 0x08, 0xe8, 0x2f, 0x00, 0x00,
+            ],
+        ),
+    ];
+
+    /// Raw DEFLATE compressor sharing one zlib [`Compress`] stream between messages (unless
+    /// `context_takeover` is false), used to generate permessage-deflate test frames.
+    pub(crate) struct Deflater {
+        context_takeover: bool,
+        compress: Compress,
+    }
+
+    impl Deflater {
+        pub(crate) fn new(context_takeover: bool) -> Self {
+            Self {
+                context_takeover,
+                compress: Compress::new_with_window_bits(
+                    Compression::best(),
+                    false,
+                    DEFAULT_WINDOW_BITS,
+                ),
+            }
+        }
+
+        /// Compress a single message, removing the sync flush trailer.
+        pub(crate) fn deflate(&mut self, message: &[u8]) -> Vec<u8> {
+            if !self.context_takeover {
+                self.compress.reset();
+            }
+
+            let mut output = Vec::with_capacity(message.len() + 64);
+            let mut consumed = 0;
+            loop {
+                if output.len() == output.capacity() {
+                    output.reserve(output.capacity());
+                }
+                let total_in = self.compress.total_in();
+                self.compress
+                    .compress_vec(&message[consumed..], &mut output, FlushCompress::Sync)
+                    .unwrap();
+                consumed += (self.compress.total_in() - total_in) as usize;
+                if consumed == message.len() && output.len() < output.capacity() {
+                    break;
+                }
+            }
+
+            // zlib emits nothing for an empty message that follows a flush, so send an empty
+            // stored block header instead (RFC 7692 section 7.2.3.6)
+            match output.strip_suffix(&SYNC_FLUSH_TRAILER) {
+                Some(payload) => payload.to_vec(),
+                None if output.is_empty() => vec![0x00],
+                None => panic!("compressed message missing sync flush trailer"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_inflate_zlib_messages_with_context_takeover() {
+        let mut inflater = Inflater::new(true, DEFAULT_WINDOW_BITS);
+
+        for (index, (expected, compressed)) in ZLIB_MESSAGES.into_iter().enumerate() {
+            let actual = inflater.inflate(compressed, usize::MAX).unwrap();
+            assert_eq!(actual, expected.as_bytes(), "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_inflate() {
+        struct TestCase {
+            input: Vec<u8>,
+            max_size: usize,
+            expected: Result<&'static [u8], ()>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: stored block, followed by the sync flush block header
+                input: vec![
+                    0x00, 0x05, 0x00, 0xfa, 0xff, b'h', b'e', b'l', b'l', b'o', 0x00,
+                ],
+                max_size: usize::MAX,
+                expected: Ok(b"hello"),
+            },
+            TestCase {
+                // TC1: fixed Huffman block (RFC 7692 section 7.2.3.1 example)
+                input: vec![0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00],
+                max_size: usize::MAX,
+                expected: Ok(b"Hello"),
+            },
+            TestCase {
+                // TC2: fixed Huffman block exceeding max size
+                input: vec![0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00],
+                max_size: 4,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC3: stored block length mismatch
+                input: vec![0x00, 0x05, 0x00, 0x00, 0x00, b'h', b'e', b'l', b'l', b'o'],
+                max_size: usize::MAX,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC4: invalid block type
+                input: vec![0x06],
+                max_size: usize::MAX,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC5: back-reference before the start of the window
+                input: {
+                    let mut deflater = Deflater::new(true);
+                    deflater.deflate(b"abcdef");
+                    deflater.deflate(b"abcdef")
+                },
+                max_size: usize::MAX,
+                expected: Err(()),
+            },
+            TestCase {
+                // TC6: dynamic Huffman block with too many length symbols
+                input: vec![0xfc, 0xff],
+                max_size: usize::MAX,
+                expected: Err(()),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual =
+                Inflater::new(false, DEFAULT_WINDOW_BITS).inflate(&test.input, test.max_size);
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "TC{} failed", index),
+                (Err(_), Err(_)) => {
+                    // Test passed
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deflate_inflate_round_trip() {
+        let messages = [
+            r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"snapshot"}"#,
+            r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update"}"#,
+            "",
+            "a",
+        ];
+
+        for context_takeover in [true, false] {
+            let mut deflater = Deflater::new(context_takeover);
+            let mut inflater = Inflater::new(context_takeover, DEFAULT_WINDOW_BITS);
+
+            for (index, message) in messages.iter().enumerate() {
+                let compressed = deflater.deflate(message.as_bytes());
+                let actual = inflater.inflate(&compressed, usize::MAX).unwrap();
+                assert_eq!(actual, message.as_bytes(), "TC{} failed", index);
+            }
+        }
+    }
+}
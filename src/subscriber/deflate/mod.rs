@@ -0,0 +1,761 @@
+use self::inflate::{Inflater, DEFAULT_WINDOW_BITS};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// DEFLATE decompressor used to inflate permessage-deflate compressed messages.
+pub mod inflate;
+
+/// `Sec-WebSocket-Extensions` handshake header offering permessage-deflate to the server.
+pub const PERMESSAGE_DEFLATE_OFFER: &str = "permessage-deflate";
+
+/// End of the HTTP handshake response headers.
+const HANDSHAKE_END: &[u8] = b"\r\n\r\n";
+
+/// Size of each read from the inner stream whilst compressed frames are being inflated.
+const READ_CHUNK: usize = 16 * 1024;
+
+/// Maximum size of a compressed frame payload buffered by a [`DeflateStream`] if the
+/// [`WsConfig`](super::transport::WsConfig) leaves it unlimited (ie/ the tungstenite default).
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 << 20;
+
+/// Maximum size of a compressed or inflated message buffered by a [`DeflateStream`] if the
+/// [`WsConfig`](super::transport::WsConfig) leaves it unlimited (ie/ the tungstenite default).
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+/// WebSocket frame opcodes.
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_CLOSE: u8 = 0x8;
+
+/// permessage-deflate (RFC 7692) parameters accepted by the server in it's handshake response.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+pub struct PermessageDeflate {
+    /// If true, the server resets it's compression context after every message.
+    pub server_no_context_takeover: bool,
+    /// Base-2 logarithm of the server LZ77 sliding window size, defaults to 15 if `None`.
+    pub server_max_window_bits: Option<u8>,
+}
+
+impl PermessageDeflate {
+    /// Parse the permessage-deflate parameters from a `Sec-WebSocket-Extensions` header value,
+    /// returning `None` if permessage-deflate was not accepted.
+    ///
+    /// eg/ `permessage-deflate; server_no_context_takeover; server_max_window_bits=10`
+    pub fn parse(header: &str) -> Option<Self> {
+        header.split(',').find_map(|extension| {
+            let mut params = extension.split(';').map(str::trim);
+            if !params.next()?.eq_ignore_ascii_case("permessage-deflate") {
+                return None;
+            }
+
+            Some(params.fold(Self::default(), |mut negotiated, param| {
+                let (name, value) = match param.split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                    None => (param, None),
+                };
+                if name.eq_ignore_ascii_case("server_no_context_takeover") {
+                    negotiated.server_no_context_takeover = true;
+                } else if name.eq_ignore_ascii_case("server_max_window_bits") {
+                    negotiated.server_max_window_bits = value.and_then(|bits| bits.parse().ok());
+                }
+                negotiated
+            }))
+        })
+    }
+}
+
+/// Byte stream between a WebSocket and it's underlying transport during the HTTP handshake, that
+/// yields nothing beyond the end of the handshake response.
+///
+/// Used to determine if permessage-deflate was negotiated before deciding which stream the
+/// WebSocket reads from, so any WebSocket frames received alongside the handshake response are
+/// kept in [`HandshakeStream::take_remaining`] rather than being consumed by tungstenite.
+#[derive(Debug)]
+pub struct HandshakeStream<S> {
+    inner: S,
+    /// Last bytes yielded, in case the end of the handshake response spans two reads.
+    tail: Vec<u8>,
+    /// Bytes read from the inner stream beyond the end of the handshake response.
+    remaining: Vec<u8>,
+    complete: bool,
+}
+
+impl<S> HandshakeStream<S> {
+    /// Construct a new [`HandshakeStream`] wrapping the inner transport.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            tail: Vec::with_capacity(HANDSHAKE_END.len()),
+            remaining: Vec::new(),
+            complete: false,
+        }
+    }
+
+    /// Take the bytes read from the inner stream beyond the end of the handshake response.
+    pub fn take_remaining(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.remaining)
+    }
+}
+
+impl<S> AsyncRead for HandshakeStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.complete {
+            return Poll::Ready(Ok(()));
+        }
+
+        let mut chunk = vec![0u8; buf.remaining().min(READ_CHUNK)];
+        let mut chunk = ReadBuf::new(&mut chunk);
+        match Pin::new(&mut this.inner).poll_read(cx, &mut chunk) {
+            Poll::Ready(Ok(())) => {
+                let filled = chunk.filled();
+                let window = [this.tail.as_slice(), filled].concat();
+                let end = window
+                    .windows(HANDSHAKE_END.len())
+                    .position(|window| window == HANDSHAKE_END)
+                    .map(|position| position + HANDSHAKE_END.len() - this.tail.len());
+
+                let len = end.unwrap_or(filled.len());
+                buf.put_slice(&filled[..len]);
+                if end.is_some() {
+                    this.remaining.extend_from_slice(&filled[len..]);
+                    this.complete = true;
+                } else {
+                    let start = window.len().saturating_sub(HANDSHAKE_END.len() - 1);
+                    this.tail = window[start..].to_vec();
+                }
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S> AsyncWrite for HandshakeStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// Byte stream between a WebSocket and it's underlying transport that transparently inflates
+/// permessage-deflate compressed messages sent by the server.
+///
+/// Since tungstenite rejects frames with the RSV1 bit set, the [`DeflateStream`] rewrites each
+/// compressed message into a single uncompressed frame before tungstenite reads it. Outbound
+/// messages are always sent uncompressed, which RFC 7692 permits.
+///
+/// Frames are buffered whole before being processed, so frame & message sizes are always capped,
+/// falling back to [`DEFAULT_MAX_FRAME_SIZE`] & [`DEFAULT_MAX_MESSAGE_SIZE`] if unlimited.
+///
+/// Only used once the server has accepted the [`PERMESSAGE_DEFLATE_OFFER`] in it's handshake
+/// response, see [`HandshakeStream`].
+#[derive(Debug)]
+pub struct DeflateStream<S> {
+    inner: S,
+    negotiated: PermessageDeflate,
+    inflater: Inflater,
+    /// Opcode & payload of a fragmented compressed message being received.
+    fragmented: Option<(u8, Vec<u8>)>,
+    max_frame_size: usize,
+    max_message_size: usize,
+    /// Bytes read from the inner stream that have not been processed yet.
+    input: Vec<u8>,
+    /// If true, the [`Self::input`] has not been processed since it was last extended.
+    unprocessed: bool,
+    /// Processed bytes waiting to be read by the WebSocket.
+    output: Vec<u8>,
+    output_position: usize,
+}
+
+/// Decoded header of a WebSocket frame.
+struct FrameHeader {
+    is_final: bool,
+    rsv1: bool,
+    opcode: u8,
+    masked: bool,
+    header_len: usize,
+    payload_len: usize,
+}
+
+impl FrameHeader {
+    /// Parse the header of the next frame, returning `None` if more bytes are required.
+    fn parse(buffer: &[u8]) -> Option<Self> {
+        let (first, second) = (*buffer.first()?, *buffer.get(1)?);
+        let masked = second & 0x80 != 0;
+
+        let (payload_len, mut header_len) = match second & 0x7f {
+            126 => (
+                u16::from_be_bytes(buffer.get(2..4)?.try_into().ok()?) as usize,
+                4,
+            ),
+            127 => (
+                usize::try_from(u64::from_be_bytes(buffer.get(2..10)?.try_into().ok()?))
+                    .unwrap_or(usize::MAX),
+                10,
+            ),
+            len => (len as usize, 2),
+        };
+        if masked {
+            header_len += 4;
+        }
+
+        Some(Self {
+            is_final: first & 0x80 != 0,
+            rsv1: first & 0x40 != 0,
+            opcode: first & 0x0f,
+            masked,
+            header_len,
+            payload_len,
+        })
+    }
+}
+
+/// Encode an unmasked, final frame with the provided opcode & payload.
+fn encode_frame(output: &mut Vec<u8>, opcode: u8, payload: &[u8]) {
+    output.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => output.push(len as u8),
+        len @ 126..=0xffff => {
+            output.push(126);
+            output.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            output.push(127);
+            output.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    output.extend_from_slice(payload);
+}
+
+impl<S> DeflateStream<S> {
+    /// Construct a new [`DeflateStream`] wrapping the inner transport, inflating the messages
+    /// compressed with the provided [`PermessageDeflate`] parameters accepted by the server.
+    ///
+    /// Any bytes already read from the inner transport beyond the handshake response (see
+    /// [`HandshakeStream::take_remaining`]) are processed before reading further.
+    ///
+    /// A `None` max frame or message size falls back to [`DEFAULT_MAX_FRAME_SIZE`] or
+    /// [`DEFAULT_MAX_MESSAGE_SIZE`] respectively.
+    pub fn new(
+        inner: S,
+        negotiated: PermessageDeflate,
+        remaining: Vec<u8>,
+        max_frame_size: Option<usize>,
+        max_message_size: Option<usize>,
+    ) -> Self {
+        Self {
+            inner,
+            negotiated,
+            inflater: Inflater::new(
+                !negotiated.server_no_context_takeover,
+                negotiated
+                    .server_max_window_bits
+                    .unwrap_or(DEFAULT_WINDOW_BITS),
+            ),
+            fragmented: None,
+            max_frame_size: max_frame_size.unwrap_or(DEFAULT_MAX_FRAME_SIZE),
+            max_message_size: max_message_size.unwrap_or(DEFAULT_MAX_MESSAGE_SIZE),
+            unprocessed: !remaining.is_empty(),
+            input: remaining,
+            output: Vec::new(),
+            output_position: 0,
+        }
+    }
+
+    /// Negotiated [`PermessageDeflate`] parameters.
+    pub fn negotiated(&self) -> PermessageDeflate {
+        self.negotiated
+    }
+
+    /// Reference to the inner transport.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Mutable reference to the inner transport.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Process the buffered input into output readable by the WebSocket.
+    fn process(&mut self) -> io::Result<()> {
+        self.unprocessed = false;
+
+        let mut consumed = 0;
+        while let Some(header) = FrameHeader::parse(&self.input[consumed..]) {
+            // Frames are buffered whole, so reject any exceeding the cap before buffering them
+            if header.payload_len > self.max_frame_size {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "permessage-deflate: frame exceeds max frame size",
+                ));
+            }
+
+            let frame_len = header.header_len + header.payload_len;
+            if self.input.len() - consumed < frame_len {
+                break;
+            }
+            let frame = &self.input[consumed..consumed + frame_len];
+            let payload = &frame[header.header_len..];
+            consumed += frame_len;
+
+            // Masked frames from a server are invalid, so leave them for tungstenite to reject
+            if header.masked || header.opcode >= OPCODE_CLOSE {
+                self.output.extend_from_slice(frame);
+                continue;
+            }
+
+            match (header.opcode, &mut self.fragmented) {
+                (OPCODE_CONTINUATION, Some((_, message))) => {
+                    if message.len() + payload.len() > self.max_message_size {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "permessage-deflate: compressed message exceeds max message size",
+                        ));
+                    }
+                    message.extend_from_slice(payload);
+                    if header.is_final {
+                        let (opcode, message) = self.fragmented.take().unwrap_or_default();
+                        let message = self.inflater.inflate(&message, self.max_message_size)?;
+                        encode_frame(&mut self.output, opcode, &message);
+                    }
+                }
+                (opcode, None) if header.rsv1 && opcode != OPCODE_CONTINUATION => {
+                    if header.is_final {
+                        let message = self.inflater.inflate(payload, self.max_message_size)?;
+                        encode_frame(&mut self.output, opcode, &message);
+                    } else {
+                        self.fragmented = Some((opcode, payload.to_vec()));
+                    }
+                }
+                _ => self.output.extend_from_slice(frame),
+            }
+        }
+        self.input.drain(..consumed);
+
+        Ok(())
+    }
+}
+
+impl<S> AsyncRead for DeflateStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            // Serve processed output first
+            if this.output_position < this.output.len() {
+                let len = buf
+                    .remaining()
+                    .min(this.output.len() - this.output_position);
+                buf.put_slice(&this.output[this.output_position..this.output_position + len]);
+                this.output_position += len;
+                if this.output_position == this.output.len() {
+                    this.output.clear();
+                    this.output_position = 0;
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            // Process bytes read alongside the handshake response before reading further
+            if this.unprocessed {
+                this.process()?;
+                continue;
+            }
+
+            let mut chunk = [0u8; READ_CHUNK];
+            let mut chunk = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut this.inner).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(())) if chunk.filled().is_empty() => {
+                    // End of stream, so pass through any partial frame for tungstenite to handle
+                    this.output.append(&mut this.input);
+                    if this.output.is_empty() {
+                        return Poll::Ready(Ok(()));
+                    }
+                }
+                Poll::Ready(Ok(())) => {
+                    this.input.extend_from_slice(chunk.filled());
+                    this.process()?;
+                }
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S> AsyncWrite for DeflateStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscriber::deflate::inflate::tests::{Deflater, ZLIB_MESSAGES};
+    use std::{collections::VecDeque, future::poll_fn};
+
+    const HANDSHAKE: &[u8] = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+        Sec-WebSocket-Extensions: permessage-deflate\r\n\r\n";
+
+    const OPCODE_TEXT: u8 = 0x1;
+    const OPCODE_PING: u8 = 0x9;
+
+    /// In-memory transport that yields the provided chunks of bytes, one per read.
+    struct MockTransport {
+        chunks: VecDeque<Vec<u8>>,
+    }
+
+    impl AsyncRead for MockTransport {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            if let Some(mut chunk) = self.chunks.pop_front() {
+                if chunk.len() > buf.remaining() {
+                    self.chunks.push_front(chunk.split_off(buf.remaining()));
+                }
+                buf.put_slice(&chunk);
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Encode a server frame with the provided flags.
+    fn frame(opcode: u8, is_final: bool, rsv1: bool, payload: &[u8]) -> Vec<u8> {
+        let mut frame = Vec::new();
+        encode_frame(&mut frame, opcode, payload);
+        if !is_final {
+            frame[0] &= 0x7f;
+        }
+        if rsv1 {
+            frame[0] |= 0x40;
+        }
+        frame
+    }
+
+    /// Read every byte of the provided [`DeflateStream`] until the end of stream.
+    async fn read_to_end(stream: &mut DeflateStream<MockTransport>) -> io::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        loop {
+            let mut buffer = [0u8; 1024];
+            let mut buffer = ReadBuf::new(&mut buffer);
+            poll_fn(|cx| Pin::new(&mut *stream).poll_read(cx, &mut buffer)).await?;
+            if buffer.filled().is_empty() {
+                return Ok(output);
+            }
+            output.extend_from_slice(buffer.filled());
+        }
+    }
+
+    #[test]
+    fn test_permessage_deflate_parse() {
+        struct TestCase {
+            input: &'static str,
+            expected: Option<PermessageDeflate>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: permessage-deflate with default parameters
+                input: "permessage-deflate",
+                expected: Some(PermessageDeflate::default()),
+            },
+            TestCase {
+                // TC1: permessage-deflate with server parameters
+                input: "permessage-deflate; server_no_context_takeover; server_max_window_bits=10",
+                expected: Some(PermessageDeflate {
+                    server_no_context_takeover: true,
+                    server_max_window_bits: Some(10),
+                }),
+            },
+            TestCase {
+                // TC2: permessage-deflate after another extension, with a quoted parameter
+                input: "x-webkit-deflate-frame, Permessage-Deflate; server_max_window_bits=\"12\"",
+                expected: Some(PermessageDeflate {
+                    server_no_context_takeover: false,
+                    server_max_window_bits: Some(12),
+                }),
+            },
+            TestCase {
+                // TC3: permessage-deflate not accepted
+                input: "x-webkit-deflate-frame",
+                expected: None,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = PermessageDeflate::parse(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handshake_stream() {
+        struct TestCase {
+            chunk_size: usize,
+            expected_remaining: Vec<u8>,
+        }
+
+        let frames = [
+            frame(OPCODE_TEXT, true, true, ZLIB_MESSAGES[0].1),
+            frame(OPCODE_TEXT, true, false, b"uncompressed"),
+        ]
+        .concat();
+        let bytes = [HANDSHAKE, &frames].concat();
+
+        let tests = vec![
+            TestCase {
+                // TC0: frames read alongside the handshake response are remaining
+                chunk_size: usize::MAX,
+                expected_remaining: frames.clone(),
+            },
+            TestCase {
+                // TC1: end of the handshake response spans two reads
+                chunk_size: HANDSHAKE.len() - 2,
+                expected_remaining: frames[..HANDSHAKE.len() - 4].to_vec(),
+            },
+            TestCase {
+                // TC2: handshake response read one byte at a time
+                chunk_size: 1,
+                expected_remaining: vec![],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let transport = MockTransport {
+                chunks: bytes
+                    .chunks(test.chunk_size.min(bytes.len()))
+                    .map(<[u8]>::to_vec)
+                    .collect(),
+            };
+            let mut stream = HandshakeStream::new(transport);
+
+            let mut actual = Vec::new();
+            loop {
+                let mut buffer = [0u8; 1024];
+                let mut buffer = ReadBuf::new(&mut buffer);
+                poll_fn(|cx| Pin::new(&mut stream).poll_read(cx, &mut buffer))
+                    .await
+                    .unwrap();
+                if buffer.filled().is_empty() {
+                    break;
+                }
+                actual.extend_from_slice(buffer.filled());
+            }
+
+            assert_eq!(actual, HANDSHAKE, "TC{} failed", index);
+            assert_eq!(
+                stream.take_remaining(),
+                test.expected_remaining,
+                "TC{} failed",
+                index
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deflate_stream() {
+        struct TestCase {
+            remaining: Vec<u8>,
+            frames: Vec<Vec<u8>>,
+            chunk_size: usize,
+            expected: Vec<u8>,
+        }
+
+        let (zlib_first, zlib_second) = (ZLIB_MESSAGES[0], ZLIB_MESSAGES[1]);
+        let (fragment_first, fragment_second) = zlib_first.1.split_at(40);
+
+        let tests = vec![
+            TestCase {
+                // TC0: compressed messages inflated, read one byte at a time
+                remaining: vec![],
+                frames: vec![
+                    frame(OPCODE_TEXT, true, true, zlib_first.1),
+                    frame(OPCODE_TEXT, true, true, zlib_second.1),
+                ],
+                chunk_size: 1,
+                expected: [
+                    frame(OPCODE_TEXT, true, false, zlib_first.0.as_bytes()),
+                    frame(OPCODE_TEXT, true, false, zlib_second.0.as_bytes()),
+                ]
+                .concat(),
+            },
+            TestCase {
+                // TC1: fragmented compressed message with interleaved Ping, read in one chunk
+                remaining: vec![],
+                frames: vec![
+                    frame(OPCODE_TEXT, false, true, fragment_first),
+                    frame(OPCODE_PING, true, false, b"ping"),
+                    frame(OPCODE_CONTINUATION, true, false, fragment_second),
+                    frame(OPCODE_TEXT, true, false, b"uncompressed"),
+                ],
+                chunk_size: usize::MAX,
+                expected: [
+                    frame(OPCODE_PING, true, false, b"ping"),
+                    frame(OPCODE_TEXT, true, false, zlib_first.0.as_bytes()),
+                    frame(OPCODE_TEXT, true, false, b"uncompressed"),
+                ]
+                .concat(),
+            },
+            TestCase {
+                // TC2: compressed message read alongside the handshake response inflated first
+                remaining: frame(OPCODE_TEXT, true, true, zlib_first.1),
+                frames: vec![frame(OPCODE_TEXT, true, true, zlib_second.1)],
+                chunk_size: 7,
+                expected: [
+                    frame(OPCODE_TEXT, true, false, zlib_first.0.as_bytes()),
+                    frame(OPCODE_TEXT, true, false, zlib_second.0.as_bytes()),
+                ]
+                .concat(),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let bytes = test.frames.concat();
+            let transport = MockTransport {
+                chunks: bytes
+                    .chunks(test.chunk_size.min(bytes.len()))
+                    .map(<[u8]>::to_vec)
+                    .collect(),
+            };
+            let mut stream = DeflateStream::new(
+                transport,
+                PermessageDeflate::default(),
+                test.remaining,
+                None,
+                None,
+            );
+
+            let actual = read_to_end(&mut stream).await.unwrap();
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deflate_stream_max_message_size_exceeded() {
+        let transport = MockTransport {
+            chunks: VecDeque::from([frame(OPCODE_TEXT, true, true, ZLIB_MESSAGES[0].1)]),
+        };
+        let mut stream = DeflateStream::new(
+            transport,
+            PermessageDeflate::default(),
+            vec![],
+            None,
+            Some(128),
+        );
+
+        let actual = read_to_end(&mut stream).await;
+        assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn test_deflate_stream_default_max_frame_size_exceeded() {
+        // Frame header claiming a payload beyond the default cap, which is never buffered
+        let mut header = vec![0x80 | 0x40 | OPCODE_TEXT, 127];
+        header.extend_from_slice(&(DEFAULT_MAX_FRAME_SIZE as u64 + 1).to_be_bytes());
+        let transport = MockTransport {
+            chunks: VecDeque::from([header]),
+        };
+        let mut stream =
+            DeflateStream::new(transport, PermessageDeflate::default(), vec![], None, None);
+
+        let actual = read_to_end(&mut stream).await;
+        assert_eq!(actual.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// Bandwidth savings of permessage-deflate on a sample of OKX BTC-USDT `books` messages,
+    /// compressed by zlib with context takeover as the OKX server does.
+    #[tokio::test]
+    async fn test_okx_books_bandwidth_savings() {
+        let messages = include_str!("../../../tests/fixtures/okx_books_btc_usdt.jsonl")
+            .lines()
+            .collect::<Vec<_>>();
+
+        let mut deflater = Deflater::new(true);
+        let frames = messages
+            .iter()
+            .map(|message| {
+                frame(
+                    OPCODE_TEXT,
+                    true,
+                    true,
+                    &deflater.deflate(message.as_bytes()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let uncompressed = messages.iter().map(|message| message.len()).sum::<usize>();
+        let compressed = frames.iter().map(Vec::len).sum::<usize>();
+        let savings = 1.0 - compressed as f64 / uncompressed as f64;
+        assert_eq!(messages.len(), 151);
+        assert_eq!(uncompressed, 70833);
+        assert!(
+            (0.74..0.75).contains(&savings),
+            "unexpected bandwidth savings: {savings}"
+        );
+
+        // Every message is inflated back to the original
+        let transport = MockTransport {
+            chunks: frames.into(),
+        };
+        let mut stream =
+            DeflateStream::new(transport, PermessageDeflate::default(), vec![], None, None);
+        let actual = read_to_end(&mut stream).await.unwrap();
+
+        let expected = messages.iter().fold(Vec::new(), |mut expected, message| {
+            encode_frame(&mut expected, OPCODE_TEXT, message.as_bytes());
+            expected
+        });
+        assert_eq!(actual, expected);
+    }
+}
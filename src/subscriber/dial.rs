@@ -0,0 +1,247 @@
+use futures::{stream::FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    io,
+    net::SocketAddr,
+    time::Duration,
+};
+use tokio::net::{lookup_host, TcpStream};
+use tracing::debug;
+
+/// [`Duration`] an [`IpFamily::HappyEyeballs`] connection attempt is given before the attempt to
+/// the next address is started in parallel, as recommended by RFC 8305.
+pub const HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(250);
+
+/// IP address family preference used to dial the TCP connection of an exchange
+/// [`WebSocket`](super::transport::WebSocket), see
+/// [`WsConfig::ip_family`](super::transport::WsConfig::ip_family).
+///
+/// Useful in dual-stack environments where one family is unreliable (or slower) for a given
+/// exchange. Connections tunnelled through a [`Proxy`](super::proxy::Proxy) are resolved by the
+/// proxy, so the preference does not apply to them.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IpFamily {
+    /// Try each resolved address in the order returned by the system resolver.
+    #[default]
+    System,
+    /// Only connect to the IPv4 addresses of the host.
+    V4,
+    /// Only connect to the IPv6 addresses of the host.
+    V6,
+    /// Race the resolved addresses, alternating IPv6 & IPv4, starting the next attempt every
+    /// [`HAPPY_EYEBALLS_DELAY`] (or as soon as an attempt fails) and using the first that
+    /// connects (RFC 8305).
+    HappyEyeballs,
+}
+
+impl IpFamily {
+    /// Filter & order the provided resolved addresses into the addresses [`Self`] connects to,
+    /// in order of preference.
+    pub fn addrs(&self, addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+        match self {
+            Self::System => addrs,
+            Self::V4 => addrs.into_iter().filter(SocketAddr::is_ipv4).collect(),
+            Self::V6 => addrs.into_iter().filter(SocketAddr::is_ipv6).collect(),
+            Self::HappyEyeballs => {
+                let (mut v6, mut v4): (Vec<_>, Vec<_>) =
+                    addrs.into_iter().partition(SocketAddr::is_ipv6);
+                let (mut v6, mut v4) = (v6.drain(..), v4.drain(..));
+
+                let mut interleaved = Vec::with_capacity(v6.len() + v4.len());
+                loop {
+                    match (v6.next(), v4.next()) {
+                        (None, None) => break interleaved,
+                        (v6, v4) => interleaved.extend(v6.into_iter().chain(v4)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Display for IpFamily {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::System => "system",
+                Self::V4 => "ipv4",
+                Self::V6 => "ipv6",
+                Self::HappyEyeballs => "happy_eyeballs",
+            }
+        )
+    }
+}
+
+/// Open a [`TcpStream`] to the provided host & port, resolving & connecting to it's addresses
+/// according to the provided [`IpFamily`] preference.
+pub async fn connect(host: &str, port: u16, ip_family: IpFamily) -> io::Result<TcpStream> {
+    if ip_family == IpFamily::System {
+        return TcpStream::connect((host, port)).await;
+    }
+
+    let addrs = ip_family.addrs(lookup_host((host, port)).await?.collect());
+    if addrs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("{host} did not resolve to any {ip_family} address"),
+        ));
+    }
+
+    debug!(host, port, %ip_family, ?addrs, "connecting to resolved addresses");
+    match ip_family {
+        IpFamily::HappyEyeballs => race(addrs, HAPPY_EYEBALLS_DELAY).await,
+        _ => TcpStream::connect(addrs.as_slice()).await,
+    }
+}
+
+/// Race connection attempts to the provided addresses in order, starting the next attempt every
+/// `delay` or as soon as an attempt fails, returning the first [`TcpStream`] that connects.
+///
+/// If every attempt fails, the last error is returned.
+async fn race(addrs: Vec<SocketAddr>, delay: Duration) -> io::Result<TcpStream> {
+    let mut addrs = addrs.into_iter();
+    let mut attempts = FuturesUnordered::new();
+    let mut last_error = None;
+
+    loop {
+        if attempts.is_empty() {
+            match addrs.next() {
+                Some(addr) => attempts.push(TcpStream::connect(addr)),
+                None => {
+                    break Err(last_error.unwrap_or_else(|| {
+                        io::Error::new(io::ErrorKind::AddrNotAvailable, "no addresses to connect")
+                    }))
+                }
+            }
+        }
+
+        tokio::select! {
+            Some(result) = attempts.next() => match result {
+                Ok(stream) => break Ok(stream),
+                Err(error) => {
+                    last_error = Some(error);
+                    if let Some(addr) = addrs.next() {
+                        attempts.push(TcpStream::connect(addr));
+                    }
+                }
+            },
+            _ = tokio::time::sleep(delay), if addrs.len() > 0 => {
+                if let Some(addr) = addrs.next() {
+                    attempts.push(TcpStream::connect(addr));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_ip_family_addrs() {
+        struct TestCase {
+            ip_family: IpFamily,
+            expected: Vec<&'static str>,
+        }
+
+        let addrs = [
+            "[::1]:443",
+            "[::2]:443",
+            "10.0.0.1:443",
+            "10.0.0.2:443",
+            "10.0.0.3:443",
+        ]
+        .map(|addr| addr.parse::<SocketAddr>().unwrap())
+        .to_vec();
+
+        let tests = vec![
+            TestCase {
+                // TC0: system resolver order is retained
+                ip_family: IpFamily::System,
+                expected: vec![
+                    "[::1]:443",
+                    "[::2]:443",
+                    "10.0.0.1:443",
+                    "10.0.0.2:443",
+                    "10.0.0.3:443",
+                ],
+            },
+            TestCase {
+                // TC1: IPv4 only
+                ip_family: IpFamily::V4,
+                expected: vec!["10.0.0.1:443", "10.0.0.2:443", "10.0.0.3:443"],
+            },
+            TestCase {
+                // TC2: IPv6 only
+                ip_family: IpFamily::V6,
+                expected: vec!["[::1]:443", "[::2]:443"],
+            },
+            TestCase {
+                // TC3: happy eyeballs alternates families, starting with IPv6
+                ip_family: IpFamily::HappyEyeballs,
+                expected: vec![
+                    "[::1]:443",
+                    "10.0.0.1:443",
+                    "[::2]:443",
+                    "10.0.0.2:443",
+                    "10.0.0.3:443",
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = test.ip_family.addrs(addrs.clone());
+            let expected = test
+                .expected
+                .into_iter()
+                .map(|addr| addr.parse::<SocketAddr>().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_ip_family() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        for ip_family in [IpFamily::System, IpFamily::V4, IpFamily::HappyEyeballs] {
+            let stream = connect("127.0.0.1", port, ip_family).await.unwrap();
+            assert!(stream.peer_addr().unwrap().is_ipv4(), "{ip_family} failed");
+        }
+
+        let error = connect("127.0.0.1", port, IpFamily::V6).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrNotAvailable);
+    }
+
+    #[tokio::test]
+    async fn test_race_falls_back_to_next_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open = listener.local_addr().unwrap();
+
+        // Bind & drop a listener so it's port refuses connections
+        let closed = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let stream = race(vec![closed, open], Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), open);
+
+        let error = race(vec![closed], Duration::from_secs(60))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+    }
+}
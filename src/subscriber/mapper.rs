@@ -1,5 +1,7 @@
 use crate::{
+    error::DataError,
     exchange::{subscription::ExchangeSub, Connector},
+    streams::config::StreamConfig,
     subscription::{Map, SubKind, Subscription, SubscriptionMeta},
     Identifier,
 };
@@ -9,8 +11,17 @@ use std::collections::HashMap;
 
 /// Defines how to map a collection of Barter [`Subscription`]s into exchange specific
 /// [`SubscriptionMeta`], containing subscription payloads that are sent to the exchange.
+///
+/// The [`StreamConfig`] of the connection is handed to the
+/// [`Connector::subscribe_requests`] of the [`Subscription`]s, failing if they cannot be
+/// constructed (eg/ an authenticated channel without
+/// [`Credentials`](crate::subscriber::auth::Credentials)).
 pub trait SubscriptionMapper {
-    fn map<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> SubscriptionMeta
+    #[allow(clippy::result_large_err)]
+    fn map<Exchange, Kind>(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        config: &StreamConfig,
+    ) -> Result<SubscriptionMeta, DataError>
     where
         Exchange: Connector,
         Kind: SubKind,
@@ -23,7 +34,10 @@ pub trait SubscriptionMapper {
 pub struct WebSocketSubMapper;
 
 impl SubscriptionMapper for WebSocketSubMapper {
-    fn map<Exchange, Kind>(subscriptions: &[Subscription<Exchange, Kind>]) -> SubscriptionMeta
+    fn map<Exchange, Kind>(
+        subscriptions: &[Subscription<Exchange, Kind>],
+        config: &StreamConfig,
+    ) -> Result<SubscriptionMeta, DataError>
     where
         Exchange: Connector,
         Kind: SubKind,
@@ -52,12 +66,18 @@ impl SubscriptionMapper for WebSocketSubMapper {
             })
             .collect::<Vec<ExchangeSub<Exchange::Channel, Exchange::Market>>>();
 
-        // Construct WebSocket message subscriptions requests
-        let subscriptions = Exchange::requests(exchange_subs);
+        // Construct WebSocket message subscriptions requests using the Connector instance shared
+        // by the Subscriptions
+        let subscriptions = match subscriptions.first() {
+            Some(subscription) => subscription
+                .exchange
+                .subscribe_requests(exchange_subs, config)?,
+            None => Exchange::requests(exchange_subs),
+        };
 
-        SubscriptionMeta {
+        Ok(SubscriptionMeta {
             instrument_map,
             subscriptions,
-        }
+        })
     }
 }
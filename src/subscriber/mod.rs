@@ -1,21 +1,21 @@
 use self::{
+    buffer::ValidationBuffer,
     mapper::{SubscriptionMapper, WebSocketSubMapper},
+    transport::{connect, WebSocket},
     validator::SubscriptionValidator,
 };
 use crate::{
+    error::DataError,
     exchange::Connector,
+    streams::config::StreamConfig,
     subscription::{Map, SubKind, Subscription, SubscriptionMeta},
     Identifier,
 };
 use async_trait::async_trait;
-use barter_integration::{
-    error::SocketError,
-    model::Instrument,
-    protocol::websocket::{connect, WebSocket},
-};
+use barter_integration::{error::SocketError, model::Instrument};
 use futures::SinkExt;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::{debug, debug_span, info, warn, Instrument as _};
 
 /// [`SubscriptionMapper`](mapper::SubscriptionMapper) implementations defining how to map a
 /// collection of Barter [`Subscription`]s into exchange specific [`SubscriptionMeta`].
@@ -25,14 +25,51 @@ pub mod mapper;
 /// validate actioned [`Subscription`]s were successful.
 pub mod validator;
 
-/// Defines how to connect to a socket and subscribe to market data streams.
+/// [`ConnectionTicket`](ticket::ConnectionTicket) client identifier included in exchange
+/// subscribe requests where supported.
+pub mod ticket;
+
+/// [`WsConfig`](transport::WsConfig) WebSocket & TCP transport options applied when a
+/// [`Subscriber`] connects.
+pub mod transport;
+
+/// [`IpFamily`](dial::IpFamily) address family preference used to dial the TCP connection of a
+/// [`WebSocket`].
+pub mod dial;
+
+/// [`Proxy`](proxy::Proxy) HTTP CONNECT & SOCKS5 tunnelling of exchange WebSocket & REST
+/// connections.
+pub mod proxy;
+
+/// [`ValidationBuffer`](buffer::ValidationBuffer) of market data messages received whilst
+/// validating [`Subscription`]s.
+pub mod buffer;
+
+/// [`OutboundObserver`](observer::OutboundObserver) callback observing the raw subscription
+/// payloads sent to an exchange.
+pub mod observer;
+
+/// Optional exchange [`Credentials`](auth::Credentials) used to log in to connections before
+/// subscribing.
+pub mod auth;
+
+/// [`DeflateStream`](deflate::DeflateStream) that inflates permessage-deflate compressed
+/// messages received over a [`WebSocket`].
+pub mod deflate;
+
+/// Defines how to connect to a socket and subscribe to market data streams, using the
+/// [`StreamConfig`] of the connection.
+///
+/// Returns the subscribed [`WebSocket`], the [`Map<Instrument>`] used to identify incoming
+/// messages, and the market data messages buffered whilst validating the subscriptions.
 #[async_trait]
 pub trait Subscriber {
     type SubMapper: SubscriptionMapper;
 
     async fn subscribe<Exchange, Kind>(
         subscriptions: &[Subscription<Exchange, Kind>],
-    ) -> Result<(WebSocket, Map<Instrument>), SocketError>
+        config: &StreamConfig,
+    ) -> Result<(WebSocket, Map<Instrument>, ValidationBuffer), DataError>
     where
         Exchange: Connector + Send + Sync,
         Kind: SubKind + Send + Sync,
@@ -49,7 +86,8 @@ impl Subscriber for WebSocketSubscriber {
 
     async fn subscribe<Exchange, Kind>(
         subscriptions: &[Subscription<Exchange, Kind>],
-    ) -> Result<(WebSocket, Map<Instrument>), SocketError>
+        config: &StreamConfig,
+    ) -> Result<(WebSocket, Map<Instrument>, ValidationBuffer), DataError>
     where
         Exchange: Connector + Send + Sync,
         Kind: SubKind + Send + Sync,
@@ -57,31 +95,423 @@ impl Subscriber for WebSocketSubscriber {
     {
         // Define variables for logging ergonomics
         let exchange = Exchange::ID;
-        let url = Exchange::url()?;
-        debug!(%exchange, %url, ?subscriptions, "subscribing to WebSocket");
 
-        // Connect to exchange
-        let mut websocket = connect(url).await?;
+        // Determine server Url, common to every Subscription since they share a SubKind &
+        // Connector instance, unless it's overridden for this consumer loop
+        let connector = subscriptions
+            .first()
+            .map(|subscription| &subscription.exchange);
+        let url =
+            match (&config.endpoints.websocket, subscriptions.first()) {
+                (Some(url), _) => url.clone(),
+                (None, Some(subscription)) => subscription
+                    .exchange
+                    .subscribe_url(&Identifier::<Exchange::Channel>::id(subscription))?,
+                (None, None) => Exchange::url()?,
+            };
+        debug!(%exchange, %url, ?subscriptions, "connecting to WebSocket");
+
+        // Connect to exchange using the WsConfig of this connection
+        let mut ws_config = config.ws;
+        ws_config
+            .permessage_deflate
+            .get_or_insert(Exchange::permessage_deflate());
+        let mut websocket = connect(url, &ws_config, config.proxy.as_ref()).await?;
         debug!(%exchange, ?subscriptions, "connected to WebSocket");
 
+        // Log in before subscribing if the Connector instance or this consumer loop has
+        // Credentials for the exchange
+        auth::login(connector, &mut websocket, config.credentials.as_ref()).await?;
+
         // Map &[Subscription<Exchange, Kind>] to SubscriptionMeta
         let SubscriptionMeta {
             instrument_map,
             subscriptions,
-        } = Self::SubMapper::map::<Exchange, Kind>(subscriptions);
+        } = Self::SubMapper::map::<Exchange, Kind>(subscriptions, config)?;
 
         // Send Subscriptions over WebSocket
         for subscription in subscriptions {
             debug!(%exchange, payload = ?subscription, "sending exchange subscription");
-            websocket.send(subscription).await?;
+            if let Some(observer) = &config.outbound_observer {
+                observer.notify(exchange, &subscription);
+            }
+            websocket
+                .send(subscription)
+                .await
+                .map_err(SocketError::WebSocket)?;
         }
 
         // Validate Subscription responses
-        let map =
-            Exchange::SubValidator::validate::<Exchange, Kind>(instrument_map, &mut websocket)
-                .await?;
+        let mut buffer = ValidationBuffer::new(ws_config.buffer_until_validated);
+        let map = Exchange::SubValidator::validate::<Exchange, Kind>(
+            instrument_map,
+            &mut websocket,
+            &mut buffer,
+            config,
+        )
+        .instrument(debug_span!("validate"))
+        .await?;
+
+        if buffer.dropped() > 0 {
+            warn!(
+                %exchange,
+                buffered = buffer.len(),
+                dropped = buffer.dropped(),
+                "dropped oldest market data messages received whilst validating subscriptions"
+            );
+        }
+
+        // Validated subscriptions mean any exchange maintenance is over
+        config.status.clear_maintenance(exchange);
+
+        info!(%exchange, buffered = buffer.len(), "subscribed to WebSocket");
+        Ok((websocket, map, buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{MarketEvent, MarketIter},
+        exchange::{subscription::ExchangeSub, ExchangeId, StreamSelector},
+        streams::builder::StreamBuilder,
+        subscriber::validator::WebSocketSubValidator,
+        subscription::trade::{PublicTrade, PublicTrades},
+        transformer::stateless::StatelessTransformer,
+        ExchangeWsStream,
+    };
+    use crate::{num, num::Num};
+    use barter_integration::{
+        model::{Exchange, InstrumentKind, Side, SubscriptionId},
+        protocol::websocket::WsMessage,
+        Validator,
+    };
+    use chrono::{DateTime, Utc};
+    use futures::StreamExt;
+    use std::sync::OnceLock;
+    use tokio::{net::TcpListener, task::JoinHandle};
+    use tokio_tungstenite::tungstenite::Message;
+    use url::Url;
+
+    /// Url of the local mock exchange server used by the zero-config [`StaticExchange`].
+    static STATIC_URL: OnceLock<Url> = OnceLock::new();
+
+    /// Zero-config mock [`Connector`] that only implements the associated functions.
+    #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Deserialize, Serialize)]
+    struct StaticExchange;
+
+    /// Mock [`Connector`] instance configured with a server [`Url`], a subscription depth & an
+    /// API key.
+    #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Deserialize, Serialize)]
+    struct ConfiguredExchange {
+        url: String,
+        depth: u8,
+        api_key: Option<String>,
+    }
+
+    #[derive(Debug)]
+    struct MockChannel;
+
+    impl AsRef<str> for MockChannel {
+        fn as_ref(&self) -> &str {
+            "trades"
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockMarket(String);
+
+    impl AsRef<str> for MockMarket {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct MockSubResponse {
+        result: String,
+    }
+
+    impl Validator for MockSubResponse {
+        fn validate(self) -> Result<Self, SocketError> {
+            match self.result.as_str() {
+                "ok" => Ok(self),
+                _ => Err(SocketError::Subscribe(self.result)),
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct MockTrade {
+        id: SubscriptionId,
+        price: Num,
+    }
+
+    impl Identifier<Option<SubscriptionId>> for MockTrade {
+        fn id(&self) -> Option<SubscriptionId> {
+            Some(self.id.clone())
+        }
+    }
+
+    impl From<(ExchangeId, Instrument, DateTime<Utc>, MockTrade)> for MarketIter<PublicTrade> {
+        fn from(
+            (exchange_id, instrument, received_time, trade): (
+                ExchangeId,
+                Instrument,
+                DateTime<Utc>,
+                MockTrade,
+            ),
+        ) -> Self {
+            Self(smallvec::smallvec![Ok(MarketEvent {
+                exchange_time: Utc::now(),
+                raw_exchange_time: None,
+                received_time,
+                exchange: Exchange::from(exchange_id),
+                instrument,
+                kind: PublicTrade {
+                    id: trade.id.to_string(),
+                    price: trade.price,
+                    amount: num!(1.0),
+                    side: Side::Buy,
+                },
+                meta: (),
+            })])
+        }
+    }
+
+    macro_rules! impl_mock_connector {
+        ($exchange:ty) => {
+            impl Identifier<MockChannel> for Subscription<$exchange, PublicTrades> {
+                fn id(&self) -> MockChannel {
+                    MockChannel
+                }
+            }
+
+            impl Identifier<MockMarket> for Subscription<$exchange, PublicTrades> {
+                fn id(&self) -> MockMarket {
+                    MockMarket(format!(
+                        "{}_{}",
+                        self.instrument.base, self.instrument.quote
+                    ))
+                }
+            }
+
+            impl StreamSelector<PublicTrades> for $exchange {
+                type Stream = ExchangeWsStream<StatelessTransformer<Self, PublicTrades, MockTrade>>;
+            }
+        };
+    }
+
+    impl_mock_connector!(StaticExchange);
+    impl_mock_connector!(ConfiguredExchange);
+
+    impl Connector for StaticExchange {
+        const ID: ExchangeId = ExchangeId::Okx;
+        type Channel = MockChannel;
+        type Market = MockMarket;
+        type Subscriber = WebSocketSubscriber;
+        type SubValidator = WebSocketSubValidator;
+        type SubResponse = MockSubResponse;
+
+        fn url() -> Result<Url, SocketError> {
+            Ok(STATIC_URL.get().unwrap().clone())
+        }
+
+        fn requests(
+            exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+        ) -> Vec<WsMessage> {
+            exchange_subs
+                .into_iter()
+                .map(|sub| WsMessage::Text(sub.id().to_string()))
+                .collect()
+        }
+    }
+
+    impl Connector for ConfiguredExchange {
+        const ID: ExchangeId = ExchangeId::Okx;
+        type Channel = MockChannel;
+        type Market = MockMarket;
+        type Subscriber = WebSocketSubscriber;
+        type SubValidator = WebSocketSubValidator;
+        type SubResponse = MockSubResponse;
+
+        fn url() -> Result<Url, SocketError> {
+            Err(SocketError::Subscribe(
+                "ConfiguredExchange has no static Url".to_owned(),
+            ))
+        }
+
+        fn subscribe_url(&self, _: &Self::Channel) -> Result<Url, SocketError> {
+            Url::parse(&self.url).map_err(SocketError::UrlParse)
+        }
+
+        fn requests(_: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
+            unreachable!("ConfiguredExchange always uses it's instance subscribe_requests")
+        }
+
+        fn subscribe_requests(
+            &self,
+            exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>,
+            _: &StreamConfig,
+        ) -> Result<Vec<WsMessage>, DataError> {
+            Ok(exchange_subs
+                .into_iter()
+                .map(|sub| WsMessage::Text(format!("{}@{}", sub.id(), self.depth)))
+                .collect())
+        }
+
+        fn login(credentials: &auth::Credentials) -> Result<Option<WsMessage>, DataError> {
+            Ok(Some(WsMessage::Text(format!(
+                "login|{}",
+                credentials.api_key
+            ))))
+        }
+
+        fn login_response(payload: &str) -> Option<Result<(), DataError>> {
+            (payload == r#"{"login":"ok"}"#).then_some(Ok(()))
+        }
+
+        fn credentials(&self) -> Option<auth::Credentials> {
+            self.api_key
+                .as_ref()
+                .map(|api_key| auth::Credentials::new(api_key, "secret"))
+        }
+    }
+
+    /// Spawn a mock exchange server that answers a login request, validates the subscription &
+    /// sends a single trade, returning it's [`Url`] and the requests it received.
+    async fn spawn_server() -> (Url, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+
+            let mut requests = Vec::new();
+            loop {
+                let request = websocket.next().await.unwrap().unwrap();
+                let request = request.to_text().unwrap().to_owned();
+                let login = request.starts_with("login|");
+                requests.push(request);
+                if login {
+                    websocket
+                        .send(Message::Text(r#"{"login":"ok"}"#.to_owned()))
+                        .await
+                        .unwrap();
+                } else {
+                    break;
+                }
+            }
+
+            for message in [
+                r#"{"result":"ok"}"#,
+                r#"{"id":"trades|btc_usdt","price":20000.0}"#,
+            ] {
+                websocket
+                    .send(Message::Text(message.to_owned()))
+                    .await
+                    .unwrap();
+            }
+
+            requests
+        });
+
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_zero_config_connector() {
+        let (url, server) = spawn_server().await;
+        STATIC_URL.set(url).unwrap();
+
+        let mut streams = StreamBuilder::<PublicTrades>::new()
+            .subscribe([(
+                StaticExchange,
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )])
+            .init()
+            .await
+            .unwrap();
+
+        let trade = streams
+            .select(ExchangeId::Okx)
+            .unwrap()
+            .recv()
+            .await
+            .unwrap();
+        assert_eq!(trade.kind.price, num!(20_000.0));
+        assert_eq!(server.await.unwrap(), vec!["trades|btc_usdt"]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_configured_connector_instance() {
+        let (url, server) = spawn_server().await;
+        let exchange = ConfiguredExchange {
+            url: url.to_string(),
+            depth: 50,
+            api_key: Some("key".to_owned()),
+        };
+
+        let mut streams = StreamBuilder::<PublicTrades>::new()
+            .subscribe_with(
+                exchange,
+                [(("btc", "usdt", InstrumentKind::Spot), PublicTrades)],
+            )
+            .init()
+            .await
+            .unwrap();
+
+        let trade = streams
+            .select(ExchangeId::Okx)
+            .unwrap()
+            .recv()
+            .await
+            .unwrap();
+        assert_eq!(trade.kind.price, num!(20_000.0));
+
+        // Connected to the instance Url, logged in with the instance Credentials & subscribed
+        // with the instance depth
+        assert_eq!(
+            server.await.unwrap(),
+            vec!["login|key", "trades|btc_usdt@50"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_rejects_mixed_connector_instances() {
+        let instance = |depth| ConfiguredExchange {
+            url: "ws://127.0.0.1:1".to_owned(),
+            depth,
+            api_key: None,
+        };
+
+        let result = StreamBuilder::<PublicTrades>::new()
+            .subscribe([
+                (
+                    instance(5),
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                ),
+                (
+                    instance(10),
+                    "eth",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                ),
+            ])
+            .init()
+            .await;
 
-        info!(%exchange, "subscribed to WebSocket");
-        Ok((websocket, map))
+        assert!(matches!(
+            result,
+            Err(DataError::Socket(SocketError::Subscribe(_)))
+        ));
     }
 }
@@ -0,0 +1,75 @@
+use crate::exchange::ExchangeId;
+use barter_integration::protocol::websocket::WsMessage;
+use std::sync::Arc;
+
+/// Callback fired with each raw outbound subscription [`WsMessage`], see [`OutboundObserver`].
+pub type OnOutboundSubscription = Arc<dyn Fn(ExchangeId, &WsMessage) + Send + Sync>;
+
+/// Callback observing each raw subscription [`WsMessage`] a
+/// [`Subscriber`](super::Subscriber) sends to an exchange, eg/ to diagnose why an exchange
+/// rejected a subscription without resorting to packet captures.
+///
+/// Runs inline on the send path immediately before each payload is sent, for every
+/// (re-)connection, so it should be cheap (eg/ logging or pushing to a channel).
+///
+/// Set via
+/// [`StreamBuilder::on_outbound_subscription`](crate::streams::builder::StreamBuilder::on_outbound_subscription),
+/// which hands it to each consumer loop via it's
+/// [`StreamConfig`](crate::streams::config::StreamConfig).
+/// Every payload is also logged at `DEBUG` level by the [`Subscriber`](super::Subscriber).
+#[derive(Clone)]
+pub struct OutboundObserver(OnOutboundSubscription);
+
+impl std::fmt::Debug for OutboundObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OutboundObserver").finish()
+    }
+}
+
+impl OutboundObserver {
+    /// Construct a new [`Self`] from the provided callback.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(ExchangeId, &WsMessage) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    /// Notify the [`OutboundObserver`] of the provided outbound subscription [`WsMessage`].
+    pub fn notify(&self, exchange: ExchangeId, message: &WsMessage) {
+        (self.0)(exchange, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_outbound_observer_notify() {
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observer = OutboundObserver::new({
+            let observed = Arc::clone(&observed);
+            move |exchange, message: &WsMessage| {
+                observed
+                    .lock()
+                    .unwrap()
+                    .push((exchange, message.to_text().unwrap().to_string()))
+            }
+        });
+
+        observer.notify(ExchangeId::Okx, &WsMessage::Text("0".to_string()));
+        observer
+            .clone()
+            .notify(ExchangeId::Bitfinex, &WsMessage::Text("1".to_string()));
+
+        assert_eq!(
+            *observed.lock().unwrap(),
+            vec![
+                (ExchangeId::Okx, "0".to_string()),
+                (ExchangeId::Bitfinex, "1".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Client identifier (eg/ a ticket or client-id) that [`Connector`](crate::exchange::Connector)s
+/// include in their subscribe requests where the exchange supports one, correlating connections
+/// in the exchange logs for debugging.
+///
+/// Set via [`StreamBuilder::connection_ticket`](crate::streams::builder::StreamBuilder::connection_ticket),
+/// defaulting to a randomly generated UUID, and handed to the
+/// [`Connector::subscribe_requests`](crate::exchange::Connector::subscribe_requests) of each
+/// connection via it's [`StreamConfig`](crate::streams::config::StreamConfig).
+/// [`Connector`](crate::exchange::Connector)s that have no use for it (or cannot represent it)
+/// ignore it.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct ConnectionTicket(pub String);
+
+impl ConnectionTicket {
+    /// Construct a new [`Self`] from the provided user supplied ticket.
+    pub fn new<S>(ticket: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self(ticket.into())
+    }
+
+    /// Generate a new [`Self`] from a random (version 4) UUID, formatted as 32 lowercase hex
+    /// digits without hyphens so it's accepted by exchanges that only allow alphanumeric ids.
+    pub fn generate() -> Self {
+        let uuid = (rand::random::<u128>() & !(0xF000 << 64) & !(0xC << 60))
+            | (0x4000 << 64)
+            | (0x8 << 60);
+        Self(format!("{uuid:032x}"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Determine if this [`ConnectionTicket`] is alphanumeric & at most `max_len` characters,
+    /// which is a common exchange restriction on client supplied ids.
+    pub fn is_alphanumeric(&self, max_len: usize) -> bool {
+        !self.0.is_empty()
+            && self.0.len() <= max_len
+            && self.0.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
+impl Default for ConnectionTicket {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+impl Display for ConnectionTicket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for ConnectionTicket {
+    fn from(ticket: &str) -> Self {
+        Self::new(ticket)
+    }
+}
+
+impl From<String> for ConnectionTicket {
+    fn from(ticket: String) -> Self {
+        Self::new(ticket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_ticket_generate() {
+        let ticket = ConnectionTicket::generate();
+
+        assert_eq!(ticket.as_str().len(), 32);
+        assert!(ticket.is_alphanumeric(32));
+        assert_eq!(&ticket.as_str()[12..13], "4", "version 4 UUID");
+        assert!(
+            matches!(&ticket.as_str()[16..17], "8" | "9" | "a" | "b"),
+            "RFC 4122 variant"
+        );
+        assert_ne!(ticket, ConnectionTicket::generate());
+    }
+}
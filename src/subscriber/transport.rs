@@ -0,0 +1,618 @@
+use super::{
+    deflate::{DeflateStream, HandshakeStream, PermessageDeflate, PERMESSAGE_DEFLATE_OFFER},
+    dial::IpFamily,
+    proxy::{connect_tcp, Proxy},
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::websocket::{WsError, WsMessage},
+};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    Sink, Stream,
+};
+use rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use serde::{Deserialize, Serialize};
+use socket2::{SockRef, TcpKeepalive};
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_tungstenite::{
+    tungstenite::{
+        client::IntoClientRequest,
+        error::{TlsError, UrlError},
+        http::{header::SEC_WEBSOCKET_EXTENSIONS, HeaderValue},
+        protocol::{Role, WebSocketConfig},
+    },
+    MaybeTlsStream, WebSocketStream,
+};
+use tracing::debug;
+
+/// Exchange WebSocket connection established via [`connect`].
+///
+/// Connected via the default tungstenite path, unless permessage-deflate was offered & accepted
+/// by the server, in which case compressed messages are transparently inflated by a
+/// [`DeflateStream`].
+#[derive(Debug)]
+pub enum WebSocket {
+    Plain(WebSocketStream<MaybeTlsStream<TcpStream>>),
+    Deflate(WebSocketStream<DeflateStream<MaybeTlsStream<TcpStream>>>),
+}
+
+impl WebSocket {
+    /// Negotiated [`PermessageDeflate`] parameters, or `None` if the WebSocket messages are
+    /// uncompressed.
+    pub fn negotiated(&self) -> Option<PermessageDeflate> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Deflate(websocket) => Some(websocket.get_ref().negotiated()),
+        }
+    }
+
+    /// Reference to the underlying transport.
+    pub fn get_ref(&self) -> &MaybeTlsStream<TcpStream> {
+        match self {
+            Self::Plain(websocket) => websocket.get_ref(),
+            Self::Deflate(websocket) => websocket.get_ref().get_ref(),
+        }
+    }
+}
+
+impl Stream for WebSocket {
+    type Item = Result<WsMessage, WsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            Self::Plain(websocket) => Pin::new(websocket).poll_next(cx),
+            Self::Deflate(websocket) => Pin::new(websocket).poll_next(cx),
+        }
+    }
+}
+
+impl Sink<WsMessage> for WebSocket {
+    type Error = WsError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Self::Plain(websocket) => Pin::new(websocket).poll_ready(cx),
+            Self::Deflate(websocket) => Pin::new(websocket).poll_ready(cx),
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: WsMessage) -> Result<(), Self::Error> {
+        match self.get_mut() {
+            Self::Plain(websocket) => Pin::new(websocket).start_send(item),
+            Self::Deflate(websocket) => Pin::new(websocket).start_send(item),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Self::Plain(websocket) => Pin::new(websocket).poll_flush(cx),
+            Self::Deflate(websocket) => Pin::new(websocket).poll_flush(cx),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Self::Plain(websocket) => Pin::new(websocket).poll_close(cx),
+            Self::Deflate(websocket) => Pin::new(websocket).poll_close(cx),
+        }
+    }
+}
+
+/// [`WebSocket`] sink half used to send [`WsMessage`]s to the exchange.
+pub type WsSink = SplitSink<WebSocket, WsMessage>;
+
+/// [`WebSocket`] stream half used to receive [`WsMessage`]s from the exchange.
+pub type WsStream = SplitStream<WebSocket>;
+
+/// WebSocket transport options used when a [`Subscriber`](super::Subscriber) connects to an
+/// exchange.
+///
+/// Maps to the underlying tungstenite [`WebSocketConfig`], plus TCP options applied to the
+/// socket before the WebSocket handshake. The [`Default`] matches the tungstenite defaults, so
+/// only exchanges that need it (eg/ large OrderBook snapshots exceeding the default max message
+/// size) have to be configured.
+///
+/// Set per exchange via
+/// [`StreamBuilder::ws_config`](crate::streams::builder::StreamBuilder::ws_config), and handed to
+/// the [`Subscriber`](super::Subscriber) of each connection via it's
+/// [`StreamConfig`](crate::streams::config::StreamConfig).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct WsConfig {
+    /// Maximum size of a WebSocket message in bytes, `None` means unlimited. Defaults to 64 MiB.
+    pub max_message_size: Option<usize>,
+    /// Maximum size of a single WebSocket frame payload in bytes, `None` means unlimited.
+    /// Defaults to 16 MiB.
+    pub max_frame_size: Option<usize>,
+    /// Maximum number of outgoing messages buffered before sending applies backpressure,
+    /// `None` means unlimited. Defaults to unlimited.
+    pub max_send_queue: Option<usize>,
+    /// If true, Nagle's algorithm is disabled (ie/ `TCP_NODELAY`), so small messages such as
+    /// subscription requests are sent without delay. Defaults to false.
+    pub nodelay: bool,
+    /// Idle [`Duration`] before TCP keepalive probes are sent, `None` disables TCP keepalive.
+    /// Defaults to `None`.
+    pub keepalive: Option<Duration>,
+    /// If true, permessage-deflate compression is offered in the WebSocket handshake and
+    /// compressed messages are inflated transparently. Defaults to `None`, meaning the
+    /// [`Connector::permessage_deflate`](crate::exchange::Connector::permessage_deflate) default
+    /// is used.
+    pub permessage_deflate: Option<bool>,
+    /// Maximum number of market data messages received whilst subscriptions are still being
+    /// validated that are buffered & replayed in order once every subscription is validated,
+    /// so no events are distributed before the connection is fully subscribed. Defaults to
+    /// `None`, meaning such messages are discarded, see
+    /// [`ValidationBuffer`](super::buffer::ValidationBuffer).
+    pub buffer_until_validated: Option<usize>,
+    /// [`IpFamily`] preference used to resolve & dial the exchange host. Defaults to
+    /// [`IpFamily::System`], ie/ each address in the system resolver order.
+    #[serde(default)]
+    pub ip_family: IpFamily,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        let websocket = WebSocketConfig::default();
+        Self {
+            max_message_size: websocket.max_message_size,
+            max_frame_size: websocket.max_frame_size,
+            max_send_queue: websocket.max_send_queue,
+            nodelay: false,
+            keepalive: None,
+            permessage_deflate: None,
+            buffer_until_validated: None,
+            ip_family: IpFamily::System,
+        }
+    }
+}
+
+impl WsConfig {
+    /// Construct a new [`Self`] with the default transport options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of a WebSocket message in bytes, `None` means unlimited.
+    pub fn with_max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Set the maximum size of a single WebSocket frame payload in bytes, `None` means
+    /// unlimited.
+    pub fn with_max_frame_size(mut self, max_frame_size: Option<usize>) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Set the maximum number of buffered outgoing messages, `None` means unlimited.
+    pub fn with_max_send_queue(mut self, max_send_queue: Option<usize>) -> Self {
+        self.max_send_queue = max_send_queue;
+        self
+    }
+
+    /// Set whether Nagle's algorithm is disabled (ie/ `TCP_NODELAY`).
+    pub fn with_nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = nodelay;
+        self
+    }
+
+    /// Set the idle [`Duration`] before TCP keepalive probes are sent, `None` disables TCP
+    /// keepalive.
+    pub fn with_keepalive(mut self, keepalive: Option<Duration>) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
+    /// Set whether permessage-deflate compression is offered, overriding the
+    /// [`Connector::permessage_deflate`](crate::exchange::Connector::permessage_deflate) default.
+    pub fn with_permessage_deflate(mut self, permessage_deflate: bool) -> Self {
+        self.permessage_deflate = Some(permessage_deflate);
+        self
+    }
+
+    /// Set the maximum number of market data messages buffered whilst validating
+    /// subscriptions, `None` discards them.
+    pub fn with_buffer_until_validated(mut self, buffer_until_validated: Option<usize>) -> Self {
+        self.buffer_until_validated = buffer_until_validated;
+        self
+    }
+
+    /// Set the [`IpFamily`] preference used to resolve & dial the exchange host.
+    pub fn with_ip_family(mut self, ip_family: IpFamily) -> Self {
+        self.ip_family = ip_family;
+        self
+    }
+
+    /// Tungstenite [`WebSocketConfig`] of [`Self`].
+    pub fn websocket_config(&self) -> WebSocketConfig {
+        WebSocketConfig {
+            max_send_queue: self.max_send_queue,
+            max_message_size: self.max_message_size,
+            max_frame_size: self.max_frame_size,
+            ..WebSocketConfig::default()
+        }
+    }
+}
+
+/// Connect asynchronously to a [`WebSocket`] server using the provided [`WsConfig`].
+///
+/// Equivalent to [`barter_integration::protocol::websocket::connect`] apart from the
+/// [`WsConfig`] transport options being applied, and the connection being tunnelled through the
+/// provided [`Proxy`] if there is one. If [`WsConfig::permessage_deflate`] is enabled,
+/// permessage-deflate is offered in the handshake and, only if the server accepts it,
+/// compressed messages are inflated by a [`DeflateStream`] before they reach the [`WebSocket`].
+pub async fn connect<R>(
+    request: R,
+    config: &WsConfig,
+    proxy: Option<&Proxy>,
+) -> Result<WebSocket, SocketError>
+where
+    R: IntoClientRequest + Unpin + Debug,
+{
+    debug!(
+        ?request,
+        ?config,
+        "attempting to establish WebSocket connection"
+    );
+    let mut request = request.into_client_request()?;
+
+    let host = request
+        .uri()
+        .host()
+        .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+        .ok_or(WsError::Url(UrlError::NoHostName))?
+        .to_owned();
+    let tls = match request.uri().scheme_str() {
+        Some("wss") => true,
+        Some("ws") => false,
+        _ => return Err(WsError::Url(UrlError::UnsupportedUrlScheme).into()),
+    };
+    let port = request
+        .uri()
+        .port_u16()
+        .unwrap_or(if tls { 443 } else { 80 });
+
+    // TCP connection is tunnelled through the Proxy, if any
+    let stream = connect_tcp(&host, port, config.ip_family, proxy)
+        .await
+        .map_err(WsError::Io)?;
+    stream.set_nodelay(config.nodelay).map_err(WsError::Io)?;
+    if let Some(keepalive) = config.keepalive {
+        SockRef::from(&stream)
+            .set_tcp_keepalive(&TcpKeepalive::new().with_time(keepalive))
+            .map_err(WsError::Io)?;
+    }
+
+    // Without permessage-deflate, connect via the default tungstenite path
+    if !config.permessage_deflate.unwrap_or_default() {
+        let (websocket, _) = tokio_tungstenite::client_async_tls_with_config(
+            request,
+            stream,
+            Some(config.websocket_config()),
+            None,
+        )
+        .await
+        .map_err(SocketError::WebSocket)?;
+
+        debug!("established WebSocket connection");
+        return Ok(WebSocket::Plain(websocket));
+    }
+    request.headers_mut().insert(
+        SEC_WEBSOCKET_EXTENSIONS,
+        HeaderValue::from_static(PERMESSAGE_DEFLATE_OFFER),
+    );
+
+    // TLS is established beneath any DeflateStream, so it operates on plaintext frames
+    let mut stream = if tls {
+        let domain = ServerName::try_from(host.as_str())
+            .map_err(|_| WsError::Tls(TlsError::InvalidDnsName))?;
+        TlsConnector::from(tls_config())
+            .connect(domain, stream)
+            .await
+            .map(MaybeTlsStream::Rustls)
+            .map_err(WsError::Io)?
+    } else {
+        MaybeTlsStream::Plain(stream)
+    };
+
+    // Perform the handshake without consuming any WebSocket frames, so the stream the WebSocket
+    // reads from can be decided once it's known if permessage-deflate was negotiated
+    let (mut handshake, response) = tokio_tungstenite::client_async_with_config(
+        request,
+        HandshakeStream::new(&mut stream),
+        Some(config.websocket_config()),
+    )
+    .await
+    .map_err(SocketError::WebSocket)?;
+    let remaining = handshake.get_mut().take_remaining();
+    drop(handshake);
+
+    let negotiated = response
+        .headers()
+        .get_all(SEC_WEBSOCKET_EXTENSIONS)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .find_map(PermessageDeflate::parse);
+
+    let websocket = match negotiated {
+        Some(negotiated) => WebSocket::Deflate(
+            WebSocketStream::from_raw_socket(
+                DeflateStream::new(
+                    stream,
+                    negotiated,
+                    remaining,
+                    config.max_frame_size,
+                    config.max_message_size,
+                ),
+                Role::Client,
+                Some(config.websocket_config()),
+            )
+            .await,
+        ),
+        None => WebSocket::Plain(
+            WebSocketStream::from_partially_read(
+                stream,
+                remaining,
+                Role::Client,
+                Some(config.websocket_config()),
+            )
+            .await,
+        ),
+    };
+
+    debug!(
+        negotiated = ?websocket.negotiated(),
+        "established WebSocket connection"
+    );
+    Ok(websocket)
+}
+
+/// Rustls [`ClientConfig`] trusting the webpki root certificates, equivalent to the one
+/// tokio-tungstenite uses by default.
+pub(crate) fn tls_config() -> Arc<ClientConfig> {
+    static TLS_CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    TLS_CONFIG
+        .get_or_init(|| {
+            let mut root_store = RootCertStore::empty();
+            root_store.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(
+                |anchor| {
+                    OwnedTrustAnchor::from_subject_spki_name_constraints(
+                        anchor.subject,
+                        anchor.spki,
+                        anchor.name_constraints,
+                    )
+                },
+            ));
+
+            Arc::new(
+                ClientConfig::builder()
+                    .with_safe_defaults()
+                    .with_root_certificates(root_store)
+                    .with_no_client_auth(),
+            )
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscriber::deflate::inflate::tests::Deflater;
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::{
+        error::CapacityError,
+        handshake::server::{Request, Response},
+        protocol::frame::{
+            coding::{Data, OpCode},
+            Frame,
+        },
+        Message,
+    };
+
+    /// Spawn a mock exchange server that sends the provided snapshot to it's first client,
+    /// returning the server Url.
+    async fn mock_server(snapshot: String) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let _ = websocket.send(Message::Text(snapshot)).await;
+        });
+
+        url
+    }
+
+    /// Spawn a mock exchange server that accepts permessage-deflate with the provided response
+    /// header value (if offered), then sends the provided frames to it's first client, returning
+    /// the server Url.
+    async fn mock_deflate_server(accept: &'static str, frames: Vec<Frame>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let callback = |request: &Request, mut response: Response| {
+                if request.headers().contains_key(SEC_WEBSOCKET_EXTENSIONS) {
+                    response
+                        .headers_mut()
+                        .insert(SEC_WEBSOCKET_EXTENSIONS, HeaderValue::from_static(accept));
+                }
+                Ok(response)
+            };
+            let mut websocket = tokio_tungstenite::accept_hdr_async(tcp, callback)
+                .await
+                .unwrap();
+            for frame in frames {
+                let _ = websocket.feed(Message::Frame(frame)).await;
+            }
+            let _ = websocket.flush().await;
+        });
+
+        url
+    }
+
+    fn compressed_frame(payload: Vec<u8>, opcode: OpCode, is_final: bool, rsv1: bool) -> Frame {
+        let mut frame = Frame::message(payload, opcode, is_final);
+        frame.header_mut().rsv1 = rsv1;
+        frame
+    }
+
+    fn snapshot(len: usize) -> String {
+        format!(r#"{{"bids":"{}"}}"#, "1".repeat(len))
+    }
+
+    #[test]
+    fn test_ws_config_default_matches_tungstenite() {
+        let actual = WsConfig::default().websocket_config();
+        let expected = WebSocketConfig::default();
+
+        assert_eq!(actual.max_message_size, expected.max_message_size);
+        assert_eq!(actual.max_frame_size, expected.max_frame_size);
+        assert_eq!(actual.max_send_queue, expected.max_send_queue);
+        assert_eq!(
+            actual.accept_unmasked_frames,
+            expected.accept_unmasked_frames
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_max_message_size_exceeded() {
+        let url = mock_server(snapshot(4096)).await;
+        let config = WsConfig::new().with_max_message_size(Some(1024));
+
+        let mut websocket = connect(url, &config, None).await.unwrap();
+        let actual = websocket.next().await.unwrap();
+
+        assert!(
+            matches!(
+                actual,
+                Err(WsError::Capacity(CapacityError::MessageTooLong {
+                    max_size: 1024,
+                    ..
+                }))
+            ),
+            "unexpected: {actual:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_custom_limits_allow_large_snapshot() {
+        let snapshot = snapshot(4096);
+        let url = mock_server(snapshot.clone()).await;
+        let config = WsConfig::new()
+            .with_max_message_size(Some(8192))
+            .with_max_frame_size(Some(8192))
+            .with_nodelay(true)
+            .with_keepalive(Some(Duration::from_secs(30)));
+
+        let mut websocket = connect(url, &config, None).await.unwrap();
+        match websocket.get_ref() {
+            MaybeTlsStream::Plain(stream) => assert!(stream.nodelay().unwrap()),
+            _ => panic!("expected plain TcpStream"),
+        }
+
+        let actual = websocket.next().await.unwrap().unwrap();
+        assert_eq!(actual, Message::Text(snapshot));
+    }
+
+    #[tokio::test]
+    async fn test_connect_permessage_deflate() {
+        let messages = [snapshot(64), snapshot(2048), snapshot(16)];
+        let mut deflater = Deflater::new(true);
+        let compressed = messages
+            .iter()
+            .map(|message| deflater.deflate(message.as_bytes()))
+            .collect::<Vec<_>>();
+
+        let text = OpCode::Data(Data::Text);
+        let continuation = OpCode::Data(Data::Continue);
+        let (first, second) = compressed[1].split_at(compressed[1].len() / 2);
+        let frames = vec![
+            // Single frame compressed message
+            compressed_frame(compressed[0].clone(), text, true, true),
+            // Fragmented compressed message, with a Ping interleaved
+            compressed_frame(first.to_vec(), text, false, true),
+            Frame::ping(b"ping".to_vec()),
+            compressed_frame(second.to_vec(), continuation, true, false),
+            // Uncompressed message
+            compressed_frame(b"uncompressed".to_vec(), text, true, false),
+            // Compressed message referencing the previous compressed messages
+            compressed_frame(compressed[2].clone(), text, true, true),
+        ];
+        let url = mock_deflate_server("permessage-deflate", frames).await;
+
+        let config = WsConfig::new().with_permessage_deflate(true);
+        let mut websocket = connect(url, &config, None).await.unwrap();
+        assert_eq!(websocket.negotiated(), Some(PermessageDeflate::default()));
+
+        let expected = vec![
+            Message::Text(messages[0].clone()),
+            Message::Ping(b"ping".to_vec()),
+            Message::Text(messages[1].clone()),
+            Message::Text("uncompressed".to_string()),
+            Message::Text(messages[2].clone()),
+        ];
+        for (index, expected) in expected.into_iter().enumerate() {
+            let actual = websocket.next().await.unwrap().unwrap();
+            assert_eq!(actual, expected, "TC{} failed", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_permessage_deflate_not_negotiated() {
+        struct TestCase {
+            offered: bool,
+            accept: &'static str,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: permessage-deflate not offered
+                offered: false,
+                accept: "permessage-deflate",
+            },
+            TestCase {
+                // TC1: permessage-deflate offered, but server only accepts another extension
+                offered: true,
+                accept: "x-webkit-deflate-frame",
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let frames = vec![compressed_frame(
+                b"uncompressed".to_vec(),
+                OpCode::Data(Data::Text),
+                true,
+                false,
+            )];
+            let url = mock_deflate_server(test.accept, frames).await;
+
+            let config = WsConfig::new().with_permessage_deflate(test.offered);
+            let mut websocket = connect(url, &config, None).await.unwrap();
+            assert!(
+                matches!(websocket, WebSocket::Plain(_)),
+                "TC{} failed",
+                index
+            );
+
+            let actual = websocket.next().await.unwrap().unwrap();
+            assert_eq!(
+                actual,
+                Message::Text("uncompressed".to_string()),
+                "TC{} failed",
+                index
+            );
+        }
+    }
+}
@@ -1,15 +1,15 @@
 use crate::{
     exchange::Connector,
+    parser::WsParser,
+    streams::config::StreamConfig,
+    subscriber::{buffer::ValidationBuffer, transport::WebSocket},
     subscription::{Map, SubKind},
 };
 use async_trait::async_trait;
 use barter_integration::{
     error::SocketError,
     model::Instrument,
-    protocol::{
-        websocket::{WebSocket, WebSocketParser},
-        StreamParser,
-    },
+    protocol::{websocket::WsMessage, StreamParser},
     Validator,
 };
 use futures::StreamExt;
@@ -18,6 +18,11 @@ use tracing::debug;
 
 /// Defines how to validate that actioned market data
 /// [`Subscription`](crate::subscription::Subscription)s were accepted by the exchange.
+///
+/// Market data messages received before every subscription is validated are pushed to the
+/// provided [`ValidationBuffer`], which discards them unless it's enabled. Exchange status
+/// messages are recorded via the [`StatusHandling`](crate::exchange::status::StatusHandling) of
+/// the provided [`StreamConfig`].
 #[async_trait]
 pub trait SubscriptionValidator {
     type Parser: StreamParser;
@@ -25,6 +30,8 @@ pub trait SubscriptionValidator {
     async fn validate<Exchange, Kind>(
         instrument_map: Map<Instrument>,
         websocket: &mut WebSocket,
+        buffer: &mut ValidationBuffer,
+        config: &StreamConfig,
     ) -> Result<Map<Instrument>, SocketError>
     where
         Exchange: Connector + Send,
@@ -37,11 +44,13 @@ pub struct WebSocketSubValidator;
 
 #[async_trait]
 impl SubscriptionValidator for WebSocketSubValidator {
-    type Parser = WebSocketParser;
+    type Parser = WsParser;
 
     async fn validate<Exchange, Kind>(
         instrument_map: Map<Instrument>,
         websocket: &mut WebSocket,
+        buffer: &mut ValidationBuffer,
+        config: &StreamConfig,
     ) -> Result<Map<Instrument>, SocketError>
     where
         Exchange: Connector + Send,
@@ -53,6 +62,12 @@ impl SubscriptionValidator for WebSocketSubValidator {
 
         // Parameter to keep track of successful Subscription outcomes
         let mut success_responses = 0usize;
+        debug!(
+            exchange = %Exchange::ID,
+            %expected_responses,
+            ?timeout,
+            "validating exchange WebSocket subscriptions"
+        );
 
         loop {
             // Break if all Subscriptions were a success
@@ -75,6 +90,27 @@ impl SubscriptionValidator for WebSocketSubValidator {
                         None => break Err(SocketError::Subscribe("WebSocket stream terminated unexpectedly".to_string()))
                     };
 
+                    // Record exchange status messages, failing fast if the exchange is under maintenance
+                    if let Ok(WsMessage::Text(text)) = &response {
+                        if let Some(status) = Exchange::exchange_status(text) {
+                            let system = status.status;
+                            config.status.record(Exchange::ID, status);
+                            if system.is_maintenance() {
+                                break Err(SocketError::Subscribe(format!(
+                                    "received exchange status {system}: subscription attempts \
+                                     are paused until it's back online"
+                                )))
+                            }
+                            continue
+                        }
+                    }
+
+                    // Retain a copy of the raw message in case it's market data to be buffered
+                    let raw = match (buffer.is_enabled(), &response) {
+                        (true, Ok(message)) => Some(message.clone()),
+                        _ => None,
+                    };
+
                     match Self::Parser::parse::<Exchange::SubResponse>(response) {
                         Some(Ok(response)) => match response.validate() {
                             // Subscription success
@@ -102,6 +138,9 @@ impl SubscriptionValidator for WebSocketSubValidator {
                                 %payload,
                                 "failed to deserialise non SubResponse payload"
                             );
+                            if let Some(raw) = raw {
+                                buffer.push(raw);
+                            }
                             continue
                         }
                         Some(Err(SocketError::Terminated(close_frame))) => {
@@ -0,0 +1,23 @@
+use super::SubKind;
+use crate::{num::Num, subscription::candle::Interval};
+use barter_macro::{DeSubKind, SerSubKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`AveragePrice`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct AveragePrices;
+
+impl SubKind for AveragePrices {
+    type Event = AveragePrice;
+}
+
+/// Normalised Barter [`AveragePrice`] model, ie/ the volume weighted average price of an
+/// instrument over the exchange defined trailing [`Interval`].
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct AveragePrice {
+    pub interval: Interval,
+    pub price: Num,
+    pub last_trade_time: DateTime<Utc>,
+}
@@ -0,0 +1,59 @@
+use super::SubKind;
+use crate::num::Num;
+use barter_integration::model::Symbol;
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`Balance`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events for the base & quote assets of the
+/// [`Subscription`](super::Subscription) instrument, held by the logged in account.
+///
+/// Private, so it requires the exchange
+/// [`Credentials`](crate::subscriber::auth::Credentials).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct Balances;
+
+impl SubKind for Balances {
+    type Event = Balance;
+}
+
+/// Normalised Barter [`Balance`] model of a single asset.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Balance {
+    pub asset: Symbol,
+    pub total: Num,
+    pub available: Num,
+}
+
+impl Balance {
+    /// Amount of the asset that is unavailable, eg/ reserved by open orders.
+    pub fn locked(&self) -> Num {
+        self.total - self.available
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_balance_serde() {
+        let balance = Balance {
+            asset: Symbol::from("btc"),
+            total: num!(1.5),
+            available: num!(1.0),
+        };
+
+        let serialised = serde_json::to_value(&balance).unwrap();
+        assert_eq!(
+            serialised,
+            serde_json::json!({"asset": "btc", "total": num!(1.5), "available": num!(1.0)})
+        );
+        assert_eq!(
+            serde_json::from_value::<Balance>(serialised).unwrap(),
+            balance
+        );
+        assert_eq!(balance.locked(), num!(0.5));
+    }
+}
@@ -2,11 +2,13 @@ use super::SubKind;
 use crate::{
     event::{MarketEvent, MarketIter},
     exchange::ExchangeId,
+    num::{self, Num},
 };
 use barter_integration::model::{Exchange, Instrument, Side};
 use barter_macro::{DeSubKind, SerSubKind};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 use std::cmp::Ordering;
 use tracing::debug;
 
@@ -34,7 +36,7 @@ impl OrderBookL1 {
     /// Calculate the mid price by taking the average of the best bid and ask prices.
     ///
     /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-    pub fn mid_price(&self) -> f64 {
+    pub fn mid_price(&self) -> Num {
         mid_price(self.best_bid.price, self.best_ask.price)
     }
 
@@ -42,7 +44,7 @@ impl OrderBookL1 {
     /// with their associated amount.
     ///
     /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-    pub fn volume_weighed_mid_price(&self) -> f64 {
+    pub fn volume_weighed_mid_price(&self) -> Num {
         volume_weighted_mid_price(self.best_bid, self.best_ask)
     }
 }
@@ -87,10 +89,16 @@ impl OrderBook {
         self.clone()
     }
 
+    /// Evict the [`Level`]s furthest from the touch on each [`OrderBookSide`] until at most
+    /// `max_levels` remain per side, returning the total number of [`Level`]s evicted.
+    pub fn evict(&mut self, max_levels: usize) -> usize {
+        self.bids.evict(max_levels) + self.asks.evict(max_levels)
+    }
+
     /// Calculate the mid price by taking the average of the best bid and ask prices.
     ///
     /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-    pub fn mid_price(&self) -> Option<f64> {
+    pub fn mid_price(&self) -> Option<Num> {
         match (self.bids.levels.first(), self.asks.levels.first()) {
             (Some(best_bid), Some(best_ask)) => Some(mid_price(best_bid.price, best_ask.price)),
             (Some(best_bid), None) => Some(best_bid.price),
@@ -103,7 +111,7 @@ impl OrderBook {
     /// with their associated amount.
     ///
     /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-    pub fn volume_weighed_mid_price(&self) -> Option<f64> {
+    pub fn volume_weighed_mid_price(&self) -> Option<Num> {
         match (self.bids.levels.first(), self.asks.levels.first()) {
             (Some(best_bid), Some(best_ask)) => {
                 Some(volume_weighted_mid_price(*best_bid, *best_ask))
@@ -135,6 +143,11 @@ impl OrderBookSide {
         }
     }
 
+    /// [`Level`]s of this [`OrderBookSide`], ordered from best to worst once sorted.
+    pub fn levels(&self) -> &[Level] {
+        &self.levels
+    }
+
     /// Upsert a collection of [`Level`]s into this [`OrderBookSide`].
     pub fn upsert<Iter, L>(&mut self, levels: Iter)
     where
@@ -156,12 +169,25 @@ impl OrderBookSide {
     /// #### 2 Level Does Not Exist
     /// 2a) New value is > 0, insert new level
     /// 2b) New value is 0, log error and continue
+    ///
+    /// #### 3 Level Is Invalid
+    /// 3) New value is negative or not finite (eg/ a malformed payload), log error and continue
     pub fn upsert_single<L>(&mut self, new_level: L)
     where
         L: Into<Level>,
     {
         let new_level = new_level.into();
 
+        // Scenario 3: Level is invalid => log error & continue, so the OrderBook is never corrupted
+        if !new_level.is_valid() {
+            debug!(
+                ?new_level,
+                side = %self.side,
+                "Level to upsert is invalid",
+            );
+            return;
+        }
+
         match self
             .levels
             .iter_mut()
@@ -169,7 +195,7 @@ impl OrderBookSide {
             .find(|(_index, level)| level.eq_price(new_level.price))
         {
             // Scenario 1a: Level exists & new value is 0 => remove Level
-            Some((index, _)) if new_level.amount == 0.0 => {
+            Some((index, _)) if new_level.amount == num::zero() => {
                 self.levels.remove(index);
             }
 
@@ -179,7 +205,7 @@ impl OrderBookSide {
             }
 
             // Scenario 2a: Level does not exist & new value > 0 => insert new Level
-            None if new_level.amount > 0.0 => self.levels.push(new_level),
+            None if new_level.amount > num::zero() => self.levels.push(new_level),
 
             // Scenario 2b: Level does not exist & new value is 0 => log error & continue
             _ => {
@@ -192,6 +218,20 @@ impl OrderBookSide {
         };
     }
 
+    /// Evict the [`Level`]s furthest from the touch (ie/ the worst prices) until at most
+    /// `max_levels` remain, returning the number of [`Level`]s evicted. The best [`Level`]s are
+    /// never evicted, and this [`OrderBookSide`] is left sorted if any are.
+    pub fn evict(&mut self, max_levels: usize) -> usize {
+        if self.levels.len() <= max_levels {
+            return 0;
+        }
+
+        self.sort();
+        let evicted = self.levels.len() - max_levels;
+        self.levels.truncate(max_levels);
+        evicted
+    }
+
     /// Sort this [`OrderBookSide`] (bids are reversed).
     pub fn sort(&mut self) {
         // Sort Levels
@@ -207,13 +247,13 @@ impl OrderBookSide {
 /// Normalised Barter OrderBook [`Level`].
 #[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct Level {
-    pub price: f64,
-    pub amount: f64,
+    pub price: Num,
+    pub amount: Num,
 }
 
 impl<T> From<(T, T)> for Level
 where
-    T: Into<f64>,
+    T: Into<Num>,
 {
     fn from((price, amount): (T, T)) -> Self {
         Self::new(price, amount)
@@ -227,6 +267,9 @@ impl Ord for Level {
     }
 }
 
+// partial_cmp is the canonical comparison so it returns None, rather than panicking, for a NaN
+// price or amount
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Level {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.price.partial_cmp(&other.price)? {
@@ -241,7 +284,7 @@ impl Eq for Level {}
 impl Level {
     pub fn new<T>(price: T, amount: T) -> Self
     where
-        T: Into<f64>,
+        T: Into<Num>,
     {
         Self {
             price: price.into(),
@@ -249,10 +292,30 @@ impl Level {
         }
     }
 
-    pub fn eq_price(&self, price: f64) -> bool {
+    #[cfg(not(feature = "decimal"))]
+    pub fn eq_price(&self, price: Num) -> bool {
         let diff = (price - self.price).abs();
         f64::EPSILON > diff
     }
+
+    #[cfg(feature = "decimal")]
+    pub fn eq_price(&self, price: Num) -> bool {
+        price == self.price
+    }
+
+    /// Determines if this [`Level`] can be upserted into an [`OrderBookSide`], ie/ it's price &
+    /// amount are finite and it's amount is not negative.
+    #[cfg(not(feature = "decimal"))]
+    pub fn is_valid(&self) -> bool {
+        self.price.is_finite() && self.amount.is_finite() && self.amount >= num::zero()
+    }
+
+    /// Determines if this [`Level`] can be upserted into an [`OrderBookSide`], ie/ it's amount is
+    /// not negative.
+    #[cfg(feature = "decimal")]
+    pub fn is_valid(&self) -> bool {
+        self.amount >= num::zero()
+    }
 }
 
 // Todo: Add tests
@@ -260,27 +323,43 @@ impl Level {
 /// Calculate the mid price by taking the average of the best bid and ask prices.
 ///
 /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-pub fn mid_price(best_bid_price: f64, best_ask_price: f64) -> f64 {
-    (best_bid_price + best_ask_price) / 2.0
+pub fn mid_price(best_bid_price: Num, best_ask_price: Num) -> Num {
+    (best_bid_price + best_ask_price) / Num::from(2u8)
 }
 
 /// Calculate the volume weighted mid price (micro-price), weighing the best bid and ask prices
 /// with their associated amount.
 ///
+/// Falls back to the [`mid_price`] if the total amount is zero (eg/ an empty L1 level), since
+/// there is nothing to weigh the prices with (and a `Decimal` division by zero panics).
+///
 /// See Docs: <https://www.quantstart.com/articles/high-frequency-trading-ii-limit-order-book>
-pub fn volume_weighted_mid_price(best_bid: Level, best_ask: Level) -> f64 {
-    ((best_bid.price * best_ask.amount) + (best_ask.price * best_bid.amount))
-        / (best_bid.amount + best_ask.amount)
+pub fn volume_weighted_mid_price(best_bid: Level, best_ask: Level) -> Num {
+    let total_amount = best_bid.amount + best_ask.amount;
+    if total_amount == num::zero() {
+        return mid_price(best_bid.price, best_ask.price);
+    }
+
+    ((best_bid.price * best_ask.amount) + (best_ask.price * best_bid.amount)) / total_amount
 }
 
-impl From<(ExchangeId, Instrument, OrderBook)> for MarketIter<OrderBook> {
-    fn from((exchange_id, instrument, book): (ExchangeId, Instrument, OrderBook)) -> Self {
-        Self(vec![Ok(MarketEvent {
+impl From<(ExchangeId, Instrument, DateTime<Utc>, OrderBook)> for MarketIter<OrderBook> {
+    fn from(
+        (exchange_id, instrument, received_time, book): (
+            ExchangeId,
+            Instrument,
+            DateTime<Utc>,
+            OrderBook,
+        ),
+    ) -> Self {
+        Self(smallvec![Ok(MarketEvent {
             exchange_time: book.last_update_time,
-            received_time: Utc::now(),
+            raw_exchange_time: None,
+            received_time,
             exchange: Exchange::from(exchange_id),
             instrument,
             kind: book,
+            meta: (),
         })])
     }
 }
@@ -288,6 +367,7 @@ impl From<(ExchangeId, Instrument, OrderBook)> for MarketIter<OrderBook> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::num;
 
     mod order_book_l1 {
         use super::*;
@@ -296,7 +376,7 @@ mod tests {
         fn test_mid_price() {
             struct TestCase {
                 input: OrderBookL1,
-                expected: f64,
+                expected: Num,
             }
 
             let tests = vec![
@@ -307,7 +387,7 @@ mod tests {
                         best_bid: Level::new(100, 999999),
                         best_ask: Level::new(200, 1),
                     },
-                    expected: 150.0,
+                    expected: num!(150.0),
                 },
                 TestCase {
                     // TC1
@@ -316,7 +396,7 @@ mod tests {
                         best_bid: Level::new(50, 1),
                         best_ask: Level::new(250, 999999),
                     },
-                    expected: 150.0,
+                    expected: num!(150.0),
                 },
                 TestCase {
                     // TC2
@@ -325,7 +405,7 @@ mod tests {
                         best_bid: Level::new(10, 999999),
                         best_ask: Level::new(250, 999999),
                     },
-                    expected: 130.0,
+                    expected: num!(130.0),
                 },
             ];
 
@@ -338,7 +418,7 @@ mod tests {
         fn test_volume_weighted_mid_price() {
             struct TestCase {
                 input: OrderBookL1,
-                expected: f64,
+                expected: Num,
             }
 
             let tests = vec![
@@ -349,7 +429,7 @@ mod tests {
                         best_bid: Level::new(100, 100),
                         best_ask: Level::new(200, 100),
                     },
-                    expected: 150.0,
+                    expected: num!(150.0),
                 },
                 TestCase {
                     // TC1: volume affects mid-price
@@ -358,7 +438,7 @@ mod tests {
                         best_bid: Level::new(100, 600),
                         best_ask: Level::new(200, 1000),
                     },
-                    expected: 137.5,
+                    expected: num!(137.5),
                 },
                 TestCase {
                     // TC2: volume the same and price the same
@@ -367,7 +447,16 @@ mod tests {
                         best_bid: Level::new(1000, 999999),
                         best_ask: Level::new(1000, 999999),
                     },
-                    expected: 1000.0,
+                    expected: num!(1000.0),
+                },
+                TestCase {
+                    // TC3: zero total volume falls back to the non-weighted mid price
+                    input: OrderBookL1 {
+                        last_update_time: Default::default(),
+                        best_bid: Level::new(100, 0),
+                        best_ask: Level::new(200, 0),
+                    },
+                    expected: num!(150.0),
                 },
             ];
 
@@ -388,7 +477,7 @@ mod tests {
         fn test_mid_price() {
             struct TestCase {
                 input: OrderBook,
-                expected: Option<f64>,
+                expected: Option<Num>,
             }
 
             let tests = vec![
@@ -413,14 +502,17 @@ mod tests {
                         last_update_time: Default::default(),
                         bids: OrderBookSide {
                             side: Side::Buy,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(100.0), num!(100.0)),
+                                Level::new(num!(50.0), num!(100.0)),
+                            ],
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
                             levels: vec![],
                         },
                     },
-                    expected: Some(100.0),
+                    expected: Some(num!(100.0)),
                 },
                 TestCase {
                     // TC2: no bids in the book so take ask price
@@ -432,10 +524,13 @@ mod tests {
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
-                            levels: vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(50.0), num!(100.0)),
+                                Level::new(num!(100.0), num!(100.0)),
+                            ],
                         },
                     },
-                    expected: Some(50.0),
+                    expected: Some(num!(50.0)),
                 },
                 TestCase {
                     // TC3: best bid and ask amount is the same, so regular mid-price
@@ -443,14 +538,20 @@ mod tests {
                         last_update_time: Default::default(),
                         bids: OrderBookSide {
                             side: Side::Buy,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(100.0), num!(100.0)),
+                                Level::new(num!(50.0), num!(100.0)),
+                            ],
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
-                            levels: vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(200.0), num!(100.0)),
+                                Level::new(num!(300.0), num!(100.0)),
+                            ],
                         },
                     },
-                    expected: Some(150.0),
+                    expected: Some(num!(150.0)),
                 },
             ];
 
@@ -463,7 +564,7 @@ mod tests {
         fn test_volume_weighted_mid_price() {
             struct TestCase {
                 input: OrderBook,
-                expected: Option<f64>,
+                expected: Option<Num>,
             }
 
             let tests = vec![
@@ -488,14 +589,17 @@ mod tests {
                         last_update_time: Default::default(),
                         bids: OrderBookSide {
                             side: Side::Buy,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(100.0), num!(100.0)),
+                                Level::new(num!(50.0), num!(100.0)),
+                            ],
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
                             levels: vec![],
                         },
                     },
-                    expected: Some(100.0),
+                    expected: Some(num!(100.0)),
                 },
                 TestCase {
                     // TC2: no bids in the book so take ask price
@@ -507,10 +611,13 @@ mod tests {
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
-                            levels: vec![Level::new(50.0, 100.0), Level::new(100.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(50.0), num!(100.0)),
+                                Level::new(num!(100.0), num!(100.0)),
+                            ],
                         },
                     },
-                    expected: Some(50.0),
+                    expected: Some(num!(50.0)),
                 },
                 TestCase {
                     // TC3: best bid and ask amount is the same, so regular mid-price
@@ -518,14 +625,20 @@ mod tests {
                         last_update_time: Default::default(),
                         bids: OrderBookSide {
                             side: Side::Buy,
-                            levels: vec![Level::new(100.0, 100.0), Level::new(50.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(100.0), num!(100.0)),
+                                Level::new(num!(50.0), num!(100.0)),
+                            ],
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
-                            levels: vec![Level::new(200.0, 100.0), Level::new(300.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(200.0), num!(100.0)),
+                                Level::new(num!(300.0), num!(100.0)),
+                            ],
                         },
                     },
-                    expected: Some(150.0),
+                    expected: Some(num!(150.0)),
                 },
                 TestCase {
                     // TC4: valid volume weighted mid-price
@@ -533,14 +646,20 @@ mod tests {
                         last_update_time: Default::default(),
                         bids: OrderBookSide {
                             side: Side::Buy,
-                            levels: vec![Level::new(100.0, 3000.0), Level::new(50.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(100.0), num!(3000.0)),
+                                Level::new(num!(50.0), num!(100.0)),
+                            ],
                         },
                         asks: OrderBookSide {
                             side: Side::Sell,
-                            levels: vec![Level::new(200.0, 1000.0), Level::new(300.0, 100.0)],
+                            levels: vec![
+                                Level::new(num!(200.0), num!(1000.0)),
+                                Level::new(num!(300.0), num!(100.0)),
+                            ],
                         },
                     },
-                    expected: Some(175.0),
+                    expected: Some(num!(175.0)),
                 },
             ];
 
@@ -619,6 +738,18 @@ mod tests {
                         vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1)],
                     ),
                 },
+                TestCase {
+                    // TC4: Level exists & new value is negative => no change
+                    book_side: OrderBookSide::new(
+                        Side::Buy,
+                        vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1)],
+                    ),
+                    new_level: Level::new(100, -1),
+                    expected: OrderBookSide::new(
+                        Side::Buy,
+                        vec![Level::new(80, 1), Level::new(90, 1), Level::new(100, 1)],
+                    ),
+                },
             ];
 
             for (index, mut test) in tests.into_iter().enumerate() {
@@ -796,6 +927,74 @@ mod tests {
                 assert_eq!(test.input, test.expected, "TC{} failed", index);
             }
         }
+
+        #[test]
+        fn test_evict() {
+            struct TestCase {
+                input: OrderBookSide,
+                max_levels: usize,
+                expected: OrderBookSide,
+                expected_evicted: usize,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: bids within cap are untouched
+                    input: OrderBookSide::new(
+                        Side::Buy,
+                        vec![Level::new(90, 1), Level::new(100, 1)],
+                    ),
+                    max_levels: 2,
+                    expected: OrderBookSide::new(
+                        Side::Buy,
+                        vec![Level::new(90, 1), Level::new(100, 1)],
+                    ),
+                    expected_evicted: 0,
+                },
+                TestCase {
+                    // TC1: lowest bids are evicted
+                    input: OrderBookSide::new(
+                        Side::Buy,
+                        vec![
+                            Level::new(90, 1),
+                            Level::new(120, 1),
+                            Level::new(80, 1),
+                            Level::new(110, 1),
+                        ],
+                    ),
+                    max_levels: 2,
+                    expected: OrderBookSide::new(
+                        Side::Buy,
+                        vec![Level::new(120, 1), Level::new(110, 1)],
+                    ),
+                    expected_evicted: 2,
+                },
+                TestCase {
+                    // TC2: highest asks are evicted
+                    input: OrderBookSide::new(
+                        Side::Sell,
+                        vec![
+                            Level::new(90, 1),
+                            Level::new(120, 1),
+                            Level::new(80, 1),
+                            Level::new(110, 1),
+                        ],
+                    ),
+                    max_levels: 3,
+                    expected: OrderBookSide::new(
+                        Side::Sell,
+                        vec![Level::new(80, 1), Level::new(90, 1), Level::new(110, 1)],
+                    ),
+                    expected_evicted: 1,
+                },
+            ];
+
+            for (index, mut test) in tests.into_iter().enumerate() {
+                let evicted = test.input.evict(test.max_levels);
+                assert_eq!(evicted, test.expected_evicted, "TC{} failed", index);
+                assert_eq!(test.input, test.expected, "TC{} failed", index);
+            }
+        }
     }
 
     mod level {
@@ -883,6 +1082,22 @@ mod tests {
             }
         }
 
+        #[test]
+        #[cfg(not(feature = "decimal"))]
+        fn test_partial_ord_nan() {
+            // A NaN price or amount is incomparable, rather than panicking
+            let level = Level::new(100.0, 100.0);
+            assert_eq!(level.partial_cmp(&Level::new(f64::NAN, 100.0)), None);
+            assert_eq!(level.partial_cmp(&Level::new(100.0, f64::NAN)), None);
+            assert_eq!(Level::new(f64::NAN, 100.0).partial_cmp(&level), None);
+
+            // NaN amount is irrelevant if the prices differ
+            assert_eq!(
+                level.partial_cmp(&Level::new(10.0, f64::NAN)),
+                Some(Ordering::Greater)
+            );
+        }
+
         #[test]
         fn test_eq_price() {
             struct TestCase {
@@ -1,24 +1,135 @@
 use super::SubKind;
+use crate::num::Num;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 /// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`Candle`]
 /// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+///
+/// Parameterised by the [`Interval`] of the [`Candle`]s to stream. Serialises the interval
+/// along with the kind, eg/ `{"candles": "1m"}`.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
-pub struct Candles;
+#[serde(from = "CandlesKind", into = "CandlesKind")]
+pub struct Candles(pub Interval);
 
 impl SubKind for Candles {
     type Event = Candle;
 }
 
+impl Display for Candles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "candles_{}", self.0)
+    }
+}
+
+/// Serde representation of [`Candles`] that tags the [`Interval`] with the [`SubKind`] name.
+#[derive(Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CandlesKind {
+    Candles(Interval),
+}
+
+impl From<CandlesKind> for Candles {
+    fn from(kind: CandlesKind) -> Self {
+        match kind {
+            CandlesKind::Candles(interval) => Self(interval),
+        }
+    }
+}
+
+impl From<Candles> for CandlesKind {
+    fn from(candles: Candles) -> Self {
+        Self::Candles(candles.0)
+    }
+}
+
+/// Duration of time each [`Candle`] spans.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum Interval {
+    #[serde(rename = "1m")]
+    M1,
+    #[serde(rename = "3m")]
+    M3,
+    #[serde(rename = "5m")]
+    M5,
+    #[serde(rename = "15m")]
+    M15,
+    #[serde(rename = "30m")]
+    M30,
+    #[serde(rename = "1h")]
+    H1,
+    #[serde(rename = "2h")]
+    H2,
+    #[serde(rename = "4h")]
+    H4,
+    #[serde(rename = "6h")]
+    H6,
+    #[serde(rename = "12h")]
+    H12,
+    #[serde(rename = "1d")]
+    D1,
+    #[serde(rename = "1w")]
+    W1,
+}
+
+impl Interval {
+    /// Return the `&str` representation of this [`Interval`], eg/ "1m".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::M1 => "1m",
+            Interval::M3 => "3m",
+            Interval::M5 => "5m",
+            Interval::M15 => "15m",
+            Interval::M30 => "30m",
+            Interval::H1 => "1h",
+            Interval::H2 => "2h",
+            Interval::H4 => "4h",
+            Interval::H6 => "6h",
+            Interval::H12 => "12h",
+            Interval::D1 => "1d",
+            Interval::W1 => "1w",
+        }
+    }
+
+    /// Return the [`chrono::Duration`] spanned by this [`Interval`].
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            Interval::M1 => chrono::Duration::minutes(1),
+            Interval::M3 => chrono::Duration::minutes(3),
+            Interval::M5 => chrono::Duration::minutes(5),
+            Interval::M15 => chrono::Duration::minutes(15),
+            Interval::M30 => chrono::Duration::minutes(30),
+            Interval::H1 => chrono::Duration::hours(1),
+            Interval::H2 => chrono::Duration::hours(2),
+            Interval::H4 => chrono::Duration::hours(4),
+            Interval::H6 => chrono::Duration::hours(6),
+            Interval::H12 => chrono::Duration::hours(12),
+            Interval::D1 => chrono::Duration::days(1),
+            Interval::W1 => chrono::Duration::weeks(1),
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Normalised Barter OHLCV [`Candle`] model.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Candle {
     pub close_time: DateTime<Utc>,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
-    pub volume: f64,
+    pub open: Num,
+    pub high: Num,
+    pub low: Num,
+    pub close: Num,
+    pub volume: Num,
     pub trade_count: u64,
+    /// True if the [`Candle`] was fetched via the exchange REST API (eg/ a backfill of the
+    /// trailing candles emitted before the live candles), rather than streamed live. Omitted
+    /// from the serialised representation when false.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub historical: bool,
 }
@@ -0,0 +1,26 @@
+use super::SubKind;
+use crate::event::DataKind;
+use barter_macro::{DeSubKind, SerSubKind};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields both
+/// [`PublicTrade`](super::trade::PublicTrade) and [`OrderBookL1`](super::book::OrderBookL1)
+/// [`MarketEvent<DataKind>`](crate::event::MarketEvent) events for an instrument, routed through
+/// a single connection.
+///
+/// ### Ordering
+/// Exchanges that multiplex both channels over one socket (eg/ the Binance combined stream) emit
+/// the events in the exact order they arrive on the socket, which is the order the exchange
+/// produced them in. This is useful for accurately reconstructing the sequence of market events.
+/// See [`Streams`](crate::streams::Streams) ordering.
+///
+/// No such guarantee can be made when trades & order books are consumed from separate
+/// connections (eg/ by combining [`PublicTrades`](super::trade::PublicTrades) and
+/// [`OrderBooksL1`](super::book::OrderBooksL1) streams with the
+/// [`MultiStreamBuilder`](crate::streams::builder::multi::MultiStreamBuilder)), since each socket
+/// is subject to independent network delays.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct TradesAndBooksL1;
+
+impl SubKind for TradesAndBooksL1 {
+    type Event = DataKind;
+}
@@ -0,0 +1,459 @@
+use super::{
+    book::{OrderBooksL1, OrderBooksL2},
+    candle::Candles,
+    instrument_status::InstrumentStatuses,
+    liquidation::Liquidations,
+    trade::PublicTrades,
+    SubKind, Subscription,
+};
+use crate::{
+    exchange::{ExchangeId, StreamSelector},
+    streams::{builder::StreamBuilder, meta::EventMeta},
+    Identifier,
+};
+use barter_integration::model::{InstrumentKind, Symbol};
+use std::fmt::{Display, Formatter};
+
+/// Placeholder exchange of a [`Subscription`] to an instrument on every exchange supporting the
+/// [`SubKind`], constructed via [`Subscription::everywhere`].
+///
+/// Expanded into a [`Subscription`] per exchange by
+/// [`StreamBuilder::subscribe_everywhere`](crate::streams::builder::StreamBuilder::subscribe_everywhere).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Everywhere;
+
+impl Display for Everywhere {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "everywhere")
+    }
+}
+
+impl<Kind> Subscription<Everywhere, Kind> {
+    /// Constructs a new [`Subscription`] to the provided instrument on every exchange that
+    /// supports the [`SubKind`] & [`InstrumentKind`], see [`SubscribeEverywhere::exchanges`].
+    ///
+    /// ```rust,no_run
+    /// # async fn liquidity() {
+    /// use barter_data::{
+    ///     streams::Streams,
+    ///     subscription::{trade::PublicTrades, Subscription},
+    /// };
+    /// use barter_integration::model::InstrumentKind;
+    ///
+    /// // Monitor btc_usdt trades on every exchange, with events tagged by exchange
+    /// let streams = Streams::<PublicTrades>::builder()
+    ///     .subscribe_everywhere([Subscription::everywhere(
+    ///         "btc",
+    ///         "usdt",
+    ///         InstrumentKind::Spot,
+    ///         PublicTrades,
+    ///     )])
+    ///     .init()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn everywhere<S>(base: S, quote: S, instrument_kind: InstrumentKind, kind: Kind) -> Self
+    where
+        S: Into<Symbol>,
+    {
+        Self::new(Everywhere, (base, quote, instrument_kind), kind)
+    }
+}
+
+/// [`SubKind`]s that can be subscribed to on every exchange with a [`StreamSelector`] for them,
+/// or on any one of those exchanges identified at runtime by it's [`ExchangeId`] (eg/ from a
+/// configuration file).
+///
+/// Only exchanges with a [`StreamSelector`] for the [`SubKind`] whose cargo feature is enabled
+/// are included, filtered by whether they support the [`InstrumentKind`] of the instrument (see
+/// [`ExchangeId::supports`]). Whether the instrument is actually listed on each exchange is not
+/// known until it's subscription is actioned.
+pub trait SubscribeEverywhere
+where
+    Self: SubKind + Sized,
+{
+    /// Every enabled exchange with a [`StreamSelector`] for [`Self`], regardless of the
+    /// [`InstrumentKind`]s it supports.
+    fn connectors() -> Vec<ExchangeId>;
+
+    /// Every enabled exchange with a [`StreamSelector`] for [`Self`] that supports the provided
+    /// [`InstrumentKind`].
+    fn exchanges(instrument_kind: InstrumentKind) -> Vec<ExchangeId> {
+        let mut exchanges = Self::connectors();
+        exchanges.retain(|exchange| exchange.supports(instrument_kind));
+        exchanges
+    }
+
+    /// Add a [`Subscription`] to the provided [`StreamBuilder`] for each of the
+    /// [`exchanges`](Self::exchanges) supporting the [`Subscription`] instrument, each actioned
+    /// on a distinct connection.
+    fn subscribe_everywhere<Meta>(
+        builder: StreamBuilder<Self, Meta>,
+        subscription: &Subscription<Everywhere, Self>,
+    ) -> StreamBuilder<Self, Meta>
+    where
+        Meta: EventMeta;
+
+    /// Add the provided [`Subscription`]s to the exchange identified by the [`ExchangeId`] to
+    /// the provided [`StreamBuilder`] on a single connection, using the default
+    /// [`Connector`](crate::exchange::Connector) of the exchange.
+    ///
+    /// Every [`Subscription`] must be to the provided exchange, which must be one of the
+    /// [`connectors`](Self::connectors), else the [`StreamBuilder`] is returned unchanged.
+    fn subscribe_exchange<Meta>(
+        builder: StreamBuilder<Self, Meta>,
+        exchange: ExchangeId,
+        subscriptions: Vec<Subscription<ExchangeId, Self>>,
+    ) -> StreamBuilder<Self, Meta>
+    where
+        Meta: EventMeta;
+}
+
+/// Add the [`Subscription`] to the provided [`StreamBuilder`] for the `Exchange`, if it
+/// supports the [`Subscription`] [`InstrumentKind`] & [`SubKind`] parameters (eg/ the
+/// [`Candles`] interval, see [`StreamSelector::unsupported`]).
+#[cfg_attr(
+    not(any(
+        feature = "binance",
+        feature = "bitfinex",
+        feature = "coinbase",
+        feature = "gateio",
+        feature = "kraken",
+        feature = "okx"
+    )),
+    allow(dead_code)
+)]
+fn subscribe_on<Exchange, Kind, Meta>(
+    builder: StreamBuilder<Kind, Meta>,
+    subscription: &Subscription<Everywhere, Kind>,
+) -> StreamBuilder<Kind, Meta>
+where
+    Exchange: StreamSelector<Kind> + Default + Ord + Send + Sync + 'static,
+    Kind: SubKind + Ord + Send + Sync + 'static,
+    Kind::Event: Send,
+    Meta: EventMeta,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    if !Exchange::ID.supports(subscription.instrument.kind)
+        || Exchange::unsupported(&subscription.kind).is_some()
+    {
+        return builder;
+    }
+
+    builder.subscribe([Subscription::new(
+        Exchange::default(),
+        subscription.instrument.clone(),
+        subscription.kind.clone(),
+    )])
+}
+
+/// Add the [`Subscription`]s to the provided [`StreamBuilder`] on a single connection using the
+/// default `Exchange` [`Connector`](crate::exchange::Connector).
+///
+/// Unlike [`subscribe_on`], unsupported [`Subscription`]s are added so they are recorded as
+/// invalid by the [`StreamBuilder`].
+#[cfg_attr(
+    not(any(
+        feature = "binance",
+        feature = "bitfinex",
+        feature = "coinbase",
+        feature = "gateio",
+        feature = "kraken",
+        feature = "okx"
+    )),
+    allow(dead_code)
+)]
+fn subscribe_default<Exchange, Kind, Meta>(
+    builder: StreamBuilder<Kind, Meta>,
+    subscriptions: Vec<Subscription<ExchangeId, Kind>>,
+) -> StreamBuilder<Kind, Meta>
+where
+    Exchange: StreamSelector<Kind> + Default + Ord + Send + Sync + 'static,
+    Kind: SubKind + Ord + Send + Sync + 'static,
+    Kind::Event: Send,
+    Meta: EventMeta,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    builder.subscribe(subscriptions.into_iter().map(|subscription| {
+        Subscription::new(
+            Exchange::default(),
+            subscription.instrument,
+            subscription.kind,
+        )
+    }))
+}
+
+/// Implement [`SubscribeEverywhere`] for a [`SubKind`] using the provided exchanges, grouped by
+/// the cargo feature that enables them.
+macro_rules! impl_subscribe_everywhere {
+    ($kind:ty => { $($feature:literal => [$($exchange:ty),+ $(,)?]),+ $(,)? }) => {
+        impl SubscribeEverywhere for $kind {
+            #[allow(unused_mut)]
+            fn connectors() -> Vec<ExchangeId> {
+                let mut exchanges: Vec<ExchangeId> = Vec::new();
+                $(
+                    #[cfg(feature = $feature)]
+                    exchanges.extend([$(<$exchange as crate::exchange::Connector>::ID),+]);
+                )+
+                exchanges
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn subscribe_everywhere<Meta>(
+                mut builder: StreamBuilder<Self, Meta>,
+                subscription: &Subscription<Everywhere, Self>,
+            ) -> StreamBuilder<Self, Meta>
+            where
+                Meta: EventMeta,
+            {
+                $(
+                    #[cfg(feature = $feature)]
+                    {
+                        $(builder = subscribe_on::<$exchange, Self, Meta>(builder, subscription);)+
+                    }
+                )+
+                builder
+            }
+
+            #[allow(unused_variables)]
+            fn subscribe_exchange<Meta>(
+                builder: StreamBuilder<Self, Meta>,
+                exchange: ExchangeId,
+                subscriptions: Vec<Subscription<ExchangeId, Self>>,
+            ) -> StreamBuilder<Self, Meta>
+            where
+                Meta: EventMeta,
+            {
+                $(
+                    #[cfg(feature = $feature)]
+                    {
+                        $(
+                            if exchange == <$exchange as crate::exchange::Connector>::ID {
+                                return subscribe_default::<$exchange, Self, Meta>(
+                                    builder,
+                                    subscriptions,
+                                );
+                            }
+                        )+
+                    }
+                )+
+                builder
+            }
+        }
+    };
+}
+
+impl_subscribe_everywhere!(PublicTrades => {
+    "binance" => [
+        crate::exchange::binance::spot::BinanceSpot,
+        crate::exchange::binance::futures::BinanceFuturesUsd,
+        crate::exchange::binance::us::BinanceUs,
+    ],
+    "bitfinex" => [crate::exchange::bitfinex::Bitfinex],
+    "coinbase" => [crate::exchange::coinbase::Coinbase],
+    "gateio" => [
+        crate::exchange::gateio::spot::GateioSpot,
+        crate::exchange::gateio::futures::GateioFuturesUsd,
+        crate::exchange::gateio::futures::GateioFuturesBtc,
+    ],
+    "kraken" => [crate::exchange::kraken::Kraken],
+    "okx" => [crate::exchange::okx::Okx],
+});
+
+impl_subscribe_everywhere!(OrderBooksL1 => {
+    "binance" => [
+        crate::exchange::binance::spot::BinanceSpot,
+        crate::exchange::binance::futures::BinanceFuturesUsd,
+        crate::exchange::binance::us::BinanceUs,
+    ],
+    "coinbase" => [crate::exchange::coinbase::Coinbase],
+    "kraken" => [crate::exchange::kraken::Kraken],
+});
+
+impl_subscribe_everywhere!(OrderBooksL2 => {
+    "binance" => [
+        crate::exchange::binance::spot::BinanceSpot,
+        crate::exchange::binance::futures::BinanceFuturesUsd,
+    ],
+    "bitfinex" => [crate::exchange::bitfinex::Bitfinex],
+});
+
+impl_subscribe_everywhere!(Candles => {
+    "binance" => [
+        crate::exchange::binance::spot::BinanceSpot,
+        crate::exchange::binance::futures::BinanceFuturesUsd,
+        crate::exchange::binance::us::BinanceUs,
+    ],
+    "kraken" => [crate::exchange::kraken::Kraken],
+});
+
+impl_subscribe_everywhere!(Liquidations => {
+    "binance" => [crate::exchange::binance::futures::BinanceFuturesUsd],
+});
+
+impl_subscribe_everywhere!(InstrumentStatuses => {
+    "binance" => [
+        crate::exchange::binance::spot::BinanceSpot,
+        crate::exchange::binance::futures::BinanceFuturesUsd,
+    ],
+    "okx" => [crate::exchange::okx::Okx],
+});
+
+#[cfg(all(
+    test,
+    feature = "binance",
+    feature = "bitfinex",
+    feature = "coinbase",
+    feature = "gateio",
+    feature = "kraken",
+    feature = "okx"
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_everywhere_exchanges() {
+        struct TestCase {
+            input: Vec<ExchangeId>,
+            expected: Vec<ExchangeId>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: PublicTrades Spot
+                input: PublicTrades::exchanges(InstrumentKind::Spot),
+                expected: vec![
+                    ExchangeId::BinanceSpot,
+                    ExchangeId::BinanceUs,
+                    ExchangeId::Bitfinex,
+                    ExchangeId::Coinbase,
+                    ExchangeId::GateioSpot,
+                    ExchangeId::Kraken,
+                    ExchangeId::Okx,
+                ],
+            },
+            TestCase {
+                // TC1: PublicTrades FuturePerpetual
+                input: PublicTrades::exchanges(InstrumentKind::FuturePerpetual),
+                expected: vec![
+                    ExchangeId::BinanceFuturesUsd,
+                    ExchangeId::GateioFuturesUsd,
+                    ExchangeId::GateioFuturesBtc,
+                    ExchangeId::Okx,
+                ],
+            },
+            TestCase {
+                // TC2: OrderBooksL1 Spot
+                input: OrderBooksL1::exchanges(InstrumentKind::Spot),
+                expected: vec![
+                    ExchangeId::BinanceSpot,
+                    ExchangeId::BinanceUs,
+                    ExchangeId::Coinbase,
+                    ExchangeId::Kraken,
+                ],
+            },
+            TestCase {
+                // TC3: Liquidations Spot is not supported anywhere
+                input: Liquidations::exchanges(InstrumentKind::Spot),
+                expected: vec![],
+            },
+            TestCase {
+                // TC4: InstrumentStatuses FuturePerpetual
+                input: InstrumentStatuses::exchanges(InstrumentKind::FuturePerpetual),
+                expected: vec![ExchangeId::BinanceFuturesUsd, ExchangeId::Okx],
+            },
+            TestCase {
+                // TC5: Candles Spot
+                input: Candles::exchanges(InstrumentKind::Spot),
+                expected: vec![
+                    ExchangeId::BinanceSpot,
+                    ExchangeId::BinanceUs,
+                    ExchangeId::Kraken,
+                ],
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            assert_eq!(test.input, test.expected, "TC{} failed", index);
+        }
+    }
+
+    #[test]
+    fn test_subscribe_everywhere_adds_connection_per_exchange() {
+        let builder =
+            StreamBuilder::<PublicTrades>::new().subscribe_everywhere([Subscription::everywhere(
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )]);
+
+        let mut actual = builder.channels.keys().copied().collect::<Vec<_>>();
+        actual.sort();
+        let mut expected = PublicTrades::exchanges(InstrumentKind::Spot);
+        expected.sort();
+
+        assert_eq!(actual, expected);
+        assert_eq!(builder.futures.len(), actual.len());
+        assert!(builder.invalid.is_empty());
+    }
+
+    #[test]
+    fn test_subscribe_everywhere_skips_unsupported_kind() {
+        use crate::subscription::candle::Interval;
+
+        // Kraken does not serve 3m candles
+        let builder =
+            StreamBuilder::<Candles>::new().subscribe_everywhere([Subscription::everywhere(
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                Candles(Interval::M3),
+            )]);
+
+        let mut actual = builder.channels.keys().copied().collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(actual, vec![ExchangeId::BinanceSpot, ExchangeId::BinanceUs]);
+        assert!(builder.invalid.is_empty());
+    }
+
+    #[test]
+    fn test_subscriptions_from_config_across_exchanges() {
+        // Single configuration file spanning several exchanges & connections
+        let config = r#"[
+            [
+                {"exchange": "binance_spot", "base": "btc", "quote": "usdt", "instrument_type": "spot", "kind": "order_books_l1"},
+                {"exchange": "kraken", "base": "btc", "quote": "usd", "instrument_type": "spot", "kind": "order_books_l1"},
+                {"exchange": "binance_spot", "base": "eth", "quote": "usdt", "instrument_type": "spot", "kind": "order_books_l1"}
+            ],
+            [
+                {"exchange": "binance_futures_usd", "base": "btc", "quote": "usdt", "instrument_type": "future_perpetual", "kind": "order_books_l1"},
+                {"exchange": "okx", "base": "btc", "quote": "usdt", "instrument_type": "spot", "kind": "order_books_l1"}
+            ]
+        ]"#;
+        let config =
+            serde_json::from_str::<Vec<Vec<Subscription<ExchangeId, OrderBooksL1>>>>(config)
+                .unwrap();
+
+        let builder = StreamBuilder::<OrderBooksL1>::new().subscriptions_from(config);
+
+        // One connection per exchange of each collection
+        let mut actual = builder.channels.keys().copied().collect::<Vec<_>>();
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![
+                ExchangeId::BinanceFuturesUsd,
+                ExchangeId::BinanceSpot,
+                ExchangeId::Kraken
+            ]
+        );
+        assert_eq!(builder.futures.len(), 3);
+
+        // Okx has no OrderBooksL1 stream
+        assert_eq!(builder.invalid.len(), 1);
+        assert_eq!(builder.invalid[0].exchange, ExchangeId::Okx);
+    }
+}
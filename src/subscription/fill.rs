@@ -0,0 +1,106 @@
+use super::SubKind;
+use crate::num::Num;
+use barter_integration::model::{Side, Symbol};
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`Fill`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events for the trades of the logged in account.
+///
+/// Private, so it requires the exchange
+/// [`Credentials`](crate::subscriber::auth::Credentials).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct Fills;
+
+impl SubKind for Fills {
+    type Event = Fill;
+}
+
+/// Normalised Barter [`Fill`] model, ie/ a trade of one of the account's orders.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Fill {
+    pub trade_id: String,
+    pub order_id: String,
+    pub side: Side,
+    pub price: Num,
+    pub amount: Num,
+    pub liquidity: Liquidity,
+    /// Fee charged for the trade, negative for a rebate, or `None` if the exchange does not
+    /// include it.
+    #[serde(default)]
+    pub fee: Option<Num>,
+    /// Asset the [`Self::fee`] is charged in, if the exchange includes it.
+    #[serde(default)]
+    pub fee_asset: Option<Symbol>,
+}
+
+impl Fill {
+    /// Quote asset notional of the trade, ie/ price * amount.
+    pub fn notional(&self) -> Num {
+        self.price * self.amount
+    }
+}
+
+/// Whether a [`Fill`] provided (maker) or removed (taker) liquidity from the OrderBook.
+///
+/// Exchange liquidity flags are mapped as follows:
+///
+/// | Liquidity | Binance | Coinbase              | Kraken                                       | Okx             |
+/// |:---------:|:-------:|:---------------------:|:--------------------------------------------:|:---------------:|
+/// | `Maker`   |         | own `maker_order_id`  | `maker: true`, or any non-market order       | `execType: "M"` |
+/// | `Taker`   |         | own `taker_order_id`  | `maker: false`, or a market order            | `execType: "T"` |
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_fill_serde() {
+        let fill = Fill {
+            trade_id: "10".to_string(),
+            order_id: "1".to_string(),
+            side: Side::Sell,
+            price: num!(100.0),
+            amount: num!(0.5),
+            liquidity: Liquidity::Maker,
+            fee: Some(num!(0.05)),
+            fee_asset: Some(Symbol::from("usdt")),
+        };
+
+        let serialised = serde_json::to_value(&fill).unwrap();
+        assert_eq!(
+            serialised,
+            serde_json::json!({
+                "trade_id": "10",
+                "order_id": "1",
+                "side": "Sell",
+                "price": num!(100.0),
+                "amount": num!(0.5),
+                "liquidity": "maker",
+                "fee": num!(0.05),
+                "fee_asset": "usdt"
+            })
+        );
+        assert_eq!(serde_json::from_value::<Fill>(serialised).unwrap(), fill);
+        assert_eq!(fill.notional(), num!(50.0));
+
+        // Fills serialised before the fee was added remain valid
+        let legacy = r#"{"trade_id":"10","order_id":"1","side":"Sell","price":100.0,"amount":0.5,"liquidity":"taker"}"#;
+        assert_eq!(
+            serde_json::from_str::<Fill>(legacy).unwrap(),
+            Fill {
+                liquidity: Liquidity::Taker,
+                fee: None,
+                fee_asset: None,
+                ..fill
+            }
+        );
+    }
+}
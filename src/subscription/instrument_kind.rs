@@ -0,0 +1,206 @@
+use crate::error::DataError;
+use barter_integration::model::InstrumentKind;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Normalised name of an [`InstrumentKind`], parsed leniently from user input (eg/ config
+/// files & CLI arguments) and displayed as the canonical `snake_case` name.
+///
+/// Parsing is case insensitive, treats `-` & whitespace as `_`, and accepts common exchange
+/// aliases, see [`parse_instrument_kind`]. Serialises as the canonical name & deserialises from
+/// any accepted name, so it can be used directly in config structs.
+///
+/// ```rust
+/// use barter_data::subscription::instrument_kind::InstrumentKindName;
+/// use barter_integration::model::InstrumentKind;
+///
+/// let kind: InstrumentKindName = "Swap".parse().unwrap();
+/// assert_eq!(InstrumentKind::from(kind), InstrumentKind::FuturePerpetual);
+/// assert_eq!(kind.to_string(), "future_perpetual");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct InstrumentKindName(pub InstrumentKind);
+
+impl FromStr for InstrumentKindName {
+    type Err = DataError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_instrument_kind(input).map(Self)
+    }
+}
+
+impl Display for InstrumentKindName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<InstrumentKind> for InstrumentKindName {
+    fn from(kind: InstrumentKind) -> Self {
+        Self(kind)
+    }
+}
+
+impl From<InstrumentKindName> for InstrumentKind {
+    fn from(name: InstrumentKindName) -> Self {
+        name.0
+    }
+}
+
+impl<'de> Deserialize<'de> for InstrumentKindName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let input = String::deserialize(deserializer)?;
+        input.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for InstrumentKindName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Parse an [`InstrumentKind`] from user input, accepting the canonical names it's
+/// [`Display`] produces as well as common aliases:
+/// - `spot` for [`InstrumentKind::Spot`]
+/// - `future_perpetual`, `perpetual`, `perp`, `swap`, `perpetual_future` & `future` for
+///   [`InstrumentKind::FuturePerpetual`]
+///
+/// `future` maps to [`InstrumentKind::FuturePerpetual`] since it's the only futures contract
+/// Barter models, eg/ Binance "USD-M Futures".
+///
+/// Unknown values fail with a [`DataError::UnknownInstrumentKind`] listing the accepted names.
+#[allow(clippy::result_large_err)]
+pub fn parse_instrument_kind(input: &str) -> Result<InstrumentKind, DataError> {
+    let normalised = input
+        .trim()
+        .to_ascii_lowercase()
+        .replace(|char: char| char == '-' || char.is_whitespace(), "_");
+
+    match normalised.as_str() {
+        "spot" => Ok(InstrumentKind::Spot),
+        "future_perpetual" | "perpetual" | "perp" | "swap" | "perpetual_future" | "future" => {
+            Ok(InstrumentKind::FuturePerpetual)
+        }
+        _ => Err(DataError::UnknownInstrumentKind(input.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instrument_kind() {
+        struct TestCase {
+            input: &'static str,
+            expected: Result<InstrumentKind, DataError>,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: canonical Spot
+                input: "spot",
+                expected: Ok(InstrumentKind::Spot),
+            },
+            TestCase {
+                // TC1: canonical FuturePerpetual
+                input: "future_perpetual",
+                expected: Ok(InstrumentKind::FuturePerpetual),
+            },
+            TestCase {
+                // TC2: swap alias w/ mixed case & whitespace
+                input: " Swap ",
+                expected: Ok(InstrumentKind::FuturePerpetual),
+            },
+            TestCase {
+                // TC3: perpetual alias
+                input: "perpetual",
+                expected: Ok(InstrumentKind::FuturePerpetual),
+            },
+            TestCase {
+                // TC4: future alias
+                input: "FUTURE",
+                expected: Ok(InstrumentKind::FuturePerpetual),
+            },
+            TestCase {
+                // TC5: hyphenated name
+                input: "future-perpetual",
+                expected: Ok(InstrumentKind::FuturePerpetual),
+            },
+            TestCase {
+                // TC6: unknown value
+                input: "option",
+                expected: Err(DataError::UnknownInstrumentKind("option".to_string())),
+            },
+            TestCase {
+                // TC7: empty value
+                input: "",
+                expected: Err(DataError::UnknownInstrumentKind("".to_string())),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = parse_instrument_kind(test.input);
+            match (actual, test.expected) {
+                (Ok(actual), Ok(expected)) => {
+                    assert_eq!(actual, expected, "TC{} failed", index)
+                }
+                (Err(actual), Err(expected)) => {
+                    assert_eq!(
+                        actual.to_string(),
+                        expected.to_string(),
+                        "TC{} failed",
+                        index
+                    )
+                }
+                (actual, expected) => {
+                    // Test failed
+                    panic!("TC{index} failed because actual != expected. \nActual: {actual:?}\nExpected: {expected:?}\n");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_instrument_kind_name_round_trip() {
+        for kind in [InstrumentKind::Spot, InstrumentKind::FuturePerpetual] {
+            let name = InstrumentKindName(kind);
+
+            // Display -> FromStr
+            let display = name.to_string();
+            assert_eq!(display, kind.to_string());
+            assert_eq!(display.parse::<InstrumentKindName>().unwrap(), name);
+
+            // Serialize -> Deserialize
+            let json = serde_json::to_string(&name).unwrap();
+            assert_eq!(json, format!("\"{kind}\""));
+            assert_eq!(
+                serde_json::from_str::<InstrumentKindName>(&json).unwrap(),
+                name
+            );
+
+            // Canonical name is compatible with the InstrumentKind serde representation
+            assert_eq!(serde_json::from_str::<InstrumentKind>(&json).unwrap(), kind);
+        }
+
+        // Aliases deserialise, unknown values fail with a clear error
+        assert_eq!(
+            serde_json::from_str::<InstrumentKindName>(r#""swap""#).unwrap(),
+            InstrumentKindName(InstrumentKind::FuturePerpetual)
+        );
+        let error = serde_json::from_str::<InstrumentKindName>(r#""option""#).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("UnknownInstrumentKind: \"option\""));
+    }
+}
@@ -0,0 +1,72 @@
+use super::SubKind;
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`InstrumentStatus`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events each time the trading status of an
+/// instrument changes, eg/ a trading halt or delisting.
+///
+/// The current status is yielded first, followed by each change. Streamed where the exchange
+/// has a WebSocket channel (eg/ Okx "instruments"), otherwise polled by a
+/// [`PollingStream`](crate::streams::polling::PollingStream) (eg/ Binance `exchangeInfo`).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct InstrumentStatuses;
+
+impl SubKind for InstrumentStatuses {
+    type Event = InstrumentStatus;
+}
+
+/// Normalised Barter [`InstrumentStatus`] model, ie/ the trading status of an instrument.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct InstrumentStatus {
+    pub status: TradingStatus,
+    /// Exchange specific status the [`TradingStatus`] was normalised from (eg/ "BREAK").
+    pub exchange_status: String,
+}
+
+impl InstrumentStatus {
+    /// Determine if the instrument is currently trading.
+    pub fn is_trading(&self) -> bool {
+        self.status.is_trading()
+    }
+}
+
+/// Normalised Barter [`TradingStatus`] of an [`InstrumentStatus`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradingStatus {
+    /// Newly listed instrument that has not started trading yet.
+    PreTrading,
+    Trading,
+    /// Listed instrument that is temporarily not trading, eg/ a trading halt or auction.
+    Halted,
+    /// Instrument that is (or is about to be) removed from the exchange, and will not resume
+    /// trading.
+    Delisted,
+    /// Status the exchange reported that is not recognised.
+    Unknown,
+}
+
+impl TradingStatus {
+    /// Determine if [`Self`] is [`TradingStatus::Trading`].
+    pub fn is_trading(&self) -> bool {
+        matches!(self, Self::Trading)
+    }
+}
+
+impl Display for TradingStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::PreTrading => "pre_trading",
+                Self::Trading => "trading",
+                Self::Halted => "halted",
+                Self::Delisted => "delisted",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
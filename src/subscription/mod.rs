@@ -11,15 +11,51 @@ use std::{
     fmt::{Debug, Display, Formatter},
 };
 
+/// Average price [`SubKind`] and the associated Barter output data model.
+pub mod average_price;
+
+/// Private account balance [`SubKind`] and the associated Barter output data model.
+pub mod balance;
+
 /// OrderBook [`SubKind`]s and the associated Barter output data models.
 pub mod book;
 
 /// Candle [`SubKind`] and the associated Barter output data model.
 pub mod candle;
 
+/// [`SubKind`]s that combine several kinds of market data routed through a single connection,
+/// yielding [`DataKind`](crate::event::DataKind) events in exchange order.
+pub mod combined;
+
+/// [`Everywhere`](everywhere::Everywhere) [`Subscription`]s to an instrument on every exchange
+/// with a [`StreamSelector`] for the [`SubKind`].
+pub mod everywhere;
+
+/// Private account fill [`SubKind`] and the associated Barter output data model.
+pub mod fill;
+
+/// Lenient parsing & normalised display of the
+/// [`InstrumentKind`] of a [`Subscription`], eg/ for config driven subscriptions.
+pub mod instrument_kind;
+
+/// Instrument trading status [`SubKind`] and the associated Barter output data model.
+pub mod instrument_status;
+
 /// Liquidation [`SubKind`] and the associated Barter output data model.
 pub mod liquidation;
 
+/// Open interest [`SubKind`] and the associated Barter output data model.
+pub mod open_interest;
+
+/// Private account order update [`SubKind`] and the associated Barter output data model.
+pub mod order;
+
+/// Private account position [`SubKind`] and the associated Barter output data model.
+pub mod position;
+
+/// Rolling window ticker [`SubKind`] and the associated Barter output data model.
+pub mod ticker;
+
 /// Public trade [`SubKind`] and the associated Barter output data model.
 pub mod trade;
 
@@ -31,6 +67,13 @@ where
     type Event: Debug;
 }
 
+/// Short name of a [`SubKind`] used to label logs & metrics, eg/ "PublicTrades".
+pub fn sub_kind_name<Kind>() -> &'static str {
+    let name = std::any::type_name::<Kind>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 /// Barter [`Subscription`] used to subscribe to a [`SubKind`] for a particular exchange
 /// [`Instrument`].
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
@@ -100,12 +143,27 @@ where
         let exchange = Exchange::ID;
 
         // Validate the Exchange supports the Subscription InstrumentKind
-        match self.instrument.kind {
-            InstrumentKind::Spot if exchange.supports_spot() => Ok(self),
-            InstrumentKind::FuturePerpetual if exchange.supports_futures() => Ok(self),
-            other => Err(SocketError::Unsupported {
+        if !exchange.supports(self.instrument.kind) {
+            return Err(SocketError::Unsupported {
+                entity: exchange.as_str(),
+                item: self.instrument.kind.to_string(),
+            });
+        }
+
+        // Validate the Exchange supports the Subscription Instrument
+        if let Some(reason) = Exchange::unsupported_instrument(&self.instrument) {
+            return Err(SocketError::Unsupported {
                 entity: exchange.as_str(),
-                item: other.to_string(),
+                item: reason,
+            });
+        }
+
+        // Validate the Exchange supports the Subscription SubKind parameters
+        match Exchange::unsupported(&self.kind) {
+            None => Ok(self),
+            Some(reason) => Err(SocketError::Unsupported {
+                entity: exchange.as_str(),
+                item: reason,
             }),
         }
     }
@@ -140,6 +198,7 @@ impl<T> FromIterator<(SubscriptionId, T)> for Map<T> {
 
 impl<T> Map<T> {
     /// Find the `T` associated with the provided [`SubscriptionId`].
+    #[allow(clippy::result_large_err)]
     pub fn find(&self, id: &SubscriptionId) -> Result<T, SocketError>
     where
         T: Clone,
@@ -151,6 +210,7 @@ impl<T> Map<T> {
     }
 
     /// Find the mutable reference to `T` associated with the provided [`SubscriptionId`].
+    #[allow(clippy::result_large_err)]
     pub fn find_mut(&mut self, id: &SubscriptionId) -> Result<&mut T, SocketError> {
         self.0
             .get_mut(id)
@@ -162,6 +222,18 @@ impl<T> Map<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sub_kind_name() {
+        assert_eq!(sub_kind_name::<trade::PublicTrades>(), "PublicTrades");
+        assert_eq!(sub_kind_name::<Vec<trade::PublicTrades>>(), "Vec");
+    }
+
+    #[cfg(all(
+        feature = "binance",
+        feature = "coinbase",
+        feature = "gateio",
+        feature = "okx"
+    ))]
     mod subscription {
         use super::*;
         use crate::exchange::coinbase::Coinbase;
@@ -172,9 +244,13 @@ mod tests {
             use super::*;
             use crate::exchange::binance::futures::BinanceFuturesUsd;
             use crate::exchange::binance::spot::BinanceSpot;
+            use crate::exchange::binance::us::BinanceUs;
             use crate::exchange::gateio::futures::GateioFuturesUsd;
             use crate::exchange::okx::Okx;
+            use crate::exchange::ExchangeId;
             use crate::subscription::book::OrderBooksL2;
+            use crate::subscription::candle::{Candles, Interval};
+            use crate::subscription::liquidation::Liquidations;
             use crate::subscription::trade::PublicTrades;
 
             #[test]
@@ -207,6 +283,21 @@ mod tests {
                 serde_json::from_str::<Subscription<BinanceSpot, PublicTrades>>(input).unwrap();
             }
 
+            #[test]
+            fn test_subscription_binance_us_public_trades() {
+                let input = r#"
+                {
+                    "exchange": "binance_us",
+                    "base": "btc",
+                    "quote": "usd",
+                    "instrument_type": "spot",
+                    "kind": "public_trades"
+                }
+                "#;
+
+                serde_json::from_str::<Subscription<BinanceUs, PublicTrades>>(input).unwrap();
+            }
+
             #[test]
             fn test_subscription_binance_futures_usd_order_books_l2() {
                 let input = r#"
@@ -238,6 +329,125 @@ mod tests {
                 serde_json::from_str::<Subscription<GateioFuturesUsd, PublicTrades>>(input)
                     .unwrap();
             }
+
+            #[test]
+            fn test_subscription_binance_futures_usd_liquidations() {
+                let input = r#"
+                {
+                    "exchange": "binance_futures_usd",
+                    "base": "btc",
+                    "quote": "usdt",
+                    "instrument_type": "future_perpetual",
+                    "kind": "liquidations"
+                }
+                "#;
+
+                serde_json::from_str::<Subscription<BinanceFuturesUsd, Liquidations>>(input)
+                    .unwrap();
+            }
+
+            #[test]
+            fn test_subscription_candles_with_interval() {
+                let input = r#"
+                {
+                    "exchange": "binance_spot",
+                    "base": "btc",
+                    "quote": "usdt",
+                    "instrument_type": "spot",
+                    "kind": { "candles": "15m" }
+                }
+                "#;
+
+                let actual = serde_json::from_str::<Subscription<BinanceSpot, Candles>>(input);
+                let expected = Subscription::from((
+                    BinanceSpot::default(),
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    Candles(Interval::M15),
+                ));
+
+                assert_eq!(actual.unwrap(), expected);
+            }
+
+            #[test]
+            fn test_subscription_exchange_id_matches_connector() {
+                // Subscriptions identified by ExchangeId share the serde representation of the
+                // Connector they identify, so either can be used in a configuration file
+                let connector = Subscription::from((
+                    BinanceSpot::default(),
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    Candles(Interval::M1),
+                ));
+                let serialised = serde_json::to_string(&connector).unwrap();
+
+                let actual =
+                    serde_json::from_str::<Subscription<ExchangeId, Candles>>(&serialised).unwrap();
+                let expected = Subscription::from((
+                    ExchangeId::BinanceSpot,
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    Candles(Interval::M1),
+                ));
+
+                assert_eq!(actual, expected);
+                assert_eq!(serde_json::to_string(&actual).unwrap(), serialised);
+            }
+
+            #[test]
+            fn test_stream_config_round_trip() {
+                struct TestCase {
+                    input: Vec<Vec<Subscription<BinanceSpot, Candles>>>,
+                }
+
+                let tests = vec![
+                    TestCase {
+                        // TC0: empty configuration
+                        input: vec![],
+                    },
+                    TestCase {
+                        // TC1: multiple connections w/ parameterised SubKinds
+                        input: vec![
+                            vec![
+                                Subscription::from((
+                                    BinanceSpot::default(),
+                                    "btc",
+                                    "usdt",
+                                    InstrumentKind::Spot,
+                                    Candles(Interval::M1),
+                                )),
+                                Subscription::from((
+                                    BinanceSpot::default(),
+                                    "eth",
+                                    "usdt",
+                                    InstrumentKind::Spot,
+                                    Candles(Interval::H4),
+                                )),
+                            ],
+                            vec![Subscription::from((
+                                BinanceSpot::default(),
+                                "btc",
+                                "usdt",
+                                InstrumentKind::Spot,
+                                Candles(Interval::W1),
+                            ))],
+                        ],
+                    },
+                ];
+
+                for (index, test) in tests.into_iter().enumerate() {
+                    let serialised = serde_json::to_string(&test.input).unwrap();
+                    let actual =
+                        serde_json::from_str::<Vec<Vec<Subscription<BinanceSpot, Candles>>>>(
+                            &serialised,
+                        )
+                        .unwrap();
+                    assert_eq!(actual, test.input, "TC{} failed", index);
+                }
+            }
         }
 
         #[test]
@@ -358,6 +568,46 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn test_validate_binance_us_public_trades() {
+            use crate::exchange::binance::us::BinanceUs;
+
+            struct TestCase {
+                input: Subscription<BinanceUs, PublicTrades>,
+                expected: bool,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: Valid BinanceUs Spot PublicTrades subscription
+                    input: Subscription::from((
+                        BinanceUs::default(),
+                        "btc",
+                        "usd",
+                        InstrumentKind::Spot,
+                        PublicTrades,
+                    )),
+                    expected: true,
+                },
+                TestCase {
+                    // TC1: Invalid BinanceUs FuturePerpetual PublicTrades subscription
+                    input: Subscription::from((
+                        BinanceUs::default(),
+                        "btc",
+                        "usd",
+                        InstrumentKind::FuturePerpetual,
+                        PublicTrades,
+                    )),
+                    expected: false,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = test.input.validate().is_ok();
+                assert_eq!(actual, test.expected, "TC{} failed", index);
+            }
+        }
     }
 
     mod instrument_map {
@@ -0,0 +1,23 @@
+use super::SubKind;
+use crate::num::Num;
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`OpenInterest`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+///
+/// Open interest is only available via exchange REST APIs, so it is polled by a
+/// [`PollingStream`](crate::streams::polling::PollingStream) rather than streamed.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct OpenInterests;
+
+impl SubKind for OpenInterests {
+    type Event = OpenInterest;
+}
+
+/// Normalised Barter [`OpenInterest`] model, ie/ the total number of outstanding contracts of a
+/// derivative instrument.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OpenInterest {
+    pub contracts: Num,
+}
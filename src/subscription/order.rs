@@ -0,0 +1,191 @@
+use super::SubKind;
+use crate::{num, num::Num};
+use barter_integration::model::Side;
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`OrderUpdate`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events for the orders of the logged in account.
+///
+/// Private, so it requires the exchange
+/// [`Credentials`](crate::subscriber::auth::Credentials).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct OrderUpdates;
+
+impl SubKind for OrderUpdates {
+    type Event = OrderUpdate;
+}
+
+/// Normalised Barter [`OrderUpdate`] model, ie/ the latest state of an order.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OrderUpdate {
+    pub order_id: String,
+    pub client_order_id: Option<String>,
+    pub status: OrderStatus,
+    pub side: Side,
+    /// Limit price of the order, `None` for market orders.
+    pub price: Option<Num>,
+    pub amount: Num,
+    pub filled_amount: Num,
+    /// Exchange provided reason the order was cancelled or rejected, if any.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl OrderUpdate {
+    /// Amount of the order that remains to be filled, or zero once it's finished.
+    pub fn remaining_amount(&self) -> Num {
+        if self.status.is_finished() || self.filled_amount >= self.amount {
+            num::zero()
+        } else {
+            self.amount - self.filled_amount
+        }
+    }
+}
+
+/// Normalised Barter [`OrderStatus`] of an [`OrderUpdate`].
+///
+/// Exchange statuses are mapped as follows:
+///
+/// | OrderStatus       | Binance                                   | Coinbase           | Kraken                               | Okx                            |
+/// |:-----------------:|:-----------------------------------------:|:------------------:|:------------------------------------:|:------------------------------:|
+/// | `Open`            | `NEW`, `PENDING_CANCEL`                   | `open`             | `pending`, `open` (nothing executed) | `live`                         |
+/// | `PartiallyFilled` | `PARTIALLY_FILLED`                        | `match`            | `pending`, `open` (some executed)    | `partially_filled`             |
+/// | `Filled`          | `FILLED`                                  | `done` (filled)    | `closed`                             | `filled`                       |
+/// | `Cancelled`       | `CANCELED`, `EXPIRED`, `EXPIRED_IN_MATCH` | `done` (canceled)  | `canceled`, `expired`                | `canceled`, `mmp_canceled`     |
+/// | `Rejected`        | `REJECTED`                                |                    |                                      |                                |
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    /// Determine if an order with [`Self`] is still working on the OrderBook.
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open | Self::PartiallyFilled)
+    }
+
+    /// Determine if an order with [`Self`] is finished, and will receive no further updates.
+    pub fn is_finished(&self) -> bool {
+        !self.is_open()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_order_update_serde() {
+        let update = OrderUpdate {
+            order_id: "1".to_string(),
+            client_order_id: Some("client".to_string()),
+            status: OrderStatus::PartiallyFilled,
+            side: Side::Buy,
+            price: Some(num!(100.0)),
+            amount: num!(2.0),
+            filled_amount: num!(0.5),
+            reason: None,
+        };
+
+        let serialised = serde_json::to_value(&update).unwrap();
+        assert_eq!(
+            serialised,
+            serde_json::json!({
+                "order_id": "1",
+                "client_order_id": "client",
+                "status": "partially_filled",
+                "side": "Buy",
+                "price": num!(100.0),
+                "amount": num!(2.0),
+                "filled_amount": num!(0.5),
+                "reason": null
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<OrderUpdate>(serialised).unwrap(),
+            update
+        );
+
+        // Updates serialised before the reason was added remain valid
+        let legacy = r#"{"order_id":"1","client_order_id":"client","status":"partially_filled","side":"Buy","price":100.0,"amount":2.0,"filled_amount":0.5}"#;
+        assert_eq!(serde_json::from_str::<OrderUpdate>(legacy).unwrap(), update);
+    }
+
+    #[test]
+    fn test_order_update_remaining_amount() {
+        struct TestCase {
+            status: OrderStatus,
+            filled_amount: Num,
+            expected: Num,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: open order with nothing filled
+                status: OrderStatus::Open,
+                filled_amount: num!(0.0),
+                expected: num!(2.0),
+            },
+            TestCase {
+                // TC1: partially filled order
+                status: OrderStatus::PartiallyFilled,
+                filled_amount: num!(0.5),
+                expected: num!(1.5),
+            },
+            TestCase {
+                // TC2: cancelled order has nothing remaining
+                status: OrderStatus::Cancelled,
+                filled_amount: num!(0.5),
+                expected: num!(0.0),
+            },
+            TestCase {
+                // TC3: overfilled open order has nothing remaining
+                status: OrderStatus::Open,
+                filled_amount: num!(3.0),
+                expected: num!(0.0),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let update = OrderUpdate {
+                order_id: "1".to_string(),
+                client_order_id: None,
+                status: test.status,
+                side: Side::Sell,
+                price: None,
+                amount: num!(2.0),
+                filled_amount: test.filled_amount,
+                reason: None,
+            };
+            assert_eq!(update.remaining_amount(), test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_order_status_serde() {
+        let statuses = [
+            (OrderStatus::Open, r#""open""#, true),
+            (OrderStatus::PartiallyFilled, r#""partially_filled""#, true),
+            (OrderStatus::Filled, r#""filled""#, false),
+            (OrderStatus::Cancelled, r#""cancelled""#, false),
+            (OrderStatus::Rejected, r#""rejected""#, false),
+        ];
+
+        for (status, expected, is_open) in statuses {
+            assert_eq!(serde_json::to_string(&status).unwrap(), expected);
+            assert_eq!(
+                serde_json::from_str::<OrderStatus>(expected).unwrap(),
+                status
+            );
+            assert_eq!(status.is_open(), is_open);
+            assert_eq!(status.is_finished(), !is_open);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use super::SubKind;
+use crate::num::Num;
+use barter_integration::model::Side;
+use barter_macro::{DeSubKind, SerSubKind};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields [`Position`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events for the derivative positions of the
+/// logged in account in the [`Subscription`](super::Subscription) instrument.
+///
+/// Private, so it requires the exchange
+/// [`Credentials`](crate::subscriber::auth::Credentials).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct Positions;
+
+impl SubKind for Positions {
+    type Event = Position;
+}
+
+/// Normalised Barter [`Position`] model, ie/ the latest state of a position in the
+/// [`MarketEvent`](crate::event::MarketEvent) instrument.
+///
+/// A closed position is yielded with a zero amount.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Position {
+    /// [`Side::Buy`] for a long position, [`Side::Sell`] for a short position.
+    pub side: Side,
+    /// Absolute size of the position, in contracts for exchanges that trade derivatives in
+    /// contracts.
+    pub amount: Num,
+    /// Average entry price of the position, `None` once it's closed.
+    pub entry_price: Option<Num>,
+    pub unrealised_pnl: Num,
+}
+
+impl Position {
+    /// Size of the position signed by it's [`Side`], ie/ positive if long & negative if short.
+    pub fn signed_amount(&self) -> Num {
+        match self.side {
+            Side::Buy => self.amount,
+            Side::Sell => -self.amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num;
+
+    #[test]
+    fn test_position_serde() {
+        let position = Position {
+            side: Side::Sell,
+            amount: num!(2.0),
+            entry_price: Some(num!(30000.0)),
+            unrealised_pnl: num!(-12.5),
+        };
+
+        let serialised = serde_json::to_value(position).unwrap();
+        assert_eq!(
+            serialised,
+            serde_json::json!({
+                "side": "Sell",
+                "amount": num!(2.0),
+                "entry_price": num!(30000.0),
+                "unrealised_pnl": num!(-12.5)
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Position>(serialised).unwrap(),
+            position
+        );
+        assert_eq!(position.signed_amount(), num!(-2.0));
+    }
+
+    #[test]
+    fn test_de_positions() {
+        let actual = serde_json::from_str::<Positions>(r#""positions""#).unwrap();
+        assert_eq!(actual, Positions);
+        assert_eq!(serde_json::to_string(&actual).unwrap(), r#""positions""#);
+    }
+}
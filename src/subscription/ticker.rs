@@ -0,0 +1,107 @@
+use super::SubKind;
+use crate::{num::Num, subscription::candle::Interval};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields rolling window
+/// [`Ticker`] [`MarketEvent<T>`](crate::event::MarketEvent) events.
+///
+/// Parameterised by the [`Interval`] size of the rolling window. Serialises the window along
+/// with the kind, eg/ `{"tickers": "4h"}`.
+///
+/// Note:
+/// Exchanges only support a subset of window sizes, unsupported windows are rejected by the
+/// [`StreamBuilder`](crate::streams::builder::StreamBuilder) before subscribing.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(from = "TickersKind", into = "TickersKind")]
+pub struct Tickers(pub Interval);
+
+impl SubKind for Tickers {
+    type Event = Ticker;
+}
+
+impl Display for Tickers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tickers_{}", self.0)
+    }
+}
+
+/// Serde representation of [`Tickers`] that tags the window [`Interval`] with the [`SubKind`]
+/// name.
+#[derive(Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TickersKind {
+    Tickers(Interval),
+}
+
+impl From<TickersKind> for Tickers {
+    fn from(kind: TickersKind) -> Self {
+        match kind {
+            TickersKind::Tickers(window) => Self(window),
+        }
+    }
+}
+
+impl From<Tickers> for TickersKind {
+    fn from(tickers: Tickers) -> Self {
+        Self::Tickers(tickers.0)
+    }
+}
+
+/// How often the [`Ticker`]s of an exchange offering both a real-time & a batched ticker channel
+/// are updated, see
+/// [`StreamBuilder::ticker_updates`](crate::streams::builder::StreamBuilder::ticker_updates).
+///
+/// Exchanges with a single ticker channel ignore it.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TickerUpdates {
+    /// Periodic batched updates (eg/ every 5 seconds on the Coinbase `ticker_batch` channel).
+    #[default]
+    Batched,
+    /// Real-time updates on every trade (eg/ the Coinbase `ticker` channel).
+    Realtime,
+}
+
+/// Normalised Barter rolling window [`Ticker`] model.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Ticker {
+    pub window: Interval,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    pub open: Num,
+    pub high: Num,
+    pub low: Num,
+    pub close: Num,
+    /// Volume weighted average price of the window, if the exchange includes it.
+    #[serde(default)]
+    pub vwap: Option<Num>,
+    pub volume: Num,
+    /// Quote asset volume of the window, if the exchange includes it.
+    #[serde(default)]
+    pub quote_volume: Option<Num>,
+    pub price_change: Num,
+    pub price_change_percent: Num,
+    /// Number of trades in the window, if the exchange includes it.
+    #[serde(default)]
+    pub trade_count: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de_tickers() {
+        let actual = serde_json::from_str::<Tickers>(r#"{"tickers": "4h"}"#).unwrap();
+        assert_eq!(actual, Tickers(Interval::H4));
+        assert_eq!(actual.to_string(), "tickers_4h");
+        assert_eq!(
+            serde_json::to_string(&actual).unwrap(),
+            r#"{"tickers":"4h"}"#
+        );
+    }
+}
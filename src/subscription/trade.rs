@@ -1,4 +1,5 @@
 use super::SubKind;
+use crate::num::Num;
 use barter_integration::model::Side;
 use barter_macro::{DeSubKind, SerSubKind};
 use serde::{Deserialize, Serialize};
@@ -12,11 +13,44 @@ impl SubKind for PublicTrades {
     type Event = PublicTrade;
 }
 
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields every individual
+/// [`PublicTrade`] [`MarketEvent<T>`](crate::event::MarketEvent) event, for exchanges whose
+/// standard trades channel aggregates fills.
+///
+/// ### Supported Exchanges
+/// - [`Okx`](crate::exchange::okx::Okx) "trades-all" channel, see
+///   [`OkxChannel::TRADES_ALL`](crate::exchange::okx::channel::OkxChannel::TRADES_ALL).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct PublicTradesAll;
+
+impl SubKind for PublicTradesAll {
+    type Event = PublicTrade;
+}
+
+/// Barter [`Subscription`](super::Subscription) [`SubKind`] that yields aggregated
+/// [`PublicTrade`] [`MarketEvent<T>`](crate::event::MarketEvent) events, for exchanges whose
+/// standard trades channel pushes every individual trade.
+///
+/// Each aggregated [`PublicTrade`] combines the consecutive fills of the same taker order at
+/// the same price, so the [`PublicTrade::id`] identifies the aggregate rather than a single
+/// trade. Prefer [`PublicTrades`] when complete trade flow is required (eg/ trade counts), and
+/// [`PublicTradesAggregated`] when only volume at price matters, since it's a lower volume feed.
+///
+/// ### Supported Exchanges
+/// - [`Binance`](crate::exchange::binance::Binance) "@aggTrade" channel, see
+///   [`BinanceChannel::AGG_TRADES`](crate::exchange::binance::channel::BinanceChannel::AGG_TRADES).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, DeSubKind, SerSubKind)]
+pub struct PublicTradesAggregated;
+
+impl SubKind for PublicTradesAggregated {
+    type Event = PublicTrade;
+}
+
 /// Normalised Barter [`PublicTrade`] model.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct PublicTrade {
     pub id: String,
-    pub price: f64,
-    pub amount: f64,
+    pub price: Num,
+    pub amount: Num,
     pub side: Side,
 }
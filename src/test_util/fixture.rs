@@ -0,0 +1,273 @@
+use crate::{
+    error::DataError,
+    event::MarketIter,
+    exchange::{Connector, ExchangeId},
+    streams::config::StreamConfig,
+    subscriber::Subscriber,
+    subscription::{SubKind, Subscription},
+    Identifier,
+};
+use barter_integration::{
+    error::SocketError,
+    model::{Instrument, SubscriptionId},
+    protocol::websocket::WsMessage,
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info};
+
+/// Suffix of the committed expected normalised output of a fixture, eg/ "0001.expected.json" is
+/// the expected output of the raw "0001.json" fixture.
+pub const FIXTURE_EXPECTED_SUFFIX: &str = ".expected.json";
+
+/// Environment variable that, when set, makes [`assert_fixtures`] (re)write the expected output
+/// of every fixture rather than comparing against it.
+pub const UPDATE_FIXTURES_ENV: &str = "UPDATE_FIXTURES";
+
+/// Directory of the raw exchange message fixtures of an exchange channel, ie/
+/// `{root}/{exchange}/{channel}`.
+///
+/// eg/ `tests/fixtures/binance_spot/public_trades`
+pub fn fixture_dir<P>(root: P, exchange: ExchangeId, channel: &str) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    root.as_ref().join(exchange.as_str()).join(channel)
+}
+
+/// Deserialise the provided raw exchange message as the `Model` & normalise it into the
+/// [`MarketIter<Event>`] the production [`StatelessTransformer`] would yield, returning it's
+/// JSON snapshot.
+///
+/// The message is deserialised by the production JSON backend (see
+/// [`from_bytes`](crate::parser::from_bytes)), so the fixtures also guard the `simd-json`
+/// feature.
+///
+/// The snapshot contains the [`SubscriptionId`] of the message & every normalised event (or
+/// error). The message is normalised as if it was received at the Unix epoch, so times that are
+/// not part of the message (eg/ `received_time`) are deterministic.
+///
+/// [`StatelessTransformer`]: crate::transformer::stateless::StatelessTransformer
+pub fn normalise<Model, Event>(
+    exchange: ExchangeId,
+    instrument: &Instrument,
+    payload: &str,
+) -> Result<Value, serde_json::Error>
+where
+    Model: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
+    MarketIter<Event>: From<(ExchangeId, Instrument, DateTime<Utc>, Model)>,
+    Event: Serialize,
+{
+    let model = crate::parser::from_bytes::<Model>(&mut payload.as_bytes().to_vec())?;
+    let subscription_id = model.id();
+
+    let events = MarketIter::<Event>::from((
+        exchange,
+        instrument.clone(),
+        DateTime::<Utc>::UNIX_EPOCH,
+        model,
+    ))
+    .0
+    .into_iter()
+    .map(|event| match event {
+        Ok(event) => serde_json::to_value(event),
+        Err(error) => Ok(json!({ "error": error.to_string() })),
+    })
+    .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+    Ok(json!({
+        "subscription_id": subscription_id,
+        "events": events,
+    }))
+}
+
+/// Assert every raw exchange message fixture in the provided directory deserialises as the
+/// `Model`, and that it's normalised snapshot (see [`normalise`]) equals the committed
+/// expected output, returning the number of fixtures checked.
+///
+/// Fixtures are the `*.json` files of the directory, each with the expected output alongside it
+/// in the `*.expected.json` file of the same name. Set the [`UPDATE_FIXTURES_ENV`] environment
+/// variable to (re)write the expected outputs after an intentional change, or after adding
+/// fixtures, and review the diff.
+///
+/// ### Panics
+/// Panics if the directory contains no fixtures, if any fixture fails to deserialise, or if a
+/// normalised snapshot is missing or differs from it's expected output.
+pub fn assert_fixtures<Model, Event, P>(
+    dir: P,
+    exchange: ExchangeId,
+    instrument: &Instrument,
+) -> usize
+where
+    Model: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
+    MarketIter<Event>: From<(ExchangeId, Instrument, DateTime<Utc>, Model)>,
+    Event: Serialize,
+    P: AsRef<Path>,
+{
+    let dir = dir.as_ref();
+    let update = std::env::var_os(UPDATE_FIXTURES_ENV).is_some();
+
+    let fixtures = fixtures(dir)
+        .unwrap_or_else(|error| panic!("failed to read fixture dir {}: {error}", dir.display()));
+    assert!(
+        !fixtures.is_empty(),
+        "fixture dir {} contains no fixtures",
+        dir.display()
+    );
+
+    for fixture in &fixtures {
+        let payload = fs::read_to_string(fixture)
+            .unwrap_or_else(|error| panic!("failed to read {}: {error}", fixture.display()));
+
+        let actual = normalise::<Model, Event>(exchange, instrument, &payload)
+            .unwrap_or_else(|error| panic!("{} failed to deserialise: {error}", fixture.display()));
+
+        let expected_path = expected_path(fixture);
+        if update {
+            let mut output = serde_json::to_string_pretty(&actual).unwrap();
+            output.push('\n');
+            fs::write(&expected_path, output).unwrap_or_else(|error| {
+                panic!("failed to write {}: {error}", expected_path.display())
+            });
+            continue;
+        }
+
+        let expected = fs::read_to_string(&expected_path).unwrap_or_else(|error| {
+            panic!(
+                "failed to read {} ({error}), run with {UPDATE_FIXTURES_ENV}=1 to generate it",
+                expected_path.display()
+            )
+        });
+        let expected = serde_json::from_str::<Value>(&expected)
+            .unwrap_or_else(|error| panic!("invalid {}: {error}", expected_path.display()));
+
+        assert_eq!(
+            actual,
+            expected,
+            "{} normalised output changed, if intentional run with {UPDATE_FIXTURES_ENV}=1 & \
+            review the diff",
+            fixture.display()
+        );
+    }
+
+    fixtures.len()
+}
+
+/// Connect & subscribe to the provided [`Subscription`]s using the production [`Connector`],
+/// writing the next `count` raw text frames that deserialise as the `Model` to the fixture
+/// layout (see [`fixture_dir`]), returning the paths written.
+///
+/// Frames that don't deserialise as the `Model` (eg/ heartbeats) are skipped. Each fixture is
+/// named after the number of existing fixtures in the directory, so captures accumulate rather
+/// than overwrite. Used by the `capture_fixtures` example to grow the corpus.
+pub async fn capture_fixtures<Exchange, Kind, Model, P>(
+    subscriptions: &[Subscription<Exchange, Kind>],
+    root: P,
+    channel: &str,
+    count: usize,
+) -> Result<Vec<PathBuf>, DataError>
+where
+    Exchange: Connector + Send + Sync,
+    Kind: SubKind + Send + Sync,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+    Model: for<'de> Deserialize<'de>,
+    P: AsRef<Path>,
+{
+    let dir = fixture_dir(root, Exchange::ID, channel);
+    fs::create_dir_all(&dir)?;
+    let mut next = fixtures(&dir)?.len();
+
+    // Connect & subscribe, replaying any messages buffered whilst validating
+    let (websocket, _, buffer) =
+        Exchange::Subscriber::subscribe(subscriptions, &StreamConfig::default()).await?;
+    let mut frames = buffer.into_stream(websocket);
+
+    let mut written = Vec::with_capacity(count);
+    while written.len() < count {
+        let frame = match frames.next().await {
+            Some(Ok((_, WsMessage::Text(frame)))) => frame,
+            Some(Ok(_)) => continue,
+            Some(Err(error)) => return Err(SocketError::WebSocket(error).into()),
+            None => return Err(SocketError::Terminated("capture".to_string()).into()),
+        };
+
+        if serde_json::from_str::<Model>(&frame).is_err() {
+            debug!(exchange = %Exchange::ID, %frame, "skipping frame that is not a fixture model");
+            continue;
+        }
+
+        let path = dir.join(format!("{next:04}.json"));
+        fs::write(&path, format!("{frame}\n"))?;
+        info!(exchange = %Exchange::ID, path = %path.display(), "captured fixture");
+
+        written.push(path);
+        next += 1;
+    }
+
+    Ok(written)
+}
+
+/// Sorted raw exchange message fixtures of the provided directory, ie/ every `*.json` file that
+/// is not an expected output.
+fn fixtures(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut fixtures = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.ends_with(".json") && !name.ends_with(FIXTURE_EXPECTED_SUFFIX)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    fixtures.sort();
+    Ok(fixtures)
+}
+
+/// Path of the expected normalised output of the provided fixture, eg/ "0001.expected.json".
+fn expected_path(fixture: &Path) -> PathBuf {
+    fixture.with_extension(FIXTURE_EXPECTED_SUFFIX.trim_start_matches('.'))
+}
+
+#[cfg(all(test, feature = "binance"))]
+mod tests {
+    use super::*;
+    use crate::exchange::binance::trade::BinanceTrade;
+    use crate::subscription::trade::PublicTrade;
+    use barter_integration::model::InstrumentKind;
+
+    #[test]
+    fn test_normalise() {
+        let actual = normalise::<BinanceTrade, PublicTrade>(
+            ExchangeId::BinanceSpot,
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#,
+        )
+        .unwrap();
+
+        assert_eq!(actual["subscription_id"], json!("@trade|ETHUSDT"));
+        let events = actual["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["received_time"], json!("1970-01-01T00:00:00Z"));
+        assert_eq!(events[0]["kind"]["id"], json!("1000000000"));
+    }
+
+    #[test]
+    fn test_expected_path() {
+        assert_eq!(
+            expected_path(Path::new(
+                "tests/fixtures/binance_spot/public_trades/0001.json"
+            )),
+            PathBuf::from("tests/fixtures/binance_spot/public_trades/0001.expected.json")
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use crate::error::DataError;
+use futures::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+use tracing::{debug, warn};
+
+/// Captured exchange message fixture corpus harness, asserting every fixture deserialises &
+/// normalises into it's committed expected output, and capturing new fixtures from live
+/// exchanges.
+pub mod fixture;
+
+/// [`TradeFixture`](trade::TradeFixture) builder of market events & [`run_adapter`](trade::run_adapter)
+/// harness for testing stream adapters & sinks.
+pub mod trade;
+
+/// Maximum [`Duration`] a [`MockExchangeServer`] connection waits for the client frame of a
+/// [`MockStep::Expect`], before recording a failure & dropping the connection.
+pub const MOCK_EXPECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Client frame expected by a [`MockStep::Expect`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expect {
+    /// Any text frame.
+    Any,
+    /// Text frame equal to the provided text.
+    Text(String),
+    /// Text frame containing the provided text.
+    Contains(String),
+    /// JSON text frame equal to the provided JSON.
+    Json(Value),
+    /// JSON text frame containing every field of the provided JSON, ignoring any other fields
+    /// (eg/ the Gateio subscription "time").
+    JsonSubset(Value),
+}
+
+impl Expect {
+    /// Determine if the provided client text frame satisfies [`Self`].
+    pub fn matches(&self, frame: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Text(text) => frame == text,
+            Self::Contains(text) => frame.contains(text.as_str()),
+            Self::Json(expected) => {
+                serde_json::from_str::<Value>(frame).is_ok_and(|actual| &actual == expected)
+            }
+            Self::JsonSubset(expected) => serde_json::from_str::<Value>(frame)
+                .is_ok_and(|actual| is_json_subset(expected, &actual)),
+        }
+    }
+}
+
+/// Determine if every field of the expected JSON object is present & equal in the actual JSON,
+/// recursing into nested objects. Any other JSON values must be equal.
+fn is_json_subset(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            expected.iter().all(|(key, expected)| {
+                actual
+                    .get(key)
+                    .is_some_and(|actual| is_json_subset(expected, actual))
+            })
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+/// Step of a [`MockScript`] played to a client connection of a [`MockExchangeServer`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum MockStep {
+    /// Wait for the next client text frame, recording a failure if it does not satisfy the
+    /// [`Expect`] (eg/ an unexpected subscription payload).
+    Expect(Expect),
+    /// Send a text frame, eg/ a subscription ack, a captured market data payload, or a malformed
+    /// payload.
+    Send(String),
+    /// Send a binary frame.
+    SendBinary(Vec<u8>),
+    /// Wait for the [`Duration`] before the next step, eg/ to delay a subscription ack.
+    Delay(Duration),
+    /// Close the connection with a WebSocket close frame.
+    Close,
+    /// Drop the connection without a WebSocket close handshake, simulating a mid-stream
+    /// disconnect.
+    Disconnect,
+}
+
+/// Scripted sequence of [`MockStep`]s played to one client connection of a
+/// [`MockExchangeServer`].
+///
+/// Once every step has been played (without a [`MockStep::Close`] or [`MockStep::Disconnect`]),
+/// the connection stays open & keeps recording client frames until the client disconnects.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct MockScript {
+    pub steps: Vec<MockStep>,
+}
+
+impl MockScript {
+    /// Construct a new empty [`Self`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for the next client text frame, expecting it to satisfy the provided [`Expect`].
+    pub fn expect(mut self, expect: Expect) -> Self {
+        self.steps.push(MockStep::Expect(expect));
+        self
+    }
+
+    /// Wait for the next client text frame, expecting it to be equal to the provided JSON.
+    pub fn expect_json(self, json: Value) -> Self {
+        self.expect(Expect::Json(json))
+    }
+
+    /// Send the provided text frame.
+    pub fn send<S>(mut self, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.steps.push(MockStep::Send(text.into()));
+        self
+    }
+
+    /// Send the provided binary frame.
+    pub fn send_binary(mut self, bytes: Vec<u8>) -> Self {
+        self.steps.push(MockStep::SendBinary(bytes));
+        self
+    }
+
+    /// Wait for the provided [`Duration`] before the next step.
+    pub fn delay(mut self, duration: Duration) -> Self {
+        self.steps.push(MockStep::Delay(duration));
+        self
+    }
+
+    /// Close the connection with a WebSocket close frame.
+    pub fn close(mut self) -> Self {
+        self.steps.push(MockStep::Close);
+        self
+    }
+
+    /// Drop the connection without a WebSocket close handshake.
+    pub fn disconnect(mut self) -> Self {
+        self.steps.push(MockStep::Disconnect);
+        self
+    }
+}
+
+/// Observations of a [`MockExchangeServer`], shared with it's connection tasks.
+#[derive(Debug, Default)]
+struct MockState {
+    scripts: VecDeque<MockScript>,
+    connections: usize,
+    disconnections: usize,
+    close_frames: usize,
+    received: Vec<String>,
+    failures: Vec<String>,
+}
+
+/// Mock exchange WebSocket server for integration testing the full subscribe -> validate ->
+/// stream pipeline of the production [`Connector`](crate::exchange::Connector)s without hitting
+/// live exchanges.
+///
+/// Binds an ephemeral local port & plays the next [`MockScript`] to each accepted connection, in
+/// the order provided. Connections accepted after every [`MockScript`] has been played receive
+/// an empty [`MockScript`]. Point a connector at it via
+/// [`StreamBuilder::with_url`](crate::streams::builder::StreamBuilder::with_url) using
+/// [`MockExchangeServer::url`].
+///
+/// Available with the `test-util` feature.
+///
+/// ```rust,ignore
+/// let server = MockExchangeServer::bind([MockScript::new()
+///     .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+///     .send(r#"{"result":null,"id":1}"#)
+///     .send(captured_trade_payload)])
+/// .await?;
+///
+/// let streams = Streams::<PublicTrades>::builder()
+///     .subscribe([(BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+///     .with_url(ExchangeId::BinanceSpot, server.url())
+///     .init()
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct MockExchangeServer {
+    local_addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    acceptor: JoinHandle<()>,
+}
+
+impl Drop for MockExchangeServer {
+    fn drop(&mut self) {
+        self.acceptor.abort();
+    }
+}
+
+impl MockExchangeServer {
+    /// Bind a [`Self`] to an ephemeral local port & start accepting connections, playing the
+    /// provided [`MockScript`]s to each connection in order.
+    pub async fn bind<Scripts>(scripts: Scripts) -> Result<Self, DataError>
+    where
+        Scripts: IntoIterator<Item = MockScript>,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let local_addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState {
+            scripts: scripts.into_iter().collect(),
+            ..MockState::default()
+        }));
+
+        let acceptor = tokio::spawn(accept_connections(listener, Arc::clone(&state)));
+        debug!(%local_addr, "MockExchangeServer listening");
+
+        Ok(Self {
+            local_addr,
+            state,
+            acceptor,
+        })
+    }
+
+    /// Local address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// `ws` url of the server, eg/ for
+    /// [`StreamBuilder::with_url`](crate::streams::builder::StreamBuilder::with_url).
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.local_addr)
+    }
+
+    /// Number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.lock().connections
+    }
+
+    /// Number of accepted connections that have since ended, eg/ closed by the client.
+    pub fn disconnections(&self) -> usize {
+        self.lock().disconnections
+    }
+
+    /// Number of WebSocket close frames received from clients so far, across all connections.
+    ///
+    /// Unlike [`Self::disconnections`], connections dropped without a close handshake are not
+    /// counted.
+    pub fn close_frames(&self) -> usize {
+        self.lock().close_frames
+    }
+
+    /// Every client text frame received so far, across all connections.
+    pub fn received(&self) -> Vec<String> {
+        self.lock().received.clone()
+    }
+
+    /// Every unsatisfied [`MockStep::Expect`] so far, across all connections.
+    pub fn failures(&self) -> Vec<String> {
+        self.lock().failures.clone()
+    }
+
+    /// Panic if any [`MockStep::Expect`] has not been satisfied so far.
+    pub fn assert_expectations(&self) {
+        let failures = self.failures();
+        assert!(
+            failures.is_empty(),
+            "MockExchangeServer expectations failed: {failures:#?}"
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        lock(&self.state)
+    }
+}
+
+async fn accept_connections(listener: TcpListener, state: Arc<Mutex<MockState>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, client)) => {
+                // Send scripted frames immediately rather than batching them until acknowledged,
+                // so scripts also play out promptly under paused tokio time
+                if let Err(error) = stream.set_nodelay(true) {
+                    warn!(%error, "MockExchangeServer failed to set TCP_NODELAY");
+                }
+
+                let script = {
+                    let mut state = lock(&state);
+                    state.connections += 1;
+                    state.scripts.pop_front().unwrap_or_default()
+                };
+
+                let state = Arc::clone(&state);
+                tokio::spawn(async move {
+                    debug!(%client, "MockExchangeServer client connected");
+                    serve_connection(stream, script, Arc::clone(&state)).await;
+                    lock(&state).disconnections += 1;
+                    debug!(%client, "MockExchangeServer client disconnected");
+                });
+            }
+            Err(error) => warn!(%error, "MockExchangeServer failed to accept connection"),
+        }
+    }
+}
+
+async fn serve_connection(stream: TcpStream, script: MockScript, state: Arc<Mutex<MockState>>) {
+    let mut websocket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(websocket) => websocket,
+        Err(error) => {
+            debug!(%error, "MockExchangeServer client WebSocket handshake failed");
+            return;
+        }
+    };
+
+    for step in script.steps {
+        let sent = match step {
+            MockStep::Expect(expect) => {
+                let frame =
+                    tokio::time::timeout(MOCK_EXPECT_TIMEOUT, next_text(&mut websocket, &state))
+                        .await;
+
+                let mut state = lock(&state);
+                match frame {
+                    Ok(Some(frame)) => {
+                        if !expect.matches(&frame) {
+                            state
+                                .failures
+                                .push(format!("expected {expect:?}, received: {frame}"));
+                        }
+                        state.received.push(frame);
+                        continue;
+                    }
+                    Ok(None) => {
+                        state
+                            .failures
+                            .push(format!("expected {expect:?}, client disconnected"));
+                        return;
+                    }
+                    Err(_) => {
+                        state.failures.push(format!(
+                            "expected {expect:?}, timed out after {MOCK_EXPECT_TIMEOUT:?}"
+                        ));
+                        return;
+                    }
+                }
+            }
+            MockStep::Send(text) => websocket.send(Message::Text(text)).await,
+            MockStep::SendBinary(bytes) => websocket.send(Message::Binary(bytes)).await,
+            MockStep::Delay(duration) => {
+                tokio::time::sleep(duration).await;
+                continue;
+            }
+            MockStep::Close => {
+                let _ = websocket.close(None).await;
+                break;
+            }
+            MockStep::Disconnect => return,
+        };
+
+        if let Err(error) = sent {
+            debug!(%error, "MockExchangeServer failed to write to client");
+            return;
+        }
+    }
+
+    // Keep recording client frames (& responding to pings) until the client disconnects
+    while let Some(frame) = next_text(&mut websocket, &state).await {
+        lock(&state).received.push(frame);
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> MutexGuard<'_, MockState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Receive the next client text frame, or `None` once the client has disconnected, recording
+/// any client close frame. Other control frames are handled by tungstenite & skipped.
+async fn next_text(
+    websocket: &mut WebSocketStream<TcpStream>,
+    state: &Mutex<MockState>,
+) -> Option<String> {
+    loop {
+        match websocket.next().await? {
+            Ok(Message::Text(text)) => return Some(text),
+            Ok(Message::Binary(bytes)) => {
+                return Some(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            Ok(Message::Close(_)) => {
+                lock(state).close_frames += 1;
+                return None;
+            }
+            Err(_) => return None,
+            Ok(_) => continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_expect_matches() {
+        struct TestCase {
+            expect: Expect,
+            input: &'static str,
+            expected: bool,
+        }
+
+        let tests = vec![
+            TestCase {
+                // TC0: any frame
+                expect: Expect::Any,
+                input: "not json",
+                expected: true,
+            },
+            TestCase {
+                // TC1: JSON equal regardless of formatting & field order
+                expect: Expect::Json(json!({"method": "SUBSCRIBE", "id": 1})),
+                input: r#"{ "id": 1, "method": "SUBSCRIBE" }"#,
+                expected: true,
+            },
+            TestCase {
+                // TC2: JSON with an additional field is not equal
+                expect: Expect::Json(json!({"method": "SUBSCRIBE"})),
+                input: r#"{"method":"SUBSCRIBE","id":1}"#,
+                expected: false,
+            },
+            TestCase {
+                // TC3: JSON subset ignores additional fields, including nested
+                expect: Expect::JsonSubset(json!({"event": "subscribe", "payload": ["BTC_USDT"]})),
+                input: r#"{"time":1606292218,"channel":"spot.trades","event":"subscribe","payload":["BTC_USDT"]}"#,
+                expected: true,
+            },
+            TestCase {
+                // TC4: JSON subset with a different value
+                expect: Expect::JsonSubset(json!({"payload": ["BTC_USDT"]})),
+                input: r#"{"payload":["ETH_USDT"]}"#,
+                expected: false,
+            },
+            TestCase {
+                // TC5: malformed JSON
+                expect: Expect::JsonSubset(json!({})),
+                input: "{",
+                expected: false,
+            },
+            TestCase {
+                // TC6: contains text
+                expect: Expect::Contains("ping".to_string()),
+                input: r#"{"op":"ping"}"#,
+                expected: true,
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let actual = test.expect.matches(test.input);
+            assert_eq!(actual, test.expected, "TC{} failed", index);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{event::MarketEvent, num::Num, subscription::trade::PublicTrade};
+use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+
+/// Default exchange time of a [`TradeFixture`] trade at offset zero, in Unix milliseconds.
+pub const DEFAULT_TRADE_FIXTURE_BASE_MS: i64 = 1_700_000_000_000;
+
+/// Builder of [`MarketEvent`] fixtures for testing stream adapters (eg/
+/// [`RollingVwap`](crate::streams::adapter::vwap::RollingVwap)) & sinks, each event timed by it's
+/// millisecond offset from the base exchange time.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TradeFixture {
+    /// Exchange time of an event at offset zero, in Unix milliseconds.
+    pub base_ms: i64,
+    /// Milliseconds between the exchange & received time of every event.
+    pub latency_ms: i64,
+    pub exchange: Exchange,
+    /// Quote asset of every event [`Instrument`].
+    pub quote: String,
+    /// [`InstrumentKind`] of every event [`Instrument`].
+    pub instrument_kind: InstrumentKind,
+}
+
+impl Default for TradeFixture {
+    fn default() -> Self {
+        Self {
+            base_ms: DEFAULT_TRADE_FIXTURE_BASE_MS,
+            latency_ms: 0,
+            exchange: Exchange::from("binance_spot"),
+            quote: "usdt".to_string(),
+            instrument_kind: InstrumentKind::Spot,
+        }
+    }
+}
+
+impl TradeFixture {
+    /// Construct a new [`Self`] of "binance_spot" "{base}_usdt" spot events at offsets from
+    /// [`DEFAULT_TRADE_FIXTURE_BASE_MS`], received at their exchange time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the exchange time of an event at offset zero, in Unix milliseconds.
+    pub fn with_base_ms(mut self, base_ms: i64) -> Self {
+        self.base_ms = base_ms;
+        self
+    }
+
+    /// Set the milliseconds between the exchange & received time of every event.
+    pub fn with_latency_ms(mut self, latency_ms: i64) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+
+    /// Set the [`Exchange`] & quote asset of every event.
+    pub fn with_market(mut self, exchange: &'static str, quote: &str) -> Self {
+        self.exchange = Exchange::from(exchange);
+        self.quote = quote.to_string();
+        self
+    }
+
+    /// Set the [`InstrumentKind`] of every event.
+    pub fn with_instrument_kind(mut self, instrument_kind: InstrumentKind) -> Self {
+        self.instrument_kind = instrument_kind;
+        self
+    }
+
+    /// Exchange time at the provided millisecond offset from the base exchange time.
+    pub fn time(&self, offset_ms: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.base_ms + offset_ms).unwrap()
+    }
+
+    /// [`Instrument`] of the provided base asset.
+    pub fn instrument(&self, base: &str) -> Instrument {
+        Instrument::from((base, self.quote.as_str(), self.instrument_kind))
+    }
+
+    /// Construct a [`MarketEvent<T>`](MarketEvent) of the provided base asset, exchanged at the
+    /// provided millisecond offset.
+    pub fn event<T>(&self, offset_ms: i64, base: &str, kind: T) -> MarketEvent<T> {
+        MarketEvent {
+            exchange_time: self.time(offset_ms),
+            raw_exchange_time: None,
+            received_time: self.time(offset_ms + self.latency_ms),
+            exchange: self.exchange.clone(),
+            instrument: self.instrument(base),
+            kind,
+            meta: (),
+        }
+    }
+
+    /// Construct a [`MarketEvent<PublicTrade>`](MarketEvent) of the provided base asset,
+    /// exchanged at the provided millisecond offset, which is also it's trade id.
+    pub fn trade(
+        &self,
+        offset_ms: i64,
+        base: &str,
+        price: Num,
+        amount: Num,
+        side: Side,
+    ) -> MarketEvent<PublicTrade> {
+        self.event(
+            offset_ms,
+            base,
+            PublicTrade {
+                id: offset_ms.to_string(),
+                price,
+                amount,
+                side,
+            },
+        )
+    }
+}
+
+/// Apply the provided stream adapter to a stream of the input
+/// [`MarketEvent<T>`](MarketEvent)s, collecting every item it yields once the inputs are
+/// exhausted.
+///
+/// eg/ `run_adapter(trades, |trades| trades.vwap(config)).await`
+pub async fn run_adapter<T, Adapter>(
+    inputs: Vec<MarketEvent<T>>,
+    adapter: impl FnOnce(futures::stream::Iter<std::vec::IntoIter<MarketEvent<T>>>) -> Adapter,
+) -> Vec<Adapter::Item>
+where
+    Adapter: Stream,
+{
+    adapter(futures::stream::iter(inputs)).collect().await
+}
@@ -1,20 +1,30 @@
 use crate::{
     error::DataError,
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketEvents, MarketIter},
     exchange::Connector,
+    streams::config::StreamConfig,
     subscription::{book::OrderBook, Map, SubKind},
     transformer::ExchangeTransformer,
     Identifier,
 };
 use async_trait::async_trait;
 use barter_integration::{
+    error::SocketError,
     model::{Instrument, SubscriptionId},
     protocol::websocket::WsMessage,
     Transformer,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
-use tokio::sync::mpsc;
+use smallvec::smallvec;
+use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, info, warn};
+
+/// Maximum number of updates buffered for an [`InstrumentOrderBook`] whilst it re-synchronises
+/// (see [`OrderBookUpdater::RESYNC_INSTRUMENT`]), beyond which the whole connection is
+/// re-initialised instead.
+pub const MAX_RESYNC_BUFFERED_UPDATES: usize = 10_000;
 
 /// Defines how to apply a [`Self::Update`] to an [`Self::OrderBook`].
 #[async_trait]
@@ -25,17 +35,30 @@ where
     type OrderBook;
     type Update;
 
+    /// Determine if an [`InstrumentOrderBook`] whose update fails an integrity check (ie/ a
+    /// terminal [`DataError`] such as a sequence gap) can be re-initialised in isolation via
+    /// [`OrderBookUpdater::init`] (eg/ by re-fetching it's HTTP snapshot), leaving the other
+    /// OrderBooks of the connection undisturbed.
+    ///
+    /// Whilst re-initialising, updates to the [`InstrumentOrderBook`] are buffered and then
+    /// replayed onto the new [`InstrumentOrderBook`]. Defaults to `false`, where an integrity
+    /// failure re-initialises the whole connection.
+    const RESYNC_INSTRUMENT: bool = false;
+
     /// Initialises the [`InstrumentOrderBook`] for the provided [`Instrument`]. This often requires
-    /// a HTTP call to receive a starting [`OrderBook`] snapshot.
+    /// a HTTP call, using the [`StreamConfig`] of the connection, to receive a starting
+    /// [`OrderBook`] snapshot.
     async fn init<Exchange, Kind>(
         ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
         instrument: Instrument,
+        config: &StreamConfig,
     ) -> Result<InstrumentOrderBook<Self>, DataError>
     where
         Exchange: Send,
         Kind: Send;
 
     /// Apply the [`Self::Update`] to the provided mutable [`Self::OrderBook`].
+    #[allow(clippy::result_large_err)]
     fn update(
         &mut self,
         book: &mut Self::OrderBook,
@@ -55,24 +78,65 @@ pub struct InstrumentOrderBook<Updater> {
 /// Standard generic [`ExchangeTransformer`] to translate exchange specific OrderBook types into
 /// normalised Barter OrderBook types. Requires an exchange specific [`OrderBookUpdater`]
 /// implementation.
-#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
-pub struct MultiBookTransformer<Exchange, Kind, Updater> {
+///
+/// If the [`OrderBookUpdater::RESYNC_INSTRUMENT`], an [`InstrumentOrderBook`] that fails an
+/// integrity check is re-initialised in isolation, yielding a non-terminal
+/// [`DataError::InstrumentResync`] rather than the terminal [`DataError`].
+pub struct MultiBookTransformer<Exchange, Kind, Updater>
+where
+    Updater: OrderBookUpdater,
+{
     pub book_map: Map<InstrumentOrderBook<Updater>>,
+    /// Cap on the number of [`Level`](crate::subscription::book::Level)s retained on each side
+    /// of every [`OrderBook`], taken from the [`StreamConfig::max_book_levels`] of the connection.
+    pub max_levels: Option<usize>,
+    ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+    /// [`StreamConfig`] of the connection, used to re-initialise [`InstrumentOrderBook`]s.
+    config: StreamConfig,
+    /// [`BookResync`] of every [`InstrumentOrderBook`] currently re-initialising.
+    resyncs: HashMap<SubscriptionId, BookResync<Updater>>,
     phantom: PhantomData<(Exchange, Kind)>,
 }
 
+impl<Exchange, Kind, Updater> Debug for MultiBookTransformer<Exchange, Kind, Updater>
+where
+    Updater: OrderBookUpdater + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiBookTransformer")
+            .field("book_map", &self.book_map)
+            .field("max_levels", &self.max_levels)
+            .field("resyncing", &self.resyncs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// In-flight re-initialisation of an [`InstrumentOrderBook`] that failed an integrity check,
+/// buffering it's updates until the new [`InstrumentOrderBook`] is received.
+struct BookResync<Updater>
+where
+    Updater: OrderBookUpdater,
+{
+    /// Terminal [`DataError`] that triggered the re-initialisation, yielded if it fails.
+    error: DataError,
+    book_rx: oneshot::Receiver<Result<InstrumentOrderBook<Updater>, DataError>>,
+    /// Buffered updates alongside the time each was received.
+    buffered: Vec<(DateTime<Utc>, Updater::Update)>,
+}
+
 #[async_trait]
 impl<Exchange, Kind, Updater> ExchangeTransformer<Exchange, Kind>
     for MultiBookTransformer<Exchange, Kind, Updater>
 where
-    Exchange: Connector + Send,
-    Kind: SubKind<Event = OrderBook> + Send,
-    Updater: OrderBookUpdater<OrderBook = Kind::Event> + Send,
-    Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
+    Exchange: Connector + Send + 'static,
+    Kind: SubKind<Event = OrderBook> + Send + 'static,
+    Updater: OrderBookUpdater<OrderBook = Kind::Event> + Send + 'static,
+    Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de> + Send,
 {
     async fn new(
         ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
         map: Map<Instrument>,
+        config: &StreamConfig,
     ) -> Result<Self, DataError> {
         // Initialise InstrumentOrderBooks for all Subscriptions
         let (sub_ids, init_book_requests): (Vec<_>, Vec<_>) = map
@@ -81,7 +145,7 @@ where
             .map(|(sub_id, instrument)| {
                 (
                     sub_id,
-                    Updater::init::<Exchange, Kind>(ws_sink_tx.clone(), instrument),
+                    Updater::init::<Exchange, Kind>(ws_sink_tx.clone(), instrument, config),
                 )
             })
             .unzip();
@@ -95,39 +159,63 @@ where
         // Construct OrderBookMap if all requests successful
         let book_map = sub_ids
             .into_iter()
-            .zip(init_order_books.into_iter())
+            .zip(init_order_books)
             .collect::<Map<InstrumentOrderBook<Updater>>>();
 
-        Ok(Self {
+        Ok(Self::from_books(ws_sink_tx, book_map, config))
+    }
+}
+
+impl<Exchange, Kind, Updater> MultiBookTransformer<Exchange, Kind, Updater>
+where
+    Updater: OrderBookUpdater,
+{
+    /// Construct a new [`Self`] managing the provided, already initialised,
+    /// [`InstrumentOrderBook`]s using the [`StreamConfig`] of the connection.
+    pub fn from_books(
+        ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+        book_map: Map<InstrumentOrderBook<Updater>>,
+        config: &StreamConfig,
+    ) -> Self {
+        Self {
             book_map,
-            phantom: PhantomData::default(),
-        })
+            max_levels: config.max_book_levels,
+            ws_sink_tx,
+            config: config.clone(),
+            resyncs: HashMap::new(),
+            phantom: PhantomData,
+        }
     }
 }
 
 impl<Exchange, Kind, Updater> Transformer for MultiBookTransformer<Exchange, Kind, Updater>
 where
-    Exchange: Connector,
-    Kind: SubKind<Event = OrderBook>,
-    Updater: OrderBookUpdater<OrderBook = Kind::Event>,
+    Exchange: Connector + Send + 'static,
+    Kind: SubKind<Event = OrderBook> + Send + 'static,
+    Updater: OrderBookUpdater<OrderBook = Kind::Event> + Send + 'static,
     Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
 {
     type Error = DataError;
-    type Input = Updater::Update;
+    type Input = (DateTime<Utc>, Updater::Update);
     type Output = MarketEvent<Kind::Event>;
-    type OutputIter = Vec<Result<Self::Output, Self::Error>>;
+    type OutputIter = MarketEvents<Kind::Event>;
 
-    fn transform(&mut self, update: Self::Input) -> Self::OutputIter {
+    fn transform(&mut self, (received_time, update): Self::Input) -> Self::OutputIter {
         // Determine if the update has an identifiable SubscriptionId
         let subscription_id = match update.id() {
             Some(subscription_id) => subscription_id,
-            None => return vec![],
+            None => return smallvec![],
         };
 
+        // Buffer updates of an InstrumentOrderBook that is re-initialising
+        if self.resyncs.contains_key(&subscription_id) {
+            return self.resync(subscription_id, received_time, update);
+        }
+
         // Retrieve the InstrumentOrderBook associated with this update (snapshot or delta)
         let book = match self.book_map.find_mut(&subscription_id) {
             Ok(book) => book,
-            Err(unidentifiable) => return vec![Err(DataError::Socket(unidentifiable))],
+            Err(unidentifiable) => return smallvec![Err(DataError::Socket(unidentifiable))],
         };
 
         // De-structure for ease
@@ -138,12 +226,398 @@ where
         } = book;
 
         // Apply update (snapshot or delta) to OrderBook & generate Market<OrderBook> snapshot
-        match updater.update(book, update) {
-            Ok(Some(book)) => {
-                MarketIter::<OrderBook>::from((Exchange::ID, instrument.clone(), book)).0
+        let snapshot = updater.update(book, update);
+
+        // Evict the deepest Levels of an OrderBook exceeding the cap
+        if let Some(max_levels) = self.max_levels {
+            let evicted = book.evict(max_levels);
+            if evicted > 0 {
+                debug!(
+                    exchange = %Exchange::ID,
+                    %instrument,
+                    evicted,
+                    max_levels,
+                    "evicted OrderBook Levels exceeding cap"
+                );
+                #[cfg(feature = "metrics")]
+                crate::metrics::MetricsRegistry::global()
+                    .counter(
+                        crate::metrics::BOOK_LEVELS_EVICTED,
+                        &[("exchange", Exchange::ID.as_str())],
+                    )
+                    .inc_by(evicted as u64);
             }
-            Ok(None) => vec![],
-            Err(error) => vec![Err(error)],
         }
+
+        match snapshot {
+            Ok(Some(mut snapshot)) => {
+                if let Some(max_levels) = self.max_levels {
+                    snapshot.evict(max_levels);
+                }
+                MarketIter::<OrderBook>::from((
+                    Exchange::ID,
+                    instrument.clone(),
+                    received_time,
+                    snapshot,
+                ))
+                .0
+            }
+            Ok(None) => smallvec![],
+            Err(error) if error.is_terminal() && Updater::RESYNC_INSTRUMENT => {
+                let instrument = instrument.clone();
+                self.start_resync(subscription_id, instrument, error)
+            }
+            Err(error) => smallvec![Err(error)],
+        }
+    }
+}
+
+impl<Exchange, Kind, Updater> MultiBookTransformer<Exchange, Kind, Updater>
+where
+    Exchange: Connector + Send + 'static,
+    Kind: SubKind<Event = OrderBook> + Send + 'static,
+    Updater: OrderBookUpdater<OrderBook = Kind::Event> + Send + 'static,
+    Updater::Update: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
+{
+    /// Re-initialise the [`InstrumentOrderBook`] of the provided [`SubscriptionId`] in isolation
+    /// after it failed an integrity check with the provided terminal [`DataError`].
+    ///
+    /// The re-initialisation runs on a separate task using the [`StreamConfig`] of this
+    /// connection, whilst updates to the [`InstrumentOrderBook`] are buffered.
+    fn start_resync(
+        &mut self,
+        subscription_id: SubscriptionId,
+        instrument: Instrument,
+        error: DataError,
+    ) -> MarketEvents<Kind::Event> {
+        let (book_tx, book_rx) = oneshot::channel();
+        let (ws_sink_tx, config) = (self.ws_sink_tx.clone(), self.config.clone());
+        let init_instrument = instrument.clone();
+        tokio::spawn(async move {
+            let book = Updater::init::<Exchange, Kind>(ws_sink_tx, init_instrument, &config).await;
+            let _ = book_tx.send(book);
+        });
+
+        let resync = DataError::InstrumentResync {
+            instrument,
+            reason: error.to_string(),
+        };
+        self.resyncs.insert(
+            subscription_id,
+            BookResync {
+                error,
+                book_rx,
+                buffered: Vec::new(),
+            },
+        );
+
+        smallvec![Err(resync)]
+    }
+
+    /// Buffer the update of a re-initialising [`InstrumentOrderBook`], replaying every buffered
+    /// update onto the new [`InstrumentOrderBook`] once it has been received.
+    ///
+    /// Falls back to yielding the terminal [`DataError`] that triggered the re-initialisation
+    /// (re-initialising the whole connection) if it fails, or buffers too many updates.
+    fn resync(
+        &mut self,
+        subscription_id: SubscriptionId,
+        received_time: DateTime<Utc>,
+        update: Updater::Update,
+    ) -> MarketEvents<Kind::Event> {
+        let Some(resync) = self.resyncs.get_mut(&subscription_id) else {
+            return smallvec![];
+        };
+
+        let book = match resync.book_rx.try_recv() {
+            Err(oneshot::error::TryRecvError::Empty)
+                if resync.buffered.len() < MAX_RESYNC_BUFFERED_UPDATES =>
+            {
+                resync.buffered.push((received_time, update));
+                return smallvec![];
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {
+                Err(DataError::Socket(SocketError::Exchange(format!(
+                    "exceeded {MAX_RESYNC_BUFFERED_UPDATES} buffered updates"
+                ))))
+            }
+            Err(oneshot::error::TryRecvError::Closed) => Err(DataError::Socket(
+                SocketError::Exchange("re-initialisation task ended".to_string()),
+            )),
+            Ok(book) => book,
+        };
+
+        let Some(resync) = self.resyncs.remove(&subscription_id) else {
+            return smallvec![];
+        };
+
+        match book {
+            Ok(book) => {
+                info!(
+                    exchange = %Exchange::ID,
+                    instrument = %book.instrument,
+                    replayed = resync.buffered.len() + 1,
+                    "re-initialised OrderBook"
+                );
+                self.book_map.0.insert(subscription_id, book);
+
+                // Replay buffered updates, which may themselves trigger another re-initialisation
+                resync
+                    .buffered
+                    .into_iter()
+                    .chain(std::iter::once((received_time, update)))
+                    .flat_map(|input| self.transform(input))
+                    .collect()
+            }
+            Err(error) => {
+                warn!(
+                    exchange = %Exchange::ID,
+                    %error,
+                    action = "re-initialising Stream",
+                    "failed to re-initialise OrderBook"
+                );
+                smallvec![Err(resync.error)]
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "binance"))]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::binance::spot::BinanceSpot,
+        subscription::book::{Level, OrderBookSide, OrderBooksL2},
+    };
+    use crate::{num, num::Num};
+    use barter_integration::model::{InstrumentKind, Side};
+
+    #[derive(Clone, PartialEq, Debug, Deserialize)]
+    struct Update {
+        bids: Vec<(Num, Num)>,
+        asks: Vec<(Num, Num)>,
+    }
+
+    impl Identifier<Option<SubscriptionId>> for Update {
+        fn id(&self) -> Option<SubscriptionId> {
+            Some(SubscriptionId::from("book"))
+        }
+    }
+
+    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+    struct Updater;
+
+    #[async_trait]
+    impl OrderBookUpdater for Updater {
+        type OrderBook = OrderBook;
+        type Update = Update;
+
+        async fn init<Exchange, Kind>(
+            _: mpsc::UnboundedSender<WsMessage>,
+            _: Instrument,
+            _: &StreamConfig,
+        ) -> Result<InstrumentOrderBook<Self>, DataError> {
+            unimplemented!()
+        }
+
+        fn update(
+            &mut self,
+            book: &mut Self::OrderBook,
+            update: Self::Update,
+        ) -> Result<Option<Self::OrderBook>, DataError> {
+            book.bids.upsert(update.bids);
+            book.asks.upsert(update.asks);
+            Ok(Some(book.snapshot()))
+        }
+    }
+
+    #[test]
+    fn test_multi_book_transformer_evicts_levels_exceeding_cap() {
+        let mut transformer = MultiBookTransformer::<BinanceSpot, OrderBooksL2, Updater> {
+            book_map: Map::from_iter([(
+                SubscriptionId::from("book"),
+                InstrumentOrderBook {
+                    instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                    updater: Updater,
+                    book: OrderBook {
+                        last_update_time: Utc::now(),
+                        bids: OrderBookSide::new(
+                            Side::Buy,
+                            [(num!(99.0), num!(1.0)), (num!(98.0), num!(1.0))],
+                        ),
+                        asks: OrderBookSide::new(Side::Sell, [(num!(101.0), num!(1.0))]),
+                    },
+                },
+            )]),
+            max_levels: Some(2),
+            ws_sink_tx: mpsc::unbounded_channel().0,
+            config: StreamConfig::default(),
+            resyncs: HashMap::new(),
+            phantom: PhantomData,
+        };
+
+        let events = transformer.transform((
+            Utc::now(),
+            Update {
+                bids: vec![(num!(100.0), num!(2.0)), (num!(97.0), num!(1.0))],
+                asks: vec![(num!(102.0), num!(1.0))],
+            },
+        ));
+
+        // Best bid & ask are correct, whilst the deepest bids are evicted
+        let event = events.into_iter().next().unwrap().unwrap();
+        assert_eq!(
+            event.kind.bids.levels(),
+            [
+                Level::new(num!(100.0), num!(2.0)),
+                Level::new(num!(99.0), num!(1.0))
+            ]
+        );
+        assert_eq!(
+            event.kind.asks.levels(),
+            [
+                Level::new(num!(101.0), num!(1.0)),
+                Level::new(num!(102.0), num!(1.0))
+            ]
+        );
+
+        // Managed OrderBook is also capped
+        let book = &transformer.book_map.0[&SubscriptionId::from("book")].book;
+        assert_eq!(book.bids.levels().len(), 2);
+    }
+
+    #[derive(Clone, PartialEq, Debug, Deserialize)]
+    struct SequencedUpdate {
+        id: String,
+        sequence: u64,
+    }
+
+    impl Identifier<Option<SubscriptionId>> for SequencedUpdate {
+        fn id(&self) -> Option<SubscriptionId> {
+            Some(SubscriptionId::from(self.id.as_str()))
+        }
+    }
+
+    #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+    struct SequencedUpdater {
+        last_sequence: u64,
+    }
+
+    #[async_trait]
+    impl OrderBookUpdater for SequencedUpdater {
+        type OrderBook = OrderBook;
+        type Update = SequencedUpdate;
+
+        const RESYNC_INSTRUMENT: bool = true;
+
+        async fn init<Exchange, Kind>(
+            _: mpsc::UnboundedSender<WsMessage>,
+            instrument: Instrument,
+            _: &StreamConfig,
+        ) -> Result<InstrumentOrderBook<Self>, DataError> {
+            // Mock HTTP snapshot at sequence 10, failing for the "fail" instrument
+            match instrument.base.as_ref() {
+                "fail" => Err(DataError::Socket(SocketError::Exchange(
+                    "snapshot unavailable".to_string(),
+                ))),
+                _ => Ok(sequenced_book(instrument.base.as_ref(), 10)),
+            }
+        }
+
+        fn update(
+            &mut self,
+            book: &mut Self::OrderBook,
+            update: Self::Update,
+        ) -> Result<Option<Self::OrderBook>, DataError> {
+            if update.sequence != self.last_sequence + 1 {
+                return Err(DataError::InvalidSequence {
+                    prev_last_update_id: self.last_sequence,
+                    first_update_id: update.sequence,
+                });
+            }
+            self.last_sequence = update.sequence;
+            Ok(Some(book.snapshot()))
+        }
+    }
+
+    fn sequenced_book(base: &str, last_sequence: u64) -> InstrumentOrderBook<SequencedUpdater> {
+        InstrumentOrderBook {
+            instrument: Instrument::from((base, "usdt", InstrumentKind::Spot)),
+            updater: SequencedUpdater { last_sequence },
+            book: OrderBook {
+                last_update_time: Utc::now(),
+                bids: OrderBookSide::new(Side::Buy, [(num!(99.0), num!(1.0))]),
+                asks: OrderBookSide::new(Side::Sell, [(num!(101.0), num!(1.0))]),
+            },
+        }
+    }
+
+    fn update(id: &str, sequence: u64) -> (DateTime<Utc>, SequencedUpdate) {
+        (
+            Utc::now(),
+            SequencedUpdate {
+                id: id.to_string(),
+                sequence,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_multi_book_transformer_resyncs_instrument_in_isolation() {
+        let mut transformer = MultiBookTransformer::<BinanceSpot, OrderBooksL2, SequencedUpdater> {
+            book_map: Map::from_iter([
+                (SubscriptionId::from("btc"), sequenced_book("btc", 1)),
+                (SubscriptionId::from("eth"), sequenced_book("eth", 1)),
+                (SubscriptionId::from("fail"), sequenced_book("fail", 1)),
+            ]),
+            max_levels: None,
+            ws_sink_tx: mpsc::unbounded_channel().0,
+            config: StreamConfig::default(),
+            resyncs: HashMap::new(),
+            phantom: PhantomData,
+        };
+
+        // Sequence gap re-initialises the "btc" OrderBook in isolation, without a terminal error
+        let events = transformer.transform(update("btc", 5));
+        assert!(matches!(
+            events.as_slice(),
+            [Err(DataError::InstrumentResync { instrument, .. })] if instrument.base.as_ref() == "btc"
+        ));
+
+        // Other OrderBooks are undisturbed
+        let events = transformer.transform(update("eth", 2));
+        assert!(matches!(events.as_slice(), [Ok(_)]));
+
+        // Updates are buffered until the re-initialised OrderBook is received
+        assert!(transformer.transform(update("btc", 11)).is_empty());
+        tokio::task::yield_now().await;
+
+        // Buffered updates are replayed onto the re-initialised OrderBook
+        let events = transformer.transform(update("btc", 12));
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(Result::is_ok));
+        assert_eq!(
+            transformer.book_map.0[&SubscriptionId::from("btc")]
+                .updater
+                .last_sequence,
+            12
+        );
+        assert!(transformer.resyncs.is_empty());
+
+        // Failed re-initialisation falls back to the terminal error, re-initialising the Stream
+        let events = transformer.transform(update("fail", 5));
+        assert!(matches!(
+            events.as_slice(),
+            [Err(DataError::InstrumentResync { .. })]
+        ));
+        tokio::task::yield_now().await;
+        let events = transformer.transform(update("fail", 6));
+        assert!(matches!(
+            events.as_slice(),
+            [Err(DataError::InvalidSequence {
+                prev_last_update_id: 1,
+                first_update_id: 5
+            })]
+        ));
     }
 }
@@ -0,0 +1,135 @@
+use crate::{
+    connect,
+    error::DataError,
+    event::{MarketEvent, MarketEvents},
+    exchange::Connector,
+    parser::strict::{self, StrictStream},
+    streams::config::StreamConfig,
+    subscription::{Map, SubKind, Subscription},
+    ExchangeWsStream, Identifier,
+};
+use barter_integration::{model::Instrument, protocol::websocket::WsMessage, Transformer};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::marker::PhantomData;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// User defined normalisation of an exchange's raw messages into
+/// [`MarketEvent<Kind::Event>`](MarketEvent)s, wrapping or replacing the built-in
+/// [`ExchangeTransformer`](super::ExchangeTransformer) of an exchange & [`SubKind`].
+///
+/// Injected via
+/// [`StreamBuilder::subscribe_with_transformer`](crate::streams::builder::StreamBuilder::subscribe_with_transformer),
+/// eg/ to attach extra fields to each event, or to normalise a message the built-in transformer
+/// ignores. To wrap the built-in normalisation of a stateless stream, deserialise the same
+/// exchange message & call [`stateless::transform`](super::stateless::transform).
+///
+/// ### Contract
+/// - **Input**: every text & binary WebSocket message is deserialised into the
+///   [`Input`](CustomTransformer::Input) before [`transform`](CustomTransformer::transform) is
+///   called. Messages that fail to deserialise never reach the [`CustomTransformer`], and are
+///   logged & skipped like those of the built-in transformers.
+/// - **Statefulness**: each connection drives it's own clone of the provided [`CustomTransformer`],
+///   so any state (eg/ sequence numbers) is scoped to a connection and every re-connection
+///   starts from the state it was provided with.
+/// - **Errors**: per-message failures should be yielded as `Err(DataError)` rather than
+///   panicking. Non-terminal errors are logged & skipped by the consumer loop, whereas a
+///   terminal error (see [`DataError::is_terminal`]) re-initialises the connection.
+/// - **Hot path**: [`transform`](CustomTransformer::transform) runs synchronously on the consumer
+///   loop task for every message, so it must not block.
+pub trait CustomTransformer<Exchange, Kind>
+where
+    Self: Clone + Send + Sync + 'static,
+    Kind: SubKind,
+{
+    /// Exchange specific message deserialised from each raw WebSocket message.
+    type Input: for<'de> Deserialize<'de>;
+
+    /// Transform the exchange specific `input`, received at the provided `received_time`, into
+    /// normalised [`MarketEvent<Kind::Event>`](MarketEvent)s, using the [`Map<Instrument>`] of
+    /// the connection's [`Subscription`]s to identify the [`Instrument`] of the `input`.
+    fn transform(
+        &mut self,
+        received_time: DateTime<Utc>,
+        input: Self::Input,
+        instrument_map: &Map<Instrument>,
+    ) -> MarketEvents<Kind::Event>;
+}
+
+/// [`Transformer`] driving a [`CustomTransformer`] on a single connection.
+#[derive(Clone, Debug)]
+pub struct CustomExchangeTransformer<Exchange, Kind, Custom> {
+    custom: Custom,
+    instrument_map: Map<Instrument>,
+    /// Held so the WebSocket connection stays open until the stream is dropped.
+    _ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+    phantom: PhantomData<(Exchange, Kind)>,
+}
+
+impl<Exchange, Kind, Custom> CustomExchangeTransformer<Exchange, Kind, Custom> {
+    /// Construct a new [`Self`] driving the provided [`CustomTransformer`].
+    pub fn new(
+        custom: Custom,
+        ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+        instrument_map: Map<Instrument>,
+    ) -> Self {
+        Self {
+            custom,
+            instrument_map,
+            _ws_sink_tx: ws_sink_tx,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Exchange, Kind, Custom> Transformer for CustomExchangeTransformer<Exchange, Kind, Custom>
+where
+    Kind: SubKind,
+    Custom: CustomTransformer<Exchange, Kind>,
+{
+    type Error = DataError;
+    type Input = (DateTime<Utc>, Custom::Input);
+    type Output = MarketEvent<Kind::Event>;
+    type OutputIter = MarketEvents<Kind::Event>;
+
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        self.custom
+            .transform(received_time, input, &self.instrument_map)
+    }
+}
+
+/// Convenient type alias for an [`ExchangeWsStream`] driven by a [`CustomTransformer`].
+pub type CustomWsStream<Exchange, Kind, Custom> =
+    ExchangeWsStream<CustomExchangeTransformer<Exchange, Kind, Custom>>;
+
+/// Connect & subscribe to the provided [`Subscription`]s using the provided [`StreamConfig`],
+/// returning a [`CustomWsStream`] driven by the provided [`CustomTransformer`].
+///
+/// The connection is established exactly as a built-in
+/// [`MarketStream`](crate::MarketStream) (ie/ buffered replay, pings & exchange status handling).
+pub async fn init<Exchange, Kind, Custom>(
+    subscriptions: &[Subscription<Exchange, Kind>],
+    custom: Custom,
+    config: &StreamConfig,
+) -> Result<CustomWsStream<Exchange, Kind, Custom>, DataError>
+where
+    Exchange: Connector + Send + Sync,
+    Kind: SubKind + Send + Sync,
+    Custom: CustomTransformer<Exchange, Kind>,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    // Connect & subscribe
+    let (ws_stream, ws_sink_tx, map) = connect(subscriptions, config).await?;
+    debug!(exchange = %Exchange::ID, "initialised MarketStream custom transformer");
+
+    Ok(ExchangeWsStream::new(
+        StrictStream::new::<Custom::Input>(
+            Exchange::ID,
+            strict::channel(subscriptions),
+            config.deserialise_mode,
+            ws_stream,
+        ),
+        CustomExchangeTransformer::new(custom, ws_sink_tx, map),
+    ))
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::DataError,
     event::MarketEvent,
+    streams::config::StreamConfig,
     subscription::{Map, SubKind},
 };
 use async_trait::async_trait;
@@ -10,23 +11,32 @@ use tokio::sync::mpsc;
 /// Generic OrderBook [`ExchangeTransformer`]s.
 pub mod book;
 
+/// [`CustomTransformer`](custom::CustomTransformer) trait that users implement to wrap or replace
+/// the built-in normalisation of an exchange & [`SubKind`].
+pub mod custom;
+
 /// Generic stateless [`ExchangeTransformer`] often used for transforming
 /// [`PublicTrades`](crate::subscription::trade::PublicTrades) streams.
 pub mod stateless;
 
 /// Defines how to construct a [`Transformer`] used by [`MarketStream`](super::MarketStream)s to
 /// translate exchange specific types to normalised Barter types.
+///
+/// The [`Transformer::Input`] is a `(received_time, message)` tuple, where `received_time` is the
+/// time the exchange specific `message` was read from the WebSocket, see
+/// [`ExchangeWsStream`](crate::ExchangeWsStream).
 #[async_trait]
 pub trait ExchangeTransformer<Exchange, Kind>
 where
     Self: Transformer<Output = MarketEvent<Kind::Event>, Error = DataError> + Sized,
     Kind: SubKind,
 {
-    /// Construct a new [`Self`].
+    /// Construct a new [`Self`] using the [`StreamConfig`] of the connection.
     ///
     /// The [`mpsc::UnboundedSender`] can be used by [`Self`] to send messages back to the exchange.
     async fn new(
         ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
         instrument_map: Map<Instrument>,
+        config: &StreamConfig,
     ) -> Result<Self, DataError>;
 }
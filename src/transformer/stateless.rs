@@ -1,8 +1,9 @@
 use super::ExchangeTransformer;
 use crate::{
     error::DataError,
-    event::{MarketEvent, MarketIter},
+    event::{MarketEvent, MarketEvents, MarketIter},
     exchange::{Connector, ExchangeId},
+    streams::config::StreamConfig,
     subscription::{Map, SubKind},
     Identifier,
 };
@@ -12,7 +13,9 @@ use barter_integration::{
     protocol::websocket::WsMessage,
     Transformer,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 use std::marker::PhantomData;
 use tokio::sync::mpsc;
 
@@ -33,15 +36,16 @@ where
     Exchange: Connector + Send,
     Kind: SubKind + Send,
     Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
-    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Input)>,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, DateTime<Utc>, Input)>,
 {
     async fn new(
         _: mpsc::UnboundedSender<WsMessage>,
         instrument_map: Map<Instrument>,
+        _: &StreamConfig,
     ) -> Result<Self, DataError> {
         Ok(Self {
             instrument_map,
-            phantom: PhantomData::default(),
+            phantom: PhantomData,
         })
     }
 }
@@ -51,24 +55,46 @@ where
     Exchange: Connector,
     Kind: SubKind,
     Input: Identifier<Option<SubscriptionId>> + for<'de> Deserialize<'de>,
-    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, Input)>,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, DateTime<Utc>, Input)>,
 {
     type Error = DataError;
-    type Input = Input;
+    type Input = (DateTime<Utc>, Input);
     type Output = MarketEvent<Kind::Event>;
-    type OutputIter = Vec<Result<Self::Output, Self::Error>>;
+    type OutputIter = MarketEvents<Kind::Event>;
 
-    fn transform(&mut self, input: Self::Input) -> Self::OutputIter {
-        // Determine if the message has an identifiable SubscriptionId
-        let subscription_id = match input.id() {
-            Some(subscription_id) => subscription_id,
-            None => return vec![],
-        };
+    fn transform(&mut self, (received_time, input): Self::Input) -> Self::OutputIter {
+        transform::<Exchange, Kind, Input>(received_time, input, &self.instrument_map)
+    }
+}
+
+/// Built-in stateless normalisation of an exchange specific `Input` into
+/// [`MarketEvent<Kind::Event>`](MarketEvent)s, using the [`Instrument`] associated with it's
+/// [`SubscriptionId`]. The provided `received_time` is the time the `input` was received.
+///
+/// Input without an identifiable [`SubscriptionId`] (eg/ heartbeats) yields no events. Useful for
+/// [`CustomTransformer`](super::custom::CustomTransformer)s that wrap the built-in normalisation.
+pub fn transform<Exchange, Kind, Input>(
+    received_time: DateTime<Utc>,
+    input: Input,
+    instrument_map: &Map<Instrument>,
+) -> MarketEvents<Kind::Event>
+where
+    Exchange: Connector,
+    Kind: SubKind,
+    Input: Identifier<Option<SubscriptionId>>,
+    MarketIter<Kind::Event>: From<(ExchangeId, Instrument, DateTime<Utc>, Input)>,
+{
+    // Determine if the message has an identifiable SubscriptionId
+    let subscription_id = match input.id() {
+        Some(subscription_id) => subscription_id,
+        None => return smallvec![],
+    };
 
-        // Find Instrument associated with Input and transform
-        match self.instrument_map.find(&subscription_id) {
-            Ok(instrument) => MarketIter::<Kind::Event>::from((Exchange::ID, instrument, input)).0,
-            Err(unidentifiable) => vec![Err(DataError::Socket(unidentifiable))],
+    // Find Instrument associated with Input and transform
+    match instrument_map.find(&subscription_id) {
+        Ok(instrument) => {
+            MarketIter::<Kind::Event>::from((Exchange::ID, instrument, received_time, input)).0
         }
+        Err(unidentifiable) => smallvec![Err(DataError::Socket(unidentifiable))],
     }
 }
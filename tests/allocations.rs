@@ -0,0 +1,282 @@
+//! Allocation budget tests guarding the per-message hot path of exchange message models.
+//!
+//! Symbols, channel names & numeric strings are deserialised from the borrowed payload (see
+//! `barter_integration::de::de_str`), so the only allocations are the data the parsed message
+//! owns, as listed in each test. If one of these tests fails, a change has added (or removed) an
+//! allocation per message - update the budget only if that is intentional.
+
+use barter_data::{
+    exchange::{
+        binance::{
+            book::{l1::BinanceOrderBookL1, l2::BinanceOrderBookL2Snapshot},
+            candle::BinanceKline,
+            combined::BinanceTradeOrOrderBookL1,
+            futures::liquidation::BinanceLiquidation,
+            spot::{
+                average_price::BinanceAveragePrice,
+                l2::{BinanceSpotBookUpdater, BinanceSpotOrderBookL2Delta},
+                ticker::BinanceTicker,
+                BinanceSpot,
+            },
+            trade::{BinanceAggTrade, BinanceTrade},
+        },
+        bitfinex::{book::BitfinexBookMessage, message::BitfinexMessage},
+        coinbase::{ticker::CoinbaseTicker, trade::CoinbaseTrade},
+        gateio::{
+            futures::trade::GateioFuturesTrades,
+            spot::{trade::GateioSpotTrade, GateioSpot},
+        },
+        kraken::{book::l1::KrakenOrderBookL1, candle::KrakenCandles, trade::KrakenTrades},
+        okx::trade::OkxTrades,
+    },
+    parser::WsParser,
+    streams::config::StreamConfig,
+    subscription::{book::OrderBook, trade::PublicTrades, Map},
+    transformer::{book::OrderBookUpdater, stateless::StatelessTransformer, ExchangeTransformer},
+};
+use barter_integration::{
+    model::{Instrument, InstrumentKind, SubscriptionId},
+    protocol::{websocket::WsMessage, StreamParser},
+    Transformer,
+};
+use chrono::Utc;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// [`GlobalAlloc`] that counts the allocations made by the current thread whilst counting.
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Allocations the JSON backend makes per message, ie/ the `simd-json` parse tape.
+const BACKEND: usize = if cfg!(feature = "simd-json") { 1 } else { 0 };
+
+/// Allocations the [`Num`](barter_data::num::Num) makes per JSON float number it parses, ie/ the
+/// `rust_decimal` parse of the formatted float.
+const FLOAT: usize = if cfg!(feature = "decimal") { 1 } else { 0 };
+
+/// Parse the provided payload with the [`WsParser`], returning the number of allocations it made
+/// alongside the parsed message.
+///
+/// The payload is parsed once beforehand, so per thread state of the JSON backend (eg/ the
+/// reused `simd-json` buffers) is not counted.
+fn count_parse_allocations<T>(payload: &str) -> (usize, T)
+where
+    T: serde::de::DeserializeOwned,
+{
+    WsParser::parse::<T>(Ok(WsMessage::Text(payload.to_string())));
+
+    let message = WsMessage::Text(payload.to_string());
+    count_allocations(|| WsParser::parse::<T>(Ok(message)).unwrap().unwrap())
+}
+
+/// Parse the `0000.json` fixture of the provided `tests/fixtures/{exchange}/{channel}` directory
+/// with the [`WsParser`], returning the number of allocations it made.
+fn count_fixture_parse_allocations<T>(fixture: &str) -> usize
+where
+    T: serde::de::DeserializeOwned,
+{
+    let path = format!(
+        "{}/tests/fixtures/{fixture}/0000.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let payload = std::fs::read_to_string(path).unwrap();
+    count_parse_allocations::<T>(payload.trim()).0
+}
+
+/// Run the provided closure, returning the number of allocations it made alongside it's output.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    ALLOCATIONS.with(|count| count.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    let output = f();
+    COUNTING.with(|counting| counting.set(false));
+    (ALLOCATIONS.with(Cell::get), output)
+}
+
+#[tokio::test]
+async fn binance_trade_allocations() {
+    let payload = r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#;
+
+    let instrument_map = Map::from_iter([(
+        SubscriptionId::from("@trade|ETHUSDT"),
+        Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+    )]);
+    let (ws_sink_tx, _ws_sink_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut transformer: StatelessTransformer<BinanceSpot, PublicTrades, BinanceTrade> =
+        ExchangeTransformer::new(ws_sink_tx, instrument_map, &StreamConfig::default())
+            .await
+            .unwrap();
+
+    // Parse: SubscriptionId
+    let (parse, trade) = count_parse_allocations::<BinanceTrade>(payload);
+    assert_eq!(parse, BACKEND + 1);
+
+    // Transform: SubscriptionId lookup key, Instrument base & quote, trade id
+    let (transform, events) = count_allocations(|| transformer.transform((Utc::now(), trade)));
+    assert_eq!(transform, 4);
+
+    let event = events.into_iter().next().unwrap().unwrap();
+    assert_eq!(event.kind.id, "1000000000");
+}
+
+#[tokio::test]
+async fn gateio_spot_trade_allocations() {
+    let payload = r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.trades","event":"update","result":{"id":309143071,"create_time":1606292218,"create_time_ms":"1606292218213.4578","side":"sell","currency_pair":"GT_USDT","amount":"16.4700000000","price":"0.4705000000"}}"#;
+
+    let instrument_map = Map::from_iter([(
+        SubscriptionId::from("spot.trades|GT_USDT"),
+        Instrument::from(("gt", "usdt", InstrumentKind::Spot)),
+    )]);
+    let (ws_sink_tx, _ws_sink_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut transformer: StatelessTransformer<GateioSpot, PublicTrades, GateioSpotTrade> =
+        ExchangeTransformer::new(ws_sink_tx, instrument_map, &StreamConfig::default())
+            .await
+            .unwrap();
+
+    // Parse: market
+    let (parse, trade) = count_parse_allocations::<GateioSpotTrade>(payload);
+    assert_eq!(parse, BACKEND + 1);
+
+    // Transform: SubscriptionId lookup key, Instrument base & quote, trade id
+    let (transform, events) = count_allocations(|| transformer.transform((Utc::now(), trade)));
+    assert_eq!(transform, 4);
+
+    let event = events.into_iter().next().unwrap().unwrap();
+    assert_eq!(event.kind.id, "309143071");
+}
+
+#[test]
+fn binance_spot_depth_allocations() {
+    let snapshot = r#"{"lastUpdateId":100,"bids":[["1209.67","85.48"],["1209.66","20.68"]],"asks":[["1209.68","1.0"],["1209.69","2.0"]]}"#;
+    let payload = r#"{"e":"depthUpdate","E":1671656397761,"s":"ETHUSDT","U":101,"u":105,"b":[["1209.67","0.0"],["1209.65","3.0"]],"a":[["1209.68","4.0"]]}"#;
+
+    let snapshot = serde_json::from_str::<BinanceOrderBookL2Snapshot>(snapshot).unwrap();
+    let mut updater = BinanceSpotBookUpdater::new(snapshot.last_update_id);
+    let mut book = OrderBook::from(snapshot);
+
+    // Parse: SubscriptionId, bids & asks Levels
+    let (parse, delta) = count_parse_allocations::<BinanceSpotOrderBookL2Delta>(payload);
+    assert_eq!(parse, BACKEND + 3);
+
+    // Update: OrderBook snapshot bids & asks
+    let (update, snapshot) =
+        count_allocations(|| updater.update(&mut book, delta).unwrap().unwrap());
+    assert_eq!(update, 2);
+
+    assert_eq!(snapshot.bids.levels().len(), 2);
+    assert_eq!(snapshot.asks.levels().len(), 2);
+}
+
+#[test]
+fn binance_parse_allocations() {
+    // SubscriptionId
+    let parse =
+        count_fixture_parse_allocations::<BinanceAggTrade>("binance_spot/public_trades_aggregated");
+    assert_eq!(parse, BACKEND + 1);
+    let parse =
+        count_fixture_parse_allocations::<BinanceOrderBookL1>("binance_spot/order_books_l1");
+    assert_eq!(parse, BACKEND + 1);
+    let parse =
+        count_fixture_parse_allocations::<BinanceLiquidation>("binance_futures_usd/liquidations");
+    assert_eq!(parse, BACKEND + 1);
+
+    // Market
+    let parse = count_fixture_parse_allocations::<BinanceKline>("binance_spot/candles");
+    assert_eq!(parse, BACKEND + 1);
+    let parse = count_fixture_parse_allocations::<BinanceTicker>("binance_spot/tickers");
+    assert_eq!(parse, BACKEND + 1);
+    let parse =
+        count_fixture_parse_allocations::<BinanceAveragePrice>("binance_spot/average_prices");
+    assert_eq!(parse, BACKEND + 1);
+
+    // SubscriptionId of the trade or OrderBookL1
+    let trade = r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#;
+    let (parse, _) = count_parse_allocations::<BinanceTradeOrOrderBookL1>(trade);
+    assert_eq!(parse, BACKEND + 1);
+    let book = r#"{"u":22606535573,"s":"ETHUSDT","b":"1215.27000000","B":"32.49110000","a":"1215.28000000","A":"13.93900000"}"#;
+    let (parse, _) = count_parse_allocations::<BinanceTradeOrOrderBookL1>(book);
+    assert_eq!(parse, BACKEND + 1);
+}
+
+#[test]
+fn gateio_parse_allocations() {
+    // Market
+    let parse = count_fixture_parse_allocations::<GateioSpotTrade>("gateio_spot/public_trades");
+    assert_eq!(parse, BACKEND + 1);
+
+    // Trades, market of each trade
+    let trades = r#"{"time":1669843487,"time_ms":1669843487733,"channel":"futures.trades","event":"update","result":[{"contract":"ETH_USDT","create_time":1669843487,"create_time_ms":1669843487724,"id":180276616,"price":"1287","size":3}]}"#;
+    let (parse, _) = count_parse_allocations::<GateioFuturesTrades>(trades);
+    assert_eq!(parse, BACKEND + 2);
+}
+
+#[test]
+fn kraken_parse_allocations() {
+    // Trades, SubscriptionId
+    let parse = count_fixture_parse_allocations::<KrakenTrades>("kraken/public_trades");
+    assert_eq!(parse, BACKEND + 2);
+
+    // SubscriptionId
+    let parse = count_fixture_parse_allocations::<KrakenOrderBookL1>("kraken/order_books_l1");
+    assert_eq!(parse, BACKEND + 1);
+    let candle = r#"[42,["1542057314.748456","1542057360.000000","3586.70000","3586.70000","3586.60000","3586.60000","3586.68894","0.03373000",2],"ohlc-5","XBT/USD"]"#;
+    let (parse, _) = count_parse_allocations::<KrakenCandles>(candle);
+    assert_eq!(parse, BACKEND + 1);
+}
+
+#[test]
+fn coinbase_parse_allocations() {
+    // SubscriptionId
+    let parse = count_fixture_parse_allocations::<CoinbaseTrade>("coinbase/public_trades");
+    assert_eq!(parse, BACKEND + 1);
+    let parse = count_fixture_parse_allocations::<CoinbaseTicker>("coinbase/tickers");
+    assert_eq!(parse, BACKEND + 1);
+}
+
+#[test]
+fn bitfinex_parse_allocations() {
+    // None, the SubscriptionId is the integer channel id
+    let (parse, _) = count_parse_allocations::<BitfinexMessage>(r#"[420191,"hb"]"#);
+    assert_eq!(parse, BACKEND);
+
+    // Float amount
+    let parse = count_fixture_parse_allocations::<BitfinexMessage>("bitfinex/public_trades");
+    assert_eq!(parse, BACKEND + FLOAT);
+
+    // Float price & amount
+    let (parse, _) = count_parse_allocations::<BitfinexBookMessage>(r#"[17082,[7254.7,3,3.3]]"#);
+    assert_eq!(parse, BACKEND + 2 * FLOAT);
+
+    // Snapshot levels, float prices & amounts
+    let snapshot = r#"[17082,[[7254.7,3,3.3],[7254.8,1,-0.7]]]"#;
+    let (parse, _) = count_parse_allocations::<BitfinexBookMessage>(snapshot);
+    assert_eq!(parse, BACKEND + 1 + 4 * FLOAT);
+}
+
+#[test]
+fn okx_parse_allocations() {
+    // SubscriptionId, trades, trade id
+    let parse = count_fixture_parse_allocations::<OkxTrades>("okx/public_trades");
+    assert_eq!(parse, BACKEND + 3);
+}
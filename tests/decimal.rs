@@ -0,0 +1,188 @@
+use barter_data::{
+    event::MarketIter,
+    exchange::{
+        binance::{
+            book::l1::BinanceOrderBookL1, candle::BinanceKline,
+            futures::liquidation::BinanceLiquidation, trade::BinanceTrade,
+        },
+        bitfinex::message::{BitfinexChannelMessage, BitfinexMessage, BitfinexPayload},
+        kraken::book::l1::KrakenOrderBookL1,
+        ExchangeId,
+    },
+    subscription::{
+        book::{Level, OrderBookSide},
+        candle::Candle,
+        liquidation::Liquidation,
+        trade::PublicTrade,
+    },
+};
+use barter_integration::model::{Instrument, InstrumentKind, Side};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+fn decimal(input: &str) -> Decimal {
+    Decimal::from_str(input).unwrap()
+}
+
+#[test]
+fn test_binance_trade_preserves_exact_values() {
+    let input = r#"
+    {
+        "e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19000000",
+        "q":"0.00000001234","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true
+    }
+    "#;
+
+    let trade = serde_json::from_str::<BinanceTrade>(input).unwrap();
+
+    // Exact string representation (including trailing zeros) is retained for checksums
+    assert_eq!(trade.price.to_string(), "10000.19000000");
+    assert_eq!(trade.amount, decimal("0.00000001234"));
+}
+
+#[test]
+fn test_binance_trade_serialises_exact_values_end_to_end() {
+    let input = r#"
+    {
+        "e":"trade","E":1649324825173,"s":"ETHUSDT","t":1000000000,"p":"10000.19000000",
+        "q":"0.00000001234","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true
+    }
+    "#;
+
+    let trade = serde_json::from_str::<BinanceTrade>(input).unwrap();
+    let event = MarketIter::<PublicTrade>::from((
+        ExchangeId::BinanceSpot,
+        Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        Utc::now(),
+        trade,
+    ))
+    .0
+    .remove(0)
+    .unwrap();
+
+    // Decimals are serialised as strings, so no f64 round-trip occurs downstream either
+    let serialised = serde_json::to_value(&event).unwrap();
+    assert_eq!(serialised["kind"]["price"], "10000.19000000");
+    assert_eq!(serialised["kind"]["amount"], "0.00000001234");
+}
+
+#[test]
+fn test_binance_order_book_l1_preserves_exact_values() {
+    let input = r#"
+    {
+        "u":22606535573,"s":"ETHUSDT","b":"1215.27000000","B":"32.49110000",
+        "a":"1215.28000000","A":"13.93900000"
+    }
+    "#;
+
+    let book = serde_json::from_str::<BinanceOrderBookL1>(input).unwrap();
+
+    assert_eq!(book.best_bid_price.to_string(), "1215.27000000");
+    assert_eq!(book.best_ask_amount.to_string(), "13.93900000");
+}
+
+#[test]
+fn test_kraken_order_book_l1_preserves_exact_values() {
+    let input = r#"
+    [0, ["5698.40000", "5700.00000", "1542057299.545897", "1.01234567", "0.98765432"], "spread", "XBT/USD"]
+    "#;
+
+    let book = serde_json::from_str::<KrakenOrderBookL1>(input).unwrap();
+    let serialised = serde_json::to_string(&book).unwrap();
+
+    assert!(serialised.contains(r#""5698.40000""#));
+    assert!(serialised.contains(r#""0.98765432""#));
+}
+
+#[test]
+fn test_binance_candle_preserves_exact_values() {
+    let input = r#"
+    {
+        "e":"kline","E":1672515782136,"s":"BTCUSDT",
+        "k":{
+            "t":1672515780000,"T":1672515839999,"s":"BTCUSDT","i":"1m","f":100,"L":200,
+            "o":"16500.10000000","c":"16510.20000000","h":"16520.00000000","l":"16490.00000000",
+            "v":"0.00000001234","n":101,"x":true,"q":"173355.10","V":"5.5","Q":"90812.12","B":"0"
+        }
+    }
+    "#;
+
+    let kline = serde_json::from_str::<BinanceKline>(input).unwrap();
+    let candle = Candle::from(&kline.kline);
+
+    assert_eq!(candle.open.to_string(), "16500.10000000");
+    assert_eq!(candle.high, decimal("16520"));
+    assert_eq!(candle.volume, decimal("0.00000001234"));
+}
+
+#[test]
+fn test_binance_liquidation_preserves_exact_values() {
+    let input = r#"
+    {
+        "e":"forceOrder","E":1665523974222,
+        "o":{
+            "s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.00000001234","p":"18917.15",
+            "ap":"18990.00","X":"FILLED","l":"0.009","z":"0.009","T":1665523974217
+        }
+    }
+    "#;
+
+    let liquidation = serde_json::from_str::<BinanceLiquidation>(input).unwrap();
+    let event = MarketIter::<Liquidation>::from((
+        ExchangeId::BinanceFuturesUsd,
+        Instrument::from(("btc", "usdt", InstrumentKind::FuturePerpetual)),
+        Utc::now(),
+        liquidation,
+    ))
+    .0
+    .remove(0)
+    .unwrap();
+
+    assert_eq!(event.kind.price, decimal("18917.15"));
+    assert_eq!(event.kind.quantity, decimal("0.00000001234"));
+}
+
+#[test]
+fn test_bitfinex_float_trade() {
+    let input = r#"[420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#;
+
+    let message = serde_json::from_str::<BitfinexMessage>(input).unwrap();
+
+    match message {
+        BitfinexMessage::Channel(BitfinexChannelMessage {
+            payload: BitfinexPayload::Trade(trade),
+            ..
+        }) => {
+            assert_eq!(trade.price, decimal("19027.02807752"));
+            assert_eq!(trade.amount, decimal("0.08980641"));
+            assert_eq!(trade.side, Side::Sell);
+        }
+        _ => panic!("expected BitfinexPayload::Trade"),
+    }
+}
+
+#[test]
+fn test_order_book_side_upsert_exact_price_match() {
+    let mut bids = OrderBookSide::new(
+        Side::Buy,
+        vec![
+            Level::new(decimal("0.30000000"), decimal("1")),
+            Level::new(decimal("0.1"), decimal("1")),
+        ],
+    );
+
+    // 0.3 == 0.30000000, so the existing level is removed rather than a new one inserted
+    bids.upsert_single(Level::new(decimal("0.3"), Decimal::ZERO));
+    bids.upsert_single(Level::new(decimal("0.2"), decimal("2")));
+
+    let expected = OrderBookSide::new(
+        Side::Buy,
+        vec![
+            Level::new(decimal("0.1"), decimal("1")),
+            Level::new(decimal("0.2"), decimal("2")),
+        ],
+    );
+
+    assert_eq!(bids, expected);
+}
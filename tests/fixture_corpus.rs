@@ -0,0 +1,162 @@
+//! Captured exchange message fixture corpus tests guarding the deserialisation & normalisation
+//! of every stateless exchange message model.
+//!
+//! Each test checks the `tests/fixtures/{exchange}/{channel}/*.json` raw messages against their
+//! committed `*.expected.json` normalised output. If one of these tests fails, the parsing or
+//! normalisation of an exchange message has changed. If that is intentional, re-run with
+//! `UPDATE_FIXTURES=1` & review the diff. Grow the corpus using the `capture_fixtures` example.
+#![cfg(not(feature = "decimal"))]
+
+use barter_data::{
+    exchange::{
+        binance::{
+            book::l1::BinanceOrderBookL1,
+            candle::BinanceKline,
+            futures::liquidation::BinanceLiquidation,
+            spot::{average_price::BinanceAveragePrice, ticker::BinanceTicker},
+            trade::{BinanceAggTrade, BinanceTrade},
+        },
+        bitfinex::message::BitfinexMessage,
+        coinbase::{book::l1::CoinbaseOrderBookL1, ticker::CoinbaseTicker, trade::CoinbaseTrade},
+        gateio::spot::trade::GateioSpotTrade,
+        kraken::{book::l1::KrakenOrderBookL1, trade::KrakenTrades},
+        okx::trade::OkxTrades,
+        ExchangeId,
+    },
+    subscription::{
+        average_price::AveragePrice, book::OrderBookL1, candle::Candle, liquidation::Liquidation,
+        ticker::Ticker, trade::PublicTrade,
+    },
+    test_util::fixture::{assert_fixtures, fixture_dir},
+};
+use barter_integration::model::{Instrument, InstrumentKind};
+use std::path::PathBuf;
+
+fn dir(exchange: ExchangeId, channel: &str) -> PathBuf {
+    fixture_dir(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"),
+        exchange,
+        channel,
+    )
+}
+
+fn instrument(base: &str, quote: &str, kind: InstrumentKind) -> Instrument {
+    Instrument::from((base, quote, kind))
+}
+
+#[test]
+fn test_fixtures_binance_spot() {
+    let exchange = ExchangeId::BinanceSpot;
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::Spot);
+
+    assert_fixtures::<BinanceTrade, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usdt,
+    );
+    assert_fixtures::<BinanceAggTrade, PublicTrade, _>(
+        dir(exchange, "public_trades_aggregated"),
+        exchange,
+        &btc_usdt,
+    );
+    assert_fixtures::<BinanceOrderBookL1, OrderBookL1, _>(
+        dir(exchange, "order_books_l1"),
+        exchange,
+        &btc_usdt,
+    );
+    assert_fixtures::<BinanceKline, Candle, _>(dir(exchange, "candles"), exchange, &btc_usdt);
+    assert_fixtures::<BinanceAveragePrice, AveragePrice, _>(
+        dir(exchange, "average_prices"),
+        exchange,
+        &btc_usdt,
+    );
+    assert_fixtures::<BinanceTicker, Ticker, _>(dir(exchange, "tickers"), exchange, &btc_usdt);
+}
+
+#[test]
+fn test_fixtures_binance_futures_usd() {
+    let exchange = ExchangeId::BinanceFuturesUsd;
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::FuturePerpetual);
+
+    assert_fixtures::<BinanceTrade, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usdt,
+    );
+    assert_fixtures::<BinanceLiquidation, Liquidation, _>(
+        dir(exchange, "liquidations"),
+        exchange,
+        &btc_usdt,
+    );
+}
+
+#[test]
+fn test_fixtures_bitfinex() {
+    let exchange = ExchangeId::Bitfinex;
+    let btc_usd = instrument("btc", "usd", InstrumentKind::Spot);
+
+    assert_fixtures::<BitfinexMessage, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usd,
+    );
+}
+
+#[test]
+fn test_fixtures_coinbase() {
+    let exchange = ExchangeId::Coinbase;
+    let btc_usd = instrument("btc", "usd", InstrumentKind::Spot);
+
+    assert_fixtures::<CoinbaseTrade, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usd,
+    );
+    assert_fixtures::<CoinbaseOrderBookL1, OrderBookL1, _>(
+        dir(exchange, "order_books_l1"),
+        exchange,
+        &btc_usd,
+    );
+    assert_fixtures::<CoinbaseTicker, Ticker, _>(dir(exchange, "tickers"), exchange, &btc_usd);
+}
+
+#[test]
+fn test_fixtures_gateio_spot() {
+    let exchange = ExchangeId::GateioSpot;
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::Spot);
+
+    assert_fixtures::<GateioSpotTrade, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usdt,
+    );
+}
+
+#[test]
+fn test_fixtures_kraken() {
+    let exchange = ExchangeId::Kraken;
+    let xbt_usd = instrument("xbt", "usd", InstrumentKind::Spot);
+
+    assert_fixtures::<KrakenTrades, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &xbt_usd,
+    );
+    assert_fixtures::<KrakenOrderBookL1, OrderBookL1, _>(
+        dir(exchange, "order_books_l1"),
+        exchange,
+        &xbt_usd,
+    );
+}
+
+#[test]
+fn test_fixtures_okx() {
+    let exchange = ExchangeId::Okx;
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::Spot);
+
+    assert_fixtures::<OkxTrades, PublicTrade, _>(
+        dir(exchange, "public_trades"),
+        exchange,
+        &btc_usdt,
+    );
+}
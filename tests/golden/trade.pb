@@ -0,0 +1,4 @@
+Ý
+Ý="binance_spot*
+btcusdt2
+116500.50.25 
\ No newline at end of file
@@ -0,0 +1,379 @@
+//! Live smoke tests connecting to the real exchanges, used as a manual pre-release health check:
+//!
+//! `cargo test --test live -- --ignored live_`
+//!
+//! Each test subscribes to one liquid instrument per supported [`SubKind`] of an exchange, and
+//! asserts at least one event of each deserialises within [`LIVE_TIMEOUT`]. Every channel is
+//! reported (see [`ChannelReport`]), and failures state the exchange, channel & raw payload that
+//! failed to deserialise, so API changes are caught before they break production.
+//!
+//! Set `RUST_LOG=barter_data=debug` to trace the connections.
+#![cfg(not(feature = "decimal"))]
+
+use barter_data::{
+    error::DataError,
+    exchange::{
+        binance::{futures::BinanceFuturesUsd, spot::BinanceSpot, us::BinanceUs},
+        bitfinex::Bitfinex,
+        coinbase::Coinbase,
+        gateio::{
+            futures::{GateioFuturesBtc, GateioFuturesUsd},
+            spot::GateioSpot,
+        },
+        kraken::Kraken,
+        okx::Okx,
+        ExchangeId, StreamSelector,
+    },
+    subscription::{
+        average_price::AveragePrices,
+        book::{OrderBooksL1, OrderBooksL2},
+        candle::{Candles, Interval},
+        combined::TradesAndBooksL1,
+        liquidation::Liquidations,
+        open_interest::OpenInterests,
+        ticker::Tickers,
+        trade::{PublicTrades, PublicTradesAggregated, PublicTradesAll},
+        SubKind, Subscription,
+    },
+    Identifier, MarketStream,
+};
+use barter_integration::{error::SocketError, model::InstrumentKind};
+use futures::{future::BoxFuture, StreamExt};
+use std::{fmt::Write, time::Duration};
+
+/// Maximum duration to wait for the first event of each channel.
+const LIVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of characters of a raw payload included in a [`ChannelReport`].
+const MAX_PAYLOAD_CHARS: usize = 512;
+
+/// Whether a channel must yield an event within the [`LIVE_TIMEOUT`], or may legitimately be
+/// quiet (eg/ liquidations only occur during volatility).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Activity {
+    Required,
+    MayBeQuiet,
+}
+
+/// Outcome of a single live channel check.
+#[derive(Clone, Debug)]
+enum Outcome {
+    /// An event deserialised, after skipping the provided number of unparseable payloads.
+    Received { skipped: usize },
+    /// No event was received within the [`LIVE_TIMEOUT`], but the channel may be quiet.
+    Quiet,
+    /// The channel failed, with the raw payload that failed to deserialise, if any.
+    Failed {
+        error: String,
+        payload: Option<String>,
+    },
+}
+
+/// Report of a single live channel check.
+#[derive(Clone, Debug)]
+struct ChannelReport {
+    exchange: ExchangeId,
+    channel: &'static str,
+    outcome: Outcome,
+}
+
+/// Check the live `channel` of an exchange yields at least one event that deserialises.
+///
+/// Unparseable payloads are skipped (eg/ an exchange system message), but the first one is
+/// reported if no event deserialises before the [`LIVE_TIMEOUT`].
+fn check<Exchange, Kind>(
+    channel: &'static str,
+    subscription: impl Into<Subscription<Exchange, Kind>>,
+    activity: Activity,
+) -> BoxFuture<'static, ChannelReport>
+where
+    Exchange: StreamSelector<Kind> + Send + Sync + 'static,
+    Kind: SubKind + Send + Sync + 'static,
+    Kind::Event: Send,
+    Subscription<Exchange, Kind>: Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    let subscriptions = vec![subscription.into()];
+
+    Box::pin(async move {
+        let mut skipped = 0;
+        let mut first_unparsed = None;
+
+        let checked = tokio::time::timeout(LIVE_TIMEOUT, async {
+            let mut stream = Exchange::Stream::init(&subscriptions).await?;
+
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(_) => return Ok(true),
+                    Err(DataError::Socket(SocketError::Deserialise { error, payload })) => {
+                        skipped += 1;
+                        first_unparsed.get_or_insert((error.to_string(), payload));
+                    }
+                    Err(error) if error.is_terminal() => return Err(error),
+                    Err(_) => {}
+                }
+            }
+
+            Ok(false)
+        })
+        .await;
+
+        let outcome = match (checked, first_unparsed) {
+            (Ok(Ok(true)), _) => Outcome::Received { skipped },
+            (Ok(Err(error)), _) => Outcome::Failed {
+                error: error.to_string(),
+                payload: None,
+            },
+            (_, Some((error, payload))) => Outcome::Failed {
+                error,
+                payload: Some(payload.chars().take(MAX_PAYLOAD_CHARS).collect()),
+            },
+            (Ok(Ok(false)), None) => Outcome::Failed {
+                error: "MarketStream ended before any event was received".to_owned(),
+                payload: None,
+            },
+            (Err(_), None) if activity == Activity::MayBeQuiet => Outcome::Quiet,
+            (Err(_), None) => Outcome::Failed {
+                error: format!("no event received within {LIVE_TIMEOUT:?}"),
+                payload: None,
+            },
+        };
+
+        ChannelReport {
+            exchange: Exchange::ID,
+            channel,
+            outcome,
+        }
+    })
+}
+
+/// Concurrently run the provided channel checks, printing a report of which channels worked &
+/// failing with the exchange, channel & raw payload of every failed channel.
+async fn assert_live(checks: Vec<BoxFuture<'static, ChannelReport>>) {
+    let reports = futures::future::join_all(checks).await;
+
+    let mut failures = String::new();
+    for report in &reports {
+        match &report.outcome {
+            Outcome::Received { skipped } => println!(
+                "[ok]     {} {} (skipped {skipped} unparseable payloads)",
+                report.exchange, report.channel
+            ),
+            Outcome::Quiet => println!(
+                "[quiet]  {} {} (no event within {LIVE_TIMEOUT:?})",
+                report.exchange, report.channel
+            ),
+            Outcome::Failed { error, payload } => {
+                println!("[FAILED] {} {}", report.exchange, report.channel);
+                let _ = writeln!(
+                    failures,
+                    "\n{} {} failed: {error}\n  payload: {}",
+                    report.exchange,
+                    report.channel,
+                    payload.as_deref().unwrap_or("<none>")
+                );
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "live channels failed:{failures}");
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Binance"]
+async fn live_binance_spot() {
+    fn sub<Kind>(kind: Kind) -> Subscription<BinanceSpot, Kind> {
+        Subscription::new(
+            BinanceSpot::default(),
+            ("btc", "usdt", InstrumentKind::Spot),
+            kind,
+        )
+    }
+
+    assert_live(vec![
+        check("public_trades", sub(PublicTrades), Activity::Required),
+        check(
+            "public_trades_aggregated",
+            sub(PublicTradesAggregated),
+            Activity::Required,
+        ),
+        check("order_books_l1", sub(OrderBooksL1), Activity::Required),
+        check("order_books_l2", sub(OrderBooksL2), Activity::Required),
+        check("candles", sub(Candles(Interval::M1)), Activity::Required),
+        check(
+            "trades_and_books_l1",
+            sub(TradesAndBooksL1),
+            Activity::Required,
+        ),
+        check("average_prices", sub(AveragePrices), Activity::Required),
+        check("tickers", sub(Tickers(Interval::H1)), Activity::Required),
+    ])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Binance"]
+async fn live_binance_futures_usd() {
+    fn sub<Kind>(kind: Kind) -> Subscription<BinanceFuturesUsd, Kind> {
+        Subscription::new(
+            BinanceFuturesUsd::default(),
+            ("btc", "usdt", InstrumentKind::FuturePerpetual),
+            kind,
+        )
+    }
+
+    assert_live(vec![
+        check("public_trades", sub(PublicTrades), Activity::Required),
+        check(
+            "public_trades_aggregated",
+            sub(PublicTradesAggregated),
+            Activity::Required,
+        ),
+        check("order_books_l1", sub(OrderBooksL1), Activity::Required),
+        check("order_books_l2", sub(OrderBooksL2), Activity::Required),
+        check("candles", sub(Candles(Interval::M1)), Activity::Required),
+        check("liquidations", sub(Liquidations), Activity::MayBeQuiet),
+        check("open_interests", sub(OpenInterests), Activity::Required),
+        check(
+            "trades_and_books_l1",
+            sub(TradesAndBooksL1),
+            Activity::Required,
+        ),
+    ])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to BinanceUs"]
+async fn live_binance_us() {
+    fn sub<Kind>(kind: Kind) -> Subscription<BinanceUs, Kind> {
+        Subscription::new(
+            BinanceUs::default(),
+            ("btc", "usdt", InstrumentKind::Spot),
+            kind,
+        )
+    }
+
+    assert_live(vec![
+        check("public_trades", sub(PublicTrades), Activity::MayBeQuiet),
+        check("order_books_l1", sub(OrderBooksL1), Activity::Required),
+        check("candles", sub(Candles(Interval::M1)), Activity::Required),
+        check(
+            "trades_and_books_l1",
+            sub(TradesAndBooksL1),
+            Activity::Required,
+        ),
+    ])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Bitfinex"]
+async fn live_bitfinex() {
+    assert_live(vec![check(
+        "public_trades",
+        (
+            Bitfinex::default(),
+            "btc",
+            "usd",
+            InstrumentKind::Spot,
+            PublicTrades,
+        ),
+        Activity::Required,
+    )])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Coinbase"]
+async fn live_coinbase() {
+    assert_live(vec![check(
+        "public_trades",
+        (Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades),
+        Activity::Required,
+    )])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Gateio"]
+async fn live_gateio() {
+    assert_live(vec![
+        check(
+            "public_trades",
+            (
+                GateioSpot::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            ),
+            Activity::Required,
+        ),
+        check(
+            "public_trades",
+            (
+                GateioFuturesUsd::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::FuturePerpetual,
+                PublicTrades,
+            ),
+            Activity::Required,
+        ),
+        check(
+            "public_trades",
+            (
+                GateioFuturesBtc::default(),
+                "btc",
+                "usd",
+                InstrumentKind::FuturePerpetual,
+                PublicTrades,
+            ),
+            Activity::MayBeQuiet,
+        ),
+    ])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Kraken"]
+async fn live_kraken() {
+    fn sub<Kind>(kind: Kind) -> Subscription<Kraken, Kind> {
+        Subscription::new(Kraken, ("xbt", "usd", InstrumentKind::Spot), kind)
+    }
+
+    assert_live(vec![
+        check("public_trades", sub(PublicTrades), Activity::Required),
+        check("order_books_l1", sub(OrderBooksL1), Activity::Required),
+    ])
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Okx"]
+async fn live_okx() {
+    assert_live(vec![
+        check(
+            "public_trades",
+            (Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades),
+            Activity::Required,
+        ),
+        check(
+            "public_trades_all",
+            (Okx, "btc", "usdt", InstrumentKind::Spot, PublicTradesAll),
+            Activity::Required,
+        ),
+        check(
+            "public_trades",
+            (
+                Okx,
+                "btc",
+                "usdt",
+                InstrumentKind::FuturePerpetual,
+                PublicTrades,
+            ),
+            Activity::Required,
+        ),
+    ])
+    .await;
+}
@@ -0,0 +1,1210 @@
+use barter_data::{
+    error::DataError,
+    event::{MarketEvent, MarketEvents},
+    exchange::{
+        binance::{futures::BinanceFuturesUsd, spot::BinanceSpot},
+        bitfinex::Bitfinex,
+        coinbase::Coinbase,
+        endpoint::Endpoints,
+        gateio::spot::GateioSpot,
+        kraken::Kraken,
+        okx::{trade::OkxTrades, Okx},
+        status::{ExchangeStatus, StatusHandling, SystemStatus},
+        ExchangeId, StreamSelector,
+    },
+    num,
+    num::Num,
+    streams::{
+        config::StreamConfig, consumer::consume, health::ConnectionHealth, latency::LatencyTracker,
+        meta::EventSender, Streams,
+    },
+    subscriber::auth::Credentials,
+    subscription::{
+        balance::Balances,
+        book::{Level, OrderBooksL1, OrderBooksL2},
+        candle::Interval,
+        order::{OrderStatus, OrderUpdates},
+        ticker::{TickerUpdates, Tickers},
+        trade::{PublicTrade, PublicTrades},
+        Map, Subscription,
+    },
+    test_util::{Expect, MockExchangeServer, MockScript},
+    transformer::{custom::CustomTransformer, stateless},
+    MarketStream,
+};
+use barter_integration::{
+    error::SocketError,
+    model::{Instrument, InstrumentKind, Side},
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde_json::json;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::mpsc,
+};
+
+/// Receive the next [`MarketEvent<PublicTrade>`], failing the test if none arrives in time.
+async fn next_trade<Meta>(
+    rx: &mut mpsc::UnboundedReceiver<MarketEvent<PublicTrade, Meta>>,
+) -> MarketEvent<PublicTrade, Meta> {
+    tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("timed out waiting for MarketEvent<PublicTrade>")
+        .expect("MarketEvent<PublicTrade> stream ended")
+}
+
+/// Summarise a [`MarketEvent<PublicTrade>`] as (instrument, price, amount, side).
+fn summary(event: &MarketEvent<PublicTrade>) -> (Instrument, Num, Num, Side) {
+    (
+        event.instrument.clone(),
+        event.kind.price,
+        event.kind.amount,
+        event.kind.side,
+    )
+}
+
+fn instrument(base: &str, quote: &str, kind: InstrumentKind) -> Instrument {
+    Instrument::from((base, quote, kind))
+}
+
+/// Wait until the [`MockExchangeServer`] has observed the expected number of disconnections,
+/// failing the test if it does not in time.
+async fn await_disconnections(server: &MockExchangeServer, expected: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.disconnections() < expected {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the client connection to close");
+}
+
+#[tokio::test]
+async fn test_mock_binance_spot_trades() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let trade = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&trade),
+        (
+            instrument("btc", "usdt", InstrumentKind::Spot),
+            num!(10000.19),
+            num!(0.239),
+            Side::Buy
+        )
+    );
+    assert_eq!(trade.kind.id, "1000000000");
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_spot_trades_with_meta() {
+    #[derive(Clone, Default, Debug, PartialEq)]
+    struct Strategy(&'static str);
+
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade", "ethusdt@trade"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"trade","E":1649324825173,"s":"ETHUSDT","t":1,"p":"1000.0","q":"1.0","b":1,"a":2,"T":1749354825200,"m":false,"M":true}"#)
+        .send(r#"{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":2,"p":"10000.0","q":"1.0","b":1,"a":2,"T":1749354825200,"m":true,"M":true}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder_with_meta()
+        .subscribe_with_meta([
+            (
+                (
+                    BinanceSpot::default(),
+                    "btc",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                ),
+                Strategy("momentum"),
+            ),
+            (
+                (
+                    BinanceSpot::default(),
+                    "eth",
+                    "usdt",
+                    InstrumentKind::Spot,
+                    PublicTrades,
+                ),
+                Strategy("arbitrage"),
+            ),
+        ])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let eth = next_trade(&mut trades).await;
+    assert_eq!(
+        eth.instrument,
+        instrument("eth", "usdt", InstrumentKind::Spot)
+    );
+    assert_eq!(eth.meta, Strategy("arbitrage"));
+
+    let btc = next_trade(&mut trades).await;
+    assert_eq!(
+        btc.instrument,
+        instrument("btc", "usdt", InstrumentKind::Spot)
+    );
+    assert_eq!(btc.meta, Strategy("momentum"));
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_spot_order_book_l2_buffers_deltas_until_snapshot() {
+    // Mock Binance REST depth snapshot endpoint, with lastUpdateId 100
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let rest_url = format!("http://{}", listener.local_addr().unwrap());
+    let rest = tokio::spawn(async move {
+        let (mut tcp, _) = listener.accept().await.unwrap();
+        let mut request = vec![0u8; 4096];
+        let read = tcp.read(&mut request).await.unwrap();
+        let body = r#"{"lastUpdateId":100,"bids":[["10.0","1.0"]],"asks":[["100.0","1.0"]]}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        tcp.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..read]).into_owned()
+    });
+
+    // Every delta is sent before the snapshot is fetched, so they are buffered until it's applied
+    let delta = |first: u64, last: u64, bid: &str| {
+        format!(
+            r#"{{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":{first},"u":{last},"b":[["{bid}","1.0"]],"a":[]}}"#
+        )
+    };
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@depth@100ms"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        // Stale: u < lastUpdateId
+        .send(delta(90, 95, "1.0"))
+        // Stale: u == lastUpdateId
+        .send(delta(96, 100, "2.0"))
+        // First applied: U <= lastUpdateId+1 <= u
+        .send(delta(99, 105, "50.0"))
+        .send(delta(106, 108, "60.0"))])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<OrderBooksL2>::builder()
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            OrderBooksL2,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .with_rest_url(ExchangeId::BinanceSpot, rest_url)
+        .init()
+        .await
+        .unwrap();
+    let mut books = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let mut bids = Vec::new();
+    while bids.len() < 2 {
+        let book = tokio::time::timeout(Duration::from_secs(5), books.recv())
+            .await
+            .expect("timed out waiting for OrderBook")
+            .expect("OrderBook stream ended");
+        bids.push(book.kind.bids.levels().to_vec());
+    }
+
+    // Stale deltas are dropped, so the first OrderBook is the snapshot plus the first applied delta
+    assert_eq!(
+        bids,
+        vec![
+            vec![
+                Level::new(num!(50.0), num!(1.0)),
+                Level::new(num!(10.0), num!(1.0))
+            ],
+            vec![
+                Level::new(num!(60.0), num!(1.0)),
+                Level::new(num!(50.0), num!(1.0)),
+                Level::new(num!(10.0), num!(1.0))
+            ],
+        ]
+    );
+
+    let request = rest.await.unwrap();
+    assert!(
+        request.starts_with("GET /depth?symbol=BTCUSDT&limit=100 HTTP/1.1"),
+        "{request}"
+    );
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_spot_order_book_l2_max_book_levels() {
+    // Mock Binance REST depth snapshot endpoint, with two Levels on each side
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let rest_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut tcp, _) = listener.accept().await.unwrap();
+        let mut request = vec![0u8; 4096];
+        let _ = tcp.read(&mut request).await.unwrap();
+        let body = r#"{"lastUpdateId":100,"bids":[["10.0","1.0"],["9.0","1.0"]],"asks":[["100.0","1.0"],["101.0","1.0"]]}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        tcp.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@depth@100ms"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":101,"u":101,"b":[["8.0","1.0"]],"a":[["99.0","1.0"]]}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<OrderBooksL2>::builder()
+        .max_book_levels(Some(2))
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            OrderBooksL2,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .with_rest_url(ExchangeId::BinanceSpot, rest_url)
+        .init()
+        .await
+        .unwrap();
+    let mut books = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let book = tokio::time::timeout(Duration::from_secs(5), books.recv())
+        .await
+        .expect("timed out waiting for OrderBook")
+        .expect("OrderBook stream ended");
+
+    // Levels furthest from the touch are evicted beyond the configured cap
+    assert_eq!(
+        book.kind.bids.levels(),
+        [
+            Level::new(num!(10.0), num!(1.0)),
+            Level::new(num!(9.0), num!(1.0))
+        ]
+    );
+    assert_eq!(
+        book.kind.asks.levels(),
+        [
+            Level::new(num!(99.0), num!(1.0)),
+            Level::new(num!(100.0), num!(1.0))
+        ]
+    );
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_futures_trades_delayed_ack() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+        // Ack within the subscription timeout still validates the subscription
+        .delay(Duration::from_millis(500))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"trade","E":1649839266194,"T":1749354825200,"s":"BTCUSDT","t":1000000000,"p":"10000.19","q":"0.239000","X":"MARKET","m":true}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            BinanceFuturesUsd::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::FuturePerpetual,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::BinanceFuturesUsd, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::BinanceFuturesUsd).unwrap();
+
+    assert_eq!(
+        summary(&next_trade(&mut trades).await),
+        (
+            instrument("btc", "usdt", InstrumentKind::FuturePerpetual),
+            num!(10000.19),
+            num!(0.239),
+            Side::Sell
+        )
+    );
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_bitfinex_trades() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .send(r#"{"event":"info","version":2,"serverId":"5b73a436-19ca-4a15-8160-9069bdd7f181","platform":{"status":1}}"#)
+        .expect_json(json!({"event": "subscribe", "channel": "trades", "symbol": "tBTCUSD"}))
+        .send(r#"{"event":"subscribed","channel":"trades","chanId":420191,"symbol":"tBTCUSD","pair":"BTCUSD"}"#)
+        .send(r#"[420191,[[1225484397,1665452200021,0.5,19000.0]]]"#)
+        .send(r#"[420191,"hb"]"#)
+        .send(r#"[420191,"te",[1225484398,1665452200022,-0.08980641,19027.02807752]]"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            Bitfinex::default(),
+            "btc",
+            "usd",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::Bitfinex, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Bitfinex).unwrap();
+
+    // Snapshot & heartbeat are not published
+    let trade = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&trade),
+        (
+            instrument("btc", "usd", InstrumentKind::Spot),
+            num!(19027.02807752),
+            num!(0.08980641),
+            Side::Sell
+        )
+    );
+    assert_eq!(trade.kind.id, "1225484398");
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_coinbase_trades_skips_malformed_frame() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"type": "subscribe", "product_ids": ["BTC-USD"], "channels": ["matches"]}))
+        .send(r#"{"type":"subscriptions","channels":[{"name":"matches","product_ids":["BTC-USD"]}]}"#)
+        .send(r#"{"type":"match","trade_id":"#)
+        .send(r#"{"type":"match","trade_id":10,"sequence":50,"maker_order_id":"ac928c66-ca53-498f-9c13-a110027a60e8","taker_order_id":"132fb6ae-456b-4654-b4e0-d681ac05cea1","time":"2014-11-07T08:19:27.028459Z","product_id":"BTC-USD","size":"5.23512","price":"400.23","side":"sell"}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+        .with_url(ExchangeId::Coinbase, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Coinbase).unwrap();
+
+    // Malformed frame is skipped without terminating the stream
+    let trade = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&trade),
+        (
+            instrument("btc", "usd", InstrumentKind::Spot),
+            num!(400.23),
+            num!(5.23512),
+            Side::Sell
+        )
+    );
+    assert_eq!(trade.kind.id, "10");
+    assert_eq!(server.connections(), 1);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_coinbase_order_books_l1() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"type": "subscribe", "product_ids": ["BTC-USD"], "channels": ["ticker"]}))
+        .send(r#"{"type":"subscriptions","channels":[{"name":"ticker","product_ids":["BTC-USD"]}]}"#)
+        .send(r#"{"type":"ticker","sequence":50,"product_id":"BTC-USD","price":"1100","open_24h":"1000","volume_24h":"10","low_24h":"900","high_24h":"1200","volume_30d":"300","best_bid":"1099","best_bid_size":"1.5","best_ask":"1100","best_ask_size":"0.5","time":"2022-10-19T23:28:22.061769Z"}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<OrderBooksL1>::builder()
+        .subscribe([(Coinbase, "btc", "usd", InstrumentKind::Spot, OrderBooksL1)])
+        .with_url(ExchangeId::Coinbase, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut books = streams.select(ExchangeId::Coinbase).unwrap();
+
+    let book = tokio::time::timeout(Duration::from_secs(5), books.recv())
+        .await
+        .expect("timed out waiting for an OrderBookL1")
+        .expect("OrderBookL1 stream ended");
+    assert_eq!(
+        (book.kind.best_bid, book.kind.best_ask),
+        (
+            Level::new(num!(1099.0), num!(1.5)),
+            Level::new(num!(1100.0), num!(0.5))
+        )
+    );
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_coinbase_tickers_ticker_updates() {
+    struct TestCase {
+        ticker_updates: Option<TickerUpdates>,
+        expected_channel: &'static str,
+    }
+
+    let tests = vec![
+        TestCase {
+            // TC0: batched ticker updates by default
+            ticker_updates: None,
+            expected_channel: "ticker_batch",
+        },
+        TestCase {
+            // TC1: real-time ticker updates
+            ticker_updates: Some(TickerUpdates::Realtime),
+            expected_channel: "ticker",
+        },
+    ];
+
+    for (index, test) in tests.into_iter().enumerate() {
+        let server = MockExchangeServer::bind([MockScript::new()
+            .expect_json(json!({"type": "subscribe", "product_ids": ["BTC-USD"], "channels": [test.expected_channel]}))
+            .send(json!({"type": "subscriptions", "channels": [{"name": test.expected_channel, "product_ids": ["BTC-USD"]}]}).to_string())
+            .send(r#"{"type":"ticker","sequence":50,"product_id":"BTC-USD","price":"1100","open_24h":"1000","volume_24h":"10","low_24h":"900","high_24h":"1200","volume_30d":"300","best_bid":"1099","best_bid_size":"1","best_ask":"1100","best_ask_size":"1","time":"2022-10-19T23:28:22.061769Z"}"#)])
+        .await
+        .unwrap();
+
+        let mut builder = Streams::<Tickers>::builder()
+            .subscribe([(
+                Coinbase,
+                "btc",
+                "usd",
+                InstrumentKind::Spot,
+                Tickers(Interval::D1),
+            )])
+            .with_url(ExchangeId::Coinbase, server.url());
+        if let Some(ticker_updates) = test.ticker_updates {
+            builder = builder.ticker_updates(ticker_updates);
+        }
+        let mut streams = builder.init().await.unwrap();
+        let mut tickers = streams.select(ExchangeId::Coinbase).unwrap();
+
+        let ticker = tokio::time::timeout(Duration::from_secs(5), tickers.recv())
+            .await
+            .expect("timed out waiting for a Ticker")
+            .expect("Ticker stream ended");
+        assert_eq!(
+            (ticker.kind.close, ticker.kind.price_change),
+            (num!(1100.0), num!(100.0)),
+            "TC{} failed",
+            index
+        );
+        server.assert_expectations();
+    }
+}
+
+#[tokio::test]
+async fn test_mock_gateio_spot_trades() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        // Subscription "time" is the current time
+        .expect(Expect::JsonSubset(json!({
+            "channel": "spot.trades",
+            "event": "subscribe",
+            "payload": ["BTC_USDT"]
+        })))
+        .send(r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.trades","event":"subscribe","result":{"status":"success"}}"#)
+        // Application-level ping is sent once subscribed, & it's pong is not parsed
+        .expect_json(json!({"channel": "spot.ping"}))
+        .send(r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.pong","event":"","result":null}"#)
+        .send(r#"{"time":1606292218,"time_ms":1606292218231,"channel":"spot.trades","event":"update","result":{"id":309143071,"create_time":1606292218,"create_time_ms":"1606292218213.4578","side":"sell","currency_pair":"BTC_USDT","amount":"16.4700000000","price":"0.4705000000"}}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            GateioSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::GateioSpot, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::GateioSpot).unwrap();
+
+    let trade = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&trade),
+        (
+            instrument("btc", "usdt", InstrumentKind::Spot),
+            num!(0.4705),
+            num!(16.47),
+            Side::Sell
+        )
+    );
+    assert_eq!(trade.kind.id, "309143071");
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_kraken_trades() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .send(r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"online","version":"1.0.0"}"#)
+        // Kraken lists Bitcoin as "XBT"
+        .expect_json(json!({"event": "subscribe", "pair": ["XBT/USD"], "subscription": {"name": "trade"}}))
+        .send(r#"{"channelID":0,"channelName":"trade","event":"subscriptionStatus","pair":"XBT/USD","status":"subscribed","subscription":{"name":"trade"}}"#)
+        .send(r#"{"event":"heartbeat"}"#)
+        .send(r#"[0,[["5541.20000","0.15850568","1534614057.321597","s","l",""],["6060.00000","0.02455000","1534614057.324998","b","l",""]],"trade","XBT/USD"]"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(Kraken, "btc", "usd", InstrumentKind::Spot, PublicTrades)])
+        .with_url(ExchangeId::Kraken, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Kraken).unwrap();
+
+    let btc_usd = instrument("btc", "usd", InstrumentKind::Spot);
+    assert_eq!(
+        summary(&next_trade(&mut trades).await),
+        (btc_usd.clone(), num!(5541.2), num!(0.15850568), Side::Sell)
+    );
+    assert_eq!(
+        summary(&next_trade(&mut trades).await),
+        (btc_usd, num!(6060.0), num!(0.02455), Side::Buy)
+    );
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_kraken_maintenance_fails_subscription_validation() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .send(r#"{"connectionID":8628615390848610000,"event":"systemStatus","status":"maintenance","version":"1.0.0"}"#)
+        .expect_json(json!({"event": "subscribe", "pair": ["XBT/USD"], "subscription": {"name": "trade"}}))])
+    .await
+    .unwrap();
+
+    let observed = Arc::new(Mutex::new(Vec::new()));
+    let config = StreamConfig {
+        endpoints: Endpoints {
+            websocket: Some(Endpoints::parse_websocket(&server.url()).unwrap()),
+            rest: None,
+        },
+        status: StatusHandling {
+            probe_interval: Some(Duration::from_secs(5)),
+            observer: Some(Arc::new({
+                let observed = Arc::clone(&observed);
+                move |exchange, status: &ExchangeStatus| {
+                    observed.lock().unwrap().push((exchange, status.status))
+                }
+            })),
+        },
+        ..StreamConfig::default()
+    };
+
+    let subscriptions = [Subscription::from((
+        Kraken,
+        "btc",
+        "usd",
+        InstrumentKind::Spot,
+        PublicTrades,
+    ))];
+    let result =
+        <Kraken as StreamSelector<PublicTrades>>::Stream::init_with(&subscriptions, &config).await;
+
+    match result {
+        Err(DataError::Socket(SocketError::Subscribe(reason))) => {
+            assert!(
+                reason.contains("maintenance"),
+                "unexpected reason: {reason}"
+            );
+        }
+        other => panic!(
+            "expected SocketError::Subscribe, found: {:?}",
+            other.map(|_| ())
+        ),
+    }
+    assert_eq!(
+        *observed.lock().unwrap(),
+        vec![(ExchangeId::Kraken, SystemStatus::Maintenance)]
+    );
+    assert_eq!(
+        config.status.maintenance_backoff(ExchangeId::Kraken),
+        Some(Duration::from_secs(5))
+    );
+    server.assert_expectations();
+
+    // Kraken status is global, so leave it online for the other tests
+    config.status.clear_maintenance(ExchangeId::Kraken);
+}
+
+#[tokio::test]
+async fn test_mock_okx_trades_reconnect_after_disconnect() {
+    let subscribe = json!({
+        "op": "subscribe",
+        "args": [{"channel": "trades", "instId": "BTC-USDT"}],
+        "id": "barter1",
+    });
+    let ack = r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#;
+    let trade = |id: &str, px: &str| {
+        format!(
+            r#"{{"arg":{{"channel":"trades","instId":"BTC-USDT"}},"data":[{{"instId":"BTC-USDT","tradeId":"{id}","px":"{px}","sz":"0.12060306","side":"buy","ts":"1630048897897"}}]}}"#
+        )
+    };
+
+    let server = MockExchangeServer::bind([
+        MockScript::new()
+            // Subscription "id" is the ConnectionTicket of every connection
+            .expect(Expect::JsonSubset(subscribe.clone()))
+            .send(ack)
+            .send(trade("130639474", "42219.9"))
+            .disconnect(),
+        MockScript::new()
+            .expect(Expect::JsonSubset(subscribe))
+            .send(ack)
+            .send(trade("130639475", "42220.1")),
+    ])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+        .with_url(ExchangeId::Okx, server.url())
+        .connection_ticket("barter1")
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Okx).unwrap();
+
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::Spot);
+    let first = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&first),
+        (btc_usdt.clone(), num!(42219.9), num!(0.12060306), Side::Buy)
+    );
+    assert_eq!(first.kind.id, "130639474");
+
+    // Mid-stream disconnect re-connects & re-subscribes
+    let second = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&second),
+        (btc_usdt, num!(42220.1), num!(0.12060306), Side::Buy)
+    );
+    assert_eq!(second.kind.id, "130639475");
+    assert_eq!(server.connections(), 2);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_spot_trades_backfill_retries_failed_reconnect() {
+    // Mock Binance REST recent trades endpoint, with one trade preceding the live trades
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let rest_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut tcp, _) = listener.accept().await.unwrap();
+        let mut request = vec![0u8; 4096];
+        let _ = tcp.read(&mut request).await.unwrap();
+        let body = r#"[{"id":999999999,"price":"9999.0","qty":"1.0","quoteQty":"9999.0","time":1649324825000,"isBuyerMaker":true,"isBestMatch":true}]"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        tcp.write_all(response.as_bytes()).await.unwrap();
+    });
+
+    let subscribe = json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1});
+    let ack = r#"{"result":null,"id":1}"#;
+    let trade = |id: u64, price: &str| {
+        format!(
+            r#"{{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":{id},"p":"{price}","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}}"#
+        )
+    };
+
+    let server = MockExchangeServer::bind([
+        // Initial backfilled connection disconnects mid-stream
+        MockScript::new()
+            .expect_json(subscribe.clone())
+            .send(ack)
+            .send(trade(1000000000, "10000.19"))
+            .disconnect(),
+        // First re-connection fails before the subscription is acknowledged
+        MockScript::new().close(),
+        // Second re-connection succeeds
+        MockScript::new()
+            .expect_json(subscribe)
+            .send(ack)
+            .send(trade(1000000001, "10000.20")),
+    ])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe_with_backfill(
+            [(
+                BinanceSpot::default(),
+                "btc",
+                "usdt",
+                InstrumentKind::Spot,
+                PublicTrades,
+            )],
+            1,
+        )
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .with_rest_url(ExchangeId::BinanceSpot, rest_url)
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        ids.push(next_trade(&mut trades).await.kind.id);
+    }
+
+    // Backfilled trade, then the live trade of the initial & the second re-connection
+    assert_eq!(ids, vec!["999999999", "1000000000", "1000000001"]);
+    assert_eq!(server.connections(), 3);
+    server.assert_expectations();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mock_okx_quiet_instrument_pinged_whilst_idle() {
+    // Paused time auto-advances to the next timer whenever the runtime parks, even if socket IO
+    // is pending, so tick every 100ms to handle the mock IO promptly
+    let ticker = tokio::spawn(async {
+        let mut ticks = tokio::time::interval(Duration::from_millis(100));
+        loop {
+            ticks.tick().await;
+        }
+    });
+
+    let trade = |id: &str| {
+        format!(
+            r#"{{"arg":{{"channel":"trades","instId":"BTC-USDT"}},"data":[{{"instId":"BTC-USDT","tradeId":"{id}","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}}]}}"#
+        )
+    };
+
+    // Quiet instrument: ~2.5 minutes without a trade, kept alive by pings sent after ~25s idle
+    let mut script = MockScript::new()
+        .expect(Expect::JsonSubset(json!({"op": "subscribe"})))
+        .send(r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#)
+        .send(trade("130639474"));
+    for _ in 0..6 {
+        script = script
+            .delay(Duration::from_secs(24))
+            .expect(Expect::Text("ping".to_string()))
+            .send("pong");
+    }
+    let server = MockExchangeServer::bind([script.send(trade("130639475"))])
+        .await
+        .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([(Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades)])
+        .with_url(ExchangeId::Okx, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Okx).unwrap();
+
+    assert_eq!(next_trade(&mut trades).await.kind.id, "130639474");
+
+    // Pongs are not parsed, & the idle connection is never dropped
+    let second = tokio::time::timeout(Duration::from_secs(180), trades.recv())
+        .await
+        .expect("timed out waiting for MarketEvent<PublicTrade>")
+        .unwrap();
+    assert_eq!(second.kind.id, "130639475");
+    assert_eq!(server.connections(), 1);
+    assert_eq!(server.disconnections(), 0);
+
+    // Pings are only sent once subscribed, never mid handshake
+    let received = server.received();
+    assert!(received[0].contains("subscribe"));
+    assert_eq!(received[1..], vec!["ping".to_string(); 6]);
+    server.assert_expectations();
+    ticker.abort();
+}
+
+/// [`CustomTransformer`] wrapping the built-in Okx normalisation, prefixing each trade id with
+/// the sequence number of the trade on it's connection.
+#[derive(Clone, Debug, Default)]
+struct SequencedOkxTrades {
+    sequence: u64,
+}
+
+impl CustomTransformer<Okx, PublicTrades> for SequencedOkxTrades {
+    type Input = OkxTrades;
+
+    fn transform(
+        &mut self,
+        received_time: DateTime<Utc>,
+        input: Self::Input,
+        instrument_map: &Map<Instrument>,
+    ) -> MarketEvents<PublicTrade> {
+        let mut events =
+            stateless::transform::<Okx, PublicTrades, _>(received_time, input, instrument_map);
+        for event in events.iter_mut().flatten() {
+            self.sequence += 1;
+            event.kind.id = format!("{}:{}", self.sequence, event.kind.id);
+        }
+        events
+    }
+}
+
+#[tokio::test]
+async fn test_mock_okx_trades_custom_transformer() {
+    let subscribe =
+        json!({"op": "subscribe", "args": [{"channel": "trades", "instId": "BTC-USDT"}]});
+    let ack = r#"{"event":"subscribe","arg":{"channel":"trades","instId":"BTC-USDT"}}"#;
+    let trade = |id: &str| {
+        format!(
+            r#"{{"arg":{{"channel":"trades","instId":"BTC-USDT"}},"data":[{{"instId":"BTC-USDT","tradeId":"{id}","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}}]}}"#
+        )
+    };
+
+    let server = MockExchangeServer::bind([
+        MockScript::new()
+            .expect(Expect::JsonSubset(subscribe.clone()))
+            .send(ack)
+            .send(trade("130639474"))
+            .send(trade("130639475"))
+            .disconnect(),
+        MockScript::new()
+            .expect(Expect::JsonSubset(subscribe))
+            .send(ack)
+            .send(trade("130639476")),
+    ])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe_with_transformer(
+            [(Okx, "btc", "usdt", InstrumentKind::Spot, PublicTrades)],
+            SequencedOkxTrades::default(),
+        )
+        .with_url(ExchangeId::Okx, server.url())
+        .init()
+        .await
+        .unwrap();
+    let mut trades = streams.select(ExchangeId::Okx).unwrap();
+
+    let btc_usdt = instrument("btc", "usdt", InstrumentKind::Spot);
+    let first = next_trade(&mut trades).await;
+    assert_eq!(
+        summary(&first),
+        (btc_usdt, num!(42219.9), num!(0.12060306), Side::Buy)
+    );
+    assert_eq!(first.kind.id, "1:130639474");
+    assert_eq!(next_trade(&mut trades).await.kind.id, "2:130639475");
+
+    // Each connection drives it's own clone of the CustomTransformer
+    assert_eq!(next_trade(&mut trades).await.kind.id, "1:130639476");
+    assert_eq!(server.connections(), 2);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_okx_private_orders_login() {
+    let login = json!({"op": "login"});
+    let subscribe = json!({
+        "op": "subscribe",
+        "args": [{"channel": "orders", "instType": "SPOT", "instId": "BTC-USDT"}]
+    });
+
+    let server = MockExchangeServer::bind([MockScript::new()
+        // Subscriptions are only sent once logged in
+        .expect(Expect::JsonSubset(login))
+        .send(r#"{"event":"login","code":"0","msg":"","connId":"a4d3ae55"}"#)
+        .expect(Expect::JsonSubset(subscribe))
+        .send(r#"{"event":"subscribe","arg":{"channel":"orders","instType":"SPOT","instId":"BTC-USDT"}}"#)
+        .send(r#"{"arg":{"channel":"orders","instType":"SPOT","instId":"BTC-USDT","uid":"614488474791936"},"data":[{"instType":"SPOT","instId":"BTC-USDT","ordId":"452197707845865472","clOrdId":"","px":"31527.1","sz":"0.001","ordType":"limit","side":"sell","accFillSz":"0","fillPx":"","fillSz":"0","tradeId":"","state":"live","cTime":"1654084334977","uTime":"1654084334977"}]}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<OrderUpdates>::builder()
+        .subscribe([(Okx, "btc", "usdt", InstrumentKind::Spot, OrderUpdates)])
+        .with_url(ExchangeId::Okx, server.url())
+        .credentials(
+            ExchangeId::Okx,
+            Credentials::new("key", "s3cr3t").with_passphrase("p4ssphrase"),
+        )
+        .init()
+        .await
+        .unwrap();
+    let mut orders = streams.select(ExchangeId::Okx).unwrap();
+
+    let order = tokio::time::timeout(Duration::from_secs(5), orders.recv())
+        .await
+        .expect("timed out waiting for OrderUpdate")
+        .expect("OrderUpdate stream ended");
+    assert_eq!(
+        order.instrument,
+        instrument("btc", "usdt", InstrumentKind::Spot)
+    );
+    assert_eq!(order.kind.order_id, "452197707845865472");
+    assert_eq!(order.kind.status, OrderStatus::Open);
+    assert_eq!(order.kind.side, Side::Sell);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_okx_login_failure_is_auth_error() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect(Expect::JsonSubset(json!({"op": "login"})))
+        .send(r#"{"event":"error","code":"60009","msg":"Login failed.","connId":"a4d3ae55"}"#)])
+    .await
+    .unwrap();
+
+    let subscriptions = [Subscription::from((
+        Okx,
+        "btc",
+        "usdt",
+        InstrumentKind::Spot,
+        OrderUpdates,
+    ))];
+    let config = StreamConfig {
+        endpoints: Endpoints {
+            websocket: Some(Endpoints::parse_websocket(&server.url()).unwrap()),
+            rest: None,
+        },
+        credentials: Some(Credentials::new("key", "s3cr3t").with_passphrase("p4ssphrase")),
+        ..StreamConfig::default()
+    };
+
+    let result =
+        <Okx as StreamSelector<OrderUpdates>>::Stream::init_with(&subscriptions, &config).await;
+
+    match result {
+        Err(DataError::Auth { exchange, reason }) => {
+            assert_eq!(exchange, ExchangeId::Okx);
+            assert!(reason.contains("60009"), "unexpected reason: {reason}");
+        }
+        other => panic!("expected DataError::Auth, found: {:?}", other.map(|_| ())),
+    }
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_binance_user_data_balances() {
+    // Mock Binance REST listenKey endpoint
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let rest_url = format!("http://{}", listener.local_addr().unwrap());
+    let rest = tokio::spawn(async move {
+        let (mut tcp, _) = listener.accept().await.unwrap();
+        let mut request = vec![0u8; 4096];
+        let read = tcp.read(&mut request).await.unwrap();
+        let body = r#"{"listenKey":"pqia91ma19a5s61cv6a8"}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        tcp.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..read]).to_lowercase()
+    });
+
+    // User data stream pushes account events without any subscription request
+    let server = MockExchangeServer::bind([MockScript::new()
+        .send(r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"USDT","f":"100.5","l":"50.0"},{"a":"BNB","f":"1.0","l":"0.0"}]}"#)])
+    .await
+    .unwrap();
+
+    let mut streams = Streams::<Balances>::builder()
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            Balances,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .with_rest_url(ExchangeId::BinanceSpot, rest_url)
+        .credentials(
+            ExchangeId::BinanceSpot,
+            Credentials::new("api_key", "secret"),
+        )
+        .init()
+        .await
+        .unwrap();
+    let mut balances = streams.select(ExchangeId::BinanceSpot).unwrap();
+
+    let balance = tokio::time::timeout(Duration::from_secs(5), balances.recv())
+        .await
+        .expect("timed out waiting for Balance")
+        .expect("Balance stream ended");
+    assert_eq!(
+        balance.instrument,
+        instrument("btc", "usdt", InstrumentKind::Spot)
+    );
+    assert_eq!(balance.kind.asset.as_ref(), "usdt");
+    assert_eq!(
+        (balance.kind.total, balance.kind.available),
+        (num!(150.5), num!(100.5))
+    );
+
+    let request = rest.await.unwrap();
+    assert!(
+        request.starts_with("post /userdatastream http/1.1"),
+        "{request}"
+    );
+    assert!(request.contains("x-mbx-apikey: api_key"), "{request}");
+    assert!(server.received().is_empty());
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_consumer_dropped_shuts_down_consumer_loop() {
+    // Connection stays open after the trade, so only the dropped receiver can end it
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#)])
+    .await
+    .unwrap();
+
+    let subscriptions = vec![Subscription::from((
+        BinanceSpot::default(),
+        "btc",
+        "usdt",
+        InstrumentKind::Spot,
+        PublicTrades,
+    ))];
+    let config = StreamConfig {
+        endpoints: Endpoints {
+            websocket: Some(Endpoints::parse_websocket(&server.url()).unwrap()),
+            rest: None,
+        },
+        ..StreamConfig::default()
+    };
+    let (exchange_tx, mut exchange_rx) = mpsc::unbounded_channel();
+    let consumer = tokio::spawn(consume(
+        subscriptions,
+        EventSender::from(exchange_tx),
+        None,
+        None,
+        Arc::new(ConnectionHealth::new::<PublicTrades>(
+            ExchangeId::BinanceSpot,
+            1,
+            Default::default(),
+        )),
+        LatencyTracker::default(),
+        config,
+    ));
+
+    next_trade(&mut exchange_rx).await;
+    drop(exchange_rx);
+
+    let error = tokio::time::timeout(Duration::from_secs(5), consumer)
+        .await
+        .expect("consumer loop should exit once it's receiver is dropped")
+        .unwrap();
+    assert!(
+        matches!(error, DataError::ConsumerDropped(ExchangeId::BinanceSpot)),
+        "unexpected error: {error}"
+    );
+    await_disconnections(&server, 1).await;
+    assert_eq!(server.connections(), 1);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_joined_streams_dropped_closes_connection() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)
+        .send(r#"{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":1000000000,"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}"#)])
+    .await
+    .unwrap();
+
+    let streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .init()
+        .await
+        .unwrap();
+
+    // Common pattern of taking the first n events, then dropping the stream
+    let trades = tokio_stream::wrappers::UnboundedReceiverStream::new(streams.join().await)
+        .take(1)
+        .collect::<Vec<_>>();
+    let trades = tokio::time::timeout(Duration::from_secs(5), trades)
+        .await
+        .expect("timed out waiting for MarketEvent<PublicTrade>");
+    assert_eq!(trades.len(), 1);
+
+    // Quiet connection is still closed, rather than leaked until it errors
+    await_disconnections(&server, 1).await;
+    assert_eq!(server.connections(), 1);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_mock_exchange_server_records_unexpected_subscription() {
+    let server = MockExchangeServer::bind([MockScript::new()
+        .expect_json(json!({"method": "SUBSCRIBE", "params": ["ethusdt@trade"], "id": 1}))
+        .send(r#"{"result":null,"id":1}"#)])
+    .await
+    .unwrap();
+
+    let _streams = Streams::<PublicTrades>::builder()
+        .subscribe([(
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )])
+        .with_url(ExchangeId::BinanceSpot, server.url())
+        .init()
+        .await
+        .unwrap();
+
+    // Connections are established in the background
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.failures().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the subscription");
+
+    assert_eq!(
+        server.received(),
+        vec![r#"{"id":1,"method":"SUBSCRIBE","params":["btcusdt@trade"]}"#.to_string()]
+    );
+    assert_eq!(server.failures().len(), 1);
+}
@@ -0,0 +1,296 @@
+//! Property-based tests of [`OrderBook`] delta application.
+//!
+//! Random but valid snapshot & delta sequences (as an exchange would send them) are applied by the
+//! production [`BinanceSpotBookUpdater`], asserting the resulting [`OrderBook`]s always match a
+//! reference model, are sorted, have positive amounts, are never crossed, and are unchanged when
+//! an already applied update is re-applied. Arbitrary (including invalid) levels are also upserted
+//! directly to assert an [`OrderBookSide`] can never be corrupted by a malformed payload.
+//!
+//! Minimal counterexamples found by proptest are persisted in the
+//! `order_book_properties.proptest-regressions` file alongside & replayed before any new cases.
+//! Raw message counterexamples found by the `fuzz` targets are committed to `fuzz/regressions`,
+//! and the Binance OrderBook L2 deltas among them are also replayed here.
+#![cfg(not(feature = "decimal"))]
+
+use barter_data::{
+    exchange::binance::{
+        book::BinanceLevel,
+        spot::l2::{BinanceSpotBookUpdater, BinanceSpotOrderBookL2Delta},
+    },
+    subscription::book::{Level, OrderBook, OrderBookSide},
+    transformer::book::OrderBookUpdater,
+};
+use barter_integration::model::{Side, SubscriptionId};
+use chrono::Utc;
+use proptest::prelude::*;
+use std::{collections::BTreeMap, fs};
+
+/// Reference model of one side of an [`OrderBook`], ie/ price ticks -> amount lots.
+type ModelSide = BTreeMap<i64, u64>;
+
+/// Random but valid OrderBook snapshot followed by the deltas an exchange could send after it.
+#[derive(Debug, Clone)]
+struct BookSequence {
+    snapshot_id: u64,
+    bids: ModelSide,
+    asks: ModelSide,
+    deltas: Vec<BinanceSpotOrderBookL2Delta>,
+}
+
+/// Random mutation of the reference model, used to generate the next delta of a [`BookSequence`].
+#[derive(Debug, Clone)]
+struct Step {
+    mid_change: i64,
+    bids: Vec<(i64, u64)>,
+    asks: Vec<(i64, u64)>,
+    update_count: u64,
+}
+
+fn price(ticks: i64) -> f64 {
+    ticks as f64 / 2.0
+}
+
+fn amount(lots: u64) -> f64 {
+    lots as f64 / 1000.0
+}
+
+fn level_ops() -> impl Strategy<Value = Vec<(i64, u64)>> {
+    // Offsets from the mid price & amount lots, where zero lots removes the level
+    prop::collection::vec((1i64..50, prop_oneof![Just(0u64), 1u64..1000]), 0..10)
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    (-5i64..=5, level_ops(), level_ops(), 1u64..5).prop_map(
+        |(mid_change, bids, asks, update_count)| Step {
+            mid_change,
+            bids,
+            asks,
+            update_count,
+        },
+    )
+}
+
+fn book_sequence() -> impl Strategy<Value = BookSequence> {
+    (
+        1_000i64..2_000,
+        1u64..1_000_000,
+        prop::collection::btree_map(1i64..50, 1u64..1000, 0..20),
+        prop::collection::btree_map(1i64..50, 1u64..1000, 0..20),
+        prop::collection::vec(step(), 0..50),
+    )
+        .prop_map(|(mid, snapshot_id, bid_offsets, ask_offsets, steps)| {
+            let bids = bid_offsets
+                .into_iter()
+                .map(|(offset, lots)| (mid - offset, lots))
+                .collect::<ModelSide>();
+            let asks = ask_offsets
+                .into_iter()
+                .map(|(offset, lots)| (mid + offset, lots))
+                .collect::<ModelSide>();
+
+            let mut model = (bids.clone(), asks.clone());
+            let mut mid = mid;
+            let mut last_update_id = snapshot_id;
+
+            let deltas = steps
+                .into_iter()
+                .map(|step| {
+                    mid += step.mid_change;
+
+                    // Remove the levels the new mid price would otherwise cross
+                    let mut bid_levels = model
+                        .0
+                        .range(mid..)
+                        .map(|(ticks, _)| (*ticks, 0))
+                        .collect::<Vec<_>>();
+                    let mut ask_levels = model
+                        .1
+                        .range(..=mid)
+                        .map(|(ticks, _)| (*ticks, 0))
+                        .collect::<Vec<_>>();
+
+                    bid_levels.extend(step.bids.iter().map(|(offset, lots)| (mid - offset, *lots)));
+                    ask_levels.extend(step.asks.iter().map(|(offset, lots)| (mid + offset, *lots)));
+
+                    apply(&mut model.0, &bid_levels);
+                    apply(&mut model.1, &ask_levels);
+
+                    let first_update_id = last_update_id + 1;
+                    last_update_id += step.update_count;
+
+                    BinanceSpotOrderBookL2Delta {
+                        subscription_id: SubscriptionId::from("@depth@100ms|BTCUSDT"),
+                        first_update_id,
+                        last_update_id,
+                        bids: binance_levels(&bid_levels),
+                        asks: binance_levels(&ask_levels),
+                    }
+                })
+                .collect();
+
+            BookSequence {
+                snapshot_id,
+                bids,
+                asks,
+                deltas,
+            }
+        })
+}
+
+fn apply(side: &mut ModelSide, levels: &[(i64, u64)]) {
+    for (ticks, lots) in levels {
+        match lots {
+            0 => side.remove(ticks),
+            lots => side.insert(*ticks, *lots),
+        };
+    }
+}
+
+fn binance_levels(levels: &[(i64, u64)]) -> Vec<BinanceLevel> {
+    levels
+        .iter()
+        .map(|(ticks, lots)| BinanceLevel {
+            price: price(*ticks),
+            amount: amount(*lots),
+        })
+        .collect()
+}
+
+fn model_levels<'a>(levels: impl Iterator<Item = (&'a i64, &'a u64)>) -> Vec<Level> {
+    levels
+        .map(|(ticks, lots)| Level::new(price(*ticks), amount(*lots)))
+        .collect()
+}
+
+fn assert_book_invariants(book: &OrderBook) -> Result<(), TestCaseError> {
+    let (bids, asks) = (book.bids.levels(), book.asks.levels());
+
+    for level in bids.iter().chain(asks) {
+        prop_assert!(level.amount > 0.0, "non-positive amount: {level:?}");
+    }
+    for pair in bids.windows(2) {
+        prop_assert!(pair[0].price > pair[1].price, "unsorted bids: {bids:?}");
+    }
+    for pair in asks.windows(2) {
+        prop_assert!(pair[0].price < pair[1].price, "unsorted asks: {asks:?}");
+    }
+    if let (Some(best_bid), Some(best_ask)) = (bids.first(), asks.first()) {
+        prop_assert!(
+            best_bid.price < best_ask.price,
+            "crossed book: {best_bid:?} >= {best_ask:?}"
+        );
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_valid_deltas_maintain_book_invariants(sequence in book_sequence()) {
+        let mut updater = BinanceSpotBookUpdater::new(sequence.snapshot_id);
+        let mut book = OrderBook {
+            last_update_time: Utc::now(),
+            bids: OrderBookSide::new(Side::Buy, model_levels(sequence.bids.iter())),
+            asks: OrderBookSide::new(Side::Sell, model_levels(sequence.asks.iter())),
+        };
+        let mut model = (sequence.bids, sequence.asks);
+
+        for delta in sequence.deltas {
+            apply(&mut model.0, &delta_ticks(&delta.bids));
+            apply(&mut model.1, &delta_ticks(&delta.asks));
+
+            let snapshot = updater
+                .update(&mut book, delta.clone())
+                .map_err(|error| TestCaseError::fail(error.to_string()))?
+                .expect("valid delta produced no snapshot");
+
+            assert_book_invariants(&snapshot)?;
+            prop_assert_eq!(snapshot.bids.levels(), model_levels(model.0.iter().rev()));
+            prop_assert_eq!(snapshot.asks.levels(), model_levels(model.1.iter()));
+
+            // Re-applying the same update id is dropped & leaves the OrderBook untouched
+            prop_assert!(updater.update(&mut book, delta).unwrap().is_none());
+            prop_assert_eq!(&book.snapshot(), &snapshot);
+        }
+    }
+
+    #[test]
+    fn test_upsert_is_idempotent(
+        levels in prop::collection::vec((1i64..100, 0u64..100), 0..50),
+        side in prop_oneof![Just(Side::Buy), Just(Side::Sell)],
+    ) {
+        let levels = levels
+            .into_iter()
+            .map(|(ticks, lots)| Level::new(price(ticks), amount(lots)))
+            .collect::<Vec<_>>();
+
+        let mut once = OrderBookSide::new(side, Vec::<Level>::new());
+        once.upsert(levels.clone());
+        once.sort();
+
+        let mut twice = once.clone();
+        twice.upsert(levels);
+        twice.sort();
+
+        prop_assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_arbitrary_levels_never_corrupt_book(
+        levels in prop::collection::vec((any::<f64>(), any::<f64>()), 0..50),
+    ) {
+        let mut book = OrderBook {
+            last_update_time: Utc::now(),
+            bids: OrderBookSide::new(Side::Buy, Vec::<Level>::new()),
+            asks: OrderBookSide::new(Side::Sell, Vec::<Level>::new()),
+        };
+
+        book.bids.upsert(levels.iter().copied());
+        book.asks.upsert(levels.iter().copied());
+
+        // Sorting must not panic, & only finite Levels with positive amounts may be kept
+        let snapshot = book.snapshot();
+        for level in snapshot.bids.levels().iter().chain(snapshot.asks.levels()) {
+            prop_assert!(level.price.is_finite(), "non-finite price: {level:?}");
+            prop_assert!(
+                level.amount.is_finite() && level.amount > 0.0,
+                "invalid amount: {level:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_fuzz_regressions_maintain_book_invariants() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions/binance");
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let payload = fs::read_to_string(&path).unwrap();
+        let delta = serde_json::from_str::<BinanceSpotOrderBookL2Delta>(&payload)
+            .unwrap_or_else(|error| panic!("{} failed to deserialise: {error}", path.display()));
+
+        let mut updater = BinanceSpotBookUpdater::new(delta.first_update_id - 1);
+        let mut book = OrderBook {
+            last_update_time: Utc::now(),
+            bids: OrderBookSide::new(Side::Buy, Vec::<Level>::new()),
+            asks: OrderBookSide::new(Side::Sell, Vec::<Level>::new()),
+        };
+
+        let snapshot = updater.update(&mut book, delta).unwrap().unwrap();
+        assert_book_invariants(&snapshot)
+            .unwrap_or_else(|error| panic!("{} failed: {error}", path.display()));
+    }
+}
+
+fn delta_ticks(levels: &[BinanceLevel]) -> Vec<(i64, u64)> {
+    levels
+        .iter()
+        .map(|level| {
+            (
+                (level.price * 2.0).round() as i64,
+                (level.amount * 1000.0).round() as u64,
+            )
+        })
+        .collect()
+}
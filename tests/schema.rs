@@ -0,0 +1,191 @@
+//! Golden file tests guarding the serialised [`MarketEventEnvelope`] & protobuf schemas.
+//!
+//! If one of these tests fails, the serde representation of a normalised event has changed. If
+//! that is intentional, increment `MARKET_EVENT_SCHEMA_VERSION` and update the golden files.
+#![cfg(not(feature = "decimal"))]
+
+use barter_data::{
+    error::DataError,
+    event::{DataKind, MarketEvent, MarketEventEnvelope, MARKET_EVENT_SCHEMA_VERSION},
+    subscription::{
+        book::{Level, OrderBook, OrderBookL1, OrderBookSide},
+        candle::Candle,
+        liquidation::Liquidation,
+        trade::PublicTrade,
+    },
+};
+use barter_integration::model::{Exchange, Instrument, InstrumentKind, Side};
+use chrono::{DateTime, TimeZone, Utc};
+
+fn time(ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(ms).unwrap()
+}
+
+fn event<T>(kind: T) -> MarketEvent<T> {
+    MarketEvent {
+        exchange_time: time(1672531200000),
+        raw_exchange_time: None,
+        received_time: time(1672531200001),
+        exchange: Exchange::from("binance_spot"),
+        instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+        kind,
+        meta: (),
+    }
+}
+
+fn trade() -> MarketEvent<PublicTrade> {
+    event(PublicTrade {
+        id: "1".to_string(),
+        price: 16500.5,
+        amount: 0.25,
+        side: Side::Buy,
+    })
+}
+
+fn order_book_l1() -> MarketEvent<OrderBookL1> {
+    event(OrderBookL1 {
+        last_update_time: time(1672531200000),
+        best_bid: Level::new(16500.0, 1.5),
+        best_ask: Level::new(16500.5, 2.0),
+    })
+}
+
+fn order_book() -> MarketEvent<OrderBook> {
+    event(OrderBook {
+        last_update_time: time(1672531200000),
+        bids: OrderBookSide::new(Side::Buy, [(16500.0, 1.5), (16499.5, 3.0)]),
+        asks: OrderBookSide::new(Side::Sell, [(16500.5, 2.0)]),
+    })
+}
+
+fn candle() -> MarketEvent<Candle> {
+    event(Candle {
+        close_time: time(1672531259999),
+        open: 16500.0,
+        high: 16510.0,
+        low: 16495.5,
+        close: 16505.0,
+        volume: 12.5,
+        trade_count: 420,
+        historical: false,
+    })
+}
+
+fn liquidation() -> MarketEvent<Liquidation> {
+    event(Liquidation {
+        side: Side::Sell,
+        price: 16400.0,
+        quantity: 0.75,
+        time: time(1672531200000),
+    })
+}
+
+/// Assert the provided event serialises to the golden JSON, and round-trips back to itself.
+fn assert_golden<T>(event: MarketEvent<T>, golden: &str)
+where
+    T: Clone + PartialEq + std::fmt::Debug,
+    MarketEvent<DataKind>: From<MarketEvent<T>>,
+    MarketEvent<T>: TryFrom<MarketEvent<DataKind>, Error = DataError>,
+{
+    let json = event.to_json().unwrap();
+    let actual = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+    let expected = serde_json::from_str::<serde_json::Value>(golden).unwrap();
+    assert_eq!(actual, expected, "serialised representation changed");
+
+    let actual = MarketEvent::<T>::from_json(golden).unwrap();
+    assert_eq!(actual, event, "typed round-trip failed");
+
+    assert_eq!(
+        from_json_data_kind(golden),
+        MarketEvent::<DataKind>::from(event.clone()),
+        "DataKind round-trip failed"
+    );
+
+    #[cfg(feature = "proto")]
+    {
+        let actual = MarketEvent::<T>::from_protobuf(&event.to_protobuf()).unwrap();
+        assert_eq!(actual, event, "protobuf round-trip failed");
+    }
+}
+
+fn from_json_data_kind(input: &str) -> MarketEvent<DataKind> {
+    MarketEvent::<DataKind>::from_json(input).unwrap()
+}
+
+#[test]
+fn test_golden_trade() {
+    assert_golden(trade(), include_str!("golden/trade.json"));
+}
+
+#[test]
+fn test_golden_order_book_l1() {
+    assert_golden(order_book_l1(), include_str!("golden/order_book_l1.json"));
+}
+
+#[test]
+fn test_golden_order_book() {
+    assert_golden(order_book(), include_str!("golden/order_book.json"));
+}
+
+#[test]
+fn test_golden_candle() {
+    assert_golden(candle(), include_str!("golden/candle.json"));
+}
+
+#[test]
+fn test_golden_liquidation() {
+    assert_golden(liquidation(), include_str!("golden/liquidation.json"));
+}
+
+#[test]
+fn test_from_json_unsupported_version() {
+    let mut envelope = MarketEventEnvelope::from(MarketEvent::<DataKind>::from(trade()));
+    envelope.version = MARKET_EVENT_SCHEMA_VERSION + 1;
+    let input = serde_json::to_string(&envelope).unwrap();
+
+    let actual = MarketEvent::<PublicTrade>::from_json(&input);
+
+    assert!(matches!(
+        actual,
+        Err(DataError::UnsupportedSchemaVersion(_))
+    ));
+}
+
+#[test]
+fn test_from_json_kind_mismatch() {
+    let actual = MarketEvent::<PublicTrade>::from_json(include_str!("golden/candle.json"));
+
+    assert!(matches!(
+        actual,
+        Err(DataError::KindMismatch {
+            expected: "trade",
+            actual: "candle"
+        })
+    ));
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn test_golden_protobuf_trade() {
+    let golden = include_bytes!("golden/trade.pb");
+
+    assert_eq!(trade().to_protobuf(), golden, "protobuf encoding changed");
+    assert_eq!(
+        MarketEvent::<PublicTrade>::from_protobuf(golden).unwrap(),
+        trade()
+    );
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn test_from_protobuf_kind_mismatch() {
+    let actual = MarketEvent::<PublicTrade>::from_protobuf(&candle().to_protobuf());
+
+    assert!(matches!(
+        actual,
+        Err(DataError::KindMismatch {
+            expected: "trade",
+            actual: "candle"
+        })
+    ));
+}
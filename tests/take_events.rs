@@ -0,0 +1,129 @@
+use barter_data::{
+    exchange::{binance::spot::BinanceSpot, endpoint::Endpoints, StreamSelector},
+    streams::config::StreamConfig,
+    subscription::{trade::PublicTrades, Subscription},
+    test_util::{MockExchangeServer, MockScript},
+    MarketStream,
+};
+use barter_integration::model::InstrumentKind;
+use serde_json::json;
+use std::time::Duration;
+
+type BinanceSpotTrades = <BinanceSpot as StreamSelector<PublicTrades>>::Stream;
+
+fn subscriptions() -> Vec<Subscription<BinanceSpot, PublicTrades>> {
+    vec![Subscription::from((
+        BinanceSpot::default(),
+        "btc",
+        "usdt",
+        InstrumentKind::Spot,
+        PublicTrades,
+    ))]
+}
+
+/// [`MockScript`] acknowledging the btcusdt trade subscription, then sending trades with ids
+/// `1..=trades`.
+fn script(trades: u64) -> MockScript {
+    (1..=trades).fold(
+        MockScript::new()
+            .expect_json(json!({"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}))
+            .send(r#"{"result":null,"id":1}"#),
+        |script, id| {
+            script.send(format!(
+                r#"{{"e":"trade","E":1649324825173,"s":"BTCUSDT","t":{id},"p":"10000.19","q":"0.239000","b":10108767791,"a":10108764858,"T":1749354825200,"m":false,"M":true}}"#
+            ))
+        },
+    )
+}
+
+/// Initialise a [`BinanceSpotTrades`] stream connected to the provided [`MockExchangeServer`].
+async fn init(server: &MockExchangeServer) -> BinanceSpotTrades {
+    let config = StreamConfig {
+        endpoints: Endpoints {
+            websocket: Some(Endpoints::parse_websocket(&server.url()).unwrap()),
+            rest: None,
+        },
+        ..StreamConfig::default()
+    };
+
+    BinanceSpotTrades::init_with(&subscriptions(), &config)
+        .await
+        .unwrap()
+}
+
+/// Wait until the [`MockExchangeServer`] has received a client close frame, failing the test if
+/// it does not in time.
+async fn await_close_frame(server: &MockExchangeServer) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.close_frames() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the client close frame");
+}
+
+#[tokio::test]
+async fn test_take_events_mock_binance_spot_trades() {
+    const N: usize = 5;
+
+    // More trades than requested, so take_events must stop after exactly N
+    let server = MockExchangeServer::bind([script(8)]).await.unwrap();
+
+    let trades = init(&server).await.take_events(N).await.unwrap();
+
+    let ids = trades
+        .into_iter()
+        .map(|trade| trade.kind.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+
+    await_close_frame(&server).await;
+    assert_eq!(server.close_frames(), 1);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+async fn test_take_until_deadline_mock_binance_spot_trades() {
+    // Connection stays open & idle after the trades, so only the deadline ends the stream
+    let server = MockExchangeServer::bind([script(3)]).await.unwrap();
+
+    let trades = init(&server)
+        .await
+        .take_until_deadline(Duration::from_millis(500))
+        .await
+        .unwrap();
+
+    let ids = trades
+        .into_iter()
+        .map(|trade| trade.kind.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2", "3"]);
+
+    await_close_frame(&server).await;
+    assert_eq!(server.close_frames(), 1);
+    server.assert_expectations();
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Binance"]
+async fn test_take_events_binance_spot_trades() {
+    const N: usize = 5;
+
+    let stream = BinanceSpotTrades::init(&subscriptions()).await.unwrap();
+    let trades = stream.take_events(N).await.unwrap();
+
+    assert_eq!(trades.len(), N);
+}
+
+#[tokio::test]
+#[ignore = "requires a network connection to Binance"]
+async fn test_take_until_deadline_binance_spot_trades() {
+    let stream = BinanceSpotTrades::init(&subscriptions()).await.unwrap();
+    let trades = stream
+        .take_until_deadline(Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert!(!trades.is_empty());
+}